@@ -0,0 +1,2 @@
+-- Add post_copy_script to project_repos: a command run in the worktree after copy files are copied
+ALTER TABLE project_repos ADD COLUMN post_copy_script TEXT;
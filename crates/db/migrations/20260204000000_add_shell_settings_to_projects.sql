@@ -0,0 +1,4 @@
+-- Shell used for project scripts (NULL = platform default) and whether spawned
+-- commands inherit the user's login-shell environment
+ALTER TABLE projects ADD COLUMN script_shell TEXT;
+ALTER TABLE projects ADD COLUMN inherit_login_env INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,15 @@
+-- Tools a project needs on PATH (node, pnpm, cargo, docker, ...), checked by
+-- the toolchain doctor before agents run into a missing binary
+
+CREATE TABLE project_tool_requirements (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    name            TEXT NOT NULL,
+    version         TEXT,
+    check_command   TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_project_tool_requirements_project_id ON project_tool_requirements(project_id);
@@ -0,0 +1,50 @@
+-- Embedding status for conversation messages and coding agent summaries, so
+-- semantic search covers more than task titles and descriptions. The vectors
+-- themselves live in vec0 tables created at runtime, like task_embeddings.
+
+CREATE TABLE conversation_message_embedding_status (
+    message_id       BLOB PRIMARY KEY REFERENCES conversation_messages(id) ON DELETE CASCADE,
+    needs_embedding  INTEGER NOT NULL DEFAULT 1,
+    last_embedded_at TEXT,
+    embedding_model  TEXT
+);
+
+INSERT INTO conversation_message_embedding_status(message_id, needs_embedding)
+SELECT id, 1 FROM conversation_messages;
+
+CREATE TRIGGER conversation_message_embedding_status_insert AFTER INSERT ON conversation_messages BEGIN
+    INSERT INTO conversation_message_embedding_status(message_id, needs_embedding)
+    VALUES (NEW.id, 1);
+END;
+
+CREATE TRIGGER conversation_message_embedding_invalidate AFTER UPDATE ON conversation_messages
+WHEN OLD.content != NEW.content BEGIN
+    UPDATE conversation_message_embedding_status
+    SET needs_embedding = 1
+    WHERE message_id = NEW.id;
+END;
+
+-- Summaries are written when a coding agent turn finishes, so rows are only
+-- tracked once a summary exists
+CREATE TABLE execution_summary_embedding_status (
+    turn_id          BLOB PRIMARY KEY REFERENCES coding_agent_turns(id) ON DELETE CASCADE,
+    needs_embedding  INTEGER NOT NULL DEFAULT 1,
+    last_embedded_at TEXT,
+    embedding_model  TEXT
+);
+
+INSERT INTO execution_summary_embedding_status(turn_id, needs_embedding)
+SELECT id, 1 FROM coding_agent_turns WHERE summary IS NOT NULL AND summary != '';
+
+CREATE TRIGGER execution_summary_embedding_status_insert AFTER INSERT ON coding_agent_turns
+WHEN NEW.summary IS NOT NULL AND NEW.summary != '' BEGIN
+    INSERT INTO execution_summary_embedding_status(turn_id, needs_embedding)
+    VALUES (NEW.id, 1);
+END;
+
+CREATE TRIGGER execution_summary_embedding_status_update AFTER UPDATE ON coding_agent_turns
+WHEN NEW.summary IS NOT NULL AND NEW.summary != '' AND OLD.summary IS NOT NEW.summary BEGIN
+    INSERT INTO execution_summary_embedding_status(turn_id, needs_embedding)
+    VALUES (NEW.id, 1)
+    ON CONFLICT(turn_id) DO UPDATE SET needs_embedding = 1;
+END;
@@ -0,0 +1,4 @@
+-- Wrap every execution for a project in `nix develop` / `devenv shell`
+-- (or a custom command prefix) so agents get the pinned toolchain
+ALTER TABLE projects ADD COLUMN dev_shell TEXT;
+ALTER TABLE projects ADD COLUMN dev_shell_command TEXT;
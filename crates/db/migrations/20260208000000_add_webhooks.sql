@@ -0,0 +1,21 @@
+-- Outgoing webhooks POSTed to user-defined URLs when domain events fire.
+-- project_id NULL subscribes to events from every project; events is a JSON
+-- array of event names.
+
+CREATE TABLE webhooks (
+    id                   BLOB PRIMARY KEY,
+    project_id           BLOB,
+    name                 TEXT NOT NULL,
+    url                  TEXT NOT NULL,
+    secret               TEXT,
+    events               TEXT NOT NULL DEFAULT '[]',
+    payload_template     TEXT,
+    enabled              INTEGER NOT NULL DEFAULT 1,
+    last_delivery_at     TEXT,
+    last_delivery_error  TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhooks_project_id ON webhooks(project_id);
@@ -0,0 +1,15 @@
+-- Periodic CPU/RSS samples of each running execution's process tree, summed
+-- over the group leader and all of its descendants.
+
+CREATE TABLE execution_process_resource_samples (
+    id                    BLOB PRIMARY KEY,
+    execution_process_id  BLOB NOT NULL,
+    cpu_percent           REAL NOT NULL,
+    rss_bytes             INTEGER NOT NULL,
+    process_count         INTEGER NOT NULL,
+    sampled_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_process_resource_samples_process
+    ON execution_process_resource_samples(execution_process_id, sampled_at);
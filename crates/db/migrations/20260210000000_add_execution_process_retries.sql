@@ -0,0 +1,22 @@
+-- Automatic re-runs of coding agent executions that failed for a transient
+-- reason. One row per retry, linking the failed process to its replacement.
+
+CREATE TABLE execution_process_retries (
+    id                          BLOB PRIMARY KEY,
+    execution_process_id        BLOB NOT NULL,
+    retry_execution_process_id  BLOB,
+    attempt                     INTEGER NOT NULL,
+    reason                      TEXT NOT NULL
+                                    CHECK (reason IN ('rate_limit', 'network', 'startup_crash')),
+    delay_secs                  INTEGER NOT NULL,
+    matched_line                TEXT,
+    error                       TEXT,
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (retry_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_execution_process_retries_process
+    ON execution_process_retries(execution_process_id);
+CREATE INDEX idx_execution_process_retries_retry_process
+    ON execution_process_retries(retry_execution_process_id);
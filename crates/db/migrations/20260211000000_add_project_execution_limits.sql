@@ -0,0 +1,14 @@
+-- Per-project cap on concurrently running executions, and a user-controlled
+-- order for the execution queue backlog.
+
+ALTER TABLE projects ADD COLUMN max_concurrent_executions INTEGER;
+
+ALTER TABLE execution_queue ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- Existing entries keep their FIFO order
+UPDATE execution_queue
+SET position = (
+    SELECT COUNT(*)
+    FROM execution_queue q
+    WHERE q.queued_at <= execution_queue.queued_at
+);
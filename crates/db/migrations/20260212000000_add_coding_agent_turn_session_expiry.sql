@@ -0,0 +1,4 @@
+-- Agent sessions that can no longer be resumed, e.g. after a CLI upgrade.
+-- Follow-ups skip expired session ids and start a fresh agent session.
+
+ALTER TABLE coding_agent_turns ADD COLUMN agent_session_expired BOOLEAN NOT NULL DEFAULT FALSE;
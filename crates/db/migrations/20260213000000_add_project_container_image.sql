@@ -0,0 +1,3 @@
+-- Image that executions of projects using the `docker` dev shell run in.
+
+ALTER TABLE projects ADD COLUMN container_image TEXT;
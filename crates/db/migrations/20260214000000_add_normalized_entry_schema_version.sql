@@ -0,0 +1,3 @@
+-- Entries stored before versioning are schema version 0 and get upgraded on read
+
+ALTER TABLE execution_process_normalized_entries ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,22 @@
+-- External links attached to tasks: design docs, tickets and pull requests.
+-- unfurled_* columns hold the title and status fetched from the linked
+-- service; include_in_prompt adds the link to the task's agent prompt.
+
+CREATE TABLE task_links (
+    id                 BLOB PRIMARY KEY,
+    task_id            BLOB NOT NULL,
+    url                TEXT NOT NULL,
+    title              TEXT,
+    kind               TEXT NOT NULL DEFAULT 'other'
+                       CHECK (kind IN ('design_doc', 'ticket', 'pull_request', 'other')),
+    include_in_prompt  INTEGER NOT NULL DEFAULT 1,
+    unfurled_title     TEXT,
+    unfurled_status    TEXT,
+    unfurled_at        TEXT,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (task_id, url)
+);
+
+CREATE INDEX idx_task_links_task_id ON task_links(task_id);
@@ -0,0 +1,25 @@
+-- Recurring tasks. When next_run_at passes, the scheduler creates a task from
+-- task_title/task_description, opens a workspace on repos (a JSON array of
+-- {repo_id, target_branch}) and starts it with executor_profile_id.
+
+CREATE TABLE task_schedules (
+    id                   BLOB PRIMARY KEY,
+    project_id           BLOB NOT NULL,
+    cron                 TEXT NOT NULL,
+    task_title           TEXT NOT NULL,
+    task_description     TEXT,
+    executor_profile_id  TEXT NOT NULL,
+    repos                TEXT NOT NULL DEFAULT '[]',
+    enabled              INTEGER NOT NULL DEFAULT 1,
+    next_run_at          TEXT,
+    last_run_at          TEXT,
+    last_task_id         BLOB,
+    last_error           TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (last_task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_schedules_project_id ON task_schedules(project_id);
+CREATE INDEX idx_task_schedules_next_run_at ON task_schedules(next_run_at) WHERE enabled = 1;
@@ -0,0 +1,23 @@
+-- Merge queue entries, persisted so queued merges survive a restart. position
+-- orders a project's queue; needs_revalidation is set on entries reloaded at
+-- startup, whose branches may have moved while the server was down.
+
+CREATE TABLE merge_queue_entries (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB NOT NULL,
+    workspace_id        BLOB NOT NULL UNIQUE,
+    repo_id             BLOB NOT NULL,
+    commit_message      TEXT NOT NULL,
+    status              TEXT NOT NULL DEFAULT 'queued'
+                           CHECK (status IN ('queued', 'merging')),
+    position            INTEGER NOT NULL,
+    needs_revalidation  INTEGER NOT NULL DEFAULT 0,
+    queued_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_merge_queue_entries_project_position
+    ON merge_queue_entries(project_id, position);
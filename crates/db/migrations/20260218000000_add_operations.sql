@@ -0,0 +1,26 @@
+-- History of long-running operations (merges, commit message generation,
+-- backups, restores and imports). progress is a percentage, NULL when the
+-- operation can't estimate it. Rows still 'running' at startup were cut short
+-- by a restart and are marked failed.
+
+CREATE TABLE operations (
+    id            BLOB PRIMARY KEY,
+    kind          TEXT NOT NULL
+                     CHECK (kind IN ('merge', 'commit_message', 'backup', 'restore', 'import')),
+    state         TEXT NOT NULL DEFAULT 'running'
+                     CHECK (state IN ('running', 'succeeded', 'failed')),
+    workspace_id  BLOB,
+    task_id       BLOB,
+    progress      INTEGER CHECK (progress BETWEEN 0 AND 100),
+    message       TEXT,
+    error         TEXT,
+    started_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    finished_at   TEXT,
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_operations_started_at ON operations(started_at);
+CREATE INDEX idx_operations_workspace_id ON operations(workspace_id);
+CREATE INDEX idx_operations_running ON operations(state) WHERE state = 'running';
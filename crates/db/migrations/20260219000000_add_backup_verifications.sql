@@ -0,0 +1,19 @@
+-- Results of checking backup archives: right after each backup, on the
+-- scheduled restore drill, or on request. table_counts is a JSON array of
+-- {table, rows, expected_rows}; expected_rows is only set after a backup,
+-- when the live database was counted just before archiving.
+
+CREATE TABLE backup_verifications (
+    id            BLOB PRIMARY KEY,
+    backup_id     TEXT NOT NULL,
+    kind          TEXT NOT NULL CHECK (kind IN ('after_backup', 'drill', 'manual')),
+    passed        INTEGER NOT NULL,
+    integrity     TEXT,
+    table_counts  TEXT NOT NULL DEFAULT '[]',
+    error         TEXT,
+    duration_ms   INTEGER NOT NULL,
+    verified_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_backup_verifications_backup_id ON backup_verifications(backup_id);
+CREATE INDEX idx_backup_verifications_verified_at ON backup_verifications(verified_at);
@@ -0,0 +1,84 @@
+-- Full-text search over conversation messages and execution log entries.
+-- Both are indexed in one FTS5 table. text_search_documents maps each FTS
+-- row back to the message or log entry it came from, so the row can be found
+-- by key when a log entry is rewritten while its process is still running.
+--
+-- '_' is a token character so identifiers like parse_entry match as a whole.
+
+CREATE TABLE text_search_documents (
+    id                      INTEGER PRIMARY KEY,
+    conversation_message_id BLOB UNIQUE,
+    execution_process_id    BLOB,
+    entry_index             INTEGER,
+    UNIQUE (execution_process_id, entry_index)
+);
+
+CREATE VIRTUAL TABLE text_search_fts USING fts5(
+    content,
+    tokenize = "unicode61 tokenchars '_'"
+);
+
+-- Index existing rows
+INSERT INTO text_search_documents (conversation_message_id)
+SELECT id FROM conversation_messages;
+
+INSERT INTO text_search_documents (execution_process_id, entry_index)
+SELECT execution_id, entry_index FROM execution_process_normalized_entries;
+
+INSERT INTO text_search_fts (rowid, content)
+SELECT d.id, cm.content
+FROM text_search_documents d
+JOIN conversation_messages cm ON cm.id = d.conversation_message_id;
+
+INSERT INTO text_search_fts (rowid, content)
+SELECT d.id, COALESCE(json_extract(e.entry_json, '$.content'), '')
+FROM text_search_documents d
+JOIN execution_process_normalized_entries e
+  ON e.execution_id = d.execution_process_id AND e.entry_index = d.entry_index;
+
+-- Keep the index in sync with conversation_messages
+CREATE TRIGGER text_search_conversation_messages_insert AFTER INSERT ON conversation_messages BEGIN
+    INSERT INTO text_search_documents (conversation_message_id) VALUES (NEW.id);
+    INSERT INTO text_search_fts (rowid, content)
+    SELECT id, NEW.content FROM text_search_documents WHERE conversation_message_id = NEW.id;
+END;
+
+CREATE TRIGGER text_search_conversation_messages_update AFTER UPDATE OF content ON conversation_messages BEGIN
+    UPDATE text_search_fts SET content = NEW.content
+    WHERE rowid = (SELECT id FROM text_search_documents WHERE conversation_message_id = NEW.id);
+END;
+
+CREATE TRIGGER text_search_conversation_messages_delete AFTER DELETE ON conversation_messages BEGIN
+    DELETE FROM text_search_fts
+    WHERE rowid = (SELECT id FROM text_search_documents WHERE conversation_message_id = OLD.id);
+    DELETE FROM text_search_documents WHERE conversation_message_id = OLD.id;
+END;
+
+-- Keep the index in sync with execution_process_normalized_entries. Entries
+-- are upserted while a process streams, which fires the UPDATE trigger.
+CREATE TRIGGER text_search_normalized_entries_insert AFTER INSERT ON execution_process_normalized_entries BEGIN
+    INSERT INTO text_search_documents (execution_process_id, entry_index)
+    VALUES (NEW.execution_id, NEW.entry_index);
+    INSERT INTO text_search_fts (rowid, content)
+    SELECT id, COALESCE(json_extract(NEW.entry_json, '$.content'), '')
+    FROM text_search_documents
+    WHERE execution_process_id = NEW.execution_id AND entry_index = NEW.entry_index;
+END;
+
+CREATE TRIGGER text_search_normalized_entries_update AFTER UPDATE OF entry_json ON execution_process_normalized_entries BEGIN
+    UPDATE text_search_fts SET content = COALESCE(json_extract(NEW.entry_json, '$.content'), '')
+    WHERE rowid = (
+        SELECT id FROM text_search_documents
+        WHERE execution_process_id = NEW.execution_id AND entry_index = NEW.entry_index
+    );
+END;
+
+CREATE TRIGGER text_search_normalized_entries_delete AFTER DELETE ON execution_process_normalized_entries BEGIN
+    DELETE FROM text_search_fts
+    WHERE rowid = (
+        SELECT id FROM text_search_documents
+        WHERE execution_process_id = OLD.execution_id AND entry_index = OLD.entry_index
+    );
+    DELETE FROM text_search_documents
+    WHERE execution_process_id = OLD.execution_id AND entry_index = OLD.entry_index;
+END;
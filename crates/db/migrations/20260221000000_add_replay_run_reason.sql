@@ -0,0 +1,139 @@
+-- Add 'replay' to run_reason CHECK constraint for re-running past executions
+-- SQLite requires column recreation to modify CHECK constraints
+
+-- 1. Add replacement column with the wider CHECK
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                              'cleanupscript',
+                              'codingagent',
+                              'devserver',
+                              'internalagent',
+                              'disposableconversation',
+                              'replay'));
+
+-- 2. Copy existing values
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+-- 3. Drop triggers and indexes that reference the old column
+DROP TRIGGER IF EXISTS update_task_execution_status_after_process_insert;
+DROP TRIGGER IF EXISTS update_task_execution_status_after_process_update;
+DROP TRIGGER IF EXISTS update_task_execution_status_after_process_delete;
+
+DROP INDEX IF EXISTS idx_execution_processes_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_status_run_reason;
+DROP INDEX IF EXISTS idx_execution_processes_session_run_reason_created;
+DROP INDEX IF EXISTS idx_execution_processes_conversation_run_reason_created;
+
+-- 4. Remove the old column
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+-- 5. Rename new column to canonical name
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+
+-- 6. Re-create indexes
+CREATE INDEX idx_execution_processes_run_reason
+  ON execution_processes(run_reason);
+
+CREATE INDEX idx_execution_processes_session_status_run_reason
+  ON execution_processes (session_id, status, run_reason);
+
+CREATE INDEX idx_execution_processes_session_run_reason_created
+  ON execution_processes (session_id, run_reason, created_at DESC);
+
+CREATE INDEX idx_execution_processes_conversation_run_reason_created
+  ON execution_processes (conversation_session_id, run_reason, created_at DESC);
+
+-- 7. Re-create triggers unchanged; replays don't count towards task status
+-- When an execution_process is inserted, update task status
+CREATE TRIGGER update_task_execution_status_after_process_insert
+AFTER INSERT ON execution_processes
+FOR EACH ROW
+WHEN NEW.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+BEGIN
+    UPDATE tasks
+    SET has_in_progress_attempt = CASE WHEN NEW.status = 'running' THEN 1 ELSE has_in_progress_attempt END,
+        last_attempt_failed = CASE WHEN NEW.status IN ('failed', 'killed') THEN 1 ELSE 0 END
+    WHERE id = (
+        SELECT w.task_id
+        FROM sessions s
+        JOIN workspaces w ON w.id = s.workspace_id
+        WHERE s.id = NEW.session_id
+    );
+END;
+
+-- When an execution_process status changes, update task status
+CREATE TRIGGER update_task_execution_status_after_process_update
+AFTER UPDATE OF status ON execution_processes
+FOR EACH ROW
+WHEN OLD.status != NEW.status AND NEW.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+BEGIN
+    UPDATE tasks
+    SET has_in_progress_attempt = (
+            SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+            FROM workspaces w
+            JOIN sessions s ON s.workspace_id = w.id
+            JOIN execution_processes ep ON ep.session_id = s.id
+            WHERE w.task_id = tasks.id
+              AND ep.status = 'running'
+              AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+        ),
+        last_attempt_failed = COALESCE((
+            SELECT CASE WHEN ep_status IN ('failed', 'killed') THEN 1 ELSE 0 END
+            FROM (
+                SELECT ep.status AS ep_status
+                FROM workspaces w
+                JOIN sessions s ON s.workspace_id = w.id
+                JOIN execution_processes ep ON ep.session_id = s.id
+                WHERE w.task_id = tasks.id
+                  AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+                ORDER BY ep.created_at DESC
+                LIMIT 1
+            )
+        ), 0)
+    WHERE id = (
+        SELECT w.task_id
+        FROM sessions s
+        JOIN workspaces w ON w.id = s.workspace_id
+        WHERE s.id = NEW.session_id
+    );
+END;
+
+-- When an execution_process is deleted, recalculate task status
+CREATE TRIGGER update_task_execution_status_after_process_delete
+AFTER DELETE ON execution_processes
+FOR EACH ROW
+WHEN OLD.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+BEGIN
+    UPDATE tasks
+    SET has_in_progress_attempt = (
+            SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+            FROM workspaces w
+            JOIN sessions s ON s.workspace_id = w.id
+            JOIN execution_processes ep ON ep.session_id = s.id
+            WHERE w.task_id = tasks.id
+              AND ep.status = 'running'
+              AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+        ),
+        last_attempt_failed = COALESCE((
+            SELECT CASE WHEN ep_status IN ('failed', 'killed') THEN 1 ELSE 0 END
+            FROM (
+                SELECT ep.status AS ep_status
+                FROM workspaces w
+                JOIN sessions s ON s.workspace_id = w.id
+                JOIN execution_processes ep ON ep.session_id = s.id
+                WHERE w.task_id = tasks.id
+                  AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+                ORDER BY ep.created_at DESC
+                LIMIT 1
+            )
+        ), 0)
+    WHERE id = (
+        SELECT w.task_id
+        FROM sessions s
+        JOIN workspaces w ON w.id = s.workspace_id
+        WHERE s.id = OLD.session_id
+    );
+END;
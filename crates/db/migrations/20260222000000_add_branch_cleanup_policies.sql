@@ -0,0 +1,12 @@
+-- Per-project settings for the branch janitor, which removes local task
+-- branches once their workspace is gone and their merge is confirmed.
+-- mode 'archive' moves the branch under refs/vk-archive/ instead of deleting
+-- it. Projects without a row behave as mode 'off'.
+
+CREATE TABLE branch_cleanup_policies (
+    project_id    BLOB PRIMARY KEY,
+    mode          TEXT NOT NULL DEFAULT 'off' CHECK (mode IN ('off', 'delete', 'archive')),
+    min_age_days  INTEGER NOT NULL DEFAULT 7,
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Repo-level setup/cleanup scripts, used by projects that don't set their own
+ALTER TABLE repos ADD COLUMN setup_script TEXT;
+ALTER TABLE repos ADD COLUMN cleanup_script TEXT;
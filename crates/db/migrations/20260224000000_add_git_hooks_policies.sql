@@ -0,0 +1,12 @@
+-- Per-project handling of repo-local git hooks (husky, pre-commit) when
+-- vibe-kanban commits or squash-merges. 'run' lets hooks block the commit,
+-- 'skip' passes --no-verify, and 'report' runs them but commits anyway with
+-- --no-verify if they fail, logging their output. Projects without a row
+-- behave as mode 'run'.
+
+CREATE TABLE git_hooks_policies (
+    project_id    BLOB PRIMARY KEY,
+    mode          TEXT NOT NULL DEFAULT 'run' CHECK (mode IN ('run', 'skip', 'report')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Run the pre-commit framework on an agent's changed files after each
+-- successful run, in repos with a .pre-commit-config.yaml
+ALTER TABLE git_hooks_policies ADD COLUMN run_pre_commit BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,11 @@
+-- Per-project sandbox for agent and script executions: writable directories
+-- besides the workspace, denied commands and network access, stored as the
+-- JSON-serialized executors::sandbox::SandboxPolicy. Projects without a row
+-- run unsandboxed.
+
+CREATE TABLE sandbox_policies (
+    project_id    BLOB PRIMARY KEY,
+    policy        TEXT NOT NULL,
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,40 @@
+-- Guided `git bisect` runs over a repo's good..bad range. Each tested commit
+-- is a bisect_steps row with the validation command's exit code, the tail
+-- of its output and the verdict given to git, which an agent may have
+-- decided from the output. A run ends 'found' with culprit_commit set, or
+-- 'failed' with error. Runs still 'running' at startup were cut short by a
+-- restart and are marked failed.
+
+CREATE TABLE bisect_runs (
+    id              BLOB PRIMARY KEY,
+    repo_id         BLOB NOT NULL,
+    task_id         BLOB,
+    good_commit     TEXT NOT NULL,
+    bad_commit      TEXT NOT NULL,
+    command         TEXT NOT NULL,
+    use_agent       BOOLEAN NOT NULL DEFAULT FALSE,
+    status          TEXT NOT NULL DEFAULT 'running'
+                       CHECK (status IN ('running', 'found', 'failed')),
+    culprit_commit  TEXT,
+    error           TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_bisect_runs_task_id ON bisect_runs(task_id);
+
+CREATE TABLE bisect_steps (
+    id                 BLOB PRIMARY KEY,
+    run_id             BLOB NOT NULL,
+    commit_sha         TEXT NOT NULL,
+    exit_code          INTEGER,
+    output             TEXT NOT NULL,
+    verdict            TEXT NOT NULL CHECK (verdict IN ('good', 'bad', 'skip')),
+    agent_interpreted  BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (run_id) REFERENCES bisect_runs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_bisect_steps_run_id ON bisect_steps(run_id);
@@ -0,0 +1,5 @@
+-- Task priority for the execution queue: 0 = low, 1 = normal, 2 = high,
+-- 3 = urgent. Queued executions of higher-priority tasks start first.
+
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 1
+    CHECK (priority BETWEEN 0 AND 3);
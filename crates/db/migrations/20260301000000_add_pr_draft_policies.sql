@@ -0,0 +1,16 @@
+-- Per-project handling of draft pull requests. 'draft_by_default' opens
+-- task attempt PRs as drafts when the request does not say, and
+-- 'auto_ready' lets the PR monitor mark them ready for review once the
+-- review-attention analysis finds the task needs no attention. Projects
+-- without a row open regular PRs.
+
+CREATE TABLE pr_draft_policies (
+    project_id        BLOB PRIMARY KEY,
+    draft_by_default  INTEGER NOT NULL DEFAULT 0,
+    auto_ready        INTEGER NOT NULL DEFAULT 0,
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Whether the PR was opened as a draft and has not been marked ready by us
+ALTER TABLE merges ADD COLUMN pr_is_draft INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Library of user-defined prompts with `{variable}` placeholders. The
+-- config references templates by id for commit messages, feedback
+-- collection and review attention.
+
+CREATE TABLE prompt_templates (
+    id           BLOB PRIMARY KEY,
+    name         TEXT NOT NULL,
+    description  TEXT,
+    content      TEXT NOT NULL CHECK(content != ''),
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,28 @@
+-- Accounts for servers shared by several people. With no users the server
+-- stays in single-user mode and needs no sign-in. preferences holds the
+-- user's overlay on the global config (theme, editor, notifications) as JSON.
+
+CREATE TABLE users (
+    id             BLOB PRIMARY KEY,
+    username       TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    display_name   TEXT,
+    password_hash  TEXT NOT NULL,
+    preferences    TEXT NOT NULL DEFAULT '{}',
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+-- Sign-in sessions; only a SHA-256 hash of the cookie token is stored
+CREATE TABLE user_sessions (
+    id          BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    token_hash  TEXT NOT NULL UNIQUE,
+    expires_at  TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_user_sessions_user_id ON user_sessions(user_id);
+
+ALTER TABLE tasks ADD COLUMN created_by BLOB REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE operations ADD COLUMN created_by BLOB REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,13 @@
+-- Per-project checks the merge queue runs before merging a branch.
+-- 'check_command' runs in the rebased worktree and must exit 0;
+-- 'require_github_checks' needs every GitHub check run on the pushed branch
+-- to have passed. Projects without a row merge without checks.
+
+CREATE TABLE merge_check_policies (
+    project_id             BLOB PRIMARY KEY,
+    check_command          TEXT,
+    require_github_checks  INTEGER NOT NULL DEFAULT 0,
+    timeout_secs           INTEGER NOT NULL DEFAULT 600,
+    updated_at             TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,22 @@
+-- Permission problems found in a workspace after an execution: files made
+-- world-writable, setuid/setgid bits, and new symlinks that point outside
+-- the worktree. Merging the workspace is blocked while any finding has no
+-- acknowledged_at.
+
+CREATE TABLE permission_findings (
+    id                    BLOB PRIMARY KEY,
+    execution_process_id  BLOB NOT NULL,
+    workspace_id          BLOB NOT NULL,
+    kind                  TEXT NOT NULL
+                             CHECK (kind IN ('world_writable', 'setuid', 'symlink_escape')),
+    path                  TEXT NOT NULL,
+    detail                TEXT NOT NULL,
+    acknowledged_at       TEXT,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_permission_findings_workspace_id ON permission_findings(workspace_id);
+CREATE INDEX idx_permission_findings_execution_process_id
+    ON permission_findings(execution_process_id);
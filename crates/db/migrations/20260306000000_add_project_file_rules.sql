@@ -0,0 +1,17 @@
+-- File patterns an agent may or may not change, checked against the live
+-- diff while it runs. 'deny' rules forbid matching paths; when any
+-- 'allow_only' rule applies, every changed path must match one of them.
+-- Rules with a task_label only apply to tasks tagged #<task_label>.
+
+CREATE TABLE project_file_rules (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    pattern         TEXT NOT NULL,
+    effect          TEXT NOT NULL CHECK (effect IN ('deny', 'allow_only')),
+    task_label      TEXT,
+    reason          TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_file_rules_project_id ON project_file_rules(project_id);
@@ -0,0 +1,15 @@
+-- Administrative actions that change or remove user data, kept for
+-- compliance. details is a JSON object whose shape depends on action; it
+-- never holds the data that was removed.
+
+CREATE TABLE audit_log (
+    id              BLOB PRIMARY KEY,
+    action          TEXT NOT NULL CHECK (action IN ('redaction')),
+    actor_id        BLOB,
+    summary         TEXT NOT NULL,
+    details         TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at);
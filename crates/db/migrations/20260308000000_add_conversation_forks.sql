@@ -0,0 +1,12 @@
+-- A conversation forked from another at one of its messages. The fork starts
+-- with copies of the messages up to that point, and its first agent turn
+-- resumes fork_agent_session_id, the agent session as of that message.
+
+ALTER TABLE conversation_sessions ADD COLUMN forked_from_session_id BLOB
+    REFERENCES conversation_sessions(id) ON DELETE SET NULL;
+ALTER TABLE conversation_sessions ADD COLUMN forked_from_message_id BLOB
+    REFERENCES conversation_messages(id) ON DELETE SET NULL;
+ALTER TABLE conversation_sessions ADD COLUMN fork_agent_session_id TEXT;
+
+CREATE INDEX idx_conversation_sessions_forked_from_session_id
+    ON conversation_sessions(forked_from_session_id);
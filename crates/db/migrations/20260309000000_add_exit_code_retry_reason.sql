@@ -0,0 +1,15 @@
+-- Add 'exit_code' to the retry reason CHECK constraint for retries of
+-- executions that exited with a code the retry policy treats as transient.
+-- SQLite requires column recreation to modify CHECK constraints
+
+ALTER TABLE execution_process_retries
+  ADD COLUMN reason_new TEXT NOT NULL DEFAULT 'network'
+    CHECK (reason_new IN ('rate_limit', 'network', 'startup_crash', 'exit_code'));
+
+UPDATE execution_process_retries
+  SET reason_new = reason;
+
+ALTER TABLE execution_process_retries DROP COLUMN reason;
+
+ALTER TABLE execution_process_retries
+  RENAME COLUMN reason_new TO reason;
@@ -0,0 +1,15 @@
+-- Add 'project_purge' to the audit_log action CHECK constraint, recorded
+-- when a project is deleted together with all data derived from it.
+-- SQLite requires column recreation to modify CHECK constraints
+
+ALTER TABLE audit_log
+  ADD COLUMN action_new TEXT NOT NULL DEFAULT 'redaction'
+    CHECK (action_new IN ('redaction', 'project_purge'));
+
+UPDATE audit_log
+  SET action_new = action;
+
+ALTER TABLE audit_log DROP COLUMN action;
+
+ALTER TABLE audit_log
+  RENAME COLUMN action_new TO action;
@@ -0,0 +1,16 @@
+-- Environment variables set for every execution of one task, on top of the
+-- VK_* variables. Secret values are masked when listed.
+
+CREATE TABLE task_env_vars (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    value       TEXT NOT NULL,
+    secret      INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (task_id, name)
+);
+
+CREATE INDEX idx_task_env_vars_task_id ON task_env_vars(task_id);
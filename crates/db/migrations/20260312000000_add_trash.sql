@@ -0,0 +1,9 @@
+-- Deleting a task or project moves it to the trash: deleted_at is set and the
+-- row is left out of lookups until it is restored or purged after the
+-- configured retention. Tasks of a trashed project go and come back with it.
+
+ALTER TABLE projects ADD COLUMN deleted_at TEXT;
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_projects_deleted_at ON projects(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at) WHERE deleted_at IS NOT NULL;
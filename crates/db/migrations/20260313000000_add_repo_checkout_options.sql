@@ -0,0 +1,3 @@
+-- Opt-outs for fetching submodules and LFS objects into new worktrees
+ALTER TABLE repos ADD COLUMN skip_submodules INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE repos ADD COLUMN skip_lfs INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+-- Tool calls agents may make without asking for approval, per project. A row
+-- without an executor is the project's default; a row for an executor
+-- replaces it for that executor. List columns hold JSON string arrays.
+
+CREATE TABLE approval_policies (
+    id                BLOB PRIMARY KEY,
+    project_id        BLOB NOT NULL,
+    executor          TEXT,
+    allowed_tools     TEXT NOT NULL DEFAULT '[]',
+    allowed_commands  TEXT NOT NULL DEFAULT '[]',
+    path_globs        TEXT NOT NULL DEFAULT '[]',
+    max_file_size     INTEGER,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_approval_policies_project_executor
+    ON approval_policies(project_id, COALESCE(executor, ''));
@@ -0,0 +1,35 @@
+-- People following a task. Watchers get notifications of their own when the
+-- task changes status, an execution fails or its work is merged.
+
+CREATE TABLE task_watchers (
+    task_id    BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    user_id    BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, user_id)
+);
+
+CREATE INDEX idx_task_watchers_user_id ON task_watchers(user_id);
+
+-- The user a notification is meant for; NULL for everyone
+ALTER TABLE notifications ADD COLUMN user_id BLOB REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id);
+
+-- Add the task watching types, and 'agent_question_needed' which was never
+-- allowed, to the notification_type CHECK constraint.
+-- SQLite requires column recreation to modify CHECK constraints
+
+ALTER TABLE notifications
+  ADD COLUMN notification_type_new TEXT NOT NULL DEFAULT 'agent_complete'
+    CHECK (notification_type_new IN ('agent_complete', 'agent_approval_needed',
+                                     'agent_question_needed', 'agent_error',
+                                     'conversation_response', 'task_status_changed',
+                                     'task_merged'));
+
+UPDATE notifications
+  SET notification_type_new = notification_type;
+
+ALTER TABLE notifications DROP COLUMN notification_type;
+
+ALTER TABLE notifications
+  RENAME COLUMN notification_type_new TO notification_type;
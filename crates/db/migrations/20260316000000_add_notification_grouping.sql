@@ -0,0 +1,8 @@
+-- Notifications sharing a group key within a short window are folded into
+-- one, e.g. "5 executions failed in project X". occurrences counts how many
+-- were folded into the row.
+
+ALTER TABLE notifications ADD COLUMN group_key TEXT;
+ALTER TABLE notifications ADD COLUMN occurrences INTEGER NOT NULL DEFAULT 1;
+
+CREATE INDEX idx_notifications_group_key ON notifications(group_key, created_at);
@@ -0,0 +1,13 @@
+-- Why a failed execution failed, as classified from its stderr and exit
+-- code, with a suggested fix shown in notifications and the UI.
+
+CREATE TABLE execution_process_failures (
+    execution_process_id  BLOB PRIMARY KEY,
+    reason                TEXT NOT NULL
+                              CHECK (reason IN ('auth_expired', 'rate_limited', 'disk_full',
+                                                'cli_not_found', 'merge_conflict', 'network')),
+    matched_line          TEXT,
+    suggested_fix         TEXT NOT NULL,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- Bumped on every save, so editors of the same scratch (e.g. task notes open
+-- in two tabs) can detect that someone else saved first.
+
+ALTER TABLE scratch ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
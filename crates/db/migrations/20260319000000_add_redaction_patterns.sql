@@ -0,0 +1,10 @@
+-- Per-project regexes masked in agent and script output, on top of the
+-- built-in and configured detectors, stored as a JSON array of strings.
+-- Projects without a row have no patterns of their own.
+
+CREATE TABLE redaction_patterns (
+    project_id    BLOB PRIMARY KEY,
+    patterns      TEXT NOT NULL,
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
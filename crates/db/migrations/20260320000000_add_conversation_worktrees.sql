@@ -0,0 +1,15 @@
+-- Worktrees created for conversations. Conversations forked from one share
+-- its container, so a container is only removed once every conversation
+-- using it has been idle long enough, and recreated when one resumes.
+
+CREATE TABLE conversation_worktrees (
+    conversation_session_id BLOB PRIMARY KEY REFERENCES conversation_sessions(id) ON DELETE CASCADE,
+    container_ref           TEXT NOT NULL,
+    branch                  TEXT NOT NULL,
+    last_used_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    -- Set when the worktrees were removed; cleared when they are recreated
+    released_at             TEXT,
+    created_at              TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_conversation_worktrees_container_ref ON conversation_worktrees(container_ref);
@@ -0,0 +1,17 @@
+-- Blueprints for new projects. repositories is a JSON array of
+-- {display_name, path, url, setup_script, copy_files}; a repo whose path does
+-- not exist is cloned from url. tasks is a JSON array of {title, description}
+-- created in every project made from the template, and labels a JSON array
+-- of strings to find templates by.
+
+CREATE TABLE project_templates (
+    id                   BLOB PRIMARY KEY,
+    name                 TEXT NOT NULL,
+    description          TEXT,
+    repositories         TEXT NOT NULL DEFAULT '[]',
+    executor_profile_id  TEXT,
+    tasks                TEXT NOT NULL DEFAULT '[]',
+    labels               TEXT NOT NULL DEFAULT '[]',
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,45 @@
+-- Facts and preferences remembered across a project's tasks and
+-- conversations. Memories are extracted from finished task attempts by an
+-- internal agent pass when a project opts in, or written by hand, and the
+-- most relevant ones are added to new prompts. Vectors live in a vec0 table
+-- created at runtime, like the other embeddings.
+
+CREATE TABLE project_memory_settings (
+    project_id    BLOB PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    enabled       INTEGER NOT NULL DEFAULT 0,
+    -- How many memories are added to a prompt
+    max_injected  INTEGER NOT NULL DEFAULT 5,
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE project_memories (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    kind            TEXT NOT NULL DEFAULT 'fact' CHECK (kind IN ('fact', 'preference')),
+    content         TEXT NOT NULL,
+    -- Task whose attempt the memory was extracted from; NULL when written by hand
+    source_task_id  BLOB REFERENCES tasks(id) ON DELETE SET NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_project_memories_project_id ON project_memories(project_id);
+
+CREATE TABLE project_memory_embedding_status (
+    memory_id        BLOB PRIMARY KEY REFERENCES project_memories(id) ON DELETE CASCADE,
+    needs_embedding  INTEGER NOT NULL DEFAULT 1,
+    last_embedded_at TEXT,
+    embedding_model  TEXT
+);
+
+CREATE TRIGGER project_memory_embedding_status_insert AFTER INSERT ON project_memories BEGIN
+    INSERT INTO project_memory_embedding_status(memory_id, needs_embedding)
+    VALUES (NEW.id, 1);
+END;
+
+CREATE TRIGGER project_memory_embedding_invalidate AFTER UPDATE ON project_memories
+WHEN OLD.content != NEW.content BEGIN
+    UPDATE project_memory_embedding_status
+    SET needs_embedding = 1
+    WHERE memory_id = NEW.id;
+END;
@@ -0,0 +1,20 @@
+-- Comments left on lines of a workspace's diff. Unresolved comments can be
+-- compiled into a follow-up prompt for the agent; sending them resolves them.
+
+CREATE TABLE review_comments (
+    id            BLOB PRIMARY KEY,
+    workspace_id  BLOB NOT NULL REFERENCES workspaces(id) ON DELETE CASCADE,
+    -- Path as shown in the diff, prefixed with the repo name
+    file_path     TEXT NOT NULL,
+    -- Which side of the diff the line numbers refer to
+    side          TEXT NOT NULL DEFAULT 'new' CHECK (side IN ('old', 'new')),
+    line_start    INTEGER NOT NULL,
+    line_end      INTEGER NOT NULL,
+    body          TEXT NOT NULL,
+    resolved      INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    CHECK (line_start >= 1 AND line_end >= line_start)
+);
+
+CREATE INDEX idx_review_comments_workspace_id ON review_comments(workspace_id);
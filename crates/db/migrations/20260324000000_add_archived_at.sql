@@ -0,0 +1,10 @@
+-- Archived projects and tasks stay visible on request but are finished with:
+-- list endpoints leave them out unless asked, and background workers (PR
+-- monitor, embeddings, file-search cache warming, autopilot) skip them. A
+-- task of an archived project counts as archived.
+
+ALTER TABLE projects ADD COLUMN archived_at TEXT;
+ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+
+CREATE INDEX idx_projects_archived_at ON projects(archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX idx_tasks_archived_at ON tasks(archived_at) WHERE archived_at IS NOT NULL;
@@ -0,0 +1,5 @@
+PRAGMA foreign_keys = ON;
+
+-- Run reason of a queued follow-up; NULL starts a coding agent turn.
+-- Replays are queued like follow-ups but must keep their own run reason.
+ALTER TABLE execution_queue ADD COLUMN run_reason TEXT;
@@ -0,0 +1,6 @@
+PRAGMA foreign_keys = ON;
+
+-- Retry record of a queued re-run of a transiently failed execution; the
+-- started execution is linked to it so later failures count as further attempts.
+ALTER TABLE execution_queue ADD COLUMN retry_id BLOB
+    REFERENCES execution_process_retries(id) ON DELETE SET NULL;
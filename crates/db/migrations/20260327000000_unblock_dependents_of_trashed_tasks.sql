@@ -0,0 +1,80 @@
+-- A trashed dependency no longer blocks its dependents; restoring it blocks
+-- them again until it is done. The is_blocked triggers are recreated to leave
+-- trashed dependencies out, and dependents are recomputed when a task is
+-- trashed or restored.
+
+DROP TRIGGER IF EXISTS update_is_blocked_after_dependency_delete;
+DROP TRIGGER IF EXISTS update_is_blocked_after_dependency_insert;
+DROP TRIGGER IF EXISTS update_dependents_is_blocked_after_task_status_change;
+
+CREATE TRIGGER update_is_blocked_after_dependency_delete
+AFTER DELETE ON task_dependencies
+FOR EACH ROW
+BEGIN
+    UPDATE tasks
+    SET is_blocked = (
+        SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+        FROM task_dependencies td
+        JOIN tasks dep ON dep.id = td.depends_on_id
+        WHERE td.task_id = OLD.task_id AND dep.status != 'done' AND dep.deleted_at IS NULL
+    )
+    WHERE id = OLD.task_id;
+END;
+
+CREATE TRIGGER update_is_blocked_after_dependency_insert
+AFTER INSERT ON task_dependencies
+FOR EACH ROW
+BEGIN
+    UPDATE tasks
+    SET is_blocked = (
+        SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+        FROM task_dependencies td
+        JOIN tasks dep ON dep.id = td.depends_on_id
+        WHERE td.task_id = NEW.task_id AND dep.status != 'done' AND dep.deleted_at IS NULL
+    )
+    WHERE id = NEW.task_id;
+END;
+
+CREATE TRIGGER update_dependents_is_blocked_after_task_status_change
+AFTER UPDATE OF status ON tasks
+FOR EACH ROW
+WHEN OLD.status != NEW.status
+BEGIN
+    UPDATE tasks
+    SET is_blocked = (
+        SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+        FROM task_dependencies td
+        JOIN tasks dep ON dep.id = td.depends_on_id
+        WHERE td.task_id = tasks.id AND dep.status != 'done' AND dep.deleted_at IS NULL
+    )
+    WHERE id IN (
+        SELECT task_id FROM task_dependencies WHERE depends_on_id = NEW.id
+    );
+END;
+
+-- Trigger to update is_blocked for dependent tasks when a task is trashed or restored
+CREATE TRIGGER update_dependents_is_blocked_after_task_trash
+AFTER UPDATE OF deleted_at ON tasks
+FOR EACH ROW
+WHEN OLD.deleted_at IS NOT NEW.deleted_at
+BEGIN
+    UPDATE tasks
+    SET is_blocked = (
+        SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+        FROM task_dependencies td
+        JOIN tasks dep ON dep.id = td.depends_on_id
+        WHERE td.task_id = tasks.id AND dep.status != 'done' AND dep.deleted_at IS NULL
+    )
+    WHERE id IN (
+        SELECT task_id FROM task_dependencies WHERE depends_on_id = NEW.id
+    );
+END;
+
+-- Unblock dependents of tasks already in the trash
+UPDATE tasks
+SET is_blocked = (
+    SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
+    FROM task_dependencies td
+    JOIN tasks dep ON dep.id = td.depends_on_id
+    WHERE td.task_id = tasks.id AND dep.status != 'done' AND dep.deleted_at IS NULL
+);
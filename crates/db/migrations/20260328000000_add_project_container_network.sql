@@ -0,0 +1,4 @@
+-- Docker network that executions of projects using the `docker` dev shell
+-- run on; NULL keeps the host network.
+
+ALTER TABLE projects ADD COLUMN container_network TEXT;
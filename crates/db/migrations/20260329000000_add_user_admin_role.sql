@@ -0,0 +1,8 @@
+-- Admins manage accounts; there is always at least one. The oldest existing
+-- account becomes the first admin.
+
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE users
+SET is_admin = TRUE
+WHERE id = (SELECT id FROM users ORDER BY created_at ASC LIMIT 1);
@@ -0,0 +1,5 @@
+-- Project secret values move to the secret store, like credentials in the
+-- config. secret_ref names a secret's store entry; values saved before it
+-- existed are moved into the store at startup and blanked here.
+
+ALTER TABLE project_secrets ADD COLUMN secret_ref TEXT;
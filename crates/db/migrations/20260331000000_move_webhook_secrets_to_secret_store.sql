@@ -0,0 +1,4 @@
+-- Webhook signing secrets now live in the secret store; `secret_ref` names
+-- the store entry. `secret` only keeps values saved by older versions until
+-- they are moved into the store at startup.
+ALTER TABLE webhooks ADD COLUMN secret_ref TEXT;
@@ -0,0 +1,16 @@
+-- Add 'git_hooks' to the failure reason CHECK constraint for runs whose
+-- commit went through without the repo's hooks after they failed.
+-- SQLite requires column recreation to modify CHECK constraints
+
+ALTER TABLE execution_process_failures
+  ADD COLUMN reason_new TEXT NOT NULL DEFAULT 'network'
+    CHECK (reason_new IN ('auth_expired', 'rate_limited', 'disk_full',
+                          'cli_not_found', 'merge_conflict', 'network', 'git_hooks'));
+
+UPDATE execution_process_failures
+  SET reason_new = reason;
+
+ALTER TABLE execution_process_failures DROP COLUMN reason;
+
+ALTER TABLE execution_process_failures
+  RENAME COLUMN reason_new TO reason;
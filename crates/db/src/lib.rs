@@ -1,4 +1,5 @@
 use std::{
+    path::Path,
     str::FromStr,
     sync::{Arc, Once},
     time::Duration,
@@ -7,17 +8,19 @@ use std::{
 // Re-export for external use
 pub use sqlx::migrate::MigrateError as SqlxMigrateError;
 use sqlx::{
-    Error, Pool, Row, Sqlite,
+    Connection, Error, Pool, Row, Sqlite,
     migrate::MigrateError,
     sqlite::{
         SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePoolOptions,
         SqliteSynchronous,
     },
 };
-use utils::assets::asset_dir;
 
+pub mod location;
 pub mod models;
 
+pub use location::{DB_FILE, database_files, database_path};
+
 static SQLITE_VEC_INIT: Once = Once::new();
 static mut SQLITE_VEC_AVAILABLE: bool = false;
 
@@ -89,11 +92,8 @@ impl DBService {
             .acquire_timeout(Duration::from_secs(30))
     }
 
-    fn connect_options() -> Result<SqliteConnectOptions, Error> {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
+    fn connect_options(path: &Path) -> Result<SqliteConnectOptions, Error> {
+        let database_url = format!("sqlite://{}", path.to_string_lossy());
         Ok(SqliteConnectOptions::from_str(&database_url)?
             .create_if_missing(true)
             .journal_mode(SqliteJournalMode::Wal)
@@ -166,7 +166,7 @@ impl DBService {
                     Ok(())
                 } else {
                     // Could not repair - provide helpful error message
-                    let db_path = asset_dir().join("db.sqlite");
+                    let db_path = database_path()?;
                     tracing::error!(
                         "Migration {} is recorded in the database but no matching migration file exists.\n\
                         This typically happens when a migration file was renamed after being applied.\n\
@@ -186,12 +186,46 @@ impl DBService {
         }
     }
 
+    /// Version of the newest migration bundled with this build
+    pub fn latest_migration_version() -> Option<i64> {
+        sqlx::migrate!("./migrations")
+            .migrations
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+    }
+
+    /// Version of the newest migration applied to the SQLite database at
+    /// `path`, or None if the database is missing or was never migrated
+    pub async fn applied_migration_version(path: &Path) -> Result<Option<i64>, Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let options = SqliteConnectOptions::new().filename(path).read_only(true);
+        let mut conn = SqliteConnection::connect_with(&options).await?;
+        let migrated: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+        )
+        .fetch_one(&mut conn)
+        .await?;
+        let version = if migrated {
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+                .fetch_one(&mut conn)
+                .await?
+        } else {
+            None
+        };
+        let _ = conn.close().await;
+        Ok(version)
+    }
+
     pub async fn new() -> Result<DBService, Error> {
+        let path = database_path()?;
         // Initialize sqlite-vec before creating any connections
         init_sqlite_vec();
 
         let pool = Self::pool_options()
-            .connect_with(Self::connect_options()?)
+            .connect_with(Self::connect_options(&path)?)
             .await?;
         Self::run_migrations_with_repair(&pool).await?;
         sqlx::query("PRAGMA optimize").execute(&pool).await?;
@@ -208,6 +242,7 @@ impl DBService {
             + Sync
             + 'static,
     {
+        let path = database_path()?;
         // Initialize sqlite-vec before creating any connections
         init_sqlite_vec();
 
@@ -220,7 +255,7 @@ impl DBService {
                     Ok(())
                 })
             })
-            .connect_with(Self::connect_options()?)
+            .connect_with(Self::connect_options(&path)?)
             .await?;
         Self::run_migrations_with_repair(&pool).await?;
         sqlx::query("PRAGMA optimize").execute(&pool).await?;
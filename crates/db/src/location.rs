@@ -0,0 +1,77 @@
+//! Where the SQLite database lives.
+//!
+//! The database is `db.sqlite` in the asset directory unless `VK_DATABASE_URL`
+//! points somewhere else. Everything that reads or replaces the database file
+//! directly (backups, restores, relocation) resolves it through
+//! [`database_path`], so they all act on the database the pool opened.
+
+use std::path::{Path, PathBuf};
+
+use sqlx::Error;
+use utils::assets::asset_dir;
+
+/// Environment variable used to move the database out of the asset directory
+pub const DATABASE_URL_ENV: &str = "VK_DATABASE_URL";
+
+/// File name of the database in the asset directory and in backup archives
+pub const DB_FILE: &str = "db.sqlite";
+
+/// Path of the database: the `sqlite://` URL in `VK_DATABASE_URL`, or
+/// `db.sqlite` in the asset directory
+pub fn database_path() -> Result<PathBuf, Error> {
+    match std::env::var(DATABASE_URL_ENV) {
+        Ok(url) if !url.trim().is_empty() => parse_database_url(url.trim()),
+        _ => Ok(asset_dir().join(DB_FILE)),
+    }
+}
+
+/// Parse a `sqlite://<path>` URL
+pub fn parse_database_url(url: &str) -> Result<PathBuf, Error> {
+    url.strip_prefix("sqlite://")
+        .or_else(|| url.strip_prefix("sqlite:"))
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            Error::Configuration(
+                format!("Unsupported {DATABASE_URL_ENV} '{url}': expected sqlite://<path>").into(),
+            )
+        })
+}
+
+/// The database at `path` followed by the `-wal` and `-shm` files SQLite keeps
+/// next to it
+pub fn database_files(path: &Path) -> [PathBuf; 3] {
+    let with_suffix = |suffix: &str| {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        PathBuf::from(file)
+    };
+    [path.to_path_buf(), with_suffix("-wal"), with_suffix("-shm")]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_database_url() {
+        assert_eq!(
+            parse_database_url("sqlite:///tmp/vk.sqlite").unwrap(),
+            PathBuf::from("/tmp/vk.sqlite")
+        );
+        assert!(parse_database_url("postgres://vk@localhost/vk").is_err());
+        assert!(parse_database_url("sqlite://").is_err());
+    }
+
+    #[test]
+    fn test_database_files() {
+        assert_eq!(
+            database_files(Path::new("/data/vk.sqlite")),
+            [
+                PathBuf::from("/data/vk.sqlite"),
+                PathBuf::from("/data/vk.sqlite-wal"),
+                PathBuf::from("/data/vk.sqlite-shm"),
+            ]
+        );
+    }
+}
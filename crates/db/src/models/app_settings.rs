@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 #[derive(Debug, Error)]
 pub enum AppSettingsError {
@@ -20,7 +21,7 @@ pub struct AppSettings {
 }
 
 /// Response type for GitHub settings status (never exposes the actual token)
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct GitHubSettingsStatus {
     pub configured: bool,
 }
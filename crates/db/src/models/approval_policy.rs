@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Tool calls an agent may make in a project without asking for approval.
+/// A tool call is approved automatically when its tool or shell command is
+/// allowed and it stays within the path and size limits.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ApprovalPolicy {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Executor this policy applies to; None for the project's default, used
+    /// by executors without a policy of their own
+    #[schema(value_type = Option<String>)]
+    pub executor: Option<BaseCodingAgent>,
+    /// Tools approved without asking, by name (e.g. `Read`, `Grep`)
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub allowed_tools: Json<Vec<String>>,
+    /// Shell commands approved without asking, matched as a prefix on word
+    /// boundaries (`cargo test` allows `cargo test -p db`)
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub allowed_commands: Json<Vec<String>>,
+    /// Gitignore-style patterns relative to the workspace. When set, a tool
+    /// call touching a file is only approved automatically if every path
+    /// matches one of them
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub path_globs: Json<Vec<String>>,
+    /// Largest content, in bytes, a tool call may write without asking
+    #[ts(type = "number | null")]
+    pub max_file_size: Option<i64>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpsertApprovalPolicy {
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub executor: Option<BaseCodingAgent>,
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub allowed_commands: Vec<String>,
+    #[serde(default)]
+    pub path_globs: Vec<String>,
+    #[ts(type = "number | null")]
+    pub max_file_size: Option<i64>,
+}
+
+impl ApprovalPolicy {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalPolicy,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      allowed_tools as "allowed_tools!: Json<Vec<String>>",
+                      allowed_commands as "allowed_commands!: Json<Vec<String>>",
+                      path_globs as "path_globs!: Json<Vec<String>>",
+                      max_file_size,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_policies
+               WHERE project_id = $1
+               ORDER BY executor IS NOT NULL, executor ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The policy for `executor` in the project, falling back to the
+    /// project's default
+    pub async fn find_for_executor(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        executor: BaseCodingAgent,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalPolicy,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      executor as "executor: BaseCodingAgent",
+                      allowed_tools as "allowed_tools!: Json<Vec<String>>",
+                      allowed_commands as "allowed_commands!: Json<Vec<String>>",
+                      path_globs as "path_globs!: Json<Vec<String>>",
+                      max_file_size,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_policies
+               WHERE project_id = $1
+                 AND (executor = $2 OR executor IS NULL)
+               ORDER BY executor IS NULL
+               LIMIT 1"#,
+            project_id,
+            executor
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Save the project's policy for `data.executor`, replacing any existing one
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertApprovalPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        let allowed_tools = Json(&data.allowed_tools);
+        let allowed_commands = Json(&data.allowed_commands);
+        let path_globs = Json(&data.path_globs);
+        let mut tx = pool.begin().await?;
+
+        // The unique index is on an expression, which ON CONFLICT can't target
+        let updated = sqlx::query_as!(
+            ApprovalPolicy,
+            r#"UPDATE approval_policies
+               SET allowed_tools = $3,
+                   allowed_commands = $4,
+                   path_globs = $5,
+                   max_file_size = $6,
+                   updated_at = datetime('now', 'subsec')
+               WHERE project_id = $1 AND executor IS $2
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         executor as "executor: BaseCodingAgent",
+                         allowed_tools as "allowed_tools!: Json<Vec<String>>",
+                         allowed_commands as "allowed_commands!: Json<Vec<String>>",
+                         path_globs as "path_globs!: Json<Vec<String>>",
+                         max_file_size,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.executor,
+            allowed_tools,
+            allowed_commands,
+            path_globs,
+            data.max_file_size
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let policy = match updated {
+            Some(policy) => policy,
+            None => {
+                let id = Uuid::new_v4();
+                sqlx::query_as!(
+                    ApprovalPolicy,
+                    r#"INSERT INTO approval_policies
+                           (id, project_id, executor, allowed_tools, allowed_commands,
+                            path_globs, max_file_size)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7)
+                       RETURNING id as "id!: Uuid",
+                                 project_id as "project_id!: Uuid",
+                                 executor as "executor: BaseCodingAgent",
+                                 allowed_tools as "allowed_tools!: Json<Vec<String>>",
+                                 allowed_commands as "allowed_commands!: Json<Vec<String>>",
+                                 path_globs as "path_globs!: Json<Vec<String>>",
+                                 max_file_size,
+                                 created_at as "created_at!: DateTime<Utc>",
+                                 updated_at as "updated_at!: DateTime<Utc>""#,
+                    id,
+                    project_id,
+                    data.executor,
+                    allowed_tools,
+                    allowed_commands,
+                    path_globs,
+                    data.max_file_size
+                )
+                .fetch_one(&mut *tx)
+                .await?
+            }
+        };
+        tx.commit().await?;
+        Ok(policy)
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM approval_policies WHERE project_id = $1 AND id = $2",
+            project_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "audit_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// Text replaced with a placeholder across stored task and agent data
+    Redaction,
+    /// A project deleted together with all data derived from it
+    ProjectPurge,
+}
+
+/// An administrative action that changed or removed user data
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct AuditLogEntry {
+    pub id: Uuid,
+    pub action: AuditAction,
+    /// The signed-in user who acted; None on a server without accounts
+    pub actor_id: Option<Uuid>,
+    /// One line for lists
+    pub summary: String,
+    /// JSON object whose shape depends on the action
+    pub details: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    pub async fn create(
+        executor: impl Executor<'_, Database = Sqlite>,
+        action: AuditAction,
+        actor_id: Option<Uuid>,
+        summary: &str,
+        details: &serde_json::Value,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let details = details.to_string();
+        sqlx::query_as!(
+            AuditLogEntry,
+            r#"INSERT INTO audit_log (id, action, actor_id, summary, details)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         action as "action!: AuditAction",
+                         actor_id as "actor_id: Uuid",
+                         summary,
+                         details,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            action,
+            actor_id,
+            summary,
+            details
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Most recent first
+    pub async fn find_recent(pool: &SqlitePool, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AuditLogEntry,
+            r#"SELECT id as "id!: Uuid",
+                      action as "action!: AuditAction",
+                      actor_id as "actor_id: Uuid",
+                      summary,
+                      details,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM audit_log
+               ORDER BY created_at DESC
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Why a backup archive was checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "backup_verification_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BackupVerificationKind {
+    /// Run on every archive as soon as it is created
+    AfterBackup,
+    /// Scheduled rehearsal of a restore from the latest archive
+    Drill,
+    /// Requested through the API
+    Manual,
+}
+
+/// Rows found in one table of the archived database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows: i64,
+    /// Rows in the live database when the archive was made; None when the
+    /// archive is checked later and there is nothing to compare with
+    pub expected_rows: Option<i64>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct BackupVerification {
+    pub id: Uuid,
+    /// Archive filename without `.zip`
+    pub backup_id: String,
+    pub kind: BackupVerificationKind,
+    pub passed: bool,
+    /// Output of `PRAGMA integrity_check`; None when the database could not
+    /// be opened
+    pub integrity: Option<String>,
+    #[ts(type = "Array<TableRowCount>")]
+    #[schema(value_type = Vec<TableRowCount>)]
+    pub table_counts: Json<Vec<TableRowCount>>,
+    /// Why the check failed
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    #[ts(type = "Date")]
+    pub verified_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateBackupVerification {
+    pub backup_id: String,
+    pub kind: BackupVerificationKind,
+    pub integrity: Option<String>,
+    pub table_counts: Vec<TableRowCount>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl BackupVerification {
+    /// Record a check; it passed when `data.error` is None
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateBackupVerification,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let passed = data.error.is_none();
+        let table_counts = Json(&data.table_counts);
+        sqlx::query_as!(
+            BackupVerification,
+            r#"INSERT INTO backup_verifications
+                   (id, backup_id, kind, passed, integrity, table_counts, error, duration_ms)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id as "id!: Uuid",
+                         backup_id,
+                         kind as "kind!: BackupVerificationKind",
+                         passed as "passed!: bool",
+                         integrity,
+                         table_counts as "table_counts!: Json<Vec<TableRowCount>>",
+                         error,
+                         duration_ms,
+                         verified_at as "verified_at!: DateTime<Utc>""#,
+            id,
+            data.backup_id,
+            data.kind,
+            passed,
+            data.integrity,
+            table_counts,
+            data.error,
+            data.duration_ms
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Most recent checks first, optionally of a single archive
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        backup_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BackupVerification,
+            r#"SELECT id as "id!: Uuid",
+                      backup_id,
+                      kind as "kind!: BackupVerificationKind",
+                      passed as "passed!: bool",
+                      integrity,
+                      table_counts as "table_counts!: Json<Vec<TableRowCount>>",
+                      error,
+                      duration_ms,
+                      verified_at as "verified_at!: DateTime<Utc>"
+               FROM backup_verifications
+               WHERE ($1 IS NULL OR backup_id = $1)
+               ORDER BY julianday(verified_at) DESC
+               LIMIT $2"#,
+            backup_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The latest check of every archive that has been checked
+    pub async fn find_latest_per_backup(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BackupVerification,
+            r#"SELECT v.id as "id!: Uuid",
+                      v.backup_id,
+                      v.kind as "kind!: BackupVerificationKind",
+                      v.passed as "passed!: bool",
+                      v.integrity,
+                      v.table_counts as "table_counts!: Json<Vec<TableRowCount>>",
+                      v.error,
+                      v.duration_ms,
+                      v.verified_at as "verified_at!: DateTime<Utc>"
+               FROM backup_verifications v
+               WHERE v.verified_at = (
+                   SELECT MAX(verified_at) FROM backup_verifications
+                   WHERE backup_id = v.backup_id
+               )"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// When a check of this kind last ran, to schedule the next one
+    pub async fn last_verified_at(
+        pool: &SqlitePool,
+        kind: BackupVerificationKind,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT MAX(verified_at) as "verified_at: DateTime<Utc>"
+               FROM backup_verifications
+               WHERE kind = $1"#,
+            kind
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,264 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "bisect_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BisectStatus {
+    Running,
+    /// The first bad commit was identified
+    Found,
+    Failed,
+}
+
+/// What a tested commit was reported to `git bisect` as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "bisect_verdict", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BisectVerdict {
+    Good,
+    Bad,
+    /// The commit could not be tested
+    Skip,
+}
+
+/// A guided `git bisect` over a repo's good..bad range
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct BisectRun {
+    pub id: Uuid,
+    pub repo_id: Uuid,
+    /// Task the culprit is reported on
+    pub task_id: Option<Uuid>,
+    pub good_commit: String,
+    pub bad_commit: String,
+    /// Validation command; exit 0 is good, 125 untestable, 1-127 bad
+    pub command: String,
+    /// Ask an agent to judge each step's output instead of trusting the exit code
+    pub use_agent: bool,
+    pub status: BisectStatus,
+    pub culprit_commit: Option<String>,
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct BisectStep {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    pub commit_sha: String,
+    /// None when the command timed out or was killed by a signal
+    pub exit_code: Option<i64>,
+    /// Tail of the command's combined stdout and stderr
+    pub output: String,
+    pub verdict: BisectVerdict,
+    /// The verdict came from an agent reading the output
+    pub agent_interpreted: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateBisectRun {
+    pub repo_id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub good_commit: String,
+    pub bad_commit: String,
+    pub command: String,
+    pub use_agent: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateBisectStep {
+    pub commit_sha: String,
+    pub exit_code: Option<i64>,
+    pub output: String,
+    pub verdict: BisectVerdict,
+    pub agent_interpreted: bool,
+}
+
+impl BisectRun {
+    pub async fn create(pool: &SqlitePool, data: &CreateBisectRun) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            BisectRun,
+            r#"INSERT INTO bisect_runs (id, repo_id, task_id, good_commit, bad_commit, command, use_agent)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         task_id as "task_id: Uuid",
+                         good_commit,
+                         bad_commit,
+                         command,
+                         use_agent as "use_agent!: bool",
+                         status as "status!: BisectStatus",
+                         culprit_commit,
+                         error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.repo_id,
+            data.task_id,
+            data.good_commit,
+            data.bad_commit,
+            data.command,
+            data.use_agent
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BisectRun,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      task_id as "task_id: Uuid",
+                      good_commit,
+                      bad_commit,
+                      command,
+                      use_agent as "use_agent!: bool",
+                      status as "status!: BisectStatus",
+                      culprit_commit,
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM bisect_runs
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BisectRun,
+            r#"SELECT id as "id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      task_id as "task_id: Uuid",
+                      good_commit,
+                      bad_commit,
+                      command,
+                      use_agent as "use_agent!: bool",
+                      status as "status!: BisectStatus",
+                      culprit_commit,
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM bisect_runs
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// End a run: `culprit` when the first bad commit was found, otherwise `error`
+    pub async fn finish(
+        pool: &SqlitePool,
+        id: Uuid,
+        culprit: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let status = if culprit.is_some() {
+            BisectStatus::Found
+        } else {
+            BisectStatus::Failed
+        };
+        sqlx::query!(
+            r#"UPDATE bisect_runs
+               SET status = $2,
+                   culprit_commit = $3,
+                   error = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status,
+            culprit,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Fail every run still marked running. Called at startup, when nothing
+    /// can be running yet.
+    pub async fn fail_interrupted(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE bisect_runs
+               SET status = 'failed',
+                   error = 'Interrupted by a server restart',
+                   updated_at = datetime('now', 'subsec')
+               WHERE status = 'running'"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl BisectStep {
+    pub async fn create(
+        pool: &SqlitePool,
+        run_id: Uuid,
+        data: &CreateBisectStep,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            BisectStep,
+            r#"INSERT INTO bisect_steps (id, run_id, commit_sha, exit_code, output, verdict, agent_interpreted)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         run_id as "run_id!: Uuid",
+                         commit_sha,
+                         exit_code,
+                         output,
+                         verdict as "verdict!: BisectVerdict",
+                         agent_interpreted as "agent_interpreted!: bool",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            run_id,
+            data.commit_sha,
+            data.exit_code,
+            data.output,
+            data.verdict,
+            data.agent_interpreted
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Steps of a run in the order they were tested
+    pub async fn find_by_run_id(pool: &SqlitePool, run_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BisectStep,
+            r#"SELECT id as "id!: Uuid",
+                      run_id as "run_id!: Uuid",
+                      commit_sha,
+                      exit_code,
+                      output,
+                      verdict as "verdict!: BisectVerdict",
+                      agent_interpreted as "agent_interpreted!: bool",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM bisect_steps
+               WHERE run_id = $1
+               ORDER BY created_at ASC"#,
+            run_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
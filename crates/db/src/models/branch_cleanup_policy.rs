@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What the branch janitor does with a project's stale task branches
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema,
+)]
+#[sqlx(type_name = "branch_cleanup_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BranchCleanupMode {
+    /// Leave branches alone
+    #[default]
+    Off,
+    /// Delete the local branch
+    Delete,
+    /// Move the branch to `refs/vk-archive/<branch>` so it can be recovered
+    Archive,
+}
+
+pub const DEFAULT_MIN_AGE_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct BranchCleanupPolicy {
+    pub project_id: Uuid,
+    pub mode: BranchCleanupMode,
+    /// Days a merge must be confirmed for before its branch is touched
+    #[ts(type = "number")]
+    pub min_age_days: i64,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateBranchCleanupPolicy {
+    pub mode: BranchCleanupMode,
+    #[ts(type = "number")]
+    pub min_age_days: i64,
+}
+
+/// A task branch whose workspace has been cleaned up and whose merge into
+/// the target branch is confirmed
+#[derive(Debug, Clone, FromRow)]
+pub struct StaleTaskBranch {
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub repo_path: PathBuf,
+    pub branch: String,
+    pub target_branch_name: String,
+    pub merged_at: DateTime<Utc>,
+    /// Commit the latest merge created on the target branch; None when a PR
+    /// was merged without its merge commit being recorded
+    pub merge_commit: Option<String>,
+}
+
+impl BranchCleanupPolicy {
+    /// The project's policy, or the default (`off`) when none was saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let policy = sqlx::query_as!(
+            BranchCleanupPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      mode as "mode!: BranchCleanupMode",
+                      min_age_days,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM branch_cleanup_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.unwrap_or(Self {
+            project_id,
+            mode: BranchCleanupMode::Off,
+            min_age_days: DEFAULT_MIN_AGE_DAYS,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    /// Policies of every project that has cleanup turned on
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BranchCleanupPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      mode as "mode!: BranchCleanupMode",
+                      min_age_days,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM branch_cleanup_policies
+               WHERE mode != 'off'"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateBranchCleanupPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        let min_age_days = data.min_age_days.max(0);
+        sqlx::query_as!(
+            BranchCleanupPolicy,
+            r#"INSERT INTO branch_cleanup_policies (project_id, mode, min_age_days)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   mode = excluded.mode,
+                   min_age_days = excluded.min_age_days,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         mode as "mode!: BranchCleanupMode",
+                         min_age_days,
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.mode,
+            min_age_days
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+impl StaleTaskBranch {
+    /// Branches of `project_id` whose workspace no longer has a worktree and
+    /// that were merged (directly or through a merged PR) before `merged_before`.
+    /// Branches still used by a live workspace in the same repo are excluded.
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        merged_before: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            StaleTaskBranch,
+            r#"SELECT w.id as "workspace_id!: Uuid",
+                      r.id as "repo_id!: Uuid",
+                      r.name as "repo_name!",
+                      r.path as "repo_path!: PathBuf",
+                      w.branch as "branch!",
+                      m.target_branch_name as "target_branch_name!",
+                      MAX(COALESCE(m.pr_merged_at, m.created_at)) as "merged_at!: DateTime<Utc>",
+                      COALESCE(m.merge_commit, m.pr_merge_commit_sha) as merge_commit
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN merges m ON m.workspace_id = w.id
+               JOIN repos r ON r.id = m.repo_id
+               WHERE t.project_id = $1
+                 AND w.container_ref IS NULL
+                 AND (m.merge_type = 'direct' OR m.pr_status = 'merged')
+                 AND NOT EXISTS (
+                     SELECT 1
+                     FROM workspaces live
+                     JOIN workspace_repos wr ON wr.workspace_id = live.id
+                     WHERE live.branch = w.branch
+                       AND wr.repo_id = r.id
+                       AND live.container_ref IS NOT NULL
+                 )
+               GROUP BY w.id, r.id
+               HAVING julianday(MAX(COALESCE(m.pr_merged_at, m.created_at))) < julianday($2)
+               ORDER BY r.name, w.branch"#,
+            project_id,
+            merged_before
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -11,6 +11,9 @@ pub struct CodingAgentTurn {
     pub agent_session_id: Option<String>, // Session ID from Claude/Amp coding agent
     pub prompt: Option<String>,           // The prompt sent to the executor
     pub summary: Option<String>,          // Final assistant message/summary
+    /// The agent could not resume `agent_session_id`; follow-ups start a new
+    /// agent session instead
+    pub agent_session_expired: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -35,6 +38,7 @@ impl CodingAgentTurn {
                 agent_session_id,
                 prompt,
                 summary,
+                agent_session_expired as "agent_session_expired!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM coding_agent_turns
@@ -57,6 +61,7 @@ impl CodingAgentTurn {
                 agent_session_id,
                 prompt,
                 summary,
+                agent_session_expired as "agent_session_expired!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>"
                FROM coding_agent_turns
@@ -96,6 +101,7 @@ impl CodingAgentTurn {
                 agent_session_id,
                 prompt,
                 summary,
+                agent_session_expired as "agent_session_expired!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -131,6 +137,53 @@ impl CodingAgentTurn {
         Ok(())
     }
 
+    /// Turns of a session's coding agent executions, oldest first
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CodingAgentTurn,
+            r#"SELECT
+                cat.id as "id!: Uuid",
+                cat.execution_process_id as "execution_process_id!: Uuid",
+                cat.agent_session_id,
+                cat.prompt,
+                cat.summary,
+                cat.agent_session_expired as "agent_session_expired!: bool",
+                cat.created_at as "created_at!: DateTime<Utc>",
+                cat.updated_at as "updated_at!: DateTime<Utc>"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON ep.id = cat.execution_process_id
+               WHERE ep.session_id = $1
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.dropped = FALSE
+               ORDER BY ep.created_at ASC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Flag every turn of an agent session that can no longer be resumed
+    pub async fn mark_agent_session_expired(
+        pool: &SqlitePool,
+        agent_session_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE coding_agent_turns
+               SET agent_session_expired = TRUE, updated_at = $1
+               WHERE agent_session_id = $2"#,
+            now,
+            agent_session_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Update coding agent turn summary
     pub async fn update_summary(
         pool: &SqlitePool,
@@ -1,15 +1,16 @@
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 const DEFAULT_PAGE_LIMIT: usize = 50;
 const MAX_PAGE_LIMIT: usize = 200;
 
-#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[sqlx(type_name = "message_role", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum MessageRole {
@@ -17,7 +18,7 @@ pub enum MessageRole {
     Assistant,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct ConversationMessage {
     pub id: Uuid,
     pub conversation_session_id: Uuid,
@@ -79,7 +80,7 @@ impl MessageCursor {
 }
 
 /// Paginated response for conversation messages
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ConversationMessagesPage {
     pub messages: Vec<ConversationMessage>,
     pub next_cursor: Option<String>,
@@ -117,6 +118,41 @@ impl ConversationMessage {
         .map_err(ConversationMessageError::from)
     }
 
+    /// Copy `message` into another conversation, keeping its timestamps so
+    /// the copies sort the same way as the originals
+    pub async fn create_copy(
+        executor: impl Executor<'_, Database = Sqlite>,
+        conversation_session_id: Uuid,
+        message: &ConversationMessage,
+    ) -> Result<Self, ConversationMessageError> {
+        let id = Uuid::new_v4();
+
+        sqlx::query_as!(
+            Self,
+            r#"INSERT INTO conversation_messages (id, conversation_session_id, execution_process_id, role, content, metadata, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id AS "id!: Uuid",
+                         conversation_session_id AS "conversation_session_id!: Uuid",
+                         execution_process_id AS "execution_process_id: Uuid",
+                         role AS "role!: MessageRole",
+                         content,
+                         metadata,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            id,
+            conversation_session_id,
+            message.execution_process_id,
+            message.role,
+            message.content,
+            message.metadata,
+            message.created_at,
+            message.updated_at,
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(ConversationMessageError::from)
+    }
+
     pub async fn find_by_id(
         pool: &SqlitePool,
         id: Uuid,
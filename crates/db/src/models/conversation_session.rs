@@ -3,9 +3,12 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+use super::conversation_message::{ConversationMessage, ConversationMessageError};
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[sqlx(type_name = "conversation_session_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum ConversationSessionStatus {
@@ -13,7 +16,7 @@ pub enum ConversationSessionStatus {
     Archived,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct ConversationSession {
     pub id: Uuid,
     pub project_id: Uuid,
@@ -22,6 +25,12 @@ pub struct ConversationSession {
     pub executor: Option<String>,
     pub worktree_path: Option<String>,
     pub worktree_branch: Option<String>,
+    /// The conversation this one was forked from
+    pub forked_from_session_id: Option<Uuid>,
+    /// The last message copied from the original conversation
+    pub forked_from_message_id: Option<Uuid>,
+    /// Agent session the fork's first turn resumes; None starts a new one
+    pub fork_agent_session_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -50,6 +59,8 @@ pub enum ConversationSessionError {
     NotFound,
     #[error("Project not found")]
     ProjectNotFound,
+    #[error(transparent)]
+    Message(#[from] ConversationMessageError),
 }
 
 impl ConversationSession {
@@ -71,6 +82,9 @@ impl ConversationSession {
                          executor,
                          worktree_path,
                          worktree_branch,
+                         forked_from_session_id AS "forked_from_session_id: Uuid",
+                         forked_from_message_id AS "forked_from_message_id: Uuid",
+                         fork_agent_session_id,
                          created_at AS "created_at!: DateTime<Utc>",
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
@@ -86,6 +100,60 @@ impl ConversationSession {
         .map_err(ConversationSessionError::from)
     }
 
+    /// Create a fork of `source` holding copies of `messages`, the original
+    /// messages up to and including the one forked at. Both happen in one
+    /// transaction so a fork never has only part of its history.
+    pub async fn create_fork(
+        pool: &SqlitePool,
+        source: &ConversationSession,
+        title: String,
+        messages: &[ConversationMessage],
+        agent_session_id: Option<String>,
+    ) -> Result<Self, ConversationSessionError> {
+        let id = Uuid::new_v4();
+        let status = ConversationSessionStatus::Active;
+        let forked_from_message_id = messages.last().map(|message| message.id);
+
+        let mut tx = pool.begin().await?;
+        let fork = sqlx::query_as!(
+            Self,
+            r#"INSERT INTO conversation_sessions (id, project_id, title, status, executor, worktree_path, worktree_branch,
+                                                  forked_from_session_id, forked_from_message_id, fork_agent_session_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               RETURNING id AS "id!: Uuid",
+                         project_id AS "project_id!: Uuid",
+                         title,
+                         status AS "status!: ConversationSessionStatus",
+                         executor,
+                         worktree_path,
+                         worktree_branch,
+                         forked_from_session_id AS "forked_from_session_id: Uuid",
+                         forked_from_message_id AS "forked_from_message_id: Uuid",
+                         fork_agent_session_id,
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            id,
+            source.project_id,
+            title,
+            status,
+            source.executor,
+            source.worktree_path,
+            source.worktree_branch,
+            source.id,
+            forked_from_message_id,
+            agent_session_id,
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for message in messages {
+            ConversationMessage::create_copy(&mut *tx, fork.id, message).await?;
+        }
+        tx.commit().await?;
+
+        Ok(fork)
+    }
+
     pub async fn find_by_id(
         pool: &SqlitePool,
         id: Uuid,
@@ -99,6 +167,9 @@ impl ConversationSession {
                       executor,
                       worktree_path,
                       worktree_branch,
+                      forked_from_session_id AS "forked_from_session_id: Uuid",
+                      forked_from_message_id AS "forked_from_message_id: Uuid",
+                      fork_agent_session_id,
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM conversation_sessions
@@ -138,6 +209,9 @@ impl ConversationSession {
                       executor,
                       worktree_path,
                       worktree_branch,
+                      forked_from_session_id AS "forked_from_session_id: Uuid",
+                      forked_from_message_id AS "forked_from_message_id: Uuid",
+                      fork_agent_session_id,
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM conversation_sessions
@@ -188,6 +262,9 @@ impl ConversationSession {
                       executor,
                       worktree_path,
                       worktree_branch,
+                      forked_from_session_id AS "forked_from_session_id: Uuid",
+                      forked_from_message_id AS "forked_from_message_id: Uuid",
+                      fork_agent_session_id,
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM conversation_sessions
@@ -231,6 +308,9 @@ impl ConversationSession {
                          executor,
                          worktree_path,
                          worktree_branch,
+                         forked_from_session_id AS "forked_from_session_id: Uuid",
+                         forked_from_message_id AS "forked_from_message_id: Uuid",
+                         fork_agent_session_id,
                          created_at AS "created_at!: DateTime<Utc>",
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
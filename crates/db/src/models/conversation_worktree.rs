@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Worktrees created for a conversation, laid out like a task workspace in
+/// `container_ref`. Forks share their original's container.
+#[derive(Debug, Clone, FromRow)]
+pub struct ConversationWorktree {
+    pub conversation_session_id: Uuid,
+    pub container_ref: String,
+    pub branch: String,
+    pub last_used_at: DateTime<Utc>,
+    /// When the worktrees were removed for being idle; None while they exist
+    pub released_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ConversationWorktree {
+    pub async fn create(
+        pool: &SqlitePool,
+        conversation_session_id: Uuid,
+        container_ref: &str,
+        branch: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ConversationWorktree,
+            r#"INSERT INTO conversation_worktrees (conversation_session_id, container_ref, branch)
+               VALUES ($1, $2, $3)
+               RETURNING conversation_session_id as "conversation_session_id!: Uuid",
+                         container_ref,
+                         branch,
+                         last_used_at as "last_used_at!: DateTime<Utc>",
+                         released_at as "released_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            conversation_session_id,
+            container_ref,
+            branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_conversation_session_id(
+        pool: &SqlitePool,
+        conversation_session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ConversationWorktree,
+            r#"SELECT conversation_session_id as "conversation_session_id!: Uuid",
+                      container_ref,
+                      branch,
+                      last_used_at as "last_used_at!: DateTime<Utc>",
+                      released_at as "released_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM conversation_worktrees
+               WHERE conversation_session_id = $1"#,
+            conversation_session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Record use of the container, which also marks it as existing again
+    pub async fn touch(pool: &SqlitePool, container_ref: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE conversation_worktrees
+               SET last_used_at = datetime('now', 'subsec'),
+                   released_at = NULL
+               WHERE container_ref = $1"#,
+            container_ref
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_released(pool: &SqlitePool, container_ref: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE conversation_worktrees
+               SET released_at = datetime('now', 'subsec')
+               WHERE container_ref = $1"#,
+            container_ref
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Number of conversations using the container
+    pub async fn count_by_container_ref(
+        pool: &SqlitePool,
+        container_ref: &str,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM conversation_worktrees WHERE container_ref = $1"#,
+            container_ref
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn container_ref_exists(
+        pool: &SqlitePool,
+        container_ref: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM conversation_worktrees WHERE container_ref = ?) as "exists!: bool""#,
+            container_ref
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.exists)
+    }
+
+    /// One row per container whose conversations have nothing running and
+    /// have all been idle for 72 hours, like expired task workspaces
+    pub async fn find_expired_for_cleanup(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ConversationWorktree,
+            r#"SELECT cw.conversation_session_id as "conversation_session_id!: Uuid",
+                      cw.container_ref as "container_ref!",
+                      cw.branch as "branch!",
+                      cw.last_used_at as "last_used_at!: DateTime<Utc>",
+                      cw.released_at as "released_at: DateTime<Utc>",
+                      cw.created_at as "created_at!: DateTime<Utc>"
+               FROM conversation_worktrees cw
+               LEFT JOIN execution_processes ep
+                   ON ep.conversation_session_id = cw.conversation_session_id
+               WHERE cw.released_at IS NULL
+               GROUP BY cw.container_ref
+               HAVING SUM(CASE WHEN ep.id IS NOT NULL AND ep.completed_at IS NULL THEN 1 ELSE 0 END) = 0
+                  AND datetime('now', '-72 hours') > datetime(MAX(cw.last_used_at))
+                  AND (MAX(ep.completed_at) IS NULL
+                       OR datetime('now', '-72 hours') > datetime(MAX(ep.completed_at)))
+               ORDER BY MAX(cw.last_used_at) ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
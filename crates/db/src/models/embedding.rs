@@ -20,16 +20,24 @@ pub struct EmbeddingStatus {
 impl EmbeddingStatus {
     /// Find tasks that need embeddings generated.
     /// Returns tasks where needs_embedding = 1, limited by the specified count.
+    /// Archived tasks, and tasks of archived projects, wait until unarchived;
+    /// trashed ones wait until restored.
     pub async fn find_pending(pool: &SqlitePool, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             EmbeddingStatus,
             r#"SELECT
-                task_id as "task_id!: Uuid",
-                needs_embedding as "needs_embedding!: bool",
-                last_embedded_at as "last_embedded_at: DateTime<Utc>",
-                embedding_model
-            FROM task_embedding_status
-            WHERE needs_embedding = 1
+                s.task_id as "task_id!: Uuid",
+                s.needs_embedding as "needs_embedding!: bool",
+                s.last_embedded_at as "last_embedded_at: DateTime<Utc>",
+                s.embedding_model
+            FROM task_embedding_status s
+            JOIN tasks t ON t.id = s.task_id
+            JOIN projects p ON p.id = t.project_id
+            WHERE s.needs_embedding = 1
+              AND t.archived_at IS NULL
+              AND p.archived_at IS NULL
+              AND t.deleted_at IS NULL
+              AND p.deleted_at IS NULL
             LIMIT $1"#,
             limit
         )
@@ -124,33 +132,7 @@ impl TaskEmbedding {
         task_rowid: i64,
         embedding: &[f32],
     ) -> Result<(), sqlx::Error> {
-        if embedding.len() != EMBEDDING_DIMENSION {
-            return Err(sqlx::Error::Protocol(format!(
-                "Embedding dimension mismatch: expected {}, got {}",
-                EMBEDDING_DIMENSION,
-                embedding.len()
-            )));
-        }
-
-        let embedding_bytes = Self::serialize_embedding(embedding);
-
-        // vec0 virtual tables don't support INSERT OR REPLACE or ON CONFLICT,
-        // so we manually delete any existing row first
-        sqlx::query("DELETE FROM task_embeddings WHERE task_rowid = $1")
-            .bind(task_rowid)
-            .execute(pool)
-            .await?;
-
-        sqlx::query(
-            r#"INSERT INTO task_embeddings(task_rowid, embedding)
-               VALUES ($1, $2)"#,
-        )
-        .bind(task_rowid)
-        .bind(&embedding_bytes)
-        .execute(pool)
-        .await?;
-
-        Ok(())
+        upsert_vector(pool, "task_embeddings", "task_rowid", task_rowid, embedding).await
     }
 
     /// Get the rowid for a task by its UUID.
@@ -167,6 +149,214 @@ impl TaskEmbedding {
     }
 }
 
+/// Insert or replace one row of a vec0 table keyed by the source row's rowid.
+///
+/// vec0 virtual tables don't support INSERT OR REPLACE or ON CONFLICT,
+/// so any existing row is deleted first.
+async fn upsert_vector(
+    pool: &SqlitePool,
+    table: &str,
+    key_column: &str,
+    rowid: i64,
+    embedding: &[f32],
+) -> Result<(), sqlx::Error> {
+    if embedding.len() != EMBEDDING_DIMENSION {
+        return Err(sqlx::Error::Protocol(format!(
+            "Embedding dimension mismatch: expected {}, got {}",
+            EMBEDDING_DIMENSION,
+            embedding.len()
+        )));
+    }
+
+    let embedding_bytes = TaskEmbedding::serialize_embedding(embedding);
+
+    sqlx::query(&format!("DELETE FROM {table} WHERE {key_column} = $1"))
+        .bind(rowid)
+        .execute(pool)
+        .await?;
+
+    sqlx::query(&format!(
+        "INSERT INTO {table}({key_column}, embedding) VALUES ($1, $2)"
+    ))
+    .bind(rowid)
+    .bind(&embedding_bytes)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Content other than tasks that is embedded for semantic search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingSource {
+    ConversationMessage,
+    ExecutionSummary,
+    ProjectMemory,
+}
+
+/// A source row waiting to be embedded
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingEmbedding {
+    pub id: Uuid,
+    pub rowid: i64,
+    pub text: String,
+}
+
+impl EmbeddingSource {
+    pub const ALL: [Self; 3] = [
+        Self::ConversationMessage,
+        Self::ExecutionSummary,
+        Self::ProjectMemory,
+    ];
+
+    /// vec0 table holding the vectors, keyed by the source table's rowid
+    pub fn vector_table(&self) -> &'static str {
+        match self {
+            Self::ConversationMessage => "conversation_message_embeddings",
+            Self::ExecutionSummary => "execution_summary_embeddings",
+            Self::ProjectMemory => "project_memory_embeddings",
+        }
+    }
+
+    pub fn key_column(&self) -> &'static str {
+        match self {
+            Self::ConversationMessage => "message_rowid",
+            Self::ExecutionSummary => "turn_rowid",
+            Self::ProjectMemory => "memory_rowid",
+        }
+    }
+
+    /// Create the vec0 table for this source if it doesn't exist.
+    /// Must be called after the sqlite-vec extension is loaded.
+    pub async fn ensure_table_exists(&self, pool: &SqlitePool) -> Result<(), sqlx::Error> {
+        sqlx::query(&format!(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS {}
+             USING vec0({} INTEGER PRIMARY KEY, embedding FLOAT[384])",
+            self.vector_table(),
+            self.key_column()
+        ))
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Rows whose content changed since they were last embedded. Rows of
+    /// archived tasks and projects wait until they are unarchived, and rows
+    /// of trashed ones until they are restored.
+    pub async fn find_pending(
+        &self,
+        pool: &SqlitePool,
+        limit: i64,
+    ) -> Result<Vec<PendingEmbedding>, sqlx::Error> {
+        let sql = match self {
+            Self::ConversationMessage => {
+                r#"SELECT m.id, m.rowid AS rowid, m.content AS text
+                   FROM conversation_message_embedding_status s
+                   JOIN conversation_messages m ON m.id = s.message_id
+                   JOIN conversation_sessions c ON c.id = m.conversation_session_id
+                   JOIN projects p ON p.id = c.project_id
+                   WHERE s.needs_embedding = 1
+                     AND p.archived_at IS NULL AND p.deleted_at IS NULL
+                   LIMIT $1"#
+            }
+            Self::ExecutionSummary => {
+                r#"SELECT t.id, t.rowid AS rowid, t.summary AS text
+                   FROM execution_summary_embedding_status s
+                   JOIN coding_agent_turns t ON t.id = s.turn_id
+                   JOIN execution_processes ep ON ep.id = t.execution_process_id
+                   LEFT JOIN sessions se ON se.id = ep.session_id
+                   LEFT JOIN workspaces w ON w.id = se.workspace_id
+                   LEFT JOIN tasks tk ON tk.id = w.task_id
+                   LEFT JOIN conversation_sessions c ON c.id = ep.conversation_session_id
+                   LEFT JOIN projects p ON p.id = COALESCE(tk.project_id, c.project_id)
+                   WHERE s.needs_embedding = 1 AND t.summary IS NOT NULL
+                     AND tk.archived_at IS NULL AND p.archived_at IS NULL
+                     AND tk.deleted_at IS NULL AND p.deleted_at IS NULL
+                   LIMIT $1"#
+            }
+            Self::ProjectMemory => {
+                r#"SELECT m.id, m.rowid AS rowid, m.content AS text
+                   FROM project_memory_embedding_status s
+                   JOIN project_memories m ON m.id = s.memory_id
+                   JOIN projects p ON p.id = m.project_id
+                   WHERE s.needs_embedding = 1
+                     AND p.archived_at IS NULL AND p.deleted_at IS NULL
+                   LIMIT $1"#
+            }
+        };
+        sqlx::query_as::<_, PendingEmbedding>(sql)
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn upsert(
+        &self,
+        pool: &SqlitePool,
+        rowid: i64,
+        embedding: &[f32],
+    ) -> Result<(), sqlx::Error> {
+        upsert_vector(
+            pool,
+            self.vector_table(),
+            self.key_column(),
+            rowid,
+            embedding,
+        )
+        .await
+    }
+
+    pub async fn mark_embedded(
+        &self,
+        pool: &SqlitePool,
+        id: Uuid,
+        model_name: &str,
+    ) -> Result<(), sqlx::Error> {
+        match self {
+            Self::ConversationMessage => {
+                sqlx::query!(
+                    r#"UPDATE conversation_message_embedding_status
+                       SET needs_embedding = 0,
+                           last_embedded_at = CURRENT_TIMESTAMP,
+                           embedding_model = $2
+                       WHERE message_id = $1"#,
+                    id,
+                    model_name
+                )
+                .execute(pool)
+                .await?;
+            }
+            Self::ExecutionSummary => {
+                sqlx::query!(
+                    r#"UPDATE execution_summary_embedding_status
+                       SET needs_embedding = 0,
+                           last_embedded_at = CURRENT_TIMESTAMP,
+                           embedding_model = $2
+                       WHERE turn_id = $1"#,
+                    id,
+                    model_name
+                )
+                .execute(pool)
+                .await?;
+            }
+            Self::ProjectMemory => {
+                sqlx::query!(
+                    r#"UPDATE project_memory_embedding_status
+                       SET needs_embedding = 0,
+                           last_embedded_at = CURRENT_TIMESTAMP,
+                           embedding_model = $2
+                       WHERE memory_id = $1"#,
+                    id,
+                    model_name
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,6 +8,7 @@ use serde_json::Value;
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{
@@ -15,7 +16,7 @@ use super::{
     project::Project,
     repo::Repo,
     session::Session,
-    task::Task,
+    task::{Task, TaskPriority},
     workspace::Workspace,
     workspace_repo::WorkspaceRepo,
 };
@@ -36,10 +37,10 @@ pub enum ExecutionProcessError {
     ValidationError(String),
 }
 
-#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[sqlx(type_name = "execution_process_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 pub enum ExecutionProcessStatus {
     Running,
     Completed,
@@ -47,7 +48,7 @@ pub enum ExecutionProcessStatus {
     Killed,
 }
 
-#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[sqlx(type_name = "execution_process_run_reason", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum ExecutionProcessRunReason {
@@ -57,9 +58,11 @@ pub enum ExecutionProcessRunReason {
     DevServer,
     InternalAgent,
     DisposableConversation,
+    /// Re-run of a past execution in a fresh workspace
+    Replay,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct ExecutionProcess {
     pub id: Uuid,
     /// Session ID for workspace-based executions (nullable for conversation-based)
@@ -68,6 +71,7 @@ pub struct ExecutionProcess {
     pub conversation_session_id: Option<Uuid>,
     pub run_reason: ExecutionProcessRunReason,
     #[ts(type = "ExecutorAction")]
+    #[schema(value_type = Object)]
     pub executor_action: sqlx::types::Json<ExecutorActionField>,
     pub status: ExecutionProcessStatus,
     pub exit_code: Option<i64>,
@@ -298,14 +302,53 @@ impl ExecutionProcess {
     }
 
     /// Count running agents that count towards concurrency limit.
-    /// Only counts 'setupscript', 'cleanupscript', and 'codingagent'.
+    /// Only counts 'setupscript', 'cleanupscript', 'codingagent' and 'replay'.
     /// Excludes 'devserver' and 'internalagent' as they don't count towards the limit.
     pub async fn count_running_agents(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
         sqlx::query_scalar!(
             r#"SELECT COUNT(*) AS "count!: i64"
                FROM execution_processes
                WHERE status = 'running'
-                 AND run_reason IN ('setupscript', 'cleanupscript', 'codingagent')"#
+                 AND run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'replay')"#
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Count running agents of a project's workspaces, as counted by
+    /// [`Self::count_running_agents`]
+    pub async fn count_running_agents_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               WHERE ep.status = 'running'
+                 AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'replay')
+                 AND t.project_id = ?"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Count running agent executions of one executor (e.g. `CLAUDE_CODE`),
+    /// including internal agents since they draw on the same account
+    pub async fn count_running_for_executor(
+        pool: &SqlitePool,
+        executor: &str,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64"
+               FROM execution_processes
+               WHERE status = 'running'
+                 AND run_reason IN ('codingagent', 'internalagent')
+                 AND json_extract(executor_action, '$.typ.executor_profile_id.executor') = $1"#,
+            executor
         )
         .fetch_one(pool)
         .await
@@ -333,6 +376,30 @@ impl ExecutionProcess {
         .await
     }
 
+    /// The running coding agent of the lowest-priority task below `priority`,
+    /// most recently started first, i.e. the one a preemption loses least by
+    pub async fn find_running_agent_below_priority(
+        pool: &SqlitePool,
+        priority: TaskPriority,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT ep.id as "id!: Uuid", ep.session_id as "session_id: Uuid", ep.conversation_session_id as "conversation_session_id: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
+                      ep.dropped as "dropped!: bool", ep.input_tokens, ep.output_tokens, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               WHERE ep.status = 'running' AND ep.run_reason = 'codingagent' AND t.priority < $1
+               ORDER BY t.priority ASC, ep.started_at DESC
+               LIMIT 1"#,
+            priority
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     /// Check if there are running processes (excluding dev servers) for a workspace (across all sessions)
     pub async fn has_running_non_dev_server_processes_for_workspace(
         pool: &SqlitePool,
@@ -372,6 +439,27 @@ impl ExecutionProcess {
         Ok(exists)
     }
 
+    /// Check if there are any running processes for a project's tasks
+    pub async fn has_running_processes_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let exists: bool = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1 FROM execution_processes ep
+                JOIN sessions s ON ep.session_id = s.id
+                JOIN workspaces w ON s.workspace_id = w.id
+                JOIN tasks t ON w.task_id = t.id
+                WHERE t.project_id = $1
+                  AND ep.status = 'running'
+            ) AS "exists!: bool""#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
     /// Find running dev servers for a specific workspace (across all sessions)
     pub async fn find_running_dev_servers_by_workspace(
         pool: &SqlitePool,
@@ -418,7 +506,8 @@ impl ExecutionProcess {
             session_id
         );
         let row = sqlx::query!(
-            r#"SELECT cat.agent_session_id
+            r#"SELECT cat.agent_session_id,
+                      cat.agent_session_expired as "agent_session_expired!: bool"
                FROM execution_processes ep
                JOIN coding_agent_turns cat ON ep.id = cat.execution_process_id
                WHERE ep.session_id = $1
@@ -434,7 +523,10 @@ impl ExecutionProcess {
 
         tracing::info!("Latest coding agent turn session id: {:?}", row);
 
-        Ok(row.and_then(|r| r.agent_session_id))
+        // An expired latest session means the next turn starts a new one
+        Ok(row
+            .filter(|r| !r.agent_session_expired)
+            .and_then(|r| r.agent_session_id))
     }
 
     /// Find latest coding_agent_turn agent_session_id by session and executor profile
@@ -452,7 +544,8 @@ impl ExecutionProcess {
 
         let executor_str = executor_profile_id.executor.to_string();
         let row = sqlx::query!(
-            r#"SELECT cat.agent_session_id
+            r#"SELECT cat.agent_session_id,
+                      cat.agent_session_expired as "agent_session_expired!: bool"
                FROM execution_processes ep
                JOIN coding_agent_turns cat ON ep.id = cat.execution_process_id
                WHERE ep.session_id = $1
@@ -478,7 +571,10 @@ impl ExecutionProcess {
             row
         );
 
-        Ok(row.and_then(|r| r.agent_session_id))
+        // An expired latest session means the next turn starts a new one
+        Ok(row
+            .filter(|r| !r.agent_session_expired)
+            .and_then(|r| r.agent_session_id))
     }
 
     /// Find latest execution process by session and run reason
@@ -664,7 +760,8 @@ impl ExecutionProcess {
         conversation_session_id: Uuid,
     ) -> Result<Option<String>, sqlx::Error> {
         let row = sqlx::query!(
-            r#"SELECT cat.agent_session_id
+            r#"SELECT cat.agent_session_id,
+                      cat.agent_session_expired as "agent_session_expired!: bool"
                FROM execution_processes ep
                JOIN coding_agent_turns cat ON ep.id = cat.execution_process_id
                WHERE ep.conversation_session_id = $1
@@ -677,7 +774,10 @@ impl ExecutionProcess {
         .fetch_optional(pool)
         .await?;
 
-        Ok(row.and_then(|r| r.agent_session_id))
+        // An expired latest session means the next turn starts a new one
+        Ok(row
+            .filter(|r| !r.agent_session_expired)
+            .and_then(|r| r.agent_session_id))
     }
 
     pub async fn was_stopped(pool: &SqlitePool, id: Uuid) -> bool {
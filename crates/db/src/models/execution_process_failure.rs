@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What made an execution fail, as far as its output tells
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type, Display, ToSchema,
+)]
+#[sqlx(type_name = "execution_failure_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FailureReason {
+    /// The agent's login or API key expired or was rejected
+    AuthExpired,
+    RateLimited,
+    DiskFull,
+    /// A command the execution runs isn't installed
+    CliNotFound,
+    MergeConflict,
+    Network,
+    /// The repo's git hooks failed, so the run was committed without them
+    GitHooks,
+}
+
+impl FailureReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FailureReason::AuthExpired => "Authentication expired",
+            FailureReason::RateLimited => "Rate limited",
+            FailureReason::DiskFull => "Disk full",
+            FailureReason::CliNotFound => "Command not found",
+            FailureReason::MergeConflict => "Merge conflict",
+            FailureReason::Network => "Network error",
+            FailureReason::GitHooks => "Git hooks failed",
+        }
+    }
+}
+
+/// The classified cause of a failed execution
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ExecutionProcessFailure {
+    pub execution_process_id: Uuid,
+    pub reason: FailureReason,
+    /// The stderr line, or the exit code, the failure was classified by
+    pub matched_line: Option<String>,
+    /// What the user can do about it
+    pub suggested_fix: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessFailure {
+    /// Record why `execution_process_id` failed, replacing an earlier record
+    pub async fn upsert(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        reason: FailureReason,
+        matched_line: Option<&str>,
+        suggested_fix: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessFailure,
+            r#"INSERT INTO execution_process_failures (
+                    execution_process_id, reason, matched_line, suggested_fix
+                ) VALUES ($1, $2, $3, $4)
+                ON CONFLICT (execution_process_id) DO UPDATE SET
+                    reason = excluded.reason,
+                    matched_line = excluded.matched_line,
+                    suggested_fix = excluded.suggested_fix
+                RETURNING execution_process_id as "execution_process_id!: Uuid",
+                          reason as "reason!: FailureReason",
+                          matched_line,
+                          suggested_fix,
+                          created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            reason,
+            matched_line,
+            suggested_fix
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessFailure,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      reason as "reason!: FailureReason",
+                      matched_line,
+                      suggested_fix,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_failures
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Classified failures of all executions in a session, oldest first
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessFailure,
+            r#"SELECT f.execution_process_id as "execution_process_id!: Uuid",
+                      f.reason as "reason!: FailureReason",
+                      f.matched_line,
+                      f.suggested_fix,
+                      f.created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_failures f
+               JOIN execution_processes ep ON ep.id = f.execution_process_id
+               WHERE ep.session_id = $1
+               ORDER BY f.created_at ASC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
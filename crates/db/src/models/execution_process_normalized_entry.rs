@@ -1,21 +1,27 @@
-use executors::logs::NormalizedEntry;
+use executors::logs::{
+    NormalizedEntry,
+    schema::{NORMALIZED_ENTRY_SCHEMA_VERSION, parse_entry},
+};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow)]
 struct ExecutionProcessNormalizedEntryRow {
     entry_index: i64,
     entry_json: String,
+    schema_version: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExecutionProcessNormalizedEntry {
     pub entry_index: i64,
+    #[schema(value_type = Object)]
     pub entry: NormalizedEntry,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExecutionProcessNormalizedEntriesPage {
     pub entries: Vec<ExecutionProcessNormalizedEntry>,
     pub next_before_index: Option<i64>,
@@ -34,13 +40,16 @@ impl ExecutionProcessNormalizedEntry {
             r#"INSERT INTO execution_process_normalized_entries (
                     execution_id,
                     entry_index,
-                    entry_json
-                ) VALUES ($1, $2, $3)
+                    entry_json,
+                    schema_version
+                ) VALUES ($1, $2, $3, $4)
                 ON CONFLICT(execution_id, entry_index)
-                DO UPDATE SET entry_json = excluded.entry_json"#,
+                DO UPDATE SET entry_json = excluded.entry_json,
+                              schema_version = excluded.schema_version"#,
             execution_id,
             entry_index,
-            entry_json
+            entry_json,
+            NORMALIZED_ENTRY_SCHEMA_VERSION
         )
         .execute(pool)
         .await?;
@@ -96,7 +105,8 @@ impl ExecutionProcessNormalizedEntry {
                 ExecutionProcessNormalizedEntryRow,
                 r#"SELECT
                         entry_index,
-                        entry_json
+                        entry_json,
+                        schema_version
                    FROM execution_process_normalized_entries
                    WHERE execution_id = $1
                      AND entry_index < $2
@@ -113,7 +123,8 @@ impl ExecutionProcessNormalizedEntry {
                 ExecutionProcessNormalizedEntryRow,
                 r#"SELECT
                         entry_index,
-                        entry_json
+                        entry_json,
+                        schema_version
                    FROM execution_process_normalized_entries
                    WHERE execution_id = $1
                    ORDER BY entry_index DESC
@@ -134,7 +145,7 @@ impl ExecutionProcessNormalizedEntry {
 
         let mut entries = Vec::with_capacity(rows.len());
         for row in rows {
-            let entry = serde_json::from_str(&row.entry_json)?;
+            let entry = parse_entry(&row.entry_json, row.schema_version)?;
             entries.push(ExecutionProcessNormalizedEntry {
                 entry_index: row.entry_index,
                 entry,
@@ -163,7 +174,8 @@ impl ExecutionProcessNormalizedEntry {
             ExecutionProcessNormalizedEntryRow,
             r#"SELECT
                     entry_index,
-                    entry_json
+                    entry_json,
+                    schema_version
                FROM execution_process_normalized_entries
                WHERE execution_id = $1
                ORDER BY entry_index ASC"#,
@@ -174,7 +186,7 @@ impl ExecutionProcessNormalizedEntry {
 
         let mut entries = Vec::with_capacity(rows.len());
         for row in rows {
-            let entry = serde_json::from_str(&row.entry_json)?;
+            let entry = parse_entry(&row.entry_json, row.schema_version)?;
             entries.push(ExecutionProcessNormalizedEntry {
                 entry_index: row.entry_index,
                 entry,
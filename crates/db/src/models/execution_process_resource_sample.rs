@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// CPU and memory of an execution's whole process tree at one point in time
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ExecutionProcessResourceSample {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    /// Summed over every process in the tree; 100 is one fully busy core
+    pub cpu_percent: f64,
+    #[ts(type = "number")]
+    pub rss_bytes: i64,
+    #[ts(type = "number")]
+    pub process_count: i64,
+    #[ts(type = "Date")]
+    pub sampled_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessResourceSample {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        cpu_percent: f64,
+        rss_bytes: i64,
+        process_count: i64,
+        sampled_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO execution_process_resource_samples (
+                    id, execution_process_id, cpu_percent, rss_bytes, process_count, sampled_at
+                ) VALUES ($1, $2, $3, $4, $5, $6)"#,
+            id,
+            execution_process_id,
+            cpu_percent,
+            rss_bytes,
+            process_count,
+            sampled_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Samples for one execution, oldest first
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessResourceSample,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      cpu_percent as "cpu_percent!: f64",
+                      rss_bytes as "rss_bytes!: i64",
+                      process_count as "process_count!: i64",
+                      sampled_at as "sampled_at!: DateTime<Utc>"
+               FROM execution_process_resource_samples
+               WHERE execution_process_id = $1
+               ORDER BY sampled_at ASC"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Delete every sample taken more than `max_age` ago, returning how many
+    /// were removed
+    pub async fn delete_older_than(
+        pool: &SqlitePool,
+        max_age: std::time::Duration,
+    ) -> Result<u64, sqlx::Error> {
+        let Some(cutoff) = chrono::Duration::from_std(max_age)
+            .ok()
+            .and_then(|max_age| Utc::now().checked_sub_signed(max_age))
+        else {
+            return Ok(0);
+        };
+        let result = sqlx::query!(
+            "DELETE FROM execution_process_resource_samples WHERE sampled_at < $1",
+            cutoff
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
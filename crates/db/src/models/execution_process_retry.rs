@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Why a failed execution was considered worth re-running
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type, Display, ToSchema,
+)]
+#[sqlx(type_name = "execution_retry_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RetryReason {
+    RateLimit,
+    Network,
+    StartupCrash,
+    /// Exited with a code the retry policy lists as transient
+    ExitCode,
+}
+
+/// An automatic re-run of a failed execution
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ExecutionProcessRetry {
+    pub id: Uuid,
+    /// The execution that failed
+    pub execution_process_id: Uuid,
+    /// The execution started in its place; None until it starts, or if it
+    /// never did (see `error`)
+    pub retry_execution_process_id: Option<Uuid>,
+    /// 1 for the first retry of the original run
+    #[ts(type = "number")]
+    pub attempt: i64,
+    pub reason: RetryReason,
+    #[ts(type = "number")]
+    pub delay_secs: i64,
+    /// The stderr line, or the exit code, the failure was classified by
+    pub matched_line: Option<String>,
+    /// Why the retry was not started
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessRetry {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        attempt: i64,
+        reason: RetryReason,
+        delay_secs: i64,
+        matched_line: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ExecutionProcessRetry,
+            r#"INSERT INTO execution_process_retries (
+                    id, execution_process_id, attempt, reason, delay_secs, matched_line
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id as "id!: Uuid",
+                          execution_process_id as "execution_process_id!: Uuid",
+                          retry_execution_process_id as "retry_execution_process_id: Uuid",
+                          attempt as "attempt!: i64",
+                          reason as "reason!: RetryReason",
+                          delay_secs as "delay_secs!: i64",
+                          matched_line,
+                          error,
+                          created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            execution_process_id,
+            attempt,
+            reason,
+            delay_secs,
+            matched_line
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_retry_execution_process(
+        pool: &SqlitePool,
+        id: Uuid,
+        retry_execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_process_retries SET retry_execution_process_id = $2 WHERE id = $1",
+            id,
+            retry_execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_error(pool: &SqlitePool, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE execution_process_retries SET error = $2 WHERE id = $1",
+            id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The retry that started `retry_execution_process_id`, if it was one
+    pub async fn find_by_retry_execution_process_id(
+        pool: &SqlitePool,
+        retry_execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessRetry,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      retry_execution_process_id as "retry_execution_process_id: Uuid",
+                      attempt as "attempt!: i64",
+                      reason as "reason!: RetryReason",
+                      delay_secs as "delay_secs!: i64",
+                      matched_line,
+                      error,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_retries
+               WHERE retry_execution_process_id = $1"#,
+            retry_execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Retries of all executions in a session, oldest first
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessRetry,
+            r#"SELECT r.id as "id!: Uuid",
+                      r.execution_process_id as "execution_process_id!: Uuid",
+                      r.retry_execution_process_id as "retry_execution_process_id: Uuid",
+                      r.attempt as "attempt!: i64",
+                      r.reason as "reason!: RetryReason",
+                      r.delay_secs as "delay_secs!: i64",
+                      r.matched_line,
+                      r.error,
+                      r.created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_retries r
+               JOIN execution_processes ep ON ep.id = r.execution_process_id
+               WHERE ep.session_id = $1
+               ORDER BY r.created_at ASC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
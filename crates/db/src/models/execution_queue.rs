@@ -3,26 +3,56 @@ use executors::{actions::ExecutorAction, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::{execution_process::ExecutionProcessRunReason, task::TaskPriority};
+
 /// Represents an entry in the execution queue.
 /// Presence in this table means the workspace is waiting to execute.
 /// When execution starts, the row is deleted.
 ///
 /// For initial workspace starts: session_id and executor_action are None.
 /// For follow-up executions: session_id and executor_action are populated.
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ExecutionQueue {
     pub id: Uuid,
     pub workspace_id: Uuid,
     #[ts(type = "ExecutorProfileId")]
+    #[schema(value_type = Object)]
     pub executor_profile_id: sqlx::types::Json<ExecutorProfileId>,
     pub queued_at: DateTime<Utc>,
     /// Session ID for follow-up executions (None for initial workspace starts)
     pub session_id: Option<Uuid>,
     /// Serialized ExecutorAction for follow-up executions (None for initial workspace starts)
     pub executor_action: Option<String>,
+    /// Place in the queue; lower runs first. Reordering only swaps the
+    /// positions of existing entries.
+    #[ts(type = "number")]
+    pub position: i64,
+    /// Run reason of the queued follow-up; None starts a coding agent turn
+    pub run_reason: Option<ExecutionProcessRunReason>,
+    /// Retry record of a queued re-run of a transiently failed execution
+    pub retry_id: Option<Uuid>,
+}
+
+/// A queue entry of a project, with the task it will run for
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ProjectQueueEntry {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_priority: TaskPriority,
+    #[ts(type = "ExecutorProfileId")]
+    #[schema(value_type = Object)]
+    pub executor_profile_id: sqlx::types::Json<ExecutorProfileId>,
+    pub queued_at: DateTime<Utc>,
+    pub session_id: Option<Uuid>,
+    #[ts(type = "number")]
+    pub position: i64,
 }
 
 impl ExecutionQueue {
@@ -49,8 +79,8 @@ impl ExecutionQueue {
             .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
 
         sqlx::query!(
-            r#"INSERT INTO execution_queue (id, workspace_id, executor_profile_id)
-               VALUES (?, ?, ?)"#,
+            r#"INSERT INTO execution_queue (id, workspace_id, executor_profile_id, position)
+               VALUES (?, ?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM execution_queue))"#,
             id,
             workspace_id,
             executor_profile_json
@@ -69,6 +99,56 @@ impl ExecutionQueue {
         workspace_id: Uuid,
         session_id: Uuid,
         executor_action: &ExecutorAction,
+    ) -> Result<Self, sqlx::Error> {
+        Self::insert_follow_up(pool, workspace_id, session_id, executor_action, None, None).await
+    }
+
+    /// Insert a new queue entry for a replay of a past execution, started in
+    /// the fresh session `session_id`
+    pub async fn create_replay(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        session_id: Uuid,
+        executor_action: &ExecutorAction,
+    ) -> Result<Self, sqlx::Error> {
+        Self::insert_follow_up(
+            pool,
+            workspace_id,
+            session_id,
+            executor_action,
+            Some(ExecutionProcessRunReason::Replay),
+            None,
+        )
+        .await
+    }
+
+    /// Insert a new queue entry re-running a transiently failed coding agent
+    /// execution; the started execution is recorded on the retry `retry_id`
+    pub async fn create_retry(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        session_id: Uuid,
+        executor_action: &ExecutorAction,
+        retry_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        Self::insert_follow_up(
+            pool,
+            workspace_id,
+            session_id,
+            executor_action,
+            None,
+            Some(retry_id),
+        )
+        .await
+    }
+
+    async fn insert_follow_up(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        session_id: Uuid,
+        executor_action: &ExecutorAction,
+        run_reason: Option<ExecutionProcessRunReason>,
+        retry_id: Option<Uuid>,
     ) -> Result<Self, sqlx::Error> {
         let id = Uuid::new_v4();
 
@@ -95,13 +175,15 @@ impl ExecutionQueue {
             serde_json::to_string(executor_action).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
 
         sqlx::query!(
-            r#"INSERT INTO execution_queue (id, workspace_id, executor_profile_id, session_id, executor_action)
-               VALUES (?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO execution_queue (id, workspace_id, executor_profile_id, session_id, executor_action, run_reason, retry_id, position)
+               VALUES (?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(position), 0) + 1 FROM execution_queue))"#,
             id,
             workspace_id,
             executor_profile_json,
             session_id,
-            executor_action_json
+            executor_action_json,
+            run_reason,
+            retry_id
         )
         .execute(pool)
         .await?;
@@ -121,7 +203,10 @@ impl ExecutionQueue {
                 executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
                 queued_at AS "queued_at!: DateTime<Utc>",
                 session_id AS "session_id: Uuid",
-                executor_action AS "executor_action: String"
+                executor_action AS "executor_action: String",
+                position AS "position!: i64",
+                run_reason AS "run_reason: ExecutionProcessRunReason",
+                retry_id AS "retry_id: Uuid"
             FROM execution_queue
             WHERE id = ?"#,
             id
@@ -130,22 +215,51 @@ impl ExecutionQueue {
         .await
     }
 
-    /// Pop the oldest waiting entry (SELECT + DELETE) - FIFO by queued_at
-    /// Returns None if queue is empty
-    pub async fn pop_next(pool: &SqlitePool) -> Result<Option<Self>, sqlx::Error> {
-        // Get the oldest entry
+    /// Pop the first waiting entry (SELECT + DELETE) in queue order (entries
+    /// of higher-priority tasks first, then by position), skipping
+    /// entries whose project already runs its `max_concurrent_executions` and
+    /// entries of `blocked_executors` (e.g. `CLAUDE_CODE`).
+    /// Returns None if no entry can start.
+    pub async fn pop_next(
+        pool: &SqlitePool,
+        blocked_executors: &[String],
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let blocked_json = serde_json::to_string(blocked_executors)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
         let entry = sqlx::query_as!(
             ExecutionQueue,
             r#"SELECT
-                id AS "id!: Uuid",
-                workspace_id AS "workspace_id!: Uuid",
-                executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
-                queued_at AS "queued_at!: DateTime<Utc>",
-                session_id AS "session_id: Uuid",
-                executor_action AS "executor_action: String"
-            FROM execution_queue
-            ORDER BY queued_at ASC
-            LIMIT 1"#
+                q.id AS "id!: Uuid",
+                q.workspace_id AS "workspace_id!: Uuid",
+                q.executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                q.queued_at AS "queued_at!: DateTime<Utc>",
+                q.session_id AS "session_id: Uuid",
+                q.executor_action AS "executor_action: String",
+                q.position AS "position!: i64",
+                q.run_reason AS "run_reason: ExecutionProcessRunReason",
+                q.retry_id AS "retry_id: Uuid"
+            FROM execution_queue q
+            JOIN workspaces w ON w.id = q.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            JOIN projects p ON p.id = t.project_id
+            WHERE json_extract(q.executor_profile_id, '$.executor')
+                    NOT IN (SELECT value FROM json_each($1))
+              AND (
+                  p.max_concurrent_executions IS NULL
+                  OR (
+                      SELECT COUNT(*)
+                      FROM execution_processes ep
+                      JOIN sessions s ON s.id = ep.session_id
+                      JOIN workspaces w2 ON w2.id = s.workspace_id
+                      JOIN tasks t2 ON t2.id = w2.task_id
+                      WHERE t2.project_id = p.id
+                        AND ep.status = 'running'
+                        AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'replay')
+                  ) < p.max_concurrent_executions
+              )
+            ORDER BY t.priority DESC, q.position ASC, q.queued_at ASC
+            LIMIT 1"#,
+            blocked_json
         )
         .fetch_optional(pool)
         .await?;
@@ -160,6 +274,133 @@ impl ExecutionQueue {
         Ok(entry)
     }
 
+    /// Waiting entries of a project's workspaces, in queue order
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectQueueEntry>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectQueueEntry,
+            r#"SELECT
+                q.id AS "id!: Uuid",
+                q.workspace_id AS "workspace_id!: Uuid",
+                t.id AS "task_id!: Uuid",
+                t.title AS "task_title!",
+                t.priority AS "task_priority!: TaskPriority",
+                q.executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                q.queued_at AS "queued_at!: DateTime<Utc>",
+                q.session_id AS "session_id: Uuid",
+                q.position AS "position!: i64"
+            FROM execution_queue q
+            JOIN workspaces w ON w.id = q.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.project_id = ?
+            ORDER BY t.priority DESC, q.position ASC, q.queued_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Reorder a project's entries to follow `entry_ids`, which must list
+    /// exactly the project's current entries. The positions the project
+    /// already holds are handed out again, so other projects keep their
+    /// places; entries of higher-priority tasks still start first. Returns
+    /// false if `entry_ids` does not match the queue.
+    pub async fn reorder_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        entry_ids: &[Uuid],
+    ) -> Result<bool, sqlx::Error> {
+        let current = Self::find_by_project_id(pool, project_id).await?;
+        let mut requested = entry_ids.to_vec();
+        requested.sort();
+        requested.dedup();
+        let mut existing: Vec<Uuid> = current.iter().map(|e| e.id).collect();
+        existing.sort();
+        if requested.len() != entry_ids.len() || requested != existing {
+            return Ok(false);
+        }
+
+        // The project's entries are listed by priority first, so their
+        // positions need not be ascending
+        let mut positions: Vec<i64> = current.iter().map(|e| e.position).collect();
+        positions.sort_unstable();
+
+        let mut tx = pool.begin().await?;
+        for (id, position) in entry_ids.iter().zip(positions) {
+            sqlx::query!(
+                "UPDATE execution_queue SET position = ? WHERE id = ?",
+                position,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Priority of the most urgent waiting entry that `pop_next` could start
+    /// with the same `blocked_executors`. Entries held back by their
+    /// project's limit or their provider don't count: preempting an agent
+    /// for them would only hand its slot back to the agent's own resume.
+    pub async fn find_highest_priority(
+        pool: &SqlitePool,
+        blocked_executors: &[String],
+    ) -> Result<Option<TaskPriority>, sqlx::Error> {
+        let blocked_json = serde_json::to_string(blocked_executors)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        sqlx::query_scalar!(
+            r#"SELECT t.priority AS "priority!: TaskPriority"
+            FROM execution_queue q
+            JOIN workspaces w ON w.id = q.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            JOIN projects p ON p.id = t.project_id
+            WHERE json_extract(q.executor_profile_id, '$.executor')
+                    NOT IN (SELECT value FROM json_each($1))
+              AND (
+                  p.max_concurrent_executions IS NULL
+                  OR (
+                      SELECT COUNT(*)
+                      FROM execution_processes ep
+                      JOIN sessions s ON s.id = ep.session_id
+                      JOIN workspaces w2 ON w2.id = s.workspace_id
+                      JOIN tasks t2 ON t2.id = w2.task_id
+                      WHERE t2.project_id = p.id
+                        AND ep.status = 'running'
+                        AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent', 'replay')
+                  ) < p.max_concurrent_executions
+              )
+            ORDER BY t.priority DESC
+            LIMIT 1"#,
+            blocked_json
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// All waiting entries, in queue order
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionQueue,
+            r#"SELECT
+                id AS "id!: Uuid",
+                workspace_id AS "workspace_id!: Uuid",
+                executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                queued_at AS "queued_at!: DateTime<Utc>",
+                session_id AS "session_id: Uuid",
+                executor_action AS "executor_action: String",
+                position AS "position!: i64",
+                run_reason AS "run_reason: ExecutionProcessRunReason",
+                retry_id AS "retry_id: Uuid"
+            FROM execution_queue
+            ORDER BY position ASC, queued_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Check if a workspace has a pending queue entry
     pub async fn find_by_workspace(
         pool: &SqlitePool,
@@ -173,7 +414,10 @@ impl ExecutionQueue {
                 executor_profile_id AS "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
                 queued_at AS "queued_at!: DateTime<Utc>",
                 session_id AS "session_id: Uuid",
-                executor_action AS "executor_action: String"
+                executor_action AS "executor_action: String",
+                position AS "position!: i64",
+                run_reason AS "run_reason: ExecutionProcessRunReason",
+                retry_id AS "retry_id: Uuid"
             FROM execution_queue
             WHERE workspace_id = ?"#,
             workspace_id
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How repo-local git hooks are treated when vibe-kanban commits. With the
+/// project's sandbox enabled, hooks run inside it.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema,
+)]
+#[sqlx(type_name = "git_hooks_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GitHooksMode {
+    /// Run hooks; a failing hook fails the commit
+    #[default]
+    Run,
+    /// Commit with `--no-verify`
+    Skip,
+    /// Run hooks, and if they fail commit with `--no-verify` and report
+    /// their output on the execution or merge
+    Report,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct GitHooksPolicy {
+    pub project_id: Uuid,
+    pub mode: GitHooksMode,
+    /// Run `pre-commit` on the agent's changed files after each successful
+    /// run in repos that have a `.pre-commit-config.yaml`
+    pub run_pre_commit: bool,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateGitHooksPolicy {
+    pub mode: GitHooksMode,
+    #[serde(default)]
+    pub run_pre_commit: bool,
+}
+
+impl GitHooksPolicy {
+    /// The project's policy, or the default (`run`) when none was saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let policy = sqlx::query_as!(
+            GitHooksPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      mode as "mode!: GitHooksMode",
+                      run_pre_commit as "run_pre_commit!: bool",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM git_hooks_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.unwrap_or(Self {
+            project_id,
+            mode: GitHooksMode::Run,
+            run_pre_commit: false,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn mode_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<GitHooksMode, sqlx::Error> {
+        Ok(Self::find_or_default(pool, project_id).await?.mode)
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateGitHooksPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            GitHooksPolicy,
+            r#"INSERT INTO git_hooks_policies (project_id, mode, run_pre_commit)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   mode = excluded.mode,
+                   run_pre_commit = excluded.run_pre_commit,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         mode as "mode!: GitHooksMode",
+                         run_pre_commit as "run_pre_commit!: bool",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.mode,
+            data.run_pre_commit
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
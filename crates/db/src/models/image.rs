@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, QueryBuilder, SqlitePool};
+use sqlx::{Executor, FromRow, QueryBuilder, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -180,7 +180,12 @@ impl Image {
         Ok(())
     }
 
-    pub async fn find_orphaned_images(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+    /// Images no task or conversation refers to that were last uploaded
+    /// before `cutoff`
+    pub async fn find_orphaned_images(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Image,
             r#"SELECT i.id as "id!: Uuid",
@@ -195,11 +200,84 @@ impl Image {
                LEFT JOIN task_images ti ON i.id = ti.image_id
                LEFT JOIN conversation_images ci ON i.id = ci.image_id
                WHERE ti.task_id IS NULL
-                 AND ci.conversation_session_id IS NULL"#
+                 AND ci.conversation_session_id IS NULL
+                 AND julianday(i.updated_at) < julianday($1)"#,
+            cutoff
         )
         .fetch_all(pool)
         .await
     }
+
+    /// Images attached to tasks or conversations of the project and to
+    /// nothing outside it
+    pub async fn find_exclusive_to_project(
+        executor: impl Executor<'_, Database = Sqlite>,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Image,
+            r#"SELECT i.id as "id!: Uuid",
+                      i.file_path as "file_path!",
+                      i.original_name as "original_name!",
+                      i.mime_type,
+                      i.size_bytes as "size_bytes!",
+                      i.hash as "hash!",
+                      i.created_at as "created_at!: DateTime<Utc>",
+                      i.updated_at as "updated_at!: DateTime<Utc>"
+               FROM images i
+               WHERE (EXISTS (SELECT 1 FROM task_images ti JOIN tasks t ON t.id = ti.task_id
+                              WHERE ti.image_id = i.id AND t.project_id = $1)
+                      OR EXISTS (SELECT 1 FROM conversation_images ci
+                                 JOIN conversation_sessions c ON c.id = ci.conversation_session_id
+                                 WHERE ci.image_id = i.id AND c.project_id = $1))
+                 AND NOT EXISTS (SELECT 1 FROM task_images ti JOIN tasks t ON t.id = ti.task_id
+                                 WHERE ti.image_id = i.id AND t.project_id != $1)
+                 AND NOT EXISTS (SELECT 1 FROM conversation_images ci
+                                 JOIN conversation_sessions c ON c.id = ci.conversation_session_id
+                                 WHERE ci.image_id = i.id AND c.project_id != $1)"#,
+            project_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Delete the image unless it was referenced or uploaded again since
+    /// [`Self::find_orphaned_images`] returned it. Returns whether it was deleted.
+    pub async fn delete_if_orphaned(
+        pool: &SqlitePool,
+        id: Uuid,
+        cutoff: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM images
+               WHERE id = $1
+                 AND julianday(updated_at) < julianday($2)
+                 AND NOT EXISTS (SELECT 1 FROM task_images WHERE image_id = $1)
+                 AND NOT EXISTS (SELECT 1 FROM conversation_images WHERE image_id = $1)"#,
+            id,
+            cutoff
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark an existing image as uploaded again, restarting its grace period
+    pub async fn touch(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE images SET updated_at = datetime('now', 'subsec') WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_all_file_paths(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT file_path as "file_path!" FROM images"#)
+            .fetch_all(pool)
+            .await
+    }
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
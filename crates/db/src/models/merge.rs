@@ -2,9 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, ToSchema)]
 #[sqlx(type_name = "merge_status", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum MergeStatus {
@@ -14,14 +15,14 @@ pub enum MergeStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Merge {
     Direct(DirectMerge),
     Pr(PrMerge),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct DirectMerge {
     pub id: Uuid,
     pub workspace_id: Uuid,
@@ -32,7 +33,7 @@ pub struct DirectMerge {
 }
 
 /// PR merge - represents a pull request merge
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PrMerge {
     pub id: Uuid,
     pub workspace_id: Uuid,
@@ -40,9 +41,11 @@ pub struct PrMerge {
     pub created_at: DateTime<Utc>,
     pub target_branch_name: String,
     pub pr_info: PullRequestInfo,
+    /// Opened as a draft and not yet marked ready for review by the PR monitor
+    pub is_draft: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PullRequestInfo {
     pub number: i64,
     pub url: String,
@@ -71,6 +74,7 @@ struct MergeRow {
     pr_status: Option<MergeStatus>,
     pr_merged_at: Option<DateTime<Utc>>,
     pr_merge_commit_sha: Option<String>,
+    pr_is_draft: bool,
     created_at: DateTime<Utc>,
 }
 
@@ -109,6 +113,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -131,6 +136,7 @@ impl Merge {
         target_branch_name: &str,
         pr_number: i64,
         pr_url: &str,
+        is_draft: bool,
     ) -> Result<PrMerge, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -138,8 +144,8 @@ impl Merge {
         sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
-                id, workspace_id, repo_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name
-            ) VALUES ($1, $2, $3, 'pr', $4, $5, 'open', $6, $7)
+                id, workspace_id, repo_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name, pr_is_draft
+            ) VALUES ($1, $2, $3, 'pr', $4, $5, 'open', $6, $7, $8)
             RETURNING
                 id as "id!: Uuid",
                 workspace_id as "workspace_id!: Uuid",
@@ -151,6 +157,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -160,14 +167,16 @@ impl Merge {
             pr_number,
             pr_url,
             now,
-            target_branch_name
+            target_branch_name,
+            is_draft
         )
         .fetch_one(pool)
         .await
         .map(Into::into)
     }
 
-    /// Get all open PRs for monitoring
+    /// Get all open PRs for monitoring, except those of archived tasks and
+    /// projects
     pub async fn get_open_prs(pool: &SqlitePool) -> Result<Vec<PrMerge>, sqlx::Error> {
         let rows = sqlx::query_as!(
             MergeRow,
@@ -182,10 +191,17 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges
                WHERE merge_type = 'pr' AND pr_status = 'open'
+                 AND workspace_id NOT IN (
+                     SELECT w.id FROM workspaces w
+                     JOIN tasks t ON t.id = w.task_id
+                     JOIN projects p ON p.id = t.project_id
+                     WHERE t.archived_at IS NOT NULL OR p.archived_at IS NOT NULL
+                 )
                ORDER BY created_at DESC"#,
         )
         .fetch_all(pool)
@@ -223,6 +239,36 @@ impl Merge {
 
         Ok(())
     }
+
+    /// Record that a draft PR was marked ready for review
+    pub async fn mark_ready(pool: &SqlitePool, merge_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE merges SET pr_is_draft = FALSE WHERE id = $1",
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether work from any of the task's workspaces was merged, directly or
+    /// through a merged PR
+    pub async fn task_has_merged(pool: &SqlitePool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1
+                   FROM merges m
+                   JOIN workspaces w ON w.id = m.workspace_id
+                   WHERE w.task_id = $1
+                     AND (m.merge_type = 'direct' OR m.pr_status = 'merged')
+               ) as "merged!: bool""#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
     /// Find all merges for a workspace (returns both direct and PR merges)
     pub async fn find_by_workspace_id(
         pool: &SqlitePool,
@@ -242,6 +288,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges
@@ -275,6 +322,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges
@@ -320,6 +368,7 @@ impl From<MergeRow> for PrMerge {
                 merged_at: row.pr_merged_at,
                 merge_commit_sha: row.pr_merge_commit_sha,
             },
+            is_draft: row.pr_is_draft,
             created_at: row.created_at,
         }
     }
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How long the check command may run when the project does not say
+pub const DEFAULT_CHECK_TIMEOUT_SECS: i64 = 600;
+
+/// Checks the merge queue requires to pass before it merges a branch
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct MergeCheckPolicy {
+    pub project_id: Uuid,
+    /// Shell command run in the rebased worktree; a non-zero exit fails the
+    /// entry
+    pub check_command: Option<String>,
+    /// Every GitHub check run on the pushed task branch must have passed
+    pub require_github_checks: bool,
+    #[ts(type = "number")]
+    pub timeout_secs: i64,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateMergeCheckPolicy {
+    pub check_command: Option<String>,
+    #[serde(default)]
+    pub require_github_checks: bool,
+    #[ts(type = "number | null")]
+    pub timeout_secs: Option<i64>,
+}
+
+impl MergeCheckPolicy {
+    /// Whether the queue has anything to check
+    pub fn is_enabled(&self) -> bool {
+        self.check_command.is_some() || self.require_github_checks
+    }
+
+    /// The project's policy, or the default (no checks) when none was saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let policy = sqlx::query_as!(
+            MergeCheckPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      check_command,
+                      require_github_checks as "require_github_checks!: bool",
+                      timeout_secs as "timeout_secs!: i64",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM merge_check_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.unwrap_or(Self {
+            project_id,
+            check_command: None,
+            require_github_checks: false,
+            timeout_secs: DEFAULT_CHECK_TIMEOUT_SECS,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateMergeCheckPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        let check_command = data
+            .check_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty());
+        let timeout_secs = data.timeout_secs.unwrap_or(DEFAULT_CHECK_TIMEOUT_SECS);
+        sqlx::query_as!(
+            MergeCheckPolicy,
+            r#"INSERT INTO merge_check_policies
+                   (project_id, check_command, require_github_checks, timeout_secs)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   check_command = excluded.check_command,
+                   require_github_checks = excluded.require_github_checks,
+                   timeout_secs = excluded.timeout_secs,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         check_command,
+                         require_github_checks as "require_github_checks!: bool",
+                         timeout_secs as "timeout_secs!: i64",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            check_command,
+            data.require_github_checks,
+            timeout_secs
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
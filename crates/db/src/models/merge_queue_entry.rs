@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Status of a merge queue entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "merge_queue_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum MergeQueueStatus {
+    /// Entry is waiting in queue
+    Queued,
+    /// Entry is currently being merged
+    Merging,
+}
+
+/// An entry in the merge queue
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct MergeQueueEntry {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    pub queued_at: DateTime<Utc>,
+    pub status: MergeQueueStatus,
+    pub commit_message: String,
+    /// Set on entries reloaded after a restart; the processor checks they
+    /// still apply before merging them
+    pub needs_revalidation: bool,
+}
+
+impl MergeQueueEntry {
+    pub fn new(
+        project_id: Uuid,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        commit_message: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            project_id,
+            workspace_id,
+            repo_id,
+            queued_at: Utc::now(),
+            status: MergeQueueStatus::Queued,
+            commit_message,
+            needs_revalidation: false,
+        }
+    }
+
+    /// All persisted entries, in queue order
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            MergeQueueEntry,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      queued_at as "queued_at!: DateTime<Utc>",
+                      status as "status!: MergeQueueStatus",
+                      commit_message,
+                      needs_revalidation as "needs_revalidation!: bool"
+               FROM merge_queue_entries
+               ORDER BY position ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Persist an entry at the back of the queue, replacing the workspace's
+    /// previous entry if it had one
+    pub async fn upsert(pool: &SqlitePool, entry: &MergeQueueEntry) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO merge_queue_entries
+                   (id, project_id, workspace_id, repo_id, commit_message, status,
+                    position, needs_revalidation, queued_at)
+               VALUES ($1, $2, $3, $4, $5, $6,
+                       (SELECT COALESCE(MAX(position), 0) + 1 FROM merge_queue_entries),
+                       $7, $8)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   id = excluded.id,
+                   project_id = excluded.project_id,
+                   repo_id = excluded.repo_id,
+                   commit_message = excluded.commit_message,
+                   status = excluded.status,
+                   position = excluded.position,
+                   needs_revalidation = excluded.needs_revalidation,
+                   queued_at = excluded.queued_at,
+                   updated_at = datetime('now', 'subsec')"#,
+            entry.id,
+            entry.project_id,
+            entry.workspace_id,
+            entry.repo_id,
+            entry.commit_message,
+            entry.status,
+            entry.needs_revalidation,
+            entry.queued_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: MergeQueueStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE merge_queue_entries
+               SET status = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Rewrite queue positions so entries sort in the order of `ids`
+    pub async fn set_positions(pool: &SqlitePool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (idx, id) in ids.iter().enumerate() {
+            let position = idx as i64 + 1;
+            sqlx::query!(
+                "UPDATE merge_queue_entries SET position = $2 WHERE id = $1",
+                id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Put every entry back to Queued and flag it for revalidation. Run at
+    /// startup: a merge that was in flight when the server stopped never
+    /// finished, and any branch may have moved in the meantime.
+    pub async fn mark_all_for_revalidation(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE merge_queue_entries
+               SET status = 'queued',
+                   needs_revalidation = 1,
+                   updated_at = datetime('now', 'subsec')"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM merge_queue_entries WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
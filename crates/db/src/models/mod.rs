@@ -1,28 +1,67 @@
 pub mod agent_feedback;
 pub mod app_settings;
+pub mod approval_policy;
+pub mod audit_log;
+pub mod backup_verification;
+pub mod bisect;
+pub mod branch_cleanup_policy;
 pub mod coding_agent_turn;
 pub mod conversation_message;
 pub mod conversation_session;
+pub mod conversation_worktree;
 pub mod embedding;
 pub mod execution_process;
+pub mod execution_process_failure;
 pub mod execution_process_logs;
 pub mod execution_process_normalized_entry;
 pub mod execution_process_repo_state;
+pub mod execution_process_resource_sample;
+pub mod execution_process_retry;
 pub mod execution_queue;
 pub mod gantt;
+pub mod git_hooks_policy;
 pub mod image;
 pub mod merge;
+pub mod merge_check_policy;
+pub mod merge_queue_entry;
 pub mod notification;
+pub mod operation;
+pub mod permission_finding;
+pub mod pr_draft_policy;
 pub mod project;
+pub mod project_dashboard;
+pub mod project_env_file;
+pub mod project_file_rule;
+pub mod project_memory;
+pub mod project_purge;
 pub mod project_repo;
+pub mod project_secret;
+pub mod project_template;
+pub mod project_tool_requirement;
+pub mod prompt_template;
+pub mod redaction;
+pub mod redaction_patterns;
 pub mod repo;
 pub mod review_attention;
+pub mod review_comment;
+pub mod sandbox_policy;
 pub mod scratch;
+pub mod semantic_search;
 pub mod session;
 pub mod tag;
 pub mod task;
 pub mod task_dependency;
+pub mod task_env_var;
 pub mod task_group;
+pub mod task_link;
+pub mod task_schedule;
+pub mod task_watcher;
+pub mod text_search;
+pub mod time_tracking;
+pub mod token_usage;
+pub mod trash;
+pub mod user;
 pub mod user_question;
+pub mod webhook;
 pub mod workspace;
 pub mod workspace_repo;
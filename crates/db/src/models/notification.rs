@@ -3,10 +3,21 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug,
+    Clone,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    TS,
+    EnumString,
+    Display,
+    Default,
+    ToSchema,
 )]
 #[sqlx(type_name = "notification_type", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -18,9 +29,13 @@ pub enum NotificationType {
     AgentQuestionNeeded,
     AgentError,
     ConversationResponse,
+    /// A watched task moved to another status
+    TaskStatusChanged,
+    /// A watched task's work was merged
+    TaskMerged,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Notification {
     pub id: Uuid,
     pub project_id: Option<Uuid>,
@@ -32,6 +47,14 @@ pub struct Notification {
     pub workspace_id: Option<Uuid>,
     pub session_id: Option<Uuid>,
     pub conversation_session_id: Option<Uuid>,
+    /// The user this notification is meant for; None for everyone
+    pub user_id: Option<Uuid>,
+    /// Notifications with the same key raised shortly after this one are
+    /// folded into it
+    pub group_key: Option<String>,
+    /// How many notifications this one stands for
+    #[ts(type = "number")]
+    pub occurrences: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -46,6 +69,8 @@ pub struct CreateNotification {
     pub workspace_id: Option<Uuid>,
     pub session_id: Option<Uuid>,
     pub conversation_session_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub group_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -56,7 +81,33 @@ pub struct UpdateNotification {
     pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+/// Narrows a notification listing; the default matches everything meant for
+/// everyone
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    pub notification_type: Option<NotificationType>,
+    pub unread_only: bool,
+    /// The signed-in user; notifications meant for anyone else are left out
+    pub recipient: Option<Uuid>,
+    /// Only notifications meant for `recipient`, such as those about the
+    /// tasks they watch
+    pub targeted_only: bool,
+}
+
+impl NotificationFilter {
+    pub fn matches(&self, notification: &Notification) -> bool {
+        self.notification_type
+            .as_ref()
+            .is_none_or(|t| *t == notification.notification_type)
+            && (!self.unread_only || !notification.is_read)
+            && notification
+                .user_id
+                .is_none_or(|user_id| Some(user_id) == self.recipient)
+            && (!self.targeted_only || notification.user_id.is_some())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct NotificationStats {
     pub total: i64,
     pub unread: i64,
@@ -70,8 +121,8 @@ impl Notification {
 
         sqlx::query_as!(
             Notification,
-            r#"INSERT INTO notifications (id, project_id, notification_type, title, message, metadata, workspace_id, session_id, conversation_session_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            r#"INSERT INTO notifications (id, project_id, notification_type, title, message, metadata, workspace_id, session_id, conversation_session_id, user_id, group_key)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                RETURNING id AS "id!: Uuid",
                          project_id AS "project_id: Uuid",
                          notification_type AS "notification_type!: NotificationType",
@@ -82,6 +133,9 @@ impl Notification {
                          workspace_id AS "workspace_id: Uuid",
                          session_id AS "session_id: Uuid",
                          conversation_session_id AS "conversation_session_id: Uuid",
+                         user_id AS "user_id: Uuid",
+                         group_key,
+                         occurrences AS "occurrences!: i64",
                          created_at AS "created_at!: DateTime<Utc>",
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
@@ -92,7 +146,80 @@ impl Notification {
             metadata_json,
             data.workspace_id,
             data.session_id,
-            data.conversation_session_id
+            data.conversation_session_id,
+            data.user_id,
+            data.group_key
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Fold `data` into the latest notification with its group key raised
+    /// within `window`, or create it when there is none. `summarize` titles
+    /// and describes a notification standing for the given number of them.
+    pub async fn create_grouped(
+        pool: &SqlitePool,
+        data: &CreateNotification,
+        window: chrono::Duration,
+        summarize: impl FnOnce(i64) -> (String, String),
+    ) -> Result<Self, sqlx::Error> {
+        let Some(group_key) = data.group_key.as_deref() else {
+            return Self::create(pool, data).await;
+        };
+        let since = format!("-{} seconds", window.num_seconds());
+        let latest = sqlx::query!(
+            r#"SELECT id AS "id!: Uuid", occurrences AS "occurrences!: i64"
+               FROM notifications
+               WHERE group_key = $1
+                 AND user_id IS $2
+                 AND created_at >= datetime('now', 'subsec', $3)
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            group_key,
+            data.user_id,
+            since
+        )
+        .fetch_optional(pool)
+        .await?;
+        let Some(latest) = latest else {
+            return Self::create(pool, data).await;
+        };
+
+        let occurrences = latest.occurrences + 1;
+        let (title, message) = summarize(occurrences);
+        let metadata_json = data.metadata.as_ref().map(|m| m.to_string());
+        sqlx::query_as!(
+            Notification,
+            r#"UPDATE notifications
+               SET title = $2,
+                   message = $3,
+                   metadata = $4,
+                   workspace_id = $5,
+                   occurrences = $6,
+                   is_read = 0,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id AS "id!: Uuid",
+                         project_id AS "project_id: Uuid",
+                         notification_type AS "notification_type!: NotificationType",
+                         title,
+                         message,
+                         is_read AS "is_read!: bool",
+                         metadata AS "metadata: serde_json::Value",
+                         workspace_id AS "workspace_id: Uuid",
+                         session_id AS "session_id: Uuid",
+                         conversation_session_id AS "conversation_session_id: Uuid",
+                         user_id AS "user_id: Uuid",
+                         group_key,
+                         occurrences AS "occurrences!: i64",
+                         created_at AS "created_at!: DateTime<Utc>",
+                         updated_at AS "updated_at!: DateTime<Utc>""#,
+            latest.id,
+            title,
+            message,
+            metadata_json,
+            data.workspace_id,
+            occurrences
         )
         .fetch_one(pool)
         .await
@@ -111,6 +238,9 @@ impl Notification {
                       workspace_id AS "workspace_id: Uuid",
                       session_id AS "session_id: Uuid",
                       conversation_session_id AS "conversation_session_id: Uuid",
+                      user_id AS "user_id: Uuid",
+                      group_key,
+                      occurrences AS "occurrences!: i64",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM notifications
@@ -134,6 +264,9 @@ impl Notification {
                       workspace_id AS "workspace_id: Uuid",
                       session_id AS "session_id: Uuid",
                       conversation_session_id AS "conversation_session_id: Uuid",
+                      user_id AS "user_id: Uuid",
+                      group_key,
+                      occurrences AS "occurrences!: i64",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM notifications
@@ -147,9 +280,11 @@ impl Notification {
     pub async fn find_by_project_id(
         pool: &SqlitePool,
         project_id: Uuid,
+        filter: &NotificationFilter,
         limit: Option<i64>,
     ) -> Result<Vec<Self>, sqlx::Error> {
         let limit = limit.unwrap_or(100);
+        let notification_type = filter.notification_type.as_ref().map(|t| t.to_string());
         sqlx::query_as!(
             Notification,
             r#"SELECT id AS "id!: Uuid",
@@ -162,13 +297,24 @@ impl Notification {
                       workspace_id AS "workspace_id: Uuid",
                       session_id AS "session_id: Uuid",
                       conversation_session_id AS "conversation_session_id: Uuid",
+                      user_id AS "user_id: Uuid",
+                      group_key,
+                      occurrences AS "occurrences!: i64",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM notifications
                WHERE project_id = $1
+                 AND ($2 IS NULL OR notification_type = $2)
+                 AND (NOT $3 OR is_read = 0)
+                 AND (user_id IS NULL OR user_id = $4)
+                 AND (NOT $5 OR user_id IS NOT NULL)
                ORDER BY created_at DESC
-               LIMIT $2"#,
+               LIMIT $6"#,
             project_id,
+            notification_type,
+            filter.unread_only,
+            filter.recipient,
+            filter.targeted_only,
             limit
         )
         .fetch_all(pool)
@@ -177,9 +323,11 @@ impl Notification {
 
     pub async fn find_global(
         pool: &SqlitePool,
+        filter: &NotificationFilter,
         limit: Option<i64>,
     ) -> Result<Vec<Self>, sqlx::Error> {
         let limit = limit.unwrap_or(100);
+        let notification_type = filter.notification_type.as_ref().map(|t| t.to_string());
         sqlx::query_as!(
             Notification,
             r#"SELECT id AS "id!: Uuid",
@@ -192,12 +340,23 @@ impl Notification {
                       workspace_id AS "workspace_id: Uuid",
                       session_id AS "session_id: Uuid",
                       conversation_session_id AS "conversation_session_id: Uuid",
+                      user_id AS "user_id: Uuid",
+                      group_key,
+                      occurrences AS "occurrences!: i64",
                       created_at AS "created_at!: DateTime<Utc>",
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM notifications
                WHERE project_id IS NULL
+                 AND ($1 IS NULL OR notification_type = $1)
+                 AND (NOT $2 OR is_read = 0)
+                 AND (user_id IS NULL OR user_id = $3)
+                 AND (NOT $4 OR user_id IS NOT NULL)
                ORDER BY created_at DESC
-               LIMIT $1"#,
+               LIMIT $5"#,
+            notification_type,
+            filter.unread_only,
+            filter.recipient,
+            filter.targeted_only,
             limit
         )
         .fetch_all(pool)
@@ -231,6 +390,9 @@ impl Notification {
                          workspace_id AS "workspace_id: Uuid",
                          session_id AS "session_id: Uuid",
                          conversation_session_id AS "conversation_session_id: Uuid",
+                         user_id AS "user_id: Uuid",
+                         group_key,
+                         occurrences AS "occurrences!: i64",
                          created_at AS "created_at!: DateTime<Utc>",
                          updated_at AS "updated_at!: DateTime<Utc>""#,
             id,
@@ -251,20 +413,53 @@ impl Notification {
         Ok(result.rows_affected())
     }
 
+    /// Set the read state of the given notifications, skipping those already
+    /// in that state and those meant for someone other than `recipient`
+    pub async fn mark_read(
+        pool: &SqlitePool,
+        ids: &[Uuid],
+        is_read: bool,
+        recipient: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let ids_json = serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string());
+        let result = sqlx::query!(
+            r#"UPDATE notifications
+               SET is_read = $2, updated_at = datetime('now', 'subsec')
+               WHERE id IN (SELECT value FROM json_each($1))
+                 AND is_read != $2
+                 AND (user_id IS NULL OR user_id = $3)"#,
+            ids_json,
+            is_read,
+            recipient
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Mark every unread notification of the project (or the global ones)
+    /// that `recipient` can see read, optionally only those of one type
     pub async fn mark_all_read(
         pool: &SqlitePool,
         project_id: Option<Uuid>,
+        notification_type: Option<&NotificationType>,
+        recipient: Option<Uuid>,
     ) -> Result<u64, sqlx::Error> {
+        let notification_type = notification_type.map(|t| t.to_string());
         let result = if let Some(pid) = project_id {
             sqlx::query!(
-                "UPDATE notifications SET is_read = 1, updated_at = datetime('now', 'subsec') WHERE project_id = $1 AND is_read = 0",
-                pid
+                "UPDATE notifications SET is_read = 1, updated_at = datetime('now', 'subsec') WHERE project_id = $1 AND is_read = 0 AND ($2 IS NULL OR notification_type = $2) AND (user_id IS NULL OR user_id = $3)",
+                pid,
+                notification_type,
+                recipient
             )
             .execute(pool)
             .await?
         } else {
             sqlx::query!(
-                "UPDATE notifications SET is_read = 1, updated_at = datetime('now', 'subsec') WHERE project_id IS NULL AND is_read = 0"
+                "UPDATE notifications SET is_read = 1, updated_at = datetime('now', 'subsec') WHERE project_id IS NULL AND is_read = 0 AND ($1 IS NULL OR notification_type = $1) AND (user_id IS NULL OR user_id = $2)",
+                notification_type,
+                recipient
             )
             .execute(pool)
             .await?
@@ -272,9 +467,12 @@ impl Notification {
         Ok(result.rows_affected())
     }
 
+    /// Counts of the notifications of the project (or the global ones) that
+    /// `recipient` can see
     pub async fn get_stats(
         pool: &SqlitePool,
         project_id: Option<Uuid>,
+        recipient: Option<Uuid>,
     ) -> Result<NotificationStats, sqlx::Error> {
         if let Some(pid) = project_id {
             let stats = sqlx::query!(
@@ -282,8 +480,9 @@ impl Notification {
                     COUNT(*) AS "total!: i64",
                     SUM(CASE WHEN is_read = 0 THEN 1 ELSE 0 END) AS "unread!: i64"
                    FROM notifications
-                   WHERE project_id = $1"#,
-                pid
+                   WHERE project_id = $1 AND (user_id IS NULL OR user_id = $2)"#,
+                pid,
+                recipient
             )
             .fetch_one(pool)
             .await?;
@@ -298,7 +497,8 @@ impl Notification {
                     COUNT(*) AS "total!: i64",
                     SUM(CASE WHEN is_read = 0 THEN 1 ELSE 0 END) AS "unread!: i64"
                    FROM notifications
-                   WHERE project_id IS NULL"#
+                   WHERE project_id IS NULL AND (user_id IS NULL OR user_id = $1)"#,
+                recipient
             )
             .fetch_one(pool)
             .await?;
@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a long-running operation does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "operation_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Merge,
+    CommitMessage,
+    Backup,
+    Restore,
+    Import,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "operation_state", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OperationState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// A long-running operation, kept after it finishes as history
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Operation {
+    pub id: Uuid,
+    pub kind: OperationKind,
+    pub state: OperationState,
+    pub workspace_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    /// Percent complete, 0-100; None when the operation can't estimate it
+    pub progress: Option<i64>,
+    /// What the operation is currently doing
+    pub message: Option<String>,
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub finished_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+    /// The user who started the operation, when the server has accounts
+    pub created_by: Option<Uuid>,
+}
+
+impl Operation {
+    pub fn is_finished(&self) -> bool {
+        self.state != OperationState::Running
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        kind: OperationKind,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Operation,
+            r#"INSERT INTO operations (id, kind, workspace_id, task_id, created_by)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!: OperationKind",
+                         state as "state!: OperationState",
+                         workspace_id as "workspace_id: Uuid",
+                         task_id as "task_id: Uuid",
+                         progress,
+                         message,
+                         error,
+                         started_at as "started_at!: DateTime<Utc>",
+                         finished_at as "finished_at: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>",
+                         created_by as "created_by: Uuid""#,
+            id,
+            kind,
+            workspace_id,
+            task_id,
+            created_by
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Operation,
+            r#"SELECT id as "id!: Uuid",
+                      kind as "kind!: OperationKind",
+                      state as "state!: OperationState",
+                      workspace_id as "workspace_id: Uuid",
+                      task_id as "task_id: Uuid",
+                      progress,
+                      message,
+                      error,
+                      started_at as "started_at!: DateTime<Utc>",
+                      finished_at as "finished_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>",
+                      created_by as "created_by: Uuid"
+               FROM operations
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent operations first, optionally narrowed to a workspace, a
+    /// kind or a state
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        workspace_id: Option<Uuid>,
+        kind: Option<OperationKind>,
+        state: Option<OperationState>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Operation,
+            r#"SELECT id as "id!: Uuid",
+                      kind as "kind!: OperationKind",
+                      state as "state!: OperationState",
+                      workspace_id as "workspace_id: Uuid",
+                      task_id as "task_id: Uuid",
+                      progress,
+                      message,
+                      error,
+                      started_at as "started_at!: DateTime<Utc>",
+                      finished_at as "finished_at: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>",
+                      created_by as "created_by: Uuid"
+               FROM operations
+               WHERE ($1 IS NULL OR workspace_id = $1)
+                 AND ($2 IS NULL OR kind = $2)
+                 AND ($3 IS NULL OR state = $3)
+               ORDER BY julianday(started_at) DESC
+               LIMIT $4"#,
+            workspace_id,
+            kind,
+            state,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record progress on a running operation; `message` replaces the
+    /// previous one only when given
+    pub async fn update_progress(
+        pool: &SqlitePool,
+        id: Uuid,
+        progress: Option<i64>,
+        message: Option<&str>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Operation,
+            r#"UPDATE operations
+               SET progress = $2,
+                   message = COALESCE($3, message),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND state = 'running'
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!: OperationKind",
+                         state as "state!: OperationState",
+                         workspace_id as "workspace_id: Uuid",
+                         task_id as "task_id: Uuid",
+                         progress,
+                         message,
+                         error,
+                         started_at as "started_at!: DateTime<Utc>",
+                         finished_at as "finished_at: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>",
+                         created_by as "created_by: Uuid""#,
+            id,
+            progress,
+            message
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Finish a running operation: succeeded when `error` is None, failed
+    /// otherwise
+    pub async fn finish(
+        pool: &SqlitePool,
+        id: Uuid,
+        error: Option<&str>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let state = if error.is_some() {
+            OperationState::Failed
+        } else {
+            OperationState::Succeeded
+        };
+        sqlx::query_as!(
+            Operation,
+            r#"UPDATE operations
+               SET state = $2,
+                   error = $3,
+                   progress = CASE WHEN $3 IS NULL THEN 100 ELSE progress END,
+                   finished_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND state = 'running'
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!: OperationKind",
+                         state as "state!: OperationState",
+                         workspace_id as "workspace_id: Uuid",
+                         task_id as "task_id: Uuid",
+                         progress,
+                         message,
+                         error,
+                         started_at as "started_at!: DateTime<Utc>",
+                         finished_at as "finished_at: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>",
+                         created_by as "created_by: Uuid""#,
+            id,
+            state,
+            error
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Fail every operation still marked running. Called at startup, when
+    /// nothing can be running yet.
+    pub async fn fail_interrupted(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE operations
+               SET state = 'failed',
+                   error = 'Interrupted by a server restart',
+                   finished_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE state = 'running'"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "permission_finding_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionFindingKind {
+    /// A file or directory anyone on the machine can write to
+    WorldWritable,
+    /// A setuid or setgid bit
+    Setuid,
+    /// A new symlink resolving outside the worktree
+    SymlinkEscape,
+}
+
+/// A permission problem an execution left in its workspace
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct PermissionFinding {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub kind: PermissionFindingKind,
+    /// Path relative to the workspace
+    pub path: String,
+    /// Mode bits, or the symlink's target
+    pub detail: String,
+    /// Set once a user has looked at it; merging is blocked until then
+    #[ts(type = "Date | null")]
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePermissionFinding {
+    pub kind: PermissionFindingKind,
+    pub path: String,
+    pub detail: String,
+}
+
+impl PermissionFinding {
+    pub async fn create_many(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        findings: &[CreatePermissionFinding],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for finding in findings {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO permission_findings
+                       (id, execution_process_id, workspace_id, kind, path, detail)
+                   VALUES ($1, $2, $3, $4, $5, $6)"#,
+                id,
+                execution_process_id,
+                workspace_id,
+                finding.kind,
+                finding.path,
+                finding.detail
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Most recent first
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PermissionFinding,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      kind as "kind!: PermissionFindingKind",
+                      path,
+                      detail,
+                      acknowledged_at as "acknowledged_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM permission_findings
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn count_unacknowledged(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM permission_findings
+               WHERE workspace_id = $1 AND acknowledged_at IS NULL"#,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Acknowledge every open finding of the workspace; returns how many
+    pub async fn acknowledge_all(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE permission_findings
+               SET acknowledged_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1 AND acknowledged_at IS NULL"#,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct PrDraftPolicy {
+    pub project_id: Uuid,
+    /// Open task attempt PRs as drafts unless the request says otherwise
+    pub draft_by_default: bool,
+    /// Mark draft PRs ready for review once the review-attention analysis
+    /// finds the task needs no attention
+    pub auto_ready: bool,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdatePrDraftPolicy {
+    #[serde(default)]
+    pub draft_by_default: bool,
+    #[serde(default)]
+    pub auto_ready: bool,
+}
+
+impl PrDraftPolicy {
+    /// The project's policy, or the default (regular PRs) when none was saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let policy = sqlx::query_as!(
+            PrDraftPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      draft_by_default as "draft_by_default!: bool",
+                      auto_ready as "auto_ready!: bool",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_draft_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.unwrap_or(Self {
+            project_id,
+            draft_by_default: false,
+            auto_ready: false,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdatePrDraftPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            PrDraftPolicy,
+            r#"INSERT INTO pr_draft_policies (project_id, draft_by_default, auto_ready)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   draft_by_default = excluded.draft_by_default,
+                   auto_ready = excluded.auto_ready,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         draft_by_default as "draft_by_default!: bool",
+                         auto_ready as "auto_ready!: bool",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.draft_by_default,
+            data.auto_ready
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use executors::{actions::script::ScriptShell, dev_shell::DevShell};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::project_repo::CreateProjectRepo;
@@ -17,7 +19,7 @@ pub enum ProjectError {
     CreateFailed(String),
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Project {
     pub id: Uuid,
     pub name: String,
@@ -25,19 +27,42 @@ pub struct Project {
     pub dev_script_working_dir: Option<String>,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    /// Shell for project scripts; `None` uses the platform default
+    #[schema(value_type = Option<String>)]
+    pub script_shell: Option<ScriptShell>,
+    /// Inject the user's login-shell environment into spawned commands
+    pub inherit_login_env: bool,
+    /// Nix/devenv shell every execution is wrapped in; `None` runs commands directly
+    #[schema(value_type = Option<String>)]
+    pub dev_shell: Option<DevShell>,
+    /// Command prefix replacing the dev shell's default, e.g. `nix develop .#ci --command`
+    pub dev_shell_command: Option<String>,
+    /// Image `Docker` dev shell executions run in, e.g. `node:22`; `None` uses
+    /// the workspace's devcontainer.json
+    pub container_image: Option<String>,
+    /// Docker network the project's image runs on, e.g. `bridge` or a user
+    /// network; `None` uses the host's so dev servers stay reachable
+    pub container_network: Option<String>,
+    /// Coding agents and scripts that may run at once for this project, on
+    /// top of the global limit; `None` means no per-project limit
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
+    /// Set while the project is archived; background workers skip it and its tasks
+    #[ts(type = "Date | null")]
+    pub archived_at: Option<DateTime<Utc>>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Default, ToSchema)]
 pub struct ProjectTaskCounts {
     pub inprogress: i64,
     pub inreview: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ProjectWithTaskCounts {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -45,28 +70,45 @@ pub struct ProjectWithTaskCounts {
     pub task_counts: ProjectTaskCounts,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 pub struct CreateProject {
     pub name: String,
     pub repositories: Vec<CreateProjectRepo>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct UpdateProject {
     pub name: Option<String>,
     pub dev_script: Option<String>,
     pub dev_script_working_dir: Option<String>,
     pub default_agent_working_dir: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub script_shell: Option<ScriptShell>,
+    #[serde(default)]
+    pub inherit_login_env: Option<bool>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub dev_shell: Option<DevShell>,
+    #[serde(default)]
+    pub dev_shell_command: Option<String>,
+    #[serde(default)]
+    pub container_image: Option<String>,
+    #[serde(default)]
+    pub container_network: Option<String>,
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub max_concurrent_executions: Option<i64>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct SearchResult {
     pub path: String,
     pub is_file: bool,
     pub match_type: SearchMatchType,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub enum SearchMatchType {
     FileName,
     DirectoryName,
@@ -75,12 +117,17 @@ pub enum SearchMatchType {
 
 impl Project {
     pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
-        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM projects"#)
-            .fetch_one(pool)
-            .await
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM projects WHERE deleted_at IS NULL"#
+        )
+        .fetch_one(pool)
+        .await
     }
 
-    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<ProjectWithTaskCounts>, sqlx::Error> {
+    pub async fn find_all(
+        pool: &SqlitePool,
+        include_archived: bool,
+    ) -> Result<Vec<ProjectWithTaskCounts>, sqlx::Error> {
         #[derive(FromRow)]
         struct Row {
             id: Uuid,
@@ -89,6 +136,14 @@ impl Project {
             dev_script_working_dir: Option<String>,
             default_agent_working_dir: Option<String>,
             remote_project_id: Option<Uuid>,
+            script_shell: Option<ScriptShell>,
+            inherit_login_env: bool,
+            dev_shell: Option<DevShell>,
+            dev_shell_command: Option<String>,
+            container_image: Option<String>,
+            container_network: Option<String>,
+            max_concurrent_executions: Option<i64>,
+            archived_at: Option<DateTime<Utc>>,
             created_at: DateTime<Utc>,
             updated_at: DateTime<Utc>,
             inprogress: i64,
@@ -103,15 +158,25 @@ impl Project {
                 p.dev_script_working_dir,
                 p.default_agent_working_dir,
                 p.remote_project_id,
+                p.script_shell,
+                p.inherit_login_env,
+                p.dev_shell,
+                p.dev_shell_command,
+                p.container_image,
+                p.container_network,
+                p.max_concurrent_executions,
+                p.archived_at,
                 p.created_at,
                 p.updated_at,
                 COALESCE(SUM(CASE WHEN t.status = 'inprogress' THEN 1 ELSE 0 END), 0) AS inprogress,
                 COALESCE(SUM(CASE WHEN t.status = 'inreview' THEN 1 ELSE 0 END), 0) AS inreview
             FROM projects p
-            LEFT JOIN tasks t ON t.project_id = p.id
+            LEFT JOIN tasks t ON t.project_id = p.id AND t.deleted_at IS NULL
+            WHERE p.deleted_at IS NULL AND ($1 OR p.archived_at IS NULL)
             GROUP BY p.id
             ORDER BY p.created_at DESC"#,
         )
+        .bind(include_archived)
         .fetch_all(pool)
         .await?;
 
@@ -125,6 +190,14 @@ impl Project {
                     dev_script_working_dir: row.dev_script_working_dir,
                     default_agent_working_dir: row.default_agent_working_dir,
                     remote_project_id: row.remote_project_id,
+                    script_shell: row.script_shell,
+                    inherit_login_env: row.inherit_login_env,
+                    dev_shell: row.dev_shell,
+                    dev_shell_command: row.dev_shell_command,
+                    container_image: row.container_image,
+                    container_network: row.container_network,
+                    max_concurrent_executions: row.max_concurrent_executions,
+                    archived_at: row.archived_at,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -136,7 +209,8 @@ impl Project {
             .collect())
     }
 
-    /// Find the most actively used projects based on recent task activity
+    /// Find the most actively used projects based on recent task activity.
+    /// Archived projects are left out.
     pub async fn find_most_active(pool: &SqlitePool, limit: i32) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
@@ -144,12 +218,21 @@ impl Project {
             SELECT p.id as "id!: Uuid", p.name, p.dev_script, p.dev_script_working_dir,
                    p.default_agent_working_dir,
                    p.remote_project_id as "remote_project_id: Uuid",
-                   p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
+                   p.script_shell as "script_shell: ScriptShell",
+                   p.inherit_login_env as "inherit_login_env!: bool",
+                   p.dev_shell as "dev_shell: DevShell",
+                   p.dev_shell_command,
+                   p.container_image,
+                   p.container_network,
+                   p.max_concurrent_executions,
+                   p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>",
+                   p.archived_at as "archived_at: DateTime<Utc>"
             FROM projects p
-            WHERE p.id IN (
+            WHERE p.deleted_at IS NULL AND p.archived_at IS NULL AND p.id IN (
                 SELECT DISTINCT t.project_id
                 FROM tasks t
                 INNER JOIN workspaces w ON w.task_id = t.id
+                WHERE t.deleted_at IS NULL
                 ORDER BY w.updated_at DESC
             )
             LIMIT $1
@@ -169,16 +252,26 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      script_shell as "script_shell: ScriptShell",
+                      inherit_login_env as "inherit_login_env!: bool",
+                      dev_shell as "dev_shell: DevShell",
+                      dev_shell_command,
+                      container_image,
+                      container_network,
+                      max_concurrent_executions,
                       created_at as "created_at!: DateTime<Utc>",
-                      updated_at as "updated_at!: DateTime<Utc>"
+                      updated_at as "updated_at!: DateTime<Utc>",
+
+                      archived_at as "archived_at: DateTime<Utc>"
                FROM projects
-               WHERE id = $1"#,
+               WHERE id = $1 AND deleted_at IS NULL"#,
             id
         )
         .fetch_optional(pool)
         .await
     }
 
+    /// Also finds trashed projects, so change events can tell them apart
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
@@ -188,8 +281,17 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      script_shell as "script_shell: ScriptShell",
+                      inherit_login_env as "inherit_login_env!: bool",
+                      dev_shell as "dev_shell: DevShell",
+                      dev_shell_command,
+                      container_image,
+                      container_network,
+                      max_concurrent_executions,
                       created_at as "created_at!: DateTime<Utc>",
-                      updated_at as "updated_at!: DateTime<Utc>"
+                      updated_at as "updated_at!: DateTime<Utc>",
+
+                      archived_at as "archived_at: DateTime<Utc>"
                FROM projects
                WHERE rowid = $1"#,
             rowid
@@ -211,6 +313,14 @@ impl Project {
             dev_script_working_dir: Option<String>,
             default_agent_working_dir: Option<String>,
             remote_project_id: Option<Uuid>,
+            script_shell: Option<ScriptShell>,
+            inherit_login_env: bool,
+            dev_shell: Option<DevShell>,
+            dev_shell_command: Option<String>,
+            container_image: Option<String>,
+            container_network: Option<String>,
+            max_concurrent_executions: Option<i64>,
+            archived_at: Option<DateTime<Utc>>,
             created_at: DateTime<Utc>,
             updated_at: DateTime<Utc>,
             inprogress: i64,
@@ -225,13 +335,21 @@ impl Project {
                 p.dev_script_working_dir,
                 p.default_agent_working_dir,
                 p.remote_project_id,
+                p.script_shell,
+                p.inherit_login_env,
+                p.dev_shell,
+                p.dev_shell_command,
+                p.container_image,
+                p.container_network,
+                p.max_concurrent_executions,
+                p.archived_at,
                 p.created_at,
                 p.updated_at,
                 COALESCE(SUM(CASE WHEN t.status = 'inprogress' THEN 1 ELSE 0 END), 0) AS inprogress,
                 COALESCE(SUM(CASE WHEN t.status = 'inreview' THEN 1 ELSE 0 END), 0) AS inreview
             FROM projects p
-            LEFT JOIN tasks t ON t.project_id = p.id
-            WHERE p.id = $1
+            LEFT JOIN tasks t ON t.project_id = p.id AND t.deleted_at IS NULL
+            WHERE p.id = $1 AND p.deleted_at IS NULL
             GROUP BY p.id"#,
         )
         .bind(id)
@@ -246,6 +364,14 @@ impl Project {
                 dev_script_working_dir: row.dev_script_working_dir,
                 default_agent_working_dir: row.default_agent_working_dir,
                 remote_project_id: row.remote_project_id,
+                script_shell: row.script_shell,
+                inherit_login_env: row.inherit_login_env,
+                dev_shell: row.dev_shell,
+                dev_shell_command: row.dev_shell_command,
+                container_image: row.container_image,
+                container_network: row.container_network,
+                max_concurrent_executions: row.max_concurrent_executions,
+                archived_at: row.archived_at,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             },
@@ -268,10 +394,19 @@ impl Project {
                       dev_script_working_dir,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      script_shell as "script_shell: ScriptShell",
+                      inherit_login_env as "inherit_login_env!: bool",
+                      dev_shell as "dev_shell: DevShell",
+                      dev_shell_command,
+                      container_image,
+                      container_network,
+                      max_concurrent_executions,
                       created_at as "created_at!: DateTime<Utc>",
-                      updated_at as "updated_at!: DateTime<Utc>"
+                      updated_at as "updated_at!: DateTime<Utc>",
+
+                      archived_at as "archived_at: DateTime<Utc>"
                FROM projects
-               WHERE remote_project_id = $1
+               WHERE remote_project_id = $1 AND deleted_at IS NULL
                LIMIT 1"#,
             remote_project_id
         )
@@ -298,8 +433,17 @@ impl Project {
                           dev_script_working_dir,
                           default_agent_working_dir,
                           remote_project_id as "remote_project_id: Uuid",
+                          script_shell as "script_shell: ScriptShell",
+                          inherit_login_env as "inherit_login_env!: bool",
+                          dev_shell as "dev_shell: DevShell",
+                          dev_shell_command,
+                          container_image,
+                          container_network,
+                          max_concurrent_executions,
                           created_at as "created_at!: DateTime<Utc>",
-                          updated_at as "updated_at!: DateTime<Utc>""#,
+                          updated_at as "updated_at!: DateTime<Utc>",
+
+                          archived_at as "archived_at: DateTime<Utc>""#,
             project_id,
             data.name,
         )
@@ -320,11 +464,22 @@ impl Project {
         let dev_script = payload.dev_script.clone();
         let dev_script_working_dir = payload.dev_script_working_dir.clone();
         let default_agent_working_dir = payload.default_agent_working_dir.clone();
+        let script_shell = payload.script_shell;
+        let inherit_login_env = payload
+            .inherit_login_env
+            .unwrap_or(existing.inherit_login_env);
+        let dev_shell = payload.dev_shell;
+        let dev_shell_command = payload.dev_shell_command.clone();
+        let container_image = payload.container_image.clone();
+        let container_network = payload.container_network.clone();
+        let max_concurrent_executions = payload.max_concurrent_executions.filter(|n| *n > 0);
 
         sqlx::query_as!(
             Project,
             r#"UPDATE projects
-               SET name = $2, dev_script = $3, dev_script_working_dir = $4, default_agent_working_dir = $5
+               SET name = $2, dev_script = $3, dev_script_working_dir = $4, default_agent_working_dir = $5,
+                   script_shell = $6, inherit_login_env = $7, dev_shell = $8, dev_shell_command = $9,
+                   container_image = $10, container_network = $11, max_concurrent_executions = $12
                WHERE id = $1
                RETURNING id as "id!: Uuid",
                          name,
@@ -332,13 +487,29 @@ impl Project {
                          dev_script_working_dir,
                          default_agent_working_dir,
                          remote_project_id as "remote_project_id: Uuid",
+                         script_shell as "script_shell: ScriptShell",
+                         inherit_login_env as "inherit_login_env!: bool",
+                         dev_shell as "dev_shell: DevShell",
+                         dev_shell_command,
+                         container_image,
+                         container_network,
+                         max_concurrent_executions,
                          created_at as "created_at!: DateTime<Utc>",
-                         updated_at as "updated_at!: DateTime<Utc>""#,
+                         updated_at as "updated_at!: DateTime<Utc>",
+
+                         archived_at as "archived_at: DateTime<Utc>""#,
             id,
             name,
             dev_script,
             dev_script_working_dir,
             default_agent_working_dir,
+            script_shell,
+            inherit_login_env,
+            dev_shell,
+            dev_shell_command,
+            container_image,
+            container_network,
+            max_concurrent_executions,
         )
         .fetch_one(pool)
         .await
@@ -399,6 +570,60 @@ impl Project {
         Ok(())
     }
 
+    /// Archive the project; its tasks count as archived along with it
+    pub async fn archive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE projects
+               SET archived_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL AND archived_at IS NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn unarchive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE projects
+               SET archived_at = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL AND archived_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Move the project to the trash; its tasks are hidden along with it
+    pub async fn trash(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE projects
+               SET deleted_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE projects
+               SET deleted_at = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(pool)
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Tasks of a project that are not done or cancelled, by status
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct OpenTaskCounts {
+    pub todo: i64,
+    pub inprogress: i64,
+    pub inreview: i64,
+}
+
+/// Review attention verdicts since a point in time. A run "passes" when the
+/// review found nothing that needs attention, which lets autopilot merge it.
+#[derive(Debug, Clone, Default)]
+pub struct AutopilotOutcomes {
+    pub reviewed: i64,
+    pub passed: i64,
+}
+
+/// Aggregate queries behind the project dashboard
+pub struct ProjectDashboard;
+
+impl ProjectDashboard {
+    pub async fn open_task_counts(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<OpenTaskCounts, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT
+                COALESCE(SUM(CASE WHEN status = 'todo' THEN 1 ELSE 0 END), 0) AS "todo!: i64",
+                COALESCE(SUM(CASE WHEN status = 'inprogress' THEN 1 ELSE 0 END), 0) AS "inprogress!: i64",
+                COALESCE(SUM(CASE WHEN status = 'inreview' THEN 1 ELSE 0 END), 0) AS "inreview!: i64"
+               FROM tasks
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(OpenTaskCounts {
+            todo: row.todo,
+            inprogress: row.inprogress,
+            inreview: row.inreview,
+        })
+    }
+
+    pub async fn autopilot_outcomes(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<AutopilotOutcomes, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT
+                COUNT(*) AS "reviewed!: i64",
+                COALESCE(SUM(CASE WHEN ra.needs_attention = 0 THEN 1 ELSE 0 END), 0) AS "passed!: i64"
+               FROM review_attention ra
+               JOIN tasks t ON t.id = ra.task_id
+               WHERE t.project_id = $1
+                 AND datetime(ra.created_at) >= datetime($2)"#,
+            project_id,
+            since
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(AutopilotOutcomes {
+            reviewed: row.reviewed,
+            passed: row.passed,
+        })
+    }
+
+    /// Mean seconds from a task's creation to its first merge, over tasks
+    /// first merged since `since`. None when no task was merged.
+    pub async fn mean_seconds_to_merge(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Option<f64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT AVG((julianday(m.merged_at) - julianday(t.created_at)) * 86400.0) AS "seconds?: f64"
+               FROM tasks t
+               JOIN (
+                   SELECT w.task_id, MIN(COALESCE(m.pr_merged_at, m.created_at)) AS merged_at
+                   FROM merges m
+                   JOIN workspaces w ON w.id = m.workspace_id
+                   WHERE m.merge_type = 'direct' OR m.pr_status = 'merged'
+                   GROUP BY w.task_id
+               ) m ON m.task_id = t.id
+               WHERE t.project_id = $1
+                 AND julianday(m.merged_at) >= julianday($2)"#,
+            project_id,
+            since
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Open tasks whose latest setup or cleanup script run failed
+    pub async fn count_failing_validations(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(DISTINCT t.id) AS "count!: i64"
+               FROM tasks t
+               JOIN workspaces w ON w.task_id = t.id
+               JOIN sessions s ON s.workspace_id = w.id
+               JOIN execution_processes ep ON ep.session_id = s.id
+               WHERE t.project_id = $1
+                 AND t.status NOT IN ('done', 'cancelled')
+                 AND ep.run_reason IN ('setupscript', 'cleanupscript')
+                 AND ep.status = 'failed'
+                 AND ep.dropped = 0
+                 AND ep.created_at = (
+                     SELECT MAX(ep2.created_at)
+                     FROM execution_processes ep2
+                     JOIN sessions s2 ON s2.id = ep2.session_id
+                     WHERE s2.workspace_id = w.id
+                       AND ep2.run_reason = ep.run_reason
+                       AND ep2.dropped = 0
+                 )"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Whether an execution process ran in one of the project's workspaces
+    pub async fn execution_in_project(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1
+                   FROM execution_processes ep
+                   JOIN sessions s ON s.id = ep.session_id
+                   JOIN workspaces w ON w.id = s.workspace_id
+                   JOIN tasks t ON t.id = w.task_id
+                   WHERE ep.id = $1 AND t.project_id = $2
+               ) AS "exists!: bool""#,
+            execution_process_id,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Worktree paths of the project's workspaces that still have one
+    pub async fn worktree_paths(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT w.container_ref AS "container_ref!"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1 AND w.container_ref IS NOT NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema)]
+#[sqlx(type_name = "file_rule_effect", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FileRuleEffect {
+    /// The agent may not change matching paths
+    Deny,
+    /// The agent may only change paths matching one of these rules
+    AllowOnly,
+}
+
+/// A file pattern the agent may or may not change, e.g. never `*.sql`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectFileRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Gitignore-style pattern relative to the repo root
+    pub pattern: String,
+    pub effect: FileRuleEffect,
+    /// Only applies to tasks tagged `#<task_label>` in their title or
+    /// description; applies to every task when unset
+    pub task_label: Option<String>,
+    /// Told to the agent when it breaks the rule
+    pub reason: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateFileRule {
+    pub pattern: String,
+    pub effect: FileRuleEffect,
+    pub task_label: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl ProjectFileRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectFileRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      pattern,
+                      effect as "effect!: FileRuleEffect",
+                      task_label,
+                      reason,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_file_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the project's rules with `rules`
+    pub async fn replace_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        rules: &[CreateFileRule],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM project_file_rules WHERE project_id = $1",
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for rule in rules {
+            let id = Uuid::new_v4();
+            let pattern = rule.pattern.trim();
+            let task_label = rule
+                .task_label
+                .as_deref()
+                .map(|label| label.trim().trim_start_matches('#'))
+                .filter(|label| !label.is_empty());
+            let reason = rule
+                .reason
+                .as_deref()
+                .map(str::trim)
+                .filter(|reason| !reason.is_empty());
+            sqlx::query!(
+                r#"INSERT INTO project_file_rules (id, project_id, pattern, effect, task_label, reason)
+                   VALUES ($1, $2, $3, $4, $5, $6)"#,
+                id,
+                project_id,
+                pattern,
+                rule.effect,
+                task_label,
+                reason
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Self::find_by_project_id(pool, project_id).await
+    }
+}
@@ -0,0 +1,282 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::embedding::{EMBEDDING_DIMENSION, EmbeddingSource, TaskEmbedding};
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[sqlx(type_name = "project_memory_kind", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectMemoryKind {
+    /// Something true about the project, e.g. how to run its tests
+    Fact,
+    /// How the people working on the project want things done
+    Preference,
+}
+
+/// Whether a project collects memories and how many go into each prompt
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectMemorySettings {
+    pub project_id: Uuid,
+    /// Extract memories from finished task attempts and add them to prompts
+    pub enabled: bool,
+    /// How many of the most relevant memories are added to a prompt
+    #[ts(type = "number")]
+    pub max_injected: i64,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateProjectMemorySettings {
+    pub enabled: bool,
+    #[ts(type = "number")]
+    pub max_injected: i64,
+}
+
+/// A fact or preference remembered across a project's tasks and conversations
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectMemory {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub kind: ProjectMemoryKind,
+    pub content: String,
+    /// Task whose attempt the memory was extracted from; null when written by hand
+    pub source_task_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateProjectMemory {
+    pub kind: ProjectMemoryKind,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateProjectMemory {
+    pub kind: ProjectMemoryKind,
+    pub content: String,
+}
+
+impl ProjectMemorySettings {
+    /// The project's settings, or the default (disabled) when none were saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            ProjectMemorySettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      enabled as "enabled!: bool",
+                      max_injected,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_memory_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(settings.unwrap_or(Self {
+            project_id,
+            enabled: false,
+            max_injected: 5,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpdateProjectMemorySettings,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMemorySettings,
+            r#"INSERT INTO project_memory_settings (project_id, enabled, max_injected)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   enabled = excluded.enabled,
+                   max_injected = excluded.max_injected,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         enabled as "enabled!: bool",
+                         max_injected,
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.enabled,
+            data.max_injected
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+impl ProjectMemory {
+    /// The project's memories, newest first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMemory,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ProjectMemoryKind",
+                      content,
+                      source_task_id as "source_task_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_memories
+               WHERE project_id = $1
+               ORDER BY created_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMemory,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ProjectMemoryKind",
+                      content,
+                      source_task_id as "source_task_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_memories
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectMemory,
+        source_task_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectMemory,
+            r#"INSERT INTO project_memories (id, project_id, kind, content, source_task_id)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         kind as "kind!: ProjectMemoryKind",
+                         content,
+                         source_task_id as "source_task_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.kind,
+            data.content,
+            source_task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateProjectMemory,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMemory,
+            r#"UPDATE project_memories
+               SET kind = $2, content = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         kind as "kind!: ProjectMemoryKind",
+                         content,
+                         source_task_id as "source_task_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.kind,
+            data.content
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_memories WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// The project's newest memories, used when no embeddings are available
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMemory,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ProjectMemoryKind",
+                      content,
+                      source_task_id as "source_task_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_memories
+               WHERE project_id = $1
+               ORDER BY updated_at DESC
+               LIMIT $2"#,
+            project_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The project's memories nearest to the query. Memories whose content
+    /// changed since they were embedded are skipped. Requires sqlite-vec.
+    pub async fn search(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        query_embedding: &[f32],
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        if query_embedding.len() != EMBEDDING_DIMENSION {
+            return Err(sqlx::Error::Protocol(format!(
+                "Query embedding dimension mismatch: expected {}, got {}",
+                EMBEDDING_DIMENSION,
+                query_embedding.len()
+            )));
+        }
+        EmbeddingSource::ProjectMemory
+            .ensure_table_exists(pool)
+            .await?;
+
+        let query_bytes = TaskEmbedding::serialize_embedding(query_embedding);
+        sqlx::query_as::<_, Self>(
+            r#"SELECT m.id, m.project_id, m.kind, m.content, m.source_task_id,
+                      m.created_at, m.updated_at
+               FROM project_memory_embeddings e
+               JOIN project_memories m ON m.rowid = e.memory_rowid
+               JOIN project_memory_embedding_status s ON s.memory_id = m.id
+               WHERE m.project_id = ?2 AND s.needs_embedding = 0
+               ORDER BY vec_distance_cosine(e.embedding, ?1) ASC
+               LIMIT ?3"#,
+        )
+        .bind(&query_bytes)
+        .bind(project_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,271 @@
+//! Deleting a project together with everything derived from it.
+//!
+//! Most rows follow the project through `ON DELETE CASCADE`, but some are
+//! only unlinked by it: images reached through join tables, operations and
+//! bisect runs that outlive their task, and the sqlite-vec vectors keyed by
+//! rowid. Those are deleted explicitly here. Files on disk (worktrees, image
+//! files, backups) are left to the caller.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, Transaction};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{embedding::EmbeddingSource, image::Image};
+
+/// Rows that belong to a project, per kind of data
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectPurgeCounts {
+    #[ts(type = "number")]
+    pub tasks: u64,
+    #[ts(type = "number")]
+    pub workspaces: u64,
+    #[ts(type = "number")]
+    pub sessions: u64,
+    #[ts(type = "number")]
+    pub execution_processes: u64,
+    /// Coding agent prompts and summaries
+    #[ts(type = "number")]
+    pub agent_turns: u64,
+    #[ts(type = "number")]
+    pub normalized_entries: u64,
+    #[ts(type = "number")]
+    pub execution_logs: u64,
+    #[ts(type = "number")]
+    pub conversations: u64,
+    #[ts(type = "number")]
+    pub conversation_messages: u64,
+    #[ts(type = "number")]
+    pub operations: u64,
+    /// Images used by nothing outside the project
+    #[ts(type = "number")]
+    pub images: u64,
+    /// Semantic search vectors of tasks, messages and agent summaries
+    #[ts(type = "number")]
+    pub embeddings: u64,
+}
+
+/// What [`purge`] deleted
+#[derive(Debug, Clone)]
+pub struct PurgedProject {
+    pub counts: ProjectPurgeCounts,
+    /// Image records removed; their files are still in the image cache
+    pub images: Vec<Image>,
+}
+
+/// vec0 tables with the query selecting the project's rowids in each. The
+/// tables only exist once sqlite-vec has been loaded.
+fn vector_tables() -> [(&'static str, &'static str, &'static str); 4] {
+    [
+        (
+            "task_embeddings",
+            "task_rowid",
+            "SELECT rowid FROM tasks WHERE project_id = $1",
+        ),
+        (
+            EmbeddingSource::ConversationMessage.vector_table(),
+            EmbeddingSource::ConversationMessage.key_column(),
+            r#"SELECT m.rowid FROM conversation_messages m
+               JOIN conversation_sessions c ON c.id = m.conversation_session_id
+               WHERE c.project_id = $1"#,
+        ),
+        (
+            EmbeddingSource::ExecutionSummary.vector_table(),
+            EmbeddingSource::ExecutionSummary.key_column(),
+            r#"SELECT t.rowid FROM coding_agent_turns t
+               JOIN execution_processes ep ON ep.id = t.execution_process_id
+               LEFT JOIN sessions s ON s.id = ep.session_id
+               LEFT JOIN workspaces w ON w.id = s.workspace_id
+               LEFT JOIN tasks tk ON tk.id = w.task_id
+               LEFT JOIN conversation_sessions c ON c.id = ep.conversation_session_id
+               WHERE tk.project_id = $1 OR c.project_id = $1"#,
+        ),
+        (
+            EmbeddingSource::ProjectMemory.vector_table(),
+            EmbeddingSource::ProjectMemory.key_column(),
+            "SELECT rowid FROM project_memories WHERE project_id = $1",
+        ),
+    ]
+}
+
+async fn table_exists(conn: &mut SqliteConnection, table: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = $1)"#,
+    )
+    .bind(table)
+    .fetch_one(conn)
+    .await
+}
+
+/// Counts of what [`purge`] would delete, without deleting anything
+pub async fn count(
+    conn: &mut SqliteConnection,
+    project_id: Uuid,
+) -> Result<ProjectPurgeCounts, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"WITH project_tasks AS (SELECT id FROM tasks WHERE project_id = $1),
+                project_workspaces AS (
+                    SELECT id FROM workspaces WHERE task_id IN (SELECT id FROM project_tasks)
+                ),
+                project_sessions AS (
+                    SELECT id FROM sessions
+                    WHERE workspace_id IN (SELECT id FROM project_workspaces)
+                ),
+                project_conversations AS (
+                    SELECT id FROM conversation_sessions WHERE project_id = $1
+                ),
+                project_processes AS (
+                    SELECT id FROM execution_processes
+                    WHERE session_id IN (SELECT id FROM project_sessions)
+                       OR conversation_session_id IN (SELECT id FROM project_conversations)
+                )
+           SELECT (SELECT COUNT(*) FROM project_tasks) AS "tasks!: i64",
+                  (SELECT COUNT(*) FROM project_workspaces) AS "workspaces!: i64",
+                  (SELECT COUNT(*) FROM project_sessions) AS "sessions!: i64",
+                  (SELECT COUNT(*) FROM project_processes) AS "execution_processes!: i64",
+                  (SELECT COUNT(*) FROM coding_agent_turns
+                   WHERE execution_process_id IN (SELECT id FROM project_processes))
+                      AS "agent_turns!: i64",
+                  (SELECT COUNT(*) FROM execution_process_normalized_entries
+                   WHERE execution_id IN (SELECT id FROM project_processes))
+                      AS "normalized_entries!: i64",
+                  (SELECT COUNT(*) FROM execution_process_logs
+                   WHERE execution_id IN (SELECT id FROM project_processes))
+                      AS "execution_logs!: i64",
+                  (SELECT COUNT(*) FROM project_conversations) AS "conversations!: i64",
+                  (SELECT COUNT(*) FROM conversation_messages
+                   WHERE conversation_session_id IN (SELECT id FROM project_conversations))
+                      AS "conversation_messages!: i64",
+                  (SELECT COUNT(*) FROM operations
+                   WHERE task_id IN (SELECT id FROM project_tasks)
+                      OR workspace_id IN (SELECT id FROM project_workspaces))
+                      AS "operations!: i64""#,
+        project_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let images = Image::find_exclusive_to_project(&mut *conn, project_id).await?;
+
+    let mut embeddings = 0;
+    for (table, key_column, rowids) in vector_tables() {
+        if !table_exists(conn, table).await? {
+            continue;
+        }
+        let rows: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {table} WHERE {key_column} IN ({rowids})"
+        ))
+        .bind(project_id)
+        .fetch_one(&mut *conn)
+        .await?;
+        embeddings += rows as u64;
+    }
+
+    Ok(ProjectPurgeCounts {
+        tasks: row.tasks as u64,
+        workspaces: row.workspaces as u64,
+        sessions: row.sessions as u64,
+        execution_processes: row.execution_processes as u64,
+        agent_turns: row.agent_turns as u64,
+        normalized_entries: row.normalized_entries as u64,
+        execution_logs: row.execution_logs as u64,
+        conversations: row.conversations as u64,
+        conversation_messages: row.conversation_messages as u64,
+        operations: row.operations as u64,
+        images: images.len() as u64,
+        embeddings,
+    })
+}
+
+/// Delete the project and everything derived from it inside `tx`. The
+/// caller commits, then removes the files of the returned images.
+pub async fn purge(
+    tx: &mut Transaction<'_, Sqlite>,
+    project_id: Uuid,
+) -> Result<PurgedProject, sqlx::Error> {
+    let counts = count(&mut **tx, project_id).await?;
+    let images = Image::find_exclusive_to_project(&mut **tx, project_id).await?;
+
+    // Vectors are keyed by the rowids of rows about to be deleted
+    for (table, key_column, rowids) in vector_tables() {
+        if table_exists(&mut **tx, table).await? {
+            sqlx::query(&format!(
+                "DELETE FROM {table} WHERE {key_column} IN ({rowids})"
+            ))
+            .bind(project_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    sqlx::query!(
+        r#"DELETE FROM operations
+           WHERE task_id IN (SELECT id FROM tasks WHERE project_id = $1)
+              OR workspace_id IN (
+                  SELECT w.id FROM workspaces w
+                  JOIN tasks t ON t.id = w.task_id
+                  WHERE t.project_id = $1
+              )"#,
+        project_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"DELETE FROM bisect_runs
+           WHERE task_id IN (SELECT id FROM tasks WHERE project_id = $1)"#,
+        project_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Subtasks in other projects would otherwise block deleting the
+    // workspaces they were started from
+    sqlx::query!(
+        r#"UPDATE tasks
+           SET parent_workspace_id = NULL
+           WHERE project_id != $1
+             AND parent_workspace_id IN (
+                 SELECT w.id FROM workspaces w
+                 JOIN tasks t ON t.id = w.task_id
+                 WHERE t.project_id = $1
+             )"#,
+        project_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM projects WHERE id = $1", project_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for image in &images {
+        sqlx::query!("DELETE FROM images WHERE id = $1", image.id)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    Ok(PurgedProject { counts, images })
+}
+
+/// Execution processes of the project still running, including dev servers
+/// and conversations
+pub async fn count_running_processes(
+    pool: &SqlitePool,
+    project_id: Uuid,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!: i64"
+           FROM execution_processes ep
+           LEFT JOIN sessions s ON s.id = ep.session_id
+           LEFT JOIN workspaces w ON w.id = s.workspace_id
+           LEFT JOIN tasks t ON t.id = w.task_id
+           LEFT JOIN conversation_sessions c ON c.id = ep.conversation_session_id
+           WHERE ep.status = 'running'
+             AND (t.project_id = $1 OR c.project_id = $1)"#,
+        project_id
+    )
+    .fetch_one(pool)
+    .await
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::repo::Repo;
@@ -19,7 +20,7 @@ pub enum ProjectRepoError {
     AlreadyExists,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct ProjectRepo {
     pub id: Uuid,
     pub project_id: Uuid,
@@ -27,11 +28,13 @@ pub struct ProjectRepo {
     pub setup_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    pub post_copy_script: Option<String>,
     pub parallel_setup_script: bool,
     pub merge_target_branch: Option<String>,
 }
 
-/// ProjectRepo with the associated repo name (for script execution in worktrees)
+/// ProjectRepo with the associated repo name (for script execution in worktrees).
+/// Scripts not set on the project fall back to the repo's own.
 #[derive(Debug, Clone, FromRow)]
 pub struct ProjectRepoWithName {
     pub id: Uuid,
@@ -41,22 +44,24 @@ pub struct ProjectRepoWithName {
     pub setup_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    pub post_copy_script: Option<String>,
     pub parallel_setup_script: bool,
     pub merge_target_branch: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 pub struct CreateProjectRepo {
     pub display_name: String,
     pub git_repo_path: String,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct UpdateProjectRepo {
     pub setup_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    pub post_copy_script: Option<String>,
     pub parallel_setup_script: Option<bool>,
     pub merge_target_branch: Option<String>,
 }
@@ -74,6 +79,7 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
+                      post_copy_script,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       merge_target_branch
                FROM project_repos
@@ -96,6 +102,7 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
+                      post_copy_script,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       merge_target_branch
                FROM project_repos
@@ -116,9 +123,10 @@ impl ProjectRepo {
                       pr.project_id as "project_id!: Uuid",
                       pr.repo_id as "repo_id!: Uuid",
                       r.name as "repo_name!",
-                      pr.setup_script,
-                      pr.cleanup_script,
+                      COALESCE(pr.setup_script, r.setup_script) as setup_script,
+                      COALESCE(pr.cleanup_script, r.cleanup_script) as cleanup_script,
                       pr.copy_files,
+                      pr.post_copy_script,
                       pr.parallel_setup_script as "parallel_setup_script!: bool",
                       pr.merge_target_branch
                FROM project_repos pr
@@ -141,6 +149,10 @@ impl ProjectRepo {
                       r.path,
                       r.name,
                       r.display_name, 
+                      r.setup_script,
+                      r.cleanup_script,
+                      r.skip_submodules as "skip_submodules!: bool",
+                      r.skip_lfs as "skip_lfs!: bool",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -166,6 +178,7 @@ impl ProjectRepo {
                       setup_script,
                       cleanup_script,
                       copy_files,
+                      post_copy_script,
                       parallel_setup_script as "parallel_setup_script!: bool",
                       merge_target_branch
                FROM project_repos
@@ -242,6 +255,7 @@ impl ProjectRepo {
                          setup_script,
                          cleanup_script,
                          copy_files,
+                         post_copy_script,
                          parallel_setup_script as "parallel_setup_script!: bool",
                          merge_target_branch"#,
             id,
@@ -264,6 +278,7 @@ impl ProjectRepo {
         let setup_script = payload.setup_script.clone();
         let cleanup_script = payload.cleanup_script.clone();
         let copy_files = payload.copy_files.clone();
+        let post_copy_script = payload.post_copy_script.clone();
         let parallel_setup_script = payload
             .parallel_setup_script
             .unwrap_or(existing.parallel_setup_script);
@@ -275,20 +290,23 @@ impl ProjectRepo {
                SET setup_script = $1,
                    cleanup_script = $2,
                    copy_files = $3,
-                   parallel_setup_script = $4,
-                   merge_target_branch = $5
-               WHERE project_id = $6 AND repo_id = $7
+                   post_copy_script = $4,
+                   parallel_setup_script = $5,
+                   merge_target_branch = $6
+               WHERE project_id = $7 AND repo_id = $8
                RETURNING id as "id!: Uuid",
                          project_id as "project_id!: Uuid",
                          repo_id as "repo_id!: Uuid",
                          setup_script,
                          cleanup_script,
                          copy_files,
+                         post_copy_script,
                          parallel_setup_script as "parallel_setup_script!: bool",
                          merge_target_branch"#,
             setup_script,
             cleanup_script,
             copy_files,
+            post_copy_script,
             parallel_setup_script,
             merge_target_branch,
             project_id,
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A project-scoped secret. The value is kept in the secret store; API
+/// responses only expose the name and timestamps.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectSecret {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpsertProjectSecret {
+    pub value: String,
+}
+
+/// A secret saved before values moved to the secret store
+#[derive(Debug, Clone)]
+pub struct PlaintextProjectSecret {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub value: String,
+}
+
+impl ProjectSecret {
+    /// Secret names follow env var rules so they can be referenced from templates
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_secrets
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Secret store entries of a project's secrets, keyed by secret name
+    pub async fn refs_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<String, String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT name, secret_ref as "secret_ref!"
+               FROM project_secrets
+               WHERE project_id = $1 AND secret_ref IS NOT NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.name, row.secret_ref))
+            .collect())
+    }
+
+    /// Secrets whose values are still in the database
+    pub async fn find_plaintext(
+        pool: &SqlitePool,
+    ) -> Result<Vec<PlaintextProjectSecret>, sqlx::Error> {
+        sqlx::query_as!(
+            PlaintextProjectSecret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      value
+               FROM project_secrets
+               WHERE secret_ref IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether any secret keeps its value in the store entry `secret_ref`
+    pub async fn secret_ref_exists(
+        pool: &SqlitePool,
+        secret_ref: &str,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM project_secrets WHERE secret_ref = $1) as "exists!: bool""#,
+            secret_ref
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Point a secret at its store entry and blank the value kept here
+    pub async fn set_secret_ref(
+        pool: &SqlitePool,
+        id: Uuid,
+        secret_ref: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE project_secrets SET secret_ref = $2, value = '' WHERE id = $1"#,
+            id,
+            secret_ref
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Create or update a secret whose value is in the store entry `secret_ref`
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        secret_ref: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"INSERT INTO project_secrets (id, project_id, name, value, secret_ref)
+               VALUES ($1, $2, $3, '', $4)
+               ON CONFLICT(project_id, name) DO UPDATE SET
+                   value = '',
+                   secret_ref = excluded.secret_ref,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            secret_ref
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_secrets WHERE project_id = $1 AND name = $2",
+            project_id,
+            name
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
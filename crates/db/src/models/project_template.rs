@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A repository a project template adds to its projects
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectTemplateRepo {
+    pub display_name: String,
+    /// Where the repository lives. Relative paths are under the default
+    /// clone directory.
+    pub path: String,
+    /// Cloned into `path` when nothing is there yet
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub setup_script: Option<String>,
+    /// Comma-separated globs copied into new worktrees
+    #[serde(default)]
+    pub copy_files: Option<String>,
+}
+
+/// A task created in every project made from a template
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectTemplateTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Blueprint for new projects: their repositories, the tasks they start with
+/// and the coding agent to start those tasks with
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    #[ts(type = "Array<ProjectTemplateRepo>")]
+    #[schema(value_type = Vec<ProjectTemplateRepo>)]
+    pub repositories: Json<Vec<ProjectTemplateRepo>>,
+    #[ts(type = "ExecutorProfileId | null")]
+    #[schema(value_type = Option<Object>)]
+    pub executor_profile_id: Option<Json<ExecutorProfileId>>,
+    #[ts(type = "Array<ProjectTemplateTask>")]
+    #[schema(value_type = Vec<ProjectTemplateTask>)]
+    pub tasks: Json<Vec<ProjectTemplateTask>>,
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub labels: Json<Vec<String>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Settings of a new template, or all settings of an existing one
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpsertProjectTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub repositories: Vec<ProjectTemplateRepo>,
+    #[schema(value_type = Option<Object>)]
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub tasks: Vec<ProjectTemplateTask>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl ProjectTemplate {
+    /// All templates, or only those labelled `label`
+    pub async fn find_all(
+        pool: &SqlitePool,
+        label: Option<&str>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      repositories as "repositories!: Json<Vec<ProjectTemplateRepo>>",
+                      executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>",
+                      tasks as "tasks!: Json<Vec<ProjectTemplateTask>>",
+                      labels as "labels!: Json<Vec<String>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_templates
+               WHERE $1 IS NULL
+                  OR EXISTS (SELECT 1 FROM json_each(project_templates.labels) WHERE value = $1)
+               ORDER BY name ASC"#,
+            label
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      repositories as "repositories!: Json<Vec<ProjectTemplateRepo>>",
+                      executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>",
+                      tasks as "tasks!: Json<Vec<ProjectTemplateTask>>",
+                      labels as "labels!: Json<Vec<String>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &UpsertProjectTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let repositories = Json(&data.repositories);
+        let executor_profile_id = data.executor_profile_id.as_ref().map(Json);
+        let tasks = Json(&data.tasks);
+        let labels = Json(&data.labels);
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"INSERT INTO project_templates (id, name, description, repositories, executor_profile_id, tasks, labels)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         description,
+                         repositories as "repositories!: Json<Vec<ProjectTemplateRepo>>",
+                         executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>",
+                         tasks as "tasks!: Json<Vec<ProjectTemplateTask>>",
+                         labels as "labels!: Json<Vec<String>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.description,
+            repositories,
+            executor_profile_id,
+            tasks,
+            labels
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpsertProjectTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let repositories = Json(&data.repositories);
+        let executor_profile_id = data.executor_profile_id.as_ref().map(Json);
+        let tasks = Json(&data.tasks);
+        let labels = Json(&data.labels);
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"UPDATE project_templates
+               SET name = $2, description = $3, repositories = $4, executor_profile_id = $5,
+                   tasks = $6, labels = $7, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         description,
+                         repositories as "repositories!: Json<Vec<ProjectTemplateRepo>>",
+                         executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>",
+                         tasks as "tasks!: Json<Vec<ProjectTemplateTask>>",
+                         labels as "labels!: Json<Vec<String>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.description,
+            repositories,
+            executor_profile_id,
+            tasks,
+            labels
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
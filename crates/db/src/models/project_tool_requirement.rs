@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A tool the project expects on PATH, e.g. `node` at `>=20` or `pnpm`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectToolRequirement {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Version requirement such as `20`, `>=1.80` or `^9.1`. Any version passes when unset.
+    pub version: Option<String>,
+    /// Command that prints the tool's version. Defaults to `<name> --version`.
+    pub check_command: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateToolRequirement {
+    pub name: String,
+    pub version: Option<String>,
+    pub check_command: Option<String>,
+}
+
+impl ProjectToolRequirement {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectToolRequirement,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      version,
+                      check_command,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_tool_requirements
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the project's requirements with `requirements`
+    pub async fn replace_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        requirements: &[CreateToolRequirement],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM project_tool_requirements WHERE project_id = $1",
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for requirement in requirements {
+            let id = Uuid::new_v4();
+            let name = requirement.name.trim();
+            let version = requirement
+                .version
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            let check_command = requirement
+                .check_command
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty());
+            sqlx::query!(
+                r#"INSERT INTO project_tool_requirements (id, project_id, name, version, check_command)
+                   VALUES ($1, $2, $3, $4, $5)
+                   ON CONFLICT(project_id, name) DO UPDATE SET
+                       version = excluded.version,
+                       check_command = excluded.check_command"#,
+                id,
+                project_id,
+                name,
+                version,
+                check_command
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Self::find_by_project_id(pool, project_id).await
+    }
+}
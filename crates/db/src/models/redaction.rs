@@ -0,0 +1,273 @@
+//! Replacing a string everywhere task and agent text is stored.
+//!
+//! Covers task titles and descriptions, conversation titles and messages,
+//! coding agent prompts and summaries, normalized log entries and raw
+//! execution logs. Message metadata and the last two hold JSON, where only
+//! string values are rewritten so keys and structure stay intact. Search
+//! indexes and embeddings follow through the existing update triggers.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Rows changed per kind of data
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct RedactionCounts {
+    #[ts(type = "number")]
+    pub tasks: u64,
+    #[ts(type = "number")]
+    pub conversations: u64,
+    #[ts(type = "number")]
+    pub conversation_messages: u64,
+    /// Coding agent prompts and summaries
+    #[ts(type = "number")]
+    pub agent_turns: u64,
+    #[ts(type = "number")]
+    pub normalized_entries: u64,
+    #[ts(type = "number")]
+    pub execution_logs: u64,
+}
+
+impl RedactionCounts {
+    pub fn total(&self) -> u64 {
+        self.tasks
+            + self.conversations
+            + self.conversation_messages
+            + self.agent_turns
+            + self.normalized_entries
+            + self.execution_logs
+    }
+}
+
+/// Replace every occurrence of `text` with `placeholder` inside `tx`. The
+/// caller commits, or rolls back to only count what would change.
+pub async fn redact(
+    tx: &mut Transaction<'_, Sqlite>,
+    text: &str,
+    placeholder: &str,
+) -> Result<RedactionCounts, sqlx::Error> {
+    // Finds rows whose JSON holds the text escaped; matches are then replaced
+    // in the parsed string values, so keys and structure are never touched
+    let text_json = json_escaped(text);
+
+    let tasks = sqlx::query!(
+        r#"UPDATE tasks
+           SET title = REPLACE(title, $1, $2),
+               description = REPLACE(description, $1, $2),
+               updated_at = datetime('now', 'subsec')
+           WHERE instr(title, $1) > 0 OR instr(description, $1) > 0"#,
+        text,
+        placeholder
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let conversations = sqlx::query!(
+        r#"UPDATE conversation_sessions
+           SET title = REPLACE(title, $1, $2)
+           WHERE instr(title, $1) > 0"#,
+        text,
+        placeholder
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let mut conversation_messages = 0;
+    let rows = sqlx::query!(
+        r#"SELECT id AS "id!: Uuid", content, metadata
+           FROM conversation_messages
+           WHERE instr(content, $1) > 0 OR instr(metadata, $1) > 0 OR instr(metadata, $2) > 0"#,
+        text,
+        text_json
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    for row in rows {
+        let content = row.content.replace(text, placeholder);
+        let metadata = row
+            .metadata
+            .as_deref()
+            .and_then(|metadata| redact_json(metadata, text, placeholder));
+        if content == row.content && metadata.is_none() {
+            continue;
+        }
+        let metadata = metadata.or(row.metadata);
+        sqlx::query!(
+            r#"UPDATE conversation_messages
+               SET content = $1, metadata = $2
+               WHERE id = $3"#,
+            content,
+            metadata,
+            row.id
+        )
+        .execute(&mut **tx)
+        .await?;
+        conversation_messages += 1;
+    }
+
+    let agent_turns = sqlx::query!(
+        r#"UPDATE coding_agent_turns
+           SET prompt = REPLACE(prompt, $1, $2),
+               summary = REPLACE(summary, $1, $2)
+           WHERE instr(prompt, $1) > 0 OR instr(summary, $1) > 0"#,
+        text,
+        placeholder
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let mut normalized_entries = 0;
+    let rows = sqlx::query!(
+        r#"SELECT rowid AS "rowid!: i64", entry_json
+           FROM execution_process_normalized_entries
+           WHERE instr(entry_json, $1) > 0 OR instr(entry_json, $2) > 0"#,
+        text,
+        text_json
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    for row in rows {
+        let Some(entry_json) = redact_json(&row.entry_json, text, placeholder) else {
+            continue;
+        };
+        sqlx::query!(
+            "UPDATE execution_process_normalized_entries SET entry_json = $1 WHERE rowid = $2",
+            entry_json,
+            row.rowid
+        )
+        .execute(&mut **tx)
+        .await?;
+        normalized_entries += 1;
+    }
+
+    let mut execution_logs = 0;
+    let rows = sqlx::query!(
+        r#"SELECT rowid AS "rowid!: i64", logs
+           FROM execution_process_logs
+           WHERE instr(logs, $1) > 0 OR instr(logs, $2) > 0"#,
+        text,
+        text_json
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    for row in rows {
+        let Some(logs) = redact_jsonl(&row.logs, text, placeholder) else {
+            continue;
+        };
+        let byte_size = logs.len() as i64;
+        sqlx::query!(
+            "UPDATE execution_process_logs SET logs = $1, byte_size = $2 WHERE rowid = $3",
+            logs,
+            byte_size,
+            row.rowid
+        )
+        .execute(&mut **tx)
+        .await?;
+        execution_logs += 1;
+    }
+
+    Ok(RedactionCounts {
+        tasks: tasks.rows_affected(),
+        conversations: conversations.rows_affected(),
+        conversation_messages,
+        agent_turns: agent_turns.rows_affected(),
+        normalized_entries,
+        execution_logs,
+    })
+}
+
+/// Counts of what [`redact`] would change, without changing anything
+pub async fn count_redactable(
+    pool: &SqlitePool,
+    text: &str,
+    placeholder: &str,
+) -> Result<RedactionCounts, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let counts = redact(&mut tx, text, placeholder).await?;
+    tx.rollback().await?;
+    Ok(counts)
+}
+
+/// Replace `text` inside the string values of the JSON document `json`,
+/// leaving keys and structure alone. Text that is not JSON is replaced as is.
+/// Returns None when nothing changed.
+fn redact_json(json: &str, text: &str, placeholder: &str) -> Option<String> {
+    let Ok(mut value) = serde_json::from_str::<Value>(json) else {
+        return json.contains(text).then(|| json.replace(text, placeholder));
+    };
+    redact_value(&mut value, text, placeholder).then(|| value.to_string())
+}
+
+/// [`redact_json`] on each line of a JSONL document
+fn redact_jsonl(jsonl: &str, text: &str, placeholder: &str) -> Option<String> {
+    let mut changed = false;
+    let lines: Vec<String> = jsonl
+        .split('\n')
+        .map(|line| match redact_json(line, text, placeholder) {
+            Some(redacted) => {
+                changed = true;
+                redacted
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    changed.then(|| lines.join("\n"))
+}
+
+fn redact_value(value: &mut Value, text: &str, placeholder: &str) -> bool {
+    match value {
+        Value::String(s) if s.contains(text) => {
+            *s = s.replace(text, placeholder);
+            true
+        }
+        Value::Array(items) => items.iter_mut().fold(false, |changed, item| {
+            redact_value(item, text, placeholder) | changed
+        }),
+        Value::Object(map) => map.values_mut().fold(false, |changed, item| {
+            redact_value(item, text, placeholder) | changed
+        }),
+        _ => false,
+    }
+}
+
+/// `text` as it appears inside a JSON string
+fn json_escaped(text: &str) -> String {
+    let quoted = serde_json::Value::from(text).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text_for_json_columns() {
+        assert_eq!(json_escaped("sk-live-123"), "sk-live-123");
+        assert_eq!(json_escaped(r#"pa"ss\word"#), r#"pa\"ss\\word"#);
+    }
+
+    #[test]
+    fn redacts_string_values_but_not_keys() {
+        let json = r#"{"type":"tool_use","content":"the content of token","metadata":{"content":["content"]}}"#;
+        assert_eq!(
+            redact_json(json, "content", "[REDACTED]").unwrap(),
+            r#"{"type":"tool_use","content":"the [REDACTED] of token","metadata":{"content":["[REDACTED]"]}}"#
+        );
+        assert_eq!(
+            redact_json(r#"{"content":1}"#, "content", "[REDACTED]"),
+            None
+        );
+    }
+
+    #[test]
+    fn redacts_each_jsonl_line() {
+        let jsonl = "{\"Stdout\":\"key=abc\"}\nnot json abc\n{\"abc\":true}";
+        assert_eq!(
+            redact_jsonl(jsonl, "abc", "***").unwrap(),
+            "{\"Stdout\":\"key=***\"}\nnot json ***\n{\"abc\":true}"
+        );
+    }
+}
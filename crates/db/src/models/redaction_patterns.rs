@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Regexes masked in a project's agent and script output
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectRedactionPatterns {
+    pub project_id: Uuid,
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub patterns: Json<Vec<String>>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectRedactionPatterns {
+    /// The project's patterns, or none when none were saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let patterns = sqlx::query_as!(
+            ProjectRedactionPatterns,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      patterns as "patterns!: Json<Vec<String>>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM redaction_patterns
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(patterns.unwrap_or(Self {
+            project_id,
+            patterns: Json(Vec::new()),
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        patterns: &[String],
+    ) -> Result<Self, sqlx::Error> {
+        let patterns = Json(patterns);
+        sqlx::query_as!(
+            ProjectRedactionPatterns,
+            r#"INSERT INTO redaction_patterns (project_id, patterns)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   patterns = excluded.patterns,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         patterns as "patterns!: Json<Vec<String>>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            patterns
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -15,18 +16,39 @@ pub enum RepoError {
     NotFound,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Repo {
     pub id: Uuid,
     pub path: PathBuf,
     pub name: String,
     pub display_name: String,
+    /// Runs in this repo's worktree before the coding agent starts, in every
+    /// project that does not set its own setup script for the repo
+    pub setup_script: Option<String>,
+    /// Runs after the coding agent finishes, unless the project overrides it
+    pub cleanup_script: Option<String>,
+    /// Don't run `git submodule update` in new worktrees of this repo
+    pub skip_submodules: bool,
+    /// Don't run `git lfs pull` in new worktrees of this repo
+    pub skip_lfs: bool,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateRepoScripts {
+    pub setup_script: Option<String>,
+    pub cleanup_script: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateRepoCheckoutOptions {
+    pub skip_submodules: bool,
+    pub skip_lfs: bool,
+}
+
 impl Repo {
     /// Get repos that still have the migration sentinel as their name.
     /// Used by the startup backfill to fix repo names.
@@ -37,6 +59,10 @@ impl Repo {
                       path,
                       name,
                       display_name,
+                      setup_script,
+                      cleanup_script,
+                      skip_submodules as "skip_submodules!: bool",
+                      skip_lfs as "skip_lfs!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -70,6 +96,10 @@ impl Repo {
                       path,
                       name,
                       display_name,
+                      setup_script,
+                      cleanup_script,
+                      skip_submodules as "skip_submodules!: bool",
+                      skip_lfs as "skip_lfs!: bool",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -105,6 +135,10 @@ impl Repo {
                          path,
                          name,
                          display_name,
+                         setup_script,
+                         cleanup_script,
+                         skip_submodules as "skip_submodules!: bool",
+                         skip_lfs as "skip_lfs!: bool",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -116,6 +150,77 @@ impl Repo {
         .await
     }
 
+    pub async fn update_scripts(
+        pool: &SqlitePool,
+        id: Uuid,
+        payload: &UpdateRepoScripts,
+    ) -> Result<Self, RepoError> {
+        let setup_script = payload
+            .setup_script
+            .as_deref()
+            .filter(|s| !s.trim().is_empty());
+        let cleanup_script = payload
+            .cleanup_script
+            .as_deref()
+            .filter(|s| !s.trim().is_empty());
+
+        sqlx::query_as!(
+            Repo,
+            r#"UPDATE repos
+               SET setup_script = $1,
+                   cleanup_script = $2,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $3
+               RETURNING id as "id!: Uuid",
+                         path,
+                         name,
+                         display_name,
+                         setup_script,
+                         cleanup_script,
+                         skip_submodules as "skip_submodules!: bool",
+                         skip_lfs as "skip_lfs!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            setup_script,
+            cleanup_script,
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(RepoError::NotFound)
+    }
+
+    pub async fn update_checkout_options(
+        pool: &SqlitePool,
+        id: Uuid,
+        payload: &UpdateRepoCheckoutOptions,
+    ) -> Result<Self, RepoError> {
+        sqlx::query_as!(
+            Repo,
+            r#"UPDATE repos
+               SET skip_submodules = $1,
+                   skip_lfs = $2,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $3
+               RETURNING id as "id!: Uuid",
+                         path,
+                         name,
+                         display_name,
+                         setup_script,
+                         cleanup_script,
+                         skip_submodules as "skip_submodules!: bool",
+                         skip_lfs as "skip_lfs!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            payload.skip_submodules,
+            payload.skip_lfs,
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(RepoError::NotFound)
+    }
+
     pub async fn delete_orphaned(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!(
             r#"DELETE FROM repos
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Type, Serialize, Deserialize, TS, ToSchema,
+)]
+#[sqlx(type_name = "review_comment_side", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewCommentSide {
+    /// Lines of the file at the workspace's base commit
+    Old,
+    /// Lines of the file as changed in the workspace
+    #[default]
+    New,
+}
+
+/// A comment on a line range of a workspace's diff
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ReviewComment {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// Path as shown in the diff, prefixed with the repo name
+    pub file_path: String,
+    pub side: ReviewCommentSide,
+    #[ts(type = "number")]
+    pub line_start: i64,
+    /// Same as `line_start` for a single line
+    #[ts(type = "number")]
+    pub line_end: i64,
+    /// Markdown
+    pub body: String,
+    /// Set by hand, or when the comment was sent to the agent
+    pub resolved: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateReviewComment {
+    pub file_path: String,
+    #[serde(default)]
+    pub side: ReviewCommentSide,
+    #[ts(type = "number")]
+    pub line_start: i64,
+    /// Defaults to `line_start`
+    #[ts(type = "number | null")]
+    pub line_end: Option<i64>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateReviewComment {
+    pub body: Option<String>,
+    pub resolved: Option<bool>,
+}
+
+impl ReviewComment {
+    /// Comments of the workspace, in the order they were written
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      file_path,
+                      side as "side!: ReviewCommentSide",
+                      line_start,
+                      line_end,
+                      body,
+                      resolved as "resolved!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE workspace_id = $1
+               ORDER BY created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Unresolved comments of the workspace, grouped by file in line order
+    pub async fn find_unresolved_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      file_path,
+                      side as "side!: ReviewCommentSide",
+                      line_start,
+                      line_end,
+                      body,
+                      resolved as "resolved!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE workspace_id = $1 AND resolved = 0
+               ORDER BY file_path ASC, line_start ASC, created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      file_path,
+                      side as "side!: ReviewCommentSide",
+                      line_start,
+                      line_end,
+                      body,
+                      resolved as "resolved!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &CreateReviewComment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let line_end = data.line_end.unwrap_or(data.line_start);
+        sqlx::query_as!(
+            ReviewComment,
+            r#"INSERT INTO review_comments
+                   (id, workspace_id, file_path, side, line_start, line_end, body)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         file_path,
+                         side as "side!: ReviewCommentSide",
+                         line_start,
+                         line_end,
+                         body,
+                         resolved as "resolved!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            data.file_path,
+            data.side,
+            data.line_start,
+            line_end,
+            data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Change the body and/or resolved flag; omitted fields are kept
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateReviewComment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewComment,
+            r#"UPDATE review_comments
+               SET body = COALESCE($2, body),
+                   resolved = COALESCE($3, resolved),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         file_path,
+                         side as "side!: ReviewCommentSide",
+                         line_start,
+                         line_end,
+                         body,
+                         resolved as "resolved!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.body,
+            data.resolved
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM review_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Resolve the given comments, e.g. once they were sent to the agent
+    pub async fn resolve_many(pool: &SqlitePool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for id in ids {
+            sqlx::query!(
+                r#"UPDATE review_comments
+                   SET resolved = 1, updated_at = datetime('now', 'subsec')
+                   WHERE id = $1"#,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
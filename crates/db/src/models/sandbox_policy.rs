@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use executors::sandbox::SandboxPolicy;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A project's sandbox for agent and script executions
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectSandboxPolicy {
+    pub project_id: Uuid,
+    #[ts(type = "SandboxPolicy")]
+    #[schema(value_type = Object)]
+    pub policy: Json<SandboxPolicy>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectSandboxPolicy {
+    /// The project's policy, or the default (disabled) when none was saved
+    pub async fn find_or_default(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let policy = sqlx::query_as!(
+            ProjectSandboxPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      policy as "policy!: Json<SandboxPolicy>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM sandbox_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.unwrap_or(Self {
+            project_id,
+            policy: Json(SandboxPolicy::default()),
+            updated_at: Utc::now(),
+        }))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        policy: &SandboxPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        let policy = Json(policy);
+        sqlx::query_as!(
+            ProjectSandboxPolicy,
+            r#"INSERT INTO sandbox_policies (project_id, policy)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   policy = excluded.policy,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         policy as "policy!: Json<SandboxPolicy>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            policy
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
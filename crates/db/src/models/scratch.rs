@@ -4,6 +4,7 @@ use sqlx::{FromRow, SqlitePool};
 use strum_macros::{Display, EnumDiscriminants, EnumString};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -17,7 +18,7 @@ pub enum ScratchError {
 }
 
 /// Data for a draft follow-up scratch
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct DraftFollowUpData {
     pub message: String,
     #[serde(default)]
@@ -26,17 +27,19 @@ pub struct DraftFollowUpData {
 
 /// The payload of a scratch, tagged by type. The type is part of the composite primary key.
 /// Data is stored as markdown string.
-#[derive(Debug, Clone, Serialize, Deserialize, TS, EnumDiscriminants)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, EnumDiscriminants, ToSchema)]
 #[serde(tag = "type", content = "data", rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum_discriminants(name(ScratchType))]
 #[strum_discriminants(derive(Display, EnumString, Serialize, Deserialize, TS))]
-#[strum_discriminants(ts(use_ts_enum))]
+#[strum_discriminants(ts(repr(enum = name)))]
 #[strum_discriminants(serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 #[strum_discriminants(strum(serialize_all = "SCREAMING_SNAKE_CASE"))]
 pub enum ScratchPayload {
     DraftTask(String),
     DraftFollowUp(DraftFollowUpData),
     DraftConversationMessage(DraftFollowUpData),
+    // Review notes kept alongside a task, keyed by the task's id
+    TaskNotes(String),
 }
 
 impl ScratchPayload {
@@ -65,14 +68,18 @@ struct ScratchRow {
     pub payload: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct Scratch {
     pub id: Uuid,
     pub payload: ScratchPayload,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every save
+    #[ts(type = "number")]
+    pub version: i64,
 }
 
 impl Scratch {
@@ -97,18 +104,19 @@ impl TryFrom<ScratchRow> for Scratch {
             payload,
             created_at: r.created_at,
             updated_at: r.updated_at,
+            version: r.version,
         })
     }
 }
 
 /// Request body for creating a scratch (id comes from URL path, type from payload)
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateScratch {
     pub payload: ScratchPayload,
 }
 
 /// Request body for updating a scratch
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateScratch {
     pub payload: ScratchPayload,
 }
@@ -132,7 +140,8 @@ impl Scratch {
                 scratch_type,
                 payload,
                 created_at      as "created_at!: DateTime<Utc>",
-                updated_at      as "updated_at!: DateTime<Utc>"
+                updated_at      as "updated_at!: DateTime<Utc>",
+                version
             "#,
             id,
             scratch_type_str,
@@ -158,7 +167,8 @@ impl Scratch {
                 scratch_type,
                 payload,
                 created_at      as "created_at!: DateTime<Utc>",
-                updated_at      as "updated_at!: DateTime<Utc>"
+                updated_at      as "updated_at!: DateTime<Utc>",
+                version
             FROM scratch
             WHERE id = $1 AND scratch_type = $2
             "#,
@@ -181,7 +191,8 @@ impl Scratch {
                 scratch_type,
                 payload,
                 created_at      as "created_at!: DateTime<Utc>",
-                updated_at      as "updated_at!: DateTime<Utc>"
+                updated_at      as "updated_at!: DateTime<Utc>",
+                version
             FROM scratch
             ORDER BY created_at DESC
             "#
@@ -215,13 +226,15 @@ impl Scratch {
             VALUES ($1, $2, $3)
             ON CONFLICT(id, scratch_type) DO UPDATE SET
                 payload = excluded.payload,
-                updated_at = datetime('now', 'subsec')
+                updated_at = datetime('now', 'subsec'),
+                version = scratch.version + 1
             RETURNING
                 id              as "id!: Uuid",
                 scratch_type,
                 payload,
                 created_at      as "created_at!: DateTime<Utc>",
-                updated_at      as "updated_at!: DateTime<Utc>"
+                updated_at      as "updated_at!: DateTime<Utc>",
+                version
             "#,
             id,
             scratch_type_str,
@@ -233,6 +246,68 @@ impl Scratch {
         Scratch::try_from(row)
     }
 
+    /// Save a scratch only if nobody saved it since `expected_version` was
+    /// read; an `expected_version` of 0 means it doesn't exist yet. Returns
+    /// None when the scratch was saved, created or deleted in the meantime.
+    pub async fn save_if_version(
+        pool: &SqlitePool,
+        id: Uuid,
+        payload: &ScratchPayload,
+        expected_version: i64,
+    ) -> Result<Option<Self>, ScratchError> {
+        let scratch_type_str = payload.scratch_type().to_string();
+        let payload_str = serde_json::to_string(payload)?;
+
+        let row = if expected_version == 0 {
+            sqlx::query_as!(
+                ScratchRow,
+                r#"
+                INSERT INTO scratch (id, scratch_type, payload)
+                VALUES ($1, $2, $3)
+                ON CONFLICT(id, scratch_type) DO NOTHING
+                RETURNING
+                    id              as "id!: Uuid",
+                    scratch_type,
+                    payload,
+                    created_at      as "created_at!: DateTime<Utc>",
+                    updated_at      as "updated_at!: DateTime<Utc>",
+                    version
+                "#,
+                id,
+                scratch_type_str,
+                payload_str,
+            )
+            .fetch_optional(pool)
+            .await?
+        } else {
+            sqlx::query_as!(
+                ScratchRow,
+                r#"
+                UPDATE scratch
+                SET payload = $3,
+                    updated_at = datetime('now', 'subsec'),
+                    version = version + 1
+                WHERE id = $1 AND scratch_type = $2 AND version = $4
+                RETURNING
+                    id              as "id!: Uuid",
+                    scratch_type,
+                    payload,
+                    created_at      as "created_at!: DateTime<Utc>",
+                    updated_at      as "updated_at!: DateTime<Utc>",
+                    version
+                "#,
+                id,
+                scratch_type_str,
+                payload_str,
+                expected_version,
+            )
+            .fetch_optional(pool)
+            .await?
+        };
+
+        row.map(Scratch::try_from).transpose()
+    }
+
     pub async fn delete(
         pool: &SqlitePool,
         id: Uuid,
@@ -261,7 +336,8 @@ impl Scratch {
                 scratch_type,
                 payload,
                 created_at      as "created_at!: DateTime<Utc>",
-                updated_at      as "updated_at!: DateTime<Utc>"
+                updated_at      as "updated_at!: DateTime<Utc>",
+                version
             FROM scratch
             WHERE rowid = $1
             "#,
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utils::text::truncate_to_char_boundary;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    conversation_message::MessageRole,
+    embedding::{EMBEDDING_DIMENSION, EmbeddingSource, TaskEmbedding},
+};
+
+/// Longest snippet of matched content returned to clients
+const SNIPPET_MAX_BYTES: usize = 500;
+
+/// A conversation message ranked by similarity to the query
+#[derive(Debug, Clone, FromRow, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationMessageMatch {
+    pub message_id: Uuid,
+    pub conversation_session_id: Uuid,
+    pub session_title: String,
+    pub role: MessageRole,
+    /// Start of the message content
+    pub snippet: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    pub similarity_score: f64,
+}
+
+/// A coding agent summary ranked by similarity to the query
+#[derive(Debug, Clone, FromRow, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionSummaryMatch {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    /// Start of the agent's final summary
+    pub snippet: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    pub similarity_score: f64,
+}
+
+fn snippet(content: &str) -> String {
+    let content = content.trim();
+    let truncated = truncate_to_char_boundary(content, SNIPPET_MAX_BYTES);
+    if truncated.len() < content.len() {
+        format!("{truncated}…")
+    } else {
+        truncated.to_string()
+    }
+}
+
+fn check_dimension(query_embedding: &[f32]) -> Result<(), sqlx::Error> {
+    if query_embedding.len() != EMBEDDING_DIMENSION {
+        return Err(sqlx::Error::Protocol(format!(
+            "Query embedding dimension mismatch: expected {}, got {}",
+            EMBEDDING_DIMENSION,
+            query_embedding.len()
+        )));
+    }
+    Ok(())
+}
+
+impl ConversationMessageMatch {
+    /// Nearest conversation messages in the project, unless it is trashed.
+    /// Requires sqlite-vec.
+    pub async fn search(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        query_embedding: &[f32],
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        check_dimension(query_embedding)?;
+        EmbeddingSource::ConversationMessage
+            .ensure_table_exists(pool)
+            .await?;
+
+        let query_bytes = TaskEmbedding::serialize_embedding(query_embedding);
+        let mut matches = sqlx::query_as::<_, Self>(
+            r#"SELECT
+                m.id AS message_id,
+                m.conversation_session_id,
+                cs.title AS session_title,
+                m.role,
+                m.content AS snippet,
+                m.created_at,
+                -- Convert cosine distance (0-2) to similarity score (1-0)
+                1.0 - (vec_distance_cosine(e.embedding, ?1) / 2.0) AS similarity_score
+            FROM conversation_message_embeddings e
+            JOIN conversation_messages m ON m.rowid = e.message_rowid
+            JOIN conversation_sessions cs ON cs.id = m.conversation_session_id
+            JOIN projects p ON p.id = cs.project_id
+            WHERE cs.project_id = ?2 AND p.deleted_at IS NULL
+            ORDER BY similarity_score DESC
+            LIMIT ?3"#,
+        )
+        .bind(&query_bytes)
+        .bind(project_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        for m in &mut matches {
+            m.snippet = snippet(&m.snippet);
+        }
+        Ok(matches)
+    }
+}
+
+impl ExecutionSummaryMatch {
+    /// Nearest coding agent summaries for tasks in the project, leaving out
+    /// trashed tasks and projects. Requires sqlite-vec.
+    pub async fn search(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        query_embedding: &[f32],
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        check_dimension(query_embedding)?;
+        EmbeddingSource::ExecutionSummary
+            .ensure_table_exists(pool)
+            .await?;
+
+        let query_bytes = TaskEmbedding::serialize_embedding(query_embedding);
+        let mut matches = sqlx::query_as::<_, Self>(
+            r#"SELECT
+                ep.id AS execution_process_id,
+                w.id AS workspace_id,
+                t.id AS task_id,
+                t.title AS task_title,
+                cat.summary AS snippet,
+                cat.updated_at AS created_at,
+                1.0 - (vec_distance_cosine(e.embedding, ?1) / 2.0) AS similarity_score
+            FROM execution_summary_embeddings e
+            JOIN coding_agent_turns cat ON cat.rowid = e.turn_rowid
+            JOIN execution_processes ep ON ep.id = cat.execution_process_id
+            JOIN sessions s ON s.id = ep.session_id
+            JOIN workspaces w ON w.id = s.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            JOIN projects p ON p.id = t.project_id
+            WHERE t.project_id = ?2 AND cat.summary IS NOT NULL
+              AND t.deleted_at IS NULL AND p.deleted_at IS NULL
+            ORDER BY similarity_score DESC
+            LIMIT ?3"#,
+        )
+        .bind(&query_bytes)
+        .bind(project_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        for m in &mut matches {
+            m.snippet = snippet(&m.snippet);
+        }
+        Ok(matches)
+    }
+}
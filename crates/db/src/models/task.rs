@@ -3,12 +3,23 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{project::Project, workspace::Workspace};
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug,
+    Clone,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    TS,
+    EnumString,
+    Display,
+    Default,
+    ToSchema,
 )]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -22,6 +33,33 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+/// How urgently a task's queued executions should start. Higher priorities
+/// start first and may preempt lower ones when `preempt_lower_priority` is set.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    Default,
+    ToSchema,
+)]
+#[repr(i64)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPriority {
+    Low = 0,
+    #[default]
+    Normal = 1,
+    High = 2,
+    Urgent = 3,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS, Default)]
 pub enum TaskOrderBy {
     CreatedAtAsc,
@@ -42,7 +80,7 @@ impl TaskOrderBy {
     }
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Task {
     pub id: Uuid,
     pub project_id: Uuid, // Foreign key to Project
@@ -62,12 +100,18 @@ pub struct Task {
     pub last_executor: String,
     // Review attention status: None = not analyzed, Some(true) = needs attention, Some(false) = looks good
     pub needs_attention: Option<bool>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    /// The user who created the task, when the server has accounts
+    pub created_by: Option<Uuid>,
+    /// Set while the task is archived; background workers skip it
+    pub archived_at: Option<DateTime<Utc>>,
 }
 
 /// Wrapper around Task for API responses.
 /// Task now contains materialized status columns directly (is_blocked, has_in_progress_attempt, etc.)
 /// This wrapper provides Deref/DerefMut for convenient field access and exists for API compatibility.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskWithAttemptStatus {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -94,14 +138,14 @@ impl std::ops::DerefMut for TaskWithAttemptStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskRelationships {
     pub parent_task: Option<Task>, // The task that owns the parent workspace
     pub current_workspace: Workspace, // The workspace we're viewing
     pub children: Vec<Task>,       // Tasks created from this workspace
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateTask {
     pub project_id: Uuid,
     pub title: String,
@@ -111,6 +155,12 @@ pub struct CreateTask {
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
     pub task_group_id: Option<Uuid>,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    /// Set by the server from the signed-in user
+    #[serde(skip)]
+    #[ts(skip)]
+    pub created_by: Option<Uuid>,
 }
 
 impl CreateTask {
@@ -128,6 +178,8 @@ impl CreateTask {
             image_ids: None,
             shared_task_id: None,
             task_group_id: None,
+            priority: None,
+            created_by: None,
         }
     }
 
@@ -147,11 +199,13 @@ impl CreateTask {
             image_ids: None,
             shared_task_id: Some(shared_task_id),
             task_group_id: None,
+            priority: None,
+            created_by: None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateTask {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -159,6 +213,8 @@ pub struct UpdateTask {
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub task_group_id: Option<Uuid>,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
 }
 
 impl Task {
@@ -174,6 +230,7 @@ impl Task {
         Project::find_by_id(pool, self.project_id).await
     }
 
+    /// The project's tasks, leaving out archived ones
     pub async fn find_by_project_id_with_attempt_status(
         pool: &SqlitePool,
         project_id: Uuid,
@@ -196,9 +253,12 @@ impl Task {
                 last_attempt_failed AS "last_attempt_failed!: bool",
                 is_queued AS "is_queued!: bool",
                 last_executor AS "last_executor!: String",
-                needs_attention AS "needs_attention: bool"
+                needs_attention AS "needs_attention: bool",
+                priority AS "priority!: TaskPriority",
+                created_by AS "created_by: Uuid",
+                archived_at AS "archived_at: DateTime<Utc>"
             FROM tasks
-            WHERE project_id = $1
+            WHERE project_id = $1 AND deleted_at IS NULL AND archived_at IS NULL
             ORDER BY created_at DESC"#,
             project_id
         )
@@ -233,9 +293,14 @@ impl Task {
                 last_attempt_failed AS "last_attempt_failed!: bool",
                 is_queued AS "is_queued!: bool",
                 last_executor AS "last_executor!: String",
-                needs_attention AS "needs_attention: bool"
+                needs_attention AS "needs_attention: bool",
+                priority AS "priority!: TaskPriority",
+                created_by AS "created_by: Uuid",
+                archived_at AS "archived_at: DateTime<Utc>"
             FROM tasks
-            WHERE id = $1"#,
+            WHERE id = $1
+              AND deleted_at IS NULL
+              AND project_id NOT IN (SELECT id FROM projects WHERE deleted_at IS NOT NULL)"#,
             task_id
         )
         .fetch_optional(pool)
@@ -250,6 +315,7 @@ impl Task {
         query: Option<String>,
         status: Option<TaskStatus>,
         task_group_id: Option<Uuid>,
+        include_archived: bool,
         order_by: TaskOrderBy,
         limit: i64,
         offset: i64,
@@ -261,13 +327,16 @@ impl Task {
             r#"SELECT COUNT(*) as "count!: i64"
                FROM tasks t
                WHERE t.project_id = $1
+                 AND t.deleted_at IS NULL
                  AND ($2 IS NULL OR t.status = $2)
                  AND ($3 IS NULL OR t.title LIKE $3 OR t.description LIKE $3)
-                 AND ($4 IS NULL OR t.task_group_id = $4)"#,
+                 AND ($4 IS NULL OR t.task_group_id = $4)
+                 AND ($5 OR t.archived_at IS NULL)"#,
             project_id,
             status,
             search_pattern,
-            task_group_id_str
+            task_group_id_str,
+            include_archived
         )
         .fetch_one(pool)
         .await?
@@ -293,12 +362,17 @@ impl Task {
                 t.last_attempt_failed,
                 t.is_queued,
                 t.last_executor,
-                t.needs_attention
+                t.needs_attention,
+                t.priority,
+                t.created_by,
+                t.archived_at
             FROM tasks t
             WHERE t.project_id = ?1
+              AND t.deleted_at IS NULL
               AND (?2 IS NULL OR t.status = ?2)
               AND (?5 IS NULL OR t.title LIKE ?5 OR t.description LIKE ?5)
               AND (?6 IS NULL OR t.task_group_id = ?6)
+              AND (?7 OR t.archived_at IS NULL)
             ORDER BY {}
             LIMIT ?3 OFFSET ?4"#,
             order_by.to_sql()
@@ -311,6 +385,7 @@ impl Task {
             .bind(offset)
             .bind(search_pattern)
             .bind(task_group_id_str)
+            .bind(include_archived)
             .fetch_all(pool)
             .await?;
 
@@ -325,19 +400,39 @@ impl Task {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
                FROM tasks
-               WHERE id = $1"#,
+               WHERE id = $1
+                 AND deleted_at IS NULL
+                 AND project_id NOT IN (SELECT id FROM projects WHERE deleted_at IS NOT NULL)"#,
             id
         )
         .fetch_optional(pool)
         .await
     }
 
+    /// Every task of the project, archived ones included, oldest first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NULL
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Also finds trashed tasks, so change events can tell them apart
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -355,7 +450,7 @@ impl Task {
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -368,7 +463,7 @@ impl Task {
     pub async fn find_all_shared(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id IS NOT NULL"#
         )
@@ -382,11 +477,12 @@ impl Task {
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
         let status = data.status.clone().unwrap_or_default();
+        let priority = data.priority.unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id, task_group_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id, task_group_id, priority, created_by)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
@@ -394,7 +490,9 @@ impl Task {
             status,
             data.parent_workspace_id,
             data.shared_task_id,
-            data.task_group_id
+            data.task_group_id,
+            priority,
+            data.created_by
         )
         .fetch_one(pool)
         .await
@@ -416,7 +514,7 @@ impl Task {
             r#"UPDATE tasks
                SET title = $3, description = $4, status = $5, parent_workspace_id = $6, task_group_id = $7
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>""#,
             id,
             project_id,
             title,
@@ -444,6 +542,21 @@ impl Task {
         Ok(())
     }
 
+    pub async fn update_priority(
+        pool: &SqlitePool,
+        id: Uuid,
+        priority: TaskPriority,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET priority = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            priority
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     /// Update the needs_attention field for a task.
     /// None = not analyzed, Some(true) = needs attention, Some(false) = looks good
     pub async fn update_needs_attention(
@@ -517,6 +630,75 @@ impl Task {
         Ok(result.rows_affected())
     }
 
+    pub async fn archive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL AND archived_at IS NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn unarchive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL AND archived_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Whether the task or its project is archived. Background workers skip
+    /// such tasks.
+    pub async fn is_archived(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM tasks t
+                   JOIN projects p ON p.id = t.project_id
+                   WHERE t.id = $1
+                     AND (t.archived_at IS NOT NULL OR p.archived_at IS NOT NULL)
+               ) AS "archived!: bool""#,
+            id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Move the task to the trash
+    pub async fn trash(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET deleted_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET deleted_at = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND deleted_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
     where
         E: Executor<'e, Database = Sqlite>,
@@ -594,9 +776,9 @@ impl Task {
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", task_group_id as "task_group_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", is_blocked as "is_blocked!: bool", has_in_progress_attempt as "has_in_progress_attempt!: bool", last_attempt_failed as "last_attempt_failed!: bool", is_queued as "is_queued!: bool", last_executor as "last_executor!: String", needs_attention as "needs_attention: bool", priority as "priority!: TaskPriority", created_by as "created_by: Uuid", archived_at as "archived_at: DateTime<Utc>"
                FROM tasks
-               WHERE parent_workspace_id = $1
+               WHERE parent_workspace_id = $1 AND deleted_at IS NULL
                ORDER BY created_at DESC"#,
             workspace_id,
         )
@@ -607,7 +789,7 @@ impl Task {
     /// Escape special FTS5 query syntax characters.
     /// FTS5 special characters: " * ^ - : OR AND NOT NEAR
     /// We wrap tokens in double quotes to treat them as literals.
-    fn escape_fts5_query(query: &str) -> String {
+    pub(crate) fn escape_fts5_query(query: &str) -> String {
         // Split on whitespace, wrap each token in quotes, and join with spaces
         // This ensures special characters are treated as literals
         query
@@ -660,6 +842,9 @@ impl Task {
             is_queued: bool,
             last_executor: String,
             needs_attention: Option<bool>,
+            priority: TaskPriority,
+            created_by: Option<Uuid>,
+            archived_at: Option<DateTime<Utc>>,
             rank_score: f64,
         }
 
@@ -682,11 +867,15 @@ impl Task {
   t.is_queued,
   t.last_executor,
   t.needs_attention,
+  t.priority,
+  t.created_by,
+  t.archived_at,
   -bm25(tasks_fts) AS rank_score
 FROM tasks_fts
 JOIN tasks t ON t.rowid = tasks_fts.rowid
 WHERE tasks_fts MATCH ?1
   AND t.project_id = ?2
+  AND t.deleted_at IS NULL
   AND (?3 IS NULL OR t.status = ?3)
   AND (?4 IS NULL OR t.task_group_id = ?4)
 ORDER BY rank_score DESC
@@ -722,6 +911,9 @@ LIMIT ?5"#,
                             is_queued: rec.is_queued,
                             last_executor: rec.last_executor,
                             needs_attention: rec.needs_attention,
+                            priority: rec.priority,
+                            created_by: rec.created_by,
+                            archived_at: rec.archived_at,
                         },
                     },
                     rec.rank_score,
@@ -792,6 +984,9 @@ LIMIT ?5"#,
             is_queued: i64,
             executor: String,
             needs_attention: Option<bool>,
+            priority: TaskPriority,
+            created_by: Option<Uuid>,
+            archived_at: Option<DateTime<Utc>>,
             hybrid_score: f64,
         }
 
@@ -835,6 +1030,9 @@ LIMIT ?5"#,
                 t.is_queued,
                 t.last_executor AS executor,
                 t.needs_attention,
+                t.priority,
+                t.created_by,
+                t.archived_at,
 
                 -- Hybrid score calculation:
                 -- When both exist: weighted combination
@@ -854,6 +1052,7 @@ LIMIT ?5"#,
             LEFT JOIN vector_scores vs ON vs.task_rowid = t.rowid
             LEFT JOIN fts_scores fs ON fs.rowid = t.rowid
             WHERE t.project_id = ?2
+                AND t.deleted_at IS NULL
                 AND (vs.score IS NOT NULL OR fs.score IS NOT NULL)
                 AND (?4 IS NULL OR t.status = ?4)
                 AND (?5 IS NULL OR t.task_group_id = ?5)
@@ -887,12 +1086,16 @@ LIMIT ?5"#,
                 t.is_queued,
                 t.last_executor AS executor,
                 t.needs_attention,
+                t.priority,
+                t.created_by,
+                t.archived_at,
 
                 vs.score AS hybrid_score
 
             FROM tasks t
             JOIN vector_scores vs ON vs.task_rowid = t.rowid
             WHERE t.project_id = ?2
+                AND t.deleted_at IS NULL
                 AND (?4 IS NULL OR t.status = ?4)
                 AND (?5 IS NULL OR t.task_group_id = ?5)
             ORDER BY hybrid_score DESC
@@ -945,6 +1148,9 @@ LIMIT ?5"#,
                             is_queued: rec.is_queued != 0,
                             last_executor: rec.executor,
                             needs_attention: rec.needs_attention,
+                            priority: rec.priority,
+                            created_by: rec.created_by,
+                            archived_at: rec.archived_at,
                         },
                     },
                     rec.hybrid_score,
@@ -1005,7 +1211,7 @@ LIMIT ?5"#,
                     SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
                     FROM task_dependencies td
                     JOIN tasks dep ON dep.id = td.depends_on_id
-                    WHERE td.task_id = tasks.id AND dep.status != 'done'
+                    WHERE td.task_id = tasks.id AND dep.status != 'done' AND dep.deleted_at IS NULL
                 ),
                 has_in_progress_attempt = (
                     SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
@@ -1070,7 +1276,7 @@ LIMIT ?5"#,
                         SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
                         FROM task_dependencies td
                         JOIN tasks dep ON dep.id = td.depends_on_id
-                        WHERE td.task_id = tasks.id AND dep.status != 'done'
+                        WHERE td.task_id = tasks.id AND dep.status != 'done' AND dep.deleted_at IS NULL
                     ),
                     has_in_progress_attempt = (
                         SELECT CASE WHEN COUNT(*) > 0 THEN 1 ELSE 0 END
@@ -1189,9 +1395,16 @@ mod tests {
 
         // Test with wrong dimension (too short)
         let wrong_embedding: Vec<f32> = vec![0.0; 100];
-        let result =
-            Task::search_hybrid(&pool, project_id, &wrong_embedding, "test query", None, None, 10)
-                .await;
+        let result = Task::search_hybrid(
+            &pool,
+            project_id,
+            &wrong_embedding,
+            "test query",
+            None,
+            None,
+            10,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1199,9 +1412,16 @@ mod tests {
 
         // Test with wrong dimension (too long)
         let wrong_embedding: Vec<f32> = vec![0.0; 500];
-        let result =
-            Task::search_hybrid(&pool, project_id, &wrong_embedding, "test query", None, None, 10)
-                .await;
+        let result = Task::search_hybrid(
+            &pool,
+            project_id,
+            &wrong_embedding,
+            "test query",
+            None,
+            None,
+            10,
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -5,9 +5,10 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::task::{Task, TaskStatus};
+use super::task::{Task, TaskPriority, TaskStatus};
 
 #[derive(Debug, Error)]
 pub enum TaskDependencyError {
@@ -21,7 +22,7 @@ pub enum TaskDependencyError {
     CycleDetected,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskDependency {
     pub id: Uuid,
     pub task_id: Uuid,
@@ -76,6 +77,27 @@ impl TaskDependency {
         Ok(())
     }
 
+    /// Dependencies between the project's tasks
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"SELECT td.id as "id!: Uuid",
+                      td.task_id as "task_id!: Uuid",
+                      td.depends_on_id as "depends_on_id!: Uuid",
+                      td.created_at as "created_at!: DateTime<Utc>"
+               FROM task_dependencies td
+               JOIN tasks t ON t.id = td.task_id
+               WHERE t.project_id = $1 AND t.deleted_at IS NULL
+               ORDER BY td.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_blocked_by(
         pool: &SqlitePool,
         task_id: Uuid,
@@ -97,7 +119,10 @@ impl TaskDependency {
                       t.last_attempt_failed as "last_attempt_failed!: bool",
                       t.is_queued as "is_queued!: bool",
                       t.last_executor as "last_executor!: String",
-                      t.needs_attention as "needs_attention: bool"
+                      t.needs_attention as "needs_attention: bool",
+                      t.priority as "priority!: TaskPriority",
+                      t.created_by as "created_by: Uuid",
+                      t.archived_at as "archived_at: DateTime<Utc>"
                FROM task_dependencies td
                JOIN tasks t ON t.id = td.depends_on_id
                WHERE td.task_id = $1
@@ -126,7 +151,10 @@ impl TaskDependency {
                       t.last_attempt_failed as "last_attempt_failed!: bool",
                       t.is_queued as "is_queued!: bool",
                       t.last_executor as "last_executor!: String",
-                      t.needs_attention as "needs_attention: bool"
+                      t.needs_attention as "needs_attention: bool",
+                      t.priority as "priority!: TaskPriority",
+                      t.created_by as "created_by: Uuid",
+                      t.archived_at as "archived_at: DateTime<Utc>"
                FROM task_dependencies td
                JOIN tasks t ON t.id = td.task_id
                WHERE td.depends_on_id = $1
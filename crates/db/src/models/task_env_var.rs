@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Shown in place of a secret value
+pub const MASKED_VALUE: &str = "********";
+
+/// An environment variable set for every execution of a task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskEnvVar {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub name: String,
+    /// Masked when `secret` is set, except when loaded for an execution
+    pub value: String,
+    pub secret: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpsertTaskEnvVar {
+    pub value: String,
+    #[serde(default)]
+    pub secret: bool,
+}
+
+impl TaskEnvVar {
+    /// Names follow env var rules, the same as project secrets
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// The variable as it may be shown, with a secret value replaced
+    pub fn masked(mut self) -> Self {
+        if self.secret {
+            self.value = MASKED_VALUE.to_string();
+        }
+        self
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskEnvVar,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      name,
+                      value,
+                      secret as "secret!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_env_vars
+               WHERE task_id = $1
+               ORDER BY name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Unmasked values for an execution, keyed by name
+    pub async fn values_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<HashMap<String, String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT name, value FROM task_env_vars WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.name, row.value)).collect())
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        name: &str,
+        data: &UpsertTaskEnvVar,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskEnvVar,
+            r#"INSERT INTO task_env_vars (id, task_id, name, value, secret)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(task_id, name) DO UPDATE SET
+                   value = excluded.value,
+                   secret = excluded.secret,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         name,
+                         value,
+                         secret as "secret!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            name,
+            data.value,
+            data.secret
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, task_id: Uuid, name: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_env_vars WHERE task_id = $1 AND name = $2",
+            task_id,
+            name
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_only_secret_values() {
+        let var = TaskEnvVar {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            name: "API_TOKEN".to_string(),
+            value: "abc123".to_string(),
+            secret: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(var.clone().masked().value, MASKED_VALUE);
+        let plain = TaskEnvVar {
+            secret: false,
+            ..var
+        };
+        assert_eq!(plain.masked().value, "abc123");
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -19,7 +20,7 @@ pub enum MergeError {
     Database(#[from] sqlx::Error),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Default, ToSchema)]
 pub struct TaskStatusCounts {
     pub todo: i64,
     pub inprogress: i64,
@@ -28,7 +29,7 @@ pub struct TaskStatusCounts {
     pub cancelled: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskGroupWithStats {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -36,7 +37,7 @@ pub struct TaskGroupWithStats {
     pub task_counts: TaskStatusCounts,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskGroup {
     pub id: Uuid,
     pub project_id: Uuid,
@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What an external link points at
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type, Display, Default, ToSchema,
+)]
+#[sqlx(type_name = "task_link_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskLinkKind {
+    DesignDoc,
+    Ticket,
+    PullRequest,
+    #[default]
+    Other,
+}
+
+/// A design doc, ticket or pull request referenced by a task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskLink {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub url: String,
+    /// Title given by the user; takes precedence over `unfurled_title`
+    pub title: Option<String>,
+    pub kind: TaskLinkKind,
+    /// Whether the link is listed in the prompt of new attempts
+    pub include_in_prompt: bool,
+    /// Title fetched from GitHub, Linear or Figma
+    pub unfurled_title: Option<String>,
+    /// Status fetched from the linked service, e.g. `open`, `merged` or
+    /// `In Progress`
+    pub unfurled_status: Option<String>,
+    #[ts(type = "Date | null")]
+    pub unfurled_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateTaskLink {
+    pub url: String,
+    pub title: Option<String>,
+    /// Inferred from the URL when omitted
+    #[ts(optional)]
+    pub kind: Option<TaskLinkKind>,
+    #[serde(default = "default_include_in_prompt")]
+    #[ts(optional)]
+    pub include_in_prompt: bool,
+}
+
+fn default_include_in_prompt() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateTaskLink {
+    pub title: Option<String>,
+    pub kind: Option<TaskLinkKind>,
+    pub include_in_prompt: Option<bool>,
+}
+
+impl TaskLink {
+    /// The user's title, else the unfurled one, else the URL
+    pub fn display_title(&self) -> &str {
+        self.title
+            .as_deref()
+            .or(self.unfurled_title.as_deref())
+            .unwrap_or(&self.url)
+    }
+
+    /// The "Related links" section appended to a task's prompt, or None when
+    /// no link is included in prompts
+    pub fn prompt_section(links: &[TaskLink]) -> Option<String> {
+        let lines: Vec<String> = links
+            .iter()
+            .filter(|link| link.include_in_prompt)
+            .map(|link| {
+                let kind = match link.kind {
+                    TaskLinkKind::DesignDoc => "Design doc",
+                    TaskLinkKind::Ticket => "Ticket",
+                    TaskLinkKind::PullRequest => "Pull request",
+                    TaskLinkKind::Other => "Link",
+                };
+                let status = link
+                    .unfurled_status
+                    .as_deref()
+                    .map(|status| format!(" ({status})"))
+                    .unwrap_or_default();
+                if link.display_title() == link.url {
+                    format!("- {kind}{status}: {}", link.url)
+                } else {
+                    format!("- {kind}{status}: {} <{}>", link.display_title(), link.url)
+                }
+            })
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        Some(format!("Related links:\n{}", lines.join("\n")))
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      url,
+                      title,
+                      kind as "kind!: TaskLinkKind",
+                      include_in_prompt as "include_in_prompt!: bool",
+                      unfurled_title,
+                      unfurled_status,
+                      unfurled_at as "unfurled_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_links
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      url,
+                      title,
+                      kind as "kind!: TaskLinkKind",
+                      include_in_prompt as "include_in_prompt!: bool",
+                      unfurled_title,
+                      unfurled_status,
+                      unfurled_at as "unfurled_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_links
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        data: &CreateTaskLink,
+        kind: TaskLinkKind,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskLink,
+            r#"INSERT INTO task_links (id, task_id, url, title, kind, include_in_prompt)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         url,
+                         title,
+                         kind as "kind!: TaskLinkKind",
+                         include_in_prompt as "include_in_prompt!: bool",
+                         unfurled_title,
+                         unfurled_status,
+                         unfurled_at as "unfurled_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.url,
+            data.title,
+            kind,
+            data.include_in_prompt
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Fields left `None` are kept; an empty title clears it
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskLink,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let title = match &data.title {
+            Some(title) if title.trim().is_empty() => None,
+            Some(title) => Some(title.trim().to_string()),
+            None => existing.title,
+        };
+        let kind = data.kind.unwrap_or(existing.kind);
+        let include_in_prompt = data.include_in_prompt.unwrap_or(existing.include_in_prompt);
+
+        sqlx::query_as!(
+            TaskLink,
+            r#"UPDATE task_links
+               SET title = $2, kind = $3, include_in_prompt = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         url,
+                         title,
+                         kind as "kind!: TaskLinkKind",
+                         include_in_prompt as "include_in_prompt!: bool",
+                         unfurled_title,
+                         unfurled_status,
+                         unfurled_at as "unfurled_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            title,
+            kind,
+            include_in_prompt
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_unfurled(
+        pool: &SqlitePool,
+        id: Uuid,
+        unfurled_title: Option<&str>,
+        unfurled_status: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLink,
+            r#"UPDATE task_links
+               SET unfurled_title = $2, unfurled_status = $3,
+                   unfurled_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         url,
+                         title,
+                         kind as "kind!: TaskLinkKind",
+                         include_in_prompt as "include_in_prompt!: bool",
+                         unfurled_title,
+                         unfurled_status,
+                         unfurled_at as "unfurled_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            unfurled_title,
+            unfurled_status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_links WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,275 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A repository the scheduled task's workspace is opened on
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ScheduleRepo {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+/// A task created and started on a cron schedule
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskSchedule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Five-field cron expression in the server's local time, e.g.
+    /// `0 9 * * 1-5` for weekdays at 09:00
+    pub cron: String,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    #[ts(type = "ExecutorProfileId")]
+    #[schema(value_type = Object)]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    #[ts(type = "Array<ScheduleRepo>")]
+    #[schema(value_type = Vec<ScheduleRepo>)]
+    pub repos: Json<Vec<ScheduleRepo>>,
+    pub enabled: bool,
+    /// None while disabled
+    #[ts(type = "Date | null")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date | null")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Task created by the most recent run
+    pub last_task_id: Option<Uuid>,
+    /// Why the most recent run failed; None when it succeeded
+    pub last_error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateTaskSchedule {
+    pub cron: String,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    #[schema(value_type = Object)]
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<ScheduleRepo>,
+    #[serde(default = "default_enabled")]
+    #[ts(optional)]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Replaces a schedule's settings
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateTaskSchedule {
+    pub cron: String,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    #[schema(value_type = Object)]
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<ScheduleRepo>,
+    pub enabled: bool,
+}
+
+impl TaskSchedule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      cron,
+                      task_title,
+                      task_description,
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<ScheduleRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      last_task_id as "last_task_id: Uuid",
+                      last_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      cron,
+                      task_title,
+                      task_description,
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<ScheduleRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      last_task_id as "last_task_id: Uuid",
+                      last_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled schedules whose next run is at or before `now`
+    pub async fn find_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      cron,
+                      task_title,
+                      task_description,
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<ScheduleRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      last_task_id as "last_task_id: Uuid",
+                      last_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE enabled = 1 AND next_run_at IS NOT NULL AND next_run_at <= $1
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"INSERT INTO task_schedules (
+                    id, project_id, cron, task_title, task_description,
+                    executor_profile_id, repos, enabled, next_run_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         cron,
+                         task_title,
+                         task_description,
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         repos as "repos!: Json<Vec<ScheduleRepo>>",
+                         enabled as "enabled!: bool",
+                         next_run_at as "next_run_at: DateTime<Utc>",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         last_task_id as "last_task_id: Uuid",
+                         last_error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.cron,
+            data.task_title,
+            data.task_description,
+            executor_profile_id,
+            repos,
+            data.enabled,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"UPDATE task_schedules
+               SET cron = $2, task_title = $3, task_description = $4,
+                   executor_profile_id = $5, repos = $6, enabled = $7, next_run_at = $8,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         cron,
+                         task_title,
+                         task_description,
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         repos as "repos!: Json<Vec<ScheduleRepo>>",
+                         enabled as "enabled!: bool",
+                         next_run_at as "next_run_at: DateTime<Utc>",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         last_task_id as "last_task_id: Uuid",
+                         last_error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.cron,
+            data.task_title,
+            data.task_description,
+            executor_profile_id,
+            repos,
+            data.enabled,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record the outcome of a run and when the next one is due
+    pub async fn record_run(
+        pool: &SqlitePool,
+        id: Uuid,
+        last_task_id: Option<Uuid>,
+        last_error: Option<&str>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE task_schedules
+               SET last_run_at = $2, last_task_id = COALESCE($3, last_task_id),
+                   last_error = $4, next_run_at = $5
+               WHERE id = $1"#,
+            id,
+            now,
+            last_task_id,
+            last_error,
+            next_run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_schedules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
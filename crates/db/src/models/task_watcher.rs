@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::user::User;
+
+/// Someone following a task; watchers get notifications of their own when it
+/// changes status, an execution fails or its work is merged
+pub struct TaskWatcher;
+
+impl TaskWatcher {
+    /// Start watching `task_id`; watching it again is a no-op
+    pub async fn watch(pool: &SqlitePool, task_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO task_watchers (task_id, user_id)
+               VALUES ($1, $2)
+               ON CONFLICT (task_id, user_id) DO NOTHING"#,
+            task_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unwatch(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_watchers WHERE task_id = $1 AND user_id = $2",
+            task_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Users watching `task_id`, in the order they started watching
+    pub async fn find_users_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<User>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT u.id as "id!: Uuid",
+                      u.username,
+                      u.display_name,
+                      u.is_admin as "is_admin!: bool",
+                      u.created_at as "created_at!: DateTime<Utc>",
+                      u.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_watchers tw
+               JOIN users u ON u.id = tw.user_id
+               WHERE tw.task_id = $1
+               ORDER BY tw.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_user_ids_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT user_id as "user_id!: Uuid" FROM task_watchers WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::Task;
+
+/// Wrapped around matched terms by `snippet()`, then split out into parts
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+/// Tokens of context returned around a match
+const SNIPPET_TOKENS: i64 = 24;
+
+/// What a text search match was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, TS, ToSchema)]
+#[sqlx(type_name = "text_search_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TextSearchSource {
+    ConversationMessage,
+    ExecutionLog,
+}
+
+/// A run of snippet text; `highlighted` runs matched the query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+pub struct HighlightedText {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// A conversation message or execution log entry matching a text search
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TextSearchMatch {
+    pub source: TextSearchSource,
+    /// None for processes that belong to neither a task nor a conversation
+    pub project_id: Option<Uuid>,
+    pub executor: Option<String>,
+    /// Task title, or the conversation title for conversation matches
+    pub title: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub conversation_session_id: Option<Uuid>,
+    pub conversation_message_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    /// Position of the matching entry in the process's normalized log
+    pub entry_index: Option<i64>,
+    /// Matched content around the query terms
+    pub snippet: Vec<HighlightedText>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    /// BM25 relevance; higher is better
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TextSearchFilters {
+    pub project_id: Option<Uuid>,
+    pub executor: Option<String>,
+    /// Inclusive lower bound on when the message was sent or the process started
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound, like `from`
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(FromRow)]
+struct TextSearchRow {
+    source: TextSearchSource,
+    project_id: Option<Uuid>,
+    executor: Option<String>,
+    title: Option<String>,
+    task_id: Option<Uuid>,
+    workspace_id: Option<Uuid>,
+    conversation_session_id: Option<Uuid>,
+    conversation_message_id: Option<Uuid>,
+    execution_process_id: Option<Uuid>,
+    entry_index: Option<i64>,
+    snippet: String,
+    created_at: DateTime<Utc>,
+    score: f64,
+}
+
+/// Split a `snippet()` result into plain and highlighted runs
+fn highlight_parts(snippet: &str) -> Vec<HighlightedText> {
+    let mut parts = Vec::new();
+    let mut push = |text: &str, highlighted: bool| {
+        if !text.is_empty() {
+            parts.push(HighlightedText {
+                text: text.to_string(),
+                highlighted,
+            });
+        }
+    };
+    let mut segments = snippet.split(MATCH_START);
+    if let Some(leading) = segments.next() {
+        push(leading, false);
+    }
+    for segment in segments {
+        let (matched, rest) = segment.split_once(MATCH_END).unwrap_or((segment, ""));
+        push(matched, true);
+        push(rest, false);
+    }
+    parts
+}
+
+impl TextSearchMatch {
+    /// Search conversation messages and execution log entries with FTS5,
+    /// best BM25 matches first. Trashed tasks and projects are left out.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        filters: &TextSearchFilters,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let trimmed_query = query.trim();
+        if trimmed_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<TextSearchRow> = sqlx::query_as(
+            r#"SELECT
+  CASE WHEN d.conversation_message_id IS NULL THEN 'execution_log'
+       ELSE 'conversation_message' END AS source,
+  COALESCE(t.project_id, cs.project_id) AS project_id,
+  COALESCE(s.executor, cs.executor) AS executor,
+  COALESCE(t.title, cs.title) AS title,
+  t.id AS task_id,
+  w.id AS workspace_id,
+  cs.id AS conversation_session_id,
+  d.conversation_message_id,
+  d.execution_process_id,
+  d.entry_index,
+  snippet(text_search_fts, 0, char(2), char(3), '…', ?7) AS snippet,
+  COALESCE(cm.created_at, ep.started_at) AS created_at,
+  -bm25(text_search_fts) AS score
+FROM text_search_fts
+JOIN text_search_documents d ON d.id = text_search_fts.rowid
+LEFT JOIN conversation_messages cm ON cm.id = d.conversation_message_id
+LEFT JOIN execution_processes ep ON ep.id = d.execution_process_id
+LEFT JOIN sessions s ON s.id = ep.session_id
+LEFT JOIN workspaces w ON w.id = s.workspace_id
+LEFT JOIN tasks t ON t.id = w.task_id
+LEFT JOIN conversation_sessions cs
+  ON cs.id = COALESCE(cm.conversation_session_id, ep.conversation_session_id)
+LEFT JOIN projects p ON p.id = COALESCE(t.project_id, cs.project_id)
+WHERE text_search_fts MATCH ?1
+  AND t.deleted_at IS NULL
+  AND p.deleted_at IS NULL
+  AND (?2 IS NULL OR COALESCE(t.project_id, cs.project_id) = ?2)
+  AND (?3 IS NULL OR COALESCE(s.executor, cs.executor) = ?3)
+  AND (?4 IS NULL OR julianday(COALESCE(cm.created_at, ep.started_at)) >= julianday(?4))
+  AND (?5 IS NULL OR julianday(COALESCE(cm.created_at, ep.started_at)) < julianday(?5))
+ORDER BY score DESC
+LIMIT ?6"#,
+        )
+        .bind(Task::escape_fts5_query(trimmed_query))
+        .bind(filters.project_id)
+        .bind(filters.executor.as_deref())
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(limit)
+        .bind(SNIPPET_TOKENS)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TextSearchMatch {
+                source: row.source,
+                project_id: row.project_id,
+                executor: row.executor,
+                title: row.title,
+                task_id: row.task_id,
+                workspace_id: row.workspace_id,
+                conversation_session_id: row.conversation_session_id,
+                conversation_message_id: row.conversation_message_id,
+                execution_process_id: row.execution_process_id,
+                entry_index: row.entry_index,
+                snippet: highlight_parts(&row.snippet),
+                created_at: row.created_at,
+                score: row.score,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(text: &str, highlighted: bool) -> HighlightedText {
+        HighlightedText {
+            text: text.to_string(),
+            highlighted,
+        }
+    }
+
+    #[test]
+    fn splits_snippet_into_highlighted_parts() {
+        assert_eq!(
+            highlight_parts("…calls \u{2}parse_entry\u{3} then \u{2}upgrade\u{3}"),
+            vec![
+                part("…calls ", false),
+                part("parse_entry", true),
+                part(" then ", false),
+                part("upgrade", true),
+            ]
+        );
+        assert_eq!(highlight_parts("no match"), vec![part("no match", false)]);
+        assert!(highlight_parts("").is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::execution_process::ExecutionProcessRunReason;
+
+/// Wall-clock span of one execution in a task's workspaces. Dev servers are
+/// left out: they run until stopped, whether or not anyone is working.
+#[derive(Debug, Clone)]
+pub struct ExecutionSpan {
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub run_reason: ExecutionProcessRunReason,
+    pub started_at: DateTime<Utc>,
+    /// None while the execution is still running
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ExecutionSpan {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionSpan,
+            r#"SELECT
+                t.id AS "task_id!: Uuid",
+                t.title AS "task_title!: String",
+                ep.run_reason AS "run_reason!: ExecutionProcessRunReason",
+                ep.started_at AS "started_at!: DateTime<Utc>",
+                ep.completed_at AS "completed_at: DateTime<Utc>"
+            FROM execution_processes ep
+            JOIN sessions s ON s.id = ep.session_id
+            JOIN workspaces w ON w.id = s.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.id = $1
+              AND ep.run_reason != 'devserver'
+            ORDER BY ep.started_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Spans of executions in a project started between `from` and `to`
+    /// (inclusive `YYYY-MM-DD` days, in UTC)
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionSpan,
+            r#"SELECT
+                t.id AS "task_id!: Uuid",
+                t.title AS "task_title!: String",
+                ep.run_reason AS "run_reason!: ExecutionProcessRunReason",
+                ep.started_at AS "started_at!: DateTime<Utc>",
+                ep.completed_at AS "completed_at: DateTime<Utc>"
+            FROM execution_processes ep
+            JOIN sessions s ON s.id = ep.session_id
+            JOIN workspaces w ON w.id = s.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            WHERE t.project_id = $1
+              AND ep.run_reason != 'devserver'
+              AND date(ep.started_at) BETWEEN $2 AND $3
+            ORDER BY ep.started_at ASC"#,
+            project_id,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,59 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Token usage of executions started on one day, for one executor profile and
+/// project. Only executions that reported usage are counted.
+#[derive(Debug, Clone)]
+pub struct TokenUsageRow {
+    /// Executor as stored in the execution's action, e.g. `CLAUDE_CODE`
+    pub executor: String,
+    pub variant: Option<String>,
+    /// None for executions outside any task, such as conversations
+    pub project_id: Option<Uuid>,
+    pub project_name: Option<String>,
+    /// `YYYY-MM-DD`, in UTC
+    pub day: String,
+    pub executions: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
+impl TokenUsageRow {
+    /// Usage of executions started between `from` and `to` (inclusive
+    /// `YYYY-MM-DD` days), optionally limited to one project
+    pub async fn find_in_range(
+        pool: &SqlitePool,
+        from: &str,
+        to: &str,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TokenUsageRow,
+            r#"SELECT
+                json_extract(ep.executor_action, '$.typ.executor_profile_id.executor') AS "executor!: String",
+                json_extract(ep.executor_action, '$.typ.executor_profile_id.variant') AS "variant: String",
+                p.id AS "project_id: Uuid",
+                p.name AS "project_name: String",
+                date(ep.started_at) AS "day!: String",
+                COUNT(*) AS "executions!: i64",
+                SUM(COALESCE(ep.input_tokens, 0)) AS "input_tokens!: i64",
+                SUM(COALESCE(ep.output_tokens, 0)) AS "output_tokens!: i64"
+            FROM execution_processes ep
+            LEFT JOIN sessions s ON s.id = ep.session_id
+            LEFT JOIN workspaces w ON w.id = s.workspace_id
+            LEFT JOIN tasks t ON t.id = w.task_id
+            LEFT JOIN projects p ON p.id = t.project_id
+            WHERE (ep.input_tokens IS NOT NULL OR ep.output_tokens IS NOT NULL)
+              AND json_extract(ep.executor_action, '$.typ.executor_profile_id.executor') IS NOT NULL
+              AND date(ep.started_at) BETWEEN $1 AND $2
+              AND ($3 IS NULL OR p.id = $3)
+            GROUP BY 1, 2, 3, 5
+            ORDER BY 5 ASC"#,
+            from,
+            to,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashItemKind {
+    Project,
+    Task,
+}
+
+/// A deleted task or project that can still be restored. Tasks of a trashed
+/// project are not listed on their own; they come back with the project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TrashItem {
+    pub kind: TrashItemKind,
+    pub id: Uuid,
+    /// Project name or task title
+    pub name: String,
+    /// The task's project; None for projects
+    pub project_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashItem {
+    /// Everything in the trash, most recently deleted first
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        let mut items = Self::find_deleted_before(pool, "+0 days").await?;
+        items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(items)
+    }
+
+    /// Items deleted more than `retention_days` days ago
+    pub async fn find_expired(
+        pool: &SqlitePool,
+        retention_days: u32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        Self::find_deleted_before(pool, &format!("-{retention_days} days")).await
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        kind: TrashItemKind,
+        id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        Ok(Self::find_all(pool)
+            .await?
+            .into_iter()
+            .find(|item| item.kind == kind && item.id == id))
+    }
+
+    /// Items whose `deleted_at` is before now shifted by `modifier`, an
+    /// SQLite date modifier such as `-30 days`
+    async fn find_deleted_before(
+        pool: &SqlitePool,
+        modifier: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let projects = sqlx::query!(
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      deleted_at as "deleted_at!: DateTime<Utc>"
+               FROM projects
+               WHERE deleted_at IS NOT NULL
+                 AND deleted_at <= datetime('now', 'subsec', $1)"#,
+            modifier
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let tasks = sqlx::query!(
+            r#"SELECT t.id as "id!: Uuid",
+                      t.title,
+                      t.project_id as "project_id!: Uuid",
+                      t.deleted_at as "deleted_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.deleted_at IS NOT NULL
+                 AND p.deleted_at IS NULL
+                 AND t.deleted_at <= datetime('now', 'subsec', $1)"#,
+            modifier
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(projects
+            .into_iter()
+            .map(|row| TrashItem {
+                kind: TrashItemKind::Project,
+                id: row.id,
+                name: row.name,
+                project_id: None,
+                deleted_at: row.deleted_at,
+            })
+            .chain(tasks.into_iter().map(|row| TrashItem {
+                kind: TrashItemKind::Task,
+                id: row.id,
+                name: row.title,
+                project_id: Some(row.project_id),
+                deleted_at: row.deleted_at,
+            }))
+            .collect())
+    }
+}
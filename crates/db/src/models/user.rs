@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A person signing in to a shared server. The password hash and the
+/// preferences JSON are only read through dedicated queries.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    /// Admins create and delete accounts
+    pub is_admin: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateUser {
+    pub username: String,
+    pub display_name: Option<String>,
+    pub password: String,
+    #[serde(default)]
+    pub is_admin: bool,
+    /// Token printed on the server's stderr at startup, required to create
+    /// the first account
+    #[serde(default)]
+    pub setup_token: Option<String>,
+}
+
+impl User {
+    /// Whether any account exists, i.e. whether the server requires sign-in
+    pub async fn any_exist(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+        let exists =
+            sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM users) as "exists!: bool""#)
+                .fetch_one(pool)
+                .await?;
+        Ok(exists)
+    }
+
+    pub async fn count_admins(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM users WHERE is_admin = TRUE"#)
+            .fetch_one(pool)
+            .await
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      is_admin as "is_admin!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               ORDER BY username ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      is_admin as "is_admin!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The user named `username` (case-insensitive) with their password hash
+    pub async fn find_with_password_hash(
+        pool: &SqlitePool,
+        username: &str,
+    ) -> Result<Option<(Self, String)>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      is_admin as "is_admin!: bool",
+                      password_hash,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE username = $1 COLLATE NOCASE"#,
+            username
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| {
+            (
+                User {
+                    id: row.id,
+                    username: row.username,
+                    display_name: row.display_name,
+                    is_admin: row.is_admin,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                },
+                row.password_hash,
+            )
+        }))
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        username: &str,
+        display_name: Option<&str>,
+        password_hash: &str,
+        is_admin: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, username, display_name, password_hash, is_admin)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         username,
+                         display_name,
+                         is_admin as "is_admin!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            username,
+            display_name,
+            password_hash,
+            is_admin
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Raw JSON of the user's config overlay
+    pub async fn preferences(pool: &SqlitePool, id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!("SELECT preferences FROM users WHERE id = $1", id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn set_preferences(
+        pool: &SqlitePool,
+        id: Uuid,
+        preferences: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE users SET preferences = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            preferences
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// A sign-in session, looked up by the hash of its cookie token
+pub struct UserSession;
+
+impl UserSession {
+    pub async fn create(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO user_sessions (id, user_id, token_hash, expires_at) VALUES ($1, $2, $3, $4)",
+            id,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The user signed in with the session `token_hash`, unless it expired
+    pub async fn find_user(
+        pool: &SqlitePool,
+        token_hash: &str,
+    ) -> Result<Option<User>, sqlx::Error> {
+        // Compared as bound timestamps so both sides share one text format
+        let now = Utc::now();
+        sqlx::query_as!(
+            User,
+            r#"SELECT u.id as "id!: Uuid",
+                      u.username,
+                      u.display_name,
+                      u.is_admin as "is_admin!: bool",
+                      u.created_at as "created_at!: DateTime<Utc>",
+                      u.updated_at as "updated_at!: DateTime<Utc>"
+               FROM user_sessions s
+               JOIN users u ON u.id = s.user_id
+               WHERE s.token_hash = $1 AND s.expires_at > $2"#,
+            token_hash,
+            now
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, token_hash: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM user_sessions WHERE token_hash = $1",
+            token_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!("DELETE FROM user_sessions WHERE expires_at <= $1", now)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,293 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Domain events a webhook can subscribe to
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display, EnumString, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookEvent {
+    TaskStatusChanged,
+    ExecutionCompleted,
+    WorkspaceCreated,
+}
+
+/// An outgoing webhook. The signing secret is kept in the secret store and
+/// never serialized; API responses only say whether one is set.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Webhook {
+    pub id: Uuid,
+    /// Only deliver events from this project; `None` delivers from every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub url: String,
+    /// Secret store entry holding the signing secret
+    #[serde(skip)]
+    #[ts(skip)]
+    pub secret_ref: Option<String>,
+    pub has_secret: bool,
+    #[ts(type = "Array<WebhookEvent>")]
+    #[schema(value_type = Vec<WebhookEvent>)]
+    pub events: Json<Vec<WebhookEvent>>,
+    /// JSON body with `{{path}}` placeholders into the default payload, e.g.
+    /// `{"text": "{{data.task.title}} is now {{data.task.status}}"}`
+    pub payload_template: Option<String>,
+    pub enabled: bool,
+    #[ts(type = "Date | null")]
+    pub last_delivery_at: Option<DateTime<Utc>>,
+    /// Error from the most recent delivery; `None` when it succeeded
+    pub last_delivery_error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct CreateWebhook {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+    pub payload_template: Option<String>,
+    #[serde(default = "default_enabled")]
+    #[ts(optional)]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Replaces a webhook's settings. `secret` is kept when `None` and cleared
+/// when empty.
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct UpdateWebhook {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+    pub payload_template: Option<String>,
+    pub enabled: bool,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      url,
+                      secret_ref,
+                      secret_ref IS NOT NULL as "has_secret!: bool",
+                      events as "events!: Json<Vec<WebhookEvent>>",
+                      payload_template,
+                      enabled as "enabled!: bool",
+                      last_delivery_at as "last_delivery_at: DateTime<Utc>",
+                      last_delivery_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      url,
+                      secret_ref,
+                      secret_ref IS NOT NULL as "has_secret!: bool",
+                      events as "events!: Json<Vec<WebhookEvent>>",
+                      payload_template,
+                      enabled as "enabled!: bool",
+                      last_delivery_at as "last_delivery_at: DateTime<Utc>",
+                      last_delivery_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled webhooks that receive events from `project_id`
+    pub async fn find_enabled_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      url,
+                      secret_ref,
+                      secret_ref IS NOT NULL as "has_secret!: bool",
+                      events as "events!: Json<Vec<WebhookEvent>>",
+                      payload_template,
+                      enabled as "enabled!: bool",
+                      last_delivery_at as "last_delivery_at: DateTime<Utc>",
+                      last_delivery_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE enabled = 1 AND (project_id IS NULL OR project_id = $1)"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create a webhook whose signing secret, if any, is in the store entry
+    /// `secret_ref`
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateWebhook,
+        secret_ref: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let events = Json(&data.events);
+        sqlx::query!(
+            r#"INSERT INTO webhooks (id, project_id, name, url, secret_ref, events, payload_template, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+            id,
+            data.project_id,
+            data.name,
+            data.url,
+            secret_ref,
+            events,
+            data.payload_template,
+            data.enabled
+        )
+        .execute(pool)
+        .await?;
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace a webhook's settings; `secret_ref` replaces its secret store
+    /// entry, `None` leaving it unsigned
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateWebhook,
+        secret_ref: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let events = Json(&data.events);
+        let result = sqlx::query!(
+            r#"UPDATE webhooks
+               SET project_id = $2, name = $3, url = $4, secret_ref = $5, events = $6,
+                   payload_template = $7, enabled = $8, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            data.project_id,
+            data.name,
+            data.url,
+            secret_ref,
+            events,
+            data.payload_template,
+            data.enabled
+        )
+        .execute(pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Secrets saved in the database by older versions, by webhook ID
+    pub async fn find_plaintext_secrets(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", secret as "secret!"
+               FROM webhooks
+               WHERE secret IS NOT NULL"#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|row| (row.id, row.secret)).collect())
+    }
+
+    /// Point the webhook at the store entry now holding its secret, dropping
+    /// the value saved in the database
+    pub async fn set_secret_ref(
+        pool: &SqlitePool,
+        id: Uuid,
+        secret_ref: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE webhooks SET secret_ref = $2, secret = NULL WHERE id = $1",
+            id,
+            secret_ref
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn secret_ref_exists(
+        pool: &SqlitePool,
+        secret_ref: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM webhooks WHERE secret_ref = $1"#,
+            secret_ref
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// Record the outcome of the latest delivery attempt
+    pub async fn record_delivery(
+        pool: &SqlitePool,
+        id: Uuid,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE webhooks
+               SET last_delivery_at = datetime('now', 'subsec'), last_delivery_error = $2
+               WHERE id = $1"#,
+            id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM webhooks WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
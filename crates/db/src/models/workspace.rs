@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{
@@ -45,7 +46,7 @@ pub enum WorkspaceStatus {
     ExecutorFailed,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Workspace {
     pub id: Uuid,
     pub task_id: Uuid,
@@ -58,7 +59,7 @@ pub struct Workspace {
 }
 
 /// Workspace with its latest session (if any) and queue info
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct WorkspaceWithSession {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -79,7 +80,7 @@ pub struct CreatePrParams<'a> {
     pub base_branch: Option<&'a str>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
 }
@@ -91,7 +92,7 @@ pub struct AttemptResumeContext {
     pub cumulative_diffs: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct WorkspaceContext {
     pub workspace: Workspace,
     pub task: Task,
@@ -337,6 +338,17 @@ impl Workspace {
         Ok(result.exists)
     }
 
+    pub async fn branch_exists(pool: &SqlitePool, branch: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT EXISTS(SELECT 1 FROM workspaces WHERE branch = ?) as "exists!: bool""#,
+            branch
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result.exists)
+    }
+
     /// Find workspaces that are expired (72+ hours since last activity) and eligible for cleanup
     pub async fn find_expired_for_cleanup(
         pool: &SqlitePool,
@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, QueryBuilder, SqlitePool};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::repo::Repo;
@@ -26,7 +27,7 @@ pub struct CreateWorkspaceRepo {
     pub target_branch: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct RepoWithTargetBranch {
     #[serde(flatten)]
@@ -41,6 +42,16 @@ pub struct RepoWithCopyFiles {
     pub path: PathBuf,
     pub name: String,
     pub copy_files: Option<String>,
+    pub post_copy_script: Option<String>,
+}
+
+/// A workspace whose target branch in a repo is another task's branch
+#[derive(Debug, Clone)]
+pub struct StackedWorkspace {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub branch: String,
+    pub container_ref: Option<String>,
 }
 
 impl WorkspaceRepo {
@@ -98,6 +109,10 @@ impl WorkspaceRepo {
                       r.path,
                       r.name,
                       r.display_name,
+                      r.setup_script,
+                      r.cleanup_script,
+                      r.skip_submodules as "skip_submodules!: bool",
+                      r.skip_lfs as "skip_lfs!: bool",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -119,6 +134,10 @@ impl WorkspaceRepo {
                       r.path,
                       r.name,
                       r.display_name,
+                      r.setup_script,
+                      r.cleanup_script,
+                      r.skip_submodules as "skip_submodules!: bool",
+                      r.skip_lfs as "skip_lfs!: bool",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -139,6 +158,10 @@ impl WorkspaceRepo {
                     path: PathBuf::from(row.path),
                     name: row.name,
                     display_name: row.display_name,
+                    setup_script: row.setup_script,
+                    cleanup_script: row.cleanup_script,
+                    skip_submodules: row.skip_submodules,
+                    skip_lfs: row.skip_lfs,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -186,9 +209,11 @@ impl WorkspaceRepo {
         Ok(())
     }
 
-    pub async fn update_target_branch_for_children_of_workspace(
+    /// Retarget every workspace stacked on `old_branch` in the workspace's
+    /// repos after the branch was renamed
+    pub async fn update_target_branch_for_stacked_on_workspace(
         pool: &SqlitePool,
-        parent_workspace_id: Uuid,
+        workspace_id: Uuid,
         old_branch: &str,
         new_branch: &str,
     ) -> Result<u64, sqlx::Error> {
@@ -196,20 +221,67 @@ impl WorkspaceRepo {
             r#"UPDATE workspace_repos
                SET target_branch = $1, updated_at = datetime('now')
                WHERE target_branch = $2
-                 AND workspace_id IN (
-                     SELECT w.id FROM workspaces w
-                     JOIN tasks t ON w.task_id = t.id
-                     WHERE t.parent_workspace_id = $3
+                 AND workspace_id != $3
+                 AND repo_id IN (
+                     SELECT repo_id FROM workspace_repos WHERE workspace_id = $3
                  )"#,
             new_branch,
             old_branch,
-            parent_workspace_id
+            workspace_id
         )
         .execute(pool)
         .await?;
         Ok(result.rows_affected())
     }
 
+    /// Workspaces of unfinished tasks targeting `branch` in a repo, oldest
+    /// first
+    pub async fn find_stacked_on(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        branch: &str,
+    ) -> Result<Vec<StackedWorkspace>, sqlx::Error> {
+        sqlx::query_as!(
+            StackedWorkspace,
+            r#"SELECT w.id as "workspace_id!: Uuid",
+                      w.task_id as "task_id!: Uuid",
+                      w.branch,
+                      w.container_ref
+               FROM workspace_repos wr
+               JOIN workspaces w ON w.id = wr.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE wr.repo_id = $1
+                 AND wr.target_branch = $2
+                 AND w.branch != $2
+                 AND t.status NOT IN ('done', 'cancelled')
+               ORDER BY w.created_at ASC"#,
+            repo_id,
+            branch
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The workspace whose branch is `branch` in a repo, i.e. the one a
+    /// workspace targeting `branch` is stacked on
+    pub async fn find_stack_parent(
+        pool: &SqlitePool,
+        repo_id: Uuid,
+        branch: &str,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT w.id as "id!: Uuid"
+               FROM workspaces w
+               JOIN workspace_repos wr ON wr.workspace_id = w.id
+               WHERE wr.repo_id = $1 AND w.branch = $2
+               LIMIT 1"#,
+            repo_id,
+            branch
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_unique_repos_for_task(
         pool: &SqlitePool,
         task_id: Uuid,
@@ -220,6 +292,10 @@ impl WorkspaceRepo {
                       r.path,
                       r.name,
                       r.display_name,
+                      r.setup_script,
+                      r.cleanup_script,
+                      r.skip_submodules as "skip_submodules!: bool",
+                      r.skip_lfs as "skip_lfs!: bool",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -240,7 +316,7 @@ impl WorkspaceRepo {
         workspace_id: Uuid,
     ) -> Result<Vec<RepoWithCopyFiles>, sqlx::Error> {
         let rows = sqlx::query!(
-            r#"SELECT r.id as "id!: Uuid", r.path, r.name, pr.copy_files
+            r#"SELECT r.id as "id!: Uuid", r.path, r.name, pr.copy_files, pr.post_copy_script
                FROM repos r
                JOIN workspace_repos wr ON r.id = wr.repo_id
                JOIN workspaces w ON w.id = wr.workspace_id
@@ -259,6 +335,7 @@ impl WorkspaceRepo {
                 path: PathBuf::from(row.path),
                 name: row.name,
                 copy_files: row.copy_files,
+                post_copy_script: row.post_copy_script,
             })
             .collect())
     }
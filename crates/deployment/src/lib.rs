@@ -20,6 +20,7 @@ use services::services::{
     approvals::Approvals,
     auth::AuthContext,
     backup_service::BackupService,
+    branch_janitor::BranchJanitor,
     config::{Config, ConfigError},
     container::{ContainerError, ContainerService},
     domain_events::HookExecutionStore,
@@ -34,13 +35,17 @@ use services::services::{
     image::{ImageError, ImageService},
     merge_queue_store::MergeQueueStore,
     operation_status::OperationStatusStore,
+    operations::OperationTracker,
     pr_cache::PrCache,
     pr_monitor::PrMonitorService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    repo_metadata_cache::RepoMetadataCache,
+    secrets::SecretService,
     share::SharePublisher,
     skills_cache::GlobalSkillsCache,
+    trash::TrashService,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -130,6 +135,10 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn operation_status(&self) -> &OperationStatusStore;
 
+    fn operations(&self) -> &OperationTracker;
+
+    fn secrets(&self) -> &SecretService;
+
     fn merge_queue_store(&self) -> &MergeQueueStore;
 
     fn hook_execution_store(&self) -> &HookExecutionStore;
@@ -138,6 +147,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn pr_cache(&self) -> &Arc<PrCache>;
 
+    fn repo_metadata_cache(&self) -> &Arc<RepoMetadataCache>;
+
     fn server_log_store(&self) -> &Arc<ServerLogStore>;
 
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
@@ -152,6 +163,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     async fn spawn_pr_monitor_service(&self) -> tokio::task::JoinHandle<()> {
         let db = self.db().clone();
+        let config = self.config().clone();
+        let secrets = self.secrets().clone();
         let analytics = self
             .analytics()
             .as_ref()
@@ -162,16 +175,30 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         let publisher = self.share_publisher().ok();
         // Note: Event dispatcher is passed as None here. Override this method in
         // LocalDeployment to provide an event dispatcher for TaskStatusChanged events.
-        PrMonitorService::spawn(db, analytics, publisher, None).await
+        PrMonitorService::spawn(db, config, secrets, analytics, publisher, None).await
     }
 
     fn spawn_embedding_worker(&self) -> tokio::task::JoinHandle<()> {
         EmbeddingWorker::spawn(self.embedding().clone(), self.db().clone())
     }
 
+    fn spawn_branch_janitor(&self) -> tokio::task::JoinHandle<()> {
+        BranchJanitor::spawn(self.db().clone(), self.git().clone())
+    }
+
+    fn spawn_trash_purge(&self) -> tokio::task::JoinHandle<()> {
+        TrashService::spawn(self.db().clone(), self.config().clone())
+    }
+
     async fn spawn_backup_service(&self) -> tokio::task::JoinHandle<()> {
         let config = self.config().clone();
-        BackupService::spawn(config).await
+        BackupService::spawn(
+            config,
+            self.db().pool.clone(),
+            self.operations().clone(),
+            self.secrets().clone(),
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -63,6 +63,19 @@ impl ExecutorAction {
             ExecutorActionType::ScriptRequest(_) => None,
         }
     }
+
+    /// Working directory relative to the workspace, if the action sets one
+    pub fn working_dir(&self) -> Option<&str> {
+        match self.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                request.working_dir.as_deref()
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                request.working_dir.as_deref()
+            }
+            ExecutorActionType::ScriptRequest(request) => request.working_dir.as_deref(),
+        }
+    }
 }
 
 #[async_trait]
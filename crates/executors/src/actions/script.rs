@@ -3,6 +3,8 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use strum_macros::{Display, EnumString};
 use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::shell::get_shell_command;
@@ -10,6 +12,7 @@ use workspace_utils::shell::get_shell_command;
 use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
+    command::CommandParts,
     env::ExecutionEnv,
     executors::{ExecutorError, SpawnedChild},
 };
@@ -19,6 +22,47 @@ pub enum ScriptRequestLanguage {
     Bash,
 }
 
+/// Shell used to run project scripts. `None` on a project means the platform
+/// default (`cmd /C` on Windows, `$SHELL -c` elsewhere).
+///
+/// Bash runs as a login shell and zsh as an interactive one, so version
+/// managers set up in profile or rc files (nvm, pyenv, asdf) are on PATH.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type, Display, EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum ScriptShell {
+    Bash,
+    Zsh,
+    Sh,
+    #[serde(rename = "powershell")]
+    #[strum(serialize = "powershell")]
+    #[sqlx(rename = "powershell")]
+    PowerShell,
+    Cmd,
+}
+
+impl ScriptShell {
+    /// Program and arguments that precede the script text
+    pub fn command(&self) -> (String, Vec<&'static str>) {
+        match self {
+            ScriptShell::Bash => ("bash".into(), vec!["-lc"]),
+            ScriptShell::Zsh => ("zsh".into(), vec!["-ic"]),
+            ScriptShell::Sh => ("sh".into(), vec!["-c"]),
+            ScriptShell::PowerShell => {
+                let program = if cfg!(windows) { "powershell" } else { "pwsh" };
+                (
+                    program.into(),
+                    vec!["-NoLogo", "-NonInteractive", "-Command"],
+                )
+            }
+            ScriptShell::Cmd => ("cmd".into(), vec!["/C"]),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub enum ScriptContext {
     SetupScript,
@@ -52,15 +96,26 @@ impl Executable for ScriptRequest {
             None => current_dir.to_path_buf(),
         };
 
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let (shell_cmd, shell_args) = match env.shell {
+            Some(shell) => shell.command(),
+            None => {
+                let (shell_cmd, shell_arg) = get_shell_command();
+                (shell_cmd, vec![shell_arg])
+            }
+        };
+        let mut shell_args: Vec<String> = shell_args.into_iter().map(String::from).collect();
+        shell_args.push(self.script.clone());
+        let (program, args) = env
+            .wrap(CommandParts::new(shell_cmd, shell_args))
+            .into_parts();
+
+        let mut command = Command::new(program);
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
-            .arg(shell_arg)
-            .arg(&self.script)
+            .args(args)
             .current_dir(&effective_dir);
 
         // Apply environment variables
@@ -53,6 +53,11 @@ pub trait ExecutorApprovalService: Send + Sync {
     /// Unregister the protocol peer when execution completes.
     /// The default implementation does nothing.
     async fn unregister_protocol_peer(&self) {}
+
+    /// Mark the task as needing the user's attention without pausing the
+    /// agent, e.g. for a warning raised while approvals are off.
+    /// The default implementation does nothing.
+    async fn flag_attention(&self, _reason: String) {}
 }
 
 #[derive(Debug, Default)]
@@ -29,6 +29,22 @@ impl CommandParts {
         Self { program, args }
     }
 
+    /// Run this command through `prefix`, e.g. `nix develop --command <program> <args>`.
+    /// The original program is then resolved by the wrapper rather than on our PATH.
+    pub fn with_prefix(self, prefix: &[String]) -> Self {
+        let Some((program, prefix_args)) = prefix.split_first() else {
+            return self;
+        };
+        let mut args = prefix_args.to_vec();
+        args.push(self.program);
+        args.extend(self.args);
+        Self::new(program.clone(), args)
+    }
+
+    pub fn into_parts(self) -> (String, Vec<String>) {
+        (self.program, self.args)
+    }
+
     pub async fn into_resolved(self) -> Result<(PathBuf, Vec<String>), ExecutorError> {
         let CommandParts { program, args } = self;
         let executable = resolve_executable_path(&program)
@@ -129,7 +145,7 @@ impl CommandBuilder {
     }
 }
 
-fn split_command_line(input: &str) -> Result<Vec<String>, CommandBuildError> {
+pub(crate) fn split_command_line(input: &str) -> Result<Vec<String>, CommandBuildError> {
     #[cfg(windows)]
     {
         let parts = winsplit::split(input);
@@ -177,4 +193,17 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn with_prefix_wraps_program() {
+        let parts = CommandParts::new("npx".to_string(), vec!["-y".to_string()]);
+        let prefix = ["nix", "develop", "--command"].map(String::from);
+
+        let (program, args) = parts.with_prefix(&prefix).into_parts();
+        assert_eq!(program, "nix");
+        assert_eq!(args, vec!["develop", "--command", "npx", "-y"]);
+
+        let parts = CommandParts::new("npx".to_string(), vec![]);
+        assert_eq!(parts.with_prefix(&[]).into_parts().0, "npx");
+    }
 }
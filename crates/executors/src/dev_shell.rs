@@ -0,0 +1,138 @@
+//! Running executions inside a project's Nix or devenv shell, so agents and
+//! scripts see the toolchain pinned by the project rather than the host's.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+
+use crate::command::split_command_line;
+
+/// Development shell every execution for a project is wrapped in.
+/// `None` on a project runs commands directly.
+///
+/// `Auto` runs coding agents in the workspace's devcontainer when a
+/// devcontainer.json is found and Docker is available, and otherwise uses
+/// `devenv shell` when devenv.nix sits in the working directory and
+/// `nix develop` when a flake.nix is found; `Custom` only uses the
+/// project's configured command prefix. `Devcontainer` runs coding agents in
+/// a container built from the workspace's devcontainer.json, and `Docker`
+/// runs every execution in a container of the project's image; the container
+/// service sets both up since they need Docker.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Type, Display, EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum DevShell {
+    Auto,
+    Nix,
+    Devenv,
+    Custom,
+    Devcontainer,
+    Docker,
+}
+
+const FLAKE_FILE: &str = "flake.nix";
+const DEVENV_FILE: &str = "devenv.nix";
+
+fn find_marker<'a>(dirs: &'a [PathBuf], marker: &str) -> Option<&'a Path> {
+    dirs.iter()
+        .map(PathBuf::as_path)
+        .find(|dir| dir.join(marker).is_file())
+}
+
+impl DevShell {
+    /// Command prefix for an execution whose working directory is `dirs[0]`;
+    /// the remaining entries are repo worktrees searched for a flake.
+    ///
+    /// A non-empty `command` replaces the default prefix. Returns `None` when
+    /// nothing should wrap the execution, e.g. `Auto` without a flake.
+    pub fn resolve_prefix(&self, command: Option<&str>, dirs: &[PathBuf]) -> Option<Vec<String>> {
+        if matches!(self, DevShell::Devcontainer | DevShell::Docker) {
+            return None;
+        }
+
+        let mode = match self {
+            DevShell::Auto if dirs.first()?.join(DEVENV_FILE).is_file() => DevShell::Devenv,
+            DevShell::Auto => {
+                find_marker(dirs, FLAKE_FILE)?;
+                DevShell::Nix
+            }
+            other => *other,
+        };
+
+        if let Some(command) = command.map(str::trim).filter(|c| !c.is_empty()) {
+            return match split_command_line(command) {
+                Ok(parts) if !parts.is_empty() => Some(parts),
+                _ => {
+                    tracing::warn!("Ignoring unparseable dev shell command '{}'", command);
+                    None
+                }
+            };
+        }
+
+        match mode {
+            DevShell::Nix => {
+                // Point at the flake explicitly: the working directory is the
+                // workspace root, while the flake usually lives in a repo
+                let flake_dir =
+                    find_marker(dirs, FLAKE_FILE).or(dirs.first().map(PathBuf::as_path))?;
+                Some(vec![
+                    "nix".to_string(),
+                    "develop".to_string(),
+                    flake_dir.to_string_lossy().into_owned(),
+                    "--command".to_string(),
+                ])
+            }
+            DevShell::Devenv => Some(vec![
+                "devenv".to_string(),
+                "shell".to_string(),
+                "--".to_string(),
+            ]),
+            DevShell::Auto | DevShell::Custom | DevShell::Devcontainer | DevShell::Docker => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_detects_flake_in_repo() {
+        let workspace = std::env::temp_dir().join(format!("vk-dev-shell-{}", uuid::Uuid::new_v4()));
+        let repo = workspace.join("app");
+        std::fs::create_dir_all(&repo).unwrap();
+        let dirs = vec![workspace.clone(), repo.clone()];
+
+        assert_eq!(DevShell::Auto.resolve_prefix(None, &dirs), None);
+
+        std::fs::write(repo.join(FLAKE_FILE), "{}").unwrap();
+        let prefix = DevShell::Auto.resolve_prefix(None, &dirs).unwrap();
+        assert_eq!(prefix[..2], ["nix", "develop"]);
+        assert_eq!(prefix[2], repo.to_string_lossy());
+        assert_eq!(prefix[3], "--command");
+
+        std::fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn custom_command_overrides_default() {
+        let dirs = vec![std::env::temp_dir()];
+        assert_eq!(
+            DevShell::Devenv.resolve_prefix(Some("devenv shell --impure --"), &dirs),
+            Some(vec![
+                "devenv".to_string(),
+                "shell".to_string(),
+                "--impure".to_string(),
+                "--".to_string()
+            ])
+        );
+        assert_eq!(DevShell::Custom.resolve_prefix(None, &dirs), None);
+    }
+}
@@ -2,18 +2,27 @@ use std::collections::HashMap;
 
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{
+    actions::script::ScriptShell,
+    command::{CmdOverrides, CommandParts},
+};
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// Shell for script actions; `None` uses the platform default
+    pub shell: Option<ScriptShell>,
+    /// Command every executor and script is run through, e.g. `nix develop --command`
+    pub command_prefix: Vec<String>,
 }
 
 impl ExecutionEnv {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            shell: None,
+            command_prefix: Vec::new(),
         }
     }
 
@@ -50,6 +59,11 @@ impl ExecutionEnv {
         }
     }
 
+    /// Apply the command prefix, if any, to a command about to be spawned
+    pub fn wrap(&self, parts: CommandParts) -> CommandParts {
+        parts.with_prefix(&self.command_prefix)
+    }
+
     pub fn contains_key(&self, key: &str) -> bool {
         self.vars.contains_key(key)
     }
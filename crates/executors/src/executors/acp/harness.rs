@@ -77,7 +77,7 @@ impl AcpAgentHarness {
         cmd_overrides: &CmdOverrides,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.wrap(command_parts).into_resolved().await?;
         let mut command = Command::new(program_path);
         command
             .kill_on_drop(true)
@@ -126,7 +126,7 @@ impl AcpAgentHarness {
         cmd_overrides: &CmdOverrides,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.wrap(command_parts).into_resolved().await?;
         let mut command = Command::new(program_path);
         command
             .kill_on_drop(true)
@@ -21,7 +21,10 @@ use workspace_utils::{
 };
 
 use self::{
-    client::{AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient},
+    client::{
+        AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient, INJECTION_REPORT_CALLBACK_ID,
+        INJECTION_SCAN_CALLBACK_ID,
+    },
     protocol::ProtocolPeer,
     types::PermissionMode,
 };
@@ -39,6 +42,7 @@ use crate::{
         stderr_processor::normalize_stderr_logs,
         utils::{EntryIndexProvider, patch::ConversationPatch},
     },
+    prompt_injection::{self, InjectionFinding},
     stdout_dup::create_stdout_pipe_writer,
 };
 
@@ -129,6 +133,12 @@ pub struct ClaudeCode {
     pub dangerously_skip_permissions: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_api_key: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Trusted Workspace",
+        description = "Skip prompt-injection scanning of attached files, files read and tool results. With plan or approvals enabled flagged content needs approval; otherwise it is reported and the task flagged for attention."
+    )]
+    pub trusted_workspace: Option<bool>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 
@@ -191,8 +201,8 @@ impl ClaudeCode {
     }
 
     pub fn get_hooks(&self) -> Option<serde_json::Value> {
-        if self.plan.unwrap_or(false) {
-            Some(serde_json::json!({
+        let mut hooks = if self.plan.unwrap_or(false) {
+            serde_json::json!({
                 "PreToolUse": [
                     {
                         "matcher": "^ExitPlanMode$",
@@ -203,19 +213,47 @@ impl ClaudeCode {
                         "hookCallbackIds": [AUTO_APPROVE_CALLBACK_ID],
                     }
                 ]
-            }))
+            })
         } else if self.approvals.unwrap_or(false) {
-            Some(serde_json::json!({
+            serde_json::json!({
                 "PreToolUse": [
                     {
                         "matcher": "^(?!(Glob|Grep|NotebookRead|Read|Task|TodoWrite)$).*",
                         "hookCallbackIds": ["tool_approval"],
                     }
                 ]
-            }))
+            })
+        } else if self.trusted_workspace.unwrap_or(false) {
+            return None;
         } else {
-            None
+            // Nothing can be asked with approvals off, so results are scanned
+            // after the fact and findings reported instead
+            return Some(serde_json::json!({
+                "PostToolUse": [
+                    {
+                        "matcher": "^(Read|NotebookRead|WebFetch|WebSearch|mcp__.*)$",
+                        "hookCallbackIds": [INJECTION_REPORT_CALLBACK_ID],
+                    }
+                ]
+            }));
+        };
+
+        if !self.trusted_workspace.unwrap_or(false) {
+            // An `ask` from the scan outranks the auto-approval of reads
+            if let Some(pre_tool_use) = hooks["PreToolUse"].as_array_mut() {
+                pre_tool_use.push(serde_json::json!({
+                    "matcher": "^(Read|NotebookRead)$",
+                    "hookCallbackIds": [INJECTION_SCAN_CALLBACK_ID],
+                }));
+            }
+            hooks["PostToolUse"] = serde_json::json!([
+                {
+                    "matcher": "^(WebFetch|WebSearch|mcp__.*)$",
+                    "hookCallbackIds": [INJECTION_SCAN_CALLBACK_ID],
+                }
+            ]);
         }
+        Some(hooks)
     }
 }
 
@@ -321,7 +359,7 @@ impl ClaudeCode {
         command_parts: CommandParts,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.wrap(command_parts).into_resolved().await?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
         let mut command = Command::new(program_path);
@@ -362,6 +400,8 @@ impl ClaudeCode {
         // Spawn task to handle the SDK client with control protocol
         let prompt_clone = combined_prompt.clone();
         let approvals_clone = self.approvals_service.clone();
+        let scan_attachments = !self.trusted_workspace.unwrap_or(false);
+        let attachments_dir = current_dir.to_path_buf();
         tokio::spawn(async move {
             let log_writer = LogWriter::new(new_stdout);
             let client = ClaudeAgentClient::new(log_writer.clone(), approvals_clone.clone());
@@ -392,6 +432,14 @@ impl ClaudeCode {
                 tracing::warn!("Failed to set permission mode to {permission_mode}: {e}");
             }
 
+            if scan_attachments
+                && let Err(e) = client
+                    .report_attached_files(&prompt_clone, &attachments_dir)
+                    .await
+            {
+                tracing::warn!("Failed to scan attached files: {e}");
+            }
+
             // Send user message
             if let Err(e) = protocol_peer.send_user_message(prompt_clone).await {
                 tracing::error!("Failed to send prompt: {e}");
@@ -636,6 +684,7 @@ impl ClaudeLogProcessor {
             ClaudeJson::Result { session_id, .. } => session_id.clone(),
             ClaudeJson::StreamEvent { .. } => None, // session might not have been initialized yet
             ClaudeJson::ApprovalResponse { .. } => None,
+            ClaudeJson::PromptInjectionWarning { .. } => None,
             ClaudeJson::Unknown { .. } => None,
         }
     }
@@ -1369,6 +1418,36 @@ impl ClaudeLogProcessor {
                     patches.push(ConversationPatch::add_normalized_entry(idx, entry));
                 }
             }
+            ClaudeJson::PromptInjectionWarning {
+                tool_name,
+                path,
+                findings,
+                reported_only,
+                ..
+            } => {
+                let source = match path {
+                    Some(path) => format!("`{}`", make_path_relative(path, worktree_path)),
+                    None => format!("the result of {tool_name}"),
+                };
+                let advice = if *reported_only {
+                    "Check what the agent did with it."
+                } else {
+                    "Review it before approving."
+                };
+                let entry = NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::ErrorMessage {
+                        error_type: NormalizedEntryError::Other,
+                    },
+                    content: format!(
+                        "Possible prompt injection in {source}. {advice}\n\n{}",
+                        prompt_injection::describe(findings)
+                    ),
+                    metadata: None,
+                };
+                let idx = entry_index_provider.next();
+                patches.push(ConversationPatch::add_normalized_entry(idx, entry));
+            }
             ClaudeJson::Unknown { data } => {
                 let entry = NormalizedEntry {
                     timestamp: None,
@@ -1711,6 +1790,18 @@ pub enum ClaudeJson {
         tool_name: String,
         approval_status: ApprovalStatus,
     },
+    #[serde(rename = "prompt_injection_warning")]
+    PromptInjectionWarning {
+        call_id: Option<String>,
+        tool_name: String,
+        /// File that was scanned; None when a tool result was scanned
+        path: Option<String>,
+        findings: Vec<InjectionFinding>,
+        /// Reported without asking, because approvals were off or the user
+        /// attached the file
+        #[serde(default)]
+        reported_only: bool,
+    },
     // Catch-all for unknown message types
     #[serde(untagged)]
     Unknown {
@@ -2265,6 +2356,7 @@ mod tests {
             },
             approvals_service: None,
             disable_api_key: None,
+            trusted_workspace: None,
         };
         let msg_store = Arc::new(MsgStore::new());
         let current_dir = std::path::PathBuf::from("/tmp/test-worktree");
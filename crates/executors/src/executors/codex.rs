@@ -206,6 +206,18 @@ impl StandardCodingAgentExecutor for Codex {
             AvailabilityInfo::NotFound
         }
     }
+
+    fn auth_status_command(&self) -> Option<CommandParts> {
+        let base = self
+            .cmd
+            .base_command_override
+            .as_deref()
+            .unwrap_or(Self::base_command());
+        CommandBuilder::new(base)
+            .params(["login", "status"])
+            .build_initial()
+            .ok()
+    }
 }
 
 impl Codex {
@@ -300,7 +312,7 @@ impl Codex {
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.wrap(command_parts).into_resolved().await?;
 
         let mut process = Command::new(program_path);
         process
@@ -16,7 +16,7 @@ use workspace_utils::{
 };
 
 use crate::{
-    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
     env::ExecutionEnv,
     executors::{
         AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
@@ -79,7 +79,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let command_parts = self.build_command_builder().build_initial()?;
 
-        let (executable_path, args) = command_parts.into_resolved().await?;
+        let (executable_path, args) = env.wrap(command_parts).into_resolved().await?;
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
@@ -118,7 +118,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
         let command_parts = self
             .build_command_builder()
             .build_follow_up(&["--resume".to_string(), session_id.to_string()])?;
-        let (executable_path, args) = command_parts.into_resolved().await?;
+        let (executable_path, args) = env.wrap(command_parts).into_resolved().await?;
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
@@ -505,6 +505,18 @@ impl StandardCodingAgentExecutor for CursorAgent {
             AvailabilityInfo::NotFound
         }
     }
+
+    fn auth_status_command(&self) -> Option<CommandParts> {
+        let base = self
+            .cmd
+            .base_command_override
+            .as_deref()
+            .unwrap_or(Self::base_command());
+        CommandBuilder::new(base)
+            .params(["status"])
+            .build_initial()
+            .ok()
+    }
 }
 /* ===========================
 Typed Cursor JSON structures
@@ -112,7 +112,7 @@ async fn spawn_droid(
     env: &ExecutionEnv,
     cmd_overrides: &crate::command::CmdOverrides,
 ) -> Result<SpawnedChild, ExecutorError> {
-    let (program_path, args) = command_parts.into_resolved().await?;
+    let (program_path, args) = env.wrap(command_parts).into_resolved().await?;
 
     let mut command = Command::new(program_path);
     command
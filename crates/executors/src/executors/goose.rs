@@ -0,0 +1,159 @@
+use std::{path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use derivative::Derivative;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    approvals::ExecutorApprovalService,
+    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        gemini::AcpAgentHarness,
+    },
+};
+
+#[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
+#[derivative(Debug, PartialEq)]
+pub struct Goose {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Provider",
+        description = "LLM provider to use (e.g. anthropic, openai, ollama). Defaults to the provider in Goose's own config."
+    )]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "Model to use with the provider. Defaults to the model in Goose's own config."
+    )]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Auto Approve",
+        description = "Run tools without asking for approval"
+    )]
+    pub auto_approve: Option<bool>,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+    #[serde(skip)]
+    #[ts(skip)]
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub approvals: Option<Arc<dyn ExecutorApprovalService>>,
+}
+
+impl Goose {
+    fn build_command_builder(&self) -> CommandBuilder {
+        let builder = CommandBuilder::new("goose").params(["acp"]);
+        apply_overrides(builder, &self.cmd)
+    }
+
+    /// Goose reads its provider, model and approval mode from the environment
+    fn goose_env(&self, env: &ExecutionEnv) -> ExecutionEnv {
+        let mut env = env.clone();
+        if let Some(provider) = &self.provider {
+            env.insert("GOOSE_PROVIDER", provider);
+        }
+        if let Some(model) = &self.model {
+            env.insert("GOOSE_MODEL", model);
+        }
+        let mode = if self.auto_approve.unwrap_or(false) {
+            "auto"
+        } else {
+            "approve"
+        };
+        env.insert("GOOSE_MODE", mode);
+        env
+    }
+
+    fn approvals(&self) -> Option<Arc<dyn ExecutorApprovalService>> {
+        if self.auto_approve.unwrap_or(false) {
+            None
+        } else {
+            self.approvals.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Goose {
+    fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
+        self.approvals = Some(approvals);
+    }
+
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let goose_command = self.build_command_builder().build_initial()?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let harness = AcpAgentHarness::with_session_namespace("goose_sessions");
+        harness
+            .spawn_with_command(
+                current_dir,
+                combined_prompt,
+                goose_command,
+                &self.goose_env(env),
+                &self.cmd,
+                self.approvals(),
+            )
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let goose_command = self.build_command_builder().build_follow_up(&[])?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let harness = AcpAgentHarness::with_session_namespace("goose_sessions");
+        harness
+            .spawn_follow_up_with_command(
+                current_dir,
+                combined_prompt,
+                session_id,
+                goose_command,
+                &self.goose_env(env),
+                &self.cmd,
+                self.approvals(),
+            )
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+        crate::executors::acp::normalize_logs(msg_store, worktree_path);
+    }
+
+    // Goose keeps its extensions in YAML, which the MCP editor can't write
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let config_found = dirs::home_dir()
+            .map(|home| {
+                home.join(".config")
+                    .join("goose")
+                    .join("config.yaml")
+                    .exists()
+            })
+            .unwrap_or(false);
+
+        if config_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
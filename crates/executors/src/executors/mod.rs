@@ -15,7 +15,7 @@ use workspace_utils::msg_store::MsgStore;
 use crate::{
     actions::ExecutorAction,
     approvals::ExecutorApprovalService,
-    command::CommandBuildError,
+    command::{CommandBuildError, CommandParts},
     env::ExecutionEnv,
     executors::{
         amp::Amp,
@@ -25,6 +25,7 @@ use crate::{
         cursor::CursorAgent,
         droid::Droid,
         gemini::Gemini,
+        goose::Goose,
         opencode::Opencode,
         qwen::QwenCode,
     },
@@ -39,12 +40,13 @@ pub mod copilot;
 pub mod cursor;
 pub mod droid;
 pub mod gemini;
+pub mod goose;
 pub mod opencode;
 pub mod qwen;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 pub enum BaseAgentCapability {
     SessionFork,
     /// Agent requires a setup script before it can run (e.g., login, installation)
@@ -90,7 +92,7 @@ pub enum ExecutorError {
     // Only add Hash; Eq/PartialEq are already provided by EnumDiscriminants.
     derive(EnumString, Hash, strum_macros::Display, Serialize, Deserialize, TS, Type),
     strum(serialize_all = "SCREAMING_SNAKE_CASE"),
-    ts(use_ts_enum),
+    ts(repr(enum = name)),
     serde(rename_all = "SCREAMING_SNAKE_CASE"),
     sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")
 )]
@@ -107,6 +109,7 @@ pub enum CodingAgent {
     QwenCode,
     Copilot,
     Droid,
+    Goose,
 }
 
 impl CodingAgent {
@@ -167,6 +170,7 @@ impl CodingAgent {
             | Self::Gemini(_)
             | Self::QwenCode(_)
             | Self::Droid(_)
+            | Self::Goose(_)
             | Self::Opencode(_) => vec![BaseAgentCapability::SessionFork],
             Self::Codex(_) => vec![
                 BaseAgentCapability::SessionFork,
@@ -253,6 +257,12 @@ pub trait StandardCodingAgentExecutor {
             AvailabilityInfo::NotFound
         }
     }
+
+    /// A cheap command telling whether the CLI is signed in: it exits zero
+    /// when it is. None for CLIs without one.
+    fn auth_status_command(&self) -> Option<CommandParts> {
+        None
+    }
 }
 
 /// Result communicated through the exit signal
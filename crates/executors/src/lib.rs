@@ -1,9 +1,12 @@
 pub mod actions;
 pub mod approvals;
 pub mod command;
+pub mod dev_shell;
 pub mod env;
 pub mod executors;
 pub mod logs;
 pub mod mcp_config;
 pub mod profile;
+pub mod prompt_injection;
+pub mod sandbox;
 pub mod stdout_dup;
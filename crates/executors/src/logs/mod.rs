@@ -4,6 +4,7 @@ use ts_rs::TS;
 use workspace_utils::approvals::{ApprovalStatus, QuestionAnswer, QuestionData};
 
 pub mod plain_text_processor;
+pub mod schema;
 pub mod stderr_processor;
 pub mod utils;
 
@@ -0,0 +1,118 @@
+//! Versioning of persisted [`NormalizedEntry`] JSON.
+//!
+//! Stored entries carry the schema version they were written with. Reading
+//! one runs the upgrade shim of every later version before deserializing, so
+//! old logs keep rendering after the format changes. Changing the format
+//! means bumping [`NORMALIZED_ENTRY_SCHEMA_VERSION`] and appending a shim to
+//! [`UPGRADES`].
+
+use serde_json::{Map, Value, json};
+
+use super::NormalizedEntry;
+
+/// Version written with every entry persisted now
+pub const NORMALIZED_ENTRY_SCHEMA_VERSION: i64 = 1;
+
+/// `UPGRADES[n]` turns a version `n` entry into a version `n + 1` one
+const UPGRADES: [fn(&mut Map<String, Value>); NORMALIZED_ENTRY_SCHEMA_VERSION as usize] =
+    [upgrade_v0];
+
+/// Version 0 covers entries stored before versioning. Error messages had no
+/// `error_type`, tool uses had no `status`, and next actions had no
+/// `needs_setup`.
+fn upgrade_v0(entry: &mut Map<String, Value>) {
+    let Some(Value::Object(entry_type)) = entry.get_mut("entry_type") else {
+        return;
+    };
+    match entry_type.get("type").and_then(Value::as_str) {
+        Some("error_message") => {
+            entry_type
+                .entry("error_type")
+                .or_insert_with(|| json!({ "type": "other" }));
+        }
+        // Only finished tool calls were persisted
+        Some("tool_use") => {
+            entry_type
+                .entry("status")
+                .or_insert_with(|| json!({ "status": "success" }));
+        }
+        Some("next_action") => {
+            entry_type
+                .entry("needs_setup")
+                .or_insert(Value::Bool(false));
+        }
+        _ => {}
+    }
+}
+
+/// Deserialize an entry stored with schema `version`, upgrading it first.
+/// Entries from a newer version are read as is, ignoring unknown fields.
+pub fn upgrade_entry(mut value: Value, version: i64) -> Result<NormalizedEntry, serde_json::Error> {
+    if let Value::Object(entry) = &mut value {
+        let from = version.clamp(0, NORMALIZED_ENTRY_SCHEMA_VERSION) as usize;
+        for upgrade in &UPGRADES[from..] {
+            upgrade(entry);
+        }
+    }
+    serde_json::from_value(value)
+}
+
+/// [`upgrade_entry`] for an entry's stored JSON text
+pub fn parse_entry(json: &str, version: i64) -> Result<NormalizedEntry, serde_json::Error> {
+    upgrade_entry(serde_json::from_str(json)?, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::logs::{NormalizedEntryError, NormalizedEntryType, ToolStatus};
+
+    /// Entries as they were stored by earlier releases
+    const FIXTURES: &str = include_str!("schema_fixtures.json");
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        name: String,
+        version: i64,
+        entry: Value,
+    }
+
+    #[test]
+    fn upgrades_stored_fixtures() {
+        let fixtures: Vec<Fixture> = serde_json::from_str(FIXTURES).unwrap();
+        for fixture in &fixtures {
+            let entry = upgrade_entry(fixture.entry.clone(), fixture.version)
+                .unwrap_or_else(|e| panic!("{}: {e}", fixture.name));
+
+            // Written back at the current version, it must read unchanged
+            let stored = serde_json::to_string(&entry).unwrap();
+            let reread = parse_entry(&stored, NORMALIZED_ENTRY_SCHEMA_VERSION).unwrap();
+            assert_eq!(
+                serde_json::to_value(&reread).unwrap(),
+                serde_json::to_value(&entry).unwrap(),
+                "{}",
+                fixture.name
+            );
+
+            match (fixture.name.as_str(), &entry.entry_type) {
+                ("v0_error_message", NormalizedEntryType::ErrorMessage { error_type }) => {
+                    assert_eq!(*error_type, NormalizedEntryError::Other)
+                }
+                ("v0_tool_use", NormalizedEntryType::ToolUse { status, .. }) => {
+                    assert!(matches!(status, ToolStatus::Success))
+                }
+                ("v0_next_action", NormalizedEntryType::NextAction { needs_setup, .. }) => {
+                    assert!(!needs_setup)
+                }
+                ("v1_tool_use_failed", NormalizedEntryType::ToolUse { status, .. }) => {
+                    assert!(matches!(status, ToolStatus::Failed))
+                }
+                (_, NormalizedEntryType::AssistantMessage) => {}
+                (name, other) => panic!("{name}: unexpected entry type {other:?}"),
+            }
+        }
+        assert!(fixtures.len() >= 5);
+    }
+}
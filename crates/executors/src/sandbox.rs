@@ -0,0 +1,325 @@
+//! Confining executions to their workspace with an OS sandbox.
+//!
+//! A project's [`SandboxPolicy`] lists the directories executions may write
+//! to besides the workspace, commands they may not run, and whether they may
+//! reach the network. It is enforced by running the spawned command through
+//! bubblewrap (`bwrap`) on Linux or `sandbox-exec` on macOS: the rest of the
+//! filesystem is read-only, denied executables are masked, and with
+//! [`SandboxNetwork::Deny`] the process gets no network access.
+//!
+//! Agents that keep state in the home directory (e.g. `~/.claude`) need that
+//! directory listed in `allowed_dirs`, and [`SandboxNetwork::Deny`] also cuts
+//! off hosted model APIs, so it only suits agents that talk to a local model.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path;
+
+/// Whether sandboxed executions may use the network
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxNetwork {
+    #[default]
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct SandboxPolicy {
+    /// Sandbox every agent and script run for the project
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directories writable besides the workspace; absolute or starting with `~/`
+    #[serde(default)]
+    pub allowed_dirs: Vec<String>,
+    /// Executables that cannot be run, by name (`curl`) or absolute path
+    #[serde(default)]
+    pub denied_commands: Vec<String>,
+    #[serde(default)]
+    pub network: SandboxNetwork,
+}
+
+#[derive(Debug, Error)]
+pub enum SandboxError {
+    #[error("No sandbox is available on this system: install bubblewrap (bwrap) on Linux")]
+    Unavailable,
+    #[error("Allowed directory must be absolute or start with ~/: {0}")]
+    InvalidAllowedDir(String),
+    #[error("Working directory {0} is outside the workspace")]
+    OutsideWorkspace(String),
+}
+
+/// OS facility the sandbox is built on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SandboxBackend {
+    Bubblewrap(PathBuf),
+    SandboxExec(PathBuf),
+}
+
+impl SandboxBackend {
+    pub async fn detect() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            resolve_executable_path("sandbox-exec")
+                .await
+                .map(SandboxBackend::SandboxExec)
+        } else if cfg!(target_os = "linux") {
+            resolve_executable_path("bwrap")
+                .await
+                .map(SandboxBackend::Bubblewrap)
+        } else {
+            None
+        }
+    }
+}
+
+impl SandboxPolicy {
+    pub fn validate(&self) -> Result<(), SandboxError> {
+        for dir in &self.allowed_dirs {
+            expand_dir(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Reject an action whose `working_dir`, relative to `workspace_dir`,
+    /// would leave the workspace
+    pub fn check_working_dir(
+        &self,
+        workspace_dir: &Path,
+        working_dir: Option<&str>,
+    ) -> Result<(), SandboxError> {
+        let Some(working_dir) = working_dir else {
+            return Ok(());
+        };
+        let escapes = Path::new(working_dir).components().any(|component| {
+            matches!(
+                component,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if escapes {
+            return Err(SandboxError::OutsideWorkspace(
+                workspace_dir
+                    .join(working_dir)
+                    .to_string_lossy()
+                    .into_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Command prefix that runs an execution in the sandbox, with
+    /// `writable_dirs` (the workspace and the parts of the repos' git dirs a
+    /// commit writes to) and the policy's allowed directories left writable
+    pub async fn command_prefix(
+        &self,
+        writable_dirs: &[PathBuf],
+    ) -> Result<Vec<String>, SandboxError> {
+        let backend = SandboxBackend::detect()
+            .await
+            .ok_or(SandboxError::Unavailable)?;
+
+        let mut writable: Vec<PathBuf> = writable_dirs.to_vec();
+        for dir in &self.allowed_dirs {
+            writable.push(expand_dir(dir)?);
+        }
+        // Symlinked locations (macOS /var, /tmp) must be given as their targets
+        let writable: Vec<PathBuf> = writable
+            .into_iter()
+            .filter_map(|dir| canonical_dir(&dir))
+            .collect();
+
+        let mut denied = Vec::new();
+        for command in &self.denied_commands {
+            denied.extend(resolve_denied_command(command).await);
+        }
+
+        Ok(match backend {
+            SandboxBackend::Bubblewrap(bwrap) => {
+                bubblewrap_prefix(&bwrap, &writable, &denied, self.network)
+            }
+            SandboxBackend::SandboxExec(sandbox_exec) => vec![
+                sandbox_exec.to_string_lossy().into_owned(),
+                "-p".to_string(),
+                seatbelt_profile(&writable, &denied, self.network),
+            ],
+        })
+    }
+}
+
+fn expand_dir(dir: &str) -> Result<PathBuf, SandboxError> {
+    let dir = dir.trim();
+    let path = match dir.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .ok_or_else(|| SandboxError::InvalidAllowedDir(dir.to_string()))?
+            .join(rest),
+        None => PathBuf::from(dir),
+    };
+    if !path.is_absolute() {
+        return Err(SandboxError::InvalidAllowedDir(dir.to_string()));
+    }
+    Ok(path)
+}
+
+/// Canonical path, or None when it does not exist
+fn canonical_dir(path: &Path) -> Option<PathBuf> {
+    match std::fs::canonicalize(path) {
+        Ok(path) => Some(path),
+        Err(_) => {
+            tracing::debug!("Skipping missing sandbox directory {}", path.display());
+            None
+        }
+    }
+}
+
+/// Paths that must not be executed for `command`: where it is found on PATH
+/// and, when that is a symlink, its target
+async fn resolve_denied_command(command: &str) -> Vec<PathBuf> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Vec::new();
+    }
+    let path = if Path::new(command).is_absolute() {
+        Some(PathBuf::from(command))
+    } else {
+        resolve_executable_path(command).await
+    };
+    let Some(path) = path else {
+        return Vec::new();
+    };
+
+    let mut paths = vec![path.clone()];
+    if let Ok(target) = std::fs::canonicalize(&path)
+        && target != path
+    {
+        paths.push(target);
+    }
+    paths
+}
+
+fn bubblewrap_prefix(
+    bwrap: &Path,
+    writable: &[PathBuf],
+    denied: &[PathBuf],
+    network: SandboxNetwork,
+) -> Vec<String> {
+    let mut args: Vec<String> = [
+        "--die-with-parent",
+        "--ro-bind",
+        "/",
+        "/",
+        "--dev",
+        "/dev",
+        "--proc",
+        "/proc",
+        "--tmpfs",
+        "/tmp",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect();
+
+    // Later mounts win, so writable dirs under /tmp survive the tmpfs
+    for dir in writable {
+        let dir = dir.to_string_lossy().into_owned();
+        args.extend(["--bind".to_string(), dir.clone(), dir]);
+    }
+    // bwrap resolves symlinked destinations, so mask the real files only
+    for path in denied.iter().filter(|path| !path.is_symlink()) {
+        args.extend([
+            "--ro-bind".to_string(),
+            "/dev/null".to_string(),
+            path.to_string_lossy().into_owned(),
+        ]);
+    }
+    if network == SandboxNetwork::Deny {
+        args.push("--unshare-net".to_string());
+    }
+    args.push("--".to_string());
+
+    let mut prefix = vec![bwrap.to_string_lossy().into_owned()];
+    prefix.extend(args);
+    prefix
+}
+
+fn seatbelt_string(path: &Path) -> String {
+    let escaped = path
+        .to_string_lossy()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+fn seatbelt_profile(writable: &[PathBuf], denied: &[PathBuf], network: SandboxNetwork) -> String {
+    let mut profile = String::from("(version 1)\n(allow default)\n(deny file-write*)\n");
+    // Temp dirs and devices stay writable, as most tools expect
+    profile.push_str(
+        "(allow file-write* (subpath \"/private/tmp\") (subpath \"/private/var/folders\") \
+         (regex #\"^/dev/\")",
+    );
+    for dir in writable {
+        profile.push_str(&format!(" (subpath {})", seatbelt_string(dir)));
+    }
+    profile.push_str(")\n");
+    for path in denied {
+        profile.push_str(&format!(
+            "(deny process-exec (literal {}))\n",
+            seatbelt_string(path)
+        ));
+    }
+    if network == SandboxNetwork::Deny {
+        profile.push_str("(deny network-outbound (remote ip \"*:*\"))\n");
+        profile.push_str("(deny network-inbound (local ip \"*:*\"))\n");
+    }
+    profile
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn working_dir_must_stay_in_workspace() {
+        let policy = SandboxPolicy::default();
+        let workspace = Path::new("/work/ws");
+        assert!(policy.check_working_dir(workspace, None).is_ok());
+        assert!(policy.check_working_dir(workspace, Some("app/src")).is_ok());
+        assert!(
+            policy
+                .check_working_dir(workspace, Some("../other"))
+                .is_err()
+        );
+        assert!(policy.check_working_dir(workspace, Some("/etc")).is_err());
+    }
+
+    #[test]
+    fn bubblewrap_masks_denied_commands_and_network() {
+        let prefix = bubblewrap_prefix(
+            Path::new("/usr/bin/bwrap"),
+            &[PathBuf::from("/work/ws")],
+            &[PathBuf::from("/nonexistent/curl")],
+            SandboxNetwork::Deny,
+        );
+        let joined = prefix.join(" ");
+        assert!(joined.starts_with("/usr/bin/bwrap --die-with-parent --ro-bind / /"));
+        assert!(joined.contains("--bind /work/ws /work/ws"));
+        assert!(joined.contains("--ro-bind /dev/null /nonexistent/curl"));
+        assert!(joined.ends_with("--unshare-net --"));
+    }
+
+    #[test]
+    fn allowed_dirs_must_be_absolute() {
+        let mut policy = SandboxPolicy {
+            allowed_dirs: vec!["/opt/cache".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_ok());
+        policy.allowed_dirs.push("relative/dir".to_string());
+        assert!(matches!(
+            policy.validate(),
+            Err(SandboxError::InvalidAllowedDir(_))
+        ));
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(windows)]
+use std::process::Stdio;
+
 use command_group::AsyncGroupChild;
 #[cfg(unix)]
 use nix::{
@@ -5,9 +8,13 @@ use nix::{
     unistd::{Pid, getpgid},
 };
 use services::services::container::ContainerError;
-#[cfg(unix)]
 use tokio::time::Duration;
 
+/// How long each graceful shutdown step gets before escalating
+const GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Stop an execution and every process it started. Executions are spawned
+/// with pipes rather than a PTY, so the group holds the whole tree.
 pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
     // hit the whole process group, not just the leader
     #[cfg(unix)]
@@ -25,20 +32,139 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
                         e
                     );
                 }
-                tokio::time::sleep(Duration::from_secs(2)).await;
-                if child
-                    .inner()
-                    .try_wait()
-                    .map_err(ContainerError::Io)?
-                    .is_some()
-                {
+                if wait_for_exit(child).await? {
                     break;
                 }
             }
         }
     }
 
+    // On Windows the group is a job object (created by command_group). Ask the
+    // process tree to close first, then `kill` below terminates the whole job,
+    // which also reaches grandchildren that outlived the leader.
+    #[cfg(windows)]
+    {
+        if let Some(pid) = child.inner().id() {
+            let result = tokio::process::Command::new("taskkill")
+                .args(["/PID", &pid.to_string(), "/T"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+            if let Err(e) = result {
+                tracing::warn!("Failed to run taskkill for process {}: {}", pid, e);
+            }
+            wait_for_exit(child).await?;
+        }
+    }
+
     let _ = child.kill().await;
     let _ = child.wait().await;
     Ok(())
 }
+
+/// Wait up to `GRACE_PERIOD` for the group leader to exit
+async fn wait_for_exit(child: &mut AsyncGroupChild) -> Result<bool, ContainerError> {
+    tokio::time::sleep(GRACE_PERIOD).await;
+    Ok(child
+        .inner()
+        .try_wait()
+        .map_err(ContainerError::Io)?
+        .is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use command_group::AsyncCommandGroup;
+    use tokio::process::Command;
+
+    use super::*;
+
+    /// A shell that starts a long-running grandchild, writes its PID to
+    /// `pid_file` and then waits, per platform
+    fn process_tree_command(pid_file: &Path) -> Command {
+        #[cfg(unix)]
+        {
+            let mut command = Command::new("sh");
+            command
+                .args(["-c", "sleep 30 & echo $! > \"$1\"; wait", "sh"])
+                .arg(pid_file);
+            command
+        }
+        #[cfg(windows)]
+        {
+            let script = format!(
+                "$p = Start-Process ping -ArgumentList '-n','30','127.0.0.1' -WindowStyle Hidden -PassThru; \
+                 Set-Content -Path '{}' -Value $p.Id; Start-Sleep 30",
+                pid_file.display()
+            );
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-Command", script.as_str()]);
+            command
+        }
+    }
+
+    async fn read_pid(pid_file: &Path) -> u32 {
+        for _ in 0..100 {
+            if let Ok(pid) = std::fs::read_to_string(pid_file)
+                .unwrap_or_default()
+                .trim()
+                .parse()
+            {
+                return pid;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("grandchild never wrote its PID");
+    }
+
+    #[cfg(unix)]
+    fn process_alive(pid: u32) -> bool {
+        // An orphan killed with the group lingers as a zombie until init
+        // reaps it, but no longer runs
+        let zombie = std::fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|stat| {
+            stat.rsplit(')')
+                .next()
+                .is_some_and(|rest| rest.trim_start().starts_with('Z'))
+        });
+        nix::sys::signal::kill(Pid::from_raw(pid as i32), None).is_ok() && !zombie
+    }
+
+    #[cfg(windows)]
+    fn process_alive(pid: u32) -> bool {
+        let filter = format!("PID eq {pid}");
+        std::process::Command::new("tasklist")
+            .args(["/FI", filter.as_str(), "/NH"])
+            .output()
+            .is_ok_and(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .split_whitespace()
+                    .any(|field| field == pid.to_string())
+            })
+    }
+
+    #[tokio::test]
+    async fn test_kill_process_group_terminates_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("grandchild.pid");
+        let mut child = process_tree_command(&pid_file).group_spawn().unwrap();
+        let grandchild = read_pid(&pid_file).await;
+        assert!(process_alive(grandchild));
+
+        kill_process_group(&mut child).await.unwrap();
+
+        assert!(child.try_wait().unwrap().is_some());
+        // The leader and the grandchild both exit: a process group on Unix,
+        // the job object command_group assigns the tree to on Windows
+        for _ in 0..20 {
+            if !process_alive(grandchild) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("grandchild {grandchild} outlived the group kill");
+    }
+}
@@ -4,10 +4,10 @@ use std::{
     path::{Path, PathBuf},
     str::FromStr,
     sync::{Arc, LazyLock, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use dashmap::DashSet;
+use dashmap::{DashMap, DashSet};
 
 /// Tracks which projects have an active merge queue processor.
 /// This prevents multiple processors from being spawned for the same project,
@@ -44,17 +44,29 @@ use db::{
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
+        execution_process_failure::{ExecutionProcessFailure, FailureReason},
         execution_process_normalized_entry::ExecutionProcessNormalizedEntry,
         execution_process_repo_state::ExecutionProcessRepoState,
+        execution_process_resource_sample::ExecutionProcessResourceSample,
+        execution_process_retry::ExecutionProcessRetry,
+        execution_queue::ExecutionQueue,
+        git_hooks_policy::{GitHooksMode, GitHooksPolicy},
         merge::Merge,
+        operation::OperationKind,
+        permission_finding::PermissionFinding,
+        project_file_rule::ProjectFileRule,
+        project_memory::ProjectMemory,
         project_repo::ProjectRepo,
+        redaction_patterns::ProjectRedactionPatterns,
         repo::Repo,
         review_attention::{CreateReviewAttention, ReviewAttention},
+        sandbox_policy::ProjectSandboxPolicy,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         session::{CreateSession, Session},
         task::{Task, TaskStatus},
-        workspace::Workspace,
-        workspace_repo::WorkspaceRepo,
+        task_env_var::TaskEnvVar,
+        workspace::{CreateWorkspace, Workspace},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
@@ -65,6 +77,7 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
+    dev_shell::DevShell,
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
     logs::{
@@ -76,49 +89,99 @@ use executors::{
     },
     profile::ExecutorProfileId,
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
+    agent_auth::{AgentAuthMonitor, AgentAuthState},
     analytics::AnalyticsContext,
+    anomaly_detection::{ActivityClock, Anomaly, AnomalyDetector},
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    blame_context,
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
     conversation::ConversationService,
+    copy_files::{CopyFilePreview, CopyTemplateVars},
+    devcontainer::{self, Devcontainer, DevcontainerError, DevcontainerLog},
     diff_stream::{self, DiffStreamHandle},
     domain_events::{
         AutopilotHandler, DispatcherBuilder, DomainEvent, DomainEventDispatcher,
         EventDispatchCallback, ExecutionTrigger, ExecutionTriggerCallback,
         FeedbackCollectionHandler, HandlerContext, HookExecutionStore,
-        HookExecutionUpdaterHandler, NotificationHandler, RemoteSyncHandler,
-        ReviewAttentionHandler, WebSocketBroadcastHandler,
+        HookExecutionUpdaterHandler, NotificationHandler, ProjectMemoryHandler,
+        RemoteSyncHandler, ReviewAttentionHandler, TaskWatcherHandler,
+        WebSocketBroadcastHandler, WebhookHandler,
     },
+    embedding::EmbeddingService,
+    env_file,
+    execution_retry::{
+        STARTUP_WINDOW, classify_exit_code, classify_failure, policy_for_profile, retry_delay,
+    },
+    failure_classification::{ClassifiedFailure, classify_execution_failure, suggested_fix},
     feedback::FeedbackService,
-    git::{Commit, DiffTarget, GitCli, GitService},
+    file_rules::{self, FileRuleViolation, FileRules},
+    git::{Commit, CommitHooks, DiffTarget, GitCli, GitService, resolve_inline_diff_limit},
+    git_hooks::{self, HookSandbox},
     image::ImageService,
     merge_queue_processor::MergeQueueProcessor,
     merge_queue_store::MergeQueueStore,
-    notification::NotificationService,
+    notification::{NotificationService, signed_out_message},
     operation_status::{OperationStatus, OperationStatusStore, OperationStatusType},
+    operations::OperationTracker,
+    output_redaction::{self, OutputRedactor},
+    permission_audit,
+    pre_commit::{self, PreCommitRunner},
+    project_memory, project_secrets, prompt_templates,
+    provider_rate_limit::ProviderRateLimiter,
     queued_message::QueuedMessageService,
+    resource_monitor::ResourceMonitor,
     review_attention::ReviewAttentionService,
+    secrets::SecretService,
     share::SharePublisher,
     skills_cache::GlobalSkillsCache,
+    ssh_remote::SshRemote,
+    staged_selection::StagedSelections,
+    stale_session::{detect_stale_session, recovery_prompt},
     watcher_manager::WatcherManager,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+    worktree_manager::WorktreeManager,
+};
+use tokio::{
+    sync::{RwLock, broadcast::error::RecvError},
+    task::JoinHandle,
 };
-use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
     assets::ClaudeCodeHookAssets,
     diff::create_unified_diff,
+    git::add_git_exclude,
     log_msg::LogMsg,
     msg_store::MsgStore,
-    text::{git_branch_id, short_uuid, truncate_to_char_boundary},
+    path::remove_dir_all_async,
+    shell::login_shell_env,
+    text::{git_branch_id, short_uuid, truncate_output, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
 use crate::{command, copy};
 
+/// How often running executions' process trees are sampled for CPU/RSS.
+const RESOURCE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// How long resource samples are kept before the sampler deletes them
+const RESOURCE_SAMPLE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// How often the sampler deletes samples past RESOURCE_SAMPLE_RETENTION
+const RESOURCE_SAMPLE_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Hook output beyond this is cut before it is recorded on the execution
+const MAX_HOOK_OUTPUT_CHARS: usize = 8_000;
+/// How often the execution queue is retried, so entries held back by a
+/// per-minute provider rate limit start once the window frees up
+const QUEUE_RETRY_INTERVAL: Duration = Duration::from_secs(15);
+/// How often a running agent's changes are checked against its project's
+/// file rules
+const FILE_RULE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// How often an agent waiting for an approval is checked on again before
+/// its inactivity and duration limits apply
+const APPROVAL_WAIT_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -143,8 +206,27 @@ pub struct LocalContainerService {
     merge_queue_store: Arc<RwLock<Option<MergeQueueStore>>>,
     /// OperationStatusStore for tracking merge operations - set after construction
     operation_status: Arc<RwLock<Option<OperationStatusStore>>>,
+    /// Records merges and commit message generation in the operation history
+    operations: OperationTracker,
+    /// Credentials referenced from the config, e.g. the Langfuse secret key
+    secrets: SecretService,
     /// Domain event dispatcher for routing events to handlers
     event_dispatcher: Arc<DomainEventDispatcher>,
+    /// Samples CPU/RSS of running executions' process trees
+    resource_monitor: ResourceMonitor,
+    agent_auth: AgentAuthMonitor,
+    /// Per-provider limits on starting coding agents
+    provider_rate_limiter: ProviderRateLimiter,
+    /// Embeds prompts to find the project memories relevant to them
+    embedding: EmbeddingService,
+    /// Execution IDs interrupted for breaking file rules - their exit monitor
+    /// sends the violations back to the agent
+    file_rule_interrupts: Arc<DashSet<Uuid>>,
+    /// Executions running on the SSH remote, with the local workspace their
+    /// copy is brought back to
+    remote_executions: Arc<DashMap<Uuid, (SshRemote, PathBuf)>>,
+    /// Repos whose next auto-commit only includes what the user staged
+    staged_selections: StagedSelections,
 }
 
 impl LocalContainerService {
@@ -161,6 +243,9 @@ impl LocalContainerService {
         publisher: Result<SharePublisher, RemoteClientNotConfigured>,
         skills_cache: GlobalSkillsCache,
         hook_execution_store: HookExecutionStore,
+        operations: OperationTracker,
+        secrets: SecretService,
+        embedding: EmbeddingService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
@@ -289,6 +374,44 @@ impl LocalContainerService {
 
                             Ok(review_exec.id)
                         }
+                        ExecutionTrigger::ProjectMemory {
+                            task_id,
+                            execution_process_id,
+                        } => {
+                            let ctx = ExecutionProcess::load_context(
+                                &container.db.pool,
+                                execution_process_id,
+                            )
+                            .await
+                            .map_err(|e| anyhow!("Failed to load execution context: {e}"))?;
+
+                            let agent_session_id =
+                                ExecutionProcess::find_latest_coding_agent_turn_session_id(
+                                    &container.db.pool,
+                                    ctx.session.id,
+                                )
+                                .await
+                                .map_err(|e| anyhow!("Failed to query agent session ID: {e}"))?
+                                .ok_or_else(|| {
+                                    anyhow!(
+                                        "No agent session ID found for session {}, cannot extract project memories",
+                                        ctx.session.id
+                                    )
+                                })?;
+
+                            let memory_exec = container
+                                .collect_project_memories(&ctx, &agent_session_id)
+                                .await
+                                .map_err(|e| {
+                                    anyhow!(
+                                        "Failed to start memory extraction for task {}: {}",
+                                        task_id,
+                                        e
+                                    )
+                                })?;
+
+                            Ok(memory_exec.id)
+                        }
                         ExecutionTrigger::ProcessQueue => {
                             // Process the execution queue to start any waiting workspaces
                             container.process_queue().await.map_err(|e| {
@@ -308,12 +431,18 @@ impl LocalContainerService {
         let event_dispatcher = Arc::new(
             DispatcherBuilder::new()
                 .with_handler(WebSocketBroadcastHandler::new())
-                .with_handler(NotificationHandler::new(notification_service.clone()))
+                .with_handler(NotificationHandler::new(
+                    notification_service.clone(),
+                    secrets.clone(),
+                ))
+                .with_handler(TaskWatcherHandler::new())
                 .with_handler(AutopilotHandler::new())
                 .with_handler(RemoteSyncHandler::new(publisher.clone().ok()))
                 .with_handler(ReviewAttentionHandler::new())
+                .with_handler(ProjectMemoryHandler::new())
                 .with_handler(HookExecutionUpdaterHandler::new())
                 .with_handler(FeedbackCollectionHandler::new(db.clone()))
+                .with_handler(WebhookHandler::new(secrets.clone()))
                 .with_context(HandlerContext::new(
                     db.clone(),
                     config.clone(),
@@ -325,6 +454,7 @@ impl LocalContainerService {
                 .build(),
         );
 
+        let operation_status = Arc::new(RwLock::new(None));
         let container = LocalContainerService {
             db,
             child_store,
@@ -343,14 +473,26 @@ impl LocalContainerService {
             feedback_pending_cleanup,
             running_workspaces,
             merge_queue_store: Arc::new(RwLock::new(None)),
-            operation_status: Arc::new(RwLock::new(None)),
+            operation_status: operation_status.clone(),
+            operations,
+            secrets,
             event_dispatcher,
+            resource_monitor: ResourceMonitor::new(),
+            agent_auth: AgentAuthMonitor::new(),
+            provider_rate_limiter: ProviderRateLimiter::new(operation_status),
+            embedding,
+            file_rule_interrupts: Arc::new(DashSet::new()),
+            remote_executions: Arc::new(DashMap::new()),
+            staged_selections: StagedSelections::new(),
         };
 
         // Initialize the late-bound container reference so the callback can use it
         *container_ref.write().await = Some(container.clone());
 
         container.spawn_workspace_cleanup().await;
+        container.spawn_resource_sampler();
+        container.agent_auth.spawn();
+        container.spawn_queue_retry();
 
         container
     }
@@ -367,6 +509,83 @@ impl LocalContainerService {
         *self.operation_status.write().await = Some(operation_status);
     }
 
+    /// Start a merge queue processor for a project unless one is already
+    /// running for it.
+    pub fn spawn_merge_queue_processor(
+        &self,
+        project_id: Uuid,
+        merge_queue_store: MergeQueueStore,
+        operation_status: OperationStatusStore,
+    ) {
+        let should_spawn = {
+            let mut active = match ACTIVE_MERGE_PROCESSORS.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => {
+                    tracing::error!("ACTIVE_MERGE_PROCESSORS mutex poisoned");
+                    poisoned.into_inner()
+                }
+            };
+            active.insert(project_id)
+        };
+        if !should_spawn {
+            tracing::debug!(
+                project_id = %project_id,
+                "Merge queue processor already running for project"
+            );
+            return;
+        }
+
+        tracing::info!(project_id = %project_id, "Spawning merge queue processor");
+
+        let processor = MergeQueueProcessor::with_operation_status(
+            self.db.pool.clone(),
+            self.git.clone(),
+            merge_queue_store,
+            operation_status,
+            self.config.clone(),
+        );
+        let event_dispatcher = self.event_dispatcher.clone();
+        let event_dispatch_callback: EventDispatchCallback = Arc::new(move |event: DomainEvent| {
+            let dispatcher = event_dispatcher.clone();
+            Box::pin(async move {
+                dispatcher.dispatch(event).await;
+            })
+        });
+        let processor = processor
+            .with_operations(self.operations.clone())
+            .with_event_dispatcher(event_dispatch_callback);
+
+        tokio::spawn(async move {
+            if let Err(e) = processor.process_project_queue(project_id).await {
+                tracing::error!(
+                    %project_id,
+                    error = %e,
+                    "Failed to process merge queue"
+                );
+            }
+            // Always remove from active set when done
+            if let Ok(mut active) = ACTIVE_MERGE_PROCESSORS.lock() {
+                active.remove(&project_id);
+            }
+        });
+    }
+
+    /// Restart processing of merge queues reloaded from the database, e.g.
+    /// after a restart.
+    pub fn resume_merge_queues(
+        &self,
+        merge_queue_store: &MergeQueueStore,
+        operation_status: &OperationStatusStore,
+    ) {
+        for project_id in merge_queue_store.projects_with_queued_entries() {
+            self.spawn_merge_queue_processor(
+                project_id,
+                merge_queue_store.clone(),
+                operation_status.clone(),
+            );
+        }
+    }
+
     pub async fn get_child_from_store(&self, id: &Uuid) -> Option<Arc<RwLock<AsyncGroupChild>>> {
         let map = self.child_store.read().await;
         map.get(id).cloned()
@@ -408,7 +627,7 @@ impl LocalContainerService {
                 workspace.id
             );
             if workspace_dir.exists()
-                && let Err(e) = tokio::fs::remove_dir_all(&workspace_dir).await
+                && let Err(e) = remove_dir_all_async(&workspace_dir).await
             {
                 tracing::warn!("Failed to remove workspace directory: {}", e);
             }
@@ -424,6 +643,8 @@ impl LocalContainerService {
                 });
         }
 
+        devcontainer::remove_container(workspace.id).await;
+
         // Clear container_ref so this workspace won't be picked up again
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
     }
@@ -457,137 +678,647 @@ impl LocalContainerService {
                     .unwrap_or_else(|e| {
                         tracing::error!("Failed to clean up expired workspaces: {}", e)
                     });
+                match ConversationService::cleanup_expired_worktrees(&db.pool).await {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        tracing::info!("Cleaned up worktrees of {} idle conversations", count)
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to clean up expired conversation worktrees: {}", e)
+                    }
+                }
             }
         });
     }
 
-    /// Record the current HEAD commit for each repository as the "after" state.
-    /// Errors are silently ignored since this runs after the main execution completes
-    /// and failure should not block process finalization.
-    async fn update_after_head_commits(&self, exec_id: Uuid) {
-        if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, exec_id).await {
-            let workspace_root = self.workspace_to_current_dir(&ctx.workspace);
-            for repo in &ctx.repos {
-                let repo_path = workspace_root.join(&repo.name);
-                if let Ok(head) = self.git().get_head_info(&repo_path) {
-                    let _ = ExecutionProcessRepoState::update_after_head_commit(
-                        &self.db.pool,
-                        exec_id,
-                        repo.id,
-                        &head.oid,
-                    )
-                    .await;
+    /// Retry the execution queue every QUEUE_RETRY_INTERVAL while it has entries.
+    /// Finished executions already trigger a retry; this covers rate limit
+    /// windows expiring with nothing finishing.
+    pub fn spawn_queue_retry(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(QUEUE_RETRY_INTERVAL);
+            loop {
+                interval.tick().await;
+                match ExecutionQueue::count(&container.db.pool).await {
+                    Ok(0) => continue,
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!("Failed to count execution queue: {}", e);
+                        continue;
+                    }
+                }
+                if let Err(e) = container.process_queue().await {
+                    tracing::error!("Failed to process execution queue: {}", e);
                 }
             }
-        }
+        });
     }
 
-    /// Get the commit message based on the execution run reason.
-    async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
-        match ctx.execution_process.run_reason {
-            ExecutionProcessRunReason::CodingAgent => {
-                // Try to retrieve the task summary from the coding agent turn
-                // otherwise fallback to default message
-                match CodingAgentTurn::find_by_execution_process_id(
-                    &self.db().pool,
-                    ctx.execution_process.id,
-                )
-                .await
-                {
-                    Ok(Some(turn)) if turn.summary.is_some() => turn.summary.unwrap(),
-                    Ok(_) => {
-                        tracing::debug!(
-                            "No summary found for execution process {}, using default message",
-                            ctx.execution_process.id
-                        );
-                        format!(
-                            "Commit changes from coding agent for workspace {}",
-                            ctx.workspace.id
-                        )
+    /// Sample CPU/RSS of every running execution's process group every
+    /// RESOURCE_SAMPLE_INTERVAL, persisting each aggregate as a time series.
+    /// Samples older than RESOURCE_SAMPLE_RETENTION are deleted periodically.
+    pub fn spawn_resource_sampler(&self) {
+        let child_store = self.child_store.clone();
+        let monitor = self.resource_monitor.clone();
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RESOURCE_SAMPLE_INTERVAL);
+            let mut last_prune: Option<Instant> = None;
+            loop {
+                interval.tick().await;
+                if last_prune.is_none_or(|at| at.elapsed() >= RESOURCE_SAMPLE_PRUNE_INTERVAL) {
+                    last_prune = Some(Instant::now());
+                    match ExecutionProcessResourceSample::delete_older_than(
+                        &db.pool,
+                        RESOURCE_SAMPLE_RETENTION,
+                    )
+                    .await
+                    {
+                        Ok(0) => {}
+                        Ok(n) => tracing::debug!("Deleted {} expired resource samples", n),
+                        Err(e) => {
+                            tracing::warn!("Failed to delete expired resource samples: {}", e)
+                        }
                     }
-                    Err(e) => {
-                        tracing::debug!(
-                            "Failed to retrieve summary for execution process {}: {}",
-                            ctx.execution_process.id,
+                }
+                let roots: Vec<(Uuid, u32)> = {
+                    let map = child_store.read().await;
+                    let mut roots = Vec::with_capacity(map.len());
+                    for (exec_id, child) in map.iter() {
+                        // Skip children that are being waited on or killed
+                        let Ok(child) = child.try_read() else {
+                            continue;
+                        };
+                        if let Some(pid) = child.inner().id() {
+                            roots.push((*exec_id, pid));
+                        }
+                    }
+                    roots
+                };
+
+                let sampler = monitor.clone();
+                let samples =
+                    match tokio::task::spawn_blocking(move || sampler.sample(&roots)).await {
+                        Ok(samples) => samples,
+                        Err(e) => {
+                            tracing::warn!("Resource sampling task failed: {}", e);
+                            continue;
+                        }
+                    };
+                for sample in samples {
+                    if let Err(e) = ExecutionProcessResourceSample::create(
+                        &db.pool,
+                        sample.execution_process_id,
+                        f64::from(sample.cpu_percent),
+                        sample.rss_bytes as i64,
+                        sample.process_count as i64,
+                        sample.sampled_at,
+                    )
+                    .await
+                    {
+                        tracing::warn!(
+                            "Failed to store resource sample for {}: {}",
+                            sample.execution_process_id,
                             e
                         );
-                        format!(
-                            "Commit changes from coding agent for workspace {}",
-                            ctx.workspace.id
-                        )
                     }
                 }
             }
-            ExecutionProcessRunReason::CleanupScript => {
-                format!("Cleanup script changes for workspace {}", ctx.workspace.id)
-            }
-            _ => format!(
-                "Changes from execution process {}",
-                ctx.execution_process.id
-            ),
-        }
+        });
     }
 
-    /// Check which repos have uncommitted changes. Fails if any repo is inaccessible.
-    fn check_repos_for_changes(
-        &self,
-        workspace_root: &Path,
-        repos: &[Repo],
-    ) -> Result<Vec<(Repo, PathBuf)>, ContainerError> {
-        let git = GitCli::new();
-        let mut repos_with_changes = Vec::new();
+    /// Watch a coding agent's log for loops and runaway output, interrupting
+    /// the execution once a threshold for its profile is crossed
+    fn spawn_anomaly_monitor(&self, exec_id: Uuid, profile: ExecutorProfileId) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let thresholds = {
+                let config = container.config.read().await;
+                if !config.anomaly_detection.enabled {
+                    return;
+                }
+                config.anomaly_detection.thresholds_for(&profile).clone()
+            };
+            // Only the stream is kept so the store can still be torn down
+            let Some(mut stream) = container
+                .msg_stores
+                .read()
+                .await
+                .get(&exec_id)
+                .map(|store| store.history_plus_stream())
+            else {
+                return;
+            };
 
-        for repo in repos {
-            let worktree_path = workspace_root.join(&repo.name);
+            let mut detector = AnomalyDetector::new(thresholds);
+            let anomaly = loop {
+                match stream.next().await {
+                    Some(Ok(LogMsg::Finished)) | None => return,
+                    Some(Ok(msg)) => {
+                        if let Some(anomaly) = detector.observe(&msg) {
+                            break anomaly;
+                        }
+                    }
+                    Some(Err(_)) => continue,
+                }
+            };
+            drop(stream);
+            container.interrupt_for_anomaly(exec_id, anomaly).await;
+        });
+    }
 
-            match git.has_changes(&worktree_path) {
-                Ok(true) => {
-                    repos_with_changes.push((repo.clone(), worktree_path));
+    /// Interrupt a coding agent that produces no output for its profile's
+    /// inactivity timeout, or runs past its maximum duration. Time spent
+    /// waiting for the user to answer an approval doesn't count as inactive.
+    fn spawn_activity_watchdog(&self, exec_id: Uuid) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let Ok(Some(process)) = ExecutionProcess::find_by_id(&container.db.pool, exec_id).await
+            else {
+                return;
+            };
+            let profile = match process.executor_action().map(|action| action.typ().clone()) {
+                Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => {
+                    request.executor_profile_id
                 }
-                Ok(false) => {
-                    tracing::debug!("No changes in repo '{}'", repo.name);
+                Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => {
+                    request.executor_profile_id
                 }
-                Err(e) => {
-                    return Err(ContainerError::Other(anyhow!(
-                        "Pre-flight check failed for repo '{}': {}",
-                        repo.name,
-                        e
-                    )));
+                _ => return,
+            };
+            let thresholds = {
+                let config = container.config.read().await;
+                if !config.anomaly_detection.enabled {
+                    return;
                 }
-            }
-        }
+                config.anomaly_detection.thresholds_for(&profile).clone()
+            };
+            let Some(mut messages) = container
+                .msg_stores
+                .read()
+                .await
+                .get(&exec_id)
+                .map(|store| store.get_receiver())
+            else {
+                return;
+            };
 
-        Ok(repos_with_changes)
+            let mut clock = ActivityClock::new(&thresholds, Instant::now());
+            let anomaly = loop {
+                let Some(deadline) = clock.next_deadline() else {
+                    return;
+                };
+                tokio::select! {
+                    msg = messages.recv() => match msg {
+                        Ok(LogMsg::Finished) | Err(RecvError::Closed) => return,
+                        // Lagging behind still means there was output
+                        Ok(_) | Err(RecvError::Lagged(_)) => clock.touch(Instant::now()),
+                    },
+                    _ = tokio::time::sleep_until(deadline.into()) => {
+                        let now = Instant::now();
+                        if container
+                            .approvals
+                            .pending_execution_process_ids()
+                            .contains(&exec_id)
+                        {
+                            clock.touch(now);
+                            tokio::time::sleep(APPROVAL_WAIT_RECHECK_INTERVAL).await;
+                            continue;
+                        }
+                        if let Some(anomaly) = clock.check(now) {
+                            break anomaly;
+                        }
+                    }
+                }
+            };
+            drop(messages);
+            container.interrupt_for_anomaly(exec_id, anomaly).await;
+        });
     }
 
-    /// Commit changes to each repo. Logs failures but continues with other repos.
-    fn commit_repos(&self, repos_with_changes: Vec<(Repo, PathBuf)>, message: &str) -> bool {
-        let mut any_committed = false;
-
-        for (repo, worktree_path) in repos_with_changes {
-            tracing::debug!(
-                "Committing changes for repo '{}' at {:?}",
-                repo.name,
-                &worktree_path
-            );
-
-            match self.git().commit(&worktree_path, message) {
-                Ok(true) => {
-                    any_committed = true;
-                    tracing::info!("Committed changes in repo '{}'", repo.name);
-                }
-                Ok(false) => {
-                    tracing::warn!("No changes committed in repo '{}' (unexpected)", repo.name);
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to commit in repo '{}': {}", repo.name, e);
-                }
+    async fn interrupt_for_anomaly(&self, exec_id: Uuid, anomaly: Anomaly) {
+        let ctx = match ExecutionProcess::load_context(&self.db.pool, exec_id).await {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load context for stuck execution {}: {}",
+                    exec_id,
+                    e
+                );
+                return;
             }
+        };
+        if ctx.execution_process.status != ExecutionProcessStatus::Running {
+            return;
         }
 
-        any_committed
-    }
-
+        tracing::warn!("Interrupting execution {}: {}", exec_id, anomaly);
+        // Leave the reason in the conversation before the store is closed
+        if let Some(store) = self.msg_stores.read().await.get(&exec_id) {
+            store.push_stderr(format!("Interrupted by Vibe Kanban: {anomaly}\n"));
+        }
+        if let Err(e) = self
+            .stop_execution(&ctx.execution_process, ExecutionProcessStatus::Killed)
+            .await
+        {
+            tracing::error!("Failed to interrupt stuck execution {}: {}", exec_id, e);
+            return;
+        }
+
+        let title = format!("Agent Stopped: {}", ctx.task.title);
+        let message = format!("'{}' was interrupted because {}", ctx.task.title, anomaly);
+        self.notification_service
+            .notify_error(&title, &message)
+            .await;
+        if let Err(e) = NotificationService::notify_agent_anomaly(
+            &self.db.pool,
+            ctx.project.id,
+            ctx.workspace.id,
+            &ctx.task.title,
+            &anomaly.to_string(),
+        )
+        .await
+        {
+            tracing::warn!("Failed to create in-app anomaly notification: {}", e);
+        }
+    }
+
+    /// Check a coding agent's changes against its project's file rules while
+    /// it runs, interrupting it at the first violation
+    fn spawn_file_rule_monitor(&self, exec_id: Uuid) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let Ok(ctx) = ExecutionProcess::load_context(&container.db.pool, exec_id).await else {
+                return;
+            };
+            let Some(rules) = container.file_rules_for(&ctx).await.map(Arc::new) else {
+                return;
+            };
+
+            let mut interval = tokio::time::interval(FILE_RULE_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                match ExecutionProcess::find_by_id(&container.db.pool, exec_id).await {
+                    Ok(Some(process)) if process.status == ExecutionProcessStatus::Running => {}
+                    _ => return,
+                }
+                let violations = container.file_rule_violations(&ctx, &rules).await;
+                if !violations.is_empty() {
+                    container.interrupt_for_file_rules(&ctx, &violations).await;
+                    return;
+                }
+            }
+        });
+    }
+
+    /// The project's file rules that apply to a coding agent run. None when
+    /// there are none, or when the run is already the follow-up for a
+    /// violation, so an agent that cannot comply is not interrupted in a loop.
+    async fn file_rules_for(&self, ctx: &ExecutionContext) -> Option<FileRules> {
+        let prompt = match ctx
+            .execution_process
+            .executor_action()
+            .map(|action| action.typ().clone())
+        {
+            Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => request.prompt,
+            Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => request.prompt,
+            _ => return None,
+        };
+        if file_rules::is_feedback_prompt(&prompt) {
+            return None;
+        }
+        let rules = match ProjectFileRule::find_by_project_id(&self.db.pool, ctx.project.id).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::warn!("Failed to load file rules: {}", e);
+                return None;
+            }
+        };
+        FileRules::for_task(&rules, &ctx.task)
+    }
+
+    /// Paths changed since the run started, in any of the workspace's repos,
+    /// that break `rules`
+    async fn file_rule_violations(
+        &self,
+        ctx: &ExecutionContext,
+        rules: &Arc<FileRules>,
+    ) -> Vec<FileRuleViolation> {
+        let Some(container_ref) = ctx.workspace.container_ref.as_ref() else {
+            return Vec::new();
+        };
+        let workspace_root = PathBuf::from(container_ref);
+        let repo_states = match ExecutionProcessRepoState::find_by_execution_process_id(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(states) => states,
+            Err(e) => {
+                tracing::warn!("Failed to load repo states for file rules: {}", e);
+                return Vec::new();
+            }
+        };
+        let targets: Vec<(String, PathBuf, Commit)> = ctx
+            .repos
+            .iter()
+            .filter_map(|repo| {
+                let base_commit = repo_states
+                    .iter()
+                    .find(|state| state.repo_id == repo.id)
+                    .and_then(|state| state.before_head_commit.as_deref())?;
+                Some((
+                    repo.name.clone(),
+                    workspace_root.join(&repo.name),
+                    Commit::new(base_commit),
+                ))
+            })
+            .collect();
+
+        let git = self.git().clone();
+        let rules = rules.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut violations = Vec::new();
+            for (repo_name, worktree_path, base_commit) in targets {
+                match git.get_changed_paths(&worktree_path, &base_commit) {
+                    Ok(paths) => violations.extend(rules.check(&repo_name, &paths)),
+                    Err(e) => tracing::warn!(
+                        "Failed to list changed files in repo '{}': {}",
+                        repo_name,
+                        e
+                    ),
+                }
+            }
+            violations
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    async fn interrupt_for_file_rules(
+        &self,
+        ctx: &ExecutionContext,
+        violations: &[FileRuleViolation],
+    ) {
+        let exec_id = ctx.execution_process.id;
+        let paths = violations
+            .iter()
+            .map(|violation| format!("{}/{}", violation.repo_name, violation.path))
+            .collect::<Vec<_>>()
+            .join(", ");
+        tracing::warn!(
+            "Interrupting execution {}: changes to {} break the project's file rules",
+            exec_id,
+            paths
+        );
+        if let Some(store) = self.msg_stores.read().await.get(&exec_id) {
+            store.push_stderr(format!(
+                "Interrupted by Vibe Kanban: changes to {paths} break the project's file rules\n"
+            ));
+        }
+        self.file_rule_interrupts.insert(exec_id);
+        if let Err(e) = self
+            .stop_execution(&ctx.execution_process, ExecutionProcessStatus::Killed)
+            .await
+        {
+            self.file_rule_interrupts.remove(&exec_id);
+            tracing::error!("Failed to interrupt execution {}: {}", exec_id, e);
+        }
+    }
+
+    /// Send file rule violations back to the agent as a follow-up, for runs
+    /// that completed or were interrupted for them. Returns true when the
+    /// follow-up was scheduled; the run is then neither committed nor
+    /// finalized.
+    async fn follow_up_file_rule_violations(&self, ctx: &ExecutionContext) -> bool {
+        let interrupted = self
+            .file_rule_interrupts
+            .remove(&ctx.execution_process.id)
+            .is_some();
+        if !interrupted && ctx.execution_process.status != ExecutionProcessStatus::Completed {
+            return false;
+        }
+        let Some(rules) = self.file_rules_for(ctx).await.map(Arc::new) else {
+            return false;
+        };
+        let violations = self.file_rule_violations(ctx, &rules).await;
+        if violations.is_empty() {
+            return false;
+        }
+        let variant = match ctx
+            .execution_process
+            .executor_action()
+            .map(|action| action.typ().clone())
+        {
+            Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => {
+                request.executor_profile_id.variant
+            }
+            Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => {
+                request.executor_profile_id.variant
+            }
+            _ => return false,
+        };
+
+        let follow_up = DraftFollowUpData {
+            message: file_rules::feedback_prompt(&violations),
+            variant,
+        };
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            // This run's exit monitor releases the workspace just after
+            // this returns
+            if !container
+                .wait_for_workspace_idle(ctx.workspace.id, Duration::from_secs(30))
+                .await
+            {
+                return;
+            }
+            if let Err(e) = container.start_queued_follow_up(&ctx, &follow_up).await {
+                tracing::error!("Failed to send file rule violations to the agent: {}", e);
+                container
+                    .finalize_task(container.publisher.as_ref().ok(), &ctx)
+                    .await;
+            }
+        });
+        true
+    }
+
+    /// Record the current HEAD commit for each repository as the "after" state.
+    /// Errors are silently ignored since this runs after the main execution completes
+    /// and failure should not block process finalization.
+    async fn update_after_head_commits(&self, exec_id: Uuid) {
+        if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, exec_id).await {
+            let workspace_root = self.workspace_to_current_dir(&ctx.workspace);
+            for repo in &ctx.repos {
+                let repo_path = workspace_root.join(&repo.name);
+                if let Ok(head) = self.git().get_head_info(&repo_path) {
+                    let _ = ExecutionProcessRepoState::update_after_head_commit(
+                        &self.db.pool,
+                        exec_id,
+                        repo.id,
+                        &head.oid,
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
+    /// Get the commit message based on the execution run reason.
+    async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
+        match ctx.execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent => {
+                // Try to retrieve the task summary from the coding agent turn
+                // otherwise fallback to default message
+                match CodingAgentTurn::find_by_execution_process_id(
+                    &self.db().pool,
+                    ctx.execution_process.id,
+                )
+                .await
+                {
+                    Ok(Some(turn)) if turn.summary.is_some() => turn.summary.unwrap(),
+                    Ok(_) => {
+                        tracing::debug!(
+                            "No summary found for execution process {}, using default message",
+                            ctx.execution_process.id
+                        );
+                        format!(
+                            "Commit changes from coding agent for workspace {}",
+                            ctx.workspace.id
+                        )
+                    }
+                    Err(e) => {
+                        tracing::debug!(
+                            "Failed to retrieve summary for execution process {}: {}",
+                            ctx.execution_process.id,
+                            e
+                        );
+                        format!(
+                            "Commit changes from coding agent for workspace {}",
+                            ctx.workspace.id
+                        )
+                    }
+                }
+            }
+            ExecutionProcessRunReason::CleanupScript => {
+                format!("Cleanup script changes for workspace {}", ctx.workspace.id)
+            }
+            _ => format!(
+                "Changes from execution process {}",
+                ctx.execution_process.id
+            ),
+        }
+    }
+
+    /// Check which repos have uncommitted changes. Fails if any repo is inaccessible.
+    fn check_repos_for_changes(
+        &self,
+        workspace_root: &Path,
+        repos: &[Repo],
+    ) -> Result<Vec<(Repo, PathBuf)>, ContainerError> {
+        let git = GitCli::new();
+        let mut repos_with_changes = Vec::new();
+
+        for repo in repos {
+            let worktree_path = workspace_root.join(&repo.name);
+
+            match git.has_changes(&worktree_path) {
+                Ok(true) => {
+                    repos_with_changes.push((repo.clone(), worktree_path));
+                }
+                Ok(false) => {
+                    tracing::debug!("No changes in repo '{}'", repo.name);
+                }
+                Err(e) => {
+                    return Err(ContainerError::Other(anyhow!(
+                        "Pre-flight check failed for repo '{}': {}",
+                        repo.name,
+                        e
+                    )));
+                }
+            }
+        }
+
+        Ok(repos_with_changes)
+    }
+
+    /// Commit changes to each repo. Logs failures but continues with other repos.
+    /// Repos in `staged_repo_ids` only commit what the user staged. Returns
+    /// whether anything was committed and the output of hooks that failed,
+    /// by repo name.
+    fn commit_repos(
+        &self,
+        repos_with_changes: Vec<(Repo, PathBuf, CommitHooks)>,
+        staged_repo_ids: &HashSet<Uuid>,
+        message: &str,
+    ) -> (bool, Vec<(String, String)>) {
+        let mut any_committed = false;
+        let mut hook_failures = Vec::new();
+
+        for (repo, worktree_path, hooks) in repos_with_changes {
+            tracing::debug!(
+                "Committing changes for repo '{}' at {:?}",
+                repo.name,
+                &worktree_path
+            );
+
+            let result = if staged_repo_ids.contains(&repo.id) {
+                self.git()
+                    .commit_staged_with_hooks(&worktree_path, message, &hooks)
+            } else {
+                self.git()
+                    .commit_with_hooks(&worktree_path, message, &hooks)
+            };
+            match result {
+                Ok(commit) if commit.committed => {
+                    any_committed = true;
+                    tracing::info!("Committed changes in repo '{}'", repo.name);
+                    if let Some(output) = commit.hook_failure {
+                        hook_failures.push((repo.name.clone(), output));
+                    }
+                }
+                Ok(_) => {
+                    tracing::warn!("No changes committed in repo '{}' (unexpected)", repo.name);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to commit in repo '{}': {}", repo.name, e);
+                }
+            }
+        }
+
+        (any_committed, hook_failures)
+    }
+
+    /// Record hooks that failed on an execution's commit, so the run shows
+    /// what was committed without them
+    async fn record_hook_failures(&self, ctx: &ExecutionContext, failures: &[(String, String)]) {
+        let output = failures
+            .iter()
+            .map(|(repo_name, output)| format!("{repo_name}:\n{output}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let reason = FailureReason::GitHooks;
+        if let Err(e) = ExecutionProcessFailure::upsert(
+            &self.db.pool,
+            ctx.execution_process.id,
+            reason,
+            Some(truncate_output(&output, MAX_HOOK_OUTPUT_CHARS).as_str()),
+            &suggested_fix(reason, None),
+        )
+        .await
+        {
+            tracing::error!(
+                "Failed to record git hook failures of {}: {}",
+                ctx.execution_process.id,
+                e
+            );
+        }
+    }
+
     /// Spawn a background task that polls the child process for completion and
     /// cleans up the execution entry when it exits.
     pub fn spawn_exit_monitor(
@@ -609,6 +1340,9 @@ impl LocalContainerService {
         let running_workspaces = self.running_workspaces.clone();
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
+        if matches!(run_reason, ExecutionProcessRunReason::CodingAgent) {
+            self.spawn_activity_watchdog(exec_id);
+        }
 
         tokio::spawn(async move {
             let mut exit_signal_future = exit_signal
@@ -687,6 +1421,8 @@ impl LocalContainerService {
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
 
+            container.sync_back_from_remote(exec_id).await;
+
             if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
                 && let Err(e) =
                     ExecutionProcess::update_completion(&db.pool, exec_id, status, exit_code).await
@@ -695,6 +1431,9 @@ impl LocalContainerService {
             }
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                let failure = container.record_failure_reason(&ctx).await;
+                container.observe_agent_auth(&ctx, failure.as_ref());
+
                 // Emit ExecutionCompleted event for handlers
                 container
                     .event_dispatcher
@@ -709,6 +1448,18 @@ impl LocalContainerService {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
+                container.audit_permissions(&ctx).await;
+
+                // File rule violations go back to the agent before anything
+                // is committed
+                let rule_follow_up = container.follow_up_file_rule_violations(&ctx).await;
+
+                // Transient failures are re-run instead of finalizing the task,
+                // and follow-ups whose agent session is gone start a new one
+                let retry_scheduled = rule_follow_up
+                    || container.recover_stale_session(&ctx).await
+                    || container.schedule_retry_if_transient(&ctx).await;
+
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
@@ -722,7 +1473,10 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Running
                 );
 
-                if success || cleanup_done {
+                // Set when pre-commit failures were sent back to the agent
+                let mut validation_follow_up = false;
+
+                if (success || cleanup_done) && !rule_follow_up {
                     // Commit changes (if any) and get feedback about whether changes were made
                     let changes_committed = match container.try_commit_changes(&ctx).await {
                         Ok(committed) => committed,
@@ -733,7 +1487,14 @@ impl LocalContainerService {
                         }
                     };
 
-                    let should_start_next = if matches!(
+                    if success && changes_committed {
+                        validation_follow_up = container.validate_with_pre_commit(&ctx).await;
+                    }
+
+                    let should_start_next = if validation_follow_up {
+                        // The follow-up carries the cleanup action instead
+                        false
+                    } else if matches!(
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
                     ) {
@@ -747,7 +1508,7 @@ impl LocalContainerService {
                         if let Err(e) = container.try_start_next_action(&ctx).await {
                             tracing::error!("Failed to start next action after completion: {}", e);
                         }
-                    } else {
+                    } else if !validation_follow_up {
                         tracing::info!(
                             "Skipping cleanup script for workspace {} - no changes made by coding agent",
                             ctx.workspace.id
@@ -758,7 +1519,7 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                if !retry_scheduled && !validation_follow_up && container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
                     let should_execute_queued = !matches!(
@@ -930,25 +1691,214 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    /// Point `env` at the workspace's devcontainer, streaming setup progress
+    /// into the execution's log. The container runs `image` on `network` when
+    /// given, and otherwise comes from the workspace's devcontainer.json;
+    /// without one, or without Docker, the execution runs on the host. An
+    /// explicit image requires Docker.
+    async fn enter_devcontainer(
+        &self,
+        workspace_id: Uuid,
+        exec_id: Uuid,
+        current_dir: &Path,
+        repos: &[Repo],
+        image: Option<&str>,
+        network: Option<&str>,
+        env: &mut ExecutionEnv,
+    ) -> Result<(), ContainerError> {
+        let config_path = match image {
+            Some(_) => None,
+            None => {
+                let mut dirs = vec![current_dir.to_path_buf()];
+                dirs.extend(repos.iter().map(|r| current_dir.join(&r.name)));
+                let Some(config_path) = Devcontainer::find(&dirs) else {
+                    tracing::debug!(
+                        "No devcontainer.json in workspace {}, running on the host",
+                        workspace_id
+                    );
+                    return Ok(());
+                };
+                Some(config_path)
+            }
+        };
+
         let store = Arc::new(MsgStore::new());
+        self.msg_stores.write().await.insert(exec_id, store.clone());
+        let log = DevcontainerLog::new(store);
 
-        let out = child.inner().stdout.take().expect("no stdout");
-        let err = child.inner().stderr.take().expect("no stderr");
+        if !devcontainer::docker_available().await {
+            if let Some(image) = image {
+                if let Some(store) = self.msg_stores.write().await.remove(&exec_id) {
+                    store.push_finished();
+                }
+                return Err(ContainerError::Other(anyhow!(
+                    "Docker is not available to run image {image}"
+                )));
+            }
+            log.status("Docker is not available, running on the host instead");
+            return Ok(());
+        }
+
+        // Worktrees point back into their repo's .git directory
+        let mut mounts = vec![current_dir.to_path_buf()];
+        mounts.extend(
+            repos
+                .iter()
+                .map(|r| r.path.join(".git"))
+                .filter(|git_dir| git_dir.is_dir()),
+        );
 
-        // Map stdout bytes -> LogMsg::Stdout
-        let out = ReaderStream::new(out)
-            .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+        let prefix = async {
+            let devcontainer = match &config_path {
+                Some(config_path) => Devcontainer::load(config_path).await?,
+                None => Devcontainer::from_image(image.unwrap_or_default(), network),
+            };
+            let container = devcontainer
+                .ensure_container(workspace_id, &mounts, &log)
+                .await?;
+            log.status(format!("Running in devcontainer {container}"));
+            Ok::<_, DevcontainerError>(devcontainer.exec_prefix(
+                &container,
+                current_dir,
+                env.vars.keys(),
+            ))
+        }
+        .await;
+
+        match prefix {
+            Ok(prefix) => {
+                env.command_prefix = prefix;
+                Ok(())
+            }
+            Err(e) => {
+                if let Some(store) = self.msg_stores.write().await.remove(&exec_id) {
+                    store.push_finished();
+                }
+                Err(ContainerError::Other(anyhow!(
+                    "Devcontainer setup failed: {e}"
+                )))
+            }
+        }
+    }
 
-        // Map stderr bytes -> LogMsg::Stderr
-        let err = ReaderStream::new(err)
-            .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+    /// The SSH remote executions run on, if one is configured
+    async fn ssh_remote(&self) -> Result<Option<SshRemote>, ContainerError> {
+        let config = self.config.read().await.ssh_remote.clone();
+        SshRemote::from_config(&config)
+            .map_err(|e| ContainerError::Other(anyhow!("Invalid SSH remote: {e}")))
+    }
 
-        // If you have a JSON Patch source, map it to LogMsg::JsonPatch too, then select all three.
+    /// Copy the workspace to the SSH remote and run the execution there. The
+    /// copy comes back when the execution exits.
+    async fn enter_ssh_remote(
+        &self,
+        remote: SshRemote,
+        exec_id: Uuid,
+        current_dir: &Path,
+        working_dir: Option<&str>,
+        env: &mut ExecutionEnv,
+    ) -> Result<(), ContainerError> {
+        let remote_dir = remote.remote_dir(current_dir);
+        let synced = async {
+            SshRemote::check_tools().await?;
+            remote.sync_to_remote(current_dir, &remote_dir).await
+        }
+        .await;
+        if let Err(e) = synced {
+            return Err(ContainerError::Other(anyhow!(
+                "Copying the workspace to the SSH remote failed: {e}"
+            )));
+        }
 
-        // Merge and forward into the store
-        let merged = select(out, err); // Stream<Item = Result<LogMsg, io::Error>>
-        store.clone().spawn_forwarder(merged);
+        let prefix = remote.command_prefix(
+            &remote_dir,
+            exec_id,
+            working_dir,
+            env.vars.keys(),
+            &env.command_prefix,
+        );
+        env.command_prefix = prefix;
+        self.remote_executions
+            .insert(exec_id, (remote, current_dir.to_path_buf()));
+        Ok(())
+    }
+
+    /// Copy a remote execution's workspace back before its exit is handled
+    async fn sync_back_from_remote(&self, exec_id: Uuid) {
+        let Some((_, (remote, local_dir))) = self.remote_executions.remove(&exec_id) else {
+            return;
+        };
+        let remote_dir = remote.remote_dir(&local_dir);
+        if let Err(e) = remote.sync_from_remote(&remote_dir, &local_dir).await {
+            tracing::error!(
+                "Failed to copy workspace back from the SSH remote for execution {}: {}",
+                exec_id,
+                e
+            );
+        }
+    }
+
+    /// Masks secrets in the output of an execution for `project_id`; None
+    /// when there is nothing to mask
+    async fn output_redactor(&self, project_id: Option<Uuid>) -> Option<Arc<OutputRedactor>> {
+        let config = self.config.read().await.output_redaction.clone();
+        if !config.enabled {
+            return None;
+        }
+
+        let mut patterns = Vec::new();
+        let mut secrets = HashMap::new();
+        if let Some(project_id) = project_id {
+            match ProjectRedactionPatterns::find_or_default(&self.db.pool, project_id).await {
+                Ok(saved) => patterns = saved.patterns.0,
+                Err(e) => tracing::warn!("Failed to load redaction patterns: {}", e),
+            }
+            match project_secrets::values(&self.db.pool, &self.secrets, project_id).await {
+                Ok(values) => secrets = values,
+                Err(e) => tracing::warn!("Failed to load project secrets for redaction: {}", e),
+            }
+        }
+
+        let redactor = OutputRedactor::new(&config, &patterns, secrets.into_values());
+        (!redactor.is_empty()).then(|| Arc::new(redactor))
+    }
+
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        redactor: Option<Arc<OutputRedactor>>,
+    ) {
+        // Devcontainer setup may already have logged into this execution's store
+        let existing = self.msg_stores().read().await.get(&id).cloned();
+        let store = existing.unwrap_or_else(|| Arc::new(MsgStore::new()));
+
+        let out = child.inner().stdout.take().expect("no stdout");
+        let err = child.inner().stderr.take().expect("no stderr");
+
+        match redactor {
+            // Secrets are masked before anything reaches the store
+            Some(redactor) => {
+                let out = output_redaction::redact_output(ReaderStream::new(out), redactor.clone())
+                    .map_ok(LogMsg::Stdout);
+                let err = output_redaction::redact_output(ReaderStream::new(err), redactor)
+                    .map_ok(LogMsg::Stderr);
+                store.clone().spawn_forwarder(select(out, err));
+            }
+            None => {
+                // Map stdout bytes -> LogMsg::Stdout
+                let out = ReaderStream::new(out)
+                    .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+
+                // Map stderr bytes -> LogMsg::Stderr
+                let err = ReaderStream::new(err)
+                    .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+
+                // Merge and forward into the store
+                let merged = select(out, err); // Stream<Item = Result<LogMsg, io::Error>>
+                store.clone().spawn_forwarder(merged);
+            }
+        }
 
         let mut map = self.msg_stores().write().await;
         map.insert(id, store);
@@ -963,6 +1913,7 @@ impl LocalContainerService {
         worktree_path: &Path,
         base_commit: &Commit,
         stats_only: bool,
+        max_inline_bytes: usize,
         path_prefix: Option<String>,
     ) -> Result<DiffStreamHandle, ContainerError> {
         diff_stream::create(
@@ -970,6 +1921,7 @@ impl LocalContainerService {
             worktree_path.to_path_buf(),
             base_commit.clone(),
             stats_only,
+            max_inline_bytes,
             path_prefix,
             Some(&self.watcher_manager),
         )
@@ -1008,6 +1960,47 @@ impl LocalContainerService {
         None
     }
 
+    /// Record permission problems the execution left in its workspace.
+    /// Merging is blocked until they are acknowledged.
+    async fn audit_permissions(&self, ctx: &ExecutionContext) {
+        let Some(container_ref) = &ctx.workspace.container_ref else {
+            return;
+        };
+        let root = PathBuf::from(container_ref);
+        let since = ctx.execution_process.started_at;
+        let findings = match tokio::task::spawn_blocking(move || {
+            permission_audit::audit_workspace(&root, since)
+        })
+        .await
+        {
+            Ok(findings) => findings,
+            Err(e) => {
+                tracing::warn!("Permission audit task failed: {}", e);
+                return;
+            }
+        };
+        if findings.is_empty() {
+            return;
+        }
+
+        tracing::warn!(
+            workspace_id = %ctx.workspace.id,
+            execution_process_id = %ctx.execution_process.id,
+            count = findings.len(),
+            "Execution left permission problems in the workspace"
+        );
+        if let Err(e) = PermissionFinding::create_many(
+            &self.db.pool,
+            ctx.execution_process.id,
+            ctx.workspace.id,
+            &findings,
+        )
+        .await
+        {
+            tracing::error!("Failed to record permission findings: {}", e);
+        }
+    }
+
     /// Update the coding agent turn summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's a coding agent turn for this execution process
@@ -1029,6 +2022,7 @@ impl LocalContainerService {
 
     /// Copy project files and images to the workspace.
     /// Skips files/images that already exist (fast no-op if all exist).
+    /// Runs each repo's post-copy script when new files were copied.
     async fn copy_files_and_images(
         &self,
         workspace_dir: &Path,
@@ -1036,12 +2030,21 @@ impl LocalContainerService {
     ) -> Result<(), ContainerError> {
         let repos = WorkspaceRepo::find_repos_with_copy_files(&self.db.pool, workspace.id).await?;
 
+        let mut base_vars = CopyTemplateVars::new()
+            .with("workspace_branch", workspace.branch.clone())
+            .with("workspace_dir", workspace_dir.to_string_lossy());
+        if let Some(port) = copy::allocate_port() {
+            base_vars = base_vars.with("port", port.to_string());
+        }
+
         for repo in &repos {
             if let Some(copy_files) = &repo.copy_files
                 && !copy_files.trim().is_empty()
             {
                 let worktree_path = workspace_dir.join(&repo.name);
-                self.copy_project_files(&repo.path, &worktree_path, copy_files)
+                let vars = base_vars.clone().with("repo_name", repo.name.clone());
+                let copied = self
+                    .copy_project_files(&repo.path, &worktree_path, copy_files, &vars)
                     .await
                     .unwrap_or_else(|e| {
                         tracing::warn!(
@@ -1049,7 +2052,16 @@ impl LocalContainerService {
                             repo.name,
                             e
                         );
+                        0
                     });
+
+                if copied > 0
+                    && let Some(script) = &repo.post_copy_script
+                    && !script.trim().is_empty()
+                    && let Err(e) = copy::run_post_copy_script(script, &worktree_path, &vars).await
+                {
+                    tracing::warn!("Post-copy script failed for repo '{}': {}", repo.name, e);
+                }
             }
         }
 
@@ -1064,6 +2076,22 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Render the project's managed `.env` into each worktree.
+    /// Failures (e.g. a missing secret) are logged and don't block workspace creation.
+    async fn sync_env_files(&self, workspace_dir: &Path, project_id: Uuid, repos: &[Repo]) {
+        if let Err(e) = env_file::sync_workspace_env_files(
+            &self.db.pool,
+            &self.secrets,
+            project_id,
+            workspace_dir,
+            repos,
+        )
+        .await
+        {
+            tracing::warn!("Failed to write managed env files to workspace: {}", e);
+        }
+    }
+
     /// Create workspace-level CLAUDE.md and AGENTS.md files that import from each repo.
     /// Uses the @import syntax to reference each repo's config files.
     /// Skips creating files if they already exist or if no repos have the source file.
@@ -1212,76 +2240,17 @@ impl LocalContainerService {
             }
 
             // Add .claude/ to .git/info/exclude
-            // Handle git worktrees where .git is a file pointing to the real git dir
-            let dot_git_path = worktree_path.join(".git");
-            let git_dir = if dot_git_path.is_file() {
-                // In a worktree, .git is a file containing "gitdir: /path/to/real/.git/worktrees/..."
-                match tokio::fs::read_to_string(&dot_git_path).await {
-                    Ok(content) => {
-                        if let Some(gitdir) = content.strip_prefix("gitdir: ") {
-                            PathBuf::from(gitdir.trim())
-                        } else {
-                            tracing::warn!(
-                                "Unexpected .git file format for repo '{}', skipping exclude",
-                                repo.name
-                            );
-                            continue;
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to read .git file for repo '{}': {}", repo.name, e);
-                        continue;
-                    }
-                }
-            } else {
-                dot_git_path
-            };
-
-            let git_info_dir = git_dir.join("info");
-            let exclude_path = git_info_dir.join("exclude");
-
-            // Create .git/info directory if it doesn't exist
-            if let Err(e) = tokio::fs::create_dir_all(&git_info_dir).await {
-                tracing::warn!(
-                    "Failed to create .git/info directory for repo '{}': {}",
-                    repo.name,
-                    e
-                );
-                continue;
-            }
-
-            // Read existing exclude content or start fresh
-            let existing_content = tokio::fs::read_to_string(&exclude_path)
-                .await
-                .unwrap_or_default();
-
-            // Check if .claude/ is already excluded
-            let claude_pattern = ".claude/";
-            if !existing_content
-                .lines()
-                .any(|line| line.trim() == claude_pattern)
-            {
-                // Append .claude/ to exclude file
-                let new_content = if existing_content.is_empty() || existing_content.ends_with('\n')
-                {
-                    format!("{}{}\n", existing_content, claude_pattern)
-                } else {
-                    format!("{}\n{}\n", existing_content, claude_pattern)
-                };
-
-                if let Err(e) = tokio::fs::write(&exclude_path, &new_content).await {
-                    tracing::warn!(
-                        "Failed to update .git/info/exclude for repo '{}': {}",
-                        repo.name,
-                        e
-                    );
-                    continue;
-                }
-
-                tracing::debug!(
+            match add_git_exclude(&worktree_path, ".claude/").await {
+                Ok(true) => tracing::debug!(
                     "Added .claude/ to .git/info/exclude for repo '{}'",
                     repo.name
-                );
+                ),
+                Ok(false) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to update .git/info/exclude for repo '{}': {}",
+                    repo.name,
+                    e
+                ),
             }
         }
 
@@ -1440,128 +2409,688 @@ impl LocalContainerService {
                 {
                     tracing::error!("Failed to store assistant message: {}", e);
                 }
-
-                // Send notification
-                if let Err(e) = NotificationService::notify_conversation_response(
-                    &db.pool,
-                    conversation.project_id,
-                    conversation_session_id,
-                    assistant_message.as_deref(),
-                )
-                .await
-                {
-                    tracing::error!("Failed to send conversation response notification: {}", e);
+
+                // Send notification
+                if let Err(e) = NotificationService::notify_conversation_response(
+                    &db.pool,
+                    conversation.project_id,
+                    conversation_session_id,
+                    assistant_message.as_deref(),
+                )
+                .await
+                {
+                    tracing::error!("Failed to send conversation response notification: {}", e);
+                }
+
+                // Send the next queued message (if any). The rest stay queued
+                // and are sent as each execution completes. The draft isn't
+                // touched: queued messages were taken out of it when queued.
+                if let Some(queued_msg) =
+                    container.queued_message_service.take_queued(conversation_session_id)
+                {
+                    tracing::info!(
+                        "Found queued message for conversation {}, starting follow-up execution",
+                        conversation_session_id
+                    );
+
+                    // Start the queued follow-up execution
+                    if let Err(e) = container
+                        .start_queued_conversation_follow_up(&conversation, &queued_msg.data)
+                        .await
+                    {
+                        tracing::error!("Failed to start queued conversation follow-up: {}", e);
+                    }
+                }
+            } else if let Ok(Some(execution_process)) =
+                ExecutionProcess::find_by_id(&db.pool, exec_id).await
+                && let Some(conversation_session_id) = execution_process.conversation_session_id
+            {
+                // Execution failed or was killed - discard the queued messages
+                let discarded = container
+                    .queued_message_service
+                    .cancel_queued(conversation_session_id);
+                if !discarded.is_empty() {
+                    tracing::info!(
+                        "Discarding {} queued message(s) for conversation {} due to execution status {:?}",
+                        discarded.len(),
+                        conversation_session_id,
+                        status
+                    );
+                }
+            }
+        })
+    }
+
+    /// Start a follow-up execution from a queued message
+    async fn start_queued_follow_up(
+        &self,
+        ctx: &ExecutionContext,
+        queued_data: &DraftFollowUpData,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        // Get executor profile from the latest CodingAgent process in this session
+        let initial_executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(&self.db.pool, ctx.session.id)
+                .await
+                .map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to get executor profile: {e}"))
+                })?;
+
+        let executor_profile_id = ExecutorProfileId {
+            executor: initial_executor_profile_id.executor,
+            variant: queued_data.variant.clone(),
+        };
+
+        // Get latest agent session ID for session continuity (from coding agent turns)
+        let latest_agent_session_id = ExecutionProcess::find_latest_coding_agent_turn_session_id(
+            &self.db.pool,
+            ctx.session.id,
+        )
+        .await?;
+
+        let project_repos =
+            ProjectRepo::find_by_project_id_with_names(&self.db.pool, ctx.project.id).await?;
+        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+
+        let working_dir = ctx
+            .workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: queued_data.message.clone(),
+                session_id: agent_session_id,
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir: working_dir.clone(),
+            })
+        } else {
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: queued_data.message.clone(),
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir,
+            })
+        };
+
+        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+            None,
+        )
+        .await
+    }
+
+    /// Everything the execution wrote to stderr, while its logs are in memory
+    async fn stderr_of(&self, exec_id: Uuid) -> Option<String> {
+        let msg_store = self.msg_stores.read().await.get(&exec_id).cloned()?;
+        Some(
+            msg_store
+                .get_history()
+                .into_iter()
+                .filter_map(|msg| match msg {
+                    LogMsg::Stderr(s) => Some(s),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Record why a failed execution failed, when its output tells, so
+    /// notifications about it can suggest a fix
+    async fn record_failure_reason(&self, ctx: &ExecutionContext) -> Option<ClassifiedFailure> {
+        let process = &ctx.execution_process;
+        if !matches!(process.status, ExecutionProcessStatus::Failed) {
+            return None;
+        }
+        let stderr = self.stderr_of(process.id).await?;
+        let executor = process
+            .executor_action()
+            .ok()
+            .and_then(|action| action.base_executor());
+        let failure = classify_execution_failure(&stderr, process.exit_code, executor)?;
+        if let Err(e) = ExecutionProcessFailure::upsert(
+            &self.db.pool,
+            process.id,
+            failure.reason,
+            Some(&failure.matched_line),
+            &failure.suggested_fix,
+        )
+        .await
+        {
+            tracing::error!("Failed to record why {} failed: {}", process.id, e);
+        }
+        Some(failure)
+    }
+
+    /// Keep the agent's known login state in line with how its run ended
+    fn observe_agent_auth(&self, ctx: &ExecutionContext, failure: Option<&ClassifiedFailure>) {
+        let process = &ctx.execution_process;
+        if !matches!(process.run_reason, ExecutionProcessRunReason::CodingAgent) {
+            return;
+        }
+        let Some(executor) = process
+            .executor_action()
+            .ok()
+            .and_then(|action| action.base_executor())
+        else {
+            return;
+        };
+        self.agent_auth.observe_execution(
+            executor,
+            matches!(process.status, ExecutionProcessStatus::Completed),
+            failure.map(|failure| (failure.reason, Some(failure.matched_line.as_str()))),
+        );
+    }
+
+    /// Warn that an execution was started with an agent known to be signed
+    /// out; it still runs, in case the login was renewed since
+    async fn warn_if_signed_out(
+        &self,
+        executor: BaseCodingAgent,
+        workspace: &Workspace,
+        task: &Task,
+        project_id: Uuid,
+    ) {
+        let Some(status) = self
+            .agent_auth
+            .status(executor)
+            .filter(|status| status.state == AgentAuthState::SignedOut)
+        else {
+            return;
+        };
+        self.notification_service
+            .notify_error(
+                &format!("{} is signed out", executor),
+                &signed_out_message(&task.title, &status),
+            )
+            .await;
+        if let Err(e) = NotificationService::notify_agent_signed_out(
+            &self.db.pool,
+            project_id,
+            workspace.id,
+            &task.title,
+            &status,
+        )
+        .await
+        {
+            tracing::warn!("Failed to create signed out notification: {}", e);
+        }
+    }
+
+    /// Schedule a re-run of a coding agent execution that failed for a
+    /// transient reason, if the retry policy allows another attempt.
+    /// Returns whether a retry was scheduled; the task is then left in
+    /// progress instead of being finalized.
+    async fn schedule_retry_if_transient(&self, ctx: &ExecutionContext) -> bool {
+        let process = &ctx.execution_process;
+        if !matches!(process.run_reason, ExecutionProcessRunReason::CodingAgent)
+            || !matches!(process.status, ExecutionProcessStatus::Failed)
+        {
+            return false;
+        }
+
+        let policy = {
+            let config = self.config.read().await;
+            let profile = match process.executor_action().map(|action| action.typ().clone()) {
+                Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => {
+                    Some(request.executor_profile_id)
+                }
+                Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => {
+                    Some(request.executor_profile_id)
+                }
+                _ => None,
+            };
+            match profile {
+                Some(profile) => policy_for_profile(
+                    &config.retry_policy,
+                    &config.retry_policy_overrides,
+                    &profile,
+                )
+                .clone(),
+                None => config.retry_policy.clone(),
+            }
+        };
+
+        let Some(stderr) = self.stderr_of(process.id).await else {
+            return false;
+        };
+        let runtime = process
+            .completed_at
+            .and_then(|completed_at| (completed_at - process.started_at).to_std().ok())
+            .unwrap_or_default();
+        let Some(failure) = classify_failure(&stderr, runtime)
+            .or_else(|| classify_exit_code(&policy, process.exit_code))
+        else {
+            return false;
+        };
+
+        let attempt = match ExecutionProcessRetry::find_by_retry_execution_process_id(
+            &self.db.pool,
+            process.id,
+        )
+        .await
+        {
+            Ok(previous) => previous.map_or(1, |retry| retry.attempt + 1),
+            Err(e) => {
+                tracing::error!("Failed to look up retries of {}: {}", process.id, e);
+                return false;
+            }
+        };
+        let Some(delay) = retry_delay(&policy, attempt as u32) else {
+            tracing::info!(
+                "Execution {} failed ({}), but retry {} is not allowed by the retry policy",
+                process.id,
+                failure.reason,
+                attempt
+            );
+            return false;
+        };
+
+        let retry = match ExecutionProcessRetry::create(
+            &self.db.pool,
+            process.id,
+            attempt,
+            failure.reason,
+            delay.as_secs() as i64,
+            Some(&failure.matched_line),
+        )
+        .await
+        {
+            Ok(retry) => retry,
+            Err(e) => {
+                tracing::error!("Failed to record retry of {}: {}", process.id, e);
+                return false;
+            }
+        };
+        tracing::info!(
+            "Execution {} failed ({}: {}), retrying in {:?} (attempt {}/{})",
+            process.id,
+            failure.reason,
+            failure.matched_line,
+            delay,
+            attempt,
+            policy.max_attempts
+        );
+
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let error = match container.start_retry(&ctx, retry.id).await {
+                Ok(true) => return,
+                Ok(false) => "Superseded by another execution in the workspace".to_string(),
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to retry execution {}: {}",
+                        ctx.execution_process.id,
+                        e
+                    );
+                    e.to_string()
+                }
+            };
+            // Nothing else will finalize the failed run, unless a newer
+            // execution is running in the workspace
+            let superseded = ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                &container.db.pool,
+                ctx.workspace.id,
+            )
+            .await
+            .unwrap_or(false);
+            if !superseded {
+                container
+                    .finalize_task(container.publisher.as_ref().ok(), &ctx)
+                    .await;
+            }
+            if let Err(e) =
+                ExecutionProcessRetry::set_error(&container.db.pool, retry.id, &error).await
+            {
+                tracing::error!("Failed to record retry error: {}", e);
+            }
+        });
+        true
+    }
+
+    /// Restart a follow-up that failed because the agent could not resume its
+    /// session. The dead session id is flagged expired and the prompt is
+    /// queued as a new agent session, prefixed with a summary of the earlier
+    /// turns. Returns whether the restart was scheduled.
+    async fn recover_stale_session(&self, ctx: &ExecutionContext) -> bool {
+        let process = &ctx.execution_process;
+        if !matches!(process.run_reason, ExecutionProcessRunReason::CodingAgent)
+            || !matches!(process.status, ExecutionProcessStatus::Failed)
+        {
+            return false;
+        }
+        let Ok(action) = process.executor_action() else {
+            return false;
+        };
+        let ExecutorActionType::CodingAgentFollowUpRequest(follow_up) = action.typ() else {
+            return false;
+        };
+        // Resume failures happen as the agent starts
+        let runtime = process
+            .completed_at
+            .and_then(|completed_at| (completed_at - process.started_at).to_std().ok())
+            .unwrap_or_default();
+        if runtime > STARTUP_WINDOW {
+            return false;
+        }
+
+        let Some(msg_store) = self.msg_stores.read().await.get(&process.id).cloned() else {
+            return false;
+        };
+        let stderr = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::Stderr(s) => Some(s),
+                _ => None,
+            })
+            .collect::<String>();
+        let Some(matched_line) = detect_stale_session(&stderr) else {
+            return false;
+        };
+
+        let pool = &self.db.pool;
+        if let Err(e) =
+            CodingAgentTurn::mark_agent_session_expired(pool, &follow_up.session_id).await
+        {
+            tracing::error!(
+                "Failed to flag agent session {} as expired: {}",
+                follow_up.session_id,
+                e
+            );
+            return false;
+        }
+        let turns = match CodingAgentTurn::find_by_session_id(pool, ctx.session.id).await {
+            Ok(turns) => turns,
+            Err(e) => {
+                tracing::error!("Failed to load turns of session {}: {}", ctx.session.id, e);
+                return false;
+            }
+        };
+        let prompt = recovery_prompt(
+            turns
+                .iter()
+                .filter(|turn| turn.execution_process_id != process.id),
+            &follow_up.prompt,
+        );
+        tracing::info!(
+            "Agent session {} of execution {} could not be resumed ({}), starting a new session",
+            follow_up.session_id,
+            process.id,
+            matched_line
+        );
+
+        let restart = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id: follow_up.executor_profile_id.clone(),
+                working_dir: follow_up.working_dir.clone(),
+            }),
+            action.next_action.clone(),
+        );
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            // The failed run's exit monitor releases the workspace just after
+            // this returns
+            if !container
+                .wait_for_workspace_idle(ctx.workspace.id, Duration::from_secs(30))
+                .await
+            {
+                return;
+            }
+            // Queued like any follow-up, so it waits for a slot under the
+            // concurrency limits
+            if let Err(e) = ExecutionQueue::create_follow_up(
+                &container.db.pool,
+                ctx.workspace.id,
+                ctx.session.id,
+                &restart,
+            )
+            .await
+            {
+                tracing::error!(
+                    "Failed to queue a new agent session after execution {}: {}",
+                    ctx.execution_process.id,
+                    e
+                );
+                container
+                    .finalize_task(container.publisher.as_ref().ok(), &ctx)
+                    .await;
+                return;
+            }
+            if let Err(e) = container.process_queue().await {
+                tracing::error!("Failed to process execution queue: {}", e);
+            }
+        });
+        true
+    }
+
+    /// Run pre-commit on the files a successful coding agent run changed, when
+    /// the project opted in. Fixes are committed; failures that remain are sent
+    /// back to the agent as a follow-up. Returns whether a follow-up was
+    /// scheduled, in which case the task is left in progress.
+    async fn validate_with_pre_commit(&self, ctx: &ExecutionContext) -> bool {
+        if !matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) {
+            return false;
+        }
+        let pool = &self.db.pool;
+        match GitHooksPolicy::find_or_default(pool, ctx.project.id).await {
+            Ok(policy) if policy.run_pre_commit => {}
+            Ok(_) => return false,
+            Err(e) => {
+                tracing::warn!("Failed to load git hooks policy: {}", e);
+                return false;
+            }
+        }
+        let Some(container_ref) = ctx.workspace.container_ref.as_ref() else {
+            return false;
+        };
+        let workspace_root = PathBuf::from(container_ref);
+        let configured: Vec<&Repo> = ctx
+            .repos
+            .iter()
+            .filter(|repo| PreCommitRunner::is_configured(&workspace_root.join(&repo.name)))
+            .collect();
+        if configured.is_empty() {
+            return false;
+        }
+        let Some(runner) = PreCommitRunner::detect().await else {
+            tracing::warn!(
+                "Skipping pre-commit validation for workspace {}: pre-commit is not installed",
+                ctx.workspace.id
+            );
+            return false;
+        };
+        let repo_states = match ExecutionProcessRepoState::find_by_execution_process_id(
+            pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(states) => states,
+            Err(e) => {
+                tracing::warn!("Failed to load repo states for pre-commit: {}", e);
+                return false;
+            }
+        };
+
+        let mut failures = Vec::new();
+        for repo in &configured {
+            let Some(base_commit) = repo_states
+                .iter()
+                .find(|state| state.repo_id == repo.id)
+                .and_then(|state| state.before_head_commit.as_deref())
+            else {
+                continue;
+            };
+            let worktree_path = workspace_root.join(&repo.name);
+            // The config and its hooks are repo content, so they run in the
+            // project's sandbox, which needs pre-commit's cache to be writable
+            let cache_dirs: Vec<PathBuf> = PreCommitRunner::cache_dir()
+                .filter(|dir| std::fs::create_dir_all(dir).is_ok())
+                .into_iter()
+                .collect();
+            let command_prefix = match git_hooks::hook_sandbox(
+                pool,
+                ctx.project.id,
+                &repo.path,
+                &worktree_path,
+                &cache_dirs,
+            )
+            .await
+            {
+                Ok(HookSandbox::Disabled) => Vec::new(),
+                Ok(HookSandbox::Prefix(prefix)) => prefix,
+                Ok(HookSandbox::Unavailable(e)) => {
+                    tracing::warn!("Skipping pre-commit in repo '{}': {}", repo.name, e);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to load sandbox policy for pre-commit: {}", e);
+                    continue;
                 }
-
-                // Process queued message (if any)
-                if let Some(queued_msg) =
-                    container.queued_message_service.take_queued(conversation_session_id)
-                {
-                    tracing::info!(
-                        "Found queued message for conversation {}, starting follow-up execution",
-                        conversation_session_id
+            };
+            let report = match PreCommitRunner::changed_files(&worktree_path, base_commit) {
+                Ok(files) => runner.run(&worktree_path, &files, &command_prefix).await,
+                Err(e) => Err(e),
+            };
+            let report = match report {
+                Ok(report) => report,
+                Err(e) => {
+                    tracing::warn!("pre-commit failed to run in repo '{}': {}", repo.name, e);
+                    continue;
+                }
+            };
+            if report.fixed {
+                // The hooks just ran, so the fix commit skips them
+                if let Err(e) = self.git().commit_with_hooks(
+                    &worktree_path,
+                    "Apply pre-commit fixes",
+                    &GitHooksMode::Skip.into(),
+                ) {
+                    tracing::warn!(
+                        "Failed to commit pre-commit fixes in repo '{}': {}",
+                        repo.name,
+                        e
                     );
+                }
+            }
+            if let Some(output) = report.failures {
+                failures.push((repo.name.clone(), output));
+            }
+        }
+        if failures.is_empty() {
+            return false;
+        }
 
-                    // Delete the scratch since we're consuming the queued message
-                    if let Err(e) = Scratch::delete(
-                        &db.pool,
-                        conversation_session_id,
-                        &ScratchType::DraftConversationMessage,
-                    )
-                    .await
-                    {
-                        tracing::warn!(
-                            "Failed to delete scratch after consuming queued conversation message: {}",
-                            e
-                        );
-                    }
+        let (previous_prompt, variant) = match ctx
+            .execution_process
+            .executor_action()
+            .map(|action| action.typ().clone())
+        {
+            Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => {
+                (request.prompt, request.executor_profile_id.variant)
+            }
+            Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => {
+                (request.prompt, request.executor_profile_id.variant)
+            }
+            _ => return false,
+        };
+        if pre_commit::is_feedback_prompt(&previous_prompt) {
+            tracing::warn!(
+                "pre-commit still fails in workspace {} after feeding failures back once",
+                ctx.workspace.id
+            );
+            return false;
+        }
 
-                    // Start the queued follow-up execution
-                    if let Err(e) = container
-                        .start_queued_conversation_follow_up(&conversation, &queued_msg.data)
-                        .await
-                    {
-                        tracing::error!("Failed to start queued conversation follow-up: {}", e);
-                    }
-                }
-            } else if let Ok(Some(execution_process)) =
-                ExecutionProcess::find_by_id(&db.pool, exec_id).await
-                && let Some(conversation_session_id) = execution_process.conversation_session_id
+        let worktrees: Vec<PathBuf> = configured
+            .iter()
+            .map(|repo| workspace_root.join(&repo.name))
+            .collect();
+        let follow_up = DraftFollowUpData {
+            message: blame_context::with_blame_context(
+                pre_commit::feedback_prompt(&failures),
+                &worktrees,
+            ),
+            variant,
+        };
+        let container = self.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            // This run's exit monitor releases the workspace just after
+            // this returns
+            if !container
+                .wait_for_workspace_idle(ctx.workspace.id, Duration::from_secs(30))
+                .await
             {
-                // Execution failed or was killed - discard the queued message
-                if container.queued_message_service.take_queued(conversation_session_id).is_some() {
-                    tracing::info!(
-                        "Discarding queued message for conversation {} due to execution status {:?}",
-                        conversation_session_id,
-                        status
-                    );
-                }
+                return;
             }
-        })
+            if let Err(e) = container.start_queued_follow_up(&ctx, &follow_up).await {
+                tracing::error!("Failed to send pre-commit failures to the agent: {}", e);
+                container
+                    .finalize_task(container.publisher.as_ref().ok(), &ctx)
+                    .await;
+            }
+        });
+        true
     }
 
-    /// Start a follow-up execution from a queued message
-    async fn start_queued_follow_up(
+    /// Queue a re-run of the failed execution's action, so it waits for a
+    /// slot under the concurrency limits like any other follow-up. Returns
+    /// false without queueing anything if the workspace is not released in
+    /// time or the user has moved on in it in the meantime.
+    async fn start_retry(
         &self,
         ctx: &ExecutionContext,
-        queued_data: &DraftFollowUpData,
-    ) -> Result<ExecutionProcess, ContainerError> {
-        // Get executor profile from the latest CodingAgent process in this session
-        let initial_executor_profile_id =
-            ExecutionProcess::latest_executor_profile_for_session(&self.db.pool, ctx.session.id)
-                .await
-                .map_err(|e| {
-                    ContainerError::Other(anyhow!("Failed to get executor profile: {e}"))
-                })?;
-
-        let executor_profile_id = ExecutorProfileId {
-            executor: initial_executor_profile_id.executor,
-            variant: queued_data.variant.clone(),
-        };
-
-        // Get latest agent session ID for session continuity (from coding agent turns)
-        let latest_agent_session_id = ExecutionProcess::find_latest_coding_agent_turn_session_id(
-            &self.db.pool,
+        retry_id: Uuid,
+    ) -> Result<bool, ContainerError> {
+        let pool = &self.db.pool;
+        // The failed run's exit monitor releases the workspace just after
+        // scheduling the retry
+        if !self
+            .wait_for_workspace_idle(ctx.workspace.id, Duration::from_secs(30))
+            .await
+        {
+            return Ok(false);
+        }
+        let latest = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
             ctx.session.id,
+            &ExecutionProcessRunReason::CodingAgent,
         )
         .await?;
+        if latest.map(|p| p.id) != Some(ctx.execution_process.id)
+            || ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                ctx.workspace.id,
+            )
+            .await?
+        {
+            return Ok(false);
+        }
 
-        let project_repos =
-            ProjectRepo::find_by_project_id_with_names(&self.db.pool, ctx.project.id).await?;
-        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
-
-        let working_dir = ctx
-            .workspace
-            .agent_working_dir
-            .as_ref()
-            .filter(|dir| !dir.is_empty())
-            .cloned();
-
-        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
-            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-                prompt: queued_data.message.clone(),
-                session_id: agent_session_id,
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir: working_dir.clone(),
-            })
-        } else {
-            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt: queued_data.message.clone(),
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir,
-            })
-        };
-
-        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
-
-        self.start_execution(
-            &ctx.workspace,
-            &ctx.session,
-            &action,
-            &ExecutionProcessRunReason::CodingAgent,
-            None,
-        )
-        .await
+        let action = ctx
+            .execution_process
+            .executor_action()
+            .map_err(ContainerError::Other)?;
+        ExecutionQueue::create_retry(pool, ctx.workspace.id, ctx.session.id, action, retry_id)
+            .await?;
+        if let Err(e) = self.process_queue().await {
+            tracing::error!("Failed to process execution queue: {}", e);
+        }
+        Ok(true)
     }
 
     /// Start a follow-up conversation execution from a queued message
@@ -1657,11 +3186,28 @@ impl LocalContainerService {
             .filter(|dir| !dir.is_empty())
             .cloned();
 
+        let template_id = self.config.read().await.feedback_template_id;
+        let custom_prompt = prompt_templates::resolve(&self.db.pool, template_id)
+            .await
+            .map(|template| {
+                prompt_templates::render(
+                    &template,
+                    &[
+                        ("task_title", ctx.task.title.as_str()),
+                        (
+                            "task_description",
+                            ctx.task.description.as_deref().unwrap_or_default(),
+                        ),
+                    ],
+                )
+            });
+
         // Create the feedback action
         let action = FeedbackService::create_feedback_action(
             agent_session_id.to_string(),
             executor_profile_id,
             working_dir,
+            custom_prompt,
         );
 
         // Start the feedback execution with InternalAgent run reason and "feedback" purpose
@@ -1854,10 +3400,11 @@ impl LocalContainerService {
         agent_session_id: &str,
     ) -> Result<ExecutionProcess, ContainerError> {
         // Check if review attention is enabled in config
-        let (review_attention_profile, review_attention_prompt) = {
+        let (review_attention_profile, template_id, custom_prompt) = {
             let config = self.config.read().await;
             (
                 config.review_attention_executor_profile.clone(),
+                config.review_attention_template_id,
                 config.review_attention_prompt.clone(),
             )
         };
@@ -1867,6 +3414,9 @@ impl LocalContainerService {
                 "Review attention is disabled (no executor profile configured)"
             )));
         };
+        let review_attention_prompt = prompt_templates::resolve(&self.db.pool, template_id)
+            .await
+            .or(custom_prompt);
 
         // Get the CodingAgentTurn to retrieve prompt and summary
         let turn =
@@ -1952,7 +3502,6 @@ impl LocalContainerService {
         let feedback_pending_cleanup = self.feedback_pending_cleanup.clone();
 
         // Clone services needed for autopilot merge
-        let git = self.git.clone();
         let config = self.config.clone();
         let merge_queue_store = self.merge_queue_store.clone();
         let operation_status = self.operation_status.clone();
@@ -2137,10 +3686,8 @@ impl LocalContainerService {
 
                     // Spawn autopilot merge in a separate task
                     let db_clone = db.clone();
-                    let git_clone = git.clone();
                     let merge_queue_store_clone = merge_queue_store.clone();
                     let operation_status_clone = operation_status.clone();
-                    let config_clone = config.clone();
                     let container_clone = container.clone();
 
                     tokio::spawn(async move {
@@ -2344,98 +3891,217 @@ impl LocalContainerService {
                                 }
                             };
 
-                            tracing::info!(
-                                task_id = %task_id,
-                                workspace_id = %workspace_id,
-                                repo_id = %workspace_repo.repo_id,
-                                "Autopilot: enqueueing task for merge"
-                            );
+                            tracing::info!(
+                                task_id = %task_id,
+                                workspace_id = %workspace_id,
+                                repo_id = %workspace_repo.repo_id,
+                                "Autopilot: enqueueing task for merge"
+                            );
+
+                            merge_queue_store.enqueue(
+                                project_id,
+                                workspace_id,
+                                workspace_repo.repo_id,
+                                commit_message,
+                            );
+                        }
+
+                        // Spawn the merge queue processor if not already running
+                        container_clone.spawn_merge_queue_processor(
+                            project_id,
+                            merge_queue_store,
+                            operation_status,
+                        );
+                    });
+                } else {
+                    tracing::debug!(
+                        task_id = %task_id,
+                        "Autopilot merge skipped: autopilot is disabled"
+                    );
+                }
+            }
+
+            // Final cleanup after successful processing
+            cleanup(msg_stores, feedback_pending_cleanup, db, review_exec_id).await;
+        });
+    }
+
+    /// Ask the coding agent to distill what it learned into project memories.
+    ///
+    /// Sends a follow-up listing the project's existing memories and spawns a
+    /// parser that stores the new ones when the execution completes.
+    async fn collect_project_memories(
+        &self,
+        ctx: &ExecutionContext,
+        agent_session_id: &str,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(&self.db.pool, ctx.session.id)
+                .await
+                .map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to get executor profile: {e}"))
+                })?;
+
+        let existing =
+            ProjectMemory::find_by_project_id(&self.db.pool, ctx.task.project_id).await?;
+
+        let working_dir = ctx
+            .workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action = project_memory::create_extraction_action(
+            agent_session_id.to_string(),
+            executor_profile_id,
+            working_dir,
+            &existing,
+        );
+
+        let memory_exec = self
+            .start_execution(
+                &ctx.workspace,
+                &ctx.session,
+                &action,
+                &ExecutionProcessRunReason::InternalAgent,
+                Some("project_memory"),
+            )
+            .await?;
+
+        // The parser reads the response from the msg_store, so the exit monitor must leave it
+        self.feedback_pending_cleanup
+            .write()
+            .await
+            .insert(memory_exec.id);
+
+        self.spawn_project_memory_parser(memory_exec.id, ctx.task.project_id, ctx.task.id);
+
+        Ok(memory_exec)
+    }
+
+    /// Spawn a background task that stores the memories from a memory
+    /// extraction execution once it completes.
+    ///
+    /// Failures are logged but don't affect the task.
+    fn spawn_project_memory_parser(&self, memory_exec_id: Uuid, project_id: Uuid, task_id: Uuid) {
+        let db = self.db.clone();
+        let msg_stores = self.msg_stores.clone();
+        let feedback_pending_cleanup = self.feedback_pending_cleanup.clone();
+
+        tokio::spawn(async move {
+            let cleanup = |msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+                           feedback_pending_cleanup: Arc<RwLock<HashSet<Uuid>>>,
+                           db: DBService,
+                           exec_id: Uuid| async move {
+                feedback_pending_cleanup.write().await.remove(&exec_id);
+
+                if let Some(msg_arc) = msg_stores.write().await.remove(&exec_id) {
+                    if let Some((input_tokens, output_tokens)) =
+                        extract_token_usage_from_msg_store(&msg_arc)
+                        && let Err(e) = ExecutionProcess::update_token_usage(
+                            &db.pool,
+                            exec_id,
+                            Some(input_tokens),
+                            Some(output_tokens),
+                        )
+                        .await
+                    {
+                        tracing::warn!("Failed to update token usage for {}: {}", exec_id, e);
+                    }
+
+                    msg_arc.push_finished();
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    if let Err(arc) = Arc::try_unwrap(msg_arc) {
+                        tracing::error!(
+                            "There are still {} strong Arcs to MsgStore for {}",
+                            Arc::strong_count(&arc),
+                            exec_id
+                        );
+                    }
+                }
+            };
 
-                            merge_queue_store.enqueue(
-                                project_id,
-                                workspace_id,
-                                workspace_repo.repo_id,
-                                commit_message,
+            loop {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+
+                match ExecutionProcess::find_by_id(&db.pool, memory_exec_id).await {
+                    Ok(Some(exec)) => match exec.status {
+                        ExecutionProcessStatus::Running => continue,
+                        ExecutionProcessStatus::Completed => break,
+                        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed => {
+                            tracing::warn!(
+                                "Memory extraction {} ended with status {:?}, skipping parsing",
+                                memory_exec_id,
+                                exec.status
                             );
                         }
+                    },
+                    Ok(None) => {
+                        tracing::warn!(
+                            "Memory extraction {} not found, stopping parser",
+                            memory_exec_id
+                        );
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to query memory extraction {}: {}",
+                            memory_exec_id,
+                            e
+                        );
+                    }
+                }
+                cleanup(msg_stores, feedback_pending_cleanup, db, memory_exec_id).await;
+                return;
+            }
 
-                        // Spawn the merge queue processor if not already running
-                        let should_spawn = {
-                            let mut active = match ACTIVE_MERGE_PROCESSORS.lock() {
-                                Ok(guard) => guard,
-                                Err(poisoned) => {
-                                    tracing::error!("ACTIVE_MERGE_PROCESSORS mutex poisoned");
-                                    poisoned.into_inner()
-                                }
-                            };
-                            if active.contains(&project_id) {
-                                false
-                            } else {
-                                active.insert(project_id);
-                                true
-                            }
-                        };
-
-                        if should_spawn {
-                            tracing::info!(
-                                project_id = %project_id,
-                                "Autopilot: spawning merge queue processor"
-                            );
+            let assistant_message = {
+                let stores = msg_stores.read().await;
+                stores
+                    .get(&memory_exec_id)
+                    .and_then(|store| extract_assistant_message_from_msg_store(store))
+            };
 
-                            let processor_pool = db_clone.pool.clone();
-                            let processor_git = git_clone.clone();
-                            let processor_store = merge_queue_store.clone();
-                            let processor_op_status = operation_status.clone();
-                            let processor_config = config_clone.clone();
-
-                            // Create event dispatch callback from container
-                            let event_dispatcher_clone = container_clone.event_dispatcher.clone();
-                            let event_dispatch_callback: EventDispatchCallback =
-                                Arc::new(move |event: DomainEvent| {
-                                    let dispatcher = event_dispatcher_clone.clone();
-                                    Box::pin(async move {
-                                        dispatcher.dispatch(event).await;
-                                    })
-                                });
-
-                            tokio::spawn(async move {
-                                let processor = MergeQueueProcessor::with_operation_status(
-                                    processor_pool,
-                                    processor_git,
-                                    processor_store,
-                                    processor_op_status,
-                                    processor_config,
-                                )
-                                .with_event_dispatcher(event_dispatch_callback);
-                                if let Err(e) = processor.process_project_queue(project_id).await {
-                                    tracing::error!(
-                                        %project_id,
-                                        error = %e,
-                                        "Autopilot: failed to process merge queue"
-                                    );
-                                }
-                                // Always remove from active set when done
-                                if let Ok(mut active) = ACTIVE_MERGE_PROCESSORS.lock() {
-                                    active.remove(&project_id);
-                                }
-                            });
-                        } else {
-                            tracing::debug!(
-                                project_id = %project_id,
-                                "Autopilot: merge queue processor already running for project"
-                            );
-                        }
-                    });
-                } else {
-                    tracing::debug!(
-                        task_id = %task_id,
-                        "Autopilot merge skipped: autopilot is disabled"
+            let extracted = match assistant_message
+                .as_deref()
+                .map(project_memory::parse_response)
+            {
+                Some(Ok(extracted)) => extracted,
+                Some(Err(e)) => {
+                    tracing::warn!(
+                        "Failed to parse memory extraction {}: {}",
+                        memory_exec_id,
+                        e
+                    );
+                    Vec::new()
+                }
+                None => {
+                    tracing::warn!(
+                        "No assistant message found for memory extraction {}",
+                        memory_exec_id
                     );
+                    Vec::new()
+                }
+            };
+
+            if !extracted.is_empty() {
+                match project_memory::store_extracted(&db.pool, project_id, task_id, extracted)
+                    .await
+                {
+                    Ok(stored) => tracing::info!(
+                        "Stored {} project memories from task {}",
+                        stored.len(),
+                        task_id
+                    ),
+                    Err(e) => tracing::warn!(
+                        "Failed to store project memories from task {}: {}",
+                        task_id,
+                        e
+                    ),
                 }
             }
 
-            // Final cleanup after successful processing
-            cleanup(msg_stores, feedback_pending_cleanup, db, review_exec_id).await;
+            cleanup(msg_stores, feedback_pending_cleanup, db, memory_exec_id).await;
         });
     }
 
@@ -2460,14 +4126,12 @@ impl LocalContainerService {
         operation_status: &OperationStatusStore,
     ) -> Result<Option<String>, ContainerError> {
         // Check if commit message auto-generation is enabled
-        let (auto_generate_enabled, prompt_template, executor_profile_from_config) = {
+        let (auto_generate_enabled, template_id, custom_prompt, executor_profile_from_config) = {
             let config = self.config.read().await;
             (
                 config.commit_message_auto_generate_enabled,
-                config
-                    .commit_message_prompt
-                    .clone()
-                    .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE_PROMPT.to_string()),
+                config.commit_message_template_id,
+                config.commit_message_prompt.clone(),
                 config.commit_message_executor_profile.clone(),
             )
         };
@@ -2480,6 +4144,11 @@ impl LocalContainerService {
             return Ok(None);
         }
 
+        let prompt_template = prompt_templates::resolve(&self.db.pool, template_id)
+            .await
+            .or(custom_prompt)
+            .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE_PROMPT.to_string());
+
         // Set operation status to GeneratingCommit
         operation_status.set(OperationStatus::new(
             workspace.id,
@@ -2488,13 +4157,19 @@ impl LocalContainerService {
         ));
 
         let result = self
-            .generate_autopilot_commit_message_inner(
-                task,
-                workspace,
-                repo,
-                workspace_repo,
-                &prompt_template,
-                executor_profile_from_config,
+            .operations
+            .track(
+                OperationKind::CommitMessage,
+                Some(workspace.id),
+                Some(task.id),
+                self.generate_autopilot_commit_message_inner(
+                    task,
+                    workspace,
+                    repo,
+                    workspace_repo,
+                    &prompt_template,
+                    executor_profile_from_config,
+                ),
             )
             .await;
 
@@ -2562,10 +4237,14 @@ impl LocalContainerService {
             .description
             .as_deref()
             .unwrap_or("No description provided");
-        let prompt = prompt_template
-            .replace("{task_title}", &task.title)
-            .replace("{task_description}", task_description)
-            .replace("{diff}", &diff_string);
+        let prompt = prompt_templates::render(
+            prompt_template,
+            &[
+                ("task_title", task.title.as_str()),
+                ("task_description", task_description),
+                ("diff", diff_string.as_str()),
+            ],
+        );
 
         // Get or create a session for this operation
         let session = match Session::find_latest_by_workspace_id(&self.db.pool, workspace.id).await
@@ -2725,6 +4404,151 @@ impl LocalContainerService {
             }
         }
     }
+
+    /// Re-run a past execution in a new workspace for the same task, with
+    /// every repository reset to the commit it was at when the original
+    /// execution started.
+    ///
+    /// Only the execution's own action runs: setup scripts and chained
+    /// actions such as cleanup scripts are skipped. A follow-up is replayed as
+    /// an initial request with the same prompt, since the agent session it
+    /// continued does not exist in the new workspace. Returns no execution
+    /// when the concurrency limits queued the replay instead.
+    pub async fn replay_execution(
+        &self,
+        execution_process_id: Uuid,
+    ) -> Result<(Workspace, Option<ExecutionProcess>), ContainerError> {
+        let pool = &self.db.pool;
+        let original = ExecutionProcess::find_by_id(pool, execution_process_id)
+            .await?
+            .ok_or(ContainerError::ExecutionNotFound(execution_process_id))?;
+        let session_id = original.session_id.ok_or_else(|| {
+            ContainerError::Other(anyhow!(
+                "Only workspace executions can be replayed, not conversation executions"
+            ))
+        })?;
+        let original_session = Session::find_by_id(pool, session_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let original_workspace = Workspace::find_by_id(pool, original_session.workspace_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let task = original_workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let action = original.executor_action()?;
+        let typ = match action.typ() {
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                    prompt: request.prompt.clone(),
+                    executor_profile_id: request.executor_profile_id.clone(),
+                    working_dir: request.working_dir.clone(),
+                })
+            }
+            typ => typ.clone(),
+        };
+        let action = ExecutorAction::new(typ, None);
+
+        let repo_states =
+            ExecutionProcessRepoState::find_by_execution_process_id(pool, original.id).await?;
+        let repositories =
+            WorkspaceRepo::find_repos_for_workspace(pool, original_workspace.id).await?;
+        let mut start_commits = Vec::with_capacity(repositories.len());
+        for repo in &repositories {
+            let commit = repo_states
+                .iter()
+                .find(|state| state.repo_id == repo.id)
+                .and_then(|state| state.before_head_commit.clone())
+                .ok_or_else(|| {
+                    ContainerError::Other(anyhow!(
+                        "No starting commit was recorded for repository {}",
+                        repo.name
+                    ))
+                })?;
+            start_commits.push((repo.name.clone(), commit));
+        }
+
+        let workspace_id = Uuid::new_v4();
+        let repo_ids: Vec<Uuid> = repositories.iter().map(|repo| repo.id).collect();
+        let branch = self
+            .unique_git_branch_for_workspace(&workspace_id, &task.title, &repo_ids)
+            .await?;
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir: original_workspace.agent_working_dir.clone(),
+            },
+            workspace_id,
+            task.id,
+        )
+        .await?;
+        let workspace_repos: Vec<CreateWorkspaceRepo> =
+            WorkspaceRepo::find_by_workspace_id(pool, original_workspace.id)
+                .await?
+                .into_iter()
+                .map(|repo| CreateWorkspaceRepo {
+                    repo_id: repo.repo_id,
+                    target_branch: repo.target_branch,
+                })
+                .collect();
+        WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+
+        let workspace_dir = PathBuf::from(self.create(&workspace).await?);
+        for (repo_name, commit) in &start_commits {
+            self.git()
+                .reset_worktree_to_commit(&workspace_dir.join(repo_name), commit, true)?;
+        }
+        let workspace = Workspace::find_by_id(pool, workspace.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let session = Session::create(
+            pool,
+            &CreateSession {
+                executor: original_session.executor.clone(),
+            },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+
+        // Replays count towards the concurrency limits like any agent turn
+        let queue = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                self.should_queue_execution(&workspace, &request.executor_profile_id)
+                    .await?
+            }
+            _ => self.at_agent_capacity().await? || self.at_project_capacity(&workspace).await?,
+        };
+        if queue {
+            tracing::info!(
+                original_execution_id = %original.id,
+                workspace_id = %workspace.id,
+                "At concurrency limit, queueing replay"
+            );
+            ExecutionQueue::create_replay(pool, workspace.id, session.id, &action).await?;
+            return Ok((workspace, None));
+        }
+
+        tracing::info!(
+            original_execution_id = %original.id,
+            workspace_id = %workspace.id,
+            "Replaying execution"
+        );
+        let execution_process = self
+            .start_execution(
+                &workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::Replay,
+                None,
+            )
+            .await?;
+        Ok((workspace, Some(execution_process)))
+    }
 }
 
 fn failure_exit_status() -> std::process::ExitStatus {
@@ -2774,6 +4598,26 @@ impl ContainerService for LocalContainerService {
         &self.skills_cache
     }
 
+    fn resource_monitor(&self) -> &ResourceMonitor {
+        &self.resource_monitor
+    }
+
+    fn agent_auth(&self) -> &AgentAuthMonitor {
+        &self.agent_auth
+    }
+
+    fn provider_rate_limiter(&self) -> &ProviderRateLimiter {
+        &self.provider_rate_limiter
+    }
+
+    fn staged_selections(&self) -> &StagedSelections {
+        &self.staged_selections
+    }
+
+    fn embedding(&self) -> &EmbeddingService {
+        &self.embedding
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
@@ -2979,6 +4823,13 @@ impl ContainerService for LocalContainerService {
         self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
             .await?;
 
+        self.sync_env_files(
+            &created_workspace.workspace_dir,
+            task.project_id,
+            &repositories,
+        )
+        .await;
+
         Self::create_workspace_config_files(&created_workspace.workspace_dir, &repositories)
             .await?;
 
@@ -3000,6 +4851,14 @@ impl ContainerService for LocalContainerService {
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         self.try_stop(workspace, true).await;
+        if let (Some(container_ref), Ok(Some(remote))) =
+            (&workspace.container_ref, self.ssh_remote().await)
+        {
+            let remote_dir = remote.remote_dir(Path::new(container_ref));
+            if let Err(e) = remote.remove_dir(&remote_dir).await {
+                tracing::warn!("Failed to remove {} on the SSH remote: {}", remote_dir, e);
+            }
+        }
         Self::cleanup_workspace(&self.db, workspace).await;
         Ok(())
     }
@@ -3045,6 +4904,11 @@ impl ContainerService for LocalContainerService {
         self.copy_files_and_images(&workspace_dir, workspace)
             .await?;
 
+        if let Some(task) = workspace.parent_task(&self.db.pool).await? {
+            self.sync_env_files(&workspace_dir, task.project_id, &repositories)
+                .await;
+        }
+
         Self::create_workspace_config_files(&workspace_dir, &repositories).await?;
 
         // Deploy Claude Code hooks (idempotent, graceful if assets don't exist)
@@ -3108,16 +4972,19 @@ impl ContainerService for LocalContainerService {
         let approvals_service: Arc<dyn ExecutorApprovalService> =
             match executor_action.base_executor() {
                 Some(
-                    BaseCodingAgent::Codex
+                    executor @ (BaseCodingAgent::Codex
                     | BaseCodingAgent::ClaudeCode
                     | BaseCodingAgent::Gemini
                     | BaseCodingAgent::QwenCode
-                    | BaseCodingAgent::Opencode,
+                    | BaseCodingAgent::Goose
+                    | BaseCodingAgent::Opencode),
                 ) => ExecutorApprovalBridge::new(
                     self.approvals.clone(),
                     self.db.clone(),
                     self.notification_service.clone(),
+                    self.event_dispatch_callback(),
                     execution_process.id,
+                    executor,
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
@@ -3137,6 +5004,23 @@ impl ContainerService for LocalContainerService {
             .await?
             .ok_or(ContainerError::Other(anyhow!("Project not found for task")))?;
 
+        if matches!(
+            execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) && let Some(executor) = executor_action.base_executor()
+        {
+            self.warn_if_signed_out(executor, workspace, &task, project.id)
+                .await;
+        }
+
+        // Login-shell vars go first so VK_* and profile overrides win
+        env.shell = project.script_shell;
+        if project.inherit_login_env
+            && let Some(login_env) = login_shell_env().await
+        {
+            env.merge(&login_env);
+        }
+
         env.insert("VK_PROJECT_NAME", &project.name);
         env.insert("VK_PROJECT_ID", project.id.to_string());
         env.insert("VK_TASK_ID", task.id.to_string());
@@ -3151,6 +5035,9 @@ impl ContainerService for LocalContainerService {
         let repo_names: Vec<&str> = workspace_repos.iter().map(|r| r.name.as_str()).collect();
         env.insert("VK_REPO_NAMES", repo_names.join(","));
 
+        // Task env vars come after VK_* so a task can point at its own endpoints
+        env.merge(&TaskEnvVar::values_by_task_id(&self.db.pool, task.id).await?);
+
         // Inject Langfuse credentials if enabled (for executors with hook support)
         {
             let config_guard = self.config.read().await;
@@ -3159,7 +5046,9 @@ impl ContainerService for LocalContainerService {
                 if let Some(ref key) = config_guard.langfuse_public_key {
                     env.insert("LANGFUSE_PUBLIC_KEY", key);
                 }
-                if let Some(ref key) = config_guard.langfuse_secret_key {
+                if let Some(ref secret) = config_guard.langfuse_secret_key
+                    && let Some(key) = self.secrets.resolve(secret).await
+                {
                     env.insert("LANGFUSE_SECRET_KEY", key);
                 }
                 if let Some(ref host) = config_guard.langfuse_host {
@@ -3168,8 +5057,120 @@ impl ContainerService for LocalContainerService {
             }
         }
 
+        // Run everything through the project's Nix/devenv shell when configured.
+        // Devcontainers only wrap coding agents; scripts and dev servers stay on the host.
+        // The Docker dev shell runs every execution in the project's image.
+        let mut dirs = vec![current_dir.clone()];
+        dirs.extend(workspace_repos.iter().map(|r| current_dir.join(&r.name)));
+        // Auto picks the workspace's devcontainer for coding agents when Docker is up
+        let dev_shell = if project.dev_shell == Some(DevShell::Auto)
+            && executor_action.base_executor().is_some()
+            && Devcontainer::find(&dirs).is_some()
+            && devcontainer::docker_available().await
+        {
+            Some(DevShell::Devcontainer)
+        } else {
+            project.dev_shell
+        };
+        match dev_shell {
+            Some(DevShell::Devcontainer) => {
+                if executor_action.base_executor().is_some() {
+                    self.enter_devcontainer(
+                        workspace.id,
+                        execution_process.id,
+                        &current_dir,
+                        &workspace_repos,
+                        None,
+                        None,
+                        &mut env,
+                    )
+                    .await?;
+                }
+            }
+            Some(DevShell::Docker) => {
+                let image = project
+                    .container_image
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|image| !image.is_empty());
+                self.enter_devcontainer(
+                    workspace.id,
+                    execution_process.id,
+                    &current_dir,
+                    &workspace_repos,
+                    image,
+                    project.container_network.as_deref(),
+                    &mut env,
+                )
+                .await?;
+            }
+            Some(dev_shell) => {
+                match dev_shell.resolve_prefix(project.dev_shell_command.as_deref(), &dirs) {
+                    Some(prefix) => env.command_prefix = prefix,
+                    None => tracing::debug!(
+                        "No {} dev shell found for workspace {}, running commands directly",
+                        dev_shell,
+                        workspace.id
+                    ),
+                }
+            }
+            None => {}
+        }
+
+        // Confine the execution to the workspace when the project is sandboxed.
+        // Executions already running in a container are left as they are.
+        let containerized = match dev_shell {
+            Some(DevShell::Docker) => true,
+            Some(DevShell::Devcontainer) => executor_action.base_executor().is_some(),
+            _ => false,
+        };
+        // Executions holding the workspace run on the SSH remote when one is
+        // configured. Dev servers and internal agents stay local so they don't
+        // race a running agent's copy.
+        let remote = if containerized
+            || matches!(
+                execution_process.run_reason,
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::InternalAgent
+            ) {
+            None
+        } else {
+            self.ssh_remote().await?
+        };
+        let offloaded = containerized || remote.is_some();
+        let sandbox = ProjectSandboxPolicy::find_or_default(&self.db.pool, project.id)
+            .await?
+            .policy
+            .0;
+        if sandbox.enabled && !offloaded {
+            sandbox.check_working_dir(&current_dir, executor_action.working_dir())?;
+            // Agents commit in their worktree, which writes to the repo's git dir.
+            // Only the parts a commit needs are writable: hooks and config would
+            // run outside the sandbox on the server's next git operation.
+            let mut writable = vec![current_dir.clone()];
+            for repo in &workspace_repos {
+                writable.extend(WorktreeManager::commit_dirs(
+                    &repo.path,
+                    &current_dir.join(&repo.name),
+                ));
+            }
+            let mut prefix = sandbox.command_prefix(&writable).await?;
+            prefix.append(&mut env.command_prefix);
+            env.command_prefix = prefix;
+        }
+
+        if let Some(remote) = remote {
+            self.enter_ssh_remote(
+                remote,
+                execution_process.id,
+                &current_dir,
+                executor_action.working_dir(),
+                &mut env,
+            )
+            .await?;
+        }
+
         // Create the child and stream, add to execution tracker with timeout
-        let mut spawned = tokio::time::timeout(
+        let spawned = tokio::time::timeout(
             Duration::from_secs(30),
             executor_action.spawn(&current_dir, approvals_service, &env),
         )
@@ -3178,11 +5179,39 @@ impl ContainerService for LocalContainerService {
             ContainerError::Other(anyhow!(
                 "Timeout: process took more than 30 seconds to start"
             ))
-        })??;
+        })
+        .and_then(|spawned| spawned.map_err(ContainerError::from));
+        let mut spawned = match spawned {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                // Nothing ran on the SSH remote, so there is nothing to copy back
+                self.remote_executions.remove(&execution_process.id);
+                return Err(e);
+            }
+        };
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        let redactor = self.output_redactor(Some(project.id)).await;
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child, redactor)
             .await;
 
+        match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                self.spawn_anomaly_monitor(
+                    execution_process.id,
+                    request.executor_profile_id.clone(),
+                );
+                self.spawn_file_rule_monitor(execution_process.id);
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                self.spawn_anomaly_monitor(
+                    execution_process.id,
+                    request.executor_profile_id.clone(),
+                );
+                self.spawn_file_rule_monitor(execution_process.id);
+            }
+            ExecutorActionType::ScriptRequest(_) => {}
+        }
+
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
 
@@ -3301,6 +5330,23 @@ impl ContainerService for LocalContainerService {
             }
         }
 
+        // Closing the ssh connection leaves the remote side running
+        let remote = self
+            .remote_executions
+            .get(&execution_process.id)
+            .map(|entry| entry.value().clone());
+        if let Some((remote, local_dir)) = remote
+            && let Err(e) = remote
+                .kill_execution(&remote.remote_dir(&local_dir), execution_process.id)
+                .await
+        {
+            tracing::warn!(
+                "Failed to stop execution process {} on the SSH remote: {}",
+                execution_process.id,
+                e
+            );
+        }
+
         // Kill the child process and remove from the store
         {
             let mut child_guard = child.write().await;
@@ -3362,8 +5408,17 @@ impl ContainerService for LocalContainerService {
         &self,
         workspace: &Workspace,
         stats_only: bool,
+        max_inline_bytes: Option<usize>,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
+        let configured = self
+            .config
+            .read()
+            .await
+            .max_inline_diff_kb
+            .map(|kb| kb as usize * 1024);
+        let max_inline_bytes = resolve_inline_diff_limit(max_inline_bytes, configured);
+
         let workspace_repos =
             WorkspaceRepo::find_by_workspace_id(&self.db.pool, workspace.id).await?;
         let target_branches: HashMap<_, _> = workspace_repos
@@ -3411,6 +5466,7 @@ impl ContainerService for LocalContainerService {
                     &worktree_path,
                     &base_commit,
                     stats_only,
+                    max_inline_bytes,
                     Some(repo.name.clone()),
                 )
                 .await?;
@@ -3443,31 +5499,71 @@ impl ContainerService for LocalContainerService {
             .ok_or_else(|| ContainerError::Other(anyhow!("Container reference not found")))?;
         let workspace_root = PathBuf::from(container_ref);
 
+        // A staged selection covers the commits of the turn it was made in: the
+        // agent's and, when one runs after it, the cleanup script's
+        let turn_ends = matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::CleanupScript
+        ) || !ctx
+            .execution_process
+            .executor_action()
+            .is_ok_and(|action| action.next_action().is_some());
+        let staged_repo_ids: HashSet<Uuid> = ctx
+            .repos
+            .iter()
+            .map(|repo| repo.id)
+            .filter(|repo_id| {
+                if turn_ends {
+                    self.staged_selections.take(ctx.workspace.id, *repo_id)
+                } else {
+                    self.staged_selections.contains(ctx.workspace.id, *repo_id)
+                }
+            })
+            .collect();
+
         let repos_with_changes = self.check_repos_for_changes(&workspace_root, &ctx.repos)?;
         if repos_with_changes.is_empty() {
             tracing::debug!("No changes to commit in any repository");
             return Ok(false);
         }
 
-        Ok(self.commit_repos(repos_with_changes, &message))
+        let mut repos_with_hooks = Vec::with_capacity(repos_with_changes.len());
+        for (repo, worktree_path) in repos_with_changes {
+            let hooks = git_hooks::commit_hooks_for_project(
+                &self.db.pool,
+                ctx.project.id,
+                &repo.path,
+                &worktree_path,
+            )
+            .await?;
+            repos_with_hooks.push((repo, worktree_path, hooks));
+        }
+        let (committed, hook_failures) =
+            self.commit_repos(repos_with_hooks, &staged_repo_ids, &message);
+        if !hook_failures.is_empty() {
+            self.record_hook_failures(ctx, &hook_failures).await;
+        }
+        Ok(committed)
     }
 
     /// Copy files from the original project directory to the worktree.
-    /// Skips files that already exist at target with same size.
+    /// Skips files that already exist at target.
     async fn copy_project_files(
         &self,
         source_dir: &Path,
         target_dir: &Path,
         copy_files: &str,
-    ) -> Result<(), ContainerError> {
+        template_vars: &CopyTemplateVars,
+    ) -> Result<usize, ContainerError> {
         let source_dir = source_dir.to_path_buf();
         let target_dir = target_dir.to_path_buf();
         let copy_files = copy_files.to_string();
+        let template_vars = template_vars.clone();
 
         tokio::time::timeout(
             std::time::Duration::from_secs(30),
             tokio::task::spawn_blocking(move || {
-                copy::copy_project_files_impl(&source_dir, &target_dir, &copy_files)
+                copy::copy_project_files_impl(&source_dir, &target_dir, &copy_files, &template_vars)
             }),
         )
         .await
@@ -3475,6 +5571,35 @@ impl ContainerService for LocalContainerService {
         .map_err(|e| ContainerError::Other(anyhow!("Copy files task failed: {e}")))?
     }
 
+    async fn preview_project_files(
+        &self,
+        source_dir: &Path,
+        target_dir: Option<&Path>,
+        copy_files: &str,
+    ) -> Result<Vec<CopyFilePreview>, ContainerError> {
+        let source_dir = source_dir.to_path_buf();
+        let target_dir = target_dir.map(Path::to_path_buf);
+        let copy_files = copy_files.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            copy::preview_project_files(&source_dir, target_dir.as_deref(), &copy_files)
+        })
+        .await
+        .map_err(|e| ContainerError::Other(anyhow!("Copy files preview task failed: {e}")))
+    }
+
+    async fn wait_for_workspace_idle(&self, workspace_id: Uuid, timeout: Duration) -> bool {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+        tokio::time::timeout(timeout, async {
+            while self.running_workspaces.contains(&workspace_id) {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
     async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
         tracing::info!("Killing all running processes");
         let running_processes = ExecutionProcess::find_running(&self.db.pool).await?;
@@ -3520,7 +5645,16 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        let project_id = match execution_process.conversation_session_id {
+            Some(conversation_id) => {
+                ConversationSession::find_by_id(&self.db.pool, conversation_id)
+                    .await?
+                    .map(|conversation| conversation.project_id)
+            }
+            None => None,
+        };
+        let redactor = self.output_redactor(project_id).await;
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child, redactor)
             .await;
 
         self.add_child_to_store(execution_process.id, spawned.child)
@@ -2,34 +2,62 @@ use std::{
     collections::HashSet,
     fs,
     path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
 };
 
 use anyhow::anyhow;
 use globwalk::GlobWalkerBuilder;
-use services::services::container::ContainerError;
+use services::services::{
+    container::ContainerError,
+    copy_files::{CopyFileKind, CopyFilePreview, CopyTemplateVars, TEMPLATE_SUFFIX},
+};
+use utils::{path::long_path, shell::get_shell_command};
+
+/// Post-copy scripts are meant for quick setup steps (e.g. `direnv allow`)
+const POST_COPY_SCRIPT_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// Normalize pattern for cross-platform glob matching (convert backslashes to forward slashes)
 fn normalize_pattern(pattern: &str) -> String {
     pattern.replace('\\', "/")
 }
 
-/// Create a symlink at `link` pointing to `target`.
-/// On Unix, creates a symlink directly. On other platforms, attempts to copy the target.
-fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+/// Recreate the symlink `source_link` at `link`, pointing to `target`.
+/// On Windows, creating symlinks needs Developer Mode or admin rights, so file
+/// links fall back to copying the file they point to.
+fn create_symlink(source_link: &Path, target: &Path, link: &Path) -> std::io::Result<()> {
     #[cfg(unix)]
     {
+        let _ = source_link;
         std::os::unix::fs::symlink(target, link)
     }
-    #[cfg(not(unix))]
+    #[cfg(windows)]
     {
-        // On non-Unix platforms, copy the target if it exists and is a file
-        if let Ok(metadata) = fs::metadata(target) {
-            if metadata.is_file() {
-                fs::copy(target, link)?;
-                return Ok(());
+        // Resolve through the source link so relative targets are interpreted
+        // from the link's directory rather than the current working directory
+        let resolved = fs::metadata(source_link);
+        let is_dir = resolved.as_ref().is_ok_and(|m| m.is_dir());
+        let result = if is_dir {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        };
+        match result {
+            Ok(()) => Ok(()),
+            Err(_) if resolved.is_ok_and(|m| m.is_file()) => {
+                fs::copy(source_link, link).map(|_| ())
             }
+            Err(e) => Err(e),
         }
-        // For directories or non-existent targets, skip
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        // Copy the target if it exists and is a file
+        if fs::metadata(source_link).is_ok_and(|m| m.is_file()) {
+            fs::copy(source_link, link)?;
+            return Ok(());
+        }
+        let _ = target;
         Err(std::io::Error::new(
             std::io::ErrorKind::Unsupported,
             "symlinks not supported on this platform",
@@ -37,13 +65,34 @@ fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
     }
 }
 
-/// Copy project files from source to target directory based on glob patterns.
-/// Skips files that already exist at target with same size.
-pub(crate) fn copy_project_files_impl(
+/// A single entry the copy engine resolved from the copy-files patterns.
+#[derive(Debug, Clone)]
+pub(crate) struct PlannedCopy {
+    pub source_path: PathBuf,
+    pub relative_source: PathBuf,
+    pub relative_target: PathBuf,
+    pub kind: CopyFileKind,
+    pub target_exists: bool,
+}
+
+impl From<&PlannedCopy> for CopyFilePreview {
+    fn from(entry: &PlannedCopy) -> Self {
+        Self {
+            source: entry.relative_source.to_string_lossy().replace('\\', "/"),
+            target: entry.relative_target.to_string_lossy().replace('\\', "/"),
+            kind: entry.kind,
+            already_exists: entry.target_exists,
+        }
+    }
+}
+
+/// Resolve copy-files patterns into the list of entries that would be copied.
+/// Does not touch the target directory.
+pub(crate) fn plan_project_files(
     source_dir: &Path,
     target_dir: &Path,
     copy_files: &str,
-) -> Result<(), ContainerError> {
+) -> Vec<PlannedCopy> {
     let patterns: Vec<&str> = copy_files
         .split(',')
         .map(|s| s.trim())
@@ -52,6 +101,7 @@ pub(crate) fn copy_project_files_impl(
 
     // Track files to avoid duplicates
     let mut seen = HashSet::new();
+    let mut planned = Vec::new();
 
     for pattern in patterns {
         let pattern = normalize_pattern(pattern);
@@ -64,13 +114,15 @@ pub(crate) fn copy_project_files_impl(
             .unwrap_or(false);
 
         if is_file_or_symlink {
-            if let Err(e) = copy_single_entry(&pattern_path, source_dir, target_dir, &mut seen) {
-                tracing::warn!(
+            match plan_single_entry(&pattern_path, source_dir, target_dir, &mut seen) {
+                Ok(Some(entry)) => planned.push(entry),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
                     "Failed to copy {} (from {}): {}",
                     pattern,
                     pattern_path.display(),
                     e
-                );
+                ),
             }
             continue;
         }
@@ -94,22 +146,50 @@ pub(crate) fn copy_project_files_impl(
         };
 
         for entry in walker.flatten() {
-            if let Err(e) = copy_single_entry(entry.path(), source_dir, target_dir, &mut seen) {
-                tracing::warn!("Failed to copy {:?}: {e}", entry.path());
+            match plan_single_entry(entry.path(), source_dir, target_dir, &mut seen) {
+                Ok(Some(entry)) => planned.push(entry),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to copy {:?}: {e}", entry.path()),
             }
         }
     }
 
-    Ok(())
+    planned
 }
 
-/// Copy a single file or symlink from source to target.
-fn copy_single_entry(
+/// Copy project files from source to target directory based on glob patterns.
+/// Skips files that already exist at target. Files ending in `.template` are
+/// rendered with `vars` and written without the suffix.
+/// Returns the number of entries copied.
+pub(crate) fn copy_project_files_impl(
+    source_dir: &Path,
+    target_dir: &Path,
+    copy_files: &str,
+    vars: &CopyTemplateVars,
+) -> Result<usize, ContainerError> {
+    let mut copied = 0;
+
+    for entry in plan_project_files(source_dir, target_dir, copy_files) {
+        if entry.target_exists {
+            continue;
+        }
+        match copy_single_entry(&entry, target_dir, vars) {
+            Ok(()) => copied += 1,
+            Err(e) => tracing::warn!("Failed to copy {:?}: {e}", entry.source_path),
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Validate a candidate source path and describe how it would be copied.
+/// Returns `Ok(None)` for duplicates.
+fn plan_single_entry(
     source_path: &Path,
     source_root: &Path,
     target_root: &Path,
     seen: &mut HashSet<PathBuf>,
-) -> Result<bool, ContainerError> {
+) -> Result<Option<PlannedCopy>, ContainerError> {
     // Use symlink_metadata to get info about the path itself, not the target
     let metadata = source_path.symlink_metadata()?;
     let is_symlink = metadata.is_symlink();
@@ -146,21 +226,56 @@ fn copy_single_entry(
     }
 
     if !seen.insert(key_path) {
-        return Ok(false);
+        return Ok(None);
     }
 
-    let relative_path = source_path.strip_prefix(source_root).map_err(|e| {
-        ContainerError::Other(anyhow!(
-            "Failed to get relative path for {source_path:?}: {e}"
-        ))
-    })?;
+    let relative_source = source_path
+        .strip_prefix(source_root)
+        .map_err(|e| {
+            ContainerError::Other(anyhow!(
+                "Failed to get relative path for {source_path:?}: {e}"
+            ))
+        })?
+        .to_path_buf();
+
+    let template_target = if is_symlink {
+        None
+    } else {
+        relative_source
+            .to_str()
+            .and_then(|p| p.strip_suffix(TEMPLATE_SUFFIX))
+            .filter(|p| !p.is_empty() && !p.ends_with('/'))
+            .map(PathBuf::from)
+    };
 
-    let target_path = target_root.join(relative_path);
+    let (kind, relative_target) = match template_target {
+        Some(target) => (CopyFileKind::Template, target),
+        None if is_symlink => (CopyFileKind::Symlink, relative_source.clone()),
+        None => (CopyFileKind::File, relative_source.clone()),
+    };
 
     // Check if target already exists (use symlink_metadata to detect symlinks too)
-    if target_path.symlink_metadata().is_ok() {
-        return Ok(false);
-    }
+    let target_exists = target_root
+        .join(&relative_target)
+        .symlink_metadata()
+        .is_ok();
+
+    Ok(Some(PlannedCopy {
+        source_path: source_path.to_path_buf(),
+        relative_source,
+        relative_target,
+        kind,
+        target_exists,
+    }))
+}
+
+/// Copy a single planned file, symlink, or template into the target root.
+fn copy_single_entry(
+    entry: &PlannedCopy,
+    target_root: &Path,
+    vars: &CopyTemplateVars,
+) -> Result<(), ContainerError> {
+    let target_path = long_path(target_root.join(&entry.relative_target));
 
     if let Some(parent) = target_path.parent()
         && !parent.exists()
@@ -168,21 +283,97 @@ fn copy_single_entry(
         fs::create_dir_all(parent)?;
     }
 
-    if is_symlink {
-        // Read the symlink target and recreate it
-        let link_target = fs::read_link(source_path)?;
-        if let Err(e) = create_symlink(&link_target, &target_path) {
-            tracing::warn!(
-                "Failed to create symlink {:?} -> {:?}: {e}",
-                target_path,
-                link_target
-            );
+    match entry.kind {
+        CopyFileKind::Symlink => {
+            // Read the symlink target and recreate it
+            let link_target = fs::read_link(&entry.source_path)?;
+            if let Err(e) = create_symlink(&entry.source_path, &link_target, &target_path) {
+                tracing::warn!(
+                    "Failed to create symlink {:?} -> {:?}: {e}",
+                    target_path,
+                    link_target
+                );
+            }
         }
-    } else {
-        fs::copy(source_path, &target_path)?;
+        CopyFileKind::Template => {
+            let content = fs::read_to_string(&entry.source_path)?;
+            fs::write(&target_path, vars.render(&content))?;
+        }
+        CopyFileKind::File => {
+            fs::copy(&entry.source_path, &target_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Preview what `copy_project_files_impl` would copy. With no target
+/// directory, every entry is reported as not yet existing.
+pub(crate) fn preview_project_files(
+    source_dir: &Path,
+    target_dir: Option<&Path>,
+    copy_files: &str,
+) -> Vec<CopyFilePreview> {
+    let mut planned = match target_dir {
+        Some(target_dir) => plan_project_files(source_dir, target_dir, copy_files),
+        None => {
+            let mut planned = plan_project_files(source_dir, source_dir, copy_files);
+            for entry in &mut planned {
+                entry.target_exists = false;
+            }
+            planned
+        }
+    };
+    planned.sort_by(|a, b| a.relative_target.cmp(&b.relative_target));
+    planned.iter().map(CopyFilePreview::from).collect()
+}
+
+/// Run a repo's post-copy script in the worktree. Template variables are
+/// exposed as `VK_*` environment variables.
+pub(crate) async fn run_post_copy_script(
+    script: &str,
+    worktree_path: &Path,
+    vars: &CopyTemplateVars,
+) -> Result<(), ContainerError> {
+    let (shell_cmd, shell_arg) = get_shell_command();
+    let mut command = tokio::process::Command::new(shell_cmd);
+    command
+        .arg(shell_arg)
+        .arg(script)
+        .current_dir(worktree_path)
+        .envs(vars.env_vars())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(POST_COPY_SCRIPT_TIMEOUT, command.output())
+        .await
+        .map_err(|_| {
+            ContainerError::Other(anyhow!(
+                "Post-copy script timed out after {}s",
+                POST_COPY_SCRIPT_TIMEOUT.as_secs()
+            ))
+        })??;
+
+    if !output.status.success() {
+        return Err(ContainerError::Other(anyhow!(
+            "Post-copy script exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
     }
 
-    Ok(true)
+    Ok(())
+}
+
+/// Reserve an ephemeral localhost port for the `{{port}}` template variable.
+/// The port is released immediately, so it is a best-effort suggestion.
+pub(crate) fn allocate_port() -> Option<u16> {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .ok()
 }
 
 #[cfg(test)]
@@ -213,6 +404,7 @@ mod tests {
             source_dir.path(),
             target_dir.path(),
             ".env, *.json, src, config",
+            &CopyTemplateVars::new(),
         )
         .unwrap();
 
@@ -228,8 +420,12 @@ mod tests {
         let source_dir = TempDir::new().unwrap();
         let target_dir = TempDir::new().unwrap();
 
-        let result =
-            copy_project_files_impl(source_dir.path(), target_dir.path(), "nonexistent.txt");
+        let result = copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "nonexistent.txt",
+            &CopyTemplateVars::new(),
+        );
 
         assert!(result.is_ok());
         assert!(!target_dir.path().join("nonexistent.txt").exists());
@@ -240,7 +436,12 @@ mod tests {
         let source_dir = TempDir::new().unwrap();
         let target_dir = TempDir::new().unwrap();
 
-        let result = copy_project_files_impl(source_dir.path(), target_dir.path(), "");
+        let result = copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "",
+            &CopyTemplateVars::new(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(fs::read_dir(target_dir.path()).unwrap().count(), 0);
@@ -253,7 +454,13 @@ mod tests {
 
         fs::write(source_dir.path().join("test.txt"), "content").unwrap();
 
-        copy_project_files_impl(source_dir.path(), target_dir.path(), "  test.txt  ,  ").unwrap();
+        copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "  test.txt  ,  ",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         assert!(target_dir.path().join("test.txt").exists());
     }
@@ -271,7 +478,13 @@ mod tests {
         fs::create_dir(&nested_dir).unwrap();
         fs::write(nested_dir.join("deep.txt"), "deep").unwrap();
 
-        copy_project_files_impl(source_dir.path(), target_dir.path(), "config").unwrap();
+        copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "config",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         assert!(target_dir.path().join("config/app.json").exists());
         assert!(target_dir.path().join("config/nested/deep.txt").exists());
@@ -288,7 +501,12 @@ mod tests {
         fs::write(&outside_file, "secret").unwrap();
 
         // Pattern referencing parent directory should resolve to outside_file and be rejected
-        let result = copy_project_files_impl(source_dir.path(), target_dir.path(), "../secret.txt");
+        let result = copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "../secret.txt",
+            &CopyTemplateVars::new(),
+        );
 
         assert!(result.is_ok());
         assert_eq!(fs::read_dir(target_dir.path()).unwrap().count(), 0);
@@ -315,7 +533,13 @@ mod tests {
         fs::write(deep_dir.join("deep.yml"), "deep: config").unwrap();
 
         // Copy all YAML files recursively
-        copy_project_files_impl(source_dir.path(), target_dir.path(), "config/**/*.yml").unwrap();
+        copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "config/**/*.yml",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         // Verify only YAML files are copied
         assert!(target_dir.path().join("config/app.yml").exists());
@@ -349,8 +573,13 @@ mod tests {
         fs::write(src_dir.join("main.rs"), "main code").unwrap();
 
         // Copy with overlapping patterns: glob and specific file
-        copy_project_files_impl(source_dir.path(), target_dir.path(), "src/*.rs, src/lib.rs")
-            .unwrap();
+        copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "src/*.rs, src/lib.rs",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         // Verify file exists once (deduplication works)
         let target_file = target_dir.path().join("src/lib.rs");
@@ -372,7 +601,13 @@ mod tests {
         fs::write(src_dir.join("lib.rs"), "library code").unwrap();
 
         // Copy single file by exact path (exercises fast path)
-        copy_project_files_impl(source_dir.path(), target_dir.path(), "src/lib.rs").unwrap();
+        copy_project_files_impl(
+            source_dir.path(),
+            target_dir.path(),
+            "src/lib.rs",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         // Verify file is copied
         let target_file = target_dir.path().join("src/lib.rs");
@@ -391,7 +626,7 @@ mod tests {
         fs::write(src.path().join("real.txt"), "content").unwrap();
         symlink("real.txt", src.path().join("link.txt")).unwrap();
 
-        copy_project_files_impl(src.path(), dst.path(), "*.txt").unwrap();
+        copy_project_files_impl(src.path(), dst.path(), "*.txt", &CopyTemplateVars::new()).unwrap();
 
         // Both the file and symlink should be copied
         assert!(dst.path().join("real.txt").exists());
@@ -419,7 +654,13 @@ mod tests {
         symlink("data", src.path().join("data-link")).unwrap();
 
         // Copy the symlink directly
-        copy_project_files_impl(src.path(), dst.path(), "data-link").unwrap();
+        copy_project_files_impl(
+            src.path(),
+            dst.path(),
+            "data-link",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         // The symlink should be recreated
         let link_path = dst.path().join("data-link");
@@ -437,7 +678,13 @@ mod tests {
         // Create a symlink to a non-existent target
         symlink("nonexistent.txt", src.path().join("broken.txt")).unwrap();
 
-        copy_project_files_impl(src.path(), dst.path(), "broken.txt").unwrap();
+        copy_project_files_impl(
+            src.path(),
+            dst.path(),
+            "broken.txt",
+            &CopyTemplateVars::new(),
+        )
+        .unwrap();
 
         // The broken symlink should be copied
         let link_path = dst.path().join("broken.txt");
@@ -462,7 +709,8 @@ mod tests {
         symlink("base.yml", config_dir.join("current.yml")).unwrap();
 
         // Copy the whole directory
-        copy_project_files_impl(src.path(), dst.path(), "config").unwrap();
+        copy_project_files_impl(src.path(), dst.path(), "config", &CopyTemplateVars::new())
+            .unwrap();
 
         // Both file and symlink should be copied
         assert!(dst.path().join("config/base.yml").exists());
@@ -473,4 +721,57 @@ mod tests {
             "base.yml"
         );
     }
+
+    #[test]
+    fn test_template_files_are_rendered_without_suffix() {
+        let src = TempDir::new().unwrap();
+        let dst = TempDir::new().unwrap();
+
+        fs::write(
+            src.path().join(".env.template"),
+            "BRANCH={{workspace_branch}}\nPORT={{port}}\n",
+        )
+        .unwrap();
+
+        let vars = CopyTemplateVars::new()
+            .with("workspace_branch", "vk/abcd-test")
+            .with("port", "4100");
+        let copied =
+            copy_project_files_impl(src.path(), dst.path(), ".env.template", &vars).unwrap();
+
+        assert_eq!(copied, 1);
+        assert!(!dst.path().join(".env.template").exists());
+        assert_eq!(
+            fs::read_to_string(dst.path().join(".env")).unwrap(),
+            "BRANCH=vk/abcd-test\nPORT=4100\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_reports_kinds_and_existing_targets() {
+        let src = TempDir::new().unwrap();
+        let dst = TempDir::new().unwrap();
+
+        fs::write(src.path().join("config.json"), "{}").unwrap();
+        fs::write(src.path().join(".env.template"), "PORT={{port}}").unwrap();
+        fs::write(dst.path().join("config.json"), "{}").unwrap();
+
+        let mut previews: Vec<CopyFilePreview> =
+            plan_project_files(src.path(), dst.path(), "config.json, .env.template")
+                .iter()
+                .map(CopyFilePreview::from)
+                .collect();
+        previews.sort_by(|a, b| a.target.cmp(&b.target));
+
+        assert_eq!(previews.len(), 2);
+        assert_eq!(previews[0].target, ".env");
+        assert_eq!(previews[0].kind, CopyFileKind::Template);
+        assert!(!previews[0].already_exists);
+        assert_eq!(previews[1].target, "config.json");
+        assert_eq!(previews[1].kind, CopyFileKind::File);
+        assert!(previews[1].already_exists);
+
+        // Planning never writes to the target directory
+        assert!(!dst.path().join(".env").exists());
+    }
 }
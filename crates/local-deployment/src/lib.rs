@@ -1,7 +1,7 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use db::DBService;
+use db::{DBService, models::bisect::BisectRun};
 use deployment::{Deployment, DeploymentError, RemoteClientNotConfigured};
 use executors::profile::ExecutorConfigs;
 use services::services::{
@@ -17,17 +17,23 @@ use services::services::{
     filesystem::FilesystemService,
     git::GitService,
     git_watcher::GitWatcherManager,
-    image::ImageService,
+    image::{ImageService, ORPHAN_GRACE_PERIOD},
     merge_queue_store::MergeQueueStore,
     oauth_credentials::OAuthCredentials,
     operation_status::OperationStatusStore,
+    operations::OperationTracker,
     pr_cache::PrCache,
     project::ProjectService,
+    project_secrets,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    repo_metadata_cache::RepoMetadataCache,
+    scheduler::SchedulerService,
+    secrets::SecretService,
     share::{ShareConfig, SharePublisher},
     skills_cache::GlobalSkillsCache,
+    webhook_secrets,
 };
 use tokio::sync::{Mutex, RwLock};
 use utils::{
@@ -43,6 +49,9 @@ mod command;
 pub mod container;
 mod copy;
 
+/// How often unreferenced images are cleaned up, starting at launch
+const IMAGE_GC_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
 #[derive(Clone)]
 pub struct LocalDeployment {
     config: Arc<RwLock<Config>>,
@@ -68,10 +77,13 @@ pub struct LocalDeployment {
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
     git_watcher: GitWatcherManager,
     operation_status: OperationStatusStore,
+    operations: OperationTracker,
+    secrets: SecretService,
     merge_queue_store: MergeQueueStore,
     hook_execution_store: HookExecutionStore,
     skills_cache: GlobalSkillsCache,
     pr_cache: Arc<PrCache>,
+    repo_metadata_cache: Arc<RepoMetadataCache>,
     server_log_store: Arc<ServerLogStore>,
 }
 
@@ -156,9 +168,13 @@ impl LocalDeployment {
         {
             let image_service = image.clone();
             tokio::spawn(async move {
-                tracing::info!("Starting orphaned image cleanup...");
-                if let Err(e) = image_service.delete_orphaned_images().await {
-                    tracing::error!("Failed to clean up orphaned images: {}", e);
+                let mut gc_interval = tokio::time::interval(IMAGE_GC_INTERVAL);
+                loop {
+                    gc_interval.tick().await;
+                    tracing::info!("Starting orphaned image cleanup...");
+                    if let Err(e) = image_service.collect_garbage(ORPHAN_GRACE_PERIOD).await {
+                        tracing::error!("Failed to clean up orphaned images: {}", e);
+                    }
                 }
             });
         }
@@ -207,8 +223,22 @@ impl LocalDeployment {
 
         // Create stores that use the events_msg_store for broadcasting
         let operation_status = OperationStatusStore::new(events_msg_store.clone());
-        let merge_queue_store = MergeQueueStore::new(events_msg_store.clone());
+        let merge_queue_store =
+            MergeQueueStore::load(events_msg_store.clone(), db.pool.clone()).await?;
         let hook_execution_store = HookExecutionStore::new(events_msg_store.clone());
+        let operations = OperationTracker::load(db.pool.clone()).await?;
+        let interrupted_bisects = BisectRun::fail_interrupted(&db.pool).await?;
+        if interrupted_bisects > 0 {
+            tracing::info!(
+                count = interrupted_bisects,
+                "Marked bisect runs interrupted by the last shutdown as failed"
+            );
+        }
+        let secrets = SecretService::new();
+        project_secrets::migrate_plaintext(&db.pool, &secrets).await?;
+        project_secrets::remove_orphans(&db.pool, &secrets).await?;
+        webhook_secrets::migrate_plaintext(&db.pool, &secrets).await?;
+        webhook_secrets::remove_orphans(&db.pool, &secrets).await?;
 
         // We need to make analytics accessible to the ContainerService
         // TODO: Handle this more gracefully
@@ -229,6 +259,9 @@ impl LocalDeployment {
             share_publisher.clone(),
             skills_cache.clone(),
             hook_execution_store.clone(),
+            operations.clone(),
+            secrets.clone(),
+            embedding.clone(),
         )
         .await;
 
@@ -236,6 +269,8 @@ impl LocalDeployment {
 
         let file_search_cache = Arc::new(FileSearchCache::new());
         let pr_cache = Arc::new(PrCache::new());
+        let repo_metadata_cache =
+            Arc::new(RepoMetadataCache::new(git.clone(), git_watcher.clone()));
 
         let deployment = Self {
             config,
@@ -261,10 +296,13 @@ impl LocalDeployment {
             oauth_handoffs,
             git_watcher,
             operation_status,
+            operations,
+            secrets,
             merge_queue_store,
             hook_execution_store,
             skills_cache,
             pr_cache,
+            repo_metadata_cache,
             server_log_store,
         };
 
@@ -348,6 +386,17 @@ impl LocalDeployment {
             handle.shutdown().await;
         }
     }
+
+    /// Starts the background service that runs due task schedules.
+    pub fn spawn_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        SchedulerService::spawn(self.container.clone())
+    }
+
+    /// Resume merge queues persisted before the last shutdown
+    pub fn resume_merge_queues(&self) {
+        self.container
+            .resume_merge_queues(&self.merge_queue_store, &self.operation_status);
+    }
 }
 
 #[async_trait]
@@ -433,6 +482,14 @@ impl Deployment for LocalDeployment {
         &self.operation_status
     }
 
+    fn operations(&self) -> &OperationTracker {
+        &self.operations
+    }
+
+    fn secrets(&self) -> &SecretService {
+        &self.secrets
+    }
+
     fn merge_queue_store(&self) -> &MergeQueueStore {
         &self.merge_queue_store
     }
@@ -449,6 +506,10 @@ impl Deployment for LocalDeployment {
         &self.pr_cache
     }
 
+    fn repo_metadata_cache(&self) -> &Arc<RepoMetadataCache> {
+        &self.repo_metadata_cache
+    }
+
     fn server_log_store(&self) -> &Arc<ServerLogStore> {
         &self.server_log_store
     }
@@ -331,6 +331,8 @@ async fn create_tasks(
                 image_ids: None,
                 shared_task_id: None,
                 task_group_id: Some(group.id),
+                priority: None,
+                created_by: None,
             },
             Uuid::new_v4(),
         )
@@ -26,9 +26,65 @@ fn generate_types_content() -> String {
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
         db::models::repo::Repo::decl(),
+        db::models::repo::UpdateRepoScripts::decl(),
+        db::models::repo::UpdateRepoCheckoutOptions::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
         db::models::project_repo::UpdateProjectRepo::decl(),
+        db::models::project_env_file::ProjectEnvFile::decl(),
+        db::models::project_env_file::UpsertProjectEnvFile::decl(),
+        db::models::project_secret::ProjectSecret::decl(),
+        db::models::project_secret::UpsertProjectSecret::decl(),
+        db::models::project_template::ProjectTemplateRepo::decl(),
+        db::models::project_template::ProjectTemplateTask::decl(),
+        db::models::project_template::ProjectTemplate::decl(),
+        db::models::project_template::UpsertProjectTemplate::decl(),
+        services::services::project_template::ProjectFromTemplate::decl(),
+        services::services::project_export::ImportedProject::decl(),
+        db::models::project_tool_requirement::ProjectToolRequirement::decl(),
+        db::models::project_tool_requirement::CreateToolRequirement::decl(),
+        db::models::branch_cleanup_policy::BranchCleanupMode::decl(),
+        db::models::branch_cleanup_policy::BranchCleanupPolicy::decl(),
+        db::models::branch_cleanup_policy::UpdateBranchCleanupPolicy::decl(),
+        db::models::git_hooks_policy::GitHooksMode::decl(),
+        db::models::git_hooks_policy::GitHooksPolicy::decl(),
+        db::models::git_hooks_policy::UpdateGitHooksPolicy::decl(),
+        db::models::pr_draft_policy::PrDraftPolicy::decl(),
+        db::models::pr_draft_policy::UpdatePrDraftPolicy::decl(),
+        db::models::merge_check_policy::MergeCheckPolicy::decl(),
+        db::models::merge_check_policy::UpdateMergeCheckPolicy::decl(),
+        db::models::project_file_rule::FileRuleEffect::decl(),
+        db::models::project_file_rule::ProjectFileRule::decl(),
+        db::models::project_file_rule::CreateFileRule::decl(),
+        db::models::project_memory::ProjectMemoryKind::decl(),
+        db::models::project_memory::ProjectMemorySettings::decl(),
+        db::models::project_memory::UpdateProjectMemorySettings::decl(),
+        db::models::project_memory::ProjectMemory::decl(),
+        db::models::project_memory::CreateProjectMemory::decl(),
+        db::models::project_memory::UpdateProjectMemory::decl(),
+        db::models::sandbox_policy::ProjectSandboxPolicy::decl(),
+        db::models::redaction_patterns::ProjectRedactionPatterns::decl(),
+        db::models::approval_policy::ApprovalPolicy::decl(),
+        db::models::approval_policy::UpsertApprovalPolicy::decl(),
+        db::models::permission_finding::PermissionFindingKind::decl(),
+        db::models::permission_finding::PermissionFinding::decl(),
+        db::models::review_comment::ReviewCommentSide::decl(),
+        db::models::review_comment::ReviewComment::decl(),
+        db::models::review_comment::CreateReviewComment::decl(),
+        db::models::review_comment::UpdateReviewComment::decl(),
+        db::models::bisect::BisectStatus::decl(),
+        db::models::bisect::BisectVerdict::decl(),
+        db::models::bisect::BisectRun::decl(),
+        db::models::bisect::BisectStep::decl(),
+        db::models::webhook::WebhookEvent::decl(),
+        db::models::webhook::Webhook::decl(),
+        db::models::webhook::CreateWebhook::decl(),
+        db::models::webhook::UpdateWebhook::decl(),
+        db::models::prompt_template::PromptTemplate::decl(),
+        db::models::prompt_template::CreatePromptTemplate::decl(),
+        db::models::prompt_template::UpdatePromptTemplate::decl(),
+        db::models::user::User::decl(),
+        db::models::user::CreateUser::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
@@ -36,6 +92,7 @@ fn generate_types_content() -> String {
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskPriority::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
@@ -44,17 +101,51 @@ fn generate_types_content() -> String {
         db::models::task_group::TaskGroup::decl(),
         db::models::task_group::TaskStatusCounts::decl(),
         db::models::task_group::TaskGroupWithStats::decl(),
+        db::models::task_env_var::TaskEnvVar::decl(),
+        db::models::task_env_var::UpsertTaskEnvVar::decl(),
+        db::models::trash::TrashItemKind::decl(),
+        db::models::trash::TrashItem::decl(),
+        db::models::task_link::TaskLinkKind::decl(),
+        db::models::task_link::TaskLink::decl(),
+        db::models::task_link::CreateTaskLink::decl(),
+        db::models::task_link::UpdateTaskLink::decl(),
+        db::models::task_schedule::ScheduleRepo::decl(),
+        db::models::task_schedule::TaskSchedule::decl(),
+        db::models::task_schedule::CreateTaskSchedule::decl(),
+        db::models::task_schedule::UpdateTaskSchedule::decl(),
         db::models::notification::Notification::decl(),
         db::models::notification::NotificationType::decl(),
         db::models::notification::CreateNotification::decl(),
         db::models::notification::UpdateNotification::decl(),
         db::models::notification::NotificationStats::decl(),
+        server::routes::notifications::MarkAllReadRequest::decl(),
+        server::routes::notifications::MarkNotificationsReadRequest::decl(),
         db::models::agent_feedback::AgentFeedback::decl(),
         db::models::agent_feedback::CreateAgentFeedback::decl(),
         db::models::review_attention::ReviewAttention::decl(),
         db::models::review_attention::CreateReviewAttention::decl(),
         server::routes::feedback::FeedbackResponse::decl(),
+        server::routes::execution_processes::ExecutionProcessResources::decl(),
+        server::routes::execution_processes::KillProcessRequest::decl(),
+        server::routes::execution_processes::ReplayExecutionResponse::decl(),
+        server::routes::backups::BackupInfo::decl(),
+        db::models::backup_verification::BackupVerificationKind::decl(),
+        db::models::backup_verification::TableRowCount::decl(),
+        db::models::backup_verification::BackupVerification::decl(),
+        services::services::backup::RestoreReport::decl(),
+        db::models::audit_log::AuditAction::decl(),
+        db::models::audit_log::AuditLogEntry::decl(),
+        db::models::redaction::RedactionCounts::decl(),
+        db::models::project_purge::ProjectPurgeCounts::decl(),
+        server::routes::admin::RedactRequest::decl(),
+        server::routes::admin::RedactionReport::decl(),
+        server::routes::project_purge::ProjectPurgeReport::decl(),
+        utils::assets::AssetDirSource::decl(),
+        server::routes::assets::AssetDirInfo::decl(),
+        server::routes::assets::RelocateAssetDirRequest::decl(),
+        services::services::asset_relocation::RelocationReport::decl(),
         db::models::gantt::GanttTask::decl(),
+        db::models::project_dashboard::OpenTaskCounts::decl(),
         server::routes::gantt::PaginatedGanttTasks::decl(),
         db::models::task_group::CreateTaskGroup::decl(),
         db::models::task_group::UpdateTaskGroup::decl(),
@@ -72,7 +163,13 @@ fn generate_types_content() -> String {
         server::routes::conversations::CreateConversationResponse::decl(),
         server::routes::conversations::UpdateConversationRequest::decl(),
         server::routes::conversations::SendMessageRequest::decl(),
+        server::routes::conversations::ForkConversationRequest::decl(),
+        server::routes::conversations::ForkConversationResponse::decl(),
+        server::routes::conversations::queue::ReorderConversationQueueRequest::decl(),
         server::routes::task_groups::MergeTaskGroupRequest::decl(),
+        server::routes::task_notes::TaskNotes::decl(),
+        server::routes::task_notes::UpdateTaskNotes::decl(),
+        server::routes::task_watchers::TaskWatchers::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
@@ -91,6 +188,11 @@ fn generate_types_content() -> String {
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_resource_sample::ExecutionProcessResourceSample::decl(),
+        db::models::execution_process_failure::FailureReason::decl(),
+        db::models::execution_process_failure::ExecutionProcessFailure::decl(),
+        db::models::execution_process_retry::RetryReason::decl(),
+        db::models::execution_process_retry::ExecutionProcessRetry::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -106,6 +208,7 @@ fn generate_types_content() -> String {
         utils::approvals::ApprovalRequest::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
+        utils::diff::DiffFileMode::decl(),
         utils::assets::CustomSoundInfo::decl(),
         utils::claude_accounts::SavedAccount::decl(),
         utils::claude_accounts::SaveAccountRequest::decl(),
@@ -152,6 +255,7 @@ fn generate_types_content() -> String {
         server::routes::projects::MatchingTaskGroup::decl(),
         server::routes::projects::WorktreeInfo::decl(),
         server::routes::projects::ProjectWorktreesResponse::decl(),
+        server::routes::project_dashboard::ProjectDashboardResponse::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::repo::CloneRepoRequest::decl(),
@@ -168,8 +272,17 @@ fn generate_types_content() -> String {
         server::routes::repo::CreateRepoPrError::decl(),
         server::routes::repo::PushBranchRequest::decl(),
         server::routes::repo::PushBranchError::decl(),
+        services::services::repo_stats::RepoStats::decl(),
+        services::services::repo_stats::DailyCommitCount::decl(),
+        services::services::repo_stats::Contributor::decl(),
+        services::services::repo_stats::LanguageStat::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::prompt_templates::PreviewPromptTemplateRequest::decl(),
+        server::routes::project_templates::CreateProjectFromTemplate::decl(),
+        server::routes::project_export::ImportProjectRequest::decl(),
         server::routes::oauth::TokenResponse::decl(),
+        server::routes::users::SessionStatus::decl(),
+        server::routes::users::LoginRequest::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
         server::routes::config::McpServerQuery::decl(),
@@ -195,10 +308,13 @@ fn generate_types_content() -> String {
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
         server::routes::sessions::FollowUpResult::decl(),
+        server::routes::sessions::InterruptAndFollowUpRequest::decl(),
         db::models::execution_queue::ExecutionQueue::decl(),
+        db::models::execution_queue::ProjectQueueEntry::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
         server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
+        server::routes::task_attempts::MergeTaskAttemptResponse::decl(),
         server::routes::task_attempts::GenerateCommitMessageRequest::decl(),
         server::routes::task_attempts::GenerateCommitMessageResponse::decl(),
         server::routes::task_attempts::PushTaskAttemptRequest::decl(),
@@ -209,9 +325,23 @@ fn generate_types_content() -> String {
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::task_plans::BulkCreateTasksRequest::decl(),
+        server::routes::task_plans::StartPlannedTasks::decl(),
+        services::services::task_plan::PlannedTask::decl(),
+        services::services::task_plan::CreatedPlan::decl(),
+        server::routes::tasks::TaskMatchWithScore::decl(),
+        db::models::semantic_search::ConversationMessageMatch::decl(),
+        db::models::semantic_search::ExecutionSummaryMatch::decl(),
+        server::routes::search::SemanticSearchRequest::decl(),
+        server::routes::search::SemanticSearchResponse::decl(),
+        db::models::text_search::TextSearchSource::decl(),
+        db::models::text_search::HighlightedText::decl(),
+        db::models::text_search::TextSearchMatch::decl(),
+        server::routes::search::TextSearchRequest::decl(),
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
+        services::services::image::ImageGcReport::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
         server::routes::task_attempts::WorkspaceRepoInput::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
@@ -219,6 +349,13 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::gh_cli_setup::GhCliSetupError::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
         server::routes::task_attempts::AbortConflictsRequest::decl(),
+        server::routes::task_attempts::CherryPickRequest::decl(),
+        server::routes::task_attempts::CherryPickResponse::decl(),
+        server::routes::task_attempts::CherryPickOntoRequest::decl(),
+        server::routes::task_attempts::CherryPickOntoResponse::decl(),
+        server::routes::task_attempts::ConflictsResponse::decl(),
+        server::routes::task_attempts::ResolveConflictRequest::decl(),
+        server::routes::task_attempts::ContinueConflictsRequest::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::PushError::decl(),
         server::routes::task_attempts::pr::CreatePrError::decl(),
@@ -235,11 +372,39 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RepoBranchStatus::decl(),
         server::routes::task_attempts::QueueMergeRequest::decl(),
         server::routes::task_attempts::QueueMergeError::decl(),
+        server::routes::task_attempts::permission_findings::AcknowledgePermissionFindingsResponse::decl(),
+        server::routes::task_attempts::review_comments::SendReviewCommentsRequest::decl(),
+        server::routes::task_attempts::stage::StageChangesRequest::decl(),
+        server::routes::task_attempts::stage::StagedChanges::decl(),
+        services::services::workspace_bundle::ImportedWorkspace::decl(),
         server::routes::projects::MergeQueueCountResponse::decl(),
+        server::routes::projects::ReorderProjectQueueRequest::decl(),
+        server::routes::projects::ReorderMergeQueueRequest::decl(),
+        server::routes::project_env::ProjectEnvFileResponse::decl(),
+        services::services::toolchain_doctor::ToolStatus::decl(),
+        services::services::toolchain_doctor::ToolCheck::decl(),
+        services::services::toolchain_doctor::ToolchainReport::decl(),
+        services::services::branch_janitor::BranchCleanupOutcome::decl(),
+        services::services::branch_janitor::BranchCleanupEntry::decl(),
+        services::services::branch_janitor::BranchCleanupReport::decl(),
+        server::routes::project_branch_cleanup::RunBranchCleanupRequest::decl(),
+        server::routes::project_git_hooks::PreCommitStatus::decl(),
+        server::routes::project_redaction::UpdateRedactionPatterns::decl(),
+        server::routes::project_sandbox::SandboxStatus::decl(),
+        server::routes::bisect::StartBisectRequest::decl(),
+        server::routes::bisect::BisectRunDetails::decl(),
+        services::services::copy_files::CopyFileKind::decl(),
+        services::services::copy_files::CopyFilePreview::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
+        services::services::secrets::SecretRef::decl(),
+        services::services::secrets::SecretBackend::decl(),
+        server::routes::secrets::SecretsResponse::decl(),
+        server::routes::secrets::SetSecretRequest::decl(),
         services::services::config::Config::decl(),
+        services::services::config::compatibility::ConfigCompatibilityReport::decl(),
         services::services::config::NotificationConfig::decl(),
+        services::services::config::SlackNotificationConfig::decl(),
         services::services::config::ThemeMode::decl(),
         services::services::config::EditorConfig::decl(),
         services::services::config::EditorType::decl(),
@@ -252,6 +417,28 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::BackupConfig::decl(),
+        services::services::config::S3BackupConfig::decl(),
+        services::services::config::RetryPolicyConfig::decl(),
+        services::services::config::GitLabConfig::decl(),
+        services::services::config::BitbucketConfig::decl(),
+        services::services::config::GiteaConfig::decl(),
+        services::services::config::LinearConfig::decl(),
+        services::services::ssh_remote::SshRemoteConfig::decl(),
+        services::services::git_remote::GitRemoteProviderKind::decl(),
+        services::services::config::AnomalyDetectionConfig::decl(),
+        services::services::config::AnomalyThresholds::decl(),
+        services::services::config::UsagePricingConfig::decl(),
+        services::services::config::TokenPrice::decl(),
+        services::services::config::ProviderRateLimit::decl(),
+        services::services::config::OutputRedactionConfig::decl(),
+        services::services::usage::UsageReport::decl(),
+        services::services::usage::UsageTotals::decl(),
+        services::services::usage::ExecutorUsage::decl(),
+        services::services::usage::ProjectUsage::decl(),
+        services::services::usage::DailyUsage::decl(),
+        services::services::time_tracking::TimeTotals::decl(),
+        services::services::time_tracking::TaskTime::decl(),
+        services::services::time_tracking::ProjectTimeReport::decl(),
         services::services::git::GitBranch::decl(),
         services::services::git_watcher::GitStateChange::decl(),
         services::services::git_watcher::GitStateChangeKind::decl(),
@@ -259,10 +446,27 @@ fn generate_types_content() -> String {
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::operation_status::OperationStatus::decl(),
+        services::services::resource_monitor::ProcessStats::decl(),
+        services::services::resource_monitor::ResourceSample::decl(),
+        services::services::agent_auth::AgentAuthState::decl(),
+        services::services::agent_auth::AgentAuthStatus::decl(),
         services::services::operation_status::OperationStatusType::decl(),
-        services::services::merge_queue_store::MergeQueueEntry::decl(),
-        services::services::merge_queue_store::MergeQueueStatus::decl(),
+        db::models::merge_queue_entry::MergeQueueEntry::decl(),
+        db::models::merge_queue_entry::MergeQueueStatus::decl(),
+        services::services::merge_queue_store::QueuedMerge::decl(),
+        services::services::merge_queue_store::MergeQueueOutcome::decl(),
+        services::services::merge_queue_store::MergeQueueRun::decl(),
+        db::models::operation::OperationKind::decl(),
+        db::models::operation::OperationState::decl(),
+        db::models::operation::Operation::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::git::ConflictHunk::decl(),
+        services::services::git::ConflictedFile::decl(),
+        services::services::git::HunkResolution::decl(),
+        services::services::git::ConflictResolution::decl(),
+        services::services::worktree_manager::WorktreeIssue::decl(),
+        services::services::worktree_manager::WorktreeRepair::decl(),
+        services::services::workspace_manager::RepoWorktreeRepair::decl(),
         services::services::domain_events::HookPoint::decl(),
         services::services::domain_events::HookExecutionStatus::decl(),
         services::services::domain_events::HookExecution::decl(),
@@ -272,6 +476,10 @@ fn generate_types_content() -> String {
         executors::actions::script::ScriptContext::decl(),
         executors::actions::script::ScriptRequest::decl(),
         executors::actions::script::ScriptRequestLanguage::decl(),
+        executors::actions::script::ScriptShell::decl(),
+        executors::dev_shell::DevShell::decl(),
+        executors::sandbox::SandboxNetwork::decl(),
+        executors::sandbox::SandboxPolicy::decl(),
         executors::executors::BaseCodingAgent::decl(),
         executors::executors::CodingAgent::decl(),
         executors::executors::AvailabilityInfo::decl(),
@@ -298,6 +506,7 @@ fn generate_types_content() -> String {
         executors::executors::droid::Droid::decl(),
         executors::executors::droid::Autonomy::decl(),
         executors::executors::droid::ReasoningEffortLevel::decl(),
+        executors::executors::goose::Goose::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
@@ -407,6 +616,10 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "droid",
             generate_json_schema::<executors::executors::droid::Droid>()?,
         ),
+        (
+            "goose",
+            generate_json_schema::<executors::executors::goose::Goose>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
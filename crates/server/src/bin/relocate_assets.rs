@@ -0,0 +1,36 @@
+//! Move the asset directory while the app is stopped:
+//!
+//! ```text
+//! relocate_assets /mnt/big-disk/vibe-kanban
+//! ```
+//!
+//! The app picks up the new location on its next start. Set `VK_ASSET_DIR`
+//! instead to point a single run somewhere else.
+
+use std::path::PathBuf;
+
+use db::database_path;
+use services::services::asset_relocation::prepare_relocation;
+use utils::assets::asset_dir;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let (Some(target), None) = (args.next(), args.next()) else {
+        anyhow::bail!("Usage: relocate_assets <target-dir>");
+    };
+
+    let from = asset_dir();
+    let relocation = prepare_relocation(&from, &database_path()?, &PathBuf::from(&target))?;
+    println!("Copying {} to {}...", from.display(), target);
+    let report = relocation.run().await?;
+    println!(
+        "Copied the database and {} other files ({} bytes) to {}.",
+        report.files_copied, report.bytes_copied, report.to
+    );
+    println!(
+        "The app will use the new directory when it next starts; {} can be deleted after that.",
+        report.from
+    );
+    Ok(())
+}
@@ -8,23 +8,35 @@ use db::models::{
     conversation_message::ConversationMessageError, conversation_session::ConversationSessionError,
     execution_process::ExecutionProcessError, project::ProjectError,
     project_repo::ProjectRepoError, repo::RepoError, scratch::ScratchError, session::SessionError,
-    workspace::WorkspaceError,
+    task_dependency::TaskDependencyError, workspace::WorkspaceError,
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    asset_relocation::RelocationError,
+    backup::BackupError,
+    bitbucket::BitbucketServiceError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     conversation::ConversationServiceError,
     git::GitServiceError,
+    git_remote::GitRemoteError,
+    gitea::GiteaServiceError,
     github::GitHubServiceError,
+    gitlab::GitLabServiceError,
     gix_reader::GixReaderError,
     image::ImageError,
     project::ProjectServiceError,
+    project_export::ProjectExportError,
+    project_template::ProjectTemplateError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    resource_monitor::ResourceMonitorError,
+    secrets::SecretStoreError,
     share::ShareError,
+    toolchain_doctor::ToolchainDoctorError,
+    workspace_bundle::WorkspaceBundleError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -56,6 +68,8 @@ pub enum ApiError {
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
     #[error(transparent)]
+    GitRemote(#[from] GitRemoteError),
+    #[error(transparent)]
     Deployment(#[from] DeploymentError),
     #[error(transparent)]
     Container(#[from] ContainerError),
@@ -148,6 +162,13 @@ impl IntoResponse for ApiError {
                 ConversationServiceError::NotFound => {
                     (StatusCode::NOT_FOUND, "ConversationServiceError")
                 }
+                ConversationServiceError::InvalidForkPoint(_)
+                | ConversationServiceError::NoRepositories => {
+                    (StatusCode::BAD_REQUEST, "ConversationServiceError")
+                }
+                ConversationServiceError::Message(ConversationMessageError::NotFound) => {
+                    (StatusCode::NOT_FOUND, "ConversationServiceError")
+                }
                 _ => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "ConversationServiceError",
@@ -157,12 +178,16 @@ impl IntoResponse for ApiError {
             ApiError::GitService(git_err) => match git_err {
                 GitServiceError::MergeConflicts(_) => (StatusCode::CONFLICT, "GitServiceError"),
                 GitServiceError::RebaseInProgress => (StatusCode::CONFLICT, "GitServiceError"),
+                GitServiceError::OperationInProgress(_) => {
+                    (StatusCode::CONFLICT, "GitServiceError")
+                }
                 GitServiceError::BranchNotFound(_) => (StatusCode::NOT_FOUND, "GitServiceError"),
                 GitServiceError::InvalidRepository(_) => {
                     (StatusCode::BAD_REQUEST, "GitServiceError")
                 }
                 GitServiceError::WorktreeDirty(_, _) => (StatusCode::CONFLICT, "GitServiceError"),
                 GitServiceError::BranchesDiverged(_) => (StatusCode::CONFLICT, "GitServiceError"),
+                GitServiceError::ConflictResolution(_) => (StatusCode::CONFLICT, "GitServiceError"),
                 // Handle gix-specific errors with appropriate status codes
                 GitServiceError::GixReader(gix_err) => match gix_err {
                     GixReaderError::ReferenceNotFound(_) => {
@@ -194,11 +219,27 @@ impl IntoResponse for ApiError {
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
+            ApiError::GitRemote(remote_err) => match remote_err {
+                // Missing or broken settings are for the user to fix
+                GitRemoteError::UnsupportedRemote(_)
+                | GitRemoteError::GitLab(
+                    GitLabServiceError::NotConfigured | GitLabServiceError::InvalidUrl(_),
+                )
+                | GitRemoteError::Bitbucket(BitbucketServiceError::NotConfigured)
+                | GitRemoteError::Gitea(GiteaServiceError::NotConfigured) => {
+                    (StatusCode::BAD_REQUEST, "GitRemoteError")
+                }
+                GitRemoteError::GitHub(_) => {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitRemoteError"),
+            },
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
             ApiError::Container(container_err) => match container_err {
                 ContainerError::WorkspaceAlreadyRunning(_) => {
                     (StatusCode::CONFLICT, "WorkspaceAlreadyRunning")
                 }
+                ContainerError::Sandbox(_) => (StatusCode::BAD_REQUEST, "SandboxError"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             },
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
@@ -277,6 +318,11 @@ impl IntoResponse for ApiError {
                 GitServiceError::RebaseInProgress => {
                     "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.".to_string()
                 }
+                GitServiceError::OperationInProgress(op) => format!(
+                    "A {} is already in progress. Resolve conflicts or abort the {}, then retry.",
+                    op.command(),
+                    op.command()
+                ),
                 GitServiceError::BranchNotFound(branch) => {
                     format!("Branch '{}' not found.", branch)
                 }
@@ -289,6 +335,9 @@ impl IntoResponse for ApiError {
                 GitServiceError::BranchesDiverged(msg) => {
                     format!("Branches have diverged: {}", msg)
                 }
+                GitServiceError::ConflictResolution(msg) => {
+                    format!("Cannot resolve conflicts: {}", msg)
+                }
                 // User-friendly messages for gix errors
                 GitServiceError::GixReader(gix_err) => match gix_err {
                     GixReaderError::ReferenceNotFound(ref_name) => {
@@ -324,6 +373,7 @@ impl IntoResponse for ApiError {
                 ContainerError::WorkspaceAlreadyRunning(_) => {
                     "An agent is already running for this workspace. Please wait for it to complete.".to_string()
                 }
+                ContainerError::Sandbox(sandbox_err) => sandbox_err.to_string(),
                 _ => format!("ContainerError: {}", container_err),
             },
             ApiError::RemoteClient(err) => match err {
@@ -512,6 +562,54 @@ impl From<RepoServiceError> for ApiError {
     }
 }
 
+impl From<ToolchainDoctorError> for ApiError {
+    fn from(err: ToolchainDoctorError) -> Self {
+        match err {
+            ToolchainDoctorError::Database(db_err) => ApiError::Database(db_err),
+            ToolchainDoctorError::Io(io_err) => ApiError::Io(io_err),
+            ToolchainDoctorError::GitCli(git_cli_err) => ApiError::BadRequest(format!(
+                "Failed to create toolchain doctor worktree: {}",
+                git_cli_err
+            )),
+        }
+    }
+}
+
+impl From<BackupError> for ApiError {
+    fn from(err: BackupError) -> Self {
+        match err {
+            BackupError::Io(io_err) => ApiError::Io(io_err),
+            BackupError::NotFound(id) => ApiError::NotFound(format!("Backup not found: {id}")),
+            BackupError::InvalidArchive(_) => ApiError::BadRequest(err.to_string()),
+            _ => ApiError::Internal(err.to_string()),
+        }
+    }
+}
+
+impl From<RelocationError> for ApiError {
+    fn from(err: RelocationError) -> Self {
+        match err {
+            RelocationError::Io(io_err) => ApiError::Io(io_err),
+            RelocationError::Database(db_err) => ApiError::Database(db_err),
+            RelocationError::SetByEnvironment => ApiError::Conflict(err.to_string()),
+            RelocationError::InvalidTarget(_) => ApiError::BadRequest(err.to_string()),
+            RelocationError::Mismatch(_) | RelocationError::Integrity(_) => {
+                ApiError::Internal(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<ResourceMonitorError> for ApiError {
+    fn from(err: ResourceMonitorError) -> Self {
+        match err {
+            ResourceMonitorError::NotRunning(_) => ApiError::Conflict(err.to_string()),
+            ResourceMonitorError::NotInTree { .. } => ApiError::BadRequest(err.to_string()),
+            ResourceMonitorError::KillFailed(_) => ApiError::Internal(err.to_string()),
+        }
+    }
+}
+
 impl From<ProjectRepoError> for ApiError {
     fn from(err: ProjectRepoError) -> Self {
         match err {
@@ -525,3 +623,59 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+impl From<ProjectTemplateError> for ApiError {
+    fn from(err: ProjectTemplateError) -> Self {
+        match err {
+            ProjectTemplateError::Database(db_err) => ApiError::Database(db_err),
+            ProjectTemplateError::Repo(repo_err) => repo_err.into(),
+            ProjectTemplateError::Project(project_err) => project_err.into(),
+            ProjectTemplateError::ProjectRepo(project_repo_err) => project_repo_err.into(),
+            ProjectTemplateError::MissingRepository(..) => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<ProjectExportError> for ApiError {
+    fn from(err: ProjectExportError) -> Self {
+        match err {
+            ProjectExportError::Database(db_err) => ApiError::Database(db_err),
+            ProjectExportError::Scratch(scratch_err) => ApiError::ScratchError(scratch_err),
+            ProjectExportError::Project(project_err) => project_err.into(),
+            ProjectExportError::ProjectRepo(project_repo_err) => project_repo_err.into(),
+            ProjectExportError::Dependency(TaskDependencyError::Database(db_err)) => {
+                ApiError::Database(db_err)
+            }
+            ProjectExportError::Dependency(_) | ProjectExportError::UnsupportedVersion(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<WorkspaceBundleError> for ApiError {
+    fn from(err: WorkspaceBundleError) -> Self {
+        match err {
+            WorkspaceBundleError::Database(db_err) => ApiError::Database(db_err),
+            WorkspaceBundleError::Git(git_err) => ApiError::GitService(git_err),
+            WorkspaceBundleError::Scratch(scratch_err) => ApiError::ScratchError(scratch_err),
+            WorkspaceBundleError::Session(session_err) => ApiError::Session(session_err),
+            WorkspaceBundleError::Io(io_err) => ApiError::Io(io_err),
+            WorkspaceBundleError::Log(_) => ApiError::Internal(err.to_string()),
+            WorkspaceBundleError::Invalid(_)
+            | WorkspaceBundleError::UnsupportedVersion(_)
+            | WorkspaceBundleError::MissingRepository(_) => ApiError::BadRequest(err.to_string()),
+            WorkspaceBundleError::BranchExists(..) => ApiError::Conflict(err.to_string()),
+        }
+    }
+}
+
+impl From<SecretStoreError> for ApiError {
+    fn from(err: SecretStoreError) -> Self {
+        match err {
+            SecretStoreError::Io(io_err) => ApiError::Io(io_err),
+            SecretStoreError::InvalidName(_) => ApiError::BadRequest(err.to_string()),
+            _ => ApiError::Internal(err.to_string()),
+        }
+    }
+}
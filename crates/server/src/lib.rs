@@ -1,11 +1,27 @@
+use std::sync::LazyLock;
+
+use tokio_util::sync::CancellationToken;
+
 pub mod error;
+pub mod listener;
 pub mod mcp;
 pub mod middleware;
 pub mod routes;
 
+pub use listener::start_server;
+
 pub type DeploymentImpl = local_deployment::LocalDeployment;
 
-/// Waits for shutdown signals (Ctrl+C or SIGTERM on Unix).
+static SHUTDOWN_REQUESTED: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+
+/// Shut the server down gracefully, as on Ctrl+C. Requests in flight still
+/// get their responses.
+pub fn request_shutdown() {
+    SHUTDOWN_REQUESTED.cancel();
+}
+
+/// Waits for shutdown signals (Ctrl+C or SIGTERM on Unix) or
+/// [`request_shutdown`].
 pub async fn shutdown_signal() {
     let ctrl_c = async {
         if let Err(e) = tokio::signal::ctrl_c().await {
@@ -29,12 +45,16 @@ pub async fn shutdown_signal() {
         tokio::select! {
             _ = ctrl_c => {},
             _ = terminate => {},
+            _ = SHUTDOWN_REQUESTED.cancelled() => {},
         }
     }
 
     #[cfg(not(unix))]
     {
-        ctrl_c.await;
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = SHUTDOWN_REQUESTED.cancelled() => {},
+        }
     }
 }
 
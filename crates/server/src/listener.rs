@@ -0,0 +1,186 @@
+//! Where the server listens for requests.
+//!
+//! By default the server binds a TCP port (`HOST`, `BACKEND_PORT` or `PORT`),
+//! which any local program can reach. With `VIBE_KANBAN_SOCKET` set it
+//! listens on a Unix domain socket at that path instead, readable only by the
+//! current user, or on Windows on the named pipe of that name (e.g.
+//! `\\.\pipe\vibe-kanban`). The desktop app connects over the socket.
+
+use axum::{Router, routing::IntoMakeService};
+use strip_ansi_escapes::strip;
+
+use crate::shutdown_signal;
+
+/// Environment variable naming the socket or pipe to listen on
+pub const SOCKET_ENV: &str = "VIBE_KANBAN_SOCKET";
+
+/// Serve `app` until a shutdown signal is received
+pub async fn start_server(app: IntoMakeService<Router>) -> anyhow::Result<()> {
+    match std::env::var(SOCKET_ENV) {
+        Ok(socket) if !socket.trim().is_empty() => serve_socket(socket.trim(), app).await,
+        _ => serve_tcp(app).await,
+    }
+}
+
+async fn serve_tcp(app: IntoMakeService<Router>) -> anyhow::Result<()> {
+    let port_str = std::env::var("BACKEND_PORT")
+        .or_else(|_| std::env::var("PORT"))
+        .map_err(|_| anyhow::anyhow!("BACKEND_PORT or PORT environment variable must be set"))?;
+
+    // remove any ANSI codes, then parse
+    let cleaned =
+        String::from_utf8(strip(port_str.as_bytes())).expect("UTF-8 after stripping ANSI");
+    let port: u16 = cleaned
+        .trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid port value '{}': {}", cleaned.trim(), e))?;
+
+    let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
+    let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
+    let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
+
+    tracing::info!("Server running on http://{host}:{actual_port}");
+
+    if !cfg!(debug_assertions) {
+        tracing::info!("Opening browser...");
+        tokio::spawn(async move {
+            if let Err(e) =
+                utils::browser::open_browser(&format!("http://127.0.0.1:{actual_port}")).await
+            {
+                tracing::warn!(
+                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
+                    e,
+                    actual_port
+                );
+            }
+        });
+    }
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn serve_socket(path: &str, app: IntoMakeService<Router>) -> anyhow::Result<()> {
+    use std::{
+        fs,
+        os::unix::fs::{DirBuilderExt, PermissionsExt},
+        path::Path,
+    };
+
+    let path = Path::new(path);
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+    // A socket left behind by a server that didn't shut down cleanly
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    // Bind inside a directory only we can enter and restrict the socket
+    // there, so it is never reachable by others before it is moved in place
+    let staging = parent.join(format!(".vibe-kanban-socket-{}", uuid::Uuid::new_v4()));
+    fs::DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join("socket");
+    let bound = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_file(&staged);
+    let _ = fs::remove_dir(&staging);
+    let listener = bound?;
+
+    tracing::info!("Server running on unix socket {}", path.display());
+
+    let result = axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await;
+    let _ = fs::remove_file(path);
+    result?;
+    Ok(())
+}
+
+#[cfg(windows)]
+async fn serve_socket(name: &str, app: IntoMakeService<Router>) -> anyhow::Result<()> {
+    let listener = named_pipe::NamedPipeListener::bind(name)?;
+
+    tracing::info!("Server running on named pipe {}", name);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+#[cfg(windows)]
+mod named_pipe {
+    use std::{io, time::Duration};
+
+    use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+
+    /// Accepts connections on a named pipe. Each instance of a pipe serves one
+    /// client, so a new instance is created for the next client as soon as one
+    /// connects.
+    pub struct NamedPipeListener {
+        name: String,
+        next: NamedPipeServer,
+    }
+
+    impl NamedPipeListener {
+        pub fn bind(name: &str) -> io::Result<Self> {
+            // Fails if another process already owns a pipe of that name
+            let next = ServerOptions::new()
+                .first_pipe_instance(true)
+                .reject_remote_clients(true)
+                .create(name)?;
+            Ok(Self {
+                name: name.to_string(),
+                next,
+            })
+        }
+
+        fn create_instance(&self) -> io::Result<NamedPipeServer> {
+            ServerOptions::new()
+                .reject_remote_clients(true)
+                .create(&self.name)
+        }
+    }
+
+    impl axum::serve::Listener for NamedPipeListener {
+        type Io = NamedPipeServer;
+        type Addr = String;
+
+        async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+            loop {
+                let result = match self.next.connect().await {
+                    Ok(()) => self
+                        .create_instance()
+                        .map(|next| std::mem::replace(&mut self.next, next)),
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(connected) => return (connected, self.name.clone()),
+                    Err(e) => {
+                        tracing::error!("Named pipe accept error: {}", e);
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                        // Start over with a fresh instance
+                        if let Ok(next) = self.create_instance() {
+                            self.next = next;
+                        }
+                    }
+                }
+            }
+        }
+
+        fn local_addr(&self) -> io::Result<Self::Addr> {
+            Ok(self.name.clone())
+        }
+    }
+}
@@ -2,20 +2,31 @@ use std::sync::Arc;
 
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, perform_cleanup_actions, routes, shutdown_signal};
-use services::services::container::ContainerService;
+use server::{
+    DeploymentImpl,
+    middleware::auth::{print_setup_token, write_internal_token},
+    perform_cleanup_actions, routes, start_server,
+};
+use services::services::{
+    backup::{
+        AssetPaths, apply_pending_restore, backup_before_migrations, restore_pre_migration_backup,
+        set_aside_pending_restore,
+    },
+    container::ContainerService,
+};
 use sqlx::Error as SqlxError;
-use strip_ansi_escapes::strip;
 use thiserror::Error;
 use tracing_subscriber::{EnvFilter, prelude::*};
 use utils::{
-    assets::{alerts_dir, asset_dir},
-    browser::open_browser,
+    assets::{alerts_dir, asset_dir, backup_dir},
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
     server_log_layer::ServerLogLayer,
     server_log_store::ServerLogStore,
 };
 
+/// Puts back the database snapshot taken before the last migrations, then exits
+const ROLLBACK_MIGRATION_FLAG: &str = "--rollback-last-migration-restore";
+
 #[derive(Debug, Error)]
 pub enum VibeKanbanError {
     #[error(transparent)]
@@ -68,8 +79,58 @@ async fn main() -> Result<(), VibeKanbanError> {
         );
     }
 
+    let asset_paths = AssetPaths::resolve()
+        .map_err(|e| anyhow::anyhow!("Failed to resolve the database location: {e}"))?;
+
+    // A restore staged while the app ran replaces the database before it
+    // opens. One that can't be applied is set aside so the app still starts.
+    match apply_pending_restore(&asset_paths).await {
+        Ok(Some(report)) => tracing::info!(
+            "Restored {} from a backup; replaced files are in {}",
+            report.restored.join(", "),
+            report.rollback_dir
+        ),
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!("Failed to apply staged restore: {}", e);
+            match set_aside_pending_restore(&asset_paths) {
+                Ok(Some(dir)) => {
+                    tracing::warn!("Moved the staged restore to {}", dir.display())
+                }
+                Ok(None) => {}
+                Err(e) => tracing::error!("Failed to move the staged restore aside: {}", e),
+            }
+        }
+    }
+
+    if std::env::args().any(|arg| arg == ROLLBACK_MIGRATION_FLAG) {
+        let report = restore_pre_migration_backup(&asset_paths, &backup_dir())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to restore pre-migration backup: {e}"))?;
+        tracing::info!(
+            "Restored {}. Start the previous version to keep using this database; \
+            this version would migrate it again. Replaced files are in {}",
+            report.restored.join(", "),
+            report.rollback_dir
+        );
+        return Ok(());
+    }
+
+    // Migrations cannot be undone, so keep a copy of the database they change
+    backup_before_migrations(&asset_paths, &backup_dir())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to back up before migrating: {e}"))?;
+
+    // Lets the MCP task server call the API once sign-in is required
+    if let Err(e) = write_internal_token() {
+        tracing::warn!("Failed to write internal API token: {}", e);
+    }
+
     let deployment = DeploymentImpl::new_with_log_store(server_log_store).await?;
     deployment.update_sentry_scope().await?;
+    if let Err(e) = print_setup_token(&deployment).await {
+        tracing::warn!("Failed to check for accounts: {}", e);
+    }
     let deployment_for_orphan_cleanup = deployment.clone();
     tokio::spawn(async move {
         if let Err(e) = deployment_for_orphan_cleanup
@@ -103,6 +164,10 @@ async fn main() -> Result<(), VibeKanbanError> {
     deployment.spawn_pr_monitor_service().await;
     deployment.spawn_embedding_worker();
     deployment.spawn_backup_service().await;
+    deployment.spawn_branch_janitor();
+    deployment.spawn_trash_purge();
+    deployment.spawn_scheduler();
+    deployment.resume_merge_queues();
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -130,40 +195,7 @@ async fn main() -> Result<(), VibeKanbanError> {
 
     let app_router = routes::router(deployment.clone());
 
-    let port_str = std::env::var("BACKEND_PORT")
-        .or_else(|_| std::env::var("PORT"))
-        .map_err(|_| anyhow::anyhow!("BACKEND_PORT or PORT environment variable must be set"))?;
-
-    // remove any ANSI codes, then parse
-    let cleaned =
-        String::from_utf8(strip(port_str.as_bytes())).expect("UTF-8 after stripping ANSI");
-    let port: u16 = cleaned
-        .trim()
-        .parse()
-        .map_err(|e| anyhow::anyhow!("Invalid port value '{}': {}", cleaned.trim(), e))?;
-
-    let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
-    let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
-
-    tracing::info!("Server running on http://{host}:{actual_port}");
-
-    if !cfg!(debug_assertions) {
-        tracing::info!("Opening browser...");
-        tokio::spawn(async move {
-            if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
-                tracing::warn!(
-                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
-                    e,
-                    actual_port
-                );
-            }
-        });
-    }
-
-    axum::serve(listener, app_router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    start_server(app_router).await?;
 
     perform_cleanup_actions(&deployment).await;
 
@@ -23,6 +23,7 @@ use rmcp::{
 use schemars;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use utils::assets::internal_token_path;
 use uuid::Uuid;
 
 use crate::routes::{
@@ -736,13 +737,33 @@ pub struct McpContext {
 impl TaskServer {
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: Self::build_client(),
             base_url: base_url.to_string(),
             tool_router: Self::tool_router(),
             context: None,
         }
     }
 
+    /// HTTP client authenticated with the server's internal token, when the
+    /// server has written one, so requests pass once sign-in is required
+    fn build_client() -> reqwest::Client {
+        let mut headers = reqwest::header::HeaderMap::new();
+        match std::fs::read_to_string(internal_token_path()) {
+            Ok(token) => {
+                if let Ok(value) =
+                    reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+                {
+                    headers.insert(reqwest::header::AUTHORIZATION, value);
+                }
+            }
+            Err(e) => tracing::debug!("No internal API token available: {}", e),
+        }
+        reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new())
+    }
+
     pub async fn init(mut self) -> Self {
         let context = self.fetch_context_at_startup().await;
 
@@ -1093,6 +1114,8 @@ impl TaskServer {
                 image_ids: None,
                 shared_task_id: None,
                 task_group_id,
+                priority: None,
+                created_by: None,
             }))
             .await
         {
@@ -1178,6 +1201,8 @@ impl TaskServer {
                     image_ids: None,
                     shared_task_id: None,
                     task_group_id: task.task_group_id,
+                    priority: None,
+                    created_by: None,
                 }))
                 .await
             {
@@ -1250,6 +1275,8 @@ impl TaskServer {
                 image_ids: None,
                 shared_task_id: None,
                 task_group_id,
+                priority: None,
+                created_by: None,
             }))
             .await
         {
@@ -1511,6 +1538,7 @@ impl TaskServer {
             parent_workspace_id: None,
             image_ids: None,
             task_group_id,
+            priority: None,
         };
         let url = self.url(&format!("/api/tasks/{}", task_id));
         let updated_task: Task = match self.send_json(self.client.put(&url).json(&payload)).await {
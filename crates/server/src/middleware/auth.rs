@@ -0,0 +1,209 @@
+//! Sign-in for servers shared by several people.
+//!
+//! A server without accounts works as before: every request is let through.
+//! Once the first user is created, API requests need a session cookie, or
+//! the internal token that the server hands to its own helper processes.
+//! Creating the first account takes the setup token printed on the server's
+//! stderr, so nobody else reaching the server can claim it.
+
+use std::{fmt::Write as _, io::Write as _, sync::LazyLock};
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use db::models::user::{User, UserSession};
+use deployment::Deployment;
+use rand::{Rng, distributions::Alphanumeric};
+use services::services::config::{Config, user_preferences::UserPreferences};
+use sha2::{Digest, Sha256};
+use utils::assets::internal_token_path;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub const SESSION_COOKIE: &str = "vk_session";
+
+/// How long a sign-in lasts
+pub const SESSION_TTL: chrono::Duration = chrono::Duration::days(30);
+
+/// Generated once per process, so a token left over from an earlier run is
+/// worthless
+static INTERNAL_TOKEN: LazyLock<String> = LazyLock::new(generate_token);
+
+/// Required to create the first account; generated once per process
+static SETUP_TOKEN: LazyLock<String> = LazyLock::new(generate_token);
+
+/// The signed-in user making a request; None on a server without accounts
+/// and for requests made with the internal token
+#[derive(Debug, Clone, Default)]
+pub struct CurrentUser(pub Option<User>);
+
+impl CurrentUser {
+    pub fn id(&self) -> Option<Uuid> {
+        self.0.as_ref().map(|user| user.id)
+    }
+
+    /// Reject requests not made by a signed-in admin
+    pub fn require_admin(&self) -> Result<(), ApiError> {
+        match &self.0 {
+            Some(user) if user.is_admin => Ok(()),
+            _ => Err(ApiError::Forbidden("Only admins can do this".to_string())),
+        }
+    }
+
+    /// Like [`Self::require_admin`], but lets every request through on a
+    /// server without accounts, where there is nobody to be admin
+    pub async fn require_admin_if_accounts(
+        &self,
+        deployment: &DeploymentImpl,
+    ) -> Result<(), ApiError> {
+        if self.0.is_none() && !User::any_exist(&deployment.db().pool).await? {
+            return Ok(());
+        }
+        self.require_admin()
+    }
+
+    /// The global config with this user's preferences applied
+    pub async fn config(&self, deployment: &DeploymentImpl) -> Config {
+        let mut config = deployment.config().read().await.clone();
+        if let Some(id) = self.id() {
+            match User::preferences(&deployment.db().pool, id).await {
+                Ok(Some(raw)) => UserPreferences::from_json(&raw).apply(&mut config),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load preferences of user {}: {}", id, e),
+            }
+        }
+        config
+    }
+}
+
+/// Write the internal token where helper processes started by this server
+/// can read it. Only the owner of the asset directory may read the file,
+/// from the moment it is created.
+pub fn write_internal_token() -> std::io::Result<()> {
+    let path = internal_token_path();
+    // A file left by an earlier run may have looser permissions
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&path)?
+        .write_all(INTERNAL_TOKEN.as_str().as_bytes())
+}
+
+/// Print the setup token while the server has no accounts, so whoever
+/// started the server can create the first one. It goes to stderr only:
+/// the server log is readable over the API while there are no accounts.
+pub async fn print_setup_token(deployment: &DeploymentImpl) -> Result<(), ApiError> {
+    if !User::any_exist(&deployment.db().pool).await? {
+        eprintln!(
+            "To create the first account, use the setup token {}",
+            SETUP_TOKEN.as_str()
+        );
+    }
+    Ok(())
+}
+
+pub fn is_setup_token(token: &str) -> bool {
+    token == SETUP_TOKEN.as_str()
+}
+
+pub fn generate_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(48)
+        .map(char::from)
+        .collect()
+}
+
+/// Sessions are stored by hash, so a leaked database doesn't sign anyone in
+pub fn hash_token(token: &str) -> String {
+    let mut output = String::with_capacity(64);
+    for byte in Sha256::digest(token.as_bytes()) {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+/// The session token sent in the request's cookies
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value)
+}
+
+/// `Set-Cookie` value starting a session, or ending it when `token` is None
+pub fn session_cookie(token: Option<&str>) -> HeaderValue {
+    let cookie = match token {
+        Some(token) => format!(
+            "{SESSION_COOKIE}={token}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}",
+            SESSION_TTL.num_seconds()
+        ),
+        None => format!("{SESSION_COOKIE}=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0"),
+    };
+    HeaderValue::from_str(&cookie).expect("session cookie is a valid header value")
+}
+
+/// The user signed in with the request's session cookie
+pub async fn session_user(
+    deployment: &DeploymentImpl,
+    headers: &HeaderMap,
+) -> Result<Option<User>, ApiError> {
+    let Some(token) = session_token(headers) else {
+        return Ok(None);
+    };
+    Ok(UserSession::find_user(&deployment.db().pool, &hash_token(token)).await?)
+}
+
+fn has_internal_token(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| token == INTERNAL_TOKEN.as_str())
+}
+
+/// Resolve the signed-in user into a [`CurrentUser`] extension, rejecting
+/// the request when the server has accounts and nobody is signed in
+pub async fn require_user_middleware(
+    State(deployment): State<DeploymentImpl>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let current_user = match resolve_current_user(&deployment, request.headers()).await {
+        Ok(current_user) => current_user,
+        Err(e) => return e.into_response(),
+    };
+    request.extensions_mut().insert(current_user);
+    next.run(request).await
+}
+
+async fn resolve_current_user(
+    deployment: &DeploymentImpl,
+    headers: &HeaderMap,
+) -> Result<CurrentUser, ApiError> {
+    if let Some(user) = session_user(deployment, headers).await? {
+        return Ok(CurrentUser(Some(user)));
+    }
+    if has_internal_token(headers) || !User::any_exist(&deployment.db().pool).await? {
+        return Ok(CurrentUser(None));
+    }
+    Err(ApiError::Unauthorized)
+}
@@ -6,8 +6,9 @@ use axum::{
 };
 use db::models::{
     conversation_session::ConversationSession, execution_process::ExecutionProcess,
-    notification::Notification, project::Project, session::Session, tag::Tag, task::Task,
-    task_group::TaskGroup, workspace::Workspace,
+    notification::Notification, project::Project, project_template::ProjectTemplate,
+    prompt_template::PromptTemplate, session::Session, tag::Tag, task::Task, task_group::TaskGroup,
+    webhook::Webhook, workspace::Workspace,
 };
 use deployment::Deployment;
 use uuid::Uuid;
@@ -87,6 +88,19 @@ pub async fn load_workspace_middleware(
         }
     };
 
+    // Workspaces of trashed tasks are hidden along with their task
+    match Task::find_by_id(&deployment.db().pool, workspace.task_id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            tracing::warn!("Task of Workspace {} is in the trash", workspace_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch task of Workspace {}: {}", workspace_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
     // Insert the workspace into extensions
     request.extensions_mut().insert(workspace);
 
@@ -242,3 +256,69 @@ pub async fn load_notification_middleware(
     request.extensions_mut().insert(notification);
     Ok(next.run(request).await)
 }
+
+pub async fn load_webhook_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let webhook = match Webhook::find_by_id(&deployment.db().pool, webhook_id).await {
+        Ok(Some(webhook)) => webhook,
+        Ok(None) => {
+            tracing::warn!("Webhook {} not found", webhook_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch webhook {}: {}", webhook_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(webhook);
+    Ok(next.run(request).await)
+}
+
+pub async fn load_prompt_template_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let template = match PromptTemplate::find_by_id(&deployment.db().pool, template_id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            tracing::warn!("Prompt template {} not found", template_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch prompt template {}: {}", template_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(template);
+    Ok(next.run(request).await)
+}
+
+pub async fn load_project_template_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let template = match ProjectTemplate::find_by_id(&deployment.db().pool, template_id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            tracing::warn!("Project template {} not found", template_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch project template {}: {}", template_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(template);
+    Ok(next.run(request).await)
+}
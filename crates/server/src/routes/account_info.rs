@@ -2,6 +2,7 @@ use axum::{Router, response::Json as ResponseJson, routing::get};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 
 use crate::DeploymentImpl;
 
@@ -9,14 +10,14 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new().route("/account-info", get(get_account_info))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInfo {
     pub claude: Option<ClaudeAccountInfo>,
     pub codex: Option<CodexAccountInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaudeAccountInfo {
     pub subscription_type: String,
@@ -24,7 +25,7 @@ pub struct ClaudeAccountInfo {
     pub usage: Option<ClaudeUsage>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ClaudeUsage {
     pub five_hour: UsageLimit,
@@ -33,7 +34,7 @@ pub struct ClaudeUsage {
     pub seven_day_sonnet: Option<UsageLimit>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageLimit {
     /// Usage percentage from 0 to 100
@@ -42,7 +43,7 @@ pub struct UsageLimit {
     pub resets_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CodexAccountInfo {
     pub plan_type: String,
@@ -190,6 +191,12 @@ fn read_codex_account_info() -> Option<CodexAccountInfo> {
     })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/account-info",
+    tag = "account_info",
+    responses((status = 200, description = "Success", body = ApiResponse<AccountInfo>))
+)]
 async fn get_account_info() -> ResponseJson<ApiResponse<AccountInfo>> {
     let claude = read_claude_account_info().await;
     let codex = read_codex_account_info();
@@ -0,0 +1,164 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    audit_log::{AuditAction, AuditLogEntry},
+    redaction::{self, RedactionCounts},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::CurrentUser};
+
+/// Shorter strings match too much unrelated text to redact safely
+const MIN_REDACTION_CHARS: usize = 4;
+const DEFAULT_PLACEHOLDER: &str = "[REDACTED]";
+const DEFAULT_AUDIT_LOG_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct RedactRequest {
+    /// Exact, case-sensitive text to remove
+    pub text: String,
+    /// Defaults to `[REDACTED]`
+    pub placeholder: Option<String>,
+    /// Why, e.g. an incident reference; kept in the audit log
+    pub reason: Option<String>,
+    /// Only count what would change
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct RedactionReport {
+    pub counts: RedactionCounts,
+    pub dry_run: bool,
+    /// The audit log entry recording the redaction; None for dry runs and
+    /// when nothing matched
+    pub audit_log_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    pub limit: Option<i64>,
+}
+
+/// POST /api/admin/redactions - Replace a string across tasks,
+/// conversations and agent logs, recording it in the audit log
+#[utoipa::path(
+    post,
+    path = "/api/admin/redactions",
+    tag = "admin",
+    request_body = RedactRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RedactionReport>),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn redact(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RedactRequest>,
+) -> Result<ResponseJson<ApiResponse<RedactionReport>>, ApiError> {
+    current_user.require_admin_if_accounts(&deployment).await?;
+    if payload.text.trim().chars().count() < MIN_REDACTION_CHARS {
+        return Err(ApiError::BadRequest(format!(
+            "Text to redact must be at least {MIN_REDACTION_CHARS} characters"
+        )));
+    }
+    let placeholder = payload
+        .placeholder
+        .filter(|placeholder| !placeholder.is_empty())
+        .unwrap_or_else(|| DEFAULT_PLACEHOLDER.to_string());
+    if placeholder.contains(&payload.text) {
+        return Err(ApiError::BadRequest(
+            "Placeholder cannot contain the text to redact".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+
+    if payload.dry_run {
+        let counts = redaction::count_redactable(pool, &payload.text, &placeholder).await?;
+        return Ok(ResponseJson(ApiResponse::success(RedactionReport {
+            counts,
+            dry_run: true,
+            audit_log_id: None,
+        })));
+    }
+
+    let mut tx = pool.begin().await?;
+    let counts = redaction::redact(&mut tx, &payload.text, &placeholder).await?;
+    let audit_log_id = if counts.total() > 0 {
+        // The redacted text itself is never logged, only its length
+        let details = serde_json::json!({
+            "placeholder": placeholder,
+            "text_length": payload.text.chars().count(),
+            "reason": payload.reason,
+            "counts": counts,
+        });
+        let summary = format!("Redacted text from {} record(s)", counts.total());
+        let entry = AuditLogEntry::create(
+            &mut *tx,
+            AuditAction::Redaction,
+            current_user.id(),
+            &summary,
+            &details,
+        )
+        .await?;
+        Some(entry.id)
+    } else {
+        None
+    };
+    tx.commit().await?;
+
+    tracing::info!(
+        user_id = ?current_user.id(),
+        records = counts.total(),
+        "Redacted text across stored task and agent data"
+    );
+    Ok(ResponseJson(ApiResponse::success(RedactionReport {
+        counts,
+        dry_run: false,
+        audit_log_id,
+    })))
+}
+
+/// GET /api/admin/audit-log - Administrative actions, newest first
+#[utoipa::path(
+    get,
+    path = "/api/admin/audit-log",
+    tag = "admin",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<AuditLogEntry>>),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn get_audit_log(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<AuditLogEntry>>>, ApiError> {
+    current_user.require_admin_if_accounts(&deployment).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_LOG_LIMIT)
+        .clamp(1, 1000);
+    let entries = AuditLogEntry::find_recent(&deployment.db().pool, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/admin/redactions", post(redact))
+        .route("/admin/audit-log", get(get_audit_log))
+}
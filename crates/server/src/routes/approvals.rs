@@ -21,6 +21,14 @@ use utils::approvals::{
 
 use crate::DeploymentImpl;
 
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/respond",
+    tag = "approvals",
+    params(("id" = String, Path)),
+    request_body = ApprovalResponse,
+    responses((status = 200, description = "Success", body = ApprovalStatus))
+)]
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
@@ -146,7 +154,11 @@ async fn trigger_follow_up_for_answered_question(
     let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
 
     // Check if we should queue this execution due to concurrency limit
-    if deployment.container().should_queue_execution().await? {
+    if deployment
+        .container()
+        .should_queue_execution(&ctx.workspace, &executor_profile_id)
+        .await?
+    {
         tracing::info!(
             "At concurrency limit, queueing follow-up for answered question {} workspace {}",
             approval_id,
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::database_path;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    asset_relocation::{RelocationReport, prepare_relocation},
+    container::ContainerService,
+};
+use ts_rs::TS;
+use utils::{
+    assets::{AssetDirSource, asset_dir, asset_dir_source, default_asset_dir},
+    response::ApiResponse,
+};
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::CurrentUser, request_shutdown};
+
+/// Where the app keeps its database, config files and sounds
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct AssetDirInfo {
+    pub path: String,
+    pub source: AssetDirSource,
+    /// Where the assets live when neither relocated nor overridden
+    pub default_path: String,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct RelocateAssetDirRequest {
+    /// Absolute path of an empty or missing directory
+    pub target_dir: String,
+}
+
+/// GET /api/assets/location - The asset directory in use
+#[utoipa::path(
+    get,
+    path = "/api/assets/location",
+    tag = "assets",
+    responses((status = 200, description = "Success", body = ApiResponse<AssetDirInfo>))
+)]
+pub async fn get_asset_dir() -> ResponseJson<ApiResponse<AssetDirInfo>> {
+    ResponseJson(ApiResponse::success(AssetDirInfo {
+        path: asset_dir().to_string_lossy().into_owned(),
+        source: asset_dir_source(),
+        default_path: default_asset_dir().to_string_lossy().into_owned(),
+    }))
+}
+
+/// POST /api/assets/relocate - Stop running executions, copy the asset
+/// directory to a new location and shut the server down, so it uses the new
+/// location from the next start
+#[utoipa::path(
+    post,
+    path = "/api/assets/relocate",
+    tag = "assets",
+    request_body = RelocateAssetDirRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RelocationReport>),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn relocate(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<RelocateAssetDirRequest>,
+) -> Result<ResponseJson<ApiResponse<RelocationReport>>, ApiError> {
+    current_user.require_admin_if_accounts(&deployment).await?;
+    let target = PathBuf::from(request.target_dir.trim());
+    let relocation = prepare_relocation(&asset_dir(), &database_path()?, &target)?;
+
+    // Agents write logs and state while they run; stop them so nothing is
+    // written to the old directory after it was copied
+    deployment.container().kill_all_running_processes().await?;
+    let report = relocation.run().await?;
+
+    deployment
+        .track_if_analytics_allowed("asset_dir_relocated", serde_json::json!({}))
+        .await;
+
+    // Anything written from now on would only reach the old directory
+    tracing::info!(
+        "Asset directory copied to {}; shutting down to start using it",
+        report.to
+    );
+    request_shutdown();
+
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/assets/location", get(get_asset_dir))
+        .route("/assets/relocate", post(relocate))
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    backup_verification::{BackupVerification, BackupVerificationKind},
+    operation::OperationKind,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    backup::{
+        AssetPaths, BackupError, RestoreReport, list_backups, parse_backup_filename,
+        restore_backup_archive, verify_backup_archive,
+    },
+    container::ContainerService,
+};
+use ts_rs::TS;
+use utils::{assets::backup_dir, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::CurrentUser};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct BackupInfo {
+    /// Filename without `.zip`, e.g. `backup_20260101_120000`
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+    /// Most recent check of this archive; None if it was never checked
+    pub last_verification: Option<BackupVerification>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VerificationsQuery {
+    pub backup_id: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Path of a backup archive by id; only well-formed backup names, so the id
+/// cannot point outside the dir
+fn archive_path(backup_id: &str) -> Result<std::path::PathBuf, BackupError> {
+    let filename = format!("{backup_id}.zip");
+    let archive_path = backup_dir().join(&filename);
+    if parse_backup_filename(&filename).is_none() || !archive_path.is_file() {
+        return Err(BackupError::NotFound(backup_id.to_string()));
+    }
+    Ok(archive_path)
+}
+
+/// GET /api/backups - Backups in the backup directory, newest first
+#[utoipa::path(
+    get,
+    path = "/api/backups",
+    tag = "backups",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<BackupInfo>>))
+)]
+pub async fn get_backups(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackupInfo>>>, ApiError> {
+    let mut verifications: HashMap<String, BackupVerification> =
+        BackupVerification::find_latest_per_backup(&deployment.db().pool)
+            .await?
+            .into_iter()
+            .map(|v| (v.backup_id.clone(), v))
+            .collect();
+    let mut backups: Vec<BackupInfo> = list_backups(&backup_dir())?
+        .into_iter()
+        .filter_map(|backup| {
+            let id = backup.path.file_stem()?.to_str()?.to_string();
+            let size_bytes = std::fs::metadata(&backup.path).map(|m| m.len()).ok()?;
+            Some(BackupInfo {
+                last_verification: verifications.remove(&id),
+                id,
+                created_at: backup.timestamp,
+                size_bytes,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(ResponseJson(ApiResponse::success(backups)))
+}
+
+/// POST /api/backups/:id/restore - Check the backup and stage it to replace
+/// the database, config files and alert sounds when the app next starts.
+/// Running executions are stopped once the restore is staged.
+#[utoipa::path(
+    post,
+    path = "/api/backups/{backup_id}/restore",
+    tag = "backups",
+    params(("backup_id" = String, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<RestoreReport>))
+)]
+pub async fn restore_backup(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(backup_id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<RestoreReport>>, ApiError> {
+    let archive_path = archive_path(&backup_id)?;
+    let asset_paths = AssetPaths::resolve()?;
+
+    let report = deployment
+        .operations()
+        .track_for_user(
+            OperationKind::Restore,
+            None,
+            None,
+            current_user.id(),
+            restore_backup_archive(&deployment.db().pool, &archive_path, &asset_paths),
+        )
+        .await?;
+
+    // Executions would keep writing to the database the restore replaces
+    deployment.container().kill_all_running_processes().await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "backup_restored",
+            serde_json::json!({ "restored": report.restored }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// GET /api/backups/verifications - Archive checks, newest first
+#[utoipa::path(
+    get,
+    path = "/api/backups/verifications",
+    tag = "backups",
+    params(VerificationsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<BackupVerification>>))
+)]
+pub async fn get_verifications(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<VerificationsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackupVerification>>>, ApiError> {
+    const DEFAULT_LIMIT: i64 = 50;
+    const MAX_LIMIT: i64 = 200;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let verifications =
+        BackupVerification::find_recent(&deployment.db().pool, query.backup_id.as_deref(), limit)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(verifications)))
+}
+
+/// POST /api/backups/:id/verify - Check an archive would restore, without
+/// touching live data
+#[utoipa::path(
+    post,
+    path = "/api/backups/{backup_id}/verify",
+    tag = "backups",
+    params(("backup_id" = String, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BackupVerification>))
+)]
+pub async fn verify_backup(
+    State(deployment): State<DeploymentImpl>,
+    Path(backup_id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<BackupVerification>>, ApiError> {
+    let archive_path = archive_path(&backup_id)?;
+    let verification = verify_backup_archive(
+        &deployment.db().pool,
+        &archive_path,
+        BackupVerificationKind::Manual,
+        None,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(verification)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/backups", get(get_backups))
+        .route("/backups/verifications", get(get_verifications))
+        .route("/backups/{backup_id}/restore", post(restore_backup))
+        .route("/backups/{backup_id}/verify", post(verify_backup))
+}
@@ -0,0 +1,312 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    bisect::{BisectRun, BisectStep, BisectVerdict, CreateBisectRun},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process_normalized_entry::ExecutionProcessNormalizedEntry,
+    repo::{Repo, RepoError},
+    session::{CreateSession, Session},
+    task::Task,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    logs::NormalizedEntryType,
+};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    bisect::{self, BisectError, StepInterpreter, StepOutcome},
+    container::ContainerService,
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// How long an agent may take to judge one step
+const INTERPRET_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct StartBisectRequest {
+    pub repo_id: Uuid,
+    /// Commit or ref known to pass the validation command
+    pub good_commit: String,
+    /// Commit or ref known to fail it
+    pub bad_commit: String,
+    /// Shell command run at each step, from the repo root
+    pub command: String,
+    /// Task the culprit is reported on; required when `use_agent` is set, as
+    /// the agent runs in the task's latest workspace
+    pub task_id: Option<Uuid>,
+    #[serde(default)]
+    pub use_agent: bool,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct BisectRunDetails {
+    pub run: BisectRun,
+    pub steps: Vec<BisectStep>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BisectRunsQuery {
+    pub task_id: Uuid,
+}
+
+/// Asks a coding agent in the linked task's workspace whether a step passed
+struct AgentStepInterpreter {
+    deployment: DeploymentImpl,
+    workspace: Workspace,
+}
+
+impl AgentStepInterpreter {
+    async fn ask(&self, prompt: String) -> Result<Option<String>, ApiError> {
+        let pool = &self.deployment.db().pool;
+        let session = match Session::find_latest_by_workspace_id(pool, self.workspace.id).await? {
+            Some(session) => session,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    self.workspace.id,
+                )
+                .await?
+            }
+        };
+        let executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir: self
+                    .workspace
+                    .agent_working_dir
+                    .clone()
+                    .filter(|dir| !dir.is_empty()),
+            }),
+            None,
+        );
+
+        let container = self.deployment.container();
+        let execution_process = container
+            .start_execution(
+                &self.workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::InternalAgent,
+                Some("bisect_step"),
+            )
+            .await?;
+        container
+            .wait_for_execution_completion(execution_process.id, INTERPRET_TIMEOUT)
+            .await?;
+
+        let entries =
+            ExecutionProcessNormalizedEntry::fetch_all_for_execution(pool, execution_process.id)
+                .await
+                .map_err(|e| ApiError::Internal(format!("Failed to fetch agent output: {e}")))?;
+        Ok(entries
+            .iter()
+            .rev()
+            .find(|e| matches!(e.entry.entry_type, NormalizedEntryType::AssistantMessage))
+            .map(|e| e.entry.content.clone()))
+    }
+}
+
+#[async_trait]
+impl StepInterpreter for AgentStepInterpreter {
+    async fn interpret(
+        &self,
+        run: &BisectRun,
+        commit: &str,
+        outcome: &StepOutcome,
+    ) -> Option<BisectVerdict> {
+        let exit_code = outcome.exit_code.map_or_else(
+            || "none (timed out or killed)".to_string(),
+            |c| c.to_string(),
+        );
+        let prompt = format!(
+            "We are bisecting to find the commit that introduced a regression. \
+             At commit {commit} the validation command `{}` exited with code {exit_code} \
+             and printed:\n\n```\n{}\n```\n\n\
+             Decide whether this commit has the regression. Answer GOOD if it does not, \
+             BAD if it does, or SKIP if the output shows the commit cannot be tested \
+             (e.g. an unrelated build failure). Do not change any files. \
+             End your reply with the single word GOOD, BAD or SKIP on its own line.",
+            run.command, outcome.output
+        );
+        match self.ask(prompt).await {
+            Ok(answer) => answer.as_deref().and_then(bisect::parse_verdict),
+            Err(e) => {
+                tracing::warn!(
+                    run_id = %run.id,
+                    error = %e,
+                    "Agent could not interpret bisect step"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// POST /api/bisect - Start bisecting a repo's good..bad range in the background
+#[utoipa::path(
+    post,
+    path = "/api/bisect",
+    tag = "bisect",
+    request_body = StartBisectRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<BisectRun>))
+)]
+pub async fn start_bisect(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<StartBisectRequest>,
+) -> Result<ResponseJson<ApiResponse<BisectRun>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let command = payload.command.trim();
+    if command.is_empty() {
+        return Err(ApiError::BadRequest(
+            "A validation command is required".to_string(),
+        ));
+    }
+
+    let repo = Repo::find_by_id(pool, payload.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let resolve = |rev: &str| {
+        bisect::resolve_commit(&repo.path, rev).map_err(|e| match e {
+            BisectError::UnknownCommit(_) => ApiError::BadRequest(e.to_string()),
+            e => ApiError::Internal(e.to_string()),
+        })
+    };
+    let good_commit = resolve(&payload.good_commit)?;
+    let bad_commit = resolve(&payload.bad_commit)?;
+    if good_commit == bad_commit {
+        return Err(ApiError::BadRequest(
+            "Good and bad commits must differ".to_string(),
+        ));
+    }
+
+    if let Some(task_id) = payload.task_id {
+        Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or_else(|| ApiError::NotFound(format!("Task {task_id}")))?;
+    }
+    let interpreter = if payload.use_agent {
+        let task_id = payload.task_id.ok_or_else(|| {
+            ApiError::BadRequest("Agent interpretation needs a linked task".to_string())
+        })?;
+        let workspace = Workspace::find_latest_by_task_id(pool, task_id)
+            .await?
+            .ok_or_else(|| {
+                ApiError::BadRequest(
+                    "The linked task has no workspace for the agent to run in".to_string(),
+                )
+            })?;
+        Some(AgentStepInterpreter {
+            deployment: deployment.clone(),
+            workspace,
+        })
+    } else {
+        None
+    };
+
+    let run = BisectRun::create(
+        pool,
+        &CreateBisectRun {
+            repo_id: repo.id,
+            task_id: payload.task_id,
+            good_commit,
+            bad_commit,
+            command: command.to_string(),
+            use_agent: payload.use_agent,
+        },
+    )
+    .await?;
+
+    let pool = pool.clone();
+    let background_run = run.clone();
+    tokio::spawn(async move {
+        bisect::drive(
+            &pool,
+            &background_run,
+            &repo.path,
+            interpreter.as_ref().map(|i| i as &dyn StepInterpreter),
+        )
+        .await;
+    });
+
+    deployment
+        .track_if_analytics_allowed(
+            "bisect_started",
+            serde_json::json!({
+                "repo_id": run.repo_id.to_string(),
+                "use_agent": run.use_agent,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(run)))
+}
+
+/// GET /api/bisect/:id - A bisect run and the commits tested so far
+#[utoipa::path(
+    get,
+    path = "/api/bisect/{id}",
+    tag = "bisect",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BisectRunDetails>))
+)]
+pub async fn get_bisect(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<BisectRunDetails>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let run = BisectRun::find_by_id(pool, id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Bisect run {id}")))?;
+    let steps = BisectStep::find_by_run_id(pool, id).await?;
+    Ok(ResponseJson(ApiResponse::success(BisectRunDetails {
+        run,
+        steps,
+    })))
+}
+
+/// GET /api/bisect?task_id= - Bisect runs linked to a task, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/bisect",
+    tag = "bisect",
+    params(BisectRunsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<BisectRun>>))
+)]
+pub async fn list_bisects(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BisectRunsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BisectRun>>>, ApiError> {
+    let runs = BisectRun::find_by_task_id(&deployment.db().pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().nest(
+        "/bisect",
+        Router::new()
+            .route("/", get(list_bisects).post(start_bisect))
+            .route("/{id}", get(get_bisect)),
+    )
+}
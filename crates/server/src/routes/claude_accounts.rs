@@ -37,6 +37,12 @@ pub fn router() -> Router<DeploymentImpl> {
 }
 
 /// GET /api/claude-accounts - List all saved accounts
+#[utoipa::path(
+    get,
+    path = "/api/claude-accounts",
+    tag = "claude_accounts",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<SavedAccount>>))
+)]
 async fn list_accounts_handler() -> Result<ResponseJson<ApiResponse<Vec<SavedAccount>>>, ApiError> {
     let accounts = list_accounts().await.map_err(map_claude_account_error)?;
 
@@ -45,6 +51,13 @@ async fn list_accounts_handler() -> Result<ResponseJson<ApiResponse<Vec<SavedAcc
 }
 
 /// POST /api/claude-accounts/save - Save the current account
+#[utoipa::path(
+    post,
+    path = "/api/claude-accounts/save",
+    tag = "claude_accounts",
+    request_body = SaveAccountRequest,
+    responses((status = 201, description = "Created", body = ApiResponse<SavedAccount>))
+)]
 async fn save_current_account_handler(
     Json(request): Json<SaveAccountRequest>,
 ) -> Result<(StatusCode, ResponseJson<ApiResponse<SavedAccount>>), ApiError> {
@@ -65,6 +78,13 @@ async fn save_current_account_handler(
 }
 
 /// POST /api/claude-accounts/switch/{hash} - Switch to a saved account
+#[utoipa::path(
+    post,
+    path = "/api/claude-accounts/switch/{hash}",
+    tag = "claude_accounts",
+    params(("hash" = String, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 async fn switch_account_handler(
     Path(hash): Path<String>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
@@ -106,6 +126,14 @@ async fn switch_account_handler(
 }
 
 /// PUT /api/claude-accounts/{hash}/name - Update account name
+#[utoipa::path(
+    put,
+    path = "/api/claude-accounts/{hash}/name",
+    tag = "claude_accounts",
+    params(("hash" = String, Path)),
+    request_body = UpdateNameRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<SavedAccount>))
+)]
 async fn update_account_name_handler(
     Path(hash): Path<String>,
     Json(request): Json<UpdateNameRequest>,
@@ -124,6 +152,13 @@ async fn update_account_name_handler(
 }
 
 /// DELETE /api/claude-accounts/{hash} - Delete a saved account
+#[utoipa::path(
+    delete,
+    path = "/api/claude-accounts/{hash}",
+    tag = "claude_accounts",
+    params(("hash" = String, Path)),
+    responses((status = 204, description = "No content"))
+)]
 async fn delete_account_handler(Path(hash): Path<String>) -> Result<StatusCode, ApiError> {
     delete_account(&hash)
         .await
@@ -134,6 +169,12 @@ async fn delete_account_handler(Path(hash): Path<String>) -> Result<StatusCode,
 }
 
 /// GET /api/claude-accounts/current - Get the hash of the current account
+#[utoipa::path(
+    get,
+    path = "/api/claude-accounts/current",
+    tag = "claude_accounts",
+    responses((status = 200, description = "Success", body = ApiResponse<Option<String>>))
+)]
 async fn get_current_account_handler() -> Result<ResponseJson<ApiResponse<Option<String>>>, ApiError>
 {
     let current_hash = get_current_hash().await.map_err(map_claude_account_error)?;
@@ -142,6 +183,12 @@ async fn get_current_account_handler() -> Result<ResponseJson<ApiResponse<Option
 }
 
 /// GET /api/claude-accounts/current-uuid - Get the UUID of the current account
+#[utoipa::path(
+    get,
+    path = "/api/claude-accounts/current-uuid",
+    tag = "claude_accounts",
+    responses((status = 200, description = "Success", body = ApiResponse<Option<String>>))
+)]
 async fn get_current_uuid_handler() -> Result<ResponseJson<ApiResponse<Option<String>>>, ApiError> {
     let current_uuid = get_current_uuid().await.map_err(map_claude_account_error)?;
 
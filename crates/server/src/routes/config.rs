@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     body::Body,
     extract::{Path, Query, State},
     http,
     response::{Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{get, post, put},
 };
+use db::models::user::User;
 use deployment::{Deployment, DeploymentError};
 use executors::{
     command::CommandBuilder,
@@ -19,11 +20,17 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{
-    Config, ConfigError, SoundFile,
-    custom_editors::{CustomEditor, CustomEditorsConfig},
-    editor::{EditorConfig, EditorType},
-    save_config_to_file,
+use services::services::{
+    agent_auth::AgentAuthStatus,
+    config::{
+        Config, ConfigError, SoundFile,
+        compatibility::{ConfigCompatibilityReport, compatibility_report},
+        custom_editors::{CustomEditor, CustomEditorsConfig},
+        editor::{EditorConfig, EditorType},
+        save_config_to_file,
+        user_preferences::UserPreferences,
+    },
+    container::ContainerService,
 };
 use strum::IntoEnumIterator;
 use tokio::fs;
@@ -33,14 +40,16 @@ use utils::{
     assets::{CustomSoundInfo, alerts_dir, config_path, list_custom_sounds},
     response::ApiResponse,
 };
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::CurrentUser};
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/info", get(get_user_system_info))
         .route("/config", put(update_config))
+        .route("/config/compatibility", get(get_config_compatibility))
         .route(
             "/config/custom-editors",
             get(list_custom_editors).post(create_custom_editor),
@@ -62,9 +71,14 @@ pub fn router() -> Router<DeploymentImpl> {
             get(check_editor_availability),
         )
         .route("/agents/check-availability", get(check_agent_availability))
+        .route("/agents/auth-status", get(get_agent_auth_status))
+        .route(
+            "/agents/auth-status/refresh",
+            post(refresh_agent_auth_status),
+        )
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct Environment {
     pub os_type: String,
     pub os_version: String,
@@ -90,28 +104,38 @@ impl Environment {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UserSystemInfo {
+    #[schema(value_type = Object)]
     pub config: Config,
     pub analytics_user_id: String,
     pub login_status: LoginStatus,
     #[serde(flatten)]
+    #[schema(value_type = Object)]
     pub profiles: ExecutorConfigs,
     pub environment: Environment,
     /// Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+    #[schema(value_type = HashMap<String, Vec<String>>)]
     pub capabilities: HashMap<String, Vec<BaseAgentCapability>>,
 }
 
 // TODO: update frontend, BE schema has changed, this replaces GET /config and /config/constants
 #[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/info",
+    tag = "config",
+    responses((status = 200, description = "Success", body = ApiResponse<UserSystemInfo>))
+)]
 async fn get_user_system_info(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<UserSystemInfo>> {
-    let config = deployment.config().read().await;
+    let config = current_user.config(&deployment).await;
     let login_status = deployment.get_login_status().await;
 
     let user_system_info = UserSystemInfo {
-        config: config.clone(),
+        config,
         analytics_user_id: deployment.user_id().to_string(),
         login_status,
         profiles: (*ExecutorConfigs::get_cached()).clone(),
@@ -131,7 +155,15 @@ async fn get_user_system_info(
     ResponseJson(ApiResponse::success(user_system_info))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/config",
+    tag = "config",
+    request_body = serde_json::Value,
+    responses((status = 200, description = "Success", body = ApiResponse<serde_json::Value>))
+)]
 async fn update_config(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Json(new_config): Json<Config>,
 ) -> ResponseJson<ApiResponse<Config>> {
@@ -147,21 +179,59 @@ async fn update_config(
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
-    match save_config_to_file(&new_config, &config_path).await {
+    // A signed-in user's theme, editor and notifications are kept as their
+    // own preferences; everything else is shared
+    let (saved_config, preferences) = match current_user.id() {
+        Some(user_id) => {
+            let (preferences, shared) = UserPreferences::split(new_config.clone(), &old_config);
+            if let Err(e) =
+                User::set_preferences(&deployment.db().pool, user_id, &preferences.to_json()).await
+            {
+                return ResponseJson(ApiResponse::error(&format!(
+                    "Failed to save preferences: {}",
+                    e
+                )));
+            }
+            (shared, Some(preferences))
+        }
+        None => (new_config, None),
+    };
+
+    match save_config_to_file(&saved_config, &config_path).await {
         Ok(_) => {
             let mut config = deployment.config().write().await;
-            *config = new_config.clone();
+            *config = saved_config.clone();
             drop(config);
 
             // Track config events when fields transition from false → true and run side effects
-            handle_config_events(&deployment, &old_config, &new_config).await;
+            handle_config_events(&deployment, &old_config, &saved_config).await;
 
-            ResponseJson(ApiResponse::success(new_config))
+            let mut response_config = saved_config;
+            if let Some(preferences) = preferences {
+                preferences.apply(&mut response_config);
+            }
+            ResponseJson(ApiResponse::success(response_config))
         }
         Err(e) => ResponseJson(ApiResponse::error(&format!("Failed to save config: {}", e))),
     }
 }
 
+/// Fields of the config file this version can't interpret, and the backups
+/// taken before the config was migrated
+#[utoipa::path(
+    get,
+    path = "/api/config/compatibility",
+    tag = "config",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ConfigCompatibilityReport>),
+    )
+)]
+async fn get_config_compatibility()
+-> Result<ResponseJson<ApiResponse<ConfigCompatibilityReport>>, ApiError> {
+    let report = compatibility_report(&config_path()).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
 /// Track config events when fields transition from false → true
 async fn track_config_events(deployment: &DeploymentImpl, old: &Config, new: &Config) {
     let events = [
@@ -207,7 +277,7 @@ async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &C
 }
 
 /// Info about a bundled sound file
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct BundledSoundInfo {
     /// Identifier for API paths (e.g., "bundled:COW_MOOING")
     pub identifier: String,
@@ -216,13 +286,21 @@ pub struct BundledSoundInfo {
 }
 
 /// Response for GET /api/sounds listing all available sounds
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct AvailableSoundsResponse {
     pub bundled: Vec<BundledSoundInfo>,
     pub custom: Vec<CustomSoundInfo>,
 }
 
 /// Lists all available sounds (bundled and custom)
+#[utoipa::path(
+    get,
+    path = "/api/sounds",
+    tag = "config",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<AvailableSoundsResponse>),
+    )
+)]
 async fn list_sounds(
     State(_deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<AvailableSoundsResponse>> {
@@ -244,6 +322,13 @@ async fn list_sounds(
 /// Serves a sound file by identifier.
 /// - "bundled:COW_MOOING" serves the embedded bundled sound
 /// - "custom:mysound.wav" serves from the alerts directory
+#[utoipa::path(
+    get,
+    path = "/api/sounds/{identifier}",
+    tag = "config",
+    params(("identifier" = String, Path)),
+    responses((status = 200, description = "Sound file", content_type = "audio/wav"))
+)]
 async fn get_sound(Path(identifier): Path<String>) -> Result<Response, ApiError> {
     if let Some(variant) = identifier.strip_prefix("bundled:") {
         // Parse the bundled sound variant
@@ -299,21 +384,21 @@ async fn get_sound(Path(identifier): Path<String>) -> Result<Response, ApiError>
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateCustomEditorRequest {
     pub name: String,
     pub command: String,
     pub argument: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateCustomEditorRequest {
     pub name: String,
     pub command: String,
     pub argument: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CustomEditorResponse {
     pub id: Uuid,
     pub name: String,
@@ -324,16 +409,24 @@ pub struct CustomEditorResponse {
     pub available: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct ListCustomEditorsResponse {
     pub editors: Vec<CustomEditorResponse>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CheckCustomEditorAvailabilityResponse {
     pub available: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/config/custom-editors",
+    tag = "config",
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ListCustomEditorsResponse>),
+    )
+)]
 async fn list_custom_editors(
     State(_deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<ListCustomEditorsResponse>>, ApiError> {
@@ -351,6 +444,13 @@ async fn list_custom_editors(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/config/custom-editors",
+    tag = "config",
+    request_body = CreateCustomEditorRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<CustomEditorResponse>))
+)]
 async fn create_custom_editor(
     State(_deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateCustomEditorRequest>,
@@ -378,6 +478,14 @@ async fn create_custom_editor(
     ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/config/custom-editors/{id}",
+    tag = "config",
+    params(("id" = Uuid, Path)),
+    request_body = UpdateCustomEditorRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<CustomEditorResponse>))
+)]
 async fn update_custom_editor(
     State(_deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -403,6 +511,13 @@ async fn update_custom_editor(
     )))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/config/custom-editors/{id}",
+    tag = "config",
+    params(("id" = Uuid, Path)),
+    responses((status = 204, description = "No content"))
+)]
 async fn delete_custom_editor(
     State(_deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -419,6 +534,15 @@ async fn delete_custom_editor(
     Ok(http::StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/config/custom-editors/{id}/check-availability",
+    tag = "config",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CheckCustomEditorAvailabilityResponse>),
+    )
+)]
 async fn check_custom_editor_availability(
     State(_deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -464,23 +588,33 @@ fn map_custom_editor_error(err: ConfigError) -> ApiError {
     }
 }
 
-#[derive(TS, Debug, Deserialize)]
+#[derive(TS, Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct McpServerQuery {
+    #[param(value_type = String)]
     executor: BaseCodingAgent,
 }
 
-#[derive(TS, Debug, Serialize, Deserialize)]
+#[derive(TS, Debug, Serialize, Deserialize, ToSchema)]
 pub struct GetMcpServerResponse {
     // servers: HashMap<String, Value>,
+    #[schema(value_type = Object)]
     mcp_config: McpConfig,
     config_path: String,
 }
 
-#[derive(TS, Debug, Serialize, Deserialize)]
+#[derive(TS, Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateMcpServersBody {
     servers: HashMap<String, Value>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/mcp-config",
+    tag = "config",
+    params(McpServerQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<GetMcpServerResponse>))
+)]
 async fn get_mcp_servers(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<McpServerQuery>,
@@ -517,6 +651,14 @@ async fn get_mcp_servers(
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/mcp-config",
+    tag = "config",
+    params(McpServerQuery),
+    request_body = UpdateMcpServersBody,
+    responses((status = 200, description = "Success", body = ApiResponse<String>))
+)]
 async fn update_mcp_servers(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<McpServerQuery>,
@@ -645,12 +787,18 @@ fn set_mcp_servers_in_config_path(
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProfilesContent {
     pub content: String,
     pub path: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/profiles",
+    tag = "config",
+    responses((status = 200, description = "Success", body = ApiResponse<ProfilesContent>))
+)]
 async fn get_profiles(
     State(_deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<ProfilesContent>> {
@@ -671,6 +819,12 @@ async fn get_profiles(
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/profiles",
+    tag = "config",
+    responses((status = 200, description = "Success", body = ApiResponse<String>))
+)]
 async fn update_profiles(
     State(_deployment): State<DeploymentImpl>,
     body: String,
@@ -704,16 +858,27 @@ async fn update_profiles(
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CheckEditorAvailabilityQuery {
+    #[param(value_type = String)]
     editor_type: EditorType,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CheckEditorAvailabilityResponse {
     available: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/editors/check-availability",
+    tag = "config",
+    params(CheckEditorAvailabilityQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CheckEditorAvailabilityResponse>),
+    )
+)]
 async fn check_editor_availability(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<CheckEditorAvailabilityQuery>,
@@ -733,11 +898,20 @@ async fn check_editor_availability(
     }))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CheckAgentAvailabilityQuery {
+    #[param(value_type = String)]
     executor: BaseCodingAgent,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/agents/check-availability",
+    tag = "config",
+    params(CheckAgentAvailabilityQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<serde_json::Value>))
+)]
 async fn check_agent_availability(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<CheckAgentAvailabilityQuery>,
@@ -753,6 +927,36 @@ async fn check_agent_availability(
     ResponseJson(ApiResponse::success(info))
 }
 
+/// Last known login state of each coding agent that was probed or ran
+#[utoipa::path(
+    get,
+    path = "/api/agents/auth-status",
+    tag = "config",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<AgentAuthStatus>>))
+)]
+async fn get_agent_auth_status(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<AgentAuthStatus>>> {
+    ResponseJson(ApiResponse::success(
+        deployment.container().agent_auth().all(),
+    ))
+}
+
+/// Probe agents with a status command now, e.g. after signing in again
+#[utoipa::path(
+    post,
+    path = "/api/agents/auth-status/refresh",
+    tag = "config",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<AgentAuthStatus>>))
+)]
+async fn refresh_agent_auth_status(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<AgentAuthStatus>>> {
+    let agent_auth = deployment.container().agent_auth();
+    agent_auth.probe_all().await;
+    ResponseJson(ApiResponse::success(agent_auth.all()))
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
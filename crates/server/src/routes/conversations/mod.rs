@@ -15,6 +15,7 @@ use db::models::{
     conversation_session::{
         ConversationSession, ConversationSessionStatus, UpdateConversationSession,
     },
+    conversation_worktree::ConversationWorktree,
     execution_process::ExecutionProcess,
     image::ConversationImage,
 };
@@ -31,9 +32,11 @@ use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
     conversation::{ConversationService, ConversationWithMessages, SendMessageResponse},
+    project_memory,
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
@@ -43,46 +46,83 @@ use crate::{
     routes::images::{ImageResponse, process_image_upload},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListConversationsQuery {
     pub project_id: Uuid,
     pub worktree_path: Option<String>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateConversationRequest {
     pub title: String,
     pub initial_message: String,
+    #[schema(value_type = Option<Object>)]
     pub executor_profile_id: Option<ExecutorProfileId>,
     pub worktree_path: Option<String>,
     pub worktree_branch: Option<String>,
+    /// Create worktrees of the project's repos for this conversation instead
+    /// of using `worktree_path`. `worktree_branch` names their branch.
+    #[serde(default)]
+    pub create_worktree: bool,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct CreateConversationResponse {
     pub session: ConversationSession,
     pub initial_message: db::models::conversation_message::ConversationMessage,
     pub execution_process_id: Uuid,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct UpdateConversationRequest {
     pub title: Option<String>,
     pub status: Option<ConversationSessionStatus>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct SendMessageRequest {
     pub content: String,
     pub variant: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ForkConversationRequest {
+    /// The agent reply to fork at; it and everything before it is copied
+    pub message_id: Uuid,
+    /// Defaults to the original title followed by "(fork)"
+    pub title: Option<String>,
+    /// First message to send in the fork
+    pub content: Option<String>,
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct ForkConversationResponse {
+    pub session: ConversationSession,
+    /// Set when the request included `content`
+    pub sent_message: Option<SendMessageResponse>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetMessagesQuery {
     pub cursor: Option<String>,
     pub limit: Option<usize>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/conversations",
+    tag = "conversations",
+    params(
+        ("project_id" = Uuid, Path),
+        ListConversationsQuery
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ConversationSession>>),
+    )
+)]
 pub async fn list_conversations(
     State(deployment): State<DeploymentImpl>,
     axum::extract::Query(query): axum::extract::Query<ListConversationsQuery>,
@@ -96,6 +136,16 @@ pub async fn list_conversations(
     Ok(ResponseJson(ApiResponse::success(sessions)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/conversations",
+    tag = "conversations",
+    params(("project_id" = Uuid, Path)),
+    request_body = CreateConversationRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CreateConversationResponse>),
+    )
+)]
 pub async fn create_conversation(
     State(deployment): State<DeploymentImpl>,
     axum::extract::Path(project_id): axum::extract::Path<Uuid>,
@@ -120,23 +170,67 @@ pub async fn create_conversation(
 
     // Store executor name in session for future messages
     let executor_name = Some(executor_profile_id.executor.to_string());
+    let pool = &deployment.db().pool;
+
+    let new_worktree = if payload.create_worktree {
+        let branch_prefix = deployment.config().read().await.git_branch_prefix.clone();
+        Some(
+            ConversationService::create_worktree(
+                pool,
+                deployment.git(),
+                project_id,
+                &payload.title,
+                &branch_prefix,
+                payload.worktree_branch.clone(),
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    let (worktree_path, worktree_branch) = match &new_worktree {
+        Some(worktree) => (
+            Some(worktree.working_dir.to_string_lossy().to_string()),
+            Some(worktree.branch.clone()),
+        ),
+        None => (payload.worktree_path, payload.worktree_branch),
+    };
 
     let (session, initial_message) = ConversationService::create_conversation(
-        &deployment.db().pool,
+        pool,
         project_id,
         payload.title,
         payload.initial_message.clone(),
         executor_name,
-        payload.worktree_path.clone(),
-        payload.worktree_branch.clone(),
+        worktree_path.clone(),
+        worktree_branch,
     )
     .await?;
 
+    if let Some(worktree) = &new_worktree {
+        ConversationWorktree::create(
+            pool,
+            session.id,
+            &worktree.container_ref.to_string_lossy(),
+            &worktree.branch,
+        )
+        .await?;
+    }
+
+    // The stored message stays as typed; only the agent sees the project's memories
+    let prompt = project_memory::with_memories(
+        pool,
+        deployment.embedding(),
+        project_id,
+        payload.initial_message,
+    )
+    .await;
+
     // Build ExecutorAction for initial conversation
     let action_type = ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-        prompt: payload.initial_message,
+        prompt,
         executor_profile_id,
-        working_dir: payload.worktree_path.clone(),
+        working_dir: worktree_path,
     });
     let executor_action = ExecutorAction::new(action_type, None);
 
@@ -155,6 +249,15 @@ pub async fn create_conversation(
     )))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/conversations/{conversation_id}",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ConversationWithMessages>),
+    )
+)]
 pub async fn get_conversation(
     State(deployment): State<DeploymentImpl>,
     Extension(conversation): Extension<ConversationSession>,
@@ -168,6 +271,14 @@ pub async fn get_conversation(
     )))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/conversations/{conversation_id}",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    request_body = UpdateConversationRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<ConversationSession>))
+)]
 pub async fn update_conversation(
     Extension(existing): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -186,10 +297,27 @@ pub async fn update_conversation(
     Ok(ResponseJson(ApiResponse::success(conversation)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/conversations/{conversation_id}",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_conversation(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
+    if let Err(e) =
+        ConversationService::release_worktree(&deployment.db().pool, &conversation).await
+    {
+        tracing::warn!(
+            "Failed to remove worktrees of conversation {}: {}",
+            conversation.id,
+            e
+        );
+    }
+
     let rows_affected = ConversationSession::delete(&deployment.db().pool, conversation.id).await?;
 
     if rows_affected == 0 {
@@ -201,11 +329,29 @@ pub async fn delete_conversation(
 
 /// Send a message to a conversation and start agent execution.
 /// Creates a user ConversationMessage, then starts an ExecutionProcess with run_reason=DisposableConversation.
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{conversation_id}/messages",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    request_body = SendMessageRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<SendMessageResponse>))
+)]
 pub async fn send_message(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<SendMessageRequest>,
 ) -> Result<ResponseJson<ApiResponse<SendMessageResponse>>, ApiError> {
+    let response = start_message_execution(&deployment, &conversation, payload).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Add a user message to `conversation` and start the agent turn answering it
+async fn start_message_execution(
+    deployment: &DeploymentImpl,
+    conversation: &ConversationSession,
+    payload: SendMessageRequest,
+) -> Result<SendMessageResponse, ApiError> {
     let pool = &deployment.db().pool;
 
     // Create user message
@@ -268,16 +414,75 @@ pub async fn send_message(
     // Start conversation execution
     let execution_process = deployment
         .container()
-        .start_conversation_execution(&conversation, &executor_action)
+        .start_conversation_execution(conversation, &executor_action)
         .await?;
 
-    Ok(ResponseJson(ApiResponse::success(SendMessageResponse {
+    Ok(SendMessageResponse {
         user_message,
         execution_process_id: execution_process.id,
-    })))
+    })
+}
+
+/// Fork a conversation at one of the agent's replies. The new conversation
+/// starts with the messages up to that reply and continues the agent session
+/// from that point, leaving the original untouched.
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{conversation_id}/fork",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    request_body = ForkConversationRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<ForkConversationResponse>))
+)]
+pub async fn fork_conversation(
+    Extension(conversation): Extension<ConversationSession>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ForkConversationRequest>,
+) -> Result<ResponseJson<ApiResponse<ForkConversationResponse>>, ApiError> {
+    let session = ConversationService::fork_conversation(
+        &deployment.db().pool,
+        &conversation,
+        payload.message_id,
+        payload.title,
+    )
+    .await?;
+
+    let sent_message = match payload.content.filter(|content| !content.trim().is_empty()) {
+        Some(content) => Some(
+            start_message_execution(
+                &deployment,
+                &session,
+                SendMessageRequest {
+                    content,
+                    variant: payload.variant,
+                },
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    Ok(ResponseJson(ApiResponse::success(
+        ForkConversationResponse {
+            session,
+            sent_message,
+        },
+    )))
 }
 
 /// Get paginated messages in a conversation
+#[utoipa::path(
+    get,
+    path = "/api/conversations/{conversation_id}/messages",
+    tag = "conversations",
+    params(
+        ("conversation_id" = Uuid, Path),
+        GetMessagesQuery
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ConversationMessagesPage>),
+    )
+)]
 pub async fn get_messages(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -295,6 +500,13 @@ pub async fn get_messages(
 }
 
 /// Get execution processes for a conversation
+#[utoipa::path(
+    get,
+    path = "/api/conversations/{conversation_id}/executions",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ExecutionProcess>>))
+)]
 pub async fn get_executions(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -311,6 +523,17 @@ pub async fn get_executions(
 
 /// Upload an image for a conversation session.
 /// Images are stored in `.vibe-images/` and can be embedded in markdown messages.
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{conversation_id}/images/upload",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    request_body(
+        content = crate::routes::images::ImageUpload,
+        content_type = "multipart/form-data"
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_conversation_image(
     Path(conversation_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -344,6 +567,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .delete(delete_conversation),
         )
         .route("/messages", get(get_messages).post(send_message))
+        .route("/fork", post(fork_conversation))
         .route("/executions", get(get_executions))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -366,5 +590,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .nest("/conversations/{conversation_id}", conversation_actions)
         .nest("/conversations/{conversation_id}", conversation_images)
-        .nest("/conversations/{conversation_id}/queue", queue::router(deployment))
+        .nest(
+            "/conversations/{conversation_id}/queue",
+            queue::router(deployment),
+        )
 }
@@ -1,9 +1,9 @@
 use axum::{
     Extension, Json, Router,
-    extract::State,
+    extract::{Path, State},
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::get,
+    routing::{delete, get, put},
 };
 use db::models::{conversation_session::ConversationSession, scratch::DraftFollowUpData};
 use deployment::Deployment;
@@ -11,17 +11,36 @@ use serde::Deserialize;
 use services::services::queued_message::QueueStatus;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_conversation_middleware};
 
 /// Request body for queueing a follow-up message
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct QueueMessageRequest {
     pub message: String,
     pub variant: Option<String>,
 }
 
-/// Queue a follow-up message to be executed when the current execution finishes
+/// Request body for PUT /api/conversations/:id/queue/order
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ReorderConversationQueueRequest {
+    /// Every queued message of the conversation, in the desired order
+    pub message_ids: Vec<Uuid>,
+}
+
+/// Add a follow-up message to the end of the conversation's queue. Queued
+/// messages are sent one at a time, each when the execution before it
+/// completes.
+#[utoipa::path(
+    post,
+    path = "/api/conversations/{conversation_id}/queue",
+    tag = "conversations",
+    params(("conversation_id" = uuid::Uuid, Path)),
+    request_body = QueueMessageRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn queue_message(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -32,9 +51,8 @@ pub async fn queue_message(
         variant: payload.variant,
     };
 
-    let queued = deployment
-        .queued_message_service()
-        .queue_message(conversation.id, data);
+    let queue = deployment.queued_message_service();
+    queue.push_message(conversation.id, data);
 
     deployment
         .track_if_analytics_allowed(
@@ -46,12 +64,19 @@ pub async fn queue_message(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(QueueStatus::Queued {
-        message: queued,
-    })))
+    Ok(ResponseJson(ApiResponse::success(
+        queue.get_status(conversation.id),
+    )))
 }
 
-/// Cancel a queued follow-up message
+/// Cancel all queued follow-up messages
+#[utoipa::path(
+    delete,
+    path = "/api/conversations/{conversation_id}/queue",
+    tag = "conversations",
+    params(("conversation_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn cancel_queued_message(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -73,7 +98,67 @@ pub async fn cancel_queued_message(
     Ok(ResponseJson(ApiResponse::success(QueueStatus::Empty)))
 }
 
+/// Remove one message from the conversation's queue
+#[utoipa::path(
+    delete,
+    path = "/api/conversations/{conversation_id}/queue/{message_id}",
+    tag = "conversations",
+    params(
+        ("conversation_id" = Uuid, Path),
+        ("message_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
+pub async fn remove_queued_message(
+    Extension(conversation): Extension<ConversationSession>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_conversation_id, message_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    let queue = deployment.queued_message_service();
+    queue
+        .remove_message(conversation.id, message_id)
+        .ok_or_else(|| ApiError::NotFound("Queued message not found".to_string()))?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        queue.get_status(conversation.id),
+    )))
+}
+
+/// Reorder the conversation's queued messages
+#[utoipa::path(
+    put,
+    path = "/api/conversations/{conversation_id}/queue/order",
+    tag = "conversations",
+    params(("conversation_id" = Uuid, Path)),
+    request_body = ReorderConversationQueueRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
+pub async fn reorder_queued_messages(
+    Extension(conversation): Extension<ConversationSession>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderConversationQueueRequest>,
+) -> Result<ResponseJson<ApiResponse<QueueStatus>>, ApiError> {
+    let queue = deployment.queued_message_service();
+    if !queue.reorder(conversation.id, &payload.message_ids) {
+        return Err(ApiError::BadRequest(
+            "message_ids must list every queued message of the conversation exactly once"
+                .to_string(),
+        ));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        queue.get_status(conversation.id),
+    )))
+}
+
 /// Get the current queue status for a conversation
+#[utoipa::path(
+    get,
+    path = "/api/conversations/{conversation_id}/queue",
+    tag = "conversations",
+    params(("conversation_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn get_queue_status(
     Extension(conversation): Extension<ConversationSession>,
     State(deployment): State<DeploymentImpl>,
@@ -91,6 +176,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .post(queue_message)
                 .delete(cancel_queued_message),
         )
+        .route("/order", put(reorder_queued_messages))
+        .route("/{message_id}", delete(remove_queued_message))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_conversation_middleware,
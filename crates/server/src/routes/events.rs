@@ -12,6 +12,14 @@ use futures_util::TryStreamExt;
 
 use crate::DeploymentImpl;
 
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    responses(
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream"),
+    )
+)]
 pub async fn events(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
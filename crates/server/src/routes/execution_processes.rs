@@ -1,34 +1,43 @@
 use anyhow;
 use axum::{
-    Extension, Router,
-    extract::{
-        Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    Extension, Json, Router,
+    extract::{Path, Query, State, ws::Message},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_failure::ExecutionProcessFailure,
     execution_process_normalized_entry::{
         ExecutionProcessNormalizedEntriesPage, ExecutionProcessNormalizedEntry,
     },
     execution_process_repo_state::ExecutionProcessRepoState,
+    execution_process_resource_sample::ExecutionProcessResourceSample,
+    execution_queue::ExecutionQueue,
+    workspace::Workspace,
 };
 use deployment::Deployment;
-use futures_util::{StreamExt, TryStreamExt, stream::BoxStream};
-use serde::Deserialize;
-use services::services::container::{ContainerError, ContainerService};
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use futures_util::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::{ContainerError, ContainerService},
+    resource_monitor::ResourceSample,
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware,
-    routes::ws_helpers::{forward_stream_to_ws, forward_ws_messages},
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_execution_process_middleware,
+    routes::stream_transport::{LogMsgStream, MessageStream, StreamTransport},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ExecutionProcessQuery {
     pub workspace_id: Option<Uuid>,
     pub conversation_session_id: Option<Uuid>,
@@ -37,15 +46,48 @@ pub struct ExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct NormalizedEntriesQuery {
     pub before_index: Option<i64>,
     pub limit: Option<usize>,
 }
 
+/// Resource usage of one execution: the persisted time series plus the
+/// per-process breakdown of the latest sample while it is still running
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ExecutionProcessResources {
+    pub latest: Option<ResourceSample>,
+    pub history: Vec<ExecutionProcessResourceSample>,
+}
+
+/// The workspace created for a replay and the execution running in it, or
+/// its queue entry when the concurrency limits held the replay back
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ReplayExecutionResponse {
+    pub workspace: Workspace,
+    pub execution_process: Option<ExecutionProcess>,
+    pub queue_entry: Option<ExecutionQueue>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct KillProcessRequest {
+    pub pid: u32,
+}
+
 const DEFAULT_NORMALIZED_ENTRIES_LIMIT: usize = 200;
 const MAX_NORMALIZED_ENTRIES_LIMIT: usize = 500;
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ExecutionProcess>))
+)]
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -53,12 +95,22 @@ pub async fn get_execution_process_by_id(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/raw-logs/ws",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_raw_logs_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Check if the stream exists before upgrading the WebSocket
+    // Check if the stream exists before upgrading the connection
     let _stream = deployment
         .container()
         .stream_raw_logs(&exec_id)
@@ -67,18 +119,13 @@ pub async fn stream_raw_logs_ws(
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
-    Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
-            tracing::warn!("raw logs WS closed: {}", e);
-        }
-    }))
+    Ok(transport.messages("raw logs", raw_logs_stream(deployment, exec_id)))
 }
 
-async fn handle_raw_logs_ws(
-    socket: WebSocket,
+async fn raw_logs_stream(
     deployment: DeploymentImpl,
     exec_id: Uuid,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<MessageStream> {
     use std::sync::{
         Arc,
         atomic::{AtomicUsize, Ordering},
@@ -116,11 +163,21 @@ async fn handle_raw_logs_ws(
         .map_err(|e| std::io::Error::other(e.to_string()))
         .boxed();
 
-    forward_ws_messages(socket, stream).await
+    Ok(stream)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/normalized-logs/ws",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_normalized_logs_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ApiError> {
@@ -132,16 +189,21 @@ pub async fn stream_normalized_logs_ws(
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
-    // Convert the error type to anyhow::Error and turn TryStream -> Stream<Result<_, _>>
-    let stream = stream.err_into::<anyhow::Error>().into_stream();
-
-    Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_normalized_logs_ws(socket, stream).await {
-            tracing::warn!("normalized logs WS closed: {}", e);
-        }
-    }))
+    Ok(transport.log_msgs("normalized logs", async move { Ok(stream) }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/normalized-entries",
+    tag = "execution_processes",
+    params(
+        ("id" = Uuid, Path),
+        NormalizedEntriesQuery
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExecutionProcessNormalizedEntriesPage>),
+    )
+)]
 pub async fn get_normalized_entries(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -177,16 +239,13 @@ pub async fn get_normalized_entries(
     Ok(ResponseJson(ApiResponse::success(page)))
 }
 
-async fn handle_normalized_logs_ws(
-    socket: WebSocket,
-    stream: impl futures_util::Stream<Item = anyhow::Result<LogMsg>> + Unpin + Send + 'static,
-) -> anyhow::Result<()> {
-    let stream = stream
-        .map_err(|e| std::io::Error::other(e.to_string()))
-        .boxed();
-    forward_stream_to_ws(socket, stream).await
-}
-
+#[utoipa::path(
+    post,
+    path = "/api/execution-processes/{id}/stop",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -199,38 +258,152 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// GET /api/execution-processes/:id/failure - Why the execution failed, when
+/// its output told; null for executions that didn't fail or weren't classified
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/failure",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Option<ExecutionProcessFailure>>),
+    )
+)]
+pub async fn get_execution_process_failure(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ExecutionProcessFailure>>>, ApiError> {
+    let failure = ExecutionProcessFailure::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(failure)))
+}
+
+/// GET /api/execution-processes/resources - Latest samples of all running
+/// executions, heaviest CPU first
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/resources",
+    tag = "execution_processes",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ResourceSample>>))
+)]
+pub async fn get_running_resources(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ResourceSample>>>, ApiError> {
+    let samples = deployment.container().resource_monitor().latest_all();
+    Ok(ResponseJson(ApiResponse::success(samples)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/resources",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExecutionProcessResources>),
+    )
+)]
+pub async fn get_execution_process_resources(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcessResources>>, ApiError> {
+    let history = ExecutionProcessResourceSample::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    let latest = deployment
+        .container()
+        .resource_monitor()
+        .latest(execution_process.id);
+    Ok(ResponseJson(ApiResponse::success(
+        ExecutionProcessResources { latest, history },
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/resources/ws",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
+pub async fn stream_execution_process_resources_ws(
+    transport: StreamTransport,
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stream = deployment
+        .container()
+        .resource_monitor()
+        .stream(execution_process.id)
+        .map(|sample| {
+            serde_json::to_string(&sample)
+                .map(|json| Message::Text(json.into()))
+                .map_err(std::io::Error::other)
+        })
+        .boxed();
+
+    Ok(transport.messages("resource stats", async move { Ok(stream) }))
+}
+
+/// POST /api/execution-processes/:id/kill-process - Kill one process from the
+/// execution's tree, leaving the rest of the execution running
+#[utoipa::path(
+    post,
+    path = "/api/execution-processes/{id}/kill-process",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    request_body = KillProcessRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn kill_execution_process_child(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<KillProcessRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment
+        .container()
+        .resource_monitor()
+        .kill_process(execution_process.id, payload.pid)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/stream/ws",
+    tag = "execution_processes",
+    params(ExecutionProcessQuery),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_execution_processes_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ExecutionProcessQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     let show_soft_deleted = query.show_soft_deleted.unwrap_or(false);
 
     match (query.workspace_id, query.conversation_session_id) {
-        (Some(workspace_id), None) => Ok(ws.on_upgrade(move |socket| async move {
-            if let Err(e) = handle_workspace_execution_processes_ws(
-                socket,
-                deployment,
-                workspace_id,
-                show_soft_deleted,
-            )
-            .await
-            {
-                tracing::warn!("execution processes WS closed: {}", e);
-            }
-        })),
-        (None, Some(conversation_session_id)) => Ok(ws.on_upgrade(move |socket| async move {
-            if let Err(e) = handle_conversation_execution_processes_ws(
-                socket,
+        (Some(workspace_id), None) => Ok(transport.log_msgs(
+            "execution processes",
+            workspace_execution_processes_stream(deployment, workspace_id, show_soft_deleted),
+        )),
+        (None, Some(conversation_session_id)) => Ok(transport.log_msgs(
+            "execution processes",
+            conversation_execution_processes_stream(
                 deployment,
                 conversation_session_id,
                 show_soft_deleted,
-            )
-            .await
-            {
-                tracing::warn!("execution processes WS closed: {}", e);
-            }
-        })),
+            ),
+        )),
         (Some(_), Some(_)) => Err(ApiError::BadRequest(
             "Cannot specify both workspace_id and conversation_session_id".to_string(),
         )),
@@ -240,39 +413,37 @@ pub async fn stream_execution_processes_ws(
     }
 }
 
-async fn handle_workspace_execution_processes_ws(
-    socket: WebSocket,
+async fn workspace_execution_processes_stream(
     deployment: DeploymentImpl,
     workspace_id: Uuid,
     show_soft_deleted: bool,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
         .stream_execution_processes_for_workspace_raw(workspace_id, show_soft_deleted)
-        .await?;
-    stream_execution_processes_to_ws(socket, stream).await
+        .await?)
 }
 
-async fn handle_conversation_execution_processes_ws(
-    socket: WebSocket,
+async fn conversation_execution_processes_stream(
     deployment: DeploymentImpl,
     conversation_session_id: Uuid,
     show_soft_deleted: bool,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
         .stream_execution_processes_for_conversation_raw(conversation_session_id, show_soft_deleted)
-        .await?;
-    stream_execution_processes_to_ws(socket, stream).await
-}
-
-async fn stream_execution_processes_to_ws(
-    socket: WebSocket,
-    stream: BoxStream<'static, Result<LogMsg, std::io::Error>>,
-) -> anyhow::Result<()> {
-    forward_stream_to_ws(socket, stream).await
+        .await?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/repo-states",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ExecutionProcessRepoState>>),
+    )
+)]
 pub async fn get_execution_process_repo_states(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -283,14 +454,62 @@ pub async fn get_execution_process_repo_states(
     Ok(ResponseJson(ApiResponse::success(repo_states)))
 }
 
+/// POST /api/execution-processes/:id/replay - Re-run the execution in a new
+/// workspace checked out at the commits it started from
+#[utoipa::path(
+    post,
+    path = "/api/execution-processes/{id}/replay",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ReplayExecutionResponse>),
+    )
+)]
+pub async fn replay_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ReplayExecutionResponse>>, ApiError> {
+    let (workspace, replay) = deployment
+        .container()
+        .replay_execution(execution_process.id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "execution_replayed",
+            serde_json::json!({
+                "original_execution_id": execution_process.id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    let queue_entry = match replay {
+        Some(_) => None,
+        None => ExecutionQueue::find_by_workspace(&deployment.db().pool, workspace.id).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(
+        ReplayExecutionResponse {
+            workspace,
+            execution_process: replay,
+            queue_entry,
+        },
+    )))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
+        .route("/failure", get(get_execution_process_failure))
         .route("/repo-states", get(get_execution_process_repo_states))
         .route("/normalized-entries", get(get_normalized_entries))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/resources", get(get_execution_process_resources))
+        .route("/resources/ws", get(stream_execution_process_resources_ws))
+        .route("/kill-process", post(kill_execution_process_child))
+        .route("/replay", post(replay_execution_process))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
@@ -298,6 +517,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let workspaces_router = Router::new()
         .route("/stream/ws", get(stream_execution_processes_ws))
+        .route("/resources", get(get_running_resources))
         .nest("/{id}", workspace_id_router);
 
     Router::new().nest("/execution-processes", workspaces_router)
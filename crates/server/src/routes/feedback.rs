@@ -9,12 +9,13 @@ use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
 /// Response DTO that parses feedback_json into a JSON object
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[ts(export)]
 pub struct FeedbackResponse {
     pub id: Uuid,
@@ -43,7 +44,8 @@ impl From<AgentFeedback> for FeedbackResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct RecentFeedbackQuery {
     #[serde(default = "default_limit")]
     pub limit: i64,
@@ -54,6 +56,13 @@ fn default_limit() -> i64 {
 }
 
 /// GET /api/feedback/task/:task_id - Returns all feedback for a task
+#[utoipa::path(
+    get,
+    path = "/api/feedback/task/{task_id}",
+    tag = "feedback",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<FeedbackResponse>>))
+)]
 pub async fn get_feedback_by_task(
     State(deployment): State<DeploymentImpl>,
     Path(task_id): Path<Uuid>,
@@ -64,6 +73,13 @@ pub async fn get_feedback_by_task(
 }
 
 /// GET /api/feedback/workspace/:workspace_id - Returns all feedback for a workspace
+#[utoipa::path(
+    get,
+    path = "/api/feedback/workspace/{workspace_id}",
+    tag = "feedback",
+    params(("workspace_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<FeedbackResponse>>))
+)]
 pub async fn get_feedback_by_workspace(
     State(deployment): State<DeploymentImpl>,
     Path(workspace_id): Path<Uuid>,
@@ -75,6 +91,13 @@ pub async fn get_feedback_by_workspace(
 }
 
 /// GET /api/feedback/recent?limit=N - Returns N most recent feedback entries
+#[utoipa::path(
+    get,
+    path = "/api/feedback/recent",
+    tag = "feedback",
+    params(RecentFeedbackQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<FeedbackResponse>>))
+)]
 pub async fn get_recent_feedback(
     State(deployment): State<DeploymentImpl>,
     Query(params): Query<RecentFeedbackQuery>,
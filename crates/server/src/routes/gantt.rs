@@ -1,9 +1,6 @@
 use axum::{
     Extension, Router,
-    extract::{
-        Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{Path, Query, State},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::get,
@@ -13,20 +10,24 @@ use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    routes::ws_helpers::forward_stream_to_ws, DeploymentImpl, error::ApiError,
+    DeploymentImpl,
+    error::ApiError,
     middleware::load_project_middleware,
+    routes::stream_transport::{LogMsgStream, StreamTransport},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GanttQuery {
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct PaginatedGanttTasks {
@@ -35,6 +36,16 @@ pub struct PaginatedGanttTasks {
     pub has_more: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/gantt",
+    tag = "gantt",
+    params(
+        ("project_id" = Uuid, Path),
+        GanttQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<PaginatedGanttTasks>))
+)]
 pub async fn get_gantt_data(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -60,25 +71,29 @@ pub async fn get_gantt_data(
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/gantt/stream/ws",
+    tag = "gantt",
+    params(("project_id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_gantt_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Path(project_id): Path<Uuid>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_gantt_ws(socket, deployment, project_id).await {
-            tracing::warn!("gantt WS closed: {}", e);
-        }
-    })
+    transport.log_msgs("gantt", gantt_stream(deployment, project_id))
 }
 
-async fn handle_gantt_ws(
-    socket: WebSocket,
+async fn gantt_stream(
     deployment: DeploymentImpl,
     project_id: Uuid,
-) -> anyhow::Result<()> {
-    let stream = deployment.events().stream_gantt_raw(project_id).await?;
-    forward_stream_to_ws(socket, stream).await
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment.events().stream_gantt_raw(project_id).await?)
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
@@ -1,6 +1,12 @@
 use axum::response::Json;
 use utils::response::ApiResponse;
 
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "health",
+    responses((status = 200, description = "Success", body = ApiResponse<String>))
+)]
 pub async fn health_check() -> Json<ApiResponse<String>> {
     Json(ApiResponse::success("OK".to_string()))
 }
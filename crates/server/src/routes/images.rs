@@ -15,17 +15,18 @@ use db::models::{
 };
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
-use services::services::image::ImageError;
+use services::services::image::{ImageError, ImageGcReport, ORPHAN_GRACE_PERIOD};
 use sqlx::Error as SqlxError;
 use tokio::fs::File;
 use tokio_util::io::ReaderStream;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ImageResponse {
     pub id: Uuid,
     pub file_path: String, // relative path to display in markdown
@@ -54,14 +55,23 @@ impl ImageResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Multipart body of the image upload endpoints; only documents the API
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct ImageUpload {
+    #[schema(value_type = String, format = Binary)]
+    pub image: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ImageMetadataQuery {
     /// Path relative to worktree root, e.g., ".vibe-images/screenshot.png"
     pub path: String,
 }
 
 /// Metadata response for image files, used for rendering in WYSIWYG editor
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ImageMetadata {
     pub exists: bool,
@@ -72,6 +82,13 @@ pub struct ImageMetadata {
     pub proxy_url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/images/upload",
+    tag = "images",
+    request_body(content = ImageUpload, content_type = "multipart/form-data"),
+    responses((status = 200, description = "Success", body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_image(
     State(deployment): State<DeploymentImpl>,
     multipart: Multipart,
@@ -125,6 +142,14 @@ pub(crate) async fn process_image_upload(
     Err(ApiError::Image(ImageError::NotFound))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/images/task/{task_id}/upload",
+    tag = "images",
+    params(("task_id" = Uuid, Path)),
+    request_body(content = ImageUpload, content_type = "multipart/form-data"),
+    responses((status = 200, description = "Success", body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_task_image(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -139,6 +164,13 @@ pub async fn upload_task_image(
 }
 
 /// Serve an image file by ID
+#[utoipa::path(
+    get,
+    path = "/api/images/{id}/file",
+    tag = "images",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Image file", content_type = "image/*"))
+)]
 pub async fn serve_image(
     Path(image_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -172,6 +204,13 @@ pub async fn serve_image(
     Ok(response)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/images/{id}",
+    tag = "images",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_image(
     Path(image_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -181,6 +220,31 @@ pub async fn delete_image(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// POST /api/images/gc - Delete images no task or conversation uses and
+/// report the space reclaimed. Runs periodically on its own as well.
+#[utoipa::path(
+    post,
+    path = "/api/images/gc",
+    tag = "images",
+    responses((status = 200, description = "Success", body = ApiResponse<ImageGcReport>))
+)]
+pub async fn collect_image_garbage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ImageGcReport>>, ApiError> {
+    let report = deployment
+        .image()
+        .collect_garbage(ORPHAN_GRACE_PERIOD)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/images/task/{task_id}",
+    tag = "images",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ImageResponse>>))
+)]
 pub async fn get_task_images(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -259,6 +323,16 @@ where
 
 /// Get metadata for an image associated with a task.
 /// The path should be in the format `.vibe-images/{uuid}.{ext}`.
+#[utoipa::path(
+    get,
+    path = "/api/images/task/{task_id}/metadata",
+    tag = "images",
+    params(
+        ("task_id" = Uuid, Path),
+        ImageMetadataQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<ImageMetadata>))
+)]
 pub async fn get_task_image_metadata(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -274,6 +348,16 @@ pub async fn get_task_image_metadata(
 
 /// Get metadata for an image associated with a conversation session.
 /// The path should be in the format `.vibe-images/{uuid}.{ext}`.
+#[utoipa::path(
+    get,
+    path = "/api/images/conversation/{conversation_session_id}/metadata",
+    tag = "images",
+    params(
+        ("conversation_session_id" = Uuid, Path),
+        ImageMetadataQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<ImageMetadata>))
+)]
 pub async fn get_conversation_image_metadata(
     Path(conversation_session_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -293,6 +377,7 @@ pub fn routes() -> Router<DeploymentImpl> {
             "/upload",
             post(upload_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)), // 20MB limit
         )
+        .route("/gc", post(collect_image_garbage))
         .route("/{id}/file", get(serve_image))
         .route("/{id}", delete(delete_image))
         .route("/task/{task_id}", get(get_task_images))
@@ -1,20 +1,26 @@
 use axum::{
     Router,
+    middleware::from_fn_with_state,
     routing::{IntoMakeService, get},
 };
 use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::auth::require_user_middleware};
 
 pub mod account_info;
+pub mod admin;
 pub mod approvals;
+pub mod assets;
+pub mod backups;
+pub mod bisect;
 
-mod ws_helpers;
 pub mod claude_accounts;
 pub mod config;
 pub mod containers;
 pub mod conversations;
 pub mod filesystem;
+mod stream_transport;
+mod ws_helpers;
 // pub mod github;
 pub mod events;
 pub mod execution_processes;
@@ -25,11 +31,31 @@ pub mod health;
 pub mod images;
 pub mod notifications;
 pub mod oauth;
+pub mod openapi;
+pub mod operations;
 pub mod organizations;
+pub mod project_approval_policies;
+pub mod project_branch_cleanup;
+pub mod project_dashboard;
+pub mod project_env;
+pub mod project_export;
+pub mod project_file_rules;
+pub mod project_git_hooks;
+pub mod project_memories;
+pub mod project_merge_checks;
+pub mod project_pr_drafts;
+pub mod project_purge;
+pub mod project_redaction;
+pub mod project_sandbox;
+pub mod project_templates;
+pub mod project_toolchain;
 pub mod projects;
+pub mod prompt_templates;
 pub mod repo;
 pub mod review_attention;
 pub mod scratch;
+pub mod search;
+pub mod secrets;
 pub mod server_logs;
 pub mod sessions;
 pub mod settings;
@@ -38,22 +64,55 @@ pub mod skills;
 pub mod tags;
 pub mod task_attempts;
 pub mod task_dependencies;
+pub mod task_env;
 pub mod task_groups;
+pub mod task_links;
+pub mod task_notes;
+pub mod task_plans;
+pub mod task_schedules;
+pub mod task_watchers;
 pub mod tasks;
+pub mod time_tracking;
+pub mod trash;
+pub mod usage;
+pub mod users;
+pub mod webhooks;
 
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
-    let base_routes = Router::new()
-        .route("/health", get(health::health_check))
+    let protected_routes = Router::new()
         .merge(config::router())
         .merge(claude_accounts::router())
         .merge(containers::router(&deployment))
         .merge(projects::router(&deployment))
+        .merge(project_approval_policies::router(&deployment))
+        .merge(project_branch_cleanup::router(&deployment))
+        .merge(project_dashboard::router(&deployment))
+        .merge(project_env::router(&deployment))
+        .merge(project_export::router(&deployment))
+        .merge(project_file_rules::router(&deployment))
+        .merge(project_git_hooks::router(&deployment))
+        .merge(project_memories::router(&deployment))
+        .merge(project_merge_checks::router(&deployment))
+        .merge(project_pr_drafts::router(&deployment))
+        .merge(project_purge::router(&deployment))
+        .merge(project_redaction::router(&deployment))
+        .merge(project_sandbox::router(&deployment))
+        .merge(project_templates::router(&deployment))
+        .merge(project_toolchain::router(&deployment))
         .merge(tasks::router(&deployment))
         .merge(shared_tasks::router())
         .merge(task_attempts::router(&deployment))
         .merge(task_dependencies::router(&deployment))
+        .merge(task_env::router(&deployment))
         .merge(task_groups::router(&deployment))
+        .merge(task_links::router(&deployment))
+        .merge(task_notes::router(&deployment))
+        .merge(task_plans::router(&deployment))
+        .merge(task_schedules::router(&deployment))
+        .merge(task_watchers::router(&deployment))
+        .merge(time_tracking::router(&deployment))
+        .merge(trash::router())
         .merge(notifications::router(&deployment))
         .merge(conversations::router(&deployment))
         .merge(execution_processes::router(&deployment))
@@ -61,25 +120,46 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(review_attention::router(&deployment))
         .merge(gantt::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(prompt_templates::router(&deployment))
         .merge(oauth::router())
+        .merge(operations::router())
+        .merge(bisect::router())
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(assets::router())
+        .merge(backups::router())
         .merge(account_info::router())
+        .merge(admin::router())
         .merge(scratch::router(&deployment))
+        .merge(search::router())
+        .merge(secrets::router())
         .merge(server_logs::router(&deployment))
         .merge(sessions::router(&deployment))
         .merge(settings::router())
         .merge(skills::router())
+        .merge(usage::router())
+        .merge(users::router())
+        .merge(webhooks::router(&deployment))
         .nest("/images", images::routes())
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            require_user_middleware,
+        ));
+
+    let base_routes = Router::new()
+        .route("/health", get(health::health_check))
+        .merge(users::session_router())
+        .merge(protected_routes)
         .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
+        .merge(openapi::router())
         .layer(
             CorsLayer::new()
                 .allow_origin(AllowOrigin::mirror_request())
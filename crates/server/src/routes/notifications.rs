@@ -1,40 +1,52 @@
 use axum::{
     Extension, Router,
-    extract::{
-        Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
 use db::models::notification::{
-    CreateNotification, Notification, NotificationStats, UpdateNotification,
+    CreateNotification, Notification, NotificationFilter, NotificationStats, NotificationType,
+    UpdateNotification,
 };
 use deployment::Deployment;
 use serde::Deserialize;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    routes::ws_helpers::forward_stream_to_ws, DeploymentImpl, error::ApiError,
-    middleware::load_notification_middleware,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_notification_middleware},
+    routes::stream_transport::{LogMsgStream, StreamTransport},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListNotificationsQuery {
     pub project_id: Option<Uuid>,
     pub limit: Option<i64>,
+    /// Only list notifications of this type
+    pub notification_type: Option<NotificationType>,
+    /// Only list notifications that haven't been read
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Only list notifications meant for the signed-in user, such as those
+    /// about the tasks they watch
+    #[serde(default)]
+    pub targeted_only: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct NotificationStatsQuery {
     pub project_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateNotificationRequest {
     pub project_id: Option<Uuid>,
     pub notification_type: db::models::notification::NotificationType,
@@ -46,7 +58,7 @@ pub struct CreateNotificationRequest {
     pub conversation_session_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct UpdateNotificationRequest {
     pub title: Option<String>,
     pub message: Option<String>,
@@ -54,36 +66,81 @@ pub struct UpdateNotificationRequest {
     pub metadata: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct MarkAllReadRequest {
     pub project_id: Option<Uuid>,
+    /// Only mark notifications of this type read
+    #[serde(default)]
+    pub notification_type: Option<NotificationType>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct MarkNotificationsReadRequest {
+    pub notification_ids: Vec<Uuid>,
+    /// False marks the notifications unread again
+    pub is_read: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct NotificationStreamQuery {
     pub project_id: Option<Uuid>,
+    /// Only stream notifications of this type
+    pub notification_type: Option<NotificationType>,
+    /// Only stream notifications meant for the signed-in user
+    #[serde(default)]
+    pub targeted_only: bool,
     #[serde(default)]
     pub include_snapshot: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    tag = "notifications",
+    params(ListNotificationsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Notification>>))
+)]
 pub async fn list_notifications(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListNotificationsQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Notification>>>, ApiError> {
+    let filter = NotificationFilter {
+        notification_type: query.notification_type,
+        unread_only: query.unread_only,
+        recipient: current_user.id(),
+        targeted_only: query.targeted_only,
+    };
     let notifications = if let Some(project_id) = query.project_id {
-        Notification::find_by_project_id(&deployment.db().pool, project_id, query.limit).await?
+        Notification::find_by_project_id(&deployment.db().pool, project_id, &filter, query.limit)
+            .await?
     } else {
-        Notification::find_global(&deployment.db().pool, query.limit).await?
+        Notification::find_global(&deployment.db().pool, &filter, query.limit).await?
     };
     Ok(ResponseJson(ApiResponse::success(notifications)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/notifications/{notification_id}",
+    tag = "notifications",
+    params(("notification_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Notification>))
+)]
 pub async fn get_notification(
     Extension(notification): Extension<Notification>,
 ) -> Result<ResponseJson<ApiResponse<Notification>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(notification)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/notifications",
+    tag = "notifications",
+    request_body = CreateNotificationRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Notification>))
+)]
 pub async fn create_notification(
     State(deployment): State<DeploymentImpl>,
     axum::Json(payload): axum::Json<CreateNotificationRequest>,
@@ -97,12 +154,22 @@ pub async fn create_notification(
         workspace_id: payload.workspace_id,
         session_id: payload.session_id,
         conversation_session_id: payload.conversation_session_id,
+        user_id: None,
+        group_key: None,
     };
 
     let notification = Notification::create(&deployment.db().pool, &create_data).await?;
     Ok(ResponseJson(ApiResponse::success(notification)))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/notifications/{notification_id}",
+    tag = "notifications",
+    params(("notification_id" = Uuid, Path)),
+    request_body = UpdateNotificationRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Notification>))
+)]
 pub async fn update_notification(
     Extension(existing): Extension<Notification>,
     State(deployment): State<DeploymentImpl>,
@@ -122,6 +189,13 @@ pub async fn update_notification(
     Ok(ResponseJson(ApiResponse::success(notification)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/notifications/{notification_id}",
+    tag = "notifications",
+    params(("notification_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_notification(
     Extension(notification): Extension<Notification>,
     State(deployment): State<DeploymentImpl>,
@@ -135,49 +209,106 @@ pub async fn delete_notification(
     Ok((StatusCode::OK, ResponseJson(ApiResponse::success(()))))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/notifications/mark-all-read",
+    tag = "notifications",
+    request_body = MarkAllReadRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<u64>))
+)]
 pub async fn mark_all_read(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     axum::Json(payload): axum::Json<MarkAllReadRequest>,
 ) -> Result<ResponseJson<ApiResponse<u64>>, ApiError> {
-    let updated_count =
-        Notification::mark_all_read(&deployment.db().pool, payload.project_id).await?;
+    let updated_count = Notification::mark_all_read(
+        &deployment.db().pool,
+        payload.project_id,
+        payload.notification_type.as_ref(),
+        current_user.id(),
+    )
+    .await?;
     Ok(ResponseJson(ApiResponse::success(updated_count)))
 }
 
+/// Mark the given notifications read or unread; returns how many changed
+#[utoipa::path(
+    post,
+    path = "/api/notifications/mark-read",
+    tag = "notifications",
+    request_body = MarkNotificationsReadRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<u64>))
+)]
+pub async fn mark_read(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    axum::Json(payload): axum::Json<MarkNotificationsReadRequest>,
+) -> Result<ResponseJson<ApiResponse<u64>>, ApiError> {
+    let updated_count = Notification::mark_read(
+        &deployment.db().pool,
+        &payload.notification_ids,
+        payload.is_read,
+        current_user.id(),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated_count)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/stats",
+    tag = "notifications",
+    params(NotificationStatsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<NotificationStats>))
+)]
 pub async fn get_stats(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<NotificationStatsQuery>,
 ) -> Result<ResponseJson<ApiResponse<NotificationStats>>, ApiError> {
-    let stats = Notification::get_stats(&deployment.db().pool, query.project_id).await?;
+    let stats =
+        Notification::get_stats(&deployment.db().pool, query.project_id, current_user.id()).await?;
     Ok(ResponseJson(ApiResponse::success(stats)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/notifications/stream/ws",
+    tag = "notifications",
+    params(NotificationStreamQuery),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_notifications_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<NotificationStreamQuery>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) =
-            handle_notifications_ws(socket, deployment, query.project_id, query.include_snapshot)
-                .await
-        {
-            tracing::warn!("notifications WS closed: {}", e);
-        }
-    })
-}
-
-async fn handle_notifications_ws(
-    socket: WebSocket,
+    let filter = NotificationFilter {
+        notification_type: query.notification_type,
+        unread_only: false,
+        recipient: current_user.id(),
+        targeted_only: query.targeted_only,
+    };
+    transport.log_msgs(
+        "notifications",
+        notifications_stream(deployment, query.project_id, filter, query.include_snapshot),
+    )
+}
+
+async fn notifications_stream(
     deployment: DeploymentImpl,
     project_id: Option<Uuid>,
+    filter: NotificationFilter,
     include_snapshot: bool,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
-        .stream_notifications_raw(project_id, include_snapshot)
-        .await?;
-    forward_stream_to_ws(socket, stream).await
+        .stream_notifications_raw(project_id, filter, include_snapshot)
+        .await?)
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
@@ -196,6 +327,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let inner = Router::new()
         .route("/", get(list_notifications).post(create_notification))
         .route("/mark-all-read", post(mark_all_read))
+        .route("/mark-read", post(mark_read))
         .route("/stats", get(get_stats))
         .route("/stream/ws", get(stream_notifications_ws))
         .nest("/{notification_id}", notification_actions);
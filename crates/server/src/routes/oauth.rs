@@ -19,12 +19,13 @@ use utils::{
     jwt::extract_expiration,
     response::ApiResponse,
 };
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
 /// Response from GET /api/auth/token - returns the current access token
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[ts(export)]
 pub struct TokenResponse {
     pub access_token: String,
@@ -32,7 +33,7 @@ pub struct TokenResponse {
 }
 
 /// Response from GET /api/auth/user - returns the current user ID
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CurrentUserResponse {
     pub user_id: String,
@@ -48,18 +49,27 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/auth/user", get(get_current_user))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct HandoffInitPayload {
     provider: String,
     return_to: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct HandoffInitResponseBody {
     handoff_id: Uuid,
     authorize_url: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/handoff/init",
+    tag = "oauth",
+    request_body = HandoffInitPayload,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<HandoffInitResponseBody>),
+    )
+)]
 async fn handoff_init(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<HandoffInitPayload>,
@@ -89,7 +99,8 @@ async fn handoff_init(
     )))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct HandoffCompleteQuery {
     handoff_id: Uuid,
     #[serde(default)]
@@ -98,6 +109,15 @@ struct HandoffCompleteQuery {
     error: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/handoff/complete",
+    tag = "oauth",
+    params(HandoffCompleteQuery),
+    responses(
+        (status = 200, description = "Page telling the user they can close the window", content_type = "text/html"),
+    )
+)]
 async fn handoff_complete(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<HandoffCompleteQuery>,
@@ -222,6 +242,12 @@ async fn handoff_complete(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "oauth",
+    responses((status = 204, description = "No content"))
+)]
 async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode, ApiError> {
     let auth_context = deployment.auth_context();
 
@@ -239,6 +265,12 @@ async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode,
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/status",
+    tag = "oauth",
+    responses((status = 200, description = "Success", body = ApiResponse<StatusResponse>))
+)]
 async fn status(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<StatusResponse>>, ApiError> {
@@ -261,6 +293,12 @@ async fn status(
 }
 
 /// Returns the current access token (auto-refreshes if needed)
+#[utoipa::path(
+    get,
+    path = "/api/auth/token",
+    tag = "oauth",
+    responses((status = 200, description = "Success", body = ApiResponse<TokenResponse>))
+)]
 async fn get_token(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<TokenResponse>>, ApiError> {
@@ -281,6 +319,12 @@ async fn get_token(
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/user",
+    tag = "oauth",
+    responses((status = 200, description = "Success", body = ApiResponse<CurrentUserResponse>))
+)]
 async fn get_current_user(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<CurrentUserResponse>>, ApiError> {
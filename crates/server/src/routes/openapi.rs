@@ -0,0 +1,467 @@
+//! OpenAPI description of the REST API, served at `/api/openapi.json` with a
+//! Swagger UI at `/api/docs`.
+//!
+//! Handlers document themselves with `#[utoipa::path]`; a handler only shows
+//! up in the spec once it is listed in [`ApiDoc`].
+
+use axum::Router;
+use utils::response::ApiResponse;
+use utoipa::{
+    Modify, OpenApi, PartialSchema,
+    openapi::{self, ContentBuilder, ResponseBuilder},
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::{
+    account_info, admin, approvals, assets, backups, bisect, claude_accounts, config, containers,
+    conversations, events, execution_processes, feedback, filesystem, gantt, health, images,
+    notifications, oauth, operations, organizations, project_approval_policies,
+    project_branch_cleanup, project_dashboard, project_env, project_export, project_file_rules,
+    project_git_hooks, project_memories, project_merge_checks, project_pr_drafts, project_purge,
+    project_redaction, project_sandbox, project_templates, project_toolchain, projects,
+    prompt_templates, repo, review_attention, scratch, search, secrets, server_logs, sessions,
+    settings, shared_tasks, skills, tags, task_attempts, task_dependencies, task_env, task_groups,
+    task_links, task_notes, task_plans, task_schedules, task_watchers, tasks, time_tracking, trash,
+    usage, users, webhooks,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Vibe Kanban API",
+        description = "Local REST API of the Vibe Kanban server. JSON endpoints wrap their \
+                       result in an `ApiResponse` envelope."
+    ),
+    paths(
+        account_info::get_account_info,
+        admin::redact,
+        admin::get_audit_log,
+        approvals::respond_to_approval,
+        assets::get_asset_dir,
+        assets::relocate,
+        backups::get_backups,
+        backups::restore_backup,
+        backups::get_verifications,
+        backups::verify_backup,
+        bisect::start_bisect,
+        bisect::get_bisect,
+        bisect::list_bisects,
+        claude_accounts::list_accounts_handler,
+        claude_accounts::save_current_account_handler,
+        claude_accounts::switch_account_handler,
+        claude_accounts::update_account_name_handler,
+        claude_accounts::delete_account_handler,
+        claude_accounts::get_current_account_handler,
+        claude_accounts::get_current_uuid_handler,
+        config::get_user_system_info,
+        config::update_config,
+        config::get_config_compatibility,
+        config::list_sounds,
+        config::get_sound,
+        config::list_custom_editors,
+        config::create_custom_editor,
+        config::update_custom_editor,
+        config::delete_custom_editor,
+        config::check_custom_editor_availability,
+        config::get_mcp_servers,
+        config::update_mcp_servers,
+        config::get_profiles,
+        config::update_profiles,
+        config::check_editor_availability,
+        config::check_agent_availability,
+        config::get_agent_auth_status,
+        config::refresh_agent_auth_status,
+        containers::get_context,
+        conversations::list_conversations,
+        conversations::create_conversation,
+        conversations::get_conversation,
+        conversations::update_conversation,
+        conversations::delete_conversation,
+        conversations::send_message,
+        conversations::fork_conversation,
+        conversations::get_messages,
+        conversations::get_executions,
+        conversations::upload_conversation_image,
+        conversations::queue::queue_message,
+        conversations::queue::cancel_queued_message,
+        conversations::queue::remove_queued_message,
+        conversations::queue::reorder_queued_messages,
+        conversations::queue::get_queue_status,
+        events::events,
+        execution_processes::get_execution_process_by_id,
+        execution_processes::stream_raw_logs_ws,
+        execution_processes::stream_normalized_logs_ws,
+        execution_processes::get_normalized_entries,
+        execution_processes::stop_execution_process,
+        execution_processes::get_running_resources,
+        execution_processes::get_execution_process_resources,
+        execution_processes::get_execution_process_failure,
+        execution_processes::stream_execution_process_resources_ws,
+        execution_processes::kill_execution_process_child,
+        execution_processes::replay_execution_process,
+        execution_processes::stream_execution_processes_ws,
+        execution_processes::get_execution_process_repo_states,
+        feedback::get_feedback_by_task,
+        feedback::get_feedback_by_workspace,
+        feedback::get_recent_feedback,
+        filesystem::list_directory,
+        filesystem::list_git_repos,
+        gantt::get_gantt_data,
+        gantt::stream_gantt_ws,
+        health::health_check,
+        images::upload_image,
+        images::upload_task_image,
+        images::serve_image,
+        images::delete_image,
+        images::collect_image_garbage,
+        images::get_task_images,
+        images::get_task_image_metadata,
+        images::get_conversation_image_metadata,
+        notifications::list_notifications,
+        notifications::get_notification,
+        notifications::create_notification,
+        notifications::update_notification,
+        notifications::delete_notification,
+        notifications::mark_all_read,
+        notifications::mark_read,
+        notifications::get_stats,
+        notifications::stream_notifications_ws,
+        oauth::handoff_init,
+        oauth::handoff_complete,
+        oauth::logout,
+        oauth::status,
+        oauth::get_token,
+        oauth::get_current_user,
+        operations::list_operations,
+        operations::get_operation,
+        operations::stream_operation_ws,
+        organizations::list_organization_projects,
+        organizations::list_organizations,
+        organizations::get_organization,
+        organizations::create_organization,
+        organizations::update_organization,
+        organizations::delete_organization,
+        organizations::create_invitation,
+        organizations::list_invitations,
+        organizations::get_invitation,
+        organizations::revoke_invitation,
+        organizations::accept_invitation,
+        organizations::list_members,
+        organizations::remove_member,
+        organizations::update_member_role,
+        project_dashboard::get_project_dashboard,
+        project_env::get_env_file,
+        project_env::update_env_file,
+        project_env::delete_env_file,
+        project_env::get_secrets,
+        project_env::upsert_secret,
+        project_env::delete_secret,
+        project_file_rules::get_file_rules,
+        project_file_rules::update_file_rules,
+        project_branch_cleanup::get_branch_cleanup_policy,
+        project_branch_cleanup::update_branch_cleanup_policy,
+        project_branch_cleanup::run_branch_cleanup,
+        project_purge::purge_project,
+        project_git_hooks::get_git_hooks_policy,
+        project_git_hooks::update_git_hooks_policy,
+        project_git_hooks::get_pre_commit_status,
+        project_memories::get_memory_settings,
+        project_memories::update_memory_settings,
+        project_memories::get_memories,
+        project_memories::create_memory,
+        project_memories::update_memory,
+        project_memories::delete_memory,
+        project_merge_checks::get_merge_check_policy,
+        project_merge_checks::update_merge_check_policy,
+        project_pr_drafts::get_pr_draft_policy,
+        project_pr_drafts::update_pr_draft_policy,
+        project_redaction::get_redaction_patterns,
+        project_redaction::update_redaction_patterns,
+        project_sandbox::get_sandbox_policy,
+        project_sandbox::update_sandbox_policy,
+        project_sandbox::get_sandbox_status,
+        project_approval_policies::get_approval_policies,
+        project_approval_policies::upsert_approval_policy,
+        project_approval_policies::delete_approval_policy,
+        project_toolchain::get_toolchain,
+        project_toolchain::update_toolchain,
+        project_toolchain::run_toolchain_doctor,
+        projects::get_projects,
+        projects::stream_projects_ws,
+        projects::get_project,
+        projects::link_project_to_existing_remote,
+        projects::create_and_link_remote_project,
+        projects::unlink_project,
+        projects::get_remote_project_by_id,
+        projects::get_project_remote_members,
+        projects::create_project,
+        projects::update_project,
+        projects::delete_project,
+        projects::archive_project,
+        projects::unarchive_project,
+        projects::open_project_in_editor,
+        projects::search_project_files,
+        projects::get_project_repositories,
+        projects::add_project_repository,
+        projects::delete_project_repository,
+        projects::get_project_repository,
+        projects::update_project_repository,
+        projects::preview_project_repository_copy_files,
+        projects::get_project_prs,
+        projects::invalidate_project_prs_cache,
+        projects::get_project_prs_unresolved_counts,
+        projects::get_merge_queue,
+        projects::reorder_merge_queue,
+        projects::cancel_merge_queue_entry,
+        projects::get_merge_queue_runs,
+        projects::get_merge_queue_count,
+        projects::get_project_queue,
+        projects::reorder_project_queue,
+        projects::get_pr_threads,
+        projects::get_project_workspaces,
+        projects::get_project_worktrees,
+        project_export::export_project,
+        project_export::import_project,
+        project_templates::get_project_templates,
+        project_templates::create_project_template,
+        project_templates::get_project_template,
+        project_templates::update_project_template,
+        project_templates::delete_project_template,
+        project_templates::create_project_from_template,
+        prompt_templates::get_prompt_templates,
+        prompt_templates::create_prompt_template,
+        prompt_templates::get_prompt_template,
+        prompt_templates::update_prompt_template,
+        prompt_templates::delete_prompt_template,
+        prompt_templates::preview_prompt_template,
+        repo::register_repo,
+        repo::init_repo,
+        repo::clone_repo,
+        repo::get_repo_branches,
+        repo::get_repo_stats,
+        repo::create_branch,
+        repo::update_repo_scripts,
+        repo::update_repo_checkout_options,
+        repo::check_branch_merge_status,
+        repo::batch_check_branch_merge_status,
+        repo::check_branch_sync_status,
+        repo::batch_check_branch_sync_status,
+        repo::create_repo_pr,
+        repo::push_branch,
+        review_attention::get_review_attention_by_task,
+        scratch::list_scratch,
+        scratch::get_scratch,
+        scratch::create_scratch,
+        scratch::update_scratch,
+        scratch::delete_scratch,
+        scratch::stream_scratch_ws,
+        search::semantic_search,
+        search::text_search,
+        secrets::list_secrets,
+        secrets::set_secret,
+        secrets::delete_secret,
+        server_logs::stream_server_logs_ws,
+        server_logs::get_server_logs,
+        server_logs::stream_filtered_server_logs_ws,
+        sessions::get_sessions,
+        sessions::get_session,
+        sessions::get_session_retries,
+        sessions::get_session_failures,
+        sessions::create_session,
+        sessions::follow_up,
+        sessions::interrupt_and_follow_up,
+        sessions::queue::queue_message,
+        sessions::queue::cancel_queued_message,
+        sessions::queue::get_queue_status,
+        settings::get_github_settings,
+        settings::set_github_token,
+        settings::delete_github_token,
+        settings::import_github_token,
+        shared_tasks::assign_shared_task,
+        shared_tasks::delete_shared_task,
+        shared_tasks::link_shared_task_to_local,
+        skills::get_skills,
+        tags::get_tags,
+        tags::create_tag,
+        tags::update_tag,
+        tags::delete_tag,
+        task_attempts::get_task_attempts,
+        task_attempts::get_task_attempt,
+        task_attempts::get_task_attempt_with_session,
+        task_attempts::create_task_attempt,
+        task_attempts::run_agent_setup,
+        task_attempts::stream_task_attempt_diff_ws,
+        task_attempts::diff_content::get_diff_file_content,
+        task_attempts::stream_workspaces_ws,
+        task_attempts::stream_git_status_ws,
+        task_attempts::merge_task_attempt,
+        task_attempts::generate_commit_message,
+        task_attempts::push_task_attempt_branch,
+        task_attempts::force_push_task_attempt_branch,
+        task_attempts::open_task_attempt_in_editor,
+        task_attempts::get_task_attempt_branch_status,
+        task_attempts::change_target_branch,
+        task_attempts::rename_branch,
+        task_attempts::rebase_task_attempt,
+        task_attempts::abort_conflicts_task_attempt,
+        task_attempts::cherry_pick_task_attempt,
+        task_attempts::cherry_pick_onto_branch,
+        task_attempts::get_conflicts,
+        task_attempts::resolve_conflict,
+        task_attempts::continue_conflicts,
+        task_attempts::start_dev_server,
+        task_attempts::get_task_attempt_children,
+        task_attempts::stop_task_attempt_execution,
+        task_attempts::repair_task_attempt,
+        task_attempts::run_setup_script,
+        task_attempts::run_cleanup_script,
+        task_attempts::gh_cli_setup_handler,
+        task_attempts::get_task_attempt_repos,
+        task_attempts::queue_merge,
+        task_attempts::cancel_queue_merge,
+        task_attempts::cancel_execution_queue,
+        task_attempts::get_queue_status,
+        task_attempts::permission_findings::get_permission_findings,
+        task_attempts::permission_findings::acknowledge_permission_findings,
+        task_attempts::review_comments::get_review_comments,
+        task_attempts::review_comments::create_review_comment,
+        task_attempts::review_comments::update_review_comment,
+        task_attempts::review_comments::delete_review_comment,
+        task_attempts::review_comments::send_review_comments,
+        task_attempts::bundle::export_bundle,
+        task_attempts::bundle::import_bundle,
+        task_attempts::stage::stage_changes,
+        task_attempts::stage::unstage_changes,
+        task_attempts::images::upload_image,
+        task_attempts::images::get_image_metadata,
+        task_attempts::images::serve_image,
+        task_attempts::pr::create_github_pr,
+        task_attempts::pr::attach_existing_pr,
+        task_attempts::pr::get_pr_comments,
+        task_dependencies::get_dependencies,
+        task_dependencies::add_dependency,
+        task_dependencies::remove_dependency,
+        task_dependencies::get_dependency_tree,
+        task_dependencies::get_dependency_context,
+        task_groups::get_task_group_stats,
+        task_groups::list_task_groups,
+        task_groups::get_task_group,
+        task_groups::create_task_group,
+        task_groups::update_task_group,
+        task_groups::delete_task_group,
+        task_groups::bulk_assign_tasks,
+        task_groups::merge_task_group,
+        task_groups::get_merge_queue_count,
+        task_env::get_task_env,
+        task_env::upsert_task_env_var,
+        task_env::delete_task_env_var,
+        task_links::get_task_links,
+        task_links::create_task_link,
+        task_links::update_task_link,
+        task_links::delete_task_link,
+        task_links::refresh_task_link,
+        task_notes::get_task_notes,
+        task_notes::update_task_notes,
+        task_notes::delete_task_notes,
+        task_plans::bulk_create_tasks,
+        task_schedules::get_schedules,
+        task_schedules::create_schedule,
+        task_schedules::update_schedule,
+        task_schedules::delete_schedule,
+        task_schedules::run_schedule_now,
+        task_watchers::get_task_watchers,
+        task_watchers::watch_task,
+        task_watchers::unwatch_task,
+        tasks::get_tasks,
+        tasks::search_tasks,
+        tasks::stream_tasks_ws,
+        tasks::get_task,
+        tasks::create_task,
+        tasks::create_task_and_start,
+        tasks::update_task,
+        tasks::delete_task,
+        tasks::archive_task,
+        tasks::unarchive_task,
+        tasks::share_task,
+        time_tracking::get_task_time,
+        time_tracking::get_project_time,
+        trash::get_trash,
+        trash::restore_project,
+        trash::restore_task,
+        usage::get_usage,
+        users::get_session,
+        users::login,
+        users::logout,
+        users::get_users,
+        users::create_user,
+        users::delete_user,
+        webhooks::get_webhooks,
+        webhooks::create_webhook,
+        webhooks::get_webhook,
+        webhooks::update_webhook,
+        webhooks::delete_webhook,
+        webhooks::test_webhook,
+    ),
+    modifiers(&ErrorResponses)
+)]
+pub struct ApiDoc;
+
+/// Documents the error envelope as every operation's default response:
+/// failures return an `ApiResponse` with `success: false` and a `message`.
+struct ErrorResponses;
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        let error = ResponseBuilder::new()
+            .description("Error")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(ApiResponse::<()>::schema()))
+                    .build(),
+            )
+            .build();
+
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                operation
+                    .responses
+                    .responses
+                    .entry("default".to_string())
+                    .or_insert_with(|| error.clone().into());
+            }
+        }
+    }
+}
+
+pub fn router() -> Router {
+    SwaggerUi::new("/api/docs")
+        .url("/api/openapi.json", ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_lists_handlers_with_error_responses() {
+        let spec = ApiDoc::openapi();
+        let health = spec
+            .paths
+            .paths
+            .get("/api/health")
+            .and_then(|item| item.get.as_ref())
+            .expect("health check is documented");
+        assert!(health.responses.responses.contains_key("200"));
+        assert!(health.responses.responses.contains_key("default"));
+        assert!(spec.to_json().is_ok());
+    }
+}
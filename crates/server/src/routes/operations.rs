@@ -0,0 +1,100 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::{IntoResponse, Json as ResponseJson},
+    routing::get,
+};
+use db::models::operation::{Operation, OperationKind, OperationState};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::stream_transport::StreamTransport};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OperationsQuery {
+    pub workspace_id: Option<Uuid>,
+    pub kind: Option<OperationKind>,
+    pub state: Option<OperationState>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/operations - Recent operations, newest first
+#[utoipa::path(
+    get,
+    path = "/api/operations",
+    tag = "operations",
+    params(OperationsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Operation>>))
+)]
+pub async fn list_operations(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OperationsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Operation>>>, ApiError> {
+    const DEFAULT_LIMIT: i64 = 50;
+    const MAX_LIMIT: i64 = 200;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let operations = Operation::find_recent(
+        &deployment.db().pool,
+        query.workspace_id,
+        query.kind,
+        query.state,
+        limit,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(operations)))
+}
+
+/// GET /api/operations/:id - Poll a single operation
+#[utoipa::path(
+    get,
+    path = "/api/operations/{operation_id}",
+    tag = "operations",
+    params(("operation_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Operation>))
+)]
+pub async fn get_operation(
+    State(deployment): State<DeploymentImpl>,
+    Path(operation_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Operation>>, ApiError> {
+    let operation = Operation::find_by_id(&deployment.db().pool, operation_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Operation not found".to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(operation)))
+}
+
+/// GET /api/operations/:id/ws - Follow a single operation until it finishes
+#[utoipa::path(
+    get,
+    path = "/api/operations/{operation_id}/ws",
+    tag = "operations",
+    params(("operation_id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
+pub async fn stream_operation_ws(
+    transport: StreamTransport,
+    State(deployment): State<DeploymentImpl>,
+    Path(operation_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stream = deployment
+        .operations()
+        .stream_raw(operation_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Operation not found".to_string()))?;
+
+    Ok(transport.log_msgs("operation", async move { Ok(stream) }))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/operations", get(list_operations))
+        .route("/operations/{operation_id}", get(get_operation))
+        .route("/operations/{operation_id}/ws", get(stream_operation_ws))
+}
@@ -0,0 +1,113 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    approval_policy::{ApprovalPolicy, UpsertApprovalPolicy},
+    project::Project,
+};
+use deployment::Deployment;
+use services::services::approvals::policy::validate_path_glob;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// GET /api/projects/:id/approval-policies - The project's default policy
+/// and any per-executor policies
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/approval-policies",
+    tag = "project_approval_policies",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ApprovalPolicy>>))
+)]
+pub async fn get_approval_policies(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalPolicy>>>, ApiError> {
+    let policies = ApprovalPolicy::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policies)))
+}
+
+/// PUT /api/projects/:id/approval-policies - Save the policy for an executor,
+/// or the project's default when no executor is given
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/approval-policies",
+    tag = "project_approval_policies",
+    params(("project_id" = Uuid, Path)),
+    request_body = UpsertApprovalPolicy,
+    responses((status = 200, description = "Success", body = ApiResponse<ApprovalPolicy>))
+)]
+pub async fn upsert_approval_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<UpsertApprovalPolicy>,
+) -> Result<ResponseJson<ApiResponse<ApprovalPolicy>>, ApiError> {
+    for list in [
+        &mut payload.allowed_tools,
+        &mut payload.allowed_commands,
+        &mut payload.path_globs,
+    ] {
+        list.iter_mut()
+            .for_each(|entry| *entry = entry.trim().to_string());
+        list.retain(|entry| !entry.is_empty());
+    }
+    for glob in &payload.path_globs {
+        validate_path_glob(glob)
+            .map_err(|e| ApiError::BadRequest(format!("Invalid path glob '{glob}': {e}")))?;
+    }
+    if payload.max_file_size.is_some_and(|size| size < 0) {
+        return Err(ApiError::BadRequest(
+            "max_file_size cannot be negative".to_string(),
+        ));
+    }
+
+    let policy = ApprovalPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// DELETE /api/projects/:id/approval-policies/:policy_id
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/approval-policies/{policy_id}",
+    tag = "project_approval_policies",
+    params(
+        ("project_id" = Uuid, Path),
+        ("policy_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_approval_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, policy_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ApprovalPolicy::delete(&deployment.db().pool, project.id, policy_id).await?;
+    if rows == 0 {
+        return Err(ApiError::NotFound("Approval policy not found".to_string()));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_approval_policies = Router::new()
+        .route(
+            "/approval-policies",
+            get(get_approval_policies).put(upsert_approval_policy),
+        )
+        .route(
+            "/approval-policies/{policy_id}",
+            delete(delete_approval_policy),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_approval_policies)
+}
@@ -0,0 +1,120 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    branch_cleanup_policy::{BranchCleanupMode, BranchCleanupPolicy, UpdateBranchCleanupPolicy},
+    project::Project,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::branch_janitor::{BranchCleanupReport, BranchJanitor};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct RunBranchCleanupRequest {
+    /// Report what would be removed without touching any repo
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Overrides the saved policy's mode for this run
+    pub mode: Option<BranchCleanupMode>,
+    /// Overrides the saved policy's minimum merge age for this run
+    #[ts(type = "number | null")]
+    pub min_age_days: Option<i64>,
+}
+
+/// GET /api/projects/:id/branch-cleanup - The project's branch cleanup policy
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/branch-cleanup",
+    tag = "project_branch_cleanup",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BranchCleanupPolicy>))
+)]
+pub async fn get_branch_cleanup_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BranchCleanupPolicy>>, ApiError> {
+    let policy = BranchCleanupPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// PUT /api/projects/:id/branch-cleanup - Save the project's branch cleanup policy
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/branch-cleanup",
+    tag = "project_branch_cleanup",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = UpdateBranchCleanupPolicy,
+    responses((status = 200, description = "Success", body = ApiResponse<BranchCleanupPolicy>))
+)]
+pub async fn update_branch_cleanup_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateBranchCleanupPolicy>,
+) -> Result<ResponseJson<ApiResponse<BranchCleanupPolicy>>, ApiError> {
+    if payload.min_age_days < 0 {
+        return Err(ApiError::BadRequest(
+            "min_age_days cannot be negative".to_string(),
+        ));
+    }
+
+    let policy = BranchCleanupPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// POST /api/projects/:id/branch-cleanup/run - Clean up merged task branches now
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/branch-cleanup/run",
+    tag = "project_branch_cleanup",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = RunBranchCleanupRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<BranchCleanupReport>))
+)]
+pub async fn run_branch_cleanup(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RunBranchCleanupRequest>,
+) -> Result<ResponseJson<ApiResponse<BranchCleanupReport>>, ApiError> {
+    let policy = BranchCleanupPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    let mode = payload.mode.unwrap_or(policy.mode);
+    if mode == BranchCleanupMode::Off && !payload.dry_run {
+        return Err(ApiError::BadRequest(
+            "Branch cleanup is off for this project; choose a mode or run a dry run".to_string(),
+        ));
+    }
+
+    let janitor = BranchJanitor::new(deployment.db().clone(), deployment.git().clone());
+    let report = janitor
+        .run(
+            project.id,
+            mode,
+            payload.min_age_days.unwrap_or(policy.min_age_days),
+            payload.dry_run,
+        )
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_branch_cleanup = Router::new()
+        .route(
+            "/branch-cleanup",
+            get(get_branch_cleanup_policy).put(update_branch_cleanup_policy),
+        )
+        .route("/branch-cleanup/run", post(run_branch_cleanup))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_branch_cleanup)
+}
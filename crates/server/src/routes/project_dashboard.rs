@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use axum::{
+    Extension, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use chrono::{Duration, Utc};
+use db::models::{
+    project::Project,
+    project_dashboard::{OpenTaskCounts, ProjectDashboard},
+};
+use deployment::Deployment;
+use futures_util::future::join_all;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::{path::dir_size_async, response::ApiResponse};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Days covered by the rate and duration indicators
+const WINDOW_DAYS: i64 = 7;
+
+/// At-a-glance health of a project
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct ProjectDashboardResponse {
+    pub open_tasks: OpenTaskCounts,
+    /// Runs whose review attention check found nothing to look at, over the
+    /// last `window_days`
+    pub autopilot_reviewed: i64,
+    pub autopilot_passed: i64,
+    /// `autopilot_passed / autopilot_reviewed`; None when nothing was reviewed
+    pub autopilot_success_rate: Option<f64>,
+    /// Mean time from task creation to its first merge, over tasks merged in
+    /// the last `window_days`
+    pub mean_seconds_to_merge: Option<f64>,
+    /// Open tasks whose latest setup or cleanup script failed
+    pub failing_validations: i64,
+    pub pending_approvals: i64,
+    /// Bytes used by the project's workspace worktrees
+    pub disk_usage_bytes: u64,
+    pub window_days: i64,
+}
+
+/// GET /api/projects/:id/dashboard
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/dashboard",
+    tag = "project_dashboard",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectDashboardResponse>))
+)]
+pub async fn get_project_dashboard(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectDashboardResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let since = Utc::now() - Duration::days(WINDOW_DAYS);
+
+    let open_tasks = ProjectDashboard::open_task_counts(pool, project.id).await?;
+    let autopilot = ProjectDashboard::autopilot_outcomes(pool, project.id, since).await?;
+    let mean_seconds_to_merge =
+        ProjectDashboard::mean_seconds_to_merge(pool, project.id, since).await?;
+    let failing_validations = ProjectDashboard::count_failing_validations(pool, project.id).await?;
+
+    let mut pending_approvals = 0;
+    for execution_process_id in deployment.approvals().pending_execution_process_ids() {
+        if ProjectDashboard::execution_in_project(pool, execution_process_id, project.id).await? {
+            pending_approvals += 1;
+        }
+    }
+
+    let worktrees = ProjectDashboard::worktree_paths(pool, project.id).await?;
+    let disk_usage_bytes = join_all(worktrees.iter().map(|path| dir_size_async(Path::new(path))))
+        .await
+        .into_iter()
+        .sum();
+
+    let autopilot_success_rate =
+        (autopilot.reviewed > 0).then(|| autopilot.passed as f64 / autopilot.reviewed as f64);
+
+    Ok(ResponseJson(ApiResponse::success(
+        ProjectDashboardResponse {
+            open_tasks,
+            autopilot_reviewed: autopilot.reviewed,
+            autopilot_passed: autopilot.passed,
+            autopilot_success_rate,
+            mean_seconds_to_merge,
+            failing_validations,
+            pending_approvals,
+            disk_usage_bytes,
+            window_days: WINDOW_DAYS,
+        },
+    )))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let dashboard = Router::new()
+        .route("/dashboard", get(get_project_dashboard))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", dashboard)
+}
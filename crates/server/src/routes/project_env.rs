@@ -0,0 +1,232 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_env_file::{ProjectEnvFile, UpsertProjectEnvFile},
+    project_secret::{ProjectSecret, UpsertProjectSecret},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::{env_file, project_secrets};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct ProjectEnvFileResponse {
+    pub env_file: Option<ProjectEnvFile>,
+    /// Secrets referenced by the template that are not defined yet
+    pub missing_secrets: Vec<String>,
+}
+
+async fn env_file_response(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+) -> Result<ProjectEnvFileResponse, ApiError> {
+    let pool = &deployment.db().pool;
+    let env_file = ProjectEnvFile::find_by_project_id(pool, project_id).await?;
+    let missing_secrets = match &env_file {
+        Some(env_file) => {
+            let secrets = project_secrets::values(pool, deployment.secrets(), project_id).await?;
+            env_file::secret_refs(&env_file.template)
+                .into_iter()
+                .filter(|name| !secrets.contains_key(name))
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(ProjectEnvFileResponse {
+        env_file,
+        missing_secrets,
+    })
+}
+
+/// Re-render managed env files in all live workspaces of the project.
+async fn rotate_env_files(deployment: &DeploymentImpl, project_id: Uuid) {
+    match env_file::sync_project_env_files(&deployment.db().pool, deployment.secrets(), project_id)
+        .await
+    {
+        Ok(count) => tracing::debug!(
+            "Synced managed env files in {} worktrees for project {}",
+            count,
+            project_id
+        ),
+        Err(e) => tracing::warn!(
+            "Failed to sync managed env files for project {}: {}",
+            project_id,
+            e
+        ),
+    }
+}
+
+/// GET /api/projects/:id/env-file
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/env-file",
+    tag = "project_env",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectEnvFileResponse>))
+)]
+pub async fn get_env_file(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvFileResponse>>, ApiError> {
+    let response = env_file_response(&deployment, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// PUT /api/projects/:id/env-file - Save the template and re-render it in live workspaces
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/env-file",
+    tag = "project_env",
+    params(("project_id" = Uuid, Path)),
+    request_body = UpsertProjectEnvFile,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectEnvFileResponse>))
+)]
+pub async fn update_env_file(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectEnvFile>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvFileResponse>>, ApiError> {
+    ProjectEnvFile::upsert(&deployment.db().pool, project.id, &payload.template).await?;
+    rotate_env_files(&deployment, project.id).await;
+
+    let response = env_file_response(&deployment, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// DELETE /api/projects/:id/env-file - Remove the template and its managed files
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/env-file",
+    tag = "project_env",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_env_file(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectEnvFile::delete(&deployment.db().pool, project.id).await?;
+    rotate_env_files(&deployment, project.id).await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// GET /api/projects/:id/secrets - List secret names (values are never returned)
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/secrets",
+    tag = "project_env",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ProjectSecret>>))
+)]
+pub async fn get_secrets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectSecret>>>, ApiError> {
+    let secrets = ProjectSecret::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(secrets)))
+}
+
+/// PUT /api/projects/:project_id/secrets/:name - Create or rotate a secret
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/secrets/{name}",
+    tag = "project_env",
+    params(
+        ("project_id" = Uuid, Path),
+        ("name" = String, Path)
+    ),
+    request_body = UpsertProjectSecret,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ProjectSecret>),
+        (status = 404, description = "Project not found")
+    )
+)]
+pub async fn upsert_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, name)): Path<(Uuid, String)>,
+    Json(payload): Json<UpsertProjectSecret>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecret>>, ApiError> {
+    if !ProjectSecret::is_valid_name(&name) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid secret name '{name}': use letters, digits and underscores"
+        )));
+    }
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Project not found".to_string()))?;
+
+    let secret_ref = deployment
+        .secrets()
+        .set(
+            &project_secrets::entry_name(project_id, &name),
+            &payload.value,
+        )
+        .await?;
+    let secret =
+        ProjectSecret::upsert(&deployment.db().pool, project_id, &name, secret_ref.name()).await?;
+    rotate_env_files(&deployment, project_id).await;
+
+    Ok(ResponseJson(ApiResponse::success(secret)))
+}
+
+/// DELETE /api/projects/:project_id/secrets/:name
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/secrets/{name}",
+    tag = "project_env",
+    params(
+        ("project_id" = Uuid, Path),
+        ("name" = String, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, name)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectSecret::delete(&deployment.db().pool, project_id, &name).await?;
+    if rows == 0 {
+        return Err(ApiError::BadRequest(format!("Secret '{name}' not found")));
+    }
+    deployment
+        .secrets()
+        .delete(&project_secrets::entry_name(project_id, &name))
+        .await?;
+    rotate_env_files(&deployment, project_id).await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_env = Router::new()
+        .route(
+            "/env-file",
+            get(get_env_file)
+                .put(update_env_file)
+                .delete(delete_env_file),
+        )
+        .route("/secrets", get(get_secrets))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .nest("/projects/{project_id}", project_env)
+        .route(
+            "/projects/{project_id}/secrets/{name}",
+            put(upsert_secret).delete(delete_secret),
+        )
+}
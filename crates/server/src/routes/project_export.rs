@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Json, Router,
+    body::Body,
+    extract::{DefaultBodyLimit, State},
+    http::{StatusCode, header},
+    middleware::from_fn_with_state,
+    response::{Json as ResponseJson, Response},
+    routing::{get, post},
+};
+use db::models::{operation::OperationKind, project::Project};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::project_export::{self, ImportedProject, ProjectExport};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_project_middleware},
+};
+
+/// Largest archive that can be imported
+pub const MAX_ARCHIVE_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ImportProjectRequest {
+    /// Name of the new project; defaults to the exported project's name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Path on this machine of each exported repository, by repository name;
+    /// repositories not listed are looked up at their exported path
+    #[serde(default)]
+    pub repo_paths: HashMap<String, String>,
+    /// The file downloaded from the export endpoint
+    #[ts(type = "unknown")]
+    #[schema(value_type = Object)]
+    pub archive: ProjectExport,
+}
+
+/// GET /api/projects/:id/export - The project's settings, repositories, task
+/// groups, tasks, attempt history and dependencies as a JSON file that can be
+/// imported on another instance. Worktrees are not included.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/export",
+    tag = "projects",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Project archive", content_type = "application/json"))
+)]
+pub async fn export_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    let archive = project_export::export_project(&deployment.db().pool, &project).await?;
+    let body = serde_json::to_vec_pretty(&archive)
+        .map_err(|e| ApiError::Internal(format!("Failed to serialize archive: {e}")))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_exported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "task_count": archive.tasks.len(),
+            }),
+        )
+        .await;
+
+    let filename = format!(
+        "{}.vibe-kanban.json",
+        project.name.replace(['/', '\\', '"'], "-")
+    );
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(Body::from(body))
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+/// POST /api/projects/import - Create a project from an exported archive,
+/// with new IDs for the project, its task groups and its tasks. Attempts are
+/// not recreated; their history is added to the notes of their task.
+#[utoipa::path(
+    post,
+    path = "/api/projects/import",
+    tag = "projects",
+    request_body = ImportProjectRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ImportedProject>),
+        (status = 400, description = "A repository is not on disk, or the archive is from a newer version")
+    )
+)]
+pub async fn import_project(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportProjectRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportedProject>>, ApiError> {
+    let name = payload
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let imported = deployment
+        .operations()
+        .track_for_user(
+            OperationKind::Import,
+            None,
+            None,
+            current_user.id(),
+            project_export::import_project(
+                &deployment.db().pool,
+                deployment.project(),
+                deployment.repo(),
+                &payload.archive,
+                name,
+                &payload.repo_paths,
+            ),
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_created",
+            serde_json::json!({
+                "project_id": imported.project.id.to_string(),
+                "repository_count": payload.archive.repos.len(),
+                "trigger": "import",
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(imported)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_export =
+        Router::new()
+            .route("/export", get(export_project))
+            .layer(from_fn_with_state(
+                deployment.clone(),
+                load_project_middleware,
+            ));
+
+    Router::new()
+        .route(
+            "/projects/import",
+            post(import_project).layer(DefaultBodyLimit::max(MAX_ARCHIVE_SIZE)),
+        )
+        .nest("/projects/{project_id}", project_export)
+}
@@ -0,0 +1,69 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    project::Project,
+    project_file_rule::{CreateFileRule, ProjectFileRule},
+};
+use deployment::Deployment;
+use services::services::file_rules;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// GET /api/projects/:id/file-rules - Files agents may or may not change
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/file-rules",
+    tag = "project_file_rules",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectFileRule>>),
+    )
+)]
+pub async fn get_file_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectFileRule>>>, ApiError> {
+    let rules = ProjectFileRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+/// PUT /api/projects/:id/file-rules - Replace the project's file rules
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/file-rules",
+    tag = "project_file_rules",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = Vec<CreateFileRule>,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectFileRule>>),
+    )
+)]
+pub async fn update_file_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<Vec<CreateFileRule>>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectFileRule>>>, ApiError> {
+    for rule in &payload {
+        file_rules::validate_pattern(&rule.pattern).map_err(|e| {
+            ApiError::BadRequest(format!("Invalid pattern '{}': {e}", rule.pattern))
+        })?;
+    }
+
+    let rules =
+        ProjectFileRule::replace_for_project(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_file_rules = Router::new()
+        .route("/file-rules", get(get_file_rules).put(update_file_rules))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_file_rules)
+}
@@ -0,0 +1,99 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    git_hooks_policy::{GitHooksPolicy, UpdateGitHooksPolicy},
+    project::Project,
+    project_repo::ProjectRepo,
+};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::pre_commit::PreCommitRunner;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct PreCommitStatus {
+    /// Whether the `pre-commit` executable was found
+    pub installed: bool,
+    /// Repos of the project with a `.pre-commit-config.yaml`
+    pub configured_repos: Vec<String>,
+}
+
+/// GET /api/projects/:id/git-hooks - How the project treats repo-local git hooks
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/git-hooks",
+    tag = "project_git_hooks",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<GitHooksPolicy>))
+)]
+pub async fn get_git_hooks_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<GitHooksPolicy>>, ApiError> {
+    let policy = GitHooksPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// PUT /api/projects/:id/git-hooks - Save the project's git hooks policy
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/git-hooks",
+    tag = "project_git_hooks",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = UpdateGitHooksPolicy,
+    responses((status = 200, description = "Success", body = ApiResponse<GitHooksPolicy>))
+)]
+pub async fn update_git_hooks_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateGitHooksPolicy>,
+) -> Result<ResponseJson<ApiResponse<GitHooksPolicy>>, ApiError> {
+    let policy = GitHooksPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// GET /api/projects/:id/pre-commit - Whether pre-commit validation can run for the project
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/pre-commit",
+    tag = "project_git_hooks",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<PreCommitStatus>))
+)]
+pub async fn get_pre_commit_status(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<PreCommitStatus>>, ApiError> {
+    let repos = ProjectRepo::find_repos_for_project(&deployment.db().pool, project.id).await?;
+    let configured_repos = repos
+        .into_iter()
+        .filter(|repo| PreCommitRunner::is_configured(&repo.path))
+        .map(|repo| repo.name)
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(PreCommitStatus {
+        installed: PreCommitRunner::detect().await.is_some(),
+        configured_repos,
+    })))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_git_hooks = Router::new()
+        .route(
+            "/git-hooks",
+            get(get_git_hooks_policy).put(update_git_hooks_policy),
+        )
+        .route("/pre-commit", get(get_pre_commit_status))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_git_hooks)
+}
@@ -0,0 +1,183 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_memory::{
+        CreateProjectMemory, ProjectMemory, ProjectMemorySettings, UpdateProjectMemory,
+        UpdateProjectMemorySettings,
+    },
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Most memories that can be added to one prompt
+const MAX_INJECTED_LIMIT: i64 = 20;
+
+fn validate_content(content: &str) -> Result<String, ApiError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Memory content cannot be empty".to_string(),
+        ));
+    }
+    Ok(content.to_string())
+}
+
+/// The memory, if it belongs to the project
+async fn find_project_memory(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    memory_id: Uuid,
+) -> Result<ProjectMemory, ApiError> {
+    ProjectMemory::find_by_id(&deployment.db().pool, memory_id)
+        .await?
+        .filter(|memory| memory.project_id == project_id)
+        .ok_or_else(|| ApiError::NotFound(format!("Memory {memory_id} not found")))
+}
+
+/// GET /api/projects/:id/memory/settings - Whether the project collects memories
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/memory/settings",
+    tag = "project_memories",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectMemorySettings>))
+)]
+pub async fn get_memory_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectMemorySettings>>, ApiError> {
+    let settings =
+        ProjectMemorySettings::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// PUT /api/projects/:id/memory/settings - Opt the project in or out of memory
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/memory/settings",
+    tag = "project_memories",
+    params(("project_id" = Uuid, Path)),
+    request_body = UpdateProjectMemorySettings,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectMemorySettings>))
+)]
+pub async fn update_memory_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateProjectMemorySettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectMemorySettings>>, ApiError> {
+    if !(0..=MAX_INJECTED_LIMIT).contains(&payload.max_injected) {
+        return Err(ApiError::BadRequest(format!(
+            "max_injected must be between 0 and {MAX_INJECTED_LIMIT}"
+        )));
+    }
+    let settings =
+        ProjectMemorySettings::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// GET /api/projects/:id/memories - Everything the project remembers, newest first
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/memories",
+    tag = "project_memories",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ProjectMemory>>))
+)]
+pub async fn get_memories(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectMemory>>>, ApiError> {
+    let memories = ProjectMemory::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(memories)))
+}
+
+/// POST /api/projects/:id/memories - Write a memory by hand
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/memories",
+    tag = "project_memories",
+    params(("project_id" = Uuid, Path)),
+    request_body = CreateProjectMemory,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectMemory>))
+)]
+pub async fn create_memory(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateProjectMemory>,
+) -> Result<ResponseJson<ApiResponse<ProjectMemory>>, ApiError> {
+    payload.content = validate_content(&payload.content)?;
+    let memory = ProjectMemory::create(&deployment.db().pool, project.id, &payload, None).await?;
+    Ok(ResponseJson(ApiResponse::success(memory)))
+}
+
+/// PUT /api/projects/:project_id/memories/:memory_id
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/memories/{memory_id}",
+    tag = "project_memories",
+    params(
+        ("project_id" = Uuid, Path),
+        ("memory_id" = Uuid, Path)
+    ),
+    request_body = UpdateProjectMemory,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectMemory>))
+)]
+pub async fn update_memory(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, memory_id)): Path<(Uuid, Uuid)>,
+    Json(mut payload): Json<UpdateProjectMemory>,
+) -> Result<ResponseJson<ApiResponse<ProjectMemory>>, ApiError> {
+    payload.content = validate_content(&payload.content)?;
+    let memory = find_project_memory(&deployment, project_id, memory_id).await?;
+    let updated = ProjectMemory::update(&deployment.db().pool, memory.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /api/projects/:project_id/memories/:memory_id
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/memories/{memory_id}",
+    tag = "project_memories",
+    params(
+        ("project_id" = Uuid, Path),
+        ("memory_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_memory(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, memory_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let memory = find_project_memory(&deployment, project_id, memory_id).await?;
+    ProjectMemory::delete(&deployment.db().pool, memory.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_memories = Router::new()
+        .route(
+            "/memory/settings",
+            get(get_memory_settings).put(update_memory_settings),
+        )
+        .route("/memories", get(get_memories).post(create_memory))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .nest("/projects/{project_id}", project_memories)
+        .route(
+            "/projects/{project_id}/memories/{memory_id}",
+            put(update_memory).delete(delete_memory),
+        )
+}
@@ -0,0 +1,65 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    merge_check_policy::{MergeCheckPolicy, UpdateMergeCheckPolicy},
+    project::Project,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// GET /api/projects/:id/merge-checks - Checks the merge queue runs before merging
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/merge-checks",
+    tag = "project_merge_checks",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<MergeCheckPolicy>))
+)]
+pub async fn get_merge_check_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<MergeCheckPolicy>>, ApiError> {
+    let policy = MergeCheckPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// PUT /api/projects/:id/merge-checks - Save the project's merge queue checks
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/merge-checks",
+    tag = "project_merge_checks",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = UpdateMergeCheckPolicy,
+    responses((status = 200, description = "Success", body = ApiResponse<MergeCheckPolicy>))
+)]
+pub async fn update_merge_check_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateMergeCheckPolicy>,
+) -> Result<ResponseJson<ApiResponse<MergeCheckPolicy>>, ApiError> {
+    if payload.timeout_secs.is_some_and(|secs| secs <= 0) {
+        return Err(ApiError::BadRequest(
+            "timeout_secs must be positive".to_string(),
+        ));
+    }
+    let policy = MergeCheckPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_merge_checks = Router::new()
+        .route(
+            "/merge-checks",
+            get(get_merge_check_policy).put(update_merge_check_policy),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_merge_checks)
+}
@@ -0,0 +1,60 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    pr_draft_policy::{PrDraftPolicy, UpdatePrDraftPolicy},
+    project::Project,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// GET /api/projects/:id/pr-drafts - Whether the project opens draft PRs and marks them ready
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/pr-drafts",
+    tag = "project_pr_drafts",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<PrDraftPolicy>))
+)]
+pub async fn get_pr_draft_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<PrDraftPolicy>>, ApiError> {
+    let policy = PrDraftPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// PUT /api/projects/:id/pr-drafts - Save the project's draft PR policy
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/pr-drafts",
+    tag = "project_pr_drafts",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = UpdatePrDraftPolicy,
+    responses((status = 200, description = "Success", body = ApiResponse<PrDraftPolicy>))
+)]
+pub async fn update_pr_draft_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdatePrDraftPolicy>,
+) -> Result<ResponseJson<ApiResponse<PrDraftPolicy>>, ApiError> {
+    let policy = PrDraftPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_pr_drafts = Router::new()
+        .route(
+            "/pr-drafts",
+            get(get_pr_draft_policy).put(update_pr_draft_policy),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_pr_drafts)
+}
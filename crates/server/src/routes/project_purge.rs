@@ -0,0 +1,219 @@
+use std::path::PathBuf;
+
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::delete,
+};
+use db::models::{
+    audit_log::{AuditAction, AuditLogEntry},
+    project::Project,
+    project_purge::{self, ProjectPurgeCounts},
+    project_repo::ProjectRepo,
+    repo::Repo,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    backup::find_single_project_backups, project_secrets, webhook_secrets,
+    workspace_manager::WorkspaceManager,
+};
+use ts_rs::TS;
+use utils::{assets::backup_dir, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_project_middleware},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PurgeProjectQuery {
+    /// Report what would be removed without removing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ProjectPurgeReport {
+    pub counts: ProjectPurgeCounts,
+    /// Workspace directories on disk whose worktrees are removed
+    #[ts(type = "number")]
+    pub workspace_dirs: u64,
+    /// File names of local backups that hold this project and nothing else
+    pub backups: Vec<String>,
+    pub dry_run: bool,
+    /// The audit log entry recording the purge; None for dry runs
+    pub audit_log_id: Option<Uuid>,
+}
+
+/// DELETE /api/projects/:id/purge - Delete the project with everything derived
+/// from it: tasks, attempts, executions and their logs, conversations,
+/// embeddings, images, worktrees and backups holding only this project
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/purge",
+    tag = "project_purge",
+    params(("project_id" = uuid::Uuid, Path), PurgeProjectQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ProjectPurgeReport>),
+        (status = 403, description = "Not an admin")
+    )
+)]
+pub async fn purge_project(
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PurgeProjectQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectPurgeReport>>, ApiError> {
+    current_user.require_admin_if_accounts(&deployment).await?;
+    let pool = &deployment.db().pool;
+    if project_purge::count_running_processes(pool, project.id).await? > 0 {
+        return Err(ApiError::Conflict(
+            "Project has running execution processes. Stop them before purging it.".to_string(),
+        ));
+    }
+
+    let workspace_dirs: Vec<PathBuf> = Workspace::fetch_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .filter_map(|workspace| workspace.container_ref.map(PathBuf::from))
+        .collect();
+    let backups = find_single_project_backups(&backup_dir(), project.id).await?;
+    let backup_names: Vec<String> = backups
+        .iter()
+        .filter_map(|backup| backup.path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect();
+
+    if query.dry_run {
+        let mut conn = pool.acquire().await?;
+        let counts = project_purge::count(&mut conn, project.id).await?;
+        return Ok(ResponseJson(ApiResponse::success(ProjectPurgeReport {
+            counts,
+            workspace_dirs: workspace_dirs.len() as u64,
+            backups: backup_names,
+            dry_run: true,
+            audit_log_id: None,
+        })));
+    }
+
+    let repositories = ProjectRepo::find_repos_for_project(pool, project.id).await?;
+
+    let mut tx = pool.begin().await?;
+    let purged = project_purge::purge(&mut tx, project.id).await?;
+    // The project name may be what the purge is meant to remove, so only
+    // its id is kept
+    let details = serde_json::json!({
+        "project_id": project.id,
+        "counts": purged.counts,
+        "workspace_dirs": workspace_dirs.len(),
+        "backups": backup_names,
+    });
+    let summary = format!(
+        "Purged a project with {} task(s) and {} conversation(s)",
+        purged.counts.tasks, purged.counts.conversations
+    );
+    let entry = AuditLogEntry::create(
+        &mut *tx,
+        AuditAction::ProjectPurge,
+        current_user.id(),
+        &summary,
+        &details,
+    )
+    .await?;
+    tx.commit().await?;
+
+    for image in &purged.images {
+        let path = deployment.image().get_absolute_path(image);
+        if let Err(e) = tokio::fs::remove_file(&path).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove image file {}: {}", path.display(), e);
+        }
+    }
+    if let Err(e) = project_secrets::remove_orphans(pool, deployment.secrets()).await {
+        tracing::warn!("Failed to remove secrets of the purged project: {}", e);
+    }
+    if let Err(e) = webhook_secrets::remove_orphans(pool, deployment.secrets()).await {
+        tracing::warn!(
+            "Failed to remove webhook secrets of the purged project: {}",
+            e
+        );
+    }
+    for backup in &backups {
+        if let Err(e) = tokio::fs::remove_file(&backup.path).await {
+            tracing::warn!("Failed to remove backup {}: {}", backup.path.display(), e);
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_purged",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "task_count": purged.counts.tasks,
+                "backup_count": backups.len(),
+            }),
+        )
+        .await;
+
+    let project_id = project.id;
+    let workspace_count = workspace_dirs.len() as u64;
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        for workspace_dir in &workspace_dirs {
+            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
+            {
+                tracing::error!(
+                    "Background workspace cleanup failed for purged project {} at {}: {}",
+                    project_id,
+                    workspace_dir.display(),
+                    e
+                );
+            }
+        }
+
+        match Repo::delete_orphaned(&pool).await {
+            Ok(count) if count > 0 => {
+                tracing::info!("Deleted {} orphaned repo records", count);
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete orphaned repos: {}", e);
+            }
+            _ => {}
+        }
+    });
+
+    tracing::info!(
+        user_id = ?current_user.id(),
+        %project_id,
+        "Purged project and all data derived from it"
+    );
+    Ok(ResponseJson(ApiResponse::success(ProjectPurgeReport {
+        counts: purged.counts,
+        workspace_dirs: workspace_count,
+        backups: backup_names,
+        dry_run: false,
+        audit_log_id: Some(entry.id),
+    })))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_purge =
+        Router::new()
+            .route("/purge", delete(purge_project))
+            .layer(from_fn_with_state(
+                deployment.clone(),
+                load_project_middleware,
+            ));
+
+    Router::new().nest("/projects/{project_id}", project_purge)
+}
@@ -0,0 +1,90 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{project::Project, redaction_patterns::ProjectRedactionPatterns};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::output_redaction::compile_pattern;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Every pattern runs over every line of output
+const MAX_PATTERNS: usize = 50;
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateRedactionPatterns {
+    /// Regexes; a named group `secret` masks only that part of a match
+    pub patterns: Vec<String>,
+}
+
+/// GET /api/projects/:id/redaction-patterns - Regexes masked in the project's execution output
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/redaction-patterns",
+    tag = "project_redaction",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectRedactionPatterns>))
+)]
+pub async fn get_redaction_patterns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectRedactionPatterns>>, ApiError> {
+    let patterns =
+        ProjectRedactionPatterns::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(patterns)))
+}
+
+/// PUT /api/projects/:id/redaction-patterns - Replace the project's redaction patterns;
+/// they apply to executions started afterwards
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/redaction-patterns",
+    tag = "project_redaction",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = UpdateRedactionPatterns,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectRedactionPatterns>))
+)]
+pub async fn update_redaction_patterns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateRedactionPatterns>,
+) -> Result<ResponseJson<ApiResponse<ProjectRedactionPatterns>>, ApiError> {
+    let patterns: Vec<String> = payload
+        .patterns
+        .into_iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+    if patterns.len() > MAX_PATTERNS {
+        return Err(ApiError::BadRequest(format!(
+            "A project can have at most {MAX_PATTERNS} redaction patterns"
+        )));
+    }
+    for pattern in &patterns {
+        compile_pattern(pattern).map_err(|e| {
+            ApiError::BadRequest(format!("Invalid redaction pattern `{pattern}`: {e}"))
+        })?;
+    }
+
+    let patterns =
+        ProjectRedactionPatterns::upsert(&deployment.db().pool, project.id, &patterns).await?;
+    Ok(ResponseJson(ApiResponse::success(patterns)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_redaction = Router::new()
+        .route(
+            "/redaction-patterns",
+            get(get_redaction_patterns).put(update_redaction_patterns),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_redaction)
+}
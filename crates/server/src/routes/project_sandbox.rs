@@ -0,0 +1,86 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{project::Project, sandbox_policy::ProjectSandboxPolicy};
+use deployment::Deployment;
+use executors::sandbox::{SandboxBackend, SandboxPolicy};
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct SandboxStatus {
+    /// Whether bubblewrap (Linux) or sandbox-exec (macOS) was found;
+    /// sandboxed executions fail to start without it
+    pub available: bool,
+}
+
+/// GET /api/projects/:id/sandbox - The project's sandbox policy
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/sandbox",
+    tag = "project_sandbox",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectSandboxPolicy>))
+)]
+pub async fn get_sandbox_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectSandboxPolicy>>, ApiError> {
+    let policy = ProjectSandboxPolicy::find_or_default(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// PUT /api/projects/:id/sandbox - Save the project's sandbox policy
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/sandbox",
+    tag = "project_sandbox",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = Object,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectSandboxPolicy>))
+)]
+pub async fn update_sandbox_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SandboxPolicy>,
+) -> Result<ResponseJson<ApiResponse<ProjectSandboxPolicy>>, ApiError> {
+    payload
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let policy = ProjectSandboxPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// GET /api/projects/:id/sandbox/status - Whether executions can be sandboxed on this host
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/sandbox/status",
+    tag = "project_sandbox",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<SandboxStatus>))
+)]
+pub async fn get_sandbox_status() -> ResponseJson<ApiResponse<SandboxStatus>> {
+    ResponseJson(ApiResponse::success(SandboxStatus {
+        available: SandboxBackend::detect().await.is_some(),
+    }))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_sandbox = Router::new()
+        .route(
+            "/sandbox",
+            get(get_sandbox_policy).put(update_sandbox_policy),
+        )
+        .route("/sandbox/status", get(get_sandbox_status))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_sandbox)
+}
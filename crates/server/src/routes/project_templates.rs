@@ -0,0 +1,224 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::project_template::{ProjectTemplate, UpsertProjectTemplate};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::project_template::{self, ProjectFromTemplate};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_template_middleware};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListProjectTemplatesQuery {
+    /// Only templates with this label
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateProjectFromTemplate {
+    pub name: String,
+}
+
+/// Trim names and labels and reject templates that could not create a project
+fn normalize(mut payload: UpsertProjectTemplate) -> Result<UpsertProjectTemplate, ApiError> {
+    payload.name = payload.name.trim().to_string();
+    if payload.name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Project template name cannot be empty".to_string(),
+        ));
+    }
+    for repo in &payload.repositories {
+        if repo.display_name.trim().is_empty() || repo.path.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "Template repositories need a name and a path".to_string(),
+            ));
+        }
+    }
+    if payload
+        .tasks
+        .iter()
+        .any(|task| task.title.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest(
+            "Template tasks need a title".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    payload.labels = payload
+        .labels
+        .into_iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty() && seen.insert(label.clone()))
+        .collect();
+    Ok(payload)
+}
+
+/// GET /api/project-templates - All project templates, optionally only those with a label
+#[utoipa::path(
+    get,
+    path = "/api/project-templates",
+    tag = "project_templates",
+    params(ListProjectTemplatesQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ProjectTemplate>>))
+)]
+pub async fn get_project_templates(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ListProjectTemplatesQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectTemplate>>>, ApiError> {
+    let templates =
+        ProjectTemplate::find_all(&deployment.db().pool, query.label.as_deref()).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+/// POST /api/project-templates - Create a project template
+#[utoipa::path(
+    post,
+    path = "/api/project-templates",
+    tag = "project_templates",
+    request_body = UpsertProjectTemplate,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectTemplate>))
+)]
+pub async fn create_project_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectTemplate>>, ApiError> {
+    let payload = normalize(payload)?;
+    let template = ProjectTemplate::create(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// GET /api/project-templates/:id
+#[utoipa::path(
+    get,
+    path = "/api/project-templates/{template_id}",
+    tag = "project_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectTemplate>))
+)]
+pub async fn get_project_template(
+    Extension(template): Extension<ProjectTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectTemplate>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// PUT /api/project-templates/:id - Replace a project template. Projects
+/// already created from it are not changed.
+#[utoipa::path(
+    put,
+    path = "/api/project-templates/{template_id}",
+    tag = "project_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    request_body = UpsertProjectTemplate,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectTemplate>))
+)]
+pub async fn update_project_template(
+    Extension(template): Extension<ProjectTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectTemplate>>, ApiError> {
+    let payload = normalize(payload)?;
+    let updated = ProjectTemplate::update(&deployment.db().pool, template.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /api/project-templates/:id
+#[utoipa::path(
+    delete,
+    path = "/api/project-templates/{template_id}",
+    tag = "project_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_project_template(
+    Extension(template): Extension<ProjectTemplate>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = ProjectTemplate::delete(&deployment.db().pool, template.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// POST /api/project-templates/:id/projects - Create a project from the
+/// template, cloning repositories that are not on disk yet and creating the
+/// template's tasks
+#[utoipa::path(
+    post,
+    path = "/api/project-templates/{template_id}/projects",
+    tag = "project_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    request_body = CreateProjectFromTemplate,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectFromTemplate>))
+)]
+pub async fn create_project_from_template(
+    Extension(template): Extension<ProjectTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectFromTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectFromTemplate>>, ApiError> {
+    let name = payload.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Project name cannot be empty".to_string(),
+        ));
+    }
+
+    let config = deployment.config().read().await.clone();
+    let created = project_template::create_project(
+        &deployment.db().pool,
+        deployment.project(),
+        deployment.repo(),
+        &config,
+        &template,
+        name,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_created",
+            serde_json::json!({
+                "project_id": created.project.id.to_string(),
+                "repository_count": template.repositories.len(),
+                "trigger": "template",
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let template_router = Router::new()
+        .route(
+            "/",
+            get(get_project_template)
+                .put(update_project_template)
+                .delete(delete_project_template),
+        )
+        .route("/projects", post(create_project_from_template))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_template_middleware,
+        ));
+
+    let inner = Router::new()
+        .route(
+            "/",
+            get(get_project_templates).post(create_project_template),
+        )
+        .nest("/{template_id}", template_router);
+
+    Router::new().nest("/project-templates", inner)
+}
@@ -0,0 +1,91 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    project::Project,
+    project_tool_requirement::{CreateToolRequirement, ProjectToolRequirement},
+};
+use deployment::Deployment;
+use services::services::toolchain_doctor::{self, ToolchainReport};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// GET /api/projects/:id/toolchain - Tools the project declares as required
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/toolchain",
+    tag = "project_toolchain",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectToolRequirement>>),
+    )
+)]
+pub async fn get_toolchain(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectToolRequirement>>>, ApiError> {
+    let requirements =
+        ProjectToolRequirement::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(requirements)))
+}
+
+/// PUT /api/projects/:id/toolchain - Replace the project's required tools
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/toolchain",
+    tag = "project_toolchain",
+    params(("project_id" = uuid::Uuid, Path)),
+    request_body = Vec<CreateToolRequirement>,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectToolRequirement>>),
+    )
+)]
+pub async fn update_toolchain(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<Vec<CreateToolRequirement>>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectToolRequirement>>>, ApiError> {
+    if payload.iter().any(|r| r.name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Tool name cannot be empty".to_string(),
+        ));
+    }
+
+    let requirements =
+        ProjectToolRequirement::replace_for_project(&deployment.db().pool, project.id, &payload)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(requirements)))
+}
+
+/// POST /api/projects/:id/toolchain/doctor - Check every required tool in a throwaway worktree
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/toolchain/doctor",
+    tag = "project_toolchain",
+    params(("project_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ToolchainReport>))
+)]
+pub async fn run_toolchain_doctor(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ToolchainReport>>, ApiError> {
+    let report = toolchain_doctor::run_doctor(&deployment.db().pool, &project).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_toolchain = Router::new()
+        .route("/toolchain", get(get_toolchain).put(update_toolchain))
+        .route("/toolchain/doctor", post(run_toolchain_doctor))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", project_toolchain)
+}
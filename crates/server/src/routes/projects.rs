@@ -3,16 +3,15 @@ use std::path::PathBuf;
 use anyhow;
 use axum::{
     Extension, Json, Router,
-    extract::{
-        Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{Path, Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use db::models::{
+    execution_process::ExecutionProcess,
+    execution_queue::{ExecutionQueue, ProjectQueueEntry},
     project::{
         CreateProject, Project, ProjectError, ProjectWithTaskCounts, SearchResult, UpdateProject,
     },
@@ -24,9 +23,12 @@ use db::models::{
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    container::ContainerService,
+    copy_files::CopyFilePreview,
     file_search_cache::SearchQuery,
     github::{GitHubService, GitHubServiceError, UnifiedPrComment},
     github_client::GitHubClient,
+    merge_queue_store::{CancelRefusal, MergeQueueRun, QueuedMerge},
     pr_cache::{PrWithComments, ProjectPrsResponse, RepoPrs},
     project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
@@ -36,33 +38,39 @@ use utils::{
     api::projects::{RemoteProject, RemoteProjectMembersResponse},
     response::ApiResponse,
 };
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_project_middleware,
-    routes::{settings::get_github_token, ws_helpers::forward_stream_to_ws},
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_project_middleware},
+    routes::{
+        settings::get_github_token,
+        stream_transport::{LogMsgStream, StreamTransport},
+    },
 };
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, ToSchema)]
 pub struct LinkToExistingRequest {
     pub remote_project_id: Uuid,
 }
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, ToSchema)]
 pub struct CreateRemoteProjectRequest {
     pub organization_id: Uuid,
     pub name: String,
 }
 
 /// A task group summary for matching against worktrees
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct MatchingTaskGroup {
     pub id: Uuid,
     pub name: String,
 }
 
 /// Worktree info with matching task groups
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct WorktreeInfo {
     pub path: String,
     pub branch: Option<String>,
@@ -71,40 +79,80 @@ pub struct WorktreeInfo {
 }
 
 /// Response for GET /api/projects/:id/worktrees
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct ProjectWorktreesResponse {
     pub worktrees: Vec<WorktreeInfo>,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListProjectsQuery {
+    /// Also list archived projects
+    pub include_archived: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    params(ListProjectsQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ProjectWithTaskCounts>>),
+    )
+)]
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ListProjectsQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<ProjectWithTaskCounts>>>, ApiError> {
-    let projects = Project::find_all(&deployment.db().pool).await?;
+    let projects = Project::find_all(
+        &deployment.db().pool,
+        query.include_archived.unwrap_or(false),
+    )
+    .await?;
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/stream/ws",
+    tag = "projects",
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_projects_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_projects_ws(socket, deployment).await {
-            tracing::warn!("projects WS closed: {}", e);
-        }
-    })
+    transport.log_msgs("projects", projects_stream(deployment))
 }
 
-async fn handle_projects_ws(socket: WebSocket, deployment: DeploymentImpl) -> anyhow::Result<()> {
-    let stream = deployment.events().stream_projects_raw().await?;
-    forward_stream_to_ws(socket, stream).await
+async fn projects_stream(deployment: DeploymentImpl) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment.events().stream_projects_raw().await?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn get_project(
     Extension(project): Extension<Project>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/link",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = LinkToExistingRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn link_project_to_existing_remote(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -119,6 +167,14 @@ pub async fn link_project_to_existing_remote(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/link/create",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = CreateRemoteProjectRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn create_and_link_remote_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -146,6 +202,13 @@ pub async fn create_and_link_remote_project(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/link",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn unlink_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -158,6 +221,13 @@ pub async fn unlink_project(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/remote-projects/{remote_project_id}",
+    tag = "projects",
+    params(("remote_project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<RemoteProject>))
+)]
 pub async fn get_remote_project_by_id(
     State(deployment): State<DeploymentImpl>,
     Path(remote_project_id): Path<Uuid>,
@@ -169,6 +239,15 @@ pub async fn get_remote_project_by_id(
     Ok(ResponseJson(ApiResponse::success(remote_project)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/remote/members",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RemoteProjectMembersResponse>),
+    )
+)]
 pub async fn get_project_remote_members(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -221,6 +300,13 @@ async fn apply_remote_project_link(
     Ok(updated_project)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProject,
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -267,6 +353,14 @@ pub async fn create_project(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = UpdateProject,
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
 pub async fn update_project(
     Extension(existing_project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -277,7 +371,15 @@ pub async fn update_project(
         .update_project(&deployment.db().pool, &existing_project, payload)
         .await
     {
-        Ok(project) => Ok(ResponseJson(ApiResponse::success(project))),
+        Ok(project) => {
+            // A raised or removed concurrency limit may free slots for queued executions
+            if project.max_concurrent_executions != existing_project.max_concurrent_executions
+                && let Err(e) = deployment.container().process_queue().await
+            {
+                tracing::error!("Failed to process execution queue: {}", e);
+            }
+            Ok(ResponseJson(ApiResponse::success(project)))
+        }
         Err(e) => {
             tracing::error!("Failed to update project: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -285,51 +387,117 @@ pub async fn update_project(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<()>),
+        (status = 409, description = "The project has running execution processes")
+    )
+)]
 pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
-    match deployment
-        .project()
-        .delete_project(&deployment.db().pool, project.id)
-        .await
-    {
-        Ok(rows_affected) => {
-            if rows_affected == 0 {
-                Err(StatusCode::NOT_FOUND)
-            } else {
-                deployment
-                    .track_if_analytics_allowed(
-                        "project_deleted",
-                        serde_json::json!({
-                            "project_id": project.id.to_string(),
-                        }),
-                    )
-                    .await;
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
 
-                Ok(ResponseJson(ApiResponse::success(())))
-            }
-        }
-        Err(e) => {
-            tracing::error!("Failed to delete project: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+    // Validate no running execution processes
+    if ExecutionProcess::has_running_processes_for_project(pool, project.id).await? {
+        return Err(ApiError::Conflict("Project has running execution processes. Please wait for them to complete or stop them first.".to_string()));
+    }
+
+    // Moved to the trash; it is deleted for good once the retention expires
+    let rows_affected = Project::trash(pool, project.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::NotFound("Project not found".to_string()));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_deleted",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /api/projects/:id/archive - Archive the project. Its tasks count as
+/// archived with it, so background workers leave them alone.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/archive",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
+pub async fn archive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::archive(pool, project.id).await? > 0 {
+        deployment
+            .track_if_analytics_allowed(
+                "project_archived",
+                serde_json::json!({
+                    "project_id": project.id.to_string(),
+                }),
+            )
+            .await;
     }
+    let project = Project::find_by_id(pool, project.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// POST /api/projects/:id/unarchive
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/unarchive",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
+pub async fn unarchive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::unarchive(pool, project.id).await?;
+    let project = Project::find_by_id(pool, project.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(project)))
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, ToSchema)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
     git_repo_path: Option<PathBuf>,
 }
 
-#[derive(Debug, serde::Serialize, ts_rs::TS)]
+#[derive(Debug, serde::Serialize, ts_rs::TS, ToSchema)]
 pub struct OpenEditorResponse {
     pub url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/open-editor",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = Option<OpenEditorRequest>,
+    responses((status = 200, description = "Success", body = ApiResponse<OpenEditorResponse>))
+)]
 pub async fn open_project_in_editor(
     Extension(project): Extension<Project>,
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<Option<OpenEditorRequest>>,
 ) -> Result<ResponseJson<ApiResponse<OpenEditorResponse>>, ApiError> {
@@ -350,7 +518,7 @@ pub async fn open_project_in_editor(
     };
 
     let editor_config = {
-        let config = deployment.config().read().await;
+        let config = current_user.config(&deployment).await;
         let editor_type_str = payload.as_ref().and_then(|req| req.editor_type.as_deref());
         config.editor.with_override(editor_type_str)?
     };
@@ -386,6 +554,16 @@ pub async fn open_project_in_editor(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/search",
+    tag = "projects",
+    params(
+        ("id" = Uuid, Path),
+        SearchQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<SearchResult>>))
+)]
 pub async fn search_project_files(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -426,6 +604,13 @@ pub async fn search_project_files(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/repositories",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Repo>>))
+)]
 pub async fn get_project_repositories(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -437,6 +622,14 @@ pub async fn get_project_repositories(
     Ok(ResponseJson(ApiResponse::success(repositories)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/repositories",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = CreateProjectRepo,
+    responses((status = 200, description = "Success", body = ApiResponse<Repo>))
+)]
 pub async fn add_project_repository(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -521,6 +714,16 @@ pub async fn add_project_repository(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(
+        ("project_id" = Uuid, Path),
+        ("repo_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -561,6 +764,16 @@ pub async fn delete_project_repository(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(
+        ("project_id" = Uuid, Path),
+        ("repo_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectRepo>))
+)]
 pub async fn get_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -574,6 +787,17 @@ pub async fn get_project_repository(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(
+        ("project_id" = Uuid, Path),
+        ("repo_id" = Uuid, Path)
+    ),
+    request_body = UpdateProjectRepo,
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectRepo>))
+)]
 pub async fn update_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -588,9 +812,67 @@ pub async fn update_project_repository(
     }
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CopyFilesPreviewQuery {
+    /// Preview these patterns instead of the saved ones (for unsaved edits)
+    pub copy_files: Option<String>,
+}
+
+/// GET /api/projects/:project_id/repositories/:repo_id/copy-files/preview
+///
+/// Dry run of the copy-files engine: lists what would be copied into a new
+/// worktree, including which entries are rendered as templates.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/repositories/{repo_id}/copy-files/preview",
+    tag = "projects",
+    params(
+        ("project_id" = Uuid, Path),
+        ("repo_id" = Uuid, Path),
+        CopyFilesPreviewQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<CopyFilePreview>>))
+)]
+pub async fn preview_project_repository_copy_files(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<CopyFilesPreviewQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<CopyFilePreview>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project_repo = ProjectRepo::find_by_project_and_repo(pool, project_id, repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found in project".to_string()))?;
+    let repo = Repo::find_by_id(pool, repo_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Repository not found".to_string()))?;
+
+    let copy_files = query
+        .copy_files
+        .or(project_repo.copy_files)
+        .unwrap_or_default();
+    if copy_files.trim().is_empty() {
+        return Ok(ResponseJson(ApiResponse::success(vec![])));
+    }
+
+    let preview = deployment
+        .container()
+        .preview_project_files(&repo.path, None, &copy_files)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(preview)))
+}
+
 /// GET /api/projects/:id/prs - Get open PRs across all repos, filtered by task group base branches.
 ///
 /// Uses server-side caching with 2-minute TTL to reduce GitHub API calls.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/prs",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectPrsResponse>))
+)]
 pub async fn get_project_prs(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -728,6 +1010,13 @@ async fn fetch_project_prs_from_github(
 }
 
 /// POST /api/projects/:id/prs/invalidate - Invalidate the PR cache for this project.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/prs/invalidate",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn invalidate_project_prs_cache(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -738,7 +1027,7 @@ pub async fn invalidate_project_prs_cache(
 }
 
 /// Unresolved count for a single PR, keyed by repo and PR number.
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct PrUnresolvedCount {
     pub repo_id: Uuid,
     pub pr_number: u64,
@@ -746,13 +1035,22 @@ pub struct PrUnresolvedCount {
 }
 
 /// Response for GET /api/projects/:id/prs/unresolved-counts
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct PrUnresolvedCountsResponse {
     pub counts: Vec<PrUnresolvedCount>,
 }
 
 /// GET /api/projects/:id/prs/unresolved-counts - Fetch unresolved comment counts for all PRs.
 /// This endpoint is designed to be called after /prs to progressively load the counts.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/prs/unresolved-counts",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PrUnresolvedCountsResponse>),
+    )
+)]
 pub async fn get_project_prs_unresolved_counts(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -858,12 +1156,21 @@ pub async fn get_project_prs_unresolved_counts(
 }
 
 /// Response for GET /api/projects/:id/merge-queue-count
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct MergeQueueCountResponse {
     pub count: i64,
 }
 
 /// GET /api/projects/:id/merge-queue-count - Get the number of entries in the merge queue
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/merge-queue-count",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<MergeQueueCountResponse>),
+    )
+)]
 pub async fn get_merge_queue_count(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -874,14 +1181,170 @@ pub async fn get_merge_queue_count(
     )))
 }
 
+/// GET /api/projects/:id/merge-queue - The project's merge queue, in processing order
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/merge-queue",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<QueuedMerge>>))
+)]
+pub async fn get_merge_queue(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedMerge>>>, ApiError> {
+    let entries = deployment
+        .merge_queue_store()
+        .positions_by_project(project.id);
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Request body for PUT /api/projects/:id/merge-queue
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ReorderMergeQueueRequest {
+    /// Every waiting entry of the project's merge queue, in the desired order
+    pub entry_ids: Vec<Uuid>,
+}
+
+/// PUT /api/projects/:id/merge-queue - Reorder the project's waiting merges
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/merge-queue",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = ReorderMergeQueueRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<QueuedMerge>>))
+)]
+pub async fn reorder_merge_queue(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderMergeQueueRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedMerge>>>, ApiError> {
+    let store = deployment.merge_queue_store();
+    if !store.reorder(project.id, &payload.entry_ids) {
+        return Err(ApiError::BadRequest(
+            "entry_ids must list every waiting merge of the project exactly once".to_string(),
+        ));
+    }
+    Ok(ResponseJson(ApiResponse::success(
+        store.positions_by_project(project.id),
+    )))
+}
+
+/// DELETE /api/projects/:id/merge-queue/:entry_id - Remove a waiting merge from the queue
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/merge-queue/{entry_id}",
+    tag = "projects",
+    params(
+        ("id" = Uuid, Path),
+        ("entry_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn cancel_merge_queue_entry(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_project_id, entry_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let entry = deployment
+        .merge_queue_store()
+        .cancel(project.id, entry_id)
+        .map_err(|refusal| match refusal {
+            CancelRefusal::NotFound => {
+                ApiError::NotFound("Merge queue entry not found".to_string())
+            }
+            CancelRefusal::Merging => {
+                ApiError::Conflict("Entry is already being merged".to_string())
+            }
+        })?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "merge_queue_entry_cancelled",
+            serde_json::json!({
+                "workspace_id": entry.workspace_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// GET /api/projects/:id/merge-queue/runs - Outcome of the processor's last run
+/// of each workspace, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/merge-queue/runs",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<MergeQueueRun>>))
+)]
+pub async fn get_merge_queue_runs(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<MergeQueueRun>>>, ApiError> {
+    let runs = deployment
+        .merge_queue_store()
+        .last_runs_by_project(project.id);
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
+/// GET /api/projects/:id/queue - Executions of the project waiting for a slot, in start order
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/queue",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ProjectQueueEntry>>))
+)]
+pub async fn get_project_queue(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectQueueEntry>>>, ApiError> {
+    let entries = ExecutionQueue::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Request body for PUT /api/projects/:id/queue
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ReorderProjectQueueRequest {
+    /// Every entry of the project's queue, in the desired start order
+    pub entry_ids: Vec<Uuid>,
+}
+
+/// PUT /api/projects/:id/queue - Reorder the project's waiting executions
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/queue",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = ReorderProjectQueueRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ProjectQueueEntry>>))
+)]
+pub async fn reorder_project_queue(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderProjectQueueRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectQueueEntry>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !ExecutionQueue::reorder_for_project(pool, project.id, &payload.entry_ids).await? {
+        return Err(ApiError::BadRequest(
+            "entry_ids must list every queued execution of the project exactly once".to_string(),
+        ));
+    }
+    let entries = ExecutionQueue::find_by_project_id(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
 /// Response for GET /api/projects/:id/prs/:repoId/:prNumber/threads
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct PrThreadsResponse {
     pub threads: Vec<UnifiedPrComment>,
 }
 
 /// Error type for GET /api/projects/:id/prs/:repoId/:prNumber/threads
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GetPrThreadsError {
@@ -894,6 +1357,19 @@ pub enum GetPrThreadsError {
 /// GET /api/projects/:id/prs/:repoId/:prNumber/threads - Get PR review threads
 ///
 /// Fetches both general and inline review comments for a specific PR.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/prs/{repo_id}/{pr_number}/threads",
+    tag = "projects",
+    params(
+        ("id" = Uuid, Path),
+        ("repo_id" = Uuid, Path),
+        ("pr_number" = i64, Path)
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PrThreadsResponse, GetPrThreadsError>),
+    )
+)]
 pub async fn get_pr_threads(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -902,15 +1378,15 @@ pub async fn get_pr_threads(
     let pool = &deployment.db().pool;
 
     // Look up the repo by ID within this project
-    let project_repo = match ProjectRepo::find_by_project_and_repo(pool, project.id, repo_id).await?
-    {
-        Some(pr) => pr,
-        None => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                GetPrThreadsError::RepoNotFound,
-            )));
-        }
-    };
+    let project_repo =
+        match ProjectRepo::find_by_project_and_repo(pool, project.id, repo_id).await? {
+            Some(pr) => pr,
+            None => {
+                return Ok(ResponseJson(ApiResponse::error_with_data(
+                    GetPrThreadsError::RepoNotFound,
+                )));
+            }
+        };
 
     let repo = match Repo::find_by_id(pool, project_repo.repo_id).await? {
         Some(r) => r,
@@ -951,9 +1427,9 @@ pub async fn get_pr_threads(
                 GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
                     ApiResponse::error_with_data(GetPrThreadsError::GithubNotConfigured),
                 )),
-                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(ApiResponse::error_with_data(
-                    GetPrThreadsError::GithubAuthFailed,
-                ))),
+                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
+                    ApiResponse::error_with_data(GetPrThreadsError::GithubAuthFailed),
+                )),
                 GitHubServiceError::RepoNotFoundOrNoAccess(_) => Ok(ResponseJson(
                     ApiResponse::error_with_data(GetPrThreadsError::RepoNotFound),
                 )),
@@ -964,6 +1440,13 @@ pub async fn get_pr_threads(
 }
 
 /// GET /api/projects/:id/workspaces - Get all workspaces for a project's tasks
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/workspaces",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Workspace>>))
+)]
 pub async fn get_project_workspaces(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -977,6 +1460,15 @@ pub async fn get_project_workspaces(
 ///
 /// Returns a list of worktrees with their branches and matching task groups.
 /// Task groups match when their base_branch equals the worktree's branch.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/worktrees",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ProjectWorktreesResponse>),
+    )
+)]
 pub async fn get_project_worktrees(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -1058,6 +1550,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/",
             get(get_project).put(update_project).delete(delete_project),
         )
+        .route("/archive", post(archive_project))
+        .route("/unarchive", post(unarchive_project))
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
@@ -1078,6 +1572,13 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/prs/{repo_id}/{pr_number}/threads", get(get_pr_threads))
         .route("/merge-queue-count", get(get_merge_queue_count))
+        .route(
+            "/merge-queue",
+            get(get_merge_queue).put(reorder_merge_queue),
+        )
+        .route("/merge-queue/runs", get(get_merge_queue_runs))
+        .route("/merge-queue/{entry_id}", delete(cancel_merge_queue_entry))
+        .route("/queue", get(get_project_queue).put(reorder_project_queue))
         .route("/workspaces", get(get_project_workspaces))
         .route("/worktrees", get(get_project_worktrees))
         .layer(from_fn_with_state(
@@ -1093,6 +1594,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .put(update_project_repository)
                 .delete(delete_project_repository),
         )
+        .route(
+            "/{project_id}/repositories/{repo_id}/copy-files/preview",
+            get(preview_project_repository_copy_files),
+        )
         .route("/stream/ws", get(stream_projects_ws))
         .nest("/{id}", project_id_router);
 
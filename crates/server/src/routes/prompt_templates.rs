@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::prompt_template::{CreatePromptTemplate, PromptTemplate, UpdatePromptTemplate};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::prompt_templates;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_prompt_template_middleware};
+
+/// Variables to substitute into a template preview
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct PreviewPromptTemplateRequest {
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+fn validate(name: &str, content: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Prompt template name cannot be empty".to_string(),
+        ));
+    }
+    if content.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Prompt template content cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /api/prompt-templates - All prompt templates
+#[utoipa::path(
+    get,
+    path = "/api/prompt-templates",
+    tag = "prompt_templates",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<PromptTemplate>>))
+)]
+pub async fn get_prompt_templates(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PromptTemplate>>>, ApiError> {
+    let templates = PromptTemplate::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+/// POST /api/prompt-templates - Create a prompt template
+#[utoipa::path(
+    post,
+    path = "/api/prompt-templates",
+    tag = "prompt_templates",
+    request_body = CreatePromptTemplate,
+    responses((status = 200, description = "Success", body = ApiResponse<PromptTemplate>))
+)]
+pub async fn create_prompt_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreatePromptTemplate>,
+) -> Result<ResponseJson<ApiResponse<PromptTemplate>>, ApiError> {
+    validate(&payload.name, &payload.content)?;
+    let template = PromptTemplate::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "prompt_template_created",
+            serde_json::json!({
+                "prompt_template_id": template.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// GET /api/prompt-templates/:id
+#[utoipa::path(
+    get,
+    path = "/api/prompt-templates/{template_id}",
+    tag = "prompt_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<PromptTemplate>))
+)]
+pub async fn get_prompt_template(
+    Extension(template): Extension<PromptTemplate>,
+) -> Result<ResponseJson<ApiResponse<PromptTemplate>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// PUT /api/prompt-templates/:id - Replace a prompt template
+#[utoipa::path(
+    put,
+    path = "/api/prompt-templates/{template_id}",
+    tag = "prompt_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    request_body = UpdatePromptTemplate,
+    responses((status = 200, description = "Success", body = ApiResponse<PromptTemplate>))
+)]
+pub async fn update_prompt_template(
+    Extension(template): Extension<PromptTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdatePromptTemplate>,
+) -> Result<ResponseJson<ApiResponse<PromptTemplate>>, ApiError> {
+    validate(&payload.name, &payload.content)?;
+    let updated = PromptTemplate::update(&deployment.db().pool, template.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /api/prompt-templates/:id. Settings still referencing the template
+/// fall back to their default prompt.
+#[utoipa::path(
+    delete,
+    path = "/api/prompt-templates/{template_id}",
+    tag = "prompt_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_prompt_template(
+    Extension(template): Extension<PromptTemplate>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = PromptTemplate::delete(&deployment.db().pool, template.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// POST /api/prompt-templates/:id/preview - The template with `variables`
+/// substituted, as an agent would receive it
+#[utoipa::path(
+    post,
+    path = "/api/prompt-templates/{template_id}/preview",
+    tag = "prompt_templates",
+    params(("template_id" = uuid::Uuid, Path)),
+    request_body = PreviewPromptTemplateRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<String>))
+)]
+pub async fn preview_prompt_template(
+    Extension(template): Extension<PromptTemplate>,
+    Json(payload): Json<PreviewPromptTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let variables: Vec<(&str, &str)> = payload
+        .variables
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let rendered = prompt_templates::render(&template.content, &variables);
+    Ok(ResponseJson(ApiResponse::success(rendered)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let template_router = Router::new()
+        .route(
+            "/",
+            get(get_prompt_template)
+                .put(update_prompt_template)
+                .delete(delete_prompt_template),
+        )
+        .route("/preview", post(preview_prompt_template))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_prompt_template_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_prompt_templates).post(create_prompt_template))
+        .nest("/{template_id}", template_router);
+
+    Router::new().nest("/prompt-templates", inner)
+}
@@ -2,17 +2,23 @@ use axum::{
     Router,
     extract::{Path, State},
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{get, post, put},
+};
+use db::models::{
+    merge::PullRequestInfo,
+    project_repo::ProjectRepo,
+    repo::{Repo, RepoError, UpdateRepoCheckoutOptions, UpdateRepoScripts},
 };
-use db::models::{merge::PullRequestInfo, project_repo::ProjectRepo, repo::Repo};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use services::services::{
     git::{GitBranch, GitCliError, GitServiceError},
     github::{CreatePrRequest, GitHubService, GitHubServiceError},
+    repo_stats::RepoStats,
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -52,7 +58,7 @@ pub struct CheckBranchMergeStatusRequest {
     pub project_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct BranchMergeStatus {
     pub exists: bool,
@@ -67,7 +73,7 @@ pub struct BatchCheckBranchMergeStatusRequest {
     pub project_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct BatchBranchMergeStatus {
     pub statuses: std::collections::HashMap<String, BranchMergeStatus>,
@@ -80,7 +86,7 @@ pub struct CheckBranchSyncStatusRequest {
     pub project_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct BranchSyncStatus {
     pub branch_name: String,
@@ -99,7 +105,7 @@ pub struct BatchCheckBranchSyncStatusRequest {
     pub project_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct BatchBranchSyncStatus {
     pub statuses: std::collections::HashMap<String, BranchSyncStatus>,
@@ -123,7 +129,7 @@ pub struct PushBranchRequest {
     pub force: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum CreateRepoPrError {
@@ -131,7 +137,7 @@ pub enum CreateRepoPrError {
     GithubCliNotLoggedIn,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(export, tag = "type", rename_all = "snake_case")]
 pub enum PushBranchError {
@@ -140,6 +146,12 @@ pub enum PushBranchError {
     AuthFailed,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos",
+    tag = "repo",
+    responses((status = 200, description = "Success", body = ApiResponse<Repo>))
+)]
 pub async fn register_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<RegisterRepoRequest>,
@@ -156,6 +168,12 @@ pub async fn register_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/init",
+    tag = "repo",
+    responses((status = 200, description = "Success", body = ApiResponse<Repo>))
+)]
 pub async fn init_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<InitRepoRequest>,
@@ -173,6 +191,12 @@ pub async fn init_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/clone",
+    tag = "repo",
+    responses((status = 200, description = "Success", body = ApiResponse<Repo>))
+)]
 pub async fn clone_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<CloneRepoRequest>,
@@ -191,6 +215,13 @@ pub async fn clone_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/repos/{repo_id}/branches",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<GitBranch>>))
+)]
 pub async fn get_repo_branches(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -200,10 +231,42 @@ pub async fn get_repo_branches(
         .get_by_id(&deployment.db().pool, repo_id)
         .await?;
 
-    let branches = deployment.git().get_all_branches(&repo.path)?;
+    let branches = deployment.repo_metadata_cache().branches(&repo.path)?;
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/repos/{repo_id}/stats",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<RepoStats>))
+)]
+pub async fn get_repo_stats(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<RepoStats>>, ApiError> {
+    let repo = deployment
+        .repo()
+        .get_by_id(&deployment.db().pool, repo_id)
+        .await?;
+
+    // Walking HEAD's tree and history is too slow for the async runtime on
+    // large repos
+    let metadata = deployment.repo_metadata_cache().clone();
+    let stats = tokio::task::spawn_blocking(move || metadata.stats(&repo.path))
+        .await
+        .map_err(|e| ApiError::Internal(format!("Failed to compute repo stats: {e}")))??;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<GitBranch>))
+)]
 pub async fn create_branch(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -234,9 +297,10 @@ pub async fn create_branch(
 
     // Create the branch
     git.create_branch(&repo.path, &payload.name, payload.base_branch.as_deref())?;
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     // Get the created branch from the list
-    let branches = git.get_all_branches(&repo.path)?;
+    let branches = deployment.repo_metadata_cache().branches(&repo.path)?;
     let created_branch = branches
         .into_iter()
         .find(|b| b.name == payload.name && !b.is_remote)
@@ -247,6 +311,62 @@ pub async fn create_branch(
     Ok(ResponseJson(ApiResponse::success(created_branch)))
 }
 
+/// PUT /api/repos/:id/scripts - Set the repo's default setup and cleanup scripts
+#[utoipa::path(
+    put,
+    path = "/api/repos/{repo_id}/scripts",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    request_body = UpdateRepoScripts,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Repo>),
+        (status = 404, description = "Repository not found"),
+    )
+)]
+pub async fn update_repo_scripts(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    ResponseJson(payload): ResponseJson<UpdateRepoScripts>,
+) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    match Repo::update_scripts(&deployment.db().pool, repo_id, &payload).await {
+        Ok(repo) => Ok(ResponseJson(ApiResponse::success(repo))),
+        Err(RepoError::NotFound) => Err(ApiError::NotFound("Repository not found".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// PUT /api/repos/:id/checkout-options - Opt the repo out of fetching
+/// submodules or LFS objects into new worktrees
+#[utoipa::path(
+    put,
+    path = "/api/repos/{repo_id}/checkout-options",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    request_body = UpdateRepoCheckoutOptions,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Repo>),
+        (status = 404, description = "Repository not found"),
+    )
+)]
+pub async fn update_repo_checkout_options(
+    State(deployment): State<DeploymentImpl>,
+    Path(repo_id): Path<Uuid>,
+    ResponseJson(payload): ResponseJson<UpdateRepoCheckoutOptions>,
+) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    match Repo::update_checkout_options(&deployment.db().pool, repo_id, &payload).await {
+        Ok(repo) => Ok(ResponseJson(ApiResponse::success(repo))),
+        Err(RepoError::NotFound) => Err(ApiError::NotFound("Repository not found".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches/check-merge-status",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BranchMergeStatus>))
+)]
 pub async fn check_branch_merge_status(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -302,6 +422,13 @@ pub async fn check_branch_merge_status(
     Ok(ResponseJson(ApiResponse::success(status)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches/batch-check-merge-status",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BatchBranchMergeStatus>))
+)]
 pub async fn batch_check_branch_merge_status(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -421,6 +548,13 @@ fn get_branch_sync_status(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches/check-sync-status",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BranchSyncStatus>))
+)]
 pub async fn check_branch_sync_status(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -436,6 +570,13 @@ pub async fn check_branch_sync_status(
     Ok(ResponseJson(ApiResponse::success(status)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches/batch-check-sync-status",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<BatchBranchSyncStatus>))
+)]
 pub async fn batch_check_branch_sync_status(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -458,6 +599,15 @@ pub async fn batch_check_branch_sync_status(
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/prs",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PullRequestInfo, CreateRepoPrError>),
+    )
+)]
 pub async fn create_repo_pr(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -503,6 +653,13 @@ pub async fn create_repo_pr(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/{repo_id}/branches/push",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<(), PushBranchError>))
+)]
 pub async fn push_branch(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
@@ -516,6 +673,7 @@ pub async fn push_branch(
     let result = deployment
         .git()
         .push_to_github(&repo.path, &payload.branch_name, payload.force);
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     match result {
         Ok(_) => Ok(ResponseJson(ApiResponse::success(()))),
@@ -556,6 +714,12 @@ pub fn router() -> Router<DeploymentImpl> {
             "/repos/{repo_id}/branches/batch-check-merge-status",
             post(batch_check_branch_merge_status),
         )
+        .route("/repos/{repo_id}/stats", get(get_repo_stats))
+        .route("/repos/{repo_id}/scripts", put(update_repo_scripts))
+        .route(
+            "/repos/{repo_id}/checkout-options",
+            put(update_repo_checkout_options),
+        )
         .route("/repos/{repo_id}/branches/push", post(push_branch))
         .route(
             "/repos/{repo_id}/branches/check-sync-status",
@@ -7,6 +7,15 @@ use uuid::Uuid;
 use crate::{DeploymentImpl, error::ApiError};
 
 /// GET /api/review-attention/task/:task_id - Returns the latest review attention for a task
+#[utoipa::path(
+    get,
+    path = "/api/review-attention/task/{task_id}",
+    tag = "review_attention",
+    params(("task_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Option<ReviewAttention>>),
+    )
+)]
 pub async fn get_review_attention_by_task(
     State(deployment): State<DeploymentImpl>,
     Path(task_id): Path<Uuid>,
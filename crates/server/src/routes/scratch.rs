@@ -1,9 +1,6 @@
 use axum::{
     Json, Router,
-    extract::{
-        Path, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{Path, State},
     response::{IntoResponse, Json as ResponseJson},
     routing::get,
 };
@@ -13,7 +10,11 @@ use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{routes::ws_helpers::forward_stream_to_ws, DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::stream_transport::{LogMsgStream, StreamTransport},
+};
 
 /// Path parameters for scratch routes with composite key
 #[derive(Deserialize)]
@@ -22,6 +23,12 @@ pub struct ScratchPath {
     id: Uuid,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch",
+    tag = "scratch",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Scratch>>))
+)]
 pub async fn list_scratch(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Scratch>>>, ApiError> {
@@ -29,6 +36,16 @@ pub async fn list_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch_items)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(
+        ("scratch_type" = String, Path),
+        ("id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<Scratch>))
+)]
 pub async fn get_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -39,6 +56,17 @@ pub async fn get_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(
+        ("scratch_type" = String, Path),
+        ("id" = Uuid, Path)
+    ),
+    request_body = CreateScratch,
+    responses((status = 200, description = "Success", body = ApiResponse<Scratch>))
+)]
 pub async fn create_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -63,6 +91,17 @@ pub async fn create_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(
+        ("scratch_type" = String, Path),
+        ("id" = Uuid, Path)
+    ),
+    request_body = UpdateScratch,
+    responses((status = 200, description = "Success", body = ApiResponse<Scratch>))
+)]
 pub async fn update_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -88,6 +127,16 @@ pub async fn update_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(
+        ("scratch_type" = String, Path),
+        ("id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -99,29 +148,36 @@ pub async fn delete_scratch(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch/{scratch_type}/{id}/stream/ws",
+    tag = "scratch",
+    params(
+        ("scratch_type" = String, Path),
+        ("id" = Uuid, Path)
+    ),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_scratch_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_scratch_ws(socket, deployment, id, scratch_type).await {
-            tracing::warn!("scratch WS closed: {}", e);
-        }
-    })
+    transport.log_msgs("scratch", scratch_stream(deployment, id, scratch_type))
 }
 
-async fn handle_scratch_ws(
-    socket: WebSocket,
+async fn scratch_stream(
     deployment: DeploymentImpl,
     id: Uuid,
     scratch_type: ScratchType,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
         .stream_scratch_raw(id, &scratch_type)
-        .await?;
-    forward_stream_to_ws(socket, stream).await
+        .await?)
 }
 
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
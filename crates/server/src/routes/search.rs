@@ -0,0 +1,176 @@
+use axum::{Json, Router, extract::State, response::Json as ResponseJson, routing::post};
+use chrono::{DateTime, Utc};
+use db::models::{
+    project::Project,
+    semantic_search::{ConversationMessageMatch, ExecutionSummaryMatch},
+    task::{Task, TaskWithAttemptStatus},
+    text_search::{TextSearchFilters, TextSearchMatch},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::tasks::TaskMatchWithScore};
+
+/// Request for semantic search across a project
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSearchRequest {
+    pub project_id: Uuid,
+    pub query: String,
+    /// Maximum matches per result kind. Defaults to 10.
+    pub limit: Option<i32>,
+}
+
+/// Ranked matches for each kind of indexed content
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSearchResponse {
+    pub tasks: Vec<TaskMatchWithScore>,
+    pub conversation_messages: Vec<ConversationMessageMatch>,
+    pub execution_summaries: Vec<ExecutionSummaryMatch>,
+    /// "vector" when embeddings were used, "keyword" when only tasks were matched by full-text search
+    pub search_method: String,
+}
+
+fn to_task_matches(results: Vec<(TaskWithAttemptStatus, f64)>) -> Vec<TaskMatchWithScore> {
+    results
+        .into_iter()
+        .map(|(task, score)| TaskMatchWithScore {
+            task,
+            similarity_score: score,
+        })
+        .collect()
+}
+
+/// POST /api/search/semantic - Search tasks, conversation messages and agent summaries
+#[utoipa::path(
+    post,
+    path = "/api/search/semantic",
+    tag = "search",
+    request_body = SemanticSearchRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<SemanticSearchResponse>))
+)]
+pub async fn semantic_search(
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<SemanticSearchRequest>,
+) -> Result<ResponseJson<ApiResponse<SemanticSearchResponse>>, ApiError> {
+    const DEFAULT_LIMIT: i32 = 10;
+    const MAX_LIMIT: i32 = 50;
+
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(ApiError::BadRequest("Query cannot be empty".to_string()));
+    }
+
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, request.project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Project {} not found", request.project_id)))?;
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as i64;
+
+    let query_embedding = if db::is_sqlite_vec_available() {
+        match deployment.embedding().embed_text(query).await {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                tracing::warn!(
+                    "Embedding generation failed, falling back to keyword search: {}",
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let response = match query_embedding {
+        Some(embedding) => SemanticSearchResponse {
+            tasks: to_task_matches(
+                Task::search_hybrid(pool, project.id, &embedding, query, None, None, limit).await?,
+            ),
+            conversation_messages: ConversationMessageMatch::search(
+                pool, project.id, &embedding, limit,
+            )
+            .await?,
+            execution_summaries: ExecutionSummaryMatch::search(pool, project.id, &embedding, limit)
+                .await?,
+            search_method: "vector".to_string(),
+        },
+        None => SemanticSearchResponse {
+            tasks: to_task_matches(
+                Task::search_fts(pool, project.id, query, None, None, limit).await?,
+            ),
+            conversation_messages: Vec::new(),
+            execution_summaries: Vec::new(),
+            search_method: "keyword".to_string(),
+        },
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Request for full-text search over conversation messages and execution logs
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TextSearchRequest {
+    pub query: String,
+    pub project_id: Option<Uuid>,
+    /// Executor as stored on the session, e.g. "CLAUDE_CODE"
+    pub executor: Option<String>,
+    /// Only matches from messages sent or processes started at or after this time
+    #[ts(type = "Date | null")]
+    pub from: Option<DateTime<Utc>>,
+    /// Only matches from before this time
+    #[ts(type = "Date | null")]
+    pub to: Option<DateTime<Utc>>,
+    /// Defaults to 20.
+    pub limit: Option<i32>,
+}
+
+/// POST /api/search/text - Find conversation messages and log entries containing the query
+#[utoipa::path(
+    post,
+    path = "/api/search/text",
+    tag = "search",
+    request_body = TextSearchRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TextSearchMatch>>))
+)]
+pub async fn text_search(
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<TextSearchRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TextSearchMatch>>>, ApiError> {
+    const DEFAULT_LIMIT: i32 = 20;
+    const MAX_LIMIT: i32 = 100;
+
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(ApiError::BadRequest("Query cannot be empty".to_string()));
+    }
+    if let (Some(from), Some(to)) = (request.from, request.to)
+        && from >= to
+    {
+        return Err(ApiError::BadRequest(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+
+    let filters = TextSearchFilters {
+        project_id: request.project_id,
+        executor: request.executor.filter(|e| !e.trim().is_empty()),
+        from: request.from,
+        to: request.to,
+    };
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as i64;
+    let matches = TextSearchMatch::search(&deployment.db().pool, query, &filters, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(matches)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/search/semantic", post(semantic_search))
+        .route("/search/text", post(text_search))
+}
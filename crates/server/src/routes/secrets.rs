@@ -0,0 +1,94 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::secrets::{SecretBackend, SecretService};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Stored secret names; values are never returned
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct SecretsResponse {
+    pub backend: SecretBackend,
+    pub names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetSecretRequest {
+    pub value: String,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/secrets", get(list_secrets))
+        .route("/secrets/{name}", put(set_secret).delete(delete_secret))
+}
+
+/// GET /api/secrets - Names of the stored secrets and where they are kept
+#[utoipa::path(
+    get,
+    path = "/api/secrets",
+    tag = "secrets",
+    responses((status = 200, description = "Success", body = ApiResponse<SecretsResponse>))
+)]
+pub async fn list_secrets(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<SecretsResponse>>, ApiError> {
+    let secrets = deployment.secrets();
+    Ok(ResponseJson(ApiResponse::success(SecretsResponse {
+        backend: secrets.backend(),
+        names: secrets.list().await?,
+    })))
+}
+
+/// PUT /api/secrets/:name - Store a secret, replacing any previous value
+#[utoipa::path(
+    put,
+    path = "/api/secrets/{name}",
+    tag = "secrets",
+    params(("name" = String, Path)),
+    request_body = SetSecretRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn set_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+    Json(payload): Json<SetSecretRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if payload.value.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Secret value cannot be empty".to_string(),
+        ));
+    }
+
+    deployment.secrets().set(&name, &payload.value).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// DELETE /api/secrets/:name - Remove a stored secret
+#[utoipa::path(
+    delete,
+    path = "/api/secrets/{name}",
+    tag = "secrets",
+    params(("name" = String, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<()>),
+        (status = 404, description = "No such secret"),
+    )
+)]
+pub async fn delete_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if !SecretService::is_valid_name(&name) || !deployment.secrets().delete(&name).await? {
+        return Err(ApiError::NotFound(format!("Secret not found: {name}")));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
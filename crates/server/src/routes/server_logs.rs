@@ -1,52 +1,153 @@
+use std::str::FromStr;
+
 use axum::{
     Router,
-    extract::{
-        State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
-    response::IntoResponse,
+    extract::{Query, State, ws::Message},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::get,
 };
+use chrono::{DateTime, Utc};
 use deployment::Deployment;
 use futures_util::{StreamExt, TryStreamExt};
+use serde::Deserialize;
+use tracing::Level;
+use utils::{
+    response::ApiResponse,
+    server_log_store::{ServerLogEntry, ServerLogFilter},
+};
+use utoipa::IntoParams;
 
-use crate::{routes::ws_helpers::forward_ws_messages, DeploymentImpl};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::stream_transport::{MessageStream, StreamTransport},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ServerLogsQuery {
+    /// Only entries logged after this RFC 3339 timestamp
+    pub since: Option<DateTime<Utc>>,
+    /// Minimum level: trace, debug, info, warn or error
+    pub level: Option<String>,
+    /// Module path prefix, e.g. `executors`
+    pub target: Option<String>,
+    /// Keep only the newest entries of the history
+    pub limit: Option<usize>,
+}
 
-/// WebSocket endpoint that streams server logs to clients.
+impl ServerLogsQuery {
+    fn filter(&self) -> Result<ServerLogFilter, ApiError> {
+        let level = self
+            .level
+            .as_deref()
+            .map(|level| {
+                Level::from_str(level)
+                    .map_err(|_| ApiError::BadRequest(format!("Unknown log level: {level}")))
+            })
+            .transpose()?;
+        Ok(ServerLogFilter {
+            since: self.since,
+            level,
+            target: self
+                .target
+                .as_deref()
+                .map(str::trim)
+                .filter(|target| !target.is_empty())
+                .map(str::to_string),
+        })
+    }
+}
+
+/// Streams server logs to clients over a WebSocket or server-sent events.
 ///
 /// Sends all historical log entries first, then streams live entries.
+#[utoipa::path(
+    get,
+    path = "/api/server-logs/ws",
+    tag = "server_logs",
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_server_logs_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_server_logs_ws(socket, deployment).await {
-            tracing::warn!("server logs WS closed: {}", e);
-        }
-    })
+    let stream = server_logs_stream(&deployment, None);
+    transport.messages("server logs", async move { Ok(stream) })
 }
 
-async fn handle_server_logs_ws(
-    socket: WebSocket,
-    deployment: DeploymentImpl,
-) -> anyhow::Result<()> {
-    let stream = deployment.server_log_store().history_plus_stream();
+/// Buffered server log entries matching the query, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/logs",
+    tag = "server_logs",
+    params(ServerLogsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ServerLogEntry>>))
+)]
+pub async fn get_server_logs(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ServerLogsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ServerLogEntry>>>, ApiError> {
+    let filter = query.filter()?;
+    let entries = deployment.server_log_store().query(&filter, query.limit);
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
 
-    // Convert each ServerLogEntry to a JSON WebSocket text message
-    let stream = stream
+/// Streams server logs matching the query: the matching history first, then
+/// live entries.
+#[utoipa::path(
+    get,
+    path = "/api/logs/stream",
+    tag = "server_logs",
+    params(ServerLogsQuery),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
+pub async fn stream_filtered_server_logs_ws(
+    transport: StreamTransport,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ServerLogsQuery>,
+) -> Result<Response, ApiError> {
+    let filter = query.filter()?;
+    let stream = server_logs_stream(&deployment, Some((filter, query.limit)));
+    Ok(transport.messages("server logs", async move { Ok(stream) }))
+}
+
+fn server_logs_stream(
+    deployment: &DeploymentImpl,
+    filter: Option<(ServerLogFilter, Option<usize>)>,
+) -> MessageStream {
+    let store = deployment.server_log_store();
+    let stream = match filter {
+        Some((filter, limit)) => store.filtered_history_plus_stream(filter, limit),
+        None => store.history_plus_stream(),
+    };
+
+    // Convert each ServerLogEntry to a JSON text message
+    stream
         .map_ok(|entry| {
             let json = serde_json::to_string(&entry).unwrap_or_default();
-            axum::extract::ws::Message::Text(json.into())
+            Message::Text(json.into())
         })
-        .boxed();
-
-    forward_ws_messages(socket, stream).await
+        .boxed()
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let _ = deployment; // unused but kept for consistency with other routers
-    Router::new().nest(
-        "/server-logs",
-        Router::new().route("/ws", get(stream_server_logs_ws)),
-    )
+    Router::new()
+        .nest(
+            "/server-logs",
+            Router::new().route("/ws", get(stream_server_logs_ws)),
+        )
+        .nest(
+            "/logs",
+            Router::new()
+                .route("/", get(get_server_logs))
+                .route("/stream", get(stream_filtered_server_logs_ws)),
+        )
 }
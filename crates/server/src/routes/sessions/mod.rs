@@ -1,5 +1,7 @@
 pub mod queue;
 
+use std::{path::PathBuf, time::Duration};
+
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
@@ -8,12 +10,15 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_failure::ExecutionProcessFailure,
+    execution_process_retry::ExecutionProcessRetry,
     execution_queue::ExecutionQueue,
     project_repo::ProjectRepo,
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
     workspace::{Workspace, WorkspaceError},
+    workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
 use executors::{
@@ -23,10 +28,11 @@ use executors::{
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use serde::{Deserialize, Serialize};
-use services::services::container::ContainerService;
-use sqlx::Error as SqlxError;
+use services::services::{blame_context, container::ContainerService};
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
@@ -35,7 +41,7 @@ use crate::{
 };
 
 /// Result of a follow-up request - can be started immediately or queued
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "status", rename_all = "snake_case")]
 #[ts(export)]
 pub enum FollowUpResult {
@@ -45,17 +51,28 @@ pub enum FollowUpResult {
     Queued { queue_entry: ExecutionQueue },
 }
 
-#[derive(Debug, Deserialize)]
+/// How long to wait for an interrupted agent's exit handling to finish
+const INTERRUPT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SessionQuery {
     pub workspace_id: Uuid,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateSessionRequest {
     pub workspace_id: Uuid,
     pub executor: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/sessions",
+    tag = "sessions",
+    params(SessionQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Session>>))
+)]
 pub async fn get_sessions(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<SessionQuery>,
@@ -65,12 +82,64 @@ pub async fn get_sessions(
     Ok(ResponseJson(ApiResponse::success(sessions)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Session>))
+)]
 pub async fn get_session(
     Extension(session): Extension<Session>,
 ) -> Result<ResponseJson<ApiResponse<Session>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+/// GET /api/sessions/:id/retries - Automatic retries of failed executions
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}/retries",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ExecutionProcessRetry>>),
+    )
+)]
+pub async fn get_session_retries(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcessRetry>>>, ApiError> {
+    let retries =
+        ExecutionProcessRetry::find_by_session_id(&deployment.db().pool, session.id).await?;
+    Ok(ResponseJson(ApiResponse::success(retries)))
+}
+
+/// GET /api/sessions/:id/failures - Classified causes of failed executions
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}/failures",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<ExecutionProcessFailure>>),
+    )
+)]
+pub async fn get_session_failures(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcessFailure>>>, ApiError> {
+    let failures =
+        ExecutionProcessFailure::find_by_session_id(&deployment.db().pool, session.id).await?;
+    Ok(ResponseJson(ApiResponse::success(failures)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/sessions",
+    tag = "sessions",
+    request_body = CreateSessionRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<Session>))
+)]
 pub async fn create_session(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateSessionRequest>,
@@ -97,7 +166,7 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub variant: Option<String>,
@@ -106,38 +175,24 @@ pub struct CreateFollowUpAttempt {
     pub perform_git_reset: Option<bool>,
 }
 
-pub async fn follow_up(
-    Extension(session): Extension<Session>,
-    State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateFollowUpAttempt>,
-) -> Result<ResponseJson<ApiResponse<FollowUpResult>>, ApiError> {
-    let pool = &deployment.db().pool;
-
-    // Load workspace from session
-    let workspace = Workspace::find_by_id(pool, session.workspace_id)
-        .await?
-        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
-            "Workspace not found".to_string(),
-        )))?;
-
-    tracing::info!("{:?}", workspace);
-
-    deployment
-        .container()
-        .ensure_container_exists(&workspace)
-        .await?;
-
+/// Executor profile for the next turn of `session`: the latest coding agent's
+/// executor, with `variant` applied when it is valid for that executor.
+pub(crate) async fn resolve_executor_profile(
+    pool: &SqlitePool,
+    session_id: Uuid,
+    variant: Option<&str>,
+) -> Result<ExecutorProfileId, ApiError> {
     // Get executor profile data from the latest CodingAgent process in this session
     let initial_executor_profile_id =
-        ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
+        ExecutionProcess::latest_executor_profile_for_session(pool, session_id).await?;
 
     // Validate that the requested variant is compatible with the current executor.
-    // If payload.variant is from a different executor (e.g., HAIKU for CLAUDE when using CODEX),
+    // If the variant is from a different executor (e.g., HAIKU for CLAUDE when using CODEX),
     // fall back to the original profile to avoid invalid combinations like CODEX:HAIKU.
-    let executor_profile_id = if let Some(ref variant) = payload.variant {
+    let executor_profile_id = if let Some(variant) = variant {
         let candidate = ExecutorProfileId {
             executor: initial_executor_profile_id.executor,
-            variant: Some(variant.clone()),
+            variant: Some(variant.to_string()),
         };
         if ExecutorConfigs::get_cached()
             .get_coding_agent(&candidate)
@@ -160,6 +215,41 @@ pub async fn follow_up(
         }
     };
 
+    Ok(executor_profile_id)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/sessions/{session_id}/follow-up",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    request_body = CreateFollowUpAttempt,
+    responses((status = 200, description = "Success", body = ApiResponse<FollowUpResult>))
+)]
+pub async fn follow_up(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateFollowUpAttempt>,
+) -> Result<ResponseJson<ApiResponse<FollowUpResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // Load workspace from session
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+
+    tracing::info!("{:?}", workspace);
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let executor_profile_id =
+        resolve_executor_profile(pool, session.id, payload.variant.as_deref()).await?;
+
     // Get parent task
     let task = workspace
         .parent_task(pool)
@@ -207,16 +297,157 @@ pub async fn follow_up(
         let _ = ExecutionProcess::drop_at_and_after(pool, session.id, proc_id).await?;
     }
 
+    let result = start_follow_up(
+        &deployment,
+        &session,
+        &workspace,
+        project.id,
+        executor_profile_id,
+        payload.prompt,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct InterruptAndFollowUpRequest {
+    /// Correction sent to the agent as the next turn
+    pub prompt: String,
+    pub variant: Option<String>,
+}
+
+/// POST /api/sessions/:id/interrupt - Stop the running agent turn (interrupt
+/// first, kill if it doesn't exit) and continue the conversation with `prompt`
+#[utoipa::path(
+    post,
+    path = "/api/sessions/{session_id}/interrupt",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    request_body = InterruptAndFollowUpRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<FollowUpResult>))
+)]
+pub async fn interrupt_and_follow_up(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<InterruptAndFollowUpRequest>,
+) -> Result<ResponseJson<ApiResponse<FollowUpResult>>, ApiError> {
+    if payload.prompt.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "A message for the agent is required".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+    let project = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // The turn may have finished on its own in the meantime; then this is a
+    // plain follow-up
+    let running = ExecutionProcess::find_latest_by_session_and_run_reason(
+        pool,
+        session.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .filter(|process| process.status == ExecutionProcessStatus::Running);
+    if let Some(process) = running {
+        deployment
+            .container()
+            .stop_execution(&process, ExecutionProcessStatus::Killed)
+            .await?;
+        // The exit monitor finalizes the stopped turn asynchronously; starting
+        // before it is done would trip the one-agent-per-workspace guard
+        if !deployment
+            .container()
+            .wait_for_workspace_idle(workspace.id, INTERRUPT_WAIT_TIMEOUT)
+            .await
+        {
+            return Err(ApiError::Conflict(
+                "The agent was stopped but is still shutting down; try again".to_string(),
+            ));
+        }
+    }
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let executor_profile_id =
+        resolve_executor_profile(pool, session.id, payload.variant.as_deref()).await?;
+    let result = start_follow_up(
+        &deployment,
+        &session,
+        &workspace,
+        project.id,
+        executor_profile_id,
+        payload.prompt,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "agent_interrupted_with_message",
+            serde_json::json!({
+                "session_id": session.id.to_string(),
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// Start (or queue, at the concurrency limit) the next coding agent turn of
+/// `session`, resuming the agent's own session when it has one.
+pub(crate) async fn start_follow_up(
+    deployment: &DeploymentImpl,
+    session: &Session,
+    workspace: &Workspace,
+    project_id: Uuid,
+    executor_profile_id: ExecutorProfileId,
+    prompt: String,
+) -> Result<FollowUpResult, ApiError> {
+    let pool = &deployment.db().pool;
+
     let latest_agent_session_id =
         ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
 
-    let prompt = payload.prompt;
-
-    let project_repos = ProjectRepo::find_by_project_id_with_names(pool, project.id).await?;
+    let project_repos = ProjectRepo::find_by_project_id_with_names(pool, project_id).await?;
     let cleanup_action = deployment
         .container()
         .cleanup_actions_for_repos(&project_repos);
 
+    // Review comments and pasted test failures point at lines; tell the agent
+    // how those lines came to be
+    let prompt = match workspace.container_ref.as_ref() {
+        Some(container_ref) => {
+            let workspace_root = PathBuf::from(container_ref);
+            let worktrees: Vec<PathBuf> =
+                WorkspaceRepo::find_repos_for_workspace(pool, workspace.id)
+                    .await?
+                    .iter()
+                    .map(|repo| workspace_root.join(&repo.name))
+                    .collect();
+            tokio::task::spawn_blocking(move || {
+                blame_context::with_blame_context(prompt, &worktrees)
+            })
+            .await
+            .map_err(|e| ApiError::Internal(format!("Failed to gather blame context: {e}")))?
+        }
+        None => prompt,
+    };
+
     let working_dir = workspace
         .agent_working_dir
         .as_ref()
@@ -243,7 +474,11 @@ pub async fn follow_up(
     let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
 
     // Check if we should queue this execution due to concurrency limit
-    if deployment.container().should_queue_execution().await? {
+    if deployment
+        .container()
+        .should_queue_execution(workspace, &executor_profile_id)
+        .await?
+    {
         tracing::info!(
             "At concurrency limit, queueing follow-up for session {} workspace {}",
             session.id,
@@ -252,16 +487,14 @@ pub async fn follow_up(
         let queue_entry =
             ExecutionQueue::create_follow_up(pool, workspace.id, session.id, &action).await?;
         // Note: is_queued is updated automatically via database trigger on execution_queue INSERT
-        return Ok(ResponseJson(ApiResponse::success(FollowUpResult::Queued {
-            queue_entry,
-        })));
+        return Ok(FollowUpResult::Queued { queue_entry });
     }
 
     let execution_process = deployment
         .container()
         .start_execution(
-            &workspace,
-            &session,
+            workspace,
+            session,
             &action,
             &ExecutionProcessRunReason::CodingAgent,
             None,
@@ -279,15 +512,16 @@ pub async fn follow_up(
         );
     }
 
-    Ok(ResponseJson(ApiResponse::success(
-        FollowUpResult::Started { execution_process },
-    )))
+    Ok(FollowUpResult::Started { execution_process })
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/interrupt", post(interrupt_and_follow_up))
+        .route("/retries", get(get_session_retries))
+        .route("/failures", get(get_session_failures))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
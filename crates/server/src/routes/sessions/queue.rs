@@ -8,17 +8,26 @@ use serde::Deserialize;
 use services::services::queued_message::QueueStatus;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_session_middleware};
 
 /// Request body for queueing a follow-up message
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct QueueMessageRequest {
     pub message: String,
     pub variant: Option<String>,
 }
 
 /// Queue a follow-up message to be executed when the current execution finishes
+#[utoipa::path(
+    post,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    request_body = QueueMessageRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn queue_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
@@ -29,9 +38,8 @@ pub async fn queue_message(
         variant: payload.variant,
     };
 
-    let queued = deployment
-        .queued_message_service()
-        .queue_message(session.id, data);
+    let queue = deployment.queued_message_service();
+    queue.queue_message(session.id, data);
 
     deployment
         .track_if_analytics_allowed(
@@ -43,12 +51,19 @@ pub async fn queue_message(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(QueueStatus::Queued {
-        message: queued,
-    })))
+    Ok(ResponseJson(ApiResponse::success(
+        queue.get_status(session.id),
+    )))
 }
 
 /// Cancel a queued follow-up message
+#[utoipa::path(
+    delete,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn cancel_queued_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
@@ -71,6 +86,13 @@ pub async fn cancel_queued_message(
 }
 
 /// Get the current queue status for a session's workspace
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<QueueStatus>))
+)]
 pub async fn get_queue_status(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
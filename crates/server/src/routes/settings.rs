@@ -8,13 +8,13 @@ use axum::{
 use db::models::app_settings::{AppSettings, GitHubSettingsStatus};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::secrets::encryption;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-mod encryption;
-
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/settings/github", get(get_github_settings))
@@ -24,19 +24,25 @@ pub fn router() -> Router<DeploymentImpl> {
 }
 
 /// Request body for setting GitHub token
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct SetGitHubTokenRequest {
     pub token: String,
 }
 
 /// Response for successful GitHub token import
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct GitHubImportResponse {
     pub success: bool,
     pub message: String,
 }
 
 /// GET /api/settings/github - Check if GitHub token is configured
+#[utoipa::path(
+    get,
+    path = "/api/settings/github",
+    tag = "settings",
+    responses((status = 200, description = "Success", body = ApiResponse<GitHubSettingsStatus>))
+)]
 async fn get_github_settings(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<GitHubSettingsStatus>>, ApiError> {
@@ -49,6 +55,13 @@ async fn get_github_settings(
 }
 
 /// PUT /api/settings/github - Set GitHub token
+#[utoipa::path(
+    put,
+    path = "/api/settings/github",
+    tag = "settings",
+    request_body = SetGitHubTokenRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<GitHubSettingsStatus>))
+)]
 async fn set_github_token(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<SetGitHubTokenRequest>,
@@ -71,6 +84,12 @@ async fn set_github_token(
 }
 
 /// DELETE /api/settings/github - Clear GitHub token
+#[utoipa::path(
+    delete,
+    path = "/api/settings/github",
+    tag = "settings",
+    responses((status = 200, description = "Success", body = ApiResponse<GitHubSettingsStatus>))
+)]
 async fn delete_github_token(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<(StatusCode, ResponseJson<ApiResponse<GitHubSettingsStatus>>), ApiError> {
@@ -87,6 +106,12 @@ async fn delete_github_token(
 }
 
 /// POST /api/settings/github/import - Import GitHub token from gh CLI
+#[utoipa::path(
+    post,
+    path = "/api/settings/github/import",
+    tag = "settings",
+    responses((status = 200, description = "Success", body = ApiResponse<GitHubImportResponse>))
+)]
 async fn import_github_token(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<GitHubImportResponse>>, ApiError> {
@@ -11,11 +11,12 @@ use serde::Deserialize;
 use services::services::share::{ShareError, SharedTaskDetails};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct AssignSharedTaskRequest {
     pub new_assignee_user_id: Option<String>,
@@ -34,6 +35,14 @@ pub fn router() -> Router<DeploymentImpl> {
         )
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/shared-tasks/{shared_task_id}/assign",
+    tag = "shared_tasks",
+    params(("shared_task_id" = Uuid, Path)),
+    request_body = AssignSharedTaskRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn assign_shared_task(
     Path(shared_task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -58,6 +67,13 @@ pub async fn assign_shared_task(
     Ok(ResponseJson(ApiResponse::success(updated_shared_task)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/shared-tasks/{shared_task_id}",
+    tag = "shared_tasks",
+    params(("shared_task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_shared_task(
     Path(shared_task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -78,6 +94,13 @@ pub async fn delete_shared_task(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/shared-tasks/link-to-local",
+    tag = "shared_tasks",
+    request_body = SharedTaskDetails,
+    responses((status = 200, description = "Success", body = ApiResponse<Option<Task>>))
+)]
 pub async fn link_shared_task_to_local(
     State(deployment): State<DeploymentImpl>,
     Json(shared_task_details): Json<SharedTaskDetails>,
@@ -5,6 +5,12 @@ use utils::response::ApiResponse;
 
 use crate::DeploymentImpl;
 
+#[utoipa::path(
+    get,
+    path = "/api/skills",
+    tag = "skills",
+    responses((status = 200, description = "Success", body = ApiResponse<serde_json::Value>))
+)]
 pub async fn get_skills(
     State(deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<SkillsData>> {
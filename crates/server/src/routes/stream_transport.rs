@@ -0,0 +1,272 @@
+//! One handler for both WebSocket and server-sent event clients.
+//!
+//! Streaming routes take a [`StreamTransport`] instead of a `WebSocketUpgrade`.
+//! Clients behind proxies that block WebSockets open the same URL with an
+//! `EventSource` instead. Each text message a WebSocket client would receive
+//! is sent as the data of an unnamed event, so clients parse both the same way.
+
+use std::{future::Future, io};
+
+use axum::{
+    extract::{
+        FromRequestParts,
+        ws::{Message, WebSocketUpgrade, rejection::WebSocketUpgradeRejection},
+    },
+    http::{HeaderMap, header, request::Parts},
+    response::{
+        IntoResponse, Response, Sse,
+        sse::{Event, KeepAlive},
+    },
+};
+use futures_util::{StreamExt, TryStreamExt, future, stream::BoxStream};
+use utils::log_msg::LogMsg;
+
+use super::ws_helpers::{forward_stream_to_ws, forward_ws_messages};
+
+pub type LogMsgStream = BoxStream<'static, Result<LogMsg, io::Error>>;
+pub type MessageStream = BoxStream<'static, Result<Message, io::Error>>;
+
+/// How the client asked to receive a stream: a WebSocket upgrade, or a
+/// request that accepts `text/event-stream`
+pub enum StreamTransport {
+    WebSocket(WebSocketUpgrade),
+    Sse,
+}
+
+impl<S> FromRequestParts<S> for StreamTransport
+where
+    S: Send + Sync,
+{
+    type Rejection = WebSocketUpgradeRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if accepts_event_stream(&parts.headers) {
+            return Ok(Self::Sse);
+        }
+        WebSocketUpgrade::from_request_parts(parts, state)
+            .await
+            .map(Self::WebSocket)
+    }
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| media_type.trim().starts_with("text/event-stream"))
+}
+
+impl StreamTransport {
+    /// Respond with the `LogMsg` stream that `stream` resolves to. `name`
+    /// labels the stream in logs when it fails.
+    pub fn log_msgs<F>(self, name: &'static str, stream: F) -> Response
+    where
+        F: Future<Output = anyhow::Result<LogMsgStream>> + Send + 'static,
+    {
+        match self {
+            Self::WebSocket(ws) => ws
+                .on_upgrade(move |socket| async move {
+                    let result = match stream.await {
+                        Ok(stream) => forward_stream_to_ws(socket, stream).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("{} WS closed: {}", name, e);
+                    }
+                })
+                .into_response(),
+            Self::Sse => sse_response(name, async move {
+                let stream = stream.await?;
+                Ok::<_, anyhow::Error>(stream.map_ok(|msg| msg.to_ws_message_unchecked()).boxed())
+            }),
+        }
+    }
+
+    /// Respond with the WebSocket message stream that `stream` resolves to.
+    /// Only text messages are sent to SSE clients.
+    pub fn messages<F>(self, name: &'static str, stream: F) -> Response
+    where
+        F: Future<Output = anyhow::Result<MessageStream>> + Send + 'static,
+    {
+        match self {
+            Self::WebSocket(ws) => ws
+                .on_upgrade(move |socket| async move {
+                    let result = match stream.await {
+                        Ok(stream) => forward_ws_messages(socket, stream).await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("{} WS closed: {}", name, e);
+                    }
+                })
+                .into_response(),
+            Self::Sse => sse_response(name, stream),
+        }
+    }
+}
+
+/// Like the WebSocket forwarders, the response ends at the first error.
+/// `EventSource` reconnects on its own when it does.
+fn sse_response<F>(name: &'static str, stream: F) -> Response
+where
+    F: Future<Output = anyhow::Result<MessageStream>> + Send + 'static,
+{
+    let messages = futures_util::stream::once(stream)
+        .map(move |result| match result {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("{} SSE closed: {}", name, e);
+                futures_util::stream::empty().boxed()
+            }
+        })
+        .flatten()
+        .take_while(move |item| {
+            if let Err(e) = item {
+                tracing::warn!("{} SSE closed: {}", name, e);
+            }
+            future::ready(item.is_ok())
+        })
+        .filter_map(|item| {
+            future::ready(match item {
+                Ok(Message::Text(text)) => {
+                    Some(Ok::<_, io::Error>(Event::default().data(text.as_str())))
+                }
+                _ => None,
+            })
+        });
+
+    Sse::new(messages)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode},
+        routing::get,
+    };
+    use futures_util::stream;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn get_stream(app: Router, accept: Option<&str>) -> Response {
+        let mut request = Request::builder().uri("/stream");
+        if let Some(accept) = accept {
+            request = request.header(header::ACCEPT, accept);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    /// The data of each event in an SSE response body
+    async fn event_data(response: Response) -> Vec<String> {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec())
+            .unwrap()
+            .split("\n\n")
+            .filter_map(|event| {
+                let data: Vec<&str> = event
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                    .collect();
+                (!data.is_empty()).then(|| data.join("\n"))
+            })
+            .collect()
+    }
+
+    fn log_msg_app(msgs: Vec<LogMsg>) -> Router {
+        Router::new().route(
+            "/stream",
+            get(move |transport: StreamTransport| async move {
+                transport.log_msgs("test", async move {
+                    Ok(stream::iter(msgs.into_iter().map(Ok)).boxed())
+                })
+            }),
+        )
+    }
+
+    #[test]
+    fn test_accepts_event_stream() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_event_stream(&headers));
+
+        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+        assert!(!accepts_event_stream(&headers));
+
+        headers.insert(
+            header::ACCEPT,
+            "application/json, text/event-stream;q=0.9".parse().unwrap(),
+        );
+        assert!(accepts_event_stream(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_log_msgs_are_sent_as_unnamed_events() {
+        let msgs = vec![LogMsg::Stdout("hello".to_string()), LogMsg::Finished];
+        let expected: Vec<String> = msgs
+            .iter()
+            .map(|msg| match msg.to_ws_message_unchecked() {
+                Message::Text(text) => text.to_string(),
+                other => panic!("expected a text message, got {other:?}"),
+            })
+            .collect();
+
+        let response = get_stream(log_msg_app(msgs), Some("text/event-stream")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        assert_eq!(event_data(response).await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_plain_request_is_not_upgraded() {
+        let response = get_stream(log_msg_app(vec![LogMsg::Finished]), None).await;
+        assert!(response.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_sse_skips_non_text_messages_and_ends_at_first_error() {
+        let app = Router::new().route(
+            "/stream",
+            get(|transport: StreamTransport| async move {
+                transport.messages("test", async {
+                    Ok(stream::iter(vec![
+                        Ok(Message::Text("first".into())),
+                        Ok(Message::Binary(vec![1, 2, 3].into())),
+                        Ok(Message::Text("second".into())),
+                        Err(io::Error::other("broken")),
+                        Ok(Message::Text("never sent".into())),
+                    ])
+                    .boxed())
+                })
+            }),
+        );
+
+        let response = get_stream(app, Some("text/event-stream")).await;
+        assert_eq!(event_data(response).await, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_that_fails_to_open_ends_empty() {
+        let app = Router::new().route(
+            "/stream",
+            get(|transport: StreamTransport| async move {
+                transport.messages("test", async { Err(anyhow::anyhow!("not found")) })
+            }),
+        );
+
+        let response = get_stream(app, Some("text/event-stream")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(event_data(response).await.is_empty());
+    }
+}
@@ -1,39 +1,36 @@
+pub mod bundle;
 pub mod codex_setup;
 pub mod cursor_setup;
+pub mod diff_content;
 pub mod gh_cli_setup;
 pub mod images;
+pub mod permission_findings;
 pub mod pr;
+pub mod review_comments;
+pub mod stage;
 pub mod util;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     path::{Path, PathBuf},
-    sync::{LazyLock, Mutex},
     time::Duration,
 };
 
-/// Tracks which projects have an active merge queue processor.
-/// This prevents multiple processors from being spawned for the same project,
-/// which could cause race conditions when processing the queue.
-static ACTIVE_MERGE_PROCESSORS: LazyLock<Mutex<HashSet<Uuid>>> =
-    LazyLock::new(|| Mutex::new(HashSet::new()));
-
 use axum::{
     Extension, Json, Router,
-    extract::{
-        Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{DefaultBodyLimit, Query, State, ws::WebSocket},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post},
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     execution_process_normalized_entry::ExecutionProcessNormalizedEntry,
     execution_queue::ExecutionQueue,
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
+    operation::OperationKind,
+    permission_finding::PermissionFinding,
     project_repo::ProjectRepo,
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
@@ -54,37 +51,97 @@ use executors::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    branch_stack,
     container::{ContainerService, StartWorkspaceResult},
     domain_events::DomainEvent,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{ConflictOp, ConflictResolution, ConflictedFile, GitCliError, GitServiceError},
+    git_hooks,
+    git_remote::{GitRemoteProvider, GitRemoteProviderKind},
     github::GitHubService,
-    merge_queue_processor::MergeQueueProcessor,
     merge_queue_store::MergeQueueEntry,
     operation_status::{OperationStatus, OperationStatusType},
+    workspace_manager::RepoWorktreeRepair,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
-    routes::{task_attempts::gh_cli_setup::GhCliSetupError, ws_helpers::forward_stream_to_ws},
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_workspace_middleware},
+    routes::{
+        stream_transport::{LogMsgStream, MessageStream, StreamTransport},
+        task_attempts::gh_cli_setup::GhCliSetupError,
+    },
 };
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct RebaseTaskAttemptRequest {
     pub repo_id: Uuid,
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct AbortConflictsRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+pub struct CherryPickRequest {
+    pub repo_id: Uuid,
+    /// Commit SHAs or refs, applied oldest first onto the task branch
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CherryPickResponse {
+    pub head_commit: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+pub struct CherryPickOntoRequest {
+    pub repo_id: Uuid,
+    /// Branch that receives the task's commits, e.g. a release branch
+    pub target_branch: String,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CherryPickOntoResponse {
+    /// New tip of the target branch
+    pub head_commit: String,
+    pub commit_count: usize,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConflictsQuery {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct ConflictsResponse {
+    /// None when no merge, rebase, cherry-pick or revert is in progress
+    pub op: Option<ConflictOp>,
+    pub files: Vec<ConflictedFile>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+pub struct ResolveConflictRequest {
+    pub repo_id: Uuid,
+    pub path: String,
+    pub resolution: ConflictResolution,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+pub struct ContinueConflictsRequest {
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GitOperationError {
@@ -92,23 +149,38 @@ pub enum GitOperationError {
     RebaseInProgress,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DiffStreamQuery {
+    /// Send paths and line counts only; contents are then fetched per file
+    /// from `/diff/content`
     #[serde(default)]
     pub stats_only: bool,
+    /// Per-file content limit in bytes for this stream, overriding the
+    /// configured one; capped at the server maximum
+    pub max_inline_bytes: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct WorkspaceStreamQuery {
     pub task_id: Uuid,
     pub include_snapshot: Option<bool>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts",
+    tag = "task_attempts",
+    params(TaskAttemptQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Workspace>>))
+)]
 pub async fn get_task_attempts(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskAttemptQuery>,
@@ -118,12 +190,26 @@ pub async fn get_task_attempts(
     Ok(ResponseJson(ApiResponse::success(workspaces)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Workspace>))
+)]
 pub async fn get_task_attempt(
     Extension(workspace): Extension<Workspace>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(workspace)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/with-session",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<WorkspaceWithSession>))
+)]
 pub async fn get_task_attempt_with_session(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -146,28 +232,37 @@ pub async fn get_task_attempt_with_session(
     Ok(ResponseJson(ApiResponse::success(workspace_with_session)))
 }
 
-#[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
+#[derive(Debug, Serialize, Deserialize, ts_rs::TS, ToSchema)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
+#[derive(Debug, Serialize, Deserialize, ts_rs::TS, ToSchema)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
     pub target_branch: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct RunAgentSetupRequest {
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct RunAgentSetupResponse {}
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts",
+    tag = "task_attempts",
+    request_body = CreateTaskAttemptBody,
+    responses((status = 200, description = "Success", body = ApiResponse<Workspace>))
+)]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
@@ -197,10 +292,11 @@ pub async fn create_task_attempt(
         .cloned();
 
     let attempt_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = payload.repos.iter().map(|r| r.repo_id).collect();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
+        .unique_git_branch_for_workspace(&attempt_id, &task.title, &repo_ids)
+        .await?;
 
     let workspace = Workspace::create(
         pool,
@@ -266,6 +362,14 @@ pub async fn create_task_attempt(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-agent-setup",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RunAgentSetupRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<RunAgentSetupResponse>))
+)]
 pub async fn run_agent_setup(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -298,33 +402,58 @@ pub async fn run_agent_setup(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/diff/ws",
+    tag = "task_attempts",
+    params(
+        ("id" = Uuid, Path),
+        DiffStreamQuery
+    ),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_task_attempt_diff_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     Query(params): Query<DiffStreamQuery>,
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
-    let stats_only = params.stats_only;
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_task_attempt_diff_ws(socket, deployment, workspace, stats_only).await
-        {
-            tracing::warn!("diff WS closed: {}", e);
-        }
-    })
+    match transport {
+        // Watches the client side too, so an idle diff watcher is dropped as
+        // soon as the client goes away
+        StreamTransport::WebSocket(ws) => ws
+            .on_upgrade(move |socket| async move {
+                if let Err(e) =
+                    handle_task_attempt_diff_ws(socket, deployment, workspace, params).await
+                {
+                    tracing::warn!("diff WS closed: {}", e);
+                }
+            })
+            .into_response(),
+        StreamTransport::Sse => StreamTransport::Sse.log_msgs("diff", async move {
+            Ok(deployment
+                .container()
+                .stream_diff(&workspace, params.stats_only, params.max_inline_bytes)
+                .await?)
+        }),
+    }
 }
 
 async fn handle_task_attempt_diff_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     workspace: Workspace,
-    stats_only: bool,
+    params: DiffStreamQuery,
 ) -> anyhow::Result<()> {
     use futures_util::{SinkExt, StreamExt, TryStreamExt};
     use utils::log_msg::LogMsg;
 
     let stream = deployment
         .container()
-        .stream_diff(&workspace, stats_only)
+        .stream_diff(&workspace, params.stats_only, params.max_inline_bytes)
         .await?;
 
     let mut stream = stream.map_ok(|msg: LogMsg| msg.to_ws_message_unchecked());
@@ -359,35 +488,40 @@ async fn handle_task_attempt_diff_ws(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/stream/ws",
+    tag = "task_attempts",
+    params(WorkspaceStreamQuery),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_workspaces_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<WorkspaceStreamQuery>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        let include_snapshot = query.include_snapshot.unwrap_or(true);
-        if let Err(e) =
-            handle_workspaces_ws(socket, deployment, query.task_id, include_snapshot).await
-        {
-            tracing::warn!("workspaces WS closed: {}", e);
-        }
-    })
+    let include_snapshot = query.include_snapshot.unwrap_or(true);
+    transport.log_msgs(
+        "workspaces",
+        workspaces_stream(deployment, query.task_id, include_snapshot),
+    )
 }
 
-async fn handle_workspaces_ws(
-    socket: WebSocket,
+async fn workspaces_stream(
     deployment: DeploymentImpl,
     task_id: Uuid,
     include_snapshot: bool,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
         .stream_workspaces_for_task_raw(task_id, include_snapshot)
-        .await?;
-    forward_stream_to_ws(socket, stream).await
+        .await?)
 }
 
-/// WebSocket message sent when git state changes in a workspace
+/// Message sent when git state changes in a workspace
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct GitStateChangedMessage {
     #[serde(rename = "type")]
@@ -395,26 +529,30 @@ pub struct GitStateChangedMessage {
     pub workspace_id: Uuid,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/git-status/ws",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_git_status_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
 ) -> impl IntoResponse {
-    let workspace_id = workspace.id;
-    ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_git_status_ws(socket, deployment, workspace).await {
-            tracing::warn!(workspace_id = %workspace_id, "git status WS closed: {}", e);
-        }
-    })
+    transport.messages("git status", git_status_stream(deployment, workspace))
 }
 
-async fn handle_git_status_ws(
-    socket: WebSocket,
+async fn git_status_stream(
     deployment: DeploymentImpl,
     workspace: Workspace,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<MessageStream> {
     use axum::extract::ws::Message;
-    use futures_util::{SinkExt, StreamExt};
+    use futures_util::StreamExt;
 
     // Get the workspace root path
     let container_ref = workspace
@@ -461,14 +599,6 @@ async fn handle_git_status_ws(
         ));
     }
 
-    let (mut sender, mut receiver) = socket.split();
-
-    // Drain client messages in background so pings/pongs work.
-    // We use AbortHandle to cancel this task when the sender loop exits.
-    let drain_handle = tokio::spawn(async move {
-        while let Some(Ok(_)) = receiver.next().await {}
-    });
-
     // Create a stream that merges all subscriptions
     let workspace_id = workspace.id;
     let message = GitStateChangedMessage {
@@ -477,62 +607,40 @@ async fn handle_git_status_ws(
     };
     let message_json = serde_json::to_string(&message)?;
 
-    // Use select_all to wait for any subscription to receive an event
-    loop {
-        // Create futures for all subscriptions
-        let futures: Vec<_> = subscriptions
-            .iter_mut()
-            .enumerate()
-            .map(|(i, sub)| Box::pin(async move { (i, sub.recv().await) }))
-            .collect();
-
-        if futures.is_empty() {
-            break;
-        }
-
-        // Wait for any subscription to receive an event
-        let (result, _index, _remaining) = futures_util::future::select_all(futures).await;
-        let (_sub_index, event) = result;
-
-        match event {
-            Some(change) => {
-                tracing::debug!(
-                    workspace_id = %workspace_id,
-                    kind = ?change.kind,
-                    path = %change.path,
-                    "Git state changed"
-                );
-
-                // Send the message to the client
-                if sender
-                    .send(Message::Text(message_json.clone().into()))
-                    .await
-                    .is_err()
-                {
-                    break; // client disconnected
+    // One message per change in any repository, until a subscription closes
+    let stream = futures_util::stream::unfold(subscriptions, move |mut subscriptions| {
+        let message_json = message_json.clone();
+        async move {
+            // Wait for any subscription to receive an event
+            let (event, ..) = futures_util::future::select_all(
+                subscriptions.iter_mut().map(|sub| Box::pin(sub.recv())),
+            )
+            .await;
+
+            match event {
+                Some(change) => {
+                    tracing::debug!(
+                        workspace_id = %workspace_id,
+                        kind = ?change.kind,
+                        path = %change.path,
+                        "Git state changed"
+                    );
+                    Some((Ok(Message::Text(message_json.into())), subscriptions))
+                }
+                None => {
+                    // In practice, all subscriptions close together when the
+                    // workspace is removed
+                    tracing::debug!(workspace_id = %workspace_id, "A git subscription closed");
+                    None
                 }
-            }
-            None => {
-                // Subscription closed, remove it
-                // Note: We can't easily remove from the vec while iterating
-                // In practice, all subscriptions will close together when workspace is removed
-                tracing::debug!(workspace_id = %workspace_id, "A git subscription closed");
-                break;
             }
         }
-    }
-
-    // Abort the drain task to ensure the receiver half is dropped.
-    // This allows the TCP connection to properly close (preventing CLOSE_WAIT).
-    drain_handle.abort();
-
-    // Explicitly close the sender to trigger TCP FIN
-    let _ = sender.close().await;
+    });
 
-    Ok(())
+    Ok(stream.boxed())
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
     pub commit_message: Option<String>,
@@ -540,27 +648,43 @@ pub struct MergeTaskAttemptRequest {
     pub generate_commit_message: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct MergeTaskAttemptResponse {
+    /// Output of the repo's git hooks when they failed and the merge was
+    /// committed without them
+    pub hook_failure: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct GenerateCommitMessageRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct GenerateCommitMessageResponse {
     pub commit_message: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/merge",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = MergeTaskAttemptRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<MergeTaskAttemptResponse>))
+)]
 pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<MergeTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<MergeTaskAttemptResponse>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -584,6 +708,8 @@ pub async fn merge_task_attempt(
         .await?
         .ok_or(ApiError::Workspace(WorkspaceError::TaskNotFound))?;
 
+    permission_findings::ensure_no_open_findings(&deployment, workspace.id).await?;
+
     // Determine commit message:
     // 1. If request.commit_message provided → use it
     // 2. Else if generate_commit_message == Some(true) → call AI generation
@@ -616,23 +742,58 @@ pub async fn merge_task_attempt(
         build_fallback_commit_message(&task)
     };
 
-    let merge_commit_id = deployment.git().merge_changes(
-        &repo.path,
-        &worktree_path,
-        &workspace.branch,
-        &workspace_repo.target_branch,
-        &commit_message,
-    )?;
+    // Hooks run where the target branch is checked out
+    let checkout_path = deployment
+        .git()
+        .find_checkout_path_for_branch(&repo.path, &workspace_repo.target_branch)?
+        .unwrap_or_else(|| repo.path.clone());
+    let hooks =
+        git_hooks::commit_hooks_for_project(pool, task.project_id, &repo.path, &checkout_path)
+            .await?;
+    let stacked_on_tip = deployment
+        .git()
+        .get_branch_oid(&repo.path, &workspace.branch)?;
+    let merge = deployment
+        .operations()
+        .track_for_user(
+            OperationKind::Merge,
+            Some(workspace.id),
+            Some(task.id),
+            current_user.id(),
+            async {
+                deployment.git().merge_changes_with_hooks(
+                    &repo.path,
+                    &worktree_path,
+                    &workspace.branch,
+                    &workspace_repo.target_branch,
+                    &commit_message,
+                    &hooks,
+                )
+            },
+        )
+        .await?;
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     Merge::create_direct(
         pool,
         workspace.id,
         workspace_repo.repo_id,
         &workspace_repo.target_branch,
-        &merge_commit_id,
+        &merge.commit,
     )
     .await?;
 
+    // Move tasks stacked on the merged branch onto its target
+    branch_stack::restack_after_merge(
+        pool,
+        deployment.git(),
+        &repo,
+        &workspace.branch,
+        &stacked_on_tip,
+        &workspace_repo.target_branch,
+    )
+    .await;
+
     let previous_status = task.status.clone();
     Task::update_status(pool, task.id, TaskStatus::Done).await?;
 
@@ -698,7 +859,11 @@ pub async fn merge_task_attempt(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(
+        MergeTaskAttemptResponse {
+            hook_failure: merge.hook_failure,
+        },
+    )))
 }
 
 /// Builds a fallback commit message from task title and description.
@@ -732,7 +897,7 @@ async fn generate_commit_message_for_merge_internal(
         OperationStatusType::GeneratingCommit,
     ));
 
-    let result = async {
+    let generation = async {
         let execution_process = pr::generate_commit_message_for_merge(
             deployment,
             workspace,
@@ -767,8 +932,16 @@ async fn generate_commit_message_for_merge_internal(
             })?;
 
         Ok::<_, ApiError>(commit_message)
-    }
-    .await;
+    };
+    let result = deployment
+        .operations()
+        .track(
+            OperationKind::CommitMessage,
+            Some(workspace.id),
+            Some(workspace.task_id),
+            generation,
+        )
+        .await;
 
     // Clear operation status after completion (success or failure)
     deployment.operation_status().clear(workspace.id);
@@ -777,8 +950,19 @@ async fn generate_commit_message_for_merge_internal(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/generate-commit-message",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = GenerateCommitMessageRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<GenerateCommitMessageResponse>),
+    )
+)]
 pub async fn generate_commit_message(
     Extension(workspace): Extension<Workspace>,
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<GenerateCommitMessageRequest>,
 ) -> Result<ResponseJson<ApiResponse<GenerateCommitMessageResponse>>, ApiError> {
@@ -805,7 +989,7 @@ pub async fn generate_commit_message(
         OperationStatusType::GeneratingCommit,
     ));
 
-    let result = async {
+    let generation = async {
         let execution_process = pr::generate_commit_message_for_merge(
             &deployment,
             &workspace,
@@ -840,8 +1024,17 @@ pub async fn generate_commit_message(
             })?;
 
         Ok::<_, ApiError>(commit_message)
-    }
-    .await;
+    };
+    let result = deployment
+        .operations()
+        .track_for_user(
+            OperationKind::CommitMessage,
+            Some(workspace.id),
+            Some(workspace.task_id),
+            current_user.id(),
+            generation,
+        )
+        .await;
 
     // Clear operation status after completion (success or failure)
     deployment.operation_status().clear(workspace.id);
@@ -853,6 +1046,14 @@ pub async fn generate_commit_message(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/push",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = PushTaskAttemptRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<(), PushError>))
+)]
 pub async fn push_task_attempt_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -892,6 +1093,7 @@ pub async fn push_task_attempt_branch(
 
     // Clear operation status after push completes (success or failure)
     deployment.operation_status().clear(workspace.id);
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     match result {
         Ok(_) => Ok(ResponseJson(ApiResponse::success(()))),
@@ -902,6 +1104,14 @@ pub async fn push_task_attempt_branch(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/push/force",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = PushTaskAttemptRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<(), PushError>))
+)]
 pub async fn force_push_task_attempt_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -941,31 +1151,41 @@ pub async fn force_push_task_attempt_branch(
 
     // Clear operation status after push completes (success or failure)
     deployment.operation_status().clear(workspace.id);
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     result?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PushError {
     ForcePushRequired,
 }
 
-#[derive(serde::Deserialize, TS)]
+#[derive(serde::Deserialize, TS, ToSchema)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
     file_path: Option<String>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct OpenEditorResponse {
     pub url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/open-editor",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = OpenEditorRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<OpenEditorResponse>))
+)]
 pub async fn open_task_attempt_in_editor(
     Extension(workspace): Extension<Workspace>,
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<OpenEditorRequest>,
 ) -> Result<ResponseJson<ApiResponse<OpenEditorResponse>>, ApiError> {
@@ -992,7 +1212,7 @@ pub async fn open_task_attempt_in_editor(
     };
 
     let editor_config = {
-        let config = deployment.config().read().await;
+        let config = current_user.config(&deployment).await;
         let editor_type_str = payload.editor_type.as_deref();
         config.editor.with_override(editor_type_str)?
     };
@@ -1032,7 +1252,7 @@ pub async fn open_task_attempt_in_editor(
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct BranchStatus {
     pub commits_behind: Option<usize>,
     pub commits_ahead: Option<usize>,
@@ -1054,9 +1274,13 @@ pub struct BranchStatus {
     /// `Some(false)` when target branch is clean or not checked out anywhere.
     /// `None` only if an error occurred while checking.
     pub target_branch_has_uncommitted_changes: Option<bool>,
+    /// Host of the repo's remote, when it is a supported one
+    pub remote_provider: Option<GitRemoteProviderKind>,
+    /// Web page of the task branch on that host; it only exists once pushed
+    pub remote_branch_url: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct RepoBranchStatus {
     pub repo_id: Uuid,
     pub repo_name: String,
@@ -1064,6 +1288,13 @@ pub struct RepoBranchStatus {
     pub status: BranchStatus,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/branch-status",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<RepoBranchStatus>>))
+)]
 pub async fn get_task_attempt_branch_status(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1120,6 +1351,7 @@ pub async fn get_task_attempt_branch_status(
 
         let worktree_path = workspace_dir.join(&repo.name);
         let git = deployment.git();
+        let metadata = deployment.repo_metadata_cache();
 
         // Run independent git operations in parallel using spawn_blocking
         let (
@@ -1131,9 +1363,9 @@ pub async fn get_task_attempt_branch_status(
             target_dirty_result,
         ) = tokio::join!(
             tokio::task::spawn_blocking({
-                let git = git.clone();
+                let metadata = metadata.clone();
                 let path = worktree_path.clone();
-                move || git.get_head_info(&path)
+                move || metadata.head_info(&path)
             }),
             tokio::task::spawn_blocking({
                 let git = git.clone();
@@ -1235,24 +1467,19 @@ pub async fn get_task_attempt_branch_status(
         });
 
         let branch_status_future = tokio::task::spawn_blocking({
-            let git = git.clone();
+            let metadata = metadata.clone();
             let repo_path = repo.path.clone();
             let branch = workspace.branch.clone();
             let target = target_branch.clone();
-            move || match target_branch_type {
-                BranchType::Local => git.get_branch_status(&repo_path, &branch, &target),
-                BranchType::Remote => {
-                    git.get_remote_branch_status(&repo_path, &branch, Some(&target))
-                }
-            }
+            move || metadata.branch_status(&repo_path, &branch, &target, target_branch_type)
         });
 
         let remote_status_future = if has_open_pr {
             Some(tokio::task::spawn_blocking({
-                let git = git.clone();
+                let metadata = metadata.clone();
                 let repo_path = repo.path.clone();
                 let branch = workspace.branch.clone();
-                move || git.get_remote_branch_status(&repo_path, &branch, None)
+                move || metadata.upstream_status(&repo_path, &branch)
             }))
         } else {
             None
@@ -1280,6 +1507,11 @@ pub async fn get_task_attempt_branch_status(
             (None, None)
         };
 
+        let remote_provider = match git.get_remote_url(&repo.path) {
+            Ok(url) => GitRemoteProvider::from_url(&url, &*deployment.config().read().await),
+            Err(_) => None,
+        };
+
         results.push(RepoBranchStatus {
             repo_id: repo.id,
             repo_name: repo.name,
@@ -1298,6 +1530,10 @@ pub async fn get_task_attempt_branch_status(
                 conflict_op,
                 conflicted_files,
                 target_branch_has_uncommitted_changes,
+                remote_provider: remote_provider.as_ref().map(GitRemoteProvider::kind),
+                remote_branch_url: remote_provider
+                    .as_ref()
+                    .map(|provider| provider.branch_url(&workspace.branch)),
             },
         });
     }
@@ -1311,30 +1547,30 @@ pub async fn get_task_attempt_branch_status(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
-#[derive(serde::Deserialize, Debug, TS)]
+#[derive(serde::Deserialize, Debug, TS, ToSchema)]
 pub struct ChangeTargetBranchRequest {
     pub repo_id: Uuid,
     pub new_target_branch: String,
 }
 
-#[derive(serde::Serialize, Debug, TS)]
+#[derive(serde::Serialize, Debug, TS, ToSchema)]
 pub struct ChangeTargetBranchResponse {
     pub repo_id: Uuid,
     pub new_target_branch: String,
     pub status: (usize, usize),
 }
 
-#[derive(serde::Deserialize, Debug, TS)]
+#[derive(serde::Deserialize, Debug, TS, ToSchema)]
 pub struct RenameBranchRequest {
     pub new_branch_name: String,
 }
 
-#[derive(serde::Serialize, Debug, TS)]
+#[derive(serde::Serialize, Debug, TS, ToSchema)]
 pub struct RenameBranchResponse {
     pub branch: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum RenameBranchError {
@@ -1347,6 +1583,16 @@ pub enum RenameBranchError {
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/change-target-branch",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = ChangeTargetBranchRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ChangeTargetBranchResponse>),
+    )
+)]
 pub async fn change_target_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1373,6 +1619,16 @@ pub async fn change_target_branch(
         )));
     };
 
+    if branch_stack::would_create_cycle(pool, repo_id, workspace.id, &new_target_branch).await? {
+        return Ok(ResponseJson(ApiResponse::error(
+            format!(
+                "Branch '{}' is this task's branch or stacked on it",
+                new_target_branch
+            )
+            .as_str(),
+        )));
+    }
+
     WorkspaceRepo::update_target_branch(pool, workspace.id, repo_id, &new_target_branch).await?;
 
     let status =
@@ -1400,6 +1656,16 @@ pub async fn change_target_branch(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/rename-branch",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RenameBranchRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<RenameBranchResponse, RenameBranchError>),
+    )
+)]
 pub async fn rename_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1479,6 +1745,7 @@ pub async fn rename_branch(
             new_branch_name,
         ) {
             Ok(()) => {
+                deployment.repo_metadata_cache().invalidate(&repo.path);
                 renamed_repos.push(repo);
             }
             Err(e) => {
@@ -1508,8 +1775,8 @@ pub async fn rename_branch(
     }
 
     Workspace::update_branch_name(pool, workspace.id, new_branch_name).await?;
-    // What will become of me?
-    let updated_children_count = WorkspaceRepo::update_target_branch_for_children_of_workspace(
+    // Subtasks and other tasks stacked on the old branch follow the rename
+    let updated_children_count = WorkspaceRepo::update_target_branch_for_stacked_on_workspace(
         pool,
         workspace.id,
         &old_branch,
@@ -1519,7 +1786,7 @@ pub async fn rename_branch(
 
     if updated_children_count > 0 {
         tracing::info!(
-            "Updated {} child task attempts to target new branch '{}'",
+            "Updated {} stacked task attempts to target new branch '{}'",
             updated_children_count,
             new_branch_name
         );
@@ -1540,6 +1807,14 @@ pub async fn rename_branch(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/rebase",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RebaseTaskAttemptRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<(), GitOperationError>))
+)]
 pub async fn rebase_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1611,6 +1886,7 @@ pub async fn rebase_task_attempt(
 
     // Clear operation status after rebase completes (success or failure)
     deployment.operation_status().clear(workspace.id);
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
     if let Err(e) = result {
         use services::services::git::GitServiceError;
@@ -1648,11 +1924,19 @@ pub async fn rebase_task_attempt(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/conflicts/abort",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = AbortConflictsRequest,
+    responses((status = 200, description = "The aborted operation, if any", body = ApiResponse<Option<ConflictOp>>))
+)]
 pub async fn abort_conflicts_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<AbortConflictsRequest>,
-) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<Option<ConflictOp>>>, ApiError> {
     let pool = &deployment.db().pool;
 
     let repo = Repo::find_by_id(pool, payload.repo_id)
@@ -1666,12 +1950,293 @@ pub async fn abort_conflicts_task_attempt(
     let workspace_path = Path::new(&container_ref);
     let worktree_path = workspace_path.join(&repo.name);
 
-    deployment.git().abort_conflicts(&worktree_path)?;
+    let aborted = deployment.git().abort_in_progress_op(&worktree_path)?;
+    deployment.repo_metadata_cache().invalidate(&repo.path);
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(aborted)))
+}
+
+/// Apply commits from elsewhere, e.g. fixes that landed on the target branch,
+/// onto the task branch
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/cherry-pick",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = CherryPickRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CherryPickResponse, GitOperationError>)
+    )
+)]
+pub async fn cherry_pick_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CherryPickRequest>,
+) -> Result<ResponseJson<ApiResponse<CherryPickResponse, GitOperationError>>, ApiError> {
+    let commits: Vec<String> = payload
+        .commits
+        .iter()
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .collect();
+    if commits.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "At least one commit is required",
+        )));
+    }
+
+    let worktree_path = conflict_worktree_path(&deployment, &workspace, payload.repo_id).await?;
+
+    deployment.operation_status().set(OperationStatus::new(
+        workspace.id,
+        workspace.task_id,
+        OperationStatusType::CherryPicking,
+    ));
+    let result = deployment
+        .git()
+        .cherry_pick_commits(&worktree_path, &commits);
+    deployment.operation_status().clear(workspace.id);
+
+    let head_commit = match result {
+        Ok(head_commit) => head_commit,
+        Err(GitServiceError::MergeConflicts(message)) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::MergeConflicts {
+                    message,
+                    op: ConflictOp::CherryPick,
+                },
+            )));
+        }
+        Err(GitServiceError::RebaseInProgress)
+        | Err(GitServiceError::OperationInProgress(ConflictOp::Rebase)) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::RebaseInProgress,
+            )));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_cherry_picked",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": payload.repo_id.to_string(),
+                "commit_count": commits.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CherryPickResponse {
+        head_commit,
+    })))
 }
 
+/// Apply the task's own commits onto another branch, e.g. to backport a fix
+/// to a release branch. The task branch is left untouched.
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/cherry-pick-onto",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = CherryPickOntoRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<CherryPickOntoResponse, GitOperationError>)
+    )
+)]
+pub async fn cherry_pick_onto_branch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CherryPickOntoRequest>,
+) -> Result<ResponseJson<ApiResponse<CherryPickOntoResponse, GitOperationError>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let target_branch = payload.target_branch.trim();
+    if target_branch.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Target branch is required",
+        )));
+    }
+
+    let workspace_repo =
+        WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, payload.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    if target_branch == workspace.branch {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Target branch must differ from the task branch",
+        )));
+    }
+
+    let commits = deployment.git().commits_not_on(
+        &repo.path,
+        &workspace.branch,
+        &workspace_repo.target_branch,
+    )?;
+    if commits.is_empty() {
+        return Ok(ResponseJson(ApiResponse::error(
+            "The task branch has no commits to cherry-pick",
+        )));
+    }
+
+    deployment.operation_status().set(OperationStatus::new(
+        workspace.id,
+        workspace.task_id,
+        OperationStatusType::CherryPicking,
+    ));
+    let result = deployment
+        .git()
+        .cherry_pick_onto_branch(&repo.path, target_branch, &commits);
+    deployment.operation_status().clear(workspace.id);
+
+    let head_commit = match result {
+        Ok(head_commit) => head_commit,
+        Err(GitServiceError::MergeConflicts(message)) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::MergeConflicts {
+                    message,
+                    op: ConflictOp::CherryPick,
+                },
+            )));
+        }
+        Err(GitServiceError::RebaseInProgress)
+        | Err(GitServiceError::OperationInProgress(ConflictOp::Rebase)) => {
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::RebaseInProgress,
+            )));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_cherry_picked_onto_branch",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "repo_id": payload.repo_id.to_string(),
+                "commit_count": commits.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CherryPickOntoResponse {
+        head_commit,
+        commit_count: commits.len(),
+    })))
+}
+
+async fn conflict_worktree_path(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<PathBuf, ApiError> {
+    let repo = Repo::find_by_id(&deployment.db().pool, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    Ok(Path::new(&container_ref).join(&repo.name))
+}
+
+fn conflicts_response(
+    deployment: &DeploymentImpl,
+    worktree_path: &Path,
+) -> Result<ConflictsResponse, ApiError> {
+    let git = deployment.git();
+    Ok(ConflictsResponse {
+        op: git.detect_conflict_op(worktree_path)?,
+        files: git.list_conflicts(worktree_path)?,
+    })
+}
+
+/// Conflicted files of an interrupted merge, rebase, cherry-pick or revert
+#[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/conflicts",
+    tag = "task_attempts",
+    params(
+        ("id" = Uuid, Path),
+        ConflictsQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<ConflictsResponse>))
+)]
+pub async fn get_conflicts(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ConflictsQuery>,
+) -> Result<ResponseJson<ApiResponse<ConflictsResponse>>, ApiError> {
+    let worktree_path = conflict_worktree_path(&deployment, &workspace, query.repo_id).await?;
+    Ok(ResponseJson(ApiResponse::success(conflicts_response(
+        &deployment,
+        &worktree_path,
+    )?)))
+}
+
+/// Resolve one conflicted file and return the conflicts that remain
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/conflicts/resolve",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = ResolveConflictRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<ConflictsResponse>))
+)]
+pub async fn resolve_conflict(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictRequest>,
+) -> Result<ResponseJson<ApiResponse<ConflictsResponse>>, ApiError> {
+    let worktree_path = conflict_worktree_path(&deployment, &workspace, payload.repo_id).await?;
+    deployment
+        .git()
+        .resolve_conflict(&worktree_path, &payload.path, &payload.resolution)?;
+    Ok(ResponseJson(ApiResponse::success(conflicts_response(
+        &deployment,
+        &worktree_path,
+    )?)))
+}
+
+/// Continue the interrupted operation once every file is resolved. A rebase
+/// can stop on a later commit, so the response lists any new conflicts.
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/conflicts/continue",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = ContinueConflictsRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<ConflictsResponse>))
+)]
+pub async fn continue_conflicts(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ContinueConflictsRequest>,
+) -> Result<ResponseJson<ApiResponse<ConflictsResponse>>, ApiError> {
+    let worktree_path = conflict_worktree_path(&deployment, &workspace, payload.repo_id).await?;
+    deployment.git().continue_conflicts(&worktree_path)?;
+    Ok(ResponseJson(ApiResponse::success(conflicts_response(
+        &deployment,
+        &worktree_path,
+    )?)))
+}
+
+#[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/start-dev-server",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn start_dev_server(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1789,6 +2354,13 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/children",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskRelationships>))
+)]
 pub async fn get_task_attempt_children(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1819,6 +2391,13 @@ pub async fn get_task_attempt_children(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/stop",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn stop_task_attempt_execution(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1837,7 +2416,44 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/repair",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<RepoWorktreeRepair>>))
+)]
+pub async fn repair_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepoWorktreeRepair>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // Repair removes lock files regardless of age, which is only safe while git is idle
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Stop running processes before repairing the workspace".to_string(),
+        ));
+    }
+
+    let repairs = deployment.container().repair_container(&workspace).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_repaired",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "issues": repairs.iter().map(|r| r.repair.issues.len()).sum::<usize>(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(repairs)))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum RunScriptError {
@@ -1846,6 +2462,15 @@ pub enum RunScriptError {
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-setup-script",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExecutionProcess, RunScriptError>),
+    )
+)]
 pub async fn run_setup_script(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1931,6 +2556,15 @@ pub async fn run_setup_script(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-cleanup-script",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExecutionProcess, RunScriptError>),
+    )
+)]
 pub async fn run_cleanup_script(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2016,6 +2650,15 @@ pub async fn run_cleanup_script(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/gh-cli-setup",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ExecutionProcess, GhCliSetupError>),
+    )
+)]
 pub async fn gh_cli_setup_handler(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2052,6 +2695,15 @@ pub async fn gh_cli_setup_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/repos",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<RepoWithTargetBranch>>),
+    )
+)]
 pub async fn get_task_attempt_repos(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2068,7 +2720,7 @@ pub async fn get_task_attempt_repos(
 // Merge Queue Endpoints
 // ============================================================================
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct QueueMergeRequest {
     pub repo_id: Uuid,
     pub commit_message: Option<String>,
@@ -2076,7 +2728,7 @@ pub struct QueueMergeRequest {
     pub generate_commit_message: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum QueueMergeError {
@@ -2085,11 +2737,28 @@ pub enum QueueMergeError {
     AlreadyMerged,
     AlreadyQueued,
     WorkspaceRepoNotFound,
-    CommitMessageGenerationFailed { message: String },
+    CommitMessageGenerationFailed {
+        message: String,
+    },
+    /// Permission findings from executions must be acknowledged first
+    UnacknowledgedPermissionFindings {
+        #[ts(type = "number")]
+        count: i64,
+    },
 }
 
 /// POST /task-attempts/{id}/queue-merge - Queue a task attempt for merge
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/queue-merge",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = QueueMergeRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<MergeQueueEntry, QueueMergeError>),
+    )
+)]
 pub async fn queue_merge(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2108,7 +2777,7 @@ pub async fn queue_merge(
         .await?
         .ok_or(RepoError::NotFound)?;
 
-    // Check if already queued using in-memory store
+    // Check if already queued
     if merge_queue_store.get(workspace.id).is_some() {
         return Ok(ResponseJson(ApiResponse::error_with_data(
             QueueMergeError::AlreadyQueued,
@@ -2127,6 +2796,15 @@ pub async fn queue_merge(
         )));
     }
 
+    let open_findings = PermissionFinding::count_unacknowledged(pool, workspace.id).await?;
+    if open_findings > 0 {
+        return Ok(ResponseJson(ApiResponse::error_with_data(
+            QueueMergeError::UnacknowledgedPermissionFindings {
+                count: open_findings,
+            },
+        )));
+    }
+
     // Check commits ahead using git service
     let (commits_ahead, _commits_behind) = deployment.git().get_branch_status(
         &repo.path,
@@ -2196,7 +2874,7 @@ pub async fn queue_merge(
         build_fallback_commit_message(&task)
     };
 
-    // Create the merge queue entry in the in-memory store
+    // Create the merge queue entry
     let entry = merge_queue_store.enqueue(
         task.project_id,
         workspace.id,
@@ -2206,52 +2884,11 @@ pub async fn queue_merge(
 
     // Spawn background processor only if one isn't already running for this project.
     // This prevents race conditions where multiple processors could claim the same entries.
-    let project_id = task.project_id;
-    let should_spawn = {
-        let mut active = ACTIVE_MERGE_PROCESSORS.lock().unwrap();
-        if active.contains(&project_id) {
-            false
-        } else {
-            active.insert(project_id);
-            true
-        }
-    };
-
-    if should_spawn {
-        let processor_pool = pool.clone();
-        let processor_git = deployment.git().clone();
-        let processor_store = deployment.merge_queue_store().clone();
-        let processor_op_status = deployment.operation_status().clone();
-        let processor_config = deployment.config().clone();
-        let event_dispatcher = deployment.container().event_dispatch_callback();
-
-        tokio::spawn(async move {
-            let mut processor = MergeQueueProcessor::with_operation_status(
-                processor_pool,
-                processor_git,
-                processor_store,
-                processor_op_status,
-                processor_config,
-            );
-
-            // Attach event dispatcher if available for TaskStatusChanged events
-            if let Some(dispatcher) = event_dispatcher {
-                processor = processor.with_event_dispatcher(dispatcher);
-            }
-
-            if let Err(e) = processor.process_project_queue(project_id).await {
-                tracing::error!(
-                    %project_id,
-                    error = %e,
-                    "Failed to process merge queue"
-                );
-            }
-            // Always remove from active set when done, regardless of success/failure
-            if let Ok(mut active) = ACTIVE_MERGE_PROCESSORS.lock() {
-                active.remove(&project_id);
-            }
-        });
-    }
+    deployment.container().spawn_merge_queue_processor(
+        task.project_id,
+        merge_queue_store.clone(),
+        deployment.operation_status().clone(),
+    );
 
     deployment
         .track_if_analytics_allowed(
@@ -2269,6 +2906,13 @@ pub async fn queue_merge(
 
 /// DELETE /task-attempts/{id}/queue-merge - Cancel a queued merge
 #[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/api/task-attempts/{id}/queue-merge",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn cancel_queue_merge(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2289,6 +2933,13 @@ pub async fn cancel_queue_merge(
 
 /// DELETE /task-attempts/{id}/execution-queue - Cancel a queued execution
 #[axum::debug_handler]
+#[utoipa::path(
+    delete,
+    path = "/api/task-attempts/{id}/execution-queue",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 204, description = "No content"))
+)]
 pub async fn cancel_execution_queue(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2312,6 +2963,15 @@ pub async fn cancel_execution_queue(
 
 /// GET /task-attempts/{id}/queue-status - Get merge queue status for a workspace
 #[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/queue-status",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Option<MergeQueueEntry>>),
+    )
+)]
 pub async fn get_queue_status(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -2332,22 +2992,55 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/diff/content", get(diff_content::get_diff_file_content))
         .route("/git-status/ws", get(stream_git_status_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/generate-commit-message", post(generate_commit_message))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
+        .route("/conflicts", get(get_conflicts))
+        .route("/conflicts/resolve", post(resolve_conflict))
+        .route("/conflicts/continue", post(continue_conflicts))
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
+        .route("/cherry-pick", post(cherry_pick_task_attempt))
+        .route("/cherry-pick-onto", post(cherry_pick_onto_branch))
         .route("/pr", post(pr::create_github_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
+        .route("/repair", post(repair_task_attempt))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/repos", get(get_task_attempt_repos))
+        .route("/bundle", get(bundle::export_bundle))
+        .route(
+            "/stage",
+            post(stage::stage_changes).delete(stage::unstage_changes),
+        )
+        .route(
+            "/permission-findings",
+            get(permission_findings::get_permission_findings),
+        )
+        .route(
+            "/permission-findings/acknowledge",
+            post(permission_findings::acknowledge_permission_findings),
+        )
+        .route(
+            "/review-comments",
+            get(review_comments::get_review_comments).post(review_comments::create_review_comment),
+        )
+        .route(
+            "/review-comments/send",
+            post(review_comments::send_review_comments),
+        )
+        .route(
+            "/review-comments/{comment_id}",
+            patch(review_comments::update_review_comment)
+                .delete(review_comments::delete_review_comment),
+        )
         .route("/queue-merge", post(queue_merge).delete(cancel_queue_merge))
         .route("/queue-status", get(get_queue_status))
         .route("/execution-queue", delete(cancel_execution_queue))
@@ -2359,6 +3052,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
         .route("/stream/ws", get(stream_workspaces_ws))
+        .route(
+            "/import",
+            post(bundle::import_bundle).layer(DefaultBodyLimit::max(bundle::MAX_BUNDLE_SIZE)),
+        )
         .nest("/{id}", task_attempt_id_router)
         .nest("/{id}/images", images::router(deployment));
 
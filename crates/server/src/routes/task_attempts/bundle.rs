@@ -0,0 +1,122 @@
+use axum::{
+    Extension,
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+};
+use db::models::{operation::OperationKind, project::Project, workspace::Workspace};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::workspace_bundle::{self, ImportedWorkspace};
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::CurrentUser};
+
+/// Largest bundle that can be imported
+pub const MAX_BUNDLE_SIZE: usize = 512 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportBundleQuery {
+    /// Project the attempt is imported into; it must have a repository named
+    /// like every repository in the bundle
+    pub project_id: Uuid,
+}
+
+/// GET /api/task-attempts/:id/bundle - The attempt as a tarball that can be
+/// imported elsewhere: git bundles of its branch, its prompts, the normalized
+/// log and a manifest
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/bundle",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Gzipped tarball", content_type = "application/gzip"))
+)]
+pub async fn export_bundle(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    let bundle =
+        workspace_bundle::export_workspace(&deployment.db().pool, deployment.git(), &workspace)
+            .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_bundle_exported",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "size_bytes": bundle.len(),
+            }),
+        )
+        .await;
+
+    let filename = format!("{}.tar.gz", workspace.branch.replace(['/', '\\', '"'], "-"));
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/gzip")
+        .header(header::CONTENT_LENGTH, bundle.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(Body::from(bundle))
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+/// POST /api/task-attempts/import - Recreate an exported attempt as a new task
+/// with a workspace on the bundled branch. The body is the tarball.
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/import",
+    tag = "task_attempts",
+    params(ImportBundleQuery),
+    request_body(content = Vec<u8>, content_type = "application/gzip"),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<ImportedWorkspace>),
+        (status = 400, description = "Not a bundle, or a bundled repository is not in the project"),
+        (status = 409, description = "The bundled branch already exists")
+    )
+)]
+pub async fn import_bundle(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ImportBundleQuery>,
+    body: Bytes,
+) -> Result<ResponseJson<ApiResponse<ImportedWorkspace>>, ApiError> {
+    let project = Project::find_by_id(&deployment.db().pool, query.project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Project {} not found", query.project_id)))?;
+
+    let imported = deployment
+        .operations()
+        .track_for_user(
+            OperationKind::Import,
+            None,
+            None,
+            current_user.id(),
+            workspace_bundle::import_workspace(
+                &deployment.db().pool,
+                deployment.git(),
+                &project,
+                &body,
+            ),
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_bundle_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "workspace_id": imported.workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(imported)))
+}
@@ -0,0 +1,238 @@
+//! On-demand file contents for the diff view.
+//!
+//! The diff stream can send only paths and line counts (`stats_only`), which
+//! keeps large diffs cheap to open. The viewer then fetches the two sides of
+//! a file from here when it is expanded, optionally limited to a byte range.
+
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use axum::{
+    Extension,
+    body::Body,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use utoipa::IntoParams;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffContentSide {
+    /// The file at the workspace's base commit
+    Old,
+    /// The file as currently in the worktree
+    New,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffContentQuery {
+    /// Path as reported by the diff stream, prefixed with the repo name
+    pub path: String,
+    #[param(value_type = String)]
+    pub side: DiffContentSide,
+}
+
+/// GET /api/task-attempts/:id/diff/content - One side of a changed file.
+/// Supports single `Range: bytes=...` requests.
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/diff/content",
+    tag = "task_attempts",
+    params(
+        ("id" = uuid::Uuid, Path),
+        DiffContentQuery
+    ),
+    responses(
+        (status = 200, description = "File contents", content_type = "text/plain"),
+        (status = 206, description = "Requested byte range of the file"),
+        (status = 404, description = "The file does not exist on that side"),
+        (status = 416, description = "Range not satisfiable")
+    )
+)]
+pub async fn get_diff_file_content(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DiffContentQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let Some((repo_name, rel_path)) = query.path.split_once('/') else {
+        return Err(ApiError::BadRequest(format!(
+            "Path '{}' is not prefixed with a repo name",
+            query.path
+        )));
+    };
+
+    let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(
+        &deployment.db().pool,
+        workspace.id,
+    )
+    .await?;
+    let Some(repo) = repos.into_iter().find(|r| r.repo.name == repo_name) else {
+        return Err(ApiError::NotFound(format!(
+            "Repo '{repo_name}' is not part of this attempt"
+        )));
+    };
+
+    let content = match query.side {
+        DiffContentSide::Old => {
+            let base_commit = deployment.git().get_base_commit(
+                &repo.repo.path,
+                &workspace.branch,
+                &repo.target_branch,
+            )?;
+            deployment
+                .git()
+                .read_file_at_commit(&repo.repo.path, &base_commit, rel_path)?
+        }
+        DiffContentSide::New => {
+            let container_ref = deployment
+                .container()
+                .ensure_container_exists(&workspace)
+                .await?;
+            let worktree_path = PathBuf::from(container_ref).join(repo_name);
+            read_worktree_file(&worktree_path, rel_path).await
+        }
+    };
+    let Some(content) = content else {
+        return Err(ApiError::NotFound(format!(
+            "{} does not exist on the {} side",
+            query.path,
+            match query.side {
+                DiffContentSide::Old => "old",
+                DiffContentSide::New => "new",
+            }
+        )));
+    };
+
+    let range_header = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    content_response(content, range_header)
+}
+
+/// Contents of `rel_path` inside `worktree_path`, refusing paths that
+/// resolve outside the worktree
+async fn read_worktree_file(worktree_path: &Path, rel_path: &str) -> Option<Vec<u8>> {
+    let canonical_worktree = tokio::fs::canonicalize(worktree_path).await.ok()?;
+    let canonical_path = tokio::fs::canonicalize(worktree_path.join(rel_path))
+        .await
+        .ok()?;
+    if !canonical_path.starts_with(&canonical_worktree) {
+        return None;
+    }
+    tokio::fs::read(&canonical_path).await.ok()
+}
+
+fn content_response(content: Vec<u8>, range_header: Option<&str>) -> Result<Response, ApiError> {
+    let total = content.len();
+    // Same heuristic git uses to decide whether a blob is binary
+    let content_type = if content.iter().take(8000).any(|b| *b == 0) {
+        "application/octet-stream"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CACHE_CONTROL, "no-store");
+
+    // An empty body has no satisfiable range, so it is always sent whole
+    let range = range_header
+        .filter(|_| total > 0)
+        .map(|value| parse_range(value, total));
+    let response = match range {
+        None | Some(Ok(None)) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, total)
+            .body(Body::from(content)),
+        Some(Ok(Some(range))) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", range.start, range.end - 1, total),
+            )
+            .header(header::CONTENT_LENGTH, range.len())
+            .body(Body::from(content[range].to_vec())),
+        Some(Err(())) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{total}"))
+            .body(Body::empty()),
+    };
+    response.map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+/// Byte range selected by a `Range` header value for a body of `len` bytes.
+/// `Ok(None)` means the header should be ignored and the whole body sent:
+/// other units and multi-range requests are not supported.
+fn parse_range(value: &str, len: usize) -> Result<Option<Range<usize>>, ()> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        ("", "") => return Ok(None),
+        // Suffix range: the last `n` bytes
+        ("", suffix) => {
+            let n: usize = suffix.parse().map_err(|_| ())?;
+            if n == 0 {
+                return Err(());
+            }
+            len.saturating_sub(n)..len
+        }
+        (start, end) => {
+            let start: usize = start.parse().map_err(|_| ())?;
+            let end = match end {
+                "" => len,
+                end => {
+                    let end: usize = end.parse().map_err(|_| ())?;
+                    if end < start {
+                        return Err(());
+                    }
+                    end.saturating_add(1).min(len)
+                }
+            };
+            start..end
+        }
+    };
+
+    if range.start >= len {
+        return Err(());
+    }
+    Ok(Some(range))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_byte_ranges() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Ok(Some(0..100)));
+        assert_eq!(parse_range("bytes=900-", 1000), Ok(Some(900..1000)));
+        assert_eq!(parse_range("bytes=-100", 1000), Ok(Some(900..1000)));
+        assert_eq!(parse_range("bytes=0-4999", 1000), Ok(Some(0..1000)));
+        assert_eq!(parse_range("bytes=-5000", 1000), Ok(Some(0..1000)));
+        assert_eq!(parse_range("bytes=0-1,5-9", 1000), Ok(None));
+        assert_eq!(parse_range("items=0-1", 1000), Ok(None));
+        assert_eq!(parse_range("bytes=1000-", 1000), Err(()));
+        assert_eq!(parse_range("bytes=5-2", 1000), Err(()));
+        assert_eq!(parse_range("bytes=0-0", 0), Err(()));
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{permission_finding::PermissionFinding, workspace::Workspace};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct AcknowledgePermissionFindingsResponse {
+    #[ts(type = "number")]
+    pub acknowledged: u64,
+}
+
+/// GET /api/task-attempts/:id/permission-findings - Permission problems
+/// executions left in the workspace
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/permission-findings",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<PermissionFinding>>))
+)]
+pub async fn get_permission_findings(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PermissionFinding>>>, ApiError> {
+    let findings =
+        PermissionFinding::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(findings)))
+}
+
+/// POST /api/task-attempts/:id/permission-findings/acknowledge - Acknowledge
+/// the open findings so the workspace can be merged
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/permission-findings/acknowledge",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<AcknowledgePermissionFindingsResponse>))
+)]
+pub async fn acknowledge_permission_findings(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AcknowledgePermissionFindingsResponse>>, ApiError> {
+    let acknowledged =
+        PermissionFinding::acknowledge_all(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        AcknowledgePermissionFindingsResponse { acknowledged },
+    )))
+}
+
+/// Refuse to merge while the workspace has unacknowledged findings
+pub(crate) async fn ensure_no_open_findings(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+) -> Result<(), ApiError> {
+    let open = PermissionFinding::count_unacknowledged(&deployment.db().pool, workspace_id).await?;
+    if open > 0 {
+        return Err(ApiError::Conflict(format!(
+            "{open} permission finding(s) must be acknowledged before merging"
+        )));
+    }
+    Ok(())
+}
@@ -8,6 +8,7 @@ use axum::{
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     merge::{Merge, MergeStatus},
+    pr_draft_policy::PrDraftPolicy,
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
@@ -22,18 +23,24 @@ use executors::actions::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    bitbucket::BitbucketServiceError,
     container::ContainerService,
     domain_events::DomainEvent,
     git::{DiffTarget, GitCliError, GitServiceError},
+    git_remote::{GitRemoteError, GitRemoteProvider, GitRemoteProviderKind},
+    gitea::GiteaServiceError,
     github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    gitlab::GitLabServiceError,
+    prompt_templates,
 };
 use ts_rs::TS;
 use utils::{diff::create_unified_diff, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct CreateGitHubPrRequest {
     pub title: String,
     pub body: Option<String>,
@@ -44,7 +51,7 @@ pub struct CreateGitHubPrRequest {
     pub auto_generate_description: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum CreatePrError {
@@ -53,9 +60,12 @@ pub enum CreatePrError {
     GitCliNotLoggedIn,
     GitCliNotInstalled,
     TargetBranchNotFound { branch: String },
+    GitlabNotConfigured,
+    BitbucketNotConfigured,
+    GiteaNotConfigured,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
     pub pr_url: Option<String>,
@@ -63,17 +73,17 @@ pub struct AttachPrResponse {
     pub pr_status: Option<MergeStatus>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct AttachExistingPrRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct PrCommentsResponse {
     pub comments: Vec<UnifiedPrComment>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GetPrCommentsError {
@@ -82,7 +92,8 @@ pub enum GetPrCommentsError {
     GithubCliNotLoggedIn,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
@@ -100,6 +111,19 @@ Analyze the changes in this branch and write:
 
 Use `gh pr edit` to update the PR."#;
 
+pub const DEFAULT_MR_DESCRIPTION_PROMPT: &str = r#"Update the GitLab merge request that was just created with a better title and description.
+The MR number is !{pr_number} and the URL is {pr_url}.
+
+Analyze the changes in this branch and write:
+1. A concise, descriptive title that summarizes the changes, postfixed with "(Vibe Kanban)"
+2. A detailed description that explains:
+   - What changes were made
+   - Why they were made (based on the task context)
+   - Any important implementation details
+   - At the end, include a note: "This MR was written using [Vibe Kanban](https://vibekanban.com)"
+
+Use `glab mr update {pr_number}` to update the MR."#;
+
 pub const DEFAULT_COMMIT_MESSAGE_PROMPT: &str = r#"Generate a concise git commit message for the following changes.
 
 Task: {task_title}
@@ -120,13 +144,14 @@ async fn trigger_pr_description_follow_up(
     workspace: &Workspace,
     pr_number: i64,
     pr_url: &str,
+    default_prompt: &str,
 ) -> Result<(), ApiError> {
     // Get the custom prompt from config, or use default
     let config = deployment.config().read().await;
     let prompt_template = config
         .pr_auto_description_prompt
         .as_deref()
-        .unwrap_or(DEFAULT_PR_DESCRIPTION_PROMPT);
+        .unwrap_or(default_prompt);
 
     // Replace placeholders in prompt
     let prompt = prompt_template
@@ -247,25 +272,32 @@ pub async fn generate_commit_message_for_merge(
         .join("\n");
 
     // Get config values
-    let (prompt_template, executor_profile_from_config) = {
+    let (template_id, custom_prompt, executor_profile_from_config) = {
         let config = deployment.config().read().await;
-        let template = config
-            .commit_message_prompt
-            .clone()
-            .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE_PROMPT.to_string());
-        let profile = config.commit_message_executor_profile.clone();
-        (template, profile)
+        (
+            config.commit_message_template_id,
+            config.commit_message_prompt.clone(),
+            config.commit_message_executor_profile.clone(),
+        )
     }; // Lock released here
+    let prompt_template = prompt_templates::resolve(&deployment.db().pool, template_id)
+        .await
+        .or(custom_prompt)
+        .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE_PROMPT.to_string());
 
     // Build the prompt with task context
     let task_description = task
         .description
         .as_deref()
         .unwrap_or("No description provided");
-    let prompt = prompt_template
-        .replace("{task_title}", &task.title)
-        .replace("{task_description}", task_description)
-        .replace("{diff}", &diff_string);
+    let prompt = prompt_templates::render(
+        &prompt_template,
+        &[
+            ("task_title", task.title.as_str()),
+            ("task_description", task_description),
+            ("diff", diff_string.as_str()),
+        ],
+    );
 
     // Get or create a session for this operation
     let session =
@@ -338,6 +370,14 @@ pub async fn generate_commit_message_for_merge(
     Ok(execution_process)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/pr",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = CreateGitHubPrRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<String, CreatePrError>))
+)]
 pub async fn create_github_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -433,86 +473,143 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
+    // Without an explicit choice, the project's policy decides whether to open a draft
+    let draft = match request.draft {
+        Some(draft) => draft,
+        None => match workspace.parent_task(pool).await? {
+            Some(task) => {
+                PrDraftPolicy::find_or_default(pool, task.project_id)
+                    .await?
+                    .draft_by_default
+            }
+            None => false,
+        },
+    };
+
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
         body: request.body.clone(),
         head_branch: workspace.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
-        draft: request.draft,
+        draft: Some(draft),
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
-
-    // Use GitHubService to create the PR
-    let github_service = GitHubService::new()?;
-    match github_service.create_pr(&repo_info, &pr_request).await {
-        Ok(pr_info) => {
-            // Update the workspace with PR information
-            if let Err(e) = Merge::create_pr(
-                pool,
-                workspace.id,
-                workspace_repo.repo_id,
-                &norm_target_branch_name,
-                pr_info.number,
-                &pr_info.url,
-            )
-            .await
-            {
-                tracing::error!("Failed to update workspace PR status: {}", e);
-            }
-
-            // Auto-open PR in browser
-            if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
-                tracing::warn!("Failed to open PR in browser: {}", e);
-            }
-            deployment
-                .track_if_analytics_allowed(
-                    "github_pr_created",
-                    serde_json::json!({
-                        "workspace_id": workspace.id.to_string(),
-                    }),
-                )
-                .await;
+    // The host of the remote decides where the PR (or GitLab MR) is opened
+    let remote_url = deployment.git().get_remote_url(&repo_path)?;
+    let provider = GitRemoteProvider::require(&remote_url, &*deployment.config().read().await)?;
+    let kind = provider.kind();
 
-            // Trigger auto-description follow-up if enabled
-            if request.auto_generate_description
-                && let Err(e) = trigger_pr_description_follow_up(
-                    &deployment,
-                    &workspace,
-                    pr_info.number,
-                    &pr_info.url,
-                )
-                .await
-            {
-                tracing::warn!(
-                    "Failed to trigger PR description follow-up for attempt {}: {}",
-                    workspace.id,
-                    e
-                );
-            }
-
-            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
-        }
+    let pr_info = match provider
+        .create_pull_request(deployment.secrets(), &pr_request)
+        .await
+    {
+        Ok(pr_info) => pr_info,
         Err(e) => {
             tracing::error!(
-                "Failed to create GitHub PR for attempt {}: {}",
+                "Failed to create {} PR for attempt {}: {}",
+                kind.name(),
                 workspace.id,
                 e
             );
-            match &e {
-                GitHubServiceError::GhCliNotInstalled(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotInstalled),
-                )),
-                GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
-                    ApiResponse::error_with_data(CreatePrError::GithubCliNotLoggedIn),
-                )),
-                _ => Err(ApiError::GitHubService(e)),
-            }
+            let error = match &e {
+                GitRemoteError::GitHub(GitHubServiceError::GhCliNotInstalled(_)) => {
+                    CreatePrError::GithubCliNotInstalled
+                }
+                GitRemoteError::GitHub(GitHubServiceError::AuthFailed(_)) => {
+                    CreatePrError::GithubCliNotLoggedIn
+                }
+                GitRemoteError::GitLab(GitLabServiceError::NotConfigured) => {
+                    CreatePrError::GitlabNotConfigured
+                }
+                GitRemoteError::Bitbucket(BitbucketServiceError::NotConfigured) => {
+                    CreatePrError::BitbucketNotConfigured
+                }
+                GitRemoteError::Gitea(GiteaServiceError::NotConfigured) => {
+                    CreatePrError::GiteaNotConfigured
+                }
+                _ => return Err(ApiError::GitRemote(e)),
+            };
+            return Ok(ResponseJson(ApiResponse::error_with_data(error)));
         }
+    };
+
+    // Update the workspace with PR information
+    if let Err(e) = Merge::create_pr(
+        pool,
+        workspace.id,
+        workspace_repo.repo_id,
+        &norm_target_branch_name,
+        pr_info.number,
+        &pr_info.url,
+        draft,
+    )
+    .await
+    {
+        tracing::error!("Failed to update workspace PR status: {}", e);
+    }
+
+    // Auto-open PR in browser
+    if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
+        tracing::warn!("Failed to open PR in browser: {}", e);
+    }
+    deployment
+        .track_if_analytics_allowed(
+            match kind {
+                GitRemoteProviderKind::Github => "github_pr_created",
+                GitRemoteProviderKind::Gitlab => "gitlab_mr_created",
+                GitRemoteProviderKind::Bitbucket => "bitbucket_pr_created",
+                GitRemoteProviderKind::Gitea => "gitea_pr_created",
+            },
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    // Trigger auto-description follow-up if enabled. The default prompts lean
+    // on `gh` and `glab`; other hosts have no CLI an agent can be expected to
+    // have, so they only get one with a custom prompt.
+    let custom_prompt = deployment
+        .config()
+        .read()
+        .await
+        .pr_auto_description_prompt
+        .is_some();
+    let default_prompt = match kind {
+        GitRemoteProviderKind::Github => Some(DEFAULT_PR_DESCRIPTION_PROMPT),
+        GitRemoteProviderKind::Gitlab => Some(DEFAULT_MR_DESCRIPTION_PROMPT),
+        _ if custom_prompt => Some(DEFAULT_PR_DESCRIPTION_PROMPT),
+        _ => None,
+    };
+    if request.auto_generate_description
+        && let Some(default_prompt) = default_prompt
+        && let Err(e) = trigger_pr_description_follow_up(
+            &deployment,
+            &workspace,
+            pr_info.number,
+            &pr_info.url,
+            default_prompt,
+        )
+        .await
+    {
+        tracing::warn!(
+            "Failed to trigger PR description follow-up for attempt {}: {}",
+            workspace.id,
+            e
+        );
     }
+
+    Ok(ResponseJson(ApiResponse::success(pr_info.url)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/pr/attach",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = AttachExistingPrRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<AttachPrResponse>))
+)]
 pub async fn attach_existing_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -545,12 +642,11 @@ pub async fn attach_existing_pr(
         })));
     }
 
-    let github_service = GitHubService::new()?;
-    let repo_info = deployment.git().get_github_repo_info(&repo.path)?;
-
     // List all PRs for branch (open, closed, and merged)
-    let prs = github_service
-        .list_all_prs_for_branch(&repo_info, &workspace.branch)
+    let remote_url = deployment.git().get_remote_url(&repo.path)?;
+    let provider = GitRemoteProvider::require(&remote_url, &*deployment.config().read().await)?;
+    let prs = provider
+        .list_pull_requests_for_branch(deployment.secrets(), &workspace.branch)
         .await?;
 
     // Take the first PR (prefer open, but also accept merged/closed)
@@ -563,6 +659,7 @@ pub async fn attach_existing_pr(
             &workspace_repo.target_branch,
             pr_info.number,
             &pr_info.url,
+            false,
         )
         .await?;
 
@@ -626,6 +723,18 @@ pub async fn attach_existing_pr(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/pr/comments",
+    tag = "task_attempts",
+    params(
+        ("id" = Uuid, Path),
+        GetPrCommentsQuery
+    ),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<PrCommentsResponse, GetPrCommentsError>),
+    )
+)]
 pub async fn get_pr_comments(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -656,6 +765,14 @@ pub async fn get_pr_comments(
         }
     };
 
+    // Comments are only fetched from GitHub; other hosts show none
+    let provider = GitRemoteProvider::from_url(&pr_info.url, &*deployment.config().read().await);
+    if !matches!(provider, Some(GitRemoteProvider::GitHub(_))) {
+        return Ok(ResponseJson(ApiResponse::success(PrCommentsResponse {
+            comments: vec![],
+        })));
+    }
+
     let github_service = GitHubService::new()?;
     let repo_info = deployment.git().get_github_repo_info(&repo.path)?;
 
@@ -0,0 +1,223 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    review_comment::{CreateReviewComment, ReviewComment, UpdateReviewComment},
+    session::Session,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{container::ContainerService, review_comments};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::sessions::{FollowUpResult, resolve_executor_profile, start_follow_up},
+};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SendReviewCommentsRequest {
+    /// Sent before the comments
+    pub message: Option<String>,
+    pub variant: Option<String>,
+}
+
+fn validate_body(body: &str) -> Result<String, ApiError> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body cannot be empty".to_string(),
+        ));
+    }
+    Ok(body.to_string())
+}
+
+/// The comment, if it belongs to the workspace
+async fn find_workspace_comment(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    comment_id: Uuid,
+) -> Result<ReviewComment, ApiError> {
+    ReviewComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.workspace_id == workspace_id)
+        .ok_or_else(|| ApiError::NotFound(format!("Review comment {comment_id} not found")))
+}
+
+/// GET /api/task-attempts/:id/review-comments - Comments on the workspace's
+/// diff, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/review-comments",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<ReviewComment>>))
+)]
+pub async fn get_review_comments(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewComment>>>, ApiError> {
+    let comments = ReviewComment::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// POST /api/task-attempts/:id/review-comments - Comment on lines of the diff
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/review-comments",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = CreateReviewComment,
+    responses((status = 200, description = "Success", body = ApiResponse<ReviewComment>))
+)]
+pub async fn create_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    payload.body = validate_body(&payload.body)?;
+    payload.file_path = payload.file_path.trim().to_string();
+    if payload.file_path.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment file path cannot be empty".to_string(),
+        ));
+    }
+    if payload.line_start < 1 || payload.line_end.is_some_and(|end| end < payload.line_start) {
+        return Err(ApiError::BadRequest(
+            "Comment lines must start at 1 and end at or after the first line".to_string(),
+        ));
+    }
+
+    let comment = ReviewComment::create(&deployment.db().pool, workspace.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+/// PATCH /api/task-attempts/:id/review-comments/:comment_id - Edit or
+/// (un)resolve a comment
+#[utoipa::path(
+    patch,
+    path = "/api/task-attempts/{id}/review-comments/{comment_id}",
+    tag = "task_attempts",
+    params(
+        ("id" = Uuid, Path),
+        ("comment_id" = Uuid, Path)
+    ),
+    request_body = UpdateReviewComment,
+    responses((status = 200, description = "Success", body = ApiResponse<ReviewComment>))
+)]
+pub async fn update_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(mut payload): Json<UpdateReviewComment>,
+) -> Result<ResponseJson<ApiResponse<ReviewComment>>, ApiError> {
+    if let Some(body) = payload.body.as_deref() {
+        payload.body = Some(validate_body(body)?);
+    }
+    let comment = find_workspace_comment(&deployment, workspace.id, comment_id).await?;
+    let updated = ReviewComment::update(&deployment.db().pool, comment.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /api/task-attempts/:id/review-comments/:comment_id
+#[utoipa::path(
+    delete,
+    path = "/api/task-attempts/{id}/review-comments/{comment_id}",
+    tag = "task_attempts",
+    params(
+        ("id" = Uuid, Path),
+        ("comment_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = find_workspace_comment(&deployment, workspace.id, comment_id).await?;
+    ReviewComment::delete(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /api/task-attempts/:id/review-comments/send - Send the unresolved
+/// comments to the agent as a follow-up in the workspace's latest session,
+/// and resolve them
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/review-comments/send",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = SendReviewCommentsRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<FollowUpResult>),
+        (status = 400, description = "No unresolved comments and no message to send")
+    )
+)]
+pub async fn send_review_comments(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendReviewCommentsRequest>,
+) -> Result<ResponseJson<ApiResponse<FollowUpResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let comments = ReviewComment::find_unresolved_by_workspace_id(pool, workspace.id).await?;
+    let Some(prompt) = review_comments::compile_prompt(&comments, payload.message.as_deref())
+    else {
+        return Err(ApiError::BadRequest(
+            "There are no unresolved review comments to send".to_string(),
+        ));
+    };
+
+    let session = Session::find_latest_by_workspace_id(pool, workspace.id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("The workspace has no session to send comments to".to_string())
+        })?;
+    let project = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let executor_profile_id =
+        resolve_executor_profile(pool, session.id, payload.variant.as_deref()).await?;
+    let result = start_follow_up(
+        &deployment,
+        &session,
+        &workspace,
+        project.id,
+        executor_profile_id,
+        prompt,
+    )
+    .await?;
+
+    let sent: Vec<Uuid> = comments.iter().map(|comment| comment.id).collect();
+    ReviewComment::resolve_many(pool, &sent).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "review_comments_sent",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "comment_count": sent.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
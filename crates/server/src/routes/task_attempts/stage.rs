@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    repo::{Repo, RepoError},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct StageChangesRequest {
+    pub repo_id: Uuid,
+    /// Files staged with all their changes, relative to the repository root
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Unified diff of the hunks to stage, like the ones picked in `git add -p`
+    #[serde(default)]
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnstageChangesQuery {
+    pub repo_id: Uuid,
+}
+
+/// What the next auto-commit of a repo will include
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct StagedChanges {
+    pub repo_id: Uuid,
+    /// Paths with staged changes, relative to the repository root
+    pub paths: Vec<String>,
+}
+
+/// Worktree of the workspace's repo `repo_id`
+async fn repo_worktree(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+) -> Result<PathBuf, ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace_repo = WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace.id, repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let repo = Repo::find_by_id(pool, workspace_repo.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(workspace)
+        .await?;
+    Ok(Path::new(&container_ref).join(&repo.name))
+}
+
+/// POST /api/task-attempts/:id/stage - Choose the files and hunks the repo's
+/// next auto-commit includes. Replaces any earlier selection; everything else
+/// stays uncommitted for the next turn.
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/stage",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = StageChangesRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<StagedChanges>),
+        (status = 400, description = "Nothing selected, or the patch does not apply")
+    )
+)]
+pub async fn stage_changes(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<StageChangesRequest>,
+) -> Result<ResponseJson<ApiResponse<StagedChanges>>, ApiError> {
+    let has_patch = request
+        .patch
+        .as_deref()
+        .is_some_and(|patch| !patch.trim().is_empty());
+    if request.paths.is_empty() && !has_patch {
+        return Err(ApiError::BadRequest(
+            "Select at least one file or hunk to stage".to_string(),
+        ));
+    }
+
+    let worktree_path = repo_worktree(&deployment, &workspace, request.repo_id).await?;
+    let paths = deployment.git().stage_selection(
+        &worktree_path,
+        &request.paths,
+        request.patch.as_deref(),
+    )?;
+
+    let staged_selections = deployment.container().staged_selections();
+    if paths.is_empty() {
+        staged_selections.remove(workspace.id, request.repo_id);
+    } else {
+        staged_selections.insert(workspace.id, request.repo_id);
+    }
+
+    Ok(ResponseJson(ApiResponse::success(StagedChanges {
+        repo_id: request.repo_id,
+        paths,
+    })))
+}
+
+/// DELETE /api/task-attempts/:id/stage - Drop the repo's selection; its next
+/// auto-commit includes every change again
+#[utoipa::path(
+    delete,
+    path = "/api/task-attempts/{id}/stage",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path), UnstageChangesQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn unstage_changes(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<UnstageChangesQuery>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let worktree_path = repo_worktree(&deployment, &workspace, query.repo_id).await?;
+    deployment.git().unstage_all(&worktree_path)?;
+    deployment
+        .container()
+        .staged_selections()
+        .remove(workspace.id, query.repo_id);
+    Ok(ResponseJson(ApiResponse::success(())))
+}
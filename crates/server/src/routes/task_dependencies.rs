@@ -15,41 +15,44 @@ use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
 
 const DEFAULT_MAX_DEPTH: u32 = 5;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AddDependencyRequest {
     pub depends_on_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DependencyDirection {
     BlockedBy,
     Blocking,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DependencyListQuery {
     pub direction: Option<DependencyDirection>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DependencyTreeQuery {
     pub max_depth: Option<u32>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskDependencyTreeNode {
     pub task: Task,
     pub dependencies: Vec<TaskDependencyTreeNode>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TaskDependencyContext {
     pub ancestors: Vec<Task>,
     pub descendants: Vec<Task>,
@@ -63,6 +66,16 @@ pub struct DependencyPath {
 
 type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/dependencies",
+    tag = "task_dependencies",
+    params(
+        ("task_id" = Uuid, Path),
+        DependencyListQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Task>>))
+)]
 pub async fn get_dependencies(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -79,6 +92,14 @@ pub async fn get_dependencies(
     Ok(ResponseJson(ApiResponse::success(dependencies)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/dependencies",
+    tag = "task_dependencies",
+    params(("task_id" = Uuid, Path)),
+    request_body = AddDependencyRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskDependency>))
+)]
 pub async fn add_dependency(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -110,6 +131,16 @@ pub async fn add_dependency(
     Ok(ResponseJson(ApiResponse::success(dependency)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/dependencies/{dep_id}",
+    tag = "task_dependencies",
+    params(
+        ("task_id" = Uuid, Path),
+        ("dep_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn remove_dependency(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -125,6 +156,16 @@ pub async fn remove_dependency(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/dependency-tree",
+    tag = "task_dependencies",
+    params(
+        ("task_id" = Uuid, Path),
+        DependencyTreeQuery
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskDependencyTreeNode>))
+)]
 pub async fn get_dependency_tree(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -138,6 +179,13 @@ pub async fn get_dependency_tree(
     Ok(ResponseJson(ApiResponse::success(tree)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/dependency-context",
+    tag = "task_dependencies",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskDependencyContext>))
+)]
 pub async fn get_dependency_context(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
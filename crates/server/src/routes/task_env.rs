@@ -0,0 +1,99 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    task::Task,
+    task_env_var::{TaskEnvVar, UpsertTaskEnvVar},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+
+/// GET /api/tasks/:id/env - The task's env vars, secret values masked
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/env",
+    tag = "task_env",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TaskEnvVar>>))
+)]
+pub async fn get_task_env(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskEnvVar>>>, ApiError> {
+    let vars = TaskEnvVar::find_by_task_id(&deployment.db().pool, task.id)
+        .await?
+        .into_iter()
+        .map(TaskEnvVar::masked)
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(vars)))
+}
+
+/// PUT /api/tasks/:task_id/env/:name - Set a variable for the task's next executions
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/env/{name}",
+    tag = "task_env",
+    params(
+        ("task_id" = Uuid, Path),
+        ("name" = String, Path)
+    ),
+    request_body = UpsertTaskEnvVar,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskEnvVar>))
+)]
+pub async fn upsert_task_env_var(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, name)): Path<(Uuid, String)>,
+    Json(payload): Json<UpsertTaskEnvVar>,
+) -> Result<ResponseJson<ApiResponse<TaskEnvVar>>, ApiError> {
+    if !TaskEnvVar::is_valid_name(&name) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid variable name '{name}': use letters, digits and underscores"
+        )));
+    }
+
+    let var = TaskEnvVar::upsert(&deployment.db().pool, task.id, &name, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(var.masked())))
+}
+
+/// DELETE /api/tasks/:task_id/env/:name
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/env/{name}",
+    tag = "task_env",
+    params(
+        ("task_id" = Uuid, Path),
+        ("name" = String, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_task_env_var(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, name)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = TaskEnvVar::delete(&deployment.db().pool, task.id, &name).await?;
+    if rows == 0 {
+        return Err(ApiError::NotFound(format!("Variable '{name}' not found")));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_env = Router::new()
+        .route("/env", get(get_task_env))
+        .route(
+            "/env/{name}",
+            put(upsert_task_env_var).delete(delete_task_env_var),
+        )
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new().nest("/tasks/{task_id}", task_env)
+}
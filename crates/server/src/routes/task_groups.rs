@@ -14,17 +14,19 @@ use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use super::projects::MergeQueueCountResponse;
 use crate::{DeploymentImpl, error::ApiError, middleware::load_task_group_middleware};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListTaskGroupsQuery {
     pub project_id: Uuid,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateTaskGroupRequest {
     pub project_id: Uuid,
     pub name: String,
@@ -32,33 +34,43 @@ pub struct CreateTaskGroupRequest {
     pub base_branch: Option<String>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct UpdateTaskGroupRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub base_branch: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct BulkAssignTasksRequest {
     pub task_ids: Vec<Uuid>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct BulkAssignTasksResponse {
     pub updated_count: u64,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct MergeTaskGroupRequest {
     pub target_group_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetTaskGroupStatsQuery {
     pub project_id: Uuid,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-groups/stats",
+    tag = "task_groups",
+    params(GetTaskGroupStatsQuery),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<Vec<TaskGroupWithStats>>),
+    )
+)]
 pub async fn get_task_group_stats(
     State(deployment): State<DeploymentImpl>,
     axum::extract::Query(query): axum::extract::Query<GetTaskGroupStatsQuery>,
@@ -67,6 +79,13 @@ pub async fn get_task_group_stats(
     Ok(ResponseJson(ApiResponse::success(stats)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-groups",
+    tag = "task_groups",
+    params(ListTaskGroupsQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TaskGroup>>))
+)]
 pub async fn list_task_groups(
     State(deployment): State<DeploymentImpl>,
     axum::extract::Query(query): axum::extract::Query<ListTaskGroupsQuery>,
@@ -75,12 +94,26 @@ pub async fn list_task_groups(
     Ok(ResponseJson(ApiResponse::success(groups)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-groups/{group_id}",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskGroup>))
+)]
 pub async fn get_task_group(
     Extension(task_group): Extension<TaskGroup>,
 ) -> Result<ResponseJson<ApiResponse<TaskGroup>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(task_group)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-groups",
+    tag = "task_groups",
+    request_body = CreateTaskGroupRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskGroup>))
+)]
 pub async fn create_task_group(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskGroupRequest>,
@@ -97,6 +130,14 @@ pub async fn create_task_group(
     Ok(ResponseJson(ApiResponse::success(task_group)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/task-groups/{group_id}",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    request_body = UpdateTaskGroupRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskGroup>))
+)]
 pub async fn update_task_group(
     Extension(existing): Extension<TaskGroup>,
     State(deployment): State<DeploymentImpl>,
@@ -115,6 +156,13 @@ pub async fn update_task_group(
     Ok(ResponseJson(ApiResponse::success(task_group)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/task-groups/{group_id}",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
 pub async fn delete_task_group(
     Extension(task_group): Extension<TaskGroup>,
     State(deployment): State<DeploymentImpl>,
@@ -128,6 +176,16 @@ pub async fn delete_task_group(
     Ok((StatusCode::OK, ResponseJson(ApiResponse::success(()))))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-groups/{group_id}/assign",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    request_body = BulkAssignTasksRequest,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<BulkAssignTasksResponse>),
+    )
+)]
 pub async fn bulk_assign_tasks(
     Extension(task_group): Extension<TaskGroup>,
     State(deployment): State<DeploymentImpl>,
@@ -152,6 +210,14 @@ pub async fn bulk_assign_tasks(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-groups/{group_id}/merge",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    request_body = MergeTaskGroupRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskGroup>))
+)]
 pub async fn merge_task_group(
     Extension(source_group): Extension<TaskGroup>,
     State(deployment): State<DeploymentImpl>,
@@ -179,6 +245,15 @@ pub async fn merge_task_group(
 }
 
 /// GET /api/task-groups/:id/merge-queue-count - Get the number of entries in the merge queue for a task group
+#[utoipa::path(
+    get,
+    path = "/api/task-groups/{group_id}/merge-queue-count",
+    tag = "task_groups",
+    params(("group_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<MergeQueueCountResponse>),
+    )
+)]
 pub async fn get_merge_queue_count(
     Extension(task_group): Extension<TaskGroup>,
     State(deployment): State<DeploymentImpl>,
@@ -0,0 +1,206 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::{
+    task::Task,
+    task_link::{CreateTaskLink, TaskLink, UpdateTaskLink},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::link_unfurl::{LinkTarget, LinkUnfurler};
+use url::Url;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
+    routes::settings::get_github_token,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TaskLinkPath {
+    pub task_id: Uuid,
+    pub link_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/links",
+    tag = "task_links",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TaskLink>>))
+)]
+pub async fn get_task_links(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLink>>>, ApiError> {
+    let links = TaskLink::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/links",
+    tag = "task_links",
+    params(("task_id" = Uuid, Path)),
+    request_body = CreateTaskLink,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskLink>))
+)]
+pub async fn create_task_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateTaskLink>,
+) -> Result<ResponseJson<ApiResponse<TaskLink>>, ApiError> {
+    payload.url = payload.url.trim().to_string();
+    match Url::parse(&payload.url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Link must be an http(s) URL".to_string(),
+            ));
+        }
+    }
+    payload.title = payload
+        .title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+
+    let kind = payload
+        .kind
+        .or_else(|| LinkTarget::parse(&payload.url).map(|target| target.kind()))
+        .unwrap_or_default();
+
+    let link = TaskLink::create(&deployment.db().pool, task.id, &payload, kind)
+        .await
+        .map_err(|err| match err.as_database_error() {
+            Some(db_err) if db_err.is_unique_violation() => {
+                ApiError::Conflict("Task already has this link".to_string())
+            }
+            _ => ApiError::Database(err),
+        })?;
+
+    let link = unfurl(&deployment, link).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/links/{link_id}",
+    tag = "task_links",
+    params(
+        ("task_id" = Uuid, Path),
+        ("link_id" = Uuid, Path)
+    ),
+    request_body = UpdateTaskLink,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskLink>))
+)]
+pub async fn update_task_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskLinkPath>,
+    Json(payload): Json<UpdateTaskLink>,
+) -> Result<ResponseJson<ApiResponse<TaskLink>>, ApiError> {
+    let pool = &deployment.db().pool;
+    find_task_link(pool, &task, params.link_id).await?;
+    let link = TaskLink::update(pool, params.link_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/links/{link_id}",
+    tag = "task_links",
+    params(
+        ("task_id" = Uuid, Path),
+        ("link_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_task_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskLinkPath>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    find_task_link(pool, &task, params.link_id).await?;
+    TaskLink::delete(pool, params.link_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Fetch the link's title and status again
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/links/{link_id}/refresh",
+    tag = "task_links",
+    params(
+        ("task_id" = Uuid, Path),
+        ("link_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskLink>))
+)]
+pub async fn refresh_task_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskLinkPath>,
+) -> Result<ResponseJson<ApiResponse<TaskLink>>, ApiError> {
+    let link = find_task_link(&deployment.db().pool, &task, params.link_id).await?;
+    let link = unfurl(&deployment, link).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+async fn find_task_link(
+    pool: &sqlx::SqlitePool,
+    task: &Task,
+    link_id: Uuid,
+) -> Result<TaskLink, ApiError> {
+    TaskLink::find_by_id(pool, link_id)
+        .await?
+        .filter(|link| link.task_id == task.id)
+        .ok_or_else(|| ApiError::NotFound("Task link not found".to_string()))
+}
+
+/// Store what the linked service says about `link`. Unfurling is best
+/// effort: on failure the link is returned as it was.
+async fn unfurl(deployment: &DeploymentImpl, link: TaskLink) -> Result<TaskLink, ApiError> {
+    let pool = &deployment.db().pool;
+    let github_token = get_github_token(pool).await.ok().flatten();
+    let linear_api_key = deployment.config().read().await.linear.api_key.clone();
+    let linear_api_key = deployment
+        .secrets()
+        .resolve_opt(linear_api_key.as_ref())
+        .await;
+    let unfurler = LinkUnfurler::new(github_token, linear_api_key)
+        .map_err(|e| ApiError::Internal(format!("Failed to create HTTP client: {e}")))?;
+
+    match unfurler.unfurl(&link.url).await {
+        Ok(Some(unfurled)) => Ok(TaskLink::set_unfurled(
+            pool,
+            link.id,
+            unfurled.title.as_deref(),
+            unfurled.status.as_deref(),
+        )
+        .await?),
+        Ok(None) => Ok(link),
+        Err(e) => {
+            tracing::warn!("Failed to unfurl task link {}: {}", link.url, e);
+            Ok(link)
+        }
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_links = Router::new()
+        .route("/links", get(get_task_links).post(create_task_link))
+        .route(
+            "/links/{link_id}",
+            put(update_task_link).delete(delete_task_link),
+        )
+        .route("/links/{link_id}/refresh", post(refresh_task_link))
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new().nest("/tasks/{task_id}", task_links)
+}
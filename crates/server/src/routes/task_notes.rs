@@ -0,0 +1,147 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    scratch::{Scratch, ScratchPayload, ScratchType},
+    task::Task,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+
+/// Notes are meant to be read by humans; anything larger is a mistake
+const MAX_NOTES_LEN: usize = 100_000;
+
+/// A task's review notes
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct TaskNotes {
+    /// Markdown
+    pub content: String,
+    /// Pass back when saving; 0 while the task has no notes
+    #[ts(type = "number")]
+    pub version: i64,
+    #[ts(type = "Date | null")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<Option<Scratch>> for TaskNotes {
+    fn from(scratch: Option<Scratch>) -> Self {
+        match scratch {
+            Some(Scratch {
+                payload: ScratchPayload::TaskNotes(content),
+                version,
+                updated_at,
+                ..
+            }) => TaskNotes {
+                content,
+                version,
+                updated_at: Some(updated_at),
+            },
+            _ => TaskNotes {
+                content: String::new(),
+                version: 0,
+                updated_at: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateTaskNotes {
+    pub content: String,
+    /// The version the edit was based on
+    #[ts(type = "number")]
+    pub version: i64,
+}
+
+/// GET /api/tasks/:id/notes - The task's review notes
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/notes",
+    tag = "task_notes",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskNotes>))
+)]
+pub async fn get_task_notes(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskNotes>>, ApiError> {
+    let scratch =
+        Scratch::find_by_id(&deployment.db().pool, task.id, &ScratchType::TaskNotes).await?;
+    Ok(ResponseJson(ApiResponse::success(scratch.into())))
+}
+
+/// PUT /api/tasks/:id/notes - Save the task's review notes, unless someone
+/// else saved them since the given version
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/notes",
+    tag = "task_notes",
+    params(("task_id" = Uuid, Path)),
+    request_body = UpdateTaskNotes,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<TaskNotes>),
+        (status = 409, description = "The notes were saved by someone else first")
+    )
+)]
+pub async fn update_task_notes(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskNotes>,
+) -> Result<ResponseJson<ApiResponse<TaskNotes>>, ApiError> {
+    if payload.content.len() > MAX_NOTES_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Notes must be at most {MAX_NOTES_LEN} bytes"
+        )));
+    }
+
+    let scratch = Scratch::save_if_version(
+        &deployment.db().pool,
+        task.id,
+        &ScratchPayload::TaskNotes(payload.content),
+        payload.version,
+    )
+    .await?
+    .ok_or_else(|| {
+        ApiError::Conflict(
+            "The notes were changed elsewhere; reload them before saving".to_string(),
+        )
+    })?;
+    Ok(ResponseJson(ApiResponse::success(Some(scratch).into())))
+}
+
+/// DELETE /api/tasks/:id/notes - Clear the task's review notes
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/notes",
+    tag = "task_notes",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskNotes>))
+)]
+pub async fn delete_task_notes(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskNotes>>, ApiError> {
+    Scratch::delete(&deployment.db().pool, task.id, &ScratchType::TaskNotes).await?;
+    Ok(ResponseJson(ApiResponse::success(None.into())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_notes = Router::new()
+        .route(
+            "/notes",
+            get(get_task_notes)
+                .put(update_task_notes)
+                .delete(delete_task_notes),
+        )
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new().nest("/tasks/{task_id}", task_notes)
+}
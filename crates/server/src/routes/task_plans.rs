@@ -0,0 +1,155 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    project::Project, project_repo::ProjectRepo, workspace_repo::CreateWorkspaceRepo,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::Deserialize;
+use services::services::task_plan::{self, CreatedPlan, PlanStart, PlannedTask, TaskPlanError};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_project_middleware},
+    routes::{task_attempts::WorkspaceRepoInput, tasks::validate_task_group_id},
+};
+
+/// A plan given either as Markdown or as a list of tasks
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct BulkCreateTasksRequest {
+    /// Headings and checklists, parsed into tasks
+    pub markdown: Option<String>,
+    pub tasks: Option<Vec<PlannedTask>>,
+    /// Group every created task belongs to
+    pub task_group_id: Option<Uuid>,
+    /// Start the tasks nothing unfinished blocks
+    pub start: Option<StartPlannedTasks>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct StartPlannedTasks {
+    #[schema(value_type = Object)]
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<WorkspaceRepoInput>,
+    /// Start at most this many, in plan order
+    pub limit: Option<usize>,
+}
+
+fn map_plan_error(error: TaskPlanError) -> ApiError {
+    match error {
+        TaskPlanError::Database(err) => ApiError::Database(err),
+        TaskPlanError::Container(err) => ApiError::Container(err),
+        other => ApiError::BadRequest(other.to_string()),
+    }
+}
+
+/// POST /api/projects/:id/tasks/bulk - Create tasks and their dependencies
+/// from a plan
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/tasks/bulk",
+    tag = "tasks",
+    params(("project_id" = Uuid, Path)),
+    request_body = BulkCreateTasksRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<CreatedPlan>))
+)]
+pub async fn bulk_create_tasks(
+    Extension(project): Extension<Project>,
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BulkCreateTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<CreatedPlan>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let tasks = match (payload.tasks, payload.markdown) {
+        (Some(tasks), None) => tasks,
+        (None, Some(markdown)) => task_plan::parse_markdown(&markdown),
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Provide either `markdown` or `tasks`".to_string(),
+            ));
+        }
+    };
+    // Fail before anything is created
+    task_plan::resolve_dependencies(&tasks).map_err(map_plan_error)?;
+
+    if let Some(task_group_id) = payload.task_group_id {
+        validate_task_group_id(pool, task_group_id, project.id).await?;
+    }
+
+    let start = match payload.start {
+        Some(start) => {
+            if start.repos.is_empty() {
+                return Err(ApiError::BadRequest(
+                    "At least one repository is required".to_string(),
+                ));
+            }
+            let project_repos = ProjectRepo::find_by_project_id(pool, project.id).await?;
+            if let Some(repo) = start
+                .repos
+                .iter()
+                .find(|repo| !project_repos.iter().any(|pr| pr.repo_id == repo.repo_id))
+            {
+                return Err(ApiError::BadRequest(format!(
+                    "Repository {} is not part of this project",
+                    repo.repo_id
+                )));
+            }
+            Some(PlanStart {
+                executor_profile_id: start.executor_profile_id,
+                repos: start
+                    .repos
+                    .into_iter()
+                    .map(|repo| CreateWorkspaceRepo {
+                        repo_id: repo.repo_id,
+                        target_branch: repo.target_branch,
+                    })
+                    .collect(),
+                limit: start.limit,
+            })
+        }
+        None => None,
+    };
+
+    let plan = task_plan::create_plan(
+        deployment.container(),
+        &project,
+        &tasks,
+        payload.task_group_id,
+        current_user.id(),
+        start.as_ref(),
+    )
+    .await
+    .map_err(map_plan_error)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_bulk_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "task_count": plan.tasks.len(),
+                "dependency_count": plan.dependencies.len(),
+                "started_count": plan.started_task_ids.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let plans = Router::new()
+        .route("/tasks/bulk", post(bulk_create_tasks))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", plans)
+}
@@ -0,0 +1,245 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use chrono::Utc;
+use db::models::{
+    project::Project,
+    project_repo::ProjectRepo,
+    task::Task,
+    task_schedule::{CreateTaskSchedule, ScheduleRepo, TaskSchedule, UpdateTaskSchedule},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::scheduler::{self, SchedulerError};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct TaskSchedulePath {
+    pub project_id: Uuid,
+    pub schedule_id: Uuid,
+}
+
+fn map_scheduler_error(error: SchedulerError) -> ApiError {
+    match error {
+        SchedulerError::Database(err) => ApiError::Database(err),
+        SchedulerError::Container(err) => ApiError::Container(err),
+        other => ApiError::BadRequest(other.to_string()),
+    }
+}
+
+/// Validate a schedule's settings, returning its first run time (None when
+/// disabled)
+async fn validate_schedule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    cron: &str,
+    task_title: &str,
+    repos: &[ScheduleRepo],
+    enabled: bool,
+) -> Result<Option<chrono::DateTime<Utc>>, ApiError> {
+    scheduler::validate_cron(cron).map_err(map_scheduler_error)?;
+    if task_title.trim().is_empty() {
+        return Err(ApiError::BadRequest("Task title is required".to_string()));
+    }
+    if repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+    let project_repos = ProjectRepo::find_by_project_id(&deployment.db().pool, project_id).await?;
+    if let Some(repo) = repos
+        .iter()
+        .find(|repo| !project_repos.iter().any(|pr| pr.repo_id == repo.repo_id))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Repository {} is not part of this project",
+            repo.repo_id
+        )));
+    }
+
+    if !enabled {
+        return Ok(None);
+    }
+    scheduler::next_run(cron, Utc::now())
+        .map(Some)
+        .map_err(map_scheduler_error)
+}
+
+async fn find_schedule(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    schedule_id: Uuid,
+) -> Result<TaskSchedule, ApiError> {
+    TaskSchedule::find_by_id(&deployment.db().pool, schedule_id)
+        .await?
+        .filter(|schedule| schedule.project_id == project.id)
+        .ok_or_else(|| ApiError::NotFound("Schedule not found".to_string()))
+}
+
+/// GET /api/projects/:id/schedules
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/schedules",
+    tag = "task_schedules",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TaskSchedule>>))
+)]
+pub async fn get_schedules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskSchedule>>>, ApiError> {
+    let schedules = TaskSchedule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedules)))
+}
+
+/// POST /api/projects/:id/schedules
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/schedules",
+    tag = "task_schedules",
+    params(("project_id" = Uuid, Path)),
+    request_body = CreateTaskSchedule,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskSchedule>))
+)]
+pub async fn create_schedule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let next_run_at = validate_schedule(
+        &deployment,
+        project.id,
+        &payload.cron,
+        &payload.task_title,
+        &payload.repos,
+        payload.enabled,
+    )
+    .await?;
+
+    let schedule =
+        TaskSchedule::create(&deployment.db().pool, project.id, &payload, next_run_at).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+/// PUT /api/projects/:id/schedules/:schedule_id
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/schedules/{schedule_id}",
+    tag = "task_schedules",
+    params(
+        ("project_id" = Uuid, Path),
+        ("schedule_id" = Uuid, Path)
+    ),
+    request_body = UpdateTaskSchedule,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskSchedule>))
+)]
+pub async fn update_schedule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskSchedulePath>,
+    Json(payload): Json<UpdateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    find_schedule(&deployment, &project, params.schedule_id).await?;
+    let next_run_at = validate_schedule(
+        &deployment,
+        project.id,
+        &payload.cron,
+        &payload.task_title,
+        &payload.repos,
+        payload.enabled,
+    )
+    .await?;
+
+    let schedule = TaskSchedule::update(
+        &deployment.db().pool,
+        params.schedule_id,
+        &payload,
+        next_run_at,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+/// DELETE /api/projects/:id/schedules/:schedule_id
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/schedules/{schedule_id}",
+    tag = "task_schedules",
+    params(
+        ("project_id" = Uuid, Path),
+        ("schedule_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_schedule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskSchedulePath>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_schedule(&deployment, &project, params.schedule_id).await?;
+    TaskSchedule::delete(&deployment.db().pool, params.schedule_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /api/projects/:id/schedules/:schedule_id/run - Run once now, keeping
+/// the schedule's next run
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/schedules/{schedule_id}/run",
+    tag = "task_schedules",
+    params(
+        ("project_id" = Uuid, Path),
+        ("schedule_id" = Uuid, Path)
+    ),
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
+pub async fn run_schedule_now(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path(params): Path<TaskSchedulePath>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let schedule = find_schedule(&deployment, &project, params.schedule_id).await?;
+    let pool = &deployment.db().pool;
+
+    match scheduler::run_schedule(deployment.container(), &schedule).await {
+        Ok(task) => {
+            TaskSchedule::record_run(pool, schedule.id, Some(task.id), None, schedule.next_run_at)
+                .await?;
+            Ok(ResponseJson(ApiResponse::success(task)))
+        }
+        Err(e) => {
+            TaskSchedule::record_run(
+                pool,
+                schedule.id,
+                None,
+                Some(&e.to_string()),
+                schedule.next_run_at,
+            )
+            .await?;
+            Err(map_scheduler_error(e))
+        }
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let schedules = Router::new()
+        .route("/schedules", get(get_schedules).post(create_schedule))
+        .route(
+            "/schedules/{schedule_id}",
+            put(update_schedule).delete(delete_schedule),
+        )
+        .route("/schedules/{schedule_id}/run", post(run_schedule_now))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{project_id}", schedules)
+}
@@ -0,0 +1,109 @@
+use axum::{
+    Extension, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{task::Task, task_watcher::TaskWatcher, user::User};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_task_middleware},
+};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct TaskWatchers {
+    pub watchers: Vec<User>,
+    /// Whether the signed-in user is one of them
+    pub watching: bool,
+}
+
+async fn task_watchers(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    current_user: &CurrentUser,
+) -> Result<TaskWatchers, ApiError> {
+    let watchers = TaskWatcher::find_users_by_task_id(&deployment.db().pool, task_id).await?;
+    let watching = current_user
+        .id()
+        .is_some_and(|id| watchers.iter().any(|user| user.id == id));
+    Ok(TaskWatchers { watchers, watching })
+}
+
+fn signed_in_user_id(current_user: &CurrentUser) -> Result<Uuid, ApiError> {
+    current_user
+        .id()
+        .ok_or_else(|| ApiError::BadRequest("Watching tasks needs a signed-in user".to_string()))
+}
+
+/// GET /api/tasks/:id/watchers - Users watching the task
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/watchers",
+    tag = "task_watchers",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskWatchers>))
+)]
+pub async fn get_task_watchers(
+    Extension(task): Extension<Task>,
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskWatchers>>, ApiError> {
+    let watchers = task_watchers(&deployment, task.id, &current_user).await?;
+    Ok(ResponseJson(ApiResponse::success(watchers)))
+}
+
+/// PUT /api/tasks/:id/watch - Watch the task as the signed-in user
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/watch",
+    tag = "task_watchers",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskWatchers>))
+)]
+pub async fn watch_task(
+    Extension(task): Extension<Task>,
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskWatchers>>, ApiError> {
+    let user_id = signed_in_user_id(&current_user)?;
+    TaskWatcher::watch(&deployment.db().pool, task.id, user_id).await?;
+    let watchers = task_watchers(&deployment, task.id, &current_user).await?;
+    Ok(ResponseJson(ApiResponse::success(watchers)))
+}
+
+/// DELETE /api/tasks/:id/watch - Stop watching the task
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/watch",
+    tag = "task_watchers",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskWatchers>))
+)]
+pub async fn unwatch_task(
+    Extension(task): Extension<Task>,
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskWatchers>>, ApiError> {
+    let user_id = signed_in_user_id(&current_user)?;
+    TaskWatcher::unwatch(&deployment.db().pool, task.id, user_id).await?;
+    let watchers = task_watchers(&deployment, task.id, &current_user).await?;
+    Ok(ResponseJson(ApiResponse::success(watchers)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_watchers = Router::new()
+        .route("/watchers", get(get_task_watchers))
+        .route("/watch", put(watch_task).delete(unwatch_task))
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new().nest("/tasks/{task_id}", task_watchers)
+}
@@ -1,12 +1,7 @@
-use std::path::PathBuf;
-
 use anyhow;
 use axum::{
     Extension, Json, Router,
-    extract::{
-        Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
-    },
+    extract::{Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
@@ -15,7 +10,6 @@ use axum::{
 use db::models::{
     image::TaskImage,
     project::{Project, ProjectError},
-    repo::Repo,
     task::{CreateTask, Task, TaskOrderBy, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_group::TaskGroup,
     workspace::{CreateWorkspace, Workspace},
@@ -28,19 +22,25 @@ use services::services::{
     container::{ContainerService, StartWorkspaceResult},
     domain_events::DomainEvent,
     share::ShareError,
-    workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::{task_attempts::WorkspaceRepoInput, ws_helpers::forward_stream_to_ws},
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::CurrentUser, load_task_middleware},
+    routes::{
+        stream_transport::{LogMsgStream, StreamTransport},
+        task_attempts::WorkspaceRepoInput,
+    },
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListTasksQuery {
     pub project_id: Uuid,
     pub query: Option<String>,
@@ -49,9 +49,11 @@ pub struct ListTasksQuery {
     pub status: Option<TaskStatus>,
     pub task_group_id: Option<Uuid>,
     pub order_by: Option<String>,
+    /// Also list archived tasks
+    pub include_archived: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PaginatedTasks {
     pub tasks: Vec<TaskWithAttemptStatus>,
@@ -60,7 +62,7 @@ pub struct PaginatedTasks {
 }
 
 /// Request for semantic task search
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchTasksRequest {
     pub project_id: Uuid,
@@ -73,7 +75,7 @@ pub struct SearchTasksRequest {
 }
 
 /// A task match with similarity score
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskMatchWithScore {
     #[serde(flatten)]
@@ -83,7 +85,7 @@ pub struct TaskMatchWithScore {
 }
 
 /// Response for semantic task search
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchTasksResponse {
     pub matches: Vec<TaskMatchWithScore>,
@@ -92,6 +94,13 @@ pub struct SearchTasksResponse {
     pub search_method: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(ListTasksQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<PaginatedTasks>))
+)]
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListTasksQuery>,
@@ -123,6 +132,7 @@ pub async fn get_tasks(
         query.query,
         query.status,
         query.task_group_id,
+        query.include_archived.unwrap_or(false),
         order_by,
         limit,
         offset,
@@ -139,6 +149,13 @@ pub async fn get_tasks(
 }
 
 /// Search tasks using semantic search (hybrid vector + FTS or FTS-only fallback)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/search",
+    tag = "tasks",
+    request_body = SearchTasksRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<SearchTasksResponse>))
+)]
 pub async fn search_tasks(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<SearchTasksRequest>,
@@ -157,7 +174,8 @@ pub async fn search_tasks(
         .ok_or_else(|| ApiError::NotFound(format!("Project {} not found", request.project_id)))?;
 
     let limit = request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as i64;
-    let use_hybrid = request.hybrid.unwrap_or(true);
+    // Vector search needs sqlite-vec; without it, degrade to keyword search
+    let use_hybrid = request.hybrid.unwrap_or(true) && db::is_sqlite_vec_available();
 
     // Try hybrid search first if requested
     if use_hybrid {
@@ -226,34 +244,41 @@ pub async fn search_tasks(
     })))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskStreamQuery {
     pub project_id: Uuid,
     pub include_snapshot: Option<bool>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/stream/ws",
+    tag = "tasks",
+    params(TaskStreamQuery),
+    responses(
+        (status = 101, description = "Switching protocols to a WebSocket"),
+        (status = 200, description = "Server-sent event stream", content_type = "text/event-stream")
+    )
+)]
 pub async fn stream_tasks_ws(
-    ws: WebSocketUpgrade,
+    transport: StreamTransport,
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskStreamQuery>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| async move {
-        let include_snapshot = query.include_snapshot.unwrap_or(true);
-        if let Err(e) =
-            handle_tasks_ws(socket, deployment, query.project_id, include_snapshot).await
-        {
-            tracing::warn!("tasks WS closed: {}", e);
-        }
-    })
+    let include_snapshot = query.include_snapshot.unwrap_or(true);
+    transport.log_msgs(
+        "tasks",
+        tasks_stream(deployment, query.project_id, include_snapshot),
+    )
 }
 
-async fn handle_tasks_ws(
-    socket: WebSocket,
+async fn tasks_stream(
     deployment: DeploymentImpl,
     project_id: Uuid,
     include_snapshot: bool,
-) -> anyhow::Result<()> {
-    let stream = deployment
+) -> anyhow::Result<LogMsgStream> {
+    Ok(deployment
         .events()
         .stream_tasks_raw(
             project_id,
@@ -261,11 +286,16 @@ async fn handle_tasks_ws(
             deployment.operation_status().clone(),
             deployment.hook_execution_store().clone(),
         )
-        .await?;
-
-    forward_stream_to_ws(socket, stream).await
+        .await?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
 pub async fn get_task(
     Extension(task): Extension<Task>,
     State(_deployment): State<DeploymentImpl>,
@@ -275,7 +305,7 @@ pub async fn get_task(
 
 /// Validates that the provided task_group_id belongs to the specified project.
 /// Returns an error if the group doesn't exist or belongs to a different project.
-async fn validate_task_group_id(
+pub(crate) async fn validate_task_group_id(
     pool: &sqlx::SqlitePool,
     task_group_id: Uuid,
     project_id: Uuid,
@@ -292,11 +322,20 @@ async fn validate_task_group_id(
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    tag = "tasks",
+    request_body = CreateTask,
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
 pub async fn create_task(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateTask>,
+    Json(mut payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     let id = Uuid::new_v4();
+    payload.created_by = current_user.id();
 
     tracing::debug!(
         "Creating task '{}' in project {}",
@@ -330,16 +369,25 @@ pub async fn create_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tasks/create-and-start",
+    tag = "tasks",
+    request_body = CreateAndStartTaskRequest,
+    responses((status = 200, description = "Success", body = ApiResponse<TaskWithAttemptStatus>))
+)]
 pub async fn create_task_and_start(
+    Extension(current_user): Extension<CurrentUser>,
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateAndStartTaskRequest>,
+    Json(mut payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
@@ -350,6 +398,7 @@ pub async fn create_task_and_start(
     let pool = &deployment.db().pool;
 
     let task_id = Uuid::new_v4();
+    payload.task.created_by = current_user.id();
     let task = Task::create(pool, &payload.task, task_id).await?;
 
     if let Some(image_ids) = &payload.task.image_ids {
@@ -373,10 +422,11 @@ pub async fn create_task_and_start(
         .ok_or(ProjectError::ProjectNotFound)?;
 
     let attempt_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = payload.repos.iter().map(|r| r.repo_id).collect();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
+        .unique_git_branch_for_workspace(&attempt_id, &task.title, &repo_ids)
+        .await?;
 
     let agent_working_dir = project
         .default_agent_working_dir
@@ -450,6 +500,14 @@ pub async fn create_task_and_start(
     )))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    request_body = UpdateTask,
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -484,7 +542,7 @@ pub async fn update_task(
         .or(existing_task.parent_workspace_id);
     let task_group_id = payload.task_group_id.or(existing_task.task_group_id);
 
-    let task = Task::update(
+    let mut task = Task::update(
         &deployment.db().pool,
         existing_task.id,
         existing_task.project_id,
@@ -496,6 +554,13 @@ pub async fn update_task(
     )
     .await?;
 
+    if let Some(priority) = payload.priority
+        && priority != task.priority
+    {
+        Task::update_priority(&deployment.db().pool, task.id, priority).await?;
+        task.priority = priority;
+    }
+
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::delete_by_task_id(&deployment.db().pool, task.id).await?;
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
@@ -539,6 +604,13 @@ async fn ensure_shared_task_auth(
     Ok(())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 202, description = "Accepted", body = ApiResponse<()>))
+)]
 pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -556,115 +628,98 @@ pub async fn delete_task(
 
     let pool = &deployment.db().pool;
 
-    // Gather task attempts data needed for background cleanup
-    let attempts = Workspace::fetch_all(pool, Some(task.id))
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
-            ApiError::Workspace(e)
-        })?;
-
-    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
-
-    // Collect workspace directories that need cleanup
-    let workspace_dirs: Vec<PathBuf> = attempts
-        .iter()
-        .filter_map(|attempt| attempt.container_ref.as_ref().map(PathBuf::from))
-        .collect();
-
+    // The shared copy is removed now; a restored task comes back unshared
     if let Some(shared_task_id) = task.shared_task_id {
         let Ok(publisher) = deployment.share_publisher() else {
             return Err(ShareError::MissingConfig("share publisher unavailable").into());
         };
         publisher.delete_shared_task(shared_task_id).await?;
+        Task::set_shared_task_id(pool, task.id, None).await?;
     }
 
-    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
-    let mut tx = pool.begin().await?;
-
-    // Nullify parent_workspace_id for all child tasks before deletion
-    // This breaks parent-child relationships to avoid foreign key constraint violations
-    let mut total_children_affected = 0u64;
-    for attempt in &attempts {
-        let children_affected =
-            Task::nullify_children_by_workspace_id(&mut *tx, attempt.id).await?;
-        total_children_affected += children_affected;
-    }
-
-    // Delete task from database (FK CASCADE will handle task_attempts and task_dependencies)
-    // Note: is_blocked of dependent tasks is updated automatically via database trigger
-    let rows_affected = Task::delete(&mut *tx, task.id).await?;
-
+    // Workspaces and their worktrees are kept until the trash is purged
+    let rows_affected = Task::trash(pool, task.id).await?;
     if rows_affected == 0 {
         return Err(ApiError::Database(SqlxError::RowNotFound));
     }
 
-    // Commit the transaction - if this fails, all changes are rolled back
-    tx.commit().await?;
-
-    if total_children_affected > 0 {
-        tracing::info!(
-            "Nullified {} child task references before deleting task {}",
-            total_children_affected,
-            task.id
-        );
-    }
-
     deployment
         .track_if_analytics_allowed(
             "task_deleted",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": task.project_id.to_string(),
-                "attempt_count": attempts.len(),
             }),
         )
         .await;
 
-    let task_id = task.id;
-    let pool = pool.clone();
-    tokio::spawn(async move {
-        tracing::info!(
-            "Starting background cleanup for task {} ({} workspaces, {} repos)",
-            task_id,
-            workspace_dirs.len(),
-            repositories.len()
-        );
-
-        for workspace_dir in &workspace_dirs {
-            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
-            {
-                tracing::error!(
-                    "Background workspace cleanup failed for task {} at {}: {}",
-                    task_id,
-                    workspace_dir.display(),
-                    e
-                );
-            }
-        }
-
-        match Repo::delete_orphaned(&pool).await {
-            Ok(count) if count > 0 => {
-                tracing::info!("Deleted {} orphaned repo records", count);
-            }
-            Err(e) => {
-                tracing::error!("Failed to delete orphaned repos: {}", e);
-            }
-            _ => {}
-        }
+    // 202 Accepted: the task stays in the trash until restored or purged
+    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+}
 
-        tracing::info!("Background cleanup completed for task {}", task_id);
-    });
+/// POST /api/tasks/:id/archive - Archive the task; background workers such
+/// as autopilot and the PR monitor skip it from then on
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/archive",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
+pub async fn archive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Task::archive(pool, task.id).await? > 0 {
+        deployment
+            .track_if_analytics_allowed(
+                "task_archived",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "project_id": task.project_id.to_string(),
+                }),
+            )
+            .await;
+    }
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
 
-    // Return 202 Accepted to indicate deletion was scheduled
-    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+/// POST /api/tasks/:id/unarchive
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/unarchive",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
+pub async fn unarchive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Task::unarchive(pool, task.id).await?;
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(task)))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct ShareTaskResponse {
     pub shared_task_id: Uuid,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/share",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<ShareTaskResponse>))
+)]
 pub async fn share_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -696,7 +751,9 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
-        .route("/share", post(share_task));
+        .route("/share", post(share_task))
+        .route("/archive", post(archive_task))
+        .route("/unarchive", post(unarchive_task));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
@@ -0,0 +1,89 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::NaiveDate;
+use db::models::{project::Project, task::Task};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::time_tracking::{self, ProjectTimeReport, TaskTime};
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{load_project_middleware, load_task_middleware},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectTimeQuery {
+    /// First day included (`YYYY-MM-DD`); defaults to 30 days before `to`
+    pub from: Option<NaiveDate>,
+    /// Last day included (`YYYY-MM-DD`); defaults to today
+    pub to: Option<NaiveDate>,
+}
+
+/// GET /api/tasks/:task_id/time - Agent, script and human wait time of a task
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/time",
+    tag = "time_tracking",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<TaskTime>))
+)]
+pub async fn get_task_time(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskTime>>, ApiError> {
+    let time = time_tracking::task_time(&deployment.db().pool, task.id, &task.title).await?;
+    Ok(ResponseJson(ApiResponse::success(time)))
+}
+
+/// GET /api/projects/:project_id/time - Time per task over a range of days
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/time",
+    tag = "time_tracking",
+    params(("project_id" = Uuid, Path), ProjectTimeQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<ProjectTimeReport>))
+)]
+pub async fn get_project_time(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectTimeQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectTimeReport>>, ApiError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(ApiError::BadRequest(
+            "`from` must not be after `to`".to_string(),
+        ));
+    }
+    let report =
+        time_tracking::project_time_report(&deployment.db().pool, project.id, query.from, query.to)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_time = Router::new()
+        .route("/time", get(get_task_time))
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+    let project_time =
+        Router::new()
+            .route("/time", get(get_project_time))
+            .layer(from_fn_with_state(
+                deployment.clone(),
+                load_project_middleware,
+            ));
+
+    Router::new()
+        .nest("/tasks/{task_id}", task_time)
+        .nest("/projects/{project_id}", project_time)
+}
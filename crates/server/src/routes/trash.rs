@@ -0,0 +1,112 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    project::Project,
+    task::Task,
+    trash::{TrashItem, TrashItemKind},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// GET /api/trash - Deleted tasks and projects that can be restored, most
+/// recently deleted first
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    tag = "trash",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<TrashItem>>))
+)]
+pub async fn get_trash(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TrashItem>>>, ApiError> {
+    let items = TrashItem::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+/// POST /api/trash/projects/:id/restore - Restore a project with its tasks
+#[utoipa::path(
+    post,
+    path = "/api/trash/projects/{project_id}/restore",
+    tag = "trash",
+    params(("project_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Project>))
+)]
+pub async fn restore_project(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::restore(pool, project_id).await? == 0 {
+        return Err(ApiError::NotFound(
+            "Project not found in the trash".to_string(),
+        ));
+    }
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Project not found".to_string()))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_restored",
+            serde_json::json!({ "project_id": project_id.to_string() }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// POST /api/trash/tasks/:id/restore - Restore a task. A task of a trashed
+/// project comes back with the project instead.
+#[utoipa::path(
+    post,
+    path = "/api/trash/tasks/{task_id}/restore",
+    tag = "trash",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Task>))
+)]
+pub async fn restore_task(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if TrashItem::find(pool, TrashItemKind::Task, task_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::NotFound(
+            "Task not found in the trash; if its project was deleted, restore the project"
+                .to_string(),
+        ));
+    }
+    Task::restore(pool, task_id).await?;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Task not found".to_string()))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task_id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/trash", get(get_trash))
+        .route(
+            "/trash/projects/{project_id}/restore",
+            post(restore_project),
+        )
+        .route("/trash/tasks/{task_id}/restore", post(restore_task))
+}
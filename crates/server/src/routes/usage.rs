@@ -0,0 +1,60 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::NaiveDate;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::usage::{self, UsageReport};
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageQuery {
+    /// First day included (`YYYY-MM-DD`); defaults to 30 days before `to`
+    pub from: Option<NaiveDate>,
+    /// Last day included (`YYYY-MM-DD`); defaults to today
+    pub to: Option<NaiveDate>,
+    pub project_id: Option<Uuid>,
+}
+
+/// GET /api/usage - Token usage and estimated cost per executor, project and day
+#[utoipa::path(
+    get,
+    path = "/api/usage",
+    tag = "usage",
+    params(UsageQuery),
+    responses((status = 200, description = "Success", body = ApiResponse<UsageReport>))
+)]
+pub async fn get_usage(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<UsageQuery>,
+) -> Result<ResponseJson<ApiResponse<UsageReport>>, ApiError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return Err(ApiError::BadRequest(
+            "`from` must not be after `to`".to_string(),
+        ));
+    }
+    let pricing = deployment.config().read().await.usage_pricing.clone();
+    let report = usage::usage_report(
+        &deployment.db().pool,
+        &pricing,
+        query.from,
+        query.to,
+        query.project_id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/usage", get(get_usage))
+}
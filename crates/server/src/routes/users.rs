@@ -0,0 +1,297 @@
+use std::sync::LazyLock;
+
+use argon2::{
+    Argon2,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{delete, get},
+};
+use chrono::Utc;
+use db::models::user::{CreateUser, User, UserSession};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{
+        CurrentUser, SESSION_TTL, generate_token, hash_token, is_setup_token, session_cookie,
+        session_token, session_user,
+    },
+};
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Checked when the username is unknown, so both failures take as long and
+/// response times don't reveal which usernames exist
+static DUMMY_PASSWORD_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password(&generate_token()).expect("hashing a random password succeeds"));
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct SessionStatus {
+    /// Whether the server has accounts, and so requires signing in
+    pub accounts_enabled: bool,
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+fn hash_password(password: &str) -> Result<String, ApiError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| ApiError::Internal(format!("Failed to hash password: {e}")))
+}
+
+fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Start a session for `user`, returning it with the cookie that carries it
+async fn start_session(deployment: &DeploymentImpl, user: User) -> Result<Response, ApiError> {
+    let token = generate_token();
+    UserSession::create(
+        &deployment.db().pool,
+        user.id,
+        &hash_token(&token),
+        Utc::now() + SESSION_TTL,
+    )
+    .await?;
+    Ok((
+        [(header::SET_COOKIE, session_cookie(Some(&token)))],
+        ResponseJson(ApiResponse::success(user)),
+    )
+        .into_response())
+}
+
+/// GET /api/session - Whether sign-in is required, and who is signed in
+#[utoipa::path(
+    get,
+    path = "/api/session",
+    tag = "users",
+    responses((status = 200, description = "Success", body = ApiResponse<SessionStatus>))
+)]
+pub async fn get_session(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<SessionStatus>>, ApiError> {
+    let accounts_enabled = User::any_exist(&deployment.db().pool).await?;
+    let user = session_user(&deployment, &headers).await?;
+    Ok(ResponseJson(ApiResponse::success(SessionStatus {
+        accounts_enabled,
+        user,
+    })))
+}
+
+/// POST /api/session - Sign in, setting the session cookie
+#[utoipa::path(
+    post,
+    path = "/api/session",
+    tag = "users",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Signed in", body = ApiResponse<User>),
+        (status = 401, description = "Unknown username or wrong password")
+    )
+)]
+pub async fn login(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some((user, password_hash)) =
+        User::find_with_password_hash(pool, payload.username.trim()).await?
+    else {
+        verify_password(&payload.password, &DUMMY_PASSWORD_HASH);
+        return Err(ApiError::Unauthorized);
+    };
+    if !verify_password(&payload.password, &password_hash) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Opportunistic cleanup; sessions are only ever looked up unexpired
+    if let Err(e) = UserSession::delete_expired(pool).await {
+        tracing::warn!("Failed to delete expired sessions: {}", e);
+    }
+    start_session(&deployment, user).await
+}
+
+/// DELETE /api/session - Sign out, clearing the session cookie
+#[utoipa::path(
+    delete,
+    path = "/api/session",
+    tag = "users",
+    responses((status = 200, description = "Signed out", body = ApiResponse<()>))
+)]
+pub async fn logout(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    if let Some(token) = session_token(&headers) {
+        UserSession::delete(&deployment.db().pool, &hash_token(token)).await?;
+    }
+    Ok((
+        [(header::SET_COOKIE, session_cookie(None))],
+        ResponseJson(ApiResponse::<()>::success(())),
+    )
+        .into_response())
+}
+
+/// GET /api/users - All accounts on this server
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<User>>))
+)]
+pub async fn get_users(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<User>>>, ApiError> {
+    let users = User::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(users)))
+}
+
+/// POST /api/users - Create an account; only admins can. The first account
+/// is created with the setup token from the server's stderr instead, is an admin,
+/// turns on sign-in for the server and signs the caller in as the new user.
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    request_body = CreateUser,
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<User>),
+        (status = 403, description = "Not an admin, or a wrong setup token"),
+        (status = 409, description = "The username is taken")
+    )
+)]
+pub async fn create_user(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateUser>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let first_user = !User::any_exist(pool).await?;
+    if first_user {
+        if !payload.setup_token.as_deref().is_some_and(is_setup_token) {
+            return Err(ApiError::Forbidden(
+                "The setup token printed on the server's stderr is required to create the first account"
+                    .to_string(),
+            ));
+        }
+    } else {
+        current_user.require_admin()?;
+    }
+
+    let username = payload.username.trim();
+    if username.is_empty() {
+        return Err(ApiError::BadRequest("Username cannot be empty".to_string()));
+    }
+    if payload.password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters"
+        )));
+    }
+
+    if User::find_with_password_hash(pool, username)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "Username '{username}' is already taken"
+        )));
+    }
+
+    let display_name = payload
+        .display_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    let user = User::create(
+        pool,
+        username,
+        display_name,
+        &hash_password(&payload.password)?,
+        first_user || payload.is_admin,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "user_created",
+            serde_json::json!({ "first_user": first_user }),
+        )
+        .await;
+
+    // Without this the creator of the first account would be locked out
+    if first_user {
+        return start_session(&deployment, user).await;
+    }
+    Ok(ResponseJson(ApiResponse::success(user)).into_response())
+}
+
+/// DELETE /api/users/:id - Delete an account and its sessions; only admins
+/// can. Tasks and operations it created are kept. The last admin cannot be
+/// deleted, so the server always has someone to manage accounts.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(("user_id" = Uuid, Path)),
+    responses(
+        (status = 200, description = "Success", body = ApiResponse<()>),
+        (status = 403, description = "Not an admin"),
+        (status = 409, description = "The user is the last admin")
+    )
+)]
+pub async fn delete_user(
+    Extension(current_user): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(user_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    current_user.require_admin()?;
+
+    let pool = &deployment.db().pool;
+    let user = User::find_by_id(pool, user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("User {user_id} not found")))?;
+    if user.is_admin && User::count_admins(pool).await? <= 1 {
+        return Err(ApiError::Conflict(
+            "The last admin cannot be deleted".to_string(),
+        ));
+    }
+
+    User::delete(pool, user_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes reachable without signing in
+pub fn session_router() -> Router<DeploymentImpl> {
+    Router::new().route("/session", get(get_session).post(login).delete(logout))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/users", get(get_users).post(create_user))
+        .route("/users/{user_id}", delete(delete_user))
+}
@@ -0,0 +1,217 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::Utc;
+use db::models::webhook::{CreateWebhook, UpdateWebhook, Webhook};
+use deployment::Deployment;
+use services::services::{
+    domain_events::handlers::webhook::{self, render_template},
+    webhook_secrets,
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_webhook_middleware};
+
+fn validate(name: &str, url: &str, payload_template: Option<&str>) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Webhook name cannot be empty".to_string(),
+        ));
+    }
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid webhook URL '{url}': expected an http(s) URL"
+            )));
+        }
+    }
+    if let Some(template) = payload_template {
+        render_template(template, &serde_json::Value::Null)
+            .map_err(|e| ApiError::BadRequest(format!("Payload template is not JSON: {e}")))?;
+    }
+    Ok(())
+}
+
+/// GET /api/webhooks - All webhooks
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    responses((status = 200, description = "Success", body = ApiResponse<Vec<Webhook>>))
+)]
+pub async fn get_webhooks(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Webhook>>>, ApiError> {
+    let webhooks = Webhook::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(webhooks)))
+}
+
+/// POST /api/webhooks - Create a webhook
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhook,
+    responses((status = 200, description = "Success", body = ApiResponse<Webhook>))
+)]
+pub async fn create_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateWebhook>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    validate(
+        &payload.name,
+        &payload.url,
+        payload.payload_template.as_deref(),
+    )?;
+    let id = Uuid::new_v4();
+    let secret_ref = match payload.secret.as_deref() {
+        Some(secret) => webhook_secrets::store(deployment.secrets(), id, secret).await?,
+        None => None,
+    };
+    let webhook = Webhook::create(
+        &deployment.db().pool,
+        id,
+        &payload,
+        secret_ref.as_ref().map(|secret_ref| secret_ref.name()),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "webhook_created",
+            serde_json::json!({
+                "webhook_id": webhook.id.to_string(),
+                "events": webhook.events.0,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+/// GET /api/webhooks/:id
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Webhook>))
+)]
+pub async fn get_webhook(
+    Extension(webhook): Extension<Webhook>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+/// PUT /api/webhooks/:id - Replace a webhook's settings
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = uuid::Uuid, Path)),
+    request_body = UpdateWebhook,
+    responses((status = 200, description = "Success", body = ApiResponse<Webhook>))
+)]
+pub async fn update_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateWebhook>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    validate(
+        &payload.name,
+        &payload.url,
+        payload.payload_template.as_deref(),
+    )?;
+    // The secret is kept when not given and removed when empty
+    let secret_ref = match payload.secret.as_deref() {
+        Some(secret) => webhook_secrets::store(deployment.secrets(), webhook.id, secret)
+            .await?
+            .map(|secret_ref| secret_ref.name().to_string()),
+        None => webhook.secret_ref.clone(),
+    };
+    let updated = Webhook::update(
+        &deployment.db().pool,
+        webhook.id,
+        &payload,
+        secret_ref.as_deref(),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /api/webhooks/:id
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<()>))
+)]
+pub async fn delete_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Webhook::delete(&deployment.db().pool, webhook.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    deployment
+        .secrets()
+        .delete(&webhook_secrets::entry_name(webhook.id))
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /api/webhooks/:id/test - Send a `ping` event and record the outcome
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{webhook_id}/test",
+    tag = "webhooks",
+    params(("webhook_id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Success", body = ApiResponse<Webhook>))
+)]
+pub async fn test_webhook(
+    Extension(webhook): Extension<Webhook>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    let payload = serde_json::json!({
+        "event": "ping",
+        "timestamp": Utc::now(),
+        "project_id": webhook.project_id,
+        "data": { "webhook_id": webhook.id },
+    });
+    let client = reqwest::Client::new();
+    let result = webhook::deliver(&client, deployment.secrets(), &webhook, "ping", &payload).await;
+
+    let pool = &deployment.db().pool;
+    Webhook::record_delivery(pool, webhook.id, result.err().as_deref()).await?;
+    let updated = Webhook::find_by_id(pool, webhook.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let webhook_router = Router::new()
+        .route(
+            "/",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route("/test", post(test_webhook))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_webhook_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_webhooks).post(create_webhook))
+        .nest("/{webhook_id}", webhook_router);
+
+    Router::new().nest("/webhooks", inner)
+}
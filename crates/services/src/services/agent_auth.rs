@@ -0,0 +1,222 @@
+//! Whether each coding agent's CLI is signed in.
+//!
+//! CLI logins expire silently, after which every execution of the agent
+//! fails. Agents whose CLI has a cheap status command (see
+//! [`StandardCodingAgentExecutor::auth_status_command`]) are probed every
+//! [`PROBE_INTERVAL`]. For every agent, an execution failing with an
+//! authentication error marks it signed out, and one completing marks it
+//! signed in again. Executions started while their agent is known to be
+//! signed out are announced, since they will most likely fail.
+
+use std::{
+    collections::HashMap,
+    process::Stdio,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::execution_process_failure::FailureReason;
+use executors::{
+    executors::{BaseCodingAgent, StandardCodingAgentExecutor},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use parking_lot::RwLock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::services::failure_classification::suggested_fix;
+
+/// How often CLIs with a status command are probed
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A probe taking longer than this is inconclusive; npx may be installing
+const PROBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Some status commands exit zero whether or not they are signed in
+static SIGNED_OUT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bnot (?:logged|signed) in\b|\blogged out\b|\bunauthenticated\b")
+        .expect("valid signed out regex")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentAuthState {
+    SignedIn,
+    SignedOut,
+}
+
+/// What is known about a coding agent's login
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct AgentAuthStatus {
+    #[schema(value_type = String)]
+    pub executor: BaseCodingAgent,
+    pub state: AgentAuthState,
+    /// The status command's output, or the error a failed execution reported
+    pub detail: Option<String>,
+    /// How to sign in again, when signed out
+    pub suggested_fix: Option<String>,
+    #[ts(type = "Date")]
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AgentAuthMonitor {
+    statuses: Arc<RwLock<HashMap<BaseCodingAgent, AgentAuthStatus>>>,
+}
+
+impl AgentAuthMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Last known status of every agent that was probed or ran
+    pub fn all(&self) -> Vec<AgentAuthStatus> {
+        let mut statuses: Vec<_> = self.statuses.read().values().cloned().collect();
+        statuses.sort_by_key(|status| status.executor.to_string());
+        statuses
+    }
+
+    pub fn status(&self, executor: BaseCodingAgent) -> Option<AgentAuthStatus> {
+        self.statuses.read().get(&executor).cloned()
+    }
+
+    /// Whether `executor` is known to be signed out
+    pub fn is_signed_out(&self, executor: BaseCodingAgent) -> bool {
+        self.status(executor)
+            .is_some_and(|status| status.state == AgentAuthState::SignedOut)
+    }
+
+    fn record(&self, executor: BaseCodingAgent, state: AgentAuthState, detail: Option<String>) {
+        let suggested_fix = (state == AgentAuthState::SignedOut)
+            .then(|| suggested_fix(FailureReason::AuthExpired, Some(executor)));
+        self.statuses.write().insert(
+            executor,
+            AgentAuthStatus {
+                executor,
+                state,
+                detail,
+                suggested_fix,
+                checked_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Learn from an execution of `executor` that finished: an authentication
+    /// failure means it is signed out, a completed run that it is signed in
+    pub fn observe_execution(
+        &self,
+        executor: BaseCodingAgent,
+        completed: bool,
+        failure: Option<(FailureReason, Option<&str>)>,
+    ) {
+        match failure {
+            Some((FailureReason::AuthExpired, detail)) => self.record(
+                executor,
+                AgentAuthState::SignedOut,
+                detail.map(str::to_string),
+            ),
+            _ if completed => self.record(executor, AgentAuthState::SignedIn, None),
+            _ => {}
+        }
+    }
+
+    /// Probe every configured agent whose CLI has a status command
+    pub async fn probe_all(&self) {
+        let profiles = ExecutorConfigs::get_cached();
+        for executor in profiles.executors.keys().copied() {
+            let Some(command) = profiles
+                .get_coding_agent(&ExecutorProfileId::new(executor))
+                .and_then(|agent| agent.auth_status_command())
+            else {
+                continue;
+            };
+            let Ok((program, args)) = command.into_resolved().await else {
+                // Not installed; availability checks report that
+                continue;
+            };
+
+            let mut probe = Command::new(program);
+            probe
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true);
+            let output = match tokio::time::timeout(PROBE_TIMEOUT, probe.output()).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => {
+                    tracing::debug!(%executor, error = %e, "Auth probe failed to run");
+                    continue;
+                }
+                Err(_) => {
+                    tracing::debug!(%executor, "Auth probe timed out");
+                    continue;
+                }
+            };
+
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            let detail = text
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string);
+            let state = if output.status.success() && !SIGNED_OUT_RE.is_match(&text) {
+                AgentAuthState::SignedIn
+            } else {
+                AgentAuthState::SignedOut
+            };
+            if state == AgentAuthState::SignedOut && !self.is_signed_out(executor) {
+                tracing::warn!(%executor, ?detail, "Coding agent CLI is signed out");
+            }
+            self.record(executor, state, detail);
+        }
+    }
+
+    /// Probe configured agents now and every [`PROBE_INTERVAL`]
+    pub fn spawn(&self) {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PROBE_INTERVAL);
+            loop {
+                interval.tick().await;
+                monitor.probe_all().await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn executions_update_the_login_state() {
+        let monitor = AgentAuthMonitor::new();
+        let executor = BaseCodingAgent::Codex;
+        assert!(!monitor.is_signed_out(executor));
+
+        monitor.observe_execution(
+            executor,
+            false,
+            Some((FailureReason::AuthExpired, Some("401 Unauthorized"))),
+        );
+        let status = monitor.status(executor).unwrap();
+        assert_eq!(status.state, AgentAuthState::SignedOut);
+        assert!(status.suggested_fix.unwrap().contains("codex login"));
+
+        // Other failures say nothing about the login
+        monitor.observe_execution(executor, false, Some((FailureReason::DiskFull, None)));
+        assert!(monitor.is_signed_out(executor));
+
+        monitor.observe_execution(executor, true, None);
+        assert!(!monitor.is_signed_out(executor));
+    }
+}
@@ -0,0 +1,377 @@
+//! Detection of coding agents that are stuck: repeating the same tool call,
+//! making edits that go nowhere, flooding their output, going silent or
+//! running for too long.
+//!
+//! [`AnomalyDetector`] is fed every message of an execution's log store in
+//! order and reports the first threshold crossed; the container then
+//! interrupts the execution. [`ActivityClock`] times the execution between
+//! messages.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use executors::logs::{
+    ActionType, FileChange, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+};
+use utils::log_msg::LogMsg;
+
+use crate::services::config::AnomalyThresholds;
+
+/// Why an execution was judged to be stuck
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anomaly {
+    RepeatedToolCall { tool_name: String, count: u32 },
+    NoProgressEdits { path: String, count: u32 },
+    RunawayOutput { bytes: u64 },
+    Inactive { minutes: u32 },
+    MaxDurationExceeded { minutes: u32 },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::RepeatedToolCall { tool_name, count } => {
+                write!(
+                    f,
+                    "the same {tool_name} call was repeated {count} times in a row"
+                )
+            }
+            Anomaly::NoProgressEdits { path, count } => {
+                write!(f, "{count} edits to {path} repeated or undid earlier edits")
+            }
+            Anomaly::RunawayOutput { bytes } => {
+                write!(
+                    f,
+                    "the agent produced {} MB of output",
+                    bytes / (1024 * 1024)
+                )
+            }
+            Anomaly::Inactive { minutes } => {
+                write!(f, "the agent produced no output for {minutes} minutes")
+            }
+            Anomaly::MaxDurationExceeded { minutes } => {
+                write!(f, "the agent ran for more than {minutes} minutes")
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct FileEditHistory {
+    /// Hashes of every change applied to the file
+    seen: HashSet<u64>,
+    /// Hash of the change that would undo the latest edit
+    last_inverse: Option<u64>,
+    no_progress: u32,
+}
+
+pub struct AnomalyDetector {
+    thresholds: AnomalyThresholds,
+    /// Entry indices already counted; entries are patched again as tool
+    /// calls complete
+    seen_entries: HashSet<usize>,
+    last_tool_call: Option<u64>,
+    repeated_tool_calls: u32,
+    edits: HashMap<String, FileEditHistory>,
+    output_bytes: u64,
+}
+
+impl AnomalyDetector {
+    pub fn new(thresholds: AnomalyThresholds) -> Self {
+        Self {
+            thresholds,
+            seen_entries: HashSet::new(),
+            last_tool_call: None,
+            repeated_tool_calls: 0,
+            edits: HashMap::new(),
+            output_bytes: 0,
+        }
+    }
+
+    /// Account for the next log message, returning the anomaly it reveals
+    pub fn observe(&mut self, msg: &LogMsg) -> Option<Anomaly> {
+        match msg {
+            LogMsg::Stdout(s) | LogMsg::Stderr(s) => {
+                self.output_bytes += s.len() as u64;
+                let limit = self.thresholds.max_output_bytes;
+                (limit > 0 && self.output_bytes > limit).then_some(Anomaly::RunawayOutput {
+                    bytes: self.output_bytes,
+                })
+            }
+            LogMsg::JsonPatch(patch) => {
+                let (index, entry) = extract_normalized_entry_from_patch(patch)?;
+                let NormalizedEntryType::ToolUse {
+                    tool_name,
+                    action_type,
+                    ..
+                } = entry.entry_type
+                else {
+                    return None;
+                };
+                if !self.seen_entries.insert(index) {
+                    return None;
+                }
+                self.observe_tool_call(&tool_name, &action_type)
+            }
+            LogMsg::SessionId(_) | LogMsg::Finished => None,
+        }
+    }
+
+    fn observe_tool_call(&mut self, tool_name: &str, action: &ActionType) -> Option<Anomaly> {
+        let signature = tool_call_signature(tool_name, action);
+        if self.last_tool_call == Some(signature) {
+            self.repeated_tool_calls += 1;
+        } else {
+            self.last_tool_call = Some(signature);
+            self.repeated_tool_calls = 1;
+        }
+        let limit = self.thresholds.max_repeated_tool_calls;
+        if limit > 0 && self.repeated_tool_calls >= limit {
+            return Some(Anomaly::RepeatedToolCall {
+                tool_name: tool_name.to_string(),
+                count: self.repeated_tool_calls,
+            });
+        }
+
+        let ActionType::FileEdit { path, changes } = action else {
+            return None;
+        };
+        let history = self.edits.entry(path.clone()).or_default();
+        for change in changes {
+            let Some((hash, inverse)) = change_hashes(change) else {
+                continue;
+            };
+            let repeats = !history.seen.insert(hash);
+            let reverts = history.last_inverse == Some(hash);
+            history.last_inverse = inverse;
+            if repeats || reverts {
+                history.no_progress += 1;
+            }
+        }
+        let limit = self.thresholds.max_no_progress_edits;
+        (limit > 0 && history.no_progress >= limit).then(|| Anomaly::NoProgressEdits {
+            path: path.clone(),
+            count: history.no_progress,
+        })
+    }
+}
+
+/// How long an execution has run, and how long since it last produced output
+pub struct ActivityClock {
+    inactivity_timeout_minutes: u32,
+    max_duration_minutes: u32,
+    started: Instant,
+    last_activity: Instant,
+}
+
+impl ActivityClock {
+    pub fn new(thresholds: &AnomalyThresholds, started: Instant) -> Self {
+        Self {
+            inactivity_timeout_minutes: thresholds.inactivity_timeout_minutes,
+            max_duration_minutes: thresholds.max_duration_minutes,
+            started,
+            last_activity: started,
+        }
+    }
+
+    /// The execution showed signs of life at `now`
+    pub fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// When [`ActivityClock::check`] should next be called, unless there is
+    /// activity first; None when neither limit is set
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let inactivity = (self.inactivity_timeout_minutes > 0)
+            .then(|| self.last_activity + minutes(self.inactivity_timeout_minutes));
+        let max_duration = (self.max_duration_minutes > 0)
+            .then(|| self.started + minutes(self.max_duration_minutes));
+        inactivity.into_iter().chain(max_duration).min()
+    }
+
+    /// The limit crossed by `now`, if any
+    pub fn check(&self, now: Instant) -> Option<Anomaly> {
+        let max = self.max_duration_minutes;
+        if max > 0 && now.duration_since(self.started) >= minutes(max) {
+            return Some(Anomaly::MaxDurationExceeded { minutes: max });
+        }
+        let inactivity = self.inactivity_timeout_minutes;
+        (inactivity > 0 && now.duration_since(self.last_activity) >= minutes(inactivity)).then_some(
+            Anomaly::Inactive {
+                minutes: inactivity,
+            },
+        )
+    }
+}
+
+fn minutes(minutes: u32) -> Duration {
+    Duration::from_secs(u64::from(minutes) * 60)
+}
+
+/// Identity of a tool call: its name and arguments, without any result
+fn tool_call_signature(tool_name: &str, action: &ActionType) -> u64 {
+    let mut value = serde_json::to_value(action).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("result");
+    }
+    hash_of(&(tool_name, value.to_string()))
+}
+
+/// Hash of a file change and, for diffs, of the diff that would undo it
+fn change_hashes(change: &FileChange) -> Option<(u64, Option<u64>)> {
+    match change {
+        FileChange::Write { content } => Some((hash_of(content), None)),
+        FileChange::Edit { unified_diff, .. } => {
+            let lines: Vec<&str> = unified_diff
+                .lines()
+                .filter(|line| {
+                    (line.starts_with('+') || line.starts_with('-'))
+                        && !line.starts_with("+++")
+                        && !line.starts_with("---")
+                })
+                .collect();
+            if lines.is_empty() {
+                return None;
+            }
+            let inverse: Vec<String> = lines
+                .iter()
+                .map(|line| match line.split_at(1) {
+                    ("+", rest) => format!("-{rest}"),
+                    (_, rest) => format!("+{rest}"),
+                })
+                .collect();
+            Some((hash_of(&lines), Some(hash_of(&inverse))))
+        }
+        FileChange::Delete | FileChange::Rename { .. } => None,
+    }
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> AnomalyDetector {
+        AnomalyDetector::new(AnomalyThresholds {
+            max_repeated_tool_calls: 3,
+            max_no_progress_edits: 2,
+            max_output_bytes: 10,
+            ..AnomalyThresholds::default()
+        })
+    }
+
+    fn edit(diff: &str) -> ActionType {
+        ActionType::FileEdit {
+            path: "src/lib.rs".to_string(),
+            changes: vec![FileChange::Edit {
+                unified_diff: diff.to_string(),
+                has_line_numbers: true,
+            }],
+        }
+    }
+
+    #[test]
+    fn flags_repeated_tool_calls() {
+        let mut detector = detector();
+        let run = |command: &str| ActionType::CommandRun {
+            command: command.to_string(),
+            result: None,
+        };
+
+        assert_eq!(detector.observe_tool_call("Bash", &run("cargo test")), None);
+        assert_eq!(detector.observe_tool_call("Bash", &run("cargo test")), None);
+        assert_eq!(
+            detector.observe_tool_call("Bash", &run("cargo check")),
+            None
+        );
+        assert_eq!(
+            detector.observe_tool_call("Bash", &run("cargo check")),
+            None
+        );
+        assert_eq!(
+            detector.observe_tool_call("Bash", &run("cargo check")),
+            Some(Anomaly::RepeatedToolCall {
+                tool_name: "Bash".to_string(),
+                count: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn flags_edits_that_undo_each_other() {
+        let mut detector = detector();
+        let forward = "@@ -1 +1 @@\n-let a = 1;\n+let a = 2;";
+        let back = "@@ -1 +1 @@\n-let a = 2;\n+let a = 1;";
+
+        assert_eq!(detector.observe_tool_call("Edit", &edit(forward)), None);
+        assert_eq!(detector.observe_tool_call("Edit", &edit(back)), None);
+        assert_eq!(
+            detector.observe_tool_call("Edit", &edit(forward)),
+            Some(Anomaly::NoProgressEdits {
+                path: "src/lib.rs".to_string(),
+                count: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn flags_runaway_output() {
+        let mut detector = detector();
+        assert_eq!(detector.observe(&LogMsg::Stdout("12345".to_string())), None);
+        assert_eq!(
+            detector.observe(&LogMsg::Stderr("678901".to_string())),
+            Some(Anomaly::RunawayOutput { bytes: 11 })
+        );
+    }
+
+    #[test]
+    fn flags_silent_and_overlong_executions() {
+        let start = Instant::now();
+        let mut clock = ActivityClock::new(
+            &AnomalyThresholds {
+                inactivity_timeout_minutes: 10,
+                max_duration_minutes: 25,
+                ..AnomalyThresholds::default()
+            },
+            start,
+        );
+        assert_eq!(clock.next_deadline(), Some(start + minutes(10)));
+        assert_eq!(clock.check(start + minutes(9)), None);
+
+        clock.touch(start + minutes(9));
+        assert_eq!(clock.check(start + minutes(18)), None);
+        assert_eq!(
+            clock.check(start + minutes(19)),
+            Some(Anomaly::Inactive { minutes: 10 })
+        );
+
+        clock.touch(start + minutes(20));
+        assert_eq!(clock.next_deadline(), Some(start + minutes(25)));
+        assert_eq!(
+            clock.check(start + minutes(25)),
+            Some(Anomaly::MaxDurationExceeded { minutes: 25 })
+        );
+    }
+
+    #[test]
+    fn zero_disables_the_clock() {
+        let clock = ActivityClock::new(
+            &AnomalyThresholds {
+                inactivity_timeout_minutes: 0,
+                max_duration_minutes: 0,
+                ..AnomalyThresholds::default()
+            },
+            Instant::now(),
+        );
+        assert_eq!(clock.next_deadline(), None);
+    }
+}
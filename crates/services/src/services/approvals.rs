@@ -1,4 +1,5 @@
 pub mod executor_approvals;
+pub mod policy;
 
 use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
 
@@ -106,6 +107,14 @@ impl Approvals {
         &self.protocol_peers
     }
 
+    /// Execution process of each approval still waiting on the user
+    pub fn pending_execution_process_ids(&self) -> Vec<Uuid> {
+        self.pending
+            .iter()
+            .map(|entry| entry.execution_process_id)
+            .collect()
+    }
+
     pub async fn create_with_waiter(
         &self,
         request: ApprovalRequest,
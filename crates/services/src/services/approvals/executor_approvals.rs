@@ -1,22 +1,31 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
-use db::{self, DBService, models::execution_process::ExecutionProcess};
+use db::{
+    self, DBService,
+    models::{approval_policy::ApprovalPolicy, execution_process::ExecutionProcess, task::Task},
+};
 use executors::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService},
-    executors::claude::protocol::ProtocolPeer,
+    executors::{BaseCodingAgent, claude::protocol::ProtocolPeer},
 };
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest, QuestionData};
 use uuid::Uuid;
 
-use crate::services::{approvals::Approvals, notification::NotificationService};
+use crate::services::{
+    approvals::{Approvals, policy::CompiledApprovalPolicy},
+    domain_events::{DomainEvent, EventDispatchCallback},
+    notification::NotificationService,
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
     notification_service: NotificationService,
+    event_dispatcher: Option<EventDispatchCallback>,
     execution_process_id: Uuid,
+    executor: BaseCodingAgent,
 }
 
 impl ExecutorApprovalBridge {
@@ -24,16 +33,58 @@ impl ExecutorApprovalBridge {
         approvals: Approvals,
         db: DBService,
         notification_service: NotificationService,
+        event_dispatcher: Option<EventDispatchCallback>,
         execution_process_id: Uuid,
+        executor: BaseCodingAgent,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
+            event_dispatcher,
             execution_process_id,
+            executor,
         })
     }
 
+    /// Whether the project's approval policy for this executor lets the tool
+    /// call go ahead without asking
+    async fn policy_approves(&self, tool_name: &str, tool_input: &Value) -> bool {
+        let Ok(ctx) =
+            ExecutionProcess::load_context(&self.db.pool, self.execution_process_id).await
+        else {
+            return false;
+        };
+        let Some(workspace_dir) = ctx.workspace.container_ref.as_deref() else {
+            return false;
+        };
+        match ApprovalPolicy::find_for_executor(&self.db.pool, ctx.project.id, self.executor).await
+        {
+            Ok(Some(policy)) => CompiledApprovalPolicy::new(policy).auto_approves(
+                tool_name,
+                tool_input,
+                Path::new(workspace_dir),
+            ),
+            Ok(None) => false,
+            Err(e) => {
+                tracing::warn!("Failed to load approval policy: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Let notification channels know the agent is waiting on the user
+    async fn dispatch_attention_needed(&self, task_id: Uuid, workspace_id: Uuid, reason: String) {
+        if let Some(dispatcher) = &self.event_dispatcher {
+            dispatcher(DomainEvent::AttentionNeeded {
+                task_id,
+                workspace_id,
+                reason,
+            })
+            .await;
+        }
+    }
+
     /// Register a protocol peer for this execution process.
     /// This allows the approval service to send tool_result messages back to Claude.
     pub async fn register_protocol_peer(&self, peer: ProtocolPeer) {
@@ -58,6 +109,14 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
+        if self.policy_approves(tool_name, &tool_input).await {
+            tracing::info!(
+                "Tool '{}' approved by the project's approval policy",
+                tool_name
+            );
+            return Ok(ApprovalStatus::Approved);
+        }
+
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
         let request = ApprovalRequest::from_create(
@@ -86,15 +145,23 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         // In-app notification when approval is needed
         if let Ok(ctx) =
             ExecutionProcess::load_context(&self.db.pool, self.execution_process_id).await
-            && let Err(e) = NotificationService::notify_agent_approval_needed(
+        {
+            if let Err(e) = NotificationService::notify_agent_approval_needed(
                 &self.db.pool,
                 ctx.project.id,
                 ctx.workspace.id,
                 tool_name,
             )
             .await
-        {
-            tracing::warn!("Failed to create in-app approval notification: {}", e);
+            {
+                tracing::warn!("Failed to create in-app approval notification: {}", e);
+            }
+            self.dispatch_attention_needed(
+                ctx.task.id,
+                ctx.workspace.id,
+                format!("Tool '{}' requires approval", tool_name),
+            )
+            .await;
         }
 
         let status = waiter.clone().await;
@@ -135,14 +202,22 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         // In-app notification when user input is needed
         if let Ok(ctx) =
             ExecutionProcess::load_context(&self.db.pool, self.execution_process_id).await
-            && let Err(e) = NotificationService::notify_agent_question(
+        {
+            if let Err(e) = NotificationService::notify_agent_question(
                 &self.db.pool,
                 ctx.project.id,
                 ctx.workspace.id,
             )
             .await
-        {
-            tracing::warn!("Failed to create in-app question notification: {}", e);
+            {
+                tracing::warn!("Failed to create in-app question notification: {}", e);
+            }
+            self.dispatch_attention_needed(
+                ctx.task.id,
+                ctx.workspace.id,
+                "Agent is asking for your input".to_string(),
+            )
+            .await;
         }
 
         let status = waiter.clone().await;
@@ -167,4 +242,17 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             .unregister_protocol_peer(&self.execution_process_id)
             .await;
     }
+
+    async fn flag_attention(&self, reason: String) {
+        let Ok(ctx) =
+            ExecutionProcess::load_context(&self.db.pool, self.execution_process_id).await
+        else {
+            return;
+        };
+        if let Err(e) = Task::update_needs_attention(&self.db.pool, ctx.task.id, Some(true)).await {
+            tracing::warn!("Failed to flag task {} for attention: {}", ctx.task.id, e);
+        }
+        self.dispatch_attention_needed(ctx.task.id, ctx.workspace.id, reason)
+            .await;
+    }
 }
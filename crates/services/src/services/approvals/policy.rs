@@ -0,0 +1,217 @@
+//! Approving agent tool calls automatically by a project's [`ApprovalPolicy`].
+//!
+//! Tool inputs differ by executor, so the fields checked are the ones the
+//! supported agents use: `file_path`, `path` and `notebook_path` for files,
+//! `command` (a string or an argv array) for shell commands, and `content` or
+//! `new_string` for written text. Anything a policy doesn't clearly allow is
+//! left for the user to approve.
+
+use std::path::{Component, Path};
+
+use db::models::approval_policy::ApprovalPolicy;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde_json::Value;
+
+const PATH_FIELDS: &[&str] = &["file_path", "path", "notebook_path"];
+const CONTENT_FIELDS: &[&str] = &["content", "new_string"];
+/// Commands using these could run more than the allowed prefix
+const SHELL_OPERATORS: &[&str] = &[";", "&", "|", "`", "$(", ">", "<", "\n"];
+
+/// A project's policy, compiled for matching
+pub struct CompiledApprovalPolicy {
+    policy: ApprovalPolicy,
+    path_globs: Option<Gitignore>,
+}
+
+impl CompiledApprovalPolicy {
+    /// Invalid path globs are skipped; they are rejected when saved
+    pub fn new(policy: ApprovalPolicy) -> Self {
+        let path_globs = (!policy.path_globs.is_empty()).then(|| {
+            let mut builder = GitignoreBuilder::new("");
+            for glob in policy.path_globs.iter() {
+                if let Err(e) = builder.add_line(None, glob.trim()) {
+                    tracing::warn!("Skipping invalid approval path glob '{}': {}", glob, e);
+                }
+            }
+            builder.build().unwrap_or_else(|_| Gitignore::empty())
+        });
+        Self { policy, path_globs }
+    }
+
+    /// Whether the tool call can go ahead without asking. `workspace_dir` is
+    /// the directory path globs are relative to.
+    pub fn auto_approves(&self, tool_name: &str, tool_input: &Value, workspace_dir: &Path) -> bool {
+        let tool_allowed = self
+            .policy
+            .allowed_tools
+            .iter()
+            .any(|tool| tool.eq_ignore_ascii_case(tool_name));
+        let command_allowed = shell_command(tool_input).is_some_and(|command| {
+            self.policy
+                .allowed_commands
+                .iter()
+                .any(|prefix| command_matches(prefix, &command))
+        });
+        if !tool_allowed && !command_allowed {
+            return false;
+        }
+
+        for path in string_fields(tool_input, PATH_FIELDS) {
+            let Some(relative) = workspace_relative(Path::new(path), workspace_dir) else {
+                return false;
+            };
+            if let Some(globs) = &self.path_globs
+                && (relative.as_os_str().is_empty()
+                    || !globs
+                        .matched_path_or_any_parents(relative, false)
+                        .is_ignore())
+            {
+                return false;
+            }
+        }
+
+        if let Some(max_file_size) = self.policy.max_file_size
+            && string_fields(tool_input, CONTENT_FIELDS)
+                .any(|content| content.len() as i64 > max_file_size)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Error message when `glob` is not a valid gitignore-style pattern
+pub fn validate_path_glob(glob: &str) -> Result<(), String> {
+    if glob.trim().is_empty() {
+        return Err("Path glob cannot be empty".to_string());
+    }
+    let mut builder = GitignoreBuilder::new("");
+    builder
+        .add_line(None, glob.trim())
+        .map_err(|e| e.to_string())?;
+    builder.build().map(|_| ()).map_err(|e| e.to_string())
+}
+
+fn string_fields<'a>(input: &'a Value, fields: &'a [&str]) -> impl Iterator<Item = &'a str> {
+    fields
+        .iter()
+        .filter_map(|field| input.get(field).and_then(Value::as_str))
+}
+
+/// The shell command a tool call runs, unwrapping `sh -c <script>` argv
+fn shell_command(input: &Value) -> Option<String> {
+    match input.get("command")? {
+        Value::String(command) => Some(command.trim().to_string()),
+        Value::Array(argv) => {
+            let argv: Vec<&str> = argv.iter().filter_map(Value::as_str).collect();
+            match argv.as_slice() {
+                [shell, "-c" | "-lc", script]
+                    if ["sh", "bash", "zsh"].iter().any(|name| {
+                        Path::new(shell)
+                            .file_name()
+                            .is_some_and(|file| file == *name)
+                    }) =>
+                {
+                    Some(script.trim().to_string())
+                }
+                [] => None,
+                argv => Some(argv.join(" ")),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `prefix` matches whole words at the start of a command with no chaining,
+/// pipes or redirects, so `cargo test` allows `cargo test -p db` but not
+/// `cargo test-all` or `cargo test && rm -rf target`
+fn command_matches(prefix: &str, command: &str) -> bool {
+    let prefix = prefix.trim();
+    if prefix.is_empty() || SHELL_OPERATORS.iter().any(|op| command.contains(op)) {
+        return false;
+    }
+    command == prefix
+        || command
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// `path` relative to the workspace, or None when it leaves the workspace
+fn workspace_relative<'a>(path: &'a Path, workspace_dir: &Path) -> Option<&'a Path> {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(workspace_dir).ok()?
+    } else {
+        path
+    };
+    relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        .then_some(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn policy(
+        tools: &[&str],
+        commands: &[&str],
+        globs: &[&str],
+        max: Option<i64>,
+    ) -> ApprovalPolicy {
+        let strings = |items: &[&str]| Json(items.iter().map(|s| s.to_string()).collect());
+        ApprovalPolicy {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            executor: None,
+            allowed_tools: strings(tools),
+            allowed_commands: strings(commands),
+            path_globs: strings(globs),
+            max_file_size: max,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn allows_listed_command_prefixes_only() {
+        let compiled = CompiledApprovalPolicy::new(policy(&[], &["cargo test"], &[], None));
+        let workspace = Path::new("/ws");
+        let run = |command: Value| {
+            compiled.auto_approves("Bash", &json!({ "command": command }), workspace)
+        };
+
+        assert!(run(json!("cargo test -p db")));
+        assert!(run(json!(["bash", "-lc", "cargo test"])));
+        assert!(!run(json!("cargo test-all")));
+        assert!(!run(json!("cargo test && rm -rf target")));
+        assert!(!run(json!("cargo build")));
+    }
+
+    #[test]
+    fn limits_paths_and_sizes() {
+        let compiled =
+            CompiledApprovalPolicy::new(policy(&["Write"], &[], &["frontend/src/"], Some(10)));
+        let workspace = Path::new("/ws");
+        let write = |path: &str, content: &str| {
+            compiled.auto_approves(
+                "Write",
+                &json!({ "file_path": path, "content": content }),
+                workspace,
+            )
+        };
+
+        assert!(write("/ws/frontend/src/App.tsx", "small"));
+        assert!(write("frontend/src/App.tsx", "small"));
+        assert!(!write("/ws/frontend/src/App.tsx", "far too much content"));
+        assert!(!write("/ws/backend/main.rs", "small"));
+        assert!(!write("/etc/passwd", "small"));
+        assert!(!write("frontend/src/../../secrets", "small"));
+        assert!(!compiled.auto_approves("Edit", &json!({}), workspace));
+    }
+}
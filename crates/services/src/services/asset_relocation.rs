@@ -0,0 +1,338 @@
+//! Moving the asset directory (database, config files, sounds, backups) to
+//! another location, e.g. a bigger disk or a synced folder.
+//!
+//! Everything is copied and checked before the new location is recorded, and
+//! the original directory is left untouched, so a failed relocation changes
+//! nothing. The running process keeps using the original directory until it
+//! restarts; see [`utils::assets::asset_dir`].
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use db::database_files;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, SqliteConnection, sqlite::SqliteConnectOptions};
+use thiserror::Error;
+use tracing::{info, warn};
+use ts_rs::TS;
+use utils::assets::{
+    ASSET_DIR_ENV, AssetDirSource, asset_dir_source, default_asset_dir, relocation_pointer_path,
+};
+use utoipa::ToSchema;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Error)]
+pub enum RelocationError {
+    #[error(
+        "The asset directory is set by {}; change the variable instead",
+        ASSET_DIR_ENV
+    )]
+    SetByEnvironment,
+    #[error("Invalid target directory: {0}")]
+    InvalidTarget(String),
+    #[error("Copy of {0} does not match the original")]
+    Mismatch(String),
+    #[error("Copied database failed integrity check: {0}")]
+    Integrity(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Outcome of a successful relocation
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct RelocationReport {
+    pub from: String,
+    pub to: String,
+    /// Files copied besides the database
+    pub files_copied: usize,
+    #[ts(type = "number")]
+    pub bytes_copied: u64,
+    /// The server shuts down after a relocation and uses the new directory
+    /// from the next start; the original can be deleted once it has
+    pub restart_required: bool,
+}
+
+/// A checked relocation target, ready to copy into
+#[derive(Debug)]
+pub struct PreparedRelocation {
+    from: PathBuf,
+    to: PathBuf,
+    db_path: PathBuf,
+    created: bool,
+}
+
+/// Check the asset directory `from` can be moved to `to`, which must be an
+/// absolute path to an empty or missing directory outside `from`. `to` is
+/// created if missing. The database at `db_path` moves along only if it is
+/// inside `from`; one set elsewhere by `VK_DATABASE_URL` stays where it is.
+pub fn prepare_relocation(
+    from: &Path,
+    db_path: &Path,
+    to: &Path,
+) -> Result<PreparedRelocation, RelocationError> {
+    if asset_dir_source() == AssetDirSource::Environment {
+        return Err(RelocationError::SetByEnvironment);
+    }
+    let (from, to, created) = prepare_target(from, to)?;
+    let db_path = fs::canonicalize(db_path).unwrap_or_else(|_| db_path.to_path_buf());
+    Ok(PreparedRelocation {
+        from,
+        to,
+        db_path,
+        created,
+    })
+}
+
+impl PreparedRelocation {
+    /// Copy the asset directory, verify the copy and record the target as the
+    /// asset directory for the next start.
+    ///
+    /// The database is written with `VACUUM INTO`, which takes a consistent
+    /// snapshot even while the app is running, and checked with `PRAGMA
+    /// integrity_check`. Every other file is compared with its original by
+    /// SHA-256. On failure the partial copy is removed.
+    pub async fn run(self) -> Result<RelocationReport, RelocationError> {
+        let Self {
+            from,
+            to,
+            db_path,
+            created,
+        } = self;
+
+        let result = async {
+            if let Ok(relative) = db_path.strip_prefix(&from) {
+                snapshot_database(&db_path, &to.join(relative)).await?;
+            }
+            let (source, target, db_path) = (from.clone(), to.clone(), db_path.clone());
+            let (files_copied, bytes_copied) =
+                tokio::task::spawn_blocking(move || copy_verified(&source, &target, &db_path))
+                    .await
+                    .map_err(|e| io::Error::other(format!("Task join error: {e}")))??;
+            record_location(&to)?;
+            Ok::<_, RelocationError>((files_copied, bytes_copied))
+        }
+        .await;
+
+        let (files_copied, bytes_copied) = match result {
+            Ok(copied) => copied,
+            Err(e) => {
+                if let Err(cleanup) = fs::remove_dir_all(&to) {
+                    warn!(
+                        "Failed to remove partial copy {}: {}",
+                        to.display(),
+                        cleanup
+                    );
+                } else if !created && let Err(cleanup) = fs::create_dir(&to) {
+                    warn!("Failed to recreate {}: {}", to.display(), cleanup);
+                }
+                return Err(e);
+            }
+        };
+
+        info!(
+            "Relocated asset directory from {} to {} ({} files, {} bytes); restart to use it",
+            from.display(),
+            to.display(),
+            files_copied,
+            bytes_copied
+        );
+        Ok(RelocationReport {
+            from: from.to_string_lossy().into_owned(),
+            to: to.to_string_lossy().into_owned(),
+            files_copied,
+            bytes_copied,
+            restart_required: true,
+        })
+    }
+}
+
+/// Check `to` is usable and create it. Returns both directories canonicalized
+/// and whether `to` was created.
+fn prepare_target(from: &Path, to: &Path) -> Result<(PathBuf, PathBuf, bool), RelocationError> {
+    let invalid = |msg: &str| RelocationError::InvalidTarget(format!("{}: {msg}", to.display()));
+    if !to.is_absolute() {
+        return Err(invalid("must be an absolute path"));
+    }
+    let created = match fs::read_dir(to) {
+        Ok(mut entries) => {
+            if entries.next().is_some() {
+                return Err(invalid("must be empty"));
+            }
+            false
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            fs::create_dir_all(to)?;
+            true
+        }
+        Err(e) if to.is_file() => return Err(invalid(&format!("not a directory ({e})"))),
+        Err(e) => return Err(e.into()),
+    };
+
+    let from = fs::canonicalize(from)?;
+    let to_canonical = fs::canonicalize(to)?;
+    if to_canonical.starts_with(&from) || from.starts_with(&to_canonical) {
+        if created {
+            let _ = fs::remove_dir_all(to);
+        }
+        return Err(invalid(
+            "must not be inside the asset directory or contain it",
+        ));
+    }
+    Ok((from, to_canonical, created))
+}
+
+async fn snapshot_database(source: &Path, target: &Path) -> Result<(), RelocationError> {
+    if !source.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let options = SqliteConnectOptions::new().filename(source).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    sqlx::query("VACUUM INTO ?")
+        .bind(target.to_string_lossy().into_owned())
+        .execute(&mut conn)
+        .await?;
+    let _ = conn.close().await;
+
+    let options = SqliteConnectOptions::new().filename(target).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut conn)
+        .await?;
+    let _ = conn.close().await;
+    if integrity != "ok" {
+        return Err(RelocationError::Integrity(integrity));
+    }
+    Ok(())
+}
+
+fn file_digest(path: &Path) -> io::Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hasher.finalize().into());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+/// Copy everything under `from` except the database at `db_path` into `to`,
+/// checking each copy against its original. Returns the number of files and
+/// bytes copied.
+///
+/// The database is snapshotted separately, and its WAL files belong to the
+/// connections of the live database, so none of them are copied.
+fn copy_verified(from: &Path, to: &Path, db_path: &Path) -> Result<(usize, u64), RelocationError> {
+    let pointer = fs::canonicalize(relocation_pointer_path()).ok();
+    let skipped = database_files(db_path);
+    let mut files = 0;
+    let mut bytes = 0;
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        for entry in fs::read_dir(from.join(&relative))? {
+            let entry = entry?;
+            let relative = relative.join(entry.file_name());
+            let source = entry.path();
+            let file_type = entry.file_type()?;
+
+            if file_type.is_dir() {
+                fs::create_dir_all(to.join(&relative))?;
+                pending.push(relative);
+            } else if file_type.is_symlink() {
+                warn!("Not copying symlink {}", source.display());
+            } else if skipped.contains(&source) || pointer.as_deref() == Some(source.as_path()) {
+                continue;
+            } else {
+                let target = to.join(&relative);
+                bytes += fs::copy(&source, &target)?;
+                if file_digest(&source)? != file_digest(&target)? {
+                    return Err(RelocationError::Mismatch(relative.display().to_string()));
+                }
+                files += 1;
+            }
+        }
+    }
+    Ok((files, bytes))
+}
+
+/// Point the default asset directory at `to`, or clear the pointer when
+/// moving back to the default
+fn record_location(to: &Path) -> io::Result<()> {
+    let pointer = relocation_pointer_path();
+    let default_dir = default_asset_dir();
+    if fs::canonicalize(&default_dir).is_ok_and(|dir| dir == to) {
+        return match fs::remove_file(&pointer) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    fs::create_dir_all(&default_dir)?;
+    let staging = pointer.with_extension("tmp");
+    fs::write(&staging, to.to_string_lossy().as_bytes())?;
+    fs::rename(&staging, &pointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn copies_files_and_skips_the_live_database() {
+        let dir = TempDir::new().unwrap();
+        let from = dir.path().join("assets");
+        let to = dir.path().join("moved");
+        fs::create_dir_all(from.join("alerts")).unwrap();
+        fs::write(from.join("config.json"), "{}").unwrap();
+        fs::write(from.join("alerts/ding.wav"), "wav").unwrap();
+        fs::write(from.join("db.sqlite-wal"), "wal").unwrap();
+        fs::create_dir(&to).unwrap();
+
+        let (files, bytes) = copy_verified(&from, &to, &from.join("db.sqlite")).unwrap();
+        assert_eq!((files, bytes), (2, 5));
+        assert_eq!(
+            fs::read_to_string(to.join("alerts/ding.wav")).unwrap(),
+            "wav"
+        );
+        assert!(!to.join("db.sqlite-wal").exists());
+    }
+
+    #[test]
+    fn rejects_unusable_targets() {
+        let dir = TempDir::new().unwrap();
+        let from = dir.path().join("assets");
+        fs::create_dir(&from).unwrap();
+        fs::write(dir.path().join("taken"), "").unwrap();
+
+        for target in [
+            PathBuf::from("relative"),
+            from.join("nested"),
+            dir.path().to_path_buf(),
+            dir.path().join("taken"),
+        ] {
+            assert!(matches!(
+                prepare_target(&from, &target),
+                Err(RelocationError::InvalidTarget(_))
+            ));
+        }
+        assert!(!from.join("nested").exists());
+
+        let (_, to, created) = prepare_target(&from, &dir.path().join("new")).unwrap();
+        assert!(created && to.is_dir());
+    }
+}
@@ -5,15 +5,20 @@ use std::{
 };
 
 use chrono::Utc;
+use db::{DB_FILE, database_files, database_path};
 use tracing::warn;
+use utils::assets::asset_dir;
 use zip::{CompressionMethod, write::SimpleFileOptions};
 
 use super::BackupError;
 
 const BUFFER_SIZE: usize = 8 * 1024;
 
-const ROOT_FILES: &[&str] = &[
-    "db.sqlite",
+/// Archive names of the database and its WAL files, in [`database_files`] order
+const DB_ENTRIES: [&str; 3] = [DB_FILE, "db.sqlite-wal", "db.sqlite-shm"];
+
+pub(super) const ROOT_FILES: &[&str] = &[
+    DB_FILE,
     "db.sqlite-wal",
     "db.sqlite-shm",
     "config.json",
@@ -22,25 +27,59 @@ const ROOT_FILES: &[&str] = &[
     "credentials.json",
 ];
 
-const ALERT_DIR: &str = "alerts";
+pub(super) const ALERT_DIR: &str = "alerts";
+
+/// Where the files a backup holds live on disk. The database is archived as
+/// `db.sqlite` even when `VK_DATABASE_URL` keeps it outside the asset directory.
+#[derive(Debug, Clone)]
+pub struct AssetPaths {
+    pub asset_dir: PathBuf,
+    pub db_path: PathBuf,
+}
+
+impl AssetPaths {
+    /// The asset directory and database of this run
+    pub fn resolve() -> Result<Self, BackupError> {
+        Ok(Self {
+            asset_dir: asset_dir(),
+            db_path: database_path()?,
+        })
+    }
+
+    /// `asset_dir` with the database at its default place inside it
+    pub fn in_dir(asset_dir: &Path) -> Self {
+        Self {
+            asset_dir: asset_dir.to_path_buf(),
+            db_path: asset_dir.join(DB_FILE),
+        }
+    }
+
+    /// Location on disk of the archive entry `name`
+    pub(super) fn path_of(&self, name: &str) -> PathBuf {
+        match DB_ENTRIES.iter().position(|f| *f == name) {
+            Some(i) => database_files(&self.db_path)[i].clone(),
+            None => self.asset_dir.join(name),
+        }
+    }
+}
 
 /// Creates a backup archive containing database, config files, and custom sounds.
 ///
 /// Returns the path to the created ZIP file.
 pub async fn create_backup_archive(
-    asset_dir: &Path,
+    paths: &AssetPaths,
     backup_dir: &Path,
 ) -> Result<PathBuf, BackupError> {
-    let asset_dir = asset_dir.to_path_buf();
+    let paths = paths.clone();
     let backup_dir = backup_dir.to_path_buf();
 
-    tokio::task::spawn_blocking(move || create_backup_archive_blocking(&asset_dir, &backup_dir))
+    tokio::task::spawn_blocking(move || create_backup_archive_blocking(&paths, &backup_dir))
         .await
         .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))?
 }
 
 fn create_backup_archive_blocking(
-    asset_dir: &Path,
+    paths: &AssetPaths,
     backup_dir: &Path,
 ) -> Result<PathBuf, BackupError> {
     std::fs::create_dir_all(backup_dir)?;
@@ -55,15 +94,16 @@ fn create_backup_archive_blocking(
     let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
 
     for file_name in ROOT_FILES {
-        let file_path = asset_dir.join(file_name);
+        let file_path = paths.path_of(file_name);
         if file_path.exists() {
             add_file_to_zip(&mut zip, &file_path, file_name, options)?;
-        } else {
+        } else if !DB_ENTRIES[1..].contains(file_name) {
+            // WAL files are absent after a checkpoint and in snapshots
             warn!("Skipping missing file: {}", file_path.display());
         }
     }
 
-    let alerts_dir = asset_dir.join(ALERT_DIR);
+    let alerts_dir = paths.asset_dir.join(ALERT_DIR);
     if alerts_dir.exists() && alerts_dir.is_dir() {
         add_directory_to_zip(&mut zip, &alerts_dir, ALERT_DIR, options)?;
     } else {
@@ -147,9 +187,10 @@ mod tests {
         std::fs::write(alerts_dir.join("sound1.mp3"), b"fake mp3 data").unwrap();
         std::fs::write(alerts_dir.join("sound2.wav"), b"fake wav data").unwrap();
 
-        let archive_path = create_backup_archive(asset_dir.path(), backup_dir.path())
-            .await
-            .unwrap();
+        let archive_path =
+            create_backup_archive(&AssetPaths::in_dir(asset_dir.path()), backup_dir.path())
+                .await
+                .unwrap();
 
         assert!(archive_path.exists());
         assert!(
@@ -189,16 +230,44 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_handles_missing_optional_files() {
+    async fn test_archives_database_outside_asset_dir() {
         let asset_dir = TempDir::new().unwrap();
+        let db_dir = TempDir::new().unwrap();
         let backup_dir = TempDir::new().unwrap();
 
-        std::fs::write(asset_dir.path().join("config.json"), b"{}").unwrap();
+        let paths = AssetPaths {
+            asset_dir: asset_dir.path().to_path_buf(),
+            db_path: db_dir.path().join("shared.sqlite"),
+        };
+        std::fs::write(&paths.db_path, b"shared database").unwrap();
+        std::fs::write(db_dir.path().join("shared.sqlite-wal"), b"wal").unwrap();
 
-        let archive_path = create_backup_archive(asset_dir.path(), backup_dir.path())
+        let archive_path = create_backup_archive(&paths, backup_dir.path())
             .await
             .unwrap();
 
+        let mut zip = zip::ZipArchive::new(File::open(&archive_path).unwrap()).unwrap();
+        let mut contents = Vec::new();
+        zip.by_name("db.sqlite")
+            .unwrap()
+            .read_to_end(&mut contents)
+            .unwrap();
+        assert_eq!(contents, b"shared database");
+        assert!(zip.by_name("db.sqlite-wal").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_handles_missing_optional_files() {
+        let asset_dir = TempDir::new().unwrap();
+        let backup_dir = TempDir::new().unwrap();
+
+        std::fs::write(asset_dir.path().join("config.json"), b"{}").unwrap();
+
+        let archive_path =
+            create_backup_archive(&AssetPaths::in_dir(asset_dir.path()), backup_dir.path())
+                .await
+                .unwrap();
+
         assert!(archive_path.exists());
 
         let file = File::open(&archive_path).unwrap();
@@ -226,9 +295,10 @@ mod tests {
         std::fs::create_dir(&alerts_dir).unwrap();
         std::fs::write(alerts_dir.join("beep.wav"), b"beep").unwrap();
 
-        let archive_path = create_backup_archive(asset_dir.path(), backup_dir.path())
-            .await
-            .unwrap();
+        let archive_path =
+            create_backup_archive(&AssetPaths::in_dir(asset_dir.path()), backup_dir.path())
+                .await
+                .unwrap();
 
         let file = File::open(&archive_path).unwrap();
         let mut zip = zip::ZipArchive::new(file).unwrap();
@@ -8,6 +8,9 @@ pub enum BackupError {
     #[error("Zip error: {0}")]
     Zip(#[from] zip::result::ZipError),
 
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
     #[error("Backup directory error: {0}")]
     BackupDirError(String),
 
@@ -16,4 +19,16 @@ pub enum BackupError {
 
     #[error("Retention policy error: {0}")]
     RetentionError(String),
+
+    #[error("Backup not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid backup archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("Restore failed: {0}")]
+    Restore(String),
+
+    #[error("Backup storage error: {0}")]
+    Storage(String),
 }
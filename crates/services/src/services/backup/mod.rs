@@ -1,9 +1,23 @@
 pub mod archive;
 pub mod error;
+pub mod pre_migration;
+pub mod restore;
 pub mod retention;
+pub mod s3;
+pub mod scope;
+pub mod storage;
+pub mod verify;
 
-pub use archive::create_backup_archive;
+pub use archive::{AssetPaths, create_backup_archive};
 pub use error::BackupError;
+pub use pre_migration::{backup_before_migrations, restore_pre_migration_backup};
+pub use restore::{
+    RestoreReport, apply_pending_restore, restore_backup_archive, set_aside_pending_restore,
+};
 pub use retention::{
     BackupFile, apply_gfs_retention, delete_old_backups, list_backups, parse_backup_filename,
 };
+pub use s3::S3BackupStorage;
+pub use scope::find_single_project_backups;
+pub use storage::{BackupStorage, LocalBackupStorage};
+pub use verify::{snapshot_database, verify_backup_archive};
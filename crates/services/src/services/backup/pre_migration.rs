@@ -0,0 +1,218 @@
+//! Snapshot taken before startup applies new migrations.
+//!
+//! Migrations only run forward, so when a build finds migrations the database
+//! has not seen yet, the asset files are archived first and the schema version
+//! they were at is recorded next to the backups. Starting the server with
+//! `--rollback-last-migration-restore` puts that snapshot back so the previous
+//! build can run again.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use db::{DB_FILE, DBService};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::{
+    AssetPaths, BackupError, RestoreReport, create_backup_archive,
+    restore::{check_sqlite_header, extract_archive, swap_in},
+};
+
+/// Written to the backup dir; describes the latest pre-migration snapshot
+pub const PRE_MIGRATION_RECORD: &str = "pre_migration.json";
+/// Kept apart from `backup_` archives so retention never prunes them
+const ARCHIVE_PREFIX: &str = "pre_migration_";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreMigrationBackup {
+    /// Archive holding the asset files as they were before migrating
+    pub archive: PathBuf,
+    /// Newest migration applied to the database in the archive
+    pub from_version: i64,
+    /// Newest migration of the build that took the snapshot
+    pub to_version: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Archive the asset files if the database at `paths.db_path` has pending
+/// migrations. Returns None when there is nothing to migrate, including for a
+/// database that does not exist yet.
+pub async fn backup_before_migrations(
+    paths: &AssetPaths,
+    backup_dir: &Path,
+) -> Result<Option<PreMigrationBackup>, BackupError> {
+    let Some(latest) = DBService::latest_migration_version() else {
+        return Ok(None);
+    };
+    let Some(applied) = DBService::applied_migration_version(&paths.db_path).await? else {
+        return Ok(None);
+    };
+    if applied >= latest {
+        return Ok(None);
+    }
+
+    let created_at = Utc::now();
+    let archive = backup_dir.join(format!(
+        "{ARCHIVE_PREFIX}{applied}_{}.zip",
+        created_at.format("%Y%m%d_%H%M%S")
+    ));
+    fs::rename(create_backup_archive(paths, backup_dir).await?, &archive)?;
+
+    // Only the latest snapshot can be rolled back to
+    if let Some(previous) = read_record(backup_dir)?
+        && previous.archive != archive
+        && let Err(e) = fs::remove_file(&previous.archive)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        warn!(
+            "Failed to remove previous pre-migration backup {}: {}",
+            previous.archive.display(),
+            e
+        );
+    }
+
+    let record = PreMigrationBackup {
+        archive,
+        from_version: applied,
+        to_version: latest,
+        created_at,
+    };
+    let json = serde_json::to_vec_pretty(&record)
+        .map_err(|e| BackupError::BackupDirError(e.to_string()))?;
+    fs::write(backup_dir.join(PRE_MIGRATION_RECORD), json)?;
+
+    info!(
+        "Backed up {} before migrating from {} to {}",
+        record.archive.display(),
+        applied,
+        latest
+    );
+    Ok(Some(record))
+}
+
+/// Put the latest pre-migration snapshot back into `paths`.
+///
+/// Must run before anything opens the database. The replaced files are kept
+/// in a rollback directory, as with a regular restore, and the record is
+/// removed so the same snapshot is not restored twice.
+pub async fn restore_pre_migration_backup(
+    paths: &AssetPaths,
+    backup_dir: &Path,
+) -> Result<RestoreReport, BackupError> {
+    let record = read_record(backup_dir)?
+        .ok_or_else(|| BackupError::NotFound("no pre-migration backup recorded".to_string()))?;
+
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let staging_dir = paths
+        .asset_dir
+        .join(format!(".restore-staging-{timestamp}"));
+    let rollback_dir = paths
+        .asset_dir
+        .join(format!(".restore-rollback-{timestamp}"));
+
+    let result = {
+        let (archive, staging_dir, rollback_dir, paths) = (
+            record.archive.clone(),
+            staging_dir.clone(),
+            rollback_dir.clone(),
+            paths.clone(),
+        );
+        tokio::task::spawn_blocking(move || {
+            let restored = extract_archive(&archive, &staging_dir)?;
+            check_sqlite_header(&staging_dir.join(DB_FILE))?;
+            swap_in(&staging_dir, &rollback_dir, &paths, &restored)?;
+            Ok::<_, BackupError>(restored)
+        })
+        .await
+        .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))?
+    };
+
+    if let Err(e) = fs::remove_dir_all(&staging_dir)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        warn!("Failed to remove restore staging dir: {}", e);
+    }
+
+    let restored = result?;
+    fs::remove_file(backup_dir.join(PRE_MIGRATION_RECORD))?;
+    info!(
+        "Restored the database to migration {} from {}; replaced files kept in {}",
+        record.from_version,
+        record.archive.display(),
+        rollback_dir.display()
+    );
+    Ok(RestoreReport {
+        restored,
+        rollback_dir: rollback_dir.to_string_lossy().into_owned(),
+        restart_required: false,
+    })
+}
+
+fn read_record(backup_dir: &Path) -> Result<Option<PreMigrationBackup>, BackupError> {
+    match fs::read(backup_dir.join(PRE_MIGRATION_RECORD)) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(|e| {
+            BackupError::BackupDirError(format!("Invalid {PRE_MIGRATION_RECORD}: {e}"))
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn restores_recorded_snapshot_once() {
+        let dir = TempDir::new().unwrap();
+        let asset_dir = dir.path().join("assets");
+        let backup_dir = dir.path().join("backups");
+        fs::create_dir_all(&asset_dir).unwrap();
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(asset_dir.join(DB_FILE), b"migrated").unwrap();
+
+        let archive = backup_dir.join("pre_migration_1_20260101_000000.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        zip.start_file(DB_FILE, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"SQLite format 3\0before").unwrap();
+        zip.finish().unwrap();
+
+        let record = PreMigrationBackup {
+            archive,
+            from_version: 1,
+            to_version: 2,
+            created_at: Utc::now(),
+        };
+        fs::write(
+            backup_dir.join(PRE_MIGRATION_RECORD),
+            serde_json::to_vec(&record).unwrap(),
+        )
+        .unwrap();
+
+        let report = restore_pre_migration_backup(&AssetPaths::in_dir(&asset_dir), &backup_dir)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read(asset_dir.join(DB_FILE)).unwrap(),
+            b"SQLite format 3\0before"
+        );
+        assert_eq!(
+            fs::read(Path::new(&report.rollback_dir).join(DB_FILE)).unwrap(),
+            b"migrated"
+        );
+
+        assert!(matches!(
+            restore_pre_migration_backup(&AssetPaths::in_dir(&asset_dir), &backup_dir).await,
+            Err(BackupError::NotFound(_))
+        ));
+    }
+}
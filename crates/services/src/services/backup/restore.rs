@@ -0,0 +1,484 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use db::DB_FILE;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    Connection, SqliteConnection, SqliteExecutor, SqlitePool, sqlite::SqliteConnectOptions,
+};
+use tracing::{info, warn};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::{
+    BackupError,
+    archive::{ALERT_DIR, AssetPaths, ROOT_FILES},
+};
+
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// Staged restore applied by [`apply_pending_restore`] on the next start
+const PENDING_DIR: &str = ".restore-pending";
+/// Describes the staged restore; lives in [`PENDING_DIR`] next to its files
+const PENDING_RECORD: &str = "restore.json";
+
+/// Outcome of a successful restore
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct RestoreReport {
+    /// Files and directories put in place from the archive
+    pub restored: Vec<String>,
+    /// Where the replaced files are moved, in case the restore needs undoing
+    pub rollback_dir: String,
+    /// The live database cannot be swapped while connections are open, so
+    /// the files are put in place when the app next starts
+    pub restart_required: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingRestore {
+    archive: PathBuf,
+    restored: Vec<String>,
+    rollback_dir: PathBuf,
+}
+
+/// Stages an archive created by [`super::create_backup_archive`] to be
+/// restored into `paths` on the next start.
+///
+/// The archive is extracted to a staging directory and validated (known entries
+/// only, an intact SQLite database no newer than this build's schema) against
+/// the `live` database. Nothing in use is touched: the staged files replace
+/// the current ones in [`apply_pending_restore`], before the database is
+/// opened. Staging again replaces an earlier staged restore.
+pub async fn restore_backup_archive(
+    live: &SqlitePool,
+    archive_path: &Path,
+    paths: &AssetPaths,
+) -> Result<RestoreReport, BackupError> {
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let staging_dir = paths
+        .asset_dir
+        .join(format!(".restore-staging-{timestamp}"));
+    let pending_dir = paths.asset_dir.join(PENDING_DIR);
+    let rollback_dir = paths
+        .asset_dir
+        .join(format!(".restore-rollback-{timestamp}"));
+
+    let extracted = {
+        let archive_path = archive_path.to_path_buf();
+        let staging_dir = staging_dir.clone();
+        tokio::task::spawn_blocking(move || extract_archive(&archive_path, &staging_dir))
+            .await
+            .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))?
+    };
+    let result: Result<Vec<String>, BackupError> = async {
+        let restored = extracted?;
+        validate_database(&staging_dir.join(DB_FILE), live).await?;
+
+        let record = PendingRestore {
+            archive: archive_path.to_path_buf(),
+            restored: restored.clone(),
+            rollback_dir: rollback_dir.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&record)
+            .map_err(|e| BackupError::BackupDirError(e.to_string()))?;
+        fs::write(staging_dir.join(PENDING_RECORD), json)?;
+
+        if let Err(e) = fs::remove_dir_all(&pending_dir)
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            return Err(e.into());
+        }
+        fs::rename(&staging_dir, &pending_dir)?;
+        Ok(restored)
+    }
+    .await;
+
+    if let Err(e) = fs::remove_dir_all(&staging_dir)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        warn!("Failed to remove restore staging dir: {}", e);
+    }
+
+    let restored = result?;
+    info!(
+        "Staged {} from {}; restart to restore them",
+        restored.join(", "),
+        archive_path.display()
+    );
+    Ok(RestoreReport {
+        restored,
+        rollback_dir: rollback_dir.to_string_lossy().into_owned(),
+        restart_required: true,
+    })
+}
+
+/// Put a restore staged by [`restore_backup_archive`] in place. Must run
+/// before anything opens the database. Returns None when no restore is staged.
+pub async fn apply_pending_restore(
+    paths: &AssetPaths,
+) -> Result<Option<RestoreReport>, BackupError> {
+    let pending_dir = paths.asset_dir.join(PENDING_DIR);
+    let record: PendingRestore = match fs::read(pending_dir.join(PENDING_RECORD)) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| BackupError::BackupDirError(format!("Invalid {PENDING_RECORD}: {e}")))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    {
+        let (pending_dir, rollback_dir, paths, restored) = (
+            pending_dir.clone(),
+            record.rollback_dir.clone(),
+            paths.clone(),
+            record.restored.clone(),
+        );
+        tokio::task::spawn_blocking(move || {
+            swap_in(&pending_dir, &rollback_dir, &paths, &restored)
+        })
+        .await
+        .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))??;
+    }
+    fs::remove_dir_all(&pending_dir)?;
+
+    info!(
+        "Restored {} from {}; replaced files kept in {}",
+        record.restored.join(", "),
+        record.archive.display(),
+        record.rollback_dir.display()
+    );
+    Ok(Some(RestoreReport {
+        restored: record.restored,
+        rollback_dir: record.rollback_dir.to_string_lossy().into_owned(),
+        restart_required: false,
+    }))
+}
+
+/// Move a staged restore that could not be applied out of the way, so the
+/// app starts on the current files instead of failing at every start.
+/// Returns where it was moved, or None when no restore is staged.
+pub fn set_aside_pending_restore(paths: &AssetPaths) -> io::Result<Option<PathBuf>> {
+    let pending_dir = paths.asset_dir.join(PENDING_DIR);
+    if !pending_dir.exists() {
+        return Ok(None);
+    }
+    let failed_dir = paths.asset_dir.join(format!(
+        ".restore-failed-{}",
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ));
+    fs::rename(&pending_dir, &failed_dir)?;
+    Ok(Some(failed_dir))
+}
+
+fn is_known_entry(name: &Path) -> bool {
+    let mut components = name.components();
+    let Some(first) = components.next().map(|c| c.as_os_str()) else {
+        return false;
+    };
+    first == ALERT_DIR || (components.next().is_none() && ROOT_FILES.iter().any(|f| first == *f))
+}
+
+/// Extract the archive into `staging_dir`, rejecting unknown or escaping
+/// entries. Returns the top-level names to swap in.
+pub(super) fn extract_archive(
+    archive_path: &Path,
+    staging_dir: &Path,
+) -> Result<Vec<String>, BackupError> {
+    let mut zip = zip::ZipArchive::new(File::open(archive_path)?)
+        .map_err(|e| BackupError::InvalidArchive(format!("Not a backup archive: {e}")))?;
+
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i)?;
+        let name = entry.enclosed_name().filter(|n| is_known_entry(n));
+        if name.is_none() {
+            return Err(BackupError::InvalidArchive(format!(
+                "Unexpected entry '{}'",
+                entry.name()
+            )));
+        }
+    }
+    if zip.index_for_name(DB_FILE).is_none() {
+        return Err(BackupError::InvalidArchive(format!(
+            "Archive does not contain {DB_FILE}"
+        )));
+    }
+
+    fs::create_dir_all(staging_dir)?;
+    zip.extract(staging_dir)?;
+
+    let mut restored: Vec<String> = ROOT_FILES
+        .iter()
+        .filter(|f| staging_dir.join(f).is_file())
+        .map(|f| f.to_string())
+        .collect();
+    if staging_dir.join(ALERT_DIR).is_dir() {
+        restored.push(ALERT_DIR.to_string());
+    }
+    Ok(restored)
+}
+
+pub(super) async fn max_migration<'e>(
+    executor: impl SqliteExecutor<'e>,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(executor)
+        .await
+}
+
+/// Check the staged database is intact and was not written by a newer build,
+/// whose migrations this one could not undo
+async fn validate_database(db_path: &Path, live: &SqlitePool) -> Result<(), BackupError> {
+    check_sqlite_header(db_path)?;
+
+    let invalid = |e: sqlx::Error| BackupError::InvalidArchive(format!("{DB_FILE}: {e}"));
+    let options = SqliteConnectOptions::new()
+        .filename(db_path)
+        .read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(invalid)?;
+
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(invalid)?;
+    if integrity != "ok" {
+        return Err(BackupError::InvalidArchive(format!(
+            "{DB_FILE} failed integrity check: {integrity}"
+        )));
+    }
+
+    let backup_version = max_migration(&mut conn).await.map_err(invalid)?;
+    let _ = conn.close().await;
+    let live_version = max_migration(live).await.map_err(invalid)?;
+    if backup_version > live_version {
+        return Err(BackupError::InvalidArchive(format!(
+            "Backup schema version {} is newer than this build ({}); upgrade before restoring",
+            backup_version.unwrap_or_default(),
+            live_version.unwrap_or_default()
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn check_sqlite_header(db_path: &Path) -> Result<(), BackupError> {
+    let mut header = [0u8; SQLITE_HEADER.len()];
+    let read = File::open(db_path)?.read_exact(&mut header);
+    if read.is_err() || header != SQLITE_HEADER {
+        return Err(BackupError::InvalidArchive(format!(
+            "{DB_FILE} is not a SQLite database"
+        )));
+    }
+    Ok(())
+}
+
+/// Move every current root file (and the alerts dir, if the archive has one)
+/// into `rollback_dir`, then move the staged copies into place. Undoes all
+/// moves if any step fails.
+pub(super) fn swap_in(
+    staging_dir: &Path,
+    rollback_dir: &Path,
+    paths: &AssetPaths,
+    restored: &[String],
+) -> Result<(), BackupError> {
+    fs::create_dir_all(rollback_dir)?;
+
+    // A stale WAL left next to a restored database would be replayed into it,
+    // so every root file moves out even when the archive lacks it
+    let mut to_replace: Vec<&str> = ROOT_FILES.to_vec();
+    if restored.iter().any(|r| r == ALERT_DIR) {
+        to_replace.push(ALERT_DIR);
+    }
+
+    let mut moved_out: Vec<&str> = Vec::new();
+    let mut moved_in: Vec<&str> = Vec::new();
+    let result = (|| -> io::Result<()> {
+        for name in &to_replace {
+            if paths.path_of(name).exists() {
+                move_path(&paths.path_of(name), &rollback_dir.join(name))?;
+                moved_out.push(*name);
+            }
+        }
+        for name in restored {
+            move_path(&staging_dir.join(name), &paths.path_of(name))?;
+            moved_in.push(name.as_str());
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        warn!("Restore failed, rolling back: {}", e);
+        for name in moved_in {
+            let _ = remove_path(&paths.path_of(name));
+        }
+        for name in moved_out {
+            if let Err(e) = move_path(&rollback_dir.join(name), &paths.path_of(name)) {
+                warn!("Failed to roll back {}: {}", name, e);
+            }
+        }
+        return Err(BackupError::Restore(e.to_string()));
+    }
+    Ok(())
+}
+
+/// Rename `from` to `to`, copying files instead when `VK_DATABASE_URL` puts
+/// the database on another filesystem than the asset directory
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices && from.is_file() => {
+            fs::copy(from, to)?;
+            fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_and_escaping_entries() {
+        let dir = TempDir::new().unwrap();
+        let staging = dir.path().join("staging");
+
+        for bad in ["../db.sqlite", "notes.txt", "alertsx/a.wav"] {
+            let archive = dir.path().join("bad.zip");
+            write_zip(&archive, &[("db.sqlite", b"db"), (bad, b"x")]);
+            assert!(matches!(
+                extract_archive(&archive, &staging),
+                Err(BackupError::InvalidArchive(_))
+            ));
+        }
+
+        let archive = dir.path().join("no_db.zip");
+        write_zip(&archive, &[("config.json", b"{}")]);
+        assert!(matches!(
+            extract_archive(&archive, &staging),
+            Err(BackupError::InvalidArchive(_))
+        ));
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn swaps_files_and_keeps_replaced_ones() {
+        let dir = TempDir::new().unwrap();
+        let asset_dir = dir.path().join("assets");
+        let staging = dir.path().join("staging");
+        let rollback = dir.path().join("rollback");
+        fs::create_dir_all(asset_dir.join(ALERT_DIR)).unwrap();
+        fs::write(asset_dir.join("db.sqlite"), b"old db").unwrap();
+        fs::write(asset_dir.join("db.sqlite-wal"), b"old wal").unwrap();
+        fs::write(asset_dir.join("config.json"), b"old").unwrap();
+        fs::write(asset_dir.join(ALERT_DIR).join("old.wav"), b"old").unwrap();
+
+        let archive = dir.path().join("backup.zip");
+        write_zip(
+            &archive,
+            &[
+                ("db.sqlite", b"new db"),
+                ("config.json", b"new"),
+                ("alerts/new.wav", b"new"),
+            ],
+        );
+        let restored = extract_archive(&archive, &staging).unwrap();
+        assert_eq!(restored, vec!["db.sqlite", "config.json", "alerts"]);
+
+        swap_in(
+            &staging,
+            &rollback,
+            &AssetPaths::in_dir(&asset_dir),
+            &restored,
+        )
+        .unwrap();
+
+        assert_eq!(fs::read(asset_dir.join("db.sqlite")).unwrap(), b"new db");
+        assert_eq!(fs::read(asset_dir.join("config.json")).unwrap(), b"new");
+        assert!(!asset_dir.join("db.sqlite-wal").exists());
+        assert!(asset_dir.join("alerts/new.wav").exists());
+        assert!(!asset_dir.join("alerts/old.wav").exists());
+
+        assert_eq!(fs::read(rollback.join("db.sqlite")).unwrap(), b"old db");
+        assert_eq!(
+            fs::read(rollback.join("db.sqlite-wal")).unwrap(),
+            b"old wal"
+        );
+        assert!(rollback.join("alerts/old.wav").exists());
+    }
+
+    #[tokio::test]
+    async fn applies_staged_restore_once() {
+        let dir = TempDir::new().unwrap();
+        let asset_dir = dir.path().join("assets");
+        let pending = asset_dir.join(PENDING_DIR);
+        let rollback = asset_dir.join(".restore-rollback-test");
+        fs::create_dir_all(&pending).unwrap();
+        fs::write(asset_dir.join("db.sqlite"), b"old db").unwrap();
+        fs::write(asset_dir.join("db.sqlite-shm"), b"old shm").unwrap();
+        fs::write(pending.join("db.sqlite"), b"new db").unwrap();
+        let record = PendingRestore {
+            archive: dir.path().join("backup.zip"),
+            restored: vec!["db.sqlite".to_string()],
+            rollback_dir: rollback.clone(),
+        };
+        fs::write(
+            pending.join(PENDING_RECORD),
+            serde_json::to_vec(&record).unwrap(),
+        )
+        .unwrap();
+
+        let paths = AssetPaths::in_dir(&asset_dir);
+        let report = apply_pending_restore(&paths).await.unwrap().unwrap();
+        assert_eq!(report.restored, vec!["db.sqlite"]);
+        assert_eq!(fs::read(asset_dir.join("db.sqlite")).unwrap(), b"new db");
+        assert!(!asset_dir.join("db.sqlite-shm").exists());
+        assert_eq!(fs::read(rollback.join("db.sqlite")).unwrap(), b"old db");
+        assert!(!pending.exists());
+
+        assert!(apply_pending_restore(&paths).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn unreadable_pending_restore_is_set_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_dir = dir.path().join("assets");
+        let pending = asset_dir.join(PENDING_DIR);
+        fs::create_dir_all(&pending).unwrap();
+        fs::write(pending.join(PENDING_RECORD), b"not json").unwrap();
+
+        let paths = AssetPaths::in_dir(&asset_dir);
+        assert!(apply_pending_restore(&paths).await.is_err());
+
+        let failed = set_aside_pending_restore(&paths).unwrap().unwrap();
+        assert!(!pending.exists());
+        assert!(failed.join(PENDING_RECORD).exists());
+        assert!(apply_pending_restore(&paths).await.unwrap().is_none());
+        assert!(set_aside_pending_restore(&paths).unwrap().is_none());
+    }
+}
@@ -269,6 +269,9 @@ mod tests {
             retention_daily_days: 7,
             retention_weekly_weeks: 4,
             retention_monthly_months: 12,
+            s3: None,
+            verify_after_backup: true,
+            drill_interval_days: 7,
         }
     }
 
@@ -540,6 +543,9 @@ mod tests {
             retention_daily_days: 3,     // Only 3 days
             retention_weekly_weeks: 2,   // Only 2 weeks
             retention_monthly_months: 6, // Only 6 months
+            s3: None,
+            verify_after_backup: true,
+            drill_interval_days: 7,
         };
         let now = Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
 
@@ -0,0 +1,64 @@
+use std::{fs, path::Path};
+
+use db::DB_FILE;
+use sqlx::{Connection, SqliteConnection, sqlite::SqliteConnectOptions};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::{
+    BackupError, BackupFile, list_backups,
+    restore::{check_sqlite_header, extract_archive},
+};
+
+/// Ids of the projects in an archive's database, read from a scratch copy
+pub async fn archived_project_ids(archive_path: &Path) -> Result<Vec<Uuid>, BackupError> {
+    let scratch = tempfile::Builder::new()
+        .prefix("vk-backup-scope-")
+        .tempdir()?;
+    let scratch_dir = scratch.path().join("assets");
+    {
+        let archive_path = archive_path.to_path_buf();
+        let scratch_dir = scratch_dir.clone();
+        tokio::task::spawn_blocking(move || extract_archive(&archive_path, &scratch_dir))
+            .await
+            .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))??;
+    }
+
+    let db_path = scratch_dir.join(DB_FILE);
+    check_sqlite_header(&db_path)?;
+    let _ = fs::remove_file(scratch_dir.join(format!("{DB_FILE}-shm")));
+
+    let invalid = |e: sqlx::Error| BackupError::InvalidArchive(format!("{DB_FILE}: {e}"));
+    // Writable, so the archived WAL is replayed into the scratch copy
+    let options = SqliteConnectOptions::new().filename(&db_path);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(invalid)?;
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM projects")
+        .fetch_all(&mut conn)
+        .await
+        .map_err(invalid)?;
+    let _ = conn.close().await;
+    Ok(ids)
+}
+
+/// Local backups holding `project_id` and no other project, so deleting them
+/// loses nothing else. Archives that cannot be read are left alone.
+pub async fn find_single_project_backups(
+    backup_dir: &Path,
+    project_id: Uuid,
+) -> Result<Vec<BackupFile>, BackupError> {
+    let mut matching = Vec::new();
+    for backup in list_backups(backup_dir)? {
+        match archived_project_ids(&backup.path).await {
+            Ok(ids) if ids == [project_id] => matching.push(backup),
+            Ok(_) => {}
+            Err(e) => warn!(
+                "Skipping unreadable backup {}: {}",
+                backup.path.display(),
+                e
+            ),
+        }
+    }
+    Ok(matching)
+}
@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::{BackupError, BackupFile, delete_old_backups, list_backups};
+
+/// Somewhere backup archives are kept.
+///
+/// Archives are always created in the local backup directory; other storages
+/// receive a copy and apply retention to their own listing. The `path` of a
+/// [`BackupFile`] returned by [`BackupStorage::list`] is the storage's own
+/// location for it (a file path locally, an object key remotely) and is what
+/// [`BackupStorage::delete`] expects back.
+#[async_trait]
+pub trait BackupStorage: Send + Sync {
+    /// Short description for logs
+    fn name(&self) -> String;
+
+    /// Store the local archive at `archive` under its filename
+    async fn upload(&self, archive: &Path) -> Result<(), BackupError>;
+
+    /// Stored backups, oldest first
+    async fn list(&self) -> Result<Vec<BackupFile>, BackupError>;
+
+    /// Delete backups by the `path` from [`BackupStorage::list`].
+    /// Returns the number deleted.
+    async fn delete(&self, backups: Vec<PathBuf>) -> Result<u32, BackupError>;
+}
+
+/// The local backup directory
+pub struct LocalBackupStorage {
+    dir: PathBuf,
+}
+
+impl LocalBackupStorage {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+#[async_trait]
+impl BackupStorage for LocalBackupStorage {
+    fn name(&self) -> String {
+        self.dir.display().to_string()
+    }
+
+    async fn upload(&self, archive: &Path) -> Result<(), BackupError> {
+        // Archives are created here, so this only copies foreign ones
+        if archive.parent() == Some(self.dir.as_path()) {
+            return Ok(());
+        }
+        let filename = archive
+            .file_name()
+            .ok_or_else(|| BackupError::InvalidFilename(archive.display().to_string()))?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tokio::fs::copy(archive, self.dir.join(filename)).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<BackupFile>, BackupError> {
+        list_backups(&self.dir)
+    }
+
+    async fn delete(&self, backups: Vec<PathBuf>) -> Result<u32, BackupError> {
+        delete_old_backups(backups)
+    }
+}
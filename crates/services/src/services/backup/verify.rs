@@ -0,0 +1,305 @@
+use std::{collections::BTreeMap, fs, path::Path, time::Instant};
+
+use db::{
+    DB_FILE,
+    models::backup_verification::{
+        BackupVerification, BackupVerificationKind, CreateBackupVerification, TableRowCount,
+    },
+};
+use sqlx::{Connection, SqliteConnection, SqlitePool, sqlite::SqliteConnectOptions};
+use tracing::{info, warn};
+
+use super::{
+    BackupError,
+    restore::{check_sqlite_header, extract_archive, max_migration},
+};
+
+/// Tables whose rows are counted in every check
+const COUNTED_TABLES: &[&str] = &[
+    "projects",
+    "repos",
+    "tasks",
+    "workspaces",
+    "sessions",
+    "execution_processes",
+    "tags",
+    "task_schedules",
+];
+
+async fn count_rows(conn: &mut SqliteConnection) -> Result<BTreeMap<String, i64>, sqlx::Error> {
+    let mut counts = BTreeMap::new();
+    for table in COUNTED_TABLES {
+        let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {table}"))
+            .fetch_one(&mut *conn)
+            .await?;
+        counts.insert(table.to_string(), rows);
+    }
+    Ok(counts)
+}
+
+/// Copy the live database to `dest` with `VACUUM INTO` and count the rows of
+/// the copy. Archiving the copy instead of the live files means the counts
+/// describe exactly what the archive holds, however busy the database is.
+pub async fn snapshot_database(
+    pool: &SqlitePool,
+    dest: &Path,
+) -> Result<BTreeMap<String, i64>, BackupError> {
+    sqlx::query("VACUUM INTO $1")
+        .bind(dest.to_string_lossy().into_owned())
+        .execute(pool)
+        .await?;
+
+    let options = SqliteConnectOptions::new().filename(dest).read_only(true);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    let counts = count_rows(&mut conn).await;
+    let _ = conn.close().await;
+    Ok(counts?)
+}
+
+/// Check an archive as a restore would use it and record the result.
+///
+/// The archive is extracted to a scratch directory with the same rules as a
+/// restore, then its database is opened, run through `PRAGMA
+/// integrity_check`, compared with the live schema version and its rows
+/// counted. With `expected` counts, from [`snapshot_database`], any table
+/// whose count differs fails the check. Nothing outside the scratch directory
+/// is touched.
+pub async fn verify_backup_archive(
+    pool: &SqlitePool,
+    archive_path: &Path,
+    kind: BackupVerificationKind,
+    expected: Option<&BTreeMap<String, i64>>,
+) -> Result<BackupVerification, sqlx::Error> {
+    let started = Instant::now();
+    let backup_id = archive_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut integrity = None;
+    let mut table_counts = Vec::new();
+    let result = check_archive(
+        pool,
+        archive_path,
+        expected,
+        &mut integrity,
+        &mut table_counts,
+    )
+    .await;
+
+    let error = result.err().map(|e| e.to_string());
+    match &error {
+        None => info!("Verified backup {}", backup_id),
+        Some(e) => warn!("Backup {} failed verification: {}", backup_id, e),
+    }
+    BackupVerification::create(
+        pool,
+        &CreateBackupVerification {
+            backup_id,
+            kind,
+            integrity,
+            table_counts,
+            error,
+            duration_ms: started.elapsed().as_millis() as i64,
+        },
+    )
+    .await
+}
+
+async fn check_archive(
+    live: &SqlitePool,
+    archive_path: &Path,
+    expected: Option<&BTreeMap<String, i64>>,
+    integrity: &mut Option<String>,
+    table_counts: &mut Vec<TableRowCount>,
+) -> Result<(), BackupError> {
+    let scratch = tempfile::Builder::new()
+        .prefix("vk-backup-verify-")
+        .tempdir()?;
+    let scratch_dir = scratch.path().join("assets");
+    {
+        let archive_path = archive_path.to_path_buf();
+        let scratch_dir = scratch_dir.clone();
+        tokio::task::spawn_blocking(move || extract_archive(&archive_path, &scratch_dir))
+            .await
+            .map_err(|e| BackupError::BackupDirError(format!("Task join error: {e}")))??;
+    }
+
+    if let Ok(config) = fs::read(scratch_dir.join("config.json"))
+        && let Err(e) = serde_json::from_slice::<serde_json::Value>(&config)
+    {
+        return Err(BackupError::InvalidArchive(format!(
+            "config.json is not valid JSON: {e}"
+        )));
+    }
+
+    let db_path = scratch_dir.join(DB_FILE);
+    check_sqlite_header(&db_path)?;
+    // The archived shared-memory file describes a WAL index of the live
+    // process; without it SQLite rebuilds the index from the WAL itself
+    let _ = fs::remove_file(scratch_dir.join(format!("{DB_FILE}-shm")));
+
+    let invalid = |e: sqlx::Error| BackupError::InvalidArchive(format!("{DB_FILE}: {e}"));
+    // Writable, so the archived WAL is replayed into the scratch copy
+    let options = SqliteConnectOptions::new().filename(&db_path);
+    let mut conn = SqliteConnection::connect_with(&options)
+        .await
+        .map_err(invalid)?;
+
+    let result: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(invalid)?;
+    *integrity = Some(result.clone());
+    if result != "ok" {
+        return Err(BackupError::InvalidArchive(format!(
+            "{DB_FILE} failed integrity check: {result}"
+        )));
+    }
+
+    let backup_version = max_migration(&mut conn).await.map_err(invalid)?;
+    let live_version = max_migration(live).await.map_err(invalid)?;
+    if backup_version > live_version {
+        return Err(BackupError::InvalidArchive(format!(
+            "Backup schema version {} is newer than this build ({})",
+            backup_version.unwrap_or_default(),
+            live_version.unwrap_or_default()
+        )));
+    }
+
+    let counts = count_rows(&mut conn).await.map_err(invalid)?;
+    let _ = conn.close().await;
+    *table_counts = counts
+        .into_iter()
+        .map(|(table, rows)| TableRowCount {
+            expected_rows: expected.and_then(|e| e.get(&table).copied()),
+            table,
+            rows,
+        })
+        .collect();
+
+    let mismatches: Vec<String> = table_counts
+        .iter()
+        .filter_map(|c| {
+            let expected = c.expected_rows?;
+            (expected != c.rows).then(|| format!("{} {} (expected {})", c.table, c.rows, expected))
+        })
+        .collect();
+    if !mismatches.is_empty() {
+        return Err(BackupError::InvalidArchive(format!(
+            "Row counts differ from the database snapshot: {}",
+            mismatches.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    async fn migrated_pool(url: &str) -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(url)
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    /// A live database plus an archive of a migrated copy of it
+    async fn setup(dir: &Path) -> (SqlitePool, std::path::PathBuf) {
+        let live = migrated_pool("sqlite::memory:").await;
+
+        let db_path = dir.join("archived.sqlite");
+        let archived = migrated_pool(&format!("sqlite://{}?mode=rwc", db_path.display())).await;
+        sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Archived')")
+            .bind(uuid::Uuid::new_v4())
+            .execute(&archived)
+            .await
+            .unwrap();
+        sqlx::query("VACUUM").execute(&archived).await.unwrap();
+        archived.close().await;
+
+        let archive = dir.join("backup_20260101_120000.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        zip.start_file(DB_FILE, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&fs::read(&db_path).unwrap()).unwrap();
+        zip.start_file("config.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.finish().unwrap();
+        (live, archive)
+    }
+
+    #[tokio::test]
+    async fn records_passing_and_mismatched_checks() {
+        let dir = TempDir::new().unwrap();
+        let (live, archive) = setup(dir.path()).await;
+
+        let verification =
+            verify_backup_archive(&live, &archive, BackupVerificationKind::Drill, None)
+                .await
+                .unwrap();
+        assert!(verification.passed, "{:?}", verification.error);
+        assert_eq!(verification.backup_id, "backup_20260101_120000");
+        assert_eq!(verification.integrity.as_deref(), Some("ok"));
+        let projects = verification
+            .table_counts
+            .iter()
+            .find(|c| c.table == "projects")
+            .unwrap();
+        assert_eq!(projects.rows, 1);
+
+        // A snapshot of the live database has no projects, so the archive
+        // doesn't match it
+        let expected = snapshot_database(&live, &dir.path().join("snapshot.sqlite"))
+            .await
+            .unwrap();
+        assert_eq!(expected.get("projects"), Some(&0));
+        let verification = verify_backup_archive(
+            &live,
+            &archive,
+            BackupVerificationKind::AfterBackup,
+            Some(&expected),
+        )
+        .await
+        .unwrap();
+        assert!(!verification.passed);
+        assert!(
+            verification
+                .error
+                .unwrap()
+                .contains("projects 1 (expected 0)")
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_a_corrupt_database() {
+        let dir = TempDir::new().unwrap();
+        let live = migrated_pool("sqlite::memory:").await;
+
+        let archive = dir.path().join("backup_20260101_120000.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        zip.start_file(DB_FILE, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"not a database").unwrap();
+        zip.finish().unwrap();
+
+        let verification =
+            verify_backup_archive(&live, &archive, BackupVerificationKind::Manual, None)
+                .await
+                .unwrap();
+        assert!(!verification.passed);
+        assert_eq!(verification.integrity, None);
+        assert!(verification.table_counts.is_empty());
+    }
+}
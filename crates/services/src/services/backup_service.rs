@@ -1,23 +1,52 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::BTreeMap, path::Path, sync::Arc, time::Duration};
 
 use chrono::Utc;
+use db::{
+    DB_FILE,
+    models::{
+        backup_verification::{BackupVerification, BackupVerificationKind},
+        operation::OperationKind,
+    },
+};
+use sqlx::SqlitePool;
 use tokio::{sync::RwLock, time::interval};
 use tracing::{debug, error, info};
-use utils::assets::{asset_dir, backup_dir};
+use utils::assets::backup_dir;
 
 use crate::services::{
-    backup::{apply_gfs_retention, create_backup_archive, delete_old_backups, list_backups},
-    config::Config,
+    backup::{
+        AssetPaths, BackupError, BackupStorage, LocalBackupStorage, S3BackupStorage,
+        apply_gfs_retention, create_backup_archive, list_backups, snapshot_database,
+        verify_backup_archive,
+    },
+    config::{BackupConfig, Config},
+    notification::NotificationService,
+    operations::OperationTracker,
+    secrets::SecretService,
 };
 
-/// Service to run periodic backups and apply retention policy.
+/// Service to run periodic backups, verify them, run restore drills and
+/// apply retention policy.
 pub struct BackupService {
     config: Arc<RwLock<Config>>,
+    pool: SqlitePool,
+    operations: OperationTracker,
+    secrets: SecretService,
 }
 
 impl BackupService {
-    pub async fn spawn(config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
-        let service = Self { config };
+    pub async fn spawn(
+        config: Arc<RwLock<Config>>,
+        pool: SqlitePool,
+        operations: OperationTracker,
+        secrets: SecretService,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            config,
+            pool,
+            operations,
+            secrets,
+        };
         tokio::spawn(async move {
             service.start().await;
         })
@@ -42,79 +71,265 @@ impl BackupService {
                 continue;
             }
 
-            let backup_interval =
-                Duration::from_secs(backup_config.interval_hours as u64 * 60 * 60);
-            let backup_dir = backup_dir();
-
-            // Check if we need to create a new backup based on the last backup time
-            match list_backups(&backup_dir) {
-                Ok(backups) => {
-                    let should_backup = if let Some(latest) = backups.last() {
-                        let elapsed = Utc::now()
-                            .signed_duration_since(latest.timestamp)
-                            .to_std()
-                            .unwrap_or(Duration::ZERO);
-                        elapsed >= backup_interval
-                    } else {
-                        // No backups exist, create one
-                        true
-                    };
-
-                    if !should_backup {
-                        debug!(
-                            "Skipping backup, last backup is recent (interval: {:?})",
-                            backup_interval
-                        );
-                        continue;
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to list backups: {}", e);
-                    // Continue to create backup anyway
-                }
+            if Self::backup_due(&backup_config) {
+                self.run_backup(&backup_config).await;
             }
+            self.run_drill_if_due(&backup_config).await;
+        }
+    }
+
+    /// Whether the last backup is older than the configured interval
+    fn backup_due(backup_config: &BackupConfig) -> bool {
+        let backup_interval = Duration::from_secs(backup_config.interval_hours as u64 * 60 * 60);
 
-            // Create backup
-            let asset_dir = asset_dir();
-            match create_backup_archive(&asset_dir, &backup_dir).await {
-                Ok(path) => {
-                    info!("Created backup: {}", path.display());
+        // Check if we need to create a new backup based on the last backup time
+        match list_backups(&backup_dir()) {
+            Ok(backups) => {
+                let should_backup = if let Some(latest) = backups.last() {
+                    let elapsed = Utc::now()
+                        .signed_duration_since(latest.timestamp)
+                        .to_std()
+                        .unwrap_or(Duration::ZERO);
+                    elapsed >= backup_interval
+                } else {
+                    // No backups exist, create one
+                    true
+                };
+
+                if !should_backup {
+                    debug!(
+                        "Skipping backup, last backup is recent (interval: {:?})",
+                        backup_interval
+                    );
                 }
-                Err(e) => {
-                    error!("Failed to create backup: {}", e);
-                    continue;
+                should_backup
+            }
+            Err(e) => {
+                error!("Failed to list backups: {}", e);
+                // Create backup anyway
+                true
+            }
+        }
+    }
+
+    async fn run_backup(&self, backup_config: &BackupConfig) {
+        let backup_dir = backup_dir();
+        let operation_id = self
+            .operations
+            .start(OperationKind::Backup, None, None)
+            .await;
+
+        self.operations
+            .progress(operation_id, 0, Some("Creating archive"))
+            .await;
+        // The archive holds a snapshot of the database rather than the live
+        // files, and the snapshot's row counts are what verification expects
+        let created = async {
+            let scratch = tempfile::Builder::new()
+                .prefix("vk-backup-snapshot-")
+                .tempdir()?;
+            let mut paths = AssetPaths::resolve()?;
+            paths.db_path = scratch.path().join(DB_FILE);
+            let expected = snapshot_database(&self.pool, &paths.db_path).await?;
+            let archive = create_backup_archive(&paths, &backup_dir).await?;
+            Ok::<_, BackupError>((archive, expected))
+        }
+        .await;
+        let (archive, expected) = match created {
+            Ok(created) => {
+                info!("Created backup: {}", created.0.display());
+                created
+            }
+            Err(e) => {
+                error!("Failed to create backup: {}", e);
+                self.operations.finish(operation_id, Err(e)).await;
+                return;
+            }
+        };
+
+        if backup_config.verify_after_backup {
+            self.operations
+                .progress(operation_id, 40, Some("Verifying archive"))
+                .await;
+            if let Err(e) = self
+                .verify(
+                    &archive,
+                    BackupVerificationKind::AfterBackup,
+                    Some(&expected),
+                )
+                .await
+            {
+                // Removed so it is neither uploaded, counted by retention,
+                // picked for a drill nor taken as the latest backup
+                if let Err(remove_err) = std::fs::remove_file(&archive) {
+                    error!(
+                        "Failed to remove unverified backup {}: {}",
+                        archive.display(),
+                        remove_err
+                    );
                 }
+                self.operations
+                    .finish(operation_id, Err(format!("Verification failed: {e}")))
+                    .await;
+                return;
             }
+        }
 
-            // Apply retention policy
-            match list_backups(&backup_dir) {
-                Ok(backups) => {
-                    let now = Utc::now();
-                    let (keep, delete) = apply_gfs_retention(backups, &backup_config, now);
-
-                    if !delete.is_empty() {
-                        info!(
-                            "Retention policy: keeping {} backups, deleting {}",
-                            keep.len(),
-                            delete.len()
-                        );
-
-                        match delete_old_backups(delete) {
-                            Ok(count) => {
-                                info!("Deleted {} old backups", count);
-                            }
-                            Err(e) => {
-                                error!("Failed to delete old backups: {}", e);
-                            }
-                        }
-                    } else {
-                        debug!("Retention policy: keeping all {} backups", keep.len());
-                    }
+        let mut upload_errors = Vec::new();
+        let mut storages: Vec<Box<dyn BackupStorage>> =
+            vec![Box::new(LocalBackupStorage::new(backup_dir))];
+        if let Some(s3) = backup_config.s3.clone() {
+            match self
+                .secrets
+                .resolve_opt(s3.secret_access_key.as_ref())
+                .await
+            {
+                Some(secret_access_key) => {
+                    storages.push(Box::new(S3BackupStorage::new(s3, secret_access_key)));
                 }
-                Err(e) => {
-                    error!("Failed to list backups for retention: {}", e);
+                None => {
+                    error!("S3 secret access key is not set; skipping the upload");
+                    upload_errors.push("S3: secret access key is not set".to_string());
                 }
             }
         }
+
+        // Archiving and verifying count for half, uploads share the rest
+        for (idx, storage) in storages.iter().enumerate() {
+            let percent = 50 + 50 * idx / storages.len();
+            self.operations
+                .progress(
+                    operation_id,
+                    percent as u8,
+                    Some(&format!("Uploading to {}", storage.name())),
+                )
+                .await;
+            if let Err(e) = storage.upload(&archive).await {
+                error!("Failed to upload backup to {}: {}", storage.name(), e);
+                upload_errors.push(format!("{}: {}", storage.name(), e));
+                continue;
+            }
+            Self::apply_retention(storage.as_ref(), backup_config).await;
+        }
+
+        let result = if upload_errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Upload failed: {}", upload_errors.join("; ")))
+        };
+        self.operations.finish(operation_id, result).await;
+    }
+
+    /// Rehearse a restore of the latest local archive once every
+    /// `drill_interval_days`
+    async fn run_drill_if_due(&self, backup_config: &BackupConfig) {
+        if backup_config.drill_interval_days == 0 {
+            return;
+        }
+        let drill_interval = chrono::Duration::days(i64::from(backup_config.drill_interval_days));
+        match BackupVerification::last_verified_at(&self.pool, BackupVerificationKind::Drill).await
+        {
+            Ok(Some(last)) if Utc::now() - last < drill_interval => return,
+            Ok(_) => {}
+            Err(e) => {
+                error!("Failed to look up the last restore drill: {}", e);
+                return;
+            }
+        }
+
+        let latest = match list_backups(&backup_dir()) {
+            Ok(backups) => backups.into_iter().last(),
+            Err(e) => {
+                error!("Failed to list backups for restore drill: {}", e);
+                return;
+            }
+        };
+        let Some(latest) = latest else {
+            debug!("No backups yet, skipping restore drill");
+            return;
+        };
+
+        info!("Running restore drill on {}", latest.path.display());
+        // Failures are recorded and notified by verify
+        let _ = self
+            .verify(&latest.path, BackupVerificationKind::Drill, None)
+            .await;
+    }
+
+    /// Verify an archive, notifying when it fails. Returns why it failed.
+    async fn verify(
+        &self,
+        archive: &Path,
+        kind: BackupVerificationKind,
+        expected: Option<&BTreeMap<String, i64>>,
+    ) -> Result<(), String> {
+        let verification = match verify_backup_archive(&self.pool, archive, kind, expected).await {
+            Ok(verification) => verification,
+            Err(e) => {
+                error!("Failed to record backup verification: {}", e);
+                return Err(e.to_string());
+            }
+        };
+        if verification.passed {
+            return Ok(());
+        }
+
+        let title = match kind {
+            BackupVerificationKind::Drill => "Restore drill failed",
+            _ => "Backup verification failed",
+        };
+        let error = verification
+            .error
+            .unwrap_or_else(|| "unknown error".to_string());
+        let message = format!("{}: {}", verification.backup_id, error);
+        NotificationService::new(self.config.clone())
+            .notify_error(title, &message)
+            .await;
+        Err(error)
+    }
+
+    /// Apply the GFS retention policy to a storage's own listing
+    async fn apply_retention(storage: &dyn BackupStorage, backup_config: &BackupConfig) {
+        let backups = match storage.list().await {
+            Ok(backups) => backups,
+            Err(e) => {
+                error!(
+                    "Failed to list backups in {} for retention: {}",
+                    storage.name(),
+                    e
+                );
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        let (keep, delete) = apply_gfs_retention(backups, backup_config, now);
+        if delete.is_empty() {
+            debug!(
+                "Retention policy: keeping all {} backups in {}",
+                keep.len(),
+                storage.name()
+            );
+            return;
+        }
+
+        info!(
+            "Retention policy: keeping {} backups in {}, deleting {}",
+            keep.len(),
+            storage.name(),
+            delete.len()
+        );
+        match storage.delete(delete).await {
+            Ok(count) => {
+                info!("Deleted {} old backups from {}", count, storage.name());
+            }
+            Err(e) => {
+                error!(
+                    "Failed to delete old backups from {}: {}",
+                    storage.name(),
+                    e
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,330 @@
+//! Guided `git bisect` over a repo's good..bad range.
+//!
+//! A run checks out the bad commit in a throwaway worktree and starts
+//! `git bisect` there. For each commit git picks, the validation command is
+//! run and its exit code decides the verdict as with `git bisect run`: 0 is
+//! good, 125 untestable, 1-127 bad, anything else (signals, timeouts)
+//! untestable. A [`StepInterpreter`], usually an agent, may read the output
+//! and decide instead. Every step is recorded and the run ends when git names
+//! the first bad commit.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use db::models::bisect::{BisectRun, BisectStep, BisectVerdict, CreateBisectStep};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::{path::get_vibe_kanban_temp_dir, shell::get_shell_command, text::truncate_output};
+
+use super::git::{GitCli, GitCliError};
+
+/// Upper bound on tested commits; enough for ranges of 2^64 commits with no skips
+const MAX_STEPS: usize = 64;
+
+const STEP_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+/// Command output beyond this is cut before it is stored or interpreted
+const MAX_OUTPUT_CHARS: usize = 8_000;
+
+const FIRST_BAD_MARKER: &str = " is the first bad commit";
+
+#[derive(Debug, Error)]
+pub enum BisectError {
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Unknown commit: {0}")]
+    UnknownCommit(String),
+    #[error("Bisect could not name a first bad commit: {0}")]
+    Inconclusive(String),
+}
+
+/// Result of running the validation command on one commit
+#[derive(Debug, Clone)]
+pub struct StepOutcome {
+    /// None when the command timed out or was killed by a signal
+    pub exit_code: Option<i32>,
+    pub output: String,
+}
+
+#[async_trait]
+pub trait StepInterpreter: Send + Sync {
+    /// Verdict for `commit` from the command's outcome, or None to go by the
+    /// exit code
+    async fn interpret(
+        &self,
+        run: &BisectRun,
+        commit: &str,
+        outcome: &StepOutcome,
+    ) -> Option<BisectVerdict>;
+}
+
+/// Full SHA of `rev` in `repo_path`
+pub fn resolve_commit(repo_path: &Path, rev: &str) -> Result<String, BisectError> {
+    let spec = format!("{}^{{commit}}", rev.trim());
+    match GitCli::new().git(repo_path, ["rev-parse", "--verify", "--quiet", &spec]) {
+        Ok(sha) if !sha.trim().is_empty() => Ok(sha.trim().to_string()),
+        Ok(_) | Err(GitCliError::CommandFailed(_)) => {
+            Err(BisectError::UnknownCommit(rev.to_string()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Drive `run` to completion and record how it ended
+pub async fn drive(
+    pool: &SqlitePool,
+    run: &BisectRun,
+    repo_path: &Path,
+    interpreter: Option<&dyn StepInterpreter>,
+) {
+    let result = run_bisect(pool, run, repo_path, interpreter).await;
+    let (culprit, error) = match &result {
+        Ok(culprit) => (Some(culprit.as_str()), None),
+        Err(e) => {
+            tracing::warn!(run_id = %run.id, error = %e, "Bisect run failed");
+            (None, Some(e.to_string()))
+        }
+    };
+    if let Err(e) = BisectRun::finish(pool, run.id, culprit, error.as_deref()).await {
+        tracing::error!(run_id = %run.id, error = %e, "Failed to record bisect result");
+    }
+}
+
+/// The first bad commit of `run`'s range
+async fn run_bisect(
+    pool: &SqlitePool,
+    run: &BisectRun,
+    repo_path: &Path,
+    interpreter: Option<&dyn StepInterpreter>,
+) -> Result<String, BisectError> {
+    let worktree = BisectWorktree::create(repo_path, &run.bad_commit).await?;
+    let result = test_commits(pool, run, &worktree, interpreter).await;
+    worktree.remove().await;
+    result
+}
+
+async fn test_commits(
+    pool: &SqlitePool,
+    run: &BisectRun,
+    worktree: &BisectWorktree,
+    interpreter: Option<&dyn StepInterpreter>,
+) -> Result<String, BisectError> {
+    let mut progress = worktree
+        .bisect(&["start", &run.bad_commit, &run.good_commit])
+        .await?;
+    for _ in 0..MAX_STEPS {
+        let commit = match progress {
+            Progress::Found(culprit) => return Ok(culprit),
+            Progress::Testing(commit) => commit,
+        };
+
+        let outcome = run_command(&worktree.path, &run.command).await?;
+        let interpreted = match interpreter {
+            Some(interpreter) => interpreter.interpret(run, &commit, &outcome).await,
+            None => None,
+        };
+        let verdict = interpreted.unwrap_or_else(|| verdict_from_exit(outcome.exit_code));
+        BisectStep::create(
+            pool,
+            run.id,
+            &CreateBisectStep {
+                commit_sha: commit,
+                exit_code: outcome.exit_code.map(i64::from),
+                output: outcome.output,
+                verdict,
+                agent_interpreted: interpreted.is_some(),
+            },
+        )
+        .await?;
+
+        progress = worktree.bisect(&[verdict_arg(verdict)]).await?;
+    }
+    Err(BisectError::Inconclusive(format!(
+        "no result after {MAX_STEPS} steps"
+    )))
+}
+
+/// Verdict `git bisect run` would give for an exit code
+fn verdict_from_exit(exit_code: Option<i32>) -> BisectVerdict {
+    match exit_code {
+        Some(0) => BisectVerdict::Good,
+        Some(125) => BisectVerdict::Skip,
+        Some(1..=127) => BisectVerdict::Bad,
+        _ => BisectVerdict::Skip,
+    }
+}
+
+fn verdict_arg(verdict: BisectVerdict) -> &'static str {
+    match verdict {
+        BisectVerdict::Good => "good",
+        BisectVerdict::Bad => "bad",
+        BisectVerdict::Skip => "skip",
+    }
+}
+
+/// Verdict named on the last non-empty line of an agent's answer, e.g. "BAD"
+pub fn parse_verdict(answer: &str) -> Option<BisectVerdict> {
+    let last_line = answer.lines().rev().find(|line| !line.trim().is_empty())?;
+    let word: String = last_line
+        .trim()
+        .trim_matches(|c: char| !c.is_ascii_alphabetic())
+        .to_ascii_lowercase();
+    match word.as_str() {
+        "good" => Some(BisectVerdict::Good),
+        "bad" => Some(BisectVerdict::Bad),
+        "skip" => Some(BisectVerdict::Skip),
+        _ => None,
+    }
+}
+
+enum Progress {
+    /// git checked out this commit to be tested next
+    Testing(String),
+    /// git named the first bad commit
+    Found(String),
+}
+
+async fn run_command(worktree_path: &Path, command: &str) -> Result<StepOutcome, BisectError> {
+    let (shell, shell_arg) = get_shell_command();
+    let mut process = Command::new(shell);
+    process
+        .arg(shell_arg)
+        .arg(command)
+        .current_dir(worktree_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(STEP_TIMEOUT, process.output()).await {
+        Ok(output) => output?,
+        Err(_) => {
+            return Ok(StepOutcome {
+                exit_code: None,
+                output: format!("Timed out after {STEP_TIMEOUT:?}"),
+            });
+        }
+    };
+    let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(StepOutcome {
+        exit_code: output.status.code(),
+        output: truncate_output(combined.trim(), MAX_OUTPUT_CHARS),
+    })
+}
+
+/// Detached worktree the bisect runs in, removed again when the run ends
+struct BisectWorktree {
+    repo_path: PathBuf,
+    path: PathBuf,
+    _dir: tempfile::TempDir,
+}
+
+impl BisectWorktree {
+    async fn create(repo_path: &Path, commit: &str) -> Result<Self, BisectError> {
+        let base = get_vibe_kanban_temp_dir();
+        tokio::fs::create_dir_all(&base).await?;
+        let dir = tempfile::Builder::new()
+            .prefix("bisect-")
+            .tempdir_in(base)?;
+        let path = dir.path().join("worktree");
+        let repo_path = repo_path.to_path_buf();
+
+        let (git_repo, git_path, commit) = (repo_path.clone(), path.clone(), commit.to_string());
+        tokio::task::spawn_blocking(move || {
+            GitCli::new().worktree_add(&git_repo, &git_path, &commit, false)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        Ok(Self {
+            repo_path,
+            path,
+            _dir: dir,
+        })
+    }
+
+    /// Run `git bisect <args>` and read where it left off
+    async fn bisect(&self, args: &[&str]) -> Result<Progress, BisectError> {
+        let path = self.path.clone();
+        let args: Vec<String> = std::iter::once("bisect")
+            .chain(args.iter().copied())
+            .map(str::to_string)
+            .collect();
+        tokio::task::spawn_blocking(move || {
+            let git = GitCli::new();
+            let output = git.git(&path, &args).map_err(|e| match e {
+                GitCliError::CommandFailed(stderr) => {
+                    BisectError::Inconclusive(stderr.lines().next().unwrap_or("").to_string())
+                }
+                e => e.into(),
+            })?;
+            if let Some(culprit) = first_bad_commit(&output) {
+                return Ok(Progress::Found(culprit));
+            }
+            let head = git.git(&path, ["rev-parse", "HEAD"])?;
+            Ok(Progress::Testing(head.trim().to_string()))
+        })
+        .await
+        .map_err(std::io::Error::other)?
+    }
+
+    async fn remove(self) {
+        let (repo_path, path) = (self.repo_path.clone(), self.path.clone());
+        let result = tokio::task::spawn_blocking(move || {
+            let git = GitCli::new();
+            let _ = git.git(&path, ["bisect", "reset"]);
+            git.worktree_remove(&repo_path, &path, true)?;
+            git.worktree_prune(&repo_path)
+        })
+        .await;
+        if !matches!(result, Ok(Ok(()))) {
+            tracing::warn!("Failed to remove bisect worktree {}", self.path.display());
+        }
+    }
+}
+
+/// SHA from git's "<sha> is the first bad commit" line
+fn first_bad_commit(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        line.strip_suffix(FIRST_BAD_MARKER)
+            .map(|sha| sha.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_follow_git_bisect_run() {
+        assert_eq!(verdict_from_exit(Some(0)), BisectVerdict::Good);
+        assert_eq!(verdict_from_exit(Some(1)), BisectVerdict::Bad);
+        assert_eq!(verdict_from_exit(Some(125)), BisectVerdict::Skip);
+        assert_eq!(verdict_from_exit(Some(139)), BisectVerdict::Skip);
+        assert_eq!(verdict_from_exit(None), BisectVerdict::Skip);
+    }
+
+    #[test]
+    fn parses_agent_verdicts_and_culprits() {
+        assert_eq!(
+            parse_verdict("The test fails on an unrelated import.\n\n**SKIP**"),
+            Some(BisectVerdict::Skip)
+        );
+        assert_eq!(parse_verdict("bad."), Some(BisectVerdict::Bad));
+        assert_eq!(parse_verdict("It looks fine"), None);
+
+        let output = "abc123 is the first bad commit\ncommit abc123\nAuthor: someone";
+        assert_eq!(first_bad_commit(output), Some("abc123".to_string()));
+        assert_eq!(first_bad_commit("Bisecting: 3 revisions left"), None);
+    }
+}
@@ -0,0 +1,315 @@
+//! Bitbucket Cloud pull requests for repos whose remote is on bitbucket.org.
+//!
+//! Talks to the REST 2.0 API with the username from [`BitbucketConfig`] and
+//! the app password it references in the secret store. Pull requests are stored as [`PullRequestInfo`] with
+//! their repo-scoped id as the number. Declined and superseded pull requests
+//! count as closed.
+
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, de::DeserializeOwned};
+use thiserror::Error;
+use tracing::info;
+
+use crate::services::{
+    config::BitbucketConfig, git_remote::split_remote_url, github::CreatePrRequest,
+};
+
+const BITBUCKET_HOST: &str = "bitbucket.org";
+const API_URL: &str = "https://api.bitbucket.org/2.0";
+
+#[derive(Debug, Error)]
+pub enum BitbucketServiceError {
+    #[error("Bitbucket credentials are not configured. Add them under Settings > Bitbucket")]
+    NotConfigured,
+    #[error("Bitbucket authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("Bitbucket repository not found or no access: {0}")]
+    RepoNotFoundOrNoAccess(String),
+    #[error("Pull request error: {0}")]
+    PullRequest(String),
+    #[error("Bitbucket is unavailable: {0}")]
+    Unavailable(String),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+impl BitbucketServiceError {
+    pub fn should_retry(&self) -> bool {
+        matches!(
+            self,
+            BitbucketServiceError::Request(_) | BitbucketServiceError::Unavailable(_)
+        )
+    }
+}
+
+/// A repository on Bitbucket Cloud
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitbucketRepoInfo {
+    pub workspace: String,
+    pub repo_slug: String,
+}
+
+impl BitbucketRepoInfo {
+    /// Parse an SSH, HTTPS or pull request URL on bitbucket.org. See tests
+    /// for examples.
+    pub fn from_remote_url(remote_url: &str) -> Option<Self> {
+        let (host, path) = split_remote_url(remote_url)?;
+        if !host.eq_ignore_ascii_case(BITBUCKET_HOST) {
+            return None;
+        }
+        let mut segments = path.trim_matches('/').split('/');
+        let workspace = segments.next().filter(|s| !s.is_empty())?;
+        let repo = segments.next()?;
+        let repo_slug = repo.strip_suffix(".git").unwrap_or(repo);
+        if repo_slug.is_empty() {
+            return None;
+        }
+        Some(Self {
+            workspace: workspace.to_string(),
+            repo_slug: repo_slug.to_string(),
+        })
+    }
+
+    /// Web page of `branch`
+    pub fn branch_url(&self, branch: &str) -> String {
+        format!(
+            "https://{BITBUCKET_HOST}/{}/{}/branch/{branch}",
+            self.workspace, self.repo_slug
+        )
+    }
+
+    fn pull_requests_url(&self) -> String {
+        format!(
+            "{API_URL}/repositories/{}/{}/pullrequests",
+            self.workspace, self.repo_slug
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    id: i64,
+    state: String,
+    links: Links,
+    merge_commit: Option<Commit>,
+    updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Links {
+    html: Link,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+}
+
+impl From<PullRequest> for PullRequestInfo {
+    fn from(pr: PullRequest) -> Self {
+        let status = match pr.state.as_str() {
+            "OPEN" => MergeStatus::Open,
+            "MERGED" => MergeStatus::Merged,
+            "DECLINED" | "SUPERSEDED" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: pr.id,
+            url: pr.links.html.href,
+            // Bitbucket has no merge timestamp; a merged PR is not updated after
+            merged_at: matches!(status, MergeStatus::Merged)
+                .then_some(pr.updated_on)
+                .flatten(),
+            status,
+            merge_commit_sha: pr.merge_commit.map(|commit| commit.hash),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitbucketService {
+    client: reqwest::Client,
+    username: String,
+    app_password: String,
+}
+
+impl BitbucketService {
+    /// `app_password` is the value of the config's secret store reference
+    pub fn new(
+        config: &BitbucketConfig,
+        app_password: Option<String>,
+    ) -> Result<Self, BitbucketServiceError> {
+        let non_empty = |value: Option<String>| {
+            value
+                .filter(|value| !value.trim().is_empty())
+                .ok_or(BitbucketServiceError::NotConfigured)
+        };
+        let username = non_empty(config.username.clone())?;
+        let app_password = non_empty(app_password)?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            client,
+            username,
+            app_password,
+        })
+    }
+
+    pub async fn create_pull_request(
+        &self,
+        repo_info: &BitbucketRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, BitbucketServiceError> {
+        let body = serde_json::json!({
+            "title": request.title,
+            "description": request.body.clone().unwrap_or_default(),
+            "source": { "branch": { "name": request.head_branch } },
+            "destination": { "branch": { "name": request.base_branch } },
+            "draft": request.draft.unwrap_or(false),
+            "close_source_branch": false,
+        });
+        let url = repo_info.pull_requests_url();
+
+        // Not retried: a request that timed out may still have created the PR
+        let pr: PullRequest = self.send(self.client.post(&url).json(&body)).await?;
+
+        info!(
+            "Created Bitbucket PR #{} for branch {} in {}/{}",
+            pr.id, request.head_branch, repo_info.workspace, repo_info.repo_slug
+        );
+        Ok(pr.into())
+    }
+
+    pub async fn get_pull_request(
+        &self,
+        repo_info: &BitbucketRepoInfo,
+        id: i64,
+    ) -> Result<PullRequestInfo, BitbucketServiceError> {
+        let url = format!("{}/{id}", repo_info.pull_requests_url());
+        let pr: PullRequest = self.send_with_retry(|| self.client.get(&url)).await?;
+        Ok(pr.into())
+    }
+
+    /// All pull requests from `branch` in any state, open ones first and
+    /// otherwise most recent first
+    pub async fn list_pull_requests_for_branch(
+        &self,
+        repo_info: &BitbucketRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, BitbucketServiceError> {
+        let url = repo_info.pull_requests_url();
+        let query = format!("source.branch.name = \"{}\"", branch.replace('"', "\\\""));
+        let page: Page<PullRequest> = self
+            .send_with_retry(|| {
+                // Only open pull requests are listed unless states are given
+                self.client.get(&url).query(&[
+                    ("q", query.as_str()),
+                    ("sort", "-created_on"),
+                    ("state", "OPEN"),
+                    ("state", "MERGED"),
+                    ("state", "DECLINED"),
+                    ("state", "SUPERSEDED"),
+                ])
+            })
+            .await?;
+
+        let mut prs: Vec<PullRequestInfo> = page.values.into_iter().map(Into::into).collect();
+        prs.sort_by_key(|pr| !matches!(pr.status, MergeStatus::Open));
+        Ok(prs)
+    }
+
+    async fn send_with_retry<T, F>(&self, build: F) -> Result<T, BitbucketServiceError>
+    where
+        T: DeserializeOwned,
+        F: Fn() -> RequestBuilder,
+    {
+        (|| async { self.send(build()).await })
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &BitbucketServiceError| e.should_retry())
+            .notify(|err: &BitbucketServiceError, dur: Duration| {
+                tracing::warn!(
+                    "Bitbucket API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, BitbucketServiceError> {
+        let response = request
+            .basic_auth(&self.username, Some(&self.app_password))
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let message = format!("{status}: {}", body.trim());
+        Err(match status {
+            StatusCode::UNAUTHORIZED => BitbucketServiceError::AuthFailed(message),
+            StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => {
+                BitbucketServiceError::RepoNotFoundOrNoAccess(message)
+            }
+            s if s.is_server_error() || s == StatusCode::TOO_MANY_REQUESTS => {
+                BitbucketServiceError::Unavailable(message)
+            }
+            _ => BitbucketServiceError::PullRequest(message),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bitbucket_remote_urls() {
+        let cases = [
+            "git@bitbucket.org:acme/widgets.git",
+            "ssh://git@bitbucket.org/acme/widgets.git",
+            "https://jdoe@bitbucket.org/acme/widgets.git",
+            "https://bitbucket.org/acme/widgets",
+            "https://bitbucket.org/acme/widgets/pull-requests/42",
+        ];
+        for url in cases {
+            let info = BitbucketRepoInfo::from_remote_url(url).unwrap();
+            assert_eq!(
+                (info.workspace.as_str(), info.repo_slug.as_str()),
+                ("acme", "widgets"),
+                "{url}"
+            );
+        }
+
+        assert!(BitbucketRepoInfo::from_remote_url("https://github.com/acme/widgets").is_none());
+        assert!(BitbucketRepoInfo::from_remote_url("https://bitbucket.org/acme").is_none());
+    }
+}
@@ -0,0 +1,312 @@
+//! `git blame` history for file/line references in follow-up prompts.
+//!
+//! Review comments (`**src/app.ts** (Line 42)`) and failing test or hook
+//! output (`src/app.ts:42:7`) point the agent at specific lines. For each
+//! such reference found in a workspace repo, the commits that last touched
+//! the surrounding lines are summarised and appended to the prompt, so the
+//! agent knows the history of the code it is about to change.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use chrono::DateTime;
+use regex::Regex;
+
+use super::git::{GitCli, GitCliError};
+
+const SECTION_HEADER: &str = "## History of referenced lines";
+
+/// References beyond this are ignored to keep prompts short
+const MAX_REFERENCES: usize = 8;
+
+/// Lines of context blamed on each side of a referenced line
+const CONTEXT_LINES: u32 = 3;
+
+static REVIEW_COMMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*\n]+)\*\* \(Line (\d+)\)").expect("valid review comment regex")
+});
+
+/// `path/to/file.ext:line`, optionally followed by `:column`
+static FILE_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[\s(\[`'\x22])(/?(?:[\w.@-]+/)*[\w@-][\w.@-]*\.[A-Za-z0-9]+):(\d+)\b")
+        .expect("valid file line regex")
+});
+
+/// A line of a file mentioned in a prompt
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LineReference {
+    pub path: String,
+    pub line: u32,
+}
+
+/// Commit that last touched a run of consecutive lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BlameHunk {
+    commit: String,
+    author: String,
+    date: String,
+    summary: String,
+    start: u32,
+    end: u32,
+}
+
+/// File/line references in `text`, in order of appearance, without duplicates
+pub fn line_references(text: &str) -> Vec<LineReference> {
+    let mut found: Vec<(usize, LineReference)> = Vec::new();
+    for re in [&*REVIEW_COMMENT_RE, &*FILE_LINE_RE] {
+        for captures in re.captures_iter(text) {
+            let (Some(path), Some(line)) = (captures.get(1), captures.get(2)) else {
+                continue;
+            };
+            let Ok(line) = line.as_str().parse::<u32>() else {
+                continue;
+            };
+            if line == 0 {
+                continue;
+            }
+            found.push((
+                path.start(),
+                LineReference {
+                    path: path.as_str().trim().to_string(),
+                    line,
+                },
+            ));
+        }
+    }
+    found.sort_by_key(|(offset, _)| *offset);
+
+    let mut references: Vec<LineReference> = Vec::new();
+    for (_, reference) in found {
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+/// `prompt` with blame history appended for the lines it references in
+/// `worktrees`; unchanged when nothing referenced can be blamed
+pub fn with_blame_context(prompt: String, worktrees: &[PathBuf]) -> String {
+    if prompt.contains(SECTION_HEADER) {
+        return prompt;
+    }
+    match blame_section(&prompt, worktrees) {
+        Some(section) => format!("{prompt}\n\n{section}"),
+        None => prompt,
+    }
+}
+
+fn blame_section(text: &str, worktrees: &[PathBuf]) -> Option<String> {
+    let git = GitCli::new();
+    let mut section = String::new();
+    for reference in line_references(text).into_iter().take(MAX_REFERENCES) {
+        let Some((worktree, path)) = locate(worktrees, &reference.path) else {
+            continue;
+        };
+        let hunks = match blame(&git, &worktree, &path, reference.line) {
+            Ok(hunks) if !hunks.is_empty() => hunks,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::debug!("Skipping blame for {}: {}", reference.path, e);
+                continue;
+            }
+        };
+        section.push_str(&format!("\n**{}:{}**\n", reference.path, reference.line));
+        for hunk in hunks {
+            section.push_str(&format_hunk(&hunk));
+        }
+    }
+    if section.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{SECTION_HEADER}\n\nThe commits that last changed the lines referenced above, \
+         from `git blame`:\n{section}"
+    ))
+}
+
+/// Worktree holding `path` and the path relative to it. Paths may be
+/// relative to a repo, prefixed with the repo's directory name, or absolute.
+fn locate(worktrees: &[PathBuf], path: &str) -> Option<(PathBuf, String)> {
+    let path = path.trim_start_matches("./");
+    for worktree in worktrees {
+        let relative = if Path::new(path).is_absolute() {
+            Path::new(path)
+                .strip_prefix(worktree)
+                .ok()
+                .map(|p| p.to_string_lossy().into_owned())
+        } else if worktree.join(path).is_file() {
+            Some(path.to_string())
+        } else {
+            worktree
+                .file_name()
+                .and_then(|name| path.strip_prefix(&*name.to_string_lossy()))
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(str::to_string)
+        };
+        if let Some(relative) = relative
+            && worktree.join(&relative).is_file()
+        {
+            return Some((worktree.clone(), relative));
+        }
+    }
+    None
+}
+
+fn blame(
+    git: &GitCli,
+    worktree: &Path,
+    path: &str,
+    line: u32,
+) -> Result<Vec<BlameHunk>, GitCliError> {
+    let start = line.saturating_sub(CONTEXT_LINES).max(1);
+    // A range past the end of the file makes git fail, so clamp it first
+    let line_count = std::fs::read_to_string(worktree.join(path))
+        .map(|content| content.lines().count() as u32)
+        .unwrap_or(0);
+    if line > line_count {
+        return Ok(Vec::new());
+    }
+    let end = (line + CONTEXT_LINES).min(line_count);
+    let range = format!("{start},{end}");
+    let output = git.git(worktree, ["blame", "--porcelain", "-L", &range, "--", path])?;
+    Ok(parse_porcelain(&output))
+}
+
+/// Hunks from `git blame --porcelain`, merging consecutive lines of a commit
+fn parse_porcelain(output: &str) -> Vec<BlameHunk> {
+    #[derive(Default)]
+    struct CommitInfo {
+        author: String,
+        date: String,
+        summary: String,
+    }
+
+    let mut commits: HashMap<String, CommitInfo> = HashMap::new();
+    let mut lines: Vec<(String, u32)> = Vec::new();
+    let mut current: Option<String> = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let mut fields = line.split(' ');
+        let first = fields.next().unwrap_or_default();
+        if first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit()) {
+            if let Some(final_line) = fields.nth(1).and_then(|n| n.parse().ok()) {
+                lines.push((first.to_string(), final_line));
+                commits.entry(first.to_string()).or_default();
+                current = Some(first.to_string());
+            }
+            continue;
+        }
+        let Some(info) = current.as_ref().and_then(|sha| commits.get_mut(sha)) else {
+            continue;
+        };
+        let value = line.split_once(' ').map(|(_, v)| v).unwrap_or_default();
+        match first {
+            "author" => info.author = value.to_string(),
+            "author-time" => {
+                info.date = value
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+            }
+            "summary" => info.summary = value.to_string(),
+            _ => {}
+        }
+    }
+
+    let mut hunks: Vec<BlameHunk> = Vec::new();
+    for (commit, line) in lines {
+        if let Some(last) = hunks.last_mut()
+            && last.commit == commit
+            && last.end + 1 == line
+        {
+            last.end = line;
+            continue;
+        }
+        let info = &commits[&commit];
+        hunks.push(BlameHunk {
+            commit,
+            author: info.author.clone(),
+            date: info.date.clone(),
+            summary: info.summary.clone(),
+            start: line,
+            end: line,
+        });
+    }
+    hunks
+}
+
+fn format_hunk(hunk: &BlameHunk) -> String {
+    let lines = if hunk.start == hunk.end {
+        format!("line {}", hunk.start)
+    } else {
+        format!("lines {}-{}", hunk.start, hunk.end)
+    };
+    if hunk.commit.chars().all(|c| c == '0') {
+        return format!("- {lines}: not committed yet\n");
+    }
+    format!(
+        "- {lines}: `{}` by {} on {}: {}\n",
+        &hunk.commit[..7],
+        hunk.author,
+        hunk.date,
+        hunk.summary
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_review_comment_and_compiler_references() {
+        let text = "## Review Comments (1)\n\n**src/app.ts** (Line 42)\n\n> Why?\n\n\
+                    error[E0308]: mismatched types\n  --> crates/db/src/lib.rs:10:5\n\
+                    FAILED tests/test_api.py:7 - assert 1 == 2\n\
+                    see src/app.ts:42 and https://example.com:8080";
+        assert_eq!(
+            line_references(text),
+            vec![
+                LineReference {
+                    path: "src/app.ts".to_string(),
+                    line: 42
+                },
+                LineReference {
+                    path: "crates/db/src/lib.rs".to_string(),
+                    line: 10
+                },
+                LineReference {
+                    path: "tests/test_api.py".to_string(),
+                    line: 7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn porcelain_lines_merge_into_hunks() {
+        let sha_a = "a".repeat(40);
+        let sha_b = "b".repeat(40);
+        let output = format!(
+            "{sha_a} 1 4 2\nauthor Ada\nauthor-time 1700000000\nsummary Add parser\n\tone\n\
+             {sha_a} 2 5\n\ttwo\n\
+             {sha_b} 9 6 1\nauthor Bob\nauthor-time 1710000000\nsummary Fix bounds\n\tthree\n"
+        );
+        let hunks = parse_porcelain(&output);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].start, hunks[0].end), (4, 5));
+        assert_eq!(
+            format_hunk(&hunks[0]),
+            "- lines 4-5: `aaaaaaa` by Ada on 2023-11-14: Add parser\n"
+        );
+        assert_eq!(hunks[1].summary, "Fix bounds");
+        assert_eq!((hunks[1].start, hunks[1].end), (6, 6));
+    }
+}
@@ -0,0 +1,273 @@
+//! Cleanup of merged task branches in the origin repos.
+//!
+//! Every workspace leaves a local branch behind in each of its repos. Once the
+//! workspace's worktree has been cleaned up and its merge (direct or through a
+//! merged PR) is older than the project's `min_age_days`, the janitor deletes
+//! the branch, or moves it to `refs/vk-archive/<branch>` when the project asks
+//! for archiving. Branches that are checked out somewhere, or whose tip is not
+//! contained in the recorded merge commit (it gained commits after the merge,
+//! or the merge commit hasn't been fetched), are left alone.
+
+use std::{collections::HashSet, time::Duration};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::branch_cleanup_policy::{BranchCleanupMode, BranchCleanupPolicy, StaleTaskBranch},
+};
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{error, info};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::services::git::{GitService, GitServiceError};
+
+/// How often enabled projects are swept
+pub const JANITOR_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BranchCleanupOutcome {
+    Deleted,
+    Archived,
+    /// Dry run: the branch would have been deleted
+    WouldDelete,
+    /// Dry run: the branch would have been archived
+    WouldArchive,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct BranchCleanupEntry {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub branch: String,
+    /// Branch tip before cleanup, for restoring a deleted branch by hand
+    pub tip_commit: String,
+    pub outcome: BranchCleanupOutcome,
+    /// Why the branch was skipped or could not be removed
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct BranchCleanupReport {
+    pub project_id: Uuid,
+    pub mode: BranchCleanupMode,
+    pub dry_run: bool,
+    pub entries: Vec<BranchCleanupEntry>,
+}
+
+#[derive(Clone)]
+pub struct BranchJanitor {
+    db: DBService,
+    git: GitService,
+}
+
+impl BranchJanitor {
+    pub fn new(db: DBService, git: GitService) -> Self {
+        Self { db, git }
+    }
+
+    pub fn spawn(db: DBService, git: GitService) -> tokio::task::JoinHandle<()> {
+        let janitor = Self::new(db, git);
+        tokio::spawn(async move {
+            janitor.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting branch janitor with interval {:?}",
+            JANITOR_INTERVAL
+        );
+
+        let mut interval = interval(JANITOR_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_enabled().await {
+                error!("Failed to clean up task branches: {}", e);
+            }
+        }
+    }
+
+    /// Sweep every project whose policy is not `off`
+    async fn run_enabled(&self) -> Result<(), sqlx::Error> {
+        for policy in BranchCleanupPolicy::find_enabled(&self.db.pool).await? {
+            let report = self
+                .run(policy.project_id, policy.mode, policy.min_age_days, false)
+                .await?;
+            let removed = report
+                .entries
+                .iter()
+                .filter(|e| {
+                    matches!(
+                        e.outcome,
+                        BranchCleanupOutcome::Deleted | BranchCleanupOutcome::Archived
+                    )
+                })
+                .count();
+            if removed > 0 {
+                info!(
+                    "Branch janitor removed {} task branches for project {}",
+                    removed, policy.project_id
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Clean up the project's stale task branches with `mode`. A dry run
+    /// reports what would happen without touching any repo. Mode `off` only
+    /// lists the candidates, each as skipped.
+    pub async fn run(
+        &self,
+        project_id: Uuid,
+        mode: BranchCleanupMode,
+        min_age_days: i64,
+        dry_run: bool,
+    ) -> Result<BranchCleanupReport, sqlx::Error> {
+        let merged_before = Utc::now() - chrono::Duration::days(min_age_days.max(0));
+        let candidates =
+            StaleTaskBranch::find_for_project(&self.db.pool, project_id, merged_before).await?;
+
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for candidate in candidates {
+            if !seen.insert((candidate.repo_id, candidate.branch.clone())) {
+                continue;
+            }
+            if let Some(entry) = self.clean_branch(&candidate, mode, dry_run) {
+                entries.push(entry);
+            }
+        }
+
+        Ok(BranchCleanupReport {
+            project_id,
+            mode,
+            dry_run,
+            entries,
+        })
+    }
+
+    /// None when the branch no longer exists in the repo
+    fn clean_branch(
+        &self,
+        candidate: &StaleTaskBranch,
+        mode: BranchCleanupMode,
+        dry_run: bool,
+    ) -> Option<BranchCleanupEntry> {
+        let repo_path = candidate.repo_path.as_path();
+        if !repo_path.exists() {
+            return None;
+        }
+
+        let entry = |tip_commit: String, outcome, reason: Option<String>| BranchCleanupEntry {
+            repo_id: candidate.repo_id,
+            repo_name: candidate.repo_name.clone(),
+            branch: candidate.branch.clone(),
+            tip_commit,
+            outcome,
+            reason,
+        };
+
+        let tip_commit = match self.git.local_branch_tip(repo_path, &candidate.branch) {
+            Ok(Some(tip)) => tip,
+            Ok(None) => return None,
+            Err(e) => {
+                return Some(entry(
+                    String::new(),
+                    BranchCleanupOutcome::Failed,
+                    Some(e.to_string()),
+                ));
+            }
+        };
+
+        let skip_reason = match self.skip_reason(candidate, &tip_commit, mode) {
+            Ok(reason) => reason,
+            Err(e) => {
+                return Some(entry(
+                    tip_commit,
+                    BranchCleanupOutcome::Failed,
+                    Some(e.to_string()),
+                ));
+            }
+        };
+        if let Some(reason) = skip_reason {
+            return Some(entry(
+                tip_commit,
+                BranchCleanupOutcome::Skipped,
+                Some(reason),
+            ));
+        }
+
+        let archive = mode == BranchCleanupMode::Archive;
+        if dry_run {
+            let outcome = if archive {
+                BranchCleanupOutcome::WouldArchive
+            } else {
+                BranchCleanupOutcome::WouldDelete
+            };
+            return Some(entry(tip_commit, outcome, None));
+        }
+
+        Some(
+            match self
+                .git
+                .remove_local_branch(repo_path, &candidate.branch, archive)
+            {
+                Ok(()) if archive => entry(tip_commit, BranchCleanupOutcome::Archived, None),
+                Ok(()) => entry(tip_commit, BranchCleanupOutcome::Deleted, None),
+                Err(e) => entry(
+                    tip_commit,
+                    BranchCleanupOutcome::Failed,
+                    Some(e.to_string()),
+                ),
+            },
+        )
+    }
+
+    /// Why the branch at `tip_commit` must be kept, if it must
+    fn skip_reason(
+        &self,
+        candidate: &StaleTaskBranch,
+        tip_commit: &str,
+        mode: BranchCleanupMode,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo_path = candidate.repo_path.as_path();
+        if let Some(path) = self
+            .git
+            .find_checkout_path_for_branch(repo_path, &candidate.branch)?
+        {
+            return Ok(Some(format!("checked out at {}", path.display())));
+        }
+
+        let Some(merge_commit) = &candidate.merge_commit else {
+            return Ok(Some(
+                "no merge commit was recorded for the merge".to_string(),
+            ));
+        };
+        match self.git.is_merged_by(repo_path, tip_commit, merge_commit)? {
+            None => {
+                return Ok(Some(format!(
+                    "merge commit {merge_commit} is not in the repo; fetch {} first",
+                    candidate.target_branch_name
+                )));
+            }
+            Some(false) => {
+                return Ok(Some(format!(
+                    "has commits that are not in the merge into {}",
+                    candidate.target_branch_name
+                )));
+            }
+            Some(true) => {}
+        }
+
+        if mode == BranchCleanupMode::Off {
+            return Ok(Some("branch cleanup is off for this project".to_string()));
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,231 @@
+//! Tasks stacked on other tasks' branches.
+//!
+//! A workspace can target another task's branch instead of the project's
+//! base branch, to build on work that hasn't merged yet. When the branch it
+//! is stacked on merges, the workspace is rebased onto the branch its parent
+//! merged into and retargeted there. Workspaces stacked on it in turn are
+//! rebased onto its new tip and keep targeting it.
+//!
+//! Rebasing replays only the commits made on top of the parent, so it works
+//! whether the parent was merged, squashed or rebased first. A workspace that
+//! can't be restacked (conflicts, uncommitted changes, no worktree, or a
+//! process still running in it) keeps its target and its task is flagged as
+//! needing attention.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use db::models::{
+    execution_process::ExecutionProcess, repo::Repo, task::Task, workspace_repo::WorkspaceRepo,
+};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::git::GitService;
+
+/// Longest chain of stacked workspaces followed; deeper ones are left alone
+const MAX_STACK_DEPTH: usize = 32;
+
+/// How restacking one workspace went
+#[derive(Debug, Clone)]
+pub struct RestackOutcome {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    /// Why the workspace couldn't be restacked; None when it was
+    pub error: Option<String>,
+}
+
+/// Whether making `workspace_id` target `target_branch` in a repo would stack
+/// it on itself, directly or through the workspaces it would be stacked on
+pub async fn would_create_cycle(
+    pool: &SqlitePool,
+    repo_id: Uuid,
+    workspace_id: Uuid,
+    target_branch: &str,
+) -> Result<bool, sqlx::Error> {
+    let mut branch = target_branch.to_string();
+    for _ in 0..MAX_STACK_DEPTH {
+        let Some(parent_id) = WorkspaceRepo::find_stack_parent(pool, repo_id, &branch).await?
+        else {
+            return Ok(false);
+        };
+        if parent_id == workspace_id {
+            return Ok(true);
+        }
+        let Some(parent) =
+            WorkspaceRepo::find_by_workspace_and_repo_id(pool, parent_id, repo_id).await?
+        else {
+            return Ok(false);
+        };
+        branch = parent.target_branch;
+    }
+    Ok(true)
+}
+
+/// Restack the workspaces stacked on `merged_branch` after it merged into
+/// `base_branch`. `merged_tip` is the commit the merged branch pointed at
+/// before it was rebased or merged, i.e. what its children were built on.
+pub async fn restack_after_merge(
+    pool: &SqlitePool,
+    git: &GitService,
+    repo: &Repo,
+    merged_branch: &str,
+    merged_tip: &str,
+    base_branch: &str,
+) -> Vec<RestackOutcome> {
+    let mut outcomes = Vec::new();
+    let mut seen = HashSet::new();
+    // (branch the children target, commit they were built on, branch to
+    // rebase them onto, whether to retarget them to it, depth)
+    let mut pending = vec![(
+        merged_branch.to_string(),
+        merged_tip.to_string(),
+        base_branch.to_string(),
+        true,
+        0,
+    )];
+
+    while let Some((parent_branch, parent_tip, new_base, retarget, depth)) = pending.pop() {
+        if depth >= MAX_STACK_DEPTH {
+            warn!(%parent_branch, "Stack too deep, not restacking further");
+            continue;
+        }
+        let children = match WorkspaceRepo::find_stacked_on(pool, repo.id, &parent_branch).await {
+            Ok(children) => children,
+            Err(e) => {
+                warn!(%parent_branch, error = %e, "Failed to find stacked workspaces");
+                continue;
+            }
+        };
+
+        for child in children {
+            if !seen.insert(child.workspace_id) {
+                continue;
+            }
+            let Some(container_ref) = &child.container_ref else {
+                outcomes.push(
+                    fail(
+                        pool,
+                        child.workspace_id,
+                        child.task_id,
+                        "it has no worktree",
+                    )
+                    .await,
+                );
+                continue;
+            };
+            // An agent or script may be committing in the worktree
+            match ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                child.workspace_id,
+            )
+            .await
+            {
+                Ok(false) => {}
+                Ok(true) => {
+                    outcomes.push(
+                        fail(
+                            pool,
+                            child.workspace_id,
+                            child.task_id,
+                            "a process is still running in it",
+                        )
+                        .await,
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    outcomes
+                        .push(fail(pool, child.workspace_id, child.task_id, &e.to_string()).await);
+                    continue;
+                }
+            }
+            let worktree_path = PathBuf::from(container_ref).join(&repo.name);
+            let child_tip = match git.get_branch_oid(&repo.path, &child.branch) {
+                Ok(tip) => tip,
+                Err(e) => {
+                    outcomes
+                        .push(fail(pool, child.workspace_id, child.task_id, &e.to_string()).await);
+                    continue;
+                }
+            };
+
+            if let Err(e) = rebase(
+                git,
+                &repo.path,
+                &worktree_path,
+                &new_base,
+                &parent_tip,
+                &child.branch,
+            ) {
+                outcomes.push(fail(pool, child.workspace_id, child.task_id, &e).await);
+                continue;
+            }
+            if retarget
+                && let Err(e) = WorkspaceRepo::update_target_branch(
+                    pool,
+                    child.workspace_id,
+                    repo.id,
+                    &new_base,
+                )
+                .await
+            {
+                outcomes.push(fail(pool, child.workspace_id, child.task_id, &e.to_string()).await);
+                continue;
+            }
+
+            info!(
+                workspace_id = %child.workspace_id,
+                branch = %child.branch,
+                onto = %new_base,
+                "Restacked workspace after the branch it was stacked on merged"
+            );
+            outcomes.push(RestackOutcome {
+                workspace_id: child.workspace_id,
+                task_id: child.task_id,
+                error: None,
+            });
+            // Its own children were built on its old tip
+            pending.push((
+                child.branch.clone(),
+                child_tip,
+                child.branch,
+                false,
+                depth + 1,
+            ));
+        }
+    }
+
+    outcomes
+}
+
+fn rebase(
+    git: &GitService,
+    repo_path: &Path,
+    worktree_path: &Path,
+    new_base: &str,
+    old_base: &str,
+    branch: &str,
+) -> Result<(), String> {
+    if !worktree_path.exists() {
+        return Err("its worktree is missing".to_string());
+    }
+    git.rebase_branch(repo_path, worktree_path, new_base, old_base, branch)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+async fn fail(pool: &SqlitePool, workspace_id: Uuid, task_id: Uuid, error: &str) -> RestackOutcome {
+    warn!(%workspace_id, %error, "Failed to restack workspace");
+    if let Err(e) = Task::update_needs_attention(pool, task_id, Some(true)).await {
+        warn!(%task_id, error = %e, "Failed to flag task for attention");
+    }
+    RestackOutcome {
+        workspace_id,
+        task_id,
+        error: Some(error.to_string()),
+    }
+}
@@ -0,0 +1,247 @@
+//! Keeping the config file usable across upgrades and downgrades.
+//!
+//! A config written by a newer build can hold fields this build doesn't know.
+//! They are written back untouched when the config is saved, together with
+//! the newer `config_version`, so returning to the newer build loses nothing.
+//! Before a config from any other version is migrated, the file is copied to
+//! `config.json.<version>.bak`.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use ts_rs::TS;
+use utils::text::strip_bom;
+use utoipa::ToSchema;
+
+use super::{Config, ConfigError};
+
+pub const CURRENT_CONFIG_VERSION: &str = "v17";
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ConfigCompatibilityReport {
+    /// `config_version` of the config file; None when there is no file yet
+    pub file_version: Option<String>,
+    /// Version this build reads and writes
+    pub current_version: String,
+    /// Whether the file was written by a newer build
+    pub written_by_newer_version: bool,
+    /// Fields in the file this build can't interpret, as dotted paths. They
+    /// are kept when the config is saved.
+    pub unknown_fields: Vec<String>,
+    /// Copies of the config file taken before it was migrated
+    pub backups: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FileAge {
+    Older,
+    Current,
+    Newer,
+}
+
+fn file_age(raw: &Value) -> FileAge {
+    let version = |v: &str| v.strip_prefix('v')?.parse::<u32>().ok();
+    let file = raw
+        .get("config_version")
+        .and_then(Value::as_str)
+        .and_then(version);
+    match (file, version(CURRENT_CONFIG_VERSION)) {
+        (Some(file), Some(current)) if file > current => FileAge::Newer,
+        (Some(file), Some(current)) if file == current => FileAge::Current,
+        _ => FileAge::Older,
+    }
+}
+
+/// `raw_config` as the current version should read it. A config from a newer
+/// build is read as the current version rather than migrated, which would
+/// fall back to the default.
+pub(super) fn readable_by_current_version(raw_config: &str) -> String {
+    match serde_json::from_str::<Value>(raw_config) {
+        Ok(mut raw) if file_age(&raw) == FileAge::Newer => {
+            if let Some(object) = raw.as_object_mut() {
+                object.insert("config_version".to_string(), CURRENT_CONFIG_VERSION.into());
+            }
+            tracing::warn!(
+                "Config was written by a newer version; fields this version doesn't know will be kept"
+            );
+            raw.to_string()
+        }
+        _ => raw_config.to_string(),
+    }
+}
+
+/// Copy the config file aside before a config of another version is migrated
+pub(super) async fn backup_before_migrating(raw_config: &str, config_path: &Path) {
+    let raw = serde_json::from_str::<Value>(raw_config).unwrap_or(Value::Null);
+    if file_age(&raw) == FileAge::Current {
+        return;
+    }
+    let version = raw
+        .get("config_version")
+        .and_then(Value::as_str)
+        .filter(|v| v.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("unversioned");
+    let backup_path = backup_path(config_path, version);
+    match tokio::fs::write(&backup_path, raw_config).await {
+        Ok(()) => tracing::info!("Backed up {} config to {}", version, backup_path.display()),
+        Err(e) => tracing::warn!(
+            "Failed to back up config to {}: {}",
+            backup_path.display(),
+            e
+        ),
+    }
+}
+
+/// Copy the fields of `existing_raw` that this build can't interpret into
+/// `config`, the serialized config about to replace it
+pub(super) fn keep_unknown_fields(config: &mut Value, existing_raw: &str) {
+    let Ok(existing) = serde_json::from_str::<Value>(existing_raw) else {
+        return;
+    };
+    for path in unknown_fields(&existing) {
+        let Some((key, parents)) = path.split_last() else {
+            continue;
+        };
+        let field = path.iter().try_fold(&existing, |v, k| v.get(k.as_str()));
+        let parent = parents
+            .iter()
+            .try_fold(&mut *config, |v, k| v.get_mut(k.as_str()));
+        if let (Some(field), Some(Value::Object(parent))) = (field, parent) {
+            parent.entry(key.clone()).or_insert_with(|| field.clone());
+        }
+    }
+    if file_age(&existing) == FileAge::Newer
+        && let (Some(version), Some(object)) =
+            (existing.get("config_version"), config.as_object_mut())
+    {
+        object.insert("config_version".to_string(), version.clone());
+    }
+}
+
+/// Which fields of the config file this build can't interpret
+pub async fn compatibility_report(
+    config_path: &Path,
+) -> Result<ConfigCompatibilityReport, ConfigError> {
+    let (file_version, written_by_newer_version, unknown) =
+        match tokio::fs::read_to_string(config_path).await {
+            Ok(raw_config) => {
+                let raw: Value = serde_json::from_str(strip_bom(&raw_config))?;
+                (
+                    raw.get("config_version")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    file_age(&raw) == FileAge::Newer,
+                    unknown_fields(&raw),
+                )
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (None, false, Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+    Ok(ConfigCompatibilityReport {
+        file_version,
+        current_version: CURRENT_CONFIG_VERSION.to_string(),
+        written_by_newer_version,
+        unknown_fields: unknown.into_iter().map(|path| path.join(".")).collect(),
+        backups: list_backups(config_path).await?,
+    })
+}
+
+fn backup_path(config_path: &Path, version: &str) -> PathBuf {
+    let mut file_name = config_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{version}.bak"));
+    config_path.with_file_name(file_name)
+}
+
+async fn list_backups(config_path: &Path) -> Result<Vec<String>, ConfigError> {
+    let (Some(dir), Some(file_name)) = (
+        config_path.parent(),
+        config_path.file_name().and_then(|name| name.to_str()),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let prefix = format!("{file_name}.");
+    let mut backups = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(&prefix) && name.ends_with(".bak") {
+            backups.push(entry.path().to_string_lossy().into_owned());
+        }
+    }
+    backups.sort();
+    Ok(backups)
+}
+
+/// Paths of the fields in `raw` that are lost when it is read as the current
+/// version. A config from an older version is migrated instead, so its
+/// leftover fields don't count.
+fn unknown_fields(raw: &Value) -> Vec<Vec<String>> {
+    if file_age(raw) == FileAge::Older {
+        return Vec::new();
+    }
+    let mut readable = raw.clone();
+    if let Some(object) = readable.as_object_mut() {
+        object.insert("config_version".to_string(), CURRENT_CONFIG_VERSION.into());
+    }
+    let Ok(known) = serde_json::from_value::<Config>(readable).and_then(serde_json::to_value)
+    else {
+        return Vec::new();
+    };
+
+    let mut paths = Vec::new();
+    collect_unknown(raw, &known, &mut Vec::new(), &mut paths);
+    paths
+}
+
+fn collect_unknown(
+    raw: &Value,
+    known: &Value,
+    path: &mut Vec<String>,
+    paths: &mut Vec<Vec<String>>,
+) {
+    let (Value::Object(raw), Value::Object(known)) = (raw, known) else {
+        return;
+    };
+    for (key, value) in raw {
+        path.push(key.clone());
+        match known.get(key) {
+            Some(known_value) => collect_unknown(value, known_value, path, paths),
+            None => paths.push(path.clone()),
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn keeps_fields_from_newer_versions() {
+        let mut newer = serde_json::to_value(Config::default()).unwrap();
+        newer["config_version"] = json!("v99");
+        newer["future_flag"] = json!(true);
+        newer["editor"]["future_setting"] = json!("x");
+
+        let config = Config::from(readable_by_current_version(&newer.to_string()));
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+
+        let mut saved = serde_json::to_value(&config).unwrap();
+        keep_unknown_fields(&mut saved, &newer.to_string());
+        assert_eq!(saved["config_version"], json!("v99"));
+        assert_eq!(saved["future_flag"], json!(true));
+        assert_eq!(saved["editor"]["future_setting"], json!("x"));
+
+        let mut older = newer.clone();
+        older["config_version"] = json!("v16");
+        assert!(unknown_fields(&older).is_empty());
+    }
+}
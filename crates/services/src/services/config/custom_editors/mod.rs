@@ -7,6 +7,7 @@ use std::{
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utils::text::strip_bom;
 use uuid::Uuid;
 
 use crate::services::config::ConfigError;
@@ -177,10 +178,11 @@ impl CustomEditorsConfig {
     async fn load_from_path(path: &Path) -> Result<Self, ConfigError> {
         match tokio::fs::read_to_string(path).await {
             Ok(content) => {
+                let content = strip_bom(&content);
                 if content.trim().is_empty() {
                     return Ok(Self::default());
                 }
-                Ok(serde_json::from_str(&content)?)
+                Ok(serde_json::from_str(content)?)
             }
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
             Err(err) => Err(err.into()),
@@ -197,10 +199,11 @@ impl CustomEditorsConfig {
         let path = utils::assets::editors_path();
         match std::fs::read_to_string(&path) {
             Ok(content) => {
+                let content = strip_bom(&content);
                 if content.trim().is_empty() {
                     return Self::default();
                 }
-                serde_json::from_str(&content).unwrap_or_else(|err| {
+                serde_json::from_str(content).unwrap_or_else(|err| {
                     tracing::error!("Failed to parse custom editors: {}", err);
                     Self::default()
                 })
@@ -45,7 +45,7 @@ pub struct EditorConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, EnumString, EnumIter, PartialEq, Eq)]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum EditorType {
@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
 use thiserror::Error;
+use utils::text::strip_bom;
 
+pub mod compatibility;
 pub mod custom_editors;
 pub mod editor;
+pub mod user_preferences;
 mod versions;
 
 pub use editor::EditorOpenError;
@@ -18,22 +21,39 @@ pub enum ConfigError {
     ValidationError(String),
 }
 
-pub type Config = versions::v15::Config;
-pub type NotificationConfig = versions::v15::NotificationConfig;
-pub type EditorConfig = versions::v15::EditorConfig;
-pub type ThemeMode = versions::v15::ThemeMode;
-pub type SoundFile = versions::v15::SoundFile;
+pub type Config = versions::v17::Config;
+pub type NotificationConfig = versions::v17::NotificationConfig;
+pub type SlackNotificationConfig = versions::v17::SlackNotificationConfig;
+pub type EditorConfig = versions::v17::EditorConfig;
+pub type ThemeMode = versions::v17::ThemeMode;
+pub type SoundFile = versions::v17::SoundFile;
 pub use versions::v2::EffectiveSound;
-pub type EditorType = versions::v15::EditorType;
-pub type GitHubConfig = versions::v15::GitHubConfig;
-pub type UiLanguage = versions::v15::UiLanguage;
-pub type ShowcaseState = versions::v15::ShowcaseState;
-pub type BackupConfig = versions::v15::BackupConfig;
+pub type EditorType = versions::v17::EditorType;
+pub type GitHubConfig = versions::v17::GitHubConfig;
+pub type UiLanguage = versions::v17::UiLanguage;
+pub type ShowcaseState = versions::v17::ShowcaseState;
+pub type BackupConfig = versions::v17::BackupConfig;
+pub type S3BackupConfig = versions::v17::S3BackupConfig;
+pub type RetryPolicyConfig = versions::v17::RetryPolicyConfig;
+pub type GitLabConfig = versions::v17::GitLabConfig;
+pub type BitbucketConfig = versions::v17::BitbucketConfig;
+pub type GiteaConfig = versions::v17::GiteaConfig;
+pub type LinearConfig = versions::v17::LinearConfig;
+pub type AnomalyDetectionConfig = versions::v17::AnomalyDetectionConfig;
+pub type AnomalyThresholds = versions::v17::AnomalyThresholds;
+pub type TokenPrice = versions::v17::TokenPrice;
+pub type UsagePricingConfig = versions::v17::UsagePricingConfig;
+pub type ProviderRateLimit = versions::v17::ProviderRateLimit;
+pub type OutputRedactionConfig = versions::v17::OutputRedactionConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
     match tokio::fs::read_to_string(config_path).await {
-        Ok(raw_config) => Config::from(raw_config),
+        Ok(raw_config) => {
+            let raw_config = strip_bom(&raw_config);
+            compatibility::backup_before_migrating(raw_config, config_path).await;
+            Config::from(compatibility::readable_by_current_version(raw_config))
+        }
         Err(_) => {
             tracing::info!("No config file found, creating one");
             Config::default()
@@ -41,12 +61,17 @@ pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
     }
 }
 
-/// Saves the config to the given path
+/// Saves the config to the given path, keeping any fields of the existing
+/// file that this version can't interpret
 pub async fn save_config_to_file(
     config: &Config,
     config_path: &PathBuf,
 ) -> Result<(), ConfigError> {
-    let raw_config = serde_json::to_string_pretty(config)?;
+    let mut value = serde_json::to_value(config)?;
+    if let Ok(existing) = tokio::fs::read_to_string(config_path).await {
+        compatibility::keep_unknown_fields(&mut value, strip_bom(&existing));
+    }
+    let raw_config = serde_json::to_string_pretty(&value)?;
     tokio::fs::write(config_path, raw_config).await?;
     Ok(())
 }
@@ -0,0 +1,95 @@
+//! Per-user settings layered over the global config.
+//!
+//! When a server has accounts, each user can pick their own theme, editor and
+//! notification settings. Unset fields follow the global config, so a user
+//! only diverges where they chose something different.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Config, EditorConfig, NotificationConfig, ThemeMode};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserPreferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemeMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<EditorConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<NotificationConfig>,
+}
+
+impl UserPreferences {
+    /// Parse stored preferences. Unreadable preferences are dropped rather
+    /// than locking the user out of their config.
+    pub fn from_json(raw: &str) -> Self {
+        serde_json::from_str(raw).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable user preferences: {}", e);
+            Self::default()
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Replace the fields of `config` this user has set
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(theme) = &self.theme {
+            config.theme = theme.clone();
+        }
+        if let Some(editor) = &self.editor {
+            config.editor = editor.clone();
+        }
+        if let Some(notifications) = &self.notifications {
+            config.notifications = notifications.clone();
+        }
+    }
+
+    /// Split a config saved by a user into their preferences and the global
+    /// config: the user's fields are taken from `saved` where they differ
+    /// from `global`, and reset to `global` in the returned config.
+    pub fn split(saved: Config, global: &Config) -> (Self, Config) {
+        let preferences = Self {
+            theme: differing(&saved.theme, &global.theme),
+            editor: differing(&saved.editor, &global.editor),
+            notifications: differing(&saved.notifications, &global.notifications),
+        };
+        let mut shared = saved;
+        shared.theme = global.theme.clone();
+        shared.editor = global.editor.clone();
+        shared.notifications = global.notifications.clone();
+        (preferences, shared)
+    }
+}
+
+/// `value` when it serializes differently from `global`. The config types
+/// don't implement `PartialEq`, so they are compared as JSON.
+fn differing<T: Clone + Serialize>(value: &T, global: &T) -> Option<T> {
+    let differs = serde_json::to_value(value).ok() != serde_json::to_value(global).ok();
+    differs.then(|| value.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_keeps_only_changed_fields_per_user() {
+        let global = Config::default();
+        let mut saved = global.clone();
+        saved.theme = ThemeMode::Dark;
+        saved.git_branch_prefix = "team".to_string();
+
+        let (preferences, shared) = UserPreferences::split(saved, &global);
+
+        assert!(matches!(preferences.theme, Some(ThemeMode::Dark)));
+        assert!(preferences.editor.is_none());
+        assert!(preferences.notifications.is_none());
+        assert!(matches!(shared.theme, ThemeMode::System));
+        assert_eq!(shared.git_branch_prefix, "team");
+
+        let mut overlaid = shared;
+        preferences.apply(&mut overlaid);
+        assert!(matches!(overlaid.theme, ThemeMode::Dark));
+    }
+}
@@ -5,6 +5,8 @@ pub(super) mod v12;
 pub(super) mod v13;
 pub(super) mod v14;
 pub(super) mod v15;
+pub(super) mod v16;
+pub(super) mod v17;
 pub(super) mod v2;
 pub(super) mod v3;
 pub(super) mod v4;
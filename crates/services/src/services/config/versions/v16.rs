@@ -0,0 +1,712 @@
+use std::collections::HashMap;
+
+use anyhow::Error;
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+pub use v15::{
+    EditorConfig, EditorType, GitHubConfig, ShowcaseState, SoundFile, ThemeMode, UiLanguage,
+};
+
+use crate::services::config::versions::{v2, v15};
+
+fn default_git_branch_prefix() -> String {
+    "vk".to_string()
+}
+
+fn default_pr_auto_description_enabled() -> bool {
+    true
+}
+
+fn default_commit_message_auto_generate_enabled() -> bool {
+    true
+}
+
+fn default_langfuse_host() -> Option<String> {
+    Some("https://cloud.langfuse.com".to_string())
+}
+
+fn default_autopilot_enabled() -> bool {
+    false
+}
+
+fn default_retry_enabled() -> bool {
+    true
+}
+
+fn default_retry_max_attempts() -> u32 {
+    2
+}
+
+fn default_retry_initial_backoff_secs() -> u32 {
+    15
+}
+
+fn default_retry_max_backoff_secs() -> u32 {
+    300
+}
+
+/// Automatic re-runs of coding agent executions that fail for a transient
+/// reason (rate limits, network errors, crashes on startup)
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RetryPolicyConfig {
+    #[serde(default = "default_retry_enabled")]
+    pub enabled: bool,
+    /// Retries after the first failure, not counting the original run
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    #[serde(default = "default_retry_initial_backoff_secs")]
+    pub initial_backoff_secs: u32,
+    #[serde(default = "default_retry_max_backoff_secs")]
+    pub max_backoff_secs: u32,
+}
+
+impl Default for RetryPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_retry_enabled(),
+            max_attempts: default_retry_max_attempts(),
+            initial_backoff_secs: default_retry_initial_backoff_secs(),
+            max_backoff_secs: default_retry_max_backoff_secs(),
+        }
+    }
+}
+
+fn default_anomaly_detection_enabled() -> bool {
+    true
+}
+
+fn default_max_repeated_tool_calls() -> u32 {
+    8
+}
+
+fn default_max_no_progress_edits() -> u32 {
+    4
+}
+
+fn default_max_output_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+/// Limits past which a coding agent is considered stuck. 0 disables a check.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AnomalyThresholds {
+    /// Identical tool calls in a row
+    #[serde(default = "default_max_repeated_tool_calls")]
+    pub max_repeated_tool_calls: u32,
+    /// Edits to one file that repeat or revert an earlier edit to it
+    #[serde(default = "default_max_no_progress_edits")]
+    pub max_no_progress_edits: u32,
+    /// Raw stdout and stderr produced by the agent process
+    #[serde(default = "default_max_output_bytes")]
+    #[ts(type = "number")]
+    pub max_output_bytes: u64,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            max_repeated_tool_calls: default_max_repeated_tool_calls(),
+            max_no_progress_edits: default_max_no_progress_edits(),
+            max_output_bytes: default_max_output_bytes(),
+        }
+    }
+}
+
+/// Interrupting coding agents that loop or flood their output
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AnomalyDetectionConfig {
+    #[serde(default = "default_anomaly_detection_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub thresholds: AnomalyThresholds,
+    /// Overrides keyed by executor profile, either `EXECUTOR` or
+    /// `EXECUTOR:VARIANT` (e.g. `CLAUDE_CODE:PLAN`)
+    #[serde(default)]
+    pub profile_thresholds: HashMap<String, AnomalyThresholds>,
+}
+
+impl Default for AnomalyDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_anomaly_detection_enabled(),
+            thresholds: AnomalyThresholds::default(),
+            profile_thresholds: HashMap::new(),
+        }
+    }
+}
+
+impl AnomalyDetectionConfig {
+    /// Thresholds for a profile: its variant's override, then its executor's,
+    /// then the defaults
+    pub fn thresholds_for(&self, profile: &ExecutorProfileId) -> &AnomalyThresholds {
+        self.profile_thresholds
+            .get(&profile.to_string())
+            .or_else(|| self.profile_thresholds.get(&profile.executor.to_string()))
+            .unwrap_or(&self.thresholds)
+    }
+}
+
+fn default_gitlab_base_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+/// Access to GitLab for opening and tracking merge requests on repos whose
+/// remote is hosted there
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitLabConfig {
+    /// Personal access token with the `api` scope
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Instance URL; remotes on this host are treated as GitLab
+    #[serde(default = "default_gitlab_base_url")]
+    pub base_url: String,
+}
+
+impl Default for GitLabConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            base_url: default_gitlab_base_url(),
+        }
+    }
+}
+
+/// Price of a million tokens in USD
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct TokenPrice {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+fn default_token_prices() -> HashMap<String, TokenPrice> {
+    [
+        (BaseCodingAgent::ClaudeCode, 3.0, 15.0),
+        (BaseCodingAgent::Codex, 1.25, 10.0),
+        (BaseCodingAgent::Gemini, 1.25, 10.0),
+    ]
+    .into_iter()
+    .map(|(executor, input_per_million, output_per_million)| {
+        (
+            executor.to_string(),
+            TokenPrice {
+                input_per_million,
+                output_per_million,
+            },
+        )
+    })
+    .collect()
+}
+
+/// Prices the usage API estimates costs with
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UsagePricingConfig {
+    /// Keyed by executor profile, either `EXECUTOR` or `EXECUTOR:VARIANT`
+    /// (e.g. `CLAUDE_CODE:OPUS`); usage of other executors is not priced
+    #[serde(default = "default_token_prices")]
+    pub prices: HashMap<String, TokenPrice>,
+}
+
+impl Default for UsagePricingConfig {
+    fn default() -> Self {
+        Self {
+            prices: default_token_prices(),
+        }
+    }
+}
+
+impl UsagePricingConfig {
+    /// Price for a profile: its variant's, then its executor's
+    pub fn price_for(&self, executor: &str, variant: Option<&str>) -> Option<&TokenPrice> {
+        variant
+            .and_then(|variant| self.prices.get(&format!("{executor}:{variant}")))
+            .or_else(|| self.prices.get(executor))
+    }
+}
+
+/// Limits on how hard one provider's account is driven by concurrent
+/// executions. 0 disables a limit.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ProviderRateLimit {
+    /// Executions of the provider running at once
+    #[serde(default)]
+    pub max_concurrent: u32,
+    /// Executions of the provider started within any 60 seconds
+    #[serde(default)]
+    pub max_starts_per_minute: u32,
+}
+
+fn default_frontend_sounds_enabled() -> bool {
+    false
+}
+
+fn default_error_sound_file() -> SoundFile {
+    SoundFile::ErrorBuzzer
+}
+
+fn default_slack_event_enabled() -> bool {
+    true
+}
+
+/// Posts notifications to a Slack channel through an incoming webhook
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SlackNotificationConfig {
+    /// Incoming webhook URL; nothing is posted while unset
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// A coding agent finished its run
+    #[serde(default = "default_slack_event_enabled")]
+    pub task_completed: bool,
+    /// An agent failed, or is waiting for an approval or an answer
+    #[serde(default = "default_slack_event_enabled")]
+    pub needs_attention: bool,
+    /// A queued merge hit conflicts or failed
+    #[serde(default = "default_slack_event_enabled")]
+    pub merge_failed: bool,
+}
+
+impl Default for SlackNotificationConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            task_completed: default_slack_event_enabled(),
+            needs_attention: default_slack_event_enabled(),
+            merge_failed: default_slack_event_enabled(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct NotificationConfig {
+    pub sound_enabled: bool,
+    pub push_enabled: bool,
+    pub sound_file: SoundFile,
+    #[serde(default = "default_error_sound_file")]
+    pub error_sound_file: SoundFile,
+    #[serde(default)]
+    pub custom_sound_path: Option<String>,
+    /// When true, the frontend handles sound playback instead of the backend.
+    /// This is useful for remote access where backend sound playback is not desired.
+    #[serde(default = "default_frontend_sounds_enabled")]
+    pub frontend_sounds_enabled: bool,
+    #[serde(default)]
+    pub slack: SlackNotificationConfig,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            sound_enabled: true,
+            push_enabled: true,
+            sound_file: SoundFile::CowMooing,
+            error_sound_file: SoundFile::ErrorBuzzer,
+            custom_sound_path: None,
+            frontend_sounds_enabled: false,
+            slack: SlackNotificationConfig::default(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Returns the effective sound to play.
+    /// If `custom_sound_path` is set, returns `Custom`, otherwise returns `Bundled`.
+    pub fn effective_sound(&self) -> v2::EffectiveSound {
+        match &self.custom_sound_path {
+            Some(path) => v2::EffectiveSound::Custom(path.clone()),
+            None => v2::EffectiveSound::Bundled(self.sound_file.clone()),
+        }
+    }
+}
+
+impl From<v15::NotificationConfig> for NotificationConfig {
+    fn from(old: v15::NotificationConfig) -> Self {
+        Self {
+            sound_enabled: old.sound_enabled,
+            push_enabled: old.push_enabled,
+            sound_file: old.sound_file,
+            error_sound_file: old.error_sound_file,
+            custom_sound_path: old.custom_sound_path,
+            frontend_sounds_enabled: old.frontend_sounds_enabled,
+            slack: SlackNotificationConfig::default(),
+        }
+    }
+}
+
+fn default_backup_enabled() -> bool {
+    true
+}
+
+fn default_backup_interval_hours() -> u32 {
+    6
+}
+
+fn default_backup_retention_hours_all() -> u32 {
+    24
+}
+
+fn default_backup_retention_daily_days() -> u32 {
+    7
+}
+
+fn default_backup_retention_weekly_weeks() -> u32 {
+    4
+}
+
+fn default_backup_retention_monthly_months() -> u32 {
+    12
+}
+
+fn default_backup_verify_after_backup() -> bool {
+    true
+}
+
+fn default_backup_drill_interval_days() -> u32 {
+    7
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BackupConfig {
+    #[serde(default = "default_backup_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+    #[serde(default = "default_backup_retention_hours_all")]
+    pub retention_hours_all: u32,
+    #[serde(default = "default_backup_retention_daily_days")]
+    pub retention_daily_days: u32,
+    #[serde(default = "default_backup_retention_weekly_weeks")]
+    pub retention_weekly_weeks: u32,
+    #[serde(default = "default_backup_retention_monthly_months")]
+    pub retention_monthly_months: u32,
+    /// Also upload archives to an S3-compatible bucket, with the same retention
+    #[serde(default)]
+    pub s3: Option<S3BackupConfig>,
+    /// Check each new archive opens and matches the live database
+    #[serde(default = "default_backup_verify_after_backup")]
+    pub verify_after_backup: bool,
+    /// Days between restore drills on the latest archive; 0 disables them
+    #[serde(default = "default_backup_drill_interval_days")]
+    pub drill_interval_days: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_backup_enabled(),
+            interval_hours: default_backup_interval_hours(),
+            retention_hours_all: default_backup_retention_hours_all(),
+            retention_daily_days: default_backup_retention_daily_days(),
+            retention_weekly_weeks: default_backup_retention_weekly_weeks(),
+            retention_monthly_months: default_backup_retention_monthly_months(),
+            s3: None,
+            verify_after_backup: default_backup_verify_after_backup(),
+            drill_interval_days: default_backup_drill_interval_days(),
+        }
+    }
+}
+
+impl From<v15::BackupConfig> for BackupConfig {
+    fn from(old: v15::BackupConfig) -> Self {
+        Self {
+            enabled: old.enabled,
+            interval_hours: old.interval_hours,
+            retention_hours_all: old.retention_hours_all,
+            retention_daily_days: old.retention_daily_days,
+            retention_weekly_weeks: old.retention_weekly_weeks,
+            retention_monthly_months: old.retention_monthly_months,
+            s3: None,
+            verify_after_backup: default_backup_verify_after_backup(),
+            drill_interval_days: default_backup_drill_interval_days(),
+        }
+    }
+}
+
+/// S3-compatible bucket that backups are copied to
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct S3BackupConfig {
+    /// Defaults to AWS for `region`; set for MinIO, R2 and other compatible stores
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub bucket: String,
+    /// Key prefix archives are stored under, e.g. `vibe-kanban/`
+    #[serde(default)]
+    pub prefix: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Address the bucket as `endpoint/bucket` rather than `bucket.endpoint`
+    #[serde(default)]
+    pub path_style: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct Config {
+    pub config_version: String,
+    pub theme: ThemeMode,
+    pub executor_profile: ExecutorProfileId,
+    pub disclaimer_acknowledged: bool,
+    pub onboarding_acknowledged: bool,
+    pub notifications: NotificationConfig,
+    pub editor: EditorConfig,
+    pub github: GitHubConfig,
+    pub analytics_enabled: bool,
+    pub workspace_dir: Option<String>,
+    pub last_app_version: Option<String>,
+    pub show_release_notes: bool,
+    #[serde(default)]
+    pub language: UiLanguage,
+    #[serde(default = "default_git_branch_prefix")]
+    pub git_branch_prefix: String,
+    #[serde(default)]
+    pub showcases: ShowcaseState,
+    #[serde(default = "default_pr_auto_description_enabled")]
+    pub pr_auto_description_enabled: bool,
+    #[serde(default)]
+    pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub default_clone_directory: Option<String>,
+    #[serde(default = "default_commit_message_auto_generate_enabled")]
+    pub commit_message_auto_generate_enabled: bool,
+    #[serde(default)]
+    pub commit_message_prompt: Option<String>,
+    #[serde(default)]
+    pub commit_message_executor_profile: Option<ExecutorProfileId>,
+    /// Maximum concurrent agent executions (0 = unlimited)
+    #[serde(default)]
+    pub max_concurrent_agents: u32,
+    // Langfuse configuration
+    #[serde(default)]
+    pub langfuse_enabled: bool,
+    #[serde(default)]
+    pub langfuse_public_key: Option<String>,
+    #[serde(default)]
+    pub langfuse_secret_key: Option<String>,
+    #[serde(default = "default_langfuse_host")]
+    pub langfuse_host: Option<String>,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    /// Executor profile for the review attention agent.
+    /// When Some, review attention uses the specified executor.
+    /// When None, review attention is disabled.
+    #[serde(default)]
+    pub review_attention_executor_profile: Option<ExecutorProfileId>,
+    /// Custom prompt for the review attention agent.
+    /// When None, uses the default prompt.
+    /// The prompt should include placeholders {task_description} and {agent_summary}.
+    #[serde(default)]
+    pub review_attention_prompt: Option<String>,
+    /// When enabled, completed tasks are automatically merged and dependent tasks are queued.
+    #[serde(default = "default_autopilot_enabled")]
+    pub autopilot_enabled: bool,
+    #[serde(default)]
+    pub retry_policy: RetryPolicyConfig,
+    #[serde(default)]
+    pub gitlab: GitLabConfig,
+    #[serde(default)]
+    pub anomaly_detection: AnomalyDetectionConfig,
+    #[serde(default)]
+    pub usage_pricing: UsagePricingConfig,
+    /// Keyed by executor (e.g. `CLAUDE_CODE`); executions over a limit wait
+    /// in the queue instead of starting
+    #[serde(default)]
+    pub provider_rate_limits: HashMap<String, ProviderRateLimit>,
+}
+
+impl Config {
+    fn from_v15_config(old_config: v15::Config) -> Self {
+        Self {
+            config_version: "v16".to_string(),
+            theme: old_config.theme,
+            executor_profile: old_config.executor_profile,
+            disclaimer_acknowledged: old_config.disclaimer_acknowledged,
+            onboarding_acknowledged: old_config.onboarding_acknowledged,
+            notifications: old_config.notifications.into(),
+            editor: old_config.editor,
+            github: old_config.github,
+            analytics_enabled: old_config.analytics_enabled,
+            workspace_dir: old_config.workspace_dir,
+            last_app_version: old_config.last_app_version,
+            show_release_notes: old_config.show_release_notes,
+            language: old_config.language,
+            git_branch_prefix: old_config.git_branch_prefix,
+            showcases: old_config.showcases,
+            pr_auto_description_enabled: old_config.pr_auto_description_enabled,
+            pr_auto_description_prompt: old_config.pr_auto_description_prompt,
+            default_clone_directory: old_config.default_clone_directory,
+            commit_message_auto_generate_enabled: old_config.commit_message_auto_generate_enabled,
+            commit_message_prompt: old_config.commit_message_prompt,
+            commit_message_executor_profile: old_config.commit_message_executor_profile,
+            max_concurrent_agents: old_config.max_concurrent_agents,
+            langfuse_enabled: old_config.langfuse_enabled,
+            langfuse_public_key: old_config.langfuse_public_key,
+            langfuse_secret_key: old_config.langfuse_secret_key,
+            langfuse_host: old_config.langfuse_host,
+            backup: old_config.backup.into(),
+            review_attention_executor_profile: old_config.review_attention_executor_profile,
+            review_attention_prompt: old_config.review_attention_prompt,
+            autopilot_enabled: old_config.autopilot_enabled,
+            retry_policy: RetryPolicyConfig::default(),
+            gitlab: GitLabConfig::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            usage_pricing: UsagePricingConfig::default(),
+            provider_rate_limits: HashMap::new(),
+        }
+    }
+
+    pub fn from_previous_version(raw_config: &str) -> Result<Self, Error> {
+        let old_config = v15::Config::from(raw_config.to_string());
+        Ok(Self::from_v15_config(old_config))
+    }
+}
+
+impl From<String> for Config {
+    fn from(raw_config: String) -> Self {
+        if let Ok(config) = serde_json::from_str::<Config>(&raw_config)
+            && config.config_version == "v16"
+        {
+            return config;
+        }
+
+        match Self::from_previous_version(&raw_config) {
+            Ok(config) => {
+                tracing::info!("Config upgraded to v16");
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Config migration failed: {}, using default", e);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            config_version: "v16".to_string(),
+            theme: ThemeMode::System,
+            executor_profile: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            disclaimer_acknowledged: false,
+            onboarding_acknowledged: false,
+            notifications: NotificationConfig::default(),
+            editor: EditorConfig::default(),
+            github: GitHubConfig::default(),
+            analytics_enabled: true,
+            workspace_dir: None,
+            last_app_version: None,
+            show_release_notes: false,
+            language: UiLanguage::default(),
+            git_branch_prefix: default_git_branch_prefix(),
+            showcases: ShowcaseState::default(),
+            pr_auto_description_enabled: true,
+            pr_auto_description_prompt: None,
+            default_clone_directory: None,
+            commit_message_auto_generate_enabled: true,
+            commit_message_prompt: None,
+            commit_message_executor_profile: None,
+            max_concurrent_agents: 0,
+            langfuse_enabled: false,
+            langfuse_public_key: None,
+            langfuse_secret_key: None,
+            langfuse_host: default_langfuse_host(),
+            backup: BackupConfig::default(),
+            review_attention_executor_profile: None,
+            review_attention_prompt: None,
+            autopilot_enabled: false,
+            retry_policy: RetryPolicyConfig::default(),
+            gitlab: GitLabConfig::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            usage_pricing: UsagePricingConfig::default(),
+            provider_rate_limits: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v15_to_v16_migration() {
+        let v15_config = v15::Config {
+            review_attention_prompt: Some("Custom review prompt".to_string()),
+            ..v15::Config::default()
+        };
+        let v15_json = serde_json::to_string(&v15_config).unwrap();
+
+        let v16_config = Config::from(v15_json);
+
+        assert_eq!(v16_config.config_version, "v16");
+        // Verify v15 fields are preserved
+        assert_eq!(v16_config.analytics_enabled, v15_config.analytics_enabled);
+        assert_eq!(v16_config.backup.enabled, v15_config.backup.enabled);
+        assert!(v16_config.backup.s3.is_none());
+        assert!(v16_config.backup.verify_after_backup);
+        assert_eq!(v16_config.backup.drill_interval_days, 7);
+        assert!(v16_config.notifications.slack.webhook_url.is_none());
+        assert_eq!(
+            v16_config.review_attention_prompt,
+            v15_config.review_attention_prompt
+        );
+        assert_eq!(v16_config.autopilot_enabled, v15_config.autopilot_enabled);
+        // Verify new field gets its defaults
+        assert!(v16_config.retry_policy.enabled);
+        assert_eq!(v16_config.retry_policy.max_attempts, 2);
+        assert!(v16_config.gitlab.token.is_none());
+        assert_eq!(v16_config.gitlab.base_url, "https://gitlab.com");
+    }
+
+    #[test]
+    fn test_v16_deserialize_partial_retry_policy() {
+        let config = Config::default();
+        let mut json_value: serde_json::Value = serde_json::to_value(&config).unwrap();
+        json_value["retry_policy"] = serde_json::json!({ "max_attempts": 5 });
+
+        let parsed: Config = serde_json::from_value(json_value).unwrap();
+        assert_eq!(parsed.retry_policy.max_attempts, 5);
+        assert!(parsed.retry_policy.enabled);
+        assert_eq!(parsed.retry_policy.initial_backoff_secs, 15);
+    }
+
+    #[test]
+    fn test_anomaly_thresholds_for_profile() {
+        let mut config = AnomalyDetectionConfig::default();
+        let executor_override = AnomalyThresholds {
+            max_repeated_tool_calls: 20,
+            ..AnomalyThresholds::default()
+        };
+        let variant_override = AnomalyThresholds {
+            max_output_bytes: 0,
+            ..AnomalyThresholds::default()
+        };
+        config
+            .profile_thresholds
+            .insert("CLAUDE_CODE".to_string(), executor_override.clone());
+        config
+            .profile_thresholds
+            .insert("CLAUDE_CODE:PLAN".to_string(), variant_override.clone());
+
+        let default_profile = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+        let plan_profile = ExecutorProfileId {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: Some("PLAN".to_string()),
+        };
+        assert_eq!(config.thresholds_for(&default_profile), &executor_override);
+        assert_eq!(config.thresholds_for(&plan_profile), &variant_override);
+        assert_eq!(
+            config.thresholds_for(&ExecutorProfileId::new(BaseCodingAgent::Codex)),
+            &AnomalyThresholds::default()
+        );
+    }
+}
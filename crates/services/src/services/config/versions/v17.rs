@@ -0,0 +1,909 @@
+use std::collections::HashMap;
+
+use anyhow::Error;
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+pub use v16::{
+    EditorConfig, EditorType, GitHubConfig, ProviderRateLimit, ShowcaseState, SoundFile, ThemeMode,
+    TokenPrice, UiLanguage, UsagePricingConfig,
+};
+
+use crate::services::{
+    config::versions::{v2, v16},
+    secrets::{
+        GITLAB_TOKEN, LANGFUSE_SECRET_KEY, S3_SECRET_ACCESS_KEY, SLACK_WEBHOOK_URL, SecretRef,
+        SecretService, SecretStore, migrate_plaintext,
+    },
+    ssh_remote::SshRemoteConfig,
+};
+
+fn default_git_branch_prefix() -> String {
+    "vk".to_string()
+}
+
+fn default_pr_auto_description_enabled() -> bool {
+    true
+}
+
+fn default_commit_message_auto_generate_enabled() -> bool {
+    true
+}
+
+fn default_langfuse_host() -> Option<String> {
+    Some("https://cloud.langfuse.com".to_string())
+}
+
+fn default_autopilot_enabled() -> bool {
+    false
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_retry_enabled() -> bool {
+    true
+}
+
+fn default_retry_max_attempts() -> u32 {
+    2
+}
+
+fn default_retry_initial_backoff_secs() -> u32 {
+    15
+}
+
+fn default_retry_max_backoff_secs() -> u32 {
+    300
+}
+
+/// Automatic re-runs of coding agent executions that fail for a transient
+/// reason (rate limits, network errors, crashes on startup)
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct RetryPolicyConfig {
+    #[serde(default = "default_retry_enabled")]
+    pub enabled: bool,
+    /// Retries after the first failure, not counting the original run
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    #[serde(default = "default_retry_initial_backoff_secs")]
+    pub initial_backoff_secs: u32,
+    #[serde(default = "default_retry_max_backoff_secs")]
+    pub max_backoff_secs: u32,
+    /// Exit codes treated as transient whatever the agent printed, for
+    /// agents that report rate limits with a dedicated code
+    #[serde(default)]
+    pub retry_on_exit_codes: Vec<i64>,
+}
+
+impl Default for RetryPolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_retry_enabled(),
+            max_attempts: default_retry_max_attempts(),
+            initial_backoff_secs: default_retry_initial_backoff_secs(),
+            max_backoff_secs: default_retry_max_backoff_secs(),
+            retry_on_exit_codes: Vec::new(),
+        }
+    }
+}
+
+impl From<v16::RetryPolicyConfig> for RetryPolicyConfig {
+    fn from(old: v16::RetryPolicyConfig) -> Self {
+        Self {
+            enabled: old.enabled,
+            max_attempts: old.max_attempts,
+            initial_backoff_secs: old.initial_backoff_secs,
+            max_backoff_secs: old.max_backoff_secs,
+            retry_on_exit_codes: Vec::new(),
+        }
+    }
+}
+
+/// Access to Bitbucket Cloud for opening and tracking pull requests on repos
+/// hosted on bitbucket.org
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BitbucketConfig {
+    /// Bitbucket username the app password belongs to
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Entry in the secret store holding an app password with pull request
+    /// read and write permissions
+    #[serde(default)]
+    pub app_password: Option<SecretRef>,
+}
+
+/// Access to a Gitea (or Forgejo) instance for opening and tracking pull
+/// requests on repos hosted there
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GiteaConfig {
+    /// Entry in the secret store holding an access token with the
+    /// `repository` scope
+    #[serde(default)]
+    pub token: Option<SecretRef>,
+    /// Instance URL; remotes on this host are treated as Gitea. Gitea has no
+    /// public default, so nothing is detected until it is set.
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+/// Access to Linear for looking up the title and status of issues linked to
+/// tasks
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct LinearConfig {
+    /// Entry in the secret store holding a personal API key; Linear links
+    /// are not unfurled while unset
+    #[serde(default)]
+    pub api_key: Option<SecretRef>,
+}
+
+fn default_anomaly_detection_enabled() -> bool {
+    true
+}
+
+fn default_max_repeated_tool_calls() -> u32 {
+    8
+}
+
+fn default_max_no_progress_edits() -> u32 {
+    4
+}
+
+fn default_max_output_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+fn default_inactivity_timeout_minutes() -> u32 {
+    60
+}
+
+/// Limits past which a coding agent is considered stuck. 0 disables a check.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AnomalyThresholds {
+    /// Identical tool calls in a row
+    #[serde(default = "default_max_repeated_tool_calls")]
+    pub max_repeated_tool_calls: u32,
+    /// Edits to one file that repeat or revert an earlier edit to it
+    #[serde(default = "default_max_no_progress_edits")]
+    pub max_no_progress_edits: u32,
+    /// Raw stdout and stderr produced by the agent process
+    #[serde(default = "default_max_output_bytes")]
+    #[ts(type = "number")]
+    pub max_output_bytes: u64,
+    /// Minutes without any new output from the agent, not counting time
+    /// spent waiting for an approval
+    #[serde(default = "default_inactivity_timeout_minutes")]
+    pub inactivity_timeout_minutes: u32,
+    /// Minutes an execution may run in total
+    #[serde(default)]
+    pub max_duration_minutes: u32,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            max_repeated_tool_calls: default_max_repeated_tool_calls(),
+            max_no_progress_edits: default_max_no_progress_edits(),
+            max_output_bytes: default_max_output_bytes(),
+            inactivity_timeout_minutes: default_inactivity_timeout_minutes(),
+            max_duration_minutes: 0,
+        }
+    }
+}
+
+/// Interrupting coding agents that loop, flood their output or stall
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct AnomalyDetectionConfig {
+    #[serde(default = "default_anomaly_detection_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub thresholds: AnomalyThresholds,
+    /// Overrides keyed by executor profile, either `EXECUTOR` or
+    /// `EXECUTOR:VARIANT` (e.g. `CLAUDE_CODE:PLAN`)
+    #[serde(default)]
+    pub profile_thresholds: HashMap<String, AnomalyThresholds>,
+}
+
+impl Default for AnomalyDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_anomaly_detection_enabled(),
+            thresholds: AnomalyThresholds::default(),
+            profile_thresholds: HashMap::new(),
+        }
+    }
+}
+
+impl From<v16::AnomalyThresholds> for AnomalyThresholds {
+    fn from(old: v16::AnomalyThresholds) -> Self {
+        Self {
+            max_repeated_tool_calls: old.max_repeated_tool_calls,
+            max_no_progress_edits: old.max_no_progress_edits,
+            max_output_bytes: old.max_output_bytes,
+            inactivity_timeout_minutes: default_inactivity_timeout_minutes(),
+            max_duration_minutes: 0,
+        }
+    }
+}
+
+impl From<v16::AnomalyDetectionConfig> for AnomalyDetectionConfig {
+    fn from(old: v16::AnomalyDetectionConfig) -> Self {
+        Self {
+            enabled: old.enabled,
+            thresholds: old.thresholds.into(),
+            profile_thresholds: old
+                .profile_thresholds
+                .into_iter()
+                .map(|(profile, thresholds)| (profile, thresholds.into()))
+                .collect(),
+        }
+    }
+}
+
+impl AnomalyDetectionConfig {
+    /// Thresholds for a profile: its variant's override, then its executor's,
+    /// then the defaults
+    pub fn thresholds_for(&self, profile: &ExecutorProfileId) -> &AnomalyThresholds {
+        self.profile_thresholds
+            .get(&profile.to_string())
+            .or_else(|| self.profile_thresholds.get(&profile.executor.to_string()))
+            .unwrap_or(&self.thresholds)
+    }
+}
+
+fn default_gitlab_base_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+/// Access to GitLab for opening and tracking merge requests on repos whose
+/// remote is hosted there
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GitLabConfig {
+    /// Entry in the secret store holding a personal access token with the
+    /// `api` scope
+    #[serde(default)]
+    pub token: Option<SecretRef>,
+    /// Instance URL; remotes on this host are treated as GitLab
+    #[serde(default = "default_gitlab_base_url")]
+    pub base_url: String,
+}
+
+impl Default for GitLabConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            base_url: default_gitlab_base_url(),
+        }
+    }
+}
+
+impl GitLabConfig {
+    fn from_v16(old: v16::GitLabConfig, secrets: &dyn SecretStore) -> Self {
+        Self {
+            token: migrate_plaintext(secrets, GITLAB_TOKEN, old.token),
+            base_url: old.base_url,
+        }
+    }
+}
+
+fn default_frontend_sounds_enabled() -> bool {
+    false
+}
+
+fn default_error_sound_file() -> SoundFile {
+    SoundFile::ErrorBuzzer
+}
+
+fn default_slack_event_enabled() -> bool {
+    true
+}
+
+/// Posts notifications to a Slack channel through an incoming webhook
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SlackNotificationConfig {
+    /// Entry in the secret store holding the incoming webhook URL; nothing is
+    /// posted while unset
+    #[serde(default)]
+    pub webhook_url: Option<SecretRef>,
+    /// A coding agent finished its run
+    #[serde(default = "default_slack_event_enabled")]
+    pub task_completed: bool,
+    /// An agent failed, or is waiting for an approval or an answer
+    #[serde(default = "default_slack_event_enabled")]
+    pub needs_attention: bool,
+    /// A queued merge hit conflicts or failed
+    #[serde(default = "default_slack_event_enabled")]
+    pub merge_failed: bool,
+}
+
+impl Default for SlackNotificationConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            task_completed: default_slack_event_enabled(),
+            needs_attention: default_slack_event_enabled(),
+            merge_failed: default_slack_event_enabled(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct NotificationConfig {
+    pub sound_enabled: bool,
+    pub push_enabled: bool,
+    pub sound_file: SoundFile,
+    #[serde(default = "default_error_sound_file")]
+    pub error_sound_file: SoundFile,
+    #[serde(default)]
+    pub custom_sound_path: Option<String>,
+    /// When true, the frontend handles sound playback instead of the backend.
+    /// This is useful for remote access where backend sound playback is not desired.
+    #[serde(default = "default_frontend_sounds_enabled")]
+    pub frontend_sounds_enabled: bool,
+    #[serde(default)]
+    pub slack: SlackNotificationConfig,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            sound_enabled: true,
+            push_enabled: true,
+            sound_file: SoundFile::CowMooing,
+            error_sound_file: SoundFile::ErrorBuzzer,
+            custom_sound_path: None,
+            frontend_sounds_enabled: false,
+            slack: SlackNotificationConfig::default(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Returns the effective sound to play.
+    /// If `custom_sound_path` is set, returns `Custom`, otherwise returns `Bundled`.
+    pub fn effective_sound(&self) -> v2::EffectiveSound {
+        match &self.custom_sound_path {
+            Some(path) => v2::EffectiveSound::Custom(path.clone()),
+            None => v2::EffectiveSound::Bundled(self.sound_file.clone()),
+        }
+    }
+
+    fn from_v16(old: v16::NotificationConfig, secrets: &dyn SecretStore) -> Self {
+        Self {
+            sound_enabled: old.sound_enabled,
+            push_enabled: old.push_enabled,
+            sound_file: old.sound_file,
+            error_sound_file: old.error_sound_file,
+            custom_sound_path: old.custom_sound_path,
+            frontend_sounds_enabled: old.frontend_sounds_enabled,
+            slack: SlackNotificationConfig {
+                webhook_url: migrate_plaintext(secrets, SLACK_WEBHOOK_URL, old.slack.webhook_url),
+                task_completed: old.slack.task_completed,
+                needs_attention: old.slack.needs_attention,
+                merge_failed: old.slack.merge_failed,
+            },
+        }
+    }
+}
+
+fn default_backup_enabled() -> bool {
+    true
+}
+
+fn default_backup_interval_hours() -> u32 {
+    6
+}
+
+fn default_backup_retention_hours_all() -> u32 {
+    24
+}
+
+fn default_backup_retention_daily_days() -> u32 {
+    7
+}
+
+fn default_backup_retention_weekly_weeks() -> u32 {
+    4
+}
+
+fn default_backup_retention_monthly_months() -> u32 {
+    12
+}
+
+fn default_backup_verify_after_backup() -> bool {
+    true
+}
+
+fn default_backup_drill_interval_days() -> u32 {
+    7
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BackupConfig {
+    #[serde(default = "default_backup_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+    #[serde(default = "default_backup_retention_hours_all")]
+    pub retention_hours_all: u32,
+    #[serde(default = "default_backup_retention_daily_days")]
+    pub retention_daily_days: u32,
+    #[serde(default = "default_backup_retention_weekly_weeks")]
+    pub retention_weekly_weeks: u32,
+    #[serde(default = "default_backup_retention_monthly_months")]
+    pub retention_monthly_months: u32,
+    /// Also upload archives to an S3-compatible bucket, with the same retention
+    #[serde(default)]
+    pub s3: Option<S3BackupConfig>,
+    /// Check each new archive opens and matches the database it was made
+    /// from. Archives that fail are deleted instead of uploaded.
+    #[serde(default = "default_backup_verify_after_backup")]
+    pub verify_after_backup: bool,
+    /// Days between restore drills on the latest archive; 0 disables them
+    #[serde(default = "default_backup_drill_interval_days")]
+    pub drill_interval_days: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_backup_enabled(),
+            interval_hours: default_backup_interval_hours(),
+            retention_hours_all: default_backup_retention_hours_all(),
+            retention_daily_days: default_backup_retention_daily_days(),
+            retention_weekly_weeks: default_backup_retention_weekly_weeks(),
+            retention_monthly_months: default_backup_retention_monthly_months(),
+            s3: None,
+            verify_after_backup: default_backup_verify_after_backup(),
+            drill_interval_days: default_backup_drill_interval_days(),
+        }
+    }
+}
+
+impl BackupConfig {
+    fn from_v16(old: v16::BackupConfig, secrets: &dyn SecretStore) -> Self {
+        Self {
+            enabled: old.enabled,
+            interval_hours: old.interval_hours,
+            retention_hours_all: old.retention_hours_all,
+            retention_daily_days: old.retention_daily_days,
+            retention_weekly_weeks: old.retention_weekly_weeks,
+            retention_monthly_months: old.retention_monthly_months,
+            s3: old.s3.map(|s3| S3BackupConfig {
+                endpoint: s3.endpoint,
+                region: s3.region,
+                bucket: s3.bucket,
+                prefix: s3.prefix,
+                access_key_id: s3.access_key_id,
+                secret_access_key: migrate_plaintext(
+                    secrets,
+                    S3_SECRET_ACCESS_KEY,
+                    Some(s3.secret_access_key),
+                ),
+                path_style: s3.path_style,
+            }),
+            verify_after_backup: old.verify_after_backup,
+            drill_interval_days: old.drill_interval_days,
+        }
+    }
+}
+
+/// S3-compatible bucket that backups are copied to
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct S3BackupConfig {
+    /// Defaults to AWS for `region`; set for MinIO, R2 and other compatible stores
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub bucket: String,
+    /// Key prefix archives are stored under, e.g. `vibe-kanban/`
+    #[serde(default)]
+    pub prefix: String,
+    pub access_key_id: String,
+    /// Entry in the secret store holding the secret access key; uploads fail
+    /// while unset
+    #[serde(default)]
+    pub secret_access_key: Option<SecretRef>,
+    /// Address the bucket as `endpoint/bucket` rather than `bucket.endpoint`
+    #[serde(default)]
+    pub path_style: bool,
+}
+
+fn default_output_redaction_enabled() -> bool {
+    true
+}
+
+fn default_redaction_builtin_patterns() -> bool {
+    true
+}
+
+fn default_redaction_entropy_threshold() -> f64 {
+    4.5
+}
+
+/// Masking secrets in agent and script output before it is stored or shown
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OutputRedactionConfig {
+    #[serde(default = "default_output_redaction_enabled")]
+    pub enabled: bool,
+    /// Mask common credential formats: cloud and API keys, access tokens,
+    /// private keys and `password=...` style assignments
+    #[serde(default = "default_redaction_builtin_patterns")]
+    pub builtin_patterns: bool,
+    /// Mask long random-looking tokens. Catches keys of unknown formats, at
+    /// the cost of sometimes masking ids
+    #[serde(default)]
+    pub entropy_detection: bool,
+    /// Bits per character from which a token counts as random
+    #[serde(default = "default_redaction_entropy_threshold")]
+    pub entropy_threshold: f64,
+    /// Regexes masked in every project's output, besides each project's own
+    #[serde(default)]
+    pub custom_patterns: Vec<String>,
+}
+
+impl Default for OutputRedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_output_redaction_enabled(),
+            builtin_patterns: default_redaction_builtin_patterns(),
+            entropy_detection: false,
+            entropy_threshold: default_redaction_entropy_threshold(),
+            custom_patterns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct Config {
+    pub config_version: String,
+    pub theme: ThemeMode,
+    pub executor_profile: ExecutorProfileId,
+    pub disclaimer_acknowledged: bool,
+    pub onboarding_acknowledged: bool,
+    pub notifications: NotificationConfig,
+    pub editor: EditorConfig,
+    pub github: GitHubConfig,
+    pub analytics_enabled: bool,
+    pub workspace_dir: Option<String>,
+    pub last_app_version: Option<String>,
+    pub show_release_notes: bool,
+    #[serde(default)]
+    pub language: UiLanguage,
+    #[serde(default = "default_git_branch_prefix")]
+    pub git_branch_prefix: String,
+    #[serde(default)]
+    pub showcases: ShowcaseState,
+    #[serde(default = "default_pr_auto_description_enabled")]
+    pub pr_auto_description_enabled: bool,
+    #[serde(default)]
+    pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub default_clone_directory: Option<String>,
+    #[serde(default = "default_commit_message_auto_generate_enabled")]
+    pub commit_message_auto_generate_enabled: bool,
+    #[serde(default)]
+    pub commit_message_prompt: Option<String>,
+    #[serde(default)]
+    pub commit_message_executor_profile: Option<ExecutorProfileId>,
+    /// Prompt template for commit messages; takes precedence over
+    /// `commit_message_prompt`
+    #[serde(default)]
+    pub commit_message_template_id: Option<Uuid>,
+    /// Maximum concurrent agent executions (0 = unlimited)
+    #[serde(default)]
+    pub max_concurrent_agents: u32,
+    /// At the concurrency limit, interrupt a running agent of a lower-priority
+    /// task for a queued higher-priority one; it is queued to resume
+    #[serde(default)]
+    pub preempt_lower_priority: bool,
+    /// Files larger than this (in KiB) are shown without contents in diffs.
+    /// None uses the 2 MiB default; capped at 64 MiB.
+    #[serde(default)]
+    pub max_inline_diff_kb: Option<u32>,
+    // Langfuse configuration
+    #[serde(default)]
+    pub langfuse_enabled: bool,
+    #[serde(default)]
+    pub langfuse_public_key: Option<String>,
+    /// Entry in the secret store holding the Langfuse secret key
+    #[serde(default)]
+    pub langfuse_secret_key: Option<SecretRef>,
+    #[serde(default = "default_langfuse_host")]
+    pub langfuse_host: Option<String>,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    /// Executor profile for the review attention agent.
+    /// When Some, review attention uses the specified executor.
+    /// When None, review attention is disabled.
+    #[serde(default)]
+    pub review_attention_executor_profile: Option<ExecutorProfileId>,
+    /// Custom prompt for the review attention agent.
+    /// When None, uses the default prompt.
+    /// The prompt should include placeholders {task_description} and {agent_summary}.
+    #[serde(default)]
+    pub review_attention_prompt: Option<String>,
+    /// Prompt template for review attention; takes precedence over
+    /// `review_attention_prompt`
+    #[serde(default)]
+    pub review_attention_template_id: Option<Uuid>,
+    /// Prompt template for collecting agent feedback when a task is done
+    #[serde(default)]
+    pub feedback_template_id: Option<Uuid>,
+    /// When enabled, completed tasks are automatically merged and dependent tasks are queued.
+    #[serde(default = "default_autopilot_enabled")]
+    pub autopilot_enabled: bool,
+    #[serde(default)]
+    pub retry_policy: RetryPolicyConfig,
+    /// Replaces `retry_policy` for executions of an executor profile. Keyed
+    /// by executor (e.g. `CLAUDE_CODE`) or executor and variant (e.g.
+    /// `CLAUDE_CODE:PLAN`); the variant key wins.
+    #[serde(default)]
+    pub retry_policy_overrides: HashMap<String, RetryPolicyConfig>,
+    #[serde(default)]
+    pub gitlab: GitLabConfig,
+    #[serde(default)]
+    pub bitbucket: BitbucketConfig,
+    #[serde(default)]
+    pub gitea: GiteaConfig,
+    #[serde(default)]
+    pub linear: LinearConfig,
+    #[serde(default)]
+    pub anomaly_detection: AnomalyDetectionConfig,
+    #[serde(default)]
+    pub usage_pricing: UsagePricingConfig,
+    /// Keyed by executor (e.g. `CLAUDE_CODE`); executions over a limit wait
+    /// in the queue instead of starting
+    #[serde(default)]
+    pub provider_rate_limits: HashMap<String, ProviderRateLimit>,
+    /// Deleted tasks and projects stay restorable for this many days before
+    /// they are purged
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Run coding agents and scripts on another machine over SSH
+    #[serde(default)]
+    pub ssh_remote: SshRemoteConfig,
+    #[serde(default)]
+    pub output_redaction: OutputRedactionConfig,
+}
+
+impl Config {
+    /// Secret values held in plaintext by v16 are moved into `secrets`
+    fn from_v16_config(old_config: v16::Config, secrets: &dyn SecretStore) -> Self {
+        Self {
+            config_version: "v17".to_string(),
+            theme: old_config.theme,
+            executor_profile: old_config.executor_profile,
+            disclaimer_acknowledged: old_config.disclaimer_acknowledged,
+            onboarding_acknowledged: old_config.onboarding_acknowledged,
+            notifications: NotificationConfig::from_v16(old_config.notifications, secrets),
+            editor: old_config.editor,
+            github: old_config.github,
+            analytics_enabled: old_config.analytics_enabled,
+            workspace_dir: old_config.workspace_dir,
+            last_app_version: old_config.last_app_version,
+            show_release_notes: old_config.show_release_notes,
+            language: old_config.language,
+            git_branch_prefix: old_config.git_branch_prefix,
+            showcases: old_config.showcases,
+            pr_auto_description_enabled: old_config.pr_auto_description_enabled,
+            pr_auto_description_prompt: old_config.pr_auto_description_prompt,
+            default_clone_directory: old_config.default_clone_directory,
+            commit_message_auto_generate_enabled: old_config.commit_message_auto_generate_enabled,
+            commit_message_prompt: old_config.commit_message_prompt,
+            commit_message_executor_profile: old_config.commit_message_executor_profile,
+            commit_message_template_id: None,
+            max_concurrent_agents: old_config.max_concurrent_agents,
+            preempt_lower_priority: false,
+            max_inline_diff_kb: None,
+            langfuse_enabled: old_config.langfuse_enabled,
+            langfuse_public_key: old_config.langfuse_public_key,
+            langfuse_secret_key: migrate_plaintext(
+                secrets,
+                LANGFUSE_SECRET_KEY,
+                old_config.langfuse_secret_key,
+            ),
+            langfuse_host: old_config.langfuse_host,
+            backup: BackupConfig::from_v16(old_config.backup, secrets),
+            review_attention_executor_profile: old_config.review_attention_executor_profile,
+            review_attention_prompt: old_config.review_attention_prompt,
+            review_attention_template_id: None,
+            feedback_template_id: None,
+            autopilot_enabled: old_config.autopilot_enabled,
+            retry_policy: old_config.retry_policy.into(),
+            retry_policy_overrides: HashMap::new(),
+            gitlab: GitLabConfig::from_v16(old_config.gitlab, secrets),
+            bitbucket: BitbucketConfig::default(),
+            gitea: GiteaConfig::default(),
+            linear: LinearConfig::default(),
+            anomaly_detection: old_config.anomaly_detection.into(),
+            usage_pricing: old_config.usage_pricing,
+            provider_rate_limits: old_config.provider_rate_limits,
+            trash_retention_days: default_trash_retention_days(),
+            ssh_remote: SshRemoteConfig::default(),
+            output_redaction: OutputRedactionConfig::default(),
+        }
+    }
+
+    pub fn from_previous_version(raw_config: &str) -> Result<Self, Error> {
+        let old_config = v16::Config::from(raw_config.to_string());
+        let secrets = SecretService::new();
+        Ok(Self::from_v16_config(old_config, secrets.store()))
+    }
+}
+
+impl From<String> for Config {
+    fn from(raw_config: String) -> Self {
+        if let Ok(config) = serde_json::from_str::<Config>(&raw_config)
+            && config.config_version == "v17"
+        {
+            return config;
+        }
+
+        match Self::from_previous_version(&raw_config) {
+            Ok(config) => {
+                tracing::info!("Config upgraded to v17");
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Config migration failed: {}, using default", e);
+                Self::default()
+            }
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            config_version: "v17".to_string(),
+            theme: ThemeMode::System,
+            executor_profile: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            disclaimer_acknowledged: false,
+            onboarding_acknowledged: false,
+            notifications: NotificationConfig::default(),
+            editor: EditorConfig::default(),
+            github: GitHubConfig::default(),
+            analytics_enabled: true,
+            workspace_dir: None,
+            last_app_version: None,
+            show_release_notes: false,
+            language: UiLanguage::default(),
+            git_branch_prefix: default_git_branch_prefix(),
+            showcases: ShowcaseState::default(),
+            pr_auto_description_enabled: true,
+            pr_auto_description_prompt: None,
+            default_clone_directory: None,
+            commit_message_auto_generate_enabled: true,
+            commit_message_prompt: None,
+            commit_message_executor_profile: None,
+            commit_message_template_id: None,
+            max_concurrent_agents: 0,
+            preempt_lower_priority: false,
+            max_inline_diff_kb: None,
+            langfuse_enabled: false,
+            langfuse_public_key: None,
+            langfuse_secret_key: None,
+            langfuse_host: default_langfuse_host(),
+            backup: BackupConfig::default(),
+            review_attention_executor_profile: None,
+            review_attention_prompt: None,
+            review_attention_template_id: None,
+            feedback_template_id: None,
+            autopilot_enabled: false,
+            retry_policy: RetryPolicyConfig::default(),
+            retry_policy_overrides: HashMap::new(),
+            gitlab: GitLabConfig::default(),
+            bitbucket: BitbucketConfig::default(),
+            gitea: GiteaConfig::default(),
+            linear: LinearConfig::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            usage_pricing: UsagePricingConfig::default(),
+            provider_rate_limits: HashMap::new(),
+            trash_retention_days: default_trash_retention_days(),
+            ssh_remote: SshRemoteConfig::default(),
+            output_redaction: OutputRedactionConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::secrets::MemoryStore;
+
+    #[test]
+    fn test_v16_to_v17_moves_langfuse_secret_into_store() {
+        let v16_config = v16::Config {
+            langfuse_enabled: true,
+            langfuse_public_key: Some("pk-lf-1".to_string()),
+            langfuse_secret_key: Some("sk-lf-1".to_string()),
+            ..v16::Config::default()
+        };
+        let store = MemoryStore::default();
+
+        let v17_config = Config::from_v16_config(v16_config, &store);
+
+        assert_eq!(v17_config.config_version, "v17");
+        assert_eq!(
+            v17_config.langfuse_secret_key,
+            Some(SecretRef::new(LANGFUSE_SECRET_KEY))
+        );
+        assert_eq!(
+            store.get(LANGFUSE_SECRET_KEY).unwrap().as_deref(),
+            Some("sk-lf-1")
+        );
+        assert_eq!(v17_config.langfuse_public_key.as_deref(), Some("pk-lf-1"));
+
+        let raw = serde_json::to_string(&v17_config).unwrap();
+        assert!(!raw.contains("sk-lf-1"));
+    }
+
+    #[test]
+    fn test_v16_to_v17_moves_integration_secrets_into_store() {
+        let mut v16_config = v16::Config::default();
+        v16_config.gitlab.token = Some("glpat-1".to_string());
+        v16_config.notifications.slack.webhook_url =
+            Some("https://hooks.slack.com/services/T/B/X".to_string());
+        v16_config.notifications.slack.merge_failed = false;
+        v16_config.backup.s3 = Some(v16::S3BackupConfig {
+            endpoint: None,
+            region: "us-east-1".to_string(),
+            bucket: "backups".to_string(),
+            prefix: String::new(),
+            access_key_id: "AKIA1".to_string(),
+            secret_access_key: "s3-secret-1".to_string(),
+            path_style: false,
+        });
+        let store = MemoryStore::default();
+
+        let v17_config = Config::from_v16_config(v16_config, &store);
+
+        assert_eq!(v17_config.gitlab.token, Some(SecretRef::new(GITLAB_TOKEN)));
+        assert_eq!(
+            v17_config.notifications.slack.webhook_url,
+            Some(SecretRef::new(SLACK_WEBHOOK_URL))
+        );
+        assert!(!v17_config.notifications.slack.merge_failed);
+        let s3 = v17_config.backup.s3.as_ref().unwrap();
+        assert_eq!(s3.access_key_id, "AKIA1");
+        assert_eq!(
+            s3.secret_access_key,
+            Some(SecretRef::new(S3_SECRET_ACCESS_KEY))
+        );
+        assert_eq!(store.get(GITLAB_TOKEN).unwrap().as_deref(), Some("glpat-1"));
+        assert_eq!(
+            store.get(S3_SECRET_ACCESS_KEY).unwrap().as_deref(),
+            Some("s3-secret-1")
+        );
+
+        let raw = serde_json::to_string(&v17_config).unwrap();
+        assert!(!raw.contains("glpat-1"));
+        assert!(!raw.contains("s3-secret-1"));
+        assert!(!raw.contains("hooks.slack.com"));
+    }
+
+    #[test]
+    fn test_v16_to_v17_without_secret_stores_nothing() {
+        let store = MemoryStore::default();
+
+        let v17_config = Config::from_v16_config(v16::Config::default(), &store);
+
+        assert!(v17_config.langfuse_secret_key.is_none());
+        assert!(store.list().unwrap().is_empty());
+    }
+}
@@ -235,7 +235,7 @@ impl GitHubConfig {
 #[derive(
     Debug, Clone, PartialEq, Serialize, Deserialize, TS, EnumString, strum_macros::EnumIter,
 )]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum SoundFile {
@@ -351,7 +351,7 @@ impl From<v1::SoundFile> for SoundFile {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, EnumString)]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum ThemeMode {
@@ -18,7 +18,7 @@ pub struct ShowcaseState {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS, EnumString)]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum ThemeMode {
@@ -16,17 +16,20 @@ use db::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
         },
+        execution_process_failure::ExecutionProcessFailure,
         execution_process_logs::ExecutionProcessLogs,
         execution_process_normalized_entry::ExecutionProcessNormalizedEntry,
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
+        execution_process_retry::ExecutionProcessRetry,
         execution_queue::ExecutionQueue,
         project::{Project, UpdateProject},
         project_repo::{ProjectRepo, ProjectRepoWithName},
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
+        task_link::TaskLink,
         workspace::{Workspace, WorkspaceError},
         workspace_repo::WorkspaceRepo,
     },
@@ -34,12 +37,14 @@ use db::{
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{ExecutorError, StandardCodingAgentExecutor, claude::SkillsData},
     logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
     profile::{ExecutorConfigs, ExecutorProfileId},
+    sandbox::SandboxError,
 };
 use futures::{StreamExt, future};
 use sqlx::{Error as SqlxError, SqlitePool};
@@ -53,18 +58,35 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    agent_auth::AgentAuthMonitor,
     config::Config,
+    conversation::ConversationService,
+    copy_files::{CopyFilePreview, CopyTemplateVars},
     domain_events::DomainEvent,
+    embedding::EmbeddingService,
     git::{GitService, GitServiceError},
-    notification::NotificationService,
+    notification::{NotificationService, failure_message},
+    project_memory,
+    provider_rate_limit::ProviderRateLimiter,
+    resource_monitor::ResourceMonitor,
     share::SharePublisher,
     skills_cache::GlobalSkillsCache,
+    staged_selection::StagedSelections,
     watcher_manager::WatcherManager,
-    workspace_manager::WorkspaceError as WorkspaceManagerError,
+    workspace_manager::{
+        RepoWorktreeRepair, WorkspaceError as WorkspaceManagerError, WorkspaceManager,
+    },
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// Sent to a preempted agent when its turn is resumed
+const PREEMPTED_RESUME_PROMPT: &str = "You were paused so a higher-priority task could run. \
+     Continue where you left off.";
+
+/// How long a preempted agent may take to shut down before its resume is queued
+const PREEMPT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Result of starting a workspace execution
 #[derive(Debug)]
 pub enum StartWorkspaceResult {
@@ -201,6 +223,43 @@ async fn persist_normalized_entries_from_store(
     Ok(())
 }
 
+/// `base`, or `base` with a short random suffix when a workspace already
+/// uses it or it exists as a branch in one of `repo_paths`
+pub async fn unique_branch_name(
+    pool: &SqlitePool,
+    git: &GitService,
+    base: &str,
+    repo_paths: &[PathBuf],
+) -> Result<String, ContainerError> {
+    const MAX_ATTEMPTS: usize = 8;
+
+    let mut candidate = base.to_string();
+    for _ in 0..MAX_ATTEMPTS {
+        let mut taken = Workspace::branch_exists(pool, &candidate).await?;
+        for path in repo_paths {
+            if taken {
+                break;
+            }
+            taken = git.check_branch_exists(path, &candidate)?;
+        }
+        if !taken {
+            if candidate != base {
+                tracing::info!(
+                    "Branch '{}' is already in use; using '{}' instead",
+                    base,
+                    candidate
+                );
+            }
+            return Ok(candidate);
+        }
+        candidate = format!("{}-{}", base, short_uuid(&Uuid::new_v4()));
+    }
+
+    Err(ContainerError::Other(anyhow!(
+        "Could not find a free branch name based on '{base}'"
+    )))
+}
+
 /// Derive the default execution purpose from the run reason.
 /// Returns a static string representing the purpose of the execution.
 pub fn purpose_from_run_reason(run_reason: &ExecutionProcessRunReason) -> &'static str {
@@ -211,6 +270,7 @@ pub fn purpose_from_run_reason(run_reason: &ExecutionProcessRunReason) -> &'stat
         ExecutionProcessRunReason::DevServer => "dev_server",
         ExecutionProcessRunReason::InternalAgent => "internal",
         ExecutionProcessRunReason::DisposableConversation => "conversation",
+        ExecutionProcessRunReason::Replay => "replay",
     }
 }
 
@@ -243,6 +303,8 @@ pub enum ContainerError {
     #[error("Workspace {0} already has a running agent")]
     WorkspaceAlreadyRunning(Uuid),
     #[error(transparent)]
+    Sandbox(#[from] SandboxError),
+    #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
@@ -268,6 +330,20 @@ pub trait ContainerService {
     /// Get the global skills cache for storing Claude Code skills data.
     fn skills_cache(&self) -> &GlobalSkillsCache;
 
+    /// Get the CPU/RSS sampler for running executions' process trees.
+    fn resource_monitor(&self) -> &ResourceMonitor;
+
+    /// Get the last known login state of each coding agent's CLI.
+    fn agent_auth(&self) -> &AgentAuthMonitor;
+
+    fn provider_rate_limiter(&self) -> &ProviderRateLimiter;
+
+    /// Get the repos whose next auto-commit only includes what the user staged.
+    fn staged_selections(&self) -> &StagedSelections;
+
+    /// Get the embedding model, used to find project memories relevant to a prompt.
+    fn embedding(&self) -> &EmbeddingService;
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf;
 
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError>;
@@ -281,10 +357,68 @@ pub trait ContainerService {
         Ok(ExecutionProcess::has_running_processes_for_task(&self.db().pool, task_id).await?)
     }
 
-    /// Check if execution should be queued based on concurrency limit.
-    /// Returns true if running agents >= max_concurrent_agents (and limit is enabled).
+    /// Check if execution should be queued based on concurrency limits.
+    /// Returns true if running agents >= max_concurrent_agents (and limit is enabled),
+    /// if the workspace's project already runs its max_concurrent_executions,
+    /// or if the executor's provider rate limit is reached (the workspace is then
+    /// shown as waiting for it). Returning false reserves a start under the
+    /// provider rate limit, so callers must start the execution right away.
+    async fn should_queue_execution(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<bool, ContainerError> {
+        if self.at_agent_capacity().await? || self.at_project_capacity(workspace).await? {
+            return Ok(true);
+        }
+
+        let provider = executor_profile_id.executor.to_string();
+        if self.blocked_providers().await?.contains(&provider) {
+            self.provider_rate_limiter().mark_waiting(workspace).await;
+            return Ok(true);
+        }
+        self.provider_rate_limiter().record_start(&provider);
+        Ok(false)
+    }
+
+    /// Check whether the workspace's project runs its max_concurrent_executions
+    async fn at_project_capacity(&self, workspace: &Workspace) -> Result<bool, ContainerError> {
+        let pool = &self.db().pool;
+        let Some(task) = workspace.parent_task(pool).await? else {
+            return Ok(false);
+        };
+        let Some(limit) = Project::find_by_id(pool, task.project_id)
+            .await?
+            .and_then(|project| project.max_concurrent_executions)
+        else {
+            return Ok(false);
+        };
+        let running_count =
+            ExecutionProcess::count_running_agents_for_project(pool, task.project_id).await?;
+        Ok(running_count >= limit)
+    }
+
+    /// Providers (executors such as `CLAUDE_CODE`) whose configured rate limit
+    /// currently allows no further start
+    async fn blocked_providers(&self) -> Result<Vec<String>, ContainerError> {
+        let limits = self.config().read().await.provider_rate_limits.clone();
+        let mut blocked = Vec::new();
+        for (provider, limit) in limits {
+            let running =
+                ExecutionProcess::count_running_for_executor(&self.db().pool, &provider).await?;
+            if !self
+                .provider_rate_limiter()
+                .allows_start(&provider, &limit, running)
+            {
+                blocked.push(provider);
+            }
+        }
+        Ok(blocked)
+    }
+
+    /// Check the global concurrency limit alone.
     /// Returns false if max_concurrent_agents == 0 (unlimited).
-    async fn should_queue_execution(&self) -> Result<bool, ContainerError> {
+    async fn at_agent_capacity(&self) -> Result<bool, ContainerError> {
         let max_concurrent = self.config().read().await.max_concurrent_agents;
 
         // 0 means unlimited - never queue
@@ -296,20 +430,96 @@ pub trait ContainerService {
         Ok(running_count >= max_concurrent as i64)
     }
 
+    /// Interrupt the running coding agent of the lowest-priority task when a
+    /// queued task that could start outranks it, and queue the agent to
+    /// resume in the same session. Returns whether an agent was preempted,
+    /// freeing its slot.
+    async fn preempt_lower_priority(&self) -> Result<bool, ContainerError> {
+        let pool = &self.db().pool;
+        let blocked = self.blocked_providers().await?;
+        let Some(priority) = ExecutionQueue::find_highest_priority(pool, &blocked).await? else {
+            return Ok(false);
+        };
+        let Some(process) =
+            ExecutionProcess::find_running_agent_below_priority(pool, priority).await?
+        else {
+            return Ok(false);
+        };
+        let ctx = ExecutionProcess::load_context(pool, process.id).await?;
+        let action = process.executor_action()?;
+        let (executor_profile_id, working_dir) = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => (
+                request.executor_profile_id.clone(),
+                request.working_dir.clone(),
+            ),
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => (
+                request.executor_profile_id.clone(),
+                request.working_dir.clone(),
+            ),
+            ExecutorActionType::ScriptRequest(_) => return Ok(false),
+        };
+        // Without an agent session to resume, the turn is started over
+        let agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, ctx.session.id)
+                .await?;
+        let resume_typ = match agent_session_id {
+            Some(agent_session_id) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt: PREEMPTED_RESUME_PROMPT.to_string(),
+                    session_id: agent_session_id,
+                    executor_profile_id,
+                    working_dir,
+                })
+            }
+            None => action.typ().clone(),
+        };
+        let resume = ExecutorAction::new(resume_typ, action.next_action.clone());
+
+        tracing::info!(
+            "Preempting execution {} of '{}' for a queued {:?} priority task",
+            process.id,
+            ctx.task.title,
+            priority
+        );
+        self.stop_execution(&process, ExecutionProcessStatus::Killed)
+            .await?;
+        // Queued before the exit handling finishes, the resume could be
+        // popped while the workspace is still held
+        if !self
+            .wait_for_workspace_idle(ctx.workspace.id, PREEMPT_WAIT_TIMEOUT)
+            .await
+        {
+            tracing::warn!(
+                "Preempted agent of workspace {} is still shutting down",
+                ctx.workspace.id
+            );
+        }
+        ExecutionQueue::create_follow_up(pool, ctx.workspace.id, ctx.session.id, &resume).await?;
+        Ok(true)
+    }
+
     /// Process the execution queue - start queued workspaces/follow-ups when slots are available.
     /// Pops entries from the queue and starts execution until at capacity or queue empty.
+    /// Entries of projects at their own limit, or of providers at their rate limit, stay
+    /// queued (see `ExecutionQueue::pop_next`).
     /// Handles both initial workspace starts (session_id is None) and follow-up executions
     /// (session_id and executor_action are populated).
     async fn process_queue(&self) -> Result<(), ContainerError> {
         loop {
             // Check if we can start more executions
-            if self.should_queue_execution().await? {
+            if self.at_agent_capacity().await? {
+                if self.config().read().await.preempt_lower_priority
+                    && self.preempt_lower_priority().await?
+                {
+                    continue;
+                }
                 // At capacity, stop processing
                 break;
             }
 
             // Try to pop next from queue
-            let entry = match ExecutionQueue::pop_next(&self.db().pool).await {
+            let blocked = self.blocked_providers().await?;
+            let entry = match ExecutionQueue::pop_next(&self.db().pool, &blocked).await {
                 Ok(Some(e)) => e,
                 Ok(None) => {
                     // Queue is empty
@@ -342,6 +552,11 @@ pub trait ContainerService {
             };
 
             // Note: is_queued is updated automatically via database trigger on execution_queue DELETE
+            self.provider_rate_limiter()
+                .record_start(&entry.executor_profile_id.executor.to_string());
+            self.provider_rate_limiter()
+                .clear_waiting(workspace.id)
+                .await;
 
             // Check if this is a follow-up or initial start
             if entry.is_follow_up() {
@@ -380,21 +595,33 @@ pub trait ContainerService {
                 );
 
                 // Start the follow-up execution directly
-                if let Err(e) = self
-                    .start_execution(
-                        &workspace,
-                        &session,
-                        &executor_action,
-                        &ExecutionProcessRunReason::CodingAgent,
-                        None,
-                    )
+                let run_reason = entry
+                    .run_reason
+                    .clone()
+                    .unwrap_or(ExecutionProcessRunReason::CodingAgent);
+                match self
+                    .start_execution(&workspace, &session, &executor_action, &run_reason, None)
                     .await
                 {
-                    tracing::error!(
-                        "Failed to start queued follow-up for workspace {}: {}",
-                        workspace.id,
-                        e
-                    );
+                    Ok(process) => {
+                        if let Some(retry_id) = entry.retry_id
+                            && let Err(e) = ExecutionProcessRetry::set_retry_execution_process(
+                                &self.db().pool,
+                                retry_id,
+                                process.id,
+                            )
+                            .await
+                        {
+                            tracing::error!("Failed to link retry {}: {}", retry_id, e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to start queued follow-up for workspace {}: {}",
+                            workspace.id,
+                            e
+                        );
+                    }
                 }
             } else {
                 // Initial workspace start
@@ -415,6 +642,26 @@ pub trait ContainerService {
             }
         }
 
+        self.sync_rate_limit_waiting().await
+    }
+
+    /// Show queued workspaces held back by a provider rate limit as waiting,
+    /// and clear the status of those that are no longer held back
+    async fn sync_rate_limit_waiting(&self) -> Result<(), ContainerError> {
+        let blocked = self.blocked_providers().await?;
+        let limiter = self.provider_rate_limiter();
+        for entry in ExecutionQueue::find_all(&self.db().pool).await? {
+            let Some(workspace) =
+                Workspace::find_by_id(&self.db().pool, entry.workspace_id).await?
+            else {
+                continue;
+            };
+            if blocked.contains(&entry.executor_profile_id.executor.to_string()) {
+                limiter.mark_waiting(&workspace).await;
+            } else {
+                limiter.clear_waiting(workspace.id).await;
+            }
+        }
         Ok(())
     }
 
@@ -464,15 +711,18 @@ pub trait ContainerService {
     /// - Always when the execution process has failed or been killed
     /// - Never when the run reason is DevServer
     /// - Never when the run reason is InternalAgent (feedback, pr_description, merge_message)
+    /// - Never when the run reason is Replay
     /// - Never when a setup script has no next_action (parallel mode)
     /// - The next action is None (no follow-up actions)
     fn should_finalize(&self, ctx: &ExecutionContext) -> bool {
-        // Never finalize DevServer or InternalAgent processes
+        // Never finalize DevServer, InternalAgent or Replay processes
         // InternalAgent is used for internal operations (feedback collection, PR descriptions, etc.)
-        // that should not affect task status
+        // and Replay reproduces past executions; neither should affect task status
         if matches!(
             ctx.execution_process.run_reason,
-            ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::InternalAgent
+            ExecutionProcessRunReason::DevServer
+                | ExecutionProcessRunReason::InternalAgent
+                | ExecutionProcessRunReason::Replay
         ) {
             return false;
         }
@@ -551,9 +801,18 @@ pub trait ContainerService {
                 }
             }
             ExecutionProcessStatus::Failed => {
+                let failure = ExecutionProcessFailure::find_by_execution_process_id(
+                    &self.db().pool,
+                    ctx.execution_process.id,
+                )
+                .await
+                .ok()
+                .flatten();
                 let message = format!(
-                    "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
-                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                    "❌ {}\nBranch: {:?}\nExecutor: {:?}",
+                    failure_message(&ctx.task.title, failure.as_ref()),
+                    ctx.workspace.branch,
+                    ctx.session.executor
                 );
                 // OS notification
                 self.notification_service()
@@ -563,8 +822,10 @@ pub trait ContainerService {
                 if let Err(e) = NotificationService::notify_agent_error(
                     &self.db().pool,
                     ctx.project.id,
+                    &ctx.project.name,
                     ctx.workspace.id,
                     &ctx.task.title,
+                    failure.as_ref(),
                 )
                 .await
                 {
@@ -800,6 +1061,13 @@ pub trait ContainerService {
                                 } else {
                                     project.default_agent_working_dir.clone()
                                 },
+                                script_shell: project.script_shell,
+                                inherit_login_env: None,
+                                dev_shell: project.dev_shell,
+                                dev_shell_command: project.dev_shell_command.clone(),
+                                container_image: project.container_image.clone(),
+                                container_network: project.container_network.clone(),
+                                max_concurrent_executions: project.max_concurrent_executions,
                             },
                         )
                         .await?;
@@ -957,6 +1225,29 @@ pub trait ContainerService {
         workspace: &Workspace,
     ) -> Result<ContainerRef, ContainerError>;
 
+    /// Check the workspace's worktrees for breakage and repair them, then make
+    /// sure the container is complete. Lock files are removed regardless of
+    /// age, so callers must make sure nothing is running in the workspace.
+    async fn repair_container(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<Vec<RepoWorktreeRepair>, ContainerError> {
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
+        let repairs = match &workspace.container_ref {
+            Some(container_ref) => {
+                WorkspaceManager::repair_workspace(
+                    Path::new(container_ref),
+                    &repos,
+                    &workspace.branch,
+                )
+                .await?
+            }
+            None => Vec::new(),
+        };
+        self.ensure_container_exists(workspace).await?;
+        Ok(repairs)
+    }
+
     async fn is_container_clean(&self, workspace: &Workspace) -> Result<bool, ContainerError>;
 
     async fn start_execution_inner(
@@ -973,20 +1264,38 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Wait until no coding agent holds the workspace, i.e. the exit handling
+    /// of a stopped agent has finished. Returns `false` on timeout.
+    async fn wait_for_workspace_idle(&self, workspace_id: Uuid, timeout: Duration) -> bool;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
+    /// Copy the configured project files into a worktree, rendering `.template`
+    /// files with `template_vars`. Returns the number of entries copied.
     async fn copy_project_files(
         &self,
         source_dir: &Path,
         target_dir: &Path,
         copy_files: &str,
-    ) -> Result<(), ContainerError>;
+        template_vars: &CopyTemplateVars,
+    ) -> Result<usize, ContainerError>;
+
+    /// Resolve what `copy_project_files` would copy without writing anything.
+    /// When `target_dir` is `None`, no target is considered to exist yet.
+    async fn preview_project_files(
+        &self,
+        source_dir: &Path,
+        target_dir: Option<&Path>,
+        copy_files: &str,
+    ) -> Result<Vec<CopyFilePreview>, ContainerError>;
 
     /// Stream diff updates as LogMsg for WebSocket endpoints.
+    /// `max_inline_bytes` overrides the configured per-file content limit.
     async fn stream_diff(
         &self,
         workspace: &Workspace,
         stats_only: bool,
+        max_inline_bytes: Option<usize>,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
@@ -1008,6 +1317,30 @@ pub trait ContainerService {
         }
     }
 
+    /// Branch name for a new workspace that no other workspace uses and that
+    /// does not already exist in any of `repo_ids`. On a collision a short
+    /// random suffix is appended, so creation does not fail later when the
+    /// worktree is set up.
+    async fn unique_git_branch_for_workspace(
+        &self,
+        workspace_id: &Uuid,
+        task_title: &str,
+        repo_ids: &[Uuid],
+    ) -> Result<String, ContainerError> {
+        let pool = &self.db().pool;
+        let mut repo_paths = Vec::with_capacity(repo_ids.len());
+        for repo_id in repo_ids {
+            if let Some(repo) = Repo::find_by_id(pool, *repo_id).await? {
+                repo_paths.push(repo.path);
+            }
+        }
+
+        let base = self
+            .git_branch_from_workspace(workspace_id, task_title)
+            .await;
+        unique_branch_name(pool, self.git(), &base, &repo_paths).await
+    }
+
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
@@ -1348,7 +1681,10 @@ pub trait ContainerService {
         executor_profile_id: ExecutorProfileId,
     ) -> Result<StartWorkspaceResult, ContainerError> {
         // Check if we should queue this execution
-        if self.should_queue_execution().await? {
+        if self
+            .should_queue_execution(workspace, &executor_profile_id)
+            .await?
+        {
             tracing::info!(
                 "At concurrency limit, queueing workspace {} for execution",
                 workspace.id
@@ -1406,7 +1742,14 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
+        let links = TaskLink::find_by_task_id(&self.db().pool, task.id).await?;
+        let prompt = match TaskLink::prompt_section(&links) {
+            Some(section) => format!("{}\n\n{section}", task.to_prompt()),
+            None => task.to_prompt(),
+        };
+        let prompt =
+            project_memory::with_memories(&self.db().pool, self.embedding(), project.id, prompt)
+                .await;
 
         let repos_with_setup: Vec<_> = project_repos
             .iter()
@@ -1485,7 +1828,7 @@ pub trait ContainerService {
         let effective_purpose = purpose.unwrap_or_else(|| purpose_from_run_reason(run_reason));
 
         // Update task status to InProgress when starting an execution
-        // Skip for DevServer and InternalAgent (internal operations should not affect task status)
+        // Skip for DevServer, InternalAgent and Replay (these should not affect task status)
         let task = workspace
             .parent_task(&self.db().pool)
             .await?
@@ -1493,7 +1836,9 @@ pub trait ContainerService {
         if task.status != TaskStatus::InProgress
             && !matches!(
                 run_reason,
-                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::InternalAgent
+                ExecutionProcessRunReason::DevServer
+                    | ExecutionProcessRunReason::InternalAgent
+                    | ExecutionProcessRunReason::Replay
             )
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
@@ -1732,6 +2077,13 @@ pub trait ContainerService {
     ) -> Result<ExecutionProcess, ContainerError> {
         use db::models::execution_process::CreateConversationExecutionProcess;
 
+        // Recreate the conversation's own worktrees if they expired while it was idle
+        ConversationService::attach_worktree(&self.db().pool, conversation_session)
+            .await
+            .map_err(|e| {
+                ContainerError::Other(anyhow!("Failed to restore conversation worktree: {}", e))
+            })?;
+
         // Determine working directory: use worktree_path if specified, otherwise use project's main repository
         let working_dir = if let Some(worktree_path) = &conversation_session.worktree_path {
             let path = PathBuf::from(worktree_path);
@@ -1892,7 +2244,9 @@ pub trait ContainerService {
     ///
     /// Default implementation returns None; implementors should override to provide
     /// a callback that dispatches to the domain event dispatcher.
-    fn event_dispatch_callback(&self) -> Option<crate::services::domain_events::EventDispatchCallback> {
+    fn event_dispatch_callback(
+        &self,
+    ) -> Option<crate::services::domain_events::EventDispatchCallback> {
         None
     }
 }
@@ -1,19 +1,31 @@
+use std::path::{Path, PathBuf};
+
 use db::models::{
+    coding_agent_turn::CodingAgentTurn,
     conversation_message::{
         ConversationMessage, ConversationMessageError, CreateConversationMessage, MessageRole,
     },
     conversation_session::{
         ConversationSession, ConversationSessionError, CreateConversationSession,
     },
+    conversation_worktree::ConversationWorktree,
     execution_process::{ExecutionProcess, ExecutionProcessError},
+    project_repo::ProjectRepo,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use thiserror::Error;
 use ts_rs::TS;
+use utils::text::{git_branch_id, short_uuid};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+use super::{
+    git::GitService,
+    workspace_manager::{RepoWorkspaceInput, WorkspaceError, WorkspaceManager},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ConversationWithMessages {
     #[serde(flatten)]
     pub session: ConversationSession,
@@ -21,7 +33,7 @@ pub struct ConversationWithMessages {
 }
 
 /// Response when sending a message that starts execution
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct SendMessageResponse {
     pub user_message: ConversationMessage,
     pub execution_process_id: Uuid,
@@ -39,6 +51,24 @@ pub enum ConversationServiceError {
     Sqlx(#[from] sqlx::Error),
     #[error("Conversation not found")]
     NotFound,
+    #[error("Cannot fork here: {0}")]
+    InvalidForkPoint(String),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+    #[error("Project has no repositories to create a worktree from")]
+    NoRepositories,
+}
+
+/// Worktrees created for a new conversation
+#[derive(Debug, Clone)]
+pub struct NewConversationWorktree {
+    pub container_ref: PathBuf,
+    pub branch: String,
+    /// Where the agent starts: the repo's worktree when the project has one
+    /// repo, otherwise the container holding all of them
+    pub working_dir: PathBuf,
 }
 
 pub struct ConversationService;
@@ -140,7 +170,8 @@ impl ConversationService {
         Ok(message)
     }
 
-    /// Get the latest agent session ID for continuing conversation
+    /// Get the latest agent session ID for continuing conversation. A fork
+    /// that has not run yet continues the agent session it was forked at.
     pub async fn get_latest_agent_session_id(
         pool: &SqlitePool,
         conversation_session_id: Uuid,
@@ -150,6 +181,208 @@ impl ConversationService {
             conversation_session_id,
         )
         .await?;
-        Ok(agent_session_id)
+        if agent_session_id.is_some() {
+            return Ok(agent_session_id);
+        }
+
+        let has_run =
+            !ExecutionProcess::find_by_conversation_session_id(pool, conversation_session_id, true)
+                .await?
+                .is_empty();
+        if has_run {
+            return Ok(None);
+        }
+        let session = ConversationSession::find_by_id(pool, conversation_session_id)
+            .await?
+            .ok_or(ConversationServiceError::NotFound)?;
+        Ok(session.fork_agent_session_id)
+    }
+
+    /// Forks `source` at the agent reply `message_id` into a new conversation.
+    ///
+    /// The fork gets copies of the messages up to and including that reply,
+    /// and its next turn resumes the agent session the reply came from, so
+    /// the agent remembers nothing said after it in the original.
+    pub async fn fork_conversation(
+        pool: &SqlitePool,
+        source: &ConversationSession,
+        message_id: Uuid,
+        title: Option<String>,
+    ) -> Result<ConversationSession, ConversationServiceError> {
+        let mut messages =
+            ConversationMessage::find_by_conversation_session_id(pool, source.id).await?;
+        let position = messages
+            .iter()
+            .position(|message| message.id == message_id)
+            .ok_or(ConversationMessageError::NotFound)?;
+        if messages[position].role != MessageRole::Assistant {
+            return Err(ConversationServiceError::InvalidForkPoint(
+                "conversations can only be forked at an agent reply".to_string(),
+            ));
+        }
+        messages.truncate(position + 1);
+
+        let agent_session_id = match messages[position].execution_process_id {
+            Some(execution_process_id) => {
+                CodingAgentTurn::find_by_execution_process_id(pool, execution_process_id)
+                    .await?
+                    .filter(|turn| !turn.agent_session_expired)
+                    .and_then(|turn| turn.agent_session_id)
+            }
+            None => None,
+        };
+        if agent_session_id.is_none() {
+            tracing::info!(
+                conversation_id = %source.id,
+                %message_id,
+                "No resumable agent session at fork point; the fork starts a new one"
+            );
+        }
+
+        let title = title
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| format!("{} (fork)", source.title));
+        let fork =
+            ConversationSession::create_fork(pool, source, title, &messages, agent_session_id)
+                .await?;
+
+        // The fork keeps working in the original's worktrees
+        if let Some(worktree) =
+            ConversationWorktree::find_by_conversation_session_id(pool, source.id).await?
+        {
+            ConversationWorktree::create(pool, fork.id, &worktree.container_ref, &worktree.branch)
+                .await?;
+        }
+        Ok(fork)
+    }
+
+    /// Create worktrees of every project repo for a new conversation, laid out
+    /// like a task workspace. They are checked out on `branch`, or on one
+    /// named after the title, off each repo's current branch.
+    pub async fn create_worktree(
+        pool: &SqlitePool,
+        git: &GitService,
+        project_id: Uuid,
+        title: &str,
+        branch_prefix: &str,
+        branch: Option<String>,
+    ) -> Result<NewConversationWorktree, ConversationServiceError> {
+        let repos = ProjectRepo::find_repos_for_project(pool, project_id).await?;
+        if repos.is_empty() {
+            return Err(ConversationServiceError::NoRepositories);
+        }
+
+        let dir_name = format!(
+            "conv-{}-{}",
+            short_uuid(&Uuid::new_v4()),
+            git_branch_id(title)
+        );
+        let branch = match branch.filter(|branch| !branch.trim().is_empty()) {
+            Some(branch) => branch,
+            None if branch_prefix.is_empty() => dir_name.clone(),
+            None => format!("{}/{}", branch_prefix, dir_name),
+        };
+
+        let mut inputs = Vec::with_capacity(repos.len());
+        for repo in repos {
+            let target_branch = git.get_current_branch(&repo.path)?;
+            inputs.push(RepoWorkspaceInput::new(repo, target_branch));
+        }
+
+        let container_ref = WorkspaceManager::get_workspace_base_dir().join(dir_name);
+        let created = WorkspaceManager::create_workspace(&container_ref, &inputs, &branch).await?;
+        let working_dir = match created.worktrees.as_slice() {
+            [only] => only.worktree_path.clone(),
+            _ => created.workspace_dir.clone(),
+        };
+
+        Ok(NewConversationWorktree {
+            container_ref: created.workspace_dir,
+            branch,
+            working_dir,
+        })
+    }
+
+    /// Recreate a conversation's worktrees if they were removed while it was
+    /// idle, and record that they are in use. Conversations without
+    /// worktrees of their own are left alone.
+    pub async fn attach_worktree(
+        pool: &SqlitePool,
+        session: &ConversationSession,
+    ) -> Result<(), ConversationServiceError> {
+        let Some(worktree) =
+            ConversationWorktree::find_by_conversation_session_id(pool, session.id).await?
+        else {
+            return Ok(());
+        };
+
+        let repos = ProjectRepo::find_repos_for_project(pool, session.project_id).await?;
+        if worktree.released_at.is_some() {
+            tracing::info!(
+                conversation_id = %session.id,
+                "Recreating worktrees at {}",
+                worktree.container_ref
+            );
+        }
+        WorkspaceManager::ensure_workspace_exists(
+            Path::new(&worktree.container_ref),
+            &repos,
+            &worktree.branch,
+        )
+        .await?;
+        ConversationWorktree::touch(pool, &worktree.container_ref).await?;
+        Ok(())
+    }
+
+    /// Remove a conversation's worktrees before it is deleted, unless one of
+    /// its forks still uses them. Their branch is kept.
+    pub async fn release_worktree(
+        pool: &SqlitePool,
+        session: &ConversationSession,
+    ) -> Result<(), ConversationServiceError> {
+        let Some(worktree) =
+            ConversationWorktree::find_by_conversation_session_id(pool, session.id).await?
+        else {
+            return Ok(());
+        };
+        if ConversationWorktree::count_by_container_ref(pool, &worktree.container_ref).await? > 1 {
+            return Ok(());
+        }
+
+        let repos = ProjectRepo::find_repos_for_project(pool, session.project_id).await?;
+        WorkspaceManager::cleanup_workspace(Path::new(&worktree.container_ref), &repos).await?;
+        Ok(())
+    }
+
+    /// Remove the worktrees of conversations that have been idle as long as an
+    /// expired task workspace. Resuming one of them recreates its worktrees.
+    pub async fn cleanup_expired_worktrees(
+        pool: &SqlitePool,
+    ) -> Result<usize, ConversationServiceError> {
+        let expired = ConversationWorktree::find_expired_for_cleanup(pool).await?;
+        let mut released = 0;
+        for worktree in &expired {
+            let Some(session) =
+                ConversationSession::find_by_id(pool, worktree.conversation_session_id).await?
+            else {
+                continue;
+            };
+            let repos = ProjectRepo::find_repos_for_project(pool, session.project_id).await?;
+            if let Err(e) =
+                WorkspaceManager::cleanup_workspace(Path::new(&worktree.container_ref), &repos)
+                    .await
+            {
+                tracing::warn!(
+                    "Failed to clean up conversation worktrees at {}: {}",
+                    worktree.container_ref,
+                    e
+                );
+                continue;
+            }
+            ConversationWorktree::mark_released(pool, &worktree.container_ref).await?;
+            released += 1;
+        }
+        Ok(released)
     }
 }
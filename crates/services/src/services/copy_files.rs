@@ -0,0 +1,130 @@
+//! Shared types for the project copy-files engine.
+//!
+//! The engine itself lives in the deployment crate; these types describe what it
+//! copies (for previews) and the values it renders into template files.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// File name suffix that marks a copied file as a template.
+/// `.env.template` is rendered and written to the worktree as `.env`.
+pub const TEMPLATE_SUFFIX: &str = ".template";
+
+/// How a copy-files entry is materialized in the worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFileKind {
+    File,
+    Symlink,
+    Template,
+}
+
+/// A single entry the copy-files engine would copy into a worktree
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct CopyFilePreview {
+    /// Path relative to the source repository
+    pub source: String,
+    /// Path relative to the worktree
+    pub target: String,
+    pub kind: CopyFileKind,
+    /// True if the target already exists and would be left untouched
+    pub already_exists: bool,
+}
+
+/// Values substituted into `{{name}}` placeholders when rendering template files.
+/// Unknown placeholders are left untouched.
+#[derive(Debug, Clone, Default)]
+pub struct CopyTemplateVars {
+    vars: BTreeMap<String, String>,
+}
+
+impl CopyTemplateVars {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.vars.get(key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Environment variables exposed to post-copy hooks (`{{port}}` -> `VK_PORT`)
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|(k, v)| (format!("VK_{}", k.to_ascii_uppercase()), v.to_string()))
+            .collect()
+    }
+
+    /// Replace `{{ name }}` placeholders with their values
+    pub fn render(&self, content: &str) -> String {
+        let mut out = String::with_capacity(content.len());
+        let mut rest = content;
+
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after_open = &rest[start + 2..];
+            let Some(end) = after_open.find("}}") else {
+                out.push_str(&rest[start..]);
+                return out;
+            };
+
+            let key = after_open[..end].trim();
+            match self.get(key) {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&rest[start..start + 2 + end + 2]),
+            }
+            rest = &after_open[end + 2..];
+        }
+
+        out.push_str(rest);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_replaces_known_placeholders() {
+        let vars = CopyTemplateVars::new()
+            .with("workspace_branch", "vk/1234-fix")
+            .with("port", "4100");
+
+        assert_eq!(
+            vars.render("BRANCH={{workspace_branch}}\nPORT={{ port }}\n"),
+            "BRANCH=vk/1234-fix\nPORT=4100\n"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_and_unterminated_placeholders() {
+        let vars = CopyTemplateVars::new().with("port", "4100");
+
+        assert_eq!(
+            vars.render("A={{unknown}} B={{port}}"),
+            "A={{unknown}} B=4100"
+        );
+        assert_eq!(vars.render("C={{port"), "C={{port");
+    }
+
+    #[test]
+    fn test_env_vars_are_prefixed() {
+        let vars = CopyTemplateVars::new().with("workspace_branch", "main");
+        assert_eq!(
+            vars.env_vars(),
+            vec![("VK_WORKSPACE_BRANCH".to_string(), "main".to_string())]
+        );
+    }
+}
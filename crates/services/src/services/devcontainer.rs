@@ -0,0 +1,693 @@
+//! Devcontainer execution environments.
+//!
+//! Projects whose dev shell is [`DevShell::Devcontainer`] run coding agents
+//! through `docker exec` in a per-workspace container built from the
+//! workspace's `devcontainer.json`, so agents get the image the project's
+//! developers use. [`DevShell::Auto`] does the same when it finds the file
+//! and Docker is available. The worktree and each repo's git directory are mounted at
+//! their host paths, which keeps every path handed to an executor valid
+//! inside the container.
+//!
+//! Only the common subset of the spec is honoured: `image`, `build`,
+//! `containerEnv`, `remoteEnv`, `containerUser`, `remoteUser`, `runArgs` and
+//! `postCreateCommand`. Features are not installed.
+//!
+//! Projects using [`DevShell::Docker`] skip the config file: every execution,
+//! scripts included, runs in a container of the image set on the project, on
+//! the project's Docker network or the host's.
+//!
+//! Both are dev shells rather than a `ContainerService` of their own: a
+//! deployment has a single container service, while the runtime is chosen
+//! per project. Worktrees, the queue, log streaming through `MsgStore` and
+//! finalizing stay in `LocalContainerService`; only the spawned command moves
+//! into the container.
+//!
+//! [`DevShell::Devcontainer`]: executors::dev_shell::DevShell::Devcontainer
+//! [`DevShell::Docker`]: executors::dev_shell::DevShell::Docker
+//! [`DevShell::Auto`]: executors::dev_shell::DevShell::Auto
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use executors::logs::{
+    NormalizedEntry, NormalizedEntryType,
+    utils::{ConversationPatch, EntryIndexProvider},
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+};
+use utils::{msg_store::MsgStore, shell::resolve_executable_path, text::short_uuid};
+use uuid::Uuid;
+
+/// Config locations checked in each directory, in order of preference
+const CONFIG_PATHS: [&str; 2] = [".devcontainer/devcontainer.json", ".devcontainer.json"];
+
+const CONTAINER_PREFIX: &str = "vk-devcontainer-";
+const WORKSPACE_LABEL: &str = "dev.vibe-kanban.workspace";
+
+/// Keeps the container alive between executions, like the devcontainer CLI's
+/// default `overrideCommand`
+const KEEP_ALIVE: &str = "while sleep 1000; do :; done";
+
+const DOCKER_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// A hung daemon must not hold up workspace cleanup
+const DOCKER_REMOVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Network of containers run from a project's image when it sets none. Dev
+/// servers run inside too and must be reachable from the host.
+const DEFAULT_IMAGE_NETWORK: &str = "host";
+
+/// Host variables that would break the container if forwarded into it
+const HOST_ONLY_VARS: [&str; 10] = [
+    "PATH", "HOME", "USER", "LOGNAME", "SHELL", "PWD", "OLDPWD", "TMPDIR", "SHLVL", "_",
+];
+
+/// Output lines kept in the setup progress message
+const LOG_TAIL_LINES: usize = 12;
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Error)]
+pub enum DevcontainerError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid {path}: {source}")]
+    InvalidConfig {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("{0} has neither an image nor a build.dockerfile")]
+    NoImage(PathBuf),
+    #[error("docker {command} failed: {output}")]
+    Docker { command: String, output: String },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildConfig {
+    pub dockerfile: Option<String>,
+    pub context: Option<String>,
+    #[serde(default)]
+    pub args: HashMap<String, String>,
+    pub target: Option<String>,
+}
+
+/// A lifecycle command: a shell string, an argv array, or named commands
+/// that the spec runs in parallel and we run one after another
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LifecycleCommand {
+    Shell(String),
+    Exec(Vec<String>),
+    Named(BTreeMap<String, LifecycleCommand>),
+}
+
+impl LifecycleCommand {
+    fn argvs(&self) -> Vec<Vec<String>> {
+        match self {
+            LifecycleCommand::Shell(script) => {
+                vec![vec!["/bin/sh".into(), "-c".into(), script.clone()]]
+            }
+            LifecycleCommand::Exec(argv) if argv.is_empty() => Vec::new(),
+            LifecycleCommand::Exec(argv) => vec![argv.clone()],
+            LifecycleCommand::Named(commands) => commands
+                .values()
+                .flat_map(LifecycleCommand::argvs)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevcontainerConfig {
+    pub image: Option<String>,
+    pub build: Option<BuildConfig>,
+    #[serde(default)]
+    pub container_env: HashMap<String, String>,
+    #[serde(default)]
+    pub remote_env: HashMap<String, Option<String>>,
+    pub container_user: Option<String>,
+    pub remote_user: Option<String>,
+    #[serde(default)]
+    pub run_args: Vec<String>,
+    pub post_create_command: Option<LifecycleCommand>,
+    #[serde(default)]
+    pub features: serde_json::Map<String, serde_json::Value>,
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+/// Consume a `//` or `/* */` comment starting at `c`, if there is one
+fn skip_comment(c: char, chars: &mut Chars<'_>) -> bool {
+    match (c, chars.peek()) {
+        ('/', Some('/')) => {
+            while chars.next_if(|&next| next != '\n').is_some() {}
+            true
+        }
+        ('/', Some('*')) => {
+            chars.next();
+            let mut prev = '\0';
+            for next in chars.by_ref() {
+                if prev == '*' && next == '/' {
+                    break;
+                }
+                prev = next;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Next character that is neither whitespace nor inside a comment
+fn next_significant(mut chars: Chars<'_>) -> Option<char> {
+    while let Some(c) = chars.next() {
+        if !c.is_whitespace() && !skip_comment(c, &mut chars) {
+            return Some(c);
+        }
+    }
+    None
+}
+
+/// Strip `//` and `/* */` comments and trailing commas from JSONC, leaving
+/// string contents untouched.
+pub fn strip_jsonc(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if skip_comment(c, &mut chars) {
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            // Drop the comma if only whitespace/comments precede the closing bracket
+            ',' if matches!(next_significant(chars.clone()), Some('}' | ']')) => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// A `devcontainer.json` found in a workspace
+#[derive(Debug, Clone)]
+pub struct Devcontainer {
+    pub config_path: PathBuf,
+    pub config: DevcontainerConfig,
+}
+
+impl Devcontainer {
+    /// First devcontainer config in `dirs` (workspace root, then repo worktrees)
+    pub fn find(dirs: &[PathBuf]) -> Option<PathBuf> {
+        dirs.iter()
+            .flat_map(|dir| CONFIG_PATHS.iter().map(move |p| dir.join(p)))
+            .find(|path| path.is_file())
+    }
+
+    /// A container of `image` on `network`, for projects that set an image
+    /// instead of shipping a devcontainer.json
+    pub fn from_image(image: impl Into<String>, network: Option<&str>) -> Self {
+        let network = network
+            .map(str::trim)
+            .filter(|network| !network.is_empty())
+            .unwrap_or(DEFAULT_IMAGE_NETWORK);
+        Self {
+            config_path: PathBuf::new(),
+            config: DevcontainerConfig {
+                image: Some(image.into()),
+                run_args: vec![format!("--network={network}")],
+                ..Default::default()
+            },
+        }
+    }
+
+    pub async fn load(config_path: &Path) -> Result<Self, DevcontainerError> {
+        let raw = tokio::fs::read_to_string(config_path).await?;
+        let config = serde_json::from_str(&strip_jsonc(&raw)).map_err(|source| {
+            DevcontainerError::InvalidConfig {
+                path: config_path.to_path_buf(),
+                source,
+            }
+        })?;
+        Ok(Self {
+            config_path: config_path.to_path_buf(),
+            config,
+        })
+    }
+
+    /// Directory `build.dockerfile` and `build.context` are relative to
+    fn config_dir(&self) -> &Path {
+        self.config_path.parent().unwrap_or(Path::new("."))
+    }
+
+    /// Start (or reuse) the workspace's container and return its name.
+    /// `mounts` are bind-mounted at their host paths; the first is the
+    /// container's working directory.
+    pub async fn ensure_container(
+        &self,
+        workspace_id: Uuid,
+        mounts: &[PathBuf],
+        log: &DevcontainerLog,
+    ) -> Result<String, DevcontainerError> {
+        let name = container_name(workspace_id);
+
+        match container_running(&name).await {
+            Some(true) => {
+                log.status(format!("Reusing devcontainer {name}"));
+                return Ok(name);
+            }
+            Some(false) => {
+                log.status(format!("Starting devcontainer {name}"));
+                run_docker(&["start".to_string(), name.clone()], log).await?;
+                return Ok(name);
+            }
+            None => {}
+        }
+
+        if !self.config.features.is_empty() {
+            tracing::warn!(
+                "Ignoring devcontainer features in {}; bake them into the image instead",
+                self.config_path.display()
+            );
+        }
+
+        let image = self.image(log).await?;
+
+        log.status(format!("Creating devcontainer {name} from {image}"));
+        let mut args = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--name".to_string(),
+            name.clone(),
+            "--label".to_string(),
+            format!("{WORKSPACE_LABEL}={workspace_id}"),
+        ];
+        for mount in mounts {
+            let mount = mount.to_string_lossy();
+            args.extend(["-v".to_string(), format!("{mount}:{mount}")]);
+        }
+        if let Some(workdir) = mounts.first() {
+            args.extend(["-w".to_string(), workdir.to_string_lossy().into_owned()]);
+        }
+        for (key, value) in &self.config.container_env {
+            args.extend(["-e".to_string(), format!("{key}={value}")]);
+        }
+        if let Some(user) = &self.config.container_user {
+            args.extend(["-u".to_string(), user.clone()]);
+        }
+        args.extend(self.config.run_args.iter().cloned());
+        args.extend([
+            image,
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            KEEP_ALIVE.to_string(),
+        ]);
+        run_docker(&args, log).await?;
+
+        if let Some(post_create) = &self.config.post_create_command {
+            log.status("Running postCreateCommand");
+            for argv in post_create.argvs() {
+                let mut args = vec!["exec".to_string()];
+                if let Some(user) = &self.config.remote_user {
+                    args.extend(["-u".to_string(), user.clone()]);
+                }
+                if let Some(workdir) = mounts.first() {
+                    args.extend(["-w".to_string(), workdir.to_string_lossy().into_owned()]);
+                }
+                args.push(name.clone());
+                args.extend(argv);
+                if let Err(e) = run_docker(&args, log).await {
+                    // Don't leave a half-initialised container around for reuse
+                    remove_container(workspace_id).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(name)
+    }
+
+    /// Image to run: the configured one, or one built from `build.dockerfile`
+    /// and tagged by the config's content so unchanged configs reuse it.
+    async fn image(&self, log: &DevcontainerLog) -> Result<String, DevcontainerError> {
+        let Some(dockerfile) = self
+            .config
+            .build
+            .as_ref()
+            .and_then(|b| b.dockerfile.as_ref())
+        else {
+            return self
+                .config
+                .image
+                .clone()
+                .ok_or_else(|| DevcontainerError::NoImage(self.config_path.clone()));
+        };
+        let build = self.config.build.clone().unwrap_or_default();
+        let dockerfile = self.config_dir().join(dockerfile);
+        let context = self
+            .config_dir()
+            .join(build.context.as_deref().unwrap_or("."));
+
+        let mut hasher = Sha256::new();
+        hasher.update(tokio::fs::read(&self.config_path).await?);
+        hasher.update(tokio::fs::read(&dockerfile).await?);
+        let digest = format!("{:x}", hasher.finalize());
+        let tag = format!("{CONTAINER_PREFIX}{}", &digest[..12]);
+
+        if image_exists(&tag).await {
+            log.status(format!("Reusing image {tag}"));
+            return Ok(tag);
+        }
+
+        log.status(format!("Building image {tag}"));
+        let mut args = vec![
+            "build".to_string(),
+            "-f".to_string(),
+            dockerfile.to_string_lossy().into_owned(),
+            "-t".to_string(),
+            tag.clone(),
+        ];
+        for (key, value) in &build.args {
+            args.extend(["--build-arg".to_string(), format!("{key}={value}")]);
+        }
+        if let Some(target) = &build.target {
+            args.extend(["--target".to_string(), target.clone()]);
+        }
+        args.push(context.to_string_lossy().into_owned());
+        run_docker(&args, log).await?;
+        Ok(tag)
+    }
+
+    /// Command prefix running a program in `container` at `working_dir`.
+    /// Variables named in `forward_env` are passed through from the
+    /// `docker exec` process's own environment.
+    pub fn exec_prefix<'a>(
+        &self,
+        container: &str,
+        working_dir: &Path,
+        forward_env: impl IntoIterator<Item = &'a String>,
+    ) -> Vec<String> {
+        let mut prefix = vec![
+            "docker".to_string(),
+            "exec".to_string(),
+            "-i".to_string(),
+            "-w".to_string(),
+            working_dir.to_string_lossy().into_owned(),
+        ];
+        if let Some(user) = &self.config.remote_user {
+            prefix.extend(["-u".to_string(), user.clone()]);
+        }
+        for (key, value) in &self.config.remote_env {
+            if let Some(value) = value {
+                prefix.extend(["-e".to_string(), format!("{key}={value}")]);
+            }
+        }
+        for key in forward_env {
+            if !HOST_ONLY_VARS.contains(&key.as_str()) && !self.config.remote_env.contains_key(key)
+            {
+                prefix.extend(["-e".to_string(), key.clone()]);
+            }
+        }
+        prefix.push(container.to_string());
+        prefix
+    }
+}
+
+pub fn container_name(workspace_id: Uuid) -> String {
+    format!("{CONTAINER_PREFIX}{}", short_uuid(&workspace_id))
+}
+
+/// Whether the docker CLI is installed and its daemon answers
+pub async fn docker_available() -> bool {
+    if resolve_executable_path("docker").await.is_none() {
+        return false;
+    }
+    let probe = Command::new("docker")
+        .args(["version", "--format", "{{.Server.Version}}"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .status();
+    matches!(
+        tokio::time::timeout(DOCKER_PROBE_TIMEOUT, probe).await,
+        Ok(Ok(status)) if status.success()
+    )
+}
+
+/// Remove the workspace's container, if any. Errors are ignored: most
+/// workspaces never had one.
+pub async fn remove_container(workspace_id: Uuid) {
+    if resolve_executable_path("docker").await.is_none() {
+        return;
+    }
+    let name = container_name(workspace_id);
+    let remove = Command::new("docker")
+        .args(["rm", "-f", name.as_str()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .status();
+    if tokio::time::timeout(DOCKER_REMOVE_TIMEOUT, remove)
+        .await
+        .is_err()
+    {
+        tracing::warn!("Timed out removing devcontainer {}", name);
+    }
+}
+
+/// `Some(running)` when the container exists
+async fn container_running(name: &str) -> Option<bool> {
+    let output = Command::new("docker")
+        .args(["container", "inspect", "-f", "{{.State.Running}}", name])
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
+
+async fn image_exists(tag: &str) -> bool {
+    Command::new("docker")
+        .args(["image", "inspect", tag])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+async fn forward_lines<R: AsyncRead + Unpin>(reader: R, log: &DevcontainerLog) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        log.line(&line);
+    }
+}
+
+/// Run a docker command, streaming its output into `log`
+async fn run_docker(args: &[String], log: &DevcontainerLog) -> Result<(), DevcontainerError> {
+    let mut child = Command::new("docker")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdout = child.stdout.take().expect("piped stdout");
+    let stderr = child.stderr.take().expect("piped stderr");
+    tokio::join!(forward_lines(stdout, log), forward_lines(stderr, log));
+
+    let status = child.wait().await?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(DevcontainerError::Docker {
+            command: args.first().cloned().unwrap_or_default(),
+            output: log.tail(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct LogState {
+    status: String,
+    tail: VecDeque<String>,
+    last_flush: Option<Instant>,
+}
+
+/// Mirrors devcontainer setup into an execution's `MsgStore` as a single
+/// system message showing the current step and the latest output lines.
+/// The executor's own log normaliser numbers its entries after it.
+pub struct DevcontainerLog {
+    store: Arc<MsgStore>,
+    index: usize,
+    state: Mutex<LogState>,
+}
+
+impl DevcontainerLog {
+    pub fn new(store: Arc<MsgStore>) -> Self {
+        let index = EntryIndexProvider::start_from(&store).next();
+        store.push_patch(ConversationPatch::add_normalized_entry(
+            index,
+            Self::entry("Preparing devcontainer".to_string()),
+        ));
+        Self {
+            store,
+            index,
+            state: Mutex::new(LogState::default()),
+        }
+    }
+
+    fn entry(content: String) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::SystemMessage,
+            content,
+            metadata: None,
+        }
+    }
+
+    pub fn status(&self, status: impl Into<String>) {
+        let status = status.into();
+        tracing::debug!("{status}");
+        let mut state = self.state.lock().unwrap();
+        state.status = status;
+        state.tail.clear();
+        self.flush(&mut state);
+    }
+
+    fn line(&self, line: &str) {
+        let mut state = self.state.lock().unwrap();
+        if state.tail.len() == LOG_TAIL_LINES {
+            state.tail.pop_front();
+        }
+        state.tail.push_back(line.to_string());
+        if state
+            .last_flush
+            .is_none_or(|at| at.elapsed() >= LOG_FLUSH_INTERVAL)
+        {
+            self.flush(&mut state);
+        }
+    }
+
+    fn tail(&self) -> String {
+        let state = self.state.lock().unwrap();
+        state.tail.iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+
+    fn flush(&self, state: &mut LogState) {
+        let mut content = state.status.clone();
+        if !state.tail.is_empty() {
+            content.push_str("\n```\n");
+            content.push_str(&state.tail.iter().cloned().collect::<Vec<_>>().join("\n"));
+            content.push_str("\n```");
+        }
+        self.store
+            .push_patch(ConversationPatch::replace(self.index, Self::entry(content)));
+        state.last_flush = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let raw = r#"{
+            // base image
+            "image": "mcr.microsoft.com/devcontainers/rust:1", /* pinned */
+            "containerEnv": { "URL": "http://example.com//x", },
+            "runArgs": ["--init",],
+        }"#;
+        let config: DevcontainerConfig = serde_json::from_str(&strip_jsonc(raw)).unwrap();
+        assert_eq!(
+            config.image.as_deref(),
+            Some("mcr.microsoft.com/devcontainers/rust:1")
+        );
+        assert_eq!(config.container_env["URL"], "http://example.com//x");
+        assert_eq!(config.run_args, vec!["--init"]);
+    }
+
+    #[test]
+    fn image_runs_on_the_project_network() {
+        let host = Devcontainer::from_image("node:22", None);
+        assert_eq!(host.config.run_args, vec!["--network=host"]);
+        let bridge = Devcontainer::from_image("node:22", Some(" bridge "));
+        assert_eq!(bridge.config.image.as_deref(), Some("node:22"));
+        assert_eq!(bridge.config.run_args, vec!["--network=bridge"]);
+    }
+
+    #[test]
+    fn exec_prefix_forwards_env_by_name() {
+        let devcontainer = Devcontainer {
+            config_path: PathBuf::from("/ws/.devcontainer/devcontainer.json"),
+            config: DevcontainerConfig {
+                remote_user: Some("vscode".to_string()),
+                ..Default::default()
+            },
+        };
+        let keys = ["VK_TASK_ID".to_string(), "PATH".to_string()];
+        let prefix = devcontainer.exec_prefix("vk-devcontainer-1", Path::new("/ws"), &keys);
+        assert_eq!(
+            prefix,
+            [
+                "docker",
+                "exec",
+                "-i",
+                "-w",
+                "/ws",
+                "-u",
+                "vscode",
+                "-e",
+                "VK_TASK_ID",
+                "vk-devcontainer-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn lifecycle_command_forms() {
+        let named: LifecycleCommand =
+            serde_json::from_str(r#"{"a": "npm ci", "b": ["cargo", "fetch"]}"#).unwrap();
+        assert_eq!(
+            named.argvs(),
+            vec![
+                vec![
+                    "/bin/sh".to_string(),
+                    "-c".to_string(),
+                    "npm ci".to_string()
+                ],
+                vec!["cargo".to_string(), "fetch".to_string()],
+            ]
+        );
+    }
+}
@@ -20,7 +20,7 @@ use utils::{
 };
 
 use crate::services::{
-    filesystem_watcher::{self, FilesystemWatcherError},
+    filesystem_watcher::{self, FilesystemWatcherError, WatcherBackend},
     git::{Commit, DiffTarget, GitService, GitServiceError},
     watcher_manager::{WatcherManager, WatcherSubscribeError},
 };
@@ -90,6 +90,7 @@ struct DiffWatcherContext {
     cumulative: Arc<AtomicUsize>,
     full_sent: Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    max_inline_bytes: usize,
     path_prefix: Option<String>,
     tx: mpsc::Sender<Result<LogMsg, io::Error>>,
 }
@@ -113,6 +114,7 @@ impl DiffWatcherContext {
         let cumulative = self.cumulative.clone();
         let full_sent = self.full_sent.clone();
         let stats_only = self.stats_only;
+        let max_inline_bytes = self.max_inline_bytes;
         let path_prefix = self.path_prefix.clone();
 
         match tokio::task::spawn_blocking(move || {
@@ -124,6 +126,7 @@ impl DiffWatcherContext {
                 &cumulative,
                 &full_sent,
                 stats_only,
+                max_inline_bytes,
                 path_prefix.as_deref(),
             )
         })
@@ -174,11 +177,13 @@ impl DiffWatcherContext {
 /// Create a diff stream with an optional shared watcher manager.
 /// If `watcher_manager` is Some, uses a shared watcher (recommended for WebSocket endpoints).
 /// If None, creates a dedicated watcher for this stream only.
+/// Files larger than `max_inline_bytes` are sent without their contents.
 pub async fn create(
     git_service: GitService,
     worktree_path: PathBuf,
     base_commit: Commit,
     stats_only: bool,
+    max_inline_bytes: usize,
     path_prefix: Option<String>,
     watcher_manager: Option<&WatcherManager>,
 ) -> Result<DiffStreamHandle, DiffStreamError> {
@@ -189,6 +194,7 @@ pub async fn create(
                 worktree_path,
                 base_commit,
                 stats_only,
+                max_inline_bytes,
                 path_prefix,
                 manager,
             )
@@ -200,6 +206,7 @@ pub async fn create(
                 worktree_path,
                 base_commit,
                 stats_only,
+                max_inline_bytes,
                 path_prefix,
             )
             .await
@@ -214,6 +221,7 @@ async fn create_with_shared_watcher(
     worktree_path: PathBuf,
     base_commit: Commit,
     stats_only: bool,
+    max_inline_bytes: usize,
     path_prefix: Option<String>,
     watcher_manager: &WatcherManager,
 ) -> Result<DiffStreamHandle, DiffStreamError> {
@@ -235,12 +243,13 @@ async fn create_with_shared_watcher(
         let path_prefix_clone = path_prefix.clone();
 
         let initial_diffs_result = tokio::task::spawn_blocking(move || {
-            git_for_diff.get_diffs(
+            git_for_diff.get_diffs_with_limit(
                 DiffTarget::Worktree {
                     worktree_path: &worktree_for_diff,
                     base_commit: &base_for_diff,
                 },
                 None,
+                max_inline_bytes,
             )
         })
         .await;
@@ -285,6 +294,7 @@ async fn create_with_shared_watcher(
             cumulative,
             full_sent,
             stats_only,
+            max_inline_bytes,
             path_prefix,
             tx: tx_clone,
         };
@@ -316,6 +326,7 @@ async fn create_with_dedicated_watcher(
     worktree_path: PathBuf,
     base_commit: Commit,
     stats_only: bool,
+    max_inline_bytes: usize,
     path_prefix: Option<String>,
 ) -> Result<DiffStreamHandle, DiffStreamError> {
     let (tx, rx) = mpsc::channel::<Result<LogMsg, io::Error>>(DIFF_STREAM_CHANNEL_CAPACITY);
@@ -335,12 +346,13 @@ async fn create_with_dedicated_watcher(
         let path_prefix_clone = path_prefix.clone();
 
         let initial_diffs_result = tokio::task::spawn_blocking(move || {
-            git_for_diff.get_diffs(
+            git_for_diff.get_diffs_with_limit(
                 DiffTarget::Worktree {
                     worktree_path: &worktree_for_diff,
                     base_commit: &base_for_diff,
                 },
                 None,
+                max_inline_bytes,
             )
         })
         .await;
@@ -381,11 +393,14 @@ async fn create_with_dedicated_watcher(
         // Set up filesystem watcher for live updates
         let worktree_for_watcher = worktree_path.clone();
         let watcher_result = tokio::task::spawn_blocking(move || {
-            filesystem_watcher::async_watcher(worktree_for_watcher)
+            filesystem_watcher::async_watcher_with_backend(
+                worktree_for_watcher,
+                WatcherBackend::detect(),
+            )
         })
         .await;
 
-        let (debouncer, mut watcher_rx, canonical_worktree_path) = match watcher_result {
+        let (watcher_guard, mut watcher_rx, canonical_worktree_path) = match watcher_result {
             Ok(Ok(parts)) => parts,
             Ok(Err(e)) => {
                 tracing::error!("Failed to set up filesystem watcher: {e}");
@@ -410,11 +425,12 @@ async fn create_with_dedicated_watcher(
             cumulative,
             full_sent,
             stats_only,
+            max_inline_bytes,
             path_prefix,
             tx: tx_clone,
         };
 
-        let _debouncer_guard = debouncer;
+        let _watcher_guard = watcher_guard;
 
         while let Some(result) = watcher_rx.next().await {
             match result {
@@ -562,16 +578,18 @@ fn process_file_changes(
     cumulative_bytes: &Arc<AtomicUsize>,
     full_sent_paths: &Arc<std::sync::RwLock<HashSet<String>>>,
     stats_only: bool,
+    max_inline_bytes: usize,
     path_prefix: Option<&str>,
 ) -> Result<Vec<LogMsg>, DiffStreamError> {
     let path_filter: Vec<&str> = changed_paths.iter().map(|s| s.as_str()).collect();
 
-    let current_diffs = git_service.get_diffs(
+    let current_diffs = git_service.get_diffs_with_limit(
         DiffTarget::Worktree {
             worktree_path,
             base_commit,
         },
         Some(&path_filter),
+        max_inline_bytes,
     )?;
 
     let mut msgs = Vec::new();
@@ -213,7 +213,7 @@ mod tests {
 
     use async_trait::async_trait;
     use db::models::{
-        task::{Task, TaskStatus},
+        task::{Task, TaskPriority, TaskStatus},
         workspace::Workspace,
     };
     use tokio::sync::RwLock;
@@ -240,6 +240,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         }
     }
 
@@ -842,6 +845,14 @@ mod tests {
             dev_script_working_dir: None,
             default_agent_working_dir: None,
             remote_project_id: None,
+            script_shell: None,
+            inherit_login_env: false,
+            dev_shell: None,
+            dev_shell_command: None,
+            container_image: None,
+            container_network: None,
+            max_concurrent_executions: None,
+            archived_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
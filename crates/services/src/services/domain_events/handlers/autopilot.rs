@@ -282,6 +282,26 @@ impl EventHandler for AutopilotHandler {
         let default_executor_profile = ctx.config.read().await.executor_profile.clone();
 
         for unblocked_task in unblocked_tasks {
+            // Archived tasks, and tasks of archived projects, are never started
+            match Task::is_archived(&ctx.db.pool, unblocked_task.id).await {
+                Ok(false) => {}
+                Ok(true) => {
+                    debug!(
+                        task_id = %unblocked_task.id,
+                        "Skipping auto-dequeue: task or its project is archived"
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    error!(
+                        task_id = %unblocked_task.id,
+                        error = %e,
+                        "Failed to check whether task is archived"
+                    );
+                    continue;
+                }
+            }
+
             // Find the latest workspace for this task, or create one if it doesn't exist
             let (workspace, is_new_workspace) =
                 match Workspace::find_latest_by_task_id(&ctx.db.pool, unblocked_task.id).await {
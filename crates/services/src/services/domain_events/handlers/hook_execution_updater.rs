@@ -173,7 +173,7 @@ mod tests {
 
     #[test]
     fn test_does_not_handle_task_status_changed() {
-        use db::models::task::{Task, TaskStatus};
+        use db::models::task::{Task, TaskPriority, TaskStatus};
 
         let handler = HookExecutionUpdaterHandler::new();
 
@@ -194,6 +194,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         };
 
         let event = DomainEvent::TaskStatusChanged {
@@ -367,7 +370,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_handles_wrong_event_type_gracefully() {
-        use db::models::task::{Task, TaskStatus};
+        use db::models::task::{Task, TaskPriority, TaskStatus};
 
         let handler = HookExecutionUpdaterHandler::new();
         let store = create_test_store();
@@ -389,6 +392,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         };
 
         let event = DomainEvent::TaskStatusChanged {
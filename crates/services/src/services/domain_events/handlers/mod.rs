@@ -7,14 +7,20 @@ mod autopilot;
 mod feedback_collection;
 mod hook_execution_updater;
 mod notifications;
+mod project_memory;
 mod remote_sync;
 mod review_attention;
+mod task_watchers;
+pub mod webhook;
 mod websocket_broadcast;
 
 pub use autopilot::AutopilotHandler;
 pub use feedback_collection::FeedbackCollectionHandler;
 pub use hook_execution_updater::HookExecutionUpdaterHandler;
 pub use notifications::NotificationHandler;
+pub use project_memory::ProjectMemoryHandler;
 pub use remote_sync::RemoteSyncHandler;
 pub use review_attention::ReviewAttentionHandler;
+pub use task_watchers::TaskWatcherHandler;
+pub use webhook::WebhookHandler;
 pub use websocket_broadcast::WebSocketBroadcastHandler;
@@ -1,21 +1,114 @@
 use async_trait::async_trait;
-use db::models::execution_process::{ExecutionProcess, ExecutionProcessStatus};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    execution_process_failure::ExecutionProcessFailure,
+    task::Task,
+};
+use uuid::Uuid;
 
 use crate::services::{
     domain_events::{DomainEvent, EventHandler, ExecutionMode, HandlerContext, HandlerError},
-    notification::NotificationService,
+    notification::{NotificationService, failure_message},
+    notification_channels::{ChannelEvent, ChannelMessage, configured_channels, post_to_channels},
+    notification_groups::{GROUP_WINDOW, NotificationGroups, count_label, execution_failed_key},
+    secrets::SecretService,
 };
 
-/// Handler for sending OS and in-app notifications when executions complete.
+/// Handler for sending OS and in-app notifications when executions complete,
+/// and posting completions, requests for attention and failed merges to the
+/// configured notification channels. Bursts of failures in a project are
+/// delivered once, followed by a summary of the rest.
+#[derive(Clone)]
 pub struct NotificationHandler {
     notification_service: NotificationService,
+    secrets: SecretService,
+    client: reqwest::Client,
+    groups: NotificationGroups,
 }
 
 impl NotificationHandler {
-    pub fn new(notification_service: NotificationService) -> Self {
+    pub fn new(notification_service: NotificationService, secrets: SecretService) -> Self {
         Self {
             notification_service,
+            secrets,
+            client: reqwest::Client::new(),
+            groups: NotificationGroups::default(),
+        }
+    }
+
+    /// OS notification and channel post summarizing the failures held back
+    /// in a grouping window
+    async fn notify_failure_summary(&self, ctx: &HandlerContext, project_name: &str, count: i64) {
+        let title = format!(
+            "{} failed in {}",
+            count_label(count, "execution"),
+            project_name
+        );
+        let message = format!(
+            "{} in the last {} minutes",
+            count_label(count, "failure"),
+            GROUP_WINDOW.as_secs() / 60
+        );
+        let frontend_sounds_enabled = {
+            let config = ctx.config.read().await;
+            config.notifications.frontend_sounds_enabled
+        };
+        if frontend_sounds_enabled {
+            self.notification_service
+                .notify_push_only(&title, &message)
+                .await;
+        } else {
+            self.notification_service
+                .notify_error(&title, &message)
+                .await;
         }
+        self.post_to_channels(
+            ctx,
+            ChannelMessage {
+                event: ChannelEvent::NeedsAttention,
+                title,
+                body: message,
+                occurrences: count,
+            },
+        )
+        .await;
+    }
+
+    /// Post to every configured channel that wants this kind of message
+    async fn post_to_channels(&self, ctx: &HandlerContext, message: ChannelMessage) {
+        let notifications = ctx.config.read().await.notifications.clone();
+        let channels = configured_channels(&self.client, &self.secrets, &notifications).await;
+        post_to_channels(&channels, &message).await;
+    }
+
+    /// Post a message about a task, titled with the task and prefixed with
+    /// its project
+    async fn post_task_message(
+        &self,
+        ctx: &HandlerContext,
+        task_id: Uuid,
+        event: ChannelEvent,
+        label: &str,
+        detail: &str,
+    ) -> Result<(), HandlerError> {
+        let Some(task) = Task::find_by_id(&ctx.db.pool, task_id).await? else {
+            return Ok(());
+        };
+        let body = match task.parent_project(&ctx.db.pool).await? {
+            Some(project) => format!("{}: {}", project.name, detail),
+            None => detail.to_string(),
+        };
+        self.post_to_channels(
+            ctx,
+            ChannelMessage {
+                event,
+                title: format!("{}: {}", label, task.title),
+                body,
+                occurrences: 1,
+            },
+        )
+        .await;
+        Ok(())
     }
 }
 
@@ -30,12 +123,44 @@ impl EventHandler for NotificationHandler {
     }
 
     fn handles(&self, event: &DomainEvent) -> bool {
-        matches!(event, DomainEvent::ExecutionCompleted { .. })
+        matches!(
+            event,
+            DomainEvent::ExecutionCompleted { .. }
+                | DomainEvent::AttentionNeeded { .. }
+                | DomainEvent::MergeFailed { .. }
+        )
     }
 
     async fn handle(&self, event: DomainEvent, ctx: &HandlerContext) -> Result<(), HandlerError> {
-        let DomainEvent::ExecutionCompleted { process, .. } = event else {
-            return Ok(());
+        let process = match event {
+            DomainEvent::ExecutionCompleted { process, .. } => process,
+            DomainEvent::AttentionNeeded {
+                task_id, reason, ..
+            } => {
+                return self
+                    .post_task_message(
+                        ctx,
+                        task_id,
+                        ChannelEvent::NeedsAttention,
+                        "Needs attention",
+                        &reason,
+                    )
+                    .await;
+            }
+            DomainEvent::MergeFailed {
+                task_id, message, ..
+            } => {
+                return self
+                    .post_task_message(
+                        ctx,
+                        task_id,
+                        ChannelEvent::MergeFailed,
+                        "Merge failed",
+                        &message,
+                    )
+                    .await;
+            }
+            _ => return Ok(()),
         };
 
         // Skip notification if process was intentionally killed by user
@@ -64,7 +189,12 @@ impl EventHandler for NotificationHandler {
         let title = format!("Task Complete: {}", execution_ctx.task.title);
 
         // Check if frontend handles sounds (skip backend sound playback if so)
-        let frontend_sounds_enabled = ctx.config.read().await.notifications.frontend_sounds_enabled;
+        let frontend_sounds_enabled = ctx
+            .config
+            .read()
+            .await
+            .notifications
+            .frontend_sounds_enabled;
 
         match process.status {
             ExecutionProcessStatus::Completed => {
@@ -95,11 +225,62 @@ impl EventHandler for NotificationHandler {
                 {
                     tracing::warn!("Failed to create in-app completion notification: {}", e);
                 }
+
+                self.post_to_channels(
+                    ctx,
+                    ChannelMessage {
+                        event: ChannelEvent::TaskCompleted,
+                        title,
+                        body: format!(
+                            "{}: completed on branch {}",
+                            execution_ctx.project.name, execution_ctx.workspace.branch
+                        ),
+                        occurrences: 1,
+                    },
+                )
+                .await;
             }
             ExecutionProcessStatus::Failed => {
+                let failure =
+                    ExecutionProcessFailure::find_by_execution_process_id(&ctx.db.pool, process.id)
+                        .await
+                        .ok()
+                        .flatten();
+
+                // In-app notification, folded with recent failures in the project
+                if let Err(e) = NotificationService::notify_agent_error(
+                    &ctx.db.pool,
+                    execution_ctx.project.id,
+                    &execution_ctx.project.name,
+                    execution_ctx.workspace.id,
+                    &execution_ctx.task.title,
+                    failure.as_ref(),
+                )
+                .await
+                {
+                    tracing::warn!("Failed to create in-app error notification: {}", e);
+                }
+
+                // Only the first failure in a window goes out now; the rest
+                // are summarized when it closes
+                let handler = self.clone();
+                let summary_ctx = ctx.clone();
+                let project_name = execution_ctx.project.name.clone();
+                let deliver_now = self.groups.admit(
+                    &execution_failed_key(execution_ctx.project.id),
+                    move |count| async move {
+                        handler
+                            .notify_failure_summary(&summary_ctx, &project_name, count)
+                            .await;
+                    },
+                );
+                if !deliver_now {
+                    return Ok(());
+                }
+
                 let message = format!(
-                    "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
-                    execution_ctx.task.title,
+                    "❌ {}\nBranch: {:?}\nExecutor: {:?}",
+                    failure_message(&execution_ctx.task.title, failure.as_ref()),
                     execution_ctx.workspace.branch,
                     execution_ctx.session.executor
                 );
@@ -115,17 +296,28 @@ impl EventHandler for NotificationHandler {
                         .await;
                 }
 
-                // In-app notification
-                if let Err(e) = NotificationService::notify_agent_error(
-                    &ctx.db.pool,
-                    execution_ctx.project.id,
-                    execution_ctx.workspace.id,
-                    &execution_ctx.task.title,
+                self.post_to_channels(
+                    ctx,
+                    ChannelMessage {
+                        event: ChannelEvent::NeedsAttention,
+                        title: format!("Task failed: {}", execution_ctx.task.title),
+                        body: match &failure {
+                            Some(failure) => format!(
+                                "{}: execution failed on branch {}: {}. {}",
+                                execution_ctx.project.name,
+                                execution_ctx.workspace.branch,
+                                failure.reason.label(),
+                                failure.suggested_fix
+                            ),
+                            None => format!(
+                                "{}: execution failed on branch {}",
+                                execution_ctx.project.name, execution_ctx.workspace.branch
+                            ),
+                        },
+                        occurrences: 1,
+                    },
                 )
-                .await
-                {
-                    tracing::warn!("Failed to create in-app error notification: {}", e);
-                }
+                .await;
             }
             ExecutionProcessStatus::Running | ExecutionProcessStatus::Killed => {
                 // Running shouldn't reach here (event is for completion)
@@ -0,0 +1,107 @@
+//! Project memory handler for extracting memories when tasks move to InReview.
+//!
+//! For projects that opted into memory, this handler asks the agent that
+//! worked on the task to distill what it learned, by calling the
+//! execution_trigger callback with the ProjectMemory variant.
+
+use async_trait::async_trait;
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    project_memory::ProjectMemorySettings,
+    task::TaskStatus,
+    workspace::Workspace,
+};
+use tracing::{debug, info};
+
+use crate::services::domain_events::{
+    DomainEvent, EventHandler, ExecutionMode, ExecutionTrigger, HandlerContext, HandlerError,
+};
+
+/// Handler that triggers memory extraction when a task moves to InReview.
+pub struct ProjectMemoryHandler;
+
+impl ProjectMemoryHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ProjectMemoryHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventHandler for ProjectMemoryHandler {
+    fn name(&self) -> &'static str {
+        "project_memory"
+    }
+
+    fn execution_mode(&self) -> ExecutionMode {
+        ExecutionMode::Spawned
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(
+            event,
+            DomainEvent::TaskStatusChanged { task, .. } if task.status == TaskStatus::InReview
+        )
+    }
+
+    async fn handle(&self, event: DomainEvent, ctx: &HandlerContext) -> Result<(), HandlerError> {
+        let DomainEvent::TaskStatusChanged { task, .. } = event else {
+            return Ok(());
+        };
+
+        let settings =
+            ProjectMemorySettings::find_or_default(&ctx.db.pool, task.project_id).await?;
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        let Some(workspace) = Workspace::find_latest_by_task_id(&ctx.db.pool, task.id).await?
+        else {
+            debug!(task_id = %task.id, "Skipping memory extraction: task has no workspace");
+            return Ok(());
+        };
+
+        let Some(execution_process) = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            &ctx.db.pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            debug!(
+                task_id = %task.id,
+                workspace_id = %workspace.id,
+                "Skipping memory extraction: no CodingAgent execution found"
+            );
+            return Ok(());
+        };
+
+        let Some(ref trigger_callback) = ctx.execution_trigger else {
+            debug!(
+                task_id = %task.id,
+                "No execution trigger callback available, skipping memory extraction"
+            );
+            return Ok(());
+        };
+
+        info!(
+            task_id = %task.id,
+            execution_process_id = %execution_process.id,
+            "Triggering project memory extraction"
+        );
+
+        trigger_callback(ExecutionTrigger::ProjectMemory {
+            task_id: task.id,
+            execution_process_id: execution_process.id,
+        })
+        .await
+        .map_err(|e| HandlerError::Failed(format!("Failed to trigger memory extraction: {e}")))?;
+
+        Ok(())
+    }
+}
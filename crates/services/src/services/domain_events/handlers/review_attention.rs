@@ -144,7 +144,7 @@ impl EventHandler for ReviewAttentionHandler {
 
 #[cfg(test)]
 mod tests {
-    use db::models::task::Task;
+    use db::models::task::{Task, TaskPriority};
     use uuid::Uuid;
 
     use super::*;
@@ -167,6 +167,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         }
     }
 
@@ -0,0 +1,120 @@
+//! Task watcher handler for notifying the users following a task.
+//!
+//! Watchers get an in-app notification of their own when a watched task moves
+//! to another status, is merged, or has an execution fail.
+
+use async_trait::async_trait;
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    merge::Merge,
+    notification::NotificationType,
+    task::TaskStatus,
+};
+
+use crate::services::{
+    domain_events::{DomainEvent, EventHandler, ExecutionMode, HandlerContext, HandlerError},
+    notification::NotificationService,
+};
+
+/// Handler that notifies a task's watchers about changes to it.
+pub struct TaskWatcherHandler;
+
+impl TaskWatcherHandler {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TaskWatcherHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn status_label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "To Do",
+        TaskStatus::InProgress => "In Progress",
+        TaskStatus::InReview => "In Review",
+        TaskStatus::Done => "Done",
+        TaskStatus::Cancelled => "Cancelled",
+    }
+}
+
+#[async_trait]
+impl EventHandler for TaskWatcherHandler {
+    fn name(&self) -> &'static str {
+        "task_watchers"
+    }
+
+    fn execution_mode(&self) -> ExecutionMode {
+        ExecutionMode::Spawned
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        match event {
+            DomainEvent::TaskStatusChanged {
+                task,
+                previous_status,
+            } => task.status != *previous_status,
+            DomainEvent::ExecutionCompleted { process, .. } => {
+                process.status == ExecutionProcessStatus::Failed && process.session_id.is_some()
+            }
+            _ => false,
+        }
+    }
+
+    async fn handle(&self, event: DomainEvent, ctx: &HandlerContext) -> Result<(), HandlerError> {
+        let pool = &ctx.db.pool;
+        match event {
+            DomainEvent::TaskStatusChanged {
+                task,
+                previous_status,
+            } => {
+                if task.status == TaskStatus::Done && Merge::task_has_merged(pool, task.id).await? {
+                    NotificationService::notify_task_watchers(
+                        pool,
+                        &task,
+                        None,
+                        NotificationType::TaskMerged,
+                        &format!("Merged: {}", task.title),
+                        &format!("'{}' was merged", task.title),
+                    )
+                    .await?;
+                } else {
+                    NotificationService::notify_task_watchers(
+                        pool,
+                        &task,
+                        None,
+                        NotificationType::TaskStatusChanged,
+                        &format!("{}: {}", status_label(&task.status), task.title),
+                        &format!(
+                            "'{}' moved from {} to {}",
+                            task.title,
+                            status_label(&previous_status),
+                            status_label(&task.status)
+                        ),
+                    )
+                    .await?;
+                }
+            }
+            DomainEvent::ExecutionCompleted { process, .. } => {
+                let execution_ctx = ExecutionProcess::load_context(pool, process.id).await?;
+                NotificationService::notify_task_watchers(
+                    pool,
+                    &execution_ctx.task,
+                    Some(execution_ctx.workspace.id),
+                    NotificationType::AgentError,
+                    &format!("Task Failed: {}", execution_ctx.task.title),
+                    &format!(
+                        "'{}' execution failed on branch {}",
+                        execution_ctx.task.title, execution_ctx.workspace.branch
+                    ),
+                )
+                .await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+//! Delivers domain events to user-defined webhook URLs.
+//!
+//! Each delivery is a JSON POST, signed with HMAC-SHA256 when the webhook has
+//! a secret in the secret store (`X-Vibe-Kanban-Signature-256: sha256=<hex>`,
+//! GitHub style), and
+//! retried with exponential backoff on network errors, 429s and 5xxs.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use db::models::{
+    task::Task,
+    webhook::{Webhook, WebhookEvent},
+};
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::services::{
+    domain_events::{DomainEvent, EventHandler, ExecutionMode, HandlerContext, HandlerError},
+    secrets::SecretService,
+    webhook_secrets,
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Vibe-Kanban-Signature-256";
+pub const EVENT_HEADER: &str = "X-Vibe-Kanban-Event";
+pub const DELIVERY_HEADER: &str = "X-Vibe-Kanban-Delivery";
+
+const MAX_ATTEMPTS: u32 = 4;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `sha256=<hex>` HMAC of `body` under `secret`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(payload, |value, key| match value {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+fn render_value(template: Value, payload: &Value) -> Value {
+    match template {
+        Value::String(s) => {
+            // A string that is a single placeholder keeps the value's JSON type
+            if let Some(path) = s
+                .strip_prefix("{{")
+                .and_then(|rest| rest.strip_suffix("}}"))
+                .filter(|path| !path.contains("{{"))
+            {
+                return lookup(payload, path.trim()).cloned().unwrap_or(Value::Null);
+            }
+
+            let mut out = String::new();
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{{") {
+                let Some(len) = rest[start..].find("}}") else {
+                    break;
+                };
+                out.push_str(&rest[..start]);
+                match lookup(payload, rest[start + 2..start + len].trim()) {
+                    Some(Value::String(value)) => out.push_str(value),
+                    Some(Value::Null) | None => {}
+                    Some(value) => out.push_str(&value.to_string()),
+                }
+                rest = &rest[start + len + 2..];
+            }
+            out.push_str(rest);
+            Value::String(out)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| render_value(item, payload))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, render_value(value, payload)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Fill a payload template's `{{path}}` placeholders from the default payload
+pub fn render_template(template: &str, payload: &Value) -> Result<Value, serde_json::Error> {
+    let template: Value = serde_json::from_str(template)?;
+    Ok(render_value(template, payload))
+}
+
+fn webhook_event(event: &DomainEvent) -> Option<WebhookEvent> {
+    match event {
+        DomainEvent::TaskStatusChanged { .. } => Some(WebhookEvent::TaskStatusChanged),
+        DomainEvent::ExecutionCompleted { .. } => Some(WebhookEvent::ExecutionCompleted),
+        DomainEvent::WorkspaceCreated { .. } => Some(WebhookEvent::WorkspaceCreated),
+        DomainEvent::WorkspaceDeleted { .. }
+        | DomainEvent::ProjectUpdated { .. }
+        | DomainEvent::AttentionNeeded { .. }
+        | DomainEvent::MergeFailed { .. } => None,
+    }
+}
+
+async fn event_project_id(
+    pool: &SqlitePool,
+    event: &DomainEvent,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let task_id = match event {
+        DomainEvent::TaskStatusChanged { task, .. } => return Ok(Some(task.project_id)),
+        DomainEvent::ExecutionCompleted { task_id, .. } => *task_id,
+        DomainEvent::WorkspaceCreated { workspace } => workspace.task_id,
+        DomainEvent::AttentionNeeded { task_id, .. } | DomainEvent::MergeFailed { task_id, .. } => {
+            *task_id
+        }
+        DomainEvent::WorkspaceDeleted { .. } | DomainEvent::ProjectUpdated { .. } => {
+            return Ok(None);
+        }
+    };
+    Ok(Task::find_by_id(pool, task_id).await?.map(|t| t.project_id))
+}
+
+/// Default body: `{ event, timestamp, project_id, data }`
+fn build_payload(event: &DomainEvent, kind: WebhookEvent, project_id: Uuid) -> Value {
+    let data = match event {
+        DomainEvent::TaskStatusChanged {
+            task,
+            previous_status,
+        } => json!({ "task": task, "previous_status": previous_status }),
+        DomainEvent::ExecutionCompleted { process, task_id } => json!({
+            "task_id": task_id,
+            // The executor action holds the full prompt; leave it out
+            "execution_process": {
+                "id": process.id,
+                "session_id": process.session_id,
+                "run_reason": process.run_reason,
+                "status": process.status,
+                "exit_code": process.exit_code,
+                "started_at": process.started_at,
+                "completed_at": process.completed_at,
+            },
+        }),
+        DomainEvent::WorkspaceCreated { workspace } => json!({ "workspace": workspace }),
+        DomainEvent::WorkspaceDeleted { .. }
+        | DomainEvent::ProjectUpdated { .. }
+        | DomainEvent::AttentionNeeded { .. }
+        | DomainEvent::MergeFailed { .. } => Value::Null,
+    };
+    json!({
+        "event": kind,
+        "timestamp": Utc::now(),
+        "project_id": project_id,
+        "data": data,
+    })
+}
+
+/// POST `payload` to the webhook, signed with its secret from the store and
+/// retrying transient failures. Returns the last error when every attempt
+/// failed.
+pub async fn deliver(
+    client: &reqwest::Client,
+    secrets: &SecretService,
+    webhook: &Webhook,
+    event: &str,
+    payload: &Value,
+) -> Result<(), String> {
+    let secret = webhook_secrets::resolve(secrets, webhook).await?;
+    let body = match &webhook.payload_template {
+        Some(template) => render_template(template, payload)
+            .map_err(|e| format!("Invalid payload template: {e}"))?,
+        None => payload.clone(),
+    };
+    let body = serde_json::to_vec(&body).map_err(|e| e.to_string())?;
+    let signature = secret.as_deref().map(|secret| sign(secret, &body));
+    let delivery_id = Uuid::new_v4().to_string();
+
+    let mut backoff = INITIAL_BACKOFF;
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = client
+            .post(&webhook.url)
+            .timeout(REQUEST_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event)
+            .header(DELIVERY_HEADER, &delivery_id)
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let retryable = match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                last_error = format!("HTTP {status}");
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                last_error = e.to_string();
+                true
+            }
+        };
+
+        if !retryable || attempt == MAX_ATTEMPTS {
+            break;
+        }
+        tracing::debug!(
+            "Webhook '{}' delivery attempt {} failed ({}), retrying in {:?}",
+            webhook.name,
+            attempt,
+            last_error,
+            backoff
+        );
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+
+    Err(last_error)
+}
+
+/// Handler that POSTs task, execution and workspace events to webhooks.
+pub struct WebhookHandler {
+    client: reqwest::Client,
+    secrets: SecretService,
+}
+
+impl WebhookHandler {
+    pub fn new(secrets: SecretService) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            secrets,
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for WebhookHandler {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    fn execution_mode(&self) -> ExecutionMode {
+        ExecutionMode::Spawned
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        webhook_event(event).is_some()
+    }
+
+    async fn handle(&self, event: DomainEvent, ctx: &HandlerContext) -> Result<(), HandlerError> {
+        let Some(kind) = webhook_event(&event) else {
+            return Ok(());
+        };
+        let pool = &ctx.db.pool;
+        let Some(project_id) = event_project_id(pool, &event).await? else {
+            return Ok(());
+        };
+
+        let webhooks: Vec<Webhook> = Webhook::find_enabled_for_project(pool, project_id)
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.subscribes_to(kind))
+            .collect();
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let payload = build_payload(&event, kind, project_id);
+        let event_name = kind.to_string();
+        let deliveries = webhooks.iter().map(|webhook| async {
+            let result = deliver(&self.client, &self.secrets, webhook, &event_name, &payload).await;
+            if let Err(e) = &result {
+                tracing::warn!("Webhook '{}' delivery failed: {}", webhook.name, e);
+            }
+            if let Err(e) =
+                Webhook::record_delivery(pool, webhook.id, result.err().as_deref()).await
+            {
+                tracing::warn!("Failed to record webhook delivery: {}", e);
+            }
+        });
+        futures::future::join_all(deliveries).await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_body_with_hmac_sha256() {
+        // Known-answer value from GitHub's webhook validation docs
+        assert_eq!(
+            sign("It's a Secret to Everybody", b"Hello, World!"),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+    }
+
+    #[test]
+    fn template_placeholders_keep_types_or_interpolate() {
+        let payload = json!({
+            "event": "task_status_changed",
+            "data": { "task": { "title": "Fix login", "status": "done", "tags": ["a"] } },
+        });
+        let rendered = render_template(
+            r#"{"text": "{{data.task.title}} is {{ data.task.status }}", "task": "{{data.task}}", "first": "{{data.task.tags.0}}", "missing": "{{nope}}"}"#,
+            &payload,
+        )
+        .unwrap();
+        assert_eq!(
+            rendered,
+            json!({
+                "text": "Fix login is done",
+                "task": { "title": "Fix login", "status": "done", "tags": ["a"] },
+                "first": "a",
+                "missing": null,
+            })
+        );
+    }
+}
@@ -146,7 +146,7 @@ mod tests {
 
     #[test]
     fn test_handles_task_status_changed() {
-        use db::models::task::{Task, TaskStatus};
+        use db::models::task::{Task, TaskPriority, TaskStatus};
 
         let handler = WebSocketBroadcastHandler::new();
         let task = Task {
@@ -166,6 +166,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         };
 
         let event = DomainEvent::TaskStatusChanged {
@@ -15,7 +15,8 @@ pub use dispatcher::{DispatcherBuilder, DomainEventDispatcher};
 pub use handler::{EventHandler, ExecutionMode, HandlerContext, HandlerError};
 pub use handlers::{
     AutopilotHandler, FeedbackCollectionHandler, HookExecutionUpdaterHandler, NotificationHandler,
-    RemoteSyncHandler, ReviewAttentionHandler, WebSocketBroadcastHandler,
+    ProjectMemoryHandler, RemoteSyncHandler, ReviewAttentionHandler, TaskWatcherHandler,
+    WebSocketBroadcastHandler, WebhookHandler,
 };
 pub use hook_execution::{HookExecution, HookExecutionStatus, HookExecutionStore};
 pub use hook_points::HookPoint;
@@ -36,6 +36,20 @@ pub enum DomainEvent {
 
     /// A project was updated.
     ProjectUpdated { project: Project },
+
+    /// An agent is waiting on the user for a tool approval or an answer.
+    AttentionNeeded {
+        task_id: Uuid,
+        workspace_id: Uuid,
+        reason: String,
+    },
+
+    /// A queued merge hit conflicts or failed.
+    MergeFailed {
+        task_id: Uuid,
+        workspace_id: Uuid,
+        message: String,
+    },
 }
 
 impl DomainEvent {
@@ -52,6 +66,8 @@ impl DomainEvent {
             DomainEvent::WorkspaceCreated { workspace } => Some(workspace.task_id),
             DomainEvent::WorkspaceDeleted { task_id, .. } => Some(*task_id),
             DomainEvent::ProjectUpdated { .. } => None,
+            DomainEvent::AttentionNeeded { task_id, .. } => Some(*task_id),
+            DomainEvent::MergeFailed { task_id, .. } => Some(*task_id),
         }
     }
 
@@ -65,6 +81,8 @@ impl DomainEvent {
             DomainEvent::WorkspaceCreated { .. } => HookPoint::PostTaskCreate,
             DomainEvent::WorkspaceDeleted { .. } => HookPoint::PostTaskStatusChange,
             DomainEvent::ProjectUpdated { .. } => HookPoint::PostTaskStatusChange, // Best approximation
+            DomainEvent::AttentionNeeded { .. } => HookPoint::PostAgentComplete, // Best approximation
+            DomainEvent::MergeFailed { .. } => HookPoint::PostTaskStatusChange, // Best approximation
         }
     }
 }
@@ -89,6 +107,12 @@ pub enum ExecutionTrigger {
         execution_process_id: Uuid,
     },
 
+    /// Trigger memory extraction for a task's project when it moves to review.
+    ProjectMemory {
+        task_id: Uuid,
+        execution_process_id: Uuid,
+    },
+
     /// Trigger processing of the execution queue.
     /// Used when new items are added to the queue (e.g., by autopilot)
     /// to ensure they are processed if capacity is available.
@@ -188,6 +188,9 @@ mod tests {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: db::models::task::TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         };
 
         let text = format_task_text(&task_with_desc);
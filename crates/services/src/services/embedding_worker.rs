@@ -3,18 +3,23 @@ use std::time::Duration;
 use db::{
     DBService,
     models::{
-        embedding::{EmbeddingStatus, TaskEmbedding},
+        embedding::{EmbeddingSource, EmbeddingStatus, TaskEmbedding},
         task::Task,
     },
 };
 use tokio::time::interval;
 use tracing::{debug, error, info};
+use utils::text::truncate_to_char_boundary;
 
 use crate::services::embedding::EmbeddingService;
 
 /// Default model name used for tracking which model generated embeddings.
 const EMBEDDING_MODEL_NAME: &str = "BAAI/bge-small-en-v1.5";
 
+/// Messages and summaries can be long; the model only looks at the first
+/// few hundred tokens anyway.
+const MAX_EMBED_TEXT_BYTES: usize = 4000;
+
 /// Background worker that polls for tasks needing embeddings and processes them.
 /// Runs in its own tokio task and processes embeddings asynchronously.
 pub struct EmbeddingWorker {
@@ -58,7 +63,13 @@ impl EmbeddingWorker {
     }
 
     /// Run the worker loop indefinitely.
+    /// Exits immediately when no vector store is available.
     async fn run(&self) {
+        if !db::is_sqlite_vec_available() {
+            info!("Vector search unavailable, EmbeddingWorker not started");
+            return;
+        }
+
         info!(
             "Starting EmbeddingWorker with poll_interval={:?}, batch_size={}",
             self.poll_interval, self.batch_size
@@ -78,6 +89,15 @@ impl EmbeddingWorker {
                 // Continue anyway - the table might already exist or be created later
             }
         }
+        for source in EmbeddingSource::ALL {
+            if let Err(e) = source.ensure_table_exists(&self.db.pool).await {
+                error!(
+                    "Failed to ensure {} table exists: {}",
+                    source.vector_table(),
+                    e
+                );
+            }
+        }
 
         let mut ticker = interval(self.poll_interval);
 
@@ -89,17 +109,17 @@ impl EmbeddingWorker {
         }
     }
 
-    /// Process a batch of pending embedding tasks.
+    /// Process a batch of pending embedding tasks, then conversation
+    /// messages and execution summaries.
     async fn process_pending_embeddings(&self) -> Result<(), anyhow::Error> {
         let pending = EmbeddingStatus::find_pending(&self.db.pool, self.batch_size).await?;
 
         if pending.is_empty() {
             debug!("No pending embeddings to process");
-            return Ok(());
+        } else {
+            info!("Processing {} pending embeddings", pending.len());
         }
 
-        info!("Processing {} pending embeddings", pending.len());
-
         for status in pending {
             if let Err(e) = self.process_single_task(status.task_id).await {
                 error!("Failed to embed task {}: {}", status.task_id, e);
@@ -107,6 +127,47 @@ impl EmbeddingWorker {
             }
         }
 
+        for source in EmbeddingSource::ALL {
+            if let Err(e) = self.process_source(source).await {
+                error!("Error processing pending {:?} embeddings: {}", source, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Embed one batch of pending rows for a non-task source.
+    async fn process_source(&self, source: EmbeddingSource) -> Result<(), anyhow::Error> {
+        let pending = source.find_pending(&self.db.pool, self.batch_size).await?;
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        debug!(
+            "Processing {} pending {:?} embeddings",
+            pending.len(),
+            source
+        );
+
+        let texts: Vec<String> = pending
+            .iter()
+            .map(|p| truncate_to_char_boundary(&p.text, MAX_EMBED_TEXT_BYTES).to_string())
+            .collect();
+        let embeddings = self.embedding_service.embed_batch(&texts).await?;
+
+        for (row, embedding) in pending.iter().zip(embeddings) {
+            if let Err(e) = source.upsert(&self.db.pool, row.rowid, &embedding).await {
+                error!("Failed to store {:?} embedding {}: {}", source, row.id, e);
+                continue;
+            }
+            if let Err(e) = source
+                .mark_embedded(&self.db.pool, row.id, EMBEDDING_MODEL_NAME)
+                .await
+            {
+                error!("Failed to mark {:?} embedding {}: {}", source, row.id, e);
+            }
+        }
+
         Ok(())
     }
 
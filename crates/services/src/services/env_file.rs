@@ -0,0 +1,259 @@
+//! Managed `.env` files for workspaces.
+//!
+//! Each project can define an env template. When a workspace is created the
+//! template is rendered into every repo worktree as `.env`, with `${secret:NAME}`
+//! references resolved from the project's secrets. The file is added to
+//! `.git/info/exclude` so it is never committed, and re-rendered across all
+//! live workspaces whenever the template or a secret changes.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use db::models::{
+    project_env_file::ProjectEnvFile, repo::Repo, workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use utils::{git::add_git_exclude, text::detect_line_ending};
+use uuid::Uuid;
+
+use super::{project_secrets, secrets::SecretService};
+
+pub const ENV_FILE_NAME: &str = ".env";
+
+/// First line of every managed file. Files without it were created by the user
+/// (or copied via copy-files) and are never overwritten or removed.
+const MANAGED_HEADER: &str =
+    "# Managed by Vibe Kanban from the project env template. Local edits are overwritten.";
+
+const SECRET_REF_OPEN: &str = "${secret:";
+
+#[derive(Debug, Error)]
+pub enum EnvFileError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Secret '{0}' referenced by the env template is not defined")]
+    MissingSecret(String),
+}
+
+/// Names of all secrets referenced by `${secret:NAME}` in `template`
+pub fn secret_refs(template: &str) -> BTreeSet<String> {
+    let mut refs = BTreeSet::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(SECRET_REF_OPEN) {
+        let after_open = &rest[start + SECRET_REF_OPEN.len()..];
+        let Some(end) = after_open.find('}') else {
+            break;
+        };
+        refs.insert(after_open[..end].trim().to_string());
+        rest = &after_open[end + 1..];
+    }
+    refs
+}
+
+/// Render the template, replacing every secret reference with its value.
+/// Fails if any referenced secret is missing so a half-rendered file is never written.
+pub fn render_env_file(
+    template: &str,
+    secrets: &HashMap<String, String>,
+) -> Result<String, EnvFileError> {
+    let eol = detect_line_ending(template);
+    let mut out = format!("{MANAGED_HEADER}{eol}");
+    let mut rest = template;
+
+    while let Some(start) = rest.find(SECRET_REF_OPEN) {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + SECRET_REF_OPEN.len()..];
+        let Some(end) = after_open.find('}') else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let name = after_open[..end].trim();
+        let value = secrets
+            .get(name)
+            .ok_or_else(|| EnvFileError::MissingSecret(name.to_string()))?;
+        out.push_str(value);
+        rest = &after_open[end + 1..];
+    }
+
+    out.push_str(rest);
+    if !out.ends_with('\n') {
+        out.push_str(eol);
+    }
+    Ok(out)
+}
+
+fn is_managed(content: &str) -> bool {
+    content.lines().next() == Some(MANAGED_HEADER)
+}
+
+/// Write (or remove, when `rendered` is `None`) the managed `.env` in a worktree.
+/// Returns false if an unmanaged `.env` is present and was left untouched.
+async fn apply_env_file(
+    worktree_path: &Path,
+    rendered: Option<&str>,
+) -> Result<bool, EnvFileError> {
+    let env_path = worktree_path.join(ENV_FILE_NAME);
+
+    match tokio::fs::read_to_string(&env_path).await {
+        Ok(existing) if !is_managed(&existing) => return Ok(false),
+        Ok(existing) if Some(existing.as_str()) == rendered => return Ok(true),
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    match rendered {
+        Some(content) => {
+            add_git_exclude(worktree_path, ENV_FILE_NAME).await?;
+            tokio::fs::write(&env_path, content).await?;
+        }
+        None => match tokio::fs::remove_file(&env_path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+
+    Ok(true)
+}
+
+async fn render_for_project(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+    project_id: Uuid,
+) -> Result<Option<String>, EnvFileError> {
+    let Some(env_file) = ProjectEnvFile::find_by_project_id(pool, project_id).await? else {
+        return Ok(None);
+    };
+    let values = project_secrets::values(pool, secrets, project_id).await?;
+    render_env_file(&env_file.template, &values).map(Some)
+}
+
+/// Write the project's managed `.env` into each repo worktree of a workspace.
+/// Returns the number of worktrees whose env file is now up to date.
+pub async fn sync_workspace_env_files(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+    project_id: Uuid,
+    workspace_dir: &Path,
+    repos: &[Repo],
+) -> Result<usize, EnvFileError> {
+    let rendered = render_for_project(pool, secrets, project_id).await?;
+    sync_rendered(workspace_dir, repos, rendered.as_deref()).await
+}
+
+async fn sync_rendered(
+    workspace_dir: &Path,
+    repos: &[Repo],
+    rendered: Option<&str>,
+) -> Result<usize, EnvFileError> {
+    let mut synced = 0;
+    for repo in repos {
+        let worktree_path = workspace_dir.join(&repo.name);
+        if !worktree_path.exists() {
+            continue;
+        }
+        if apply_env_file(&worktree_path, rendered).await? {
+            synced += 1;
+        } else {
+            tracing::warn!(
+                "Skipping managed {} for repo '{}': an unmanaged file already exists",
+                ENV_FILE_NAME,
+                repo.name
+            );
+        }
+    }
+    Ok(synced)
+}
+
+/// Re-render the managed `.env` in every live workspace of a project.
+/// Called after the env template or one of the project's secrets changes.
+pub async fn sync_project_env_files(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+    project_id: Uuid,
+) -> Result<usize, EnvFileError> {
+    let rendered = render_for_project(pool, secrets, project_id).await?;
+
+    let mut synced = 0;
+    for workspace in Workspace::fetch_by_project_id(pool, project_id).await? {
+        let Some(container_ref) = &workspace.container_ref else {
+            continue;
+        };
+        let workspace_dir = Path::new(container_ref);
+        if !workspace_dir.exists() {
+            continue;
+        }
+
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        match sync_rendered(workspace_dir, &repos, rendered.as_deref()).await {
+            Ok(count) => synced += count,
+            Err(e) => tracing::warn!(
+                "Failed to sync env files for workspace {}: {}",
+                workspace.id,
+                e
+            ),
+        }
+    }
+
+    Ok(synced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_refs() {
+        let refs = secret_refs("A=${secret:API_KEY}\nB=${secret: DB_URL }\nC=plain\n");
+        assert_eq!(
+            refs.into_iter().collect::<Vec<_>>(),
+            vec!["API_KEY".to_string(), "DB_URL".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_env_file() {
+        let secrets = HashMap::from([("API_KEY".to_string(), "s3cr3t".to_string())]);
+        let rendered = render_env_file("API_KEY=${secret:API_KEY}\nDEBUG=1", &secrets).unwrap();
+
+        assert!(is_managed(&rendered));
+        assert!(rendered.ends_with("API_KEY=s3cr3t\nDEBUG=1\n"));
+    }
+
+    #[test]
+    fn test_render_env_file_missing_secret() {
+        let err = render_env_file("TOKEN=${secret:TOKEN}", &HashMap::new()).unwrap_err();
+        assert!(matches!(err, EnvFileError::MissingSecret(name) if name == "TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_env_file_respects_unmanaged_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let env_path = dir.path().join(ENV_FILE_NAME);
+
+        let rendered = render_env_file("A=1", &HashMap::new()).unwrap();
+        assert!(apply_env_file(dir.path(), Some(&rendered)).await.unwrap());
+        assert_eq!(std::fs::read_to_string(&env_path).unwrap(), rendered);
+        let exclude = std::fs::read_to_string(dir.path().join(".git/info/exclude")).unwrap();
+        assert!(exclude.lines().any(|line| line == ENV_FILE_NAME));
+
+        // Removing the template removes the managed file
+        assert!(apply_env_file(dir.path(), None).await.unwrap());
+        assert!(!env_path.exists());
+
+        // A user-owned file is never touched
+        std::fs::write(&env_path, "MINE=1\n").unwrap();
+        assert!(!apply_env_file(dir.path(), Some(&rendered)).await.unwrap());
+        assert_eq!(std::fs::read_to_string(&env_path).unwrap(), "MINE=1\n");
+    }
+}
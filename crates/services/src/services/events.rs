@@ -295,9 +295,12 @@ impl EventService {
                 if let Ok(Some(task_with_status)) =
                     Task::find_by_id_with_attempt_status(&db.pool, task.id).await
                 {
+                    // A task restored from the trash is new to subscribers, so
+                    // updates use `add`, which also replaces an existing entry
                     let patch = match operation {
-                        SqliteOperation::Insert => task_patch::add(&task_with_status),
-                        SqliteOperation::Update => task_patch::replace(&task_with_status),
+                        SqliteOperation::Insert | SqliteOperation::Update => {
+                            task_patch::add(&task_with_status)
+                        }
                         _ => task_patch::replace(&task_with_status), // fallback
                     };
                     msg_store.push_patch(patch);
@@ -327,6 +330,15 @@ impl EventService {
 
                     return;
                 }
+
+                // Not found as a live task: it was moved to the trash
+                msg_store.push_patch(task_patch::remove(task.id));
+                if let Ok(Some(project_with_counts)) =
+                    Project::find_by_id_with_task_counts(&db.pool, task.project_id).await
+                {
+                    msg_store.push_patch(project_with_counts_patch::replace(&project_with_counts));
+                }
+                return;
             }
             RecordTypes::DeletedTask {
                 task_id: Some(task_id),
@@ -337,9 +349,13 @@ impl EventService {
                 return;
             }
             RecordTypes::Project(project) => {
+                let trashed = matches!(Project::find_by_id(&db.pool, project.id).await, Ok(None));
                 let patch = match operation {
-                    SqliteOperation::Insert => project_patch::add(project),
-                    SqliteOperation::Update => project_patch::replace(project),
+                    _ if trashed => project_patch::remove(project.id),
+                    // `add` so a project restored from the trash reappears
+                    SqliteOperation::Insert | SqliteOperation::Update => {
+                        project_patch::add(project)
+                    }
                     _ => project_patch::replace(project),
                 };
                 msg_store.push_patch(patch);
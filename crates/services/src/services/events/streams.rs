@@ -7,7 +7,7 @@ use db::models::{
     execution_process::ExecutionProcess,
     execution_queue::ExecutionQueue,
     gantt::GanttTask,
-    notification::Notification,
+    notification::{Notification, NotificationFilter},
     project::{Project, ProjectWithTaskCounts},
     scratch::Scratch,
     session::Session,
@@ -597,7 +597,7 @@ impl EventService {
         }
 
         // Get initial snapshot of projects
-        let projects = Project::find_all(&self.db.pool).await?;
+        let projects = Project::find_all(&self.db.pool, false).await?;
         let initial_msg = build_projects_snapshot(projects);
 
         let db_pool = self.db.pool.clone();
@@ -623,7 +623,7 @@ impl EventService {
                                 "projects stream lagged; resyncing snapshot"
                             );
 
-                            match Project::find_all(&db_pool).await {
+                            match Project::find_all(&db_pool, false).await {
                                 Ok(projects) => Some(Ok(build_projects_snapshot(projects))),
                                 Err(err) => {
                                     tracing::error!(
@@ -1597,6 +1597,7 @@ impl EventService {
     pub async fn stream_notifications_raw(
         &self,
         project_id: Option<Uuid>,
+        filter: NotificationFilter,
         include_snapshot: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
     {
@@ -1621,11 +1622,13 @@ impl EventService {
         }
 
         let db_pool = self.db.pool.clone();
+        let stream_filter = filter.clone();
 
-        // Get filtered event stream (notifications only, filtered by project_id)
+        // Get filtered event stream (notifications only, filtered by project_id and `filter`)
         let filtered_stream =
             BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
                 let db_pool = db_pool.clone();
+                let filter = stream_filter.clone();
                 async move {
                     match msg_result {
                         Ok(LogMsg::JsonPatch(patch)) => {
@@ -1639,6 +1642,7 @@ impl EventService {
                                             serde_json::from_value::<Notification>(op.value.clone())
                                         {
                                             notification.project_id == project_id
+                                                && filter.matches(&notification)
                                         } else {
                                             false
                                         }
@@ -1648,6 +1652,7 @@ impl EventService {
                                             serde_json::from_value::<Notification>(op.value.clone())
                                         {
                                             notification.project_id == project_id
+                                                && filter.matches(&notification)
                                         } else {
                                             false
                                         }
@@ -1676,9 +1681,10 @@ impl EventService {
                             );
                             // Resync with full snapshot on lag
                             let notifications = if let Some(pid) = project_id {
-                                Notification::find_by_project_id(&db_pool, pid, Some(100)).await
+                                Notification::find_by_project_id(&db_pool, pid, &filter, Some(100))
+                                    .await
                             } else {
-                                Notification::find_global(&db_pool, Some(100)).await
+                                Notification::find_global(&db_pool, &filter, Some(100)).await
                             };
                             match notifications {
                                 Ok(notifs) => Some(Ok(build_notifications_snapshot(notifs))),
@@ -1703,9 +1709,9 @@ impl EventService {
 
         // Get initial snapshot of notifications (last 100)
         let notifications = if let Some(pid) = project_id {
-            Notification::find_by_project_id(&self.db.pool, pid, Some(100)).await?
+            Notification::find_by_project_id(&self.db.pool, pid, &filter, Some(100)).await?
         } else {
-            Notification::find_global(&self.db.pool, Some(100)).await?
+            Notification::find_global(&self.db.pool, &filter, Some(100)).await?
         };
         let initial_msg = build_notifications_snapshot(notifications);
 
@@ -0,0 +1,209 @@
+//! Classification of failed executions as transient, and the backoff before
+//! each automatic retry.
+//!
+//! Agents report rate limits, dropped connections and crashes on stderr
+//! before exiting non-zero, so that is inspected first; a retry policy can
+//! also list exit codes to retry on. Anything not matched is treated as a
+//! real failure and left for the user.
+
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
+
+use db::models::execution_process_retry::RetryReason;
+use executors::profile::ExecutorProfileId;
+use regex::Regex;
+
+use crate::services::config::RetryPolicyConfig;
+
+/// Crashes later than this are more likely caused by the task than by the
+/// agent failing to start, so they are not retried
+pub const STARTUP_WINDOW: Duration = Duration::from_secs(60);
+
+/// Matched lines are stored for display; keep pathological ones short
+const MAX_MATCHED_LINE_LEN: usize = 500;
+
+pub(crate) static RATE_LIMIT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)rate[ _-]?limit|too many requests|overloaded|resource[ _]exhausted|\b(?:status|error|http|code)[: ]*(?:429|529)\b",
+    )
+    .expect("valid rate limit regex")
+});
+
+pub(crate) static NETWORK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:ECONNRESET|ECONNREFUSED|ETIMEDOUT|ENOTFOUND|EAI_AGAIN|ENETUNREACH|EPIPE)\b|socket hang up|fetch failed|network error|connection (?:reset|refused|closed)|\b(?:502 bad gateway|503 service unavailable|504 gateway timeout)\b",
+    )
+    .expect("valid network regex")
+});
+
+static CRASH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)segmentation fault|core dumped|\bSIG(?:SEGV|BUS|ABRT)\b|panicked at")
+        .expect("valid crash regex")
+});
+
+/// A failure worth retrying, and the stderr line that showed it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransientFailure {
+    pub reason: RetryReason,
+    pub matched_line: String,
+}
+
+/// Classify a failed execution from its stderr. Rate limits and network
+/// errors win over crashes, which only count within [`STARTUP_WINDOW`].
+pub fn classify_failure(stderr: &str, runtime: Duration) -> Option<TransientFailure> {
+    let mut patterns = vec![
+        (RetryReason::RateLimit, &*RATE_LIMIT_RE),
+        (RetryReason::Network, &*NETWORK_RE),
+    ];
+    if runtime <= STARTUP_WINDOW {
+        patterns.push((RetryReason::StartupCrash, &*CRASH_RE));
+    }
+
+    patterns.into_iter().find_map(|(reason, re)| {
+        stderr
+            .lines()
+            .rev()
+            .find(|line| re.is_match(line))
+            .map(|line| TransientFailure {
+                reason,
+                matched_line: line.trim().chars().take(MAX_MATCHED_LINE_LEN).collect(),
+            })
+    })
+}
+
+/// Classify a failed execution from its exit code, for codes `policy` lists
+/// as transient
+pub fn classify_exit_code(
+    policy: &RetryPolicyConfig,
+    exit_code: Option<i64>,
+) -> Option<TransientFailure> {
+    let code = exit_code?;
+    policy
+        .retry_on_exit_codes
+        .contains(&code)
+        .then(|| TransientFailure {
+            reason: RetryReason::ExitCode,
+            matched_line: format!("Exited with code {code}"),
+        })
+}
+
+/// The policy for executions of `profile`: the override for its variant,
+/// else the one for its executor, else `default`
+pub fn policy_for_profile<'a>(
+    default: &'a RetryPolicyConfig,
+    overrides: &'a HashMap<String, RetryPolicyConfig>,
+    profile: &ExecutorProfileId,
+) -> &'a RetryPolicyConfig {
+    overrides
+        .get(&profile.to_string())
+        .or_else(|| overrides.get(&profile.executor.to_string()))
+        .unwrap_or(default)
+}
+
+/// Delay before retry number `attempt` (1-based), or None when the policy
+/// does not allow it
+pub fn retry_delay(policy: &RetryPolicyConfig, attempt: u32) -> Option<Duration> {
+    if !policy.enabled || attempt == 0 || attempt > policy.max_attempts {
+        return None;
+    }
+    let secs = (policy.initial_backoff_secs as u64)
+        .saturating_mul(1 << (attempt - 1).min(16))
+        .min(policy.max_backoff_secs as u64);
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    #[test]
+    fn classifies_transient_stderr() {
+        let quick = Duration::from_secs(5);
+        let cases = [
+            (
+                "API Error: 429 {\"type\":\"rate_limit_error\"}",
+                RetryReason::RateLimit,
+            ),
+            ("Error: status 529 overloaded", RetryReason::RateLimit),
+            ("request failed: read ECONNRESET", RetryReason::Network),
+            ("TypeError: fetch failed", RetryReason::Network),
+            (
+                "Segmentation fault (core dumped)",
+                RetryReason::StartupCrash,
+            ),
+        ];
+        for (stderr, reason) in cases {
+            let failure = classify_failure(&format!("starting\n{stderr}\n"), quick).unwrap();
+            assert_eq!(failure.reason, reason, "{stderr}");
+            assert_eq!(failure.matched_line, stderr);
+        }
+
+        assert!(classify_failure("error: file not found: src/main.rs:429", quick).is_none());
+        assert!(classify_failure("Segmentation fault", Duration::from_secs(600)).is_none());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicyConfig {
+            enabled: true,
+            max_attempts: 4,
+            initial_backoff_secs: 10,
+            max_backoff_secs: 30,
+            retry_on_exit_codes: Vec::new(),
+        };
+        let delays: Vec<Option<u64>> = (0..=5)
+            .map(|attempt| retry_delay(&policy, attempt).map(|d| d.as_secs()))
+            .collect();
+        assert_eq!(
+            delays,
+            vec![None, Some(10), Some(20), Some(30), Some(30), None]
+        );
+
+        let disabled = RetryPolicyConfig {
+            enabled: false,
+            ..policy
+        };
+        assert_eq!(retry_delay(&disabled, 1), None);
+    }
+
+    #[test]
+    fn picks_the_most_specific_profile_override() {
+        let default = RetryPolicyConfig::default();
+        let executor_wide = RetryPolicyConfig {
+            max_attempts: 5,
+            ..RetryPolicyConfig::default()
+        };
+        let plan_variant = RetryPolicyConfig {
+            retry_on_exit_codes: vec![75],
+            ..RetryPolicyConfig::default()
+        };
+        let overrides = HashMap::from([
+            ("CLAUDE_CODE".to_string(), executor_wide),
+            ("CLAUDE_CODE:PLAN".to_string(), plan_variant),
+        ]);
+
+        let profile = |variant: Option<&str>| ExecutorProfileId {
+            executor: BaseCodingAgent::ClaudeCode,
+            variant: variant.map(str::to_string),
+        };
+        let plan = policy_for_profile(&default, &overrides, &profile(Some("PLAN")));
+        assert_eq!(plan.retry_on_exit_codes, vec![75]);
+        assert_eq!(
+            classify_exit_code(plan, Some(75)).map(|f| f.reason),
+            Some(RetryReason::ExitCode)
+        );
+        assert!(classify_exit_code(plan, Some(1)).is_none());
+
+        let other_variant = policy_for_profile(&default, &overrides, &profile(None));
+        assert_eq!(other_variant.max_attempts, 5);
+        let codex = ExecutorProfileId {
+            executor: BaseCodingAgent::Codex,
+            variant: None,
+        };
+        assert_eq!(
+            policy_for_profile(&default, &overrides, &codex).max_attempts,
+            default.max_attempts
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Why a failed execution failed, and what to do about it.
+//!
+//! Agents and scripts report most failures on stderr before exiting
+//! non-zero. The last line matching a known pattern decides the reason; the
+//! patterns are checked from the most to the least specific, so "no space
+//! left on device" in a network error's message still reads as a full disk.
+//! Exit code 127 is the shell's "command not found". Failures that match
+//! nothing are left unclassified.
+
+use std::sync::LazyLock;
+
+use db::models::execution_process_failure::FailureReason;
+use executors::executors::BaseCodingAgent;
+use regex::Regex;
+
+use crate::services::execution_retry::{NETWORK_RE, RATE_LIMIT_RE};
+
+/// Matched lines are stored for display; keep pathological ones short
+const MAX_MATCHED_LINE_LEN: usize = 500;
+
+/// Exit code of a shell asked to run a command it can't find
+const COMMAND_NOT_FOUND_EXIT_CODE: i64 = 127;
+
+static DISK_FULL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)no space left on device|\bENOSPC\b|disk (?:is )?full|disk quota exceeded")
+        .expect("valid disk full regex")
+});
+
+static AUTH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\bunauthori[sz]ed\b|invalid (?:api|x-api)[ _-]?key|authentication (?:failed|error|required)|(?:token|session|credentials?) (?:has |have )?expired|not (?:logged|signed) in|please (?:log ?in|sign in|run .*login)|\b(?:status|error|http|code)[: ]*401\b",
+    )
+    .expect("valid auth regex")
+});
+
+static CLI_NOT_FOUND_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)command not found|is not recognized as an internal or external command|spawn \S+ ENOENT|executable (?:file )?not found|could not determine executable to run",
+    )
+    .expect("valid command not found regex")
+});
+
+static MERGE_CONFLICT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)merge conflict|^CONFLICT \(|automatic merge failed|\bneeds merge\b|unmerged (?:files|paths)|could not apply [0-9a-f]{7,}",
+    )
+    .expect("valid merge conflict regex")
+});
+
+/// A classified failure, the line that showed it and a suggested fix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassifiedFailure {
+    pub reason: FailureReason,
+    pub matched_line: String,
+    pub suggested_fix: String,
+}
+
+/// Classify a failed execution from its stderr and exit code. `executor` is
+/// the coding agent that ran, if any, for agent-specific fixes.
+pub fn classify_execution_failure(
+    stderr: &str,
+    exit_code: Option<i64>,
+    executor: Option<BaseCodingAgent>,
+) -> Option<ClassifiedFailure> {
+    let patterns = [
+        (FailureReason::DiskFull, &*DISK_FULL_RE),
+        (FailureReason::AuthExpired, &*AUTH_RE),
+        (FailureReason::RateLimited, &*RATE_LIMIT_RE),
+        (FailureReason::CliNotFound, &*CLI_NOT_FOUND_RE),
+        (FailureReason::MergeConflict, &*MERGE_CONFLICT_RE),
+        (FailureReason::Network, &*NETWORK_RE),
+    ];
+
+    let matched = patterns.into_iter().find_map(|(reason, re)| {
+        stderr
+            .lines()
+            .rev()
+            .find(|line| re.is_match(line.trim()))
+            .map(|line| {
+                (
+                    reason,
+                    line.trim().chars().take(MAX_MATCHED_LINE_LEN).collect(),
+                )
+            })
+    });
+    let (reason, matched_line) = matched.or_else(|| {
+        (exit_code == Some(COMMAND_NOT_FOUND_EXIT_CODE)).then(|| {
+            (
+                FailureReason::CliNotFound,
+                format!("Exited with code {COMMAND_NOT_FOUND_EXIT_CODE}"),
+            )
+        })
+    })?;
+
+    Some(ClassifiedFailure {
+        reason,
+        matched_line,
+        suggested_fix: suggested_fix(reason, executor),
+    })
+}
+
+/// What the user can do about a failure of `reason`
+pub fn suggested_fix(reason: FailureReason, executor: Option<BaseCodingAgent>) -> String {
+    match reason {
+        FailureReason::AuthExpired => match login_command(executor) {
+            Some(command) => {
+                format!("Sign in again by running `{command}` in a terminal, then retry.")
+            }
+            None => {
+                "Sign in to the agent's CLI again or update its API key, then retry.".to_string()
+            }
+        },
+        FailureReason::RateLimited => "The provider is limiting requests. Wait a few minutes, \
+             or lower the concurrent agent limit or set a provider rate limit in settings."
+            .to_string(),
+        FailureReason::DiskFull => "The disk is full. Free up space, e.g. by emptying the \
+             trash or deleting old worktrees, then retry."
+            .to_string(),
+        FailureReason::CliNotFound => "A command the execution needs isn't installed. Install \
+             it, or make sure it is on the PATH the app was started with, then retry."
+            .to_string(),
+        FailureReason::MergeConflict => "Resolve the conflicts in the worktree, or rebase the \
+             branch onto its target branch, then retry."
+            .to_string(),
+        FailureReason::Network => {
+            "Check the network connection and proxy settings, then retry.".to_string()
+        }
+        FailureReason::GitHooks => "The changes were committed without the repo's git hooks. \
+             Fix what the hooks reported, or ask the agent to."
+            .to_string(),
+    }
+}
+
+fn login_command(executor: Option<BaseCodingAgent>) -> Option<&'static str> {
+    match executor? {
+        BaseCodingAgent::ClaudeCode => Some("claude"),
+        BaseCodingAgent::Codex => Some("codex login"),
+        BaseCodingAgent::Gemini => Some("gemini"),
+        BaseCodingAgent::Amp => Some("amp login"),
+        BaseCodingAgent::CursorAgent => Some("cursor-agent login"),
+        BaseCodingAgent::Opencode => Some("opencode auth login"),
+        BaseCodingAgent::Copilot => Some("copilot"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(stderr: &str, exit_code: Option<i64>) -> Option<FailureReason> {
+        classify_execution_failure(stderr, exit_code, None).map(|failure| failure.reason)
+    }
+
+    #[test]
+    fn classifies_common_failures() {
+        assert_eq!(
+            reason("Error: Invalid API key · Please run /login", Some(1)),
+            Some(FailureReason::AuthExpired)
+        );
+        assert_eq!(
+            reason("API Error: 429 rate_limit_error", Some(1)),
+            Some(FailureReason::RateLimited)
+        );
+        assert_eq!(
+            reason("write failed: No space left on device", Some(1)),
+            Some(FailureReason::DiskFull)
+        );
+        assert_eq!(
+            reason("sh: 1: pnpm: command not found", Some(127)),
+            Some(FailureReason::CliNotFound)
+        );
+        assert_eq!(reason("", Some(127)), Some(FailureReason::CliNotFound));
+        assert_eq!(
+            reason(
+                "CONFLICT (content): Merge conflict in src/main.rs\nAutomatic merge failed",
+                Some(1)
+            ),
+            Some(FailureReason::MergeConflict)
+        );
+        assert_eq!(
+            reason("Error: socket hang up", Some(1)),
+            Some(FailureReason::Network)
+        );
+        assert_eq!(reason("assertion failed: left == right", Some(101)), None);
+    }
+
+    #[test]
+    fn suggests_the_agents_login_command() {
+        let failure = classify_execution_failure(
+            "Your session has expired",
+            Some(1),
+            Some(BaseCodingAgent::Codex),
+        )
+        .unwrap();
+        assert_eq!(failure.reason, FailureReason::AuthExpired);
+        assert!(failure.suggested_fix.contains("`codex login`"));
+    }
+}
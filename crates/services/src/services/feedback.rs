@@ -159,6 +159,9 @@ Be specific and actionable in your feedback. If a category doesn't apply, set it
     /// * `session_id` - The session ID to continue the conversation
     /// * `executor_profile_id` - The executor profile to use
     /// * `working_dir` - Optional working directory for the agent
+    /// * `custom_prompt` - Optional prompt sent instead of the default one, e.g. a
+    ///   rendered prompt template. Its response must still be the JSON object
+    ///   that `parse_feedback_response` expects.
     ///
     /// # Returns
     /// An `ExecutorAction` configured to send the feedback prompt
@@ -166,9 +169,10 @@ Be specific and actionable in your feedback. If a category doesn't apply, set it
         session_id: String,
         executor_profile_id: ExecutorProfileId,
         working_dir: Option<String>,
+        custom_prompt: Option<String>,
     ) -> ExecutorAction {
         let follow_up = CodingAgentFollowUpRequest {
-            prompt: Self::generate_feedback_prompt(),
+            prompt: custom_prompt.unwrap_or_else(Self::generate_feedback_prompt),
             session_id,
             executor_profile_id,
             working_dir,
@@ -387,6 +391,7 @@ Let me know if you need more details."#;
             session_id.clone(),
             profile_id.clone(),
             working_dir.clone(),
+            None,
         );
 
         // Verify the action is a follow-up request
@@ -0,0 +1,256 @@
+//! Per-project rules on which files an agent may change.
+//!
+//! Rules are gitignore-style patterns. A `deny` rule forbids changing
+//! matching paths; once any `allow_only` rule applies to a task, every path
+//! the agent changes must match one of them. A rule with a task label only
+//! applies to tasks tagged `#label` in their title or description, so
+//! "only tests for #test-only tasks" needs no extra task field.
+
+use db::models::{
+    project_file_rule::{FileRuleEffect, ProjectFileRule},
+    task::Task,
+};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+const FEEDBACK_HEADER: &str = "You changed files this project does not allow you to change.";
+
+/// A changed path that breaks a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRuleViolation {
+    /// Repo the path belongs to
+    pub repo_name: String,
+    pub path: String,
+    pub effect: FileRuleEffect,
+    /// The denying pattern, or the allowed patterns joined with `, `
+    pub pattern: String,
+    pub reason: Option<String>,
+}
+
+/// The rules that apply to one task, compiled for matching
+pub struct FileRules {
+    deny: Vec<(ProjectFileRule, Gitignore)>,
+    allow_only: Vec<(ProjectFileRule, Gitignore)>,
+}
+
+impl FileRules {
+    /// Compile the rules that apply to `task`. None when none do. Rules with
+    /// invalid patterns are skipped; they are rejected when saved.
+    pub fn for_task(rules: &[ProjectFileRule], task: &Task) -> Option<Self> {
+        let mut deny = Vec::new();
+        let mut allow_only = Vec::new();
+        for rule in rules {
+            if let Some(label) = &rule.task_label
+                && !task_has_label(task, label)
+            {
+                continue;
+            }
+            let Ok(matcher) = compile(&rule.pattern) else {
+                tracing::warn!("Skipping file rule with invalid pattern '{}'", rule.pattern);
+                continue;
+            };
+            match rule.effect {
+                FileRuleEffect::Deny => deny.push((rule.clone(), matcher)),
+                FileRuleEffect::AllowOnly => allow_only.push((rule.clone(), matcher)),
+            }
+        }
+        if deny.is_empty() && allow_only.is_empty() {
+            return None;
+        }
+        Some(Self { deny, allow_only })
+    }
+
+    /// Violations among `paths`, which are relative to the root of
+    /// `repo_name`
+    pub fn check(&self, repo_name: &str, paths: &[String]) -> Vec<FileRuleViolation> {
+        let mut violations = Vec::new();
+        for path in paths {
+            if let Some((rule, _)) = self
+                .deny
+                .iter()
+                .find(|(_, matcher)| is_match(matcher, path))
+            {
+                violations.push(FileRuleViolation {
+                    repo_name: repo_name.to_string(),
+                    path: path.clone(),
+                    effect: FileRuleEffect::Deny,
+                    pattern: rule.pattern.clone(),
+                    reason: rule.reason.clone(),
+                });
+                continue;
+            }
+            if !self.allow_only.is_empty()
+                && !self
+                    .allow_only
+                    .iter()
+                    .any(|(_, matcher)| is_match(matcher, path))
+            {
+                let patterns: Vec<&str> = self
+                    .allow_only
+                    .iter()
+                    .map(|(rule, _)| rule.pattern.as_str())
+                    .collect();
+                let reasons: Vec<&str> = self
+                    .allow_only
+                    .iter()
+                    .filter_map(|(rule, _)| rule.reason.as_deref())
+                    .collect();
+                violations.push(FileRuleViolation {
+                    repo_name: repo_name.to_string(),
+                    path: path.clone(),
+                    effect: FileRuleEffect::AllowOnly,
+                    pattern: patterns.join(", "),
+                    reason: (!reasons.is_empty()).then(|| reasons.join(" ")),
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Error message when `pattern` is not a valid gitignore-style pattern
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() {
+        return Err("Pattern cannot be empty".to_string());
+    }
+    compile(pattern).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Whether the task's title or description contains `#label` as a word
+pub fn task_has_label(task: &Task, label: &str) -> bool {
+    let tag = format!("#{}", label.trim_start_matches('#'));
+    let has_tag = |text: &str| {
+        text.match_indices(&tag).any(|(start, _)| {
+            let before = text[..start].chars().next_back();
+            let after = text[start + tag.len()..].chars().next();
+            !before.is_some_and(is_label_char) && !after.is_some_and(is_label_char)
+        })
+    };
+    has_tag(&task.title) || task.description.as_deref().is_some_and(has_tag)
+}
+
+/// The follow-up sent after the agent is interrupted for `violations`
+pub fn feedback_prompt(violations: &[FileRuleViolation]) -> String {
+    let mut prompt = format!(
+        "{FEEDBACK_HEADER}\n\nYour run was stopped. Revert your changes to the files below, \
+         then continue the task without touching them again.\n\n"
+    );
+    for violation in violations {
+        let rule = match violation.effect {
+            FileRuleEffect::Deny => {
+                format!("matches the forbidden pattern `{}`", violation.pattern)
+            }
+            FileRuleEffect::AllowOnly => {
+                format!("is outside the allowed patterns `{}`", violation.pattern)
+            }
+        };
+        prompt.push_str(&format!(
+            "- `{}/{}` {rule}",
+            violation.repo_name, violation.path
+        ));
+        if let Some(reason) = &violation.reason {
+            prompt.push_str(&format!(": {reason}"));
+        }
+        prompt.push('\n');
+    }
+    prompt
+}
+
+/// Whether `prompt` is a [`feedback_prompt`]; its run is not interrupted
+/// into another follow-up so an agent that cannot comply does not loop
+pub fn is_feedback_prompt(prompt: &str) -> bool {
+    prompt.starts_with(FEEDBACK_HEADER)
+}
+
+fn compile(pattern: &str) -> Result<Gitignore, ignore::Error> {
+    let mut builder = GitignoreBuilder::new("");
+    builder.add_line(None, pattern.trim())?;
+    builder.build()
+}
+
+fn is_match(matcher: &Gitignore, path: &str) -> bool {
+    matcher.matched_path_or_any_parents(path, false).is_ignore()
+}
+
+fn is_label_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::{TaskPriority, TaskStatus};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn rule(pattern: &str, effect: FileRuleEffect, task_label: Option<&str>) -> ProjectFileRule {
+        ProjectFileRule {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            pattern: pattern.to_string(),
+            effect,
+            task_label: task_label.map(str::to_string),
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn task(title: &str) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            title: title.to_string(),
+            description: None,
+            status: TaskStatus::InProgress,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            task_group_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_blocked: false,
+            has_in_progress_attempt: false,
+            last_attempt_failed: false,
+            is_queued: false,
+            last_executor: String::new(),
+            needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
+        }
+    }
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    #[test]
+    fn deny_rules_flag_matching_paths() {
+        let rules = [rule("*.sql", FileRuleEffect::Deny, None)];
+        let rules = FileRules::for_task(&rules, &task("Add users")).unwrap();
+
+        let violations = rules.check("app", &paths(&["migrations/001_users.sql", "src/users.rs"]));
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "migrations/001_users.sql");
+        assert_eq!(violations[0].pattern, "*.sql");
+    }
+
+    #[test]
+    fn allow_only_rules_apply_to_labeled_tasks() {
+        let rules = [
+            rule("tests/", FileRuleEffect::AllowOnly, Some("test-only")),
+            rule("*_test.rs", FileRuleEffect::AllowOnly, Some("test-only")),
+        ];
+        assert!(FileRules::for_task(&rules, &task("Cover parser #test-onlyish")).is_none());
+
+        let rules = FileRules::for_task(&rules, &task("Cover parser #test-only")).unwrap();
+        let violations = rules.check(
+            "app",
+            &paths(&["tests/parser.rs", "src/parser_test.rs", "src/parser.rs"]),
+        );
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "src/parser.rs");
+        assert_eq!(violations[0].pattern, "tests/, *_test.rs");
+    }
+}
@@ -10,6 +10,7 @@ use db::models::{
     project_repo::ProjectRepo,
 };
 use fst::{Map, MapBuilder};
+use futures::StreamExt;
 use ignore::WalkBuilder;
 use moka::future::Cache;
 use notify::{RecommendedWatcher, RecursiveMode};
@@ -20,16 +21,23 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 use super::{
     file_ranker::{FileRanker, FileStats},
+    filesystem_watcher::WatcherBackend,
     git::GitService,
+    watchman::{self, WatchmanSubscription},
 };
 
+/// How long to wait after a watchman-reported file add/remove before rebuilding
+/// the index, so bursts (checkouts, builds) collapse into a single rebuild.
+const WATCHMAN_REBUILD_COALESCE: Duration = Duration::from_secs(2);
+
 /// Search mode for different use cases
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "lowercase")]
-#[derive(Default)]
+#[derive(Default, ToSchema)]
 pub enum SearchMode {
     #[default]
     TaskForm, // Default: exclude ignored files (clean results)
@@ -37,7 +45,8 @@ pub enum SearchMode {
 }
 
 /// Search query parameters for typed Axum extraction
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
     pub q: String,
     #[serde(default)]
@@ -98,6 +107,7 @@ pub struct FileSearchCache {
     file_ranker: FileRanker,
     build_queue: mpsc::UnboundedSender<PathBuf>,
     watchers: DashMap<PathBuf, RecommendedWatcher>,
+    watchman_subscriptions: DashMap<PathBuf, WatchmanSubscription>,
 }
 
 impl FileSearchCache {
@@ -133,6 +143,7 @@ impl FileSearchCache {
             file_ranker,
             build_queue: build_sender,
             watchers: DashMap::new(),
+            watchman_subscriptions: DashMap::new(),
         }
     }
 
@@ -439,6 +450,7 @@ impl FileSearchCache {
                 file_ranker: file_ranker.clone(),
                 build_queue: mpsc::unbounded_channel().0, // Dummy sender
                 watchers: DashMap::new(),
+                watchman_subscriptions: DashMap::new(),
             };
 
             match cache_builder.build_repo_cache(&repo_path).await {
@@ -507,8 +519,65 @@ impl FileSearchCache {
         });
 
         info!("Setup file watcher for repo: {:?}", repo_path);
+
+        if WatcherBackend::detect() == WatcherBackend::Watchman {
+            self.setup_watchman_refresh(&repo_path_buf).await;
+        }
+
         Ok(())
     }
+
+    /// Rebuild the index when files are added or removed, not only on HEAD changes.
+    /// Only enabled with the watchman backend, where watching the full tree is cheap.
+    async fn setup_watchman_refresh(&self, repo_path: &Path) {
+        if self.watchman_subscriptions.contains_key(repo_path) {
+            return;
+        }
+
+        let path_for_watch = repo_path.to_path_buf();
+        let (subscription, mut rx, _) = match tokio::task::spawn_blocking(move || {
+            watchman::async_watcher(path_for_watch)
+        })
+        .await
+        {
+            Ok(Ok(parts)) => parts,
+            Ok(Err(e)) => {
+                warn!("Failed to start watchman for {:?}: {}", repo_path, e);
+                return;
+            }
+            Err(e) => {
+                warn!("Watchman setup task failed for {:?}: {}", repo_path, e);
+                return;
+            }
+        };
+
+        let build_queue = self.build_queue.clone();
+        let watched_path = repo_path.to_path_buf();
+        tokio::spawn(async move {
+            while let Some(result) = rx.next().await {
+                let changes_file_set = result.is_ok_and(|events| {
+                    events
+                        .iter()
+                        .any(|event| event.kind.is_create() || event.kind.is_remove())
+                });
+                if !changes_file_set {
+                    continue;
+                }
+
+                tokio::time::sleep(WATCHMAN_REBUILD_COALESCE).await;
+                while let Ok(Some(_)) = rx.try_next() {}
+
+                if let Err(e) = build_queue.send(watched_path.clone()) {
+                    error!("Failed to enqueue cache refresh: {}", e);
+                    break;
+                }
+            }
+        });
+
+        self.watchman_subscriptions
+            .insert(repo_path.to_path_buf(), subscription);
+        info!("Setup watchman index refresh for repo: {:?}", repo_path);
+    }
 }
 
 impl Default for FileSearchCache {
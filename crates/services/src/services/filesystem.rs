@@ -9,6 +9,7 @@ use serde::Serialize;
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 #[derive(Clone)]
 pub struct FilesystemService {}
@@ -22,13 +23,13 @@ pub enum FilesystemError {
     #[error("Failed to read directory: {0}")]
     Io(#[from] std::io::Error),
 }
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct DirectoryListResponse {
     pub entries: Vec<DirectoryEntry>,
     pub current_path: String,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct DirectoryEntry {
     pub name: String,
     pub path: PathBuf,
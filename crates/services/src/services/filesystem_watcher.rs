@@ -22,6 +22,8 @@ use notify_debouncer_full::{
 };
 use thiserror::Error;
 
+use crate::services::watchman::{self, WatchmanSubscription};
+
 pub type WatcherComponents = (
     Arc<Mutex<Debouncer<RecommendedWatcher, RecommendedCache>>>,
     Receiver<DebounceEventResult>,
@@ -42,7 +44,7 @@ pub enum FilesystemWatcherError {
     InvalidPath(String),
 }
 
-fn canonicalize_lossy(path: &Path) -> PathBuf {
+pub(crate) fn canonicalize_lossy(path: &Path) -> PathBuf {
     dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
 }
 
@@ -61,7 +63,7 @@ fn platform_supports_native_recursive() -> bool {
     cfg!(target_os = "macos") || cfg!(target_os = "windows")
 }
 
-fn build_gitignore_set(root: &Path) -> Result<Gitignore, FilesystemWatcherError> {
+pub(crate) fn build_gitignore_set(root: &Path) -> Result<Gitignore, FilesystemWatcherError> {
     let mut builder = GitignoreBuilder::new(root);
 
     // Walk once to collect all .gitignore files under root
@@ -158,7 +160,11 @@ fn path_allowed(path: &Path, gi: &Gitignore, canonical_root: &Path) -> bool {
     !matched.is_ignore()
 }
 
-fn debounced_should_forward(event: &DebouncedEvent, gi: &Gitignore, canonical_root: &Path) -> bool {
+pub(crate) fn debounced_should_forward(
+    event: &DebouncedEvent,
+    gi: &Gitignore,
+    canonical_root: &Path,
+) -> bool {
     // DebouncedEvent is a struct that wraps the underlying notify::Event
     if event.kind.is_access() {
         // Ignore access events
@@ -621,3 +627,60 @@ pub fn async_watcher(root: PathBuf) -> Result<WatcherComponents, FilesystemWatch
 
     Ok((debouncer, filtered_rx, canonical_root))
 }
+
+/// Filesystem watching implementation used for workspace watches.
+///
+/// `Notify` uses the platform-native watcher via `notify` (inotify on Linux,
+/// FSEvents on macOS, ReadDirectoryChangesW on Windows). `Watchman` delegates
+/// to a running watchman daemon, which scales much better on huge repos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatcherBackend {
+    Notify,
+    Watchman,
+}
+
+impl WatcherBackend {
+    /// Pick a backend from `WATCHER_BACKEND` (`notify`, `watchman` or `auto`).
+    /// `auto` (the default) uses watchman when it is installed.
+    pub fn detect() -> Self {
+        match std::env::var("WATCHER_BACKEND") {
+            Ok(v) if v.eq_ignore_ascii_case("notify") => WatcherBackend::Notify,
+            Ok(v) if v.eq_ignore_ascii_case("watchman") => WatcherBackend::Watchman,
+            _ if watchman::is_available() => WatcherBackend::Watchman,
+            _ => WatcherBackend::Notify,
+        }
+    }
+}
+
+/// Keeps the underlying watcher alive; dropping it stops the watch.
+pub enum WatcherGuard {
+    Notify(Arc<Mutex<Debouncer<RecommendedWatcher, RecommendedCache>>>),
+    Watchman(WatchmanSubscription),
+}
+
+pub type BackendWatcherComponents = (WatcherGuard, Receiver<DebounceEventResult>, PathBuf);
+
+/// Start a watcher using the requested backend.
+/// If the watchman backend fails to start, falls back to notify transparently.
+pub fn async_watcher_with_backend(
+    root: PathBuf,
+    backend: WatcherBackend,
+) -> Result<BackendWatcherComponents, FilesystemWatcherError> {
+    if backend == WatcherBackend::Watchman {
+        match watchman::async_watcher(root.clone()) {
+            Ok((subscription, rx, canonical)) => {
+                return Ok((WatcherGuard::Watchman(subscription), rx, canonical));
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to start watchman for {:?}, falling back to notify: {}",
+                    root,
+                    e
+                );
+            }
+        }
+    }
+
+    let (debouncer, rx, canonical) = async_watcher(root)?;
+    Ok((WatcherGuard::Notify(debouncer), rx, canonical))
+}
@@ -1,16 +1,26 @@
 use std::{collections::HashMap, path::Path};
 
 use chrono::{DateTime, Utc};
+use db::models::git_hooks_policy::GitHooksMode;
 use git2::{BranchType, Error as GitError, Reference, Remote, Repository};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
-use utils::diff::{Diff, DiffChangeKind, compute_line_change_counts};
+use utils::{
+    diff::{Diff, DiffChangeKind, DiffFileMode, compute_line_change_counts},
+    path::get_vibe_kanban_temp_dir,
+};
+use utoipa::ToSchema;
 
 mod cli;
+mod conflicts;
 
 use cli::{ChangeType, StatusDiffEntry, StatusDiffOptions};
-pub use cli::{GitCli, GitCliError, WorktreeEntry};
+pub use cli::{CommitHooks, GitCli, GitCliError, WorktreeEntry};
+pub use conflicts::{
+    ConflictHunk, ConflictMarkerError, ConflictResolution, ConflictedFile, HunkResolution,
+    apply_hunk_resolutions, parse_conflict_hunks,
+};
 
 use super::gix_reader::{DiffChangeType, FileStat, GixReader, GixReaderError, TreeDiffEntry};
 use crate::services::github::GitHubRepoInfo;
@@ -37,18 +47,34 @@ pub enum GitServiceError {
     WorktreeDirty(String, String),
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error("A {} is in progress; resolve or abort it before retrying", .0.command())]
+    OperationInProgress(ConflictOp),
     #[error("Nothing to merge: {0}")]
     NothingToMerge(String),
+    #[error("Cannot resolve conflicts: {0}")]
+    ConflictResolution(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
 pub struct GitService {}
 
-// Max inline diff size for UI (in bytes). Files larger than this will have
-// their contents omitted from the diff stream to avoid UI crashes.
-const MAX_INLINE_DIFF_BYTES: usize = 2 * 1024 * 1024; // ~2MB
+// Default max inline diff size for UI (in bytes). Files larger than this will
+// have their contents omitted from the diff stream to avoid UI crashes.
+pub const DEFAULT_MAX_INLINE_DIFF_BYTES: usize = 2 * 1024 * 1024; // ~2MB
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq)]
+/// Upper bound for configured and per-request inline diff limits
+pub const MAX_INLINE_DIFF_BYTES_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Inline diff limit to use: the per-request override, else the configured
+/// limit, else the default, capped at [`MAX_INLINE_DIFF_BYTES_LIMIT`]
+pub fn resolve_inline_diff_limit(requested: Option<usize>, configured: Option<usize>) -> usize {
+    requested
+        .or(configured)
+        .unwrap_or(DEFAULT_MAX_INLINE_DIFF_BYTES)
+        .min(MAX_INLINE_DIFF_BYTES_LIMIT)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
 pub enum ConflictOp {
@@ -58,7 +84,38 @@ pub enum ConflictOp {
     Revert,
 }
 
-#[derive(Debug, Serialize, TS)]
+impl ConflictOp {
+    /// The git command that runs this operation
+    pub fn command(&self) -> &'static str {
+        match self {
+            ConflictOp::Rebase => "rebase",
+            ConflictOp::Merge => "merge",
+            ConflictOp::CherryPick => "cherry-pick",
+            ConflictOp::Revert => "revert",
+        }
+    }
+}
+
+/// Sentence listing up to 10 conflicted files, for conflict error messages
+fn conflicted_files_note(conflicts: &[String]) -> String {
+    if conflicts.is_empty() {
+        return String::new();
+    }
+    let shown = &conflicts[..conflicts.len().min(10)];
+    let list = shown.join(", ");
+    if conflicts.len() > shown.len() {
+        format!(
+            " Conflicted files (showing {} of {}): {}.",
+            shown.len(),
+            conflicts.len(),
+            list
+        )
+    } else {
+        format!(" Conflicted files: {list}.")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct GitBranch {
     pub name: String,
     pub is_current: bool,
@@ -76,6 +133,23 @@ pub struct HeadInfo {
 #[derive(Debug, Clone)]
 pub struct Commit(String);
 
+/// A commit made under a hooks policy
+#[derive(Debug, Clone, Default)]
+pub struct HookedCommit {
+    /// False when there was nothing to commit
+    pub committed: bool,
+    /// Output of the hooks when they failed in [`GitHooksMode::Report`]
+    pub hook_failure: Option<String>,
+}
+
+/// A squash merge made under a hooks policy
+#[derive(Debug, Clone)]
+pub struct HookedMerge {
+    pub commit: String,
+    /// Output of the hooks when they failed in [`GitHooksMode::Report`]
+    pub hook_failure: Option<String>,
+}
+
 impl Commit {
     pub fn new(id: impl ToString) -> Self {
         Self(id.to_string())
@@ -287,6 +361,17 @@ impl GitService {
     }
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
+        self.commit_with_hooks(path, message, &GitHooksMode::Run.into())
+            .map(|commit| commit.committed)
+    }
+
+    /// Stage and commit everything, treating repo-local hooks according to `hooks`.
+    pub fn commit_with_hooks(
+        &self,
+        path: &Path,
+        message: &str,
+        hooks: &CommitHooks,
+    ) -> Result<HookedCommit, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
         let git = GitCli::new();
         let has_changes = git
@@ -294,16 +379,77 @@ impl GitService {
             .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
         if !has_changes {
             tracing::debug!("No changes to commit!");
-            return Ok(false);
+            return Ok(HookedCommit::default());
         }
 
         git.add_all(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git add failed: {e}")))?;
         // Only ensure identity once we know we're about to commit
         self.ensure_cli_commit_identity(path)?;
-        git.commit(path, message)
+        let hook_failure = git
+            .commit_with_hooks(path, message, hooks)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
-        Ok(true)
+        Ok(HookedCommit {
+            committed: true,
+            hook_failure,
+        })
+    }
+
+    /// Commit only what is staged, leaving unstaged changes in the working tree.
+    pub fn commit_staged_with_hooks(
+        &self,
+        path: &Path,
+        message: &str,
+        hooks: &CommitHooks,
+    ) -> Result<HookedCommit, GitServiceError> {
+        let git = GitCli::new();
+        let has_staged = git
+            .has_staged_changes(path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))?;
+        if !has_staged {
+            tracing::debug!("No staged changes to commit!");
+            return Ok(HookedCommit::default());
+        }
+
+        self.ensure_cli_commit_identity(path)?;
+        let hook_failure = git
+            .commit_with_hooks(path, message, hooks)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git commit failed: {e}")))?;
+        Ok(HookedCommit {
+            committed: true,
+            hook_failure,
+        })
+    }
+
+    /// Replace the index with every change to `paths` plus the hunks of
+    /// `patch`, and return the staged paths. The index is left empty if
+    /// anything fails to stage.
+    pub fn stage_selection(
+        &self,
+        path: &Path,
+        paths: &[String],
+        patch: Option<&str>,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let git = GitCli::new();
+        let staged = git
+            .reset_index(path)
+            .and_then(|_| git.add_paths(path, paths))
+            .and_then(|_| match patch {
+                Some(patch) if !patch.trim().is_empty() => git.apply_cached(path, patch),
+                _ => Ok(()),
+            })
+            .and_then(|_| git.staged_paths(path));
+        staged.map_err(|e| {
+            let _ = git.reset_index(path);
+            GitServiceError::InvalidRepository(format!("git add failed: {e}"))
+        })
+    }
+
+    /// Unstage everything in the worktree
+    pub fn unstage_all(&self, path: &Path) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.reset_index(path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git reset failed: {e}")))
     }
 
     /// Get diffs between branches or worktree changes
@@ -311,6 +457,17 @@ impl GitService {
         &self,
         target: DiffTarget,
         path_filter: Option<&[&str]>,
+    ) -> Result<Vec<Diff>, GitServiceError> {
+        self.get_diffs_with_limit(target, path_filter, DEFAULT_MAX_INLINE_DIFF_BYTES)
+    }
+
+    /// Like [`Self::get_diffs`], omitting the contents of files larger than
+    /// `max_inline_bytes`
+    pub fn get_diffs_with_limit(
+        &self,
+        target: DiffTarget,
+        path_filter: Option<&[&str]>,
+        max_inline_bytes: usize,
     ) -> Result<Vec<Diff>, GitServiceError> {
         match target {
             DiffTarget::Worktree {
@@ -339,7 +496,7 @@ impl GitService {
                     })?;
                 Ok(entries
                     .into_iter()
-                    .map(|e| Self::status_entry_to_diff(&repo, &base_tree, e))
+                    .map(|e| Self::status_entry_to_diff(&repo, &base_tree, e, max_inline_bytes))
                     .collect())
             }
             DiffTarget::Branch {
@@ -383,7 +540,7 @@ impl GitService {
                     });
                 }
 
-                Self::convert_gix_diff_entries(&gix_repo, entries)
+                Self::convert_gix_diff_entries(&gix_repo, entries, max_inline_bytes)
             }
             DiffTarget::Commit {
                 repo_path,
@@ -427,7 +584,7 @@ impl GitService {
                     });
                 }
 
-                Self::convert_gix_diff_entries(&gix_repo, entries)
+                Self::convert_gix_diff_entries(&gix_repo, entries, max_inline_bytes)
             }
         }
     }
@@ -437,6 +594,7 @@ impl GitService {
     fn convert_gix_diff_entries(
         gix_repo: &gix::Repository,
         entries: Vec<TreeDiffEntry>,
+        max_inline_bytes: usize,
     ) -> Result<Vec<Diff>, GitServiceError> {
         let mut file_diffs = Vec::new();
 
@@ -456,7 +614,7 @@ impl GitService {
             // Check old blob size
             if let Some(old_oid) = entry.old_oid {
                 if let Ok(size) = GixReader::blob_size(gix_repo, old_oid) {
-                    if size > MAX_INLINE_DIFF_BYTES {
+                    if size > max_inline_bytes {
                         content_omitted = true;
                     }
                 }
@@ -465,7 +623,7 @@ impl GitService {
             // Check new blob size
             if let Some(new_oid) = entry.new_oid {
                 if let Ok(size) = GixReader::blob_size(gix_repo, new_oid) {
-                    if size > MAX_INLINE_DIFF_BYTES {
+                    if size > max_inline_bytes {
                         content_omitted = true;
                     }
                 }
@@ -489,18 +647,32 @@ impl GitService {
                 None
             };
 
-            // Detect pure permission changes (content identical, mode differs)
-            if matches!(change, DiffChangeKind::Modified) {
-                let mode_changed = entry.old_mode != entry.new_mode;
-                if mode_changed
-                    && old_content.is_some()
-                    && new_content.is_some()
-                    && old_content == new_content
-                {
-                    change = DiffChangeKind::PermissionChange;
-                }
+            let old_mode = entry.old_mode.and_then(Self::gix_file_mode);
+            let new_mode = entry.new_mode.and_then(Self::gix_file_mode);
+            let executable_changed = DiffFileMode::is_executable_flip(old_mode, new_mode);
+
+            // Detect pure permission changes (same blob, executable bit flipped)
+            if matches!(change, DiffChangeKind::Modified)
+                && executable_changed
+                && entry.old_oid == entry.new_oid
+            {
+                change = DiffChangeKind::PermissionChange;
             }
 
+            // A symlink's blob is its target
+            let old_symlink_target = match (old_mode, entry.old_oid) {
+                (Some(DiffFileMode::Symlink), Some(oid)) => {
+                    GixReader::read_blob(gix_repo, oid).ok().flatten()
+                }
+                _ => None,
+            };
+            let new_symlink_target = match (new_mode, entry.new_oid) {
+                (Some(DiffFileMode::Symlink), Some(oid)) => {
+                    GixReader::read_blob(gix_repo, oid).ok().flatten()
+                }
+                _ => None,
+            };
+
             // Compute line stats if we have content
             let (additions, deletions) = match (&old_content, &new_content) {
                 (Some(old), Some(new)) => {
@@ -529,12 +701,27 @@ impl GitService {
                 content_omitted,
                 additions,
                 deletions,
+                old_mode,
+                new_mode,
+                old_symlink_target,
+                new_symlink_target,
+                executable_changed,
             });
         }
 
         Ok(file_diffs)
     }
 
+    fn gix_file_mode(kind: gix::object::tree::EntryKind) -> Option<DiffFileMode> {
+        use gix::object::tree::EntryKind;
+        match kind {
+            EntryKind::Blob => Some(DiffFileMode::Regular),
+            EntryKind::BlobExecutable => Some(DiffFileMode::Executable),
+            EntryKind::Link => Some(DiffFileMode::Symlink),
+            EntryKind::Tree | EntryKind::Commit => None,
+        }
+    }
+
     /// Extract file path from a Diff (for indexing and ConversationPatch)
     pub fn diff_path(diff: &Diff) -> String {
         diff.new_path
@@ -555,7 +742,11 @@ impl GitService {
     }
 
     /// Helper function to read file content from filesystem with safety guards
-    fn read_file_to_string(repo: &Repository, rel_path: &Path) -> Option<String> {
+    fn read_file_to_string(
+        repo: &Repository,
+        rel_path: &Path,
+        max_inline_bytes: usize,
+    ) -> Option<String> {
         let workdir = repo.workdir()?;
         let abs_path = workdir.join(rel_path);
 
@@ -569,7 +760,7 @@ impl GitService {
         };
 
         // Size guard - skip files larger than UI inline threshold
-        if bytes.len() > MAX_INLINE_DIFF_BYTES {
+        if bytes.len() > max_inline_bytes {
             tracing::debug!(
                 "Skipping large file ({}KB): {:?}",
                 bytes.len() / 1024,
@@ -596,7 +787,12 @@ impl GitService {
 
     /// Create Diff entries from git_cli::StatusDiffEntry
     /// New Diff format is flattened with change kind, paths, and optional contents.
-    fn status_entry_to_diff(repo: &Repository, base_tree: &git2::Tree, e: StatusDiffEntry) -> Diff {
+    fn status_entry_to_diff(
+        repo: &Repository,
+        base_tree: &git2::Tree,
+        e: StatusDiffEntry,
+        max_inline_bytes: usize,
+    ) -> Diff {
         // Map ChangeType to DiffChangeKind
         let mut change = match e.change {
             ChangeType::Added => DiffChangeKind::Added,
@@ -621,6 +817,34 @@ impl GitService {
             ChangeType::Unknown(_) => (e.old_path.clone(), Some(e.path.clone())),
         };
 
+        let old_entry = old_path_opt
+            .as_ref()
+            .and_then(|oldp| base_tree.get_path(Path::new(oldp)).ok());
+        let old_mode = old_entry
+            .as_ref()
+            .and_then(|entry| DiffFileMode::from_git_mode(entry.filemode()));
+        let new_mode = new_path_opt
+            .as_ref()
+            .and_then(|newp| Self::worktree_file_mode(repo, Path::new(newp), old_mode));
+        let executable_changed = DiffFileMode::is_executable_flip(old_mode, new_mode);
+
+        // A symlink's blob is its target
+        let old_symlink_target = match (old_mode, &old_entry) {
+            (Some(DiffFileMode::Symlink), Some(entry)) => repo
+                .find_blob(entry.id())
+                .ok()
+                .and_then(|b| Self::blob_to_string(&b)),
+            _ => None,
+        };
+        let new_symlink_target = match (new_mode, &new_path_opt, repo.workdir()) {
+            (Some(DiffFileMode::Symlink), Some(newp), Some(workdir)) => {
+                std::fs::read_link(workdir.join(newp))
+                    .ok()
+                    .map(|target| target.to_string_lossy().into_owned())
+            }
+            _ => None,
+        };
+
         // Decide if we should omit content by size (either side)
         let mut content_omitted = false;
         // Old side (from base tree)
@@ -630,7 +854,7 @@ impl GitService {
                 && entry.kind() == Some(git2::ObjectType::Blob)
                 && let Ok(blob) = repo.find_blob(entry.id())
                 && !blob.is_binary()
-                && blob.size() > MAX_INLINE_DIFF_BYTES
+                && blob.size() > max_inline_bytes
             {
                 content_omitted = true;
             }
@@ -640,8 +864,8 @@ impl GitService {
             && let Some(workdir) = repo.workdir()
         {
             let abs = workdir.join(newp);
-            if let Ok(md) = std::fs::metadata(&abs)
-                && (md.len() as usize) > MAX_INLINE_DIFF_BYTES
+            if let Ok(md) = std::fs::symlink_metadata(&abs)
+                && (md.len() as usize) > max_inline_bytes
             {
                 content_omitted = true;
             }
@@ -665,18 +889,24 @@ impl GitService {
                 None
             };
 
-            // Load new content from filesystem (worktree) when available
-            let new_content = if let Some(ref newp) = new_path_opt {
+            // Load new content from filesystem (worktree) when available.
+            // Like git, compare a symlink by its target rather than by the
+            // file it points to.
+            let new_content = if new_mode == Some(DiffFileMode::Symlink) {
+                new_symlink_target.clone()
+            } else if let Some(ref newp) = new_path_opt {
                 let rel = std::path::Path::new(newp);
-                Self::read_file_to_string(repo, rel)
+                Self::read_file_to_string(repo, rel, max_inline_bytes)
             } else {
                 None
             };
             (old_content, new_content)
         };
 
-        // If reported as Modified but content is identical, treat as a permission-only change
+        // If reported as Modified but only the executable bit changed, treat as a
+        // permission-only change
         if matches!(change, DiffChangeKind::Modified)
+            && executable_changed
             && old_content.is_some()
             && new_content.is_some()
             && old_content == new_content
@@ -710,6 +940,41 @@ impl GitService {
             content_omitted,
             additions,
             deletions,
+            old_mode,
+            new_mode,
+            old_symlink_target,
+            new_symlink_target,
+            executable_changed,
+        }
+    }
+
+    /// Mode of a file in the worktree, in git's terms. Platforms without an
+    /// executable bit keep the mode the file had in the base tree.
+    fn worktree_file_mode(
+        repo: &Repository,
+        rel_path: &Path,
+        old_mode: Option<DiffFileMode>,
+    ) -> Option<DiffFileMode> {
+        let metadata = std::fs::symlink_metadata(repo.workdir()?.join(rel_path)).ok()?;
+        if metadata.file_type().is_symlink() {
+            return Some(DiffFileMode::Symlink);
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = old_mode;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                Some(DiffFileMode::Executable)
+            } else {
+                Some(DiffFileMode::Regular)
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            match old_mode {
+                Some(DiffFileMode::Executable) => Some(DiffFileMode::Executable),
+                _ => Some(DiffFileMode::Regular),
+            }
         }
     }
 
@@ -743,6 +1008,29 @@ impl GitService {
         base_branch_name: &str,
         commit_message: &str,
     ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_hooks(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            commit_message,
+            &GitHooksMode::Run.into(),
+        )
+        .map(|merge| merge.commit)
+    }
+
+    /// [`Self::merge_changes`] with an explicit hooks policy. Hooks only apply
+    /// when the base branch is checked out; otherwise the squash commit is
+    /// written directly to the ref and no hooks exist to run.
+    pub fn merge_changes_with_hooks(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        commit_message: &str,
+        hooks: &CommitHooks,
+    ) -> Result<HookedMerge, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
         let base_repo = self.open_repo(base_worktree_path)?;
@@ -785,12 +1073,13 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
+                let (sha, hook_failure) = git_cli
                     .merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
                         commit_message,
+                        hooks,
                     )
                     .map_err(|e| {
                         GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
@@ -804,7 +1093,10 @@ impl GitService {
                         GitServiceError::InvalidRepository(format!("git update-ref failed: {e}"))
                     })?;
 
-                Ok(sha)
+                Ok(HookedMerge {
+                    commit: sha,
+                    hook_failure,
+                })
             }
             None => {
                 // base branch not checked out anywhere - use libgit2 pure ref operations
@@ -836,7 +1128,10 @@ impl GitService {
                     "Reset task branch after squash merge",
                 )?;
 
-                Ok(squash_commit_id.to_string())
+                Ok(HookedMerge {
+                    commit: squash_commit_id.to_string(),
+                    hook_failure: None,
+                })
             }
         }
     }
@@ -913,6 +1208,27 @@ impl GitService {
         Ok(Commit::new(merge_base))
     }
 
+    /// Contents of `path` as of `commit`, or `None` when no file exists there
+    pub fn read_file_at_commit(
+        &self,
+        repo_path: &Path,
+        commit: &Commit,
+        path: &str,
+    ) -> Result<Option<Vec<u8>>, GitServiceError> {
+        let repo = Repository::open(repo_path)?;
+        let tree = repo.find_commit(commit.as_oid())?.tree()?;
+        let entry = match tree.get_path(Path::new(path)) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return Ok(None);
+        }
+        let blob = repo.find_blob(entry.id())?;
+        Ok(Some(blob.content().to_vec()))
+    }
+
     pub fn get_remote_branch_status(
         &self,
         repo_path: &Path,
@@ -1028,6 +1344,22 @@ impl GitService {
         Ok((summary.uncommitted_tracked, summary.untracked))
     }
 
+    /// Paths changed in the worktree since `base_commit`, committed or not and
+    /// including untracked files. Renames report both paths.
+    pub fn get_changed_paths(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let entries = GitCli::new()
+            .diff_status(worktree_path, base_commit, StatusDiffOptions::default())
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))?;
+        Ok(entries
+            .into_iter()
+            .flat_map(|entry| std::iter::once(entry.path).chain(entry.old_path))
+            .collect())
+    }
+
     /// Evaluate whether any action is needed to reset to `target_commit_oid` and
     /// optionally perform the actions.
     pub fn reconcile_worktree_to_commit(
@@ -1136,6 +1468,42 @@ impl GitService {
         Ok(())
     }
 
+    /// Fix the links between a worktree and its admin directory
+    pub fn repair_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.worktree_repair(repo_path, worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Write a branch and its history to a bundle file
+    pub fn create_bundle(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.bundle_create(repo_path, bundle_path, branch)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git bundle failed: {e}")))
+    }
+
+    /// Create a branch from the same branch in a bundle file
+    pub fn fetch_bundle(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.bundle_fetch(repo_path, bundle_path, branch)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git fetch failed: {e}")))
+    }
+
     pub fn prune_worktrees(&self, repo_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
         git.worktree_prune(repo_path)
@@ -1327,24 +1695,7 @@ impl GitService {
                         .unwrap_or_else(|| "(unknown)".to_string());
                     // List conflicted files (best-effort)
                     let conflicts = git.get_conflicted_files(worktree_path).unwrap_or_default();
-                    let files_part = if conflicts.is_empty() {
-                        "".to_string()
-                    } else {
-                        let mut sample = conflicts.clone();
-                        let total = sample.len();
-                        sample.truncate(10);
-                        let list = sample.join(", ");
-                        if total > sample.len() {
-                            format!(
-                                " Conflicted files (showing {} of {}): {}.",
-                                sample.len(),
-                                total,
-                                list
-                            )
-                        } else {
-                            format!(" Conflicted files: {list}.")
-                        }
-                    };
+                    let files_part = conflicted_files_note(&conflicts);
                     let msg = format!(
                         "Rebase encountered merge conflicts while rebasing '{attempt_branch}' onto '{new_base_branch}'.{files_part} Resolve conflicts and then continue or abort."
                     );
@@ -1367,6 +1718,125 @@ impl GitService {
         Ok(final_commit.id().to_string())
     }
 
+    /// Apply `commits`, oldest first, on top of the worktree's current branch.
+    /// Returns the resulting HEAD commit. On conflicts the cherry-pick is left
+    /// in progress so it can be resolved and continued, or aborted with
+    /// [`Self::abort_in_progress_op`].
+    pub fn cherry_pick_commits(
+        &self,
+        worktree_path: &Path,
+        commits: &[String],
+    ) -> Result<String, GitServiceError> {
+        if commits.is_empty() {
+            return Err(GitServiceError::InvalidRepository(
+                "No commits to cherry-pick".to_string(),
+            ));
+        }
+        self.check_worktree_clean(worktree_path)?;
+        if let Some(op) = self.detect_conflict_op(worktree_path)? {
+            return Err(GitServiceError::OperationInProgress(op));
+        }
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        let git = GitCli::new();
+        match git.cherry_pick(worktree_path, commits) {
+            Ok(()) => {}
+            Err(GitCliError::CommandFailed(stderr)) => {
+                let conflicts = git.get_conflicted_files(worktree_path).unwrap_or_default();
+                if !conflicts.is_empty() || stderr.contains("CONFLICT") {
+                    return Err(GitServiceError::MergeConflicts(format!(
+                        "Cherry-pick stopped on merge conflicts.{} Resolve conflicts and then continue or abort.",
+                        conflicted_files_note(&conflicts)
+                    )));
+                }
+                // Roll back any commits picked before the failure
+                if let Err(e) = git.abort_cherry_pick(worktree_path) {
+                    tracing::warn!("Failed to abort cherry-pick: {e}");
+                }
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "Cherry-pick failed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(self.get_head_info(worktree_path)?.oid)
+    }
+
+    /// Commits on `branch` that are not on `base_branch`, oldest first.
+    /// Merge commits are left out.
+    pub fn commits_not_on(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        base_branch: &str,
+    ) -> Result<Vec<String>, GitServiceError> {
+        let range = format!("{base_branch}..{branch}");
+        let output =
+            GitCli::new().git(repo_path, ["rev-list", "--reverse", "--no-merges", &range])?;
+        Ok(output
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Apply `commits`, oldest first, onto `target_branch`, e.g. to backport
+    /// a task to a release branch. Picks happen where the branch is checked
+    /// out, or in a temporary worktree when it is not checked out anywhere.
+    /// On conflicts the cherry-pick is aborted so the branch is left as it
+    /// was. Returns the new tip of `target_branch`.
+    pub fn cherry_pick_onto_branch(
+        &self,
+        repo_path: &Path,
+        target_branch: &str,
+        commits: &[String],
+    ) -> Result<String, GitServiceError> {
+        if !self.check_branch_exists(repo_path, target_branch)? {
+            return Err(GitServiceError::BranchNotFound(target_branch.to_string()));
+        }
+        if let Some(checkout_path) = self.find_checkout_path_for_branch(repo_path, target_branch)? {
+            return self.cherry_pick_or_abort(&checkout_path, commits);
+        }
+
+        let temp_base = get_vibe_kanban_temp_dir();
+        std::fs::create_dir_all(&temp_base)?;
+        let temp_dir = tempfile::Builder::new()
+            .prefix("cherry-pick-")
+            .tempdir_in(&temp_base)?;
+        let worktree_path = temp_dir.path().join("worktree");
+        self.add_worktree(repo_path, &worktree_path, target_branch, false)?;
+
+        let result = self.cherry_pick_or_abort(&worktree_path, commits);
+        if let Err(e) = self.remove_worktree(repo_path, &worktree_path, true) {
+            tracing::warn!("Failed to remove cherry-pick worktree: {e}");
+        }
+        let _ = self.prune_worktrees(repo_path);
+        result
+    }
+
+    fn cherry_pick_or_abort(
+        &self,
+        worktree_path: &Path,
+        commits: &[String],
+    ) -> Result<String, GitServiceError> {
+        match self.cherry_pick_commits(worktree_path, commits) {
+            Err(GitServiceError::MergeConflicts(_)) => {
+                let git = GitCli::new();
+                let conflicts = git.get_conflicted_files(worktree_path).unwrap_or_default();
+                if let Err(e) = git.abort_cherry_pick(worktree_path) {
+                    tracing::warn!("Failed to abort cherry-pick: {e}");
+                }
+                Err(GitServiceError::MergeConflicts(format!(
+                    "Cherry-pick stopped on merge conflicts and was aborted; the target branch is unchanged.{}",
+                    conflicted_files_note(&conflicts)
+                )))
+            }
+            result => result,
+        }
+    }
+
     pub fn find_branch_type(
         &self,
         repo_path: &Path,
@@ -1437,6 +1907,88 @@ impl GitService {
         Ok(())
     }
 
+    /// Tip commit of a local branch; None when the branch does not exist
+    pub fn local_branch_tip(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch = match repo.find_branch(branch_name, BranchType::Local) {
+            Ok(branch) => branch,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(branch.get().peel_to_commit()?.id().to_string()))
+    }
+
+    /// Whether `merge_commit` brought in every change up to `tip`: either `tip`
+    /// is one of its ancestors, or it is a squash merge applying the same
+    /// change as `tip` does on top of its merge base. None when the merge
+    /// commit is not in the repo, e.g. a PR merged on a remote not fetched yet.
+    pub fn is_merged_by(
+        &self,
+        repo_path: &Path,
+        tip: &str,
+        merge_commit: &str,
+    ) -> Result<Option<bool>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let merge = match repo
+            .revparse_single(merge_commit)
+            .and_then(|object| object.peel_to_commit())
+        {
+            Ok(commit) => commit,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let git = GitCli::new();
+        if git.is_ancestor(repo_path, tip, merge_commit)? {
+            return Ok(Some(true));
+        }
+        if merge.parent_count() == 0 {
+            return Ok(Some(false));
+        }
+
+        let parent = merge.parent_id(0)?.to_string();
+        let base = git.git(repo_path, ["merge-base", tip, parent.as_str()])?;
+        let branch_change = git.patch_id(repo_path, base.trim(), tip)?;
+        let merged_change = git.patch_id(repo_path, &parent, merge_commit)?;
+        let squashed = branch_change.is_some() && branch_change == merged_change;
+        Ok(Some(squashed))
+    }
+
+    /// Delete a local branch. With `archive`, its tip is first kept at
+    /// `refs/vk-archive/<branch>` so the branch can be restored later.
+    pub fn remove_local_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        archive: bool,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let mut branch = repo
+            .find_branch(branch_name, BranchType::Local)
+            .map_err(|_| GitServiceError::BranchNotFound(branch_name.to_string()))?;
+
+        if archive {
+            let oid = branch.get().target().ok_or_else(|| {
+                GitServiceError::InvalidRepository(format!(
+                    "Branch '{branch_name}' is not a direct reference"
+                ))
+            })?;
+            repo.reference(
+                &format!("refs/vk-archive/{branch_name}"),
+                oid,
+                true,
+                "vibe-kanban: archive merged task branch",
+            )?;
+        }
+
+        branch.delete()?;
+        Ok(())
+    }
+
     /// Return true if a rebase is currently in progress in this worktree.
     pub fn is_rebase_in_progress(&self, worktree_path: &Path) -> Result<bool, GitServiceError> {
         let git = GitCli::new();
@@ -1487,6 +2039,19 @@ impl GitService {
         })
     }
 
+    /// Abort whichever merge, rebase, cherry-pick or revert is in progress,
+    /// returning the operation aborted.
+    pub fn abort_in_progress_op(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Option<ConflictOp>, GitServiceError> {
+        let op = self.detect_conflict_op(worktree_path)?;
+        if op.is_some() {
+            self.abort_conflicts(worktree_path)?;
+        }
+        Ok(op)
+    }
+
     pub fn abort_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
         let git = GitCli::new();
         if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
@@ -1524,6 +2089,145 @@ impl GitService {
         Ok(())
     }
 
+    /// Conflicted files of the in-progress operation, with their base, ours
+    /// and theirs versions and the hunks left in the working tree.
+    pub fn list_conflicts(
+        &self,
+        worktree_path: &Path,
+    ) -> Result<Vec<ConflictedFile>, GitServiceError> {
+        let git = GitCli::new();
+        let mut files = Vec::new();
+        for path in self.get_conflicted_files(worktree_path)? {
+            let mut versions: [Option<Vec<u8>>; 3] = Default::default();
+            for (stage, oid) in git.unmerged_stages(worktree_path, &path)? {
+                if (1..=3).contains(&stage) {
+                    versions[usize::from(stage - 1)] = Some(git.read_blob(worktree_path, &oid)?);
+                }
+            }
+            let working = std::fs::read(worktree_path.join(&path)).ok();
+            let is_binary = versions
+                .iter()
+                .chain([&working])
+                .flatten()
+                .any(|contents| contents.contains(&0));
+            let text = |contents: Option<Vec<u8>>| {
+                contents
+                    .filter(|_| !is_binary)
+                    .map(|c| String::from_utf8_lossy(&c).into_owned())
+            };
+            let hunks = text(working)
+                .map(|working| parse_conflict_hunks(&working).unwrap_or_default())
+                .unwrap_or_default();
+            let [base, ours, theirs] = versions;
+            files.push(ConflictedFile {
+                path,
+                base: text(base),
+                ours: text(ours),
+                theirs: text(theirs),
+                hunks,
+                is_binary,
+            });
+        }
+        Ok(files)
+    }
+
+    /// Write the resolution of a conflicted file and mark it resolved
+    pub fn resolve_conflict(
+        &self,
+        worktree_path: &Path,
+        path: &str,
+        resolution: &ConflictResolution,
+    ) -> Result<(), GitServiceError> {
+        // Only conflicted paths are accepted, which also keeps writes inside
+        // the worktree
+        if !self
+            .get_conflicted_files(worktree_path)?
+            .iter()
+            .any(|p| p == path)
+        {
+            return Err(GitServiceError::ConflictResolution(format!(
+                "{path} has no unresolved conflicts"
+            )));
+        }
+
+        let git = GitCli::new();
+        let file_path = worktree_path.join(path);
+        let stage_contents = |stage: u8| -> Result<Option<Vec<u8>>, GitServiceError> {
+            match git
+                .unmerged_stages(worktree_path, path)?
+                .into_iter()
+                .find(|(s, _)| *s == stage)
+            {
+                Some((_, oid)) => Ok(Some(git.read_blob(worktree_path, &oid)?)),
+                None => Ok(None),
+            }
+        };
+        // None means the resolution is to delete the file
+        let contents = match resolution {
+            ConflictResolution::Ours => stage_contents(2)?,
+            ConflictResolution::Theirs => stage_contents(3)?,
+            ConflictResolution::Hunks { hunks } => {
+                let current = std::fs::read_to_string(&file_path)?;
+                let resolved = apply_hunk_resolutions(&current, hunks)
+                    .map_err(|e| GitServiceError::ConflictResolution(format!("{path}: {e}")))?;
+                Some(resolved.into_bytes())
+            }
+            ConflictResolution::Content { content } => Some(content.clone().into_bytes()),
+        };
+
+        match contents {
+            Some(contents) => {
+                if let Some(parent) = file_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&file_path, contents)?;
+                git.mark_resolved(worktree_path, path, false)?;
+            }
+            None => git.mark_resolved(worktree_path, path, true)?,
+        }
+        Ok(())
+    }
+
+    /// Continue the in-progress merge, rebase, cherry-pick or revert once
+    /// every conflict is resolved. A rebase or multi-commit cherry-pick may
+    /// stop again on a later commit; the new conflicts are then left in the
+    /// worktree for the caller to list.
+    pub fn continue_conflicts(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let Some(op) = self.detect_conflict_op(worktree_path)? else {
+            return Err(GitServiceError::ConflictResolution(
+                "no merge, rebase, cherry-pick or revert is in progress".to_string(),
+            ));
+        };
+        let remaining = self.get_conflicted_files(worktree_path)?;
+        if !remaining.is_empty() {
+            return Err(GitServiceError::ConflictResolution(format!(
+                "{} file(s) still have conflicts: {}",
+                remaining.len(),
+                remaining.join(", ")
+            )));
+        }
+
+        let git = GitCli::new();
+        let result = match op {
+            ConflictOp::Rebase => git.continue_rebase(worktree_path),
+            ConflictOp::Merge => git.continue_merge(worktree_path),
+            ConflictOp::CherryPick => git.continue_cherry_pick(worktree_path),
+            ConflictOp::Revert => git.continue_revert(worktree_path),
+        };
+        match result {
+            Ok(()) => Ok(()),
+            Err(_)
+                if !self
+                    .get_conflicted_files(worktree_path)
+                    .unwrap_or_default()
+                    .is_empty() =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn find_branch<'a>(
         repo: &'a Repository,
         branch_name: &str,
@@ -1542,20 +2246,26 @@ impl GitService {
     }
 
     /// Extract GitHub owner and repo name from git repo path
-    pub fn get_github_repo_info(
-        &self,
-        repo_path: &Path,
-    ) -> Result<GitHubRepoInfo, GitServiceError> {
+    /// URL of the repository's default remote
+    pub fn get_remote_url(&self, repo_path: &Path) -> Result<String, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
         let remote_name = self.default_remote_name(&repo);
         let remote = repo.find_remote(&remote_name).map_err(|_| {
             GitServiceError::InvalidRepository(format!("No '{remote_name}' remote found"))
         })?;
 
-        let url = remote
+        remote
             .url()
-            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
-        GitHubRepoInfo::from_remote_url(url).map_err(|e| {
+            .map(str::to_string)
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))
+    }
+
+    pub fn get_github_repo_info(
+        &self,
+        repo_path: &Path,
+    ) -> Result<GitHubRepoInfo, GitServiceError> {
+        let url = self.get_remote_url(repo_path)?;
+        GitHubRepoInfo::from_remote_url(&url).map_err(|e| {
             GitServiceError::InvalidRepository(format!("Failed to parse remote URL: {e}"))
         })
     }
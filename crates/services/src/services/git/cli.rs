@@ -22,6 +22,7 @@ use std::{
     process::{Command, Stdio},
 };
 
+use db::models::git_hooks_policy::GitHooksMode;
 use thiserror::Error;
 use utils::shell::resolve_executable_path_blocking; // TODO: make GitCli async
 
@@ -76,6 +77,23 @@ pub struct WorktreeEntry {
     pub is_main: bool,
 }
 
+/// How a commit treats repo-local hooks
+#[derive(Debug, Clone)]
+pub struct CommitHooks {
+    pub mode: GitHooksMode,
+    /// Command prefix the hooks run under, such as the project's sandbox
+    pub sandbox: Option<Vec<String>>,
+}
+
+impl From<GitHooksMode> for CommitHooks {
+    fn from(mode: GitHooksMode) -> Self {
+        Self {
+            mode,
+            sandbox: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct StatusDiffOptions {
     pub path_filter: Option<Vec<String>>, // pathspecs to limit diff
@@ -152,6 +170,40 @@ impl GitCli {
         Ok(())
     }
 
+    /// Run `git -C <repo> worktree repair <path>` to fix links between a
+    /// worktree and its admin directory
+    pub fn worktree_repair(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "repair".into(),
+            worktree_path.as_os_str().into(),
+        ];
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
+    /// Run `git submodule update --init --recursive` in a worktree
+    pub fn submodule_update(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        self.git(
+            worktree_path,
+            ["submodule", "update", "--init", "--recursive"],
+        )?;
+        Ok(())
+    }
+
+    /// Run `git lfs pull` in a worktree; fails if git-lfs is not installed
+    pub fn lfs_pull(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+        self.git(worktree_path, ["lfs", "pull"])?;
+        Ok(())
+    }
+
     /// Prune stale worktree metadata
     pub fn worktree_prune(&self, repo_path: &Path) -> Result<(), GitCliError> {
         self.git(repo_path, ["worktree", "prune"])?;
@@ -300,6 +352,65 @@ impl GitCli {
         Ok(())
     }
 
+    /// Stage every change under `paths` (taken literally, not as globs), including
+    /// deletions and untracked files.
+    pub fn add_paths(&self, worktree_path: &Path, paths: &[String]) -> Result<(), GitCliError> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let mut input = Vec::new();
+        for path in paths {
+            input.extend_from_slice(path.as_bytes());
+            input.push(0);
+        }
+        self.git_with_stdin(
+            worktree_path,
+            [
+                "--literal-pathspecs",
+                "add",
+                "-A",
+                "--pathspec-from-file=-",
+                "--pathspec-file-nul",
+            ],
+            None,
+            &input,
+        )?;
+        Ok(())
+    }
+
+    /// Stage the hunks of a unified diff without touching the working tree, the
+    /// non-interactive equivalent of picking them in `git add -p`. Hunk line
+    /// counts are recomputed, so hunks may be dropped from a larger diff.
+    pub fn apply_cached(&self, worktree_path: &Path, patch: &str) -> Result<(), GitCliError> {
+        let mut patch = patch.to_string();
+        if !patch.ends_with('\n') {
+            patch.push('\n');
+        }
+        self.git_with_stdin(
+            worktree_path,
+            ["apply", "--cached", "--recount", "--whitespace=nowarn", "-"],
+            None,
+            patch.as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Unstage everything, leaving the working tree as it is.
+    pub fn reset_index(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["reset", "-q"])?;
+        Ok(())
+    }
+
+    /// Paths with staged changes, relative to the repository root
+    pub fn staged_paths(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
+        let out = self.git(worktree_path, ["diff", "--cached", "--name-only", "-z"])?;
+        Ok(out
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
     /// List all worktrees in the repository.
     ///
     /// The main worktree is identified as the first entry in the list that is not bare.
@@ -402,6 +513,90 @@ impl GitCli {
         self.git(worktree_path, ["commit", "-m", message])?;
         Ok(())
     }
+
+    /// Commit staged changes, treating repo-local hooks according to `hooks`.
+    /// Returns the output of the hooks when they failed in
+    /// [`GitHooksMode::Report`], in which case the commit was made without them.
+    pub fn commit_with_hooks(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+        hooks: &CommitHooks,
+    ) -> Result<Option<String>, GitCliError> {
+        if hooks.mode == GitHooksMode::Skip {
+            self.git(worktree_path, ["commit", "--no-verify", "-m", message])?;
+            return Ok(None);
+        }
+
+        // Kept alive until the commit is done
+        let sandboxed_hooks = match &hooks.sandbox {
+            Some(prefix) => Some(self.sandboxed_hooks_dir(worktree_path, prefix)?),
+            None => None,
+        };
+        let mut args = Vec::new();
+        if let Some(dir) = &sandboxed_hooks {
+            let mut hooks_path = OsString::from("core.hooksPath=");
+            hooks_path.push(dir.path());
+            args.extend([OsString::from("-c"), hooks_path]);
+        }
+        args.extend(["commit", "-m", message].map(OsString::from));
+
+        match (self.git(worktree_path, &args), hooks.mode) {
+            (Ok(_), _) => Ok(None),
+            (Err(GitCliError::CommandFailed(output)), GitHooksMode::Report) => {
+                // If the failure was not a hook this retry fails the same way
+                self.git(worktree_path, ["commit", "--no-verify", "-m", message])?;
+                tracing::warn!(
+                    "Git hooks failed in {}; committed with --no-verify: {}",
+                    worktree_path.display(),
+                    output
+                );
+                Ok(Some(output))
+            }
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    /// Write a directory of hooks, for use as `core.hooksPath`, that run each
+    /// of the repository's own hooks under `prefix`. Git itself stays on the
+    /// host; only the hook processes are sandboxed.
+    fn sandboxed_hooks_dir(
+        &self,
+        worktree_path: &Path,
+        prefix: &[String],
+    ) -> Result<tempfile::TempDir, GitCliError> {
+        let hooks_path = self.git(worktree_path, ["rev-parse", "--git-path", "hooks"])?;
+        // Relative to the worktree unless core.hooksPath is absolute
+        let hooks_dir = worktree_path.join(hooks_path.trim());
+        let wrapped = tempfile::Builder::new()
+            .prefix("vk-hooks-")
+            .tempdir()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+
+        let Ok(entries) = std::fs::read_dir(&hooks_dir) else {
+            return Ok(wrapped);
+        };
+        for entry in entries.flatten() {
+            let hook = entry.path();
+            if hook.extension().is_some_and(|ext| ext == "sample") || !is_executable_file(&hook) {
+                continue;
+            }
+            let hook_path = hook.to_string_lossy();
+            let command = prefix
+                .iter()
+                .map(String::as_str)
+                .chain([&*hook_path])
+                .map(shell_quote)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let script = wrapped.path().join(entry.file_name());
+            write_executable(&script, &format!("#!/bin/sh\nexec {command} \"$@\"\n")).map_err(
+                |e| GitCliError::CommandFailed(format!("failed to write sandboxed hook: {e}")),
+            )?;
+        }
+        Ok(wrapped)
+    }
+
     /// Fetch a branch to the given remote using native git authentication.
     pub fn fetch_with_refspec(
         &self,
@@ -424,6 +619,40 @@ impl GitCli {
         }
     }
 
+    /// Write the full history of a local branch to a git bundle file.
+    pub fn bundle_create(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitCliError> {
+        let args = [
+            OsString::from("bundle"),
+            OsString::from("create"),
+            bundle_path.as_os_str().to_os_string(),
+            OsString::from(format!("refs/heads/{branch}")),
+        ];
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
+    /// Create a local branch from the same branch in a git bundle file. Fails
+    /// if the branch already exists.
+    pub fn bundle_fetch(
+        &self,
+        repo_path: &Path,
+        bundle_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitCliError> {
+        let args = [
+            OsString::from("fetch"),
+            bundle_path.as_os_str().to_os_string(),
+            OsString::from(format!("refs/heads/{branch}:refs/heads/{branch}")),
+        ];
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
     /// Push a branch to the given remote using native git authentication.
     pub fn push(
         &self,
@@ -555,6 +784,15 @@ impl GitCli {
         Ok(())
     }
 
+    /// Apply `commits`, oldest first, on top of HEAD with `git cherry-pick -x`,
+    /// which notes each original commit in the new message.
+    pub fn cherry_pick(&self, worktree_path: &Path, commits: &[String]) -> Result<(), GitCliError> {
+        let mut args = vec!["cherry-pick".to_string(), "-x".to_string()];
+        args.extend(commits.iter().cloned());
+        self.git_with_env(worktree_path, args, &Self::no_editor_env())
+            .map(|_| ())
+    }
+
     /// Return true if there is a rebase in progress in this worktree.
     /// We treat this as true when either of Git's rebase state directories exists:
     /// - rebase-merge (interactive rebase)
@@ -635,23 +873,70 @@ impl GitCli {
         }
     }
 
-    /// Checkout base branch, squash-merge from_branch, and commit with message. Returns new HEAD sha.
+    /// Checkout base branch, squash-merge from_branch, and commit with message. Returns new HEAD sha
+    /// and the output of hooks that failed in [`GitHooksMode::Report`].
     pub fn merge_squash_commit(
         &self,
         repo_path: &Path,
         base_branch: &str,
         from_branch: &str,
         message: &str,
-    ) -> Result<String, GitCliError> {
+        hooks: &CommitHooks,
+    ) -> Result<(String, Option<String>), GitCliError> {
         self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
         self.git(repo_path, ["merge", "--squash", "--no-commit", from_branch])
             .map(|_| ())?;
-        self.git(repo_path, ["commit", "-m", message]).map(|_| ())?;
+        let hook_failure = self.commit_with_hooks(repo_path, message, hooks)?;
         let sha = self
             .git(repo_path, ["rev-parse", "HEAD"])?
             .trim()
             .to_string();
-        Ok(sha)
+        Ok((sha, hook_failure))
+    }
+
+    /// Whether `ancestor` is reachable from `descendant`
+    /// (`git merge-base --is-ancestor`). Both must name existing commits.
+    pub fn is_ancestor(
+        &self,
+        repo_path: &Path,
+        ancestor: &str,
+        descendant: &str,
+    ) -> Result<bool, GitCliError> {
+        for rev in [ancestor, descendant] {
+            let commit = format!("{rev}^{{commit}}");
+            self.git(
+                repo_path,
+                ["rev-parse", "--verify", "--quiet", commit.as_str()],
+            )
+            .map_err(|_| GitCliError::InvalidReference(rev.to_string()))?;
+        }
+        // Exits 1 when not an ancestor; both revs were checked above
+        Ok(self
+            .git(
+                repo_path,
+                ["merge-base", "--is-ancestor", ancestor, descendant],
+            )
+            .is_ok())
+    }
+
+    /// Stable patch ID of the diff between two commits; None when they have
+    /// the same tree
+    pub fn patch_id(
+        &self,
+        repo_path: &Path,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<String>, GitCliError> {
+        let diff = self.git(
+            repo_path,
+            ["diff", "--no-ext-diff", "--no-color", "--binary", from, to],
+        )?;
+        if diff.trim().is_empty() {
+            return Ok(None);
+        }
+        let out =
+            self.git_with_stdin(repo_path, ["patch-id", "--stable"], None, diff.as_bytes())?;
+        Ok(out.split_whitespace().next().map(str::to_string))
     }
 
     /// Update a ref to a specific sha in the repo.
@@ -701,6 +986,94 @@ impl GitCli {
         Ok(files)
     }
 
+    /// Blob ids of a conflicted path's index stages: 1 = base, 2 = ours,
+    /// 3 = theirs. A stage is missing when that side has no such file.
+    pub fn unmerged_stages(
+        &self,
+        worktree_path: &Path,
+        path: &str,
+    ) -> Result<Vec<(u8, String)>, GitCliError> {
+        // Lines look like `<mode> <oid> <stage>\t<path>`
+        let out = self.git(worktree_path, ["ls-files", "--unmerged", "--", path])?;
+        let mut stages = Vec::new();
+        for line in out.lines() {
+            let Some((meta, _)) = line.split_once('\t') else {
+                continue;
+            };
+            let mut parts = meta.split_whitespace().skip(1);
+            if let (Some(oid), Some(stage)) = (parts.next(), parts.next())
+                && let Ok(stage) = stage.parse::<u8>()
+            {
+                stages.push((stage, oid.to_string()));
+            }
+        }
+        Ok(stages)
+    }
+
+    /// Raw contents of a blob
+    pub fn read_blob(&self, repo_path: &Path, oid: &str) -> Result<Vec<u8>, GitCliError> {
+        self.git_impl(repo_path, ["cat-file", "blob", oid], None, None)
+    }
+
+    /// Mark a conflicted path resolved with its working tree contents, or as
+    /// deleted when `delete` is set.
+    pub fn mark_resolved(
+        &self,
+        worktree_path: &Path,
+        path: &str,
+        delete: bool,
+    ) -> Result<(), GitCliError> {
+        if delete {
+            self.git(worktree_path, ["rm", "-q", "--ignore-unmatch", "--", path])?;
+        } else {
+            self.git(worktree_path, ["add", "--", path])?;
+        }
+        Ok(())
+    }
+
+    /// Continue an in-progress rebase once its conflicts are resolved
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["rebase", "--continue"],
+            &Self::no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge with its prepared message
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["commit", "--no-edit"],
+            &Self::no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_cherry_pick(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["cherry-pick", "--continue"],
+            &Self::no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_revert(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["revert", "--continue"],
+            &Self::no_editor_env(),
+        )
+        .map(|_| ())
+    }
+
+    /// Accept git's prepared commit messages instead of opening an editor
+    fn no_editor_env() -> [(OsString, OsString); 1] {
+        [(OsString::from("GIT_EDITOR"), OsString::from("true"))]
+    }
+
     /// Create a new branch pointing to HEAD or a base branch.
     pub fn create_branch(
         &self,
@@ -728,8 +1101,12 @@ impl GitCli {
         let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
 
         let mut cmd = Command::new(&git);
-        cmd.arg("clone")
-            .arg(url)
+        cmd.arg("clone");
+        // `clone -c` persists the setting into the new repository's config
+        if cfg!(windows) {
+            cmd.args(["-c", "core.longpaths=true"]);
+        }
+        cmd.arg(url)
             .arg(destination)
             .env("GIT_TERMINAL_PROMPT", "0")
             .stdin(Stdio::null())
@@ -819,6 +1196,11 @@ impl GitCli {
         let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
         let mut cmd = Command::new(&git);
         cmd.arg("-C").arg(repo_path);
+        // Worktrees nest deep inside the workspace dir; without this git on
+        // Windows fails on paths longer than MAX_PATH (e.g. node_modules).
+        if cfg!(windows) {
+            cmd.args(["-c", "core.longpaths=true"]);
+        }
 
         if let Some(envs) = envs {
             for (k, v) in envs {
@@ -997,6 +1379,34 @@ pub struct WorktreeStatus {
     pub entries: Vec<StatusEntry>,
 }
 
+/// Quote a value for POSIX `sh`
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn write_executable(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, process::Command};
@@ -1187,4 +1597,56 @@ mod tests {
         assert!(worktrees[0].is_main, "First should be main");
         assert!(!worktrees[1].is_main, "Second should be linked");
     }
+
+    #[test]
+    fn test_stage_selected_hunks_and_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        init_test_repo_via_cli(repo_path);
+        let lines: String = (1..=10).map(|n| format!("{n}\n")).collect();
+        fs::write(repo_path.join("a.txt"), &lines).unwrap();
+        let git_cli = GitCli::new();
+        git_cli.add_all(repo_path).unwrap();
+        git_cli.commit(repo_path, "Add a.txt").unwrap();
+
+        fs::write(
+            repo_path.join("a.txt"),
+            lines.replace("1\n2", "one\n2").replace("10\n", "ten\n"),
+        )
+        .unwrap();
+        fs::write(repo_path.join("b[1].txt"), "b\n").unwrap();
+        fs::write(repo_path.join("c.txt"), "c\n").unwrap();
+
+        let first_hunk = "diff --git a/a.txt b/a.txt\n\
+                          --- a/a.txt\n\
+                          +++ b/a.txt\n\
+                          @@ -1,3 +1,3 @@\n\
+                          -1\n\
+                          +one\n \
+                          2\n \
+                          3";
+        git_cli.apply_cached(repo_path, first_hunk).unwrap();
+        git_cli
+            .add_paths(repo_path, &["b[1].txt".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            git_cli.staged_paths(repo_path).unwrap(),
+            vec!["a.txt".to_string(), "b[1].txt".to_string()]
+        );
+        let staged_a = git_cli
+            .git(repo_path, ["diff", "--cached", "a.txt"])
+            .unwrap();
+        assert!(staged_a.contains("+one"));
+        assert!(!staged_a.contains("+ten"));
+        // The rest stays in the working tree
+        assert!(
+            fs::read_to_string(repo_path.join("a.txt"))
+                .unwrap()
+                .ends_with("ten\n")
+        );
+
+        git_cli.reset_index(repo_path).unwrap();
+        assert!(!git_cli.has_staged_changes(repo_path).unwrap());
+    }
 }
@@ -0,0 +1,280 @@
+//! Conflicted files of an interrupted merge, rebase, cherry-pick or revert,
+//! and applying the user's choice for each conflict.
+//!
+//! "Ours" and "theirs" keep git's meaning, which flips for rebases: there
+//! "ours" is the branch being rebased onto and "theirs" the commit being
+//! replayed.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConflictMarkerError {
+    #[error("conflict {0} is missing its closing marker")]
+    Unterminated(usize),
+    #[error("file has {found} conflicts but {given} resolutions were given")]
+    ResolutionCount { found: usize, given: usize },
+}
+
+/// One conflicted region of a file, as left in the working tree by git
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+pub struct ConflictHunk {
+    pub ours: String,
+    /// Only present when git writes diff3-style markers
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+/// A file with unresolved conflicts and the three versions it came from.
+/// Versions are None when that side deleted the file or it is binary.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ConflictedFile {
+    pub path: String,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Conflicted regions of the working tree file, in order. Empty for
+    /// binary files and add/delete conflicts, which resolve as a whole.
+    pub hunks: Vec<ConflictHunk>,
+    pub is_binary: bool,
+}
+
+/// How to resolve one hunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum HunkResolution {
+    Ours,
+    Theirs,
+    /// Ours followed by theirs
+    Both,
+    Custom {
+        content: String,
+    },
+}
+
+/// How to resolve a conflicted file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// Take our version of the whole file, deleting it if we deleted it
+    Ours,
+    /// Take their version of the whole file, deleting it if they deleted it
+    Theirs,
+    /// One resolution per hunk, in file order
+    Hunks { hunks: Vec<HunkResolution> },
+    /// Replace the file with this content
+    Content { content: String },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Conflict(ConflictHunk),
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\n', '\r']))
+}
+
+fn parse(content: &str) -> Result<Vec<Segment>, ConflictMarkerError> {
+    #[derive(PartialEq)]
+    enum State {
+        Text,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut hunk = ConflictHunk {
+        ours: String::new(),
+        base: None,
+        theirs: String::new(),
+    };
+    let mut state = State::Text;
+
+    for line in content.split_inclusive('\n') {
+        state = match state {
+            State::Text if is_marker(line, OURS_MARKER) => {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                State::Ours
+            }
+            State::Text => {
+                text.push_str(line);
+                State::Text
+            }
+            State::Ours | State::Base if is_marker(line, SEPARATOR) => State::Theirs,
+            State::Ours if is_marker(line, BASE_MARKER) => {
+                hunk.base = Some(String::new());
+                State::Base
+            }
+            State::Ours => {
+                hunk.ours.push_str(line);
+                State::Ours
+            }
+            State::Base => {
+                hunk.base.get_or_insert_default().push_str(line);
+                State::Base
+            }
+            State::Theirs if is_marker(line, THEIRS_MARKER) => {
+                let done = std::mem::replace(
+                    &mut hunk,
+                    ConflictHunk {
+                        ours: String::new(),
+                        base: None,
+                        theirs: String::new(),
+                    },
+                );
+                segments.push(Segment::Conflict(done));
+                State::Text
+            }
+            State::Theirs => {
+                hunk.theirs.push_str(line);
+                State::Theirs
+            }
+        };
+    }
+
+    if state != State::Text {
+        let index = segments
+            .iter()
+            .filter(|s| matches!(s, Segment::Conflict(_)))
+            .count();
+        return Err(ConflictMarkerError::Unterminated(index));
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+/// The conflicted regions of a working tree file, in order
+pub fn parse_conflict_hunks(content: &str) -> Result<Vec<ConflictHunk>, ConflictMarkerError> {
+    Ok(parse(content)?
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Conflict(hunk) => Some(hunk),
+            Segment::Text(_) => None,
+        })
+        .collect())
+}
+
+/// Replace every conflicted region of `content` with its resolution
+pub fn apply_hunk_resolutions(
+    content: &str,
+    resolutions: &[HunkResolution],
+) -> Result<String, ConflictMarkerError> {
+    let segments = parse(content)?;
+    let found = segments
+        .iter()
+        .filter(|s| matches!(s, Segment::Conflict(_)))
+        .count();
+    if found != resolutions.len() {
+        return Err(ConflictMarkerError::ResolutionCount {
+            found,
+            given: resolutions.len(),
+        });
+    }
+
+    let mut resolutions = resolutions.iter();
+    let mut resolved = String::with_capacity(content.len());
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => resolved.push_str(&text),
+            Segment::Conflict(hunk) => match resolutions.next() {
+                Some(HunkResolution::Ours) => resolved.push_str(&hunk.ours),
+                Some(HunkResolution::Theirs) => resolved.push_str(&hunk.theirs),
+                Some(HunkResolution::Both) => {
+                    resolved.push_str(&hunk.ours);
+                    resolved.push_str(&hunk.theirs);
+                }
+                Some(HunkResolution::Custom { content }) => {
+                    resolved.push_str(content);
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        resolved.push('\n');
+                    }
+                }
+                None => unreachable!("resolution count checked above"),
+            },
+        }
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICTED: &str = "fn main() {\n\
+        <<<<<<< HEAD\n    println!(\"ours\");\n\
+        ||||||| base\n    println!(\"base\");\n\
+        =======\n    println!(\"theirs\");\n\
+        >>>>>>> task\n\
+        }\n\
+        <<<<<<< HEAD\n// a\n=======\n// b\n>>>>>>> task\n";
+
+    #[test]
+    fn parses_diff3_and_merge_style_hunks() {
+        let hunks = parse_conflict_hunks(CONFLICTED).unwrap();
+        assert_eq!(
+            hunks,
+            vec![
+                ConflictHunk {
+                    ours: "    println!(\"ours\");\n".to_string(),
+                    base: Some("    println!(\"base\");\n".to_string()),
+                    theirs: "    println!(\"theirs\");\n".to_string(),
+                },
+                ConflictHunk {
+                    ours: "// a\n".to_string(),
+                    base: None,
+                    theirs: "// b\n".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            parse_conflict_hunks("<<<<<<< HEAD\nours\n=======\n"),
+            Err(ConflictMarkerError::Unterminated(0))
+        );
+    }
+
+    #[test]
+    fn applies_resolutions_in_order() {
+        let resolved = apply_hunk_resolutions(
+            CONFLICTED,
+            &[
+                HunkResolution::Theirs,
+                HunkResolution::Custom {
+                    content: "// c".to_string(),
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            resolved,
+            "fn main() {\n    println!(\"theirs\");\n}\n// c\n"
+        );
+
+        let both =
+            apply_hunk_resolutions(CONFLICTED, &[HunkResolution::Ours, HunkResolution::Both])
+                .unwrap();
+        assert!(both.ends_with("}\n// a\n// b\n"));
+
+        assert_eq!(
+            apply_hunk_resolutions(CONFLICTED, &[HunkResolution::Ours]),
+            Err(ConflictMarkerError::ResolutionCount { found: 2, given: 1 })
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Hooks policy for the commits vibe-kanban makes on a project's behalf.
+//!
+//! Hooks are repo config an agent can edit, so with the project's sandbox
+//! enabled they run under it like the agents do. When the sandbox is enabled
+//! but not available on this system the hooks are skipped rather than run on
+//! the host.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    git_hooks_policy::{GitHooksMode, GitHooksPolicy},
+    sandbox_policy::ProjectSandboxPolicy,
+};
+use executors::sandbox::SandboxError;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::{git::CommitHooks, worktree_manager::WorktreeManager};
+
+/// How repo-configured commands in a checkout run under the project's sandbox
+pub enum HookSandbox {
+    Disabled,
+    /// Command prefix that runs a command inside the sandbox
+    Prefix(Vec<String>),
+    /// The sandbox is enabled but cannot be set up on this system
+    Unavailable(SandboxError),
+}
+
+/// Sandbox for commands run in `checkout_path`, a checkout of `repo_path`.
+/// The checkout, what a commit in it writes to and `extra_writable` are
+/// writable.
+pub async fn hook_sandbox(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    repo_path: &Path,
+    checkout_path: &Path,
+    extra_writable: &[PathBuf],
+) -> Result<HookSandbox, sqlx::Error> {
+    let sandbox = ProjectSandboxPolicy::find_or_default(pool, project_id)
+        .await?
+        .policy
+        .0;
+    if !sandbox.enabled {
+        return Ok(HookSandbox::Disabled);
+    }
+
+    let mut writable = vec![checkout_path.to_path_buf()];
+    writable.extend(WorktreeManager::commit_dirs(repo_path, checkout_path));
+    writable.extend_from_slice(extra_writable);
+    Ok(match sandbox.command_prefix(&writable).await {
+        Ok(prefix) => HookSandbox::Prefix(prefix),
+        Err(e) => HookSandbox::Unavailable(e),
+    })
+}
+
+/// Hooks policy for a commit in `checkout_path`, a checkout of `repo_path`
+pub async fn commit_hooks_for_project(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    repo_path: &Path,
+    checkout_path: &Path,
+) -> Result<CommitHooks, sqlx::Error> {
+    let mode = GitHooksPolicy::mode_for_project(pool, project_id).await?;
+    if mode == GitHooksMode::Skip {
+        return Ok(mode.into());
+    }
+
+    match hook_sandbox(pool, project_id, repo_path, checkout_path, &[]).await? {
+        HookSandbox::Disabled => Ok(mode.into()),
+        HookSandbox::Prefix(prefix) => Ok(CommitHooks {
+            mode,
+            sandbox: Some(prefix),
+        }),
+        HookSandbox::Unavailable(e) => {
+            tracing::warn!("Skipping git hooks for project {project_id}: {e}");
+            Ok(GitHooksMode::Skip.into())
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! The host a repo's remote lives on, picked from its remote or PR URL.
+//!
+//! GitHub goes through the `gh` CLI; GitLab, Bitbucket Cloud and Gitea are
+//! reached over their REST APIs with credentials from the config and the
+//! secret store. Self-hosted
+//! GitLab and Gitea instances are matched against their configured base URL
+//! before falling back to bitbucket.org and github.com, so a PR stored for
+//! any of them resolves to the same host when it is refreshed later.
+
+use db::models::merge::PullRequestInfo;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use url::Url;
+use utoipa::ToSchema;
+
+use crate::services::{
+    bitbucket::{BitbucketRepoInfo, BitbucketService, BitbucketServiceError},
+    config::{BitbucketConfig, Config, GitLabConfig, GiteaConfig},
+    gitea::{GiteaRepoInfo, GiteaService, GiteaServiceError},
+    github::{CreatePrRequest, GitHubRepoInfo, GitHubService, GitHubServiceError},
+    gitlab::{GitLabRepoInfo, GitLabService, GitLabServiceError},
+    secrets::SecretService,
+};
+
+#[derive(Debug, Error)]
+pub enum GitRemoteError {
+    #[error("Remote is not on GitHub, GitLab, Bitbucket or a configured Gitea instance: {0}")]
+    UnsupportedRemote(String),
+    #[error(transparent)]
+    GitHub(#[from] GitHubServiceError),
+    #[error(transparent)]
+    GitLab(#[from] GitLabServiceError),
+    #[error(transparent)]
+    Bitbucket(#[from] BitbucketServiceError),
+    #[error(transparent)]
+    Gitea(#[from] GiteaServiceError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum GitRemoteProviderKind {
+    Github,
+    Gitlab,
+    Bitbucket,
+    Gitea,
+}
+
+impl GitRemoteProviderKind {
+    /// Display name of the host
+    pub fn name(self) -> &'static str {
+        match self {
+            GitRemoteProviderKind::Github => "GitHub",
+            GitRemoteProviderKind::Gitlab => "GitLab",
+            GitRemoteProviderKind::Bitbucket => "Bitbucket",
+            GitRemoteProviderKind::Gitea => "Gitea",
+        }
+    }
+}
+
+/// A repo on a supported host, with the config needed to reach it
+#[derive(Debug, Clone)]
+pub enum GitRemoteProvider {
+    GitHub(GitHubRepoInfo),
+    GitLab(GitLabRepoInfo, GitLabConfig),
+    Bitbucket(BitbucketRepoInfo, BitbucketConfig),
+    Gitea(GiteaRepoInfo, GiteaConfig),
+}
+
+impl GitRemoteProvider {
+    /// Detect the host of a remote or pull request URL. None when it is on
+    /// none of the supported hosts.
+    pub fn from_url(url: &str, config: &Config) -> Option<Self> {
+        if let Some(repo) = GitLabRepoInfo::from_remote_url(url, &config.gitlab.base_url) {
+            return Some(Self::GitLab(repo, config.gitlab.clone()));
+        }
+        if let Some(repo) = config
+            .gitea
+            .base_url
+            .as_deref()
+            .and_then(|base_url| GiteaRepoInfo::from_remote_url(url, base_url))
+        {
+            return Some(Self::Gitea(repo, config.gitea.clone()));
+        }
+        if let Some(repo) = BitbucketRepoInfo::from_remote_url(url) {
+            return Some(Self::Bitbucket(repo, config.bitbucket.clone()));
+        }
+        GitHubRepoInfo::from_remote_url(url).ok().map(Self::GitHub)
+    }
+
+    /// Like [`Self::from_url`], but an unsupported host is an error
+    pub fn require(url: &str, config: &Config) -> Result<Self, GitRemoteError> {
+        Self::from_url(url, config).ok_or_else(|| GitRemoteError::UnsupportedRemote(url.into()))
+    }
+
+    pub fn kind(&self) -> GitRemoteProviderKind {
+        match self {
+            Self::GitHub(_) => GitRemoteProviderKind::Github,
+            Self::GitLab(..) => GitRemoteProviderKind::Gitlab,
+            Self::Bitbucket(..) => GitRemoteProviderKind::Bitbucket,
+            Self::Gitea(..) => GitRemoteProviderKind::Gitea,
+        }
+    }
+
+    /// Web page of `branch` on the host
+    pub fn branch_url(&self, branch: &str) -> String {
+        match self {
+            Self::GitHub(repo) => format!(
+                "https://github.com/{}/{}/tree/{branch}",
+                repo.owner, repo.repo_name
+            ),
+            Self::GitLab(repo, config) => format!(
+                "{}/{}/-/tree/{branch}",
+                config.base_url.trim_end_matches('/'),
+                repo.project_path
+            ),
+            Self::Bitbucket(repo, _) => repo.branch_url(branch),
+            Self::Gitea(repo, _) => repo.branch_url(branch),
+        }
+    }
+
+    pub async fn create_pull_request(
+        &self,
+        secrets: &SecretService,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GitRemoteError> {
+        Ok(match self {
+            Self::GitHub(repo) => GitHubService::new()?.create_pr(repo, request).await?,
+            Self::GitLab(repo, config) => {
+                GitLabService::new(config, secrets.resolve_opt(config.token.as_ref()).await)?
+                    .create_merge_request(repo, request)
+                    .await?
+            }
+            Self::Bitbucket(repo, config) => {
+                BitbucketService::new(
+                    config,
+                    secrets.resolve_opt(config.app_password.as_ref()).await,
+                )?
+                .create_pull_request(repo, request)
+                .await?
+            }
+            Self::Gitea(repo, config) => {
+                GiteaService::new(secrets.resolve_opt(config.token.as_ref()).await)?
+                    .create_pull_request(repo, request)
+                    .await?
+            }
+        })
+    }
+
+    /// Current state of the pull request with host-scoped `number`
+    pub async fn get_pull_request(
+        &self,
+        secrets: &SecretService,
+        number: i64,
+    ) -> Result<PullRequestInfo, GitRemoteError> {
+        Ok(match self {
+            Self::GitHub(repo) => GitHubService::new()?.update_pr_status(repo, number).await?,
+            Self::GitLab(repo, config) => {
+                GitLabService::new(config, secrets.resolve_opt(config.token.as_ref()).await)?
+                    .get_merge_request(repo, number)
+                    .await?
+            }
+            Self::Bitbucket(repo, config) => {
+                BitbucketService::new(
+                    config,
+                    secrets.resolve_opt(config.app_password.as_ref()).await,
+                )?
+                .get_pull_request(repo, number)
+                .await?
+            }
+            Self::Gitea(repo, config) => {
+                GiteaService::new(secrets.resolve_opt(config.token.as_ref()).await)?
+                    .get_pull_request(repo, number)
+                    .await?
+            }
+        })
+    }
+
+    /// All pull requests from `branch` in any state, open ones first
+    pub async fn list_pull_requests_for_branch(
+        &self,
+        secrets: &SecretService,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, GitRemoteError> {
+        Ok(match self {
+            Self::GitHub(repo) => {
+                GitHubService::new()?
+                    .list_all_prs_for_branch(repo, branch)
+                    .await?
+            }
+            Self::GitLab(repo, config) => {
+                GitLabService::new(config, secrets.resolve_opt(config.token.as_ref()).await)?
+                    .list_merge_requests_for_branch(repo, branch)
+                    .await?
+            }
+            Self::Bitbucket(repo, config) => {
+                BitbucketService::new(
+                    config,
+                    secrets.resolve_opt(config.app_password.as_ref()).await,
+                )?
+                .list_pull_requests_for_branch(repo, branch)
+                .await?
+            }
+            Self::Gitea(repo, config) => {
+                GiteaService::new(secrets.resolve_opt(config.token.as_ref()).await)?
+                    .list_pull_requests_for_branch(repo, branch)
+                    .await?
+            }
+        })
+    }
+}
+
+/// Host and path of a remote URL, including scp-like SSH remotes
+/// (`git@host:owner/repo.git`) which are not URLs. The path starts with `/`.
+pub(crate) fn split_remote_url(remote_url: &str) -> Option<(String, String)> {
+    match Url::parse(remote_url).ok().filter(Url::has_host) {
+        Some(url) => Some((url.host_str()?.to_string(), url.path().to_string())),
+        None => {
+            let (user_host, path) = remote_url.split_once(':')?;
+            let host = user_host.rsplit('@').next()?;
+            Some((host.to_string(), format!("/{path}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_provider_from_remote_url() {
+        let mut config = Config::default();
+        config.gitea.base_url = Some("https://git.example.com".to_string());
+
+        let kind = |url: &str| GitRemoteProvider::from_url(url, &config).map(|p| p.kind());
+        assert_eq!(
+            kind("git@github.com:acme/widgets.git"),
+            Some(GitRemoteProviderKind::Github)
+        );
+        assert_eq!(
+            kind("https://gitlab.com/acme/widgets/-/merge_requests/3"),
+            Some(GitRemoteProviderKind::Gitlab)
+        );
+        assert_eq!(
+            kind("git@bitbucket.org:acme/widgets.git"),
+            Some(GitRemoteProviderKind::Bitbucket)
+        );
+        assert_eq!(
+            kind("https://git.example.com/acme/widgets/pulls/3"),
+            Some(GitRemoteProviderKind::Gitea)
+        );
+        assert_eq!(kind("https://code.internal/acme/widgets.git"), None);
+
+        let github = GitRemoteProvider::from_url("https://github.com/acme/widgets", &config);
+        assert_eq!(
+            github.unwrap().branch_url("vk/1234-fix"),
+            "https://github.com/acme/widgets/tree/vk/1234-fix"
+        );
+    }
+}
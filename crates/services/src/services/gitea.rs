@@ -0,0 +1,311 @@
+//! Gitea pull requests for repos whose remote is on the configured Gitea (or
+//! Forgejo) instance.
+//!
+//! Talks to the REST v1 API with the access token the
+//! [`GiteaConfig`](crate::services::config::GiteaConfig) references in the
+//! secret store. Gitea has no draft flag; drafts get the `WIP:` title prefix
+//! it recognises instead.
+
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use db::models::merge::{MergeStatus, PullRequestInfo};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{Deserialize, de::DeserializeOwned};
+use thiserror::Error;
+use tracing::info;
+use url::Url;
+
+use crate::services::{git_remote::split_remote_url, github::CreatePrRequest};
+
+/// Pull requests fetched when looking for the ones from a branch
+const BRANCH_SEARCH_LIMIT: &str = "50";
+
+#[derive(Debug, Error)]
+pub enum GiteaServiceError {
+    #[error("Gitea is not configured. Add an instance URL and token under Settings > Gitea")]
+    NotConfigured,
+    #[error("Gitea authentication failed: {0}")]
+    AuthFailed(String),
+    #[error("Gitea repository not found or no access: {0}")]
+    RepoNotFoundOrNoAccess(String),
+    #[error("Pull request error: {0}")]
+    PullRequest(String),
+    #[error("Gitea is unavailable: {0}")]
+    Unavailable(String),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+impl GiteaServiceError {
+    pub fn should_retry(&self) -> bool {
+        matches!(
+            self,
+            GiteaServiceError::Request(_) | GiteaServiceError::Unavailable(_)
+        )
+    }
+}
+
+/// A repository on a Gitea instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaRepoInfo {
+    /// Instance URL without a trailing slash
+    pub base_url: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl GiteaRepoInfo {
+    /// Parse an SSH, HTTPS or pull request URL, returning None unless it
+    /// points at the instance at `base_url`. See tests for examples.
+    pub fn from_remote_url(remote_url: &str, base_url: &str) -> Option<Self> {
+        let base = Url::parse(base_url).ok()?;
+        let (host, path) = split_remote_url(remote_url)?;
+        if !host.eq_ignore_ascii_case(base.host_str()?) {
+            return None;
+        }
+
+        // Instances served under a sub-path prefix every repo path with it
+        let base_path = base.path().trim_end_matches('/');
+        let path = path.strip_prefix(base_path).unwrap_or(&path);
+        let mut segments = path.trim_matches('/').split('/');
+        let owner = segments.next().filter(|s| !s.is_empty())?;
+        let repo = segments.next()?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if repo.is_empty() {
+            return None;
+        }
+        Some(Self {
+            base_url: base.as_str().trim_end_matches('/').to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Web page of `branch`
+    pub fn branch_url(&self, branch: &str) -> String {
+        format!(
+            "{}/{}/{}/src/branch/{branch}",
+            self.base_url, self.owner, self.repo
+        )
+    }
+
+    fn pulls_url(&self) -> String {
+        format!(
+            "{}/api/v1/repos/{}/{}/pulls",
+            self.base_url, self.owner, self.repo
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    number: i64,
+    html_url: String,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    head: Branch,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    #[serde(rename = "ref")]
+    name: String,
+}
+
+impl From<PullRequest> for PullRequestInfo {
+    fn from(pr: PullRequest) -> Self {
+        let status = match pr.state.as_str() {
+            _ if pr.merged => MergeStatus::Merged,
+            "open" => MergeStatus::Open,
+            "closed" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: pr.number,
+            url: pr.html_url,
+            status,
+            merged_at: pr.merged_at,
+            merge_commit_sha: pr.merge_commit_sha,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GiteaService {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl GiteaService {
+    /// `token` is the value of the config's secret store reference
+    pub fn new(token: Option<String>) -> Result<Self, GiteaServiceError> {
+        let token = token
+            .filter(|token| !token.trim().is_empty())
+            .ok_or(GiteaServiceError::NotConfigured)?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self { client, token })
+    }
+
+    pub async fn create_pull_request(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, GiteaServiceError> {
+        let title = if request.draft.unwrap_or(false) {
+            format!("WIP: {}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let body = serde_json::json!({
+            "head": request.head_branch,
+            "base": request.base_branch,
+            "title": title,
+            "body": request.body.clone().unwrap_or_default(),
+        });
+        let url = repo_info.pulls_url();
+
+        // Not retried: a request that timed out may still have created the PR
+        let pr: PullRequest = self.send(self.client.post(&url).json(&body)).await?;
+
+        info!(
+            "Created Gitea PR #{} for branch {} in {}/{}",
+            pr.number, request.head_branch, repo_info.owner, repo_info.repo
+        );
+        Ok(pr.into())
+    }
+
+    pub async fn get_pull_request(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        number: i64,
+    ) -> Result<PullRequestInfo, GiteaServiceError> {
+        let url = format!("{}/{number}", repo_info.pulls_url());
+        let pr: PullRequest = self.send_with_retry(|| self.client.get(&url)).await?;
+        Ok(pr.into())
+    }
+
+    /// Pull requests from `branch` among the most recently updated ones, open
+    /// ones first. The API cannot filter by head branch.
+    pub async fn list_pull_requests_for_branch(
+        &self,
+        repo_info: &GiteaRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<PullRequestInfo>, GiteaServiceError> {
+        let url = repo_info.pulls_url();
+        let prs: Vec<PullRequest> = self
+            .send_with_retry(|| {
+                self.client.get(&url).query(&[
+                    ("state", "all"),
+                    ("sort", "recentupdate"),
+                    ("limit", BRANCH_SEARCH_LIMIT),
+                ])
+            })
+            .await?;
+
+        let mut prs: Vec<PullRequestInfo> = prs
+            .into_iter()
+            .filter(|pr| pr.head.name == branch)
+            .map(Into::into)
+            .collect();
+        prs.sort_by_key(|pr| !matches!(pr.status, MergeStatus::Open));
+        Ok(prs)
+    }
+
+    async fn send_with_retry<T, F>(&self, build: F) -> Result<T, GiteaServiceError>
+    where
+        T: DeserializeOwned,
+        F: Fn() -> RequestBuilder,
+    {
+        (|| async { self.send(build()).await })
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e: &GiteaServiceError| e.should_retry())
+            .notify(|err: &GiteaServiceError, dur: Duration| {
+                tracing::warn!(
+                    "Gitea API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, GiteaServiceError> {
+        let response = request
+            .header("Authorization", format!("token {}", self.token))
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let message = format!("{status}: {}", body.trim());
+        Err(match status {
+            StatusCode::UNAUTHORIZED => GiteaServiceError::AuthFailed(message),
+            StatusCode::FORBIDDEN | StatusCode::NOT_FOUND => {
+                GiteaServiceError::RepoNotFoundOrNoAccess(message)
+            }
+            s if s.is_server_error() || s == StatusCode::TOO_MANY_REQUESTS => {
+                GiteaServiceError::Unavailable(message)
+            }
+            _ => GiteaServiceError::PullRequest(message),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gitea_remote_urls() {
+        let cases = [
+            "git@git.example.com:acme/widgets.git",
+            "ssh://git@git.example.com:2222/acme/widgets.git",
+            "https://git.example.com/acme/widgets.git",
+            "https://git.example.com/acme/widgets/pulls/42",
+        ];
+        for url in cases {
+            let info = GiteaRepoInfo::from_remote_url(url, "https://git.example.com").unwrap();
+            assert_eq!(
+                (info.owner.as_str(), info.repo.as_str()),
+                ("acme", "widgets")
+            );
+        }
+
+        let info = GiteaRepoInfo::from_remote_url(
+            "https://example.com/gitea/acme/widgets",
+            "https://example.com/gitea/",
+        )
+        .unwrap();
+        assert_eq!(
+            info.branch_url("feature/x"),
+            "https://example.com/gitea/acme/widgets/src/branch/feature/x"
+        );
+        assert!(
+            GiteaRepoInfo::from_remote_url(
+                "https://github.com/acme/widgets",
+                "https://git.example.com"
+            )
+            .is_none()
+        );
+    }
+}
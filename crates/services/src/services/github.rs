@@ -9,14 +9,15 @@ use thiserror::Error;
 use tokio::task;
 use tracing::info;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 mod cli;
 
+pub use cli::{CheckRun, PrCommentAuthor, ReviewCommentUser};
 use cli::{GhCli, GhCliError, PrComment, PrReviewComment};
-pub use cli::{PrCommentAuthor, ReviewCommentUser};
 
 /// Unified PR comment that can be either a general comment or review comment
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(tag = "comment_type", rename_all = "snake_case")]
 #[ts(tag = "comment_type", rename_all = "snake_case")]
 pub enum UnifiedPrComment {
@@ -278,6 +279,43 @@ impl GitHubService {
         .await
     }
 
+    /// Mark a draft pull request as ready for review
+    pub async fn mark_pr_ready(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<(), GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let cli = self.gh_cli.clone();
+            task::spawn_blocking(move || cli.mark_pr_ready(&owner, &repo, pr_number))
+                .await
+                .map_err(|err| {
+                    GitHubServiceError::PullRequest(format!(
+                        "Failed to execute GitHub CLI for marking PR #{pr_number} ready: {err}"
+                    ))
+                })?
+                .map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
     /// List all pull requests for a branch (including closed/merged)
     pub async fn list_all_prs_for_branch(
         &self,
@@ -322,6 +360,47 @@ impl GitHubService {
         .await
     }
 
+    /// Check runs GitHub has for a commit or branch
+    pub async fn get_check_runs(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        git_ref: &str,
+    ) -> Result<Vec<CheckRun>, GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let git_ref = git_ref.to_string();
+            let cli = self.gh_cli.clone();
+            task::spawn_blocking({
+                let git_ref = git_ref.clone();
+                move || cli.get_check_runs(&owner, &repo, &git_ref)
+            })
+            .await
+            .map_err(|err| {
+                GitHubServiceError::Repository(format!(
+                    "Failed to execute GitHub CLI for fetching check runs of '{git_ref}': {err}"
+                ))
+            })?
+            .map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
     /// Fetch all comments (both general and review) for a pull request
     pub async fn get_pr_comments(
         &self,
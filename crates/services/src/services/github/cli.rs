@@ -58,6 +58,28 @@ pub struct PrReviewComment {
     pub author_association: String,
 }
 
+/// A check run reported for a commit (from gh api)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    /// `queued`, `in_progress` or `completed`
+    pub status: String,
+    /// Set once completed, e.g. `success`, `failure`, `skipped`
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+
+impl CheckRun {
+    /// Completed without failing
+    pub fn passed(&self) -> bool {
+        self.status == "completed"
+            && matches!(
+                self.conclusion.as_deref(),
+                Some("success" | "neutral" | "skipped")
+            )
+    }
+}
+
 /// High-level errors originating from the GitHub CLI.
 #[derive(Debug, Error)]
 pub enum GhCliError {
@@ -191,6 +213,18 @@ impl GhCli {
         Self::parse_pr_view(&raw)
     }
 
+    /// Mark a draft pull request as ready for review.
+    pub fn mark_pr_ready(&self, owner: &str, repo: &str, pr_number: i64) -> Result<(), GhCliError> {
+        self.run([
+            "pr",
+            "ready",
+            &pr_number.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+        ])?;
+        Ok(())
+    }
+
     /// List pull requests for a branch (includes closed/merged).
     pub fn list_prs_for_branch(
         &self,
@@ -245,6 +279,20 @@ impl GhCli {
         ])?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// Fetch the check runs of a commit, branch or tag via API.
+    pub fn get_check_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> Result<Vec<CheckRun>, GhCliError> {
+        let raw = self.run([
+            "api",
+            &format!("repos/{owner}/{repo}/commits/{git_ref}/check-runs?per_page=100"),
+        ])?;
+        Self::parse_check_runs(&raw)
+    }
 }
 
 impl GhCli {
@@ -357,6 +405,22 @@ impl GhCli {
         })
     }
 
+    fn parse_check_runs(raw: &str) -> Result<Vec<CheckRun>, GhCliError> {
+        let value: Value = serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse check runs API response: {err}; raw: {raw}"
+            ))
+        })?;
+        let runs = value.get("check_runs").cloned().ok_or_else(|| {
+            GhCliError::UnexpectedOutput(format!(
+                "Check runs API response missing 'check_runs' array: {value:#?}"
+            ))
+        })?;
+        serde_json::from_value(runs).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!("Failed to parse check runs: {err}"))
+        })
+    }
+
     fn extract_pr_info(value: &Value) -> Option<PullRequestInfo> {
         let number = value.get("number")?.as_i64()?;
         let url = value.get("url")?.as_str()?.to_string();
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 #[derive(Debug, Error)]
 pub enum GitHubClientError {
@@ -16,7 +17,7 @@ pub enum GitHubClientError {
 }
 
 /// Summary of a pull request for display purposes.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PullRequestSummary {
     pub number: u64,
     pub title: String,
@@ -112,6 +112,21 @@ pub struct WorktreeStatusSummary {
     pub untracked: usize,
 }
 
+/// Author and time of a commit, for activity summaries.
+#[derive(Debug, Clone)]
+pub struct CommitAuthorship {
+    pub author_name: String,
+    pub author_email: String,
+    pub time: DateTime<Utc>,
+}
+
+/// A file in a commit's tree with its size in bytes.
+#[derive(Debug, Clone)]
+pub struct TreeFile {
+    pub path: String,
+    pub size: u64,
+}
+
 /// Read-only interface for Git repository operations using gix.
 ///
 /// `GixReader` provides a unified way to open and read from both regular Git
@@ -297,13 +312,13 @@ impl GixReader {
                 },
             };
 
-            // Only include blob entries (files), skip trees/submodules
+            // Only include blob entries (files and symlinks), skip trees/submodules
             let dominated_by_blob = matches!(
                 entry.new_mode,
-                Some(EntryKind::Blob | EntryKind::BlobExecutable)
+                Some(EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link)
             ) || matches!(
                 entry.old_mode,
-                Some(EntryKind::Blob | EntryKind::BlobExecutable)
+                Some(EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link)
             );
 
             if dominated_by_blob {
@@ -851,6 +866,86 @@ impl GixReader {
 
         Ok(stats)
     }
+
+    /// Count (local, remote) branches. Remote `HEAD` aliases are not counted.
+    pub fn branch_counts(repo: &gix::Repository) -> Result<(usize, usize), GixReaderError> {
+        let references = repo
+            .references()
+            .map_err(|e| GixReaderError::ReferenceNotFound(format!("references: {e}")))?;
+        let local = references
+            .local_branches()
+            .map_err(|e| GixReaderError::ReferenceNotFound(format!("local branches: {e}")))?
+            .filter_map(Result::ok)
+            .count();
+        let remote = references
+            .remote_branches()
+            .map_err(|e| GixReaderError::ReferenceNotFound(format!("remote branches: {e}")))?
+            .filter_map(Result::ok)
+            .filter(|reference| !reference.name().shorten().ends_with(b"/HEAD"))
+            .count();
+        Ok((local, remote))
+    }
+
+    /// Author and time of up to `limit` commits reachable from HEAD.
+    ///
+    /// Returns an empty list for a repository without commits.
+    pub fn recent_commits(
+        repo: &gix::Repository,
+        limit: usize,
+    ) -> Result<Vec<CommitAuthorship>, GixReaderError> {
+        let Ok(head_id) = repo.head_id() else {
+            return Ok(Vec::new());
+        };
+
+        let mut commits = Vec::new();
+        for info_result in repo.rev_walk([head_id.detach()]).all()?.take(limit) {
+            let info = info_result?;
+            let commit = info
+                .object()
+                .map_err(|e| GixReaderError::Diff(format!("Failed to get commit object: {e}")))?;
+            let time = commit
+                .time()
+                .map_err(|e| GixReaderError::Diff(format!("Failed to get commit time: {e}")))?;
+            let author = commit
+                .author()
+                .map_err(|e| GixReaderError::InvalidObject(format!("commit author: {e}")))?;
+            commits.push(CommitAuthorship {
+                author_name: bstr_to_string(author.name),
+                author_email: bstr_to_string(author.email),
+                time: DateTime::from_timestamp(time.seconds, 0).unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Every file in HEAD's tree, with sizes read from object headers.
+    ///
+    /// Returns an empty list for a repository without commits.
+    pub fn head_tree_files(repo: &gix::Repository) -> Result<Vec<TreeFile>, GixReaderError> {
+        let Ok(commit) = repo.head_commit() else {
+            return Ok(Vec::new());
+        };
+        let tree = commit
+            .tree()
+            .map_err(|e| GixReaderError::Diff(format!("Failed to get commit tree: {e}")))?;
+
+        let mut recorder = gix::traverse::tree::Recorder::default();
+        tree.traverse()
+            .breadthfirst(&mut recorder)
+            .map_err(|e| GixReaderError::Diff(format!("Tree traversal failed: {e}")))?;
+
+        recorder
+            .records
+            .into_iter()
+            .filter(|entry| entry.mode.is_blob())
+            .map(|entry| {
+                Ok(TreeFile {
+                    path: bstr_to_string(entry.filepath.as_ref()),
+                    size: Self::blob_size(repo, entry.oid)? as u64,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Convert BStr to String, handling non-UTF8 paths gracefully
@@ -1505,6 +1600,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repo_summary_helpers() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path();
+        init_test_repo_via_cli(repo_path);
+
+        fs::create_dir(repo_path.join("src")).unwrap();
+        fs::write(repo_path.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(repo_path.join("README.md"), "# Test").unwrap();
+        Command::new("git")
+            .args(["add", "."])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Add files"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["branch", "feature"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+
+        let repo = GixReader::open(repo_path).unwrap();
+        assert_eq!(GixReader::branch_counts(&repo).unwrap(), (2, 0));
+
+        let commits = GixReader::recent_commits(&repo, 10).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert!(commits.iter().all(|c| c.author_email == "test@test.com"));
+        assert_eq!(GixReader::recent_commits(&repo, 1).unwrap().len(), 1);
+
+        let mut files = GixReader::head_tree_files(&repo).unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let files: Vec<_> = files.iter().map(|f| (f.path.as_str(), f.size)).collect();
+        assert_eq!(files, vec![("README.md", 6), ("src/main.rs", 13)]);
+    }
+
     /// Benchmark: measure gix performance vs git CLI for branchStatus operations
     /// This test demonstrates gix is faster than spawning git CLI processes.
     /// Run with: cargo nextest run --package services test_gix_performance_vs_cli --no-capture
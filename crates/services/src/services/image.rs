@@ -1,10 +1,23 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
+use chrono::{DateTime, Duration, Utc};
 use db::models::image::{CreateImage, Image};
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// How long an unreferenced image is kept. Images are uploaded before the
+/// task or message that uses them is saved, and drafts refer to them only
+/// by path.
+pub const ORPHAN_GRACE_PERIOD: Duration = Duration::hours(24);
+
 #[derive(Debug, thiserror::Error)]
 pub enum ImageError {
     #[error("IO error: {0}")]
@@ -26,6 +39,20 @@ pub enum ImageError {
     ResponseBuildError(String),
 }
 
+/// Outcome of an image garbage collection run
+#[derive(Debug, Clone, Default, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct ImageGcReport {
+    /// Unreferenced images deleted along with their files
+    pub images_removed: usize,
+    /// Files in the image cache without an image record
+    pub stray_files_removed: usize,
+    #[ts(type = "number")]
+    pub bytes_reclaimed: u64,
+    /// Images or files that could not be deleted; retried on the next run
+    pub failed: usize,
+}
+
 #[derive(Clone)]
 pub struct ImageService {
     cache_dir: PathBuf,
@@ -35,7 +62,11 @@ pub struct ImageService {
 
 impl ImageService {
     pub async fn new(pool: SqlitePool) -> Result<Self, ImageError> {
-        let cache_dir = utils::cache_dir().join("images");
+        Self::with_cache_dir(pool, utils::cache_dir().join("images")).await
+    }
+
+    /// Keep image files in `cache_dir` instead of the app's cache
+    pub async fn with_cache_dir(pool: SqlitePool, cache_dir: PathBuf) -> Result<Self, ImageError> {
         tokio::fs::create_dir_all(&cache_dir).await?;
         Ok(Self {
             cache_dir,
@@ -77,65 +108,121 @@ impl ImageService {
             return Err(ImageError::InvalidFormat);
         }
 
-        let existing_image = Image::find_by_hash(&self.pool, &hash).await?;
-
-        if let Some(existing) = existing_image {
+        if let Some(existing) = Image::find_by_hash(&self.pool, &hash).await? {
             tracing::debug!("Reusing existing image record with hash {}", hash);
-            return Ok(existing);
+            return self.reuse_image(existing, data).await;
         }
 
         let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
         let cached_path = self.cache_dir.join(&new_filename);
         tokio::fs::write(&cached_path, data).await?;
 
-        let image = Image::create(
+        let created = Image::create(
             &self.pool,
             &CreateImage {
                 file_path: new_filename,
                 original_name: original_filename.to_string(),
                 mime_type,
                 size_bytes: file_size as i64,
-                hash,
+                hash: hash.clone(),
             },
         )
-        .await?;
+        .await;
+        match created {
+            Ok(image) => Ok(image),
+            // The same image was uploaded concurrently; keep the other copy
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                tokio::fs::remove_file(&cached_path).await?;
+                let existing = Image::find_by_hash(&self.pool, &hash)
+                    .await?
+                    .ok_or(ImageError::NotFound)?;
+                self.reuse_image(existing, data).await
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Restart the grace period of a deduplicated image and restore its file
+    /// if it went missing from the cache
+    async fn reuse_image(&self, image: Image, data: &[u8]) -> Result<Image, ImageError> {
+        Image::touch(&self.pool, image.id).await?;
+        let cached_path = self.cache_dir.join(&image.file_path);
+        if !tokio::fs::try_exists(&cached_path).await? {
+            tracing::warn!("Restoring missing cache file {}", cached_path.display());
+            tokio::fs::write(&cached_path, data).await?;
+        }
         Ok(image)
     }
 
-    pub async fn delete_orphaned_images(&self) -> Result<(), ImageError> {
-        let orphaned_images = Image::find_orphaned_images(&self.pool).await?;
-        if orphaned_images.is_empty() {
-            tracing::debug!("No orphaned images found during cleanup");
-            return Ok(());
+    /// Delete images that no task or conversation refers to, and files in the
+    /// image cache that have no image record, once they are older than
+    /// `grace_period`
+    pub async fn collect_garbage(
+        &self,
+        grace_period: Duration,
+    ) -> Result<ImageGcReport, ImageError> {
+        let cutoff = Utc::now() - grace_period;
+        let mut report = ImageGcReport::default();
+
+        for image in Image::find_orphaned_images(&self.pool, cutoff).await? {
+            let file_path = self.cache_dir.join(&image.file_path);
+            let size = file_size(&file_path).await;
+            match Image::delete_if_orphaned(&self.pool, image.id, cutoff).await {
+                // Referenced again since it was listed
+                Ok(false) => continue,
+                Ok(true) => {}
+                Err(e) => {
+                    report.failed += 1;
+                    tracing::error!("Failed to delete orphaned image {}: {}", image.id, e);
+                    continue;
+                }
+            }
+            report.images_removed += 1;
+            match remove_file_if_exists(&file_path).await {
+                Ok(()) => report.bytes_reclaimed += size,
+                Err(e) => {
+                    // The record is gone, so the next run removes it as a stray file
+                    report.failed += 1;
+                    tracing::error!("Failed to delete {}: {}", file_path.display(), e);
+                }
+            }
         }
 
-        tracing::debug!(
-            "Found {} orphaned images to clean up",
-            orphaned_images.len()
-        );
-        let mut deleted_count = 0;
-        let mut failed_count = 0;
-
-        for image in orphaned_images {
-            match self.delete_image(image.id).await {
-                Ok(_) => {
-                    deleted_count += 1;
-                    tracing::debug!("Deleted orphaned image: {}", image.id);
+        let known: HashSet<String> = Image::find_all_file_paths(&self.pool)
+            .await?
+            .into_iter()
+            .collect();
+        let cutoff = SystemTime::from(cutoff);
+        let mut entries = tokio::fs::read_dir(&self.cache_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            let is_known = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| known.contains(name));
+            if !metadata.is_file() || is_known || metadata.modified()? >= cutoff {
+                continue;
+            }
+            match tokio::fs::remove_file(entry.path()).await {
+                Ok(()) => {
+                    report.stray_files_removed += 1;
+                    report.bytes_reclaimed += metadata.len();
                 }
                 Err(e) => {
-                    failed_count += 1;
-                    tracing::error!("Failed to delete orphaned image {}: {}", image.id, e);
+                    report.failed += 1;
+                    tracing::error!("Failed to delete {}: {}", entry.path().display(), e);
                 }
             }
         }
 
         tracing::info!(
-            "Image cleanup completed: {} deleted, {} failed",
-            deleted_count,
-            failed_count
+            "Image cleanup completed: {} images and {} stray files deleted ({} bytes), {} failed",
+            report.images_removed,
+            report.stray_files_removed,
+            report.bytes_reclaimed,
+            report.failed
         );
-
-        Ok(())
+        Ok(report)
     }
 
     pub fn get_absolute_path(&self, image: &Image) -> PathBuf {
@@ -232,3 +319,17 @@ impl ImageService {
         Ok(())
     }
 }
+
+async fn file_size(path: &Path) -> u64 {
+    tokio::fs::metadata(path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+async fn remove_file_if_exists(path: &Path) -> std::io::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
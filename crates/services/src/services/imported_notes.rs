@@ -0,0 +1,21 @@
+//! Markdown recording the history of an imported attempt in its task's notes,
+//! shared by project archives and workspace bundles.
+
+/// Append one turn: what was asked of the agent and its reply, both quoted
+pub fn push_turn(notes: &mut String, prompt: Option<&str>, summary: Option<&str>) {
+    if let Some(prompt) = prompt.filter(|p| !p.trim().is_empty()) {
+        notes.push('\n');
+        notes.push_str(&quote(prompt));
+    }
+    if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+        notes.push_str("\n**Agent:**\n\n");
+        notes.push_str(&quote(summary));
+    }
+}
+
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|line| format!("> {line}\n"))
+        .collect()
+}
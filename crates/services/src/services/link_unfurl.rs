@@ -0,0 +1,338 @@
+//! Title and status lookup for links attached to tasks.
+//!
+//! GitHub issues and pull requests use the REST API, with the stored GitHub
+//! token when there is one. Linear issues need the API key the config keeps
+//! in the secret store and are skipped without it. Figma files go through
+//! Figma's public oEmbed endpoint, which has a title but no status. Other
+//! URLs are not unfurled.
+
+use std::time::Duration;
+
+use db::models::task_link::TaskLinkKind;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum LinkUnfurlError {
+    #[error("{service} returned {status}")]
+    Status {
+        service: &'static str,
+        status: reqwest::StatusCode,
+    },
+    #[error("Linear error: {0}")]
+    Linear(String),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+/// A link on a service that can be unfurled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    GitHubIssue {
+        owner: String,
+        repo: String,
+        number: u64,
+    },
+    GitHubPull {
+        owner: String,
+        repo: String,
+        number: u64,
+    },
+    /// Issue identifier such as `ENG-123`
+    Linear {
+        identifier: String,
+    },
+    Figma,
+}
+
+impl LinkTarget {
+    /// Recognise a GitHub, Linear or Figma URL. See tests for examples.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = Url::parse(url.trim()).ok()?;
+        let host = url.host_str()?.trim_start_matches("www.");
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        match (host, segments.as_slice()) {
+            ("github.com", [owner, repo, kind, number, ..]) => {
+                let number = number.parse().ok()?;
+                let (owner, repo) = (owner.to_string(), repo.to_string());
+                match *kind {
+                    "issues" => Some(Self::GitHubIssue {
+                        owner,
+                        repo,
+                        number,
+                    }),
+                    "pull" => Some(Self::GitHubPull {
+                        owner,
+                        repo,
+                        number,
+                    }),
+                    _ => None,
+                }
+            }
+            ("linear.app", [_workspace, "issue", identifier, ..]) => Some(Self::Linear {
+                identifier: identifier.to_uppercase(),
+            }),
+            ("figma.com", [kind, ..]) if matches!(*kind, "file" | "design" | "proto" | "board") => {
+                Some(Self::Figma)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> TaskLinkKind {
+        match self {
+            Self::GitHubIssue { .. } | Self::Linear { .. } => TaskLinkKind::Ticket,
+            Self::GitHubPull { .. } => TaskLinkKind::PullRequest,
+            Self::Figma => TaskLinkKind::DesignDoc,
+        }
+    }
+}
+
+/// What the linked service says about a link
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnfurledLink {
+    pub title: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    pull_request: Option<GitHubIssuePull>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssuePull {
+    merged_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearResponse {
+    data: Option<LinearData>,
+    errors: Option<Vec<LinearError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearData {
+    issue: Option<LinearIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearIssue {
+    title: String,
+    state: Option<LinearState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearState {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FigmaOembed {
+    title: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinkUnfurler {
+    client: reqwest::Client,
+    github_token: Option<String>,
+    linear_api_key: Option<String>,
+}
+
+impl LinkUnfurler {
+    /// `linear_api_key` is the value of the config's secret store reference
+    pub fn new(
+        github_token: Option<String>,
+        linear_api_key: Option<String>,
+    ) -> Result<Self, LinkUnfurlError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        let linear_api_key = linear_api_key.filter(|key| !key.trim().is_empty());
+
+        Ok(Self {
+            client,
+            github_token,
+            linear_api_key,
+        })
+    }
+
+    /// Fetch the title and status of `url`. Returns None for URLs on other
+    /// services and for Linear issues when no API key is set.
+    pub async fn unfurl(&self, url: &str) -> Result<Option<UnfurledLink>, LinkUnfurlError> {
+        match LinkTarget::parse(url) {
+            Some(LinkTarget::GitHubIssue {
+                owner,
+                repo,
+                number,
+            })
+            | Some(LinkTarget::GitHubPull {
+                owner,
+                repo,
+                number,
+            }) => self.unfurl_github(&owner, &repo, number).await.map(Some),
+            Some(LinkTarget::Linear { identifier }) => self.unfurl_linear(&identifier).await,
+            Some(LinkTarget::Figma) => self.unfurl_figma(url).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn unfurl_github(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<UnfurledLink, LinkUnfurlError> {
+        // The issues endpoint also serves pull requests, with merge state
+        let mut request = self
+            .client
+            .get(format!(
+                "https://api.github.com/repos/{owner}/{repo}/issues/{number}"
+            ))
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, "vibe-kanban");
+        if let Some(token) = &self.github_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(LinkUnfurlError::Status {
+                service: "GitHub",
+                status: response.status(),
+            });
+        }
+        let issue: GitHubIssue = response.json().await?;
+
+        let status = match &issue.pull_request {
+            Some(pull) if pull.merged_at.is_some() => "merged".to_string(),
+            Some(_) if issue.draft && issue.state == "open" => "draft".to_string(),
+            _ => issue.state,
+        };
+        Ok(UnfurledLink {
+            title: Some(issue.title),
+            status: Some(status),
+        })
+    }
+
+    async fn unfurl_linear(
+        &self,
+        identifier: &str,
+    ) -> Result<Option<UnfurledLink>, LinkUnfurlError> {
+        let Some(api_key) = &self.linear_api_key else {
+            return Ok(None);
+        };
+        let body = json!({
+            "query": "query($id: String!) { issue(id: $id) { title state { name } } }",
+            "variables": { "id": identifier },
+        });
+        let response = self
+            .client
+            .post("https://api.linear.app/graphql")
+            .header(AUTHORIZATION, api_key)
+            .json(&body)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(LinkUnfurlError::Status {
+                service: "Linear",
+                status: response.status(),
+            });
+        }
+        let response: LinearResponse = response.json().await?;
+        if let Some(error) = response.errors.into_iter().flatten().next() {
+            return Err(LinkUnfurlError::Linear(error.message));
+        }
+
+        let issue = response
+            .data
+            .and_then(|data| data.issue)
+            .ok_or_else(|| LinkUnfurlError::Linear(format!("issue {identifier} not found")))?;
+        Ok(Some(UnfurledLink {
+            title: Some(issue.title),
+            status: issue.state.map(|state| state.name),
+        }))
+    }
+
+    async fn unfurl_figma(&self, url: &str) -> Result<UnfurledLink, LinkUnfurlError> {
+        let response = self
+            .client
+            .get("https://www.figma.com/api/oembed")
+            .query(&[("url", url)])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(LinkUnfurlError::Status {
+                service: "Figma",
+                status: response.status(),
+            });
+        }
+        let oembed: FigmaOembed = response.json().await?;
+        Ok(UnfurledLink {
+            title: oembed.title,
+            status: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_links() {
+        assert_eq!(
+            LinkTarget::parse("https://github.com/BloopAI/vibe-kanban/pull/412/files"),
+            Some(LinkTarget::GitHubPull {
+                owner: "BloopAI".to_string(),
+                repo: "vibe-kanban".to_string(),
+                number: 412,
+            })
+        );
+        assert_eq!(
+            LinkTarget::parse("https://www.github.com/o/r/issues/7#issuecomment-1"),
+            Some(LinkTarget::GitHubIssue {
+                owner: "o".to_string(),
+                repo: "r".to_string(),
+                number: 7,
+            })
+        );
+        assert_eq!(
+            LinkTarget::parse("https://linear.app/acme/issue/eng-123/fix-login"),
+            Some(LinkTarget::Linear {
+                identifier: "ENG-123".to_string(),
+            })
+        );
+        assert_eq!(
+            LinkTarget::parse("https://www.figma.com/design/AbC123/Checkout?node-id=1-2"),
+            Some(LinkTarget::Figma)
+        );
+        assert_eq!(LinkTarget::Figma.kind(), TaskLinkKind::DesignDoc);
+    }
+
+    #[test]
+    fn ignores_other_links() {
+        for url in [
+            "https://github.com/o/r",
+            "https://github.com/o/r/pull/new",
+            "https://github.com/o/r/blob/main/README.md",
+            "https://linear.app/acme/project/roadmap",
+            "https://docs.google.com/document/d/1",
+            "not a url",
+        ] {
+            assert_eq!(LinkTarget::parse(url), None, "{url}");
+        }
+    }
+}
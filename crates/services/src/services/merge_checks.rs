@@ -0,0 +1,212 @@
+//! Required checks the merge queue runs before merging a branch.
+//!
+//! A project's [`MergeCheckPolicy`] can require GitHub check runs on the
+//! pushed task branch to have passed, a local command to succeed in the
+//! rebased worktree, or both. GitHub is asked first since it is cheap; a
+//! failure there skips the command. Checks that are still running count as
+//! failed, so the queue never waits on CI.
+
+use std::{path::Path, process::Stdio, time::Duration};
+
+use db::models::merge_check_policy::MergeCheckPolicy;
+use tokio::process::Command;
+use utils::{shell::get_shell_command, text::truncate_output};
+
+use super::{
+    git::GitService,
+    github::{CheckRun, GitHubService},
+};
+
+/// Command output beyond this is cut before it is stored
+const MAX_OUTPUT_CHARS: usize = 16_000;
+
+/// Why a branch did not pass its required checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckFailure {
+    /// One line for the queue entry and notifications
+    pub message: String,
+    /// Command output or the failing check runs
+    pub output: String,
+}
+
+/// Run the checks `policy` requires for `task_branch`. `worktree_path` is
+/// the task's worktree after rebasing onto the target branch.
+pub async fn run_required_checks(
+    policy: &MergeCheckPolicy,
+    git: &GitService,
+    repo_path: &Path,
+    worktree_path: &Path,
+    task_branch: &str,
+) -> Result<(), CheckFailure> {
+    if policy.require_github_checks {
+        check_github_runs(git, repo_path, task_branch).await?;
+    }
+    if let Some(command) = &policy.check_command {
+        let timeout = Duration::from_secs(policy.timeout_secs.max(1) as u64);
+        run_check_command(command, worktree_path, timeout).await?;
+    }
+    Ok(())
+}
+
+async fn check_github_runs(
+    git: &GitService,
+    repo_path: &Path,
+    task_branch: &str,
+) -> Result<(), CheckFailure> {
+    let unavailable = |err: String| CheckFailure {
+        message: "GitHub checks could not be loaded".to_string(),
+        output: err,
+    };
+    let repo_info = git
+        .get_github_repo_info(repo_path)
+        .map_err(|e| unavailable(e.to_string()))?;
+    let github = GitHubService::new().map_err(|e| unavailable(e.to_string()))?;
+    let runs = github
+        .get_check_runs(&repo_info, task_branch)
+        .await
+        .map_err(|e| unavailable(e.to_string()))?;
+    evaluate_check_runs(&runs)
+}
+
+/// Passes only when there is at least one run and every run passed
+fn evaluate_check_runs(runs: &[CheckRun]) -> Result<(), CheckFailure> {
+    if runs.is_empty() {
+        return Err(CheckFailure {
+            message: "GitHub reported no checks for the branch; is it pushed?".to_string(),
+            output: String::new(),
+        });
+    }
+    let failing: Vec<&CheckRun> = runs.iter().filter(|run| !run.passed()).collect();
+    if failing.is_empty() {
+        return Ok(());
+    }
+
+    let pending = failing
+        .iter()
+        .filter(|run| run.status != "completed")
+        .count();
+    let message = if pending == failing.len() {
+        format!("{pending} GitHub check(s) still running")
+    } else {
+        format!(
+            "{} of {} GitHub check(s) did not pass",
+            failing.len(),
+            runs.len()
+        )
+    };
+    let output = failing
+        .iter()
+        .map(|run| {
+            let state = run.conclusion.as_deref().unwrap_or(&run.status);
+            match &run.html_url {
+                Some(url) => format!("{}: {state} ({url})", run.name),
+                None => format!("{}: {state}", run.name),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(CheckFailure { message, output })
+}
+
+async fn run_check_command(
+    command: &str,
+    worktree_path: &Path,
+    timeout: Duration,
+) -> Result<(), CheckFailure> {
+    let (shell, shell_arg) = get_shell_command();
+    let mut process = Command::new(shell);
+    process
+        .arg(shell_arg)
+        .arg(command)
+        .current_dir(worktree_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = match tokio::time::timeout(timeout, process.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            return Err(CheckFailure {
+                message: format!("Check command could not be started: {e}"),
+                output: String::new(),
+            });
+        }
+        Err(_) => {
+            return Err(CheckFailure {
+                message: format!("Check command timed out after {}s", timeout.as_secs()),
+                output: String::new(),
+            });
+        }
+    };
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+    combined.push_str(&String::from_utf8_lossy(&output.stderr));
+    let message = match output.status.code() {
+        Some(code) => format!("Check command exited with code {code}"),
+        None => "Check command was killed".to_string(),
+    };
+    Err(CheckFailure {
+        message,
+        output: truncate_output(combined.trim(), MAX_OUTPUT_CHARS),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str, status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            name: name.to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            html_url: None,
+        }
+    }
+
+    #[test]
+    fn check_runs_pass_only_when_all_completed_cleanly() {
+        assert!(
+            evaluate_check_runs(&[
+                run("build", "completed", Some("success")),
+                run("lint", "completed", Some("skipped")),
+            ])
+            .is_ok()
+        );
+
+        let failure = evaluate_check_runs(&[
+            run("build", "completed", Some("success")),
+            run("test", "completed", Some("failure")),
+        ])
+        .unwrap_err();
+        assert_eq!(failure.message, "1 of 2 GitHub check(s) did not pass");
+        assert_eq!(failure.output, "test: failure");
+
+        let pending = evaluate_check_runs(&[run("test", "in_progress", None)]).unwrap_err();
+        assert_eq!(pending.message, "1 GitHub check(s) still running");
+
+        assert!(evaluate_check_runs(&[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failing_command_captures_output() {
+        let dir = std::env::temp_dir();
+
+        assert!(
+            run_check_command("true", &dir, Duration::from_secs(10))
+                .await
+                .is_ok()
+        );
+
+        let failure = run_check_command("echo broken; exit 2", &dir, Duration::from_secs(10))
+            .await
+            .unwrap_err();
+        assert_eq!(failure.message, "Check command exited with code 2");
+        assert_eq!(failure.output, "broken");
+    }
+}
@@ -1,13 +1,18 @@
 //! Merge Queue Processor Service
 //!
 //! Processes entries in the merge queue for a project, orchestrating:
-//! rebase → merge, handling conflicts by skipping to next task.
+//! rebase → required checks → merge → restack, handling conflicts and failed
+//! checks by skipping to next task. An entry stacked on a queued task's
+//! branch waits until that task has merged and it has been restacked.
 
 use std::{path::Path, sync::Arc};
 
 use db::models::{
     execution_queue::ExecutionQueue,
     merge::Merge,
+    merge_check_policy::MergeCheckPolicy,
+    operation::OperationKind,
+    permission_finding::PermissionFinding,
     repo::Repo,
     session::Session,
     task::{Task, TaskStatus},
@@ -22,14 +27,22 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::{
-    autopilot,
+    autopilot, branch_stack,
     config::Config,
     domain_events::{DomainEvent, EventDispatchCallback},
-    git::{GitService, GitServiceError},
-    merge_queue_store::{MergeQueueEntry, MergeQueueStore},
+    git::{CommitHooks, GitService, GitServiceError, HookedMerge},
+    git_hooks, merge_checks,
+    merge_queue_store::{
+        MergeQueueEntry, MergeQueueOutcome, MergeQueueRun, MergeQueueStatus, MergeQueueStore,
+    },
     operation_status::{OperationStatus, OperationStatusStore, OperationStatusType},
+    operations::OperationTracker,
 };
 
+/// Returned by `process_entry` instead of a merge commit when the branch had
+/// nothing to merge
+const NOTHING_TO_MERGE: &str = "skipped:nothing_to_merge";
+
 /// Errors that can occur during merge queue processing
 #[derive(Debug, Error)]
 pub enum MergeQueueError {
@@ -56,6 +69,12 @@ pub enum MergeQueueError {
 
     #[error("Rebase conflict: {0}")]
     RebaseConflict(String),
+
+    #[error("{0} permission finding(s) must be acknowledged before merging")]
+    UnacknowledgedPermissionFindings(i64),
+
+    #[error("Required checks failed: {message}")]
+    ChecksFailed { message: String, output: String },
 }
 
 impl MergeQueueError {
@@ -82,6 +101,7 @@ pub struct MergeQueueProcessor {
     git: GitService,
     merge_queue_store: MergeQueueStore,
     operation_status: Option<OperationStatusStore>,
+    operations: Option<OperationTracker>,
     config: Arc<RwLock<Config>>,
     event_dispatcher: Option<EventDispatchCallback>,
 }
@@ -100,6 +120,7 @@ impl MergeQueueProcessor {
             merge_queue_store,
             operation_status: None,
             config,
+            operations: None,
             event_dispatcher: None,
         }
     }
@@ -118,11 +139,18 @@ impl MergeQueueProcessor {
             merge_queue_store,
             operation_status: Some(operation_status),
             config,
+            operations: None,
             event_dispatcher: None,
         }
     }
 
     /// Set the event dispatcher callback for dispatching domain events.
+    /// Record each merge in the operation history.
+    pub fn with_operations(mut self, operations: OperationTracker) -> Self {
+        self.operations = Some(operations);
+        self
+    }
+
     pub fn with_event_dispatcher(mut self, dispatcher: EventDispatchCallback) -> Self {
         self.event_dispatcher = Some(dispatcher);
         self
@@ -133,15 +161,18 @@ impl MergeQueueProcessor {
     /// This method loops through the queue, processing each entry:
     /// 1. Claims the next queued entry (updates status to 'merging')
     /// 2. Performs rebase to update task branch with base branch changes
-    /// 3. Uses pre-populated commit message
-    /// 4. Performs the merge
+    /// 3. Runs the project's required checks, if any
+    /// 4. Uses pre-populated commit message
+    /// 5. Performs the merge
     ///
-    /// On conflict, the entry is removed and processing continues with the next entry.
+    /// On conflict or failed checks, the entry is removed and processing
+    /// continues with the next entry. Failed checks also flag the task as
+    /// needing attention.
     pub async fn process_project_queue(&self, project_id: Uuid) -> Result<(), MergeQueueError> {
         info!(%project_id, "Starting merge queue processing");
 
         loop {
-            // Claim the next queued entry from the in-memory store
+            // Claim the next queued entry from the store
             let entry = match self.merge_queue_store.claim_next(project_id) {
                 Some(entry) => entry,
                 None => {
@@ -157,35 +188,138 @@ impl MergeQueueProcessor {
                 "Processing merge queue entry"
             );
 
-            // Set Merging operation status (load workspace to get task_id)
-            if let Some(ref op_status) = self.operation_status {
-                if let Ok(Some(workspace)) =
-                    Workspace::find_by_id(&self.pool, entry.workspace_id).await
-                {
-                    op_status.set(OperationStatus::new(
-                        entry.workspace_id,
-                        workspace.task_id,
-                        OperationStatusType::Merging,
-                    ));
+            if entry.needs_revalidation {
+                match self.stale_reason(&entry).await {
+                    Ok(None) => {}
+                    Ok(Some(reason)) => {
+                        warn!(
+                            entry_id = %entry.id,
+                            %reason,
+                            "Reloaded merge queue entry no longer applies, removing entry"
+                        );
+                        self.merge_queue_store.remove(entry.workspace_id);
+                        self.merge_queue_store.record_run(MergeQueueRun::new(
+                            &entry,
+                            MergeQueueOutcome::Stale { reason },
+                        ));
+                        continue;
+                    }
+                    Err(e) => {
+                        error!(
+                            entry_id = %entry.id,
+                            error = %e,
+                            "Failed to revalidate merge queue entry, removing entry"
+                        );
+                        self.merge_queue_store.remove(entry.workspace_id);
+                        self.merge_queue_store.record_run(MergeQueueRun::new(
+                            &entry,
+                            MergeQueueOutcome::Failed {
+                                message: e.to_string(),
+                            },
+                        ));
+                        continue;
+                    }
                 }
             }
 
+            match self.queued_stack_parent(&entry).await {
+                Ok(Some(parent_workspace_id)) => {
+                    info!(
+                        entry_id = %entry.id,
+                        %parent_workspace_id,
+                        "Entry is stacked on a queued branch, waiting for it to merge"
+                    );
+                    self.merge_queue_store
+                        .requeue_after(entry.workspace_id, parent_workspace_id);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(entry_id = %entry.id, error = %e, "Failed to look up stack parent");
+                }
+            }
+
+            // Set Merging operation status (load workspace to get task_id)
+            let task_id = Workspace::find_by_id(&self.pool, entry.workspace_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|workspace| workspace.task_id);
+            if let (Some(op_status), Some(task_id)) = (&self.operation_status, task_id) {
+                op_status.set(OperationStatus::new(
+                    entry.workspace_id,
+                    task_id,
+                    OperationStatusType::Merging,
+                ));
+            }
+            let operation_id = match &self.operations {
+                Some(operations) => {
+                    operations
+                        .start(OperationKind::Merge, Some(entry.workspace_id), task_id)
+                        .await
+                }
+                None => None,
+            };
+
             // Process this entry, handling errors gracefully
-            let result = self.process_entry(&entry).await;
+            let result = self.process_entry(&entry, operation_id).await;
 
             // Clear operation status after processing (success or failure)
             if let Some(ref op_status) = self.operation_status {
                 op_status.clear(entry.workspace_id);
             }
+            if let Some(operations) = &self.operations {
+                operations
+                    .finish(operation_id, result.as_ref().map(|_| ()))
+                    .await;
+            }
 
-            match result {
-                Ok(merge_commit) => {
+            let outcome = match result {
+                Ok(merge) if merge.commit == NOTHING_TO_MERGE => {
+                    // Entry already removed in process_entry
+                    MergeQueueOutcome::NothingToMerge
+                }
+                Ok(HookedMerge {
+                    commit: merge_commit,
+                    hook_failure,
+                }) => {
                     info!(
                         entry_id = %entry.id,
                         %merge_commit,
                         "Merge completed successfully"
                     );
+                    if hook_failure.is_some() {
+                        warn!(
+                            entry_id = %entry.id,
+                            "Git hooks failed on the merge; committed without them"
+                        );
+                        if let Some(task_id) = task_id
+                            && let Err(e) =
+                                Task::update_needs_attention(&self.pool, task_id, Some(true)).await
+                        {
+                            error!(%task_id, error = %e, "Failed to flag task for attention");
+                        }
+                    }
                     // Entry already removed in process_entry
+                    MergeQueueOutcome::Merged {
+                        merge_commit,
+                        hook_failure,
+                    }
+                }
+                Err(MergeQueueError::ChecksFailed { message, output }) => {
+                    warn!(
+                        entry_id = %entry.id,
+                        %message,
+                        "Merge queue entry failed its required checks, removing entry"
+                    );
+                    self.merge_queue_store.remove(entry.workspace_id);
+                    if let Some(task_id) = task_id
+                        && let Err(e) =
+                            Task::update_needs_attention(&self.pool, task_id, Some(true)).await
+                    {
+                        error!(%task_id, error = %e, "Failed to flag task for attention");
+                    }
+                    MergeQueueOutcome::ChecksFailed { message, output }
                 }
                 Err(e) if e.is_conflict() => {
                     warn!(
@@ -195,6 +329,9 @@ impl MergeQueueProcessor {
                     );
                     self.merge_queue_store.remove(entry.workspace_id);
                     // Continue to next entry
+                    MergeQueueOutcome::Conflict {
+                        message: e.to_string(),
+                    }
                 }
                 Err(e) => {
                     error!(
@@ -204,15 +341,112 @@ impl MergeQueueProcessor {
                     );
                     self.merge_queue_store.remove(entry.workspace_id);
                     // Continue to next entry
+                    MergeQueueOutcome::Failed {
+                        message: e.to_string(),
+                    }
                 }
+            };
+            if let (
+                MergeQueueOutcome::Conflict { message }
+                | MergeQueueOutcome::ChecksFailed { message, .. }
+                | MergeQueueOutcome::Failed { message },
+                Some(task_id),
+                Some(dispatcher),
+            ) = (&outcome, task_id, &self.event_dispatcher)
+            {
+                dispatcher(DomainEvent::MergeFailed {
+                    task_id,
+                    workspace_id: entry.workspace_id,
+                    message: message.clone(),
+                })
+                .await;
             }
+            self.merge_queue_store
+                .record_run(MergeQueueRun::new(&entry, outcome));
         }
     }
 
+    async fn report_progress(&self, operation_id: Option<Uuid>, percent: u8, message: &str) {
+        if let Some(operations) = &self.operations {
+            operations
+                .progress(operation_id, percent, Some(message))
+                .await;
+        }
+    }
+
+    /// The workspace an entry is stacked on, if that workspace is waiting in
+    /// the queue too and so should merge first
+    async fn queued_stack_parent(
+        &self,
+        entry: &MergeQueueEntry,
+    ) -> Result<Option<Uuid>, MergeQueueError> {
+        let Some(workspace_repo) = WorkspaceRepo::find_by_workspace_and_repo_id(
+            &self.pool,
+            entry.workspace_id,
+            entry.repo_id,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        let parent = WorkspaceRepo::find_stack_parent(
+            &self.pool,
+            entry.repo_id,
+            &workspace_repo.target_branch,
+        )
+        .await?;
+        Ok(parent.filter(|parent_id| {
+            *parent_id != entry.workspace_id
+                && self
+                    .merge_queue_store
+                    .get(*parent_id)
+                    .is_some_and(|parent| {
+                        parent.project_id == entry.project_id
+                            && parent.status == MergeQueueStatus::Queued
+                    })
+        }))
+    }
+
+    /// Why an entry reloaded after a restart should no longer be merged, if
+    /// it shouldn't. Branch state (commits ahead, conflicts) is checked by
+    /// `process_entry` as for any other entry.
+    async fn stale_reason(
+        &self,
+        entry: &MergeQueueEntry,
+    ) -> Result<Option<String>, MergeQueueError> {
+        let Some(workspace) = Workspace::find_by_id(&self.pool, entry.workspace_id).await? else {
+            return Ok(Some("workspace was deleted".to_string()));
+        };
+        if workspace.container_ref.is_none() {
+            return Ok(Some("workspace has no worktree".to_string()));
+        }
+
+        match Task::find_by_id(&self.pool, workspace.task_id).await? {
+            None => return Ok(Some("task was deleted".to_string())),
+            Some(task) if matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled) => {
+                return Ok(Some(format!("task is already {}", task.status)));
+            }
+            Some(_) => {}
+        }
+
+        let merges =
+            Merge::find_by_workspace_and_repo_id(&self.pool, entry.workspace_id, entry.repo_id)
+                .await?;
+        if merges.iter().any(|m| matches!(m, Merge::Direct(_))) {
+            return Ok(Some("workspace was already merged".to_string()));
+        }
+
+        Ok(None)
+    }
+
     /// Process a single merge queue entry
     ///
     /// Returns the merge commit SHA on success
-    async fn process_entry(&self, entry: &MergeQueueEntry) -> Result<String, MergeQueueError> {
+    async fn process_entry(
+        &self,
+        entry: &MergeQueueEntry,
+        operation_id: Option<Uuid>,
+    ) -> Result<HookedMerge, MergeQueueError> {
         // Load required entities
         let workspace = Workspace::find_by_id(&self.pool, entry.workspace_id)
             .await?
@@ -241,6 +475,14 @@ impl MergeQueueProcessor {
         })?;
         let worktree_path = std::path::PathBuf::from(container_ref).join(&repo.name);
 
+        let open_findings =
+            PermissionFinding::count_unacknowledged(&self.pool, workspace.id).await?;
+        if open_findings > 0 {
+            return Err(MergeQueueError::UnacknowledgedPermissionFindings(
+                open_findings,
+            ));
+        }
+
         let task_branch = &workspace.branch;
         let base_branch = &workspace_repo.target_branch;
 
@@ -256,7 +498,10 @@ impl MergeQueueProcessor {
                 "Nothing to merge (0 commits ahead), removing from queue"
             );
             self.merge_queue_store.remove(entry.workspace_id);
-            return Ok("skipped:nothing_to_merge".to_string());
+            return Ok(HookedMerge {
+                commit: NOTHING_TO_MERGE.to_string(),
+                hook_failure: None,
+            });
         }
 
         info!(
@@ -269,38 +514,98 @@ impl MergeQueueProcessor {
             "Executing merge for workspace"
         );
 
+        // What workspaces stacked on this branch were built on, before the
+        // rebase rewrites it
+        let stacked_on_tip = self.git.get_branch_oid(repo_path, task_branch)?;
+
         // Step 1: Rebase task branch onto base branch
+        self.report_progress(operation_id, 10, "Rebasing onto the target branch")
+            .await;
         self.rebase_if_needed(repo_path, &worktree_path, base_branch, task_branch)
             .await?;
 
-        // Step 2: Use commit message from entry (always populated at enqueue time)
+        // Step 2: Run the project's required checks on the rebased branch
+        let checks = MergeCheckPolicy::find_or_default(&self.pool, task.project_id).await?;
+        if checks.is_enabled() {
+            self.report_progress(operation_id, 30, "Running required checks")
+                .await;
+            merge_checks::run_required_checks(
+                &checks,
+                &self.git,
+                repo_path,
+                &worktree_path,
+                task_branch,
+            )
+            .await
+            .map_err(|failure| MergeQueueError::ChecksFailed {
+                message: failure.message,
+                output: failure.output,
+            })?;
+        }
+
+        // Step 3: Use commit message from entry (always populated at enqueue time)
         let commit_message = &entry.commit_message;
 
-        // Step 3: Merge changes
-        let merge_commit = self
+        // Step 4: Merge changes
+        self.report_progress(operation_id, 50, "Merging").await;
+        // Hooks run where the target branch is checked out
+        let checkout_path = self
+            .git
+            .find_checkout_path_for_branch(repo_path, base_branch)?
+            .unwrap_or_else(|| repo_path.to_path_buf());
+        let hooks = git_hooks::commit_hooks_for_project(
+            &self.pool,
+            task.project_id,
+            repo_path,
+            &checkout_path,
+        )
+        .await?;
+        let merge = self
             .merge_changes(
                 repo_path,
                 &worktree_path,
                 task_branch,
                 base_branch,
                 commit_message,
+                &hooks,
             )
             .await?;
 
-        // Step 4: Remove the queue entry (completed successfully)
+        // Step 5: Remove the queue entry (completed successfully)
         self.merge_queue_store.remove(entry.workspace_id);
 
-        // Step 5: Create merge record
+        // Step 6: Create merge record
+        self.report_progress(operation_id, 90, "Recording the merge")
+            .await;
         Merge::create_direct(
             &self.pool,
             workspace.id,
             repo.id,
             base_branch,
-            &merge_commit,
+            &merge.commit,
         )
         .await?;
 
-        // Step 6: Update task status to Done
+        // Step 7: Move workspaces stacked on this branch onto the base branch
+        let restacked = branch_stack::restack_after_merge(
+            &self.pool,
+            &self.git,
+            &repo,
+            task_branch,
+            &stacked_on_tip,
+            base_branch,
+        )
+        .await;
+        if !restacked.is_empty() {
+            info!(
+                workspace_id = %workspace.id,
+                restacked = restacked.iter().filter(|r| r.error.is_none()).count(),
+                failed = restacked.iter().filter(|r| r.error.is_some()).count(),
+                "Restacked workspaces stacked on the merged branch"
+            );
+        }
+
+        // Step 8: Update task status to Done
         let previous_status = task.status.clone();
         Task::update_status(&self.pool, task.id, TaskStatus::Done).await?;
 
@@ -314,7 +619,7 @@ impl MergeQueueProcessor {
             "Task marked as Done after successful merge"
         );
 
-        // Step 7: Dispatch TaskStatusChanged event for handlers (autopilot, remote sync, etc.)
+        // Step 9: Dispatch TaskStatusChanged event for handlers (autopilot, remote sync, etc.)
         // The AutopilotHandler will handle auto-dequeueing unblocked dependents.
         if let Some(dispatcher) = &self.event_dispatcher {
             let mut updated_task = task.clone();
@@ -331,7 +636,7 @@ impl MergeQueueProcessor {
             self.auto_dequeue_unblocked_dependents(task.id).await;
         }
 
-        Ok(merge_commit)
+        Ok(merge)
     }
 
     /// Auto-dequeue unblocked dependent tasks when autopilot is enabled.
@@ -520,15 +825,17 @@ impl MergeQueueProcessor {
         task_branch: &str,
         base_branch: &str,
         commit_message: &str,
-    ) -> Result<String, MergeQueueError> {
-        match self.git.merge_changes(
+        hooks: &CommitHooks,
+    ) -> Result<HookedMerge, MergeQueueError> {
+        match self.git.merge_changes_with_hooks(
             repo_path,     // base_worktree_path (main repo)
             worktree_path, // task_worktree_path
             task_branch,
             base_branch,
             commit_message,
+            hooks,
         ) {
-            Ok(commit_sha) => Ok(commit_sha),
+            Ok(merge) => Ok(merge),
             Err(GitServiceError::MergeConflicts(msg)) => Err(MergeQueueError::MergeConflict(msg)),
             Err(GitServiceError::BranchesDiverged(msg)) => {
                 // If branches diverged after rebase, treat as conflict
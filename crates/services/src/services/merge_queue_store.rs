@@ -1,76 +1,197 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use chrono::{DateTime, Utc};
+pub use db::models::merge_queue_entry::{MergeQueueEntry, MergeQueueStatus};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
 use ts_rs::TS;
 use utils::msg_store::MsgStore;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::events::patches::merge_queue_patch;
 
-/// Status of a merge queue entry
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+/// A merge queue entry with its place in the project's queue
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
-#[serde(rename_all = "snake_case")]
-pub enum MergeQueueStatus {
-    /// Entry is waiting in queue
-    Queued,
-    /// Entry is currently being merged
+pub struct QueuedMerge {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub entry: MergeQueueEntry,
+    /// 1-based position; the entry being merged, if any, comes first
+    pub position: i64,
+}
+
+/// How the processor's last attempt at an entry ended
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MergeQueueOutcome {
+    Merged {
+        merge_commit: String,
+        /// Output of the repo's git hooks when they failed and the merge was
+        /// committed without them
+        hook_failure: Option<String>,
+    },
+    /// The branch had no commits ahead of its target
+    NothingToMerge,
+    /// A reloaded entry no longer applied, e.g. its task was finished while
+    /// the server was down
+    Stale {
+        reason: String,
+    },
+    Conflict {
+        message: String,
+    },
+    /// The project's required checks did not pass
+    ChecksFailed {
+        message: String,
+        output: String,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+/// Why [`MergeQueueStore::cancel`] left an entry in the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelRefusal {
+    NotFound,
+    /// The processor has already claimed the entry
     Merging,
 }
 
-/// An entry in the merge queue
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+/// The processor's last run of a workspace's queue entry
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
-pub struct MergeQueueEntry {
-    pub id: Uuid,
+pub struct MergeQueueRun {
+    pub entry_id: Uuid,
     pub project_id: Uuid,
     pub workspace_id: Uuid,
     pub repo_id: Uuid,
-    pub queued_at: DateTime<Utc>,
-    pub status: MergeQueueStatus,
     pub commit_message: String,
+    pub queued_at: DateTime<Utc>,
+    pub outcome: MergeQueueOutcome,
+    pub finished_at: DateTime<Utc>,
 }
 
-impl MergeQueueEntry {
-    pub fn new(
-        project_id: Uuid,
-        workspace_id: Uuid,
-        repo_id: Uuid,
-        commit_message: String,
-    ) -> Self {
+impl MergeQueueRun {
+    pub fn new(entry: &MergeQueueEntry, outcome: MergeQueueOutcome) -> Self {
         Self {
-            id: Uuid::new_v4(),
-            project_id,
-            workspace_id,
-            repo_id,
-            queued_at: Utc::now(),
-            status: MergeQueueStatus::Queued,
-            commit_message,
+            entry_id: entry.id,
+            project_id: entry.project_id,
+            workspace_id: entry.workspace_id,
+            repo_id: entry.repo_id,
+            commit_message: entry.commit_message.clone(),
+            queued_at: entry.queued_at,
+            outcome,
+            finished_at: Utc::now(),
         }
     }
 }
 
-/// In-memory store for merge queue entries.
-/// Entries are ephemeral - lost on server restart (acceptable).
+/// A change to mirror into the `merge_queue_entries` table
+#[derive(Debug)]
+enum PersistOp {
+    Upsert(MergeQueueEntry),
+    SetStatus(Uuid, MergeQueueStatus),
+    Delete(Uuid),
+    /// Every entry id, in queue order
+    SetPositions(Vec<Uuid>),
+}
+
+/// Store for merge queue entries.
+/// Reads are served from memory. A store created with [`Self::load`] also
+/// writes every change through to the database, in order, from a background
+/// task, so queued merges survive a restart.
 /// Uses workspace_id as primary key since each workspace can only have one queue entry.
 #[derive(Clone)]
 pub struct MergeQueueStore {
-    /// Queue entries keyed by workspace_id
+    /// Queue entries in processing order; each workspace has at most one
     entries: Arc<RwLock<Vec<MergeQueueEntry>>>,
+    /// Outcome of the processor's last run, keyed by workspace_id
+    last_runs: Arc<RwLock<HashMap<Uuid, MergeQueueRun>>>,
     /// MsgStore for broadcasting changes via SSE
     msg_store: Arc<MsgStore>,
+    /// Feeds the database writer; None for a memory-only store
+    persist: Option<mpsc::UnboundedSender<PersistOp>>,
 }
 
 impl MergeQueueStore {
+    /// Memory-only store; entries are lost on restart.
     pub fn new(msg_store: Arc<MsgStore>) -> Self {
         Self {
             entries: Arc::new(RwLock::new(Vec::new())),
+            last_runs: Arc::new(RwLock::new(HashMap::new())),
+            msg_store,
+            persist: None,
+        }
+    }
+
+    /// Database-backed store. Reloads the persisted queue, flagging every
+    /// entry for revalidation (see
+    /// [`MergeQueueEntry::mark_all_for_revalidation`]), and starts the writer
+    /// that persists later changes.
+    pub async fn load(msg_store: Arc<MsgStore>, pool: SqlitePool) -> Result<Self, sqlx::Error> {
+        let stale = MergeQueueEntry::mark_all_for_revalidation(&pool).await?;
+        let entries = MergeQueueEntry::find_all(&pool).await?;
+        if stale > 0 {
+            tracing::info!(
+                count = stale,
+                "Reloaded merge queue entries; they will be revalidated before merging"
+            );
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(Self::run_writer(pool, rx));
+
+        Ok(Self {
+            entries: Arc::new(RwLock::new(entries)),
+            last_runs: Arc::new(RwLock::new(HashMap::new())),
             msg_store,
+            persist: Some(tx),
+        })
+    }
+
+    /// Apply persist ops one at a time so the table sees changes in the same
+    /// order as memory did.
+    async fn run_writer(pool: SqlitePool, mut rx: mpsc::UnboundedReceiver<PersistOp>) {
+        while let Some(op) = rx.recv().await {
+            let result = match &op {
+                PersistOp::Upsert(entry) => MergeQueueEntry::upsert(&pool, entry).await,
+                PersistOp::SetStatus(id, status) => {
+                    MergeQueueEntry::update_status(&pool, *id, *status).await
+                }
+                PersistOp::Delete(id) => MergeQueueEntry::delete(&pool, *id).await,
+                PersistOp::SetPositions(ids) => MergeQueueEntry::set_positions(&pool, ids).await,
+            };
+            if let Err(e) = result {
+                tracing::error!(?op, error = %e, "Failed to persist merge queue change");
+            }
         }
     }
 
+    fn persist(&self, op: PersistOp) {
+        if let Some(tx) = &self.persist {
+            // Only fails once the writer is gone, i.e. during shutdown
+            let _ = tx.send(op);
+        }
+    }
+
+    /// Projects with at least one entry waiting to be merged.
+    pub fn projects_with_queued_entries(&self) -> Vec<Uuid> {
+        let mut project_ids: Vec<Uuid> = Vec::new();
+        for entry in self.entries.read().iter() {
+            if entry.status == MergeQueueStatus::Queued && !project_ids.contains(&entry.project_id)
+            {
+                project_ids.push(entry.project_id);
+            }
+        }
+        project_ids
+    }
+
     /// Add an entry to the queue.
     /// Returns the created entry.
     pub fn enqueue(
@@ -87,6 +208,7 @@ impl MergeQueueStore {
             // Remove any existing entry for this workspace
             entries.retain(|e| e.workspace_id != workspace_id);
             entries.push(entry.clone());
+            self.persist(PersistOp::Upsert(entry.clone()));
         }
 
         let patch = merge_queue_patch::add(&entry);
@@ -97,21 +219,48 @@ impl MergeQueueStore {
 
     /// Atomically claim the next Queued entry for a project.
     /// Returns the entry with status updated to Merging, or None if no Queued entries exist.
-    /// FIFO ordering: returns the entry with the oldest queued_at timestamp.
+    /// Entries are claimed in queue order: FIFO unless reordered with [`Self::reorder`].
     pub fn claim_next(&self, project_id: Uuid) -> Option<MergeQueueEntry> {
         let mut entries = self.entries.write();
 
-        // Find the oldest Queued entry for this project
+        // Find the first Queued entry for this project
         let idx = entries
             .iter()
-            .enumerate()
-            .filter(|(_, e)| e.project_id == project_id && e.status == MergeQueueStatus::Queued)
-            .min_by_key(|(_, e)| e.queued_at)
-            .map(|(idx, _)| idx)?;
+            .position(|e| e.project_id == project_id && e.status == MergeQueueStatus::Queued)?;
 
         // Update status to Merging
         entries[idx].status = MergeQueueStatus::Merging;
         let entry = entries[idx].clone();
+        self.persist(PersistOp::SetStatus(entry.id, entry.status));
+
+        // Drop lock before broadcasting
+        drop(entries);
+
+        let patch = merge_queue_patch::replace(&entry);
+        self.msg_store.push_patch(patch);
+
+        Some(entry)
+    }
+
+    /// Put a claimed entry back in the queue, right after `ahead_of`'s entry,
+    /// e.g. because it is stacked on that workspace's branch. Without an
+    /// entry for `ahead_of` it goes back where it was.
+    pub fn requeue_after(&self, workspace_id: Uuid, ahead_of: Uuid) -> Option<MergeQueueEntry> {
+        let mut entries = self.entries.write();
+        let idx = entries
+            .iter()
+            .position(|e| e.workspace_id == workspace_id)?;
+        let mut entry = entries.remove(idx);
+        entry.status = MergeQueueStatus::Queued;
+        let target = entries
+            .iter()
+            .position(|e| e.workspace_id == ahead_of)
+            .map_or(idx, |ahead_idx| ahead_idx + 1);
+        entries.insert(target, entry.clone());
+        self.persist(PersistOp::SetStatus(entry.id, entry.status));
+        self.persist(PersistOp::SetPositions(
+            entries.iter().map(|e| e.id).collect(),
+        ));
 
         // Drop lock before broadcasting
         drop(entries);
@@ -130,7 +279,9 @@ impl MergeQueueStore {
             let idx = entries
                 .iter()
                 .position(|e| e.workspace_id == workspace_id)?;
-            Some(entries.remove(idx))
+            let removed = entries.remove(idx);
+            self.persist(PersistOp::Delete(removed.id));
+            Some(removed)
         };
 
         if let Some(ref entry) = removed {
@@ -141,6 +292,33 @@ impl MergeQueueStore {
         removed
     }
 
+    /// Remove a project's waiting entry. The status check and removal happen
+    /// under one lock, so the processor cannot claim the entry in between.
+    pub fn cancel(
+        &self,
+        project_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<MergeQueueEntry, CancelRefusal> {
+        let removed = {
+            let mut entries = self.entries.write();
+            let idx = entries
+                .iter()
+                .position(|e| e.project_id == project_id && e.id == entry_id)
+                .ok_or(CancelRefusal::NotFound)?;
+            if entries[idx].status == MergeQueueStatus::Merging {
+                return Err(CancelRefusal::Merging);
+            }
+            let removed = entries.remove(idx);
+            self.persist(PersistOp::Delete(removed.id));
+            removed
+        };
+
+        let patch = merge_queue_patch::remove(removed.workspace_id);
+        self.msg_store.push_patch(patch);
+
+        Ok(removed)
+    }
+
     /// Get the queue entry for a workspace.
     pub fn get(&self, workspace_id: Uuid) -> Option<MergeQueueEntry> {
         self.entries
@@ -150,24 +328,94 @@ impl MergeQueueStore {
             .cloned()
     }
 
-    /// List all queue entries for a project, ordered by queued_at (oldest first).
+    /// List all queue entries for a project, in queue order.
     pub fn list_by_project(&self, project_id: Uuid) -> Vec<MergeQueueEntry> {
-        let entries = self.entries.read();
-        let mut result: Vec<_> = entries
+        self.entries
+            .read()
             .iter()
             .filter(|e| e.project_id == project_id)
             .cloned()
+            .collect()
+    }
+
+    /// List a project's queue with each entry's position; the entry being
+    /// merged is listed first.
+    pub fn positions_by_project(&self, project_id: Uuid) -> Vec<QueuedMerge> {
+        let mut entries = self.list_by_project(project_id);
+        entries.sort_by_key(|e| e.status != MergeQueueStatus::Merging);
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(idx, entry)| QueuedMerge {
+                entry,
+                position: idx as i64 + 1,
+            })
+            .collect()
+    }
+
+    /// Reorder a project's waiting entries. `entry_ids` must list every Queued
+    /// entry of the project exactly once; returns false (leaving the queue
+    /// untouched) when it doesn't.
+    pub fn reorder(&self, project_id: Uuid, entry_ids: &[Uuid]) -> bool {
+        let mut entries = self.entries.write();
+        let slots: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.project_id == project_id && e.status == MergeQueueStatus::Queued)
+            .map(|(idx, _)| idx)
             .collect();
-        result.sort_by_key(|e| e.queued_at);
-        result
+
+        let mut reordered: Vec<MergeQueueEntry> = Vec::with_capacity(slots.len());
+        for id in entry_ids {
+            if reordered.iter().any(|e| e.id == *id) {
+                return false;
+            }
+            let Some(&idx) = slots.iter().find(|&&idx| entries[idx].id == *id) else {
+                return false;
+            };
+            reordered.push(entries[idx].clone());
+        }
+        if reordered.len() != slots.len() {
+            return false;
+        }
+
+        for (idx, entry) in slots.into_iter().zip(reordered) {
+            entries[idx] = entry;
+        }
+        self.persist(PersistOp::SetPositions(
+            entries.iter().map(|e| e.id).collect(),
+        ));
+        true
     }
 
-    /// Get all queue entries.
+    /// Get all queue entries, in queue order.
     /// Useful for initial state sync when a client connects.
     pub fn get_all(&self) -> Vec<MergeQueueEntry> {
-        let mut entries: Vec<_> = self.entries.read().clone();
-        entries.sort_by_key(|e| e.queued_at);
-        entries
+        self.entries.read().clone()
+    }
+
+    /// Record how the processor's run of an entry ended, replacing the
+    /// workspace's previous run.
+    pub fn record_run(&self, run: MergeQueueRun) {
+        self.last_runs.write().insert(run.workspace_id, run);
+    }
+
+    /// The processor's last run for a workspace.
+    pub fn last_run(&self, workspace_id: Uuid) -> Option<MergeQueueRun> {
+        self.last_runs.read().get(&workspace_id).cloned()
+    }
+
+    /// Last run of each of a project's workspaces, most recent first.
+    pub fn last_runs_by_project(&self, project_id: Uuid) -> Vec<MergeQueueRun> {
+        let mut runs: Vec<_> = self
+            .last_runs
+            .read()
+            .values()
+            .filter(|run| run.project_id == project_id)
+            .cloned()
+            .collect();
+        runs.sort_by(|a, b| b.finished_at.cmp(&a.finished_at));
+        runs
     }
 
     /// Count entries for a project.
@@ -294,6 +542,31 @@ mod tests {
         assert!(store.remove(workspace_id).is_none());
     }
 
+    #[test]
+    fn test_cancel() {
+        let store = create_store();
+        let project_id = Uuid::new_v4();
+        let repo_id = Uuid::new_v4();
+
+        let merging = store.enqueue(project_id, Uuid::new_v4(), repo_id, "First".to_string());
+        let waiting = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Second".to_string());
+        store.claim_next(project_id).unwrap();
+
+        assert_eq!(
+            store.cancel(project_id, merging.id).unwrap_err(),
+            CancelRefusal::Merging
+        );
+        assert_eq!(
+            store.cancel(Uuid::new_v4(), waiting.id).unwrap_err(),
+            CancelRefusal::NotFound
+        );
+
+        let removed = store.cancel(project_id, waiting.id).unwrap();
+        assert_eq!(removed.workspace_id, waiting.workspace_id);
+        assert!(store.get(waiting.workspace_id).is_none());
+        assert!(store.get(merging.workspace_id).is_some());
+    }
+
     #[test]
     fn test_project_isolation() {
         let store = create_store();
@@ -378,4 +651,83 @@ mod tests {
         // Each entry should be claimed exactly once
         assert_eq!(all_claimed, expected);
     }
+
+    #[test]
+    fn test_reorder() {
+        let store = create_store();
+        let project_id = Uuid::new_v4();
+        let repo_id = Uuid::new_v4();
+
+        let first = store.enqueue(project_id, Uuid::new_v4(), repo_id, "First".to_string());
+        let second = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Second".to_string());
+        let third = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Third".to_string());
+
+        // Incomplete or duplicated orders are rejected
+        assert!(!store.reorder(project_id, &[third.id, first.id]));
+        assert!(!store.reorder(project_id, &[third.id, third.id, first.id]));
+
+        assert!(store.reorder(project_id, &[third.id, first.id, second.id]));
+        let claimed = store.claim_next(project_id).unwrap();
+        assert_eq!(claimed.id, third.id);
+
+        // The merging entry is listed first and can't be reordered
+        let positions = store.positions_by_project(project_id);
+        assert_eq!(positions[0].entry.id, third.id);
+        assert_eq!(positions[0].position, 1);
+        assert!(!store.reorder(project_id, &[third.id, second.id, first.id]));
+        assert!(store.reorder(project_id, &[second.id, first.id]));
+        let positions = store.positions_by_project(project_id);
+        let ids: Vec<_> = positions.iter().map(|p| p.entry.id).collect();
+        assert_eq!(ids, vec![third.id, second.id, first.id]);
+    }
+
+    #[test]
+    fn test_requeue_after() {
+        let store = create_store();
+        let project_id = Uuid::new_v4();
+        let repo_id = Uuid::new_v4();
+
+        let child = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Child".to_string());
+        let other = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Other".to_string());
+        let parent = store.enqueue(project_id, Uuid::new_v4(), repo_id, "Parent".to_string());
+
+        let claimed = store.claim_next(project_id).unwrap();
+        assert_eq!(claimed.id, child.id);
+        let requeued = store
+            .requeue_after(child.workspace_id, parent.workspace_id)
+            .unwrap();
+        assert_eq!(requeued.status, MergeQueueStatus::Queued);
+
+        let ids: Vec<_> = store
+            .list_by_project(project_id)
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec![other.id, parent.id, child.id]);
+    }
+
+    #[test]
+    fn test_last_runs() {
+        let store = create_store();
+        let project_id = Uuid::new_v4();
+        let workspace_id = Uuid::new_v4();
+        let entry = store.enqueue(project_id, workspace_id, Uuid::new_v4(), "Msg".to_string());
+
+        store.record_run(MergeQueueRun::new(
+            &entry,
+            MergeQueueOutcome::Conflict {
+                message: "conflict in a.rs".to_string(),
+            },
+        ));
+        store.record_run(MergeQueueRun::new(
+            &entry,
+            MergeQueueOutcome::NothingToMerge,
+        ));
+
+        let runs = store.last_runs_by_project(project_id);
+        assert_eq!(runs.len(), 1);
+        assert!(matches!(runs[0].outcome, MergeQueueOutcome::NothingToMerge));
+        assert!(store.last_runs_by_project(Uuid::new_v4()).is_empty());
+        assert_eq!(store.last_run(workspace_id).unwrap().entry_id, entry.id);
+    }
 }
@@ -1,42 +1,92 @@
+pub mod agent_auth;
 pub mod analytics;
+pub mod anomaly_detection;
 pub mod approvals;
+pub mod asset_relocation;
 pub mod auth;
 pub mod autopilot;
 pub mod backup;
 pub mod backup_service;
+pub mod bisect;
+pub mod bitbucket;
+pub mod blame_context;
+pub mod branch_janitor;
+pub mod branch_stack;
 pub mod config;
 pub mod container;
 pub mod conversation;
+pub mod copy_files;
+pub mod devcontainer;
 pub mod diff_stream;
 pub mod domain_events;
 pub mod embedding;
 pub mod embedding_worker;
+pub mod env_file;
 pub mod events;
+pub mod execution_retry;
+pub mod failure_classification;
 pub mod feedback;
 pub mod file_ranker;
+pub mod file_rules;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
+pub mod git_hooks;
+pub mod git_remote;
 pub mod git_watcher;
+pub mod gitea;
 pub mod github;
 pub mod github_client;
+pub mod gitlab;
 pub mod gix_reader;
 pub mod image;
+pub mod imported_notes;
+pub mod link_unfurl;
+pub mod merge_checks;
 pub mod merge_queue_processor;
 pub mod merge_queue_store;
 pub mod notification;
+pub mod notification_channels;
+pub mod notification_groups;
 pub mod oauth_credentials;
 pub mod operation_status;
+pub mod operations;
+pub mod output_redaction;
+pub mod permission_audit;
 pub mod pr_cache;
 pub mod pr_monitor;
+pub mod pre_commit;
 pub mod project;
+pub mod project_export;
+pub mod project_memory;
+pub mod project_secrets;
+pub mod project_template;
+pub mod prompt_templates;
+pub mod provider_rate_limit;
 pub mod queued_message;
 pub mod remote_client;
 pub mod repo;
+pub mod repo_metadata_cache;
+pub mod repo_stats;
+pub mod resource_monitor;
 pub mod review_attention;
+pub mod review_comments;
+pub mod scheduler;
+pub mod secrets;
 pub mod share;
 pub mod skills_cache;
+pub mod ssh_remote;
+pub mod staged_selection;
+pub mod stale_session;
+pub mod task_plan;
+pub mod time_tracking;
+pub mod toolchain_doctor;
+pub mod trash;
+pub mod usage;
 pub mod watcher_manager;
+pub mod watchman;
+pub mod webhook_secrets;
+pub mod workspace_bundle;
 pub mod workspace_manager;
 pub mod worktree_manager;
@@ -1,13 +1,22 @@
 use std::sync::{Arc, OnceLock};
 
-use db::models::notification::{CreateNotification, Notification, NotificationType};
+use db::models::{
+    execution_process_failure::ExecutionProcessFailure,
+    notification::{CreateNotification, Notification, NotificationType},
+    task::Task,
+    task_watcher::TaskWatcher,
+};
 use serde_json::json;
 use sqlx::SqlitePool;
 use tokio::sync::RwLock;
 use utils;
 use uuid::Uuid;
 
-use crate::services::config::{Config, EffectiveSound, NotificationConfig, SoundFile};
+use crate::services::{
+    agent_auth::AgentAuthStatus,
+    config::{Config, EffectiveSound, NotificationConfig, SoundFile},
+    notification_groups::{GROUP_WINDOW, count_label, execution_failed_key},
+};
 
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Debug, Clone)]
@@ -359,6 +368,8 @@ impl NotificationService {
                 workspace_id: Some(workspace_id),
                 session_id: None,
                 conversation_session_id: None,
+                user_id: None,
+                group_key: None,
             },
         )
         .await
@@ -385,6 +396,8 @@ impl NotificationService {
                 workspace_id: Some(workspace_id),
                 session_id: None,
                 conversation_session_id: None,
+                user_id: None,
+                group_key: None,
             },
         )
         .await
@@ -409,34 +422,148 @@ impl NotificationService {
                 workspace_id: Some(workspace_id),
                 session_id: None,
                 conversation_session_id: None,
+                user_id: None,
+                group_key: None,
             },
         )
         .await
     }
 
     /// Create an in-app notification when an agent encounters an error.
+    /// Failures in the same project shortly after each other are folded into
+    /// one notification.
     pub async fn notify_agent_error(
         pool: &SqlitePool,
         project_id: Uuid,
+        project_name: &str,
         workspace_id: Uuid,
         task_title: &str,
+        failure: Option<&ExecutionProcessFailure>,
     ) -> Result<Notification, sqlx::Error> {
-        Notification::create(
+        let window = chrono::Duration::from_std(GROUP_WINDOW).unwrap_or_default();
+        let mut metadata = json!({ "workspace_id": workspace_id.to_string() });
+        if let Some(failure) = failure {
+            metadata["failure_reason"] = json!(failure.reason);
+            metadata["suggested_fix"] = json!(failure.suggested_fix);
+        }
+        Notification::create_grouped(
             pool,
             &CreateNotification {
                 project_id: Some(project_id),
                 notification_type: NotificationType::AgentError,
                 title: format!("Task Failed: {}", task_title),
-                message: format!("'{}' execution failed", task_title),
+                message: failure_message(task_title, failure),
+                metadata: Some(metadata),
+                workspace_id: Some(workspace_id),
+                session_id: None,
+                conversation_session_id: None,
+                user_id: None,
+                group_key: Some(execution_failed_key(project_id)),
+            },
+            window,
+            |count| {
+                (
+                    format!(
+                        "{} failed in {}",
+                        count_label(count, "execution"),
+                        project_name
+                    ),
+                    format!("Latest: '{}' execution failed", task_title),
+                )
+            },
+        )
+        .await
+    }
+
+    /// Create an in-app notification when an execution starts with an agent
+    /// whose CLI is known to be signed out
+    pub async fn notify_agent_signed_out(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        workspace_id: Uuid,
+        task_title: &str,
+        status: &AgentAuthStatus,
+    ) -> Result<Notification, sqlx::Error> {
+        Notification::create(
+            pool,
+            &CreateNotification {
+                project_id: Some(project_id),
+                notification_type: NotificationType::AgentError,
+                title: format!("{} is signed out", status.executor),
+                message: signed_out_message(task_title, status),
+                metadata: Some(json!({
+                    "workspace_id": workspace_id.to_string(),
+                    "executor": status.executor.to_string(),
+                })),
+                workspace_id: Some(workspace_id),
+                session_id: None,
+                conversation_session_id: None,
+                user_id: None,
+                group_key: None,
+            },
+        )
+        .await
+    }
+
+    /// Create an in-app notification when an agent was stopped for looping
+    /// or flooding its output.
+    pub async fn notify_agent_anomaly(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        workspace_id: Uuid,
+        task_title: &str,
+        reason: &str,
+    ) -> Result<Notification, sqlx::Error> {
+        Notification::create(
+            pool,
+            &CreateNotification {
+                project_id: Some(project_id),
+                notification_type: NotificationType::AgentError,
+                title: format!("Agent Stopped: {}", task_title),
+                message: format!("'{}' was interrupted because {}", task_title, reason),
                 metadata: Some(json!({ "workspace_id": workspace_id.to_string() })),
                 workspace_id: Some(workspace_id),
                 session_id: None,
                 conversation_session_id: None,
+                user_id: None,
+                group_key: None,
             },
         )
         .await
     }
 
+    /// Create an in-app notification for each user watching `task`.
+    pub async fn notify_task_watchers(
+        pool: &SqlitePool,
+        task: &Task,
+        workspace_id: Option<Uuid>,
+        notification_type: NotificationType,
+        title: &str,
+        message: &str,
+    ) -> Result<Vec<Notification>, sqlx::Error> {
+        let mut notifications = Vec::new();
+        for user_id in TaskWatcher::find_user_ids_by_task_id(pool, task.id).await? {
+            let notification = Notification::create(
+                pool,
+                &CreateNotification {
+                    project_id: Some(task.project_id),
+                    notification_type: notification_type.clone(),
+                    title: title.to_string(),
+                    message: message.to_string(),
+                    metadata: Some(json!({ "task_id": task.id.to_string() })),
+                    workspace_id,
+                    session_id: None,
+                    conversation_session_id: None,
+                    user_id: Some(user_id),
+                    group_key: None,
+                },
+            )
+            .await?;
+            notifications.push(notification);
+        }
+        Ok(notifications)
+    }
+
     /// Create an in-app notification for a new conversation response.
     pub async fn notify_conversation_response(
         pool: &SqlitePool,
@@ -472,12 +599,39 @@ impl NotificationService {
                 workspace_id: None,
                 session_id: None,
                 conversation_session_id: Some(conversation_session_id),
+                user_id: None,
+                group_key: None,
             },
         )
         .await
     }
 }
 
+/// Warning for an execution started while its agent is signed out
+pub fn signed_out_message(task_title: &str, status: &AgentAuthStatus) -> String {
+    format!(
+        "'{}' was started with {}, which is signed out, so it will likely fail. {}",
+        task_title,
+        status.executor,
+        status.suggested_fix.as_deref().unwrap_or_default()
+    )
+    .trim_end()
+    .to_string()
+}
+
+/// "'title' execution failed", with why and what to do about it when known
+pub fn failure_message(task_title: &str, failure: Option<&ExecutionProcessFailure>) -> String {
+    match failure {
+        Some(failure) => format!(
+            "'{}' execution failed: {}. {}",
+            task_title,
+            failure.reason.label(),
+            failure.suggested_fix
+        ),
+        None => format!("'{}' execution failed", task_title),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -502,6 +656,7 @@ mod tests {
             error_sound_file: SoundFile::ErrorBuzzer,
             custom_sound_path: Some(filename),
             frontend_sounds_enabled: false,
+            slack: Default::default(),
         };
 
         let resolved = NotificationService::resolve_sound_path(&config)
@@ -523,6 +678,7 @@ mod tests {
             error_sound_file: SoundFile::ErrorBuzzer,
             custom_sound_path: Some(filename),
             frontend_sounds_enabled: false,
+            slack: Default::default(),
         };
 
         let resolved = NotificationService::resolve_sound_path(&config)
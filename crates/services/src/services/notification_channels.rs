@@ -0,0 +1,244 @@
+//! External channels that notifications are posted to, alongside the OS and
+//! in-app notifications of [`super::notification::NotificationService`].
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::services::{
+    config::{NotificationConfig, SlackNotificationConfig},
+    secrets::SecretService,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What a channel message is about; channels can turn each one off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelEvent {
+    TaskCompleted,
+    NeedsAttention,
+    MergeFailed,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelMessage {
+    pub event: ChannelEvent,
+    pub title: String,
+    pub body: String,
+    /// How many events the message stands for; more than one when a burst
+    /// of similar events was folded into a summary
+    pub occurrences: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum NotificationChannelError {
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Channel rejected the message: HTTP {0}")]
+    Rejected(reqwest::StatusCode),
+}
+
+/// Somewhere outside the app that notifications are posted to
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    /// Short description for logs
+    fn name(&self) -> &'static str;
+
+    /// Whether messages about `event` should be sent
+    fn accepts(&self, event: ChannelEvent) -> bool;
+
+    async fn send(&self, message: &ChannelMessage) -> Result<(), NotificationChannelError>;
+}
+
+/// Posts to a Slack channel through an incoming webhook
+pub struct SlackChannel {
+    client: reqwest::Client,
+    webhook_url: String,
+    config: SlackNotificationConfig,
+}
+
+impl SlackChannel {
+    /// None when no webhook URL is configured. `webhook_url` is the value of
+    /// the config's secret store reference
+    pub fn new(
+        client: reqwest::Client,
+        config: SlackNotificationConfig,
+        webhook_url: Option<String>,
+    ) -> Option<Self> {
+        let webhook_url = webhook_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())?
+            .to_string();
+        Some(Self {
+            client,
+            webhook_url,
+            config,
+        })
+    }
+
+    fn payload(message: &ChannelMessage) -> Value {
+        let emoji = match message.event {
+            ChannelEvent::TaskCompleted => ":white_check_mark:",
+            ChannelEvent::NeedsAttention => ":raising_hand:",
+            ChannelEvent::MergeFailed => ":x:",
+        };
+        let mut blocks = vec![json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "{emoji} *{}*\n{}",
+                    escape_mrkdwn(&message.title),
+                    escape_mrkdwn(&message.body)
+                ),
+            },
+        })];
+        if message.occurrences > 1 {
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "{} similar notifications grouped into this one",
+                        message.occurrences
+                    ),
+                }],
+            }));
+        }
+        json!({
+            // Shown in notifications and clients that don't render blocks
+            "text": format!("{}: {}", message.title, message.body),
+            "blocks": blocks,
+        })
+    }
+}
+
+/// Slack treats `&`, `<` and `>` as control characters in message text
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[async_trait]
+impl NotificationChannel for SlackChannel {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    fn accepts(&self, event: ChannelEvent) -> bool {
+        match event {
+            ChannelEvent::TaskCompleted => self.config.task_completed,
+            ChannelEvent::NeedsAttention => self.config.needs_attention,
+            ChannelEvent::MergeFailed => self.config.merge_failed,
+        }
+    }
+
+    async fn send(&self, message: &ChannelMessage) -> Result<(), NotificationChannelError> {
+        let response = self
+            .client
+            .post(&self.webhook_url)
+            .timeout(REQUEST_TIMEOUT)
+            .json(&Self::payload(message))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(NotificationChannelError::Rejected(response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// The channels set up in `config`
+pub async fn configured_channels(
+    client: &reqwest::Client,
+    secrets: &SecretService,
+    config: &NotificationConfig,
+) -> Vec<Box<dyn NotificationChannel>> {
+    let mut channels: Vec<Box<dyn NotificationChannel>> = Vec::new();
+    let webhook_url = secrets.resolve_opt(config.slack.webhook_url.as_ref()).await;
+    if let Some(slack) = SlackChannel::new(client.clone(), config.slack.clone(), webhook_url) {
+        channels.push(Box::new(slack));
+    }
+    channels
+}
+
+/// Send `message` to every channel that accepts it, logging failures
+pub async fn post_to_channels(channels: &[Box<dyn NotificationChannel>], message: &ChannelMessage) {
+    for channel in channels.iter().filter(|c| c.accepts(message.event)) {
+        if let Err(e) = channel.send(message).await {
+            tracing::warn!(
+                channel = channel.name(),
+                error = %e,
+                "Failed to post notification"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slack(config: SlackNotificationConfig, webhook_url: Option<&str>) -> Option<SlackChannel> {
+        SlackChannel::new(
+            reqwest::Client::new(),
+            config,
+            webhook_url.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn slack_needs_a_webhook_url() {
+        assert!(slack(SlackNotificationConfig::default(), None).is_none());
+        assert!(slack(SlackNotificationConfig::default(), Some("  ")).is_none());
+    }
+
+    #[test]
+    fn slack_respects_event_toggles() {
+        let channel = slack(
+            SlackNotificationConfig {
+                task_completed: false,
+                ..Default::default()
+            },
+            Some("https://hooks.slack.com/services/T/B/X"),
+        )
+        .unwrap();
+        assert!(!channel.accepts(ChannelEvent::TaskCompleted));
+        assert!(channel.accepts(ChannelEvent::NeedsAttention));
+        assert!(channel.accepts(ChannelEvent::MergeFailed));
+    }
+
+    #[test]
+    fn slack_payload_escapes_control_characters() {
+        let payload = SlackChannel::payload(&ChannelMessage {
+            event: ChannelEvent::MergeFailed,
+            title: "Merge failed: <script>".to_string(),
+            body: "a & b".to_string(),
+            occurrences: 1,
+        });
+        assert_eq!(payload["text"], "Merge failed: <script>: a & b");
+        assert_eq!(
+            payload["blocks"][0]["text"]["text"],
+            ":x: *Merge failed: &lt;script&gt;*\na &amp; b"
+        );
+        assert_eq!(payload["blocks"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn slack_payload_notes_grouped_events() {
+        let payload = SlackChannel::payload(&ChannelMessage {
+            event: ChannelEvent::NeedsAttention,
+            title: "5 executions failed in web".to_string(),
+            body: "Latest: 'Fix login' on branch vk/ab12-fix-login".to_string(),
+            occurrences: 5,
+        });
+        assert_eq!(
+            payload["blocks"][1]["elements"][0]["text"],
+            "5 similar notifications grouped into this one"
+        );
+    }
+}
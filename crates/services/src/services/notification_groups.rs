@@ -0,0 +1,115 @@
+//! Folding bursts of similar notifications into one.
+//!
+//! A dev server that keeps crashing fails an execution per restart. Events
+//! sharing a group key are let through one at a time: the first in a window
+//! is delivered as it is, the rest are held and delivered as a single
+//! summary when the window closes. In-app notifications are folded in the
+//! database instead, see [`db::models::notification::Notification::create_grouped`].
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use uuid::Uuid;
+
+/// How long similar notifications keep being folded together
+pub const GROUP_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Group key for executions failing in a project
+pub fn execution_failed_key(project_id: Uuid) -> String {
+    format!("execution_failed:{project_id}")
+}
+
+/// "1 execution" or "5 executions"
+pub fn count_label(count: i64, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationGroups {
+    window: Duration,
+    /// Events held back in each open window
+    held: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl Default for NotificationGroups {
+    fn default() -> Self {
+        Self::new(GROUP_WINDOW)
+    }
+}
+
+impl NotificationGroups {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            held: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Count an event under `key`. Returns true when it opens a window and
+    /// should be delivered now; otherwise it is held. When the window closes
+    /// and events were held, `summarize` is called with the number of events
+    /// seen in the window, the first included.
+    pub fn admit<F, Fut>(&self, key: &str, summarize: F) -> bool
+    where
+        F: FnOnce(i64) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        {
+            let mut held = self.held.lock().unwrap();
+            if let Some(count) = held.get_mut(key) {
+                *count += 1;
+                return false;
+            }
+            held.insert(key.to_string(), 0);
+        }
+
+        let held = self.held.clone();
+        let key = key.to_string();
+        let window = self.window;
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            let count = held.lock().unwrap().remove(&key).unwrap_or(0);
+            if count > 0 {
+                summarize(count + 1).await;
+            }
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn holds_repeats_and_summarizes_them() {
+        let groups = NotificationGroups::new(Duration::from_millis(50));
+        let summarized = Arc::new(AtomicI64::new(0));
+
+        let admit = |key: &str| {
+            let summarized = summarized.clone();
+            groups.admit(key, move |count| async move {
+                summarized.fetch_add(count, Ordering::SeqCst);
+            })
+        };
+        assert!(admit("a"));
+        assert!(!admit("a"));
+        assert!(!admit("a"));
+        assert!(admit("b"));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // Three events under "a"; "b" had nothing held, so no summary
+        assert_eq!(summarized.load(Ordering::SeqCst), 3);
+        assert!(admit("a"));
+    }
+}
@@ -16,8 +16,11 @@ use super::events::patches::operation_status_patch;
 pub enum OperationStatusType {
     GeneratingCommit,
     Rebasing,
+    CherryPicking,
     Pushing,
     Merging,
+    /// Execution queued until its provider's rate limit allows a start
+    WaitingForRateLimit,
 }
 
 /// Status of an in-progress operation
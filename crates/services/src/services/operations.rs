@@ -0,0 +1,214 @@
+//! Persisted long-running operations.
+//!
+//! Unlike [`super::operation_status::OperationStatusStore`], which only holds
+//! the operation a workspace is busy with right now, the tracker records every
+//! operation in the `operations` table with its progress and outcome, and
+//! broadcasts each change so clients can follow one operation by id.
+
+use std::{fmt::Display, future::Future};
+
+use db::models::operation::{Operation, OperationKind};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use serde_json::json;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast::{self, error::RecvError};
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+/// Capacity of the update channel; subscribers that fall further behind
+/// skip ahead
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Clone)]
+pub struct OperationTracker {
+    pool: SqlitePool,
+    updates: broadcast::Sender<Operation>,
+}
+
+impl OperationTracker {
+    pub fn new(pool: SqlitePool) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self { pool, updates }
+    }
+
+    /// Tracker for a freshly started server: operations the previous process
+    /// left running can never finish, so they are marked failed.
+    pub async fn load(pool: SqlitePool) -> Result<Self, sqlx::Error> {
+        let interrupted = Operation::fail_interrupted(&pool).await?;
+        if interrupted > 0 {
+            tracing::info!(
+                count = interrupted,
+                "Marked operations interrupted by the last shutdown as failed"
+            );
+        }
+        Ok(Self::new(pool))
+    }
+
+    /// Receive every operation change from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<Operation> {
+        self.updates.subscribe()
+    }
+
+    fn publish(&self, operation: Operation) {
+        // Err only means nobody is listening
+        let _ = self.updates.send(operation);
+    }
+
+    /// Record the start of an operation. Returns None, after logging, when the
+    /// database write fails: tracking must never stop the operation itself.
+    pub async fn start(
+        &self,
+        kind: OperationKind,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+    ) -> Option<Uuid> {
+        self.start_for_user(kind, workspace_id, task_id, None).await
+    }
+
+    /// [`Self::start`] for an operation a signed-in user asked for
+    pub async fn start_for_user(
+        &self,
+        kind: OperationKind,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+    ) -> Option<Uuid> {
+        match Operation::create(&self.pool, kind, workspace_id, task_id, created_by).await {
+            Ok(operation) => {
+                let id = operation.id;
+                self.publish(operation);
+                Some(id)
+            }
+            Err(e) => {
+                tracing::error!(?kind, error = %e, "Failed to record operation start");
+                None
+            }
+        }
+    }
+
+    /// Record progress, clamped to 0-100. A None id (see [`Self::start`]) is
+    /// ignored.
+    pub async fn progress(&self, id: Option<Uuid>, percent: u8, message: Option<&str>) {
+        let Some(id) = id else { return };
+        let percent = i64::from(percent.min(100));
+        match Operation::update_progress(&self.pool, id, Some(percent), message).await {
+            Ok(Some(operation)) => self.publish(operation),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(%id, error = %e, "Failed to record operation progress"),
+        }
+    }
+
+    /// Record how an operation ended. A None id (see [`Self::start`]) is
+    /// ignored.
+    pub async fn finish<E: Display>(&self, id: Option<Uuid>, result: Result<(), E>) {
+        let Some(id) = id else { return };
+        let error = result.err().map(|e| e.to_string());
+        match Operation::finish(&self.pool, id, error.as_deref()).await {
+            Ok(Some(operation)) => self.publish(operation),
+            Ok(None) => {}
+            Err(e) => tracing::error!(%id, error = %e, "Failed to record operation end"),
+        }
+    }
+
+    /// Stream one operation as JSON patches replacing `/operation`: its
+    /// current state, then every change, then `Finished` once it has ended.
+    /// None when no such operation exists.
+    pub async fn stream_raw(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<BoxStream<'static, Result<LogMsg, std::io::Error>>>, sqlx::Error> {
+        // Subscribe before reading the snapshot so no change falls in between
+        let rx = self.subscribe();
+        let Some(snapshot) = Operation::find_by_id(&self.pool, id).await? else {
+            return Ok(None);
+        };
+
+        let pool = self.pool.clone();
+        // Changes queued before the snapshot was read are already in it
+        let since = snapshot.updated_at;
+        let rx = (!snapshot.is_finished()).then_some(rx);
+        let updates = stream::unfold(rx, move |rx| {
+            let pool = pool.clone();
+            async move {
+                let mut rx = rx?;
+                loop {
+                    let operation = match rx.recv().await {
+                        Ok(operation) if operation.id == id && operation.updated_at >= since => {
+                            operation
+                        }
+                        Ok(_) => continue,
+                        // Missed updates; the row has the latest state
+                        Err(RecvError::Lagged(_)) => match Operation::find_by_id(&pool, id).await {
+                            Ok(Some(operation)) => operation,
+                            _ => return None,
+                        },
+                        Err(RecvError::Closed) => return None,
+                    };
+                    let rx = (!operation.is_finished()).then_some(rx);
+                    return Some((operation, rx));
+                }
+            }
+        });
+
+        let stream = stream::once(async move { snapshot })
+            .chain(updates)
+            .flat_map(|operation| {
+                let mut msgs = vec![Ok(operation_patch(&operation))];
+                if operation.is_finished() {
+                    msgs.push(Ok(LogMsg::Finished));
+                }
+                stream::iter(msgs)
+            })
+            .boxed();
+        Ok(Some(stream))
+    }
+
+    /// Run `operation` as a tracked operation, recording its outcome
+    pub async fn track<T, E, F>(
+        &self,
+        kind: OperationKind,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        operation: F,
+    ) -> Result<T, E>
+    where
+        E: Display,
+        F: Future<Output = Result<T, E>>,
+    {
+        self.track_for_user(kind, workspace_id, task_id, None, operation)
+            .await
+    }
+
+    /// [`Self::track`] for an operation a signed-in user asked for
+    pub async fn track_for_user<T, E, F>(
+        &self,
+        kind: OperationKind,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        created_by: Option<Uuid>,
+        operation: F,
+    ) -> Result<T, E>
+    where
+        E: Display,
+        F: Future<Output = Result<T, E>>,
+    {
+        let id = self
+            .start_for_user(kind, workspace_id, task_id, created_by)
+            .await;
+        let result = operation.await;
+        self.finish(id, result.as_ref().map(|_| ())).await;
+        result
+    }
+}
+
+fn operation_patch(operation: &Operation) -> LogMsg {
+    let patch = json!([{
+        "op": "replace",
+        "path": "/operation",
+        "value": operation,
+    }]);
+    LogMsg::JsonPatch(serde_json::from_value(patch).unwrap())
+}
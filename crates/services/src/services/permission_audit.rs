@@ -0,0 +1,186 @@
+//! Permission audit of a workspace after an execution.
+//!
+//! Agents and scripts can leave files behind that are dangerous to merge or
+//! to keep on the machine: files anyone can write to, setuid or setgid
+//! binaries, and symlinks that lead out of the worktree, which a later write
+//! through the link would land outside it. Only entries whose inode changed
+//! since the execution started are reported, so findings a user has already
+//! acknowledged do not come back on every run. `.git` is skipped. Other
+//! platforms have no such mode bits and are not audited.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use db::models::permission_finding::CreatePermissionFinding;
+
+/// Entries visited per audit; larger workspaces are only partly audited
+pub const MAX_AUDITED_ENTRIES: usize = 200_000;
+
+/// Permission problems under `workspace_root` created since `since`, with
+/// paths relative to the root
+#[cfg(unix)]
+pub fn audit_workspace(
+    workspace_root: &Path,
+    since: DateTime<Utc>,
+) -> Vec<CreatePermissionFinding> {
+    use std::os::unix::fs::MetadataExt;
+
+    use db::models::permission_finding::PermissionFindingKind;
+
+    let Ok(root) = dunce::canonicalize(workspace_root) else {
+        return Vec::new();
+    };
+    let since = since.timestamp();
+    let walker = ignore::WalkBuilder::new(&root)
+        .standard_filters(false)
+        .follow_links(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+
+    let mut findings = Vec::new();
+    for entry in walker.flatten().take(MAX_AUDITED_ENTRIES) {
+        let path = entry.path();
+        let Ok(metadata) = path.symlink_metadata() else {
+            continue;
+        };
+        if metadata.ctime() < since || path == root {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(&root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+
+        if metadata.file_type().is_symlink() {
+            if let Some(target) = escaping_target(&root, path) {
+                findings.push(CreatePermissionFinding {
+                    kind: PermissionFindingKind::SymlinkEscape,
+                    path: relative,
+                    detail: target,
+                });
+            }
+            continue;
+        }
+
+        let mode = metadata.mode() & 0o7777;
+        if mode & 0o6000 != 0 {
+            findings.push(CreatePermissionFinding {
+                kind: PermissionFindingKind::Setuid,
+                path: relative.clone(),
+                detail: format!("{mode:04o}"),
+            });
+        }
+        if mode & 0o002 != 0 {
+            findings.push(CreatePermissionFinding {
+                kind: PermissionFindingKind::WorldWritable,
+                path: relative,
+                detail: format!("{mode:04o}"),
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(not(unix))]
+pub fn audit_workspace(
+    _workspace_root: &Path,
+    _since: DateTime<Utc>,
+) -> Vec<CreatePermissionFinding> {
+    Vec::new()
+}
+
+/// The symlink's target when it resolves outside `root`. Dangling links are
+/// judged by their target path as written.
+#[cfg(unix)]
+fn escaping_target(root: &Path, link: &Path) -> Option<String> {
+    let target = std::fs::read_link(link).ok()?;
+    let joined = link.parent().unwrap_or(root).join(&target);
+    let resolved = dunce::canonicalize(&joined).unwrap_or_else(|_| normalize(&joined));
+    if resolved.starts_with(root) {
+        return None;
+    }
+    Some(resolved.to_string_lossy().to_string())
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+#[cfg(unix)]
+fn normalize(path: &Path) -> std::path::PathBuf {
+    use std::path::Component;
+
+    let mut normalized = std::path::PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        fs,
+        os::unix::fs::{PermissionsExt, symlink},
+    };
+
+    use chrono::Duration;
+    use db::models::permission_finding::PermissionFindingKind;
+
+    use super::*;
+
+    fn kinds(findings: &[CreatePermissionFinding]) -> Vec<(PermissionFindingKind, &str)> {
+        let mut kinds: Vec<_> = findings
+            .iter()
+            .map(|finding| (finding.kind, finding.path.as_str()))
+            .collect();
+        kinds.sort_by_key(|(_, path)| path.to_string());
+        kinds
+    }
+
+    #[test]
+    fn flags_world_writable_setuid_and_escaping_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let since = Utc::now() - Duration::seconds(5);
+        fs::write(root.join("open.sh"), "").unwrap();
+        fs::set_permissions(root.join("open.sh"), fs::Permissions::from_mode(0o666)).unwrap();
+        fs::write(root.join("suid"), "").unwrap();
+        fs::set_permissions(root.join("suid"), fs::Permissions::from_mode(0o4755)).unwrap();
+        fs::write(root.join("plain.txt"), "").unwrap();
+        symlink("/etc/hosts", root.join("hosts")).unwrap();
+        symlink("plain.txt", root.join("inside")).unwrap();
+        symlink("../../somewhere", root.join("dangling")).unwrap();
+
+        let findings = audit_workspace(root, since);
+
+        assert_eq!(
+            kinds(&findings),
+            vec![
+                (PermissionFindingKind::SymlinkEscape, "dangling"),
+                (PermissionFindingKind::SymlinkEscape, "hosts"),
+                (PermissionFindingKind::WorldWritable, "open.sh"),
+                (PermissionFindingKind::Setuid, "suid"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_entries_unchanged_since_the_execution_started() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("open.sh"), "").unwrap();
+        fs::set_permissions(
+            dir.path().join("open.sh"),
+            fs::Permissions::from_mode(0o666),
+        )
+        .unwrap();
+
+        let findings = audit_workspace(dir.path(), Utc::now() + Duration::seconds(60));
+
+        assert!(findings.is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::time::Duration;
 use moka::future::Cache;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::github_client::PullRequestSummary;
@@ -12,7 +13,7 @@ const DEFAULT_TTL_SECS: u64 = 120;
 
 /// A pull request with its optional unresolved review thread count.
 /// The count may be null if it's being loaded progressively.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PrWithComments {
     #[serde(flatten)]
     pub pr: PullRequestSummary,
@@ -20,7 +21,7 @@ pub struct PrWithComments {
 }
 
 /// PRs grouped by repository.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct RepoPrs {
     pub repo_id: Uuid,
     pub repo_name: String,
@@ -29,7 +30,7 @@ pub struct RepoPrs {
 }
 
 /// Cached response for project PRs
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ProjectPrsResponse {
     pub repos: Vec<RepoPrs>,
 }
@@ -1,39 +1,72 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
 use db::{
     DBService,
     models::{
-        merge::{Merge, MergeStatus, PrMerge},
+        merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
+        pr_draft_policy::PrDraftPolicy,
         task::{Task, TaskStatus},
         workspace::{Workspace, WorkspaceError},
     },
 };
 use serde_json::json;
-use sqlx::error::Error as SqlxError;
+use sqlx::{SqlitePool, error::Error as SqlxError};
 use thiserror::Error;
-use tokio::time::interval;
+use tokio::{sync::RwLock, time::interval};
 use tracing::{debug, error, info};
 
 use crate::services::{
     analytics::AnalyticsContext,
+    config::Config,
     domain_events::{DomainEvent, EventDispatchCallback},
-    github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    git_remote::{GitRemoteError, GitRemoteProvider},
+    github::{GitHubService, GitHubServiceError},
+    secrets::SecretService,
     share::SharePublisher,
 };
 
+/// The task of a draft PR that should now be marked ready for review: its
+/// project enables auto-ready and the review-attention analysis found the
+/// task needs no attention
+pub async fn draft_ready_for_review(
+    pool: &SqlitePool,
+    pr_merge: &PrMerge,
+) -> Result<Option<Task>, SqlxError> {
+    if !pr_merge.is_draft {
+        return Ok(None);
+    }
+    let Some(workspace) = Workspace::find_by_id(pool, pr_merge.workspace_id).await? else {
+        return Ok(None);
+    };
+    let Some(task) = Task::find_by_id(pool, workspace.task_id).await? else {
+        return Ok(None);
+    };
+    if task.needs_attention != Some(false) {
+        return Ok(None);
+    }
+    let policy = PrDraftPolicy::find_or_default(pool, task.project_id).await?;
+    Ok(policy.auto_ready.then_some(task))
+}
+
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error(transparent)]
     GitHubServiceError(#[from] GitHubServiceError),
     #[error(transparent)]
+    GitRemoteError(#[from] GitRemoteError),
+    #[error(transparent)]
     WorkspaceError(#[from] WorkspaceError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
 }
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Service to monitor pull requests (and GitLab merge requests) and update
+/// task status when they are merged. Draft GitHub PRs of projects with auto-ready
+/// enabled are marked ready for review once their task needs no attention.
 pub struct PrMonitorService {
     db: DBService,
+    config: Arc<RwLock<Config>>,
+    secrets: SecretService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
@@ -43,12 +76,16 @@ pub struct PrMonitorService {
 impl PrMonitorService {
     pub async fn spawn(
         db: DBService,
+        config: Arc<RwLock<Config>>,
+        secrets: SecretService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
         event_dispatcher: Option<EventDispatchCallback>,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
+            config,
+            secrets,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
@@ -97,24 +134,31 @@ impl PrMonitorService {
         Ok(())
     }
 
+    /// Fetch the latest state of a PR from the host its URL points at
+    async fn fetch_pr_status(&self, pr_merge: &PrMerge) -> Result<PullRequestInfo, PrMonitorError> {
+        let provider =
+            GitRemoteProvider::require(&pr_merge.pr_info.url, &*self.config.read().await)?;
+        Ok(provider
+            .get_pull_request(&self.secrets, pr_merge.pr_info.number)
+            .await?)
+    }
+
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        // GitHubService now uses gh CLI, no token needed
-        let github_service = GitHubService::new()?;
-        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
-
-        let pr_status = github_service
-            .update_pr_status(&repo_info, pr_merge.pr_info.number)
-            .await?;
+        let pr_status = self.fetch_pr_status(pr_merge).await?;
 
         debug!(
             "PR #{} status: {:?} (was open)",
             pr_merge.pr_info.number, pr_status.status
         );
 
+        if matches!(&pr_status.status, MergeStatus::Open) && pr_merge.is_draft {
+            self.mark_ready_if_reviewed(pr_merge).await?;
+        }
+
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from GitHub
+            // Update merge status with the latest information from the host
             Merge::update_status(
                 &self.db.pool,
                 pr_merge.id,
@@ -180,4 +224,31 @@ impl PrMonitorService {
 
         Ok(())
     }
+
+    /// Mark a draft GitHub PR ready for review when its project enables
+    /// auto-ready and the review-attention analysis found the task needs no
+    /// attention
+    async fn mark_ready_if_reviewed(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
+        let provider =
+            GitRemoteProvider::from_url(&pr_merge.pr_info.url, &*self.config.read().await);
+        let Some(GitRemoteProvider::GitHub(repo_info)) = provider else {
+            return Ok(());
+        };
+
+        let Some(task) = draft_ready_for_review(&self.db.pool, pr_merge).await? else {
+            return Ok(());
+        };
+
+        let github_service = GitHubService::new()?;
+        github_service
+            .mark_pr_ready(&repo_info, pr_merge.pr_info.number)
+            .await?;
+        Merge::mark_ready(&self.db.pool, pr_merge.id).await?;
+
+        info!(
+            "Marked draft PR #{} ready for review for task {}",
+            pr_merge.pr_info.number, task.id
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,205 @@
+//! pre-commit framework integration.
+//!
+//! Repos with a `.pre-commit-config.yaml` can have `pre-commit run` applied
+//! to the files an agent changed once its run is committed. Hooks that fix
+//! files (formatters, end-of-file fixers) leave the worktree dirty; the run is
+//! then repeated once so only failures that survive the fixes are reported.
+//! With the project's sandbox enabled the run happens inside it, as the
+//! config and the hooks it installs are repo content an agent can edit.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use thiserror::Error;
+use tokio::process::Command;
+use utils::{shell::resolve_executable_path, text::truncate_output};
+
+use super::git::{GitCli, GitCliError};
+
+pub const PRE_COMMIT_CONFIG: &str = ".pre-commit-config.yaml";
+
+/// First runs install hook environments, which can take minutes
+const RUN_TIMEOUT: Duration = Duration::from_secs(600);
+
+const FEEDBACK_HEADER: &str = "pre-commit checks failed on your changes.";
+
+/// Hook output beyond this is cut before it is handed to the agent
+const MAX_OUTPUT_CHARS: usize = 8_000;
+
+#[derive(Debug, Error)]
+pub enum PreCommitError {
+    #[error("pre-commit did not finish within {0:?}")]
+    Timeout(Duration),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PreCommitReport {
+    /// Hooks modified files; the worktree has uncommitted fixes
+    pub fixed: bool,
+    /// Output of the hooks that still fail after fixes were applied
+    pub failures: Option<String>,
+}
+
+pub struct PreCommitRunner {
+    executable: PathBuf,
+}
+
+impl PreCommitRunner {
+    /// None when the `pre-commit` executable cannot be found
+    pub async fn detect() -> Option<Self> {
+        resolve_executable_path("pre-commit")
+            .await
+            .map(|executable| Self { executable })
+    }
+
+    pub fn is_configured(worktree_path: &Path) -> bool {
+        worktree_path.join(PRE_COMMIT_CONFIG).is_file()
+    }
+
+    /// Where pre-commit keeps the hook environments it installs; a
+    /// sandboxed run needs to write to it
+    pub fn cache_dir() -> Option<PathBuf> {
+        if let Some(home) = std::env::var_os("PRE_COMMIT_HOME") {
+            return Some(PathBuf::from(home));
+        }
+        if let Some(cache) = std::env::var_os("XDG_CACHE_HOME") {
+            return Some(PathBuf::from(cache).join("pre-commit"));
+        }
+        dirs::home_dir().map(|home| home.join(".cache").join("pre-commit"))
+    }
+
+    /// Files changed between `base_commit` and HEAD that still exist
+    pub fn changed_files(
+        worktree_path: &Path,
+        base_commit: &str,
+    ) -> Result<Vec<String>, PreCommitError> {
+        let output = GitCli::new().git(
+            worktree_path,
+            [
+                "diff",
+                "--name-only",
+                "--diff-filter=d",
+                base_commit,
+                "HEAD",
+            ],
+        )?;
+        Ok(output
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Run the configured hooks on `files` under `command_prefix`, then
+    /// once more if they fixed anything
+    pub async fn run(
+        &self,
+        worktree_path: &Path,
+        files: &[String],
+        command_prefix: &[String],
+    ) -> Result<PreCommitReport, PreCommitError> {
+        if files.is_empty() {
+            return Ok(PreCommitReport::default());
+        }
+
+        let Some(output) = self.run_once(worktree_path, files, command_prefix).await? else {
+            return Ok(PreCommitReport::default());
+        };
+
+        let git = GitCli::new();
+        if !git.has_changes(worktree_path)? {
+            return Ok(PreCommitReport {
+                fixed: false,
+                failures: Some(output),
+            });
+        }
+
+        // Re-stage so hooks see the fixed contents, as a developer would
+        let add_args = ["add", "--"]
+            .into_iter()
+            .chain(files.iter().map(String::as_str));
+        git.git(worktree_path, add_args)?;
+        let failures = self.run_once(worktree_path, files, command_prefix).await?;
+        Ok(PreCommitReport {
+            fixed: true,
+            failures,
+        })
+    }
+
+    /// Output of a failed run, or None when every hook passed
+    async fn run_once(
+        &self,
+        worktree_path: &Path,
+        files: &[String],
+        command_prefix: &[String],
+    ) -> Result<Option<String>, PreCommitError> {
+        let mut command = match command_prefix.split_first() {
+            Some((program, args)) => {
+                let mut command = Command::new(program);
+                command.args(args).arg(&self.executable);
+                command
+            }
+            None => Command::new(&self.executable),
+        };
+        command
+            .args(["run", "--color", "never", "--files"])
+            .args(files)
+            .current_dir(worktree_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let output = match tokio::time::timeout(RUN_TIMEOUT, command.output()).await {
+            Ok(output) => output?,
+            Err(_) => return Err(PreCommitError::Timeout(RUN_TIMEOUT)),
+        };
+        if output.status.success() {
+            return Ok(None);
+        }
+
+        let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(Some(truncate_output(combined.trim(), MAX_OUTPUT_CHARS)))
+    }
+}
+
+/// Follow-up prompt asking the agent to fix hooks that still fail, given
+/// `(repo name, hook output)` pairs
+pub fn feedback_prompt(failures: &[(String, String)]) -> String {
+    let mut prompt = format!(
+        "{FEEDBACK_HEADER}\n\nAuto-fixable issues were already fixed and committed. \
+         Fix the remaining failures below without disabling the hooks.\n"
+    );
+    for (repo_name, output) in failures {
+        prompt.push_str(&format!("\n## {repo_name}\n\n```\n{output}\n```\n"));
+    }
+    prompt
+}
+
+/// Whether `prompt` is a [`feedback_prompt`]; its run is not fed back again
+/// so a hook the agent cannot satisfy does not loop
+pub fn is_feedback_prompt(prompt: &str) -> bool {
+    prompt.starts_with(FEEDBACK_HEADER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feedback_prompt_is_recognised() {
+        let prompt = feedback_prompt(&[("web".to_string(), "eslint failed".to_string())]);
+        assert!(is_feedback_prompt(&prompt));
+        assert!(prompt.contains("## web"));
+        assert!(prompt.contains("eslint failed"));
+        assert!(!is_feedback_prompt("Add a login page"));
+    }
+}
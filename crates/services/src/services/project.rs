@@ -132,6 +132,13 @@ impl ProjectService {
                     dev_script: None,
                     dev_script_working_dir: None,
                     default_agent_working_dir: Some(repo.name),
+                    script_shell: None,
+                    inherit_login_env: None,
+                    dev_shell: None,
+                    dev_shell_command: None,
+                    container_image: None,
+                    container_network: None,
+                    max_concurrent_executions: None,
                 },
             )
             .await?;
@@ -0,0 +1,540 @@
+//! Exporting a whole project to a portable archive and importing it elsewhere.
+//!
+//! The archive is JSON holding the project's settings, its repositories (by
+//! path, with their per-project scripts), task groups, tasks with their notes,
+//! the metadata of every attempt and what the coding agent was asked and
+//! answered, and the dependencies between tasks. Worktrees, branches and logs
+//! are not exported. Importing creates a new project with fresh IDs, so an
+//! archive can be imported next to the project it came from.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    coding_agent_turn::CodingAgentTurn,
+    project::{CreateProject, Project, UpdateProject},
+    project_repo::{CreateProjectRepo, ProjectRepo, ProjectRepoError, UpdateProjectRepo},
+    scratch::{Scratch, ScratchError, ScratchPayload, ScratchType},
+    session::Session,
+    task::{CreateTask, Task, TaskPriority, TaskStatus},
+    task_dependency::{TaskDependency, TaskDependencyError},
+    task_group::TaskGroup,
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use executors::{actions::script::ScriptShell, dev_shell::DevShell};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utils::path::expand_tilde;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    imported_notes,
+    project::{ProjectService, ProjectServiceError},
+    project_template::same_path,
+    repo::RepoService,
+};
+
+/// Bumped whenever an archive written by this version can't be read by an older one
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ProjectExportError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Scratch(#[from] ScratchError),
+    #[error(transparent)]
+    Project(#[from] ProjectServiceError),
+    #[error(transparent)]
+    ProjectRepo(#[from] ProjectRepoError),
+    #[error(transparent)]
+    Dependency(#[from] TaskDependencyError),
+    #[error("Archive format {0} is newer than this version supports ({EXPORT_FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExport {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ExportedProject,
+    pub repos: Vec<ExportedRepo>,
+    pub task_groups: Vec<ExportedTaskGroup>,
+    /// Oldest first
+    pub tasks: Vec<ExportedTask>,
+    pub dependencies: Vec<ExportedDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedProject {
+    pub name: String,
+    pub dev_script: Option<String>,
+    pub dev_script_working_dir: Option<String>,
+    pub default_agent_working_dir: Option<String>,
+    pub script_shell: Option<ScriptShell>,
+    pub inherit_login_env: bool,
+    pub dev_shell: Option<DevShell>,
+    pub dev_shell_command: Option<String>,
+    pub container_image: Option<String>,
+    pub container_network: Option<String>,
+    pub max_concurrent_executions: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedRepo {
+    /// Key for overriding `path` on import
+    pub name: String,
+    pub display_name: String,
+    /// Where the repository was on the exporting machine
+    pub path: String,
+    pub setup_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    pub post_copy_script: Option<String>,
+    pub parallel_setup_script: bool,
+    pub merge_target_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedTaskGroup {
+    /// ID in the exporting instance, referenced by `ExportedTask::task_group_id`
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedTask {
+    /// ID in the exporting instance, referenced by dependencies
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    pub task_group_id: Option<Uuid>,
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+    /// The task's review notes
+    pub notes: Option<String>,
+    /// Oldest first
+    pub attempts: Vec<ExportedAttempt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedAttempt {
+    pub branch: String,
+    pub created_at: DateTime<Utc>,
+    pub repos: Vec<ExportedAttemptRepo>,
+    /// Coding agent of the attempt's latest session
+    pub executor: Option<String>,
+    /// What the coding agent was asked and answered, oldest first
+    pub turns: Vec<ExportedTurn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedAttemptRepo {
+    pub name: String,
+    pub target_branch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedTurn {
+    pub prompt: Option<String>,
+    /// The agent's final message
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedDependency {
+    pub task_id: Uuid,
+    pub depends_on_id: Uuid,
+}
+
+/// The project created from an archive
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ImportedProject {
+    pub project: Project,
+    pub tasks: Vec<Task>,
+}
+
+/// Everything about `project` that is carried over to another instance
+pub async fn export_project(
+    pool: &SqlitePool,
+    project: &Project,
+) -> Result<ProjectExport, ProjectExportError> {
+    let project_repos = ProjectRepo::find_by_project_id(pool, project.id).await?;
+    let repos = ProjectRepo::find_repos_for_project(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|repo| {
+            let settings = project_repos.iter().find(|pr| pr.repo_id == repo.id);
+            ExportedRepo {
+                name: repo.name,
+                display_name: repo.display_name,
+                path: repo.path.to_string_lossy().to_string(),
+                setup_script: settings.and_then(|s| s.setup_script.clone()),
+                cleanup_script: settings.and_then(|s| s.cleanup_script.clone()),
+                copy_files: settings.and_then(|s| s.copy_files.clone()),
+                post_copy_script: settings.and_then(|s| s.post_copy_script.clone()),
+                parallel_setup_script: settings.is_some_and(|s| s.parallel_setup_script),
+                merge_target_branch: settings.and_then(|s| s.merge_target_branch.clone()),
+            }
+        })
+        .collect();
+
+    let task_groups = TaskGroup::find_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|group| ExportedTaskGroup {
+            id: group.id,
+            name: group.name,
+            description: group.description,
+            base_branch: group.base_branch,
+        })
+        .collect();
+
+    let mut attempts: HashMap<Uuid, Vec<ExportedAttempt>> = HashMap::new();
+    // Newest first, so inserting at the front keeps each task's attempts oldest first
+    for workspace in Workspace::fetch_by_project_id(pool, project.id).await? {
+        let attempt = export_attempt(pool, &workspace).await?;
+        attempts
+            .entry(workspace.task_id)
+            .or_default()
+            .insert(0, attempt);
+    }
+
+    let mut tasks = Vec::new();
+    for task in Task::find_by_project_id(pool, project.id).await? {
+        let notes = match Scratch::find_by_id(pool, task.id, &ScratchType::TaskNotes).await? {
+            Some(Scratch {
+                payload: ScratchPayload::TaskNotes(notes),
+                ..
+            }) => Some(notes),
+            _ => None,
+        };
+        tasks.push(ExportedTask {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            priority: task.priority,
+            task_group_id: task.task_group_id,
+            archived: task.archived_at.is_some(),
+            created_at: task.created_at,
+            notes,
+            attempts: attempts.remove(&task.id).unwrap_or_default(),
+        });
+    }
+
+    let dependencies = TaskDependency::find_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|dependency| ExportedDependency {
+            task_id: dependency.task_id,
+            depends_on_id: dependency.depends_on_id,
+        })
+        .collect();
+
+    Ok(ProjectExport {
+        format_version: EXPORT_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        project: ExportedProject {
+            name: project.name.clone(),
+            dev_script: project.dev_script.clone(),
+            dev_script_working_dir: project.dev_script_working_dir.clone(),
+            default_agent_working_dir: project.default_agent_working_dir.clone(),
+            script_shell: project.script_shell,
+            inherit_login_env: project.inherit_login_env,
+            dev_shell: project.dev_shell,
+            dev_shell_command: project.dev_shell_command.clone(),
+            container_image: project.container_image.clone(),
+            container_network: project.container_network.clone(),
+            max_concurrent_executions: project.max_concurrent_executions,
+        },
+        repos,
+        task_groups,
+        tasks,
+        dependencies,
+    })
+}
+
+async fn export_attempt(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+) -> Result<ExportedAttempt, ProjectExportError> {
+    let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+        .await?
+        .into_iter()
+        .map(|repo| ExportedAttemptRepo {
+            name: repo.repo.name,
+            target_branch: repo.target_branch,
+        })
+        .collect();
+
+    // Sessions come newest first
+    let sessions = Session::find_by_workspace_id(pool, workspace.id).await?;
+    let executor = sessions.iter().find_map(|session| session.executor.clone());
+    let mut turns = Vec::new();
+    for session in sessions.iter().rev() {
+        for turn in CodingAgentTurn::find_by_session_id(pool, session.id).await? {
+            turns.push(ExportedTurn {
+                prompt: turn.prompt,
+                summary: turn.summary,
+            });
+        }
+    }
+
+    Ok(ExportedAttempt {
+        branch: workspace.branch.clone(),
+        created_at: workspace.created_at,
+        repos,
+        executor,
+        turns,
+    })
+}
+
+/// Create a project from `archive`. Repositories are looked up at their
+/// exported paths unless `repo_paths` maps their name to a path on this
+/// machine. Attempts are not recreated; their history is appended to the
+/// notes of their task.
+pub async fn import_project(
+    pool: &SqlitePool,
+    project_service: &ProjectService,
+    repo_service: &RepoService,
+    archive: &ProjectExport,
+    name: Option<String>,
+    repo_paths: &HashMap<String, String>,
+) -> Result<ImportedProject, ProjectExportError> {
+    if archive.format_version > EXPORT_FORMAT_VERSION {
+        return Err(ProjectExportError::UnsupportedVersion(
+            archive.format_version,
+        ));
+    }
+
+    let paths: Vec<_> = archive
+        .repos
+        .iter()
+        .map(|repo| {
+            let path = repo_paths.get(&repo.name).unwrap_or(&repo.path);
+            expand_tilde(path.trim())
+        })
+        .collect();
+    let repositories = archive
+        .repos
+        .iter()
+        .zip(&paths)
+        .map(|(repo, path)| CreateProjectRepo {
+            display_name: repo.display_name.clone(),
+            git_repo_path: path.to_string_lossy().to_string(),
+        })
+        .collect();
+
+    let settings = &archive.project;
+    let project = project_service
+        .create_project(
+            pool,
+            repo_service,
+            CreateProject {
+                name: name.unwrap_or_else(|| settings.name.clone()),
+                repositories,
+            },
+        )
+        .await?;
+    let project = Project::update(
+        pool,
+        project.id,
+        &UpdateProject {
+            name: None,
+            dev_script: settings.dev_script.clone(),
+            dev_script_working_dir: settings.dev_script_working_dir.clone(),
+            default_agent_working_dir: settings.default_agent_working_dir.clone(),
+            script_shell: settings.script_shell,
+            inherit_login_env: Some(settings.inherit_login_env),
+            dev_shell: settings.dev_shell,
+            dev_shell_command: settings.dev_shell_command.clone(),
+            container_image: settings.container_image.clone(),
+            container_network: settings.container_network.clone(),
+            max_concurrent_executions: settings.max_concurrent_executions,
+        },
+    )
+    .await?;
+
+    let repos = project_service.get_repositories(pool, project.id).await?;
+    for (exported, path) in archive.repos.iter().zip(&paths) {
+        let Some(repo) = repos.iter().find(|repo| same_path(&repo.path, path)) else {
+            continue;
+        };
+        ProjectRepo::update(
+            pool,
+            project.id,
+            repo.id,
+            &UpdateProjectRepo {
+                setup_script: exported.setup_script.clone(),
+                cleanup_script: exported.cleanup_script.clone(),
+                copy_files: exported.copy_files.clone(),
+                post_copy_script: exported.post_copy_script.clone(),
+                parallel_setup_script: Some(exported.parallel_setup_script),
+                merge_target_branch: exported.merge_target_branch.clone(),
+            },
+        )
+        .await?;
+    }
+
+    let mut group_ids = HashMap::new();
+    for group in &archive.task_groups {
+        let created = TaskGroup::create(
+            pool,
+            project.id,
+            group.name.clone(),
+            group.description.clone(),
+            group.base_branch.clone(),
+        )
+        .await?;
+        group_ids.insert(group.id, created.id);
+    }
+
+    let mut task_ids = HashMap::new();
+    for task in &archive.tasks {
+        let create_task = CreateTask {
+            project_id: project.id,
+            title: task.title.clone(),
+            description: task.description.clone(),
+            status: Some(task.status.clone()),
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+            task_group_id: task
+                .task_group_id
+                .and_then(|id| group_ids.get(&id).copied()),
+            priority: Some(task.priority),
+            created_by: None,
+        };
+        let created = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+        if task.archived {
+            Task::archive(pool, created.id).await?;
+        }
+        let notes = import_notes(task);
+        if !notes.is_empty() {
+            Scratch::save_if_version(pool, created.id, &ScratchPayload::TaskNotes(notes), 0)
+                .await?;
+        }
+        task_ids.insert(task.id, created.id);
+    }
+
+    for dependency in &archive.dependencies {
+        let (Some(task_id), Some(depends_on_id)) = (
+            task_ids.get(&dependency.task_id),
+            task_ids.get(&dependency.depends_on_id),
+        ) else {
+            continue;
+        };
+        TaskDependency::create(pool, *task_id, *depends_on_id).await?;
+    }
+
+    let tasks = Task::find_by_project_id(pool, project.id).await?;
+    Ok(ImportedProject { project, tasks })
+}
+
+/// The task's notes followed by the history of its attempts
+fn import_notes(task: &ExportedTask) -> String {
+    let mut notes = task.notes.clone().unwrap_or_default();
+    if task.attempts.is_empty() {
+        return notes;
+    }
+    if !notes.is_empty() {
+        notes = format!("{}\n\n", notes.trim_end());
+    }
+    notes.push_str("## Imported attempts\n");
+    for attempt in &task.attempts {
+        notes.push_str(&format!(
+            "\n### `{}` ({})\n",
+            attempt.branch,
+            attempt.created_at.format("%Y-%m-%d %H:%M UTC")
+        ));
+        if let Some(executor) = &attempt.executor {
+            notes.push_str(&format!("\nAgent: {executor}\n"));
+        }
+        for repo in &attempt.repos {
+            notes.push_str(&format!("\n- {} onto `{}`", repo.name, repo.target_branch));
+        }
+        if !attempt.repos.is_empty() {
+            notes.push('\n');
+        }
+        for turn in &attempt.turns {
+            imported_notes::push_turn(&mut notes, turn.prompt.as_deref(), turn.summary.as_deref());
+        }
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn task(notes: Option<&str>, attempts: Vec<ExportedAttempt>) -> ExportedTask {
+        ExportedTask {
+            id: Uuid::new_v4(),
+            title: "Fix login".to_string(),
+            description: None,
+            status: TaskStatus::InReview,
+            priority: TaskPriority::Normal,
+            task_group_id: None,
+            archived: false,
+            created_at: Utc::now(),
+            notes: notes.map(str::to_string),
+            attempts,
+        }
+    }
+
+    #[test]
+    fn notes_without_attempts_are_kept_as_is() {
+        assert_eq!(
+            import_notes(&task(Some("Check the 2FA flow"), vec![])),
+            "Check the 2FA flow"
+        );
+        assert_eq!(import_notes(&task(None, vec![])), "");
+    }
+
+    #[test]
+    fn attempts_are_appended_to_the_notes() {
+        let attempt = ExportedAttempt {
+            branch: "vk/1a2b-fix-login".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap(),
+            repos: vec![ExportedAttemptRepo {
+                name: "web".to_string(),
+                target_branch: "main".to_string(),
+            }],
+            executor: Some("CLAUDE_CODE".to_string()),
+            turns: vec![
+                ExportedTurn {
+                    prompt: Some("Fix the login bug".to_string()),
+                    summary: Some("Fixed it".to_string()),
+                },
+                ExportedTurn {
+                    prompt: Some("Add a test".to_string()),
+                    summary: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            import_notes(&task(Some("Check the 2FA flow\n"), vec![attempt])),
+            "Check the 2FA flow\n\n\
+             ## Imported attempts\n\n\
+             ### `vk/1a2b-fix-login` (2026-03-01 09:30 UTC)\n\n\
+             Agent: CLAUDE_CODE\n\n\
+             - web onto `main`\n\n\
+             > Fix the login bug\n\n\
+             **Agent:**\n\n\
+             > Fixed it\n\n\
+             > Add a test\n"
+        );
+    }
+}
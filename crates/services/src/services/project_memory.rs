@@ -0,0 +1,316 @@
+//! Memories shared across a project's tasks and conversations.
+//!
+//! When a project opts in, each task attempt that reaches review gets an
+//! internal agent pass asking the agent to distill what it learned into short
+//! facts and preferences. The memories most relevant to a new task or
+//! conversation are added to its first prompt.
+
+use std::collections::HashSet;
+
+use db::models::project_memory::{
+    CreateProjectMemory, ProjectMemory, ProjectMemoryKind, ProjectMemorySettings,
+};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    },
+    profile::ExecutorProfileId,
+};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    embedding::EmbeddingService,
+    feedback::{FeedbackError, FeedbackService},
+    prompt_templates,
+};
+
+/// Prompt for the extraction pass. `{existing_memories}` lists what the
+/// project already remembers so the agent does not repeat it.
+pub const DEFAULT_PROJECT_MEMORY_PROMPT: &str = r#"Before this task is reviewed, record what future agents working on this project should know.
+
+Look back over this session and list durable facts about the project (how to build, test or run it, where things live, pitfalls you hit) and preferences the user expressed about how work should be done.
+
+Only include things that will still be true for other tasks. Skip anything specific to this task's change, anything obvious from reading the code, and secrets. Keep each memory to one or two sentences.
+
+## Already remembered
+{existing_memories}
+
+Do not repeat these. Respond with JSON:
+
+```json
+{
+  "memories": [
+    { "kind": "fact", "content": "<something true about the project>" },
+    { "kind": "preference", "content": "<how the user wants things done>" }
+  ]
+}
+```
+
+Respond with an empty list if there is nothing new worth remembering."#;
+
+/// Most memories stored from one extraction pass
+const MAX_MEMORIES_PER_PASS: usize = 10;
+
+/// Most existing memories listed in the extraction prompt
+const MAX_EXISTING_IN_PROMPT: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum ProjectMemoryError {
+    #[error("Failed to parse project memory response: {0}")]
+    ParseError(String),
+}
+
+/// A memory the agent proposed in its response
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExtractedMemory {
+    pub kind: ProjectMemoryKind,
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectMemoryResponse {
+    #[serde(default)]
+    memories: Vec<ExtractedMemory>,
+}
+
+/// Create the follow-up action asking the agent for memories
+pub fn create_extraction_action(
+    session_id: String,
+    executor_profile_id: ExecutorProfileId,
+    working_dir: Option<String>,
+    existing: &[ProjectMemory],
+) -> ExecutorAction {
+    let existing_memories = if existing.is_empty() {
+        "Nothing yet.".to_string()
+    } else {
+        existing
+            .iter()
+            .take(MAX_EXISTING_IN_PROMPT)
+            .map(memory_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let prompt = prompt_templates::render(
+        DEFAULT_PROJECT_MEMORY_PROMPT,
+        &[("existing_memories", &existing_memories)],
+    );
+
+    ExecutorAction::new(
+        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+            prompt,
+            session_id,
+            executor_profile_id,
+            working_dir,
+        }),
+        None,
+    )
+}
+
+/// Parse the memories out of the agent's response
+pub fn parse_response(assistant_message: &str) -> Result<Vec<ExtractedMemory>, ProjectMemoryError> {
+    let json = FeedbackService::parse_feedback_response(assistant_message)
+        .map_err(|FeedbackError::ParseError(message)| ProjectMemoryError::ParseError(message))?;
+    let response: ProjectMemoryResponse =
+        serde_json::from_str(&json).map_err(|e| ProjectMemoryError::ParseError(e.to_string()))?;
+    Ok(response.memories)
+}
+
+/// Store extracted memories, skipping blanks and ones the project already has
+pub async fn store_extracted(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    source_task_id: Uuid,
+    extracted: Vec<ExtractedMemory>,
+) -> Result<Vec<ProjectMemory>, sqlx::Error> {
+    let existing = ProjectMemory::find_by_project_id(pool, project_id).await?;
+    let mut seen: HashSet<String> = existing.iter().map(|m| normalize(&m.content)).collect();
+
+    let mut stored = Vec::new();
+    for memory in extracted {
+        if stored.len() >= MAX_MEMORIES_PER_PASS {
+            break;
+        }
+        let content = memory.content.trim();
+        if content.is_empty() || !seen.insert(normalize(content)) {
+            continue;
+        }
+        let create = CreateProjectMemory {
+            kind: memory.kind,
+            content: content.to_string(),
+        };
+        stored.push(ProjectMemory::create(pool, project_id, &create, Some(source_task_id)).await?);
+    }
+    Ok(stored)
+}
+
+/// The project's memories most relevant to `query`. Falls back to the newest
+/// memories when vector search is unavailable or nothing was embedded yet.
+pub async fn relevant_memories(
+    pool: &SqlitePool,
+    embedding: &EmbeddingService,
+    project_id: Uuid,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<ProjectMemory>, sqlx::Error> {
+    if limit <= 0 {
+        return Ok(Vec::new());
+    }
+    if db::is_sqlite_vec_available() {
+        match embedding.embed_text(query).await {
+            Ok(query_embedding) => {
+                let matches =
+                    ProjectMemory::search(pool, project_id, &query_embedding, limit).await?;
+                if !matches.is_empty() {
+                    return Ok(matches);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to embed prompt for project memory search: {}", e);
+            }
+        }
+    }
+    ProjectMemory::find_recent(pool, project_id, limit).await
+}
+
+/// Append the project's relevant memories to a new task or conversation
+/// prompt. The prompt is returned unchanged when the project has not opted
+/// in or has nothing remembered.
+pub async fn with_memories(
+    pool: &SqlitePool,
+    embedding: &EmbeddingService,
+    project_id: Uuid,
+    prompt: String,
+) -> String {
+    let settings = match ProjectMemorySettings::find_or_default(pool, project_id).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to load memory settings for project {}: {}",
+                project_id,
+                e
+            );
+            return prompt;
+        }
+    };
+    if !settings.enabled {
+        return prompt;
+    }
+
+    match relevant_memories(pool, embedding, project_id, &prompt, settings.max_injected).await {
+        Ok(memories) => match prompt_section(&memories) {
+            Some(section) => format!("{prompt}\n\n{section}"),
+            None => prompt,
+        },
+        Err(e) => {
+            tracing::warn!("Failed to load memories for project {}: {}", project_id, e);
+            prompt
+        }
+    }
+}
+
+pub fn prompt_section(memories: &[ProjectMemory]) -> Option<String> {
+    if memories.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = memories.iter().map(memory_line).collect();
+    Some(format!(
+        "Project memory (learned in earlier tasks and conversations):\n{}",
+        lines.join("\n")
+    ))
+}
+
+fn memory_line(memory: &ProjectMemory) -> String {
+    let kind = match memory.kind {
+        ProjectMemoryKind::Fact => "Fact",
+        ProjectMemoryKind::Preference => "Preference",
+    };
+    format!("- {kind}: {}", memory.content.trim())
+}
+
+/// Lowercased with whitespace collapsed, for spotting repeats
+fn normalize(content: &str) -> String {
+    content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn memory(kind: ProjectMemoryKind, content: &str) -> ProjectMemory {
+        ProjectMemory {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            kind,
+            content: content.to_string(),
+            source_task_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn parses_memories_from_a_code_block() {
+        let response = r#"Here is what I learned:
+
+```json
+{"memories": [
+  {"kind": "fact", "content": "Run `pnpm test` from the repo root"},
+  {"kind": "preference", "content": "Keep commits small"}
+]}
+```"#;
+        let memories = parse_response(response).unwrap();
+        assert_eq!(
+            memories,
+            vec![
+                ExtractedMemory {
+                    kind: ProjectMemoryKind::Fact,
+                    content: "Run `pnpm test` from the repo root".to_string(),
+                },
+                ExtractedMemory {
+                    kind: ProjectMemoryKind::Preference,
+                    content: "Keep commits small".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_list_and_unknown_kinds() {
+        assert!(parse_response(r#"{"memories": []}"#).unwrap().is_empty());
+        assert!(parse_response(r#"{"memories": [{"kind": "secret", "content": "x"}]}"#).is_err());
+        assert!(parse_response("nothing to add").is_err());
+    }
+
+    #[test]
+    fn prompt_section_lists_memories() {
+        assert_eq!(prompt_section(&[]), None);
+        let section = prompt_section(&[
+            memory(ProjectMemoryKind::Fact, "Migrations live in crates/db "),
+            memory(ProjectMemoryKind::Preference, "Prefer early returns"),
+        ])
+        .unwrap();
+        assert_eq!(
+            section,
+            "Project memory (learned in earlier tasks and conversations):\n\
+             - Fact: Migrations live in crates/db\n\
+             - Preference: Prefer early returns"
+        );
+    }
+
+    #[test]
+    fn normalize_ignores_case_and_spacing() {
+        assert_eq!(
+            normalize("  Use  PNPM\nnot npm "),
+            normalize("use pnpm not npm")
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Values of project secrets.
+//!
+//! The `project_secrets` table lists each secret's name and the [`SecretRef`]
+//! of the secret store entry holding its value, so values never sit in the
+//! database or its backups.
+
+use std::collections::HashMap;
+
+use db::models::project_secret::ProjectSecret;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::secrets::{SecretRef, SecretService};
+
+/// Start of the names of store entries holding project secrets
+const ENTRY_PREFIX: &str = "project_secret.";
+
+/// Name of the store entry holding a project secret's value
+pub fn entry_name(project_id: Uuid, name: &str) -> String {
+    format!("{ENTRY_PREFIX}{project_id}.{name}")
+}
+
+/// Values of a project's secrets, keyed by name. Secrets whose store entry
+/// cannot be read are left out, so templates report them as missing.
+pub async fn values(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+    project_id: Uuid,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    let mut values = HashMap::new();
+    for (name, secret_ref) in ProjectSecret::refs_by_project_id(pool, project_id).await? {
+        if let Some(value) = secrets.resolve(&SecretRef::new(secret_ref)).await {
+            values.insert(name, value);
+        }
+    }
+    Ok(values)
+}
+
+/// Move values saved in the database by older versions into the store. A
+/// value that cannot be stored stays in the database and is retried at the
+/// next start.
+pub async fn migrate_plaintext(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+) -> Result<(), sqlx::Error> {
+    for secret in ProjectSecret::find_plaintext(pool).await? {
+        let entry = entry_name(secret.project_id, &secret.name);
+        match secrets.set(&entry, &secret.value).await {
+            Ok(secret_ref) => {
+                ProjectSecret::set_secret_ref(pool, secret.id, secret_ref.name()).await?
+            }
+            Err(e) => tracing::warn!(
+                "Could not move project secret '{}' into the secret store: {}",
+                secret.name,
+                e
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Delete store entries no secret refers to any more, such as those of
+/// deleted projects. Returns how many were deleted.
+pub async fn remove_orphans(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+) -> Result<usize, sqlx::Error> {
+    let entries = match secrets.list().await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("Failed to list the secret store: {}", e);
+            return Ok(0);
+        }
+    };
+
+    let mut removed = 0;
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.starts_with(ENTRY_PREFIX))
+    {
+        if ProjectSecret::secret_ref_exists(pool, entry).await? {
+            continue;
+        }
+        match secrets.delete(entry).await {
+            Ok(deleted) => removed += usize::from(deleted),
+            Err(e) => tracing::warn!("Failed to delete secret '{}': {}", entry, e),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::services::secrets::{MemoryStore, SecretStore};
+
+    async fn create_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn plaintext_values_move_into_the_store() {
+        let pool = create_test_db().await;
+        let store = Arc::new(MemoryStore::default());
+        let secrets = SecretService::with_store(store.clone());
+
+        let project_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Secrets')")
+            .bind(project_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO project_secrets (id, project_id, name, value) VALUES (?, ?, 'API_KEY', 'hunter2')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        migrate_plaintext(&pool, &secrets).await.unwrap();
+
+        let entry = entry_name(project_id, "API_KEY");
+        assert_eq!(store.get(&entry).unwrap().as_deref(), Some("hunter2"));
+        let stored: String = sqlx::query_scalar("SELECT value FROM project_secrets")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, "");
+        assert!(
+            ProjectSecret::find_plaintext(&pool)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let values = values(&pool, &secrets, project_id).await.unwrap();
+        assert_eq!(values.get("API_KEY").map(String::as_str), Some("hunter2"));
+
+        // Entries outlive their project until orphans are removed
+        sqlx::query("DELETE FROM projects")
+            .execute(&pool)
+            .await
+            .unwrap();
+        store.set("gitlab_token", "glpat").unwrap();
+        assert_eq!(remove_orphans(&pool, &secrets).await.unwrap(), 1);
+        assert_eq!(store.list().unwrap(), vec!["gitlab_token".to_string()]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Projects created from templates.
+//!
+//! A template lists the repositories a project should have, cloning the ones
+//! that are not on disk yet, along with their setup scripts and the files to
+//! copy into worktrees, and the tasks every project made from it starts with.
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    project::{CreateProject, Project},
+    project_repo::{CreateProjectRepo, ProjectRepo, ProjectRepoError, UpdateProjectRepo},
+    project_template::ProjectTemplate,
+    task::{CreateTask, Task},
+};
+use executors::profile::ExecutorProfileId;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utils::path::expand_tilde;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    config::Config,
+    project::{ProjectService, ProjectServiceError},
+    repo::{RepoError, RepoService},
+};
+
+#[derive(Debug, Error)]
+pub enum ProjectTemplateError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Repo(#[from] RepoError),
+    #[error(transparent)]
+    Project(#[from] ProjectServiceError),
+    #[error(transparent)]
+    ProjectRepo(#[from] ProjectRepoError),
+    #[error("Repository '{0}' does not exist at {1} and has no URL to clone it from")]
+    MissingRepository(String, PathBuf),
+}
+
+/// A project created from a template
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ProjectFromTemplate {
+    pub project: Project,
+    /// Display names of the repositories that were cloned for the project
+    pub cloned_repositories: Vec<String>,
+    pub tasks: Vec<Task>,
+    /// The template's coding agent, for starting the tasks
+    #[schema(value_type = Option<Object>)]
+    pub executor_profile_id: Option<ExecutorProfileId>,
+}
+
+/// Create a project named `name` from `template`, cloning its missing
+/// repositories first
+pub async fn create_project(
+    pool: &SqlitePool,
+    project_service: &ProjectService,
+    repo_service: &RepoService,
+    config: &Config,
+    template: &ProjectTemplate,
+    name: String,
+) -> Result<ProjectFromTemplate, ProjectTemplateError> {
+    let mut cloned_repositories = Vec::new();
+    let mut repositories = Vec::with_capacity(template.repositories.len());
+    let mut paths = Vec::with_capacity(template.repositories.len());
+    for template_repo in template.repositories.iter() {
+        let path = resolve_repo_path(&template_repo.path, config)?;
+        if !path.exists() {
+            let Some(url) = &template_repo.url else {
+                return Err(ProjectTemplateError::MissingRepository(
+                    template_repo.display_name.clone(),
+                    path,
+                ));
+            };
+            tracing::info!(
+                "Cloning {} into {} for template '{}'",
+                url,
+                path.display(),
+                template.name
+            );
+            repo_service
+                .clone_repository(pool, url, Some(&path.to_string_lossy()), config)
+                .await?;
+            cloned_repositories.push(template_repo.display_name.clone());
+        }
+        repositories.push(CreateProjectRepo {
+            display_name: template_repo.display_name.clone(),
+            git_repo_path: path.to_string_lossy().to_string(),
+        });
+        paths.push(path);
+    }
+
+    let project = project_service
+        .create_project(pool, repo_service, CreateProject { name, repositories })
+        .await?;
+
+    let repos = project_service.get_repositories(pool, project.id).await?;
+    for (template_repo, path) in template.repositories.iter().zip(&paths) {
+        if template_repo.setup_script.is_none() && template_repo.copy_files.is_none() {
+            continue;
+        }
+        let Some(repo) = repos.iter().find(|repo| same_path(&repo.path, path)) else {
+            continue;
+        };
+        ProjectRepo::update(
+            pool,
+            project.id,
+            repo.id,
+            &UpdateProjectRepo {
+                setup_script: template_repo.setup_script.clone(),
+                cleanup_script: None,
+                copy_files: template_repo.copy_files.clone(),
+                post_copy_script: None,
+                parallel_setup_script: None,
+                merge_target_branch: None,
+            },
+        )
+        .await?;
+    }
+
+    let mut tasks = Vec::with_capacity(template.tasks.len());
+    for template_task in template.tasks.iter() {
+        let create_task = CreateTask::from_title_description(
+            project.id,
+            template_task.title.clone(),
+            template_task.description.clone(),
+        );
+        tasks.push(Task::create(pool, &create_task, Uuid::new_v4()).await?);
+    }
+
+    Ok(ProjectFromTemplate {
+        project,
+        cloned_repositories,
+        tasks,
+        executor_profile_id: template
+            .executor_profile_id
+            .as_ref()
+            .map(|profile| profile.0.clone()),
+    })
+}
+
+/// Absolute path of a template repository; relative paths are under the
+/// default clone directory
+fn resolve_repo_path(path: &str, config: &Config) -> Result<PathBuf, RepoError> {
+    let path = expand_tilde(path.trim());
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    let clone_dir = config
+        .default_clone_directory
+        .as_deref()
+        .ok_or(RepoError::NoCloneDirectory)?;
+    Ok(std::path::absolute(expand_tilde(clone_dir).join(path))?)
+}
+
+pub(crate) fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_paths_resolve_under_the_clone_directory() {
+        let config = Config {
+            default_clone_directory: Some("/srv/code".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(
+            resolve_repo_path("api", &config).unwrap(),
+            PathBuf::from("/srv/code/api")
+        );
+        assert_eq!(
+            resolve_repo_path("/opt/web", &config).unwrap(),
+            PathBuf::from("/opt/web")
+        );
+    }
+
+    #[test]
+    fn relative_paths_need_a_clone_directory() {
+        let config = Config {
+            default_clone_directory: None,
+            ..Config::default()
+        };
+        assert!(matches!(
+            resolve_repo_path("api", &config),
+            Err(RepoError::NoCloneDirectory)
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+//! Prompt templates from the user's library, referenced by id from the config.
+//!
+//! Templates use `{name}` placeholders. Each prompt that can be templated
+//! documents its variables below; unknown placeholders are left as written,
+//! so literal braces (e.g. JSON examples) need no escaping.
+
+use std::sync::LazyLock;
+
+use db::models::prompt_template::PromptTemplate;
+use regex::{Captures, Regex};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Variables available to commit message templates
+pub const COMMIT_MESSAGE_VARIABLES: &[&str] = &["task_title", "task_description", "diff"];
+
+/// Variables available to agent feedback templates
+pub const FEEDBACK_VARIABLES: &[&str] = &["task_title", "task_description"];
+
+/// Variables available to review attention templates
+pub const REVIEW_ATTENTION_VARIABLES: &[&str] = &["task_description", "agent_summary"];
+
+static PLACEHOLDER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid placeholder regex"));
+
+/// `template` with each `{name}` placeholder in `variables` replaced by its
+/// value. Substitution is a single pass, so values containing placeholders
+/// (e.g. a diff of a template file) are inserted verbatim.
+pub fn render(template: &str, variables: &[(&str, &str)]) -> String {
+    PLACEHOLDER_RE
+        .replace_all(template, |captures: &Captures| {
+            let name = &captures[1];
+            variables
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_else(|| captures[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Content of the template configured as `template_id`. A template that was
+/// deleted, or cannot be loaded, falls back to the caller's default.
+pub async fn resolve(pool: &SqlitePool, template_id: Option<Uuid>) -> Option<String> {
+    let template_id = template_id?;
+    match PromptTemplate::find_by_id(pool, template_id).await {
+        Ok(Some(template)) => Some(template.content),
+        Ok(None) => {
+            tracing::warn!(
+                "Prompt template {} is configured but no longer exists, using the default prompt",
+                template_id
+            );
+            None
+        }
+        Err(e) => {
+            tracing::error!("Failed to load prompt template {}: {}", template_id, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_substitutes_known_variables_once() {
+        let rendered = render(
+            "Task: {task_title}\n{diff}\n{\"json\": true} {unknown}",
+            &[("task_title", "Fix login"), ("diff", "+ {task_title}")],
+        );
+        assert_eq!(
+            rendered,
+            "Task: Fix login\n+ {task_title}\n{\"json\": true} {unknown}"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Per-provider limits on starting coding agents, so concurrent executions do
+//! not exhaust an account's rate limits and fail mid-run.
+//!
+//! A provider is an executor such as `CLAUDE_CODE`, limited by the
+//! [`ProviderRateLimit`] configured for it. Executions over a limit are queued
+//! instead of started, and their workspace shows a
+//! [`OperationStatusType::WaitingForRateLimit`] status until the queue starts
+//! them.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use db::models::workspace::Workspace;
+use parking_lot::Mutex;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::services::{
+    config::ProviderRateLimit,
+    operation_status::{OperationStatus, OperationStatusStore, OperationStatusType},
+};
+
+/// Window `max_starts_per_minute` is counted over
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Clone)]
+pub struct ProviderRateLimiter {
+    /// Recent start times per provider, oldest first
+    starts: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+    /// Set after construction, like the container's own store
+    operation_status: Arc<RwLock<Option<OperationStatusStore>>>,
+}
+
+impl ProviderRateLimiter {
+    pub fn new(operation_status: Arc<RwLock<Option<OperationStatusStore>>>) -> Self {
+        Self {
+            starts: Arc::new(Mutex::new(HashMap::new())),
+            operation_status,
+        }
+    }
+
+    /// Whether `limit` allows another start of `provider` while `running` of
+    /// its executions are running
+    pub fn allows_start(&self, provider: &str, limit: &ProviderRateLimit, running: i64) -> bool {
+        self.allows_start_at(provider, limit, running, Instant::now())
+    }
+
+    fn allows_start_at(
+        &self,
+        provider: &str,
+        limit: &ProviderRateLimit,
+        running: i64,
+        now: Instant,
+    ) -> bool {
+        if limit.max_concurrent > 0 && running >= i64::from(limit.max_concurrent) {
+            return false;
+        }
+        if limit.max_starts_per_minute == 0 {
+            return true;
+        }
+        let mut starts = self.starts.lock();
+        let Some(recent) = starts.get_mut(provider) else {
+            return true;
+        };
+        prune(recent, now);
+        recent.len() < limit.max_starts_per_minute as usize
+    }
+
+    /// Count a start of `provider` towards its per-minute limit
+    pub fn record_start(&self, provider: &str) {
+        self.record_start_at(provider, Instant::now());
+    }
+
+    fn record_start_at(&self, provider: &str, now: Instant) {
+        let mut starts = self.starts.lock();
+        let recent = starts.entry(provider.to_string()).or_default();
+        prune(recent, now);
+        recent.push_back(now);
+    }
+
+    /// Show the workspace as waiting for its provider's rate limit
+    pub async fn mark_waiting(&self, workspace: &Workspace) {
+        let guard = self.operation_status.read().await;
+        let Some(store) = guard.as_ref() else {
+            return;
+        };
+        // Never hide a merge or push that is in progress
+        if store.get(workspace.id).is_none() {
+            store.set(OperationStatus::new(
+                workspace.id,
+                workspace.task_id,
+                OperationStatusType::WaitingForRateLimit,
+            ));
+        }
+    }
+
+    /// Remove the waiting status, leaving other operations alone
+    pub async fn clear_waiting(&self, workspace_id: Uuid) {
+        let guard = self.operation_status.read().await;
+        let Some(store) = guard.as_ref() else {
+            return;
+        };
+        if store
+            .get(workspace_id)
+            .is_some_and(|status| status.operation_type == OperationStatusType::WaitingForRateLimit)
+        {
+            store.clear(workspace_id);
+        }
+    }
+}
+
+fn prune(recent: &mut VecDeque<Instant>, now: Instant) {
+    while recent
+        .front()
+        .is_some_and(|start| now.duration_since(*start) >= RATE_LIMIT_WINDOW)
+    {
+        recent.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_concurrency_and_start_rate() {
+        let limiter = ProviderRateLimiter::new(Arc::new(RwLock::new(None)));
+        let limit = ProviderRateLimit {
+            max_concurrent: 3,
+            max_starts_per_minute: 2,
+        };
+        let start = Instant::now();
+
+        assert!(limiter.allows_start_at("CLAUDE_CODE", &limit, 0, start));
+        assert!(!limiter.allows_start_at("CLAUDE_CODE", &limit, 3, start));
+
+        limiter.record_start_at("CLAUDE_CODE", start);
+        limiter.record_start_at("CLAUDE_CODE", start + Duration::from_secs(10));
+        assert!(!limiter.allows_start_at(
+            "CLAUDE_CODE",
+            &limit,
+            2,
+            start + Duration::from_secs(30)
+        ));
+        // Other providers are tracked separately
+        assert!(limiter.allows_start_at("CODEX", &limit, 0, start + Duration::from_secs(30)));
+        // The first start leaves the window after a minute
+        assert!(limiter.allows_start_at("CLAUDE_CODE", &limit, 2, start + RATE_LIMIT_WINDOW));
+
+        let unlimited = ProviderRateLimit::default();
+        assert!(limiter.allows_start_at("CLAUDE_CODE", &unlimited, 100, start));
+    }
+}
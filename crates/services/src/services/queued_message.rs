@@ -5,12 +5,14 @@ use dashmap::DashMap;
 use db::models::scratch::DraftFollowUpData;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Represents a queued follow-up message for a session
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct QueuedMessage {
+    pub id: Uuid,
     /// The session this message is queued for
     pub session_id: Uuid,
     /// The follow-up data (message + variant)
@@ -20,21 +22,27 @@ pub struct QueuedMessage {
 }
 
 /// Status of the queue for a session (for frontend display)
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "status", rename_all = "snake_case")]
 #[ts(export)]
 pub enum QueueStatus {
     /// No message queued
     Empty,
     /// Message is queued and waiting for execution to complete
-    Queued { message: QueuedMessage },
+    Queued {
+        /// The message sent next
+        message: QueuedMessage,
+        /// Messages queued after `message`, in the order they will be sent
+        pending: Vec<QueuedMessage>,
+    },
 }
 
 /// In-memory service for managing queued follow-up messages.
-/// One queued message per session.
+/// Each session has a queue of messages; when an execution in the session
+/// finishes, the first one is taken and sent as the next follow-up.
 #[derive(Clone)]
 pub struct QueuedMessageService {
-    queue: Arc<DashMap<Uuid, QueuedMessage>>,
+    queue: Arc<DashMap<Uuid, Vec<QueuedMessage>>>,
 }
 
 impl QueuedMessageService {
@@ -44,42 +52,120 @@ impl QueuedMessageService {
         }
     }
 
-    /// Queue a message for a session. Replaces any existing queued message.
-    pub fn queue_message(&self, session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
-        let queued = QueuedMessage {
+    fn new_message(session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
+        QueuedMessage {
+            id: Uuid::new_v4(),
             session_id,
             data,
             queued_at: Utc::now(),
-        };
-        self.queue.insert(session_id, queued.clone());
+        }
+    }
+
+    /// Queue a message for a session. Replaces any existing queued messages.
+    pub fn queue_message(&self, session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
+        let queued = Self::new_message(session_id, data);
+        self.queue.insert(session_id, vec![queued.clone()]);
+        queued
+    }
+
+    /// Add a message to the end of a session's queue
+    pub fn push_message(&self, session_id: Uuid, data: DraftFollowUpData) -> QueuedMessage {
+        let queued = Self::new_message(session_id, data);
+        self.queue
+            .entry(session_id)
+            .or_default()
+            .push(queued.clone());
         queued
     }
 
-    /// Cancel/remove a queued message for a session
-    pub fn cancel_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&session_id).map(|(_, v)| v)
+    /// Cancel/remove all queued messages for a session
+    pub fn cancel_queued(&self, session_id: Uuid) -> Vec<QueuedMessage> {
+        self.queue
+            .remove(&session_id)
+            .map(|(_, v)| v)
+            .unwrap_or_default()
+    }
+
+    /// Remove one queued message from a session's queue
+    pub fn remove_message(&self, session_id: Uuid, message_id: Uuid) -> Option<QueuedMessage> {
+        let mut messages = self.queue.get_mut(&session_id)?;
+        let idx = messages.iter().position(|m| m.id == message_id)?;
+        let removed = messages.remove(idx);
+        let now_empty = messages.is_empty();
+        drop(messages);
+        if now_empty {
+            self.queue.remove_if(&session_id, |_, v| v.is_empty());
+        }
+        Some(removed)
+    }
+
+    /// Reorder a session's queue. `message_ids` must list every queued
+    /// message exactly once; returns false and leaves the queue alone
+    /// otherwise.
+    pub fn reorder(&self, session_id: Uuid, message_ids: &[Uuid]) -> bool {
+        let Some(mut messages) = self.queue.get_mut(&session_id) else {
+            return message_ids.is_empty();
+        };
+        if message_ids.len() != messages.len() {
+            return false;
+        }
+        let mut reordered = Vec::with_capacity(messages.len());
+        for id in message_ids {
+            if reordered.iter().any(|m: &QueuedMessage| m.id == *id) {
+                return false;
+            }
+            let Some(message) = messages.iter().find(|m| m.id == *id) else {
+                return false;
+            };
+            reordered.push(message.clone());
+        }
+        *messages = reordered;
+        true
     }
 
-    /// Get the queued message for a session (if any)
+    /// Get the message sent next for a session (if any)
     pub fn get_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.get(&session_id).map(|r| r.clone())
+        self.queue
+            .get(&session_id)
+            .and_then(|messages| messages.first().cloned())
     }
 
-    /// Take (remove and return) the queued message for a session.
+    /// All queued messages for a session, in the order they will be sent
+    pub fn list_queued(&self, session_id: Uuid) -> Vec<QueuedMessage> {
+        self.queue
+            .get(&session_id)
+            .map(|messages| messages.clone())
+            .unwrap_or_default()
+    }
+
+    /// Take (remove and return) the message sent next for a session.
     /// Used by finalization flow to consume the queued message.
     pub fn take_queued(&self, session_id: Uuid) -> Option<QueuedMessage> {
-        self.queue.remove(&session_id).map(|(_, v)| v)
+        let mut messages = self.queue.get_mut(&session_id)?;
+        let taken = (!messages.is_empty()).then(|| messages.remove(0));
+        let now_empty = messages.is_empty();
+        drop(messages);
+        if now_empty {
+            self.queue.remove_if(&session_id, |_, v| v.is_empty());
+        }
+        taken
     }
 
     /// Check if a session has a queued message
     pub fn has_queued(&self, session_id: Uuid) -> bool {
-        self.queue.contains_key(&session_id)
+        self.queue
+            .get(&session_id)
+            .is_some_and(|messages| !messages.is_empty())
     }
 
     /// Get queue status for frontend display
     pub fn get_status(&self, session_id: Uuid) -> QueueStatus {
-        match self.get_queued(session_id) {
-            Some(msg) => QueueStatus::Queued { message: msg },
+        let mut messages = self.list_queued(session_id).into_iter();
+        match messages.next() {
+            Some(message) => QueueStatus::Queued {
+                message,
+                pending: messages.collect(),
+            },
             None => QueueStatus::Empty,
         }
     }
@@ -90,3 +176,51 @@ impl Default for QueuedMessageService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(message: &str) -> DraftFollowUpData {
+        DraftFollowUpData {
+            message: message.to_string(),
+            variant: None,
+        }
+    }
+
+    #[test]
+    fn pushed_messages_are_taken_in_order() {
+        let service = QueuedMessageService::new();
+        let session_id = Uuid::new_v4();
+        let first = service.push_message(session_id, data("first"));
+        let second = service.push_message(session_id, data("second"));
+        let third = service.push_message(session_id, data("third"));
+
+        assert!(service.reorder(session_id, &[third.id, first.id, second.id]));
+        assert!(!service.reorder(session_id, &[third.id, first.id]));
+        assert!(!service.reorder(session_id, &[third.id, third.id, second.id]));
+
+        assert!(service.remove_message(session_id, first.id).is_some());
+        assert_eq!(service.take_queued(session_id).unwrap().id, third.id);
+        assert_eq!(service.take_queued(session_id).unwrap().id, second.id);
+        assert!(service.take_queued(session_id).is_none());
+        assert!(!service.has_queued(session_id));
+    }
+
+    #[test]
+    fn queue_message_replaces_the_queue() {
+        let service = QueuedMessageService::new();
+        let session_id = Uuid::new_v4();
+        service.push_message(session_id, data("first"));
+        service.push_message(session_id, data("second"));
+        let replacement = service.queue_message(session_id, data("replacement"));
+
+        match service.get_status(session_id) {
+            QueueStatus::Queued { message, pending } => {
+                assert_eq!(message.id, replacement.id);
+                assert!(pending.is_empty());
+            }
+            QueueStatus::Empty => panic!("expected a queued message"),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Read-through cache for repo metadata.
+//!
+//! Branch lists, HEAD info, ahead/behind counts and repo statistics are
+//! recomputed from git on every request otherwise, which gets slow on repos
+//! with hundreds of branches or a long history. Entries are grouped by the repo's common git directory, so a
+//! main checkout and its worktrees share one group. A group is invalidated
+//! when the git watcher reports a HEAD/refs change in it, or explicitly via
+//! [`RepoMetadataCache::invalidate`] after a mutating operation.
+//!
+//! Invalidation bumps the group's generation rather than removing entries:
+//! keys carry the generation they were computed under, so a value computed
+//! concurrently with an invalidation is never served afterwards.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use dashmap::{DashMap, DashSet};
+use git2::BranchType;
+use moka::sync::Cache;
+
+use super::{
+    git::{GitBranch, GitService, GitServiceError, HeadInfo},
+    git_watcher::{GitStateChangeKind, GitWatcherManager, resolve_git_dir},
+    repo_stats::{self, RepoStats},
+};
+
+/// Backstop TTL for repos whose watcher could not be started
+const LOCAL_TTL: Duration = Duration::from_secs(600);
+
+/// Counts against remote-tracking branches can only change after a fetch,
+/// which the remote status computation itself performs; this is how often
+/// it is allowed to run per branch
+const REMOTE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PathKey {
+    path: PathBuf,
+    generation: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StatusKey {
+    path: PathBuf,
+    generation: u64,
+    branch: String,
+    /// `None` compares against the branch's upstream
+    base: Option<String>,
+}
+
+pub struct RepoMetadataCache {
+    git: GitService,
+    git_watcher: GitWatcherManager,
+    /// Generation per common git directory
+    generations: Arc<DashMap<PathBuf, u64>>,
+    /// Git directories with an invalidation task running
+    watched: DashSet<PathBuf>,
+    branches: Cache<PathKey, Vec<GitBranch>>,
+    heads: Cache<PathKey, HeadInfo>,
+    branch_status: Cache<StatusKey, (usize, usize)>,
+    remote_status: Cache<StatusKey, (usize, usize)>,
+    stats: Cache<PathKey, RepoStats>,
+}
+
+impl RepoMetadataCache {
+    pub fn new(git: GitService, git_watcher: GitWatcherManager) -> Self {
+        Self {
+            git,
+            git_watcher,
+            generations: Arc::new(DashMap::new()),
+            watched: DashSet::new(),
+            branches: Cache::builder()
+                .max_capacity(200)
+                .time_to_live(LOCAL_TTL)
+                .build(),
+            heads: Cache::builder()
+                .max_capacity(1_000)
+                .time_to_live(LOCAL_TTL)
+                .build(),
+            branch_status: Cache::builder()
+                .max_capacity(5_000)
+                .time_to_live(LOCAL_TTL)
+                .build(),
+            remote_status: Cache::builder()
+                .max_capacity(5_000)
+                .time_to_live(REMOTE_TTL)
+                .build(),
+            stats: Cache::builder()
+                .max_capacity(100)
+                .time_to_live(LOCAL_TTL)
+                .build(),
+        }
+    }
+
+    /// All local and remote branches of the repo at `repo_path`
+    pub fn branches(&self, repo_path: &Path) -> Result<Vec<GitBranch>, git2::Error> {
+        let key = self.path_key(repo_path);
+        if let Some(branches) = self.branches.get(&key) {
+            return Ok(branches);
+        }
+        let branches = self.git.get_all_branches(repo_path)?;
+        self.branches.insert(key, branches.clone());
+        Ok(branches)
+    }
+
+    /// HEAD of the repo or worktree at `path`
+    pub fn head_info(&self, path: &Path) -> Result<HeadInfo, GitServiceError> {
+        let key = self.path_key(path);
+        if let Some(head) = self.heads.get(&key) {
+            return Ok(head);
+        }
+        let head = self.git.get_head_info(path)?;
+        self.heads.insert(key, head.clone());
+        Ok(head)
+    }
+
+    /// Commits `branch` is ahead of and behind `base`. Remote bases fetch
+    /// first, so their counts are reused for [`REMOTE_TTL`] at most.
+    pub fn branch_status(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+        base: &str,
+        base_type: BranchType,
+    ) -> Result<(usize, usize), GitServiceError> {
+        let PathKey { path, generation } = self.path_key(repo_path);
+        let key = StatusKey {
+            path,
+            generation,
+            branch: branch.to_string(),
+            base: Some(base.to_string()),
+        };
+        match base_type {
+            BranchType::Local => {
+                if let Some(counts) = self.branch_status.get(&key) {
+                    return Ok(counts);
+                }
+                let counts = self.git.get_branch_status(repo_path, branch, base)?;
+                self.branch_status.insert(key, counts);
+                Ok(counts)
+            }
+            BranchType::Remote => {
+                if let Some(counts) = self.remote_status.get(&key) {
+                    return Ok(counts);
+                }
+                let counts = self
+                    .git
+                    .get_remote_branch_status(repo_path, branch, Some(base))?;
+                self.remote_status.insert(key, counts);
+                Ok(counts)
+            }
+        }
+    }
+
+    /// Commits `branch` is ahead of and behind its upstream, after a fetch
+    /// at most every [`REMOTE_TTL`]
+    pub fn upstream_status(
+        &self,
+        repo_path: &Path,
+        branch: &str,
+    ) -> Result<(usize, usize), GitServiceError> {
+        let PathKey { path, generation } = self.path_key(repo_path);
+        let key = StatusKey {
+            path,
+            generation,
+            branch: branch.to_string(),
+            base: None,
+        };
+        if let Some(counts) = self.remote_status.get(&key) {
+            return Ok(counts);
+        }
+        let counts = self.git.get_remote_branch_status(repo_path, branch, None)?;
+        self.remote_status.insert(key, counts);
+        Ok(counts)
+    }
+
+    /// Branch, history, language and size statistics of the repo at
+    /// `repo_path`
+    pub fn stats(&self, repo_path: &Path) -> Result<RepoStats, GitServiceError> {
+        let key = self.path_key(repo_path);
+        if let Some(stats) = self.stats.get(&key) {
+            return Ok(stats);
+        }
+        let stats = repo_stats::compute(repo_path)?;
+        self.stats.insert(key, stats.clone());
+        Ok(stats)
+    }
+
+    /// Drop everything cached for the repo containing `path`, including its
+    /// worktrees. Call after any operation that moves refs or HEAD.
+    pub fn invalidate(&self, path: &Path) {
+        self.bump(&common_git_dir(path));
+    }
+
+    fn bump(&self, group: &Path) {
+        *self.generations.entry(group.to_path_buf()).or_insert(0) += 1;
+    }
+
+    fn path_key(&self, path: &Path) -> PathKey {
+        let group = common_git_dir(path);
+        self.watch(path, &group);
+        let generation = self.generations.get(&group).map(|g| *g).unwrap_or(0);
+        PathKey {
+            path: path.to_path_buf(),
+            generation,
+        }
+    }
+
+    /// Start invalidating `group` on changes to the git directory of `path`
+    /// and to the group's common directory
+    fn watch(&self, path: &Path, group: &Path) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let mut targets = vec![path.to_path_buf()];
+        // The main checkout of a linked worktree owns the shared refs
+        if group.file_name().is_some_and(|name| name == ".git")
+            && let Some(main) = group.parent()
+        {
+            targets.push(main.to_path_buf());
+        }
+
+        for target in targets {
+            let Ok(git_dir) = resolve_git_dir(&target) else {
+                continue;
+            };
+            let git_dir = dunce::canonicalize(&git_dir).unwrap_or(git_dir);
+            if !self.watched.insert(git_dir.clone()) {
+                continue;
+            }
+            let mut subscription = match self.git_watcher.subscribe(target.clone()) {
+                Ok(subscription) => subscription,
+                Err(e) => {
+                    tracing::debug!("Repo metadata for {:?} relies on TTL only: {}", target, e);
+                    continue;
+                }
+            };
+            let group = group.to_path_buf();
+            let generations = Arc::clone(&self.generations);
+            handle.spawn(async move {
+                while let Some(change) = subscription.recv().await {
+                    if change.kind == GitStateChangeKind::Index {
+                        continue;
+                    }
+                    *generations.entry(group.clone()).or_insert(0) += 1;
+                }
+            });
+        }
+    }
+}
+
+/// Common git directory of the repo or worktree at `path`, identifying the
+/// group its cache entries belong to. Falls back to `path` itself.
+fn common_git_dir(path: &Path) -> PathBuf {
+    let Ok(git_dir) = resolve_git_dir(path) else {
+        return path.to_path_buf();
+    };
+    let common = std::fs::read_to_string(git_dir.join("commondir"))
+        .map(|content| git_dir.join(content.trim()))
+        .unwrap_or(git_dir);
+    dunce::canonicalize(&common).unwrap_or(common)
+}
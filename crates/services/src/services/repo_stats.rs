@@ -0,0 +1,297 @@
+//! Summary statistics for a repo's overview page.
+//!
+//! Everything is read with gix from HEAD and the refs; nothing touches the
+//! working tree. Contributor and activity figures come from the most recent
+//! [`COMMIT_SCAN_LIMIT`] commits, so they stay cheap on long histories.
+//! Languages are guessed from file extensions only.
+
+use std::{collections::HashMap, path::Path};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use ts_rs::TS;
+use utils::path::dir_size;
+use utoipa::ToSchema;
+
+use super::gix_reader::{CommitAuthorship, GixReader, GixReaderError, TreeFile};
+
+/// Days of commit activity reported, ending today (UTC)
+pub const ACTIVITY_DAYS: usize = 30;
+
+/// Most recent commits scanned for contributors and activity
+pub const COMMIT_SCAN_LIMIT: usize = 5_000;
+
+const TOP_CONTRIBUTORS: usize = 10;
+
+/// Bucket for files whose extension isn't recognised
+const OTHER_LANGUAGE: &str = "Other";
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct RepoStats {
+    pub local_branch_count: usize,
+    /// Remote-tracking branches, not counting `origin/HEAD` style aliases
+    pub remote_branch_count: usize,
+    /// One entry per day, oldest first, including days without commits
+    pub commit_activity: Vec<DailyCommitCount>,
+    /// Most active authors first
+    pub top_contributors: Vec<Contributor>,
+    /// Largest first
+    pub languages: Vec<LanguageStat>,
+    /// Files in HEAD's tree
+    pub file_count: usize,
+    /// Total size of the files in HEAD's tree
+    #[ts(type = "number")]
+    pub tracked_bytes: u64,
+    /// Size of the object database on disk, packs included
+    #[ts(type = "number")]
+    pub object_store_bytes: u64,
+    /// Commits the contributor and activity figures are based on
+    pub commits_scanned: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+pub struct DailyCommitCount {
+    pub date: NaiveDate,
+    pub commits: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+pub struct Contributor {
+    /// Name on the contributor's most recent commit
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    #[ts(type = "Date")]
+    pub last_commit_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+pub struct LanguageStat {
+    pub language: String,
+    pub files: usize,
+    #[ts(type = "number")]
+    pub bytes: u64,
+}
+
+/// Compute the statistics of the repo at `repo_path`
+pub fn compute(repo_path: &Path) -> Result<RepoStats, GixReaderError> {
+    let repo = GixReader::open(repo_path)?;
+    let (local_branch_count, remote_branch_count) = GixReader::branch_counts(&repo)?;
+    let commits = GixReader::recent_commits(&repo, COMMIT_SCAN_LIMIT)?;
+    let files = GixReader::head_tree_files(&repo)?;
+
+    Ok(RepoStats {
+        local_branch_count,
+        remote_branch_count,
+        commit_activity: commit_activity(&commits, Utc::now().date_naive()),
+        top_contributors: top_contributors(&commits),
+        languages: language_breakdown(&files),
+        file_count: files.len(),
+        tracked_bytes: files.iter().map(|file| file.size).sum(),
+        object_store_bytes: dir_size(&repo.common_dir().join("objects")),
+        commits_scanned: commits.len(),
+    })
+}
+
+fn commit_activity(commits: &[CommitAuthorship], today: NaiveDate) -> Vec<DailyCommitCount> {
+    let first_day = today - Duration::days(ACTIVITY_DAYS as i64 - 1);
+    let mut counts = vec![0; ACTIVITY_DAYS];
+    for commit in commits {
+        let day = commit.time.date_naive();
+        if day < first_day || day > today {
+            continue;
+        }
+        counts[(day - first_day).num_days() as usize] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(offset, commits)| DailyCommitCount {
+            date: first_day + Duration::days(offset as i64),
+            commits,
+        })
+        .collect()
+}
+
+/// Authors are told apart by email, case-insensitively
+fn top_contributors(commits: &[CommitAuthorship]) -> Vec<Contributor> {
+    let mut by_email: HashMap<String, Contributor> = HashMap::new();
+    for commit in commits {
+        let contributor = by_email
+            .entry(commit.author_email.to_lowercase())
+            .or_insert_with(|| Contributor {
+                name: commit.author_name.clone(),
+                email: commit.author_email.clone(),
+                commits: 0,
+                last_commit_at: commit.time,
+            });
+        contributor.commits += 1;
+        if commit.time > contributor.last_commit_at {
+            contributor.name = commit.author_name.clone();
+            contributor.last_commit_at = commit.time;
+        }
+    }
+
+    let mut contributors: Vec<_> = by_email.into_values().collect();
+    contributors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    contributors.truncate(TOP_CONTRIBUTORS);
+    contributors
+}
+
+fn language_breakdown(files: &[TreeFile]) -> Vec<LanguageStat> {
+    let mut by_language: HashMap<&'static str, LanguageStat> = HashMap::new();
+    for file in files {
+        let language = language_for_path(&file.path).unwrap_or(OTHER_LANGUAGE);
+        let stat = by_language.entry(language).or_insert_with(|| LanguageStat {
+            language: language.to_string(),
+            files: 0,
+            bytes: 0,
+        });
+        stat.files += 1;
+        stat.bytes += file.size;
+    }
+
+    let mut languages: Vec<_> = by_language.into_values().collect();
+    languages.sort_by(|a, b| {
+        b.bytes
+            .cmp(&a.bytes)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    languages
+}
+
+/// Guess a file's language from its name
+fn language_for_path(path: &str) -> Option<&'static str> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    match file_name {
+        "Dockerfile" | "Containerfile" => return Some("Dockerfile"),
+        "Makefile" | "GNUmakefile" => return Some("Makefile"),
+        _ => {}
+    }
+
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let language = match extension.to_ascii_lowercase().as_str() {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "scala" => "Scala",
+        "dart" => "Dart",
+        "ex" | "exs" => "Elixir",
+        "hs" => "Haskell",
+        "lua" => "Lua",
+        "zig" => "Zig",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" => "PowerShell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "md" | "mdx" => "Markdown",
+        "json" => "JSON",
+        "yml" | "yaml" => "YAML",
+        "toml" => "TOML",
+        "nix" => "Nix",
+        _ => return None,
+    };
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn commit(email: &str, name: &str, time: DateTime<Utc>) -> CommitAuthorship {
+        CommitAuthorship {
+            author_name: name.to_string(),
+            author_email: email.to_string(),
+            time,
+        }
+    }
+
+    #[test]
+    fn activity_covers_every_day_and_skips_older_commits() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let at = |day| Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap();
+        let commits = [
+            commit("a@x.com", "A", at(31)),
+            commit("a@x.com", "A", at(31)),
+            commit("b@x.com", "B", at(2)),
+            commit("b@x.com", "B", at(1)),
+        ];
+
+        let activity = commit_activity(&commits, today);
+
+        assert_eq!(activity.len(), ACTIVITY_DAYS);
+        assert_eq!(
+            activity[0].date,
+            NaiveDate::from_ymd_opt(2025, 3, 2).unwrap()
+        );
+        assert_eq!(activity[0].commits, 1);
+        assert_eq!(activity[ACTIVITY_DAYS - 1].date, today);
+        assert_eq!(activity[ACTIVITY_DAYS - 1].commits, 2);
+        assert_eq!(activity.iter().map(|day| day.commits).sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn contributors_merge_emails_and_keep_latest_name() {
+        let at = |hour| Utc.with_ymd_and_hms(2025, 3, 1, hour, 0, 0).unwrap();
+        let commits = [
+            commit("Ann@x.com", "Ann Lee", at(10)),
+            commit("ann@x.com", "ann", at(9)),
+            commit("bob@x.com", "Bob", at(8)),
+        ];
+
+        let contributors = top_contributors(&commits);
+
+        assert_eq!(contributors.len(), 2);
+        assert_eq!(contributors[0].name, "Ann Lee");
+        assert_eq!(contributors[0].commits, 2);
+        assert_eq!(contributors[0].last_commit_at, at(10));
+        assert_eq!(contributors[1].name, "Bob");
+    }
+
+    #[test]
+    fn languages_are_grouped_by_extension() {
+        let file = |path: &str, size| TreeFile {
+            path: path.to_string(),
+            size,
+        };
+        let files = [
+            file("src/main.rs", 300),
+            file("src/lib.RS", 200),
+            file("web/app.tsx", 100),
+            file("Dockerfile", 10),
+            file("LICENSE", 1_000),
+        ];
+
+        let languages = language_breakdown(&files);
+        let summary: Vec<_> = languages
+            .iter()
+            .map(|stat| (stat.language.as_str(), stat.files, stat.bytes))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("Other", 1, 1_000),
+                ("Rust", 2, 500),
+                ("TypeScript", 1, 100),
+                ("Dockerfile", 1, 10),
+            ]
+        );
+    }
+}
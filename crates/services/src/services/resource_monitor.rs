@@ -0,0 +1,263 @@
+//! CPU and memory sampling for running executions.
+//!
+//! Every execution runs in its own process group, so its resource usage is the
+//! group leader plus all of its descendants. [`ResourceMonitor::sample`] walks
+//! that tree for each running execution, keeps the latest sample in memory for
+//! the process breakdown and broadcasts it to live subscribers. Aggregates are
+//! persisted by the caller as a time series.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream::BoxStream};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+const BROADCAST_CAPACITY: usize = 64;
+
+#[derive(Debug, Error)]
+pub enum ResourceMonitorError {
+    #[error("Execution {0} has no sampled processes")]
+    NotRunning(Uuid),
+    #[error("Process {pid} does not belong to execution {execution_process_id}")]
+    NotInTree {
+        execution_process_id: Uuid,
+        pid: u32,
+    },
+    #[error("Failed to kill process {0}")]
+    KillFailed(u32),
+}
+
+/// One OS process within an execution's process tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProcessStats {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    /// 100 is one fully busy core
+    pub cpu_percent: f32,
+    #[ts(type = "number")]
+    pub rss_bytes: u64,
+}
+
+/// Resource usage of an execution's process tree at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ResourceSample {
+    pub execution_process_id: Uuid,
+    #[ts(type = "Date")]
+    pub sampled_at: DateTime<Utc>,
+    pub cpu_percent: f32,
+    #[ts(type = "number")]
+    pub rss_bytes: u64,
+    pub process_count: usize,
+    /// The group leader first, then its descendants breadth-first
+    pub processes: Vec<ProcessStats>,
+}
+
+/// Collect `root` and all of its descendants from a process table.
+/// Returns `None` when `root` is no longer in the table.
+pub fn build_sample(
+    execution_process_id: Uuid,
+    root: u32,
+    table: &[ProcessStats],
+    sampled_at: DateTime<Utc>,
+) -> Option<ResourceSample> {
+    let by_pid: HashMap<u32, &ProcessStats> = table.iter().map(|p| (p.pid, p)).collect();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for process in table {
+        if let Some(parent) = process.parent_pid {
+            children.entry(parent).or_default().push(process.pid);
+        }
+    }
+
+    let mut processes = vec![(*by_pid.get(&root)?).clone()];
+    let mut seen = HashSet::from([root]);
+    let mut next = 0;
+    while let Some(pid) = processes.get(next).map(|p| p.pid) {
+        next += 1;
+        let mut kids = children.get(&pid).cloned().unwrap_or_default();
+        kids.sort_unstable();
+        for kid in kids {
+            if seen.insert(kid) {
+                processes.push(by_pid[&kid].clone());
+            }
+        }
+    }
+
+    Some(ResourceSample {
+        execution_process_id,
+        sampled_at,
+        cpu_percent: processes.iter().map(|p| p.cpu_percent).sum(),
+        rss_bytes: processes.iter().map(|p| p.rss_bytes).sum(),
+        process_count: processes.len(),
+        processes,
+    })
+}
+
+/// Samples running executions' process trees and streams the results.
+#[derive(Clone)]
+pub struct ResourceMonitor {
+    /// Kept across refreshes; CPU usage is the delta since the previous one
+    system: Arc<Mutex<System>>,
+    /// Most recent sample of each running execution
+    latest: Arc<RwLock<HashMap<Uuid, ResourceSample>>>,
+    sender: broadcast::Sender<ResourceSample>,
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceMonitor {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self {
+            system: Arc::new(Mutex::new(System::new())),
+            latest: Arc::new(RwLock::new(HashMap::new())),
+            sender,
+        }
+    }
+
+    /// Refresh the OS process table and sample each `(execution_process_id,
+    /// group leader pid)`. Executions missing from `roots` are forgotten.
+    /// Blocking: reads the whole process table.
+    pub fn sample(&self, roots: &[(Uuid, u32)]) -> Vec<ResourceSample> {
+        let table = {
+            let mut system = self.system.lock();
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+            system
+                .processes()
+                .iter()
+                .map(|(pid, process)| ProcessStats {
+                    pid: pid.as_u32(),
+                    parent_pid: process.parent().map(|p| p.as_u32()),
+                    name: process.name().to_string_lossy().into_owned(),
+                    cpu_percent: process.cpu_usage(),
+                    rss_bytes: process.memory(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let sampled_at = Utc::now();
+        let samples: Vec<ResourceSample> = roots
+            .iter()
+            .filter_map(|(id, root)| build_sample(*id, *root, &table, sampled_at))
+            .collect();
+
+        *self.latest.write() = samples
+            .iter()
+            .map(|sample| (sample.execution_process_id, sample.clone()))
+            .collect();
+        for sample in &samples {
+            // No receivers is fine
+            let _ = self.sender.send(sample.clone());
+        }
+        samples
+    }
+
+    pub fn latest(&self, execution_process_id: Uuid) -> Option<ResourceSample> {
+        self.latest.read().get(&execution_process_id).cloned()
+    }
+
+    /// Latest samples of every running execution, heaviest CPU first
+    pub fn latest_all(&self) -> Vec<ResourceSample> {
+        let mut samples: Vec<ResourceSample> = self.latest.read().values().cloned().collect();
+        samples.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+        samples
+    }
+
+    /// Live samples for one execution, starting with the latest one if any
+    pub fn stream(&self, execution_process_id: Uuid) -> BoxStream<'static, ResourceSample> {
+        let initial = self.latest(execution_process_id);
+        let live = BroadcastStream::new(self.sender.subscribe()).filter_map(move |msg| {
+            futures::future::ready(
+                msg.ok()
+                    .filter(|sample| sample.execution_process_id == execution_process_id),
+            )
+        });
+        futures::stream::iter(initial).chain(live).boxed()
+    }
+
+    /// Kill a single process from an execution's tree, e.g. a runaway child of
+    /// a dev server, without stopping the whole execution
+    pub fn kill_process(
+        &self,
+        execution_process_id: Uuid,
+        pid: u32,
+    ) -> Result<(), ResourceMonitorError> {
+        let sample = self
+            .latest(execution_process_id)
+            .ok_or(ResourceMonitorError::NotRunning(execution_process_id))?;
+        if !sample.processes.iter().any(|p| p.pid == pid) {
+            return Err(ResourceMonitorError::NotInTree {
+                execution_process_id,
+                pid,
+            });
+        }
+
+        let system = self.system.lock();
+        match system.process(Pid::from_u32(pid)) {
+            Some(process) if process.kill() => Ok(()),
+            _ => Err(ResourceMonitorError::KillFailed(pid)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(
+        pid: u32,
+        parent_pid: Option<u32>,
+        cpu_percent: f32,
+        rss_bytes: u64,
+    ) -> ProcessStats {
+        ProcessStats {
+            pid,
+            parent_pid,
+            name: format!("p{pid}"),
+            cpu_percent,
+            rss_bytes,
+        }
+    }
+
+    #[test]
+    fn sample_sums_root_and_descendants_only() {
+        let table = vec![
+            process(1, None, 1.0, 10),
+            process(100, Some(1), 5.0, 100),
+            process(101, Some(100), 50.0, 1000),
+            process(102, Some(100), 20.0, 500),
+            process(103, Some(101), 10.0, 50),
+            // Sibling of the group leader: not part of the execution
+            process(200, Some(1), 99.0, 9999),
+        ];
+        let id = Uuid::new_v4();
+
+        let sample = build_sample(id, 100, &table, Utc::now()).unwrap();
+        let pids: Vec<u32> = sample.processes.iter().map(|p| p.pid).collect();
+        assert_eq!(pids, vec![100, 101, 102, 103]);
+        assert_eq!(sample.process_count, 4);
+        assert_eq!(sample.cpu_percent, 85.0);
+        assert_eq!(sample.rss_bytes, 1650);
+
+        assert!(build_sample(id, 999, &table, Utc::now()).is_none());
+    }
+}
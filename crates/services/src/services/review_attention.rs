@@ -12,6 +12,8 @@ use executors::{
 use serde::Deserialize;
 use thiserror::Error;
 
+use super::prompt_templates;
+
 /// Default prompt template for review attention analysis.
 /// Uses `{task_description}` and `{agent_summary}` as placeholders.
 pub const DEFAULT_REVIEW_ATTENTION_PROMPT: &str = r#"Analyze whether the completed work successfully addresses the original task.
@@ -277,9 +279,13 @@ impl ReviewAttentionService {
         task_description: &str,
         agent_summary: &str,
     ) -> String {
-        template
-            .replace("{task_description}", task_description)
-            .replace("{agent_summary}", agent_summary)
+        prompt_templates::render(
+            template,
+            &[
+                ("task_description", task_description),
+                ("agent_summary", agent_summary),
+            ],
+        )
     }
 }
 
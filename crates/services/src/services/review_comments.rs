@@ -0,0 +1,116 @@
+//! Turning diff review comments into a follow-up prompt.
+//!
+//! Comments use the same `**path** (Line N)` markdown the diff view has
+//! always produced, so the blame context added to follow-ups picks up the
+//! referenced lines.
+
+use db::models::review_comment::{ReviewComment, ReviewCommentSide};
+
+/// Follow-up prompt asking the agent to address `comments`, with `message`
+/// (if any) before them. `None` when there is nothing to send.
+pub fn compile_prompt(comments: &[ReviewComment], message: Option<&str>) -> Option<String> {
+    let message = message.map(str::trim).filter(|m| !m.is_empty());
+    if comments.is_empty() {
+        return message.map(str::to_string);
+    }
+
+    let mut prompt = String::new();
+    if let Some(message) = message {
+        prompt.push_str(message);
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str(&format!("## Review Comments ({})\n", comments.len()));
+    for comment in comments {
+        prompt.push('\n');
+        prompt.push_str(&format_comment(comment));
+    }
+    Some(prompt)
+}
+
+fn format_comment(comment: &ReviewComment) -> String {
+    let mut location = format!("**{}** (Line {})", comment.file_path, comment.line_start);
+    if comment.line_end > comment.line_start {
+        location.push_str(&format!(" to line {}", comment.line_end));
+    }
+    if comment.side == ReviewCommentSide::Old {
+        location.push_str(", in the original version");
+    }
+    let body = comment
+        .body
+        .trim()
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{location}\n\n{body}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn comment(
+        path: &str,
+        side: ReviewCommentSide,
+        lines: (i64, i64),
+        body: &str,
+    ) -> ReviewComment {
+        ReviewComment {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::nil(),
+            file_path: path.to_string(),
+            side,
+            line_start: lines.0,
+            line_end: lines.1,
+            body: body.to_string(),
+            resolved: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn compiles_comments_after_the_message() {
+        let prompt = compile_prompt(
+            &[
+                comment(
+                    "app/src/main.rs",
+                    ReviewCommentSide::New,
+                    (12, 12),
+                    "Handle the error\ninstead of unwrapping",
+                ),
+                comment(
+                    "app/src/lib.rs",
+                    ReviewCommentSide::Old,
+                    (3, 7),
+                    " Why was this removed? ",
+                ),
+            ],
+            Some("Please address these"),
+        )
+        .unwrap();
+        assert_eq!(
+            prompt,
+            "Please address these\n\n\
+             ## Review Comments (2)\n\n\
+             **app/src/main.rs** (Line 12)\n\n\
+             > Handle the error\n\
+             > instead of unwrapping\n\n\
+             **app/src/lib.rs** (Line 3) to line 7, in the original version\n\n\
+             > Why was this removed?\n"
+        );
+    }
+
+    #[test]
+    fn nothing_to_send() {
+        assert_eq!(compile_prompt(&[], None), None);
+        assert_eq!(compile_prompt(&[], Some("  ")), None);
+        assert_eq!(
+            compile_prompt(&[], Some("Go on")),
+            Some("Go on".to_string())
+        );
+    }
+}
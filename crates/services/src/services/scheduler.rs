@@ -0,0 +1,220 @@
+//! Recurring tasks.
+//!
+//! Every [`CHECK_INTERVAL`] the scheduler looks for enabled [`TaskSchedule`]s
+//! whose next run is due. Each one gets a new task cloned from the schedule's
+//! template, a workspace on the schedule's repos, and an execution started
+//! through [`ContainerService::start_workspace`], which queues it when the
+//! concurrency limit is reached. Runs missed while the app was closed are not
+//! caught up: the next run is always computed from the current time.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use croner::Cron;
+use db::models::{
+    project::Project,
+    task::{CreateTask, Task},
+    task_schedule::TaskSchedule,
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use thiserror::Error;
+use tokio::time::interval;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::services::container::{ContainerError, ContainerService, StartWorkspaceResult};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("Invalid cron expression '{0}': {1}")]
+    InvalidCron(String, String),
+    #[error("Cron expression '{0}' never matches")]
+    NoNextRun(String),
+    #[error("Schedule has no repositories")]
+    NoRepos,
+    #[error("Project not found")]
+    ProjectNotFound,
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+}
+
+fn parse_cron(expr: &str) -> Result<Cron, SchedulerError> {
+    Cron::new(expr.trim())
+        .parse()
+        .map_err(|e| SchedulerError::InvalidCron(expr.to_string(), e.to_string()))
+}
+
+/// Check that `expr` is a valid five-field cron expression
+pub fn validate_cron(expr: &str) -> Result<(), SchedulerError> {
+    parse_cron(expr).map(|_| ())
+}
+
+/// The first time after `after` matching `expr`, evaluated in the server's
+/// local time zone
+pub fn next_run(expr: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, SchedulerError> {
+    parse_cron(expr)?
+        .find_next_occurrence(&after.with_timezone(&Local), false)
+        .map(|next| next.with_timezone(&Utc))
+        .map_err(|_| SchedulerError::NoNextRun(expr.to_string()))
+}
+
+/// Create the schedule's task and workspace and start (or queue) it
+pub async fn run_schedule(
+    container: &impl ContainerService,
+    schedule: &TaskSchedule,
+) -> Result<Task, SchedulerError> {
+    if schedule.repos.is_empty() {
+        return Err(SchedulerError::NoRepos);
+    }
+    let pool = &container.db().pool;
+    let project = Project::find_by_id(pool, schedule.project_id)
+        .await?
+        .ok_or(SchedulerError::ProjectNotFound)?;
+
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(
+            project.id,
+            schedule.task_title.clone(),
+            schedule.task_description.clone(),
+        ),
+        Uuid::new_v4(),
+    )
+    .await?;
+
+    let workspace_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = schedule.repos.iter().map(|repo| repo.repo_id).collect();
+    let branch = container
+        .unique_git_branch_for_workspace(&workspace_id, &task.title, &repo_ids)
+        .await?;
+    let agent_working_dir = project
+        .default_agent_working_dir
+        .filter(|dir| !dir.is_empty());
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir,
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+
+    let repos: Vec<CreateWorkspaceRepo> = schedule
+        .repos
+        .iter()
+        .map(|repo| CreateWorkspaceRepo {
+            repo_id: repo.repo_id,
+            target_branch: repo.target_branch.clone(),
+        })
+        .collect();
+    WorkspaceRepo::create_many(pool, workspace.id, &repos).await?;
+
+    match container
+        .start_workspace(&workspace, schedule.executor_profile_id.0.clone())
+        .await?
+    {
+        StartWorkspaceResult::Started(_) => {
+            info!("Scheduled task '{}' started", task.title)
+        }
+        StartWorkspaceResult::Queued(_) => {
+            info!("Scheduled task '{}' queued", task.title)
+        }
+    }
+    Ok(task)
+}
+
+/// Runs due schedules in the background
+pub struct SchedulerService<C> {
+    container: C,
+}
+
+impl<C> SchedulerService<C>
+where
+    C: ContainerService + Clone + Send + Sync + 'static,
+{
+    pub fn spawn(container: C) -> tokio::task::JoinHandle<()> {
+        let service = Self { container };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!("Starting task scheduler");
+        let mut interval = interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_due().await {
+                error!("Failed to check task schedules: {}", e);
+            }
+        }
+    }
+
+    async fn run_due(&self) -> Result<(), sqlx::Error> {
+        let pool = &self.container.db().pool;
+        let now = Utc::now();
+        for schedule in TaskSchedule::find_due(pool, now).await? {
+            let (task_id, run_error) = match run_schedule(&self.container, &schedule).await {
+                Ok(task) => (Some(task.id), None),
+                Err(e) => {
+                    error!("Scheduled run of {} failed: {}", schedule.id, e);
+                    (None, Some(e.to_string()))
+                }
+            };
+            // A schedule whose cron stopped parsing is left without a next run
+            let next_run_at = next_run(&schedule.cron, now).ok();
+            if let Err(e) = TaskSchedule::record_run(
+                pool,
+                schedule.id,
+                task_id,
+                run_error.as_deref(),
+                next_run_at,
+            )
+            .await
+            {
+                error!("Failed to record run of schedule {}: {}", schedule.id, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn computes_next_run_after_now() {
+        // Quarter hours line up in every time zone, so this holds in UTC too
+        let after = Utc.with_ymd_and_hms(2026, 3, 2, 10, 7, 30).unwrap();
+        let next = next_run("*/15 * * * *", after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 2, 10, 15, 0).unwrap());
+
+        // Exactly on a match, the next run is the following one
+        let on_match = Utc.with_ymd_and_hms(2026, 3, 2, 10, 15, 0).unwrap();
+        assert_eq!(
+            next_run("*/15 * * * *", on_match).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 2, 10, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(validate_cron("0 9 * * 1-5").is_ok());
+        for expr in ["", "every day", "61 * * * *", "* * * *"] {
+            assert!(
+                matches!(validate_cron(expr), Err(SchedulerError::InvalidCron(..))),
+                "{expr}"
+            );
+        }
+    }
+}
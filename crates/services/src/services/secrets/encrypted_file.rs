@@ -0,0 +1,116 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use utils::assets::asset_dir;
+
+use super::{SecretBackend, SecretStore, SecretStoreError, encryption};
+
+/// Serialises read-modify-write cycles on the secrets file
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// All secrets in one JSON map, encrypted with the machine-derived key also
+/// used for the stored GitHub token
+pub struct EncryptedFileStore {
+    path: PathBuf,
+}
+
+impl EncryptedFileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn in_asset_dir() -> Self {
+        Self::new(asset_dir().join("secrets.enc"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_all(&self) -> Result<BTreeMap<String, String>, SecretStoreError> {
+        let encrypted = match std::fs::read_to_string(&self.path) {
+            Ok(encrypted) => encrypted,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let json = encryption::decrypt_token(encrypted.trim())?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    fn write_all(&self, secrets: &BTreeMap<String, String>) -> Result<(), SecretStoreError> {
+        let encrypted = encryption::encrypt_token(&serde_json::to_string(secrets)?)?;
+        let tmp_path = self.path.with_extension("enc.tmp");
+        std::fs::write(&tmp_path, encrypted)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::EncryptedFile
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, SecretStoreError> {
+        let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read_all()?.remove(name))
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretStoreError> {
+        let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut secrets = self.read_all()?;
+        secrets.insert(name.to_string(), value.to_string());
+        self.write_all(&secrets)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, SecretStoreError> {
+        let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut secrets = self.read_all()?;
+        if secrets.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.write_all(&secrets)?;
+        Ok(true)
+    }
+
+    fn list(&self) -> Result<Vec<String>, SecretStoreError> {
+        let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read_all()?.into_keys().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_get_delete_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EncryptedFileStore::new(dir.path().join("secrets.enc"));
+
+        assert_eq!(store.get("langfuse_secret_key").unwrap(), None);
+        store.set("langfuse_secret_key", "sk-lf-123").unwrap();
+        store.set("other", "value").unwrap();
+
+        let raw = std::fs::read_to_string(store.path()).unwrap();
+        assert!(!raw.contains("sk-lf-123"));
+
+        assert_eq!(
+            store.get("langfuse_secret_key").unwrap().as_deref(),
+            Some("sk-lf-123")
+        );
+        assert_eq!(store.list().unwrap(), vec!["langfuse_secret_key", "other"]);
+
+        assert!(store.delete("langfuse_secret_key").unwrap());
+        assert!(!store.delete("langfuse_secret_key").unwrap());
+        assert_eq!(store.list().unwrap(), vec!["other"]);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::Mutex,
+};
+
+use utils::{assets::asset_dir, shell::resolve_executable_path_blocking};
+
+use super::{SecretBackend, SecretStore, SecretStoreError};
+
+/// Service name the entries are filed under in the keychain
+const KEYCHAIN_SERVICE: &str = "vibe-kanban";
+
+/// `security` exit code for a missing item
+const MAC_ITEM_NOT_FOUND: i32 = 44;
+
+/// Serialises updates to the index of stored names
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+enum KeychainTool {
+    /// macOS login keychain through `security`
+    MacSecurity(PathBuf),
+    /// Secret Service (GNOME Keyring, KWallet) through libsecret's `secret-tool`
+    SecretTool(PathBuf),
+}
+
+/// Secrets kept in the OS keychain. Keychains cannot be listed by service
+/// without prompting, so the stored names are also kept in a plain index file.
+pub struct KeychainStore {
+    tool: KeychainTool,
+    index_path: PathBuf,
+}
+
+impl KeychainStore {
+    /// The keychain of this platform, if its command line tool is installed.
+    /// On Linux a D-Bus session is also required.
+    pub fn detect() -> Option<Self> {
+        let tool = if cfg!(target_os = "macos") {
+            resolve_executable_path_blocking("security").map(KeychainTool::MacSecurity)
+        } else if cfg!(target_os = "linux")
+            && std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+        {
+            resolve_executable_path_blocking("secret-tool").map(KeychainTool::SecretTool)
+        } else {
+            None
+        }?;
+
+        Some(Self {
+            tool,
+            index_path: asset_dir().join("keychain-secrets.json"),
+        })
+    }
+
+    fn run(program: &Path, args: &[&str], stdin: Option<&str>) -> Result<Output, SecretStoreError> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(input) = stdin
+            && let Some(mut pipe) = child.stdin.take()
+        {
+            pipe.write_all(input.as_bytes())?;
+        }
+        Ok(child.wait_with_output()?)
+    }
+
+    fn failure(output: &Output) -> SecretStoreError {
+        SecretStoreError::Keychain(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+
+    fn value_from(output: &Output) -> String {
+        let value = String::from_utf8_lossy(&output.stdout);
+        value.strip_suffix('\n').unwrap_or(&value).to_string()
+    }
+
+    fn read_index(&self) -> Vec<String> {
+        std::fs::read_to_string(&self.index_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn update_index(&self, name: &str, present: bool) -> Result<(), SecretStoreError> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut names = self.read_index();
+        names.retain(|n| n != name);
+        if present {
+            names.push(name.to_string());
+        }
+        names.sort();
+        std::fs::write(&self.index_path, serde_json::to_string_pretty(&names)?)?;
+        Ok(())
+    }
+}
+
+impl SecretStore for KeychainStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::Keychain
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, SecretStoreError> {
+        match &self.tool {
+            KeychainTool::MacSecurity(security) => {
+                let output = Self::run(
+                    security,
+                    &[
+                        "find-generic-password",
+                        "-s",
+                        KEYCHAIN_SERVICE,
+                        "-a",
+                        name,
+                        "-w",
+                    ],
+                    None,
+                )?;
+                match output.status.code() {
+                    Some(0) => Ok(Some(Self::value_from(&output))),
+                    Some(MAC_ITEM_NOT_FOUND) => Ok(None),
+                    _ => Err(Self::failure(&output)),
+                }
+            }
+            KeychainTool::SecretTool(secret_tool) => {
+                let output = Self::run(
+                    secret_tool,
+                    &["lookup", "service", KEYCHAIN_SERVICE, "account", name],
+                    None,
+                )?;
+                if output.status.success() {
+                    Ok(Some(Self::value_from(&output)))
+                } else if output.stdout.is_empty() && output.stderr.is_empty() {
+                    // lookup exits 1 without output when nothing matches
+                    Ok(None)
+                } else {
+                    Err(Self::failure(&output))
+                }
+            }
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretStoreError> {
+        let label = format!("Vibe Kanban: {name}");
+        let output = match &self.tool {
+            // -w given last without a value makes `security` prompt for the
+            // password and its confirmation, which keeps it out of argv. The
+            // prompts read one line each from stdin when it is not a terminal.
+            KeychainTool::MacSecurity(security) => {
+                if value.contains(['\n', '\r']) {
+                    return Err(SecretStoreError::Keychain(
+                        "Secrets stored in the macOS keychain cannot span lines".to_string(),
+                    ));
+                }
+                Self::run(
+                    security,
+                    &[
+                        "add-generic-password",
+                        "-U",
+                        "-s",
+                        KEYCHAIN_SERVICE,
+                        "-a",
+                        name,
+                        "-l",
+                        &label,
+                        "-w",
+                    ],
+                    Some(&format!("{value}\n{value}\n")),
+                )?
+            }
+            KeychainTool::SecretTool(secret_tool) => Self::run(
+                secret_tool,
+                &[
+                    "store",
+                    "--label",
+                    &label,
+                    "service",
+                    KEYCHAIN_SERVICE,
+                    "account",
+                    name,
+                ],
+                Some(value),
+            )?,
+        };
+        if !output.status.success() {
+            return Err(Self::failure(&output));
+        }
+        self.update_index(name, true)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, SecretStoreError> {
+        let deleted = match &self.tool {
+            KeychainTool::MacSecurity(security) => {
+                let output = Self::run(
+                    security,
+                    &[
+                        "delete-generic-password",
+                        "-s",
+                        KEYCHAIN_SERVICE,
+                        "-a",
+                        name,
+                    ],
+                    None,
+                )?;
+                match output.status.code() {
+                    Some(0) => true,
+                    Some(MAC_ITEM_NOT_FOUND) => false,
+                    _ => return Err(Self::failure(&output)),
+                }
+            }
+            KeychainTool::SecretTool(secret_tool) => {
+                // clear succeeds whether or not anything matched
+                let existed = self.get(name)?.is_some();
+                let output = Self::run(
+                    secret_tool,
+                    &["clear", "service", KEYCHAIN_SERVICE, "account", name],
+                    None,
+                )?;
+                if !output.status.success() {
+                    return Err(Self::failure(&output));
+                }
+                existed
+            }
+        };
+        self.update_index(name, false)?;
+        Ok(deleted)
+    }
+
+    fn list(&self) -> Result<Vec<String>, SecretStoreError> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read_index())
+    }
+}
@@ -0,0 +1,230 @@
+//! Storage for credentials that should not sit in the plain JSON config.
+//!
+//! Values live in the OS keychain when one is reachable (`security` on macOS,
+//! `secret-tool` on Linux) and otherwise in an encrypted file in the asset
+//! directory. The config only keeps a [`SecretRef`] naming the entry, and the
+//! API lists secret names but never returns their values.
+
+mod encrypted_file;
+pub mod encryption;
+mod keychain;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+pub use self::{encrypted_file::EncryptedFileStore, keychain::KeychainStore};
+
+/// Store entry holding the Langfuse secret key
+pub const LANGFUSE_SECRET_KEY: &str = "langfuse_secret_key";
+
+/// Store entry holding the GitLab access token
+pub const GITLAB_TOKEN: &str = "gitlab_token";
+
+/// Store entry holding the secret key of the S3 backup bucket
+pub const S3_SECRET_ACCESS_KEY: &str = "s3_secret_access_key";
+
+/// Store entry holding the Slack incoming webhook URL
+pub const SLACK_WEBHOOK_URL: &str = "slack_webhook_url";
+
+/// Set to `file` to skip the OS keychain, e.g. on headless machines whose
+/// keyring daemon prompts for unlocking
+const SECRET_BACKEND_ENV: &str = "VK_SECRET_BACKEND";
+
+#[derive(Debug, Error)]
+pub enum SecretStoreError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Encryption(#[from] encryption::EncryptionError),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+    #[error("Keychain command failed: {0}")]
+    Keychain(String),
+    #[error("Invalid secret name '{0}'")]
+    InvalidName(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretBackend {
+    Keychain,
+    EncryptedFile,
+}
+
+/// A place secret values can be kept. Implementations are blocking.
+pub trait SecretStore: Send + Sync {
+    fn backend(&self) -> SecretBackend;
+    fn get(&self, name: &str) -> Result<Option<String>, SecretStoreError>;
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretStoreError>;
+    /// False when there was no such secret
+    fn delete(&self, name: &str) -> Result<bool, SecretStoreError>;
+    /// Names of the stored secrets, sorted
+    fn list(&self) -> Result<Vec<String>, SecretStoreError>;
+}
+
+/// Name of a secret store entry, kept in the config in place of the value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(transparent)]
+pub struct SecretRef(String);
+
+impl SecretRef {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Move a plaintext value from an older config into `store`. The value is
+/// dropped with a warning if it cannot be stored, so it never lingers in the
+/// config file.
+pub fn migrate_plaintext(
+    store: &dyn SecretStore,
+    name: &str,
+    value: Option<String>,
+) -> Option<SecretRef> {
+    let value = value.filter(|v| !v.is_empty())?;
+    match store.set(name, &value) {
+        Ok(()) => Some(SecretRef::new(name)),
+        Err(e) => {
+            tracing::warn!("Could not move '{}' out of the config: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Keeps secrets in memory, for tests
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryStore(std::sync::Mutex<std::collections::BTreeMap<String, String>>);
+
+#[cfg(test)]
+impl SecretStore for MemoryStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::EncryptedFile
+    }
+
+    fn get(&self, name: &str) -> Result<Option<String>, SecretStoreError> {
+        Ok(self.0.lock().unwrap().get(name).cloned())
+    }
+
+    fn set(&self, name: &str, value: &str) -> Result<(), SecretStoreError> {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, SecretStoreError> {
+        Ok(self.0.lock().unwrap().remove(name).is_some())
+    }
+
+    fn list(&self) -> Result<Vec<String>, SecretStoreError> {
+        Ok(self.0.lock().unwrap().keys().cloned().collect())
+    }
+}
+
+#[derive(Clone)]
+pub struct SecretService {
+    store: Arc<dyn SecretStore>,
+}
+
+impl Default for SecretService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretService {
+    /// Use the OS keychain when available, the encrypted file otherwise
+    pub fn new() -> Self {
+        let force_file = std::env::var(SECRET_BACKEND_ENV)
+            .map(|v| v.eq_ignore_ascii_case("file"))
+            .unwrap_or(false);
+        let store: Arc<dyn SecretStore> = match KeychainStore::detect() {
+            Some(keychain) if !force_file => Arc::new(keychain),
+            _ => Arc::new(EncryptedFileStore::in_asset_dir()),
+        };
+        Self { store }
+    }
+
+    pub fn with_store(store: Arc<dyn SecretStore>) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &dyn SecretStore {
+        self.store.as_ref()
+    }
+
+    pub fn backend(&self) -> SecretBackend {
+        self.store.backend()
+    }
+
+    /// Letters, digits, `_`, `-` and `.`, so names are safe as keychain
+    /// accounts and map keys
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 128
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    }
+
+    /// Value of a config reference; None (with a warning) when it cannot be read
+    pub async fn resolve(&self, secret: &SecretRef) -> Option<String> {
+        match self.get(secret.name()).await {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("Failed to read secret '{}': {}", secret.name(), e);
+                None
+            }
+        }
+    }
+
+    /// Value of an optional config reference
+    pub async fn resolve_opt(&self, secret: Option<&SecretRef>) -> Option<String> {
+        match secret {
+            Some(secret) => self.resolve(secret).await,
+            None => None,
+        }
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Option<String>, SecretStoreError> {
+        let store = self.store.clone();
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || store.get(&name)).await?
+    }
+
+    pub async fn set(&self, name: &str, value: &str) -> Result<SecretRef, SecretStoreError> {
+        if !Self::is_valid_name(name) {
+            return Err(SecretStoreError::InvalidName(name.to_string()));
+        }
+        let store = self.store.clone();
+        let (name, value) = (name.to_string(), value.to_string());
+        tokio::task::spawn_blocking(move || {
+            store.set(&name, &value)?;
+            Ok(SecretRef::new(name))
+        })
+        .await?
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<bool, SecretStoreError> {
+        let store = self.store.clone();
+        let name = name.to_string();
+        tokio::task::spawn_blocking(move || store.delete(&name)).await?
+    }
+
+    pub async fn list(&self) -> Result<Vec<String>, SecretStoreError> {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || store.list()).await?
+    }
+}
@@ -8,6 +8,7 @@ use db::{
 use remote::routes::tasks::{
     AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTaskResponse, UpdateSharedTaskRequest,
 };
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{ShareError, status};
@@ -19,7 +20,7 @@ pub struct SharePublisher {
     client: RemoteClient,
 }
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ts_rs::TS, ToSchema)]
 pub struct SharedTaskDetails {
     pub id: Uuid,
     pub project_id: Uuid,
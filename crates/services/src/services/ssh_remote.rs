@@ -0,0 +1,557 @@
+//! Running executions on another machine over SSH.
+//!
+//! With [`SshRemoteConfig::enabled`] set, each workspace is mirrored into
+//! `base_dir` on the remote host and coding agents and scripts run there,
+//! so heavy agents can use a bigger machine while the UI stays local. The
+//! local worktree stays the source of truth: it is copied up with rsync
+//! before every execution and the remote copy is copied back when the
+//! execution exits, before its changes are committed. Git metadata and
+//! ignored files are never transferred, so build output stays on the host
+//! that produced it, and agents can't run git against the remote copy.
+//! Copying back only deletes local files git tracks that the execution
+//! deleted remotely, so anything created locally while it ran survives.
+//!
+//! Environment values never appear on a command line, where `ps` on either
+//! host would show them. They are written through ssh's stdin into a file
+//! only the remote user can read, which the execution sources and removes.
+//!
+//! Output streams back over the SSH connection. Paths handed to an executor
+//! on its command line still point into the local worktree, and the diff view
+//! only updates once the execution has exited and its copy has come back.
+//! Without a terminal, closing the connection doesn't send the remote process
+//! a hangup, so each execution records its process group on the remote host
+//! and stopping it kills that group.
+//!
+//! This runs inside `LocalContainerService` rather than as a
+//! `ContainerService` of its own: worktrees, git and the UI stay local and
+//! only the spawned command moves, which is the part a bigger machine helps
+//! with.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::process::Command;
+use ts_rs::TS;
+use utils::shell::resolve_executable_path;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum SshRemoteError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Remote execution needs {0}")]
+    Incomplete(&'static str),
+    #[error("{0} is not installed")]
+    MissingTool(&'static str),
+    #[error("{command} failed: {output}")]
+    Command { command: String, output: String },
+}
+
+/// Host executions run on when remote execution is enabled
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct SshRemoteConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Host name or `~/.ssh/config` alias
+    #[serde(default)]
+    pub host: String,
+    /// None uses the user from `~/.ssh/config` or the local user
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Private key to authenticate with; None uses the SSH agent
+    #[serde(default)]
+    pub identity_file: Option<String>,
+    /// Directory on the remote host workspaces are copied into
+    #[serde(default)]
+    pub base_dir: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SshRemote {
+    config: SshRemoteConfig,
+}
+
+/// Quote `value` for a POSIX shell
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+impl SshRemote {
+    /// The remote to run executions on, or None when remote execution is off
+    pub fn from_config(config: &SshRemoteConfig) -> Result<Option<Self>, SshRemoteError> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        if config.host.trim().is_empty() {
+            return Err(SshRemoteError::Incomplete("a host"));
+        }
+        if !config.base_dir.trim_end_matches('/').starts_with('/') {
+            return Err(SshRemoteError::Incomplete("an absolute base directory"));
+        }
+        Ok(Some(Self {
+            config: config.clone(),
+        }))
+    }
+
+    /// Fail early when ssh or rsync is missing locally
+    pub async fn check_tools() -> Result<(), SshRemoteError> {
+        for tool in ["ssh", "rsync"] {
+            if resolve_executable_path(tool).await.is_none() {
+                return Err(SshRemoteError::MissingTool(tool));
+            }
+        }
+        Ok(())
+    }
+
+    fn destination(&self) -> String {
+        match &self.config.user {
+            Some(user) if !user.is_empty() => format!("{user}@{}", self.config.host),
+            _ => self.config.host.clone(),
+        }
+    }
+
+    /// Options shared by every ssh invocation. Batch mode makes a missing key
+    /// fail the execution instead of waiting on a password prompt.
+    fn ssh_command(&self) -> Vec<String> {
+        let mut command = vec![
+            "ssh".to_string(),
+            "-T".to_string(),
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+        ];
+        if let Some(port) = self.config.port {
+            command.extend(["-p".to_string(), port.to_string()]);
+        }
+        if let Some(identity_file) = &self.config.identity_file {
+            command.extend(["-i".to_string(), identity_file.clone()]);
+        }
+        command
+    }
+
+    /// Where the workspace at `local_dir` is mirrored on the remote host
+    pub fn remote_dir(&self, local_dir: &Path) -> String {
+        let name = local_dir.file_name().unwrap_or_default().to_string_lossy();
+        format!("{}/{}", self.config.base_dir.trim_end_matches('/'), name)
+    }
+
+    /// Copy the workspace up, replacing whatever the last execution left
+    pub async fn sync_to_remote(
+        &self,
+        local_dir: &Path,
+        remote_dir: &str,
+    ) -> Result<(), SshRemoteError> {
+        let source = format!("{}/", local_dir.display());
+        let target = format!("{}:{}/", self.destination(), remote_dir);
+        let rsync_path = format!("mkdir -p {} && rsync", quote(remote_dir));
+        self.rsync(&["--delete", "--rsync-path", &rsync_path, &source, &target])
+            .await
+    }
+
+    /// Copy the remote workspace back over the local worktree. Files git
+    /// tracks locally that are missing remotely were deleted by the execution
+    /// and are deleted locally too; other local files missing remotely, such
+    /// as ones created while the execution ran, are kept.
+    pub async fn sync_from_remote(
+        &self,
+        remote_dir: &str,
+        local_dir: &Path,
+    ) -> Result<(), SshRemoteError> {
+        let source = format!("{}:{}/", self.destination(), remote_dir);
+        let target = format!("{}/", local_dir.display());
+        let changes = self
+            .rsync(&[
+                "--dry-run",
+                "--delete",
+                "--itemize-changes",
+                &source,
+                &target,
+            ])
+            .await?;
+        self.rsync(&[&source, &target]).await?;
+
+        for path in tracked_files(local_dir, parse_deletions(&changes)).await? {
+            match tokio::fs::remove_file(local_dir.join(&path)).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Run rsync over this remote's ssh options, returning its output
+    async fn rsync(&self, args: &[&str]) -> Result<String, SshRemoteError> {
+        let transport = self
+            .ssh_command()
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let output = Command::new("rsync")
+            .args(["-az", "--exclude=.git", "--filter=:- .gitignore"])
+            .args(["-e", &transport])
+            .args(args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(SshRemoteError::Command {
+                command: "rsync".to_string(),
+                output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            })
+        }
+    }
+
+    /// Delete a workspace's remote copy
+    pub async fn remove_dir(&self, remote_dir: &str) -> Result<(), SshRemoteError> {
+        let base_dir = format!("{}/", self.config.base_dir.trim_end_matches('/'));
+        let Some(name) = remote_dir.strip_prefix(&base_dir) else {
+            return Ok(());
+        };
+        if name.is_empty() || name.contains('/') || name == ".." {
+            return Ok(());
+        }
+        self.run(&format!(
+            "rm -rf {dir} {dir}.*.env {dir}.*.pid",
+            dir = quote(remote_dir)
+        ))
+        .await
+    }
+
+    /// File the execution `exec_id` records its remote process group in
+    fn pid_file(remote_dir: &str, exec_id: Uuid) -> String {
+        format!("{}.{}.pid", remote_dir.trim_end_matches('/'), exec_id)
+    }
+
+    /// Kill the remote process group of execution `exec_id`, which its closed
+    /// connection leaves running. sshd starts each command in a session of
+    /// its own, so the recorded pid is also the group's id.
+    pub async fn kill_execution(
+        &self,
+        remote_dir: &str,
+        exec_id: Uuid,
+    ) -> Result<(), SshRemoteError> {
+        let pid_file = quote(&Self::pid_file(remote_dir, exec_id));
+        self.run(&format!(
+            "if pid=$(cat {pid_file} 2>/dev/null); then \
+             kill -TERM -- -\"$pid\" 2>/dev/null; sleep 2; kill -KILL -- -\"$pid\" 2>/dev/null; \
+             fi; rm -f {pid_file}"
+        ))
+        .await
+    }
+
+    /// Run a shell command on the remote host
+    async fn run(&self, command: &str) -> Result<(), SshRemoteError> {
+        let ssh = self.ssh_command();
+        let output = Command::new(&ssh[0])
+            .args(&ssh[1..])
+            .arg(self.destination())
+            .arg(command)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(SshRemoteError::Command {
+                command: "ssh".to_string(),
+                output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            })
+        }
+    }
+
+    /// Command prefix running an execution in `remote_dir`, or `working_dir`
+    /// within it, on the remote host.
+    ///
+    /// ssh hands the remote shell a single command line, so a local `sh`
+    /// quotes the wrapped program and its arguments before connecting. The
+    /// variables in `forward_env` are passed along by name; their values are
+    /// read from the local process environment when it starts and sent over
+    /// a first connection's stdin. `inner` is a prefix to run on the remote
+    /// host, e.g. a Nix dev shell. The remote shell records its pid for
+    /// [`Self::kill_execution`].
+    pub fn command_prefix<'a>(
+        &self,
+        remote_dir: &str,
+        exec_id: Uuid,
+        working_dir: Option<&str>,
+        forward_env: impl IntoIterator<Item = &'a String>,
+        inner: &[String],
+    ) -> Vec<String> {
+        let ssh = self
+            .ssh_command()
+            .iter()
+            .chain([&self.destination()])
+            .map(|arg| quote(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut script =
+            String::from(r#"q() { printf "'%s'" "$(printf '%s' "$1" | sed "s/'/'\\\\''/g")"; }"#);
+        script.push('\n');
+
+        let keys: Vec<_> = forward_env
+            .into_iter()
+            .filter(|key| is_env_name(key))
+            .collect();
+        let cwd = match working_dir.filter(|dir| !dir.is_empty()) {
+            Some(dir) => format!("{remote_dir}/{dir}"),
+            None => remote_dir.to_string(),
+        };
+        let mut remote_cmd = format!(
+            "cd {} && echo $$ > {}",
+            quote(&cwd),
+            quote(&Self::pid_file(remote_dir, exec_id))
+        );
+        if !keys.is_empty() {
+            // Named per execution, as parallel setup scripts share a workspace
+            let env_file = quote(&format!(
+                "{}.{}.env",
+                remote_dir.trim_end_matches('/'),
+                Uuid::new_v4()
+            ));
+            script.push('{');
+            for key in keys {
+                script.push_str(&format!(
+                    " printf 'export %s\\n' \"$(q \"{key}=${key}\")\";"
+                ));
+            }
+            script.push_str(&format!(
+                " }} | {ssh} {} || exit 1\n",
+                quote(&format!("umask 077 && cat > {env_file}"))
+            ));
+            remote_cmd.push_str(&format!(" && . {env_file} && rm -f {env_file}"));
+        }
+        remote_cmd.push_str(" && exec");
+        script.push_str(&format!("cmd={}\n", quote(&remote_cmd)));
+        if !inner.is_empty() {
+            let inner = inner.iter().map(|arg| quote(arg)).collect::<Vec<_>>();
+            script.push_str(&format!(
+                "cmd=\"$cmd\"{}\n",
+                quote(&format!(" {}", inner.join(" ")))
+            ));
+        }
+        script.push_str("for arg; do cmd=\"$cmd $(q \"$arg\")\"; done\n");
+        script.push_str(&format!("exec {ssh} \"$cmd\""));
+
+        vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            script,
+            "vk-ssh".to_string(),
+        ]
+    }
+}
+
+/// Paths rsync's itemized output reports it would delete, relative to the
+/// target. Directories are left out; only files are deleted.
+fn parse_deletions(itemized: &str) -> Vec<String> {
+    itemized
+        .lines()
+        .filter_map(|line| line.strip_prefix("*deleting"))
+        .map(str::trim_start)
+        .filter(|path| !path.is_empty() && !path.ends_with('/'))
+        .filter(|path| !path.split('/').any(|part| part == ".."))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The `paths` under `root` git tracks, in whichever repo under `root` holds
+/// each of them
+async fn tracked_files(root: &Path, paths: Vec<String>) -> Result<Vec<String>, SshRemoteError> {
+    let mut by_repo: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    for path in paths {
+        let Some(repo) = root
+            .join(&path)
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(root))
+            .find(|dir| dir.join(".git").exists())
+            .map(Path::to_path_buf)
+        else {
+            continue;
+        };
+        by_repo.entry(repo).or_default().push(path);
+    }
+
+    let mut tracked = Vec::new();
+    for (repo, paths) in by_repo {
+        let prefix = repo
+            .strip_prefix(root)
+            .unwrap_or(Path::new(""))
+            .to_string_lossy()
+            .into_owned();
+        let relative: Vec<&str> = paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&prefix)
+                    .unwrap_or(path)
+                    .trim_start_matches('/')
+            })
+            .collect();
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["--literal-pathspecs", "ls-files", "-z", "--"])
+            .args(&relative)
+            .stdin(Stdio::null())
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(SshRemoteError::Command {
+                command: "git ls-files".to_string(),
+                output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        tracked.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .split('\0')
+                .filter(|path| !path.is_empty())
+                .map(|path| {
+                    if prefix.is_empty() {
+                        path.to_string()
+                    } else {
+                        format!("{prefix}/{path}")
+                    }
+                }),
+        );
+    }
+    Ok(tracked)
+}
+
+/// Variables safe to interpolate into the shim by name
+fn is_env_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(key, "PATH" | "HOME" | "USER" | "LOGNAME" | "SHELL" | "PWD")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_host_and_absolute_base_dir() {
+        let mut config = SshRemoteConfig::default();
+        assert!(SshRemote::from_config(&config).unwrap().is_none());
+
+        config.enabled = true;
+        config.base_dir = "/srv/vk".to_string();
+        assert!(SshRemote::from_config(&config).is_err());
+
+        config.host = "builder".to_string();
+        config.base_dir = "vk".to_string();
+        assert!(SshRemote::from_config(&config).is_err());
+
+        config.base_dir = "/srv/vk/".to_string();
+        let remote = SshRemote::from_config(&config).unwrap().unwrap();
+        assert_eq!(
+            remote.remote_dir(Path::new("/tmp/worktrees/ab12-fix-login")),
+            "/srv/vk/ab12-fix-login"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn env_values_go_over_stdin() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        // Records its arguments and stdin instead of connecting
+        let dir = tempfile::tempdir().unwrap();
+        let fake_ssh = dir.path().join("ssh");
+        fs::write(
+            &fake_ssh,
+            "#!/bin/sh\necho \"$*\" >> \"$LOG_DIR/args\"\ncat >> \"$LOG_DIR/stdin\"\n",
+        )
+        .unwrap();
+        fs::set_permissions(&fake_ssh, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let remote = SshRemote::from_config(&SshRemoteConfig {
+            enabled: true,
+            host: "builder".to_string(),
+            base_dir: "/srv/vk".to_string(),
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap();
+        let keys = ["VK_TOKEN".to_string()];
+        let prefix = remote.command_prefix("/srv/vk/ws", Uuid::nil(), Some("app"), &keys, &[]);
+        let path = format!(
+            "{}:{}",
+            dir.path().display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let status = std::process::Command::new(&prefix[0])
+            .args(&prefix[1..])
+            .args(["echo", "hi"])
+            .env("PATH", path)
+            .env("LOG_DIR", dir.path())
+            .env("VK_TOKEN", "s3cr3t 'quoted'")
+            .stdin(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let args = fs::read_to_string(dir.path().join("args")).unwrap();
+        assert!(!args.contains("s3cr3t"));
+        assert!(args.contains("cd '/srv/vk/ws/app'"));
+        assert!(args.contains("echo $$ > '/srv/vk/ws.00000000-0000-0000-0000-000000000000.pid'"));
+        let stdin = fs::read_to_string(dir.path().join("stdin")).unwrap();
+        assert_eq!(stdin.trim_end(), r"export 'VK_TOKEN=s3cr3t '\''quoted'\'''");
+    }
+
+    #[test]
+    fn parses_deleted_files_from_itemized_output() {
+        let itemized = "*deleting   src/old name.rs\n\
+                        >f.st...... src/main.rs\n\
+                        *deleting   build/\n\
+                        *deleting   ../escape\n\
+                        cd+++++++++ new/\n";
+        assert_eq!(parse_deletions(itemized), vec!["src/old name.rs"]);
+    }
+
+    #[tokio::test]
+    async fn only_tracked_files_count_as_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("app");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::write(repo.join("src/lib.rs"), "").unwrap();
+        std::fs::write(repo.join("notes.txt"), "").unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["add", "src/lib.rs"]);
+
+        let tracked = tracked_files(
+            dir.path(),
+            vec![
+                "app/src/lib.rs".to_string(),
+                "app/notes.txt".to_string(),
+                "loose.txt".to_string(),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(tracked, vec!["app/src/lib.rs"]);
+    }
+}
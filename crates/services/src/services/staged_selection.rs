@@ -0,0 +1,41 @@
+//! Files and hunks the user picked for a workspace's next auto-commit.
+//!
+//! After a coding agent turn, every change in a repo is committed. When the
+//! user staged a selection in one of the workspace's repos, only the index is
+//! committed there and the rest is left in the working tree for the next turn.
+//! Selections are kept in memory: after a restart the next commit includes
+//! everything again.
+
+use std::sync::Arc;
+
+use dashmap::DashSet;
+use uuid::Uuid;
+
+#[derive(Clone, Default)]
+pub struct StagedSelections {
+    /// (workspace id, repo id) of repos whose index holds a selection
+    repos: Arc<DashSet<(Uuid, Uuid)>>,
+}
+
+impl StagedSelections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, workspace_id: Uuid, repo_id: Uuid) {
+        self.repos.insert((workspace_id, repo_id));
+    }
+
+    pub fn remove(&self, workspace_id: Uuid, repo_id: Uuid) {
+        self.repos.remove(&(workspace_id, repo_id));
+    }
+
+    pub fn contains(&self, workspace_id: Uuid, repo_id: Uuid) -> bool {
+        self.repos.contains(&(workspace_id, repo_id))
+    }
+
+    /// Whether the repo had a selection, which is used up by the caller's commit
+    pub fn take(&self, workspace_id: Uuid, repo_id: Uuid) -> bool {
+        self.repos.remove(&(workspace_id, repo_id)).is_some()
+    }
+}
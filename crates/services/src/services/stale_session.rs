@@ -0,0 +1,132 @@
+//! Recovery from agent sessions that can no longer be resumed.
+//!
+//! Resume ids break when an agent CLI is upgraded or its local history is
+//! cleared. A follow-up that fails this way is re-sent as a new agent session
+//! whose prompt starts with a summary of the earlier turns, and the dead id is
+//! flagged so later follow-ups do not try it again.
+
+use std::sync::LazyLock;
+
+use db::models::coding_agent_turn::CodingAgentTurn;
+use regex::Regex;
+use utils::text::truncate_to_char_boundary;
+
+/// Most recent turns summarised into the new session's prompt
+pub const MAX_CONTEXT_TURNS: usize = 10;
+
+/// Long requests and outcomes are cut to keep the summary small
+const MAX_TURN_BYTES: usize = 2_000;
+
+static STALE_SESSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)no (?:conversation|session|thread|rollout|chat) found|\b(?:session|thread|conversation|chat)\b[^\n]{0,80}?\b(?:not found|does not exist|has expired|no longer exists)|\b(?:failed|unable|could not|couldn't|cannot) (?:to )?resume|invalid (?:session|thread) id",
+    )
+    .expect("valid stale session regex")
+});
+
+/// The stderr line showing that the agent could not resume its session
+pub fn detect_stale_session(stderr: &str) -> Option<String> {
+    stderr
+        .lines()
+        .rev()
+        .find(|line| STALE_SESSION_RE.is_match(line))
+        .map(|line| line.trim().to_string())
+}
+
+/// Prompt for the new agent session: a summary of the earlier turns, oldest
+/// first, followed by the follow-up that could not be sent
+pub fn recovery_prompt<'a>(
+    turns: impl IntoIterator<Item = &'a CodingAgentTurn>,
+    prompt: &str,
+) -> String {
+    let turns: Vec<&CodingAgentTurn> = turns
+        .into_iter()
+        .filter(|turn| turn.prompt.is_some() || turn.summary.is_some())
+        .collect();
+    let recent = &turns[turns.len().saturating_sub(MAX_CONTEXT_TURNS)..];
+    if recent.is_empty() {
+        return prompt.to_string();
+    }
+
+    let mut out = String::from(
+        "The previous agent session for this task could not be resumed, so this is a new \
+         session. Summary of the earlier conversation:\n",
+    );
+    for (i, turn) in recent.iter().enumerate() {
+        out.push_str(&format!("\n### Turn {}\n", i + 1));
+        if let Some(request) = &turn.prompt {
+            out.push_str(&format!(
+                "Request:\n{}\n",
+                truncate_to_char_boundary(request.trim(), MAX_TURN_BYTES)
+            ));
+        }
+        if let Some(summary) = &turn.summary {
+            out.push_str(&format!(
+                "Outcome:\n{}\n",
+                truncate_to_char_boundary(summary.trim(), MAX_TURN_BYTES)
+            ));
+        }
+    }
+    out.push_str("\n---\n\n");
+    out.push_str(prompt);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn turn(prompt: Option<&str>, summary: Option<&str>) -> CodingAgentTurn {
+        CodingAgentTurn {
+            id: Uuid::new_v4(),
+            execution_process_id: Uuid::new_v4(),
+            agent_session_id: Some("dead".to_string()),
+            prompt: prompt.map(str::to_string),
+            summary: summary.map(str::to_string),
+            agent_session_expired: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn detects_resume_failures() {
+        for line in [
+            "No conversation found with session ID: 3f2a",
+            "Error: thread 019a-55 not found",
+            "error: failed to resume session: rollout missing",
+            "Invalid session id",
+        ] {
+            assert_eq!(
+                detect_stale_session(&format!("starting\n{line}\n")).as_deref(),
+                Some(line),
+                "{line}"
+            );
+        }
+        assert!(detect_stale_session("error: file not found: src/session.rs").is_none());
+        assert!(detect_stale_session("API Error: 429 rate_limit_error").is_none());
+    }
+
+    #[test]
+    fn summarises_recent_turns() {
+        let turns = [
+            turn(Some("Add a login page"), Some("Added src/login.tsx")),
+            turn(None, None),
+            turn(Some("Use the new API"), None),
+        ];
+        let prompt = recovery_prompt(&turns, "Now add tests");
+        assert!(
+            prompt.contains(
+                "### Turn 1\nRequest:\nAdd a login page\nOutcome:\nAdded src/login.tsx\n"
+            )
+        );
+        assert!(prompt.contains("### Turn 2\nRequest:\nUse the new API\n"));
+        assert!(!prompt.contains("### Turn 3"));
+        assert!(prompt.ends_with("\n---\n\nNow add tests"));
+
+        assert_eq!(recovery_prompt(&[], "Now add tests"), "Now add tests");
+    }
+}
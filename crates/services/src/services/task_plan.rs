@@ -0,0 +1,533 @@
+//! Creating many tasks at once from a planning document.
+//!
+//! A plan is a list of [`PlannedTask`]s, given directly as JSON or parsed from
+//! Markdown by [`parse_markdown`]:
+//!
+//! - Every checklist item (`- [ ] ...`) is a task. Lines indented under it are
+//!   its description, and a checked item (`- [x] ...`) is created as done.
+//! - A heading with no checklist items or sub-headings under it is a task too,
+//!   with the text below it as its description. Other headings only group.
+//! - Dependencies are written as a `Depends on: A, B` line in the description,
+//!   or as `(depends on: A)` at the end of the title. `After:` and `Blocked by:`
+//!   work the same way. A task is referenced by its title or as `#n`, its
+//!   1-based position in the plan.
+
+use std::{collections::VecDeque, sync::LazyLock};
+
+use db::models::{
+    project::Project,
+    task::{CreateTask, Task, TaskPriority, TaskStatus},
+    task_dependency::{TaskDependency, TaskDependencyError},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use executors::profile::ExecutorProfileId;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{error, info};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::services::container::{ContainerError, ContainerService, StartWorkspaceResult};
+
+/// Largest plan accepted in one request
+pub const MAX_PLAN_TASKS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum TaskPlanError {
+    #[error("The plan contains no tasks")]
+    Empty,
+    #[error("The plan has more than {MAX_PLAN_TASKS} tasks")]
+    TooManyTasks,
+    #[error("Task #{0} has no title")]
+    MissingTitle(usize),
+    #[error("Task '{task}' depends on '{reference}', which is not in the plan")]
+    UnknownDependency { task: String, reference: String },
+    #[error("Task '{0}' depends on itself")]
+    SelfDependency(String),
+    #[error("The plan's dependencies form a cycle through '{0}'")]
+    Cycle(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Dependency(#[from] TaskDependencyError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, TS, ToSchema)]
+pub struct PlannedTask {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tasks in the same plan that must finish first, by title or as `#n`
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    /// Created as done, so it blocks nothing and is never started
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Where and with which agent to start the plan's unblocked tasks
+#[derive(Debug, Clone)]
+pub struct PlanStart {
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<CreateWorkspaceRepo>,
+    /// Start at most this many tasks, in plan order
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CreatedPlan {
+    /// In plan order
+    pub tasks: Vec<Task>,
+    pub dependencies: Vec<TaskDependency>,
+    pub started_task_ids: Vec<Uuid>,
+}
+
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.+?)(?:\s+#+)?\s*$").expect("valid heading regex"));
+
+static CHECKLIST_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*)[-*+]\s+\[([ xX])\]\s+(.+?)\s*$").expect("valid checklist regex")
+});
+
+static CODE_FENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:```|~~~)").expect("valid fence regex"));
+
+static DEPENDENCY_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:[-*+]\s+)?(?:depends on|blocked by|after)\s*:\s*(.+?)\s*$")
+        .expect("valid dependency line regex")
+});
+
+static INLINE_DEPENDENCY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\s*\((?:depends on|blocked by|after):?\s+([^)]+)\)\s*$")
+        .expect("valid inline dependency regex")
+});
+
+enum Block {
+    Heading {
+        level: usize,
+        title: String,
+        body: Vec<String>,
+    },
+    Item {
+        indent: usize,
+        title: String,
+        done: bool,
+        body: Vec<String>,
+    },
+}
+
+/// Parse a Markdown plan into tasks, in document order
+pub fn parse_markdown(markdown: &str) -> Vec<PlannedTask> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        if !in_fence {
+            if let Some(heading) = HEADING.captures(line) {
+                blocks.push(Block::Heading {
+                    level: heading[1].len(),
+                    title: heading[2].to_string(),
+                    body: Vec::new(),
+                });
+                continue;
+            }
+            if let Some(item) = CHECKLIST_ITEM.captures(line) {
+                blocks.push(Block::Item {
+                    indent: item[1].len(),
+                    title: item[3].to_string(),
+                    done: !item[2].trim().is_empty(),
+                    body: Vec::new(),
+                });
+                continue;
+            }
+        }
+        let is_fence = CODE_FENCE.is_match(line);
+        let fenced = in_fence || is_fence;
+        if is_fence {
+            in_fence = !in_fence;
+        }
+
+        match blocks.last_mut() {
+            Some(Block::Heading { body, .. }) => body.push(line.to_string()),
+            // Only lines indented under an item belong to it
+            Some(Block::Item { indent, body, .. })
+                if fenced || line.trim().is_empty() || indent_of(line) > *indent =>
+            {
+                body.push(line.to_string())
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, block)| match block {
+            Block::Heading { level, title, body } => {
+                let has_children = blocks[index + 1..]
+                    .iter()
+                    .take_while(|next| {
+                        !matches!(next, Block::Heading { level: next_level, .. } if next_level <= level)
+                    })
+                    .next()
+                    .is_some();
+                (!has_children).then(|| planned_task(title, body, false))
+            }
+            Block::Item {
+                title, done, body, ..
+            } => Some(planned_task(title, body, *done)),
+        })
+        .collect()
+}
+
+fn planned_task(title: &str, body: &[String], done: bool) -> PlannedTask {
+    let mut depends_on = Vec::new();
+    let title = match INLINE_DEPENDENCY.captures(title) {
+        Some(inline) => {
+            depends_on.extend(split_references(&inline[1]));
+            title[..inline.get(0).expect("whole match").start()].to_string()
+        }
+        None => title.to_string(),
+    };
+
+    let mut description_lines = Vec::new();
+    for line in body {
+        match DEPENDENCY_LINE.captures(line) {
+            Some(dependency) => depends_on.extend(split_references(&dependency[1])),
+            None => description_lines.push(line.as_str()),
+        }
+    }
+
+    PlannedTask {
+        title: title.trim().to_string(),
+        description: dedent(&description_lines),
+        depends_on,
+        priority: None,
+        done,
+    }
+}
+
+fn split_references(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.split([',', ';'])
+        .map(|reference| reference.trim().trim_matches(['"', '\'', '`']).trim())
+        .filter(|reference| !reference.is_empty())
+        .map(str::to_string)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Join lines with their common indentation and surrounding blank lines
+/// removed; None when nothing is left
+fn dedent(lines: &[&str]) -> Option<String> {
+    let first = lines.iter().position(|line| !line.trim().is_empty())?;
+    let last = lines.iter().rposition(|line| !line.trim().is_empty())?;
+    let lines = &lines[first..=last];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indent_of(line))
+        .min()
+        .unwrap_or(0);
+
+    let text = lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(text)
+}
+
+/// Check the plan and return, for each task, the positions of the tasks it
+/// depends on
+pub fn resolve_dependencies(tasks: &[PlannedTask]) -> Result<Vec<Vec<usize>>, TaskPlanError> {
+    if tasks.is_empty() {
+        return Err(TaskPlanError::Empty);
+    }
+    if tasks.len() > MAX_PLAN_TASKS {
+        return Err(TaskPlanError::TooManyTasks);
+    }
+    if let Some(position) = tasks.iter().position(|task| task.title.trim().is_empty()) {
+        return Err(TaskPlanError::MissingTitle(position + 1));
+    }
+
+    let find = |reference: &str| -> Option<usize> {
+        if let Some(number) = reference.strip_prefix('#')
+            && let Ok(number) = number.trim().parse::<usize>()
+        {
+            return number.checked_sub(1).filter(|&index| index < tasks.len());
+        }
+        tasks
+            .iter()
+            .position(|task| task.title.trim().eq_ignore_ascii_case(reference))
+    };
+
+    let mut dependencies = Vec::with_capacity(tasks.len());
+    for (index, task) in tasks.iter().enumerate() {
+        let mut resolved: Vec<usize> = Vec::new();
+        for reference in &task.depends_on {
+            let dependency =
+                find(reference.trim()).ok_or_else(|| TaskPlanError::UnknownDependency {
+                    task: task.title.clone(),
+                    reference: reference.clone(),
+                })?;
+            if dependency == index {
+                return Err(TaskPlanError::SelfDependency(task.title.clone()));
+            }
+            if !resolved.contains(&dependency) {
+                resolved.push(dependency);
+            }
+        }
+        dependencies.push(resolved);
+    }
+
+    // Kahn's algorithm: whatever can't be ordered is on a cycle
+    let mut waiting_on: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut ready: VecDeque<usize> = (0..tasks.len()).filter(|&i| waiting_on[i] == 0).collect();
+    let mut ordered = 0;
+    while let Some(done) = ready.pop_front() {
+        ordered += 1;
+        for (index, deps) in dependencies.iter().enumerate() {
+            if deps.contains(&done) {
+                waiting_on[index] -= 1;
+                if waiting_on[index] == 0 {
+                    ready.push_back(index);
+                }
+            }
+        }
+    }
+    if ordered < tasks.len() {
+        let on_cycle = waiting_on
+            .iter()
+            .position(|&count| count > 0)
+            .expect("an unordered task");
+        return Err(TaskPlanError::Cycle(tasks[on_cycle].title.clone()));
+    }
+
+    Ok(dependencies)
+}
+
+/// Create the plan's tasks and dependencies in `project`, then start the
+/// tasks that nothing unfinished blocks when `start` is given. A task that
+/// fails to start is logged and skipped; the plan is created regardless.
+pub async fn create_plan(
+    container: &impl ContainerService,
+    project: &Project,
+    tasks: &[PlannedTask],
+    task_group_id: Option<Uuid>,
+    created_by: Option<Uuid>,
+    start: Option<&PlanStart>,
+) -> Result<CreatedPlan, TaskPlanError> {
+    let dependencies = resolve_dependencies(tasks)?;
+    let pool = &container.db().pool;
+
+    let mut created = Vec::with_capacity(tasks.len());
+    for planned in tasks {
+        let mut create = CreateTask::from_title_description(
+            project.id,
+            planned.title.trim().to_string(),
+            planned.description.clone(),
+        );
+        if planned.done {
+            create.status = Some(TaskStatus::Done);
+        }
+        create.priority = planned.priority;
+        create.task_group_id = task_group_id;
+        create.created_by = created_by;
+        created.push(Task::create(pool, &create, Uuid::new_v4()).await?);
+    }
+
+    let mut links = Vec::new();
+    for (index, depends_on) in dependencies.iter().enumerate() {
+        for &dependency in depends_on {
+            links.push(
+                TaskDependency::create(pool, created[index].id, created[dependency].id).await?,
+            );
+        }
+    }
+
+    let mut started_task_ids = Vec::new();
+    if let Some(start) = start {
+        let unblocked = (0..tasks.len()).filter(|&index| {
+            !tasks[index].done && dependencies[index].iter().all(|&dep| tasks[dep].done)
+        });
+        for index in unblocked.take(start.limit.unwrap_or(usize::MAX)) {
+            match start_task(container, project, &created[index], start).await {
+                Ok(()) => started_task_ids.push(created[index].id),
+                Err(e) => error!(
+                    "Failed to start planned task '{}': {}",
+                    created[index].title, e
+                ),
+            }
+        }
+    }
+
+    // Reload so the blocked flags set by the dependency triggers are current
+    let mut reloaded = Vec::with_capacity(created.len());
+    for task in created {
+        reloaded.push(Task::find_by_id(pool, task.id).await?.unwrap_or(task));
+    }
+
+    Ok(CreatedPlan {
+        tasks: reloaded,
+        dependencies: links,
+        started_task_ids,
+    })
+}
+
+async fn start_task(
+    container: &impl ContainerService,
+    project: &Project,
+    task: &Task,
+    start: &PlanStart,
+) -> Result<(), TaskPlanError> {
+    let pool = &container.db().pool;
+    let workspace_id = Uuid::new_v4();
+    let repo_ids: Vec<Uuid> = start.repos.iter().map(|repo| repo.repo_id).collect();
+    let branch = container
+        .unique_git_branch_for_workspace(&workspace_id, &task.title, &repo_ids)
+        .await?;
+    let agent_working_dir = project
+        .default_agent_working_dir
+        .clone()
+        .filter(|dir| !dir.is_empty());
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir,
+        },
+        workspace_id,
+        task.id,
+    )
+    .await?;
+    WorkspaceRepo::create_many(pool, workspace.id, &start.repos).await?;
+
+    match container
+        .start_workspace(&workspace, start.executor_profile_id.clone())
+        .await?
+    {
+        StartWorkspaceResult::Started(_) => info!("Planned task '{}' started", task.title),
+        StartWorkspaceResult::Queued(_) => info!("Planned task '{}' queued", task.title),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(tasks: &[PlannedTask]) -> Vec<&str> {
+        tasks.iter().map(|task| task.title.as_str()).collect()
+    }
+
+    fn task(title: &str, depends_on: &[&str]) -> PlannedTask {
+        PlannedTask {
+            title: title.to_string(),
+            description: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            priority: None,
+            done: false,
+        }
+    }
+
+    #[test]
+    fn checklists_and_leaf_headings_become_tasks() {
+        let plan = "\
+# Auth rewrite
+
+Some context that is not a task.
+
+## Backend
+- [x] Add sessions table
+- [ ] Issue tokens
+  Use short-lived JWTs.
+
+  Depends on: Add sessions table
+- [ ] Refresh endpoint (after: Issue tokens)
+
+## Frontend
+
+Swap the login form to the new endpoint.
+Blocked by: #2
+";
+
+        let tasks = parse_markdown(plan);
+
+        assert_eq!(
+            titles(&tasks),
+            vec![
+                "Add sessions table",
+                "Issue tokens",
+                "Refresh endpoint",
+                "Frontend"
+            ]
+        );
+        assert!(tasks[0].done);
+        assert_eq!(
+            tasks[1].description.as_deref(),
+            Some("Use short-lived JWTs.")
+        );
+        assert_eq!(tasks[1].depends_on, vec!["Add sessions table"]);
+        assert_eq!(tasks[2].depends_on, vec!["Issue tokens"]);
+        assert_eq!(
+            tasks[3].description.as_deref(),
+            Some("Swap the login form to the new endpoint.")
+        );
+        assert_eq!(tasks[3].depends_on, vec!["#2"]);
+    }
+
+    #[test]
+    fn fenced_code_is_kept_in_descriptions() {
+        let plan = "## Add CLI flag\n```\n# not a heading\n- [ ] not an item\n```\n";
+
+        let tasks = parse_markdown(plan);
+
+        assert_eq!(titles(&tasks), vec!["Add CLI flag"]);
+        assert_eq!(
+            tasks[0].description.as_deref(),
+            Some("```\n# not a heading\n- [ ] not an item\n```")
+        );
+    }
+
+    #[test]
+    fn dependencies_resolve_by_title_or_position() {
+        let tasks = [
+            task("Schema", &[]),
+            task("API", &["schema"]),
+            task("UI", &["#2", "Schema"]),
+        ];
+
+        let dependencies = resolve_dependencies(&tasks).unwrap();
+
+        assert_eq!(dependencies, vec![vec![], vec![0], vec![1, 0]]);
+    }
+
+    #[test]
+    fn invalid_plans_are_rejected() {
+        assert!(matches!(
+            resolve_dependencies(&[]),
+            Err(TaskPlanError::Empty)
+        ));
+        assert!(matches!(
+            resolve_dependencies(&[task("A", &["Missing"])]),
+            Err(TaskPlanError::UnknownDependency { .. })
+        ));
+        assert!(matches!(
+            resolve_dependencies(&[task("A", &["#1"])]),
+            Err(TaskPlanError::SelfDependency(_))
+        ));
+        assert!(matches!(
+            resolve_dependencies(&[task("A", &["C"]), task("B", &["A"]), task("C", &["B"])]),
+            Err(TaskPlanError::Cycle(_))
+        ));
+    }
+}
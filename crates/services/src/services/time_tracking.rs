@@ -0,0 +1,266 @@
+//! Effort per task, derived from execution start and end times.
+//!
+//! Agent time is the wall-clock time during which at least one coding agent
+//! ran in any of the task's workspaces, so parallel attempts count once.
+//! Script time is the same for setup and cleanup scripts. Human wait time is
+//! every gap between two runs of the task: the agent had finished and the
+//! task sat until someone followed up. Time after the latest run is not
+//! counted, since the task may simply be finished.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use db::models::{execution_process::ExecutionProcessRunReason, time_tracking::ExecutionSpan};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Days covered by a project summary when the caller does not pick a range
+pub const DEFAULT_RANGE_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, TS, ToSchema)]
+pub struct TimeTotals {
+    #[ts(type = "number")]
+    pub agent_seconds: i64,
+    #[ts(type = "number")]
+    pub script_seconds: i64,
+    #[ts(type = "number")]
+    pub human_wait_seconds: i64,
+    #[ts(type = "number")]
+    pub executions: i64,
+}
+
+impl TimeTotals {
+    fn add(&mut self, other: &TimeTotals) {
+        self.agent_seconds += other.agent_seconds;
+        self.script_seconds += other.script_seconds;
+        self.human_wait_seconds += other.human_wait_seconds;
+        self.executions += other.executions;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct TaskTime {
+    pub task_id: Uuid,
+    pub task_title: String,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub totals: TimeTotals,
+    #[ts(type = "Date | null")]
+    pub first_started_at: Option<DateTime<Utc>>,
+    /// End of the latest run, or now while one is running
+    #[ts(type = "Date | null")]
+    pub last_active_at: Option<DateTime<Utc>>,
+    /// A run is in progress; it is counted up to now
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ProjectTimeReport {
+    pub project_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub totals: TimeTotals,
+    /// Tasks with runs in the range, most agent time first
+    pub tasks: Vec<TaskTime>,
+}
+
+/// Time spent on one task. `task_title` is used when the task has no runs.
+pub async fn task_time(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    task_title: &str,
+) -> Result<TaskTime, sqlx::Error> {
+    let spans = ExecutionSpan::find_by_task_id(pool, task_id).await?;
+    Ok(summarize(task_id, task_title, &spans, Utc::now()))
+}
+
+/// Time spent on a project's tasks, over runs started between `from` and
+/// `to` (inclusive). Defaults to the last [`DEFAULT_RANGE_DAYS`] days.
+pub async fn project_time_report(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<ProjectTimeReport, sqlx::Error> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+    let spans = ExecutionSpan::find_by_project_id(
+        pool,
+        project_id,
+        &from.format("%Y-%m-%d").to_string(),
+        &to.format("%Y-%m-%d").to_string(),
+    )
+    .await?;
+
+    let mut by_task: HashMap<Uuid, Vec<ExecutionSpan>> = HashMap::new();
+    for span in spans {
+        by_task.entry(span.task_id).or_default().push(span);
+    }
+
+    let now = Utc::now();
+    let mut totals = TimeTotals::default();
+    let mut tasks: Vec<TaskTime> = by_task
+        .into_iter()
+        .map(|(task_id, spans)| {
+            let task = summarize(task_id, &spans[0].task_title, &spans, now);
+            totals.add(&task.totals);
+            task
+        })
+        .collect();
+    tasks.sort_by(|a, b| {
+        b.totals
+            .agent_seconds
+            .cmp(&a.totals.agent_seconds)
+            .then_with(|| a.task_title.cmp(&b.task_title))
+    });
+
+    Ok(ProjectTimeReport {
+        project_id,
+        from,
+        to,
+        totals,
+        tasks,
+    })
+}
+
+fn summarize(
+    task_id: Uuid,
+    task_title: &str,
+    spans: &[ExecutionSpan],
+    now: DateTime<Utc>,
+) -> TaskTime {
+    let interval = |span: &ExecutionSpan| (span.started_at, span.completed_at.unwrap_or(now));
+    let agent = merge_intervals(
+        spans
+            .iter()
+            .filter(|span| is_agent_run(&span.run_reason))
+            .map(interval),
+    );
+    let scripts = merge_intervals(
+        spans
+            .iter()
+            .filter(|span| !is_agent_run(&span.run_reason))
+            .map(interval),
+    );
+    let active = merge_intervals(spans.iter().map(interval));
+
+    let human_wait_seconds = active
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].1).num_seconds())
+        .sum();
+
+    TaskTime {
+        task_id,
+        task_title: task_title.to_string(),
+        totals: TimeTotals {
+            agent_seconds: total_seconds(&agent),
+            script_seconds: total_seconds(&scripts),
+            human_wait_seconds,
+            executions: spans.len() as i64,
+        },
+        first_started_at: active.first().map(|(start, _)| *start),
+        last_active_at: active.last().map(|(_, end)| *end),
+        running: spans.iter().any(|span| span.completed_at.is_none()),
+    }
+}
+
+fn is_agent_run(run_reason: &ExecutionProcessRunReason) -> bool {
+    matches!(
+        run_reason,
+        ExecutionProcessRunReason::CodingAgent
+            | ExecutionProcessRunReason::InternalAgent
+            | ExecutionProcessRunReason::Replay
+    )
+}
+
+/// Sort intervals and merge the ones that overlap or touch
+fn merge_intervals(
+    intervals: impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut intervals: Vec<_> = intervals.filter(|(start, end)| end >= start).collect();
+    intervals.sort();
+
+    let mut merged: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn total_seconds(intervals: &[(DateTime<Utc>, DateTime<Utc>)]) -> i64 {
+    intervals
+        .iter()
+        .map(|(start, end)| (*end - *start).num_seconds())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, 10, minute, 0).unwrap()
+    }
+
+    fn span(run_reason: ExecutionProcessRunReason, start: u32, end: Option<u32>) -> ExecutionSpan {
+        ExecutionSpan {
+            task_id: Uuid::nil(),
+            task_title: "Task".to_string(),
+            run_reason,
+            started_at: at(start),
+            completed_at: end.map(at),
+        }
+    }
+
+    #[test]
+    fn parallel_agent_runs_count_once_and_gaps_are_human_wait() {
+        use ExecutionProcessRunReason::*;
+        let spans = [
+            span(SetupScript, 0, Some(2)),
+            span(CodingAgent, 2, Some(10)),
+            // A second attempt overlapping the first
+            span(CodingAgent, 5, Some(12)),
+            // Follow-up after a 20 minute review
+            span(CodingAgent, 32, Some(40)),
+        ];
+
+        let time = summarize(Uuid::nil(), "Task", &spans, at(59));
+
+        assert_eq!(time.totals.agent_seconds, (10 + 8) * 60);
+        assert_eq!(time.totals.script_seconds, 2 * 60);
+        assert_eq!(time.totals.human_wait_seconds, 20 * 60);
+        assert_eq!(time.totals.executions, 4);
+        assert_eq!(time.first_started_at, Some(at(0)));
+        assert_eq!(time.last_active_at, Some(at(40)));
+        assert!(!time.running);
+    }
+
+    #[test]
+    fn running_execution_counts_until_now() {
+        let spans = [span(ExecutionProcessRunReason::CodingAgent, 0, None)];
+
+        let time = summarize(Uuid::nil(), "Task", &spans, at(15));
+
+        assert_eq!(time.totals.agent_seconds, 15 * 60);
+        assert_eq!(time.totals.human_wait_seconds, 0);
+        assert_eq!(time.last_active_at, Some(at(15)));
+        assert!(time.running);
+    }
+
+    #[test]
+    fn task_without_runs_is_empty() {
+        let time = summarize(Uuid::nil(), "Task", &[], at(0));
+
+        assert_eq!(time.totals, TimeTotals::default());
+        assert_eq!(time.first_started_at, None);
+        assert!(!time.running);
+    }
+}
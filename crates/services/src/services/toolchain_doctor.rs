@@ -0,0 +1,431 @@
+//! Toolchain doctor.
+//!
+//! Checks the tools a project declares (node, pnpm, cargo, docker, ...)
+//! before an agent trips over a missing binary. Checks run in a throwaway
+//! detached worktree of the project's first repo, through the project's
+//! script shell and dev shell, so version files such as `.nvmrc`,
+//! `.tool-versions` or `rust-toolchain.toml` and Nix flakes are honoured the
+//! same way they are during a real run.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::LazyLock,
+    time::Duration,
+};
+
+use db::models::{
+    project::Project, project_repo::ProjectRepo, project_tool_requirement::ProjectToolRequirement,
+    repo::Repo,
+};
+use executors::{command::CommandParts, env::ExecutionEnv};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::process::Command;
+use ts_rs::TS;
+use utils::{
+    path::get_vibe_kanban_temp_dir,
+    shell::{get_shell_command, login_shell_env},
+};
+use utoipa::ToSchema;
+
+use super::git::{GitCli, GitCliError};
+
+/// How long a single version check may run before it is reported as failed
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Exit codes shells use for "command not found"
+const NOT_FOUND_EXIT_CODES: [i32; 2] = [127, 9009];
+
+static VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+(?:\.\d+){0,3}").expect("valid version regex"));
+
+#[derive(Debug, Error)]
+pub enum ToolchainDoctorError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolStatus {
+    Ok,
+    Missing,
+    VersionMismatch,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ToolCheck {
+    pub name: String,
+    pub required_version: Option<String>,
+    /// Version parsed from the check command's output
+    pub found_version: Option<String>,
+    pub status: ToolStatus,
+    /// First line of output, or the error when the command could not run
+    pub output: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ToolchainReport {
+    pub checks: Vec<ToolCheck>,
+    /// True when every declared tool is present at a matching version
+    pub healthy: bool,
+    /// Repo whose worktree the checks ran in, if the project has any
+    pub repo_name: Option<String>,
+}
+
+/// Pull the first dotted version number out of a `--version` style output
+pub fn extract_version(output: &str) -> Option<String> {
+    VERSION_RE.find(output).map(|m| m.as_str().to_string())
+}
+
+fn parse_components(version: &str) -> Option<Vec<u64>> {
+    // "20.x" means any 20 release
+    let parts = version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .take_while(|part| *part != "x" && *part != "*")
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    (!parts.is_empty()).then_some(parts)
+}
+
+fn compare(found: &[u64], required: &[u64]) -> std::cmp::Ordering {
+    let len = found.len().max(required.len());
+    let at = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| at(found, i).cmp(&at(required, i)))
+        .find(|ord| ord.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Check `found` against a requirement such as `20`, `>=1.80`, `^9.1`, `~3.11`
+/// or `>=18, <21`. A bare version matches any release with that prefix.
+pub fn version_satisfies(found: &str, requirement: &str) -> bool {
+    let Some(found) = parse_components(found) else {
+        return false;
+    };
+
+    requirement
+        .split(',')
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty())
+        .all(|clause| {
+            let (op, version) = match clause.find(|c: char| c.is_ascii_digit() || c == 'v') {
+                Some(idx) => clause.split_at(idx),
+                None => return false,
+            };
+            let Some(required) = parse_components(version) else {
+                return false;
+            };
+            let ord = compare(&found, &required);
+            match op.trim() {
+                "" | "=" | "==" => found.starts_with(&required),
+                ">=" => ord.is_ge(),
+                ">" => ord.is_gt(),
+                "<=" => ord.is_le(),
+                "<" => ord.is_lt(),
+                "^" => {
+                    let fixed = if required[0] == 0 { 2 } else { 1 };
+                    found.starts_with(&required[..fixed.min(required.len())]) && ord.is_ge()
+                }
+                "~" => found.starts_with(&required[..2.min(required.len())]) && ord.is_ge(),
+                _ => false,
+            }
+        })
+}
+
+/// Install hint for well-known tools
+fn install_hint(name: &str, version: Option<&str>) -> Option<String> {
+    let hint = match (name, version) {
+        ("node" | "nodejs", Some(v)) => {
+            format!(
+                "Install Node {v} with a version manager (e.g. `nvm install {v}`) and pin it in .nvmrc"
+            )
+        }
+        ("node" | "nodejs", None) => {
+            "Install Node with a version manager such as nvm, fnm or mise".to_string()
+        }
+        ("npm" | "npx", _) => "npm ships with Node; install or upgrade Node".to_string(),
+        ("pnpm" | "yarn", _) => format!(
+            "Run `corepack enable {name}` (bundled with Node 16.13+), or `npm install -g {name}`"
+        ),
+        ("bun", _) => "Install Bun: `curl -fsSL https://bun.sh/install | bash`".to_string(),
+        ("cargo" | "rustc" | "rustup", Some(v)) => {
+            format!("Run `rustup install {v}` or pin it in rust-toolchain.toml")
+        }
+        ("cargo" | "rustc" | "rustup", None) => {
+            "Install Rust with rustup: https://rustup.rs".to_string()
+        }
+        ("docker", _) => {
+            "Install Docker Desktop or Docker Engine and make sure the daemon is running"
+                .to_string()
+        }
+        ("python" | "python3", Some(v)) => {
+            format!("Install Python {v} (e.g. `pyenv install {v}`) and pin it in .python-version")
+        }
+        ("python" | "python3", None) => "Install Python 3, e.g. with pyenv".to_string(),
+        ("go", _) => "Install Go from https://go.dev/dl".to_string(),
+        ("gh", _) => "Install the GitHub CLI: https://cli.github.com".to_string(),
+        _ => return None,
+    };
+    Some(hint)
+}
+
+fn suggestion(
+    requirement: &ProjectToolRequirement,
+    status: ToolStatus,
+    inherit_login_env: bool,
+) -> Option<String> {
+    let hint = install_hint(&requirement.name, requirement.version.as_deref());
+    match status {
+        ToolStatus::Ok => None,
+        ToolStatus::Missing if !inherit_login_env => Some(format!(
+            "'{}' is not on PATH for the project shell. If it is set up in your shell profile, enable \"Inherit login shell environment\" for this project.{}",
+            requirement.name,
+            hint.map(|h| format!(" Otherwise: {h}")).unwrap_or_default()
+        )),
+        ToolStatus::Missing => hint.or_else(|| Some(format!("Install '{}'", requirement.name))),
+        ToolStatus::VersionMismatch => hint.or_else(|| {
+            Some(format!(
+                "Install a version of '{}' matching {}",
+                requirement.name,
+                requirement.version.as_deref().unwrap_or("the requirement")
+            ))
+        }),
+        ToolStatus::Failed => requirement.check_command.as_ref().map(|command| {
+            format!("The check command `{command}` failed; make sure it prints the version")
+        }),
+    }
+}
+
+/// Build the command the same way a script action would: project shell,
+/// wrapped in the dev shell prefix, with the login environment applied
+fn shell_command(env: &ExecutionEnv, script: &str) -> Command {
+    let (program, args) = match env.shell {
+        Some(shell) => shell.command(),
+        None => {
+            let (program, arg) = get_shell_command();
+            (program, vec![arg])
+        }
+    };
+    let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+    args.push(script.to_string());
+    let (program, args) = env.wrap(CommandParts::new(program, args)).into_parts();
+
+    let mut command = Command::new(program);
+    command.args(args);
+    env.apply_to_command(&mut command);
+    command
+}
+
+async fn run_check(
+    requirement: &ProjectToolRequirement,
+    cwd: &Path,
+    env: &ExecutionEnv,
+    inherit_login_env: bool,
+) -> ToolCheck {
+    let script = requirement
+        .check_command
+        .clone()
+        .unwrap_or_else(|| format!("{} --version", requirement.name));
+
+    let mut command = shell_command(env, &script);
+    command
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let (status, found_version, output) =
+        match tokio::time::timeout(CHECK_TIMEOUT, command.output()).await {
+            Err(_) => (
+                ToolStatus::Failed,
+                None,
+                Some(format!("Timed out after {}s", CHECK_TIMEOUT.as_secs())),
+            ),
+            Ok(Err(e)) => (ToolStatus::Failed, None, Some(e.to_string())),
+            Ok(Ok(out)) => {
+                let text = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&out.stdout),
+                    String::from_utf8_lossy(&out.stderr)
+                );
+                let first_line = text.lines().find(|l| !l.trim().is_empty());
+                let first_line = first_line.map(|l| l.trim().to_string());
+
+                if !out.status.success() {
+                    let missing = out
+                        .status
+                        .code()
+                        .is_some_and(|code| NOT_FOUND_EXIT_CODES.contains(&code));
+                    let status = if missing {
+                        ToolStatus::Missing
+                    } else {
+                        ToolStatus::Failed
+                    };
+                    (status, None, first_line)
+                } else {
+                    let found = extract_version(&text);
+                    let status = match (&requirement.version, &found) {
+                        (None, _) => ToolStatus::Ok,
+                        (Some(req), Some(found)) if version_satisfies(found, req) => ToolStatus::Ok,
+                        (Some(_), _) => ToolStatus::VersionMismatch,
+                    };
+                    (status, found, first_line)
+                }
+            }
+        };
+
+    ToolCheck {
+        name: requirement.name.clone(),
+        required_version: requirement.version.clone(),
+        found_version,
+        status,
+        output,
+        suggestion: suggestion(requirement, status, inherit_login_env),
+    }
+}
+
+/// Detached worktree that is removed again when the doctor finishes
+struct ThrowawayWorktree {
+    repo_path: PathBuf,
+    path: PathBuf,
+    _dir: tempfile::TempDir,
+}
+
+impl ThrowawayWorktree {
+    async fn create(repo: &Repo) -> Result<Self, ToolchainDoctorError> {
+        let base = get_vibe_kanban_temp_dir();
+        tokio::fs::create_dir_all(&base).await?;
+        let dir = tempfile::Builder::new()
+            .prefix("toolchain-doctor-")
+            .tempdir_in(base)?;
+        let path = dir.path().join(&repo.name);
+        let repo_path = repo.path.clone();
+
+        let (git_repo, git_path) = (repo_path.clone(), path.clone());
+        tokio::task::spawn_blocking(move || {
+            GitCli::new().worktree_add(&git_repo, &git_path, "HEAD", false)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        Ok(Self {
+            repo_path,
+            path,
+            _dir: dir,
+        })
+    }
+
+    async fn remove(self) {
+        let (repo_path, path) = (self.repo_path.clone(), self.path.clone());
+        let result = tokio::task::spawn_blocking(move || {
+            let git = GitCli::new();
+            git.worktree_remove(&repo_path, &path, true)?;
+            git.worktree_prune(&repo_path)
+        })
+        .await;
+        if !matches!(result, Ok(Ok(()))) {
+            tracing::warn!(
+                "Failed to remove toolchain doctor worktree {}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+/// Run every declared tool check for the project
+pub async fn run_doctor(
+    pool: &SqlitePool,
+    project: &Project,
+) -> Result<ToolchainReport, ToolchainDoctorError> {
+    let requirements = ProjectToolRequirement::find_by_project_id(pool, project.id).await?;
+    let repo = ProjectRepo::find_repos_for_project(pool, project.id)
+        .await?
+        .into_iter()
+        .next();
+
+    let worktree = match &repo {
+        Some(repo) if !requirements.is_empty() => Some(ThrowawayWorktree::create(repo).await?),
+        _ => None,
+    };
+    let cwd = worktree
+        .as_ref()
+        .map(|w| w.path.clone())
+        .unwrap_or_else(std::env::temp_dir);
+
+    let mut env = ExecutionEnv::new();
+    env.shell = project.script_shell;
+    if project.inherit_login_env
+        && let Some(login_env) = login_shell_env().await
+    {
+        env.merge(&login_env);
+    }
+    if let Some(dev_shell) = project.dev_shell
+        && let Some(prefix) =
+            dev_shell.resolve_prefix(project.dev_shell_command.as_deref(), &[cwd.clone()])
+    {
+        env.command_prefix = prefix;
+    }
+
+    let mut checks = Vec::with_capacity(requirements.len());
+    for requirement in &requirements {
+        checks.push(run_check(requirement, &cwd, &env, project.inherit_login_env).await);
+    }
+
+    if let Some(worktree) = worktree {
+        worktree.remove().await;
+    }
+
+    Ok(ToolchainReport {
+        healthy: checks.iter().all(|c| c.status == ToolStatus::Ok),
+        checks,
+        repo_name: repo.map(|r| r.name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_version() {
+        assert_eq!(extract_version("v20.11.1\n").as_deref(), Some("20.11.1"));
+        assert_eq!(
+            extract_version("cargo 1.83.0 (5ffbef321 2024-10-29)").as_deref(),
+            Some("1.83.0")
+        );
+        assert_eq!(
+            extract_version("Docker version 27.3.1, build ce12230").as_deref(),
+            Some("27.3.1")
+        );
+        assert_eq!(extract_version("no digits here"), None);
+    }
+
+    #[test]
+    fn test_version_satisfies() {
+        assert!(version_satisfies("20.11.1", "20"));
+        assert!(version_satisfies("20.11.1", "20.x"));
+        assert!(!version_satisfies("18.19.0", "20"));
+        assert!(version_satisfies("1.83.0", ">=1.80"));
+        assert!(!version_satisfies("1.79.0", ">=1.80"));
+        assert!(version_satisfies("9.4.0", "^9.1"));
+        assert!(!version_satisfies("10.0.0", "^9.1"));
+        assert!(version_satisfies("3.11.9", "~3.11"));
+        assert!(!version_satisfies("3.12.0", "~3.11"));
+        assert!(version_satisfies("20.1.0", ">=18, <21"));
+        assert!(!version_satisfies("22.0.0", ">=18, <21"));
+        assert!(!version_satisfies("20.0.0", "latest"));
+    }
+}
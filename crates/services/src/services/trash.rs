@@ -0,0 +1,132 @@
+//! Trash for deleted tasks and projects.
+//!
+//! Deleting a task or project only sets its `deleted_at`, which hides it from
+//! lookups and listings until it is restored. Once an item has been in the
+//! trash for `trash_retention_days`, the service deletes it for good, along
+//! with the worktrees of its workspaces.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        project::Project,
+        project_repo::ProjectRepo,
+        repo::Repo,
+        task::Task,
+        trash::{TrashItem, TrashItemKind},
+        workspace::{Workspace, WorkspaceError},
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::services::{config::Config, workspace_manager::WorkspaceManager};
+
+/// How often expired items are purged
+pub const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Error)]
+pub enum TrashError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+}
+
+pub struct TrashService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+}
+
+impl TrashService {
+    pub fn spawn(db: DBService, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = Self { db, config };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting trash purge with interval {:?}",
+            TRASH_PURGE_INTERVAL
+        );
+
+        let mut interval = interval(TRASH_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let retention_days = self.config.read().await.trash_retention_days;
+            match purge_expired(&self.db.pool, retention_days).await {
+                Ok(0) => {}
+                Ok(count) => info!("Purged {} expired items from the trash", count),
+                Err(e) => error!("Failed to purge the trash: {}", e),
+            }
+        }
+    }
+}
+
+/// Permanently delete everything trashed more than `retention_days` ago
+pub async fn purge_expired(pool: &SqlitePool, retention_days: u32) -> Result<usize, TrashError> {
+    let expired = TrashItem::find_expired(pool, retention_days).await?;
+    for item in &expired {
+        match item.kind {
+            TrashItemKind::Project => purge_project(pool, item.id).await?,
+            TrashItemKind::Task => purge_task(pool, item.id).await?,
+        }
+    }
+    Ok(expired.len())
+}
+
+/// Permanently delete a trashed project with its tasks
+pub async fn purge_project(pool: &SqlitePool, project_id: Uuid) -> Result<(), TrashError> {
+    let workspace_dirs = workspace_dirs(Workspace::fetch_by_project_id(pool, project_id).await?);
+    let repositories = ProjectRepo::find_repos_for_project(pool, project_id).await?;
+
+    Project::delete(pool, project_id).await?;
+    cleanup(pool, &workspace_dirs, &repositories).await;
+    Ok(())
+}
+
+/// Permanently delete a trashed task
+pub async fn purge_task(pool: &SqlitePool, task_id: Uuid) -> Result<(), TrashError> {
+    let workspaces = Workspace::fetch_all(pool, Some(task_id)).await?;
+    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task_id).await?;
+
+    let mut tx = pool.begin().await?;
+    // Subtasks outlive the workspaces they were started from
+    for workspace in &workspaces {
+        Task::nullify_children_by_workspace_id(&mut *tx, workspace.id).await?;
+    }
+    Task::delete(&mut *tx, task_id).await?;
+    tx.commit().await?;
+
+    cleanup(pool, &workspace_dirs(workspaces), &repositories).await;
+    Ok(())
+}
+
+fn workspace_dirs(workspaces: Vec<Workspace>) -> Vec<PathBuf> {
+    workspaces
+        .into_iter()
+        .filter_map(|workspace| workspace.container_ref.map(PathBuf::from))
+        .collect()
+}
+
+async fn cleanup(pool: &SqlitePool, workspace_dirs: &[PathBuf], repositories: &[Repo]) {
+    for workspace_dir in workspace_dirs {
+        if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, repositories).await {
+            error!(
+                "Workspace cleanup failed for purged item at {}: {}",
+                workspace_dir.display(),
+                e
+            );
+        }
+    }
+    if let Err(e) = Repo::delete_orphaned(pool).await {
+        error!("Failed to delete orphaned repos: {}", e);
+    }
+}
@@ -0,0 +1,248 @@
+//! Token usage and estimated cost, aggregated per executor, project and day.
+//!
+//! Usage is recorded per execution when its logs finish; this module only
+//! sums it up. Costs come from the prices in [`UsagePricingConfig`], so
+//! executions of an executor without a price are counted but not costed.
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate, Utc};
+use db::models::token_usage::TokenUsageRow;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::services::config::UsagePricingConfig;
+
+/// Days covered when the caller does not pick a range
+pub const DEFAULT_RANGE_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS, ToSchema)]
+pub struct UsageTotals {
+    #[ts(type = "number")]
+    pub executions: i64,
+    #[ts(type = "number")]
+    pub input_tokens: i64,
+    #[ts(type = "number")]
+    pub output_tokens: i64,
+    pub estimated_cost_usd: f64,
+    /// Executions whose executor has no price configured; the estimate
+    /// leaves them out
+    #[ts(type = "number")]
+    pub unpriced_executions: i64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, row: &TokenUsageRow, cost: Option<f64>) {
+        self.executions += row.executions;
+        self.input_tokens += row.input_tokens;
+        self.output_tokens += row.output_tokens;
+        match cost {
+            Some(cost) => self.estimated_cost_usd += cost,
+            None => self.unpriced_executions += row.executions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ExecutorUsage {
+    pub executor: String,
+    pub variant: Option<String>,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ProjectUsage {
+    /// None for usage outside any project, such as conversations
+    pub project_id: Option<Uuid>,
+    pub project_name: Option<String>,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct DailyUsage {
+    /// `YYYY-MM-DD`, in UTC
+    pub day: String,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub struct UsageReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub totals: UsageTotals,
+    /// Most expensive first
+    pub by_executor: Vec<ExecutorUsage>,
+    /// Most expensive first
+    pub by_project: Vec<ProjectUsage>,
+    /// Oldest first; days without usage are omitted
+    pub by_day: Vec<DailyUsage>,
+}
+
+/// Usage between `from` and `to` inclusive, defaulting to the last
+/// [`DEFAULT_RANGE_DAYS`] days
+pub async fn usage_report(
+    pool: &SqlitePool,
+    pricing: &UsagePricingConfig,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    project_id: Option<Uuid>,
+) -> Result<UsageReport, sqlx::Error> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+    let rows = TokenUsageRow::find_in_range(
+        pool,
+        &from.format("%Y-%m-%d").to_string(),
+        &to.format("%Y-%m-%d").to_string(),
+        project_id,
+    )
+    .await?;
+    Ok(summarize(&rows, pricing, from, to))
+}
+
+/// Estimated cost of a row, or None if its executor has no price
+fn row_cost(row: &TokenUsageRow, pricing: &UsagePricingConfig) -> Option<f64> {
+    let price = pricing.price_for(&row.executor, row.variant.as_deref())?;
+    Some(
+        (row.input_tokens as f64 * price.input_per_million
+            + row.output_tokens as f64 * price.output_per_million)
+            / 1_000_000.0,
+    )
+}
+
+/// Aggregate usage rows and price them
+pub fn summarize(
+    rows: &[TokenUsageRow],
+    pricing: &UsagePricingConfig,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> UsageReport {
+    let mut totals = UsageTotals::default();
+    let mut by_executor: BTreeMap<(String, Option<String>), UsageTotals> = BTreeMap::new();
+    let mut by_project: BTreeMap<Option<Uuid>, (Option<String>, UsageTotals)> = BTreeMap::new();
+    let mut by_day: BTreeMap<String, UsageTotals> = BTreeMap::new();
+
+    for row in rows {
+        let cost = row_cost(row, pricing);
+        totals.add(row, cost);
+        by_executor
+            .entry((row.executor.clone(), row.variant.clone()))
+            .or_default()
+            .add(row, cost);
+        by_project
+            .entry(row.project_id)
+            .or_insert_with(|| (row.project_name.clone(), UsageTotals::default()))
+            .1
+            .add(row, cost);
+        by_day.entry(row.day.clone()).or_default().add(row, cost);
+    }
+
+    let mut by_executor: Vec<ExecutorUsage> = by_executor
+        .into_iter()
+        .map(|((executor, variant), totals)| ExecutorUsage {
+            executor,
+            variant,
+            totals,
+        })
+        .collect();
+    by_executor.sort_by(|a, b| {
+        b.totals
+            .estimated_cost_usd
+            .total_cmp(&a.totals.estimated_cost_usd)
+    });
+
+    let mut by_project: Vec<ProjectUsage> = by_project
+        .into_iter()
+        .map(|(project_id, (project_name, totals))| ProjectUsage {
+            project_id,
+            project_name,
+            totals,
+        })
+        .collect();
+    by_project.sort_by(|a, b| {
+        b.totals
+            .estimated_cost_usd
+            .total_cmp(&a.totals.estimated_cost_usd)
+    });
+
+    UsageReport {
+        from,
+        to,
+        totals,
+        by_executor,
+        by_project,
+        by_day: by_day
+            .into_iter()
+            .map(|(day, totals)| DailyUsage { day, totals })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        executor: &str,
+        project: Option<Uuid>,
+        day: &str,
+        input: i64,
+        output: i64,
+    ) -> TokenUsageRow {
+        TokenUsageRow {
+            executor: executor.to_string(),
+            variant: None,
+            project_id: project,
+            project_name: project.map(|_| "widgets".to_string()),
+            day: day.to_string(),
+            executions: 1,
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn aggregates_and_prices_usage() {
+        let project = Uuid::new_v4();
+        let rows = [
+            row(
+                "CLAUDE_CODE",
+                Some(project),
+                "2026-01-01",
+                1_000_000,
+                100_000,
+            ),
+            row("CLAUDE_CODE", None, "2026-01-02", 1_000_000, 0),
+            row("AMP", Some(project), "2026-01-02", 500, 500),
+        ];
+        let day = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let report = summarize(&rows, &UsagePricingConfig::default(), day, day);
+
+        // Claude Code is priced at $3 in / $15 out per million by default
+        assert_eq!(report.totals.executions, 3);
+        assert_eq!(report.totals.input_tokens, 2_000_500);
+        assert!((report.totals.estimated_cost_usd - 7.5).abs() < 1e-9);
+        assert_eq!(report.totals.unpriced_executions, 1);
+
+        assert_eq!(report.by_executor[0].executor, "CLAUDE_CODE");
+        assert_eq!(report.by_executor[1].totals.unpriced_executions, 1);
+        assert_eq!(report.by_project.len(), 2);
+        assert_eq!(
+            report
+                .by_day
+                .iter()
+                .map(|d| (d.day.as_str(), d.totals.executions))
+                .collect::<Vec<_>>(),
+            vec![("2026-01-01", 1), ("2026-01-02", 2)]
+        );
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Maintains one watcher per workspace path, with broadcast channels for multiple subscribers.
 //! This prevents the "too many open files" error when multiple browser tabs connect to the same workspace.
+//!
+//! Watches are backed by watchman when it is installed (see `WatcherBackend::detect`), falling
+//! back to the notify-based watcher otherwise.
 
 use std::{
     collections::HashMap,
@@ -17,7 +20,7 @@ use parking_lot::RwLock;
 use thiserror::Error;
 use tokio::sync::broadcast;
 
-use crate::services::filesystem_watcher::{self, FilesystemWatcherError};
+use crate::services::filesystem_watcher::{self, FilesystemWatcherError, WatcherBackend};
 
 /// Errors that can occur when subscribing to a watcher
 #[derive(Error, Debug)]
@@ -41,15 +44,22 @@ impl SharedWatcher {
 
 /// Manages shared filesystem watchers across workspaces.
 /// Thread-safe and can be cloned cheaply.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct WatcherManager {
     inner: Arc<WatcherManagerInner>,
 }
 
-#[derive(Default)]
+impl Default for WatcherManager {
+    fn default() -> Self {
+        Self::with_backend(WatcherBackend::detect())
+    }
+}
+
 struct WatcherManagerInner {
     /// Map from canonical workspace path to shared watcher
     watchers: RwLock<HashMap<PathBuf, Weak<SharedWatcher>>>,
+    /// Backend used when creating new watchers
+    backend: WatcherBackend,
 }
 
 /// A subscription handle that automatically unsubscribes when dropped.
@@ -102,6 +112,22 @@ impl WatcherManager {
         Self::default()
     }
 
+    /// Create a watcher manager that always uses the given backend
+    pub fn with_backend(backend: WatcherBackend) -> Self {
+        tracing::info!("Filesystem watcher backend: {:?}", backend);
+        Self {
+            inner: Arc::new(WatcherManagerInner {
+                watchers: RwLock::new(HashMap::new()),
+                backend,
+            }),
+        }
+    }
+
+    /// The backend used for new watchers
+    pub fn backend(&self) -> WatcherBackend {
+        self.inner.backend
+    }
+
     /// Subscribe to filesystem events for a workspace path.
     /// Creates a new watcher if one doesn't exist, or returns a subscription to the existing one.
     pub fn subscribe(
@@ -141,8 +167,8 @@ impl WatcherManager {
         }
 
         // Create new watcher
-        let (debouncer, watcher_rx, watcher_canonical) =
-            filesystem_watcher::async_watcher(root_path)?;
+        let (guard, watcher_rx, watcher_canonical) =
+            filesystem_watcher::async_watcher_with_backend(root_path, self.inner.backend)?;
 
         // Use a broadcast channel with reasonable capacity
         // 256 should be enough for bursts of file changes
@@ -153,11 +179,10 @@ impl WatcherManager {
         // Spawn background task to forward events from the filesystem watcher to the broadcast channel
         let tx_clone = tx.clone();
         let canonical_clone = watcher_canonical.clone();
-        let debouncer_arc = debouncer; // Keep debouncer alive
 
         std::thread::spawn(move || {
-            // Keep the debouncer alive for the lifetime of this thread
-            let _debouncer = debouncer_arc;
+            // Keep the underlying watcher alive for the lifetime of this thread
+            let _guard = guard;
             let mut rx = watcher_rx;
 
             loop {
@@ -167,7 +192,7 @@ impl WatcherManager {
                 // drop (causing FD leaks).
                 let result = futures::executor::block_on(async {
                     select! {
-                        event = rx.next().fuse() => event,
+                        event = rx.next().fuse() => Some(event),
                         _ = Delay::new(Duration::from_secs(30)).fuse() => None,
                     }
                 });
@@ -181,9 +206,18 @@ impl WatcherManager {
                     break;
                 }
 
-                // Forward event if we received one (not a timeout)
-                if let Some(event) = result {
-                    let _ = tx_clone.send(Arc::new(event));
+                match result {
+                    Some(Some(event)) => {
+                        let _ = tx_clone.send(Arc::new(event));
+                    }
+                    Some(None) => {
+                        // The backend stopped producing events (e.g. the watchman
+                        // process exited); nothing more will arrive on this watcher.
+                        tracing::warn!("Watcher stream ended for {:?}", canonical_clone);
+                        break;
+                    }
+                    // Timeout: loop around and re-check the subscriber count
+                    None => {}
                 }
             }
 
@@ -263,6 +297,16 @@ mod tests {
         // until next access
     }
 
+    #[test]
+    fn test_watchman_backend_falls_back_to_notify() {
+        // Whether or not watchman is installed, subscribing must succeed
+        let manager = WatcherManager::with_backend(WatcherBackend::Watchman);
+        let temp_dir = TempDir::new().unwrap();
+
+        let _sub = manager.subscribe(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(manager.active_watcher_count(), 1);
+    }
+
     #[tokio::test]
     async fn test_subscription_receives_events() {
         let manager = WatcherManager::new();
@@ -0,0 +1,353 @@
+//! Watchman-backed filesystem watching
+//!
+//! On very large repositories the notify backend has to register one inotify
+//! watch per directory (Linux) and walk the whole tree up front. When the
+//! `watchman` daemon is installed we can instead ask it for a subscription and
+//! let it do the heavy lifting. Events are translated into the same
+//! `DebounceEventResult` shape produced by `filesystem_watcher::async_watcher`
+//! so consumers don't need to know which backend is in use.
+use std::{
+    io::{BufRead, BufReader, Write as _},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex, OnceLock},
+    time::Instant,
+};
+
+use futures::{
+    SinkExt,
+    channel::mpsc::{Receiver, channel},
+};
+use ignore::gitignore::Gitignore;
+use notify::{
+    Event, EventKind,
+    event::{CreateKind, ModifyKind, RemoveKind},
+};
+use notify_debouncer_full::{DebounceEventResult, DebouncedEvent};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+use utils::shell::resolve_executable_path_blocking;
+
+use crate::services::filesystem_watcher::{
+    ALWAYS_SKIP_DIRS, FilesystemWatcherError, build_gitignore_set, canonicalize_lossy,
+    debounced_should_forward,
+};
+
+const SUBSCRIPTION_NAME: &str = "vibe-kanban";
+
+#[derive(Debug, Error)]
+pub enum WatchmanError {
+    #[error("watchman executable not found")]
+    NotAvailable,
+    #[error("watchman command failed: {0}")]
+    CommandFailed(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Watcher(#[from] FilesystemWatcherError),
+}
+
+pub type WatchmanComponents = (WatchmanSubscription, Receiver<DebounceEventResult>, PathBuf);
+
+/// Handle to a running `watchman` subscription process.
+/// The process is killed when the last clone of the handle is dropped.
+#[derive(Clone)]
+pub struct WatchmanSubscription {
+    child: Arc<Mutex<Child>>,
+}
+
+impl Drop for WatchmanSubscription {
+    fn drop(&mut self) {
+        if Arc::strong_count(&self.child) == 1
+            && let Ok(mut child) = self.child.lock()
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Returns true if a `watchman` binary is on PATH and responds to `--version`.
+/// The result is cached for the lifetime of the process.
+pub fn is_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let Some(watchman) = resolve_executable_path_blocking("watchman") else {
+            return false;
+        };
+        Command::new(watchman)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchProjectResponse {
+    watch: Option<PathBuf>,
+    relative_path: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionPdu {
+    #[serde(default)]
+    subscription: Option<String>,
+    #[serde(default)]
+    files: Vec<WatchmanFile>,
+    #[serde(default)]
+    is_fresh_instance: bool,
+    #[serde(default)]
+    canceled: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchmanFile {
+    name: String,
+    #[serde(default = "default_true")]
+    exists: bool,
+    #[serde(default)]
+    new: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Start a watchman subscription for `root`.
+///
+/// Mirrors `filesystem_watcher::async_watcher`: events are filtered through the
+/// repository's gitignore rules and `ALWAYS_SKIP_DIRS` before being forwarded.
+pub fn async_watcher(root: PathBuf) -> Result<WatchmanComponents, WatchmanError> {
+    let watchman =
+        resolve_executable_path_blocking("watchman").ok_or(WatchmanError::NotAvailable)?;
+    let canonical_root = canonicalize_lossy(&root);
+    let gi_set = build_gitignore_set(&canonical_root)?;
+
+    let (watch_root, relative_root) = watch_project(&watchman, &canonical_root)?;
+    let command = subscribe_command(&watch_root, relative_root.as_deref());
+
+    let mut child = Command::new(&watchman)
+        .args(["--no-pretty", "--json-command", "--persistent"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    {
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| WatchmanError::CommandFailed("stdin unavailable".to_string()))?;
+        serde_json::to_writer(&mut stdin, &command)?;
+        stdin.write_all(b"\n")?;
+    }
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| WatchmanError::CommandFailed("stdout unavailable".to_string()))?;
+
+    let (mut tx, rx) = channel::<DebounceEventResult>(64);
+    let root_for_thread = canonical_root.clone();
+
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let pdu: SubscriptionPdu = match serde_json::from_str(&line) {
+                Ok(pdu) => pdu,
+                Err(e) => {
+                    tracing::warn!("Failed to parse watchman response: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(error) = pdu.error {
+                tracing::warn!("watchman error for {:?}: {}", root_for_thread, error);
+                futures::executor::block_on(async {
+                    tx.send(Err(vec![notify::Error::generic(&error)]))
+                        .await
+                        .ok();
+                });
+                break;
+            }
+            if pdu.canceled {
+                tracing::debug!("watchman subscription canceled for {:?}", root_for_thread);
+                break;
+            }
+            if pdu.subscription.is_none() || pdu.is_fresh_instance {
+                continue;
+            }
+
+            let events = pdu_to_events(pdu.files, &root_for_thread, &gi_set);
+            if events.is_empty() {
+                continue;
+            }
+
+            let sent = futures::executor::block_on(async { tx.send(Ok(events)).await });
+            if sent.is_err() {
+                break;
+            }
+        }
+
+        tracing::debug!("watchman reader exiting for {:?}", root_for_thread);
+    });
+
+    tracing::debug!("Started watchman subscription for {:?}", canonical_root);
+
+    Ok((
+        WatchmanSubscription {
+            child: Arc::new(Mutex::new(child)),
+        },
+        rx,
+        canonical_root,
+    ))
+}
+
+/// Run `watchman watch-project` and return the watched project root together
+/// with the path of `root` relative to it, if watchman chose an ancestor.
+fn watch_project(watchman: &Path, root: &Path) -> Result<(PathBuf, Option<String>), WatchmanError> {
+    let output = Command::new(watchman)
+        .arg("--no-pretty")
+        .arg("watch-project")
+        .arg(root)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(WatchmanError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let response: WatchProjectResponse = serde_json::from_slice(&output.stdout)?;
+    if let Some(error) = response.error {
+        return Err(WatchmanError::CommandFailed(error));
+    }
+    let watch = response.watch.ok_or_else(|| {
+        WatchmanError::CommandFailed("watch-project returned no root".to_string())
+    })?;
+
+    Ok((watch, response.relative_path.filter(|p| !p.is_empty())))
+}
+
+fn subscribe_command(watch_root: &Path, relative_root: Option<&str>) -> serde_json::Value {
+    let mut skip_dirs = vec![json!("anyof")];
+    for dir in ALWAYS_SKIP_DIRS {
+        skip_dirs.push(json!(["dirname", dir]));
+        skip_dirs.push(json!(["name", dir]));
+    }
+
+    let mut query = json!({
+        "expression": ["not", skip_dirs],
+        "fields": ["name", "exists", "new"],
+        "empty_on_fresh_instance": true,
+        "defer_vcs": true,
+    });
+    // File names in subscription results are relative to `relative_root`,
+    // which lines them up with the workspace path we were asked to watch.
+    if let Some(relative_root) = relative_root {
+        query["relative_root"] = json!(relative_root);
+    }
+
+    json!(["subscribe", watch_root, SUBSCRIPTION_NAME, query])
+}
+
+fn pdu_to_events(files: Vec<WatchmanFile>, root: &Path, gi: &Gitignore) -> Vec<DebouncedEvent> {
+    let now = Instant::now();
+    files
+        .into_iter()
+        .map(|file| {
+            let kind = if !file.exists {
+                EventKind::Remove(RemoveKind::Any)
+            } else if file.new {
+                EventKind::Create(CreateKind::Any)
+            } else {
+                EventKind::Modify(ModifyKind::Any)
+            };
+            let event = Event::new(kind).add_path(root.join(&file.name));
+            DebouncedEvent::new(event, now)
+        })
+        .filter(|event| debounced_should_forward(event, gi, root))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ignore::gitignore::GitignoreBuilder;
+
+    use super::*;
+
+    fn file(name: &str, exists: bool, new: bool) -> WatchmanFile {
+        WatchmanFile {
+            name: name.to_string(),
+            exists,
+            new,
+        }
+    }
+
+    #[test]
+    fn test_pdu_to_events_maps_kinds() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = canonicalize_lossy(temp_dir.path());
+        let gi = GitignoreBuilder::new(&root).build().unwrap();
+
+        let events = pdu_to_events(
+            vec![
+                file("added.rs", true, true),
+                file("changed.rs", true, false),
+                file("deleted.rs", false, false),
+            ],
+            &root,
+            &gi,
+        );
+
+        assert_eq!(events.len(), 3);
+        assert!(events[0].kind.is_create());
+        assert!(events[1].kind.is_modify());
+        assert!(events[2].kind.is_remove());
+        assert_eq!(events[0].paths, vec![root.join("added.rs")]);
+    }
+
+    #[test]
+    fn test_pdu_to_events_respects_gitignore() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = canonicalize_lossy(temp_dir.path());
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        let gi = build_gitignore_set(&root).unwrap();
+
+        let events = pdu_to_events(
+            vec![
+                file("target/debug/out", true, false),
+                file("node_modules/pkg/index.js", true, false),
+                file("src/main.rs", true, false),
+            ],
+            &root,
+            &gi,
+        );
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].paths, vec![root.join("src/main.rs")]);
+    }
+
+    #[test]
+    fn test_subscribe_command_uses_relative_root() {
+        let command = subscribe_command(Path::new("/repo"), Some("packages/app"));
+        assert_eq!(command[1], json!("/repo"));
+        assert_eq!(command[3]["relative_root"], json!("packages/app"));
+
+        let command = subscribe_command(Path::new("/repo"), None);
+        assert_eq!(command[1], json!("/repo"));
+        assert!(command[3].get("relative_root").is_none());
+    }
+}
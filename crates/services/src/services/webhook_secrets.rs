@@ -0,0 +1,157 @@
+//! Signing secrets of webhooks.
+//!
+//! A webhook row only holds the [`SecretRef`] of the secret store entry with
+//! its HMAC key, so keys never sit in the database or its backups.
+
+use db::models::webhook::Webhook;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::secrets::{SecretRef, SecretService, SecretStoreError};
+
+/// Start of the names of store entries holding webhook secrets
+const ENTRY_PREFIX: &str = "webhook_secret.";
+
+/// Name of the store entry holding a webhook's signing secret
+pub fn entry_name(webhook_id: Uuid) -> String {
+    format!("{ENTRY_PREFIX}{webhook_id}")
+}
+
+/// Store `secret` as the webhook's signing secret. An empty secret removes
+/// it; None is returned then.
+pub async fn store(
+    secrets: &SecretService,
+    webhook_id: Uuid,
+    secret: &str,
+) -> Result<Option<SecretRef>, SecretStoreError> {
+    if secret.is_empty() {
+        secrets.delete(&entry_name(webhook_id)).await?;
+        return Ok(None);
+    }
+    secrets.set(&entry_name(webhook_id), secret).await.map(Some)
+}
+
+/// The webhook's signing secret; an error when it has one the store cannot
+/// return, so the delivery isn't sent unsigned
+pub async fn resolve(secrets: &SecretService, webhook: &Webhook) -> Result<Option<String>, String> {
+    let Some(secret_ref) = &webhook.secret_ref else {
+        return Ok(None);
+    };
+    secrets
+        .resolve(&SecretRef::new(secret_ref.as_str()))
+        .await
+        .map(Some)
+        .ok_or_else(|| format!("Signing secret '{secret_ref}' is missing from the secret store"))
+}
+
+/// Move secrets saved in the database by older versions into the store. A
+/// secret that cannot be stored stays in the database and is retried at the
+/// next start.
+pub async fn migrate_plaintext(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+) -> Result<(), sqlx::Error> {
+    for (webhook_id, secret) in Webhook::find_plaintext_secrets(pool).await? {
+        match secrets.set(&entry_name(webhook_id), &secret).await {
+            Ok(secret_ref) => Webhook::set_secret_ref(pool, webhook_id, secret_ref.name()).await?,
+            Err(e) => tracing::warn!(
+                "Could not move the secret of webhook {} into the secret store: {}",
+                webhook_id,
+                e
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Delete store entries no webhook refers to any more. Returns how many were
+/// deleted.
+pub async fn remove_orphans(
+    pool: &SqlitePool,
+    secrets: &SecretService,
+) -> Result<usize, sqlx::Error> {
+    let entries = match secrets.list().await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("Failed to list the secret store: {}", e);
+            return Ok(0);
+        }
+    };
+
+    let mut removed = 0;
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.starts_with(ENTRY_PREFIX))
+    {
+        if Webhook::secret_ref_exists(pool, entry).await? {
+            continue;
+        }
+        match secrets.delete(entry).await {
+            Ok(deleted) => removed += usize::from(deleted),
+            Err(e) => tracing::warn!("Failed to delete secret '{}': {}", entry, e),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::services::secrets::{MemoryStore, SecretStore};
+
+    async fn create_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn plaintext_secrets_move_into_the_store() {
+        let pool = create_test_db().await;
+        let store = Arc::new(MemoryStore::default());
+        let secrets = SecretService::with_store(store.clone());
+
+        let webhook_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO webhooks (id, name, url, secret, events)
+             VALUES (?, 'CI', 'https://ci.example.com/hook', 'hunter2', '[]')",
+        )
+        .bind(webhook_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        migrate_plaintext(&pool, &secrets).await.unwrap();
+
+        let entry = entry_name(webhook_id);
+        assert_eq!(store.get(&entry).unwrap().as_deref(), Some("hunter2"));
+        let stored: Option<String> = sqlx::query_scalar("SELECT secret FROM webhooks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, None);
+
+        let webhook = Webhook::find_by_id(&pool, webhook_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(webhook.has_secret);
+        assert_eq!(
+            resolve(&secrets, &webhook).await.unwrap().as_deref(),
+            Some("hunter2")
+        );
+
+        // The entry outlives its webhook until orphans are removed
+        Webhook::delete(&pool, webhook_id).await.unwrap();
+        assert_eq!(remove_orphans(&pool, &secrets).await.unwrap(), 1);
+        assert!(store.list().unwrap().is_empty());
+    }
+}
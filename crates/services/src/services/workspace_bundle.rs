@@ -0,0 +1,547 @@
+//! Exporting a workspace as a tarball that can be imported on another machine.
+//!
+//! A bundle holds `manifest.json`, a git bundle of the workspace branch for
+//! every repo under `repos/`, `prompts.json` with what was asked of the coding
+//! agent, and `log.jsonl` with the normalized log of every coding agent run.
+//! Only committed work is bundled. Importing recreates the task, fetches the
+//! branch into the project's repos and creates a workspace on it with the
+//! agent runs and their logs, so the attempt can be continued with a
+//! follow-up.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
+    execution_process::{
+        CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
+    execution_process_normalized_entry::ExecutionProcessNormalizedEntry,
+    project::Project,
+    project_repo::ProjectRepo,
+    scratch::{Scratch, ScratchError, ScratchPayload},
+    session::{CreateSession, Session, SessionError},
+    task::{CreateTask, Task},
+    workspace::{CreateWorkspace, Workspace},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+    },
+    logs::NormalizedEntry,
+    profile::ExecutorProfileId,
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    git::{GitService, GitServiceError},
+    imported_notes,
+};
+
+/// Bumped whenever a bundle written by this version can't be read by an older one
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const PROMPTS_FILE: &str = "prompts.json";
+const LOG_FILE: &str = "log.jsonl";
+const REPOS_DIR: &str = "repos";
+
+#[derive(Debug, Error)]
+pub enum WorkspaceBundleError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Git(#[from] GitServiceError),
+    #[error(transparent)]
+    Scratch(#[from] ScratchError),
+    #[error(transparent)]
+    Session(#[from] SessionError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Failed to copy the normalized log: {0}")]
+    Log(anyhow::Error),
+    #[error("Not a workspace bundle: {0}")]
+    Invalid(String),
+    #[error("Bundle format {0} is newer than this version supports ({BUNDLE_FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+    #[error("The project has no repository named '{0}'")]
+    MissingRepository(String),
+    #[error("Branch '{0}' already exists in repository '{1}'")]
+    BranchExists(String, String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub task_title: String,
+    pub task_description: Option<String>,
+    /// Branch the work was done on, the same in every repo
+    pub branch: String,
+    pub agent_working_dir: Option<String>,
+    pub repos: Vec<BundleRepo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleRepo {
+    /// Matched against the names of the importing project's repos; the git
+    /// bundle is `repos/<name>.bundle`
+    pub name: String,
+    pub target_branch: String,
+    /// Commit the branch pointed at when it was exported
+    pub head_commit: String,
+}
+
+/// A prompt sent to the coding agent, in the order they were sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundlePrompt {
+    pub execution_process_id: Uuid,
+    pub executor: Option<String>,
+    /// Profile the agent ran with; only prompts that have one are recreated
+    /// as agent runs on import
+    #[serde(default)]
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub prompt: String,
+    /// The agent's final message
+    pub summary: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One line of `log.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleLogLine {
+    execution_process_id: Uuid,
+    entry_index: i64,
+    entry: NormalizedEntry,
+}
+
+/// The task and workspace created from a bundle
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct ImportedWorkspace {
+    pub task: Task,
+    pub workspace: Workspace,
+}
+
+/// The gzipped tarball of `workspace`
+pub async fn export_workspace(
+    pool: &SqlitePool,
+    git: &GitService,
+    workspace: &Workspace,
+) -> Result<Vec<u8>, WorkspaceBundleError> {
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    let repos =
+        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?;
+
+    let mut prompts = Vec::new();
+    let mut log = String::new();
+    for session in Session::find_by_workspace_id(pool, workspace.id).await? {
+        for process in ExecutionProcess::find_by_session_id(pool, session.id, false).await? {
+            if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+                continue;
+            }
+            if let Some(turn) =
+                CodingAgentTurn::find_by_execution_process_id(pool, process.id).await?
+                && let Some(prompt) = turn.prompt
+            {
+                let executor_profile_id =
+                    process
+                        .executor_action()
+                        .ok()
+                        .and_then(|action| match action.typ() {
+                            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                                Some(request.executor_profile_id.clone())
+                            }
+                            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                                Some(request.executor_profile_id.clone())
+                            }
+                            ExecutorActionType::ScriptRequest(_) => None,
+                        });
+                prompts.push(BundlePrompt {
+                    execution_process_id: process.id,
+                    executor: session.executor.clone(),
+                    executor_profile_id,
+                    prompt,
+                    summary: turn.summary,
+                    created_at: process.created_at,
+                });
+            }
+            let entries =
+                ExecutionProcessNormalizedEntry::fetch_all_for_execution(pool, process.id)
+                    .await
+                    .map_err(WorkspaceBundleError::Log)?;
+            for entry in entries {
+                let line = BundleLogLine {
+                    execution_process_id: process.id,
+                    entry_index: entry.entry_index,
+                    entry: entry.entry,
+                };
+                log.push_str(&serde_json::to_string(&line).map_err(std::io::Error::from)?);
+                log.push('\n');
+            }
+        }
+    }
+    prompts.sort_by_key(|prompt| prompt.created_at);
+
+    let scratch_dir = tempfile::tempdir()?;
+    let mut manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        task_title: task.title,
+        task_description: task.description,
+        branch: workspace.branch.clone(),
+        agent_working_dir: workspace.agent_working_dir.clone(),
+        repos: Vec::with_capacity(repos.len()),
+    };
+    let mut tarball = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for repo in repos {
+        let bundle_path = scratch_dir
+            .path()
+            .join(format!("{}.bundle", repo.repo.name));
+        git.create_bundle(&repo.repo.path, &bundle_path, &workspace.branch)?;
+        tarball.append_path_with_name(
+            &bundle_path,
+            format!("{REPOS_DIR}/{}.bundle", repo.repo.name),
+        )?;
+        manifest.repos.push(BundleRepo {
+            head_commit: git.get_branch_oid(&repo.repo.path, &workspace.branch)?,
+            name: repo.repo.name,
+            target_branch: repo.target_branch,
+        });
+    }
+
+    append_file(
+        &mut tarball,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::from)?,
+    )?;
+    append_file(
+        &mut tarball,
+        PROMPTS_FILE,
+        &serde_json::to_vec_pretty(&prompts).map_err(std::io::Error::from)?,
+    )?;
+    append_file(&mut tarball, LOG_FILE, log.as_bytes())?;
+
+    Ok(tarball.into_inner()?.finish()?)
+}
+
+/// Recreate the attempt in `bundle` as a new task of `project`, with a
+/// workspace on the bundled branch. The agent runs are recreated as completed
+/// runs of one session, with their logs, and the prompts and the agent's
+/// replies are also saved as the task's notes.
+pub async fn import_workspace(
+    pool: &SqlitePool,
+    git: &GitService,
+    project: &Project,
+    bundle: &[u8],
+) -> Result<ImportedWorkspace, WorkspaceBundleError> {
+    let unpacked = tempfile::tempdir()?;
+    tar::Archive::new(GzDecoder::new(bundle))
+        .unpack(unpacked.path())
+        .map_err(|e| WorkspaceBundleError::Invalid(e.to_string()))?;
+
+    let manifest: BundleManifest = read_json(unpacked.path(), MANIFEST_FILE)?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(WorkspaceBundleError::UnsupportedVersion(
+            manifest.format_version,
+        ));
+    }
+    let prompts: Vec<BundlePrompt> = read_json(unpacked.path(), PROMPTS_FILE)?;
+    let log = read_log(unpacked.path())?;
+
+    let project_repos = ProjectRepo::find_repos_for_project(pool, project.id).await?;
+    let mut matched = Vec::with_capacity(manifest.repos.len());
+    for bundled in &manifest.repos {
+        if bundled.name.contains(['/', '\\']) || bundled.name.starts_with('.') {
+            return Err(WorkspaceBundleError::Invalid(format!(
+                "bad repository name '{}'",
+                bundled.name
+            )));
+        }
+        let repo = project_repos
+            .iter()
+            .find(|repo| repo.name == bundled.name)
+            .ok_or_else(|| WorkspaceBundleError::MissingRepository(bundled.name.clone()))?;
+        if git.check_branch_exists(&repo.path, &manifest.branch)? {
+            return Err(WorkspaceBundleError::BranchExists(
+                manifest.branch.clone(),
+                repo.display_name.clone(),
+            ));
+        }
+        matched.push((repo, bundled));
+    }
+
+    for (repo, bundled) in &matched {
+        let bundle_path = unpacked
+            .path()
+            .join(REPOS_DIR)
+            .join(format!("{}.bundle", bundled.name));
+        git.fetch_bundle(&repo.path, &bundle_path, &manifest.branch)?;
+    }
+
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(
+            project.id,
+            manifest.task_title.clone(),
+            manifest.task_description.clone(),
+        ),
+        Uuid::new_v4(),
+    )
+    .await?;
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch: manifest.branch.clone(),
+            agent_working_dir: manifest.agent_working_dir.clone(),
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await?;
+    let workspace_repos: Vec<CreateWorkspaceRepo> = matched
+        .iter()
+        .map(|(repo, bundled)| CreateWorkspaceRepo {
+            repo_id: repo.id,
+            target_branch: bundled.target_branch.clone(),
+        })
+        .collect();
+    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+    import_runs(
+        pool,
+        workspace.id,
+        manifest.agent_working_dir.as_deref(),
+        &prompts,
+        log,
+    )
+    .await?;
+
+    if !prompts.is_empty() {
+        Scratch::save_if_version(
+            pool,
+            task.id,
+            &ScratchPayload::TaskNotes(import_notes(&manifest, &prompts)),
+            0,
+        )
+        .await?;
+    }
+
+    Ok(ImportedWorkspace { task, workspace })
+}
+
+/// Recreate every prompt with a known profile as a completed agent run of a
+/// new session, with its normalized log. Runs carry no agent session ID, so
+/// the next follow-up starts the agent afresh in the imported worktree.
+async fn import_runs(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+    working_dir: Option<&str>,
+    prompts: &[BundlePrompt],
+    mut log: HashMap<Uuid, Vec<BundleLogLine>>,
+) -> Result<(), WorkspaceBundleError> {
+    let runs: Vec<(&BundlePrompt, &ExecutorProfileId)> = prompts
+        .iter()
+        .filter_map(|prompt| Some((prompt, prompt.executor_profile_id.as_ref()?)))
+        .collect();
+    let Some((first, _)) = runs.first() else {
+        return Ok(());
+    };
+
+    let session = Session::create(
+        pool,
+        &CreateSession {
+            executor: first.executor.clone(),
+        },
+        Uuid::new_v4(),
+        workspace_id,
+    )
+    .await?;
+    for (prompt, executor_profile_id) in runs {
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: prompt.prompt.clone(),
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir: working_dir.map(str::to_string),
+            }),
+            None,
+        );
+        let process = ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await?;
+        CodingAgentTurn::create(
+            pool,
+            &CreateCodingAgentTurn {
+                execution_process_id: process.id,
+                prompt: Some(prompt.prompt.clone()),
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        if let Some(summary) = &prompt.summary {
+            CodingAgentTurn::update_summary(pool, process.id, summary).await?;
+        }
+        for line in log.remove(&prompt.execution_process_id).unwrap_or_default() {
+            ExecutionProcessNormalizedEntry::upsert(
+                pool,
+                process.id,
+                line.entry_index,
+                &line.entry,
+            )
+            .await
+            .map_err(WorkspaceBundleError::Log)?;
+        }
+        ExecutionProcess::update_completion(
+            pool,
+            process.id,
+            ExecutionProcessStatus::Completed,
+            Some(0),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+fn append_file<W: Write>(
+    tarball: &mut tar::Builder<W>,
+    name: &str,
+    contents: &[u8],
+) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp() as u64);
+    header.set_cksum();
+    tarball.append_data(&mut header, name, contents)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(
+    dir: &Path,
+    name: &str,
+) -> Result<T, WorkspaceBundleError> {
+    let mut contents = String::new();
+    std::fs::File::open(dir.join(name))
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|e| WorkspaceBundleError::Invalid(format!("{name}: {e}")))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| WorkspaceBundleError::Invalid(format!("{name}: {e}")))
+}
+
+/// Lines of `log.jsonl`, by the exported ID of their execution process
+fn read_log(dir: &Path) -> Result<HashMap<Uuid, Vec<BundleLogLine>>, WorkspaceBundleError> {
+    let contents = std::fs::read_to_string(dir.join(LOG_FILE))
+        .map_err(|e| WorkspaceBundleError::Invalid(format!("{LOG_FILE}: {e}")))?;
+    let mut log: HashMap<Uuid, Vec<BundleLogLine>> = HashMap::new();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let line: BundleLogLine = serde_json::from_str(line)
+            .map_err(|e| WorkspaceBundleError::Invalid(format!("{LOG_FILE}: {e}")))?;
+        log.entry(line.execution_process_id).or_default().push(line);
+    }
+    Ok(log)
+}
+
+/// Markdown notes recording what was asked of the agent before the import
+fn import_notes(manifest: &BundleManifest, prompts: &[BundlePrompt]) -> String {
+    let mut notes = format!(
+        "## Imported attempt\n\nExported {} from branch `{}`.\n",
+        manifest.exported_at.format("%Y-%m-%d %H:%M UTC"),
+        manifest.branch
+    );
+    for (i, prompt) in prompts.iter().enumerate() {
+        notes.push_str(&format!("\n### Prompt {}", i + 1));
+        if let Some(executor) = &prompt.executor {
+            notes.push_str(&format!(" ({executor})"));
+        }
+        notes.push('\n');
+        imported_notes::push_turn(&mut notes, Some(&prompt.prompt), prompt.summary.as_deref());
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn notes_list_prompts_and_replies() {
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap(),
+            task_title: "Fix login".to_string(),
+            task_description: None,
+            branch: "vk/1a2b-fix-login".to_string(),
+            agent_working_dir: None,
+            repos: vec![],
+        };
+        let prompt = |text: &str, summary: Option<&str>| BundlePrompt {
+            execution_process_id: Uuid::new_v4(),
+            executor: Some("CLAUDE_CODE".to_string()),
+            executor_profile_id: None,
+            prompt: text.to_string(),
+            summary: summary.map(str::to_string),
+            created_at: Utc::now(),
+        };
+
+        let notes = import_notes(
+            &manifest,
+            &[
+                prompt("Fix the login bug\nin auth.rs", Some("Fixed it")),
+                prompt("Add a test", None),
+            ],
+        );
+        assert_eq!(
+            notes,
+            "## Imported attempt\n\n\
+             Exported 2026-03-01 09:30 UTC from branch `vk/1a2b-fix-login`.\n\n\
+             ### Prompt 1 (CLAUDE_CODE)\n\n\
+             > Fix the login bug\n\
+             > in auth.rs\n\n\
+             **Agent:**\n\n\
+             > Fixed it\n\n\
+             ### Prompt 2 (CLAUDE_CODE)\n\n\
+             > Add a test\n"
+        );
+    }
+
+    #[test]
+    fn files_round_trip_through_the_tarball() {
+        let mut tarball = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        append_file(&mut tarball, PROMPTS_FILE, b"[]").unwrap();
+        append_file(&mut tarball, LOG_FILE, b"").unwrap();
+        let bytes = tarball.into_inner().unwrap().finish().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        tar::Archive::new(GzDecoder::new(bytes.as_slice()))
+            .unpack(dir.path())
+            .unwrap();
+        let prompts: Vec<BundlePrompt> = read_json(dir.path(), PROMPTS_FILE).unwrap();
+        assert!(prompts.is_empty());
+        assert!(read_log(dir.path()).unwrap().is_empty());
+        assert!(matches!(
+            read_json::<BundleManifest>(dir.path(), MANIFEST_FILE),
+            Err(WorkspaceBundleError::Invalid(_))
+        ));
+    }
+}
@@ -1,12 +1,21 @@
 use std::path::{Path, PathBuf};
 
-use db::models::{repo::Repo, workspace::Workspace as DbWorkspace};
+use db::models::{
+    conversation_worktree::ConversationWorktree, repo::Repo, workspace::Workspace as DbWorkspace,
+};
+use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
+use ts_rs::TS;
+use utils::path::remove_dir_all_async;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
+use super::{
+    git::GitCli,
+    worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager, WorktreeRepair},
+};
 
 #[derive(Debug, Clone)]
 pub struct RepoWorkspaceInput {
@@ -44,6 +53,16 @@ pub struct RepoWorktree {
     pub worktree_path: PathBuf,
 }
 
+/// Outcome of repairing one repo's worktree within a workspace
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct RepoWorktreeRepair {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub repair: WorktreeRepair,
+}
+
 /// A container directory holding worktrees for all project repos
 #[derive(Debug, Clone)]
 pub struct WorktreeContainer {
@@ -99,6 +118,7 @@ impl WorkspaceManager {
             .await
             {
                 Ok(()) => {
+                    Self::fetch_submodules_and_lfs(&input.repo, &worktree_path).await;
                     created_worktrees.push(RepoWorktree {
                         repo_id: input.repo.id,
                         repo_name: input.repo.name.clone(),
@@ -142,6 +162,35 @@ impl WorkspaceManager {
         })
     }
 
+    /// Check out the submodules and LFS objects a new worktree uses, unless
+    /// the repo opts out. Failures are logged rather than returned, since the
+    /// rest of the checkout is still usable.
+    async fn fetch_submodules_and_lfs(repo: &Repo, worktree_path: &Path) {
+        let init_submodules = !repo.skip_submodules && worktree_path.join(".gitmodules").is_file();
+        let pull_lfs = !repo.skip_lfs && uses_lfs(worktree_path).await;
+        if !init_submodules && !pull_lfs {
+            return;
+        }
+
+        let (repo_name, worktree_path) = (repo.name.clone(), worktree_path.to_path_buf());
+        let result = tokio::task::spawn_blocking(move || {
+            let git = GitCli::new();
+            if init_submodules && let Err(e) = git.submodule_update(&worktree_path) {
+                warn!(
+                    "Failed to update submodules for repo '{}': {}",
+                    repo_name, e
+                );
+            }
+            if pull_lfs && let Err(e) = git.lfs_pull(&worktree_path) {
+                warn!("Failed to pull LFS objects for repo '{}': {}", repo_name, e);
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            warn!("Submodule and LFS checkout task failed: {}", e);
+        }
+    }
+
     /// Ensure all worktrees in a workspace exist (for cold restart scenarios)
     pub async fn ensure_workspace_exists(
         workspace_dir: &Path,
@@ -183,6 +232,35 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Check every worktree of a workspace for breakage and repair it
+    pub async fn repair_workspace(
+        workspace_dir: &Path,
+        repos: &[Repo],
+        branch_name: &str,
+    ) -> Result<Vec<RepoWorktreeRepair>, WorkspaceError> {
+        if repos.is_empty() {
+            return Err(WorkspaceError::NoRepositories);
+        }
+
+        if !workspace_dir.exists() {
+            tokio::fs::create_dir_all(workspace_dir).await?;
+        }
+
+        let mut repairs = Vec::with_capacity(repos.len());
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            let repair =
+                WorktreeManager::repair_worktree(&repo.path, branch_name, &worktree_path).await?;
+            repairs.push(RepoWorktreeRepair {
+                repo_id: repo.id,
+                repo_name: repo.name.clone(),
+                repair,
+            });
+        }
+
+        Ok(repairs)
+    }
+
     /// Clean up all worktrees in a workspace
     pub async fn cleanup_workspace(
         workspace_dir: &Path,
@@ -202,7 +280,7 @@ impl WorkspaceManager {
 
         // Remove the workspace directory itself
         if workspace_dir.exists()
-            && let Err(e) = tokio::fs::remove_dir_all(workspace_dir).await
+            && let Err(e) = remove_dir_all_async(workspace_dir).await
         {
             debug!(
                 "Could not remove workspace directory {}: {}",
@@ -266,7 +344,7 @@ impl WorkspaceManager {
         WorktreeManager::move_worktree(&repo.path, &temp_path, &expected_worktree_path).await?;
 
         if temp_path.exists() {
-            let _ = tokio::fs::remove_dir_all(&temp_path).await;
+            let _ = remove_dir_all_async(&temp_path).await;
         }
 
         info!(
@@ -338,6 +416,12 @@ impl WorkspaceManager {
             }
 
             let workspace_path_str = path.to_string_lossy().to_string();
+            // Conversations keep their worktrees in the same base directory
+            if let Ok(true) =
+                ConversationWorktree::container_ref_exists(db, &workspace_path_str).await
+            {
+                continue;
+            }
             if let Ok(false) = DbWorkspace::container_ref_exists(db, &workspace_path_str).await {
                 info!("Found orphaned workspace: {}", workspace_path_str);
                 if let Err(e) = Self::cleanup_workspace_without_repos(&path).await {
@@ -385,7 +469,7 @@ impl WorkspaceManager {
         }
 
         if workspace_dir.exists()
-            && let Err(e) = tokio::fs::remove_dir_all(workspace_dir).await
+            && let Err(e) = remove_dir_all_async(workspace_dir).await
         {
             debug!(
                 "Could not remove workspace directory {}: {}",
@@ -397,3 +481,11 @@ impl WorkspaceManager {
         Ok(())
     }
 }
+
+/// Whether the worktree's top-level `.gitattributes` routes any paths through
+/// the LFS filter
+async fn uses_lfs(worktree_path: &Path) -> bool {
+    tokio::fs::read_to_string(worktree_path.join(".gitattributes"))
+        .await
+        .is_ok_and(|attributes| attributes.contains("filter=lfs"))
+}
@@ -3,12 +3,19 @@ use std::{
     fs,
     path::{Path, PathBuf},
     sync::{Arc, LazyLock, Mutex},
+    time::Duration,
 };
 
 use git2::{Error as GitError, Repository};
+use serde::Serialize;
 use thiserror::Error;
-use tracing::{debug, info, trace};
-use utils::{path::normalize_macos_private_alias, shell::resolve_executable_path};
+use tracing::{debug, info, trace, warn};
+use ts_rs::TS;
+use utils::{
+    path::{self, normalize_macos_private_alias},
+    shell::resolve_executable_path,
+};
+use utoipa::ToSchema;
 
 use super::git::{GitService, GitServiceError};
 
@@ -16,6 +23,35 @@ use super::git::{GitService, GitServiceError};
 static WORKTREE_CREATION_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+/// Lock files older than this are assumed to be left by a git process that died
+const STALE_LOCK_AGE: Duration = Duration::from_secs(120);
+
+/// Lock files git keeps in a worktree's admin directory during an operation
+const WORKTREE_LOCK_FILES: &[&str] = &["index.lock", "HEAD.lock"];
+
+/// Breakage found in a worktree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum WorktreeIssue {
+    /// The worktree directory does not exist
+    MissingDirectory,
+    /// The worktree's `.git` file is missing or points at a missing admin directory
+    BrokenGitFile,
+    /// The repository has no worktree registered at this path
+    NotRegistered,
+    /// A lock file left behind by a git process that did not finish
+    StaleLock { path: String },
+}
+
+/// Outcome of checking and repairing one worktree
+#[derive(Debug, Clone, Default, Serialize, TS, ToSchema)]
+pub struct WorktreeRepair {
+    pub issues: Vec<WorktreeIssue>,
+    /// The worktree was pruned and re-added, discarding uncommitted changes
+    pub recreated: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct WorktreeCleanup {
     pub worktree_path: PathBuf,
@@ -137,27 +173,118 @@ impl WorktreeManager {
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
-        // Get or create a lock for this specific worktree path
-        let lock = {
-            let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
-            locks
-                .entry(path_str.clone())
-                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
-                .clone()
-        };
-
         // Acquire the lock for this specific worktree path
+        let lock = Self::path_lock(&path_str);
         let _guard = lock.lock().await;
 
         // Check if worktree already exists and is properly set up
-        if Self::is_worktree_properly_set_up(repo_path, worktree_path).await? {
+        let issues = Self::check_worktree_integrity(repo_path, worktree_path, false).await?;
+        if issues.is_empty() {
             trace!("Worktree already properly set up at path: {}", path_str);
             return Ok(());
         }
 
-        // If worktree doesn't exist or isn't properly set up, recreate it
-        info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        // Repair what is broken, recreating the worktree if needed
+        info!("Worktree at path {} needs repair: {:?}", path_str, issues);
+        Self::heal_worktree(repo_path, branch_name, worktree_path, &issues).await?;
+        Ok(())
+    }
+
+    /// Check a worktree for breakage and repair it. Unlike
+    /// [`Self::ensure_worktree_exists`], lock files are removed regardless of
+    /// their age, so no git process may be running in the worktree.
+    pub async fn repair_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+    ) -> Result<WorktreeRepair, WorktreeError> {
+        let lock = Self::path_lock(&worktree_path.to_string_lossy());
+        let _guard = lock.lock().await;
+
+        let issues = Self::check_worktree_integrity(repo_path, worktree_path, true).await?;
+        if issues.is_empty() {
+            return Ok(WorktreeRepair::default());
+        }
+
+        info!(
+            "Repairing worktree at {}: {:?}",
+            worktree_path.display(),
+            issues
+        );
+        let recreated = Self::heal_worktree(repo_path, branch_name, worktree_path, &issues).await?;
+        Ok(WorktreeRepair { issues, recreated })
+    }
+
+    /// Per-path lock shared by worktree creation, repair and cleanup
+    fn path_lock(path_str: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
+        locks
+            .entry(path_str.to_string())
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone()
+    }
+
+    /// Remove stale locks, let `git worktree repair` fix broken links, and
+    /// prune and re-add the worktree when that is not enough. Returns whether
+    /// the worktree was recreated.
+    async fn heal_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+        issues: &[WorktreeIssue],
+    ) -> Result<bool, WorktreeError> {
+        for issue in issues {
+            if let WorktreeIssue::StaleLock { path } = issue {
+                warn!("Removing stale git lock file {}", path);
+                if let Err(e) = tokio::fs::remove_file(path).await
+                    && e.kind() != std::io::ErrorKind::NotFound
+                {
+                    return Err(WorktreeError::Io(e));
+                }
+            }
+        }
+
+        if issues.contains(&WorktreeIssue::MissingDirectory) {
+            Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await?;
+            return Ok(true);
+        }
+
+        let broken_link = issues.iter().any(|issue| {
+            matches!(
+                issue,
+                WorktreeIssue::BrokenGitFile | WorktreeIssue::NotRegistered
+            )
+        });
+        if !broken_link {
+            return Ok(false);
+        }
+
+        let repo_path_owned = repo_path.to_path_buf();
+        let worktree_path_owned = worktree_path.to_path_buf();
+        let repaired = tokio::task::spawn_blocking(move || {
+            GitService::new().repair_worktree(&repo_path_owned, &worktree_path_owned)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?;
+        match repaired {
+            Ok(()) => {
+                if Self::check_worktree_integrity(repo_path, worktree_path, false)
+                    .await?
+                    .is_empty()
+                {
+                    info!("Repaired worktree links at {}", worktree_path.display());
+                    return Ok(false);
+                }
+                debug!(
+                    "git worktree repair left {} broken",
+                    worktree_path.display()
+                );
+            }
+            Err(e) => debug!("git worktree repair non-fatal error: {}", e),
+        }
+
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await?;
+        Ok(true)
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -197,33 +324,97 @@ impl WorktreeManager {
         .await
     }
 
-    /// Check if a worktree is properly set up (filesystem + git metadata)
-    async fn is_worktree_properly_set_up(
+    /// Detect common worktree breakage. Lock files only count once older than
+    /// [`STALE_LOCK_AGE`] unless `include_fresh_locks` is set.
+    async fn check_worktree_integrity(
         repo_path: &Path,
         worktree_path: &Path,
-    ) -> Result<bool, WorktreeError> {
+        include_fresh_locks: bool,
+    ) -> Result<Vec<WorktreeIssue>, WorktreeError> {
         let repo_path = repo_path.to_path_buf();
         let worktree_path = worktree_path.to_path_buf();
 
-        tokio::task::spawn_blocking(move || -> Result<bool, WorktreeError> {
+        tokio::task::spawn_blocking(move || {
             // Check 1: Filesystem path must exist
             if !worktree_path.exists() {
-                return Ok(false);
+                return vec![WorktreeIssue::MissingDirectory];
             }
 
-            // Check 2: Worktree must be registered in git metadata using find_worktree
-            let repo = Repository::open(&repo_path).map_err(WorktreeError::Git)?;
-            let worktree_name = Self::find_worktree_git_internal_name(&repo_path, &worktree_path)?
-                .ok_or_else(|| WorktreeError::InvalidPath("Invalid worktree path".to_string()))?;
+            // Check 2: The `.git` file must point at an existing admin directory
+            let mut issues = Vec::new();
+            let admin_dir = Self::worktree_admin_dir(&worktree_path);
+            if admin_dir.is_none() {
+                issues.push(WorktreeIssue::BrokenGitFile);
+            }
 
-            // Try to find the worktree - if it exists and is valid, we're good
-            match repo.find_worktree(&worktree_name) {
-                Ok(_) => Ok(true),
-                Err(_) => Ok(false),
+            // Check 3: Worktree must be registered in git metadata
+            let registered = Self::find_worktree_git_internal_name(&repo_path, &worktree_path)
+                .ok()
+                .flatten()
+                .is_some_and(|name| {
+                    Repository::open(&repo_path)
+                        .and_then(|repo| repo.find_worktree(&name))
+                        .is_ok()
+                });
+            if !registered {
+                issues.push(WorktreeIssue::NotRegistered);
             }
+
+            // Check 4: No lock files left behind by a crashed git process
+            if let Some(admin_dir) = admin_dir {
+                for name in WORKTREE_LOCK_FILES {
+                    let lock = admin_dir.join(name);
+                    if Self::is_stale_lock(&lock, include_fresh_locks) {
+                        issues.push(WorktreeIssue::StaleLock {
+                            path: lock.to_string_lossy().to_string(),
+                        });
+                    }
+                }
+            }
+
+            issues
         })
         .await
-        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))
+    }
+
+    /// Directories a commit in `worktree_path` writes to: the worktree's own
+    /// admin directory and the objects, refs and reflogs of `repo_path`.
+    /// The repo's hooks and config are left out on purpose, as they run on
+    /// the host on the next git operation.
+    pub fn commit_dirs(repo_path: &Path, worktree_path: &Path) -> Vec<PathBuf> {
+        let git_dir = repo_path.join(".git");
+        let mut dirs: Vec<PathBuf> = ["objects", "refs", "logs"]
+            .iter()
+            .map(|name| git_dir.join(name))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        dirs.extend(Self::worktree_admin_dir(worktree_path));
+        dirs
+    }
+
+    /// Admin directory the worktree's `.git` file points at, if it exists
+    fn worktree_admin_dir(worktree_path: &Path) -> Option<PathBuf> {
+        let content = fs::read_to_string(worktree_path.join(".git")).ok()?;
+        let gitdir = Path::new(content.trim().strip_prefix("gitdir:")?.trim());
+        let admin_dir = if gitdir.is_absolute() {
+            gitdir.to_path_buf()
+        } else {
+            worktree_path.join(gitdir)
+        };
+        admin_dir.is_dir().then_some(admin_dir)
+    }
+
+    fn is_stale_lock(lock: &Path, include_fresh: bool) -> bool {
+        let Ok(metadata) = fs::metadata(lock) else {
+            return false;
+        };
+        include_fresh
+            || metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age >= STALE_LOCK_AGE)
     }
 
     fn find_worktree_git_internal_name(
@@ -285,7 +476,7 @@ impl WorktreeManager {
                 "Removing existing worktree directory: {}",
                 worktree_path.display()
             );
-            std::fs::remove_dir_all(worktree_path).map_err(WorktreeError::Io)?;
+            path::remove_dir_all(worktree_path).map_err(WorktreeError::Io)?;
         }
 
         // Step 4: Good-practice to clean up any other stale admin entries
@@ -386,7 +577,7 @@ impl WorktreeManager {
                     // Clean up physical directory if it exists
                     // Needed if previous attempt failed after directory creation
                     if worktree_path.exists() {
-                        std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
+                        path::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
                     if let Err(e2) = git_service.add_worktree(
                         &git_repo_path,
@@ -444,7 +635,7 @@ impl WorktreeManager {
                     "Force removing git worktree metadata: {}",
                     git_worktree_metadata_path.display()
                 );
-                std::fs::remove_dir_all(&git_worktree_metadata_path)?;
+                path::remove_dir_all(&git_worktree_metadata_path)?;
             }
         }
 
@@ -469,14 +660,7 @@ impl WorktreeManager {
         let path_str = worktree.worktree_path.to_string_lossy().to_string();
 
         // Get the same lock to ensure we don't interfere with creation
-        let lock = {
-            let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
-            locks
-                .entry(path_str.clone())
-                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
-                .clone()
-        };
-
+        let lock = Self::path_lock(&path_str);
         let _guard = lock.lock().await;
 
         // Try to determine the git repo path if not provided
@@ -537,7 +721,7 @@ impl WorktreeManager {
 
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             if worktree_path_owned.exists() {
-                std::fs::remove_dir_all(&worktree_path_owned).map_err(WorktreeError::Io)?;
+                path::remove_dir_all(&worktree_path_owned).map_err(WorktreeError::Io)?;
                 info!(
                     "Removed worktree directory: {}",
                     worktree_path_owned.display()
@@ -0,0 +1,232 @@
+//! Integration tests for archived projects and tasks.
+//!
+//! Tests verify:
+//! - Project and task listings leave archived items out unless asked
+//! - Tasks of an archived project count as archived
+//! - The PR monitor and the embedding worker skip archived tasks and projects
+//!   and pick them up again once unarchived
+
+use db::models::{
+    embedding::EmbeddingStatus,
+    merge::Merge,
+    project::Project,
+    task::{Task, TaskOrderBy},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+async fn create_project(pool: &SqlitePool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Archive')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+    id
+}
+
+async fn create_task(pool: &SqlitePool, project_id: Uuid) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Task', 'inreview')",
+    )
+    .bind(id)
+    .bind(project_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create task");
+    id
+}
+
+/// Opens a PR from a new workspace of the task, returning the PR's merge ID.
+async fn open_pr(pool: &SqlitePool, task_id: Uuid) -> Uuid {
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{workspace_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+
+    let repo_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, 'app', 'app')")
+        .bind(repo_id)
+        .bind(format!("/tmp/{repo_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create repo");
+
+    Merge::create_pr(
+        pool,
+        workspace_id,
+        repo_id,
+        "main",
+        1,
+        "https://github.com/acme/app/pull/1",
+        false,
+    )
+    .await
+    .expect("Failed to create PR")
+    .id
+}
+
+/// IDs in a stable order, as items created together can share a timestamp
+fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+    ids.sort();
+    ids
+}
+
+async fn listed_tasks(pool: &SqlitePool, project_id: Uuid, include_archived: bool) -> Vec<Uuid> {
+    let (tasks, total) = Task::find_paginated_by_project_id_with_attempt_status(
+        pool,
+        project_id,
+        None,
+        None,
+        None,
+        include_archived,
+        TaskOrderBy::CreatedAtAsc,
+        50,
+        0,
+    )
+    .await
+    .unwrap();
+    assert_eq!(total, tasks.len() as i64);
+    sorted(tasks.into_iter().map(|t| t.task.id).collect())
+}
+
+async fn monitored_prs(pool: &SqlitePool) -> Vec<Uuid> {
+    sorted(
+        Merge::get_open_prs(pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|pr| pr.id)
+            .collect(),
+    )
+}
+
+async fn pending_embeddings(pool: &SqlitePool) -> Vec<Uuid> {
+    sorted(
+        EmbeddingStatus::find_pending(pool, 50)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|status| status.task_id)
+            .collect(),
+    )
+}
+
+#[tokio::test]
+async fn test_listings_leave_out_archived_items() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let archived_project_id = create_project(&pool).await;
+    let kept = create_task(&pool, project_id).await;
+    let archived = create_task(&pool, project_id).await;
+
+    assert_eq!(Task::archive(&pool, archived).await.unwrap(), 1);
+    // Archiving again is a no-op
+    assert_eq!(Task::archive(&pool, archived).await.unwrap(), 0);
+    assert_eq!(
+        Project::archive(&pool, archived_project_id).await.unwrap(),
+        1
+    );
+
+    assert_eq!(listed_tasks(&pool, project_id, false).await, vec![kept]);
+    assert_eq!(
+        listed_tasks(&pool, project_id, true).await,
+        sorted(vec![kept, archived])
+    );
+    let board = Task::find_by_project_id_with_attempt_status(&pool, project_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        board.iter().map(|t| t.task.id).collect::<Vec<_>>(),
+        vec![kept]
+    );
+    // Archived tasks can still be opened
+    let task = Task::find_by_id(&pool, archived).await.unwrap().unwrap();
+    assert!(task.archived_at.is_some());
+
+    let projects = |include_archived| {
+        let pool = pool.clone();
+        async move {
+            Project::find_all(&pool, include_archived)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|p| p.project.id)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(projects(false).await, vec![project_id]);
+    let all = projects(true).await;
+    assert_eq!(all.len(), 2);
+    assert!(all.contains(&archived_project_id));
+
+    assert_eq!(Task::unarchive(&pool, archived).await.unwrap(), 1);
+    assert_eq!(
+        listed_tasks(&pool, project_id, false).await,
+        sorted(vec![kept, archived])
+    );
+}
+
+#[tokio::test]
+async fn test_pr_monitor_skips_archived_tasks_and_projects() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let task_id = create_task(&pool, project_id).await;
+    let other_task_id = create_task(&pool, project_id).await;
+    let pr = open_pr(&pool, task_id).await;
+    let other_pr = open_pr(&pool, other_task_id).await;
+
+    Task::archive(&pool, task_id).await.unwrap();
+    assert_eq!(monitored_prs(&pool).await, vec![other_pr]);
+
+    // Every task of an archived project counts as archived
+    Project::archive(&pool, project_id).await.unwrap();
+    assert!(monitored_prs(&pool).await.is_empty());
+
+    Project::unarchive(&pool, project_id).await.unwrap();
+    Task::unarchive(&pool, task_id).await.unwrap();
+    assert_eq!(monitored_prs(&pool).await, sorted(vec![pr, other_pr]));
+}
+
+#[tokio::test]
+async fn test_embedding_worker_skips_archived_tasks_and_projects() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let task_id = create_task(&pool, project_id).await;
+    let other_project_id = create_project(&pool).await;
+    let other_task_id = create_task(&pool, other_project_id).await;
+
+    assert_eq!(
+        pending_embeddings(&pool).await,
+        sorted(vec![task_id, other_task_id])
+    );
+
+    Task::archive(&pool, task_id).await.unwrap();
+    Project::archive(&pool, other_project_id).await.unwrap();
+    assert!(pending_embeddings(&pool).await.is_empty());
+
+    // Still pending, so unarchiving picks them up again
+    Task::unarchive(&pool, task_id).await.unwrap();
+    assert_eq!(pending_embeddings(&pool).await, vec![task_id]);
+}
@@ -14,7 +14,7 @@ use db::models::{
     execution_process::{
         ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
     },
-    task::{Task, TaskStatus},
+    task::{Task, TaskPriority, TaskStatus},
     workspace::Workspace,
 };
 use executors::{
@@ -147,6 +147,9 @@ pub async fn create_task(
         is_queued: false,
         last_executor: String::new(),
         needs_attention: None,
+        priority: TaskPriority::Normal,
+        created_by: None,
+        archived_at: None,
     }
 }
 
@@ -227,6 +230,7 @@ pub async fn create_execution(
         ExecutionProcessRunReason::InternalAgent => "internalagent",
         ExecutionProcessRunReason::DevServer => "devserver",
         ExecutionProcessRunReason::DisposableConversation => "disposableconversation",
+        ExecutionProcessRunReason::Replay => "replay",
     };
     let now = chrono::Utc::now();
     let script_request = ScriptRequest {
@@ -645,6 +649,9 @@ pub fn make_task_for_event(
         is_queued: false,
         last_executor: String::new(),
         needs_attention: None,
+        priority: TaskPriority::Normal,
+        created_by: None,
+        archived_at: None,
     }
 }
 
@@ -97,6 +97,10 @@ impl MockExecutionController {
                     ExecutionTrigger::ReviewAttention {
                         task_id,
                         execution_process_id: _,
+                    }
+                    | ExecutionTrigger::ProjectMemory {
+                        task_id,
+                        execution_process_id: _,
                     } => {
                         // Find session for task
                         let session_id = find_or_create_session_for_task(&pool, *task_id).await?;
@@ -251,6 +255,7 @@ async fn create_execution_process(
         ExecutionProcessRunReason::InternalAgent => "internalagent",
         ExecutionProcessRunReason::DevServer => "devserver",
         ExecutionProcessRunReason::DisposableConversation => "disposableconversation",
+        ExecutionProcessRunReason::Replay => "replay",
     };
 
     // Create a minimal executor action for the mock process
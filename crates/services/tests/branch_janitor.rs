@@ -0,0 +1,357 @@
+//! Integration tests for cleaning up merged task branches.
+//!
+//! Tests verify:
+//! - Only branches whose workspace is gone and whose merge is old enough are
+//!   candidates, along with the commit their latest merge recorded
+//! - A branch's tip can be read, and the branch deleted or archived
+//! - The janitor removes squash merged branches and keeps branches with
+//!   commits after the merge or whose merge commit isn't in the repo
+
+use std::path::Path;
+
+use chrono::{Duration, Utc};
+use db::{
+    DBService,
+    models::{
+        branch_cleanup_policy::{BranchCleanupMode, StaleTaskBranch},
+        repo::Repo,
+    },
+};
+use services::services::{
+    branch_janitor::{BranchCleanupOutcome, BranchCleanupReport, BranchJanitor},
+    git::{GitCli, GitService},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn git(path: &Path, args: &[&str]) -> String {
+    GitCli::new()
+        .git(path, args)
+        .unwrap_or_else(|e| panic!("git {args:?} failed: {e}"))
+        .trim()
+        .to_string()
+}
+
+fn commit_file(path: &Path, file: &str, content: &str) -> String {
+    std::fs::write(path.join(file), content).unwrap();
+    git(path, &["add", file]);
+    git(path, &["commit", "-m", &format!("Add {file}")]);
+    git(path, &["rev-parse", "HEAD"])
+}
+
+fn create_repo(root: &TempDir) -> std::path::PathBuf {
+    let repo_path = root.path().join("repo");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .unwrap();
+    git(&repo_path, &["config", "user.name", "Test User"]);
+    git(&repo_path, &["config", "user.email", "test@example.com"]);
+    repo_path
+}
+
+/// Creates `branch` off main with one commit adding `file`, then squash
+/// merges it into main, returning the squash commit
+fn squash_merged_branch(repo_path: &Path, branch: &str, file: &str) -> String {
+    git(repo_path, &["checkout", "-b", branch, "main"]);
+    commit_file(repo_path, file, branch);
+    git(repo_path, &["checkout", "main"]);
+    git(repo_path, &["merge", "--squash", branch]);
+    git(repo_path, &["commit", "-m", &format!("Squash {branch}")]);
+    git(repo_path, &["rev-parse", "HEAD"])
+}
+
+async fn create_project(pool: &SqlitePool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Janitor')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+    id
+}
+
+/// Creates a task and a workspace on `branch`, which still has a worktree
+/// when `live`
+async fn create_workspace(pool: &SqlitePool, project_id: Uuid, branch: &str, live: bool) -> Uuid {
+    let task_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, ?, 'done')")
+        .bind(task_id)
+        .bind(project_id)
+        .bind(branch)
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch, container_ref) VALUES (?, ?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(branch)
+        .bind(live.then(|| format!("/tmp/worktrees/{branch}")))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+    workspace_id
+}
+
+async fn record_direct_merge(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+    repo_id: Uuid,
+    merge_commit: &str,
+    days_ago: i64,
+) {
+    sqlx::query(
+        "INSERT INTO merges (id, workspace_id, repo_id, merge_type, merge_commit, created_at, target_branch_name)
+         VALUES (?, ?, ?, 'direct', ?, ?, 'main')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(workspace_id)
+    .bind(repo_id)
+    .bind(merge_commit)
+    .bind(Utc::now() - Duration::days(days_ago))
+    .execute(pool)
+    .await
+    .expect("Failed to record direct merge");
+}
+
+async fn record_pr(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+    repo_id: Uuid,
+    status: &str,
+    merge_commit_sha: Option<&str>,
+    days_ago: i64,
+) {
+    let merged_at = Utc::now() - Duration::days(days_ago);
+    sqlx::query(
+        "INSERT INTO merges (id, workspace_id, repo_id, merge_type, pr_number, pr_url, pr_status,
+                             pr_merged_at, pr_merge_commit_sha, created_at, target_branch_name)
+         VALUES (?, ?, ?, 'pr', 1, 'https://github.com/o/r/pull/1', ?, ?, ?, ?, 'main')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(workspace_id)
+    .bind(repo_id)
+    .bind(status)
+    .bind((status == "merged").then_some(merged_at))
+    .bind(merge_commit_sha)
+    .bind(merged_at)
+    .execute(pool)
+    .await
+    .expect("Failed to record PR");
+}
+
+#[tokio::test]
+async fn test_find_for_project_lists_old_merges_without_worktrees() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let other_project_id = create_project(&pool).await;
+    let repo = Repo::find_or_create(&pool, Path::new("/tmp/janitor-repo"), "repo")
+        .await
+        .unwrap();
+
+    let direct = create_workspace(&pool, project_id, "vk/direct", false).await;
+    record_direct_merge(&pool, direct, repo.id, "aaaa", 20).await;
+    // A later merge of the same workspace is the one reported
+    record_direct_merge(&pool, direct, repo.id, "bbbb", 10).await;
+
+    let pr = create_workspace(&pool, project_id, "vk/pr", false).await;
+    record_pr(&pool, pr, repo.id, "merged", Some("cccc"), 10).await;
+
+    let live = create_workspace(&pool, project_id, "vk/live", true).await;
+    record_direct_merge(&pool, live, repo.id, "dddd", 10).await;
+
+    let recent = create_workspace(&pool, project_id, "vk/recent", false).await;
+    record_direct_merge(&pool, recent, repo.id, "eeee", 1).await;
+
+    let open = create_workspace(&pool, project_id, "vk/open", false).await;
+    record_pr(&pool, open, repo.id, "open", None, 10).await;
+
+    // The branch of a cleaned up workspace is still used by a live one
+    let reused = create_workspace(&pool, project_id, "vk/reused", false).await;
+    record_direct_merge(&pool, reused, repo.id, "ffff", 10).await;
+    let reusing = create_workspace(&pool, project_id, "vk/reused", true).await;
+    sqlx::query(
+        "INSERT INTO workspace_repos (id, workspace_id, repo_id, target_branch) VALUES (?, ?, ?, 'main')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(reusing)
+    .bind(repo.id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let other = create_workspace(&pool, other_project_id, "vk/other", false).await;
+    record_direct_merge(&pool, other, repo.id, "9999", 10).await;
+
+    let branches =
+        StaleTaskBranch::find_for_project(&pool, project_id, Utc::now() - Duration::days(7))
+            .await
+            .unwrap();
+
+    let found: Vec<(&str, Option<&str>)> = branches
+        .iter()
+        .map(|b| (b.branch.as_str(), b.merge_commit.as_deref()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![("vk/direct", Some("bbbb")), ("vk/pr", Some("cccc"))]
+    );
+    assert_eq!(branches[0].workspace_id, direct);
+    assert_eq!(branches[0].repo_id, repo.id);
+    assert_eq!(branches[0].target_branch_name, "main");
+}
+
+#[test]
+fn test_local_branch_tip_and_remove_local_branch() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+    for branch in ["vk/deleted", "vk/archived"] {
+        git(&repo_path, &["branch", branch, "main"]);
+    }
+    let main_tip = git(&repo_path, &["rev-parse", "main"]);
+
+    assert_eq!(
+        service.local_branch_tip(&repo_path, "vk/deleted").unwrap(),
+        Some(main_tip.clone())
+    );
+    assert_eq!(
+        service.local_branch_tip(&repo_path, "vk/missing").unwrap(),
+        None
+    );
+
+    service
+        .remove_local_branch(&repo_path, "vk/deleted", false)
+        .unwrap();
+    assert_eq!(
+        service.local_branch_tip(&repo_path, "vk/deleted").unwrap(),
+        None
+    );
+
+    service
+        .remove_local_branch(&repo_path, "vk/archived", true)
+        .unwrap();
+    assert_eq!(
+        service.local_branch_tip(&repo_path, "vk/archived").unwrap(),
+        None
+    );
+    assert_eq!(
+        git(&repo_path, &["rev-parse", "refs/vk-archive/vk/archived"]),
+        main_tip
+    );
+
+    assert!(
+        service
+            .remove_local_branch(&repo_path, "vk/missing", false)
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_janitor_removes_only_branches_contained_in_their_merge() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let repo = Repo::find_or_create(&pool, &repo_path, "repo")
+        .await
+        .unwrap();
+
+    // Squash merged, with the branch left at its own commit
+    let squashed = squash_merged_branch(&repo_path, "vk/squashed", "squashed.txt");
+    let workspace = create_workspace(&pool, project_id, "vk/squashed", false).await;
+    record_direct_merge(&pool, workspace, repo.id, &squashed, 10).await;
+
+    // Moved to the merge commit, as a direct merge from the app leaves it
+    let moved = squash_merged_branch(&repo_path, "vk/moved", "moved.txt");
+    git(&repo_path, &["branch", "-f", "vk/moved", &moved]);
+    let workspace = create_workspace(&pool, project_id, "vk/moved", false).await;
+    record_direct_merge(&pool, workspace, repo.id, &moved, 10).await;
+
+    // Committed to after the merge; its commit's timestamp is irrelevant
+    let extended = squash_merged_branch(&repo_path, "vk/extended", "extended.txt");
+    git(&repo_path, &["checkout", "vk/extended"]);
+    commit_file(&repo_path, "later.txt", "later");
+    git(&repo_path, &["checkout", "main"]);
+    let workspace = create_workspace(&pool, project_id, "vk/extended", false).await;
+    record_direct_merge(&pool, workspace, repo.id, &extended, 10).await;
+
+    // Merged as a PR whose merge commit was never fetched
+    git(&repo_path, &["branch", "vk/unfetched", "main"]);
+    let workspace = create_workspace(&pool, project_id, "vk/unfetched", false).await;
+    record_pr(
+        &pool,
+        workspace,
+        repo.id,
+        "merged",
+        Some("0123456789abcdef0123456789abcdef01234567"),
+        10,
+    )
+    .await;
+
+    let janitor = BranchJanitor::new(DBService { pool }, GitService::new());
+    let outcomes = |report: &BranchCleanupReport| {
+        report
+            .entries
+            .iter()
+            .map(|e| (e.branch.clone(), e.outcome))
+            .collect::<Vec<_>>()
+    };
+
+    let dry_run = janitor
+        .run(project_id, BranchCleanupMode::Delete, 7, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcomes(&dry_run),
+        vec![
+            ("vk/extended".to_string(), BranchCleanupOutcome::Skipped),
+            ("vk/moved".to_string(), BranchCleanupOutcome::WouldDelete),
+            ("vk/squashed".to_string(), BranchCleanupOutcome::WouldDelete),
+            ("vk/unfetched".to_string(), BranchCleanupOutcome::Skipped),
+        ]
+    );
+    assert_eq!(
+        git(&repo_path, &["branch", "--list", "vk/moved"]),
+        "vk/moved"
+    );
+
+    let report = janitor
+        .run(project_id, BranchCleanupMode::Delete, 7, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcomes(&report),
+        vec![
+            ("vk/extended".to_string(), BranchCleanupOutcome::Skipped),
+            ("vk/moved".to_string(), BranchCleanupOutcome::Deleted),
+            ("vk/squashed".to_string(), BranchCleanupOutcome::Deleted),
+            ("vk/unfetched".to_string(), BranchCleanupOutcome::Skipped),
+        ]
+    );
+    let remaining = git(
+        &repo_path,
+        &["branch", "--list", "vk/*", "--format=%(refname:short)"],
+    );
+    assert_eq!(
+        remaining.lines().collect::<Vec<_>>(),
+        vec!["vk/extended", "vk/unfetched"]
+    );
+}
@@ -0,0 +1,127 @@
+//! Integration tests for picking a branch name for a new workspace.
+//!
+//! Tests verify:
+//! - A free name is used as is
+//! - A name another workspace uses gets a short random suffix
+//! - A name that already exists as a branch in one of the repos gets a
+//!   suffix too, even when no workspace uses it
+
+use std::path::{Path, PathBuf};
+
+use services::services::{
+    container::unique_branch_name,
+    git::{GitCli, GitService},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn create_repo(root: &TempDir) -> PathBuf {
+    let repo_path = root.path().join("repo");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .unwrap();
+    repo_path
+}
+
+fn create_branch(repo_path: &Path, branch: &str) {
+    GitCli::new()
+        .git(repo_path, &["branch", branch])
+        .unwrap_or_else(|e| panic!("git branch {branch} failed: {e}"));
+}
+
+async fn create_workspace(pool: &SqlitePool, branch: &str) {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Branches')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let task_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Task', 'todo')")
+        .bind(task_id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(branch)
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+}
+
+fn assert_suffixed(name: &str, base: &str) {
+    let suffix = name
+        .strip_prefix(&format!("{base}-"))
+        .unwrap_or_else(|| panic!("'{name}' should extend '{base}'"));
+    assert!(!suffix.is_empty());
+    assert!(!suffix.contains('/'));
+}
+
+#[tokio::test]
+async fn test_free_branch_name_is_kept() {
+    let pool = create_test_db().await;
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+
+    let name = unique_branch_name(&pool, &GitService::new(), "vk/1234-fix-login", &[repo_path])
+        .await
+        .unwrap();
+    assert_eq!(name, "vk/1234-fix-login");
+}
+
+#[tokio::test]
+async fn test_branch_used_by_a_workspace_is_suffixed() {
+    let pool = create_test_db().await;
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    create_workspace(&pool, "vk/1234-fix-login").await;
+
+    let name = unique_branch_name(&pool, &GitService::new(), "vk/1234-fix-login", &[repo_path])
+        .await
+        .unwrap();
+    assert_suffixed(&name, "vk/1234-fix-login");
+}
+
+#[tokio::test]
+async fn test_branch_existing_in_a_repo_is_suffixed() {
+    let pool = create_test_db().await;
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    create_branch(&repo_path, "vk/1234-fix-login");
+
+    let name = unique_branch_name(
+        &pool,
+        &GitService::new(),
+        "vk/1234-fix-login",
+        std::slice::from_ref(&repo_path),
+    )
+    .await
+    .unwrap();
+    assert_suffixed(&name, "vk/1234-fix-login");
+    assert!(
+        !GitService::new()
+            .check_branch_exists(&repo_path, &name)
+            .unwrap()
+    );
+}
@@ -0,0 +1,302 @@
+//! Integration tests for restacking stacked workspaces after a merge.
+//!
+//! Tests verify:
+//! - A two-level stack is rebased onto the base branch after its bottom
+//!   branch is squash merged, and only the direct child is retargeted
+//! - A workspace with a running process is skipped and flagged, and the
+//!   workspaces stacked on it are left alone
+
+use std::path::{Path, PathBuf};
+
+use db::models::{repo::Repo, workspace_repo::WorkspaceRepo};
+use services::services::{
+    branch_stack,
+    git::{GitCli, GitService},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn git(path: &Path, args: &[&str]) -> String {
+    GitCli::new()
+        .git(path, args)
+        .unwrap_or_else(|e| panic!("git {args:?} failed: {e}"))
+        .trim()
+        .to_string()
+}
+
+fn commit_file(path: &Path, file: &str, content: &str) -> String {
+    std::fs::write(path.join(file), content).unwrap();
+    git(path, &["add", file]);
+    git(path, &["commit", "-m", &format!("Add {file}")]);
+    git(path, &["rev-parse", "HEAD"])
+}
+
+/// A repository whose `a`, `b` and `c` branches are stacked on each other,
+/// each adding one file, with `b` and `c` checked out in worktrees under
+/// `ws-b/repo` and `ws-c/repo`
+fn create_stacked_repo(root: &TempDir) -> PathBuf {
+    let repo_path = root.path().join("repo");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .unwrap();
+    git(&repo_path, &["config", "user.name", "Test User"]);
+    git(&repo_path, &["config", "user.email", "test@example.com"]);
+
+    let mut parent = "main";
+    for branch in ["a", "b", "c"] {
+        git(&repo_path, &["checkout", "-b", branch, parent]);
+        commit_file(&repo_path, &format!("{branch}.txt"), branch);
+        parent = branch;
+    }
+    git(&repo_path, &["checkout", "main"]);
+
+    for branch in ["b", "c"] {
+        let worktree = root.path().join(format!("ws-{branch}")).join("repo");
+        git(
+            &repo_path,
+            &["worktree", "add", worktree.to_str().unwrap(), branch],
+        );
+    }
+    repo_path
+}
+
+async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, ?, 'inprogress')")
+        .bind(id)
+        .bind(project_id)
+        .bind(title)
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+    id
+}
+
+/// Creates a task with a workspace on `branch` targeting `target_branch`,
+/// returning the task and workspace IDs
+async fn create_workspace(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    repo_id: Uuid,
+    branch: &str,
+    target_branch: &str,
+    container_ref: Option<&Path>,
+) -> (Uuid, Uuid) {
+    let task_id = create_task(pool, project_id, branch).await;
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch, container_ref) VALUES (?, ?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(branch)
+        .bind(container_ref.map(|path| path.to_string_lossy().to_string()))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+    sqlx::query(
+        "INSERT INTO workspace_repos (id, workspace_id, repo_id, target_branch) VALUES (?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(workspace_id)
+    .bind(repo_id)
+    .bind(target_branch)
+    .execute(pool)
+    .await
+    .expect("Failed to create workspace repo");
+    (task_id, workspace_id)
+}
+
+async fn start_process(pool: &SqlitePool, workspace_id: Uuid) {
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, workspace_id) VALUES (?, ?)")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create session");
+    sqlx::query(
+        "INSERT INTO execution_processes (id, session_id, status, run_reason)
+         VALUES (?, ?, 'running', 'codingagent')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(session_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+}
+
+async fn target_branch(pool: &SqlitePool, workspace_id: Uuid, repo_id: Uuid) -> String {
+    WorkspaceRepo::find_by_workspace_and_repo_id(pool, workspace_id, repo_id)
+        .await
+        .unwrap()
+        .expect("workspace repo exists")
+        .target_branch
+}
+
+async fn needs_attention(pool: &SqlitePool, task_id: Uuid) -> Option<bool> {
+    sqlx::query_scalar("SELECT needs_attention FROM tasks WHERE id = ?")
+        .bind(task_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+struct Stack {
+    _root: TempDir,
+    pool: SqlitePool,
+    repo: Repo,
+    a_tip: String,
+    b: (Uuid, Uuid),
+    c: (Uuid, Uuid),
+}
+
+/// Sets up the stacked repository and its workspaces, then squash merges
+/// `a` into `main`
+async fn merged_stack() -> Stack {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_stacked_repo(&root);
+    let pool = create_test_db().await;
+
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Stacked')")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let repo = Repo::find_or_create(&pool, &repo_path, "repo")
+        .await
+        .unwrap();
+
+    create_workspace(&pool, project_id, repo.id, "a", "main", None).await;
+    let b = create_workspace(
+        &pool,
+        project_id,
+        repo.id,
+        "b",
+        "a",
+        Some(&root.path().join("ws-b")),
+    )
+    .await;
+    let c = create_workspace(
+        &pool,
+        project_id,
+        repo.id,
+        "c",
+        "b",
+        Some(&root.path().join("ws-c")),
+    )
+    .await;
+
+    let a_tip = git(&repo_path, &["rev-parse", "a"]);
+    commit_file(&repo_path, "a.txt", "a");
+
+    Stack {
+        _root: root,
+        pool,
+        repo,
+        a_tip,
+        b,
+        c,
+    }
+}
+
+#[tokio::test]
+async fn test_two_level_stack_is_restacked_onto_base() {
+    let stack = merged_stack().await;
+    let repo_path = PathBuf::from(&stack.repo.path);
+    let (b_task, b_workspace) = stack.b;
+    let (c_task, c_workspace) = stack.c;
+
+    let outcomes = branch_stack::restack_after_merge(
+        &stack.pool,
+        &GitService::new(),
+        &stack.repo,
+        "a",
+        &stack.a_tip,
+        "main",
+    )
+    .await;
+
+    assert_eq!(outcomes.len(), 2);
+    assert!(
+        outcomes.iter().all(|outcome| outcome.error.is_none()),
+        "{outcomes:?}"
+    );
+    assert_eq!(outcomes[0].workspace_id, b_workspace);
+    assert_eq!(outcomes[1].workspace_id, c_workspace);
+
+    // b now sits directly on the squashed main, and c on the rebased b
+    let main_tip = git(&repo_path, &["rev-parse", "main"]);
+    let b_tip = git(&repo_path, &["rev-parse", "b"]);
+    assert_eq!(git(&repo_path, &["rev-parse", "b^"]), main_tip);
+    assert_eq!(git(&repo_path, &["rev-parse", "c^"]), b_tip);
+    assert_eq!(
+        git(&repo_path, &["rev-list", "--count", "main..c"]),
+        "2",
+        "only b's and c's own commits are left on top of main"
+    );
+
+    assert_eq!(
+        target_branch(&stack.pool, b_workspace, stack.repo.id).await,
+        "main"
+    );
+    assert_eq!(
+        target_branch(&stack.pool, c_workspace, stack.repo.id).await,
+        "b"
+    );
+    assert_ne!(needs_attention(&stack.pool, b_task).await, Some(true));
+    assert_ne!(needs_attention(&stack.pool, c_task).await, Some(true));
+}
+
+#[tokio::test]
+async fn test_workspace_with_running_process_is_not_restacked() {
+    let stack = merged_stack().await;
+    let repo_path = PathBuf::from(&stack.repo.path);
+    let (b_task, b_workspace) = stack.b;
+    let (c_task, c_workspace) = stack.c;
+    start_process(&stack.pool, b_workspace).await;
+    let b_before = git(&repo_path, &["rev-parse", "b"]);
+    let c_before = git(&repo_path, &["rev-parse", "c"]);
+
+    let outcomes = branch_stack::restack_after_merge(
+        &stack.pool,
+        &GitService::new(),
+        &stack.repo,
+        "a",
+        &stack.a_tip,
+        "main",
+    )
+    .await;
+
+    assert_eq!(outcomes.len(), 1, "c is left for when b is restacked");
+    assert_eq!(outcomes[0].workspace_id, b_workspace);
+    assert!(outcomes[0].error.is_some());
+
+    assert_eq!(git(&repo_path, &["rev-parse", "b"]), b_before);
+    assert_eq!(git(&repo_path, &["rev-parse", "c"]), c_before);
+    assert_eq!(
+        target_branch(&stack.pool, b_workspace, stack.repo.id).await,
+        "a"
+    );
+    assert_eq!(
+        target_branch(&stack.pool, c_workspace, stack.repo.id).await,
+        "b"
+    );
+    assert_eq!(needs_attention(&stack.pool, b_task).await, Some(true));
+    assert_ne!(needs_attention(&stack.pool, c_task).await, Some(true));
+}
@@ -0,0 +1,157 @@
+//! Integration tests for cherry-picking a workspace's commits onto another
+//! branch.
+//!
+//! Tests verify:
+//! - Only the workspace branch's own commits are listed, oldest first
+//! - Picking onto a branch that is not checked out goes through a temporary
+//!   worktree that is removed afterwards
+//! - Picking onto a checked-out branch applies the commits in its checkout
+//! - On conflicts the pick is aborted and the target branch is unchanged
+
+use std::path::{Path, PathBuf};
+
+use services::services::git::{GitCli, GitService, GitServiceError};
+use tempfile::TempDir;
+
+fn git(path: &Path, args: &[&str]) -> String {
+    GitCli::new()
+        .git(path, args)
+        .unwrap_or_else(|e| panic!("git {args:?} failed: {e}"))
+        .trim()
+        .to_string()
+}
+
+fn commit_file(path: &Path, file: &str, content: &str) -> String {
+    std::fs::write(path.join(file), content).unwrap();
+    git(path, &["add", file]);
+    git(path, &["commit", "-m", &format!("Write {file}")]);
+    git(path, &["rev-parse", "HEAD"])
+}
+
+/// A repository with a `feature` branch two commits ahead of `main` and a
+/// `release` branch cut from `main`, with `main` checked out
+fn create_repo(root: &TempDir) -> PathBuf {
+    let repo_path = root.path().join("repo");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .unwrap();
+    git(&repo_path, &["config", "user.name", "Test User"]);
+    git(&repo_path, &["config", "user.email", "test@example.com"]);
+    commit_file(&repo_path, "shared.txt", "base\n");
+    git(&repo_path, &["branch", "release"]);
+
+    git(&repo_path, &["checkout", "-b", "feature"]);
+    commit_file(&repo_path, "fix.txt", "fix\n");
+    commit_file(&repo_path, "shared.txt", "feature\n");
+    git(&repo_path, &["checkout", "main"]);
+    repo_path
+}
+
+fn show(repo_path: &Path, branch: &str, file: &str) -> String {
+    git(repo_path, &["show", &format!("{branch}:{file}")])
+}
+
+#[test]
+fn test_lists_only_the_branch_commits_oldest_first() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+
+    let commits = service
+        .commits_not_on(&repo_path, "feature", "main")
+        .unwrap();
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0], git(&repo_path, &["rev-parse", "feature~1"]));
+    assert_eq!(commits[1], git(&repo_path, &["rev-parse", "feature"]));
+    assert!(
+        service
+            .commits_not_on(&repo_path, "main", "feature")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_cherry_pick_onto_branch_without_checkout() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+    let commits = service
+        .commits_not_on(&repo_path, "feature", "main")
+        .unwrap();
+
+    let tip = service
+        .cherry_pick_onto_branch(&repo_path, "release", &commits)
+        .unwrap();
+
+    assert_eq!(tip, git(&repo_path, &["rev-parse", "release"]));
+    assert_eq!(show(&repo_path, "release", "fix.txt"), "fix");
+    assert_eq!(show(&repo_path, "release", "shared.txt"), "feature");
+    assert_eq!(
+        git(&repo_path, &["rev-list", "--count", "main..release"]),
+        "2"
+    );
+    // The temporary worktree is gone and the main checkout is untouched
+    assert_eq!(git(&repo_path, &["worktree", "list"]).lines().count(), 1);
+    assert_eq!(git(&repo_path, &["branch", "--show-current"]), "main");
+    assert!(!repo_path.join("fix.txt").exists());
+}
+
+#[test]
+fn test_cherry_pick_onto_checked_out_branch() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+    let commits = service
+        .commits_not_on(&repo_path, "feature", "main")
+        .unwrap();
+
+    let tip = service
+        .cherry_pick_onto_branch(&repo_path, "main", &commits)
+        .unwrap();
+
+    assert_eq!(tip, git(&repo_path, &["rev-parse", "HEAD"]));
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("fix.txt")).unwrap(),
+        "fix\n"
+    );
+    assert!(git(&repo_path, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn test_conflicting_cherry_pick_leaves_target_unchanged() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+    let commits = service
+        .commits_not_on(&repo_path, "feature", "main")
+        .unwrap();
+
+    git(&repo_path, &["checkout", "release"]);
+    let release_tip = commit_file(&repo_path, "shared.txt", "release\n");
+    git(&repo_path, &["checkout", "main"]);
+
+    let result = service.cherry_pick_onto_branch(&repo_path, "release", &commits);
+    assert!(
+        matches!(result, Err(GitServiceError::MergeConflicts(ref msg)) if msg.contains("shared.txt")),
+        "expected a conflict on shared.txt, got {result:?}"
+    );
+    assert_eq!(git(&repo_path, &["rev-parse", "release"]), release_tip);
+    assert_eq!(show(&repo_path, "release", "shared.txt"), "release");
+    assert_eq!(git(&repo_path, &["worktree", "list"]).lines().count(), 1);
+}
+
+#[test]
+fn test_cherry_pick_onto_missing_branch_fails() {
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let service = GitService::new();
+    let commits = service
+        .commits_not_on(&repo_path, "feature", "main")
+        .unwrap();
+
+    assert!(matches!(
+        service.cherry_pick_onto_branch(&repo_path, "nope", &commits),
+        Err(GitServiceError::BranchNotFound(_))
+    ));
+}
@@ -0,0 +1,279 @@
+//! Integration tests for the worktrees of conversations.
+//!
+//! Tests verify:
+//! - Worktrees of conversations idle for 72 hours are removed and marked
+//!   released, and resuming the conversation recreates them on its branch
+//! - Recently used worktrees, and those with an execution still running in
+//!   any conversation sharing them, are kept
+//! - Forks share their original's worktrees, which expire together
+
+use std::path::{Path, PathBuf};
+
+use db::models::{
+    conversation_session::ConversationSession, conversation_worktree::ConversationWorktree,
+    project_repo::ProjectRepo,
+};
+use git2::Repository;
+use services::services::{
+    conversation::ConversationService,
+    git::GitService,
+    workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn create_repo(root: &TempDir) -> PathBuf {
+    let repo_path = root.path().join("app");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .expect("init repo");
+    let repo = Repository::open(&repo_path).unwrap();
+    let mut cfg = repo.config().unwrap();
+    cfg.set_str("user.name", "Test User").unwrap();
+    cfg.set_str("user.email", "test@example.com").unwrap();
+    cfg.set_bool("core.fsmonitor", true).unwrap();
+    repo_path
+}
+
+/// Creates a project with the repo, returning the project ID.
+async fn create_project(pool: &SqlitePool, repo_path: &Path) -> Uuid {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Conversations')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let repo_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, 'app', 'app')")
+        .bind(repo_id)
+        .bind(repo_path.to_string_lossy().to_string())
+        .execute(pool)
+        .await
+        .expect("Failed to create repo");
+
+    sqlx::query("INSERT INTO project_repos (id, project_id, repo_id) VALUES (?, ?, ?)")
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .bind(repo_id)
+        .execute(pool)
+        .await
+        .expect("Failed to add repo to project");
+    project_id
+}
+
+async fn create_conversation(pool: &SqlitePool, project_id: Uuid) -> ConversationSession {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO conversation_sessions (id, project_id, title) VALUES (?, ?, 'Chat')")
+        .bind(id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create conversation");
+    ConversationSession::find_by_id(pool, id)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+/// Creates worktrees of the project's repos for the conversation in
+/// `container_ref`, the way a new conversation gets them
+async fn create_worktree(
+    pool: &SqlitePool,
+    conversation: &ConversationSession,
+    container_ref: &Path,
+    branch: &str,
+) {
+    let inputs: Vec<RepoWorkspaceInput> =
+        ProjectRepo::find_repos_for_project(pool, conversation.project_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|repo| RepoWorkspaceInput::new(repo, "main".to_string()))
+            .collect();
+    WorkspaceManager::create_workspace(container_ref, &inputs, branch)
+        .await
+        .expect("Failed to create worktrees");
+    ConversationWorktree::create(
+        pool,
+        conversation.id,
+        &container_ref.to_string_lossy(),
+        branch,
+    )
+    .await
+    .expect("Failed to record worktree");
+}
+
+/// Moves the last use of the worktrees in `container_ref` back four days
+async fn make_idle(pool: &SqlitePool, container_ref: &Path) {
+    sqlx::query(
+        "UPDATE conversation_worktrees SET last_used_at = datetime('now', '-4 days')
+         WHERE container_ref = ?",
+    )
+    .bind(container_ref.to_string_lossy().to_string())
+    .execute(pool)
+    .await
+    .expect("Failed to backdate worktree");
+}
+
+async fn start_execution(pool: &SqlitePool, conversation_id: Uuid) {
+    sqlx::query(
+        "INSERT INTO execution_processes (id, conversation_session_id, status, run_reason)
+         VALUES (?, ?, 'running', 'disposableconversation')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(conversation_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+}
+
+async fn worktree_of(pool: &SqlitePool, conversation_id: Uuid) -> ConversationWorktree {
+    ConversationWorktree::find_by_conversation_session_id(pool, conversation_id)
+        .await
+        .unwrap()
+        .expect("conversation should have a worktree")
+}
+
+#[tokio::test]
+async fn test_idle_worktree_expires_and_is_recreated_on_resume() {
+    let pool = create_test_db().await;
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let project_id = create_project(&pool, &repo_path).await;
+    let conversation = create_conversation(&pool, project_id).await;
+    let container_ref = root.path().join("worktrees").join("conv-chat");
+    create_worktree(&pool, &conversation, &container_ref, "vk/conv-chat").await;
+
+    // Recently used worktrees are kept
+    assert_eq!(
+        ConversationService::cleanup_expired_worktrees(&pool)
+            .await
+            .unwrap(),
+        0
+    );
+
+    make_idle(&pool, &container_ref).await;
+    assert_eq!(
+        ConversationService::cleanup_expired_worktrees(&pool)
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(!container_ref.join("app").exists());
+    assert!(
+        worktree_of(&pool, conversation.id)
+            .await
+            .released_at
+            .is_some()
+    );
+    // The branch is kept for when the conversation resumes
+    assert!(
+        GitService::new()
+            .check_branch_exists(&repo_path, "vk/conv-chat")
+            .unwrap()
+    );
+    // Released worktrees are not cleaned up again
+    assert_eq!(
+        ConversationService::cleanup_expired_worktrees(&pool)
+            .await
+            .unwrap(),
+        0
+    );
+
+    ConversationService::attach_worktree(&pool, &conversation)
+        .await
+        .unwrap();
+    let worktree = Repository::open(container_ref.join("app")).unwrap();
+    assert_eq!(worktree.head().unwrap().shorthand(), Some("vk/conv-chat"));
+    let record = worktree_of(&pool, conversation.id).await;
+    assert!(record.released_at.is_none());
+    assert!(record.last_used_at > chrono::Utc::now() - chrono::Duration::hours(1));
+}
+
+#[tokio::test]
+async fn test_running_execution_in_a_fork_keeps_shared_worktree() {
+    let pool = create_test_db().await;
+    let root = TempDir::new().unwrap();
+    let repo_path = create_repo(&root);
+    let project_id = create_project(&pool, &repo_path).await;
+    let original = create_conversation(&pool, project_id).await;
+    let fork = create_conversation(&pool, project_id).await;
+    let container_ref = root.path().join("worktrees").join("conv-chat");
+    create_worktree(&pool, &original, &container_ref, "vk/conv-chat").await;
+    ConversationWorktree::create(
+        &pool,
+        fork.id,
+        &container_ref.to_string_lossy(),
+        "vk/conv-chat",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        ConversationWorktree::count_by_container_ref(&pool, &container_ref.to_string_lossy())
+            .await
+            .unwrap(),
+        2
+    );
+
+    make_idle(&pool, &container_ref).await;
+    start_execution(&pool, fork.id).await;
+    assert!(
+        ConversationWorktree::find_expired_for_cleanup(&pool)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    sqlx::query("UPDATE execution_processes SET status = 'completed', completed_at = datetime('now', '-4 days')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    // One row per shared container
+    assert_eq!(
+        ConversationService::cleanup_expired_worktrees(&pool)
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(!container_ref.join("app").exists());
+    for conversation_id in [original.id, fork.id] {
+        assert!(
+            worktree_of(&pool, conversation_id)
+                .await
+                .released_at
+                .is_some()
+        );
+    }
+
+    // Resuming either conversation brings the worktree back for both
+    ConversationService::attach_worktree(&pool, &fork)
+        .await
+        .unwrap();
+    assert!(container_ref.join("app").exists());
+    for conversation_id in [original.id, fork.id] {
+        assert!(
+            worktree_of(&pool, conversation_id)
+                .await
+                .released_at
+                .is_none()
+        );
+    }
+}
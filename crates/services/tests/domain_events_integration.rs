@@ -16,7 +16,7 @@ use std::{
 
 use async_trait::async_trait;
 use db::models::{
-    task::{Task, TaskStatus},
+    task::{Task, TaskPriority, TaskStatus},
     workspace::Workspace,
 };
 use services::services::domain_events::{
@@ -78,6 +78,9 @@ fn test_task() -> Task {
         is_queued: false,
         last_executor: String::new(),
         needs_attention: None,
+        priority: TaskPriority::Normal,
+        created_by: None,
+        archived_at: None,
     }
 }
 
@@ -4,8 +4,13 @@
 //! - Queue entry creation
 //! - Queue cancellation (delete_by_workspace)
 //! - Task materialized status update after cancellation
+//! - Which waiting entries priority preemption considers
 
-use db::models::{execution_queue::ExecutionQueue, task::Task};
+use db::models::{
+    execution_process::ExecutionProcess,
+    execution_queue::ExecutionQueue,
+    task::{Task, TaskPriority},
+};
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
 use uuid::Uuid;
@@ -261,3 +266,140 @@ async fn test_execution_queue_count() {
         .expect("Failed to get count");
     assert_eq!(count, 3);
 }
+
+/// Sets a task's priority.
+async fn set_task_priority(pool: &SqlitePool, task_id: Uuid, priority: TaskPriority) {
+    sqlx::query("UPDATE tasks SET priority = ? WHERE id = ?")
+        .bind(priority as i64)
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .expect("Failed to set task priority");
+}
+
+/// Starts a coding agent in the workspace, returning the execution process ID.
+async fn create_running_agent(pool: &SqlitePool, workspace_id: Uuid, started_at: &str) -> Uuid {
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, workspace_id) VALUES (?, ?)")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create session");
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes (id, session_id, status, run_reason, started_at)
+         VALUES (?, ?, 'running', 'codingagent', ?)",
+    )
+    .bind(id)
+    .bind(session_id)
+    .bind(started_at)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+    id
+}
+
+fn claude_code() -> ExecutorProfileId {
+    ExecutorProfileId {
+        executor: BaseCodingAgent::ClaudeCode,
+        variant: None,
+    }
+}
+
+#[tokio::test]
+async fn test_highest_priority_skips_entries_of_blocked_providers() {
+    let pool = create_test_db().await;
+    let project_id = create_test_project(&pool, "Test Project").await;
+    let urgent_task = create_test_task(&pool, project_id, "Urgent").await;
+    set_task_priority(&pool, urgent_task, TaskPriority::Urgent).await;
+    let urgent_workspace = create_test_workspace(&pool, urgent_task, "urgent").await;
+    ExecutionQueue::create(&pool, urgent_workspace, &claude_code())
+        .await
+        .expect("Failed to create queue entry");
+
+    let unblocked = ExecutionQueue::find_highest_priority(&pool, &[])
+        .await
+        .expect("Failed to find priority");
+    assert_eq!(unblocked, Some(TaskPriority::Urgent));
+
+    // The urgent entry can't start while its provider is rate limited, so
+    // nothing outranks a running agent
+    let blocked = vec!["CLAUDE_CODE".to_string()];
+    let priority = ExecutionQueue::find_highest_priority(&pool, &blocked)
+        .await
+        .expect("Failed to find priority");
+    assert_eq!(priority, None);
+    let popped = ExecutionQueue::pop_next(&pool, &blocked)
+        .await
+        .expect("Failed to pop");
+    assert!(popped.is_none());
+}
+
+#[tokio::test]
+async fn test_highest_priority_skips_projects_at_their_limit() {
+    let pool = create_test_db().await;
+    let limited_project = create_test_project(&pool, "Limited").await;
+    sqlx::query("UPDATE projects SET max_concurrent_executions = 1 WHERE id = ?")
+        .bind(limited_project)
+        .execute(&pool)
+        .await
+        .expect("Failed to set project limit");
+    let running_task = create_test_task(&pool, limited_project, "Running").await;
+    let running_workspace = create_test_workspace(&pool, running_task, "running").await;
+    create_running_agent(&pool, running_workspace, "2026-03-01 09:00:00").await;
+
+    let urgent_task = create_test_task(&pool, limited_project, "Urgent").await;
+    set_task_priority(&pool, urgent_task, TaskPriority::Urgent).await;
+    let urgent_workspace = create_test_workspace(&pool, urgent_task, "urgent").await;
+    ExecutionQueue::create(&pool, urgent_workspace, &claude_code())
+        .await
+        .expect("Failed to create queue entry");
+
+    let other_project = create_test_project(&pool, "Other").await;
+    let normal_task = create_test_task(&pool, other_project, "Normal").await;
+    let normal_workspace = create_test_workspace(&pool, normal_task, "normal").await;
+    ExecutionQueue::create(&pool, normal_workspace, &claude_code())
+        .await
+        .expect("Failed to create queue entry");
+
+    // Only the normal entry of the other project could start
+    let priority = ExecutionQueue::find_highest_priority(&pool, &[])
+        .await
+        .expect("Failed to find priority");
+    assert_eq!(priority, Some(TaskPriority::Normal));
+    let popped = ExecutionQueue::pop_next(&pool, &[])
+        .await
+        .expect("Failed to pop")
+        .expect("An entry can start");
+    assert_eq!(popped.workspace_id, normal_workspace);
+}
+
+#[tokio::test]
+async fn test_running_agent_below_priority_is_the_lowest_and_latest() {
+    let pool = create_test_db().await;
+    let project_id = create_test_project(&pool, "Test Project").await;
+
+    let mut agents = Vec::new();
+    for (title, priority, started_at) in [
+        ("Early low", TaskPriority::Low, "2026-03-01 09:00:00"),
+        ("Late low", TaskPriority::Low, "2026-03-01 10:00:00"),
+        ("Normal", TaskPriority::Normal, "2026-03-01 11:00:00"),
+    ] {
+        let task_id = create_test_task(&pool, project_id, title).await;
+        set_task_priority(&pool, task_id, priority).await;
+        let workspace_id = create_test_workspace(&pool, task_id, title).await;
+        agents.push(create_running_agent(&pool, workspace_id, started_at).await);
+    }
+
+    let preempted = ExecutionProcess::find_running_agent_below_priority(&pool, TaskPriority::High)
+        .await
+        .expect("Failed to find agent")
+        .expect("An agent runs below high priority");
+    assert_eq!(preempted.id, agents[1]);
+
+    let none = ExecutionProcess::find_running_agent_below_priority(&pool, TaskPriority::Low)
+        .await
+        .expect("Failed to find agent");
+    assert!(none.is_none());
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 use git2::{PushOptions, Repository, build::CheckoutBuilder};
-use services::services::git::{GitCli, GitCliError, GitService};
+use services::services::git::{ConflictOp, GitCli, GitCliError, GitService, GitServiceError};
 use tempfile::TempDir;
 // Avoid direct git CLI usage in tests; exercise GitService instead.
 
@@ -1406,3 +1406,60 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn cherry_pick_applies_commits_onto_task_branch() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let repo = Repository::open(&repo_path).unwrap();
+    checkout_branch(&repo, "main");
+    write_file(&repo_path, "fix.txt", "hotfix\n");
+    commit_all(&repo, "hotfix on main");
+
+    let g = GitService::new();
+    let before = g.get_head_info(&worktree_path).unwrap().oid;
+    let fix = g.get_branch_oid(&repo_path, "main").unwrap();
+    let head = g
+        .cherry_pick_commits(&worktree_path, std::slice::from_ref(&fix))
+        .expect("cherry-pick should succeed");
+
+    assert_ne!(head, before, "HEAD should move after cherry-pick");
+    assert_eq!(g.get_head_info(&worktree_path).unwrap().oid, head);
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("fix.txt")).unwrap(),
+        "hotfix\n"
+    );
+    assert!(worktree_path.join("feat.txt").exists());
+}
+
+#[test]
+fn cherry_pick_conflict_can_be_aborted() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_conflict_repo_with_worktree(&td);
+    let g = GitService::new();
+    let before = g.get_head_info(&worktree_path).unwrap().oid;
+    let conflicting = g.get_branch_oid(&repo_path, "new-base").unwrap();
+
+    let err = g
+        .cherry_pick_commits(&worktree_path, std::slice::from_ref(&conflicting))
+        .expect_err("cherry-pick should stop on conflicts");
+    assert!(matches!(err, GitServiceError::MergeConflicts(_)));
+    assert_eq!(
+        g.detect_conflict_op(&worktree_path).unwrap(),
+        Some(ConflictOp::CherryPick)
+    );
+
+    // A second operation is refused until this one is finished
+    assert!(matches!(
+        g.cherry_pick_commits(&worktree_path, std::slice::from_ref(&conflicting)),
+        Err(GitServiceError::WorktreeDirty(..) | GitServiceError::OperationInProgress(_))
+    ));
+
+    assert_eq!(
+        g.abort_in_progress_op(&worktree_path).unwrap(),
+        Some(ConflictOp::CherryPick)
+    );
+    assert_eq!(g.detect_conflict_op(&worktree_path).unwrap(), None);
+    assert_eq!(g.get_head_info(&worktree_path).unwrap().oid, before);
+    assert_eq!(g.abort_in_progress_op(&worktree_path).unwrap(), None);
+}
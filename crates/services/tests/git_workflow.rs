@@ -4,13 +4,17 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use db::models::git_hooks_policy::GitHooksMode;
 use git2::{Repository, build::CheckoutBuilder};
 use services::services::{
-    git::{DiffTarget, GitCli, GitService},
+    git::{
+        CommitHooks, DEFAULT_MAX_INLINE_DIFF_BYTES, DiffTarget, GitCli, GitService,
+        MAX_INLINE_DIFF_BYTES_LIMIT, resolve_inline_diff_limit,
+    },
     github::{GitHubRepoInfo, GitHubServiceError},
 };
 use tempfile::TempDir;
-use utils::diff::DiffChangeKind;
+use utils::diff::{DiffChangeKind, DiffFileMode};
 
 fn add_path(repo_path: &Path, path: &str) {
     let git = GitCli::new();
@@ -217,6 +221,48 @@ fn diff_added_binary_file_has_no_content() {
     assert!(bin.new_content.is_none());
 }
 
+#[test]
+fn diff_contents_are_omitted_above_inline_limit() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let _ = s.commit(&repo_path, "base").unwrap();
+    create_branch(&repo_path, "feature");
+    checkout_branch(&repo_path, "feature");
+    write_file(&repo_path, "big.txt", &"line\n".repeat(100));
+    let _ = s.commit(&repo_path, "add big file").unwrap();
+
+    let target = || DiffTarget::Branch {
+        repo_path: Path::new(&repo_path),
+        branch_name: "feature",
+        base_branch: "main",
+    };
+    let find_big = |diffs: Vec<utils::diff::Diff>| {
+        diffs
+            .into_iter()
+            .find(|d| d.new_path.as_deref() == Some("big.txt"))
+            .expect("diff present")
+    };
+
+    let inline = find_big(s.get_diffs_with_limit(target(), None, 1024).unwrap());
+    assert!(!inline.content_omitted);
+    assert!(inline.new_content.is_some());
+
+    let omitted = find_big(s.get_diffs_with_limit(target(), None, 100).unwrap());
+    assert!(omitted.content_omitted);
+    assert!(omitted.new_content.is_none());
+
+    assert_eq!(
+        resolve_inline_diff_limit(None, None),
+        DEFAULT_MAX_INLINE_DIFF_BYTES
+    );
+    assert_eq!(resolve_inline_diff_limit(Some(10), Some(20)), 10);
+    assert_eq!(
+        resolve_inline_diff_limit(Some(usize::MAX), None),
+        MAX_INLINE_DIFF_BYTES_LIMIT
+    );
+}
+
 #[test]
 fn initialize_and_default_branch_and_head_info() {
     let td = TempDir::new().unwrap();
@@ -231,6 +277,77 @@ fn initialize_and_default_branch_and_head_info() {
     assert!(!head.oid.is_empty());
 }
 
+#[cfg(unix)]
+#[test]
+fn commit_with_hooks_follows_policy() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let hook = repo_path.join(".git/hooks/pre-commit");
+    write_file(
+        &repo_path,
+        ".git/hooks/pre-commit",
+        "#!/bin/sh\necho rejected >&2\nexit 1\n",
+    );
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let s = GitService::new();
+    write_file(&repo_path, "a.txt", "a\n");
+    assert!(
+        s.commit_with_hooks(&repo_path, "run", &GitHooksMode::Run.into())
+            .is_err()
+    );
+
+    let skipped = s
+        .commit_with_hooks(&repo_path, "skip", &GitHooksMode::Skip.into())
+        .unwrap();
+    assert!(skipped.committed);
+    assert!(skipped.hook_failure.is_none());
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+
+    write_file(&repo_path, "b.txt", "b\n");
+    let reported = s
+        .commit_with_hooks(&repo_path, "report", &GitHooksMode::Report.into())
+        .unwrap();
+    assert!(reported.committed);
+    assert!(reported.hook_failure.unwrap().contains("rejected"));
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn commit_hooks_run_under_sandbox_prefix() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let hook = repo_path.join(".git/hooks/pre-commit");
+    write_file(
+        &repo_path,
+        ".git/hooks/pre-commit",
+        "#!/bin/sh\n[ \"$VK_SANDBOXED\" = 1 ] || { echo unsandboxed >&2; exit 1; }\n",
+    );
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let s = GitService::new();
+    write_file(&repo_path, "a.txt", "a\n");
+    assert!(
+        s.commit_with_hooks(&repo_path, "host", &GitHooksMode::Run.into())
+            .is_err()
+    );
+
+    let sandboxed = CommitHooks {
+        mode: GitHooksMode::Run,
+        sandbox: Some(vec!["env".to_string(), "VK_SANDBOXED=1".to_string()]),
+    };
+    let commit = s
+        .commit_with_hooks(&repo_path, "sandboxed", &sandboxed)
+        .unwrap();
+    assert!(commit.committed);
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+}
+
 #[test]
 fn commit_and_is_worktree_clean() {
     let td = TempDir::new().unwrap();
@@ -464,6 +581,47 @@ fn worktree_diff_permission_only_change() {
         .expect("p.sh diff present");
     assert!(matches!(d.change, DiffChangeKind::PermissionChange));
     assert_eq!(d.old_content, d.new_content);
+    assert_eq!(d.old_mode, Some(DiffFileMode::Regular));
+    assert_eq!(d.new_mode, Some(DiffFileMode::Executable));
+    assert!(d.executable_changed);
+}
+
+#[cfg(unix)]
+#[test]
+fn worktree_diff_reports_symlink_targets() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "target.txt", "hello\n");
+    std::os::unix::fs::symlink("target.txt", repo_path.join("link")).unwrap();
+    let _ = s.commit(&repo_path, "add link").unwrap();
+    create_branch(&repo_path, "feature");
+
+    // repoint the link; the file it pointed to is unchanged
+    write_file(&repo_path, "other.txt", "hello\n");
+    std::fs::remove_file(repo_path.join("link")).unwrap();
+    std::os::unix::fs::symlink("other.txt", repo_path.join("link")).unwrap();
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let diffs = s
+        .get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: Path::new(&repo_path),
+                base_commit: &base_commit,
+            },
+            None,
+        )
+        .unwrap();
+    let d = diffs
+        .into_iter()
+        .find(|d| d.new_path.as_deref() == Some("link"))
+        .expect("link diff present");
+    assert!(matches!(d.change, DiffChangeKind::Modified));
+    assert_eq!(d.old_mode, Some(DiffFileMode::Symlink));
+    assert_eq!(d.new_mode, Some(DiffFileMode::Symlink));
+    assert_eq!(d.old_symlink_target.as_deref(), Some("target.txt"));
+    assert_eq!(d.new_symlink_target.as_deref(), Some("other.txt"));
+    assert!(!d.executable_changed);
 }
 
 #[test]
@@ -20,7 +20,7 @@ use db::models::{
     execution_process::{
         ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
     },
-    task::{Task, TaskStatus},
+    task::{Task, TaskPriority, TaskStatus},
     workspace::Workspace,
 };
 use executors::actions::{
@@ -122,6 +122,9 @@ async fn create_test_task(
         is_queued: false,
         last_executor: String::new(),
         needs_attention: None,
+        priority: TaskPriority::Normal,
+        created_by: None,
+        archived_at: None,
     }
 }
 
@@ -189,6 +192,7 @@ async fn create_test_execution_process(
         ExecutionProcessRunReason::InternalAgent => "internalagent",
         ExecutionProcessRunReason::DevServer => "devserver",
         ExecutionProcessRunReason::DisposableConversation => "disposableconversation",
+        ExecutionProcessRunReason::Replay => "replay",
     };
     let now = chrono::Utc::now();
     let script_request = ScriptRequest {
@@ -265,8 +269,8 @@ fn create_mock_trigger_callback(capture: Arc<Mutex<TriggerCapture>>) -> Executio
                     cap.review_attention_triggers
                         .push((task_id, execution_process_id));
                 }
-                ExecutionTrigger::ProcessQueue => {
-                    // ProcessQueue doesn't capture anything specific
+                ExecutionTrigger::ProjectMemory { .. } | ExecutionTrigger::ProcessQueue => {
+                    // Nothing these tests check
                 }
             }
             Ok(Uuid::new_v4())
@@ -591,6 +595,9 @@ async fn test_review_attention_handler_ignores_other_statuses() {
             is_queued: false,
             last_executor: String::new(),
             needs_attention: None,
+            priority: TaskPriority::Normal,
+            created_by: None,
+            archived_at: None,
         };
 
         let event = DomainEvent::TaskStatusChanged {
@@ -1322,6 +1329,9 @@ async fn test_dispatcher_sets_hook_execution_id_for_spawned_handlers() {
         is_queued: false,
         last_executor: String::new(),
         needs_attention: None,
+        priority: TaskPriority::Normal,
+        created_by: None,
+        archived_at: None,
     };
 
     let event = DomainEvent::TaskStatusChanged {
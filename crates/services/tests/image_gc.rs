@@ -0,0 +1,155 @@
+//! Integration tests for image garbage collection and upload dedup.
+//!
+//! Tests verify:
+//! - Unreferenced images past the grace period are deleted with their files
+//! - Images a task refers to, and recently uploaded ones, are kept
+//! - Stray files in the image cache are removed once past the grace period
+//! - Uploading an existing image reuses its record, restarts its grace period
+//!   and restores a missing cache file
+
+use std::{
+    fs::File,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use db::models::image::Image;
+use services::services::image::{ImageService, ORPHAN_GRACE_PERIOD};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+async fn create_service(pool: &SqlitePool) -> (ImageService, TempDir) {
+    let cache_dir = TempDir::new().unwrap();
+    let service = ImageService::with_cache_dir(pool.clone(), cache_dir.path().to_path_buf())
+        .await
+        .expect("Failed to create image service");
+    (service, cache_dir)
+}
+
+/// Moves an image's last upload to before the grace period
+async fn backdate_image(pool: &SqlitePool, image_id: Uuid) {
+    sqlx::query("UPDATE images SET updated_at = datetime('now', '-2 days') WHERE id = ?")
+        .bind(image_id)
+        .execute(pool)
+        .await
+        .expect("Failed to backdate image");
+}
+
+fn backdate_file(path: &Path) {
+    let two_days_ago = SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60);
+    File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(two_days_ago))
+        .expect("Failed to backdate file");
+}
+
+async fn attach_to_task(pool: &SqlitePool, image_id: Uuid) {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Images')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let task_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Task', 'todo')")
+        .bind(task_id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+
+    sqlx::query("INSERT INTO task_images (id, task_id, image_id) VALUES (?, ?, ?)")
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(image_id)
+        .execute(pool)
+        .await
+        .expect("Failed to attach image");
+}
+
+#[tokio::test]
+async fn test_collect_garbage_removes_old_unreferenced_images() {
+    let pool = create_test_db().await;
+    let (service, cache_dir) = create_service(&pool).await;
+
+    let orphan = service.store_image(b"orphan", "orphan.png").await.unwrap();
+    let attached = service
+        .store_image(b"attached", "attached.png")
+        .await
+        .unwrap();
+    let recent = service.store_image(b"recent", "recent.png").await.unwrap();
+    attach_to_task(&pool, attached.id).await;
+    backdate_image(&pool, orphan.id).await;
+    backdate_image(&pool, attached.id).await;
+
+    let old_stray = cache_dir.path().join("old-stray.png");
+    std::fs::write(&old_stray, b"stray").unwrap();
+    backdate_file(&old_stray);
+    let new_stray = cache_dir.path().join("new-stray.png");
+    std::fs::write(&new_stray, b"stray").unwrap();
+
+    let report = service.collect_garbage(ORPHAN_GRACE_PERIOD).await.unwrap();
+    assert_eq!(report.images_removed, 1);
+    assert_eq!(report.stray_files_removed, 1);
+    assert_eq!(
+        report.bytes_reclaimed,
+        (b"orphan".len() + b"stray".len()) as u64
+    );
+    assert_eq!(report.failed, 0);
+
+    assert!(Image::find_by_id(&pool, orphan.id).await.unwrap().is_none());
+    assert!(!service.get_absolute_path(&orphan).exists());
+    for kept in [&attached, &recent] {
+        assert!(Image::find_by_id(&pool, kept.id).await.unwrap().is_some());
+        assert!(service.get_absolute_path(kept).exists());
+    }
+    assert!(!old_stray.exists());
+    assert!(new_stray.exists());
+}
+
+#[tokio::test]
+async fn test_reupload_reuses_image_and_restarts_grace_period() {
+    let pool = create_test_db().await;
+    let (service, _cache_dir) = create_service(&pool).await;
+
+    let first = service
+        .store_image(b"same bytes", "first.png")
+        .await
+        .unwrap();
+    backdate_image(&pool, first.id).await;
+    std::fs::remove_file(service.get_absolute_path(&first)).unwrap();
+
+    let second = service
+        .store_image(b"same bytes", "second.png")
+        .await
+        .unwrap();
+    assert_eq!(second.id, first.id);
+    assert_eq!(
+        std::fs::read(service.get_absolute_path(&second)).unwrap(),
+        b"same bytes"
+    );
+
+    // The upload counts as recent again, so the image is kept
+    let report = service.collect_garbage(ORPHAN_GRACE_PERIOD).await.unwrap();
+    assert_eq!(report.images_removed, 0);
+    assert!(Image::find_by_id(&pool, first.id).await.unwrap().is_some());
+}
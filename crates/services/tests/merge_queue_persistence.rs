@@ -0,0 +1,163 @@
+//! Integration tests for the persisted merge queue.
+//!
+//! Tests verify:
+//! - Queue changes are written through to the database
+//! - Reloading restores the queue order, resets in-flight merges to queued
+//!   and flags every entry for revalidation
+
+use std::{sync::Arc, time::Duration};
+
+use db::models::merge_queue_entry::{MergeQueueEntry, MergeQueueStatus};
+use services::services::merge_queue_store::MergeQueueStore;
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+/// Creates a project with a repo; returns (project_id, repo_id).
+async fn create_test_project(pool: &SqlitePool) -> (Uuid, Uuid) {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, ?)")
+        .bind(project_id)
+        .bind("Test Project")
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let repo_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, ?, ?)")
+        .bind(repo_id)
+        .bind(format!("/tmp/{repo_id}"))
+        .bind("repo")
+        .bind("repo")
+        .execute(pool)
+        .await
+        .expect("Failed to create repo");
+
+    (project_id, repo_id)
+}
+
+/// Creates a task with a workspace; returns the workspace id.
+async fn create_test_workspace(pool: &SqlitePool, project_id: Uuid, title: &str) -> Uuid {
+    let task_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, ?, 'todo')")
+        .bind(task_id)
+        .bind(project_id)
+        .bind(title)
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{title}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+    workspace_id
+}
+
+/// Waits for the store's background writer to bring the table to a state
+/// matching `done`.
+async fn wait_for_persisted(
+    pool: &SqlitePool,
+    done: impl Fn(&[MergeQueueEntry]) -> bool,
+) -> Vec<MergeQueueEntry> {
+    for _ in 0..100 {
+        let rows = MergeQueueEntry::find_all(pool).await.unwrap();
+        if done(&rows) {
+            return rows;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("merge queue changes were never persisted");
+}
+
+#[tokio::test]
+async fn test_queue_survives_reload() {
+    let pool = create_test_db().await;
+    let (project_id, repo_id) = create_test_project(&pool).await;
+    let ws1 = create_test_workspace(&pool, project_id, "first").await;
+    let ws2 = create_test_workspace(&pool, project_id, "second").await;
+    let ws3 = create_test_workspace(&pool, project_id, "third").await;
+
+    let store = MergeQueueStore::load(Arc::new(MsgStore::new()), pool.clone())
+        .await
+        .unwrap();
+    assert!(store.get_all().is_empty());
+
+    let e1 = store.enqueue(project_id, ws1, repo_id, "First".to_string());
+    let e2 = store.enqueue(project_id, ws2, repo_id, "Second".to_string());
+    let e3 = store.enqueue(project_id, ws3, repo_id, "Third".to_string());
+
+    // Merge the first entry, then move the third ahead of the second
+    let claimed = store.claim_next(project_id).unwrap();
+    assert_eq!(claimed.id, e1.id);
+    assert!(store.reorder(project_id, &[e3.id, e2.id]));
+    store.remove(ws1);
+    let e1 = store.enqueue(project_id, ws1, repo_id, "First again".to_string());
+    store.claim_next(project_id).unwrap();
+
+    let expected = vec![e3.id, e2.id, e1.id];
+    wait_for_persisted(&pool, |rows| {
+        rows.iter().map(|e| e.id).eq(expected.iter().copied())
+            && rows[0].status == MergeQueueStatus::Merging
+    })
+    .await;
+
+    // Simulate a restart
+    drop(store);
+    let reloaded = MergeQueueStore::load(Arc::new(MsgStore::new()), pool.clone())
+        .await
+        .unwrap();
+
+    let entries = reloaded.list_by_project(project_id);
+    let ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+    assert_eq!(ids, expected);
+    assert!(
+        entries
+            .iter()
+            .all(|e| e.status == MergeQueueStatus::Queued && e.needs_revalidation)
+    );
+    assert_eq!(entries[1].commit_message, "Second");
+    assert_eq!(reloaded.projects_with_queued_entries(), vec![project_id]);
+}
+
+#[tokio::test]
+async fn test_removed_entries_are_not_reloaded() {
+    let pool = create_test_db().await;
+    let (project_id, repo_id) = create_test_project(&pool).await;
+    let ws1 = create_test_workspace(&pool, project_id, "first").await;
+    let ws2 = create_test_workspace(&pool, project_id, "second").await;
+
+    let store = MergeQueueStore::load(Arc::new(MsgStore::new()), pool.clone())
+        .await
+        .unwrap();
+    store.enqueue(project_id, ws1, repo_id, "First".to_string());
+    store.enqueue(project_id, ws2, repo_id, "Second".to_string());
+    store.remove(ws1);
+    wait_for_persisted(&pool, |rows| rows.len() == 1 && rows[0].workspace_id == ws2).await;
+
+    let reloaded = MergeQueueStore::load(Arc::new(MsgStore::new()), pool.clone())
+        .await
+        .unwrap();
+    assert!(reloaded.get(ws1).is_none());
+    assert!(reloaded.get(ws2).is_some());
+}
@@ -0,0 +1,108 @@
+//! Integration tests for the operation tracker.
+//!
+//! Tests verify:
+//! - Tracked operations are recorded as succeeded or failed
+//! - Operations left running by a previous process are failed on load
+//! - Streaming an operation ends once it has finished
+
+use db::models::operation::{Operation, OperationKind, OperationState};
+use futures::StreamExt;
+use services::services::operations::OperationTracker;
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use utils::log_msg::LogMsg;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+#[tokio::test]
+async fn test_track_records_outcome() {
+    let pool = create_test_db().await;
+    let tracker = OperationTracker::new(pool.clone());
+
+    let ok: Result<u32, String> = tracker
+        .track(OperationKind::Backup, None, None, async { Ok(7) })
+        .await;
+    assert_eq!(ok, Ok(7));
+    let failed: Result<(), String> = tracker
+        .track(OperationKind::Restore, None, None, async {
+            Err("archive is corrupt".to_string())
+        })
+        .await;
+    assert!(failed.is_err());
+
+    let operations = Operation::find_recent(&pool, None, None, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(operations.len(), 2);
+
+    let backup = operations
+        .iter()
+        .find(|op| op.kind == OperationKind::Backup)
+        .unwrap();
+    assert_eq!(backup.state, OperationState::Succeeded);
+    assert_eq!(backup.progress, Some(100));
+    assert!(backup.finished_at.is_some());
+
+    let restore = operations
+        .iter()
+        .find(|op| op.kind == OperationKind::Restore)
+        .unwrap();
+    assert_eq!(restore.state, OperationState::Failed);
+    assert_eq!(restore.error.as_deref(), Some("archive is corrupt"));
+}
+
+#[tokio::test]
+async fn test_load_fails_interrupted_operations() {
+    let pool = create_test_db().await;
+    let tracker = OperationTracker::new(pool.clone());
+    let id = tracker
+        .start(OperationKind::Merge, None, None)
+        .await
+        .unwrap();
+    tracker.progress(Some(id), 40, Some("Rebasing")).await;
+
+    // Simulate a restart
+    OperationTracker::load(pool.clone()).await.unwrap();
+
+    let operation = Operation::find_by_id(&pool, id).await.unwrap().unwrap();
+    assert_eq!(operation.state, OperationState::Failed);
+    assert_eq!(operation.progress, Some(40));
+    assert_eq!(operation.message.as_deref(), Some("Rebasing"));
+}
+
+#[tokio::test]
+async fn test_stream_ends_when_operation_finishes() {
+    let pool = create_test_db().await;
+    let tracker = OperationTracker::new(pool.clone());
+    let id = tracker.start(OperationKind::Merge, None, None).await;
+
+    let mut stream = tracker.stream_raw(id.unwrap()).await.unwrap().unwrap();
+    tracker.progress(id, 50, None).await;
+    tracker.finish::<String>(id, Ok(())).await;
+
+    let mut patches = 0;
+    let mut finished = false;
+    while let Some(msg) = stream.next().await {
+        match msg.unwrap() {
+            LogMsg::JsonPatch(_) => patches += 1,
+            LogMsg::Finished => finished = true,
+            _ => {}
+        }
+    }
+    // Snapshot, progress and finish
+    assert_eq!(patches, 3);
+    assert!(finished);
+}
@@ -0,0 +1,155 @@
+//! Integration tests for marking draft PRs ready for review.
+//!
+//! Tests verify:
+//! - Draft PRs stay drafts until review attention finds the task needs none
+//! - Projects that do not enable auto-ready keep their PRs as drafts
+//! - Once marked ready, the open PR is no longer a draft and is not picked
+//!   again
+
+use db::models::{
+    merge::{Merge, PrMerge},
+    pr_draft_policy::{PrDraftPolicy, UpdatePrDraftPolicy},
+};
+use services::services::pr_monitor::draft_ready_for_review;
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+/// Opens a draft PR for a new task, returning the project and task IDs with
+/// the PR.
+async fn create_draft_pr(pool: &SqlitePool) -> (Uuid, Uuid, PrMerge) {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Drafts')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let task_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Task', 'inreview')",
+    )
+    .bind(task_id)
+    .bind(project_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{task_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+
+    let repo_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, 'app', 'app')")
+        .bind(repo_id)
+        .bind(format!("/tmp/{repo_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create repo");
+
+    let pr = Merge::create_pr(
+        pool,
+        workspace_id,
+        repo_id,
+        "main",
+        42,
+        "https://github.com/acme/app/pull/42",
+        true,
+    )
+    .await
+    .expect("Failed to create PR");
+    (project_id, task_id, pr)
+}
+
+async fn set_needs_attention(pool: &SqlitePool, task_id: Uuid, needs_attention: bool) {
+    sqlx::query("UPDATE tasks SET needs_attention = ? WHERE id = ?")
+        .bind(needs_attention)
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .expect("Failed to set needs_attention");
+}
+
+async fn set_auto_ready(pool: &SqlitePool, project_id: Uuid, auto_ready: bool) {
+    PrDraftPolicy::upsert(
+        pool,
+        project_id,
+        &UpdatePrDraftPolicy {
+            draft_by_default: true,
+            auto_ready,
+        },
+    )
+    .await
+    .expect("Failed to save draft policy");
+}
+
+#[tokio::test]
+async fn test_draft_waits_for_review_attention() {
+    let pool = create_test_db().await;
+    let (project_id, task_id, pr) = create_draft_pr(&pool).await;
+    set_auto_ready(&pool, project_id, true).await;
+
+    // Not analysed yet
+    assert!(draft_ready_for_review(&pool, &pr).await.unwrap().is_none());
+
+    set_needs_attention(&pool, task_id, true).await;
+    assert!(draft_ready_for_review(&pool, &pr).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_draft_stays_draft_without_auto_ready() {
+    let pool = create_test_db().await;
+    let (project_id, task_id, pr) = create_draft_pr(&pool).await;
+    set_needs_attention(&pool, task_id, false).await;
+
+    // No policy saved for the project
+    assert!(draft_ready_for_review(&pool, &pr).await.unwrap().is_none());
+
+    set_auto_ready(&pool, project_id, false).await;
+    assert!(draft_ready_for_review(&pool, &pr).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_reviewed_draft_is_marked_ready_once() {
+    let pool = create_test_db().await;
+    let (project_id, task_id, pr) = create_draft_pr(&pool).await;
+    set_auto_ready(&pool, project_id, true).await;
+    set_needs_attention(&pool, task_id, false).await;
+
+    let task = draft_ready_for_review(&pool, &pr)
+        .await
+        .unwrap()
+        .expect("reviewed draft should be marked ready");
+    assert_eq!(task.id, task_id);
+
+    Merge::mark_ready(&pool, pr.id).await.unwrap();
+    let open_prs = Merge::get_open_prs(&pool).await.unwrap();
+    assert_eq!(open_prs.len(), 1);
+    assert!(!open_prs[0].is_draft);
+    assert!(
+        draft_ready_for_review(&pool, &open_prs[0])
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,332 @@
+//! Integration tests for purging a project.
+//!
+//! Tests verify:
+//! - The dry-run counts cover tasks, executions, conversations, images and vectors
+//! - Purging deletes all of them, including the sqlite-vec vectors
+//! - Images also used by another project, and that project's rows, survive
+
+use db::models::{
+    embedding::{EMBEDDING_DIMENSION, EmbeddingSource, TaskEmbedding},
+    project_purge,
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database with sqlite-vec loaded, all
+/// migrations run and the vector tables created.
+async fn create_test_db() -> SqlitePool {
+    assert!(db::init_sqlite_vec(), "sqlite-vec should be available");
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    TaskEmbedding::ensure_table_exists(&pool)
+        .await
+        .expect("Failed to create task vectors");
+    for source in EmbeddingSource::ALL {
+        source
+            .ensure_table_exists(&pool)
+            .await
+            .expect("Failed to create vectors");
+    }
+
+    pool
+}
+
+async fn create_project(pool: &SqlitePool, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, ?)")
+        .bind(id)
+        .bind(name)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+    id
+}
+
+/// Creates a task and returns its ID and rowid
+async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> (Uuid, i64) {
+    let id = Uuid::new_v4();
+    let rowid: i64 = sqlx::query_scalar(
+        "INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, ?, 'done')
+         RETURNING rowid",
+    )
+    .bind(id)
+    .bind(project_id)
+    .bind(title)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create task");
+    (id, rowid)
+}
+
+/// Creates a workspace and session for the task with one finished coding
+/// agent run, returning the turn's rowid.
+async fn create_finished_attempt(pool: &SqlitePool, task_id: Uuid) -> i64 {
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{task_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, workspace_id) VALUES (?, ?)")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create session");
+
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes (id, session_id, status, run_reason)
+         VALUES (?, ?, 'completed', 'codingagent')",
+    )
+    .bind(process_id)
+    .bind(session_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+
+    sqlx::query(
+        "INSERT INTO execution_process_normalized_entries (execution_id, entry_index, entry_json)
+         VALUES (?, 0, '{}')",
+    )
+    .bind(process_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create normalized entry");
+
+    sqlx::query_scalar(
+        "INSERT INTO coding_agent_turns (id, execution_process_id, prompt, summary)
+         VALUES (?, ?, 'Fix the bug', 'Fixed the bug')
+         RETURNING rowid",
+    )
+    .bind(Uuid::new_v4())
+    .bind(process_id)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create coding agent turn")
+}
+
+/// Creates a conversation with one message, returning the conversation ID
+/// and the message's rowid.
+async fn create_conversation(pool: &SqlitePool, project_id: Uuid) -> (Uuid, i64) {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO conversation_sessions (id, project_id, title) VALUES (?, ?, 'Chat')")
+        .bind(id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create conversation");
+
+    sqlx::query(
+        "INSERT INTO execution_processes (id, conversation_session_id, status, run_reason)
+         VALUES (?, ?, 'completed', 'disposableconversation')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("Failed to create conversation process");
+
+    let rowid = sqlx::query_scalar(
+        "INSERT INTO conversation_messages (id, conversation_session_id, role, content)
+         VALUES (?, ?, 'user', 'How does the queue work?')
+         RETURNING rowid",
+    )
+    .bind(Uuid::new_v4())
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create conversation message");
+    (id, rowid)
+}
+
+async fn create_image(pool: &SqlitePool, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO images (id, file_path, original_name, size_bytes, hash)
+         VALUES (?, ?, ?, 1, ?)",
+    )
+    .bind(id)
+    .bind(format!("{id}.png"))
+    .bind(name)
+    .bind(id.to_string())
+    .execute(pool)
+    .await
+    .expect("Failed to create image");
+    id
+}
+
+async fn attach_to_task(pool: &SqlitePool, task_id: Uuid, image_id: Uuid) {
+    sqlx::query("INSERT INTO task_images (id, task_id, image_id) VALUES (?, ?, ?)")
+        .bind(Uuid::new_v4())
+        .bind(task_id)
+        .bind(image_id)
+        .execute(pool)
+        .await
+        .expect("Failed to attach image to task");
+}
+
+async fn attach_to_conversation(pool: &SqlitePool, conversation_id: Uuid, image_id: Uuid) {
+    sqlx::query(
+        "INSERT INTO conversation_images (id, conversation_session_id, image_id) VALUES (?, ?, ?)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(conversation_id)
+    .bind(image_id)
+    .execute(pool)
+    .await
+    .expect("Failed to attach image to conversation");
+}
+
+async fn count_rows(pool: &SqlitePool, sql: &str) -> i64 {
+    sqlx::query_scalar(sql)
+        .fetch_one(pool)
+        .await
+        .expect("Failed to count rows")
+}
+
+#[tokio::test]
+async fn test_purge_counts_and_deletes_project_data() {
+    let pool = create_test_db().await;
+    let vector = vec![0.1; EMBEDDING_DIMENSION];
+
+    let project_id = create_project(&pool, "Purged").await;
+    let other_project_id = create_project(&pool, "Kept").await;
+
+    let (task_id, task_rowid) = create_task(&pool, project_id, "Purged task").await;
+    let (other_task_id, other_task_rowid) = create_task(&pool, other_project_id, "Kept task").await;
+    let turn_rowid = create_finished_attempt(&pool, task_id).await;
+    let other_turn_rowid = create_finished_attempt(&pool, other_task_id).await;
+    let (conversation_id, message_rowid) = create_conversation(&pool, project_id).await;
+
+    let task_image = create_image(&pool, "task.png").await;
+    let conversation_image = create_image(&pool, "conversation.png").await;
+    let shared_image = create_image(&pool, "shared.png").await;
+    attach_to_task(&pool, task_id, task_image).await;
+    attach_to_conversation(&pool, conversation_id, conversation_image).await;
+    attach_to_task(&pool, task_id, shared_image).await;
+    attach_to_task(&pool, other_task_id, shared_image).await;
+
+    TaskEmbedding::upsert(&pool, task_rowid, &vector)
+        .await
+        .unwrap();
+    TaskEmbedding::upsert(&pool, other_task_rowid, &vector)
+        .await
+        .unwrap();
+    EmbeddingSource::ExecutionSummary
+        .upsert(&pool, turn_rowid, &vector)
+        .await
+        .unwrap();
+    EmbeddingSource::ExecutionSummary
+        .upsert(&pool, other_turn_rowid, &vector)
+        .await
+        .unwrap();
+    EmbeddingSource::ConversationMessage
+        .upsert(&pool, message_rowid, &vector)
+        .await
+        .unwrap();
+
+    // Dry run
+    let mut conn = pool.acquire().await.unwrap();
+    let counts = project_purge::count(&mut conn, project_id).await.unwrap();
+    drop(conn);
+    assert_eq!(counts.tasks, 1);
+    assert_eq!(counts.workspaces, 1);
+    assert_eq!(counts.sessions, 1);
+    assert_eq!(
+        counts.execution_processes, 2,
+        "attempt and conversation runs"
+    );
+    assert_eq!(counts.agent_turns, 1);
+    assert_eq!(counts.normalized_entries, 1);
+    assert_eq!(counts.conversations, 1);
+    assert_eq!(counts.conversation_messages, 1);
+    assert_eq!(counts.images, 2, "the shared image is not counted");
+    assert_eq!(counts.embeddings, 3);
+    assert_eq!(count_rows(&pool, "SELECT COUNT(*) FROM tasks").await, 2);
+
+    let mut tx = pool.begin().await.unwrap();
+    let purged = project_purge::purge(&mut tx, project_id).await.unwrap();
+    tx.commit().await.unwrap();
+
+    assert_eq!(purged.counts.tasks, counts.tasks);
+    assert_eq!(purged.counts.embeddings, counts.embeddings);
+    let mut purged_images: Vec<Uuid> = purged.images.iter().map(|i| i.id).collect();
+    let mut expected_images = vec![task_image, conversation_image];
+    purged_images.sort();
+    expected_images.sort();
+    assert_eq!(purged_images, expected_images);
+
+    // Only the other project's rows remain
+    for (table, remaining) in [
+        ("projects", 1),
+        ("tasks", 1),
+        ("workspaces", 1),
+        ("sessions", 1),
+        ("execution_processes", 1),
+        ("execution_process_normalized_entries", 1),
+        ("coding_agent_turns", 1),
+        ("conversation_sessions", 0),
+        ("conversation_messages", 0),
+        ("task_images", 1),
+        ("conversation_images", 0),
+        ("task_embeddings", 1),
+        ("execution_summary_embeddings", 1),
+        ("conversation_message_embeddings", 0),
+    ] {
+        assert_eq!(
+            count_rows(&pool, &format!("SELECT COUNT(*) FROM {table}")).await,
+            remaining,
+            "rows left in {table}"
+        );
+    }
+
+    let images: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM images")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(images, vec![shared_image]);
+
+    let other_task_vector: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM task_embeddings WHERE task_rowid = ?")
+            .bind(other_task_rowid)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(other_task_vector, 1);
+}
+
+#[tokio::test]
+async fn test_purge_of_empty_project_counts_nothing() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool, "Empty").await;
+    let other_project_id = create_project(&pool, "Kept").await;
+    let (other_task_id, _) = create_task(&pool, other_project_id, "Kept task").await;
+    let image = create_image(&pool, "kept.png").await;
+    attach_to_task(&pool, other_task_id, image).await;
+
+    let mut tx = pool.begin().await.unwrap();
+    let purged = project_purge::purge(&mut tx, project_id).await.unwrap();
+    tx.commit().await.unwrap();
+
+    assert_eq!(purged.counts.tasks, 0);
+    assert_eq!(purged.counts.images, 0);
+    assert_eq!(purged.counts.embeddings, 0);
+    assert!(purged.images.is_empty());
+    assert_eq!(count_rows(&pool, "SELECT COUNT(*) FROM projects").await, 1);
+    assert_eq!(count_rows(&pool, "SELECT COUNT(*) FROM images").await, 1);
+}
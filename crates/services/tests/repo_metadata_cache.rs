@@ -0,0 +1,43 @@
+use git2::BranchType;
+use services::services::{
+    git::GitService, git_watcher::GitWatcherManager, repo_metadata_cache::RepoMetadataCache,
+};
+use tempfile::TempDir;
+
+fn local_branch_names(cache: &RepoMetadataCache, repo_path: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = cache
+        .branches(repo_path)
+        .unwrap()
+        .into_iter()
+        .filter(|b| !b.is_remote)
+        .map(|b| b.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn branches_are_served_from_cache_until_invalidated() {
+    let root = TempDir::new().unwrap();
+    let repo_path = root.path().join("repo");
+    let git = GitService::new();
+    git.initialize_repo_with_main_branch(&repo_path).unwrap();
+    let cache = RepoMetadataCache::new(git.clone(), GitWatcherManager::new());
+
+    assert_eq!(local_branch_names(&cache, &repo_path), vec!["main"]);
+
+    git.create_branch(&repo_path, "feature", None).unwrap();
+    assert_eq!(local_branch_names(&cache, &repo_path), vec!["main"]);
+
+    cache.invalidate(&repo_path);
+    assert_eq!(
+        local_branch_names(&cache, &repo_path),
+        vec!["feature", "main"]
+    );
+    assert_eq!(
+        cache
+            .branch_status(&repo_path, "feature", "main", BranchType::Local)
+            .unwrap(),
+        (0, 0)
+    );
+}
@@ -0,0 +1,142 @@
+//! Integration tests for repo-level setup and cleanup scripts.
+//!
+//! Tests verify:
+//! - Projects that set no script for a repo use the repo's scripts
+//! - A project's own script takes precedence over the repo's
+//! - Saving blank scripts on a repo clears them
+
+use db::models::{
+    project_repo::ProjectRepo,
+    repo::{Repo, UpdateRepoScripts},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+async fn create_repo(pool: &SqlitePool, name: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, ?, ?)")
+        .bind(id)
+        .bind(format!("/tmp/{id}"))
+        .bind(name)
+        .bind(name)
+        .execute(pool)
+        .await
+        .expect("Failed to create repo");
+    id
+}
+
+/// Adds the repo to a new project, with the project's own setup script if
+/// given, returning the project ID.
+async fn create_project_with_repo(
+    pool: &SqlitePool,
+    repo_id: Uuid,
+    setup_script: Option<&str>,
+) -> Uuid {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Scripts')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    sqlx::query(
+        "INSERT INTO project_repos (id, project_id, repo_id, setup_script) VALUES (?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(project_id)
+    .bind(repo_id)
+    .bind(setup_script)
+    .execute(pool)
+    .await
+    .expect("Failed to add repo to project");
+    project_id
+}
+
+async fn project_scripts(pool: &SqlitePool, project_id: Uuid) -> (Option<String>, Option<String>) {
+    let repos = ProjectRepo::find_by_project_id_with_names(pool, project_id)
+        .await
+        .unwrap();
+    assert_eq!(repos.len(), 1);
+    let repo = repos.into_iter().next().unwrap();
+    (repo.setup_script, repo.cleanup_script)
+}
+
+#[tokio::test]
+async fn test_repo_scripts_are_project_fallbacks() {
+    let pool = create_test_db().await;
+    let repo_id = create_repo(&pool, "app").await;
+    let repo = Repo::update_scripts(
+        &pool,
+        repo_id,
+        &UpdateRepoScripts {
+            setup_script: Some("npm ci".to_string()),
+            cleanup_script: Some("npm run lint".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(repo.setup_script.as_deref(), Some("npm ci"));
+
+    let inheriting = create_project_with_repo(&pool, repo_id, None).await;
+    assert_eq!(
+        project_scripts(&pool, inheriting).await,
+        (Some("npm ci".to_string()), Some("npm run lint".to_string()))
+    );
+
+    let overriding = create_project_with_repo(&pool, repo_id, Some("pnpm install")).await;
+    assert_eq!(
+        project_scripts(&pool, overriding).await,
+        (
+            Some("pnpm install".to_string()),
+            Some("npm run lint".to_string())
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_blank_repo_scripts_are_cleared() {
+    let pool = create_test_db().await;
+    let repo_id = create_repo(&pool, "app").await;
+    Repo::update_scripts(
+        &pool,
+        repo_id,
+        &UpdateRepoScripts {
+            setup_script: Some("npm ci".to_string()),
+            cleanup_script: Some("npm run lint".to_string()),
+        },
+    )
+    .await
+    .unwrap();
+
+    let repo = Repo::update_scripts(
+        &pool,
+        repo_id,
+        &UpdateRepoScripts {
+            setup_script: Some("   ".to_string()),
+            cleanup_script: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(repo.setup_script, None);
+    assert_eq!(repo.cleanup_script, None);
+
+    let project_id = create_project_with_repo(&pool, repo_id, None).await;
+    assert_eq!(project_scripts(&pool, project_id).await, (None, None));
+}
@@ -0,0 +1,274 @@
+//! Integration tests for notifying the users watching a task.
+//!
+//! Tests verify:
+//! - Each watcher gets a notification of their own when the task changes
+//!   status, and users who stopped watching get none
+//! - A task moved to done whose work was merged is reported as merged
+//! - Notifications meant for one user are hidden from, and cannot be marked
+//!   read by, anyone else, while notifications for everyone stay visible
+
+use std::sync::Arc;
+
+use db::models::{
+    merge::Merge,
+    notification::{CreateNotification, Notification, NotificationFilter, NotificationType},
+    task::{Task, TaskStatus},
+    task_watcher::TaskWatcher,
+};
+use services::services::{
+    config::Config,
+    domain_events::{DomainEvent, EventHandler, HandlerContext, TaskWatcherHandler},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use tokio::sync::RwLock;
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+fn test_context(pool: &SqlitePool) -> HandlerContext {
+    let db = db::DBService { pool: pool.clone() };
+    let config = Arc::new(RwLock::new(Config::default()));
+    HandlerContext::new(db, config, Arc::new(MsgStore::default()), None)
+}
+
+async fn create_user(pool: &SqlitePool, username: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, username, password_hash) VALUES (?, ?, 'hash')")
+        .bind(id)
+        .bind(username)
+        .execute(pool)
+        .await
+        .expect("Failed to create user");
+    id
+}
+
+/// Creates a task with one workspace, returning the task and workspace IDs.
+async fn create_task(pool: &SqlitePool) -> (Uuid, Uuid) {
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Watching')")
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+
+    let task_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Fix login', 'inprogress')",
+    )
+    .bind(task_id)
+    .bind(project_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{task_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+    (task_id, workspace_id)
+}
+
+/// Moves the task to `status` and runs the watcher handler on the change
+async fn change_status(pool: &SqlitePool, task_id: Uuid, status: TaskStatus) {
+    let previous = Task::find_by_id(pool, task_id).await.unwrap().unwrap();
+    sqlx::query("UPDATE tasks SET status = ? WHERE id = ?")
+        .bind(status.to_string())
+        .bind(task_id)
+        .execute(pool)
+        .await
+        .expect("Failed to update task status");
+    let task = Task::find_by_id(pool, task_id).await.unwrap().unwrap();
+
+    let event = DomainEvent::TaskStatusChanged {
+        task,
+        previous_status: previous.status,
+    };
+    let handler = TaskWatcherHandler::new();
+    assert!(handler.handles(&event));
+    handler
+        .handle(event, &test_context(pool))
+        .await
+        .expect("Task watcher handler failed");
+}
+
+async fn notifications_for(pool: &SqlitePool, user_id: Uuid) -> Vec<Notification> {
+    let filter = NotificationFilter {
+        recipient: Some(user_id),
+        targeted_only: true,
+        ..Default::default()
+    };
+    let project_id: Uuid = sqlx::query_scalar("SELECT id FROM projects")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    Notification::find_by_project_id(pool, project_id, &filter, None)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_status_change_notifies_each_watcher() {
+    let pool = create_test_db().await;
+    let (task_id, _) = create_task(&pool).await;
+    let alice = create_user(&pool, "alice").await;
+    let bob = create_user(&pool, "bob").await;
+    let carol = create_user(&pool, "carol").await;
+    TaskWatcher::watch(&pool, task_id, alice).await.unwrap();
+    TaskWatcher::watch(&pool, task_id, bob).await.unwrap();
+    // Watching again is a no-op
+    TaskWatcher::watch(&pool, task_id, bob).await.unwrap();
+
+    change_status(&pool, task_id, TaskStatus::InReview).await;
+
+    for user_id in [alice, bob] {
+        let notifications = notifications_for(&pool, user_id).await;
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(
+            notifications[0].notification_type,
+            NotificationType::TaskStatusChanged
+        );
+        assert_eq!(notifications[0].user_id, Some(user_id));
+        assert_eq!(
+            notifications[0].metadata,
+            Some(serde_json::json!({ "task_id": task_id.to_string() }))
+        );
+    }
+    assert!(notifications_for(&pool, carol).await.is_empty());
+
+    assert_eq!(TaskWatcher::unwatch(&pool, task_id, bob).await.unwrap(), 1);
+    change_status(&pool, task_id, TaskStatus::InProgress).await;
+    assert_eq!(notifications_for(&pool, alice).await.len(), 2);
+    assert_eq!(notifications_for(&pool, bob).await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_merged_task_notifies_watchers_of_merge() {
+    let pool = create_test_db().await;
+    let (task_id, workspace_id) = create_task(&pool).await;
+    let alice = create_user(&pool, "alice").await;
+    TaskWatcher::watch(&pool, task_id, alice).await.unwrap();
+
+    let repo_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO repos (id, path, name, display_name) VALUES (?, ?, 'app', 'app')")
+        .bind(repo_id)
+        .bind(format!("/tmp/{repo_id}"))
+        .execute(&pool)
+        .await
+        .expect("Failed to create repo");
+    Merge::create_direct(&pool, workspace_id, repo_id, "main", "abc123")
+        .await
+        .unwrap();
+
+    change_status(&pool, task_id, TaskStatus::Done).await;
+
+    let notifications = notifications_for(&pool, alice).await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(
+        notifications[0].notification_type,
+        NotificationType::TaskMerged
+    );
+    assert_eq!(notifications[0].title, "Merged: Fix login");
+}
+
+#[tokio::test]
+async fn test_watcher_notifications_are_scoped_to_their_user() {
+    let pool = create_test_db().await;
+    let (task_id, workspace_id) = create_task(&pool).await;
+    let project_id = Task::find_by_id(&pool, task_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .project_id;
+    let alice = create_user(&pool, "alice").await;
+    let bob = create_user(&pool, "bob").await;
+    TaskWatcher::watch(&pool, task_id, alice).await.unwrap();
+
+    let for_everyone = Notification::create(
+        &pool,
+        &CreateNotification {
+            project_id: Some(project_id),
+            notification_type: NotificationType::AgentComplete,
+            title: "Task Complete: Fix login".to_string(),
+            message: "Done".to_string(),
+            metadata: None,
+            workspace_id: Some(workspace_id),
+            session_id: None,
+            conversation_session_id: None,
+            user_id: None,
+            group_key: None,
+        },
+    )
+    .await
+    .unwrap();
+    change_status(&pool, task_id, TaskStatus::InReview).await;
+    let for_alice = notifications_for(&pool, alice).await.remove(0);
+
+    let visible = |recipient: Option<Uuid>| {
+        let pool = pool.clone();
+        async move {
+            let filter = NotificationFilter {
+                recipient,
+                ..Default::default()
+            };
+            let notifications = Notification::find_by_project_id(&pool, project_id, &filter, None)
+                .await
+                .unwrap();
+            assert!(notifications.iter().all(|n| filter.matches(n)));
+            notifications.into_iter().map(|n| n.id).collect::<Vec<_>>()
+        }
+    };
+    let alice_sees = visible(Some(alice)).await;
+    assert_eq!(alice_sees.len(), 2);
+    assert!(alice_sees.contains(&for_alice.id));
+    assert_eq!(visible(Some(bob)).await, vec![for_everyone.id]);
+    assert_eq!(visible(None).await, vec![for_everyone.id]);
+
+    let stats = Notification::get_stats(&pool, Some(project_id), Some(bob))
+        .await
+        .unwrap();
+    assert_eq!((stats.total, stats.unread), (1, 1));
+
+    // Bob cannot mark Alice's notification read, directly or in bulk
+    assert_eq!(
+        Notification::mark_read(&pool, &[for_alice.id], true, Some(bob))
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        Notification::mark_all_read(&pool, Some(project_id), None, Some(bob))
+            .await
+            .unwrap(),
+        1
+    );
+    let stats = Notification::get_stats(&pool, Some(project_id), Some(alice))
+        .await
+        .unwrap();
+    assert_eq!((stats.total, stats.unread), (2, 1));
+
+    assert_eq!(
+        Notification::mark_read(&pool, &[for_alice.id], true, Some(alice))
+            .await
+            .unwrap(),
+        1
+    );
+}
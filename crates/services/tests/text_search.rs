@@ -0,0 +1,237 @@
+//! Integration tests for full-text search over conversations and execution logs.
+//!
+//! Tests verify:
+//! - The FTS triggers index conversation messages as they are inserted,
+//!   edited and deleted
+//! - Execution log entries are reindexed when an upsert rewrites them and
+//!   dropped from the index when deleted
+//! - Identifiers containing '_' only match as a whole
+//! - Search results carry their source and can be filtered by project
+
+use db::models::text_search::{TextSearchFilters, TextSearchMatch, TextSearchSource};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database and runs all migrations.
+async fn create_test_db() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+async fn create_project(pool: &SqlitePool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Search')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+    id
+}
+
+/// Creates a conversation in the project with one message, returning the
+/// message ID.
+async fn create_conversation(pool: &SqlitePool, project_id: Uuid, content: &str) -> Uuid {
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO conversation_sessions (id, project_id, title) VALUES (?, ?, 'Chat')")
+        .bind(session_id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create conversation");
+
+    let message_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO conversation_messages (id, conversation_session_id, role, content)
+         VALUES (?, ?, 'user', ?)",
+    )
+    .bind(message_id)
+    .bind(session_id)
+    .bind(content)
+    .execute(pool)
+    .await
+    .expect("Failed to create conversation message");
+    message_id
+}
+
+/// Creates a task with one coding agent run and no log entries yet,
+/// returning the task and execution process IDs.
+async fn create_task_with_run(pool: &SqlitePool, project_id: Uuid) -> (Uuid, Uuid) {
+    let task_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, 'Fix it', 'inprogress')",
+    )
+    .bind(task_id)
+    .bind(project_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{task_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, workspace_id, executor) VALUES (?, ?, 'CLAUDE_CODE')")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create session");
+
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes (id, session_id, status, run_reason)
+         VALUES (?, ?, 'running', 'codingagent')",
+    )
+    .bind(process_id)
+    .bind(session_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+    (task_id, process_id)
+}
+
+/// Writes a log entry the way a streaming process does, inserting it or
+/// rewriting it in place.
+async fn upsert_log_entry(pool: &SqlitePool, process_id: Uuid, entry_index: i64, content: &str) {
+    sqlx::query(
+        "INSERT INTO execution_process_normalized_entries (execution_id, entry_index, entry_json)
+         VALUES (?, ?, json_object('content', ?))
+         ON CONFLICT(execution_id, entry_index)
+         DO UPDATE SET entry_json = excluded.entry_json",
+    )
+    .bind(process_id)
+    .bind(entry_index)
+    .bind(content)
+    .execute(pool)
+    .await
+    .expect("Failed to write normalized entry");
+}
+
+async fn text_search(pool: &SqlitePool, query: &str) -> Vec<TextSearchMatch> {
+    TextSearchMatch::search(pool, query, &TextSearchFilters::default(), 10)
+        .await
+        .expect("Text search failed")
+}
+
+async fn indexed_documents(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM text_search_fts")
+        .fetch_one(pool)
+        .await
+        .expect("Failed to count indexed documents")
+}
+
+#[tokio::test]
+async fn test_conversation_messages_stay_in_sync_with_index() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let message_id = create_conversation(&pool, project_id, "Why does flaky_parser fail?").await;
+
+    let matches = text_search(&pool, "flaky_parser").await;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].source, TextSearchSource::ConversationMessage);
+    assert_eq!(matches[0].conversation_message_id, Some(message_id));
+    assert_eq!(matches[0].project_id, Some(project_id));
+    assert!(
+        matches[0]
+            .snippet
+            .iter()
+            .any(|part| part.highlighted && part.text == "flaky_parser")
+    );
+    // '_' is a token character, so part of the identifier does not match
+    assert!(text_search(&pool, "parser").await.is_empty());
+
+    sqlx::query("UPDATE conversation_messages SET content = ? WHERE id = ?")
+        .bind("Why does the tokenizer fail?")
+        .bind(message_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(text_search(&pool, "flaky_parser").await.is_empty());
+    assert_eq!(text_search(&pool, "tokenizer").await.len(), 1);
+
+    sqlx::query("DELETE FROM conversation_messages WHERE id = ?")
+        .bind(message_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(text_search(&pool, "tokenizer").await.is_empty());
+    assert_eq!(indexed_documents(&pool).await, 0);
+}
+
+#[tokio::test]
+async fn test_execution_log_entries_stay_in_sync_with_index() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let (task_id, process_id) = create_task_with_run(&pool, project_id).await;
+
+    upsert_log_entry(&pool, process_id, 0, "Reading flaky_parser").await;
+    upsert_log_entry(&pool, process_id, 1, "Running the tests").await;
+
+    let matches = text_search(&pool, "flaky_parser").await;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].source, TextSearchSource::ExecutionLog);
+    assert_eq!(matches[0].task_id, Some(task_id));
+    assert_eq!(matches[0].execution_process_id, Some(process_id));
+    assert_eq!(matches[0].entry_index, Some(0));
+    assert_eq!(matches[0].executor.as_deref(), Some("CLAUDE_CODE"));
+
+    // A streaming process rewrites entries in place
+    upsert_log_entry(&pool, process_id, 0, "Reading the tokenizer").await;
+    assert!(text_search(&pool, "flaky_parser").await.is_empty());
+    let matches = text_search(&pool, "tokenizer").await;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].entry_index, Some(0));
+    assert_eq!(indexed_documents(&pool).await, 2);
+
+    sqlx::query(
+        "DELETE FROM execution_process_normalized_entries
+         WHERE execution_id = ? AND entry_index = 0",
+    )
+    .bind(process_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert!(text_search(&pool, "tokenizer").await.is_empty());
+    assert_eq!(text_search(&pool, "tests").await.len(), 1);
+    assert_eq!(indexed_documents(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn test_search_filters_by_project() {
+    let pool = create_test_db().await;
+    let project_id = create_project(&pool).await;
+    let other_project_id = create_project(&pool).await;
+    create_conversation(&pool, project_id, "flaky_parser again").await;
+    create_conversation(&pool, other_project_id, "flaky_parser here too").await;
+    let (_, process_id) = create_task_with_run(&pool, project_id).await;
+    upsert_log_entry(&pool, process_id, 0, "Reading flaky_parser").await;
+
+    assert_eq!(text_search(&pool, "flaky_parser").await.len(), 3);
+    assert!(text_search(&pool, "   ").await.is_empty());
+
+    let filters = TextSearchFilters {
+        project_id: Some(project_id),
+        ..Default::default()
+    };
+    let matches = TextSearchMatch::search(&pool, "flaky_parser", &filters, 10)
+        .await
+        .unwrap();
+    assert_eq!(matches.len(), 2);
+    assert!(matches.iter().all(|m| m.project_id == Some(project_id)));
+}
@@ -0,0 +1,230 @@
+//! Integration tests for leaving trashed tasks and projects out of search.
+//!
+//! Tests verify:
+//! - Text and semantic search stop returning a task's logs and summaries
+//!   once the task is trashed, and return them again once it is restored
+//! - Conversations of a trashed project are no longer returned
+//! - The embedding worker no longer picks up rows of trashed tasks
+
+use db::models::{
+    embedding::{EMBEDDING_DIMENSION, EmbeddingSource, EmbeddingStatus},
+    project::Project,
+    semantic_search::{ConversationMessageMatch, ExecutionSummaryMatch},
+    task::Task,
+    text_search::{TextSearchFilters, TextSearchMatch},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Creates an in-memory SQLite database with sqlite-vec loaded and all
+/// migrations run.
+async fn create_test_db() -> SqlitePool {
+    assert!(db::init_sqlite_vec(), "sqlite-vec should be available");
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to create in-memory database");
+
+    sqlx::migrate!("../db/migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
+
+async fn create_project(pool: &SqlitePool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO projects (id, name) VALUES (?, 'Search')")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("Failed to create project");
+    id
+}
+
+/// Creates a task with one finished coding agent run whose log and summary
+/// mention `word`, returning the task ID and the turn's rowid.
+async fn create_task_with_run(pool: &SqlitePool, project_id: Uuid, word: &str) -> (Uuid, i64) {
+    let task_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, ?, ?, 'inprogress')")
+        .bind(task_id)
+        .bind(project_id)
+        .bind(format!("Fix {word}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create task");
+
+    let workspace_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO workspaces (id, task_id, branch) VALUES (?, ?, ?)")
+        .bind(workspace_id)
+        .bind(task_id)
+        .bind(format!("vk/{task_id}"))
+        .execute(pool)
+        .await
+        .expect("Failed to create workspace");
+
+    let session_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO sessions (id, workspace_id) VALUES (?, ?)")
+        .bind(session_id)
+        .bind(workspace_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create session");
+
+    let process_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO execution_processes (id, session_id, status, run_reason)
+         VALUES (?, ?, 'completed', 'codingagent')",
+    )
+    .bind(process_id)
+    .bind(session_id)
+    .execute(pool)
+    .await
+    .expect("Failed to create execution process");
+
+    sqlx::query(
+        "INSERT INTO execution_process_normalized_entries (execution_id, entry_index, entry_json)
+         VALUES (?, 0, json_object('content', ?))",
+    )
+    .bind(process_id)
+    .bind(format!("Looking into {word} now"))
+    .execute(pool)
+    .await
+    .expect("Failed to create normalized entry");
+
+    let turn_rowid = sqlx::query_scalar(
+        "INSERT INTO coding_agent_turns (id, execution_process_id, prompt, summary)
+         VALUES (?, ?, 'Fix it', ?)
+         RETURNING rowid",
+    )
+    .bind(Uuid::new_v4())
+    .bind(process_id)
+    .bind(format!("Fixed {word}"))
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create coding agent turn");
+    (task_id, turn_rowid)
+}
+
+/// Creates a conversation in the project with one message mentioning `word`,
+/// returning the message's rowid.
+async fn create_conversation(pool: &SqlitePool, project_id: Uuid, word: &str) -> i64 {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO conversation_sessions (id, project_id, title) VALUES (?, ?, 'Chat')")
+        .bind(id)
+        .bind(project_id)
+        .execute(pool)
+        .await
+        .expect("Failed to create conversation");
+
+    sqlx::query_scalar(
+        "INSERT INTO conversation_messages (id, conversation_session_id, role, content)
+         VALUES (?, ?, 'user', ?)
+         RETURNING rowid",
+    )
+    .bind(Uuid::new_v4())
+    .bind(id)
+    .bind(format!("Why does {word} fail?"))
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create conversation message")
+}
+
+async fn text_search(pool: &SqlitePool, query: &str) -> Vec<TextSearchMatch> {
+    TextSearchMatch::search(pool, query, &TextSearchFilters::default(), 10)
+        .await
+        .expect("Text search failed")
+}
+
+#[tokio::test]
+async fn test_trashed_task_is_left_out_of_search() {
+    let pool = create_test_db().await;
+    let vector = vec![0.1; EMBEDDING_DIMENSION];
+    let project_id = create_project(&pool).await;
+    let (task_id, turn_rowid) = create_task_with_run(&pool, project_id, "flaky_parser").await;
+    let (other_task_id, other_turn_rowid) =
+        create_task_with_run(&pool, project_id, "flaky_parser").await;
+    EmbeddingSource::ExecutionSummary
+        .ensure_table_exists(&pool)
+        .await
+        .unwrap();
+    for rowid in [turn_rowid, other_turn_rowid] {
+        EmbeddingSource::ExecutionSummary
+            .upsert(&pool, rowid, &vector)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(text_search(&pool, "flaky_parser").await.len(), 2);
+
+    Task::trash(&pool, task_id).await.unwrap();
+
+    let matches = text_search(&pool, "flaky_parser").await;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].task_id, Some(other_task_id));
+
+    let summaries = ExecutionSummaryMatch::search(&pool, project_id, &vector, 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        summaries.iter().map(|m| m.task_id).collect::<Vec<_>>(),
+        vec![other_task_id]
+    );
+
+    let pending_tasks: Vec<Uuid> = EmbeddingStatus::find_pending(&pool, 10)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|status| status.task_id)
+        .collect();
+    assert_eq!(pending_tasks, vec![other_task_id]);
+
+    Task::restore(&pool, task_id).await.unwrap();
+    assert_eq!(text_search(&pool, "flaky_parser").await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_conversations_of_trashed_project_are_left_out_of_search() {
+    let pool = create_test_db().await;
+    let vector = vec![0.1; EMBEDDING_DIMENSION];
+    let project_id = create_project(&pool).await;
+    let message_rowid = create_conversation(&pool, project_id, "flaky_parser").await;
+    EmbeddingSource::ConversationMessage
+        .ensure_table_exists(&pool)
+        .await
+        .unwrap();
+    EmbeddingSource::ConversationMessage
+        .upsert(&pool, message_rowid, &vector)
+        .await
+        .unwrap();
+
+    assert_eq!(text_search(&pool, "flaky_parser").await.len(), 1);
+    assert_eq!(
+        EmbeddingSource::ConversationMessage
+            .find_pending(&pool, 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    Project::trash(&pool, project_id).await.unwrap();
+
+    assert!(text_search(&pool, "flaky_parser").await.is_empty());
+    assert!(
+        ConversationMessageMatch::search(&pool, project_id, &vector, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        EmbeddingSource::ConversationMessage
+            .find_pending(&pool, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
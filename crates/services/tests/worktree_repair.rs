@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use services::services::{
+    git::GitService,
+    worktree_manager::{WorktreeIssue, WorktreeManager},
+};
+use tempfile::TempDir;
+
+fn create_repo_with_worktree(root: &TempDir) -> (PathBuf, PathBuf) {
+    let repo_path = root.path().join("repo");
+    GitService::new()
+        .initialize_repo_with_main_branch(&repo_path)
+        .expect("init repo");
+    let repo = Repository::open(&repo_path).unwrap();
+    let mut cfg = repo.config().unwrap();
+    cfg.set_str("user.name", "Test User").unwrap();
+    cfg.set_str("user.email", "test@example.com").unwrap();
+
+    let worktree_path = root.path().join("worktrees").join("repo");
+    (repo_path, worktree_path)
+}
+
+fn admin_dir(worktree_path: &Path) -> PathBuf {
+    let content = std::fs::read_to_string(worktree_path.join(".git")).unwrap();
+    PathBuf::from(content.trim().strip_prefix("gitdir:").unwrap().trim())
+}
+
+#[tokio::test]
+async fn repair_removes_lock_files_without_recreating() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = create_repo_with_worktree(&root);
+    WorktreeManager::create_worktree(&repo_path, "feature", &worktree_path, "main", true)
+        .await
+        .unwrap();
+    std::fs::write(worktree_path.join("notes.txt"), "uncommitted").unwrap();
+    let lock = admin_dir(&worktree_path).join("index.lock");
+    std::fs::write(&lock, "").unwrap();
+
+    let repair = WorktreeManager::repair_worktree(&repo_path, "feature", &worktree_path)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repair.issues,
+        vec![WorktreeIssue::StaleLock {
+            path: lock.to_string_lossy().to_string()
+        }]
+    );
+    assert!(!repair.recreated);
+    assert!(!lock.exists());
+    assert!(worktree_path.join("notes.txt").exists());
+}
+
+#[tokio::test]
+async fn ensure_recreates_worktree_with_deleted_admin_dir() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = create_repo_with_worktree(&root);
+    WorktreeManager::create_worktree(&repo_path, "feature", &worktree_path, "main", true)
+        .await
+        .unwrap();
+    std::fs::remove_dir_all(admin_dir(&worktree_path)).unwrap();
+
+    WorktreeManager::ensure_worktree_exists(&repo_path, "feature", &worktree_path)
+        .await
+        .unwrap();
+
+    let repair = WorktreeManager::repair_worktree(&repo_path, "feature", &worktree_path)
+        .await
+        .unwrap();
+    assert!(repair.issues.is_empty());
+    let worktree = Repository::open(&worktree_path).unwrap();
+    assert_eq!(worktree.head().unwrap().shorthand(), Some("feature"));
+}
+
+#[tokio::test]
+async fn commit_dirs_leave_hooks_and_config_out() {
+    let root = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = create_repo_with_worktree(&root);
+    WorktreeManager::create_worktree(&repo_path, "feature", &worktree_path, "main", true)
+        .await
+        .unwrap();
+
+    let dirs = WorktreeManager::commit_dirs(&repo_path, &worktree_path);
+    let git_dir = repo_path.join(".git");
+    assert!(dirs.contains(&git_dir.join("objects")));
+    assert!(dirs.contains(&git_dir.join("refs")));
+    assert!(dirs.iter().any(
+        |dir| dir.canonicalize().unwrap() == admin_dir(&worktree_path).canonicalize().unwrap()
+    ));
+    assert!(!dirs.contains(&git_dir));
+    assert!(
+        !dirs
+            .iter()
+            .any(|dir| dir.starts_with(git_dir.join("hooks")))
+    );
+}
@@ -0,0 +1,297 @@
+//! Talking to a backend that listens on a Unix domain socket, or a named pipe
+//! on Windows, instead of a TCP port.
+//!
+//! The webview can't open sockets, so requests go through the app:
+//! - Plain requests are made to the [`SCHEME`] URI scheme, whose handler
+//!   forwards them over the socket and returns the buffered response.
+//! - Live streams can't be buffered. The frontend opens them with the
+//!   `open_backend_stream` command instead; the app requests the server-sent
+//!   event version of the stream and passes each event's data to the webview
+//!   over a channel.
+//!
+//! Webviews don't reliably keep cookies set by custom scheme responses, and
+//! streams are requested by the app itself, so the app keeps the session
+//! cookie the backend sets and sends it with requests that carry none.
+
+use std::{collections::HashMap, sync::Arc};
+
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    client::conn::http1,
+    header::{self, HeaderMap, HeaderValue},
+    Request, Response,
+};
+use hyper_util::rt::TokioIo;
+use serde::Serialize;
+use tauri::{http, ipc::Channel};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::Mutex,
+    task::JoinHandle,
+};
+
+/// URI scheme the webview makes backend requests to
+pub const SCHEME: &str = "vibe-kanban";
+
+/// Base URL of [`SCHEME`] as the webview sees it
+pub fn base_url() -> &'static str {
+    // WebView2 only serves custom schemes over http
+    if cfg!(windows) {
+        "http://vibe-kanban.localhost"
+    } else {
+        "vibe-kanban://localhost"
+    }
+}
+
+/// Name of the backend's session cookie
+const SESSION_COOKIE: &str = "vk_session";
+
+/// The `name=value` session cookie the backend last set, if any
+static SESSION: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// Send the kept session cookie with a request that has no cookies
+fn add_session(headers: &mut HeaderMap) {
+    if headers.contains_key(header::COOKIE) {
+        return;
+    }
+    let session = SESSION.lock().expect("session lock poisoned").clone();
+    if let Some(value) = session.and_then(|cookie| HeaderValue::from_str(&cookie).ok()) {
+        headers.insert(header::COOKIE, value);
+    }
+}
+
+/// Keep the session cookie set by a response, or forget it on sign-out
+fn remember_session(headers: &HeaderMap) {
+    for value in headers.get_all(header::SET_COOKIE) {
+        if let Some(session) = value.to_str().ok().and_then(session_from_set_cookie) {
+            *SESSION.lock().expect("session lock poisoned") = session;
+        }
+    }
+}
+
+/// The session cookie a `Set-Cookie` value sets: None if it is about another
+/// cookie, Some(None) if it clears the session
+fn session_from_set_cookie(set_cookie: &str) -> Option<Option<String>> {
+    let pair = set_cookie.split(';').next()?.trim();
+    let token = pair.strip_prefix(SESSION_COOKIE)?.strip_prefix('=')?;
+    Some((!token.is_empty()).then(|| format!("{SESSION_COOKIE}={token}")))
+}
+
+trait BackendIo: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> BackendIo for T {}
+
+#[cfg(unix)]
+async fn connect(socket: &str) -> std::io::Result<Box<dyn BackendIo>> {
+    Ok(Box::new(tokio::net::UnixStream::connect(socket).await?))
+}
+
+#[cfg(windows)]
+async fn connect(socket: &str) -> std::io::Result<Box<dyn BackendIo>> {
+    use std::time::Duration;
+
+    use tokio::net::windows::named_pipe::ClientOptions;
+
+    /// Returned while every instance of the pipe is busy
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    loop {
+        match ClientOptions::new().open(socket) {
+            Ok(client) => return Ok(Box::new(client)),
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Send `request` to the backend on `socket` over a new connection
+async fn send(socket: &str, request: Request<Full<Bytes>>) -> anyhow::Result<Response<Incoming>> {
+    let io = connect(socket).await?;
+    let (mut sender, connection) = http1::handshake(TokioIo::new(io)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::debug!("Backend socket connection closed: {}", e);
+        }
+    });
+    Ok(sender.send_request(request).await?)
+}
+
+/// Forward a request made to [`SCHEME`] to the backend and buffer its response
+pub async fn forward(socket: &str, request: http::Request<Vec<u8>>) -> http::Response<Vec<u8>> {
+    match try_forward(socket, request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!("Failed to reach backend on {}: {}", socket, e);
+            http::Response::builder()
+                .status(http::StatusCode::BAD_GATEWAY)
+                .header(header::CONTENT_TYPE, "text/plain")
+                .body(format!("Failed to reach the backend: {e}").into_bytes())
+                .expect("valid response")
+        }
+    }
+}
+
+async fn try_forward(
+    socket: &str,
+    request: http::Request<Vec<u8>>,
+) -> anyhow::Result<http::Response<Vec<u8>>> {
+    let (mut parts, body) = request.into_parts();
+    // Only the path and query mean anything to the backend
+    parts.uri = parts
+        .uri
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or("/")
+        .parse()?;
+    parts
+        .headers
+        .insert(header::HOST, HeaderValue::from_static("localhost"));
+    add_session(&mut parts.headers);
+
+    let response = send(
+        socket,
+        Request::from_parts(parts, Full::new(Bytes::from(body))),
+    )
+    .await?;
+    let (parts, body) = response.into_parts();
+    remember_session(&parts.headers);
+    let body = body.collect().await?.to_bytes();
+    Ok(http::Response::from_parts(parts, body.to_vec()))
+}
+
+/// What the webview receives on a stream opened with `open_backend_stream`
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackendStreamEvent {
+    Open,
+    /// The data of one server-sent event
+    Message {
+        data: String,
+    },
+    Closed {
+        error: Option<String>,
+    },
+}
+
+/// Streams opened by the webview, so they can be closed again
+#[derive(Default)]
+pub struct BackendStreams {
+    next_id: Mutex<u32>,
+    tasks: Arc<Mutex<HashMap<u32, JoinHandle<()>>>>,
+}
+
+impl BackendStreams {
+    /// Start streaming `path` from the backend on `socket` to `channel`
+    pub async fn open(
+        &self,
+        socket: String,
+        path: String,
+        channel: Channel<BackendStreamEvent>,
+    ) -> u32 {
+        let id = {
+            let mut next_id = self.next_id.lock().await;
+            *next_id += 1;
+            *next_id
+        };
+        // Held until the task is recorded, so it can't be removed first
+        let mut tasks = self.tasks.lock().await;
+        let all_tasks = self.tasks.clone();
+        let task = tokio::spawn(async move {
+            let error = stream(&socket, &path, &channel).await.err();
+            let _ = channel.send(BackendStreamEvent::Closed {
+                error: error.map(|e| e.to_string()),
+            });
+            all_tasks.lock().await.remove(&id);
+        });
+        tasks.insert(id, task);
+        id
+    }
+
+    pub async fn close(&self, id: u32) {
+        if let Some(task) = self.tasks.lock().await.remove(&id) {
+            task.abort();
+        }
+    }
+}
+
+async fn stream(
+    socket: &str,
+    path: &str,
+    channel: &Channel<BackendStreamEvent>,
+) -> anyhow::Result<()> {
+    let mut request = Request::get(path)
+        .header(header::HOST, "localhost")
+        .header(header::ACCEPT, "text/event-stream")
+        .body(Full::new(Bytes::new()))?;
+    add_session(request.headers_mut());
+    let response = send(socket, request).await?;
+    if !response.status().is_success() {
+        anyhow::bail!("Backend responded with {}", response.status());
+    }
+    channel.send(BackendStreamEvent::Open)?;
+
+    let mut body = response.into_body();
+    // Bytes, since a frame can end inside a character
+    let mut buffer: Vec<u8> = Vec::new();
+    while let Some(frame) = body.frame().await {
+        let Ok(data) = frame?.into_data() else {
+            continue;
+        };
+        buffer.extend_from_slice(&data);
+        while let Some(end) = buffer.windows(2).position(|pair| pair == b"\n\n") {
+            let event: Vec<u8> = buffer.drain(..end + 2).collect();
+            if let Some(data) = event_data(&String::from_utf8_lossy(&event)) {
+                channel.send(BackendStreamEvent::Message { data })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The data of a server-sent event, or None for comments and keep-alives
+fn event_data(event: &str) -> Option<String> {
+    let lines: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_server_sent_event_data() {
+        assert_eq!(
+            event_data("data: {\"a\":1}\n\n"),
+            Some("{\"a\":1}".to_string())
+        );
+        assert_eq!(
+            event_data("event: message\ndata: one\ndata:two\n\n"),
+            Some("one\ntwo".to_string())
+        );
+        assert_eq!(event_data(": keep-alive\n\n"), None);
+    }
+
+    #[test]
+    fn parses_session_from_set_cookie() {
+        assert_eq!(
+            session_from_set_cookie("vk_session=abc; Path=/; HttpOnly; Max-Age=60"),
+            Some(Some("vk_session=abc".to_string()))
+        );
+        assert_eq!(
+            session_from_set_cookie("vk_session=; Path=/; Max-Age=0"),
+            Some(None)
+        );
+        assert_eq!(
+            session_from_set_cookie("vk_session_other=abc; Path=/"),
+            None
+        );
+        assert_eq!(session_from_set_cookie("theme=dark"), None);
+    }
+}
@@ -4,22 +4,53 @@
 //! - Get the current server URL
 //! - Set a custom server URL
 //! - Launch/stop the MCP server
+//! - Open live streams from a backend reached over a socket
 
-use tauri::{AppHandle, State};
+use tauri::{ipc::Channel, AppHandle, State};
 
-use crate::{mcp_launcher, state::AppState};
+use crate::{
+    backend_socket::{self, BackendStreamEvent},
+    mcp_launcher,
+    state::AppState,
+};
 
 /// Returns the backend URL from config.
 async fn get_backend_url(state: &AppState) -> String {
     state.backend_url.lock().await.clone()
 }
 
-/// Returns the current server URL from config.
+/// Returns the URL the frontend reaches the server at: the backend URL from
+/// config, or the app's own URI scheme when the backend is on a socket.
 #[tauri::command]
 pub async fn get_server_url(state: State<'_, AppState>) -> Result<String, String> {
+    if state.backend_socket.is_some() {
+        return Ok(backend_socket::base_url().to_string());
+    }
     Ok(get_backend_url(&state).await)
 }
 
+/// Streams `path` (e.g. `/api/tasks/stream/ws?project_id=...`) from a backend
+/// reached over a socket, returning an id to close the stream with.
+#[tauri::command]
+pub async fn open_backend_stream(
+    path: String,
+    on_event: Channel<BackendStreamEvent>,
+    state: State<'_, AppState>,
+) -> Result<u32, String> {
+    let socket = state
+        .backend_socket
+        .clone()
+        .ok_or_else(|| "The backend is not reached over a socket".to_string())?;
+    Ok(state.backend_streams.open(socket, path, on_event).await)
+}
+
+/// Closes a stream opened with `open_backend_stream`.
+#[tauri::command]
+pub async fn close_backend_stream(id: u32, state: State<'_, AppState>) -> Result<(), String> {
+    state.backend_streams.close(id).await;
+    Ok(())
+}
+
 /// Sets a custom server URL.
 ///
 /// The setting is persisted to the config file.
@@ -1,5 +1,6 @@
 // Tauri app library - required for mobile builds and code organization
 
+pub mod backend_socket;
 pub mod commands;
 pub mod mcp_launcher;
 pub mod state;
@@ -1,6 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri_app_lib::state::AppState;
+use tauri_app_lib::{backend_socket, state::AppState};
 
 fn main() {
     // Install rustls crypto provider before any HTTPS requests are made.
@@ -15,6 +15,7 @@ fn main() {
     // Load saved state from config
     let app_state = AppState::load_from_config();
     let mcp_port = app_state.mcp_port;
+    let backend_socket = app_state.backend_socket.clone();
 
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
@@ -29,6 +30,19 @@ fn main() {
             .build(),
     );
 
+    // Requests to the backend go through the app when it is on a socket
+    if let Some(socket) = backend_socket {
+        builder = builder.register_asynchronous_uri_scheme_protocol(
+            backend_socket::SCHEME,
+            move |_ctx, request, responder| {
+                let socket = socket.clone();
+                tauri::async_runtime::spawn(async move {
+                    responder.respond(backend_socket::forward(&socket, request).await);
+                });
+            },
+        );
+    }
+
     builder
         .manage(app_state)
         .invoke_handler(tauri::generate_handler![
@@ -36,7 +50,9 @@ fn main() {
             tauri_app_lib::commands::set_server_url,
             tauri_app_lib::commands::launch_mcp_server,
             tauri_app_lib::commands::stop_mcp_server,
-            tauri_app_lib::commands::is_mcp_server_running
+            tauri_app_lib::commands::is_mcp_server_running,
+            tauri_app_lib::commands::open_backend_stream,
+            tauri_app_lib::commands::close_backend_stream
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
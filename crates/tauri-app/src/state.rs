@@ -4,12 +4,17 @@ use serde::{Deserialize, Serialize};
 use tokio::{process::Child, sync::Mutex};
 use utils::assets::asset_dir;
 
+use crate::backend_socket::BackendStreams;
+
 /// Default MCP bridge port (matches tauri-plugin-mcp-bridge default)
 pub const DEFAULT_MCP_PORT: u16 = 9223;
 
 /// Default backend URL
 pub const DEFAULT_BACKEND_URL: &str = "http://127.0.0.1:9876";
 
+/// Environment variable naming the backend's socket, as for the server
+pub const BACKEND_SOCKET_ENV: &str = "VIBE_KANBAN_SOCKET";
+
 /// Configuration stored in tauri-config.json
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TauriConfig {
@@ -19,6 +24,10 @@ pub struct TauriConfig {
     /// MCP bridge WebSocket server port
     #[serde(default = "default_mcp_port")]
     pub mcp_port: u16,
+    /// Unix domain socket path, or Windows named pipe, the backend listens
+    /// on. Takes precedence over `backend_url` when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_socket: Option<String>,
 }
 
 impl Default for TauriConfig {
@@ -26,6 +35,7 @@ impl Default for TauriConfig {
         Self {
             backend_url: DEFAULT_BACKEND_URL.to_string(),
             mcp_port: DEFAULT_MCP_PORT,
+            backend_socket: None,
         }
     }
 }
@@ -45,6 +55,10 @@ pub struct AppState {
     pub mcp_process_handle: Arc<Mutex<Option<Child>>>,
     /// MCP bridge port from config
     pub mcp_port: u16,
+    /// Socket or named pipe to reach the backend on instead of `backend_url`
+    pub backend_socket: Option<String>,
+    /// Live streams opened over `backend_socket`
+    pub backend_streams: BackendStreams,
 }
 
 impl AppState {
@@ -53,6 +67,8 @@ impl AppState {
             backend_url: Arc::new(Mutex::new(DEFAULT_BACKEND_URL.to_string())),
             mcp_process_handle: Arc::new(Mutex::new(None)),
             mcp_port: DEFAULT_MCP_PORT,
+            backend_socket: None,
+            backend_streams: BackendStreams::default(),
         }
     }
 
@@ -76,6 +92,7 @@ impl AppState {
                         );
                         *state.backend_url.blocking_lock() = config.backend_url;
                         state.mcp_port = config.mcp_port;
+                        state.backend_socket = config.backend_socket;
                     }
                     Err(e) => {
                         tracing::warn!(
@@ -95,6 +112,15 @@ impl AppState {
             );
         }
 
+        if let Ok(socket) = std::env::var(BACKEND_SOCKET_ENV) {
+            if !socket.trim().is_empty() {
+                state.backend_socket = Some(socket.trim().to_string());
+            }
+        }
+        if let Some(socket) = &state.backend_socket {
+            tracing::info!("Connecting to the backend over {}", socket);
+        }
+
         state
     }
 
@@ -109,6 +135,7 @@ impl AppState {
         let config = TauriConfig {
             backend_url: url.clone(),
             mcp_port: self.mcp_port,
+            backend_socket: self.backend_socket.clone(),
         };
         let contents = serde_json::to_string_pretty(&config)?;
         std::fs::write(&config_path, contents)?;
@@ -132,10 +159,12 @@ mod tests {
         let config = TauriConfig {
             backend_url: "https://example.com".to_string(),
             mcp_port: 9876,
+            backend_socket: None,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains(r#""backend_url":"https://example.com""#));
         assert!(json.contains(r#""mcp_port":9876"#));
+        assert!(!json.contains("backend_socket"));
     }
 
     #[test]
@@ -152,6 +181,17 @@ mod tests {
         let config: TauriConfig = serde_json::from_str(r#"{}"#).unwrap();
         assert_eq!(config.backend_url, DEFAULT_BACKEND_URL);
         assert_eq!(config.mcp_port, DEFAULT_MCP_PORT);
+        assert_eq!(config.backend_socket, None);
+    }
+
+    #[test]
+    fn tauri_config_deserializes_with_socket() {
+        let config: TauriConfig =
+            serde_json::from_str(r#"{"backend_socket":"/tmp/vibe-kanban.sock"}"#).unwrap();
+        assert_eq!(
+            config.backend_socket.as_deref(),
+            Some("/tmp/vibe-kanban.sock")
+        );
     }
 
     #[test]
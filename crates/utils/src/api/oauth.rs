@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone, TS)]
@@ -45,7 +46,7 @@ pub struct TokenRefreshResponse {
     pub refresh_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub struct ProviderProfile {
     pub provider: String,
     pub username: Option<String>,
@@ -54,7 +55,7 @@ pub struct ProviderProfile {
     pub avatar_url: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub struct ProfileResponse {
     pub user_id: Uuid,
     pub username: Option<String>,
@@ -62,14 +63,14 @@ pub struct ProfileResponse {
     pub providers: Vec<ProviderProfile>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 #[serde(tag = "status", rename_all = "lowercase")]
 pub enum LoginStatus {
     LoggedOut,
     LoggedIn { profile: ProfileResponse },
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub struct StatusResponse {
     pub logged_in: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
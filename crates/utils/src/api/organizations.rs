@@ -2,23 +2,24 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS, ToSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[sqlx(type_name = "member_role", rename_all = "lowercase")]
 #[ts(export)]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[ts(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum MemberRole {
     Admin,
     Member,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS, ToSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[sqlx(type_name = "invitation_status", rename_all = "lowercase")]
-#[ts(use_ts_enum)]
+#[ts(repr(enum = name))]
 #[ts(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum InvitationStatus {
     Pending,
@@ -27,7 +28,7 @@ pub enum InvitationStatus {
     Expired,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS, ToSchema)]
 #[ts(export)]
 pub struct Organization {
     pub id: Uuid,
@@ -38,7 +39,7 @@ pub struct Organization {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, TS, ToSchema)]
 #[ts(export)]
 pub struct OrganizationWithRole {
     pub id: Uuid,
@@ -50,33 +51,33 @@ pub struct OrganizationWithRole {
     pub user_role: MemberRole,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ListOrganizationsResponse {
     pub organizations: Vec<OrganizationWithRole>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct GetOrganizationResponse {
     pub organization: Organization,
     pub user_role: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CreateOrganizationRequest {
     pub name: String,
     pub slug: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CreateOrganizationResponse {
     pub organization: OrganizationWithRole,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct UpdateOrganizationRequest {
     pub name: String,
@@ -84,7 +85,7 @@ pub struct UpdateOrganizationRequest {
 
 // Invitation types
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct Invitation {
     pub id: Uuid,
@@ -98,26 +99,26 @@ pub struct Invitation {
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CreateInvitationRequest {
     pub email: String,
     pub role: MemberRole,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CreateInvitationResponse {
     pub invitation: Invitation,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ListInvitationsResponse {
     pub invitations: Vec<Invitation>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct GetInvitationResponse {
     pub id: Uuid,
@@ -126,7 +127,7 @@ pub struct GetInvitationResponse {
     pub expires_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct AcceptInvitationResponse {
     pub organization_id: String,
@@ -134,7 +135,7 @@ pub struct AcceptInvitationResponse {
     pub role: MemberRole,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct RevokeInvitationRequest {
     pub invitation_id: Uuid,
 }
@@ -149,7 +150,7 @@ pub struct OrganizationMember {
     pub joined_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct OrganizationMemberWithProfile {
     pub user_id: Uuid,
@@ -162,19 +163,19 @@ pub struct OrganizationMemberWithProfile {
     pub avatar_url: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ListMembersResponse {
     pub members: Vec<OrganizationMemberWithProfile>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct UpdateMemberRoleRequest {
     pub role: MemberRole,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct UpdateMemberRoleResponse {
     pub user_id: Uuid,
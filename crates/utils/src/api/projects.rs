@@ -2,11 +2,12 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::organizations::OrganizationMemberWithProfile;
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct RemoteProject {
     pub id: Uuid,
     pub organization_id: Uuid,
@@ -21,7 +22,7 @@ pub struct ListProjectsResponse {
     pub projects: Vec<RemoteProject>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct RemoteProjectMembersResponse {
     pub organization_id: Uuid,
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 pub const APPROVAL_TIMEOUT_SECONDS: i64 = 3600; // 1 hour
@@ -23,7 +24,7 @@ pub struct QuestionData {
     pub options: Vec<QuestionOption>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct QuestionAnswer {
     pub question_index: usize,
@@ -107,7 +108,7 @@ pub struct CreateApprovalRequest {
     pub tool_call_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 #[serde(tag = "status", rename_all = "snake_case")]
 pub enum ApprovalStatus {
@@ -123,7 +124,7 @@ pub enum ApprovalStatus {
     TimedOut,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ApprovalResponse {
     pub execution_process_id: Uuid,
@@ -1,19 +1,73 @@
+use std::sync::OnceLock;
+
 use directories::ProjectDirs;
 use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 const PROJECT_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
-pub fn asset_dir() -> std::path::PathBuf {
-    let path = if cfg!(debug_assertions) {
+/// Overrides the asset directory for this process, e.g. to run against a copy
+pub const ASSET_DIR_ENV: &str = "VK_ASSET_DIR";
+
+/// File in the default asset directory holding the path the assets were
+/// relocated to
+const RELOCATION_POINTER: &str = "asset-dir-location";
+
+/// Where the asset directory came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetDirSource {
+    Default,
+    /// Moved with a relocation, recorded in the default directory
+    Relocated,
+    /// Set with the `VK_ASSET_DIR` environment variable
+    Environment,
+}
+
+/// Platform data directory, or `dev_assets` in debug builds
+pub fn default_asset_dir() -> std::path::PathBuf {
+    if cfg!(debug_assertions) {
         std::path::PathBuf::from(PROJECT_ROOT).join("../../dev_assets")
     } else {
         ProjectDirs::from("ai", "bloop", "vibe-kanban")
             .expect("OS didn't give us a home directory")
             .data_dir()
             .to_path_buf()
-    };
+    }
+    // ✔ macOS → ~/Library/Application Support/MyApp
+    // ✔ Linux → ~/.local/share/myapp   (respects XDG_DATA_HOME)
+    // ✔ Windows → %APPDATA%\Example\MyApp
+}
+
+/// Path of the file recording a relocation
+pub fn relocation_pointer_path() -> std::path::PathBuf {
+    default_asset_dir().join(RELOCATION_POINTER)
+}
+
+fn resolve_asset_dir() -> (std::path::PathBuf, AssetDirSource) {
+    if let Some(dir) = std::env::var_os(ASSET_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return (dir.into(), AssetDirSource::Environment);
+    }
+    if let Ok(pointer) = std::fs::read_to_string(relocation_pointer_path()) {
+        let dir = pointer.trim();
+        if !dir.is_empty() {
+            return (dir.into(), AssetDirSource::Relocated);
+        }
+    }
+    (default_asset_dir(), AssetDirSource::Default)
+}
+
+/// Resolved once, so a relocation only takes effect after a restart
+static ASSET_DIR: OnceLock<(std::path::PathBuf, AssetDirSource)> = OnceLock::new();
+
+pub fn asset_dir_source() -> AssetDirSource {
+    ASSET_DIR.get_or_init(resolve_asset_dir).1
+}
+
+pub fn asset_dir() -> std::path::PathBuf {
+    let path = ASSET_DIR.get_or_init(resolve_asset_dir).0.clone();
 
     // Ensure the directory exists
     if !path.exists() {
@@ -21,9 +75,6 @@ pub fn asset_dir() -> std::path::PathBuf {
     }
 
     path
-    // ✔ macOS → ~/Library/Application Support/MyApp
-    // ✔ Linux → ~/.local/share/myapp   (respects XDG_DATA_HOME)
-    // ✔ Windows → %APPDATA%\Example\MyApp
 }
 
 pub fn config_path() -> std::path::PathBuf {
@@ -50,7 +101,13 @@ pub fn backup_dir() -> std::path::PathBuf {
     asset_dir().join("backups")
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+/// Token the running server accepts from its own helper processes (e.g. the
+/// MCP task server) once sign-in is required
+pub fn internal_token_path() -> std::path::PathBuf {
+    asset_dir().join("internal-api-token")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct CustomSoundInfo {
     pub filename: String,
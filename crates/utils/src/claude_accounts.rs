@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 use crate::assets::asset_dir;
 
@@ -26,7 +27,7 @@ pub enum ClaudeAccountError {
 }
 
 /// Saved Claude account information
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SavedAccount {
@@ -45,7 +46,7 @@ pub struct SavedAccount {
 }
 
 /// Request to save the current account
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct SaveAccountRequest {
@@ -54,7 +55,7 @@ pub struct SaveAccountRequest {
 }
 
 /// Request to update account name
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct UpdateNameRequest {
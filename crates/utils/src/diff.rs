@@ -28,6 +28,16 @@ pub struct Diff {
     /// Optional precomputed stats for omitted content
     pub additions: Option<usize>,
     pub deletions: Option<usize>,
+    /// File mode before the change; None when the file was added
+    pub old_mode: Option<DiffFileMode>,
+    /// File mode after the change; None when the file was deleted
+    pub new_mode: Option<DiffFileMode>,
+    /// Where the old side pointed, when it is a symlink
+    pub old_symlink_target: Option<String>,
+    /// Where the new side points, when it is a symlink
+    pub new_symlink_target: Option<String>,
+    /// True when the executable bit was turned on or off; the modes say which
+    pub executable_changed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -42,6 +52,41 @@ pub enum DiffChangeKind {
     PermissionChange,
 }
 
+/// The kinds of blob a git tree entry can hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffFileMode {
+    /// 100644
+    Regular,
+    /// 100755
+    Executable,
+    /// 120000
+    Symlink,
+}
+
+impl DiffFileMode {
+    /// Map a raw git file mode, e.g. from `git2::TreeEntry::filemode`
+    pub fn from_git_mode(mode: i32) -> Option<Self> {
+        match mode {
+            0o100644 => Some(Self::Regular),
+            0o100755 => Some(Self::Executable),
+            0o120000 => Some(Self::Symlink),
+            _ => None,
+        }
+    }
+
+    /// Whether going from `old` to `new` flips the executable bit of a regular
+    /// file. Changes to or from a symlink are type changes, not flips.
+    pub fn is_executable_flip(old: Option<Self>, new: Option<Self>) -> bool {
+        matches!(
+            (old, new),
+            (Some(Self::Regular), Some(Self::Executable))
+                | (Some(Self::Executable), Some(Self::Regular))
+        )
+    }
+}
+
 // ==============================
 // Unified diff utility functions
 // ==============================
@@ -1,3 +1,7 @@
+use std::path::{Path, PathBuf};
+
+use crate::text::{detect_line_ending, strip_bom};
+
 pub fn is_valid_branch_prefix(prefix: &str) -> bool {
     if prefix.is_empty() {
         return true;
@@ -10,6 +14,51 @@ pub fn is_valid_branch_prefix(prefix: &str) -> bool {
     git2::Branch::name_is_valid(&format!("{prefix}/x")).unwrap_or_default()
 }
 
+/// Resolve the git dir for a worktree checkout. In a linked worktree `.git` is
+/// a file containing `gitdir: /path/to/repo/.git/worktrees/<name>`.
+pub async fn resolve_git_dir(worktree_path: &Path) -> std::io::Result<PathBuf> {
+    let dot_git_path = worktree_path.join(".git");
+    if !dot_git_path.is_file() {
+        return Ok(dot_git_path);
+    }
+
+    let content = tokio::fs::read_to_string(&dot_git_path).await?;
+    strip_bom(&content)
+        .strip_prefix("gitdir: ")
+        .map(|gitdir| PathBuf::from(gitdir.trim()))
+        .ok_or_else(|| std::io::Error::other("unexpected .git file format"))
+}
+
+/// Append `pattern` to the worktree's `.git/info/exclude` unless it is already
+/// listed. Returns true if the exclude file was changed.
+pub async fn add_git_exclude(worktree_path: &Path, pattern: &str) -> std::io::Result<bool> {
+    let git_info_dir = resolve_git_dir(worktree_path).await?.join("info");
+    let exclude_path = git_info_dir.join("exclude");
+
+    tokio::fs::create_dir_all(&git_info_dir).await?;
+
+    let existing_content = tokio::fs::read_to_string(&exclude_path)
+        .await
+        .unwrap_or_default();
+    if strip_bom(&existing_content)
+        .lines()
+        .any(|line| line.trim() == pattern)
+    {
+        return Ok(false);
+    }
+
+    // Keep CRLF files CRLF; git on Windows with autocrlf may have written it
+    let eol = detect_line_ending(&existing_content);
+    let new_content = if existing_content.is_empty() || existing_content.ends_with('\n') {
+        format!("{existing_content}{pattern}{eol}")
+    } else {
+        format!("{existing_content}{eol}{pattern}{eol}")
+    };
+    tokio::fs::write(&exclude_path, new_content).await?;
+
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +93,34 @@ mod tests {
         assert!(!is_valid_branch_prefix("foo/"));
         assert!(!is_valid_branch_prefix(".foo"));
     }
+
+    #[tokio::test]
+    async fn test_add_git_exclude_is_idempotent() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+
+        assert!(add_git_exclude(dir.path(), ".env").await.unwrap());
+        assert!(!add_git_exclude(dir.path(), ".env").await.unwrap());
+        assert!(add_git_exclude(dir.path(), ".claude/").await.unwrap());
+
+        let content = std::fs::read_to_string(dir.path().join(".git/info/exclude")).unwrap();
+        assert_eq!(content, ".env\n.claude/\n");
+    }
+
+    #[tokio::test]
+    async fn test_add_git_exclude_preserves_crlf() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git/info")).unwrap();
+        std::fs::write(
+            dir.path().join(".git/info/exclude"),
+            "# comment\r\n.env\r\n",
+        )
+        .unwrap();
+
+        assert!(!add_git_exclude(dir.path(), ".env").await.unwrap());
+        assert!(add_git_exclude(dir.path(), ".claude/").await.unwrap());
+
+        let content = std::fs::read_to_string(dir.path().join(".git/info/exclude")).unwrap();
+        assert_eq!(content, "# comment\r\n.env\r\n.claude/\r\n");
+    }
 }
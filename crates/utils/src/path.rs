@@ -125,6 +125,122 @@ pub fn expand_tilde(path_str: &str) -> std::path::PathBuf {
     shellexpand::tilde(path_str).as_ref().into()
 }
 
+/// Prefix absolute Windows paths with `\\?\` so file APIs accept paths longer
+/// than `MAX_PATH` (deep `node_modules` trees in worktrees hit this regularly).
+/// Verbatim paths skip `.`/`..` normalization, so only pass absolute, normalized
+/// paths. Returns the path unchanged on other platforms.
+pub fn long_path<P: AsRef<Path>>(p: P) -> PathBuf {
+    let p = p.as_ref();
+    if cfg!(windows)
+        && let Some(verbatim) = p.to_str().and_then(to_verbatim_path)
+    {
+        return PathBuf::from(verbatim);
+    }
+    p.to_path_buf()
+}
+
+fn to_verbatim_path(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return None;
+    }
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{unc}"));
+    }
+
+    let bytes = path.as_bytes();
+    let is_drive_absolute = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    is_drive_absolute.then(|| format!(r"\\?\{}", path.replace('/', r"\")))
+}
+
+/// `std::fs::remove_dir_all` that copes with Windows quirks: long paths,
+/// read-only files (git marks pack files read-only) and transient sharing
+/// violations from indexers or antivirus holding handles.
+pub fn remove_dir_all(path: &Path) -> std::io::Result<()> {
+    #[cfg(windows)]
+    {
+        let path = long_path(path);
+        let mut attempt = 0;
+        loop {
+            match std::fs::remove_dir_all(&path) {
+                Ok(()) => return Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(e),
+                Err(e) if attempt >= 2 => return Err(e),
+                Err(e) => {
+                    tracing::debug!("Retrying removal of {} after error: {}", path.display(), e);
+                    if e.kind() == std::io::ErrorKind::PermissionDenied {
+                        clear_readonly_recursive(&path);
+                    }
+                    attempt += 1;
+                    std::thread::sleep(std::time::Duration::from_millis(100 * attempt));
+                }
+            }
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        std::fs::remove_dir_all(path)
+    }
+}
+
+/// Async wrapper around [`remove_dir_all`].
+pub async fn remove_dir_all_async(path: &Path) -> std::io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || remove_dir_all(&path))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Total size in bytes of the files under `path`. Symlinks are not followed
+/// and entries that can't be read are skipped; a missing path is 0.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Async wrapper around [`dir_size`].
+pub async fn dir_size_async(path: &Path) -> u64 {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || dir_size(&path))
+        .await
+        .unwrap_or(0)
+}
+
+#[cfg(windows)]
+fn clear_readonly_recursive(path: &Path) {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            clear_readonly_recursive(&entry.path());
+        } else if let Ok(metadata) = entry.metadata() {
+            let mut permissions = metadata.permissions();
+            if permissions.readonly() {
+                #[allow(clippy::permissions_set_readonly_false)]
+                permissions.set_readonly(false);
+                let _ = std::fs::set_permissions(entry.path(), permissions);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,4 +288,49 @@ mod tests {
             "hello-world.txt"
         );
     }
+
+    #[test]
+    fn test_to_verbatim_path() {
+        assert_eq!(
+            to_verbatim_path(r"C:\Users\dev\worktrees\vk-1234"),
+            Some(r"\\?\C:\Users\dev\worktrees\vk-1234".to_string())
+        );
+        assert_eq!(
+            to_verbatim_path("D:/repos/app"),
+            Some(r"\\?\D:\repos\app".to_string())
+        );
+        assert_eq!(
+            to_verbatim_path(r"\\server\share\repo"),
+            Some(r"\\?\UNC\server\share\repo".to_string())
+        );
+        assert_eq!(to_verbatim_path(r"\\?\C:\already"), None);
+        assert_eq!(to_verbatim_path("relative\\path"), None);
+        assert_eq!(to_verbatim_path("/tmp/unix"), None);
+    }
+
+    #[test]
+    fn test_remove_dir_all_handles_readonly_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("worktree");
+        std::fs::create_dir_all(target.join("nested")).unwrap();
+        let file = target.join("nested").join("pack.idx");
+        std::fs::write(&file, "data").unwrap();
+        let mut permissions = std::fs::metadata(&file).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&file, permissions).unwrap();
+
+        remove_dir_all(&target).unwrap();
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_dir_size_sums_nested_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("a").join("b")).unwrap();
+        std::fs::write(dir.path().join("top.txt"), "12345").unwrap();
+        std::fs::write(dir.path().join("a").join("b").join("deep.txt"), "123").unwrap();
+
+        assert_eq!(dir_size(dir.path()), 8);
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+    }
 }
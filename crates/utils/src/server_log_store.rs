@@ -8,13 +8,15 @@ use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::BroadcastStream;
+use tracing::Level;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 /// 100 MB limit for history buffer
 const HISTORY_BYTES: usize = 100000 * 1024;
 
 /// A single server log entry captured from tracing.
-#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ServerLogEntry {
     pub timestamp: DateTime<Utc>,
@@ -33,6 +35,44 @@ impl ServerLogEntry {
     }
 }
 
+/// Narrows history and live entries by time, severity and module.
+#[derive(Clone, Debug, Default)]
+pub struct ServerLogFilter {
+    /// Only entries logged after this instant
+    pub since: Option<DateTime<Utc>>,
+    /// Minimum severity; `WARN` also lets errors through
+    pub level: Option<Level>,
+    /// Module path prefix, matched on `::` boundaries (e.g. "executors")
+    pub target: Option<String>,
+}
+
+impl ServerLogFilter {
+    pub fn matches(&self, entry: &ServerLogEntry) -> bool {
+        if let Some(since) = self.since
+            && entry.timestamp <= since
+        {
+            return false;
+        }
+        if let Some(min_level) = self.level {
+            // tracing orders levels by verbosity, so TRACE > ERROR
+            match entry.level.parse::<Level>() {
+                Ok(level) if level <= min_level => {}
+                _ => return false,
+            }
+        }
+        if let Some(target) = self.target.as_deref() {
+            let matches_prefix = entry
+                .target
+                .strip_prefix(target)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"));
+            if !matches_prefix {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 #[derive(Clone)]
 struct StoredEntry {
     entry: ServerLogEntry,
@@ -99,6 +139,21 @@ impl ServerLogStore {
             .collect()
     }
 
+    /// History entries matching `filter`, keeping the newest `limit` if set.
+    pub fn query(&self, filter: &ServerLogFilter, limit: Option<usize>) -> Vec<ServerLogEntry> {
+        let inner = self.inner.read().unwrap();
+        let mut entries: Vec<ServerLogEntry> = inner
+            .history
+            .iter()
+            .rev()
+            .filter(|s| filter.matches(&s.entry))
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|s| s.entry.clone())
+            .collect();
+        entries.reverse();
+        entries
+    }
+
     /// Subscribe to live log entries.
     pub fn subscribe(&self) -> broadcast::Receiver<ServerLogEntry> {
         self.sender.subscribe()
@@ -116,4 +171,72 @@ impl ServerLogStore {
 
         Box::pin(hist.chain(live))
     }
+
+    /// Like [`Self::history_plus_stream`], with both parts narrowed by `filter`
+    /// and the history cut to its newest `history_limit` entries.
+    pub fn filtered_history_plus_stream(
+        self: &Arc<Self>,
+        filter: ServerLogFilter,
+        history_limit: Option<usize>,
+    ) -> futures::stream::BoxStream<'static, Result<ServerLogEntry, std::io::Error>> {
+        let (history, rx) = (self.query(&filter, history_limit), self.subscribe());
+
+        let hist = futures::stream::iter(history.into_iter().map(Ok::<_, std::io::Error>));
+        let live = BroadcastStream::new(rx).filter_map(move |res| {
+            let entry = res.ok().filter(|entry| filter.matches(entry));
+            async move { entry.map(Ok::<_, std::io::Error>) }
+        });
+
+        Box::pin(hist.chain(live))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, target: &str) -> ServerLogEntry {
+        ServerLogEntry {
+            timestamp: Utc::now(),
+            level: level.to_string(),
+            target: target.to_string(),
+            message: "hello".to_string(),
+        }
+    }
+
+    #[test]
+    fn filter_by_level_and_target() {
+        let filter = ServerLogFilter {
+            level: Some(Level::WARN),
+            target: Some("executors".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entry("ERROR", "executors::claude")));
+        assert!(filter.matches(&entry("WARN", "executors")));
+        assert!(!filter.matches(&entry("INFO", "executors::claude")));
+        assert!(!filter.matches(&entry("ERROR", "executors_extra")));
+        assert!(!filter.matches(&entry("ERROR", "server::routes")));
+    }
+
+    #[test]
+    fn query_keeps_newest_matches() {
+        let store = ServerLogStore::new();
+        for i in 0..5 {
+            let mut e = entry("INFO", "server");
+            e.message = i.to_string();
+            store.push(e);
+        }
+        store.push(entry("DEBUG", "server"));
+
+        let filter = ServerLogFilter {
+            level: Some(Level::INFO),
+            ..Default::default()
+        };
+        let messages: Vec<String> = store
+            .query(&filter, Some(2))
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, vec!["3", "4"]);
+    }
 }
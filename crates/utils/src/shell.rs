@@ -1,12 +1,14 @@
 //! Cross-platform shell command utilities
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env::{join_paths, split_paths},
     ffi::{OsStr, OsString},
     path::{Path, PathBuf},
 };
 
+use tokio::sync::OnceCell;
+
 use crate::tokio::block_on;
 
 /// Returns the appropriate shell command and argument for the current platform.
@@ -75,6 +77,106 @@ pub fn merge_paths(primary: impl AsRef<OsStr>, secondary: impl AsRef<OsStr>) ->
     join_paths(merged).unwrap_or_default()
 }
 
+/// Variables describing the capturing shell itself rather than the user's setup
+const LOGIN_ENV_SKIP_VARS: &[&str] = &["PWD", "OLDPWD", "SHLVL", "_", "TERM"];
+
+/// The environment of the user's login shell (profile and rc files sourced),
+/// so tools installed through nvm, pyenv or asdf resolve for spawned commands.
+///
+/// Captured once per process; returns `None` if the shell could not be run.
+pub async fn login_shell_env() -> Option<HashMap<String, String>> {
+    static LOGIN_SHELL_ENV: OnceCell<Option<HashMap<String, String>>> = OnceCell::const_new();
+    LOGIN_SHELL_ENV
+        .get_or_init(|| async {
+            let env = capture_login_shell_env().await;
+            match &env {
+                Some(vars) => tracing::debug!("Captured {} login shell variables", vars.len()),
+                None => tracing::warn!("Failed to capture login shell environment"),
+            }
+            env
+        })
+        .await
+        .clone()
+}
+
+#[cfg(not(windows))]
+async fn capture_login_shell_env() -> Option<HashMap<String, String>> {
+    use std::{process::Stdio, time::Duration};
+
+    use tokio::process::Command;
+
+    const MARKER: &str = "__VK_LOGIN_ENV__";
+    const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    let shell = UnixShell::current_shell();
+    let mut cmd = Command::new(shell.path());
+    if shell.login() {
+        cmd.arg("-l");
+    }
+    // rc output before the marker is noise from the user's dotfiles
+    let print_env = format!("printf '%s\\n' {MARKER}; env");
+    match shell.source_command() {
+        Some(source_command) => cmd.arg("-c").arg(format!("{source_command}; {print_env}")),
+        None => cmd.arg("-c").arg(print_env),
+    };
+    cmd.env("TERM", "dumb")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(CAPTURE_TIMEOUT, cmd.spawn().ok()?.wait_with_output())
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (_, env_output) = stdout.split_once(&format!("{MARKER}\n"))?;
+    Some(parse_env_output(env_output))
+}
+
+#[cfg(windows)]
+async fn capture_login_shell_env() -> Option<HashMap<String, String>> {
+    // There is no login shell; the registry holds the user's persistent environment
+    get_fresh_path()
+        .await
+        .map(|path| HashMap::from([("PATH".to_string(), path)]))
+}
+
+/// Parse `env` output. Lines that don't start a new `NAME=` assignment are
+/// continuations of a multi-line value.
+fn parse_env_output(output: &str) -> HashMap<String, String> {
+    fn is_var_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for line in output.lines() {
+        match line.split_once('=') {
+            Some((name, value)) if is_var_name(name) => {
+                vars.push((name.to_string(), value.to_string()))
+            }
+            _ => {
+                if let Some((_, value)) = vars.last_mut() {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    vars.into_iter()
+        .filter(|(name, _)| !LOGIN_ENV_SKIP_VARS.contains(&name.as_str()))
+        .collect()
+}
+
 async fn refresh_path() -> bool {
     let Some(refreshed) = get_fresh_path().await else {
         return false;
@@ -331,3 +433,20 @@ fn get_fresh_path_blocking() -> Option<String> {
         .reduce(|a, b| merge_paths(&a, &b))
         .map(|merged| merged.to_string_lossy().into_owned())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env_output() {
+        let vars = parse_env_output(
+            "PATH=/home/me/.nvm/bin:/usr/bin\nMULTI=first\nsecond line\nSHLVL=2\nEMPTY=\n",
+        );
+
+        assert_eq!(vars["PATH"], "/home/me/.nvm/bin:/usr/bin");
+        assert_eq!(vars["MULTI"], "first\nsecond line");
+        assert_eq!(vars["EMPTY"], "");
+        assert!(!vars.contains_key("SHLVL"));
+    }
+}
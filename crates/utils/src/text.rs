@@ -40,6 +40,32 @@ pub fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     &content[..cutoff]
 }
 
+/// Strip a leading UTF-8 byte order mark. Notepad and some other Windows
+/// editors add one, which serde_json and line-based parsers choke on.
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+/// Line ending already used by `content`, so appended lines match the file.
+pub fn detect_line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// The last `max_chars` characters of command output, marked as cut when
+/// anything was dropped; the end of a log is where failures show up.
+pub fn truncate_output(output: &str, max_chars: usize) -> String {
+    let count = output.chars().count();
+    if count <= max_chars {
+        return output.to_string();
+    }
+    let tail: String = output.chars().skip(count - max_chars).collect();
+    format!("[... output truncated ...]\n{tail}")
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -57,4 +83,32 @@ mod tests {
         assert_eq!(truncate_to_char_boundary(input, 5), "🔥");
         assert_eq!(truncate_to_char_boundary(input, 3), "");
     }
+
+    #[test]
+    fn test_strip_bom_and_line_endings() {
+        use super::{detect_line_ending, strip_bom};
+
+        assert_eq!(strip_bom("\u{feff}{\"a\": 1}"), "{\"a\": 1}");
+        assert_eq!(strip_bom("plain"), "plain");
+
+        assert_eq!(detect_line_ending("a\r\nb\r\n"), "\r\n");
+        assert_eq!(detect_line_ending("a\nb\n"), "\n");
+        assert_eq!(detect_line_ending(""), "\n");
+    }
+
+    #[test]
+    fn test_truncate_output_keeps_the_tail() {
+        use super::truncate_output;
+
+        assert_eq!(truncate_output("short", 10), "short");
+
+        let long = format!("{}END", "x".repeat(10));
+        let truncated = truncate_output(&long, 10);
+        assert!(truncated.starts_with("[... output truncated ...]\n"));
+        assert!(truncated.ends_with("END"));
+        assert_eq!(
+            truncated.chars().count(),
+            "[... output truncated ...]\n".len() + 10
+        );
+    }
 }
@@ -9,6 +9,7 @@ import { usePreviousPath } from '@/hooks/usePreviousPath';
 import { useSoundEffects } from '@/hooks/useSoundEffects';
 
 import { UserSystemProvider, useUserSystem } from '@/components/ConfigProvider';
+import { SessionGate } from '@/components/SessionGate';
 import { ThemeProvider } from '@/components/ThemeProvider';
 import { SearchProvider } from '@/contexts/SearchContext';
 
@@ -81,6 +82,11 @@ const McpSettings = lazy(() =>
     default: module.McpSettings,
   }))
 );
+const UsersSettings = lazy(() =>
+  import('@/pages/settings/UsersSettings').then((module) => ({
+    default: module.UsersSettings,
+  }))
+);
 const ServerLogs = lazy(() =>
   import('@/pages/settings/ServerLogs').then((m) => ({
     default: m.ServerLogs,
@@ -266,6 +272,7 @@ function AppContent() {
                     <Route path="github" element={<GitHubSettings />} />
                     <Route path="agents" element={<AgentSettings />} />
                     <Route path="mcp" element={<McpSettings />} />
+                    <Route path="users" element={<UsersSettings />} />
                     <Route path="server-logs" element={<ServerLogs />} />
                   </Route>
                   <Route
@@ -293,27 +300,29 @@ function AppContent() {
 function App() {
   return (
     <BrowserRouter>
-      <UserSystemProvider>
-        <UnreadProvider>
-          <ClickedElementsProvider>
-            <ProjectProvider>
-              <ProjectTasksProvider>
-                <TaskSelectionProvider>
-                  <HotkeysProvider
-                    initiallyActiveScopes={['*', 'global', 'kanban']}
-                  >
-                    <NiceModal.Provider>
-                      <AppContent />
-                      <BulkActionsBar />
-                      <Toaster />
-                    </NiceModal.Provider>
-                  </HotkeysProvider>
-                </TaskSelectionProvider>
-              </ProjectTasksProvider>
-            </ProjectProvider>
-          </ClickedElementsProvider>
-        </UnreadProvider>
-      </UserSystemProvider>
+      <SessionGate>
+        <UserSystemProvider>
+          <UnreadProvider>
+            <ClickedElementsProvider>
+              <ProjectProvider>
+                <ProjectTasksProvider>
+                  <TaskSelectionProvider>
+                    <HotkeysProvider
+                      initiallyActiveScopes={['*', 'global', 'kanban']}
+                    >
+                      <NiceModal.Provider>
+                        <AppContent />
+                        <BulkActionsBar />
+                        <Toaster />
+                      </NiceModal.Provider>
+                    </HotkeysProvider>
+                  </TaskSelectionProvider>
+                </ProjectTasksProvider>
+              </ProjectProvider>
+            </ClickedElementsProvider>
+          </UnreadProvider>
+        </UserSystemProvider>
+      </SessionGate>
     </BrowserRouter>
   );
 }
@@ -1,4 +1,4 @@
-import { Diff } from 'shared/types';
+import { Diff, DiffFileMode } from 'shared/types';
 import { DiffModeEnum, DiffView, SplitSide } from '@git-diff-view/react';
 import { generateDiffFile, type DiffFile } from '@git-diff-view/file';
 import { useMemo } from 'react';
@@ -16,6 +16,7 @@ import {
   PencilLine,
   Copy,
   Key,
+  Link2,
   ExternalLink,
   MessageSquare,
 } from 'lucide-react';
@@ -35,6 +36,7 @@ import {
   useWrapTextDiff,
 } from '@/stores/useDiffViewStore';
 import { useProject } from '@/contexts/ProjectContext';
+import { useDiffFileContent } from '@/hooks/useDiffFileContent';
 
 type Props = {
   diff: Diff;
@@ -52,9 +54,33 @@ function labelAndIcon(diff: Diff) {
   if (c === 'copied') return { label: 'Copied', Icon: Copy };
   if (c === 'permissionChange')
     return { label: 'Permission Changed', Icon: Key };
+  if (diff.newMode === 'symlink' || diff.oldMode === 'symlink')
+    return { label: 'Symlink', Icon: Link2 };
   return { label: undefined as string | undefined, Icon: PencilLine };
 }
 
+const GIT_MODES: Record<DiffFileMode, string> = {
+  regular: '100644',
+  executable: '100755',
+  symlink: '120000',
+};
+
+// e.g. "100644 → 100755", when the file's mode changed
+function modeChange(diff: Diff) {
+  if (!diff.oldMode || !diff.newMode || diff.oldMode === diff.newMode) {
+    return undefined;
+  }
+  return `${GIT_MODES[diff.oldMode]} → ${GIT_MODES[diff.newMode]}`;
+}
+
+// e.g. "→ a.txt" for a new link, "a.txt → b.txt" for a repointed one
+function symlinkChange(diff: Diff) {
+  const { oldSymlinkTarget: from, newSymlinkTarget: to } = diff;
+  if (from && to && from !== to) return `${from} → ${to}`;
+  if (to ?? from) return `→ ${to ?? from}`;
+  return undefined;
+}
+
 function readPlainLine(
   diffFile: DiffFile | null,
   lineNumber: number,
@@ -95,11 +121,29 @@ export default function DiffCard({
   const newLang =
     getHighLightLanguageFromPath(newName || oldName || '') || 'plaintext';
   const { label, Icon } = labelAndIcon(diff);
+  const mode = modeChange(diff);
+  const symlink = symlinkChange(diff);
   const isOmitted = !!diff.contentOmitted;
 
+  // Contents left out of the stream are fetched once the card is expanded
+  const lazy = useDiffFileContent(
+    selectedAttempt?.id,
+    diff,
+    isOmitted && expanded
+  );
+  const lazyTooLarge = !!(lazy.oldSide?.truncated || lazy.newSide?.truncated);
+  const lazyBinary = !!(lazy.oldSide?.binary || lazy.newSide?.binary);
+  const lazyLoaded =
+    !!lazy.oldSide && !!lazy.newSide && !lazyTooLarge && !lazyBinary;
+  const showStats = isOmitted && !lazyLoaded;
+
   // Build a diff from raw contents so the viewer can expand beyond hunks
-  const oldContentSafe = diff.oldContent || '';
-  const newContentSafe = diff.newContent || '';
+  const oldContentSafe = isOmitted
+    ? (lazyLoaded && lazy.oldSide?.content) || ''
+    : diff.oldContent || '';
+  const newContentSafe = isOmitted
+    ? (lazyLoaded && lazy.newSide?.content) || ''
+    : diff.newContent || '';
   const isContentEqual = oldContentSafe === newContentSafe;
 
   const diffOptions = useMemo(
@@ -108,7 +152,7 @@ export default function DiffCard({
   );
 
   const diffFile = useMemo(() => {
-    if (isContentEqual || isOmitted) return null;
+    if (isContentEqual || showStats) return null;
     try {
       const oldFileName = oldName || newName || 'unknown';
       const newFileName = newName || oldName || 'unknown';
@@ -129,7 +173,7 @@ export default function DiffCard({
     }
   }, [
     isContentEqual,
-    isOmitted,
+    showStats,
     oldName,
     newName,
     oldLang,
@@ -139,10 +183,10 @@ export default function DiffCard({
     diffOptions,
   ]);
 
-  const add = isOmitted
+  const add = showStats
     ? (diff.additions ?? 0)
     : (diffFile?.additionLength ?? 0);
-  const del = isOmitted
+  const del = showStats
     ? (diff.deletions ?? 0)
     : (diffFile?.deletionLength ?? 0);
 
@@ -229,6 +273,26 @@ export default function DiffCard({
       ) : (
         <span>{newName}</span>
       )}
+      {symlink && <span className="ml-2">{symlink}</span>}
+      {mode && (
+        <span
+          className="ml-3 px-1.5 py-0.5 text-xs rounded border"
+          style={
+            diff.executableChanged
+              ? { color: 'hsl(var(--warning))', borderColor: 'currentColor' }
+              : undefined
+          }
+          title={
+            diff.executableChanged
+              ? diff.newMode === 'executable'
+                ? 'Executable bit set'
+                : 'Executable bit removed'
+              : 'File mode changed'
+          }
+        >
+          {mode}
+        </span>
+      )}
       <span className="ml-3" style={{ color: 'hsl(var(--console-success))' }}>
         +{add}
       </span>
@@ -323,13 +387,21 @@ export default function DiffCard({
           className="px-4 pb-4 text-xs font-mono"
           style={{ color: 'hsl(var(--muted-foreground) / 0.9)' }}
         >
-          {isOmitted
-            ? 'Content omitted due to file size. Open in editor to view.'
+          {showStats
+            ? lazy.isLoading
+              ? 'Loading file contents…'
+              : lazyTooLarge
+                ? 'File too large to display. Open in editor to view.'
+                : lazyBinary
+                  ? 'Binary file not shown.'
+                  : 'Failed to load file contents. Open in editor to view.'
             : isContentEqual
               ? diff.change === 'renamed'
                 ? 'File renamed with no content changes.'
                 : diff.change === 'permissionChange'
-                  ? 'File permission changed.'
+                  ? diff.newMode === 'executable'
+                    ? 'File made executable.'
+                    : 'File is no longer executable.'
                   : 'No content changes to display.'
               : 'Failed to render diff for this file.'}
         </div>
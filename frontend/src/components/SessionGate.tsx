@@ -0,0 +1,94 @@
+import { FormEvent, ReactNode, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Loader } from '@/components/ui/loader';
+import { useLogin, useSession } from '@/hooks/useUsers';
+
+/**
+ * Shows a sign-in form instead of the app when the server has accounts and
+ * nobody is signed in. Servers without accounts go straight to the app.
+ */
+export function SessionGate({ children }: { children: ReactNode }) {
+  const { data: session, isLoading } = useSession();
+
+  if (isLoading) {
+    return <Loader size={32} className="h-screen" />;
+  }
+  if (session?.accounts_enabled && !session.user) {
+    return <SignInForm />;
+  }
+  return <>{children}</>;
+}
+
+function SignInForm() {
+  const { t } = useTranslation('common');
+  const [username, setUsername] = useState('');
+  const [password, setPassword] = useState('');
+  const login = useLogin();
+
+  const handleSubmit = (e: FormEvent) => {
+    e.preventDefault();
+    login.mutate({ username: username.trim(), password });
+  };
+
+  return (
+    <div className="flex h-screen items-center justify-center bg-background p-4">
+      <Card className="w-full max-w-sm">
+        <CardHeader>
+          <CardTitle>{t('accountSignIn.title')}</CardTitle>
+          <CardDescription>{t('accountSignIn.description')}</CardDescription>
+        </CardHeader>
+        <CardContent>
+          <form onSubmit={handleSubmit} className="space-y-4">
+            {login.isError && (
+              <Alert variant="destructive">
+                <AlertDescription>{t('accountSignIn.failed')}</AlertDescription>
+              </Alert>
+            )}
+            <div className="space-y-2">
+              <Label htmlFor="sign-in-username">
+                {t('accountSignIn.username')}
+              </Label>
+              <Input
+                id="sign-in-username"
+                autoComplete="username"
+                autoFocus
+                value={username}
+                onChange={(e) => setUsername(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="sign-in-password">
+                {t('accountSignIn.password')}
+              </Label>
+              <Input
+                id="sign-in-password"
+                type="password"
+                autoComplete="current-password"
+                value={password}
+                onChange={(e) => setPassword(e.target.value)}
+              />
+            </div>
+            <Button
+              type="submit"
+              className="w-full"
+              disabled={!username.trim() || !password || login.isPending}
+            >
+              {t('accountSignIn.submit')}
+            </Button>
+          </form>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
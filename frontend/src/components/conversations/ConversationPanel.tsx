@@ -63,11 +63,12 @@ export function ConversationPanel({ projectId, initialConversationId, isMobile =
 
   // Queue status for queuing messages while agent is running
   const {
-    isQueued,
-    queuedMessage,
+    queuedMessages,
     isLoading: isQueueLoading,
     queueMessage,
     cancelQueue,
+    removeQueued,
+    moveQueued,
     refresh: refreshQueueStatus,
   } = useConversationQueueStatus(selectedConversation?.id);
 
@@ -208,10 +209,11 @@ export function ConversationPanel({ projectId, initialConversationId, isMobile =
         isExecutionRunning={isExecutionRunning || sendMessage.isPending}
         onStop={() => runningExecutionId && stopExecution(runningExecutionId)}
         isStopping={isStopping}
-        isQueued={isQueued}
-        queuedMessage={queuedMessage}
+        queuedMessages={queuedMessages}
         queueMessage={queueMessage}
         cancelQueue={cancelQueue}
+        removeQueued={removeQueued}
+        moveQueued={moveQueued}
         isQueueLoading={isQueueLoading}
       />
     </>
@@ -1,6 +1,14 @@
 import { useState, useCallback, useRef, useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
-import { Send, StopCircle, Loader2, Clock, X } from 'lucide-react';
+import {
+  Send,
+  StopCircle,
+  Loader2,
+  Clock,
+  X,
+  ArrowUp,
+  ArrowDown,
+} from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import WYSIWYGEditor from '@/components/ui/wysiwyg';
 import { imagesApi } from '@/lib/api';
@@ -16,10 +24,11 @@ interface MessageInputProps {
   onStop?: () => void;
   isStopping?: boolean;
   // Queue props
-  isQueued: boolean;
-  queuedMessage: QueuedMessage | null;
+  queuedMessages: QueuedMessage[];
   queueMessage: (message: string, variant: string | null) => Promise<void>;
   cancelQueue: () => Promise<void>;
+  removeQueued: (messageId: string) => Promise<void>;
+  moveQueued: (messageId: string, offset: -1 | 1) => Promise<void>;
   isQueueLoading: boolean;
   placeholder?: string;
 }
@@ -30,10 +39,11 @@ export function MessageInput({
   isExecutionRunning,
   onStop,
   isStopping = false,
-  isQueued,
-  queuedMessage,
+  queuedMessages,
   queueMessage,
   cancelQueue,
+  removeQueued,
+  moveQueued,
   isQueueLoading,
   placeholder,
 }: MessageInputProps) {
@@ -101,42 +111,18 @@ export function MessageInput({
     setLocalMessage(scratchData?.message ?? '');
   }, [isScratchLoading, scratchData?.message, isEditorFocused]);
 
-  // Refs for queue state to use in stable onChange handler
-  const isQueuedRef = useRef(isQueued);
-  useEffect(() => {
-    isQueuedRef.current = isQueued;
-  }, [isQueued]);
-
-  const cancelQueueRef = useRef(cancelQueue);
-  useEffect(() => {
-    cancelQueueRef.current = cancelQueue;
-  }, [cancelQueue]);
-
-  const queuedMessageRef = useRef(queuedMessage);
-  useEffect(() => {
-    queuedMessageRef.current = queuedMessage;
-  }, [queuedMessage]);
-
   // Ref to access debouncedSave without adding it as a dependency
   const debouncedSaveRef = useRef(debouncedSave);
   useEffect(() => {
     debouncedSaveRef.current = debouncedSave;
   }, [debouncedSave]);
 
-  // When queued, display the queued message content so user can edit it
-  const displayMessage =
-    isQueued && queuedMessage ? queuedMessage.data.message : localMessage;
-
   const defaultPlaceholder = t('conversations.messagePlaceholder', {
     defaultValue: 'Type a message...',
   });
 
   // Stable onChange handler for WYSIWYGEditor
   const handleEditorChange = useCallback((value: string) => {
-    // Auto-cancel queue when user starts editing
-    if (isQueuedRef.current) {
-      cancelQueueRef.current();
-    }
     setLocalMessage(value); // Immediate update for UI responsiveness
     debouncedSaveRef.current(value); // Debounced save to scratch
   }, []);
@@ -151,28 +137,26 @@ export function MessageInput({
     // Scratch will be cleared on successful send by parent
   }, [localMessage, onSend, cancelDebouncedSave]);
 
-  // Handler to queue the current message
+  // Handler to add the current message to the queue; the queue holds it from
+  // then on, so the draft is cleared for the next message
   const handleQueueMessage = useCallback(async () => {
-    if (!localMessage.trim()) return;
+    const trimmed = localMessage.trim();
+    if (!trimmed) return;
 
-    // Cancel any pending debounced save and save immediately before queueing
     cancelDebouncedSave();
-    await saveToScratch(localMessage);
-
-    await queueMessage(localMessage, null);
+    await queueMessage(trimmed, null);
+    setLocalMessage('');
+    await saveToScratch('');
   }, [localMessage, queueMessage, cancelDebouncedSave, saveToScratch]);
 
   // Keyboard shortcut handler - send or queue depending on state
   const handleCmdEnter = useCallback(() => {
     if (isExecutionRunning) {
-      // When running, CMD+Enter queues the message (if not already queued)
-      if (!isQueued) {
-        handleQueueMessage();
-      }
+      handleQueueMessage();
     } else {
       handleSubmit();
     }
-  }, [isExecutionRunning, isQueued, handleQueueMessage, handleSubmit]);
+  }, [isExecutionRunning, handleQueueMessage, handleSubmit]);
 
   const handlePasteFiles = useCallback(
     async (files: File[]) => {
@@ -186,24 +170,13 @@ export function MessageInput({
           );
           const imageMarkdown = `![${response.original_name}](${response.file_path})`;
 
-          // If queued, cancel queue and use queued message as base
-          if (isQueuedRef.current && queuedMessageRef.current) {
-            cancelQueueRef.current();
-            const base = queuedMessageRef.current.data.message;
-            const newMessage = base
-              ? `${base}\n\n${imageMarkdown}`
+          setLocalMessage((prev) => {
+            const newMessage = prev
+              ? `${prev}\n\n${imageMarkdown}`
               : imageMarkdown;
-            setLocalMessage(newMessage);
             debouncedSaveRef.current(newMessage);
-          } else {
-            setLocalMessage((prev) => {
-              const newMessage = prev
-                ? `${prev}\n\n${imageMarkdown}`
-                : imageMarkdown;
-              debouncedSaveRef.current(newMessage);
-              return newMessage;
-            });
-          }
+            return newMessage;
+          });
         } catch (error) {
           console.error('Failed to upload image:', error);
         }
@@ -215,16 +188,70 @@ export function MessageInput({
   return (
     <div className="border-t bg-background p-4">
       <div className="max-w-3xl mx-auto">
-        {/* Queued message indicator */}
-        {isQueued && queuedMessage && (
-          <div className="flex items-center gap-2 text-sm text-muted-foreground bg-muted p-3 rounded-md border mb-3">
-            <Clock className="h-4 w-4 flex-shrink-0" />
-            <div className="font-medium">
-              {t(
-                'conversations.queuedMessage',
-                'Message queued - will execute when current run finishes'
-              )}
+        {/* Queued messages, sent one at a time as runs finish */}
+        {queuedMessages.length > 0 && (
+          <div className="text-sm text-muted-foreground bg-muted p-3 rounded-md border mb-3">
+            <div className="flex items-center gap-2 mb-2">
+              <Clock className="h-4 w-4 flex-shrink-0" />
+              <div className="font-medium flex-1">
+                {t('conversations.queuedMessages', {
+                  count: queuedMessages.length,
+                  defaultValue:
+                    '{{count}} queued - sent in order as each run finishes',
+                })}
+              </div>
+              <Button
+                onClick={cancelQueue}
+                disabled={isQueueLoading}
+                size="sm"
+                variant="ghost"
+                className="h-7"
+              >
+                {t('conversations.clearQueue', 'Clear')}
+              </Button>
             </div>
+            <ol className="flex flex-col gap-1">
+              {queuedMessages.map((queued, index) => (
+                <li
+                  key={queued.id}
+                  className="flex items-center gap-2 bg-background rounded border px-2 py-1"
+                >
+                  <span className="flex-1 truncate">{queued.data.message}</span>
+                  <Button
+                    onClick={() => moveQueued(queued.id, -1)}
+                    disabled={isQueueLoading || index === 0}
+                    size="icon"
+                    variant="ghost"
+                    className="h-6 w-6"
+                    aria-label={t('conversations.moveQueuedUp', 'Move up')}
+                  >
+                    <ArrowUp className="h-3 w-3" />
+                  </Button>
+                  <Button
+                    onClick={() => moveQueued(queued.id, 1)}
+                    disabled={
+                      isQueueLoading || index === queuedMessages.length - 1
+                    }
+                    size="icon"
+                    variant="ghost"
+                    className="h-6 w-6"
+                    aria-label={t('conversations.moveQueuedDown', 'Move down')}
+                  >
+                    <ArrowDown className="h-3 w-3" />
+                  </Button>
+                  <Button
+                    onClick={() => removeQueued(queued.id)}
+                    disabled={isQueueLoading}
+                    size="icon"
+                    variant="ghost"
+                    className="h-6 w-6"
+                    aria-label={t('conversations.removeQueued', 'Remove')}
+                  >
+                    <X className="h-3 w-3" />
+                  </Button>
+                </li>
+              ))}
+            </ol>
           </div>
         )}
 
@@ -241,7 +268,7 @@ export function MessageInput({
           <div className={cn('flex-1 border rounded-md px-3 py-2 bg-background')}>
             <WYSIWYGEditor
               placeholder={placeholder ?? defaultPlaceholder}
-              value={displayMessage}
+              value={localMessage}
               onChange={handleEditorChange}
               disabled={false} // Never disabled - always allow typing
               onPasteFiles={handlePasteFiles}
@@ -254,39 +281,21 @@ export function MessageInput({
           <div className="flex items-center justify-end gap-2">
             {isExecutionRunning ? (
               <>
-                {/* Queue/Cancel Queue button when running */}
-                {isQueued ? (
-                  <Button
-                    onClick={cancelQueue}
-                    disabled={isQueueLoading}
-                    size="sm"
-                    variant="outline"
-                  >
-                    {isQueueLoading ? (
-                      <Loader2 className="animate-spin h-4 w-4 mr-2" />
-                    ) : (
-                      <>
-                        <X className="h-4 w-4 mr-2" />
-                        {t('conversations.cancelQueue', 'Cancel Queue')}
-                      </>
-                    )}
-                  </Button>
-                ) : (
-                  <Button
-                    onClick={handleQueueMessage}
-                    disabled={isQueueLoading || !localMessage.trim()}
-                    size="sm"
-                  >
-                    {isQueueLoading ? (
-                      <Loader2 className="animate-spin h-4 w-4 mr-2" />
-                    ) : (
-                      <>
-                        <Clock className="h-4 w-4 mr-2" />
-                        {t('conversations.queue', 'Queue')}
-                      </>
-                    )}
-                  </Button>
-                )}
+                {/* Queue button when running */}
+                <Button
+                  onClick={handleQueueMessage}
+                  disabled={isQueueLoading || !localMessage.trim()}
+                  size="sm"
+                >
+                  {isQueueLoading ? (
+                    <Loader2 className="animate-spin h-4 w-4 mr-2" />
+                  ) : (
+                    <>
+                      <Clock className="h-4 w-4 mr-2" />
+                      {t('conversations.queue', 'Queue')}
+                    </>
+                  )}
+                </Button>
                 <Button
                   onClick={onStop}
                   disabled={isStopping}
@@ -27,7 +27,7 @@ import { useUserSystem } from '@/components/ConfigProvider';
 import NiceModal, { useModal } from '@ebay/nice-modal-react';
 import { defineModal } from '@/lib/modals';
 import type { ConversationSession, ExecutorProfileId } from 'shared/types';
-import { Home, GitBranch } from 'lucide-react';
+import { Home, GitBranch, GitBranchPlus } from 'lucide-react';
 
 export interface NewConversationDialogProps {
   projectId: string;
@@ -37,6 +37,7 @@ export interface NewConversationDialogProps {
 
 const MAIN_REPO_VALUE = '__main__';
 const BRANCH_ONLY_PREFIX = '__branch__:';
+const NEW_WORKTREE_VALUE = '__new_worktree__';
 
 const NewConversationDialogImpl = NiceModal.create<NewConversationDialogProps>(
   (props) => {
@@ -70,9 +71,6 @@ const NewConversationDialogImpl = NiceModal.create<NewConversationDialogProps>(
       () => worktrees.filter((w) => !w.is_main),
       [worktrees]
     );
-    // Show selector if there are worktrees OR if a defaultBaseBranch is provided
-    const hasWorktrees = nonMainWorktrees.length > 0 || !!defaultBaseBranch;
-
     const createMutation = useCreateConversation();
     const isLoading = createMutation.isPending;
 
@@ -126,11 +124,12 @@ const NewConversationDialogImpl = NiceModal.create<NewConversationDialogProps>(
         // Determine worktree path and branch based on selection
         let worktreePath: string | null = null;
         let worktreeBranch: string | null = null;
+        const createWorktree = selectedWorktree === NEW_WORKTREE_VALUE;
 
         if (selectedWorktree.startsWith(BRANCH_ONLY_PREFIX)) {
           // Branch-only mode: no worktree path, just the branch name
           worktreeBranch = selectedWorktree.slice(BRANCH_ONLY_PREFIX.length);
-        } else if (selectedWorktree !== MAIN_REPO_VALUE) {
+        } else if (selectedWorktree !== MAIN_REPO_VALUE && !createWorktree) {
           // Regular worktree selection
           const selectedWorktreeInfo = worktrees.find(
             (w) => w.path === selectedWorktree
@@ -147,6 +146,7 @@ const NewConversationDialogImpl = NiceModal.create<NewConversationDialogProps>(
             executor_profile_id: effectiveProfile,
             worktree_path: worktreePath,
             worktree_branch: worktreeBranch,
+            create_worktree: createWorktree,
           },
         });
 
@@ -251,63 +251,71 @@ const NewConversationDialogImpl = NiceModal.create<NewConversationDialogProps>(
               </div>
             )}
 
-            {hasWorktrees && (
-              <div className="space-y-2">
-                <Label htmlFor="worktree-select">
-                  {t('conversations.worktreeLabel', {
-                    defaultValue: 'Start from',
-                  })}
-                </Label>
-                <Select
-                  value={selectedWorktree}
-                  onValueChange={setSelectedWorktree}
-                >
-                  <SelectTrigger id="worktree-select">
-                    <SelectValue />
-                  </SelectTrigger>
-                  <SelectContent>
-                    <SelectItem value={MAIN_REPO_VALUE}>
+            <div className="space-y-2">
+              <Label htmlFor="worktree-select">
+                {t('conversations.worktreeLabel', {
+                  defaultValue: 'Start from',
+                })}
+              </Label>
+              <Select
+                value={selectedWorktree}
+                onValueChange={setSelectedWorktree}
+              >
+                <SelectTrigger id="worktree-select">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value={MAIN_REPO_VALUE}>
+                    <div className="flex items-center gap-2">
+                      <Home className="h-4 w-4" />
+                      <span>
+                        {t('conversations.mainRepository', {
+                          defaultValue: 'Main repository',
+                        })}
+                      </span>
+                    </div>
+                  </SelectItem>
+                  <SelectItem value={NEW_WORKTREE_VALUE}>
+                    <div className="flex items-center gap-2">
+                      <GitBranchPlus className="h-4 w-4" />
+                      <span>
+                        {t('conversations.newWorktree', {
+                          defaultValue: 'New worktree',
+                        })}
+                      </span>
+                    </div>
+                  </SelectItem>
+                  {defaultBaseBranch && (
+                    <SelectItem
+                      value={`${BRANCH_ONLY_PREFIX}${defaultBaseBranch}`}
+                    >
                       <div className="flex items-center gap-2">
-                        <Home className="h-4 w-4" />
-                        <span>
-                          {t('conversations.mainRepository', {
-                            defaultValue: 'Main repository',
+                        <GitBranch className="h-4 w-4" />
+                        <span>{defaultBaseBranch}</span>
+                        <span className="text-muted-foreground text-xs ml-1">
+                          {t('conversations.branchOnly', {
+                            defaultValue: '(branch)',
                           })}
                         </span>
                       </div>
                     </SelectItem>
-                    {defaultBaseBranch && (
-                      <SelectItem
-                        value={`${BRANCH_ONLY_PREFIX}${defaultBaseBranch}`}
-                      >
-                        <div className="flex items-center gap-2">
-                          <GitBranch className="h-4 w-4" />
-                          <span>{defaultBaseBranch}</span>
-                          <span className="text-muted-foreground text-xs ml-1">
-                            {t('conversations.branchOnly', {
-                              defaultValue: '(branch)',
-                            })}
+                  )}
+                  {nonMainWorktrees.map((worktree) => (
+                    <SelectItem key={worktree.path} value={worktree.path}>
+                      <div className="flex items-center gap-2">
+                        <GitBranch className="h-4 w-4" />
+                        <span>{worktree.branch ?? worktree.path}</span>
+                        {worktree.branch && (
+                          <span className="text-muted-foreground text-xs ml-1 truncate max-w-[200px]">
+                            {worktree.path}
                           </span>
-                        </div>
-                      </SelectItem>
-                    )}
-                    {nonMainWorktrees.map((worktree) => (
-                      <SelectItem key={worktree.path} value={worktree.path}>
-                        <div className="flex items-center gap-2">
-                          <GitBranch className="h-4 w-4" />
-                          <span>{worktree.branch ?? worktree.path}</span>
-                          {worktree.branch && (
-                            <span className="text-muted-foreground text-xs ml-1 truncate max-w-[200px]">
-                              {worktree.path}
-                            </span>
-                          )}
-                        </div>
-                      </SelectItem>
-                    ))}
-                  </SelectContent>
-                </Select>
-              </div>
-            )}
+                        )}
+                      </div>
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
 
             <div className="space-y-2">
               <Label htmlFor="initial-message">
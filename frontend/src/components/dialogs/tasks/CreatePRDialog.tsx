@@ -14,7 +14,7 @@ import { Checkbox } from '@/components/ui/checkbox';
 import { Alert, AlertDescription, AlertTitle } from '@/components/ui/alert';
 import BranchSelector from '@/components/tasks/BranchSelector';
 import { useCallback, useEffect, useMemo, useState } from 'react';
-import { attemptsApi } from '@/lib/api.ts';
+import { attemptsApi, projectsApi } from '@/lib/api.ts';
 import { useTranslation } from 'react-i18next';
 
 import { TaskWithAttemptStatus, Workspace } from 'shared/types';
@@ -91,6 +91,25 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
       }
     }, [modal.visible, isLoaded, task, config?.pr_auto_description_enabled]);
 
+    // Default the draft checkbox to the project's draft PR policy
+    useEffect(() => {
+      if (!modal.visible) {
+        return;
+      }
+      let cancelled = false;
+      projectsApi
+        .getPrDraftPolicy(task.project_id)
+        .then((policy) => {
+          if (!cancelled) {
+            setIsDraft(policy.draft_by_default);
+          }
+        })
+        .catch(() => {});
+      return () => {
+        cancelled = true;
+      };
+    }, [modal.visible, task.project_id]);
+
     // Set default base branch when branches are loaded
     useEffect(() => {
       if (branches.length > 0 && !prBaseBranch) {
@@ -200,6 +219,18 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
           setError(result.message || t(gitCliErrorKey));
           setGhCliHelp(null);
           return;
+        } else if (result.error.type === 'gitlab_not_configured') {
+          setError(t('createPrDialog.errors.gitlabNotConfigured'));
+          setGhCliHelp(null);
+          return;
+        } else if (result.error.type === 'bitbucket_not_configured') {
+          setError(t('createPrDialog.errors.bitbucketNotConfigured'));
+          setGhCliHelp(null);
+          return;
+        } else if (result.error.type === 'gitea_not_configured') {
+          setError(t('createPrDialog.errors.giteaNotConfigured'));
+          setGhCliHelp(null);
+          return;
         } else if (result.error.type === 'target_branch_not_found') {
           setError(
             t('createPrDialog.errors.targetBranchNotFound', {
@@ -62,8 +62,8 @@ const DeleteTaskConfirmationDialogImpl =
           </DialogHeader>
 
           <Alert variant="destructive" className="mb-4">
-            <strong>Warning:</strong> This action will permanently delete the
-            task and cannot be undone.
+            <strong>Warning:</strong> The task moves to the trash and is
+            deleted for good once the trash retention period has passed.
           </Alert>
 
           {error && (
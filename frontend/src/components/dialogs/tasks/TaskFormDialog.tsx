@@ -30,6 +30,8 @@ import BranchSelector from '@/components/tasks/BranchSelector';
 import RepoBranchSelector from '@/components/tasks/RepoBranchSelector';
 import GroupSelector from '@/components/tasks/GroupSelector';
 import { DependencySection } from '@/components/tasks/DependencySection';
+import { TaskLinksSection } from '@/components/tasks/TaskLinksSection';
+import { TaskNotesSection } from '@/components/tasks/TaskNotesSection';
 import { ExecutorProfileSelector } from '@/components/settings';
 import { useUserSystem } from '@/components/ConfigProvider';
 import {
@@ -51,6 +53,7 @@ import { useHotkeysContext } from 'react-hotkeys-hook';
 import { cn } from '@/lib/utils';
 import type {
   TaskStatus,
+  TaskPriority,
   ExecutorProfileId,
   ImageResponse,
 } from 'shared/types';
@@ -61,6 +64,7 @@ interface Task {
   title: string;
   description: string | null;
   status: TaskStatus;
+  priority: TaskPriority;
   task_group_id: string | null;
   created_at: string;
   updated_at: string;
@@ -83,6 +87,7 @@ type TaskFormValues = {
   title: string;
   description: string;
   status: TaskStatus;
+  priority: TaskPriority;
   taskGroupId: string | null;
   executorProfileId: ExecutorProfileId | null;
   repoBranches: RepoBranch[];
@@ -142,6 +147,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: props.task.title,
           description: props.task.description || '',
           status: props.task.status,
+          priority: props.task.priority,
           taskGroupId: props.task.task_group_id,
           executorProfileId: baseProfile,
           repoBranches: defaultRepoBranches,
@@ -153,6 +159,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: props.initialTask.title,
           description: props.initialTask.description || '',
           status: 'todo',
+          priority: props.initialTask.priority,
           taskGroupId: null,
           executorProfileId: baseProfile,
           repoBranches: defaultRepoBranches,
@@ -166,6 +173,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
           title: '',
           description: '',
           status: 'todo',
+          priority: 'normal',
           taskGroupId: null,
           executorProfileId: baseProfile,
           repoBranches: defaultRepoBranches,
@@ -184,6 +192,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
             title: value.title,
             description: value.description,
             status: value.status,
+            priority: value.priority,
             parent_workspace_id: null,
             image_ids: images.length > 0 ? images.map((img) => img.id) : null,
             task_group_id: value.taskGroupId,
@@ -199,6 +208,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
         title: value.title,
         description: value.description,
         status: null,
+        priority: value.priority,
         parent_workspace_id:
           mode === 'subtask' ? props.parentTaskAttemptId : null,
         image_ids: imageIds,
@@ -515,6 +525,44 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
                 )}
               </form.Field>
             )}
+            {/* Priority */}
+            <form.Field name="priority">
+              {(field) => (
+                <div className="space-y-2">
+                  <Label
+                    htmlFor="task-priority"
+                    className="text-sm font-medium"
+                  >
+                    {t('taskFormDialog.priorityLabel')}
+                  </Label>
+                  <Select
+                    value={field.state.value}
+                    onValueChange={(value) =>
+                      field.handleChange(value as TaskPriority)
+                    }
+                    disabled={isSubmitting}
+                  >
+                    <SelectTrigger id="task-priority">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="low">
+                        {t('taskFormDialog.priorityOptions.low')}
+                      </SelectItem>
+                      <SelectItem value="normal">
+                        {t('taskFormDialog.priorityOptions.normal')}
+                      </SelectItem>
+                      <SelectItem value="high">
+                        {t('taskFormDialog.priorityOptions.high')}
+                      </SelectItem>
+                      <SelectItem value="urgent">
+                        {t('taskFormDialog.priorityOptions.urgent')}
+                      </SelectItem>
+                    </SelectContent>
+                  </Select>
+                </div>
+              )}
+            </form.Field>
             {/* Task Group selector */}
             <form.Field name="taskGroupId">
               {(field) => (
@@ -556,6 +604,16 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
                 />
               </div>
             )}
+            {editMode && (
+              <div className="pt-4">
+                <TaskLinksSection taskId={props.task.id} />
+              </div>
+            )}
+            {editMode && (
+              <div className="pt-4">
+                <TaskNotesSection taskId={props.task.id} />
+              </div>
+            )}
           </div>
 
           {/* Create mode dropdowns */}
@@ -1,7 +1,9 @@
 import { useNavigate } from 'react-router-dom';
 import {
+  ArrowRightLeft,
   CheckCircle2,
   AlertTriangle,
+  GitMerge,
   HelpCircle,
   XCircle,
   MessageSquare,
@@ -25,6 +27,8 @@ const NOTIFICATION_ICONS: Record<NotificationType, React.ElementType> = {
   agent_question_needed: HelpCircle,
   agent_error: XCircle,
   conversation_response: MessageSquare,
+  task_status_changed: ArrowRightLeft,
+  task_merged: GitMerge,
 };
 
 const NOTIFICATION_ICON_COLORS: Record<NotificationType, string> = {
@@ -33,6 +37,8 @@ const NOTIFICATION_ICON_COLORS: Record<NotificationType, string> = {
   agent_question_needed: 'text-purple-500',
   agent_error: 'text-red-500',
   conversation_response: 'text-blue-500',
+  task_status_changed: 'text-sky-500',
+  task_merged: 'text-violet-500',
 };
 
 interface NotificationMetadata {
@@ -57,6 +63,8 @@ export function NotificationItem({
   const navigate = useNavigate();
   const Icon = NOTIFICATION_ICONS[notification.notification_type];
   const iconColor = NOTIFICATION_ICON_COLORS[notification.notification_type];
+  // Folded from several similar notifications; show when the latest came in
+  const isGrouped = notification.occurrences > 1;
 
   const handleClick = () => {
     if (!notification.is_read) {
@@ -115,16 +123,25 @@ export function NotificationItem({
 
       <div className="flex-1 min-w-0">
         <div className="flex items-start justify-between gap-2">
-          <p
-            className={cn(
-              'text-sm truncate',
-              !notification.is_read && 'font-medium'
+          <div className="flex items-center gap-1.5 min-w-0">
+            <p
+              className={cn(
+                'text-sm truncate',
+                !notification.is_read && 'font-medium'
+              )}
+            >
+              {notification.title}
+            </p>
+            {isGrouped && (
+              <span className="text-xs rounded bg-muted px-1 text-muted-foreground shrink-0">
+                ×{notification.occurrences}
+              </span>
             )}
-          >
-            {notification.title}
-          </p>
+          </div>
           <span className="text-xs text-muted-foreground shrink-0">
-            {formatRelativeTime(notification.created_at)}
+            {formatRelativeTime(
+              isGrouped ? notification.updated_at : notification.created_at
+            )}
           </span>
         </div>
         <p className="text-xs text-muted-foreground mt-0.5 line-clamp-2">
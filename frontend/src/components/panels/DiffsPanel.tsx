@@ -55,7 +55,12 @@ export function DiffsPanel({ selectedAttempt, gitOps }: DiffsPanelProps) {
   >('loading');
   const [collapsedIds, setCollapsedIds] = useState<Set<string>>(new Set());
   const [processedIds, setProcessedIds] = useState<Set<string>>(new Set());
-  const { diffs, error } = useDiffStream(selectedAttempt?.id ?? null, !!selectedAttempt?.container_ref);
+  // Stats first; each card loads its file contents when expanded
+  const { diffs, error } = useDiffStream(
+    selectedAttempt?.id ?? null,
+    !!selectedAttempt?.container_ref,
+    { statsOnly: true }
+  );
   const { fileCount, added, deleted } = useDiffSummary(
     selectedAttempt?.id ?? null
   );
@@ -76,7 +76,7 @@ function ProjectCard({ project, isFocused, setError, onEdit }: Props) {
   const handleDelete = async (id: string, name: string) => {
     if (
       !confirm(
-        `Are you sure you want to delete "${name}"? This action cannot be undone.`
+        `Are you sure you want to delete "${name}"? It can be restored from the trash for a while.`
       )
     )
       return;
@@ -0,0 +1,96 @@
+import type { ReactNode } from 'react';
+import { useProjectDashboard } from '@/hooks';
+import { cn, formatFileSize } from '@/lib/utils';
+
+type ProjectDashboardHeaderProps = {
+  projectId: string;
+};
+
+function formatDuration(seconds: number): string {
+  const hours = seconds / 3600;
+  if (hours < 1) return `${Math.max(1, Math.round(seconds / 60))}m`;
+  if (hours < 48) return `${hours.toFixed(1)}h`;
+  return `${(hours / 24).toFixed(1)}d`;
+}
+
+function Stat({
+  label,
+  value,
+  title,
+  warn = false,
+}: {
+  label: string;
+  value: ReactNode;
+  title?: string;
+  warn?: boolean;
+}) {
+  return (
+    <div className="flex items-baseline gap-1.5" title={title}>
+      <span className="text-xs text-muted-foreground">{label}</span>
+      <span
+        className={cn(
+          'text-sm font-medium',
+          warn ? 'text-destructive' : 'text-foreground'
+        )}
+      >
+        {value}
+      </span>
+    </div>
+  );
+}
+
+export function ProjectDashboardHeader({
+  projectId,
+}: ProjectDashboardHeaderProps) {
+  const { data } = useProjectDashboard(projectId);
+  if (!data) return null;
+
+  const windowLabel = `last ${data.window_days} days`;
+  const failing = Number(data.failing_validations);
+  const approvals = Number(data.pending_approvals);
+
+  return (
+    <div className="flex flex-wrap items-center gap-x-5 gap-y-1 py-1">
+      <Stat
+        label="Open"
+        value={`${data.open_tasks.todo} / ${data.open_tasks.inprogress} / ${data.open_tasks.inreview}`}
+        title="To do / in progress / in review"
+      />
+      <Stat
+        label="Autopilot"
+        value={
+          data.autopilot_success_rate == null
+            ? '–'
+            : `${Math.round(data.autopilot_success_rate * 100)}%`
+        }
+        title={`Reviews needing no attention, ${windowLabel}`}
+      />
+      <Stat
+        label="To merge"
+        value={
+          data.mean_seconds_to_merge == null
+            ? '–'
+            : formatDuration(data.mean_seconds_to_merge)
+        }
+        title={`Mean time from creation to merge, ${windowLabel}`}
+      />
+      <Stat
+        label="Failing checks"
+        value={failing}
+        title="Open tasks whose latest setup or cleanup script failed"
+        warn={failing > 0}
+      />
+      <Stat
+        label="Approvals"
+        value={approvals}
+        title="Tool approvals waiting on you"
+        warn={approvals > 0}
+      />
+      <Stat
+        label="Disk"
+        value={formatFileSize(data.disk_usage_bytes) || '0 B'}
+        title="Space used by this project's worktrees"
+      />
+    </div>
+  );
+}
@@ -41,7 +41,7 @@ export function ProjectDetail({ projectId, onBack }: ProjectDetailProps) {
     if (!project) return;
     if (
       !confirm(
-        `Are you sure you want to delete "${project.name}"? This action cannot be undone.`
+        `Are you sure you want to delete "${project.name}"? It can be restored from the trash for a while.`
       )
     )
       return;
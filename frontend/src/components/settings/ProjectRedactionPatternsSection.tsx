@@ -0,0 +1,123 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { Loader2 } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { AutoExpandingTextarea } from '@/components/ui/auto-expanding-textarea';
+import { SettingsSection } from '@/components/settings/SettingsSection';
+import { SettingsField } from '@/components/settings/SettingsField';
+import { projectsApi } from '@/lib/api';
+import type { ProjectRedactionPatterns } from 'shared/types';
+
+const redactionPatternKeys = {
+  byProject: (projectId: string) =>
+    ['projects', projectId, 'redactionPatterns'] as const,
+};
+
+function toText(patterns: string[]): string {
+  return patterns.join('\n');
+}
+
+function fromText(text: string): string[] {
+  return text
+    .split('\n')
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0);
+}
+
+interface ProjectRedactionPatternsSectionProps {
+  projectId: string;
+}
+
+/**
+ * Extra patterns masked in this project's coding agent output, one regex
+ * per line.
+ */
+export function ProjectRedactionPatternsSection({
+  projectId,
+}: ProjectRedactionPatternsSectionProps) {
+  const { t } = useTranslation('settings');
+  const queryClient = useQueryClient();
+  const [draft, setDraft] = useState('');
+
+  const { data, isLoading } = useQuery<ProjectRedactionPatterns>({
+    queryKey: redactionPatternKeys.byProject(projectId),
+    queryFn: () => projectsApi.getRedactionPatterns(projectId),
+  });
+
+  useEffect(() => {
+    if (data) setDraft(toText(data.patterns));
+  }, [data]);
+
+  const save = useMutation({
+    mutationFn: (patterns: string[]) =>
+      projectsApi.updateRedactionPatterns(projectId, { patterns }),
+    onSuccess: (saved) => {
+      queryClient.setQueryData(
+        redactionPatternKeys.byProject(projectId),
+        saved
+      );
+    },
+  });
+
+  const saved = data ? toText(data.patterns) : '';
+  const hasChanges = toText(fromText(draft)) !== saved;
+
+  return (
+    <SettingsSection
+      id="project-redaction"
+      title={t('settings.projects.redaction.title')}
+      description={t('settings.projects.redaction.description')}
+      collapsible
+      defaultExpanded={false}
+    >
+      <div className="space-y-4">
+        {save.error && (
+          <Alert variant="destructive">
+            <AlertDescription>
+              {save.error instanceof Error
+                ? save.error.message
+                : t('settings.projects.redaction.saveError')}
+            </AlertDescription>
+          </Alert>
+        )}
+
+        <SettingsField
+          label={t('settings.projects.redaction.patterns.label')}
+          description={t('settings.projects.redaction.patterns.helper')}
+          htmlFor="redaction-patterns"
+        >
+          <AutoExpandingTextarea
+            id="redaction-patterns"
+            value={draft}
+            onChange={(e) => setDraft(e.target.value)}
+            placeholder={t('settings.projects.redaction.patterns.placeholder')}
+            maxRows={12}
+            disabled={isLoading}
+            className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+          />
+        </SettingsField>
+
+        <div className="flex justify-end gap-2">
+          <Button
+            variant="outline"
+            onClick={() => setDraft(saved)}
+            disabled={!hasChanges || save.isPending}
+          >
+            {t('settings.projects.save.discard')}
+          </Button>
+          <Button
+            onClick={() => save.mutate(fromText(draft))}
+            disabled={!hasChanges || save.isPending}
+          >
+            {save.isPending && (
+              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+            )}
+            {t('settings.projects.redaction.save')}
+          </Button>
+        </div>
+      </div>
+    </SettingsSection>
+  );
+}
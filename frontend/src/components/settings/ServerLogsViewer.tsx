@@ -82,9 +82,20 @@ function LogEntryRow({ entry }: LogEntryRowProps) {
 }
 
 export function ServerLogsViewer() {
-  const { logs, error, isConnected } = useServerLogStream();
   const [levelFilter, setLevelFilter] = useState<LogLevel>('ALL');
+  const [targetInput, setTargetInput] = useState('');
+  const [targetFilter, setTargetFilter] = useState('');
   const [searchQuery, setSearchQuery] = useState('');
+  const { logs, error, isConnected } = useServerLogStream({
+    level: levelFilter === 'ALL' ? undefined : levelFilter,
+    target: targetFilter || undefined,
+  });
+
+  // Reconnecting replays history, so wait for typing to settle
+  useEffect(() => {
+    const timer = setTimeout(() => setTargetFilter(targetInput.trim()), 400);
+    return () => clearTimeout(timer);
+  }, [targetInput]);
 
   const virtuosoRef = useRef<VirtuosoHandle>(null);
   const didInitScroll = useRef(false);
@@ -169,6 +180,14 @@ export function ServerLogsViewer() {
           </Select>
         </div>
 
+        <Input
+          type="text"
+          placeholder="Module, e.g. executors"
+          value={targetInput}
+          onChange={(e) => setTargetInput(e.target.value)}
+          className="w-[200px] h-8"
+        />
+
         <div className="flex-1 relative">
           <Search className="absolute left-2.5 top-1/2 -translate-y-1/2 h-4 w-4 text-muted-foreground" />
           <Input
@@ -15,8 +15,10 @@ interface OperationStatusBadgeProps {
 const operationLabels: Record<OperationStatusType, string> = {
   generating_commit: 'Generating...',
   rebasing: 'Rebasing...',
+  cherry_picking: 'Cherry-picking...',
   pushing: 'Pushing...',
   merging: 'Merging...',
+  waiting_for_rate_limit: 'Rate limited...',
 };
 
 export function OperationStatusBadge({ taskId }: OperationStatusBadgeProps) {
@@ -253,6 +253,18 @@ function TaskCardComponent({
             Queued
           </Badge>
         )}
+        {(task.priority === 'high' || task.priority === 'urgent') && (
+          <Badge
+            variant="outline"
+            className={
+              task.priority === 'urgent'
+                ? 'w-fit text-red-500'
+                : 'w-fit text-yellow-600'
+            }
+          >
+            {t(`taskFormDialog.priorityOptions.${task.priority}`)}
+          </Badge>
+        )}
         <OperationStatusBadge taskId={task.id} />
         <HookStatusBadge taskId={task.id} />
         <TaskGroupBadge
@@ -87,6 +87,13 @@ export function NoServerContent({
           dev_script: script,
           dev_script_working_dir: project.dev_script_working_dir ?? null,
           default_agent_working_dir: project.default_agent_working_dir ?? null,
+          script_shell: project.script_shell,
+          inherit_login_env: null,
+          dev_shell: project.dev_shell,
+          dev_shell_command: project.dev_shell_command,
+          container_image: project.container_image,
+          container_network: project.container_network,
+          max_concurrent_executions: project.max_concurrent_executions,
         },
       },
       {
@@ -131,6 +138,7 @@ export function NoServerContent({
         title: COMPANION_INSTALL_TASK_TITLE,
         description: COMPANION_INSTALL_TASK_DESCRIPTION,
         status: null,
+        priority: null,
         parent_workspace_id: null,
         image_ids: null,
         shared_task_id: null,
@@ -8,6 +8,7 @@ import {
   Paperclip,
   Terminal,
   MessageSquare,
+  CornerDownRight,
 } from 'lucide-react';
 import { Button } from '@/components/ui/button';
 import { Alert, AlertDescription } from '@/components/ui/alert';
@@ -330,6 +331,7 @@ export function TaskFollowUpSection({
     followUpError,
     setFollowUpError,
     onSendFollowUp,
+    onInterruptAndSend,
     isGloballyQueued,
     setIsGloballyQueued,
   } = useFollowUpSend({
@@ -952,6 +954,31 @@ export function TaskFollowUpSection({
                   )}
                 </Button>
               )}
+              {!isQueued && (
+                <Button
+                  onClick={onInterruptAndSend}
+                  disabled={
+                    isSendingFollowUp ||
+                    isStopping ||
+                    (!localMessage.trim() &&
+                      !conflictResolutionInstructions &&
+                      !reviewMarkdown &&
+                      !clickedMarkdown)
+                  }
+                  size="sm"
+                  variant="outline"
+                  title={t('followUp.interruptAndSendTooltip')}
+                >
+                  {isSendingFollowUp ? (
+                    <Loader2 className="animate-spin h-4 w-4 mr-2" />
+                  ) : (
+                    <>
+                      <CornerDownRight className="h-4 w-4 mr-2" />
+                      {t('followUp.interruptAndSend')}
+                    </>
+                  )}
+                </Button>
+              )}
               <Button
                 onClick={stopExecution}
                 disabled={isStopping}
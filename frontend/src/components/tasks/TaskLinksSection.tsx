@@ -0,0 +1,187 @@
+import { useState } from 'react';
+import { ExternalLink, Plus, RefreshCw, X } from 'lucide-react';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { useTaskLinkMutations, useTaskLinks } from '@/hooks';
+import { cn } from '@/lib/utils';
+import type { TaskLink, TaskLinkKind } from 'shared/types';
+
+type TaskLinksSectionProps = {
+  taskId: string;
+};
+
+const kindLabels: Record<TaskLinkKind, string> = {
+  design_doc: 'Design doc',
+  ticket: 'Ticket',
+  pull_request: 'PR',
+  other: 'Link',
+};
+
+type TaskLinkItemProps = {
+  link: TaskLink;
+  onToggleInPrompt: (include: boolean) => void;
+  onRefresh: () => void;
+  onRemove: () => void;
+  isPending: boolean;
+};
+
+function TaskLinkItem({
+  link,
+  onToggleInPrompt,
+  onRefresh,
+  onRemove,
+  isPending,
+}: TaskLinkItemProps) {
+  const title = link.title ?? link.unfurled_title ?? link.url;
+
+  return (
+    <div
+      className={cn(
+        'flex items-center justify-between gap-3',
+        'rounded-md border border-border px-2 py-1.5'
+      )}
+    >
+      <div className="flex items-center gap-2 min-w-0">
+        <Badge variant="outline" className="shrink-0">
+          {kindLabels[link.kind]}
+        </Badge>
+        <a
+          href={link.url}
+          target="_blank"
+          rel="noopener noreferrer"
+          title={link.url}
+          className="flex items-center gap-1 text-sm text-foreground truncate"
+        >
+          <span className="truncate">{title}</span>
+          <ExternalLink className="h-3 w-3 shrink-0" />
+        </a>
+        {link.unfurled_status && (
+          <Badge variant="secondary" className="shrink-0">
+            {link.unfurled_status}
+          </Badge>
+        )}
+      </div>
+      <div className="flex items-center gap-1 shrink-0">
+        <label
+          className="flex items-center gap-1.5 text-xs text-muted-foreground"
+          title="Include in agent prompts"
+        >
+          <Checkbox
+            checked={link.include_in_prompt}
+            onCheckedChange={onToggleInPrompt}
+            disabled={isPending}
+          />
+          Prompt
+        </label>
+        <Button
+          variant="icon"
+          size="sm"
+          onClick={onRefresh}
+          disabled={isPending}
+          aria-label="Refresh link"
+          className="h-7 w-7"
+        >
+          <RefreshCw className="h-4 w-4" />
+        </Button>
+        <Button
+          variant="icon"
+          size="sm"
+          onClick={onRemove}
+          disabled={isPending}
+          aria-label="Remove link"
+          className="h-7 w-7"
+        >
+          <X className="h-4 w-4" />
+        </Button>
+      </div>
+    </div>
+  );
+}
+
+export function TaskLinksSection({ taskId }: TaskLinksSectionProps) {
+  const { data: links = [], isLoading } = useTaskLinks(taskId);
+  const { createLink, updateLink, deleteLink, refreshLink } =
+    useTaskLinkMutations();
+  const [url, setUrl] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const isPending =
+    updateLink.isPending || deleteLink.isPending || refreshLink.isPending;
+
+  const handleAdd = async () => {
+    const trimmed = url.trim();
+    if (!trimmed) return;
+    setError(null);
+    try {
+      await createLink.mutateAsync({
+        taskId,
+        data: { url: trimmed, title: null },
+      });
+      setUrl('');
+    } catch (err) {
+      setError(err instanceof Error ? err.message : 'Failed to add link');
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <h4 className="text-sm font-semibold">Links</h4>
+      {isLoading ? (
+        <div className="text-sm text-muted-foreground">Loading...</div>
+      ) : links.length === 0 ? (
+        <div className="text-sm text-muted-foreground">No links.</div>
+      ) : (
+        <div className="space-y-2">
+          {links.map((link) => (
+            <TaskLinkItem
+              key={link.id}
+              link={link}
+              onToggleInPrompt={(include) =>
+                updateLink.mutate({
+                  taskId,
+                  linkId: link.id,
+                  data: {
+                    title: null,
+                    kind: null,
+                    include_in_prompt: include,
+                  },
+                })
+              }
+              onRefresh={() => refreshLink.mutate({ taskId, linkId: link.id })}
+              onRemove={() => deleteLink.mutate({ taskId, linkId: link.id })}
+              isPending={isPending}
+            />
+          ))}
+        </div>
+      )}
+
+      <div className="flex flex-col gap-2 sm:flex-row sm:items-center">
+        <Input
+          value={url}
+          onChange={(e) => setUrl(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === 'Enter') {
+              e.preventDefault();
+              handleAdd();
+            }
+          }}
+          placeholder="https://github.com/org/repo/issues/1"
+          className="sm:max-w-[20rem]"
+        />
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={handleAdd}
+          disabled={!url.trim() || createLink.isPending}
+          className="gap-2"
+        >
+          <Plus className="h-4 w-4" />
+          Add link
+        </Button>
+      </div>
+      {error && <div className="text-sm text-destructive">{error}</div>}
+    </div>
+  );
+}
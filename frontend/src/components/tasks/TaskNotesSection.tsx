@@ -0,0 +1,54 @@
+import { Button } from '@/components/ui/button';
+import { Textarea } from '@/components/ui/textarea';
+import { useTaskNotes } from '@/hooks';
+
+type TaskNotesSectionProps = {
+  taskId: string;
+};
+
+const saveStateLabels = {
+  idle: null,
+  saving: 'Saving...',
+  saved: 'Saved',
+  conflict: null,
+} as const;
+
+export function TaskNotesSection({ taskId }: TaskNotesSectionProps) {
+  const { content, update, reload, saveState, error, isLoading } =
+    useTaskNotes(taskId);
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center justify-between">
+        <h4 className="text-sm font-semibold">Notes</h4>
+        {saveStateLabels[saveState] && (
+          <span className="text-xs text-muted-foreground">
+            {saveStateLabels[saveState]}
+          </span>
+        )}
+      </div>
+      {isLoading ? (
+        <div className="text-sm text-muted-foreground">Loading...</div>
+      ) : (
+        <Textarea
+          value={content}
+          onChange={(e) => update(e.target.value)}
+          placeholder="Review notes, kept with the task and saved as you type"
+          className="rounded-md"
+          rows={5}
+        />
+      )}
+      {saveState === 'conflict' && (
+        <div className="flex items-center justify-between gap-2 text-sm">
+          <span className="text-destructive">
+            These notes were changed elsewhere. Your last edits weren't saved.
+          </span>
+          <Button variant="outline" size="sm" onClick={reload}>
+            Load latest
+          </Button>
+        </div>
+      )}
+      {error && <div className="text-sm text-destructive">{error}</div>}
+    </div>
+  );
+}
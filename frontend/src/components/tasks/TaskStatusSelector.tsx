@@ -32,6 +32,7 @@ export function TaskStatusSelector({ task, disabled, className }: Props) {
         title: null,
         description: null,
         status: newStatus as TaskStatus,
+        priority: null,
         parent_workspace_id: null,
         image_ids: null,
         task_group_id: null,
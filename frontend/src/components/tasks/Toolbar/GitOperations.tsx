@@ -38,6 +38,10 @@ import { useAttemptRepo } from '@/hooks/useAttemptRepo';
 import { useGitOperations } from '@/hooks/useGitOperations';
 import { useRepoBranches, useGenerateCommitMessage } from '@/hooks';
 import { useQueueMerge, useQueueStatus } from '@/hooks/useMergeQueue';
+import {
+  useAcknowledgePermissionFindings,
+  usePermissionFindings,
+} from '@/hooks/usePermissionFindings';
 import {
   SplitButton,
   type SplitButtonOption,
@@ -103,6 +107,13 @@ function GitOperations({
   const generateCommitMessage = useGenerateCommitMessage(selectedAttempt.id);
   const queueMerge = useQueueMerge(selectedAttempt.id);
   const { data: queueStatus } = useQueueStatus(selectedAttempt.id);
+  const { data: permissionFindings = [] } = usePermissionFindings(
+    selectedAttempt.id
+  );
+  const acknowledgeFindings = useAcknowledgePermissionFindings(
+    selectedAttempt.id
+  );
+  const openFindings = permissionFindings.filter((f) => !f.acknowledged_at);
   const isChangingTargetBranch = git.states.changeTargetBranchPending;
 
   // Local state for git operations
@@ -241,10 +252,11 @@ function GitOperations({
     if (mergeInfo.hasOpenPR) return t('git.disabled.prOpen', 'PR is open - close or merge it first');
     if (hasConflictsCalculated) return t('git.disabled.conflicts', 'Resolve conflicts before proceeding');
     if (isAttemptRunning) return t('git.disabled.attemptRunning', 'Wait for attempt to complete');
+    if (openFindings.length > 0) return t('git.disabled.permissionFindings', 'Acknowledge permission findings first');
     const commitsAhead = selectedRepoStatus?.commits_ahead ?? 0;
     if (commitsAhead === 0) return t('git.disabled.noChanges', 'No changes to merge');
     return null;
-  }, [mergeInfo.hasMergedPR, mergeInfo.hasOpenPR, hasConflictsCalculated, isAttemptRunning, selectedRepoStatus?.commits_ahead, t]);
+  }, [mergeInfo.hasMergedPR, mergeInfo.hasOpenPR, hasConflictsCalculated, isAttemptRunning, openFindings.length, selectedRepoStatus?.commits_ahead, t]);
 
   const prDisabledReason = useMemo(() => {
     if (mergeInfo.hasMergedPR) return t('git.disabled.prMerged', 'PR already merged');
@@ -592,6 +604,24 @@ function GitOperations({
         }
 
         const chips: React.ReactNode[] = [];
+        if (openFindings.length > 0) {
+          chips.push(
+            <button
+              key="permissionFindings"
+              onClick={() => acknowledgeFindings.mutate()}
+              disabled={acknowledgeFindings.isPending}
+              title={openFindings
+                .map((f) => `${f.kind}: ${f.path} (${f.detail})`)
+                .join('\n')}
+              className="inline-flex items-center gap-1 px-2 py-0.5 rounded-full bg-red-100/60 dark:bg-red-900/30 text-red-700 dark:text-red-300 hover:underline"
+            >
+              <AlertTriangle className="h-3.5 w-3.5" />
+              {t('git.status.permissionFindings', {
+                count: openFindings.length,
+              })}
+            </button>
+          );
+        }
         if (selectedRepoStatus?.target_branch_has_uncommitted_changes) {
           chips.push(
             <span
@@ -638,6 +668,14 @@ function GitOperations({
     </div>
   );
 
+  const remoteBranchUrl = getSelectedRepoStatus()?.remote_branch_url;
+  const remoteProviderName = {
+    github: 'GitHub',
+    gitlab: 'GitLab',
+    bitbucket: 'Bitbucket',
+    gitea: 'Gitea',
+  }[getSelectedRepoStatus()?.remote_provider ?? 'github'];
+
   const branchChips = (
     <>
       {/* Task branch chip */}
@@ -647,6 +685,19 @@ function GitOperations({
             <span className="hidden sm:inline-flex items-center gap-1.5 max-w-[280px] px-2 py-0.5 rounded-full bg-muted text-xs font-medium min-w-0">
               <GitBranchIcon className="h-3.5 w-3.5 text-muted-foreground shrink-0" />
               <span className="truncate">{selectedAttempt.branch}</span>
+              {remoteBranchUrl && (
+                <a
+                  href={remoteBranchUrl}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                  className="shrink-0 text-muted-foreground hover:text-foreground"
+                  aria-label={t('git.labels.viewRemoteBranch', {
+                    provider: remoteProviderName,
+                  })}
+                >
+                  <ExternalLink className="h-3 w-3" />
+                </a>
+              )}
             </span>
           </TooltipTrigger>
           <TooltipContent side="bottom">
@@ -1,5 +1,6 @@
 import { useMemo, useState } from 'react';
 import { useTranslation } from 'react-i18next';
+import { toast } from 'sonner';
 import { Button } from '@/components/ui/button';
 import {
   DropdownMenu,
@@ -30,6 +31,7 @@ import { DependencyTreeDialog } from '@/components/dialogs/tasks/DependencyTreeD
 import { AddDependencyDialog } from '@/components/dialogs/tasks/AddDependencyDialog';
 import { useProject } from '@/contexts/ProjectContext';
 import { openTaskForm } from '@/lib/openTaskForm';
+import { attemptsApi } from '@/lib/api';
 import { IdeIcon, getIdeName } from '@/components/ide/IdeIcon';
 
 import { useNavigate } from 'react-router-dom';
@@ -128,6 +130,26 @@ export function ActionsDropdown({
     ViewProcessesDialog.show({ attemptId: attempt.id });
   };
 
+  const handleRepairWorktrees = async (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (!attempt?.id) return;
+    try {
+      const repairs = await attemptsApi.repair(attempt.id);
+      const issues = repairs.reduce((n, r) => n + r.issues.length, 0);
+      if (issues === 0) {
+        toast.success(t('actionsMenu.repairWorktreesHealthy'));
+      } else {
+        toast.success(t('actionsMenu.repairWorktreesFixed', { count: issues }));
+      }
+    } catch (err) {
+      toast.error(
+        err instanceof Error
+          ? err.message
+          : t('actionsMenu.repairWorktreesFailed')
+      );
+    }
+  };
+
   const handleViewRelatedTasks = (e: React.MouseEvent) => {
     e.stopPropagation();
     if (!attempt?.id || !projectId) return;
@@ -285,6 +307,12 @@ export function ActionsDropdown({
               >
                 {t('actionsMenu.editBranchName')}
               </DropdownMenuItem>
+              <DropdownMenuItem
+                disabled={!attempt?.id}
+                onClick={handleRepairWorktrees}
+              >
+                {t('actionsMenu.repairWorktrees')}
+              </DropdownMenuItem>
               <DropdownMenuSeparator />
             </>
           )}
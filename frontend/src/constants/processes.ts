@@ -7,6 +7,7 @@ export const PROCESS_RUN_REASONS = {
   CODING_AGENT: 'codingagent' as ExecutionProcessRunReason,
   DEV_SERVER: 'devserver' as ExecutionProcessRunReason,
   INTERNAL_AGENT: 'internalagent' as ExecutionProcessRunReason,
+  REPLAY: 'replay' as ExecutionProcessRunReason,
 } as const;
 
 export const isCodingAgent = (
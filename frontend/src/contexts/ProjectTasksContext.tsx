@@ -11,6 +11,7 @@ import {
 import { useQueries, useQuery, useQueryClient } from '@tanstack/react-query';
 import { useProject } from '@/contexts/ProjectContext';
 import { tasksApi, getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 import {
   projectTasksKeys,
   setTaskInCache,
@@ -119,12 +120,14 @@ export function ProjectTasksProvider({ children }: ProjectTasksProviderProps) {
     return errorObj instanceof Error ? errorObj.message : 'Failed to load tasks';
   }, [anyQueryError, statusQueries]);
 
-  // Derive tasksById from React Query cache - single source of truth
+  // Derive tasksById from React Query cache - single source of truth.
+  // Tasks archived while the board is open are left out.
   const tasksById = useMemo(() => {
     const result: Record<string, TaskWithAttemptStatus> = {};
     for (const query of statusQueries) {
       if (!query.data) continue;
       for (const task of query.data.page.tasks) {
+        if (task.archived_at) continue;
         result[task.id] = task;
       }
     }
@@ -458,7 +461,7 @@ export function ProjectTasksProvider({ children }: ProjectTasksProviderProps) {
       return;
     }
 
-    let ws: WebSocket | null = null;
+    let ws: StreamSocket | null = null;
     let retryTimer: number | null = null;
     let retryAttempts = 0;
     let closed = false;
@@ -478,8 +481,7 @@ export function ProjectTasksProvider({ children }: ProjectTasksProviderProps) {
         projectId
       )}&include_snapshot=true`;
       const fullEndpoint = getApiBaseUrlSync() + endpoint;
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      ws = new WebSocket(wsEndpoint);
+      ws = openStreamSocket(fullEndpoint);
 
       ws.onopen = () => {
         retryAttempts = 0;
@@ -26,6 +26,12 @@ export {
   useTaskDependencies,
   useTaskDependencyTree,
 } from './useTaskDependencies';
+export { useTaskLinkMutations, useTaskLinks } from './useTaskLinks';
+export { useTaskNotes } from './useTaskNotes';
+export {
+  projectDashboardKeys,
+  useProjectDashboard,
+} from './useProjectDashboard';
 export { useImageUpload } from './useImageUpload';
 export { useTaskMutations } from './useTaskMutations';
 export { useDevServer } from './useDevServer';
@@ -40,6 +46,10 @@ export {
   useQueueStatus,
   useProjectQueueCount,
   useGroupQueueCount,
+  useProjectMergeQueue,
+  useProjectMergeQueueRuns,
+  useReorderMergeQueue,
+  useCancelMergeQueueEntry,
 } from './useMergeQueue';
 export { useGenerateCommitMessage } from './useGenerateCommitMessage';
 export { usePush } from './usePush';
@@ -1,4 +1,4 @@
-import { useState, useCallback, useEffect } from 'react';
+import { useState, useCallback, useEffect, useMemo } from 'react';
 import { conversationQueueApi } from '@/lib/api';
 import type { QueueStatus, QueuedMessage } from 'shared/types';
 
@@ -7,14 +7,20 @@ interface UseConversationQueueStatusResult {
   queueStatus: QueueStatus;
   /** Whether a message is currently queued */
   isQueued: boolean;
-  /** The queued message if any */
+  /** The message sent next, if any */
   queuedMessage: QueuedMessage | null;
+  /** Every queued message, in the order they will be sent */
+  queuedMessages: QueuedMessage[];
   /** Whether an operation is in progress */
   isLoading: boolean;
-  /** Queue a new message */
+  /** Add a message to the end of the queue */
   queueMessage: (message: string, variant: string | null) => Promise<void>;
-  /** Cancel the queued message */
+  /** Cancel all queued messages */
   cancelQueue: () => Promise<void>;
+  /** Remove one queued message */
+  removeQueued: (messageId: string) => Promise<void>;
+  /** Move a queued message up (-1) or down (1) in the queue */
+  moveQueued: (messageId: string, offset: -1 | 1) => Promise<void>;
   /** Refresh the queue status from the server */
   refresh: () => Promise<void>;
 }
@@ -65,6 +71,54 @@ export function useConversationQueueStatus(
     }
   }, [conversationId]);
 
+  const removeQueued = useCallback(
+    async (messageId: string) => {
+      if (!conversationId) return;
+      setIsLoading(true);
+      try {
+        const status = await conversationQueueApi.remove(
+          conversationId,
+          messageId
+        );
+        setQueueStatus(status);
+      } finally {
+        setIsLoading(false);
+      }
+    },
+    [conversationId]
+  );
+
+  const isQueued = queueStatus.status === 'queued';
+  const queuedMessages = useMemo(() => {
+    if (queueStatus.status !== 'queued') return [];
+    return [queueStatus.message, ...queueStatus.pending];
+  }, [queueStatus]);
+
+  const moveQueued = useCallback(
+    async (messageId: string, offset: -1 | 1) => {
+      if (!conversationId) return;
+      const ids = queuedMessages.map((m) => m.id);
+      const from = ids.indexOf(messageId);
+      const to = from + offset;
+      if (from < 0 || to < 0 || to >= ids.length) return;
+      [ids[from], ids[to]] = [ids[to], ids[from]];
+      setIsLoading(true);
+      try {
+        const status = await conversationQueueApi.reorder(conversationId, {
+          message_ids: ids,
+        });
+        setQueueStatus(status);
+      } catch (e) {
+        // The queue moved on meanwhile; show what is queued now
+        console.error('Failed to reorder conversation queue:', e);
+        await refresh();
+      } finally {
+        setIsLoading(false);
+      }
+    },
+    [conversationId, queuedMessages, refresh]
+  );
+
   // Fetch initial status when conversationId changes
   useEffect(() => {
     if (conversationId) {
@@ -74,18 +128,18 @@ export function useConversationQueueStatus(
     }
   }, [conversationId, refresh]);
 
-  const isQueued = queueStatus.status === 'queued';
-  const queuedMessage = isQueued
-    ? (queueStatus as Extract<QueueStatus, { status: 'queued' }>).message
-    : null;
+  const queuedMessage = queuedMessages[0] ?? null;
 
   return {
     queueStatus,
     isQueued,
     queuedMessage,
+    queuedMessages,
     isLoading,
     queueMessage,
     cancelQueue,
+    removeQueued,
+    moveQueued,
     refresh,
   };
 }
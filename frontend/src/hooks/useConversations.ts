@@ -8,7 +8,12 @@ import {
   type SendConversationMessageRequest,
   type ListConversationsParams,
 } from '@/lib/api';
-import type { ConversationMessage, ConversationWithMessages, SendMessageResponse } from 'shared/types';
+import type {
+  ConversationMessage,
+  ConversationWithMessages,
+  ForkConversationRequest,
+  SendMessageResponse,
+} from 'shared/types';
 
 export const conversationKeys = {
   all: ['conversations'] as const,
@@ -233,6 +238,25 @@ export function useDeleteConversation() {
   });
 }
 
+export function useForkConversation() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      conversationId,
+      data,
+    }: {
+      conversationId: string;
+      data: ForkConversationRequest;
+    }) => conversationsApi.fork(conversationId, data),
+    onSuccess: () => {
+      queryClient.invalidateQueries({
+        queryKey: conversationKeys.lists(),
+      });
+    },
+  });
+}
+
 export function useSendMessage() {
   const queryClient = useQueryClient();
 
@@ -0,0 +1,67 @@
+import { useQuery } from '@tanstack/react-query';
+import { useUserSystem } from '@/components/ConfigProvider';
+import { attemptsApi, type DiffFileContent } from '@/lib/api';
+import type { Diff } from 'shared/types';
+
+// Mirror the server's default and maximum inline diff limits
+const DEFAULT_MAX_INLINE_DIFF_KB = 2048;
+const MAX_INLINE_DIFF_KB_LIMIT = 64 * 1024;
+
+const MISSING: DiffFileContent = {
+  content: null,
+  totalBytes: 0,
+  truncated: false,
+  binary: false,
+};
+
+/**
+ * Old and new contents of a file whose diff arrived without them. Refetched
+ * whenever the streamed line counts for the file change.
+ */
+export function useDiffFileContent(
+  attemptId: string | undefined,
+  diff: Diff,
+  enabled: boolean
+) {
+  const { config } = useUserSystem();
+  // Largest side the viewer will diff, as configured for inline diffs
+  const maxBytes =
+    Math.min(
+      config?.max_inline_diff_kb ?? DEFAULT_MAX_INLINE_DIFF_KB,
+      MAX_INLINE_DIFF_KB_LIMIT
+    ) * 1024;
+  const oldPath = diff.change === 'added' ? null : diff.oldPath;
+  const newPath = diff.change === 'deleted' ? null : diff.newPath;
+
+  const query = useQuery({
+    queryKey: [
+      'diffFileContent',
+      attemptId,
+      oldPath,
+      newPath,
+      diff.additions,
+      diff.deletions,
+      maxBytes,
+    ],
+    queryFn: async () => {
+      const [oldSide, newSide] = await Promise.all([
+        oldPath
+          ? attemptsApi.getDiffFileContent(attemptId!, oldPath, 'old', maxBytes)
+          : MISSING,
+        newPath
+          ? attemptsApi.getDiffFileContent(attemptId!, newPath, 'new', maxBytes)
+          : MISSING,
+      ]);
+      return { oldSide, newSide };
+    },
+    enabled: enabled && !!attemptId,
+    staleTime: Infinity,
+  });
+
+  return {
+    oldSide: query.data?.oldSide ?? null,
+    newSide: query.data?.newSide ?? null,
+    isLoading: query.isLoading,
+    isError: query.isError,
+  } as const;
+}
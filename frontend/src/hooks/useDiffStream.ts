@@ -12,6 +12,8 @@ type DiffStreamEvent = {
 
 export interface UseDiffStreamOptions {
   statsOnly?: boolean;
+  /** Per-file content limit, overriding the configured one */
+  maxInlineBytes?: number;
 }
 
 interface UseDiffStreamResult {
@@ -27,13 +29,15 @@ export const useDiffStream = (
   const endpoint = (() => {
     if (!attemptId) return undefined;
     const query = `/api/task-attempts/${attemptId}/diff/ws`;
+    const params = new URLSearchParams();
     if (typeof options?.statsOnly === 'boolean') {
-      const params = new URLSearchParams();
       params.set('stats_only', String(options.statsOnly));
-      return `${query}?${params.toString()}`;
-    } else {
-      return query;
     }
+    if (typeof options?.maxInlineBytes === 'number') {
+      params.set('max_inline_bytes', String(options.maxInlineBytes));
+    }
+    const search = params.toString();
+    return search ? `${query}?${search}` : query;
   })();
 
   const initialData = useCallback(
@@ -1,6 +1,9 @@
 import { useCallback, useRef, useState } from 'react';
 import { sessionsApi } from '@/lib/api';
-import type { CreateFollowUpAttempt } from 'shared/types';
+import type {
+  CreateFollowUpAttempt,
+  InterruptAndFollowUpRequest,
+} from 'shared/types';
 
 type Args = {
   sessionId?: string;
@@ -32,73 +35,91 @@ export function useFollowUpSend({
   // Sync guard to prevent duplicate calls before React state updates
   const isSendingRef = useRef(false);
 
-  const onSendFollowUp = useCallback(async () => {
-    if (isSendingRef.current) return;
-    isSendingRef.current = true;
+  // With `interrupt`, the running agent turn is stopped first and the message
+  // continues the conversation from there
+  const send = useCallback(
+    async (interrupt: boolean) => {
+      if (isSendingRef.current) return;
+      isSendingRef.current = true;
 
-    if (!sessionId) {
-      isSendingRef.current = false;
-      return;
-    }
-    const extraMessage = message.trim();
-    const finalPrompt = [
+      if (!sessionId) {
+        isSendingRef.current = false;
+        return;
+      }
+      const extraMessage = message.trim();
+      const finalPrompt = [
+        conflictMarkdown,
+        clickedMarkdown?.trim(),
+        reviewMarkdown?.trim(),
+        extraMessage,
+      ]
+        .filter(Boolean)
+        .join('\n\n');
+      if (!finalPrompt) {
+        isSendingRef.current = false;
+        return;
+      }
+      try {
+        setIsSendingFollowUp(true);
+        setFollowUpError(null);
+        setIsGloballyQueued(false);
+        let result;
+        if (interrupt) {
+          const body: InterruptAndFollowUpRequest = {
+            prompt: finalPrompt,
+            variant: selectedVariant,
+          };
+          result = await sessionsApi.interruptAndFollowUp(sessionId, body);
+        } else {
+          const body: CreateFollowUpAttempt = {
+            prompt: finalPrompt,
+            variant: selectedVariant,
+            retry_process_id: null,
+            force_when_dirty: null,
+            perform_git_reset: null,
+          };
+          result = await sessionsApi.followUp(sessionId, body);
+        }
+        if (result.status === 'queued') {
+          // Follow-up was queued due to global concurrency limit
+          setIsGloballyQueued(true);
+        }
+        clearComments();
+        clearClickedElements?.();
+        onAfterSendCleanup();
+        // Don't call jumpToLogsTab() - preserves focus on the follow-up editor
+      } catch (error: unknown) {
+        const err = error as { message?: string };
+        setFollowUpError(
+          `Failed to start follow-up execution: ${err.message ?? 'Unknown error'}`
+        );
+      } finally {
+        isSendingRef.current = false;
+        setIsSendingFollowUp(false);
+      }
+    },
+    [
+      sessionId,
+      message,
       conflictMarkdown,
-      clickedMarkdown?.trim(),
-      reviewMarkdown?.trim(),
-      extraMessage,
+      reviewMarkdown,
+      clickedMarkdown,
+      selectedVariant,
+      clearComments,
+      clearClickedElements,
+      onAfterSendCleanup,
     ]
-      .filter(Boolean)
-      .join('\n\n');
-    if (!finalPrompt) {
-      isSendingRef.current = false;
-      return;
-    }
-    try {
-      setIsSendingFollowUp(true);
-      setFollowUpError(null);
-      setIsGloballyQueued(false);
-      const body: CreateFollowUpAttempt = {
-        prompt: finalPrompt,
-        variant: selectedVariant,
-        retry_process_id: null,
-        force_when_dirty: null,
-        perform_git_reset: null,
-      };
-      const result = await sessionsApi.followUp(sessionId, body);
-      if (result.status === 'queued') {
-        // Follow-up was queued due to global concurrency limit
-        setIsGloballyQueued(true);
-      }
-      clearComments();
-      clearClickedElements?.();
-      onAfterSendCleanup();
-      // Don't call jumpToLogsTab() - preserves focus on the follow-up editor
-    } catch (error: unknown) {
-      const err = error as { message?: string };
-      setFollowUpError(
-        `Failed to start follow-up execution: ${err.message ?? 'Unknown error'}`
-      );
-    } finally {
-      isSendingRef.current = false;
-      setIsSendingFollowUp(false);
-    }
-  }, [
-    sessionId,
-    message,
-    conflictMarkdown,
-    reviewMarkdown,
-    clickedMarkdown,
-    selectedVariant,
-    clearComments,
-    clearClickedElements,
-    onAfterSendCleanup,
-  ]);
+  );
+
+  const onSendFollowUp = useCallback(() => send(false), [send]);
+  const onInterruptAndSend = useCallback(() => send(true), [send]);
 
   return {
     isSendingFollowUp,
     followUpError,
     setFollowUpError,
     onSendFollowUp,
+    onInterruptAndSend,
     isGloballyQueued,
     setIsGloballyQueued,
   } as const;
@@ -6,6 +6,7 @@ import {
   type TransformOptions,
 } from '@/lib/transformGantt';
 import { ganttApi, getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 import type { GanttTask } from 'shared/types';
 import type { Operation } from 'rfc6902';
 
@@ -190,7 +191,7 @@ export const useGanttTasks = (
       return;
     }
 
-    let ws: WebSocket | null = null;
+    let ws: StreamSocket | null = null;
     let retryTimer: number | null = null;
     let retryAttempts = 0;
     let closed = false;
@@ -209,8 +210,7 @@ export const useGanttTasks = (
       const endpoint = `/api/projects/${encodeURIComponent(projectId)}/gantt/stream/ws`;
       // Prepend base URL for Tauri (where server runs on dynamic port)
       const fullEndpoint = getApiBaseUrlSync() + endpoint;
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      ws = new WebSocket(wsEndpoint);
+      ws = openStreamSocket(fullEndpoint);
 
       ws.onopen = () => {
         retryAttempts = 0;
@@ -33,7 +33,12 @@ export function useGitOperations(
 
   const merge = useMerge(
     attemptId,
-    () => setError(null),
+    (result) =>
+      setError(
+        result.hook_failure
+          ? `Merged without the repo's git hooks, which failed:\n${result.hook_failure}`
+          : null
+      ),
     (err: unknown) => {
       const message =
         err && typeof err === 'object' && 'message' in err
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { useQueryClient } from '@tanstack/react-query';
 import { getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 
 interface GitStateChangedMessage {
   message_type: 'git_state_changed';
@@ -22,7 +23,7 @@ export function useGitStateSubscription(
 ): void {
   const { enabled = true } = options;
   const queryClient = useQueryClient();
-  const wsRef = useRef<WebSocket | null>(null);
+  const wsRef = useRef<StreamSocket | null>(null);
   const retryTimerRef = useRef<number | null>(null);
   const retryAttemptsRef = useRef<number>(0);
   const [retryNonce, setRetryNonce] = useState(0);
@@ -53,12 +54,10 @@ export function useGitStateSubscription(
       return;
     }
 
-    // Build WebSocket URL
     const baseUrl = getApiBaseUrlSync();
     const httpUrl = `${baseUrl}/api/task-attempts/${attemptId}/git-status/ws`;
-    const wsUrl = httpUrl.replace(/^http/, 'ws');
 
-    const ws = new WebSocket(wsUrl);
+    const ws = openStreamSocket(httpUrl);
 
     ws.onopen = () => {
       // Reset backoff on successful connection
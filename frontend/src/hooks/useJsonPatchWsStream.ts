@@ -2,6 +2,7 @@ import { useEffect, useState, useRef } from 'react';
 import { applyPatch } from 'rfc6902';
 import type { Operation } from 'rfc6902';
 import { getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 
 type WsJsonPatchMsg = { JsonPatch: Operation[] };
 type WsFinishedMsg = { finished: boolean };
@@ -36,7 +37,7 @@ export const useJsonPatchWsStream = <T extends object>(
   const [data, setData] = useState<T | undefined>(undefined);
   const [isConnected, setIsConnected] = useState(false);
   const [error, setError] = useState<string | null>(null);
-  const wsRef = useRef<WebSocket | null>(null);
+  const wsRef = useRef<StreamSocket | null>(null);
   const dataRef = useRef<T | undefined>(undefined);
   const prevEndpointRef = useRef<string | undefined>(undefined);
   const pendingPatchesRef = useRef<Operation[]>([]);
@@ -109,16 +110,14 @@ export const useJsonPatchWsStream = <T extends object>(
       // Reset finished flag for new connection
       finishedRef.current = false;
 
-      // Build full WebSocket URL from endpoint
+      // Build full URL from endpoint
       // If endpoint is relative (starts with /), prepend the API base URL
       let fullEndpoint = endpoint;
       if (endpoint.startsWith('/')) {
         const baseUrl = getApiBaseUrlSync();
         fullEndpoint = baseUrl + endpoint;
       }
-      // Convert HTTP to WebSocket protocol
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      const ws = new WebSocket(wsEndpoint);
+      const ws = openStreamSocket(fullEndpoint);
 
       ws.onopen = () => {
         setError(null);
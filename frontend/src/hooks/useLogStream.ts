@@ -1,6 +1,7 @@
 import { useEffect, useState, useRef } from 'react';
 import type { PatchType } from 'shared/types';
 import { getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 
 type LogEntry = Extract<PatchType, { type: 'STDOUT' } | { type: 'STDERR' }>;
 
@@ -12,7 +13,7 @@ interface UseLogStreamResult {
 export const useLogStream = (processId: string): UseLogStreamResult => {
   const [logs, setLogs] = useState<LogEntry[]>([]);
   const [error, setError] = useState<string | null>(null);
-  const wsRef = useRef<WebSocket | null>(null);
+  const wsRef = useRef<StreamSocket | null>(null);
   const retryCountRef = useRef<number>(0);
   const retryTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const isIntentionallyClosed = useRef<boolean>(false);
@@ -27,11 +28,10 @@ export const useLogStream = (processId: string): UseLogStreamResult => {
     setError(null);
 
     const open = () => {
-      // Build WebSocket URL using API base URL (required for Tauri where server runs on dynamic port)
+      // Build the URL using API base URL (required for Tauri where server runs on dynamic port)
       const endpoint = `/api/execution-processes/${processId}/raw-logs/ws`;
       const fullEndpoint = getApiBaseUrlSync() + endpoint;
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      const ws = new WebSocket(wsEndpoint);
+      const ws = openStreamSocket(fullEndpoint);
       wsRef.current = ws;
       isIntentionallyClosed.current = false;
 
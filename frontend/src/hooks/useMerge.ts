@@ -1,5 +1,6 @@
 import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { attemptsApi } from '@/lib/api';
+import type { MergeTaskAttemptResponse } from 'shared/types';
 import { repoBranchKeys } from './useRepoBranches';
 
 type MergeParams = {
@@ -10,28 +11,28 @@ type MergeParams = {
 
 export function useMerge(
   attemptId?: string,
-  onSuccess?: () => void,
+  onSuccess?: (result: MergeTaskAttemptResponse) => void,
   onError?: (err: unknown) => void
 ) {
   const queryClient = useQueryClient();
 
-  return useMutation<void, unknown, MergeParams>({
+  return useMutation<MergeTaskAttemptResponse, unknown, MergeParams>({
     mutationFn: (params: MergeParams) => {
-      if (!attemptId) return Promise.resolve();
+      if (!attemptId) return Promise.resolve({ hook_failure: null });
       return attemptsApi.merge(attemptId, {
         repo_id: params.repoId,
         commit_message: params.commitMessage ?? null,
         generate_commit_message: params.generateCommitMessage ?? null,
       });
     },
-    onSuccess: () => {
+    onSuccess: (result) => {
       // Refresh attempt-specific branch information
       queryClient.invalidateQueries({ queryKey: ['branchStatus', attemptId] });
 
       // Invalidate all repo branches queries
       queryClient.invalidateQueries({ queryKey: repoBranchKeys.all });
 
-      onSuccess?.();
+      onSuccess?.(result);
     },
     onError: (err) => {
       console.error('Failed to merge:', err);
@@ -3,6 +3,8 @@ import { attemptsApi, projectsApi, taskGroupsApi, Result } from '@/lib/api';
 import type {
   MergeQueueEntry,
   MergeQueueCountResponse,
+  MergeQueueRun,
+  QueuedMerge,
   QueueMergeError,
 } from 'shared/types';
 import { repoBranchKeys } from './useRepoBranches';
@@ -15,6 +17,10 @@ export const mergeQueueKeys = {
     ['mergeQueue', 'projectCount', projectId] as const,
   groupCount: (groupId: string | undefined) =>
     ['mergeQueue', 'groupCount', groupId] as const,
+  projectEntries: (projectId: string | undefined) =>
+    ['mergeQueue', 'projectEntries', projectId] as const,
+  projectRuns: (projectId: string | undefined) =>
+    ['mergeQueue', 'projectRuns', projectId] as const,
 };
 
 type QueryOptions = {
@@ -136,3 +142,65 @@ export function useGroupQueueCount(groupId?: string, opts?: QueryOptions) {
     retry: opts?.retry ?? 2,
   });
 }
+
+export function useProjectMergeQueue(projectId?: string, opts?: QueryOptions) {
+  const enabled = (opts?.enabled ?? true) && !!projectId;
+
+  return useQuery<QueuedMerge[]>({
+    queryKey: mergeQueueKeys.projectEntries(projectId),
+    queryFn: () => projectsApi.getMergeQueue(projectId!),
+    enabled,
+    refetchInterval: opts?.refetchInterval ?? 3000,
+    staleTime: opts?.staleTime ?? 2000,
+    retry: opts?.retry ?? 2,
+  });
+}
+
+export function useProjectMergeQueueRuns(
+  projectId?: string,
+  opts?: QueryOptions
+) {
+  const enabled = (opts?.enabled ?? true) && !!projectId;
+
+  return useQuery<MergeQueueRun[]>({
+    queryKey: mergeQueueKeys.projectRuns(projectId),
+    queryFn: () => projectsApi.getMergeQueueRuns(projectId!),
+    enabled,
+    refetchInterval: opts?.refetchInterval ?? 5000,
+    staleTime: opts?.staleTime ?? 2000,
+    retry: opts?.retry ?? 2,
+  });
+}
+
+export function useReorderMergeQueue(projectId?: string) {
+  const queryClient = useQueryClient();
+
+  return useMutation<QueuedMerge[], unknown, string[]>({
+    mutationFn: (entryIds: string[]) =>
+      projectsApi.reorderMergeQueue(projectId!, { entry_ids: entryIds }),
+    onSuccess: (entries) => {
+      queryClient.setQueryData(
+        mergeQueueKeys.projectEntries(projectId),
+        entries
+      );
+    },
+    onError: (err) => {
+      console.error('Failed to reorder merge queue:', err);
+    },
+  });
+}
+
+export function useCancelMergeQueueEntry(projectId?: string) {
+  const queryClient = useQueryClient();
+
+  return useMutation<void, unknown, string>({
+    mutationFn: (entryId: string) =>
+      projectsApi.cancelMergeQueueEntry(projectId!, entryId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: mergeQueueKeys.all });
+    },
+    onError: (err) => {
+      console.error('Failed to cancel merge queue entry:', err);
+    },
+  });
+}
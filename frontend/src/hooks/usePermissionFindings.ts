@@ -0,0 +1,33 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { attemptsApi } from '@/lib/api';
+import type { PermissionFinding } from 'shared/types';
+
+export const permissionFindingKeys = {
+  attempt: (attemptId: string | undefined) =>
+    ['permissionFindings', attemptId] as const,
+};
+
+export function usePermissionFindings(attemptId?: string) {
+  return useQuery<PermissionFinding[]>({
+    queryKey: permissionFindingKeys.attempt(attemptId),
+    queryFn: () => attemptsApi.getPermissionFindings(attemptId!),
+    enabled: !!attemptId,
+    staleTime: 10000,
+  });
+}
+
+export function useAcknowledgePermissionFindings(attemptId?: string) {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: () => attemptsApi.acknowledgePermissionFindings(attemptId!),
+    onSuccess: () => {
+      queryClient.invalidateQueries({
+        queryKey: permissionFindingKeys.attempt(attemptId),
+      });
+    },
+    onError: (err) => {
+      console.error('Failed to acknowledge permission findings:', err);
+    },
+  });
+}
@@ -0,0 +1,18 @@
+import { useQuery } from '@tanstack/react-query';
+import { projectsApi } from '@/lib/api';
+import type { ProjectDashboardResponse } from 'shared/types';
+
+export const projectDashboardKeys = {
+  byProject: (projectId: string | undefined) =>
+    ['projectDashboard', projectId] as const,
+};
+
+export function useProjectDashboard(projectId?: string) {
+  return useQuery<ProjectDashboardResponse>({
+    queryKey: projectDashboardKeys.byProject(projectId),
+    queryFn: () => projectsApi.getDashboard(projectId!),
+    enabled: !!projectId,
+    staleTime: 30_000,
+    refetchInterval: 60_000,
+  });
+}
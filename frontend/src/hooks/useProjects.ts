@@ -3,6 +3,7 @@ import { useQuery } from '@tanstack/react-query';
 import type { Operation } from 'rfc6902';
 import type { ProjectWithTaskCounts } from 'shared/types';
 import { projectsApi, getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 
 export const projectsKeys = {
   all: ['projects'] as const,
@@ -38,7 +39,7 @@ export function useProjects(): UseProjectsResult {
     error: queryError,
   } = useQuery({
     queryKey: projectsKeys.list(),
-    queryFn: projectsApi.list,
+    queryFn: () => projectsApi.list(),
     staleTime: 30_000, // 30 seconds
     refetchOnMount: false,
     refetchOnWindowFocus: false,
@@ -110,7 +111,7 @@ export function useProjects(): UseProjectsResult {
 
   // WebSocket for live updates
   useEffect(() => {
-    let ws: WebSocket | null = null;
+    let ws: StreamSocket | null = null;
     let retryTimer: number | null = null;
     let retryAttempts = 0;
     let closed = false;
@@ -129,8 +130,7 @@ export function useProjects(): UseProjectsResult {
 
       const endpoint = '/api/projects/stream/ws';
       const fullEndpoint = getApiBaseUrlSync() + endpoint;
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      ws = new WebSocket(wsEndpoint);
+      ws = openStreamSocket(fullEndpoint);
 
       ws.onopen = () => {
         setIsConnected(true);
@@ -185,9 +185,12 @@ export function useProjects(): UseProjectsResult {
     };
   }, [applyProjectPatches]);
 
-  // Derive sorted projects list
+  // Derive sorted projects list; projects archived while connected stay in
+  // projectsById but are left out of the list
   const projects = useMemo(() => {
-    return Object.values(projectsById).sort(
+    return Object.values(projectsById)
+      .filter((project) => !project.archived_at)
+      .sort(
       (a, b) =>
         new Date(b.created_at as unknown as string).getTime() -
         new Date(a.created_at as unknown as string).getTime()
@@ -1,6 +1,7 @@
 import { useEffect, useState, useRef } from 'react';
 import type { ServerLogEntry } from 'shared/types';
 import { getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket, type StreamSocket } from '@/lib/streamSocket';
 
 interface UseServerLogStreamResult {
   logs: ServerLogEntry[];
@@ -8,25 +9,40 @@ interface UseServerLogStreamResult {
   isConnected: boolean;
 }
 
-export const useServerLogStream = (): UseServerLogStreamResult => {
+export interface ServerLogStreamFilter {
+  /** Minimum level, e.g. 'WARN' also includes errors */
+  level?: string;
+  /** Module path prefix, e.g. 'executors' */
+  target?: string;
+}
+
+export const useServerLogStream = (
+  filter: ServerLogStreamFilter = {}
+): UseServerLogStreamResult => {
+  const { level, target } = filter;
   const [logs, setLogs] = useState<ServerLogEntry[]>([]);
   const [error, setError] = useState<string | null>(null);
   const [isConnected, setIsConnected] = useState<boolean>(false);
-  const wsRef = useRef<WebSocket | null>(null);
+  const wsRef = useRef<StreamSocket | null>(null);
   const retryCountRef = useRef<number>(0);
   const retryTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const isIntentionallyClosed = useRef<boolean>(false);
 
   useEffect(() => {
     const open = () => {
-      const endpoint = '/api/server-logs/ws';
+      const params = new URLSearchParams();
+      if (level) params.set('level', level);
+      if (target) params.set('target', target);
+      const query = params.toString();
+      const endpoint = `/api/logs/stream${query ? `?${query}` : ''}`;
       const fullEndpoint = getApiBaseUrlSync() + endpoint;
-      const wsEndpoint = fullEndpoint.replace(/^http/, 'ws');
-      const ws = new WebSocket(wsEndpoint);
+      const ws = openStreamSocket(fullEndpoint);
       wsRef.current = ws;
       isIntentionallyClosed.current = false;
 
       ws.onopen = () => {
+        // The server replays matching history on every connection
+        setLogs([]);
         setError(null);
         setIsConnected(true);
         retryCountRef.current = 0;
@@ -73,7 +89,7 @@ export const useServerLogStream = (): UseServerLogStreamResult => {
         retryTimerRef.current = null;
       }
     };
-  }, []);
+  }, [level, target]);
 
   return { logs, error, isConnected };
 };
@@ -0,0 +1,46 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { taskLinksApi } from '@/lib/api';
+import { taskLinksKeys } from '@/lib/taskCacheHelpers';
+import type { CreateTaskLink, TaskLink, UpdateTaskLink } from 'shared/types';
+
+export function useTaskLinks(taskId?: string) {
+  return useQuery<TaskLink[]>({
+    queryKey: taskLinksKeys.byTask(taskId),
+    queryFn: () => taskLinksApi.list(taskId!),
+    enabled: !!taskId,
+    staleTime: 30_000,
+  });
+}
+
+type CreateLinkInput = { taskId: string; data: CreateTaskLink };
+type UpdateLinkInput = { taskId: string; linkId: string; data: UpdateTaskLink };
+type LinkInput = { taskId: string; linkId: string };
+
+export function useTaskLinkMutations() {
+  const queryClient = useQueryClient();
+  const invalidate = (taskId: string) =>
+    queryClient.invalidateQueries({ queryKey: taskLinksKeys.byTask(taskId) });
+
+  const createLink = useMutation<TaskLink, unknown, CreateLinkInput>({
+    mutationFn: ({ taskId, data }) => taskLinksApi.create(taskId, data),
+    onSuccess: (_link, { taskId }) => invalidate(taskId),
+  });
+
+  const updateLink = useMutation<TaskLink, unknown, UpdateLinkInput>({
+    mutationFn: ({ taskId, linkId, data }) =>
+      taskLinksApi.update(taskId, linkId, data),
+    onSuccess: (_link, { taskId }) => invalidate(taskId),
+  });
+
+  const deleteLink = useMutation<void, unknown, LinkInput>({
+    mutationFn: ({ taskId, linkId }) => taskLinksApi.delete(taskId, linkId),
+    onSuccess: (_data, { taskId }) => invalidate(taskId),
+  });
+
+  const refreshLink = useMutation<TaskLink, unknown, LinkInput>({
+    mutationFn: ({ taskId, linkId }) => taskLinksApi.refresh(taskId, linkId),
+    onSuccess: (_link, { taskId }) => invalidate(taskId),
+  });
+
+  return { createLink, updateLink, deleteLink, refreshLink };
+}
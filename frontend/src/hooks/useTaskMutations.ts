@@ -59,6 +59,7 @@ export function useTaskMutations(projectId?: string) {
         title: data.title,
         description: data.description ?? null,
         status: 'todo',
+        priority: data.priority ?? 'normal',
         parent_workspace_id: data.parent_workspace_id ?? null,
         shared_task_id: null,
         task_group_id: data.task_group_id ?? null,
@@ -70,6 +71,7 @@ export function useTaskMutations(projectId?: string) {
         is_queued: false,
         last_executor: '',
         needs_attention: null,
+        created_by: null,
       };
 
       // Snapshot for potential cleanup
@@ -129,6 +131,7 @@ export function useTaskMutations(projectId?: string) {
         title: taskData.title,
         description: taskData.description ?? null,
         status: 'inprogress',
+        priority: taskData.priority ?? 'normal',
         parent_workspace_id: taskData.parent_workspace_id ?? null,
         shared_task_id: null,
         task_group_id: taskData.task_group_id ?? null,
@@ -140,6 +143,7 @@ export function useTaskMutations(projectId?: string) {
         is_queued: false,
         last_executor: data.executor_profile_id?.executor ?? '',
         needs_attention: null,
+        created_by: null,
       };
 
       // Snapshot for potential cleanup
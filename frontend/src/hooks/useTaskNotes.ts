@@ -0,0 +1,90 @@
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { ApiError, taskNotesApi } from '@/lib/api';
+import { taskNotesKeys } from '@/lib/taskCacheHelpers';
+import { useDebouncedCallback } from './useDebouncedCallback';
+import type { TaskNotes } from 'shared/types';
+
+const AUTOSAVE_DELAY_MS = 800;
+
+export type TaskNotesSaveState = 'idle' | 'saving' | 'saved' | 'conflict';
+
+/**
+ * A task's review notes, saved automatically shortly after each edit.
+ * Saves carry the version they were based on; when someone else saved in
+ * the meantime the save is rejected and `saveState` becomes 'conflict'
+ * until `reload` replaces the local text with theirs.
+ */
+export function useTaskNotes(taskId: string) {
+  const queryClient = useQueryClient();
+  const query = useQuery<TaskNotes>({
+    queryKey: taskNotesKeys.byTask(taskId),
+    queryFn: () => taskNotesApi.get(taskId),
+  });
+
+  const [content, setContent] = useState('');
+  const [saveState, setSaveState] = useState<TaskNotesSaveState>('idle');
+  const [error, setError] = useState<string | null>(null);
+  const versionRef = useRef<number | null>(null);
+  const dirtyRef = useRef(false);
+
+  // Adopt the server's notes unless there are unsaved local edits
+  useEffect(() => {
+    if (!query.data || dirtyRef.current) return;
+    versionRef.current = query.data.version;
+    setContent(query.data.content);
+  }, [query.data]);
+
+  const save = useCallback(
+    async (text: string) => {
+      if (versionRef.current === null) return;
+      setSaveState('saving');
+      setError(null);
+      try {
+        const saved = await taskNotesApi.save(taskId, {
+          content: text,
+          version: versionRef.current,
+        });
+        versionRef.current = saved.version;
+        dirtyRef.current = false;
+        queryClient.setQueryData(taskNotesKeys.byTask(taskId), saved);
+        setSaveState('saved');
+      } catch (err) {
+        if (err instanceof ApiError && err.status === 409) {
+          setSaveState('conflict');
+        } else {
+          setSaveState('idle');
+          setError(err instanceof Error ? err.message : 'Failed to save');
+        }
+      }
+    },
+    [taskId, queryClient]
+  );
+
+  const { debounced: debouncedSave, cancel } = useDebouncedCallback(
+    save,
+    AUTOSAVE_DELAY_MS
+  );
+
+  const update = (text: string) => {
+    setContent(text);
+    dirtyRef.current = true;
+    if (saveState !== 'conflict') debouncedSave(text);
+  };
+
+  const reload = async () => {
+    cancel();
+    dirtyRef.current = false;
+    setSaveState('idle');
+    await query.refetch();
+  };
+
+  return {
+    content,
+    update,
+    reload,
+    saveState,
+    error,
+    isLoading: query.isLoading,
+  };
+}
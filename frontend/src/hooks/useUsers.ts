@@ -0,0 +1,72 @@
+import { useMutation, useQuery, useQueryClient } from '@tanstack/react-query';
+import { usersApi } from '@/lib/api';
+import type { CreateUser, SessionStatus, User } from 'shared/types';
+
+export const sessionKey = ['session'] as const;
+const usersKey = ['users'] as const;
+
+export function useSession() {
+  return useQuery<SessionStatus>({
+    queryKey: sessionKey,
+    queryFn: () => usersApi.getSession(),
+    staleTime: 5 * 60 * 1000,
+  });
+}
+
+export function useUsers() {
+  return useQuery<User[]>({
+    queryKey: usersKey,
+    queryFn: () => usersApi.list(),
+  });
+}
+
+/**
+ * Signing in or out changes what every other query returns (e.g. the
+ * config overlay), so all of them are refetched.
+ */
+export function useLogin() {
+  const queryClient = useQueryClient();
+
+  return useMutation<User, Error, { username: string; password: string }>({
+    mutationFn: ({ username, password }) => usersApi.login(username, password),
+    onSuccess: () => {
+      queryClient.invalidateQueries();
+    },
+  });
+}
+
+export function useLogout() {
+  const queryClient = useQueryClient();
+
+  return useMutation<void, Error, void>({
+    mutationFn: () => usersApi.logout(),
+    onSuccess: () => {
+      queryClient.invalidateQueries();
+    },
+  });
+}
+
+export function useCreateUser() {
+  const queryClient = useQueryClient();
+
+  return useMutation<User, Error, CreateUser>({
+    mutationFn: (data) => usersApi.create(data),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: usersKey });
+      // The first account turns sign-in on and signs its creator in
+      queryClient.invalidateQueries({ queryKey: sessionKey });
+    },
+  });
+}
+
+export function useDeleteUser() {
+  const queryClient = useQueryClient();
+
+  return useMutation<void, Error, string>({
+    mutationFn: (userId) => usersApi.delete(userId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: usersKey });
+      queryClient.invalidateQueries({ queryKey: sessionKey });
+    },
+  });
+}
@@ -12,12 +12,15 @@ import {
   BranchSyncStatus,
   CheckBranchSyncStatusRequest,
   Config,
+  ConfigCompatibilityReport,
   CreateFollowUpAttempt,
   EditorType,
   ExecutorProfileId,
   CreateGitHubPrRequest,
   CreateTask,
   CreateAndStartTaskRequest,
+  BulkCreateTasksRequest,
+  CreatedPlan,
   CreateTaskAttemptBody,
   CreateTag,
   CreateTaskGroup,
@@ -25,6 +28,11 @@ import {
   DirectoryEntry,
   ExecutionProcess,
   ExecutionProcessRepoState,
+  ExecutionProcessResources,
+  ExecutionProcessFailure,
+  ExecutionProcessRetry,
+  KillProcessRequest,
+  ReplayExecutionResponse,
   GanttTask,
   GitBranch,
   GitHubImportResponse,
@@ -33,16 +41,57 @@ import {
   ProjectWithTaskCounts,
   ProjectRepo,
   Repo,
+  RepoStats,
   RepoWithTargetBranch,
+  ResourceSample,
   CreateProject,
   CreateProjectRepo,
   UpdateProjectRepo,
+  UpdateRepoScripts,
+  UpdateRepoCheckoutOptions,
+  ProjectEnvFileResponse,
+  ProjectSecret,
+  ProjectToolRequirement,
+  CreateToolRequirement,
+  ToolchainReport,
+  BranchCleanupPolicy,
+  BranchCleanupReport,
+  RunBranchCleanupRequest,
+  UpdateBranchCleanupPolicy,
+  GitHooksPolicy,
+  UpdateGitHooksPolicy,
+  PrDraftPolicy,
+  UpdatePrDraftPolicy,
+  MergeCheckPolicy,
+  UpdateMergeCheckPolicy,
+  ProjectFileRule,
+  CreateFileRule,
+  PreCommitStatus,
+  UpsertProjectEnvFile,
+  UpsertProjectSecret,
+  CopyFilePreview,
   SearchResult,
+  SemanticSearchRequest,
+  SemanticSearchResponse,
+  TextSearchMatch,
+  TextSearchRequest,
   ShareTaskResponse,
   Task,
   TaskDependency,
   TaskGroup,
   TaskGroupWithStats,
+  TaskEnvVar,
+  TrashItem,
+  UpsertTaskEnvVar,
+  TaskLink,
+  TaskNotes,
+  UpdateTaskNotes,
+  TaskWatchers,
+  CreateTaskLink,
+  UpdateTaskLink,
+  TaskSchedule,
+  CreateTaskSchedule,
+  UpdateTaskSchedule,
   TaskRelationships,
   Tag,
   TagSearchParams,
@@ -52,10 +101,35 @@ import {
   UpdateTask,
   UpdateTag,
   UpdateTaskGroup,
+  UpdateWebhook,
+  CreateWebhook,
+  Webhook,
+  PromptTemplate,
+  CreatePromptTemplate,
+  ProjectTemplate,
+  UpsertProjectTemplate,
+  ProjectFromTemplate,
+  ImportProjectRequest,
+  ImportedProject,
+  UpdatePromptTemplate,
+  User,
+  CreateUser,
+  SessionStatus,
+  AuditLogEntry,
+  BackupInfo,
+  SecretsResponse,
+  BackupVerification,
+  RedactRequest,
+  RedactionReport,
+  ProjectPurgeReport,
+  RestoreReport,
+  AssetDirInfo,
+  RelocationReport,
   UserSystemInfo,
   McpServerQuery,
   UpdateMcpServersBody,
   GetMcpServerResponse,
+  ImageGcReport,
   ImageResponse,
   GitOperationError,
   ApprovalResponse,
@@ -69,6 +143,7 @@ import {
   UpdateCustomEditorRequest,
   CustomEditorResponse,
   ListCustomEditorsResponse,
+  AgentAuthStatus,
   AvailabilityInfo,
   BaseCodingAgent,
   RunAgentSetupRequest,
@@ -110,22 +185,48 @@ import {
   QueueStatus,
   QueueMergeRequest,
   QueueMergeError,
+  PermissionFinding,
+  AcknowledgePermissionFindingsResponse,
+  ReviewComment,
+  CreateReviewComment,
+  UpdateReviewComment,
+  SendReviewCommentsRequest,
   MergeQueueEntry,
   MergeQueueCountResponse,
+  MergeQueueRun,
+  Operation,
+  OperationKind,
+  OperationState,
+  QueuedMerge,
+  ReorderMergeQueueRequest,
+  ReorderConversationQueueRequest,
   FollowUpResult,
+  InterruptAndFollowUpRequest,
   PrCommentsResponse,
   NormalizedEntry,
   MergeTaskAttemptRequest,
+  MergeTaskAttemptResponse,
   PushTaskAttemptRequest,
   GenerateCommitMessageRequest,
   GenerateCommitMessageResponse,
   RepoBranchStatus,
   AbortConflictsRequest,
+  CherryPickOntoRequest,
+  CherryPickOntoResponse,
+  CherryPickRequest,
+  CherryPickResponse,
+  ConflictOp,
+  ConflictsResponse,
+  ResolveConflictRequest,
+  ContinueConflictsRequest,
   Session,
   Workspace,
   AvailableSoundsResponse,
+  MarkAllReadRequest,
+  MarkNotificationsReadRequest,
   Notification,
   NotificationStats,
+  NotificationType,
   UpdateNotification,
   ConversationSession,
   ConversationSessionStatus,
@@ -133,8 +234,16 @@ import {
   ConversationMessagesPage,
   ConversationWithMessages,
   SendMessageResponse,
+  ForkConversationRequest,
+  ForkConversationResponse,
   ProjectPrsResponse,
   ProjectWorktreesResponse,
+  ProjectDashboardResponse,
+  ProjectQueueEntry,
+  ReorderProjectQueueRequest,
+  UsageReport,
+  TaskTime,
+  ProjectTimeReport,
   RepoPrs,
   PrWithComments,
   PrUnresolvedCountsResponse,
@@ -147,6 +256,26 @@ import {
   UpdateNameRequest,
   PushBranchRequest,
   PushBranchError,
+  ServerLogEntry,
+  ProjectRedactionPatterns,
+  ProjectSandboxPolicy,
+  ProjectMemory,
+  ProjectMemorySettings,
+  UpdateProjectMemorySettings,
+  CreateProjectMemory,
+  UpdateProjectMemory,
+  SandboxPolicy,
+  ApprovalPolicy,
+  UpsertApprovalPolicy,
+  SandboxStatus,
+  UpdateRedactionPatterns,
+  BisectRun,
+  BisectRunDetails,
+  StartBisectRequest,
+  RepoWorktreeRepair,
+  ImportedWorkspace,
+  StageChangesRequest,
+  StagedChanges,
 } from 'shared/types';
 import type { WorkspaceWithSession } from 'shared/types';
 
@@ -190,6 +319,14 @@ export interface PaginatedGanttResponse {
   hasMore: boolean;
 }
 
+export interface DiffFileContent {
+  content: string | null;
+  totalBytes: number;
+  /** Only the first bytes of the file were downloaded */
+  truncated: boolean;
+  binary: boolean;
+}
+
 export type TaskDependencyTreeNode = {
   task: Task;
   dependencies: TaskDependencyTreeNode[];
@@ -370,8 +507,10 @@ export const handleApiResponse = async <T, E = T>(
 
 // Project Management APIs
 export const projectsApi = {
-  list: async (): Promise<ProjectWithTaskCounts[]> => {
-    const response = await makeRequest('/api/projects');
+  list: async (includeArchived = false): Promise<ProjectWithTaskCounts[]> => {
+    const response = await makeRequest(
+      `/api/projects?include_archived=${includeArchived}`
+    );
     return handleApiResponse<ProjectWithTaskCounts[]>(response);
   },
 
@@ -407,6 +546,52 @@ export const projectsApi = {
     return handleApiResponse<void>(response);
   },
 
+  // Deletes the project with all derived data, or only reports it
+  purge: async (
+    id: string,
+    dryRun: boolean = false
+  ): Promise<ProjectPurgeReport> => {
+    const response = await makeRequest(
+      `/api/projects/${id}/purge?dry_run=${dryRun}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<ProjectPurgeReport>(response);
+  },
+
+  archive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/archive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  unarchive: async (id: string): Promise<Project> => {
+    const response = await makeRequest(`/api/projects/${id}/unarchive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Project>(response);
+  },
+
+  export: async (id: string): Promise<Blob> => {
+    const response = await makeRequest(`/api/projects/${id}/export`);
+    if (!response.ok) {
+      throw new ApiError(
+        `Failed to export project (${response.status})`,
+        response.status,
+        response
+      );
+    }
+    return response.blob();
+  },
+
+  import: async (data: ImportProjectRequest): Promise<ImportedProject> => {
+    const response = await makeRequest('/api/projects/import', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ImportedProject>(response);
+  },
+
   openEditor: async (
     id: string,
     data: OpenEditorRequest
@@ -523,959 +708,2362 @@ export const projectsApi = {
     return handleApiResponse<ProjectRepo>(response);
   },
 
-  getPullRequests: async (projectId: string): Promise<ProjectPrsResponse> => {
-    const response = await makeRequest(`/api/projects/${projectId}/prs`);
-    return handleApiResponse<ProjectPrsResponse>(response);
+  previewCopyFiles: async (
+    projectId: string,
+    repoId: string,
+    copyFiles?: string
+  ): Promise<CopyFilePreview[]> => {
+    const query =
+      copyFiles !== undefined
+        ? `?copy_files=${encodeURIComponent(copyFiles)}`
+        : '';
+    const response = await makeRequest(
+      `/api/projects/${projectId}/repositories/${repoId}/copy-files/preview${query}`
+    );
+    return handleApiResponse<CopyFilePreview[]>(response);
   },
 
-  getPullRequestUnresolvedCounts: async (
-    projectId: string
-  ): Promise<PrUnresolvedCountsResponse> => {
+  getEnvFile: async (projectId: string): Promise<ProjectEnvFileResponse> => {
+    const response = await makeRequest(`/api/projects/${projectId}/env-file`);
+    return handleApiResponse<ProjectEnvFileResponse>(response);
+  },
+
+  updateEnvFile: async (
+    projectId: string,
+    data: UpsertProjectEnvFile
+  ): Promise<ProjectEnvFileResponse> => {
+    const response = await makeRequest(`/api/projects/${projectId}/env-file`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectEnvFileResponse>(response);
+  },
+
+  deleteEnvFile: async (projectId: string): Promise<void> => {
+    const response = await makeRequest(`/api/projects/${projectId}/env-file`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getSecrets: async (projectId: string): Promise<ProjectSecret[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/secrets`);
+    return handleApiResponse<ProjectSecret[]>(response);
+  },
+
+  upsertSecret: async (
+    projectId: string,
+    name: string,
+    data: UpsertProjectSecret
+  ): Promise<ProjectSecret> => {
     const response = await makeRequest(
-      `/api/projects/${projectId}/prs/unresolved-counts`
+      `/api/projects/${projectId}/secrets/${encodeURIComponent(name)}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
     );
-    return handleApiResponse<PrUnresolvedCountsResponse>(response);
+    return handleApiResponse<ProjectSecret>(response);
   },
 
-  getPrThreads: async (
+  deleteSecret: async (projectId: string, name: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/secrets/${encodeURIComponent(name)}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getToolchain: async (projectId: string): Promise<ProjectToolRequirement[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/toolchain`);
+    return handleApiResponse<ProjectToolRequirement[]>(response);
+  },
+
+  updateToolchain: async (
     projectId: string,
-    repoId: string,
-    prNumber: number
-  ): Promise<PrThreadsResponse> => {
+    data: CreateToolRequirement[]
+  ): Promise<ProjectToolRequirement[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/toolchain`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectToolRequirement[]>(response);
+  },
+
+  runToolchainDoctor: async (projectId: string): Promise<ToolchainReport> => {
     const response = await makeRequest(
-      `/api/projects/${projectId}/prs/${repoId}/${prNumber}/threads`
+      `/api/projects/${projectId}/toolchain/doctor`,
+      {
+        method: 'POST',
+      }
     );
-    return handleApiResponse<PrThreadsResponse>(response);
+    return handleApiResponse<ToolchainReport>(response);
   },
 
-  getMergeQueueCount: async (
+  getBranchCleanupPolicy: async (
     projectId: string
-  ): Promise<MergeQueueCountResponse> => {
+  ): Promise<BranchCleanupPolicy> => {
     const response = await makeRequest(
-      `/api/projects/${projectId}/merge-queue-count`
+      `/api/projects/${projectId}/branch-cleanup`
     );
-    return handleApiResponse<MergeQueueCountResponse>(response);
+    return handleApiResponse<BranchCleanupPolicy>(response);
   },
 
-  getWorkspaces: async (projectId: string): Promise<Workspace[]> => {
-    const response = await makeRequest(`/api/projects/${projectId}/workspaces`);
-    return handleApiResponse<Workspace[]>(response);
+  updateBranchCleanupPolicy: async (
+    projectId: string,
+    data: UpdateBranchCleanupPolicy
+  ): Promise<BranchCleanupPolicy> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/branch-cleanup`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<BranchCleanupPolicy>(response);
   },
 
-  getWorktrees: async (projectId: string): Promise<ProjectWorktreesResponse> => {
-    const response = await makeRequest(`/api/projects/${projectId}/worktrees`);
-    return handleApiResponse<ProjectWorktreesResponse>(response);
+  runBranchCleanup: async (
+    projectId: string,
+    data: RunBranchCleanupRequest
+  ): Promise<BranchCleanupReport> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/branch-cleanup/run`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<BranchCleanupReport>(response);
   },
-};
 
-// Gantt API
-export const ganttApi = {
-  list: async (
-    projectId: string,
-    params?: {
-      offset?: number;
-      limit?: number;
-    }
-  ): Promise<PaginatedGanttResponse> => {
-    const search = new URLSearchParams();
-    if (params?.offset !== undefined) {
-      search.set('offset', params.offset.toString());
-    }
-    if (params?.limit !== undefined) {
-      search.set('limit', params.limit.toString());
-    }
-    const queryString = search.toString();
-    const url = `/api/projects/${projectId}/gantt${queryString ? `?${queryString}` : ''}`;
-    const response = await makeRequest(url);
-    return handleApiResponse<PaginatedGanttResponse>(response);
+  getGitHooksPolicy: async (projectId: string): Promise<GitHooksPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/git-hooks`);
+    return handleApiResponse<GitHooksPolicy>(response);
   },
-};
 
-// Task Management APIs
-export const tasksApi = {
-  list: async (
+  updateGitHooksPolicy: async (
     projectId: string,
-    params?: {
-      offset?: number;
-      limit?: number;
-      status?: TaskStatus;
-      order_by?: 'created_at_asc' | 'created_at_desc' | 'updated_at_asc' | 'updated_at_desc';
-    }
-  ): Promise<PaginatedTasksResponse> => {
-    const search = new URLSearchParams({ project_id: projectId });
-    if (params?.offset !== undefined) {
-      search.set('offset', params.offset.toString());
-    }
-    if (params?.limit !== undefined) {
-      search.set('limit', params.limit.toString());
-    }
-    if (params?.status) {
-      search.set('status', params.status);
-    }
-    if (params?.order_by) {
-      search.set('order_by', params.order_by);
-    }
+    data: UpdateGitHooksPolicy
+  ): Promise<GitHooksPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/git-hooks`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<GitHooksPolicy>(response);
+  },
 
-    const response = await makeRequest(`/api/tasks?${search.toString()}`);
-    return handleApiResponse<PaginatedTasksResponse>(response);
+  getPreCommitStatus: async (projectId: string): Promise<PreCommitStatus> => {
+    const response = await makeRequest(`/api/projects/${projectId}/pre-commit`);
+    return handleApiResponse<PreCommitStatus>(response);
   },
-  getById: async (taskId: string): Promise<Task> => {
-    const response = await makeRequest(`/api/tasks/${taskId}`);
-    return handleApiResponse<Task>(response);
+
+  getPrDraftPolicy: async (projectId: string): Promise<PrDraftPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/pr-drafts`);
+    return handleApiResponse<PrDraftPolicy>(response);
   },
 
-  create: async (data: CreateTask): Promise<Task> => {
-    const response = await makeRequest(`/api/tasks`, {
-      method: 'POST',
+  updatePrDraftPolicy: async (
+    projectId: string,
+    data: UpdatePrDraftPolicy
+  ): Promise<PrDraftPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/pr-drafts`, {
+      method: 'PUT',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Task>(response);
+    return handleApiResponse<PrDraftPolicy>(response);
   },
 
-  createAndStart: async (
-    data: CreateAndStartTaskRequest
-  ): Promise<TaskWithAttemptStatus> => {
-    const response = await makeRequest(`/api/tasks/create-and-start`, {
-      method: 'POST',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<TaskWithAttemptStatus>(response);
+  getMergeCheckPolicy: async (projectId: string): Promise<MergeCheckPolicy> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/merge-checks`
+    );
+    return handleApiResponse<MergeCheckPolicy>(response);
   },
 
-  update: async (taskId: string, data: UpdateTask): Promise<Task> => {
-    const response = await makeRequest(`/api/tasks/${taskId}`, {
+  updateMergeCheckPolicy: async (
+    projectId: string,
+    data: UpdateMergeCheckPolicy
+  ): Promise<MergeCheckPolicy> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/merge-checks`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<MergeCheckPolicy>(response);
+  },
+
+  getFileRules: async (projectId: string): Promise<ProjectFileRule[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/file-rules`);
+    return handleApiResponse<ProjectFileRule[]>(response);
+  },
+
+  updateFileRules: async (
+    projectId: string,
+    rules: CreateFileRule[]
+  ): Promise<ProjectFileRule[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/file-rules`, {
       method: 'PUT',
-      body: JSON.stringify(data),
+      body: JSON.stringify(rules),
     });
-    return handleApiResponse<Task>(response);
+    return handleApiResponse<ProjectFileRule[]>(response);
   },
 
-  delete: async (taskId: string): Promise<void> => {
-    const response = await makeRequest(`/api/tasks/${taskId}`, {
-      method: 'DELETE',
-    });
-    return handleApiResponse<void>(response);
+  getSandboxPolicy: async (
+    projectId: string
+  ): Promise<ProjectSandboxPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/sandbox`);
+    return handleApiResponse<ProjectSandboxPolicy>(response);
   },
 
-  share: async (taskId: string): Promise<ShareTaskResponse> => {
-    const response = await makeRequest(`/api/tasks/${taskId}/share`, {
-      method: 'POST',
+  updateSandboxPolicy: async (
+    projectId: string,
+    data: SandboxPolicy
+  ): Promise<ProjectSandboxPolicy> => {
+    const response = await makeRequest(`/api/projects/${projectId}/sandbox`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
     });
-    return handleApiResponse<ShareTaskResponse>(response);
+    return handleApiResponse<ProjectSandboxPolicy>(response);
   },
 
-  reassign: async (
-    sharedTaskId: string,
-    data: { new_assignee_user_id: string | null }
-  ): Promise<SharedTaskResponse> => {
-    const payload = {
-      new_assignee_user_id: data.new_assignee_user_id,
-    };
+  getMemorySettings: async (
+    projectId: string
+  ): Promise<ProjectMemorySettings> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/memory/settings`
+    );
+    return handleApiResponse<ProjectMemorySettings>(response);
+  },
 
+  updateMemorySettings: async (
+    projectId: string,
+    data: UpdateProjectMemorySettings
+  ): Promise<ProjectMemorySettings> => {
     const response = await makeRequest(
-      `/api/shared-tasks/${sharedTaskId}/assign`,
+      `/api/projects/${projectId}/memory/settings`,
       {
-        method: 'POST',
-        body: JSON.stringify(payload),
+        method: 'PUT',
+        body: JSON.stringify(data),
       }
     );
-
-    return handleApiResponse<SharedTaskResponse>(response);
+    return handleApiResponse<ProjectMemorySettings>(response);
   },
 
-  unshare: async (sharedTaskId: string): Promise<void> => {
-    const response = await makeRequest(`/api/shared-tasks/${sharedTaskId}`, {
-      method: 'DELETE',
-    });
-    return handleApiResponse<void>(response);
+  getMemories: async (projectId: string): Promise<ProjectMemory[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/memories`);
+    return handleApiResponse<ProjectMemory[]>(response);
   },
 
-  linkToLocal: async (data: SharedTaskDetails): Promise<Task | null> => {
-    const response = await makeRequest(`/api/shared-tasks/link-to-local`, {
+  createMemory: async (
+    projectId: string,
+    data: CreateProjectMemory
+  ): Promise<ProjectMemory> => {
+    const response = await makeRequest(`/api/projects/${projectId}/memories`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Task | null>(response);
+    return handleApiResponse<ProjectMemory>(response);
   },
-};
 
-// Task Dependencies APIs
-export const taskDependenciesApi = {
-  getDependencies: async (
-    taskId: string,
-    direction?: DependencyDirection
-  ): Promise<Task[]> => {
-    const query = new URLSearchParams();
-    if (direction) {
-      query.set('direction', direction);
-    }
-    const suffix = query.toString();
+  updateMemory: async (
+    projectId: string,
+    memoryId: string,
+    data: UpdateProjectMemory
+  ): Promise<ProjectMemory> => {
     const response = await makeRequest(
-      `/api/tasks/${taskId}/dependencies${suffix ? `?${suffix}` : ''}`
-    );
-    return handleApiResponse<Task[]>(response);
-  },
-
-  addDependency: async (
-    taskId: string,
-    dependsOnId: string
-  ): Promise<TaskDependency> => {
-    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`, {
-      method: 'POST',
-      body: JSON.stringify({ depends_on_id: dependsOnId }),
-    });
-    return handleApiResponse<TaskDependency>(response);
-  },
-
-  removeDependency: async (
-    taskId: string,
-    dependsOnId: string
-  ): Promise<void> => {
-    const response = await makeRequest(
-      `/api/tasks/${taskId}/dependencies/${dependsOnId}`,
+      `/api/projects/${projectId}/memories/${memoryId}`,
       {
-        method: 'DELETE',
+        method: 'PUT',
+        body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponse<ProjectMemory>(response);
   },
 
-  getDependencyTree: async (
-    taskId: string,
-    maxDepth?: number
-  ): Promise<TaskDependencyTreeNode> => {
-    const query = new URLSearchParams();
-    if (maxDepth !== undefined) {
-      query.set('max_depth', maxDepth.toString());
-    }
-    const suffix = query.toString();
+  deleteMemory: async (projectId: string, memoryId: string): Promise<void> => {
     const response = await makeRequest(
-      `/api/tasks/${taskId}/dependency-tree${suffix ? `?${suffix}` : ''}`
+      `/api/projects/${projectId}/memories/${memoryId}`,
+      { method: 'DELETE' }
     );
-    return handleApiResponse<TaskDependencyTreeNode>(response);
+    return handleApiResponse<void>(response);
   },
-};
 
-// Task Groups API
-export const taskGroupsApi = {
-  getByProject: async (projectId: string): Promise<TaskGroup[]> => {
+  getRedactionPatterns: async (
+    projectId: string
+  ): Promise<ProjectRedactionPatterns> => {
     const response = await makeRequest(
-      `/api/task-groups?project_id=${encodeURIComponent(projectId)}`
+      `/api/projects/${projectId}/redaction-patterns`
     );
-    return handleApiResponse<TaskGroup[]>(response);
+    return handleApiResponse<ProjectRedactionPatterns>(response);
   },
 
-  getStatsForProject: async (projectId: string): Promise<TaskGroupWithStats[]> => {
+  updateRedactionPatterns: async (
+    projectId: string,
+    data: UpdateRedactionPatterns
+  ): Promise<ProjectRedactionPatterns> => {
     const response = await makeRequest(
-      `/api/task-groups/stats?project_id=${encodeURIComponent(projectId)}`
+      `/api/projects/${projectId}/redaction-patterns`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
     );
-    return handleApiResponse<TaskGroupWithStats[]>(response);
-  },
-
-  getById: async (groupId: string): Promise<TaskGroup> => {
-    const response = await makeRequest(`/api/task-groups/${groupId}`);
-    return handleApiResponse<TaskGroup>(response);
-  },
-
-  create: async (data: CreateTaskGroup): Promise<TaskGroup> => {
-    const response = await makeRequest('/api/task-groups', {
-      method: 'POST',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<TaskGroup>(response);
+    return handleApiResponse<ProjectRedactionPatterns>(response);
   },
 
-  update: async (groupId: string, data: UpdateTaskGroup): Promise<TaskGroup> => {
-    const response = await makeRequest(`/api/task-groups/${groupId}`, {
-      method: 'PUT',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<TaskGroup>(response);
-  },
-
-  delete: async (groupId: string): Promise<void> => {
-    const response = await makeRequest(`/api/task-groups/${groupId}`, {
-      method: 'DELETE',
-    });
-    return handleApiResponse<void>(response);
-  },
-
-  assignTasks: async (groupId: string, taskIds: string[]): Promise<void> => {
-    const response = await makeRequest(`/api/task-groups/${groupId}/assign`, {
-      method: 'POST',
-      body: JSON.stringify({ task_ids: taskIds }),
-    });
-    return handleApiResponse<void>(response);
-  },
-
-  merge: async (sourceGroupId: string, targetGroupId: string): Promise<TaskGroup> => {
-    const response = await makeRequest(`/api/task-groups/${sourceGroupId}/merge`, {
-      method: 'POST',
-      body: JSON.stringify({ target_group_id: targetGroupId }),
-    });
-    return handleApiResponse<TaskGroup>(response);
-  },
-
-  getMergeQueueCount: async (groupId: string): Promise<MergeQueueCountResponse> => {
+  getSandboxStatus: async (projectId: string): Promise<SandboxStatus> => {
     const response = await makeRequest(
-      `/api/task-groups/${groupId}/merge-queue-count`
+      `/api/projects/${projectId}/sandbox/status`
     );
-    return handleApiResponse<MergeQueueCountResponse>(response);
+    return handleApiResponse<SandboxStatus>(response);
   },
-};
 
-// Sessions API
-export const sessionsApi = {
-  getByWorkspace: async (workspaceId: string): Promise<Session[]> => {
+  getApprovalPolicies: async (projectId: string): Promise<ApprovalPolicy[]> => {
     const response = await makeRequest(
-      `/api/sessions?workspace_id=${workspaceId}`
+      `/api/projects/${projectId}/approval-policies`
     );
-    return handleApiResponse<Session[]>(response);
-  },
-
-  getById: async (sessionId: string): Promise<Session> => {
-    const response = await makeRequest(`/api/sessions/${sessionId}`);
-    return handleApiResponse<Session>(response);
-  },
-
-  create: async (data: {
-    workspace_id: string;
-    executor?: string;
-  }): Promise<Session> => {
-    const response = await makeRequest('/api/sessions', {
-      method: 'POST',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<Session>(response);
-  },
-
-  followUp: async (
-    sessionId: string,
-    data: CreateFollowUpAttempt
-  ): Promise<FollowUpResult> => {
-    const response = await makeRequest(`/api/sessions/${sessionId}/follow-up`, {
-      method: 'POST',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<FollowUpResult>(response);
+    return handleApiResponse<ApprovalPolicy[]>(response);
   },
-};
 
-// Task Attempts APIs
-export const attemptsApi = {
-  getChildren: async (attemptId: string): Promise<TaskRelationships> => {
+  upsertApprovalPolicy: async (
+    projectId: string,
+    data: UpsertApprovalPolicy
+  ): Promise<ApprovalPolicy> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/children`
+      `/api/projects/${projectId}/approval-policies`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
     );
-    return handleApiResponse<TaskRelationships>(response);
+    return handleApiResponse<ApprovalPolicy>(response);
   },
 
-  getAll: async (taskId: string): Promise<Workspace[]> => {
-    const response = await makeRequest(`/api/task-attempts?task_id=${taskId}`);
-    return handleApiResponse<Workspace[]>(response);
+  deleteApprovalPolicy: async (
+    projectId: string,
+    policyId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/approval-policies/${policyId}`,
+      { method: 'DELETE' }
+    );
+    return handleApiResponse<void>(response);
   },
 
-  get: async (attemptId: string): Promise<Workspace> => {
-    const response = await makeRequest(`/api/task-attempts/${attemptId}`);
-    return handleApiResponse<Workspace>(response);
+  getPullRequests: async (projectId: string): Promise<ProjectPrsResponse> => {
+    const response = await makeRequest(`/api/projects/${projectId}/prs`);
+    return handleApiResponse<ProjectPrsResponse>(response);
   },
 
-  /** Get workspace with latest session */
-  getWithSession: async (attemptId: string): Promise<WorkspaceWithSession> => {
+  getPullRequestUnresolvedCounts: async (
+    projectId: string
+  ): Promise<PrUnresolvedCountsResponse> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/with-session`
+      `/api/projects/${projectId}/prs/unresolved-counts`
     );
-    return handleApiResponse<WorkspaceWithSession>(response);
+    return handleApiResponse<PrUnresolvedCountsResponse>(response);
   },
 
-  create: async (data: CreateTaskAttemptBody): Promise<Workspace> => {
-    const response = await makeRequest(`/api/task-attempts`, {
-      method: 'POST',
-      body: JSON.stringify(data),
-    });
-    return handleApiResponse<Workspace>(response);
+  getPrThreads: async (
+    projectId: string,
+    repoId: string,
+    prNumber: number
+  ): Promise<PrThreadsResponse> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/prs/${repoId}/${prNumber}/threads`
+    );
+    return handleApiResponse<PrThreadsResponse>(response);
   },
 
-  stop: async (attemptId: string): Promise<void> => {
-    const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
-      method: 'POST',
-    });
-    return handleApiResponse<void>(response);
+  getMergeQueueCount: async (
+    projectId: string
+  ): Promise<MergeQueueCountResponse> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/merge-queue-count`
+    );
+    return handleApiResponse<MergeQueueCountResponse>(response);
   },
 
-  runAgentSetup: async (
-    attemptId: string,
-    data: RunAgentSetupRequest
-  ): Promise<RunAgentSetupResponse> => {
+  getMergeQueue: async (projectId: string): Promise<QueuedMerge[]> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/run-agent-setup`,
-      {
-        method: 'POST',
-        body: JSON.stringify(data),
-      }
+      `/api/projects/${projectId}/merge-queue`
     );
-    return handleApiResponse<RunAgentSetupResponse>(response);
+    return handleApiResponse<QueuedMerge[]>(response);
   },
 
-  openEditor: async (
-    attemptId: string,
-    data: OpenEditorRequest
-  ): Promise<OpenEditorResponse> => {
+  reorderMergeQueue: async (
+    projectId: string,
+    data: ReorderMergeQueueRequest
+  ): Promise<QueuedMerge[]> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/open-editor`,
+      `/api/projects/${projectId}/merge-queue`,
       {
-        method: 'POST',
+        method: 'PUT',
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<OpenEditorResponse>(response);
-  },
-
-  getBranchStatus: async (attemptId: string): Promise<RepoBranchStatus[]> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/branch-status`
-    );
-    return handleApiResponse<RepoBranchStatus[]>(response);
-  },
-
-  getRepos: async (attemptId: string): Promise<RepoWithTargetBranch[]> => {
-    const response = await makeRequest(`/api/task-attempts/${attemptId}/repos`);
-    return handleApiResponse<RepoWithTargetBranch[]>(response);
+    return handleApiResponse<QueuedMerge[]>(response);
   },
 
-  merge: async (
-    attemptId: string,
-    data: MergeTaskAttemptRequest
+  cancelMergeQueueEntry: async (
+    projectId: string,
+    entryId: string
   ): Promise<void> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/merge`,
-      {
-        method: 'POST',
-        body: JSON.stringify(data),
-      }
+      `/api/projects/${projectId}/merge-queue/${entryId}`,
+      { method: 'DELETE' }
     );
     return handleApiResponse<void>(response);
   },
 
-  generateCommitMessage: async (
-    attemptId: string,
-    data: GenerateCommitMessageRequest
-  ): Promise<GenerateCommitMessageResponse> => {
+  getMergeQueueRuns: async (projectId: string): Promise<MergeQueueRun[]> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/generate-commit-message`,
-      {
-        method: 'POST',
-        body: JSON.stringify(data),
-      }
+      `/api/projects/${projectId}/merge-queue/runs`
     );
-    return handleApiResponse<GenerateCommitMessageResponse>(response);
+    return handleApiResponse<MergeQueueRun[]>(response);
   },
 
-  push: async (
-    attemptId: string,
-    data: PushTaskAttemptRequest
-  ): Promise<Result<void, PushError>> => {
-    const response = await makeRequest(`/api/task-attempts/${attemptId}/push`, {
-      method: 'POST',
+  getQueue: async (projectId: string): Promise<ProjectQueueEntry[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/queue`);
+    return handleApiResponse<ProjectQueueEntry[]>(response);
+  },
+
+  reorderQueue: async (
+    projectId: string,
+    data: ReorderProjectQueueRequest
+  ): Promise<ProjectQueueEntry[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/queue`, {
+      method: 'PUT',
       body: JSON.stringify(data),
     });
-    return handleApiResponseAsResult<void, PushError>(response);
+    return handleApiResponse<ProjectQueueEntry[]>(response);
   },
 
-  forcePush: async (
-    attemptId: string,
-    data: PushTaskAttemptRequest
+  getWorkspaces: async (projectId: string): Promise<Workspace[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/workspaces`);
+    return handleApiResponse<Workspace[]>(response);
+  },
+
+  getWorktrees: async (projectId: string): Promise<ProjectWorktreesResponse> => {
+    const response = await makeRequest(`/api/projects/${projectId}/worktrees`);
+    return handleApiResponse<ProjectWorktreesResponse>(response);
+  },
+
+  getDashboard: async (
+    projectId: string
+  ): Promise<ProjectDashboardResponse> => {
+    const response = await makeRequest(`/api/projects/${projectId}/dashboard`);
+    return handleApiResponse<ProjectDashboardResponse>(response);
+  },
+};
+
+// Gantt API
+export const ganttApi = {
+  list: async (
+    projectId: string,
+    params?: {
+      offset?: number;
+      limit?: number;
+    }
+  ): Promise<PaginatedGanttResponse> => {
+    const search = new URLSearchParams();
+    if (params?.offset !== undefined) {
+      search.set('offset', params.offset.toString());
+    }
+    if (params?.limit !== undefined) {
+      search.set('limit', params.limit.toString());
+    }
+    const queryString = search.toString();
+    const url = `/api/projects/${projectId}/gantt${queryString ? `?${queryString}` : ''}`;
+    const response = await makeRequest(url);
+    return handleApiResponse<PaginatedGanttResponse>(response);
+  },
+};
+
+// Task Management APIs
+export const tasksApi = {
+  list: async (
+    projectId: string,
+    params?: {
+      offset?: number;
+      limit?: number;
+      status?: TaskStatus;
+      order_by?: 'created_at_asc' | 'created_at_desc' | 'updated_at_asc' | 'updated_at_desc';
+      include_archived?: boolean;
+    }
+  ): Promise<PaginatedTasksResponse> => {
+    const search = new URLSearchParams({ project_id: projectId });
+    if (params?.offset !== undefined) {
+      search.set('offset', params.offset.toString());
+    }
+    if (params?.limit !== undefined) {
+      search.set('limit', params.limit.toString());
+    }
+    if (params?.status) {
+      search.set('status', params.status);
+    }
+    if (params?.order_by) {
+      search.set('order_by', params.order_by);
+    }
+    if (params?.include_archived) {
+      search.set('include_archived', 'true');
+    }
+
+    const response = await makeRequest(`/api/tasks?${search.toString()}`);
+    return handleApiResponse<PaginatedTasksResponse>(response);
+  },
+  getById: async (taskId: string): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}`);
+    return handleApiResponse<Task>(response);
+  },
+
+  create: async (data: CreateTask): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  createAndStart: async (
+    data: CreateAndStartTaskRequest
+  ): Promise<TaskWithAttemptStatus> => {
+    const response = await makeRequest(`/api/tasks/create-and-start`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskWithAttemptStatus>(response);
+  },
+
+  bulkCreate: async (
+    projectId: string,
+    data: BulkCreateTasksRequest
+  ): Promise<CreatedPlan> => {
+    const response = await makeRequest(`/api/projects/${projectId}/tasks/bulk`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CreatedPlan>(response);
+  },
+
+  update: async (taskId: string, data: UpdateTask): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  delete: async (taskId: string): Promise<void> => {
+    const response = await makeRequest(`/api/tasks/${taskId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  archive: async (taskId: string): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/archive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  unarchive: async (taskId: string): Promise<Task> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/unarchive`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Task>(response);
+  },
+
+  share: async (taskId: string): Promise<ShareTaskResponse> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/share`, {
+      method: 'POST',
+    });
+    return handleApiResponse<ShareTaskResponse>(response);
+  },
+
+  reassign: async (
+    sharedTaskId: string,
+    data: { new_assignee_user_id: string | null }
+  ): Promise<SharedTaskResponse> => {
+    const payload = {
+      new_assignee_user_id: data.new_assignee_user_id,
+    };
+
+    const response = await makeRequest(
+      `/api/shared-tasks/${sharedTaskId}/assign`,
+      {
+        method: 'POST',
+        body: JSON.stringify(payload),
+      }
+    );
+
+    return handleApiResponse<SharedTaskResponse>(response);
+  },
+
+  unshare: async (sharedTaskId: string): Promise<void> => {
+    const response = await makeRequest(`/api/shared-tasks/${sharedTaskId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  linkToLocal: async (data: SharedTaskDetails): Promise<Task | null> => {
+    const response = await makeRequest(`/api/shared-tasks/link-to-local`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Task | null>(response);
+  },
+};
+
+// Task Dependencies APIs
+export const taskDependenciesApi = {
+  getDependencies: async (
+    taskId: string,
+    direction?: DependencyDirection
+  ): Promise<Task[]> => {
+    const query = new URLSearchParams();
+    if (direction) {
+      query.set('direction', direction);
+    }
+    const suffix = query.toString();
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/dependencies${suffix ? `?${suffix}` : ''}`
+    );
+    return handleApiResponse<Task[]>(response);
+  },
+
+  addDependency: async (
+    taskId: string,
+    dependsOnId: string
+  ): Promise<TaskDependency> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/dependencies`, {
+      method: 'POST',
+      body: JSON.stringify({ depends_on_id: dependsOnId }),
+    });
+    return handleApiResponse<TaskDependency>(response);
+  },
+
+  removeDependency: async (
+    taskId: string,
+    dependsOnId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/dependencies/${dependsOnId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getDependencyTree: async (
+    taskId: string,
+    maxDepth?: number
+  ): Promise<TaskDependencyTreeNode> => {
+    const query = new URLSearchParams();
+    if (maxDepth !== undefined) {
+      query.set('max_depth', maxDepth.toString());
+    }
+    const suffix = query.toString();
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/dependency-tree${suffix ? `?${suffix}` : ''}`
+    );
+    return handleApiResponse<TaskDependencyTreeNode>(response);
+  },
+};
+
+// Trash APIs: deleted tasks and projects until they are purged
+export const trashApi = {
+  list: async (): Promise<TrashItem[]> => {
+    const response = await makeRequest('/api/trash');
+    return handleApiResponse<TrashItem[]>(response);
+  },
+
+  restoreProject: async (projectId: string): Promise<Project> => {
+    const response = await makeRequest(
+      `/api/trash/projects/${projectId}/restore`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<Project>(response);
+  },
+
+  restoreTask: async (taskId: string): Promise<Task> => {
+    const response = await makeRequest(`/api/trash/tasks/${taskId}/restore`, {
+      method: 'POST',
+    });
+    return handleApiResponse<Task>(response);
+  },
+};
+
+// Task env var APIs; secret values come back masked
+export const taskEnvApi = {
+  list: async (taskId: string): Promise<TaskEnvVar[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/env`);
+    return handleApiResponse<TaskEnvVar[]>(response);
+  },
+
+  upsert: async (
+    taskId: string,
+    name: string,
+    data: UpsertTaskEnvVar
+  ): Promise<TaskEnvVar> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/env/${encodeURIComponent(name)}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskEnvVar>(response);
+  },
+
+  delete: async (taskId: string, name: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/env/${encodeURIComponent(name)}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Task Links APIs
+export const taskLinksApi = {
+  list: async (taskId: string): Promise<TaskLink[]> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/links`);
+    return handleApiResponse<TaskLink[]>(response);
+  },
+
+  create: async (taskId: string, data: CreateTaskLink): Promise<TaskLink> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/links`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskLink>(response);
+  },
+
+  update: async (
+    taskId: string,
+    linkId: string,
+    data: UpdateTaskLink
+  ): Promise<TaskLink> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/links/${linkId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskLink>(response);
+  },
+
+  delete: async (taskId: string, linkId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/links/${linkId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  refresh: async (taskId: string, linkId: string): Promise<TaskLink> => {
+    const response = await makeRequest(
+      `/api/tasks/${taskId}/links/${linkId}/refresh`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<TaskLink>(response);
+  },
+};
+
+// Task Schedules APIs
+export const taskSchedulesApi = {
+  list: async (projectId: string): Promise<TaskSchedule[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/schedules`);
+    return handleApiResponse<TaskSchedule[]>(response);
+  },
+
+  create: async (
+    projectId: string,
+    data: CreateTaskSchedule
+  ): Promise<TaskSchedule> => {
+    const response = await makeRequest(`/api/projects/${projectId}/schedules`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskSchedule>(response);
+  },
+
+  update: async (
+    projectId: string,
+    scheduleId: string,
+    data: UpdateTaskSchedule
+  ): Promise<TaskSchedule> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/schedules/${scheduleId}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<TaskSchedule>(response);
+  },
+
+  delete: async (projectId: string, scheduleId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/schedules/${scheduleId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  runNow: async (projectId: string, scheduleId: string): Promise<Task> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/schedules/${scheduleId}/run`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<Task>(response);
+  },
+};
+
+// Task Watchers API
+export const taskWatchersApi = {
+  get: async (taskId: string): Promise<TaskWatchers> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/watchers`);
+    return handleApiResponse<TaskWatchers>(response);
+  },
+
+  watch: async (taskId: string): Promise<TaskWatchers> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/watch`, {
+      method: 'PUT',
+    });
+    return handleApiResponse<TaskWatchers>(response);
+  },
+
+  unwatch: async (taskId: string): Promise<TaskWatchers> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/watch`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<TaskWatchers>(response);
+  },
+};
+
+// Task Notes API
+export const taskNotesApi = {
+  get: async (taskId: string): Promise<TaskNotes> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/notes`);
+    return handleApiResponse<TaskNotes>(response);
+  },
+
+  /** Fails with status 409 when the notes were saved elsewhere first */
+  save: async (taskId: string, data: UpdateTaskNotes): Promise<TaskNotes> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/notes`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskNotes>(response);
+  },
+
+  clear: async (taskId: string): Promise<TaskNotes> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/notes`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<TaskNotes>(response);
+  },
+};
+
+// Task Groups API
+export const taskGroupsApi = {
+  getByProject: async (projectId: string): Promise<TaskGroup[]> => {
+    const response = await makeRequest(
+      `/api/task-groups?project_id=${encodeURIComponent(projectId)}`
+    );
+    return handleApiResponse<TaskGroup[]>(response);
+  },
+
+  getStatsForProject: async (projectId: string): Promise<TaskGroupWithStats[]> => {
+    const response = await makeRequest(
+      `/api/task-groups/stats?project_id=${encodeURIComponent(projectId)}`
+    );
+    return handleApiResponse<TaskGroupWithStats[]>(response);
+  },
+
+  getById: async (groupId: string): Promise<TaskGroup> => {
+    const response = await makeRequest(`/api/task-groups/${groupId}`);
+    return handleApiResponse<TaskGroup>(response);
+  },
+
+  create: async (data: CreateTaskGroup): Promise<TaskGroup> => {
+    const response = await makeRequest('/api/task-groups', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskGroup>(response);
+  },
+
+  update: async (groupId: string, data: UpdateTaskGroup): Promise<TaskGroup> => {
+    const response = await makeRequest(`/api/task-groups/${groupId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskGroup>(response);
+  },
+
+  delete: async (groupId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-groups/${groupId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  assignTasks: async (groupId: string, taskIds: string[]): Promise<void> => {
+    const response = await makeRequest(`/api/task-groups/${groupId}/assign`, {
+      method: 'POST',
+      body: JSON.stringify({ task_ids: taskIds }),
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  merge: async (sourceGroupId: string, targetGroupId: string): Promise<TaskGroup> => {
+    const response = await makeRequest(`/api/task-groups/${sourceGroupId}/merge`, {
+      method: 'POST',
+      body: JSON.stringify({ target_group_id: targetGroupId }),
+    });
+    return handleApiResponse<TaskGroup>(response);
+  },
+
+  getMergeQueueCount: async (groupId: string): Promise<MergeQueueCountResponse> => {
+    const response = await makeRequest(
+      `/api/task-groups/${groupId}/merge-queue-count`
+    );
+    return handleApiResponse<MergeQueueCountResponse>(response);
+  },
+};
+
+// Sessions API
+export const sessionsApi = {
+  getByWorkspace: async (workspaceId: string): Promise<Session[]> => {
+    const response = await makeRequest(
+      `/api/sessions?workspace_id=${workspaceId}`
+    );
+    return handleApiResponse<Session[]>(response);
+  },
+
+  getById: async (sessionId: string): Promise<Session> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}`);
+    return handleApiResponse<Session>(response);
+  },
+
+  create: async (data: {
+    workspace_id: string;
+    executor?: string;
+  }): Promise<Session> => {
+    const response = await makeRequest('/api/sessions', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Session>(response);
+  },
+
+  followUp: async (
+    sessionId: string,
+    data: CreateFollowUpAttempt
+  ): Promise<FollowUpResult> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/follow-up`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<FollowUpResult>(response);
+  },
+
+  interruptAndFollowUp: async (
+    sessionId: string,
+    data: InterruptAndFollowUpRequest
+  ): Promise<FollowUpResult> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/interrupt`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<FollowUpResult>(response);
+  },
+
+  getRetries: async (sessionId: string): Promise<ExecutionProcessRetry[]> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/retries`);
+    return handleApiResponse<ExecutionProcessRetry[]>(response);
+  },
+
+  getFailures: async (
+    sessionId: string
+  ): Promise<ExecutionProcessFailure[]> => {
+    const response = await makeRequest(`/api/sessions/${sessionId}/failures`);
+    return handleApiResponse<ExecutionProcessFailure[]>(response);
+  },
+};
+
+// Task Attempts APIs
+export const attemptsApi = {
+  getChildren: async (attemptId: string): Promise<TaskRelationships> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/children`
+    );
+    return handleApiResponse<TaskRelationships>(response);
+  },
+
+  getAll: async (taskId: string): Promise<Workspace[]> => {
+    const response = await makeRequest(`/api/task-attempts?task_id=${taskId}`);
+    return handleApiResponse<Workspace[]>(response);
+  },
+
+  get: async (attemptId: string): Promise<Workspace> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}`);
+    return handleApiResponse<Workspace>(response);
+  },
+
+  /** Get workspace with latest session */
+  getWithSession: async (attemptId: string): Promise<WorkspaceWithSession> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/with-session`
+    );
+    return handleApiResponse<WorkspaceWithSession>(response);
+  },
+
+  create: async (data: CreateTaskAttemptBody): Promise<Workspace> => {
+    const response = await makeRequest(`/api/task-attempts`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Workspace>(response);
+  },
+
+  stop: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  repair: async (attemptId: string): Promise<RepoWorktreeRepair[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/repair`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<RepoWorktreeRepair[]>(response);
+  },
+
+  runAgentSetup: async (
+    attemptId: string,
+    data: RunAgentSetupRequest
+  ): Promise<RunAgentSetupResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/run-agent-setup`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<RunAgentSetupResponse>(response);
+  },
+
+  openEditor: async (
+    attemptId: string,
+    data: OpenEditorRequest
+  ): Promise<OpenEditorResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/open-editor`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<OpenEditorResponse>(response);
+  },
+
+  /**
+   * One side of a changed file, as listed by the diff stream. Only the first
+   * `maxBytes` are downloaded; `content` is null when the file does not exist
+   * on that side.
+   */
+  getDiffFileContent: async (
+    attemptId: string,
+    path: string,
+    side: 'old' | 'new',
+    maxBytes: number
+  ): Promise<DiffFileContent> => {
+    const params = new URLSearchParams({ path, side });
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/diff/content?${params.toString()}`,
+      { headers: { Range: `bytes=0-${maxBytes - 1}` } }
+    );
+    if (response.status === 404) {
+      return { content: null, totalBytes: 0, truncated: false, binary: false };
+    }
+    if (!response.ok) {
+      throw new ApiError(
+        `Failed to load ${path} (${response.status})`,
+        response.status,
+        response
+      );
+    }
+    const content = await response.text();
+    // Content-Range is "bytes start-end/total" for partial responses
+    const total = response.headers.get('Content-Range')?.split('/')[1];
+    const totalBytes = total ? Number(total) : content.length;
+    return {
+      content,
+      totalBytes,
+      truncated: response.status === 206 && totalBytes > maxBytes,
+      binary: (response.headers.get('Content-Type') ?? '').startsWith(
+        'application/octet-stream'
+      ),
+    };
+  },
+
+  getBranchStatus: async (attemptId: string): Promise<RepoBranchStatus[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/branch-status`
+    );
+    return handleApiResponse<RepoBranchStatus[]>(response);
+  },
+
+  getRepos: async (attemptId: string): Promise<RepoWithTargetBranch[]> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/repos`);
+    return handleApiResponse<RepoWithTargetBranch[]>(response);
+  },
+
+  merge: async (
+    attemptId: string,
+    data: MergeTaskAttemptRequest
+  ): Promise<MergeTaskAttemptResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/merge`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<MergeTaskAttemptResponse>(response);
+  },
+
+  generateCommitMessage: async (
+    attemptId: string,
+    data: GenerateCommitMessageRequest
+  ): Promise<GenerateCommitMessageResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/generate-commit-message`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<GenerateCommitMessageResponse>(response);
+  },
+
+  push: async (
+    attemptId: string,
+    data: PushTaskAttemptRequest
+  ): Promise<Result<void, PushError>> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/push`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponseAsResult<void, PushError>(response);
+  },
+
+  forcePush: async (
+    attemptId: string,
+    data: PushTaskAttemptRequest
   ): Promise<Result<void, PushError>> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/push/force`,
+      `/api/task-attempts/${attemptId}/push/force`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, PushError>(response);
+  },
+
+  rebase: async (
+    attemptId: string,
+    data: RebaseTaskAttemptRequest
+  ): Promise<Result<void, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/rebase`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<void, GitOperationError>(response);
+  },
+
+  change_target_branch: async (
+    attemptId: string,
+    data: ChangeTargetBranchRequest
+  ): Promise<ChangeTargetBranchResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/change-target-branch`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ChangeTargetBranchResponse>(response);
+  },
+
+  renameBranch: async (
+    attemptId: string,
+    newBranchName: string
+  ): Promise<RenameBranchResponse> => {
+    const payload: RenameBranchRequest = {
+      new_branch_name: newBranchName,
+    };
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/rename-branch`,
+      {
+        method: 'POST',
+        body: JSON.stringify(payload),
+      }
+    );
+    return handleApiResponse<RenameBranchResponse>(response);
+  },
+
+  abortConflicts: async (
+    attemptId: string,
+    data: AbortConflictsRequest
+  ): Promise<ConflictOp | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/abort`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ConflictOp | null>(response);
+  },
+
+  cherryPick: async (
+    attemptId: string,
+    data: CherryPickRequest
+  ): Promise<Result<CherryPickResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/cherry-pick`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<CherryPickResponse, GitOperationError>(
+      response
+    );
+  },
+
+  cherryPickOnto: async (
+    attemptId: string,
+    data: CherryPickOntoRequest
+  ): Promise<Result<CherryPickOntoResponse, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/cherry-pick-onto`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<
+      CherryPickOntoResponse,
+      GitOperationError
+    >(response);
+  },
+
+  getConflicts: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<ConflictsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<ConflictsResponse>(response);
+  },
+
+  resolveConflict: async (
+    attemptId: string,
+    data: ResolveConflictRequest
+  ): Promise<ConflictsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ConflictsResponse>(response);
+  },
+
+  continueConflicts: async (
+    attemptId: string,
+    data: ContinueConflictsRequest
+  ): Promise<ConflictsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/continue`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ConflictsResponse>(response);
+  },
+
+  createPR: async (
+    attemptId: string,
+    data: CreateGitHubPrRequest
+  ): Promise<Result<string, CreatePrError>> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pr`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponseAsResult<string, CreatePrError>(response);
+  },
+
+  startDevServer: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/start-dev-server`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  setupGhCli: async (attemptId: string): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/gh-cli-setup`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<ExecutionProcess, GhCliSetupError>(response);
+  },
+
+  runSetupScript: async (
+    attemptId: string
+  ): Promise<Result<ExecutionProcess, RunScriptError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/run-setup-script`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponseAsResult<ExecutionProcess, RunScriptError>(
+      response
+    );
+  },
+
+  runCleanupScript: async (
+    attemptId: string
+  ): Promise<Result<ExecutionProcess, RunScriptError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/run-cleanup-script`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponseAsResult<ExecutionProcess, RunScriptError>(
+      response
+    );
+  },
+
+  getPrComments: async (
+    attemptId: string,
+    repoId: string
+  ): Promise<PrCommentsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/comments?repo_id=${encodeURIComponent(repoId)}`
+    );
+    return handleApiResponse<PrCommentsResponse>(response);
+  },
+
+  queueMerge: async (
+    attemptId: string,
+    data: QueueMergeRequest
+  ): Promise<Result<MergeQueueEntry, QueueMergeError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/queue-merge`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponseAsResult<MergeQueueEntry, QueueMergeError>(response);
+  },
+
+  cancelQueuedMerge: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/queue-merge`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  cancelQueuedExecution: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/execution-queue`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getQueueStatus: async (attemptId: string): Promise<MergeQueueEntry | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/queue-status`
+    );
+    return handleApiResponse<MergeQueueEntry | null>(response);
+  },
+
+  getPermissionFindings: async (
+    attemptId: string
+  ): Promise<PermissionFinding[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/permission-findings`
+    );
+    return handleApiResponse<PermissionFinding[]>(response);
+  },
+
+  acknowledgePermissionFindings: async (
+    attemptId: string
+  ): Promise<AcknowledgePermissionFindingsResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/permission-findings/acknowledge`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<AcknowledgePermissionFindingsResponse>(response);
+  },
+
+  getReviewComments: async (attemptId: string): Promise<ReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`
+    );
+    return handleApiResponse<ReviewComment[]>(response);
+  },
+
+  createReviewComment: async (
+    attemptId: string,
+    data: CreateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  updateReviewComment: async (
+    attemptId: string,
+    commentId: string,
+    data: UpdateReviewComment
+  ): Promise<ReviewComment> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'PATCH',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ReviewComment>(response);
+  },
+
+  deleteReviewComment: async (
+    attemptId: string,
+    commentId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/${commentId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  sendReviewComments: async (
+    attemptId: string,
+    data: SendReviewCommentsRequest
+  ): Promise<FollowUpResult> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/review-comments/send`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<FollowUpResult>(response);
+  },
+
+  /** The attempt as a gzipped tarball, for importing on another machine */
+  exportBundle: async (attemptId: string): Promise<Blob> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/bundle`
+    );
+    if (!response.ok) {
+      throw new ApiError(
+        `Failed to export attempt (${response.status})`,
+        response.status,
+        response
+      );
+    }
+    return response.blob();
+  },
+
+  importBundle: async (
+    projectId: string,
+    bundle: Blob
+  ): Promise<ImportedWorkspace> => {
+    const response = await makeRequest(
+      `/api/task-attempts/import?project_id=${encodeURIComponent(projectId)}`,
+      {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/gzip' },
+        body: bundle,
+      }
+    );
+    return handleApiResponse<ImportedWorkspace>(response);
+  },
+
+  stageChanges: async (
+    attemptId: string,
+    data: StageChangesRequest
+  ): Promise<StagedChanges> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/stage`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<StagedChanges>(response);
+  },
+
+  unstageChanges: async (attemptId: string, repoId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/stage?repo_id=${encodeURIComponent(repoId)}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+};
+
+// Execution Process APIs
+export const executionProcessesApi = {
+  getDetails: async (processId: string): Promise<ExecutionProcess> => {
+    const response = await makeRequest(`/api/execution-processes/${processId}`);
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  getRepoStates: async (
+    processId: string
+  ): Promise<ExecutionProcessRepoState[]> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/repo-states`
+    );
+    return handleApiResponse<ExecutionProcessRepoState[]>(response);
+  },
+
+  getFailure: async (
+    processId: string
+  ): Promise<ExecutionProcessFailure | null> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/failure`
+    );
+    return handleApiResponse<ExecutionProcessFailure | null>(response);
+  },
+
+  getNormalizedEntries: async (
+    processId: string,
+    params?: { beforeIndex?: number; limit?: number }
+  ): Promise<ExecutionProcessNormalizedEntriesPage> => {
+    const query = new URLSearchParams();
+    if (params?.beforeIndex !== undefined) {
+      query.set('before_index', String(params.beforeIndex));
+    }
+    if (params?.limit !== undefined) {
+      query.set('limit', String(params.limit));
+    }
+    const suffix = query.toString();
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/normalized-entries${
+        suffix ? `?${suffix}` : ''
+      }`
+    );
+    return handleApiResponse<ExecutionProcessNormalizedEntriesPage>(response);
+  },
+
+  stopExecutionProcess: async (processId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/stop`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getRunningResources: async (): Promise<ResourceSample[]> => {
+    const response = await makeRequest('/api/execution-processes/resources');
+    return handleApiResponse<ResourceSample[]>(response);
+  },
+
+  getResources: async (
+    processId: string
+  ): Promise<ExecutionProcessResources> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/resources`
+    );
+    return handleApiResponse<ExecutionProcessResources>(response);
+  },
+
+  killChildProcess: async (processId: string, pid: number): Promise<void> => {
+    const payload: KillProcessRequest = { pid };
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/kill-process`,
       {
         method: 'POST',
+        body: JSON.stringify(payload),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  replay: async (processId: string): Promise<ReplayExecutionResponse> => {
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/replay`,
+      { method: 'POST' }
+    );
+    return handleApiResponse<ReplayExecutionResponse>(response);
+  },
+};
+
+// File System APIs
+export const fileSystemApi = {
+  list: async (path?: string): Promise<DirectoryListResponse> => {
+    const queryParam = path ? `?path=${encodeURIComponent(path)}` : '';
+    const response = await makeRequest(
+      `/api/filesystem/directory${queryParam}`
+    );
+    return handleApiResponse<DirectoryListResponse>(response);
+  },
+
+  listGitRepos: async (path?: string): Promise<DirectoryEntry[]> => {
+    const queryParam = path ? `?path=${encodeURIComponent(path)}` : '';
+    const response = await makeRequest(
+      `/api/filesystem/git-repos${queryParam}`
+    );
+    return handleApiResponse<DirectoryEntry[]>(response);
+  },
+};
+
+// Repo APIs
+export const repoApi = {
+  register: async (data: {
+    path: string;
+    display_name?: string;
+  }): Promise<Repo> => {
+    const response = await makeRequest('/api/repos', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Repo>(response);
+  },
+
+  updateScripts: async (
+    repoId: string,
+    data: UpdateRepoScripts
+  ): Promise<Repo> => {
+    const response = await makeRequest(`/api/repos/${repoId}/scripts`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Repo>(response);
+  },
+
+  updateCheckoutOptions: async (
+    repoId: string,
+    data: UpdateRepoCheckoutOptions
+  ): Promise<Repo> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/checkout-options`,
+      {
+        method: 'PUT',
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponseAsResult<void, PushError>(response);
+    return handleApiResponse<Repo>(response);
   },
 
-  rebase: async (
-    attemptId: string,
-    data: RebaseTaskAttemptRequest
-  ): Promise<Result<void, GitOperationError>> => {
+  getBranches: async (repoId: string): Promise<GitBranch[]> => {
+    const response = await makeRequest(`/api/repos/${repoId}/branches`);
+    return handleApiResponse<GitBranch[]>(response);
+  },
+
+  getStats: async (repoId: string): Promise<RepoStats> => {
+    const response = await makeRequest(`/api/repos/${repoId}/stats`);
+    return handleApiResponse<RepoStats>(response);
+  },
+
+  createBranch: async (
+    repoId: string,
+    name: string,
+    baseBranch?: string
+  ): Promise<GitBranch> => {
+    const payload: { name: string; base_branch?: string } = { name };
+    if (baseBranch !== undefined) {
+      payload.base_branch = baseBranch;
+    }
+    const response = await makeRequest(`/api/repos/${repoId}/branches`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(payload),
+    });
+    return handleApiResponse<GitBranch>(response);
+  },
+
+  checkBranchMergeStatus: async (
+    repoId: string,
+    branchName: string,
+    projectId: string
+  ): Promise<BranchMergeStatus> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/rebase`,
+      `/api/repos/${repoId}/branches/check-merge-status`,
       {
         method: 'POST',
-        body: JSON.stringify(data),
+        body: JSON.stringify({ branch_name: branchName, project_id: projectId }),
       }
     );
-    return handleApiResponseAsResult<void, GitOperationError>(response);
+    return handleApiResponse<BranchMergeStatus>(response);
   },
 
-  change_target_branch: async (
-    attemptId: string,
-    data: ChangeTargetBranchRequest
-  ): Promise<ChangeTargetBranchResponse> => {
+  batchCheckBranchMergeStatus: async (
+    repoId: string,
+    branches: string[],
+    projectId: string
+  ): Promise<BatchBranchMergeStatus> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/branches/batch-check-merge-status`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ branches, project_id: projectId }),
+      }
+    );
+    return handleApiResponse<BatchBranchMergeStatus>(response);
+  },
+
+  checkBranchSyncStatus: async (
+    repoId: string,
+    request: CheckBranchSyncStatusRequest
+  ): Promise<BranchSyncStatus> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/branches/check-sync-status`,
+      {
+        method: 'POST',
+        body: JSON.stringify(request),
+      }
+    );
+    return handleApiResponse<BranchSyncStatus>(response);
+  },
+
+  batchCheckBranchSyncStatus: async (
+    repoId: string,
+    request: BatchCheckBranchSyncStatusRequest
+  ): Promise<BatchBranchSyncStatus> => {
+    const response = await makeRequest(
+      `/api/repos/${repoId}/branches/batch-check-sync-status`,
+      {
+        method: 'POST',
+        body: JSON.stringify(request),
+      }
+    );
+    return handleApiResponse<BatchBranchSyncStatus>(response);
+  },
+
+  init: async (data: {
+    parent_path: string;
+    folder_name: string;
+  }): Promise<Repo> => {
+    const response = await makeRequest('/api/repos/init', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Repo>(response);
+  },
+
+  clone: async (data: {
+    url: string;
+    destination?: string;
+  }): Promise<Repo> => {
+    const response = await makeRequest('/api/repos/clone', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Repo>(response);
+  },
+
+  createPR: async (
+    repoId: string,
+    data: CreateRepoPrRequest
+  ): Promise<Result<PullRequestInfo, CreateRepoPrError>> => {
+    const response = await makeRequest(`/api/repos/${repoId}/prs`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponseAsResult<PullRequestInfo, CreateRepoPrError>(response);
+  },
+
+  pushBranch: async (
+    repoId: string,
+    request: PushBranchRequest
+  ): Promise<Result<void, PushBranchError>> => {
+    const response = await makeRequest(`/api/repos/${repoId}/branches/push`, {
+      method: 'POST',
+      body: JSON.stringify(request),
+    });
+    return handleApiResponseAsResult<void, PushBranchError>(response);
+  },
+};
+
+// Account Info API
+export const accountInfoApi = {
+  get: async (): Promise<AccountInfo> => {
+    const response = await makeRequest('/api/account-info');
+    return handleApiResponse<AccountInfo>(response);
+  },
+};
+
+// Config APIs (backwards compatible)
+export const configApi = {
+  getConfig: async (): Promise<UserSystemInfo> => {
+    const response = await makeRequest('/api/info', { cache: 'no-store' });
+    return handleApiResponse<UserSystemInfo>(response);
+  },
+  saveConfig: async (config: Config): Promise<Config> => {
+    const response = await makeRequest('/api/config', {
+      method: 'PUT',
+      body: JSON.stringify(config),
+    });
+    return handleApiResponse<Config>(response);
+  },
+  getCompatibility: async (): Promise<ConfigCompatibilityReport> => {
+    const response = await makeRequest('/api/config/compatibility', {
+      cache: 'no-store',
+    });
+    return handleApiResponse<ConfigCompatibilityReport>(response);
+  },
+  checkEditorAvailability: async (
+    editorType: EditorType
+  ): Promise<CheckEditorAvailabilityResponse> => {
+    const response = await makeRequest(
+      `/api/editors/check-availability?editor_type=${encodeURIComponent(editorType)}`
+    );
+    return handleApiResponse<CheckEditorAvailabilityResponse>(response);
+  },
+  checkAgentAvailability: async (
+    agent: BaseCodingAgent
+  ): Promise<AvailabilityInfo> => {
+    const response = await makeRequest(
+      `/api/agents/check-availability?executor=${encodeURIComponent(agent)}`
+    );
+    return handleApiResponse<AvailabilityInfo>(response);
+  },
+  getAgentAuthStatus: async (): Promise<AgentAuthStatus[]> => {
+    const response = await makeRequest('/api/agents/auth-status');
+    return handleApiResponse<AgentAuthStatus[]>(response);
+  },
+  refreshAgentAuthStatus: async (): Promise<AgentAuthStatus[]> => {
+    const response = await makeRequest('/api/agents/auth-status/refresh', {
+      method: 'POST',
+    });
+    return handleApiResponse<AgentAuthStatus[]>(response);
+  },
+};
+
+// Custom Editors APIs
+export const customEditorsApi = {
+  list: async (): Promise<ListCustomEditorsResponse> => {
+    const response = await makeRequest('/api/config/custom-editors');
+    return handleApiResponse<ListCustomEditorsResponse>(response);
+  },
+  create: async (
+    data: CreateCustomEditorRequest
+  ): Promise<CustomEditorResponse> => {
+    const response = await makeRequest('/api/config/custom-editors', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<CustomEditorResponse>(response);
+  },
+  update: async (
+    editorId: string,
+    data: UpdateCustomEditorRequest
+  ): Promise<CustomEditorResponse> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/change-target-branch`,
+      `/api/config/custom-editors/${editorId}`,
       {
-        method: 'POST',
+        method: 'PUT',
         body: JSON.stringify(data),
       }
     );
-    return handleApiResponse<ChangeTargetBranchResponse>(response);
+    return handleApiResponse<CustomEditorResponse>(response);
   },
-
-  renameBranch: async (
-    attemptId: string,
-    newBranchName: string
-  ): Promise<RenameBranchResponse> => {
-    const payload: RenameBranchRequest = {
-      new_branch_name: newBranchName,
-    };
+  delete: async (editorId: string): Promise<void> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/rename-branch`,
+      `/api/config/custom-editors/${editorId}`,
       {
-        method: 'POST',
-        body: JSON.stringify(payload),
+        method: 'DELETE',
       }
     );
-    return handleApiResponse<RenameBranchResponse>(response);
+    return handleApiResponse<void>(response);
   },
+};
 
-  abortConflicts: async (
-    attemptId: string,
-    data: AbortConflictsRequest
-  ): Promise<void> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/conflicts/abort`,
-      {
-        method: 'POST',
-        body: JSON.stringify(data),
-      }
-    );
-    return handleApiResponse<void>(response);
+// Task Tags APIs (all tags are global)
+export const tagsApi = {
+  list: async (params?: TagSearchParams): Promise<Tag[]> => {
+    const queryParam = params?.search
+      ? `?search=${encodeURIComponent(params.search)}`
+      : '';
+    const response = await makeRequest(`/api/tags${queryParam}`);
+    return handleApiResponse<Tag[]>(response);
   },
 
-  createPR: async (
-    attemptId: string,
-    data: CreateGitHubPrRequest
-  ): Promise<Result<string, CreatePrError>> => {
-    const response = await makeRequest(`/api/task-attempts/${attemptId}/pr`, {
+  create: async (data: CreateTag): Promise<Tag> => {
+    const response = await makeRequest('/api/tags', {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponseAsResult<string, CreatePrError>(response);
+    return handleApiResponse<Tag>(response);
   },
 
-  startDevServer: async (attemptId: string): Promise<void> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/start-dev-server`,
-      {
-        method: 'POST',
-      }
-    );
+  update: async (tagId: string, data: UpdateTag): Promise<Tag> => {
+    const response = await makeRequest(`/api/tags/${tagId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<Tag>(response);
+  },
+
+  delete: async (tagId: string): Promise<void> => {
+    const response = await makeRequest(`/api/tags/${tagId}`, {
+      method: 'DELETE',
+    });
     return handleApiResponse<void>(response);
   },
+};
 
-  setupGhCli: async (attemptId: string): Promise<ExecutionProcess> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/gh-cli-setup`,
-      {
-        method: 'POST',
-      }
-    );
-    return handleApiResponse<ExecutionProcess, GhCliSetupError>(response);
+// Server log APIs
+export const serverLogsApi = {
+  getHistory: async (params: {
+    since?: string;
+    level?: string;
+    target?: string;
+    limit?: number;
+  }): Promise<ServerLogEntry[]> => {
+    const search = new URLSearchParams();
+    if (params.since) search.set('since', params.since);
+    if (params.level) search.set('level', params.level);
+    if (params.target) search.set('target', params.target);
+    if (params.limit !== undefined) search.set('limit', String(params.limit));
+    const query = search.toString();
+    const response = await makeRequest(`/api/logs${query ? `?${query}` : ''}`);
+    return handleApiResponse<ServerLogEntry[]>(response);
   },
+};
 
-  runSetupScript: async (
-    attemptId: string
-  ): Promise<Result<ExecutionProcess, RunScriptError>> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/run-setup-script`,
-      {
-        method: 'POST',
-      }
-    );
-    return handleApiResponseAsResult<ExecutionProcess, RunScriptError>(
-      response
-    );
+// Backup APIs
+export const backupsApi = {
+  list: async (): Promise<BackupInfo[]> => {
+    const response = await makeRequest('/api/backups');
+    return handleApiResponse<BackupInfo[]>(response);
   },
 
-  runCleanupScript: async (
-    attemptId: string
-  ): Promise<Result<ExecutionProcess, RunScriptError>> => {
+  restore: async (backupId: string): Promise<RestoreReport> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/run-cleanup-script`,
+      `/api/backups/${encodeURIComponent(backupId)}/restore`,
       {
         method: 'POST',
       }
     );
-    return handleApiResponseAsResult<ExecutionProcess, RunScriptError>(
-      response
-    );
-  },
-
-  getPrComments: async (
-    attemptId: string,
-    repoId: string
-  ): Promise<PrCommentsResponse> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/pr/comments?repo_id=${encodeURIComponent(repoId)}`
-    );
-    return handleApiResponse<PrCommentsResponse>(response);
+    return handleApiResponse<RestoreReport>(response);
   },
 
-  queueMerge: async (
-    attemptId: string,
-    data: QueueMergeRequest
-  ): Promise<Result<MergeQueueEntry, QueueMergeError>> => {
+  verify: async (backupId: string): Promise<BackupVerification> => {
     const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/queue-merge`,
+      `/api/backups/${encodeURIComponent(backupId)}/verify`,
       {
         method: 'POST',
-        body: JSON.stringify(data),
       }
     );
-    return handleApiResponseAsResult<MergeQueueEntry, QueueMergeError>(response);
+    return handleApiResponse<BackupVerification>(response);
   },
 
-  cancelQueuedMerge: async (attemptId: string): Promise<void> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/queue-merge`,
-      {
-        method: 'DELETE',
-      }
-    );
-    return handleApiResponse<void>(response);
+  listVerifications: async (
+    backupId?: string
+  ): Promise<BackupVerification[]> => {
+    const query = backupId
+      ? `?${new URLSearchParams({ backup_id: backupId }).toString()}`
+      : '';
+    const response = await makeRequest(`/api/backups/verifications${query}`);
+    return handleApiResponse<BackupVerification[]>(response);
   },
+};
 
-  cancelQueuedExecution: async (attemptId: string): Promise<void> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/execution-queue`,
-      {
-        method: 'DELETE',
-      }
-    );
-    return handleApiResponse<void>(response);
+// Compliance tools: redacting leaked text and the audit log recording it
+export const adminApi = {
+  redact: async (data: RedactRequest): Promise<RedactionReport> => {
+    const response = await makeRequest('/api/admin/redactions', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<RedactionReport>(response);
   },
 
-  getQueueStatus: async (attemptId: string): Promise<MergeQueueEntry | null> => {
-    const response = await makeRequest(
-      `/api/task-attempts/${attemptId}/queue-status`
-    );
-    return handleApiResponse<MergeQueueEntry | null>(response);
+  getAuditLog: async (limit?: number): Promise<AuditLogEntry[]> => {
+    const query =
+      limit !== undefined
+        ? `?${new URLSearchParams({ limit: String(limit) }).toString()}`
+        : '';
+    const response = await makeRequest(`/api/admin/audit-log${query}`);
+    return handleApiResponse<AuditLogEntry[]>(response);
   },
 };
 
-// Execution Process APIs
-export const executionProcessesApi = {
-  getDetails: async (processId: string): Promise<ExecutionProcess> => {
-    const response = await makeRequest(`/api/execution-processes/${processId}`);
-    return handleApiResponse<ExecutionProcess>(response);
-  },
-
-  getRepoStates: async (
-    processId: string
-  ): Promise<ExecutionProcessRepoState[]> => {
-    const response = await makeRequest(
-      `/api/execution-processes/${processId}/repo-states`
-    );
-    return handleApiResponse<ExecutionProcessRepoState[]>(response);
+// Secret store APIs; values can be written but are never returned
+export const secretsApi = {
+  list: async (): Promise<SecretsResponse> => {
+    const response = await makeRequest('/api/secrets');
+    return handleApiResponse<SecretsResponse>(response);
   },
 
-  getNormalizedEntries: async (
-    processId: string,
-    params?: { beforeIndex?: number; limit?: number }
-  ): Promise<ExecutionProcessNormalizedEntriesPage> => {
-    const query = new URLSearchParams();
-    if (params?.beforeIndex !== undefined) {
-      query.set('before_index', String(params.beforeIndex));
-    }
-    if (params?.limit !== undefined) {
-      query.set('limit', String(params.limit));
-    }
-    const suffix = query.toString();
+  set: async (name: string, value: string): Promise<void> => {
     const response = await makeRequest(
-      `/api/execution-processes/${processId}/normalized-entries${
-        suffix ? `?${suffix}` : ''
-      }`
+      `/api/secrets/${encodeURIComponent(name)}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify({ value }),
+      }
     );
-    return handleApiResponse<ExecutionProcessNormalizedEntriesPage>(response);
+    return handleApiResponse<void>(response);
   },
 
-  stopExecutionProcess: async (processId: string): Promise<void> => {
+  delete: async (name: string): Promise<void> => {
     const response = await makeRequest(
-      `/api/execution-processes/${processId}/stop`,
+      `/api/secrets/${encodeURIComponent(name)}`,
       {
-        method: 'POST',
+        method: 'DELETE',
       }
     );
     return handleApiResponse<void>(response);
   },
 };
 
-// File System APIs
-export const fileSystemApi = {
-  list: async (path?: string): Promise<DirectoryListResponse> => {
-    const queryParam = path ? `?path=${encodeURIComponent(path)}` : '';
-    const response = await makeRequest(
-      `/api/filesystem/directory${queryParam}`
-    );
-    return handleApiResponse<DirectoryListResponse>(response);
-  },
-
-  listGitRepos: async (path?: string): Promise<DirectoryEntry[]> => {
-    const queryParam = path ? `?path=${encodeURIComponent(path)}` : '';
-    const response = await makeRequest(
-      `/api/filesystem/git-repos${queryParam}`
-    );
-    return handleApiResponse<DirectoryEntry[]>(response);
+// Asset directory APIs
+export const assetsApi = {
+  location: async (): Promise<AssetDirInfo> => {
+    const response = await makeRequest('/api/assets/location');
+    return handleApiResponse<AssetDirInfo>(response);
   },
-};
 
-// Repo APIs
-export const repoApi = {
-  register: async (data: {
-    path: string;
-    display_name?: string;
-  }): Promise<Repo> => {
-    const response = await makeRequest('/api/repos', {
+  relocate: async (targetDir: string): Promise<RelocationReport> => {
+    const response = await makeRequest('/api/assets/relocate', {
       method: 'POST',
-      body: JSON.stringify(data),
+      body: JSON.stringify({ target_dir: targetDir }),
     });
-    return handleApiResponse<Repo>(response);
-  },
-
-  getBranches: async (repoId: string): Promise<GitBranch[]> => {
-    const response = await makeRequest(`/api/repos/${repoId}/branches`);
-    return handleApiResponse<GitBranch[]>(response);
+    return handleApiResponse<RelocationReport>(response);
   },
+};
 
-  createBranch: async (
-    repoId: string,
-    name: string,
-    baseBranch?: string
-  ): Promise<GitBranch> => {
-    const payload: { name: string; base_branch?: string } = { name };
-    if (baseBranch !== undefined) {
-      payload.base_branch = baseBranch;
+// Operation APIs
+export const operationsApi = {
+  /** Recent long-running operations, newest first */
+  list: async (params?: {
+    workspaceId?: string;
+    kind?: OperationKind;
+    state?: OperationState;
+    limit?: number;
+  }): Promise<Operation[]> => {
+    const search = new URLSearchParams();
+    if (params?.workspaceId) {
+      search.set('workspace_id', params.workspaceId);
     }
-    const response = await makeRequest(`/api/repos/${repoId}/branches`, {
-      method: 'POST',
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify(payload),
-    });
-    return handleApiResponse<GitBranch>(response);
-  },
-
-  checkBranchMergeStatus: async (
-    repoId: string,
-    branchName: string,
-    projectId: string
-  ): Promise<BranchMergeStatus> => {
+    if (params?.kind) {
+      search.set('kind', params.kind);
+    }
+    if (params?.state) {
+      search.set('state', params.state);
+    }
+    if (params?.limit !== undefined) {
+      search.set('limit', String(params.limit));
+    }
+    const queryString = search.toString();
     const response = await makeRequest(
-      `/api/repos/${repoId}/branches/check-merge-status`,
-      {
-        method: 'POST',
-        body: JSON.stringify({ branch_name: branchName, project_id: projectId }),
-      }
+      `/api/operations${queryString ? `?${queryString}` : ''}`
     );
-    return handleApiResponse<BranchMergeStatus>(response);
+    return handleApiResponse<Operation[]>(response);
   },
-
-  batchCheckBranchMergeStatus: async (
-    repoId: string,
-    branches: string[],
-    projectId: string
-  ): Promise<BatchBranchMergeStatus> => {
+
+  get: async (operationId: string): Promise<Operation> => {
     const response = await makeRequest(
-      `/api/repos/${repoId}/branches/batch-check-merge-status`,
-      {
-        method: 'POST',
-        body: JSON.stringify({ branches, project_id: projectId }),
-      }
+      `/api/operations/${encodeURIComponent(operationId)}`
     );
-    return handleApiResponse<BatchBranchMergeStatus>(response);
+    return handleApiResponse<Operation>(response);
   },
+};
 
-  checkBranchSyncStatus: async (
-    repoId: string,
-    request: CheckBranchSyncStatusRequest
-  ): Promise<BranchSyncStatus> => {
+// Usage APIs
+export const usageApi = {
+  /** Token usage and estimated cost; dates are inclusive `YYYY-MM-DD` */
+  get: async (params?: {
+    from?: string;
+    to?: string;
+    projectId?: string;
+  }): Promise<UsageReport> => {
+    const search = new URLSearchParams();
+    if (params?.from) {
+      search.set('from', params.from);
+    }
+    if (params?.to) {
+      search.set('to', params.to);
+    }
+    if (params?.projectId) {
+      search.set('project_id', params.projectId);
+    }
+    const queryString = search.toString();
     const response = await makeRequest(
-      `/api/repos/${repoId}/branches/check-sync-status`,
-      {
-        method: 'POST',
-        body: JSON.stringify(request),
-      }
+      `/api/usage${queryString ? `?${queryString}` : ''}`
     );
-    return handleApiResponse<BranchSyncStatus>(response);
+    return handleApiResponse<UsageReport>(response);
   },
+};
 
-  batchCheckBranchSyncStatus: async (
-    repoId: string,
-    request: BatchCheckBranchSyncStatusRequest
-  ): Promise<BatchBranchSyncStatus> => {
+// Time tracking APIs
+export const timeTrackingApi = {
+  getTaskTime: async (taskId: string): Promise<TaskTime> => {
+    const response = await makeRequest(`/api/tasks/${taskId}/time`);
+    return handleApiResponse<TaskTime>(response);
+  },
+
+  /** Time per task of a project; dates are inclusive `YYYY-MM-DD` */
+  getProjectTime: async (
+    projectId: string,
+    params?: { from?: string; to?: string }
+  ): Promise<ProjectTimeReport> => {
+    const search = new URLSearchParams();
+    if (params?.from) {
+      search.set('from', params.from);
+    }
+    if (params?.to) {
+      search.set('to', params.to);
+    }
+    const queryString = search.toString();
     const response = await makeRequest(
-      `/api/repos/${repoId}/branches/batch-check-sync-status`,
-      {
-        method: 'POST',
-        body: JSON.stringify(request),
-      }
+      `/api/projects/${projectId}/time${queryString ? `?${queryString}` : ''}`
     );
-    return handleApiResponse<BatchBranchSyncStatus>(response);
+    return handleApiResponse<ProjectTimeReport>(response);
   },
+};
 
-  init: async (data: {
-    parent_path: string;
-    folder_name: string;
-  }): Promise<Repo> => {
-    const response = await makeRequest('/api/repos/init', {
+// Webhook APIs
+export const webhooksApi = {
+  list: async (): Promise<Webhook[]> => {
+    const response = await makeRequest('/api/webhooks');
+    return handleApiResponse<Webhook[]>(response);
+  },
+
+  create: async (data: CreateWebhook): Promise<Webhook> => {
+    const response = await makeRequest('/api/webhooks', {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Repo>(response);
+    return handleApiResponse<Webhook>(response);
   },
 
-  clone: async (data: {
-    url: string;
-    destination?: string;
-  }): Promise<Repo> => {
-    const response = await makeRequest('/api/repos/clone', {
-      method: 'POST',
+  update: async (webhookId: string, data: UpdateWebhook): Promise<Webhook> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}`, {
+      method: 'PUT',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Repo>(response);
+    return handleApiResponse<Webhook>(response);
   },
 
-  createPR: async (
-    repoId: string,
-    data: CreateRepoPrRequest
-  ): Promise<Result<PullRequestInfo, CreateRepoPrError>> => {
-    const response = await makeRequest(`/api/repos/${repoId}/prs`, {
-      method: 'POST',
-      body: JSON.stringify(data),
+  delete: async (webhookId: string): Promise<void> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}`, {
+      method: 'DELETE',
     });
-    return handleApiResponseAsResult<PullRequestInfo, CreateRepoPrError>(response);
+    return handleApiResponse<void>(response);
   },
 
-  pushBranch: async (
-    repoId: string,
-    request: PushBranchRequest
-  ): Promise<Result<void, PushBranchError>> => {
-    const response = await makeRequest(`/api/repos/${repoId}/branches/push`, {
+  test: async (webhookId: string): Promise<Webhook> => {
+    const response = await makeRequest(`/api/webhooks/${webhookId}/test`, {
       method: 'POST',
-      body: JSON.stringify(request),
     });
-    return handleApiResponseAsResult<void, PushBranchError>(response);
+    return handleApiResponse<Webhook>(response);
   },
 };
 
-// Account Info API
-export const accountInfoApi = {
-  get: async (): Promise<AccountInfo> => {
-    const response = await makeRequest('/api/account-info');
-    return handleApiResponse<AccountInfo>(response);
+// Prompt Templates APIs
+export const promptTemplatesApi = {
+  list: async (): Promise<PromptTemplate[]> => {
+    const response = await makeRequest('/api/prompt-templates');
+    return handleApiResponse<PromptTemplate[]>(response);
   },
-};
 
-// Config APIs (backwards compatible)
-export const configApi = {
-  getConfig: async (): Promise<UserSystemInfo> => {
-    const response = await makeRequest('/api/info', { cache: 'no-store' });
-    return handleApiResponse<UserSystemInfo>(response);
+  create: async (data: CreatePromptTemplate): Promise<PromptTemplate> => {
+    const response = await makeRequest('/api/prompt-templates', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<PromptTemplate>(response);
   },
-  saveConfig: async (config: Config): Promise<Config> => {
-    const response = await makeRequest('/api/config', {
+
+  update: async (
+    templateId: string,
+    data: UpdatePromptTemplate
+  ): Promise<PromptTemplate> => {
+    const response = await makeRequest(`/api/prompt-templates/${templateId}`, {
       method: 'PUT',
-      body: JSON.stringify(config),
+      body: JSON.stringify(data),
     });
-    return handleApiResponse<Config>(response);
+    return handleApiResponse<PromptTemplate>(response);
   },
-  checkEditorAvailability: async (
-    editorType: EditorType
-  ): Promise<CheckEditorAvailabilityResponse> => {
-    const response = await makeRequest(
-      `/api/editors/check-availability?editor_type=${encodeURIComponent(editorType)}`
-    );
-    return handleApiResponse<CheckEditorAvailabilityResponse>(response);
+
+  delete: async (templateId: string): Promise<void> => {
+    const response = await makeRequest(`/api/prompt-templates/${templateId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
   },
-  checkAgentAvailability: async (
-    agent: BaseCodingAgent
-  ): Promise<AvailabilityInfo> => {
+
+  preview: async (
+    templateId: string,
+    variables: Record<string, string>
+  ): Promise<string> => {
     const response = await makeRequest(
-      `/api/agents/check-availability?executor=${encodeURIComponent(agent)}`
+      `/api/prompt-templates/${templateId}/preview`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ variables }),
+      }
     );
-    return handleApiResponse<AvailabilityInfo>(response);
+    return handleApiResponse<string>(response);
   },
 };
 
-// Custom Editors APIs
-export const customEditorsApi = {
-  list: async (): Promise<ListCustomEditorsResponse> => {
-    const response = await makeRequest('/api/config/custom-editors');
-    return handleApiResponse<ListCustomEditorsResponse>(response);
+export const projectTemplatesApi = {
+  list: async (label?: string): Promise<ProjectTemplate[]> => {
+    const query = label ? `?label=${encodeURIComponent(label)}` : '';
+    const response = await makeRequest(`/api/project-templates${query}`);
+    return handleApiResponse<ProjectTemplate[]>(response);
   },
-  create: async (
-    data: CreateCustomEditorRequest
-  ): Promise<CustomEditorResponse> => {
-    const response = await makeRequest('/api/config/custom-editors', {
+
+  create: async (data: UpsertProjectTemplate): Promise<ProjectTemplate> => {
+    const response = await makeRequest('/api/project-templates', {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<CustomEditorResponse>(response);
+    return handleApiResponse<ProjectTemplate>(response);
   },
+
   update: async (
-    editorId: string,
-    data: UpdateCustomEditorRequest
-  ): Promise<CustomEditorResponse> => {
-    const response = await makeRequest(
-      `/api/config/custom-editors/${editorId}`,
-      {
-        method: 'PUT',
-        body: JSON.stringify(data),
-      }
-    );
-    return handleApiResponse<CustomEditorResponse>(response);
+    templateId: string,
+    data: UpsertProjectTemplate
+  ): Promise<ProjectTemplate> => {
+    const response = await makeRequest(`/api/project-templates/${templateId}`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectTemplate>(response);
   },
-  delete: async (editorId: string): Promise<void> => {
+
+  delete: async (templateId: string): Promise<void> => {
+    const response = await makeRequest(`/api/project-templates/${templateId}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  createProject: async (
+    templateId: string,
+    name: string
+  ): Promise<ProjectFromTemplate> => {
     const response = await makeRequest(
-      `/api/config/custom-editors/${editorId}`,
+      `/api/project-templates/${templateId}/projects`,
       {
-        method: 'DELETE',
+        method: 'POST',
+        body: JSON.stringify({ name }),
       }
     );
-    return handleApiResponse<void>(response);
+    return handleApiResponse<ProjectFromTemplate>(response);
   },
 };
 
-// Task Tags APIs (all tags are global)
-export const tagsApi = {
-  list: async (params?: TagSearchParams): Promise<Tag[]> => {
-    const queryParam = params?.search
-      ? `?search=${encodeURIComponent(params.search)}`
-      : '';
-    const response = await makeRequest(`/api/tags${queryParam}`);
-    return handleApiResponse<Tag[]>(response);
+// Accounts and sign-in on servers shared by several people
+export const usersApi = {
+  getSession: async (): Promise<SessionStatus> => {
+    const response = await makeRequest('/api/session', { cache: 'no-store' });
+    return handleApiResponse<SessionStatus>(response);
   },
 
-  create: async (data: CreateTag): Promise<Tag> => {
-    const response = await makeRequest('/api/tags', {
+  login: async (username: string, password: string): Promise<User> => {
+    const response = await makeRequest('/api/session', {
       method: 'POST',
-      body: JSON.stringify(data),
+      body: JSON.stringify({ username, password }),
     });
-    return handleApiResponse<Tag>(response);
+    return handleApiResponse<User>(response);
   },
 
-  update: async (tagId: string, data: UpdateTag): Promise<Tag> => {
-    const response = await makeRequest(`/api/tags/${tagId}`, {
-      method: 'PUT',
+  logout: async (): Promise<void> => {
+    const response = await makeRequest('/api/session', { method: 'DELETE' });
+    return handleApiResponse<void>(response);
+  },
+
+  list: async (): Promise<User[]> => {
+    const response = await makeRequest('/api/users');
+    return handleApiResponse<User[]>(response);
+  },
+
+  create: async (data: CreateUser): Promise<User> => {
+    const response = await makeRequest('/api/users', {
+      method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponse<Tag>(response);
+    return handleApiResponse<User>(response);
   },
 
-  delete: async (tagId: string): Promise<void> => {
-    const response = await makeRequest(`/api/tags/${tagId}`, {
+  delete: async (userId: string): Promise<void> => {
+    const response = await makeRequest(`/api/users/${userId}`, {
       method: 'DELETE',
     });
     return handleApiResponse<void>(response);
@@ -1654,6 +3242,11 @@ export const imagesApi = {
     return handleApiResponse<void>(response);
   },
 
+  collectGarbage: async (): Promise<ImageGcReport> => {
+    const response = await makeRequest('/api/images/gc', { method: 'POST' });
+    return handleApiResponse<ImageGcReport>(response);
+  },
+
   getTaskImages: async (taskId: string): Promise<ImageResponse[]> => {
     const response = await makeRequest(`/api/images/task/${taskId}`);
     return handleApiResponse<ImageResponse[]>(response);
@@ -1881,6 +3474,28 @@ export const scratchApi = {
   },
 };
 
+// Semantic search across tasks, conversations and agent summaries, and
+// full-text search over conversations and execution logs
+export const searchApi = {
+  semantic: async (
+    data: SemanticSearchRequest
+  ): Promise<SemanticSearchResponse> => {
+    const response = await makeRequest('/api/search/semantic', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<SemanticSearchResponse>(response);
+  },
+
+  text: async (data: TextSearchRequest): Promise<TextSearchMatch[]> => {
+    const response = await makeRequest('/api/search/text', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TextSearchMatch[]>(response);
+  },
+};
+
 // Queue API for session follow-up messages
 export const queueApi = {
   /**
@@ -1919,7 +3534,8 @@ export const queueApi = {
 // Queue API for conversation follow-up messages
 export const conversationQueueApi = {
   /**
-   * Queue a follow-up message to be executed when current execution finishes
+   * Add a follow-up message to the end of the queue; queued messages are
+   * sent one at a time as executions finish
    */
   queue: async (
     conversationId: string,
@@ -1936,7 +3552,7 @@ export const conversationQueueApi = {
   },
 
   /**
-   * Cancel a queued follow-up message
+   * Cancel all queued follow-up messages
    */
   cancel: async (conversationId: string): Promise<QueueStatus> => {
     const response = await makeRequest(
@@ -1948,6 +3564,39 @@ export const conversationQueueApi = {
     return handleApiResponse<QueueStatus>(response);
   },
 
+  /**
+   * Remove one queued message
+   */
+  remove: async (
+    conversationId: string,
+    messageId: string
+  ): Promise<QueueStatus> => {
+    const response = await makeRequest(
+      `/api/conversations/${conversationId}/queue/${messageId}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<QueueStatus>(response);
+  },
+
+  /**
+   * Reorder the queued messages; every queued message must be listed
+   */
+  reorder: async (
+    conversationId: string,
+    data: ReorderConversationQueueRequest
+  ): Promise<QueueStatus> => {
+    const response = await makeRequest(
+      `/api/conversations/${conversationId}/queue/order`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<QueueStatus>(response);
+  },
+
   /**
    * Get the current queue status for a conversation
    */
@@ -1972,6 +3621,9 @@ export const notificationsApi = {
   list: async (params?: {
     projectId?: string;
     limit?: number;
+    notificationType?: NotificationType;
+    unreadOnly?: boolean;
+    targetedOnly?: boolean;
   }): Promise<Notification[]> => {
     const search = new URLSearchParams();
     if (params?.projectId) {
@@ -1980,6 +3632,15 @@ export const notificationsApi = {
     if (params?.limit !== undefined) {
       search.set('limit', params.limit.toString());
     }
+    if (params?.notificationType) {
+      search.set('notification_type', params.notificationType);
+    }
+    if (params?.unreadOnly) {
+      search.set('unread_only', 'true');
+    }
+    if (params?.targetedOnly) {
+      search.set('targeted_only', 'true');
+    }
     const queryString = search.toString();
     const url = `/api/notifications${queryString ? `?${queryString}` : ''}`;
     const response = await makeRequest(url);
@@ -2011,10 +3672,32 @@ export const notificationsApi = {
     return handleApiResponse<Notification>(response);
   },
 
-  markAllRead: async (projectId?: string): Promise<number> => {
+  markManyRead: async (
+    notificationIds: string[],
+    isRead = true
+  ): Promise<number> => {
+    const body: MarkNotificationsReadRequest = {
+      notification_ids: notificationIds,
+      is_read: isRead,
+    };
+    const response = await makeRequest('/api/notifications/mark-read', {
+      method: 'POST',
+      body: JSON.stringify(body),
+    });
+    return handleApiResponse<number>(response);
+  },
+
+  markAllRead: async (
+    projectId?: string,
+    notificationType?: NotificationType
+  ): Promise<number> => {
+    const body: MarkAllReadRequest = {
+      project_id: projectId ?? null,
+      notification_type: notificationType ?? null,
+    };
     const response = await makeRequest('/api/notifications/mark-all-read', {
       method: 'POST',
-      body: JSON.stringify({ project_id: projectId ?? null }),
+      body: JSON.stringify(body),
     });
     return handleApiResponse<number>(response);
   },
@@ -2026,11 +3709,17 @@ export const notificationsApi = {
     return handleApiResponse<void>(response);
   },
 
-  getStreamUrl: (projectId?: string): string => {
+  getStreamUrl: (
+    projectId?: string,
+    notificationType?: NotificationType
+  ): string => {
     const params = new URLSearchParams();
     if (projectId) {
       params.set('project_id', projectId);
     }
+    if (notificationType) {
+      params.set('notification_type', notificationType);
+    }
     params.set('include_snapshot', 'true');
     return `/api/notifications/stream/ws?${params.toString()}`;
   },
@@ -2043,6 +3732,7 @@ export interface CreateConversationRequest {
   executor_profile_id: ExecutorProfileId | null;
   worktree_path?: string | null;
   worktree_branch?: string | null;
+  create_worktree?: boolean;
 }
 
 export interface ListConversationsParams {
@@ -2156,6 +3846,20 @@ export const conversationsApi = {
     return handleApiResponse<SendMessageResponse>(response);
   },
 
+  fork: async (
+    conversationId: string,
+    data: ForkConversationRequest
+  ): Promise<ForkConversationResponse> => {
+    const response = await makeRequest(
+      `/api/conversations/${conversationId}/fork`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ForkConversationResponse>(response);
+  },
+
   getExecutions: async (
     conversationId: string
   ): Promise<ExecutionProcess[]> => {
@@ -2315,3 +4019,28 @@ export const claudeAccountsApi = {
     return handleApiResponse<string | null>(response);
   },
 };
+
+// Bisect APIs
+export const bisectApi = {
+  /** Start bisecting a repo's good..bad range in the background */
+  start: async (data: StartBisectRequest): Promise<BisectRun> => {
+    const response = await makeRequest('/api/bisect', {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<BisectRun>(response);
+  },
+
+  /** A bisect run and the commits tested so far */
+  get: async (id: string): Promise<BisectRunDetails> => {
+    const response = await makeRequest(`/api/bisect/${id}`);
+    return handleApiResponse<BisectRunDetails>(response);
+  },
+
+  listForTask: async (taskId: string): Promise<BisectRun[]> => {
+    const response = await makeRequest(
+      `/api/bisect?task_id=${encodeURIComponent(taskId)}`
+    );
+    return handleApiResponse<BisectRun[]>(response);
+  },
+};
@@ -0,0 +1,151 @@
+/**
+ * Live streams are served over WebSockets by default. Some proxies block
+ * WebSockets, so the same endpoints also speak server-sent events; which one
+ * to use is a per-browser choice, since it depends on the network. A desktop
+ * app whose backend is on a socket relays the server-sent events itself.
+ */
+
+import {
+  closeBackendStream,
+  isBackendSocketUrl,
+  openBackendStream,
+} from './tauriApi';
+
+export type StreamTransport = 'websocket' | 'sse';
+
+const STORAGE_KEY = 'vk-stream-transport';
+
+export function getStreamTransport(): StreamTransport {
+  try {
+    return localStorage.getItem(STORAGE_KEY) === 'sse' ? 'sse' : 'websocket';
+  } catch {
+    return 'websocket';
+  }
+}
+
+export function setStreamTransport(transport: StreamTransport) {
+  try {
+    if (transport === 'sse') {
+      localStorage.setItem(STORAGE_KEY, transport);
+    } else {
+      localStorage.removeItem(STORAGE_KEY);
+    }
+  } catch {
+    // Storage unavailable; keep the default
+  }
+}
+
+/** The part of the WebSocket API the stream hooks use */
+export interface StreamSocket {
+  onopen: ((event: Event) => void) | null;
+  onmessage: ((event: MessageEvent) => void) | null;
+  onerror: ((event: Event) => void) | null;
+  onclose: ((event: CloseEvent) => void) | null;
+  close(code?: number, reason?: string): void;
+}
+
+/**
+ * Presents an EventSource like a WebSocket. EventSource reconnects on its
+ * own; this reports a dropped connection as a close instead, so the hooks'
+ * own reconnect logic stays in charge.
+ */
+class EventSourceSocket implements StreamSocket {
+  onopen: ((event: Event) => void) | null = null;
+  onmessage: ((event: MessageEvent) => void) | null = null;
+  onerror: ((event: Event) => void) | null = null;
+  onclose: ((event: CloseEvent) => void) | null = null;
+
+  private source: EventSource;
+  private closed = false;
+
+  constructor(url: string) {
+    this.source = new EventSource(url, { withCredentials: true });
+    this.source.onopen = (event) => this.onopen?.(event);
+    this.source.onmessage = (event) => this.onmessage?.(event);
+    this.source.onerror = (event) => {
+      if (this.closed) return;
+      this.closed = true;
+      this.source.close();
+      this.onerror?.(event);
+      this.emitClose(1006, '', false);
+    };
+  }
+
+  close(code = 1000, reason = '') {
+    if (this.closed) return;
+    this.closed = true;
+    this.source.close();
+    // Like a WebSocket, report the close asynchronously
+    window.setTimeout(() => this.emitClose(code, reason, true), 0);
+  }
+
+  private emitClose(code: number, reason: string, wasClean: boolean) {
+    this.onclose?.(new CloseEvent('close', { code, reason, wasClean }));
+  }
+}
+
+/**
+ * Presents a stream relayed by the desktop app like a WebSocket, reporting
+ * the end of the stream as a close like EventSourceSocket does.
+ */
+class BackendSocketStream implements StreamSocket {
+  onopen: ((event: Event) => void) | null = null;
+  onmessage: ((event: MessageEvent) => void) | null = null;
+  onerror: ((event: Event) => void) | null = null;
+  onclose: ((event: CloseEvent) => void) | null = null;
+
+  private id: Promise<number | null>;
+  private closed = false;
+
+  constructor(url: string) {
+    this.id = openBackendStream(url, (event) => {
+      if (this.closed) return;
+      switch (event.type) {
+        case 'open':
+          this.onopen?.(new Event('open'));
+          break;
+        case 'message':
+          this.onmessage?.(new MessageEvent('message', { data: event.data }));
+          break;
+        case 'closed':
+          this.fail();
+          break;
+      }
+    }).catch(() => {
+      this.fail();
+      return null;
+    });
+  }
+
+  close(code = 1000, reason = '') {
+    if (this.closed) return;
+    this.closed = true;
+    void this.id.then((id) => id !== null && closeBackendStream(id));
+    window.setTimeout(() => this.emitClose(code, reason, true), 0);
+  }
+
+  private fail() {
+    if (this.closed) return;
+    this.closed = true;
+    this.onerror?.(new Event('error'));
+    this.emitClose(1006, '', false);
+  }
+
+  private emitClose(code: number, reason: string, wasClean: boolean) {
+    this.onclose?.(new CloseEvent('close', { code, reason, wasClean }));
+  }
+}
+
+/**
+ * Open a live stream at `url` (http or https) using the transport chosen for
+ * this browser
+ */
+export function openStreamSocket(url: string): StreamSocket {
+  if (isBackendSocketUrl(url)) {
+    return new BackendSocketStream(url);
+  }
+  if (getStreamTransport() === 'sse') {
+    return new EventSourceSocket(url);
+  }
+  return new WebSocket(url.replace(/^http/, 'ws'));
+}
@@ -37,6 +37,18 @@ export const taskDependenciesKeys = {
     ['taskDependencies', taskId] as const,
 };
 
+/**
+ * Query keys for task link queries.
+ */
+export const taskLinksKeys = {
+  all: ['taskLinks'] as const,
+  byTask: (taskId: string | undefined) => ['taskLinks', taskId] as const,
+};
+
+export const taskNotesKeys = {
+  byTask: (taskId: string | undefined) => ['taskNotes', taskId] as const,
+};
+
 /**
  * Query keys for task dependency tree queries.
  */
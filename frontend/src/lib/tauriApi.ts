@@ -1,4 +1,4 @@
-import { invoke } from '@tauri-apps/api/core'
+import { Channel, invoke } from '@tauri-apps/api/core'
 
 declare global {
   interface Window {
@@ -18,6 +18,46 @@ export async function getServerUrl(): Promise<string> {
   return invoke<string>('get_server_url')
 }
 
+/**
+ * Base URLs the app serves backend requests at when the backend listens on
+ * a socket; Windows serves custom schemes over http
+ */
+const BACKEND_SOCKET_ORIGINS = [
+  'vibe-kanban://localhost',
+  'http://vibe-kanban.localhost',
+]
+
+/** Whether `url` is served by a backend the app reaches over a socket */
+export function isBackendSocketUrl(url: string): boolean {
+  return BACKEND_SOCKET_ORIGINS.some((origin) => url.startsWith(origin))
+}
+
+export type BackendStreamEvent =
+  | { type: 'open' }
+  | { type: 'message'; data: string }
+  | { type: 'closed'; error: string | null }
+
+/**
+ * Stream `url` from a backend on a socket, as server-sent event data.
+ * Resolves to an id for closeBackendStream.
+ */
+export async function openBackendStream(
+  url: string,
+  onEvent: (event: BackendStreamEvent) => void
+): Promise<number> {
+  const { pathname, search } = new URL(url)
+  const channel = new Channel<BackendStreamEvent>()
+  channel.onmessage = onEvent
+  return invoke<number>('open_backend_stream', {
+    path: `${pathname}${search}`,
+    onEvent: channel,
+  })
+}
+
+export async function closeBackendStream(id: number): Promise<void> {
+  return invoke<void>('close_backend_stream', { id })
+}
+
 export async function setServerUrl(url: string | null): Promise<void> {
   return invoke<void>('set_server_url', { url })
 }
@@ -10,7 +10,10 @@ export function formatFileSize(bytes: bigint | null | undefined): string {
   const num = Number(bytes);
   if (num < 1024) return `${num} B`;
   if (num < 1024 * 1024) return `${(num / 1024).toFixed(1)} KB`;
-  return `${(num / (1024 * 1024)).toFixed(1)} MB`;
+  if (num < 1024 * 1024 * 1024) {
+    return `${(num / (1024 * 1024)).toFixed(1)} MB`;
+  }
+  return `${(num / (1024 * 1024 * 1024)).toFixed(1)} GB`;
 }
 
 export function formatTokenCount(n: bigint | number | null | undefined): string {
@@ -16,6 +16,7 @@ import { useSearch } from '@/contexts/SearchContext';
 import { useTaskFilters } from '@/hooks/useTaskFilters';
 import { useFilteredTasks } from '@/hooks/useFilteredTasks';
 import { TaskFilterBar } from '@/components/tasks/TaskFilterBar';
+import { ProjectDashboardHeader } from '@/components/projects/ProjectDashboardHeader';
 import { useProject } from '@/contexts/ProjectContext';
 import { useTaskAttemptsStream } from '@/hooks/useTaskAttemptsStream';
 import { useTaskAttemptWithSession } from '@/hooks/useTaskAttempt';
@@ -314,6 +315,7 @@ export function ProjectTasks() {
           title: task.title,
           description: task.description,
           status: newStatus,
+          priority: null,
           parent_workspace_id: task.parent_workspace_id,
           image_ids: null,
           task_group_id: task.task_group_id,
@@ -658,6 +660,7 @@ export function ProjectTasks() {
           title: task.title,
           description: task.description,
           status: newStatus,
+          priority: null,
           parent_workspace_id: task.parent_workspace_id,
           image_ids: null,
           task_group_id: null,
@@ -738,6 +741,9 @@ export function ProjectTasks() {
       </div>
     ) : (
       <div className="flex flex-col h-full">
+        <div className="shrink-0 px-4">
+          <ProjectDashboardHeader projectId={projectId!} />
+        </div>
         <div className="shrink-0 px-4 flex items-center gap-2">
           <div className="flex-1">
             <TaskFilterBar />
@@ -17,6 +17,7 @@ import { Checkbox } from '@/components/ui/checkbox';
 import { ChevronDown, ChevronRight, Folder, Loader2, Volume2 } from 'lucide-react';
 import {
   AvailableSoundsResponse,
+  Config,
   DEFAULT_COMMIT_MESSAGE_PROMPT,
   DEFAULT_PR_DESCRIPTION_PROMPT,
   EditorType,
@@ -36,13 +37,42 @@ import { FolderPickerDialog } from '@/components/dialogs/shared/FolderPickerDial
 import ExecutorProfileSelector from '@/components/settings/ExecutorProfileSelector';
 import { CustomEditorsList } from '@/components/settings/CustomEditorsList';
 import { ServerModeSettings } from '@/components/settings/ServerModeSettings';
-import { soundsApi } from '@/lib/api';
+import { secretsApi, soundsApi } from '@/lib/api';
 import { playSound } from '@/lib/soundUtils';
+import { getStreamTransport, setStreamTransport } from '@/lib/streamSocket';
 import { SettingsSection } from '@/components/settings/SettingsSection';
 import { SettingsField } from '@/components/settings/SettingsField';
 import { Text } from '@/components/ui/text';
 import { SkeletonForm } from '@/components/ui/loading-states';
 
+/** Secret store entries the credentials entered here are saved under */
+const LANGFUSE_SECRET_KEY = 'langfuse_secret_key';
+const GITLAB_TOKEN = 'gitlab_token';
+const BITBUCKET_APP_PASSWORD = 'bitbucket_app_password';
+const GITEA_TOKEN = 'gitea_token';
+const LINEAR_API_KEY = 'linear_api_key';
+
+/** Point the config at a secret store entry that was just written */
+function withSecretRef(config: Config, name: string): Config {
+  switch (name) {
+    case LANGFUSE_SECRET_KEY:
+      return { ...config, langfuse_secret_key: name };
+    case GITLAB_TOKEN:
+      return { ...config, gitlab: { ...config.gitlab, token: name } };
+    case BITBUCKET_APP_PASSWORD:
+      return {
+        ...config,
+        bitbucket: { ...config.bitbucket, app_password: name },
+      };
+    case GITEA_TOKEN:
+      return { ...config, gitea: { ...config.gitea, token: name } };
+    case LINEAR_API_KEY:
+      return { ...config, linear: { ...config.linear, api_key: name } };
+    default:
+      return config;
+  }
+}
+
 export function GeneralSettings() {
   const { t } = useTranslation(['settings', 'common']);
 
@@ -62,6 +92,11 @@ export function GeneralSettings() {
 
   // Draft state management
   const [draft, setDraft] = useState(() => (config ? cloneDeep(config) : null));
+  // Secrets by store entry, typed only when replacing them; stored values
+  // are never sent back
+  const [secretInputs, setSecretInputs] = useState<Record<string, string>>({});
+  const setSecretInput = (name: string, value: string) =>
+    setSecretInputs((prev) => ({ ...prev, [name]: value }));
   const [dirty, setDirty] = useState(false);
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -72,6 +107,10 @@ export function GeneralSettings() {
   const [availableSounds, setAvailableSounds] = useState<AvailableSoundsResponse | null>(null);
   const [soundsLoading, setSoundsLoading] = useState(false);
   const [backupAdvancedOpen, setBackupAdvancedOpen] = useState(false);
+  // Per-browser, so it is saved right away rather than with the config
+  const [sseEnabled, setSseEnabled] = useState(
+    () => getStreamTransport() === 'sse'
+  );
   const { setTheme } = useTheme();
 
   // Check editor availability when draft editor changes
@@ -128,8 +167,10 @@ export function GeneralSettings() {
   // Check for unsaved changes
   const hasUnsavedChanges = useMemo(() => {
     if (!draft || !config) return false;
-    return !isEqual(draft, config);
-  }, [draft, config]);
+    return (
+      Object.values(secretInputs).some(Boolean) || !isEqual(draft, config)
+    );
+  }, [draft, config, secretInputs]);
 
   // Generic draft update helper
   const updateDraft = useCallback(
@@ -198,8 +239,16 @@ export function GeneralSettings() {
     setSuccess(false);
 
     try {
-      await updateAndSaveConfig(draft); // Atomically apply + persist
-      setTheme(draft.theme);
+      let next = draft;
+      for (const [name, value] of Object.entries(secretInputs)) {
+        if (!value) continue;
+        await secretsApi.set(name, value);
+        next = withSecretRef(next, name);
+      }
+      if (next !== draft) setDraft(next);
+      setSecretInputs({});
+      await updateAndSaveConfig(next); // Atomically apply + persist
+      setTheme(next.theme);
       setDirty(false);
       setSuccess(true);
       setTimeout(() => setSuccess(false), 3000);
@@ -214,6 +263,7 @@ export function GeneralSettings() {
   const handleDiscard = () => {
     if (!config) return;
     setDraft(cloneDeep(config));
+    setSecretInputs({});
     setDirty(false);
   };
 
@@ -1083,6 +1133,505 @@ export function GeneralSettings() {
           />
         </SettingsField>
 
+        <SettingsField
+          label={t('settings.general.concurrency.preempt.label')}
+          htmlFor="preempt-lower-priority"
+          description={t('settings.general.concurrency.preempt.helper')}
+          layout="horizontal"
+        >
+          <Checkbox
+            id="preempt-lower-priority"
+            checked={draft?.preempt_lower_priority ?? false}
+            onCheckedChange={(checked: boolean) =>
+              updateDraft({ preempt_lower_priority: checked })
+            }
+          />
+        </SettingsField>
+
+        <SettingsField
+          label={t('settings.general.diffs.maxInline.label')}
+          htmlFor="max-inline-diff-kb"
+          description={t('settings.general.diffs.maxInline.helper')}
+        >
+          <Input
+            id="max-inline-diff-kb"
+            type="number"
+            min="1"
+            placeholder={t('settings.general.diffs.maxInline.placeholder')}
+            value={draft?.max_inline_diff_kb ?? ''}
+            onChange={(e) => {
+              const value = parseInt(e.target.value, 10);
+              updateDraft({
+                max_inline_diff_kb: value > 0 ? value : null,
+              });
+            }}
+            className="w-32"
+          />
+        </SettingsField>
+
+        {/* Loop breaker */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.anomalyDetection.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.anomalyDetection.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.anomalyDetection.enabled.label')}
+            htmlFor="anomaly-detection-enabled"
+            description={t('settings.general.anomalyDetection.enabled.helper')}
+            layout="horizontal"
+          >
+            <Checkbox
+              id="anomaly-detection-enabled"
+              checked={draft?.anomaly_detection.enabled ?? true}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({
+                  anomaly_detection: {
+                    ...draft!.anomaly_detection,
+                    enabled: checked,
+                  },
+                })
+              }
+            />
+          </SettingsField>
+
+          {draft?.anomaly_detection.enabled && (
+            <>
+              <SettingsField
+                label={t(
+                  'settings.general.anomalyDetection.repeatedToolCalls.label'
+                )}
+                htmlFor="anomaly-repeated-tool-calls"
+                description={t(
+                  'settings.general.anomalyDetection.repeatedToolCalls.helper'
+                )}
+                indent
+              >
+                <Input
+                  id="anomaly-repeated-tool-calls"
+                  type="number"
+                  min="0"
+                  value={
+                    draft.anomaly_detection.thresholds.max_repeated_tool_calls
+                  }
+                  onChange={(e) => {
+                    const value = parseInt(e.target.value, 10) || 0;
+                    updateDraft({
+                      anomaly_detection: {
+                        ...draft!.anomaly_detection,
+                        thresholds: {
+                          ...draft!.anomaly_detection.thresholds,
+                          max_repeated_tool_calls: Math.max(0, value),
+                        },
+                      },
+                    });
+                  }}
+                  className="w-32"
+                />
+              </SettingsField>
+
+              <SettingsField
+                label={t(
+                  'settings.general.anomalyDetection.noProgressEdits.label'
+                )}
+                htmlFor="anomaly-no-progress-edits"
+                description={t(
+                  'settings.general.anomalyDetection.noProgressEdits.helper'
+                )}
+                indent
+              >
+                <Input
+                  id="anomaly-no-progress-edits"
+                  type="number"
+                  min="0"
+                  value={
+                    draft.anomaly_detection.thresholds.max_no_progress_edits
+                  }
+                  onChange={(e) => {
+                    const value = parseInt(e.target.value, 10) || 0;
+                    updateDraft({
+                      anomaly_detection: {
+                        ...draft!.anomaly_detection,
+                        thresholds: {
+                          ...draft!.anomaly_detection.thresholds,
+                          max_no_progress_edits: Math.max(0, value),
+                        },
+                      },
+                    });
+                  }}
+                  className="w-32"
+                />
+              </SettingsField>
+
+              <SettingsField
+                label={t('settings.general.anomalyDetection.outputMb.label')}
+                htmlFor="anomaly-output-mb"
+                description={t(
+                  'settings.general.anomalyDetection.outputMb.helper'
+                )}
+                indent
+              >
+                <Input
+                  id="anomaly-output-mb"
+                  type="number"
+                  min="0"
+                  value={Math.round(
+                    draft.anomaly_detection.thresholds.max_output_bytes /
+                      (1024 * 1024)
+                  )}
+                  onChange={(e) => {
+                    const value = parseInt(e.target.value, 10) || 0;
+                    updateDraft({
+                      anomaly_detection: {
+                        ...draft!.anomaly_detection,
+                        thresholds: {
+                          ...draft!.anomaly_detection.thresholds,
+                          max_output_bytes: Math.max(0, value) * 1024 * 1024,
+                        },
+                      },
+                    });
+                  }}
+                  className="w-32"
+                />
+              </SettingsField>
+
+              <SettingsField
+                label={t(
+                  'settings.general.anomalyDetection.inactivityMinutes.label'
+                )}
+                htmlFor="anomaly-inactivity-minutes"
+                description={t(
+                  'settings.general.anomalyDetection.inactivityMinutes.helper'
+                )}
+                indent
+              >
+                <Input
+                  id="anomaly-inactivity-minutes"
+                  type="number"
+                  min="0"
+                  value={
+                    draft.anomaly_detection.thresholds.inactivity_timeout_minutes
+                  }
+                  onChange={(e) => {
+                    const value = parseInt(e.target.value, 10) || 0;
+                    updateDraft({
+                      anomaly_detection: {
+                        ...draft!.anomaly_detection,
+                        thresholds: {
+                          ...draft!.anomaly_detection.thresholds,
+                          inactivity_timeout_minutes: Math.max(0, value),
+                        },
+                      },
+                    });
+                  }}
+                  className="w-32"
+                />
+              </SettingsField>
+
+              <SettingsField
+                label={t(
+                  'settings.general.anomalyDetection.maxDurationMinutes.label'
+                )}
+                htmlFor="anomaly-max-duration-minutes"
+                description={t(
+                  'settings.general.anomalyDetection.maxDurationMinutes.helper'
+                )}
+                indent
+              >
+                <Input
+                  id="anomaly-max-duration-minutes"
+                  type="number"
+                  min="0"
+                  value={
+                    draft.anomaly_detection.thresholds.max_duration_minutes
+                  }
+                  onChange={(e) => {
+                    const value = parseInt(e.target.value, 10) || 0;
+                    updateDraft({
+                      anomaly_detection: {
+                        ...draft!.anomaly_detection,
+                        thresholds: {
+                          ...draft!.anomaly_detection.thresholds,
+                          max_duration_minutes: Math.max(0, value),
+                        },
+                      },
+                    });
+                  }}
+                  className="w-32"
+                />
+              </SettingsField>
+            </>
+          )}
+        </div>
+
+        {/* Output redaction */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.outputRedaction.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.outputRedaction.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.outputRedaction.enabled.label')}
+            htmlFor="output-redaction-enabled"
+            description={t('settings.general.outputRedaction.enabled.helper')}
+            layout="horizontal"
+          >
+            <Checkbox
+              id="output-redaction-enabled"
+              checked={draft?.output_redaction.enabled ?? true}
+              onCheckedChange={(checked: boolean) =>
+                updateDraft({
+                  output_redaction: {
+                    ...draft!.output_redaction,
+                    enabled: checked,
+                  },
+                })
+              }
+            />
+          </SettingsField>
+
+          {draft?.output_redaction.enabled && (
+            <>
+              <SettingsField
+                label={t(
+                  'settings.general.outputRedaction.builtinPatterns.label'
+                )}
+                htmlFor="output-redaction-builtin"
+                description={t(
+                  'settings.general.outputRedaction.builtinPatterns.helper'
+                )}
+                layout="horizontal"
+                indent
+              >
+                <Checkbox
+                  id="output-redaction-builtin"
+                  checked={draft.output_redaction.builtin_patterns}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({
+                      output_redaction: {
+                        ...draft!.output_redaction,
+                        builtin_patterns: checked,
+                      },
+                    })
+                  }
+                />
+              </SettingsField>
+
+              <SettingsField
+                label={t(
+                  'settings.general.outputRedaction.entropyDetection.label'
+                )}
+                htmlFor="output-redaction-entropy"
+                description={t(
+                  'settings.general.outputRedaction.entropyDetection.helper'
+                )}
+                layout="horizontal"
+                indent
+              >
+                <Checkbox
+                  id="output-redaction-entropy"
+                  checked={draft.output_redaction.entropy_detection}
+                  onCheckedChange={(checked: boolean) =>
+                    updateDraft({
+                      output_redaction: {
+                        ...draft!.output_redaction,
+                        entropy_detection: checked,
+                      },
+                    })
+                  }
+                />
+              </SettingsField>
+            </>
+          )}
+        </div>
+
+        {/* GitLab */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.gitlab.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.gitlab.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.gitlab.token.label')}
+            htmlFor="gitlab-token"
+            description={t('settings.general.gitlab.token.helper')}
+          >
+            <Input
+              id="gitlab-token"
+              type="password"
+              placeholder={
+                draft?.gitlab.token
+                  ? t('settings.general.gitlab.token.stored')
+                  : t('settings.general.gitlab.token.placeholder')
+              }
+              value={secretInputs[GITLAB_TOKEN] ?? ''}
+              onChange={(e) => setSecretInput(GITLAB_TOKEN, e.target.value)}
+            />
+          </SettingsField>
+
+          <SettingsField
+            label={t('settings.general.gitlab.baseUrl.label')}
+            htmlFor="gitlab-base-url"
+            description={t('settings.general.gitlab.baseUrl.helper')}
+          >
+            <Input
+              id="gitlab-base-url"
+              type="text"
+              placeholder="https://gitlab.com"
+              value={draft?.gitlab.base_url ?? ''}
+              onChange={(e) =>
+                updateDraft({
+                  gitlab: { ...draft!.gitlab, base_url: e.target.value },
+                })
+              }
+            />
+          </SettingsField>
+        </div>
+
+        {/* Bitbucket */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.bitbucket.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.bitbucket.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.bitbucket.username.label')}
+            htmlFor="bitbucket-username"
+          >
+            <Input
+              id="bitbucket-username"
+              type="text"
+              value={draft?.bitbucket.username ?? ''}
+              onChange={(e) =>
+                updateDraft({
+                  bitbucket: {
+                    ...draft!.bitbucket,
+                    username: e.target.value || null,
+                  },
+                })
+              }
+            />
+          </SettingsField>
+
+          <SettingsField
+            label={t('settings.general.bitbucket.appPassword.label')}
+            htmlFor="bitbucket-app-password"
+            description={t('settings.general.bitbucket.appPassword.helper')}
+          >
+            <Input
+              id="bitbucket-app-password"
+              type="password"
+              placeholder={
+                draft?.bitbucket.app_password
+                  ? t('settings.general.bitbucket.appPassword.stored')
+                  : undefined
+              }
+              value={secretInputs[BITBUCKET_APP_PASSWORD] ?? ''}
+              onChange={(e) =>
+                setSecretInput(BITBUCKET_APP_PASSWORD, e.target.value)
+              }
+            />
+          </SettingsField>
+        </div>
+
+        {/* Gitea */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.gitea.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.gitea.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.gitea.token.label')}
+            htmlFor="gitea-token"
+            description={t('settings.general.gitea.token.helper')}
+          >
+            <Input
+              id="gitea-token"
+              type="password"
+              placeholder={
+                draft?.gitea.token
+                  ? t('settings.general.gitea.token.stored')
+                  : undefined
+              }
+              value={secretInputs[GITEA_TOKEN] ?? ''}
+              onChange={(e) => setSecretInput(GITEA_TOKEN, e.target.value)}
+            />
+          </SettingsField>
+
+          <SettingsField
+            label={t('settings.general.gitea.baseUrl.label')}
+            htmlFor="gitea-base-url"
+            description={t('settings.general.gitea.baseUrl.helper')}
+          >
+            <Input
+              id="gitea-base-url"
+              type="text"
+              placeholder="https://gitea.example.com"
+              value={draft?.gitea.base_url ?? ''}
+              onChange={(e) =>
+                updateDraft({
+                  gitea: { ...draft!.gitea, base_url: e.target.value || null },
+                })
+              }
+            />
+          </SettingsField>
+        </div>
+
+        {/* Linear */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.linear.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.linear.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.linear.apiKey.label')}
+            htmlFor="linear-api-key"
+            description={t('settings.general.linear.apiKey.helper')}
+          >
+            <Input
+              id="linear-api-key"
+              type="password"
+              placeholder={
+                draft?.linear.api_key
+                  ? t('settings.general.linear.apiKey.stored')
+                  : undefined
+              }
+              value={secretInputs[LINEAR_API_KEY] ?? ''}
+              onChange={(e) => setSecretInput(LINEAR_API_KEY, e.target.value)}
+            />
+          </SettingsField>
+        </div>
+
         {/* Observability */}
         <div className="pt-4 border-t space-y-4">
           <div>
@@ -1137,10 +1686,14 @@ export function GeneralSettings() {
                 <Input
                   id="langfuse-secret-key"
                   type="password"
-                  placeholder={t('settings.general.observability.langfuse.secretKey.placeholder')}
-                  value={draft?.langfuse_secret_key ?? ''}
+                  placeholder={
+                    draft?.langfuse_secret_key
+                      ? t('settings.general.observability.langfuse.secretKey.stored')
+                      : t('settings.general.observability.langfuse.secretKey.placeholder')
+                  }
+                  value={secretInputs[LANGFUSE_SECRET_KEY] ?? ''}
                   onChange={(e) =>
-                    updateDraft({ langfuse_secret_key: e.target.value || null })
+                    setSecretInput(LANGFUSE_SECRET_KEY, e.target.value)
                   }
                 />
               </SettingsField>
@@ -1205,6 +1758,34 @@ export function GeneralSettings() {
           <TagManager />
         </div>
 
+        {/* Connection */}
+        <div className="pt-4 border-t space-y-4">
+          <div>
+            <Text size="sm" className="font-medium">
+              {t('settings.general.connection.title')}
+            </Text>
+            <Text variant="secondary" size="sm" as="p">
+              {t('settings.general.connection.description')}
+            </Text>
+          </div>
+
+          <SettingsField
+            label={t('settings.general.connection.sse.label')}
+            htmlFor="stream-transport-sse"
+            description={t('settings.general.connection.sse.helper')}
+            layout="horizontal"
+          >
+            <Checkbox
+              id="stream-transport-sse"
+              checked={sseEnabled}
+              onCheckedChange={(checked: boolean) => {
+                setStreamTransport(checked ? 'sse' : 'websocket');
+                setSseEnabled(checked);
+              }}
+            />
+          </SettingsField>
+        </div>
+
         {/* Safety */}
         <div className="pt-4 border-t space-y-4">
           <div>
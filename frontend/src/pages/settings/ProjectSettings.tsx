@@ -20,6 +20,7 @@ import { Text } from '@/components/ui/text';
 import { ListItem } from '@/components/ui/list-item';
 import { SettingsSection } from '@/components/settings/SettingsSection';
 import { SettingsField } from '@/components/settings/SettingsField';
+import { ProjectRedactionPatternsSection } from '@/components/settings/ProjectRedactionPatternsSection';
 import { SkeletonForm, SkeletonList } from '@/components/ui/loading-states';
 import { FolderGit2, Loader2, Plus, Trash2, Users } from 'lucide-react';
 import { useProjects } from '@/hooks/useProjects';
@@ -32,13 +33,39 @@ import { TaskGroupFormDialog } from '@/components/dialogs';
 import { RepoPickerDialog } from '@/components/dialogs/shared/RepoPickerDialog';
 import { projectsApi } from '@/lib/api';
 import { repoBranchKeys } from '@/hooks/useRepoBranches';
-import type { Project, ProjectRepo, Repo, UpdateProject } from 'shared/types';
+import type {
+  DevShell,
+  Project,
+  ProjectRepo,
+  Repo,
+  ScriptShell,
+  UpdateProject,
+} from 'shared/types';
+
+const DEFAULT_SHELL = 'default';
+const SCRIPT_SHELLS: ScriptShell[] = ['bash', 'zsh', 'sh', 'powershell', 'cmd'];
+const NO_DEV_SHELL = 'none';
+const DEV_SHELLS: DevShell[] = [
+  'auto',
+  'nix',
+  'devenv',
+  'custom',
+  'devcontainer',
+  'docker',
+];
 
 interface ProjectFormState {
   name: string;
   dev_script: string;
   dev_script_working_dir: string;
   default_agent_working_dir: string;
+  script_shell: ScriptShell | null;
+  inherit_login_env: boolean;
+  dev_shell: DevShell | null;
+  dev_shell_command: string;
+  container_image: string;
+  container_network: string;
+  max_concurrent_executions: string;
 }
 
 interface RepoScriptsFormState {
@@ -46,6 +73,7 @@ interface RepoScriptsFormState {
   parallel_setup_script: boolean;
   cleanup_script: string;
   copy_files: string;
+  post_copy_script: string;
   merge_target_branch: string;
 }
 
@@ -55,6 +83,14 @@ function projectToFormState(project: Project): ProjectFormState {
     dev_script: project.dev_script ?? '',
     dev_script_working_dir: project.dev_script_working_dir ?? '',
     default_agent_working_dir: project.default_agent_working_dir ?? '',
+    script_shell: project.script_shell,
+    inherit_login_env: project.inherit_login_env,
+    dev_shell: project.dev_shell,
+    dev_shell_command: project.dev_shell_command ?? '',
+    container_image: project.container_image ?? '',
+    container_network: project.container_network ?? '',
+    max_concurrent_executions:
+      project.max_concurrent_executions?.toString() ?? '',
   };
 }
 
@@ -66,6 +102,7 @@ function projectRepoToScriptsFormState(
     parallel_setup_script: projectRepo?.parallel_setup_script ?? false,
     cleanup_script: projectRepo?.cleanup_script ?? '',
     copy_files: projectRepo?.copy_files ?? '',
+    post_copy_script: projectRepo?.post_copy_script ?? '',
     merge_target_branch: projectRepo?.merge_target_branch ?? '',
   };
 }
@@ -435,6 +472,14 @@ export function ProjectSettings() {
         dev_script_working_dir: draft.dev_script_working_dir.trim() || null,
         default_agent_working_dir:
           draft.default_agent_working_dir.trim() || null,
+        script_shell: draft.script_shell,
+        inherit_login_env: draft.inherit_login_env,
+        dev_shell: draft.dev_shell,
+        dev_shell_command: draft.dev_shell_command.trim() || null,
+        container_image: draft.container_image.trim() || null,
+        container_network: draft.container_network.trim() || null,
+        max_concurrent_executions:
+          parseInt(draft.max_concurrent_executions, 10) || null,
       };
 
       updateProject.mutate({
@@ -463,6 +508,7 @@ export function ProjectSettings() {
           setup_script: scriptsDraft.setup_script.trim() || null,
           cleanup_script: scriptsDraft.cleanup_script.trim() || null,
           copy_files: scriptsDraft.copy_files.trim() || null,
+          post_copy_script: scriptsDraft.post_copy_script.trim() || null,
           parallel_setup_script: scriptsDraft.parallel_setup_script,
           merge_target_branch: scriptsDraft.merge_target_branch.trim() || null,
         }
@@ -669,6 +715,170 @@ export function ProjectSettings() {
                 />
               </SettingsField>
 
+              <SettingsField
+                label={t('settings.projects.scripts.shell.label')}
+                description={t('settings.projects.scripts.shell.helper')}
+                htmlFor="script-shell"
+              >
+                <Select
+                  value={draft.script_shell ?? DEFAULT_SHELL}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      script_shell:
+                        value === DEFAULT_SHELL ? null : (value as ScriptShell),
+                    })
+                  }
+                >
+                  <SelectTrigger id="script-shell">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={DEFAULT_SHELL}>
+                      {t('settings.projects.scripts.shell.default')}
+                    </SelectItem>
+                    {SCRIPT_SHELLS.map((shell) => (
+                      <SelectItem key={shell} value={shell}>
+                        {shell}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </SettingsField>
+
+              <div className="flex items-center space-x-2">
+                <Checkbox
+                  id="inherit-login-env"
+                  checked={draft.inherit_login_env}
+                  onCheckedChange={(checked) =>
+                    updateDraft({ inherit_login_env: checked === true })
+                  }
+                />
+                <Label
+                  htmlFor="inherit-login-env"
+                  className="text-sm font-normal cursor-pointer"
+                >
+                  {t('settings.projects.scripts.shell.loginEnvLabel')}
+                </Label>
+              </div>
+              <Text variant="secondary" size="sm" as="p" className="pl-6">
+                {t('settings.projects.scripts.shell.loginEnvHelper')}
+              </Text>
+
+              <SettingsField
+                label={t('settings.projects.scripts.devShell.label')}
+                description={t('settings.projects.scripts.devShell.helper')}
+                htmlFor="dev-shell"
+              >
+                <Select
+                  value={draft.dev_shell ?? NO_DEV_SHELL}
+                  onValueChange={(value) =>
+                    updateDraft({
+                      dev_shell:
+                        value === NO_DEV_SHELL ? null : (value as DevShell),
+                    })
+                  }
+                >
+                  <SelectTrigger id="dev-shell">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value={NO_DEV_SHELL}>
+                      {t('settings.projects.scripts.devShell.none')}
+                    </SelectItem>
+                    {DEV_SHELLS.map((shell) => (
+                      <SelectItem key={shell} value={shell}>
+                        {t(
+                          `settings.projects.scripts.devShell.options.${shell}`
+                        )}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+              </SettingsField>
+
+              {draft.dev_shell &&
+                draft.dev_shell !== 'devcontainer' &&
+                draft.dev_shell !== 'docker' && (
+                  <SettingsField
+                    label={t('settings.projects.scripts.devShell.commandLabel')}
+                    description={t(
+                      'settings.projects.scripts.devShell.commandHelper'
+                    )}
+                    htmlFor="dev-shell-command"
+                  >
+                    <Input
+                      id="dev-shell-command"
+                      value={draft.dev_shell_command}
+                      onChange={(e) =>
+                        updateDraft({ dev_shell_command: e.target.value })
+                      }
+                      placeholder={t(
+                        'settings.projects.scripts.devShell.commandPlaceholder'
+                      )}
+                      className="font-mono"
+                    />
+                  </SettingsField>
+                )}
+
+              {draft.dev_shell === 'docker' && (
+                <>
+                  <SettingsField
+                    label={t('settings.projects.scripts.devShell.imageLabel')}
+                    description={t(
+                      'settings.projects.scripts.devShell.imageHelper'
+                    )}
+                    htmlFor="container-image"
+                  >
+                    <Input
+                      id="container-image"
+                      value={draft.container_image}
+                      onChange={(e) =>
+                        updateDraft({ container_image: e.target.value })
+                      }
+                      placeholder="node:22"
+                      className="font-mono"
+                    />
+                  </SettingsField>
+                  <SettingsField
+                    label={t(
+                      'settings.projects.scripts.devShell.networkLabel'
+                    )}
+                    description={t(
+                      'settings.projects.scripts.devShell.networkHelper'
+                    )}
+                    htmlFor="container-network"
+                  >
+                    <Input
+                      id="container-network"
+                      value={draft.container_network}
+                      onChange={(e) =>
+                        updateDraft({ container_network: e.target.value })
+                      }
+                      placeholder="host"
+                      className="font-mono"
+                    />
+                  </SettingsField>
+                </>
+              )}
+
+              <SettingsField
+                label={t('settings.projects.concurrency.label')}
+                description={t('settings.projects.concurrency.helper')}
+                htmlFor="max-concurrent-executions"
+              >
+                <Input
+                  id="max-concurrent-executions"
+                  type="number"
+                  min={1}
+                  value={draft.max_concurrent_executions}
+                  onChange={(e) =>
+                    updateDraft({ max_concurrent_executions: e.target.value })
+                  }
+                  placeholder={t('settings.projects.concurrency.placeholder')}
+                  className="w-32"
+                />
+              </SettingsField>
+
               {/* Save Button */}
               <div className="flex items-center justify-between pt-4 border-t">
                 {hasUnsavedProjectChanges ? (
@@ -1041,6 +1251,26 @@ export function ProjectSettings() {
                         />
                       </SettingsField>
 
+                      <SettingsField
+                        label={t('settings.projects.scripts.postCopy.label')}
+                        description={t(
+                          'settings.projects.scripts.postCopy.helper'
+                        )}
+                        htmlFor="post-copy-script"
+                      >
+                        <AutoExpandingTextarea
+                          id="post-copy-script"
+                          value={scriptsDraft.post_copy_script}
+                          onChange={(e) =>
+                            updateScriptsDraft({
+                              post_copy_script: e.target.value,
+                            })
+                          }
+                          maxRows={12}
+                          className="w-full px-3 py-2 border border-input bg-background text-foreground rounded-md focus:outline-none focus:ring-2 focus:ring-ring font-mono"
+                        />
+                      </SettingsField>
+
                       {/* Scripts Save Buttons */}
                       <div className="flex items-center justify-between pt-4 border-t">
                         {hasUnsavedScriptsChanges ? (
@@ -1080,6 +1310,8 @@ export function ProjectSettings() {
             </div>
           </SettingsSection>
 
+          <ProjectRedactionPatternsSection projectId={selectedProject.id} />
+
           {/* Sticky Save Button for Project Name */}
           {hasUnsavedProjectChanges && (
             <div className="sticky bottom-0 z-10 bg-background/80 backdrop-blur-sm border-t py-4">
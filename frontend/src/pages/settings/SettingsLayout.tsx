@@ -1,6 +1,6 @@
 import { NavLink, Outlet } from 'react-router-dom';
 import { useTranslation } from 'react-i18next';
-import { Settings, Cpu, Server, X, FolderOpen, Building2, Github, Terminal } from 'lucide-react';
+import { Settings, Cpu, Server, X, FolderOpen, Building2, Github, Terminal, Users } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { Button } from '@/components/ui/button';
 import { useEffect } from 'react';
@@ -34,6 +34,10 @@ const settingsNavigation = [
     path: 'mcp',
     icon: Server,
   },
+  {
+    path: 'users',
+    icon: Users,
+  },
   {
     path: 'server-logs',
     icon: Terminal,
@@ -0,0 +1,274 @@
+import { FormEvent, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from '@/components/ui/card';
+import { Alert, AlertDescription } from '@/components/ui/alert';
+import { Badge } from '@/components/ui/badge';
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { Loader2, LogOut, Trash2, UserPlus } from 'lucide-react';
+import {
+  useCreateUser,
+  useDeleteUser,
+  useLogout,
+  useSession,
+  useUsers,
+} from '@/hooks/useUsers';
+import type { User } from 'shared/types';
+
+export function UsersSettings() {
+  const { t } = useTranslation('settings');
+  const [username, setUsername] = useState('');
+  const [displayName, setDisplayName] = useState('');
+  const [password, setPassword] = useState('');
+  const [isAdmin, setIsAdmin] = useState(false);
+  const [setupToken, setSetupToken] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const { data: session } = useSession();
+  const { data: users, isLoading } = useUsers();
+  const createUser = useCreateUser();
+  const deleteUser = useDeleteUser();
+  const logout = useLogout();
+
+  const currentUserId = session?.user?.id;
+  const accountsEnabled = session?.accounts_enabled ?? false;
+  const currentUserIsAdmin = session?.user?.is_admin ?? false;
+
+  const handleCreate = async (e: FormEvent) => {
+    e.preventDefault();
+    setError(null);
+    try {
+      await createUser.mutateAsync({
+        username: username.trim(),
+        display_name: displayName.trim() || null,
+        password,
+        is_admin: isAdmin,
+        setup_token: accountsEnabled ? null : setupToken.trim(),
+      });
+      setUsername('');
+      setDisplayName('');
+      setPassword('');
+      setIsAdmin(false);
+      setSetupToken('');
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.users.errors.createFailed')
+      );
+    }
+  };
+
+  const handleDelete = async (user: User) => {
+    const confirmed = window.confirm(
+      t('settings.users.confirmDelete', { username: user.username })
+    );
+    if (!confirmed) return;
+
+    setError(null);
+    try {
+      await deleteUser.mutateAsync(user.id);
+    } catch (err) {
+      setError(
+        err instanceof Error
+          ? err.message
+          : t('settings.users.errors.deleteFailed')
+      );
+    }
+  };
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center py-8">
+        <Loader2 className="h-8 w-8 animate-spin" />
+        <span className="ml-2">{t('settings.users.loading')}</span>
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-6">
+      {error && (
+        <Alert variant="destructive">
+          <AlertDescription>{error}</AlertDescription>
+        </Alert>
+      )}
+
+      <Card>
+        <CardHeader>
+          <div className="flex items-center justify-between">
+            <div>
+              <CardTitle>{t('settings.users.title')}</CardTitle>
+              <CardDescription>
+                {accountsEnabled
+                  ? t('settings.users.description')
+                  : t('settings.users.singleUserDescription')}
+              </CardDescription>
+            </div>
+            {session?.user && (
+              <Button
+                variant="outline"
+                onClick={() => logout.mutate()}
+                disabled={logout.isPending}
+              >
+                <LogOut className="h-4 w-4 mr-2" />
+                {t('settings.users.signOut')}
+              </Button>
+            )}
+          </div>
+        </CardHeader>
+        <CardContent className="space-y-2">
+          {users?.length === 0 && (
+            <p className="text-sm text-muted-foreground">
+              {t('settings.users.empty')}
+            </p>
+          )}
+          {users?.map((user) => (
+            <div
+              key={user.id}
+              className="flex items-center justify-between p-3 border rounded-md"
+            >
+              <div className="flex items-center gap-2">
+                <span className="font-medium">
+                  {user.display_name ?? user.username}
+                </span>
+                {user.display_name && (
+                  <span className="text-sm text-muted-foreground">
+                    {user.username}
+                  </span>
+                )}
+                {user.is_admin && (
+                  <Badge variant="outline">{t('settings.users.admin')}</Badge>
+                )}
+                {user.id === currentUserId && (
+                  <Badge variant="secondary">{t('settings.users.you')}</Badge>
+                )}
+              </div>
+              {currentUserIsAdmin && (
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => handleDelete(user)}
+                  disabled={deleteUser.isPending}
+                  title={t('settings.users.delete')}
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              )}
+            </div>
+          ))}
+        </CardContent>
+      </Card>
+
+      {(!accountsEnabled || currentUserIsAdmin) && (
+        <Card>
+          <CardHeader>
+            <CardTitle>{t('settings.users.create.title')}</CardTitle>
+            <CardDescription>
+              {accountsEnabled
+                ? t('settings.users.create.description')
+                : t('settings.users.create.firstUserDescription')}
+            </CardDescription>
+          </CardHeader>
+          <CardContent>
+            <form onSubmit={handleCreate} className="space-y-4">
+              <div className="space-y-2">
+                <Label htmlFor="new-user-username">
+                  {t('settings.users.create.username')}
+                </Label>
+                <Input
+                  id="new-user-username"
+                  autoComplete="off"
+                  value={username}
+                  onChange={(e) => setUsername(e.target.value)}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="new-user-display-name">
+                  {t('settings.users.create.displayName')}
+                </Label>
+                <Input
+                  id="new-user-display-name"
+                  autoComplete="off"
+                  value={displayName}
+                  onChange={(e) => setDisplayName(e.target.value)}
+                />
+              </div>
+              <div className="space-y-2">
+                <Label htmlFor="new-user-password">
+                  {t('settings.users.create.password')}
+                </Label>
+                <Input
+                  id="new-user-password"
+                  type="password"
+                  autoComplete="new-password"
+                  value={password}
+                  onChange={(e) => setPassword(e.target.value)}
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.users.create.passwordHelper')}
+                </p>
+              </div>
+              {accountsEnabled ? (
+                <div className="flex items-start space-x-2">
+                  <Checkbox
+                    id="new-user-admin"
+                    checked={isAdmin}
+                    onCheckedChange={setIsAdmin}
+                  />
+                  <div className="space-y-0.5">
+                    <Label htmlFor="new-user-admin" className="cursor-pointer">
+                      {t('settings.users.create.admin')}
+                    </Label>
+                    <p className="text-sm text-muted-foreground">
+                      {t('settings.users.create.adminHelper')}
+                    </p>
+                  </div>
+                </div>
+              ) : (
+                <div className="space-y-2">
+                  <Label htmlFor="new-user-setup-token">
+                    {t('settings.users.create.setupToken')}
+                  </Label>
+                  <Input
+                    id="new-user-setup-token"
+                    autoComplete="off"
+                    value={setupToken}
+                    onChange={(e) => setSetupToken(e.target.value)}
+                  />
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.users.create.setupTokenHelper')}
+                  </p>
+                </div>
+              )}
+              <Button
+                type="submit"
+                disabled={
+                  !username.trim() ||
+                  !password ||
+                  (!accountsEnabled && !setupToken.trim()) ||
+                  createUser.isPending
+                }
+              >
+                {createUser.isPending ? (
+                  <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+                ) : (
+                  <UserPlus className="h-4 w-4 mr-2" />
+                )}
+                {t('settings.users.create.submit')}
+              </Button>
+            </form>
+          </CardContent>
+        </Card>
+      )}
+    </div>
+  );
+}
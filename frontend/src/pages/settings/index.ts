@@ -5,3 +5,4 @@ export { OrganizationSettings } from './OrganizationSettings';
 export { GitHubSettings } from './GitHubSettings';
 export { AgentSettings } from './AgentSettings';
 export { McpSettings } from './McpSettings';
+export { UsersSettings } from './UsersSettings';
@@ -1,6 +1,7 @@
-// streamJsonPatchEntries.ts - WebSocket JSON patch streaming utility
+// streamJsonPatchEntries.ts - JSON patch streaming utility
 import { applyPatch, type Operation } from 'rfc6902';
 import { getApiBaseUrlSync } from '@/lib/api';
+import { openStreamSocket } from '@/lib/streamSocket';
 
 type PatchContainer<E = unknown> = { entries: E[] };
 
@@ -51,13 +52,12 @@ export function streamJsonPatchEntries<E = unknown>(
   const subscribers = new Set<(entries: E[]) => void>();
   if (opts.onEntries) subscribers.add(opts.onEntries);
 
-  // Build full WebSocket URL (prepend base URL for Tauri where server runs on dynamic port)
+  // Build full URL (prepend base URL for Tauri where server runs on dynamic port)
   let fullUrl = url;
   if (url.startsWith('/')) {
     fullUrl = getApiBaseUrlSync() + url;
   }
-  const wsUrl = fullUrl.replace(/^http/, 'ws');
-  const ws = new WebSocket(wsUrl);
+  const ws = openStreamSocket(fullUrl);
 
   const notify = () => {
     for (const cb of subscribers) {
@@ -117,21 +117,21 @@ export function streamJsonPatchEntries<E = unknown>(
     }
   };
 
-  ws.addEventListener('open', () => {
+  ws.onopen = () => {
     connected = true;
     opts.onConnect?.();
-  });
+  };
 
-  ws.addEventListener('message', handleMessage);
+  ws.onmessage = handleMessage;
 
-  ws.addEventListener('error', (err) => {
+  ws.onerror = (err) => {
     connected = false;
     opts.onError?.(err);
-  });
+  };
 
-  ws.addEventListener('close', () => {
+  ws.onclose = () => {
     connected = false;
-  });
+  };
 
   return {
     getEntries(): E[] {
@@ -12,640 +12,2471 @@ export type SharedTask = { id: string, organization_id: string, project_id: stri
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
 
-export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Shell for project scripts; `None` uses the platform default
+ */
+script_shell: ScriptShell | null, 
+/**
+ * Inject the user's login-shell environment into spawned commands
+ */
+inherit_login_env: boolean, 
+/**
+ * Nix/devenv shell every execution is wrapped in; `None` runs commands directly
+ */
+dev_shell: DevShell | null, 
+/**
+ * Command prefix replacing the dev shell's default, e.g. `nix develop .#ci --command`
+ */
+dev_shell_command: string | null, 
+/**
+ * Image `Docker` dev shell executions run in, e.g. `node:22`; `None` uses
+ * the workspace's devcontainer.json
+ */
+container_image: string | null, 
+/**
+ * Docker network the project's image runs on, e.g. `bridge` or a user
+ * network; `None` uses the host's so dev servers stay reachable
+ */
+container_network: string | null, 
+/**
+ * Coding agents and scripts that may run at once for this project, on
+ * top of the global limit; `None` means no per-project limit
+ */
+max_concurrent_executions: number | null, 
+/**
+ * Set while the project is archived; background workers skip it and its tasks
+ */
+archived_at: Date | null, created_at: Date, updated_at: Date, };
 
 export type ProjectTaskCounts = { inprogress: bigint, inreview: bigint, };
 
-export type ProjectWithTaskCounts = { task_counts: ProjectTaskCounts, id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type ProjectWithTaskCounts = { task_counts: ProjectTaskCounts, id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Shell for project scripts; `None` uses the platform default
+ */
+script_shell: ScriptShell | null, 
+/**
+ * Inject the user's login-shell environment into spawned commands
+ */
+inherit_login_env: boolean, 
+/**
+ * Nix/devenv shell every execution is wrapped in; `None` runs commands directly
+ */
+dev_shell: DevShell | null, 
+/**
+ * Command prefix replacing the dev shell's default, e.g. `nix develop .#ci --command`
+ */
+dev_shell_command: string | null, 
+/**
+ * Image `Docker` dev shell executions run in, e.g. `node:22`; `None` uses
+ * the workspace's devcontainer.json
+ */
+container_image: string | null, 
+/**
+ * Docker network the project's image runs on, e.g. `bridge` or a user
+ * network; `None` uses the host's so dev servers stay reachable
+ */
+container_network: string | null, 
+/**
+ * Coding agents and scripts that may run at once for this project, on
+ * top of the global limit; `None` means no per-project limit
+ */
+max_concurrent_executions: number | null, 
+/**
+ * Set while the project is archived; background workers skip it and its tasks
+ */
+archived_at: Date | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, script_shell: ScriptShell | null, inherit_login_env: boolean | null, dev_shell: DevShell | null, dev_shell_command: string | null, container_image: string | null, container_network: string | null, max_concurrent_executions: number | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
-
-export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, merge_target_branch: string | null, };
-
-export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
-
-export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, merge_target_branch: string | null, };
+export type Repo = { id: string, path: string, name: string, display_name: string, 
+/**
+ * Runs in this repo's worktree before the coding agent starts, in every
+ * project that does not set its own setup script for the repo
+ */
+setup_script: string | null, 
+/**
+ * Runs after the coding agent finishes, unless the project overrides it
+ */
+cleanup_script: string | null, 
+/**
+ * Don't run `git submodule update` in new worktrees of this repo
+ */
+skip_submodules: boolean, 
+/**
+ * Don't run `git lfs pull` in new worktrees of this repo
+ */
+skip_lfs: boolean, created_at: Date, updated_at: Date, };
 
-export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+export type UpdateRepoScripts = { setup_script: string | null, cleanup_script: string | null, };
 
-export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+export type UpdateRepoCheckoutOptions = { skip_submodules: boolean, skip_lfs: boolean, };
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, post_copy_script: string | null, parallel_setup_script: boolean, merge_target_branch: string | null, };
 
-export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
+export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
-export type CreateTag = { tag_name: string, content: string, };
+export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, post_copy_script: string | null, parallel_setup_script: boolean | null, merge_target_branch: string | null, };
 
-export type UpdateTag = { tag_name: string | null, content: string | null, };
+export type ProjectEnvFile = { project_id: string, template: string, created_at: Date, updated_at: Date, };
 
-export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+export type UpsertProjectEnvFile = { template: string, };
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, created_at: string, updated_at: string, is_blocked: boolean, has_in_progress_attempt: boolean, last_attempt_failed: boolean, is_queued: boolean, last_executor: string, needs_attention: boolean | null, };
+export type ProjectSecret = { id: string, project_id: string, name: string, created_at: Date, updated_at: Date, };
 
-export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, created_at: string, updated_at: string, is_blocked: boolean, has_in_progress_attempt: boolean, last_attempt_failed: boolean, is_queued: boolean, last_executor: string, needs_attention: boolean | null, };
+export type UpsertProjectSecret = { value: string, };
 
-export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+/**
+ * A repository a project template adds to its projects
+ */
+export type ProjectTemplateRepo = { display_name: string, 
+/**
+ * Where the repository lives. Relative paths are under the default
+ * clone directory.
+ */
+path: string, 
+/**
+ * Cloned into `path` when nothing is there yet
+ */
+url: string | null, setup_script: string | null, 
+/**
+ * Comma-separated globs copied into new worktrees
+ */
+copy_files: string | null, };
 
-export type EmbeddingStatus = { task_id: string, needs_embedding: boolean, last_embedded_at: string | null, embedding_model: string | null, };
+/**
+ * A task created in every project made from a template
+ */
+export type ProjectTemplateTask = { title: string, description: string | null, };
 
-export type TaskDependency = { id: string, task_id: string, depends_on_id: string, created_at: string, };
+/**
+ * Blueprint for new projects: their repositories, the tasks they start with
+ * and the coding agent to start those tasks with
+ */
+export type ProjectTemplate = { id: string, name: string, description: string | null, repositories: Array<ProjectTemplateRepo>, executor_profile_id: ExecutorProfileId | null, tasks: Array<ProjectTemplateTask>, labels: Array<string>, created_at: Date, updated_at: Date, };
 
-export type TaskGroup = { id: string, project_id: string, name: string, description: string | null, base_branch: string | null, created_at: string, updated_at: string, };
+/**
+ * Settings of a new template, or all settings of an existing one
+ */
+export type UpsertProjectTemplate = { name: string, description: string | null, repositories: Array<ProjectTemplateRepo>, executor_profile_id: ExecutorProfileId | null, tasks: Array<ProjectTemplateTask>, labels: Array<string>, };
 
-export type TaskStatusCounts = { todo: bigint, inprogress: bigint, inreview: bigint, done: bigint, cancelled: bigint, };
+/**
+ * A project created from a template
+ */
+export type ProjectFromTemplate = { project: Project, 
+/**
+ * Display names of the repositories that were cloned for the project
+ */
+cloned_repositories: Array<string>, tasks: Array<Task>, 
+/**
+ * The template's coding agent, for starting the tasks
+ */
+executor_profile_id: ExecutorProfileId | null, };
 
-export type TaskGroupWithStats = { task_counts: TaskStatusCounts, id: string, project_id: string, name: string, description: string | null, base_branch: string | null, created_at: string, updated_at: string, };
+/**
+ * The project created from an archive
+ */
+export type ImportedProject = { project: Project, tasks: Array<Task>, };
 
-export type Notification = { id: string, project_id: string | null, notification_type: NotificationType, title: string, message: string, is_read: boolean, metadata: JsonValue | null, workspace_id: string | null, session_id: string | null, conversation_session_id: string | null, created_at: string, updated_at: string, };
+export type ProjectToolRequirement = { id: string, project_id: string, name: string, 
+/**
+ * Version requirement such as `20`, `>=1.80` or `^9.1`. Any version passes when unset.
+ */
+version: string | null, 
+/**
+ * Command that prints the tool's version. Defaults to `<name> --version`.
+ */
+check_command: string | null, created_at: Date, };
 
-export type NotificationType = "agent_complete" | "agent_approval_needed" | "agent_question_needed" | "agent_error" | "conversation_response";
+export type CreateToolRequirement = { name: string, version: string | null, check_command: string | null, };
 
-export type CreateNotification = { project_id: string | null, notification_type: NotificationType, title: string, message: string, metadata: JsonValue | null, workspace_id: string | null, session_id: string | null, conversation_session_id: string | null, };
+export type BranchCleanupMode = "off" | "delete" | "archive";
 
-export type UpdateNotification = { title: string | null, message: string | null, is_read: boolean | null, metadata: JsonValue | null, };
+export type BranchCleanupPolicy = { project_id: string, mode: BranchCleanupMode, 
+/**
+ * Days a merge must be confirmed for before its branch is touched
+ */
+min_age_days: number, updated_at: Date, };
 
-export type NotificationStats = { total: bigint, unread: bigint, };
+export type UpdateBranchCleanupPolicy = { mode: BranchCleanupMode, min_age_days: number, };
 
-export type AgentFeedback = { id: string, execution_process_id: string, task_id: string, workspace_id: string, feedback_json: string | null, collected_at: string, created_at: string, updated_at: string, };
+/**
+ * How repo-local git hooks are treated when vibe-kanban commits
+ */
+export type GitHooksMode = "run" | "skip" | "report";
 
-export type CreateAgentFeedback = { execution_process_id: string, task_id: string, workspace_id: string, feedback_json: string | null, };
+export type GitHooksPolicy = { project_id: string, mode: GitHooksMode, 
+/**
+ * Run `pre-commit` on the agent's changed files after each successful
+ * run in repos that have a `.pre-commit-config.yaml`
+ */
+run_pre_commit: boolean, updated_at: Date, };
 
-export type ReviewAttention = { id: string, execution_process_id: string, task_id: string, workspace_id: string, needs_attention: boolean, reasoning: string | null, analyzed_at: string, created_at: string, updated_at: string, };
+export type UpdateGitHooksPolicy = { mode: GitHooksMode, run_pre_commit: boolean, };
 
-export type CreateReviewAttention = { execution_process_id: string, task_id: string, workspace_id: string, needs_attention: boolean, reasoning: string | null, };
+export type PrDraftPolicy = { project_id: string, 
+/**
+ * Open task attempt PRs as drafts unless the request says otherwise
+ */
+draft_by_default: boolean, 
+/**
+ * Mark draft PRs ready for review once the review-attention analysis
+ * finds the task needs no attention
+ */
+auto_ready: boolean, updated_at: Date, };
 
-export type FeedbackResponse = { id: string, task_id: string, workspace_id: string, execution_process_id: string, feedback: JsonValue | null, collected_at: string, };
+export type UpdatePrDraftPolicy = { draft_by_default: boolean, auto_ready: boolean, };
 
-export type GanttTask = { id: string, name: string, start: string, end: string, progress: number, dependencies: Array<string>, task_status: TaskStatus, task_group_id: string | null, total_input_tokens: bigint | null, total_output_tokens: bigint | null, token_usage_metadata: JsonValue | null, };
+/**
+ * Checks the merge queue requires to pass before it merges a branch
+ */
+export type MergeCheckPolicy = { project_id: string, 
+/**
+ * Shell command run in the rebased worktree; a non-zero exit fails the
+ * entry
+ */
+check_command: string | null, 
+/**
+ * Every GitHub check run on the pushed task branch must have passed
+ */
+require_github_checks: boolean, timeout_secs: number, updated_at: Date, };
 
-export type PaginatedGanttTasks = { tasks: Array<GanttTask>, total: bigint, hasMore: boolean, };
+export type UpdateMergeCheckPolicy = { check_command: string | null, require_github_checks: boolean, timeout_secs: number | null, };
 
-export type CreateTaskGroup = { project_id: string, name: string, description: string | null, base_branch: string | null, };
+export type FileRuleEffect = "deny" | "allow_only";
 
-export type UpdateTaskGroup = { name: string | null, description: string | null, base_branch: string | null, };
+/**
+ * A file pattern the agent may or may not change, e.g. never `*.sql`
+ */
+export type ProjectFileRule = { id: string, project_id: string, 
+/**
+ * Gitignore-style pattern relative to the repo root
+ */
+pattern: string, effect: FileRuleEffect, 
+/**
+ * Only applies to tasks tagged `#<task_label>` in their title or
+ * description; applies to every task when unset
+ */
+task_label: string | null, 
+/**
+ * Told to the agent when it breaks the rule
+ */
+reason: string | null, created_at: Date, };
 
-export type ConversationSession = { id: string, project_id: string, title: string, status: ConversationSessionStatus, executor: string | null, worktree_path: string | null, worktree_branch: string | null, created_at: string, updated_at: string, };
+export type CreateFileRule = { pattern: string, effect: FileRuleEffect, task_label: string | null, reason: string | null, };
 
-export type ConversationSessionStatus = "active" | "archived";
+export type ProjectMemoryKind = "fact" | "preference";
 
-export type CreateConversationSession = { project_id: string, title: string, executor: string | null, worktree_path: string | null, worktree_branch: string | null, };
+/**
+ * Whether a project collects memories and how many go into each prompt
+ */
+export type ProjectMemorySettings = { project_id: string, 
+/**
+ * Extract memories from finished task attempts and add them to prompts
+ */
+enabled: boolean, 
+/**
+ * How many of the most relevant memories are added to a prompt
+ */
+max_injected: number, updated_at: Date, };
 
-export type UpdateConversationSession = { title: string | null, status: ConversationSessionStatus | null, executor: string | null, };
+export type UpdateProjectMemorySettings = { enabled: boolean, max_injected: number, };
 
-export type ConversationMessage = { id: string, conversation_session_id: string, execution_process_id: string | null, role: MessageRole, content: string, metadata: string | null, created_at: string, updated_at: string, };
+/**
+ * A fact or preference remembered across a project's tasks and conversations
+ */
+export type ProjectMemory = { id: string, project_id: string, kind: ProjectMemoryKind, content: string, 
+/**
+ * Task whose attempt the memory was extracted from; null when written by hand
+ */
+source_task_id: string | null, created_at: Date, updated_at: Date, };
 
-export type ConversationMessagesPage = { messages: Array<ConversationMessage>, next_cursor: string | null, has_more: boolean, };
+export type CreateProjectMemory = { kind: ProjectMemoryKind, content: string, };
 
-export type MessageRole = "user" | "assistant";
+export type UpdateProjectMemory = { kind: ProjectMemoryKind, content: string, };
 
-export type CreateConversationMessage = { conversation_session_id: string, execution_process_id: string | null, role: MessageRole, content: string, metadata: string | null, };
+/**
+ * A project's sandbox for agent and script executions
+ */
+export type ProjectSandboxPolicy = { project_id: string, policy: SandboxPolicy, updated_at: Date, };
 
-export type ConversationWithMessages = { messages: Array<ConversationMessage>, id: string, project_id: string, title: string, status: ConversationSessionStatus, executor: string | null, worktree_path: string | null, worktree_branch: string | null, created_at: string, updated_at: string, };
+/**
+ * Regexes masked in a project's agent and script output
+ */
+export type ProjectRedactionPatterns = { project_id: string, patterns: Array<string>, updated_at: Date, };
 
-export type SendMessageResponse = { user_message: ConversationMessage, execution_process_id: string, };
+/**
+ * Tool calls an agent may make in a project without asking for approval.
+ * A tool call is approved automatically when its tool or shell command is
+ * allowed and it stays within the path and size limits.
+ */
+export type ApprovalPolicy = { id: string, project_id: string, 
+/**
+ * Executor this policy applies to; None for the project's default, used
+ * by executors without a policy of their own
+ */
+executor: BaseCodingAgent | null, 
+/**
+ * Tools approved without asking, by name (e.g. `Read`, `Grep`)
+ */
+allowed_tools: Array<string>, 
+/**
+ * Shell commands approved without asking, matched as a prefix on word
+ * boundaries (`cargo test` allows `cargo test -p db`)
+ */
+allowed_commands: Array<string>, 
+/**
+ * Gitignore-style patterns relative to the workspace. When set, a tool
+ * call touching a file is only approved automatically if every path
+ * matches one of them
+ */
+path_globs: Array<string>, 
+/**
+ * Largest content, in bytes, a tool call may write without asking
+ */
+max_file_size: number | null, created_at: Date, updated_at: Date, };
 
-export type CreateConversationRequest = { title: string, initial_message: string, executor_profile_id: ExecutorProfileId | null, worktree_path: string | null, worktree_branch: string | null, };
+export type UpsertApprovalPolicy = { executor: BaseCodingAgent | null, allowed_tools: Array<string>, allowed_commands: Array<string>, path_globs: Array<string>, max_file_size: number | null, };
 
-export type CreateConversationResponse = { session: ConversationSession, initial_message: ConversationMessage, execution_process_id: string, };
+export type PermissionFindingKind = "world_writable" | "setuid" | "symlink_escape";
 
-export type UpdateConversationRequest = { title: string | null, status: ConversationSessionStatus | null, };
+/**
+ * A permission problem an execution left in its workspace
+ */
+export type PermissionFinding = { id: string, execution_process_id: string, workspace_id: string, kind: PermissionFindingKind, 
+/**
+ * Path relative to the workspace
+ */
+path: string, 
+/**
+ * Mode bits, or the symlink's target
+ */
+detail: string, 
+/**
+ * Set once a user has looked at it; merging is blocked until then
+ */
+acknowledged_at: Date | null, created_at: Date, };
 
-export type SendMessageRequest = { content: string, variant: string | null, };
+export type ReviewCommentSide = "old" | "new";
 
-export type MergeTaskGroupRequest = { target_group_id: string, };
+/**
+ * A comment on a line range of a workspace's diff
+ */
+export type ReviewComment = { id: string, workspace_id: string, 
+/**
+ * Path as shown in the diff, prefixed with the repo name
+ */
+file_path: string, side: ReviewCommentSide, line_start: number, 
+/**
+ * Same as `line_start` for a single line
+ */
+line_end: number, 
+/**
+ * Markdown
+ */
+body: string, 
+/**
+ * Set by hand, or when the comment was sent to the agent
+ */
+resolved: boolean, created_at: Date, updated_at: Date, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, task_group_id: string | null, };
+export type CreateReviewComment = { file_path: string, side: ReviewCommentSide, line_start: number, 
+/**
+ * Defaults to `line_start`
+ */
+line_end: number | null, body: string, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, task_group_id: string | null, };
+export type UpdateReviewComment = { body: string | null, resolved: boolean | null, };
 
-export type DraftFollowUpData = { message: string, variant: string | null, };
+export type BisectStatus = "running" | "found" | "failed";
 
-export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData } | { "type": "DRAFT_CONVERSATION_MESSAGE", "data": DraftFollowUpData };
+/**
+ * What a tested commit was reported to `git bisect` as
+ */
+export type BisectVerdict = "good" | "bad" | "skip";
 
-export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP", DRAFT_CONVERSATION_MESSAGE = "DRAFT_CONVERSATION_MESSAGE" }
+/**
+ * A guided `git bisect` over a repo's good..bad range
+ */
+export type BisectRun = { id: string, repo_id: string, 
+/**
+ * Task the culprit is reported on
+ */
+task_id: string | null, good_commit: string, bad_commit: string, 
+/**
+ * Validation command; exit 0 is good, 125 untestable, 1-127 bad
+ */
+command: string, 
+/**
+ * Ask an agent to judge each step's output instead of trusting the exit code
+ */
+use_agent: boolean, status: BisectStatus, culprit_commit: string | null, error: string | null, created_at: Date, updated_at: Date, };
 
-export type Scratch = { id: string, payload: ScratchPayload, created_at: string, updated_at: string, };
+export type BisectStep = { id: string, run_id: string, commit_sha: string, 
+/**
+ * None when the command timed out or was killed by a signal
+ */
+exit_code: bigint | null, 
+/**
+ * Tail of the command's combined stdout and stderr
+ */
+output: string, verdict: BisectVerdict, 
+/**
+ * The verdict came from an agent reading the output
+ */
+agent_interpreted: boolean, created_at: Date, };
 
-export type CreateScratch = { payload: ScratchPayload, };
+/**
+ * Domain events a webhook can subscribe to
+ */
+export type WebhookEvent = "task_status_changed" | "execution_completed" | "workspace_created";
 
-export type UpdateScratch = { payload: ScratchPayload, };
+/**
+ * An outgoing webhook. The signing secret is kept in the secret store and
+ * never serialized; API responses only say whether one is set.
+ */
+export type Webhook = { id: string, 
+/**
+ * Only deliver events from this project; `None` delivers from every project
+ */
+project_id: string | null, name: string, url: string, has_secret: boolean, events: Array<WebhookEvent>, 
+/**
+ * JSON body with `{{path}}` placeholders into the default payload, e.g.
+ * `{"text": "{{data.task.title}} is now {{data.task.status}}"}`
+ */
+payload_template: string | null, enabled: boolean, last_delivery_at: Date | null, 
+/**
+ * Error from the most recent delivery; `None` when it succeeded
+ */
+last_delivery_error: string | null, created_at: Date, updated_at: Date, };
 
-export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+export type CreateWebhook = { project_id: string | null, name: string, url: string, secret: string | null, events: Array<WebhookEvent>, payload_template: string | null, enabled?: boolean, };
 
-export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
+/**
+ * Replaces a webhook's settings. `secret` is kept when `None` and cleared
+ * when empty.
+ */
+export type UpdateWebhook = { project_id: string | null, name: string, url: string, secret: string | null, events: Array<WebhookEvent>, payload_template: string | null, enabled: boolean, };
 
-export type GitHubSettingsStatus = { configured: boolean, };
+/**
+ * A reusable prompt. `{name}` placeholders in `content` are substituted
+ * with the variables of whatever uses the template.
+ */
+export type PromptTemplate = { id: string, name: string, description: string | null, content: string, created_at: Date, updated_at: Date, };
 
-export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type CreatePromptTemplate = { name: string, description: string | null, content: string, };
 
-export type WorkspaceWithSession = { session: Session | null, 
 /**
- * Executor name if workspace is queued for execution (populated from execution_queue)
+ * Replaces a template's name, description and content
  */
-queued_executor: string | null, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
-
-export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+export type UpdatePromptTemplate = { name: string, description: string | null, content: string, };
 
-export type ExecutionProcess = { id: string, 
 /**
- * Session ID for workspace-based executions (nullable for conversation-based)
+ * A person signing in to a shared server. The password hash and the
+ * preferences JSON are only read through dedicated queries.
  */
-session_id: string | null, 
+export type User = { id: string, username: string, display_name: string | null, 
 /**
- * Conversation session ID for disposable conversations (nullable for workspace-based)
+ * Admins create and delete accounts
  */
-conversation_session_id: string | null, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+is_admin: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateUser = { username: string, display_name: string | null, password: string, is_admin: boolean, 
 /**
- * dropped: true if this process is excluded from the current
- * history view (due to restore/trimming). Hidden from logs/timeline;
- * still listed in the Processes tab.
+ * Token printed on the server's stderr at startup, required to create
+ * the first account
  */
-dropped: boolean, input_tokens: bigint | null, output_tokens: bigint | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+setup_token: string | null, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "internalagent" | "disposableconversation";
+export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
-export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
 
-export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
+export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
-export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+export type CreateTag = { tag_name: string, content: string, };
 
-export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+export type UpdateTag = { tag_name: string | null, content: string | null, };
 
-export type MergeStatus = "open" | "merged" | "closed" | "unknown";
+export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+/**
+ * How urgently a task's queued executions should start. Higher priorities
+ * start first and may preempt lower ones when `preempt_lower_priority` is set.
+ */
+export type TaskPriority = "low" | "normal" | "high" | "urgent";
 
-export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "answered", answers: Array<QuestionAnswer>, } | { "status": "timed_out" };
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, created_at: string, updated_at: string, is_blocked: boolean, has_in_progress_attempt: boolean, last_attempt_failed: boolean, is_queued: boolean, last_executor: string, needs_attention: boolean | null, priority: TaskPriority, 
+/**
+ * The user who created the task, when the server has accounts
+ */
+created_by: string | null, 
+/**
+ * Set while the task is archived; background workers skip it
+ */
+archived_at: string | null, };
 
-export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
+export type TaskWithAttemptStatus = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, created_at: string, updated_at: string, is_blocked: boolean, has_in_progress_attempt: boolean, last_attempt_failed: boolean, is_queued: boolean, last_executor: string, needs_attention: boolean | null, priority: TaskPriority, 
+/**
+ * The user who created the task, when the server has accounts
+ */
+created_by: string | null, 
+/**
+ * Set while the task is archived; background workers skip it
+ */
+archived_at: string | null, };
 
-export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, answers?: Array<QuestionAnswer>, };
+export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
-export type QuestionOption = { label: string, description?: string, };
+export type EmbeddingStatus = { task_id: string, needs_embedding: boolean, last_embedded_at: string | null, embedding_model: string | null, };
 
-export type QuestionData = { question: string, header?: string, multi_select: boolean, options: Array<QuestionOption>, };
+export type TaskDependency = { id: string, task_id: string, depends_on_id: string, created_at: string, };
 
-export type QuestionAnswer = { question_index: number, selected_indices: Array<number>, other_text?: string, };
+export type TaskGroup = { id: string, project_id: string, name: string, description: string | null, base_branch: string | null, created_at: string, updated_at: string, };
 
-export type ApprovalRequestType = { "type": "tool_approval", tool_name: string, tool_input: JsonValue, } | { "type": "user_question", questions: Array<QuestionData>, };
+export type TaskStatusCounts = { todo: bigint, inprogress: bigint, inreview: bigint, done: bigint, cancelled: bigint, };
 
-export type ApprovalRequest = { id: string, request_type: ApprovalRequestType, tool_call_id: string, execution_process_id: string, created_at: string, timeout_at?: string, };
+export type TaskGroupWithStats = { task_counts: TaskStatusCounts, id: string, project_id: string, name: string, description: string | null, base_branch: string | null, created_at: string, updated_at: string, };
 
-export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
- * True when file contents are intentionally omitted (e.g., too large)
+ * An environment variable set for every execution of a task
  */
-contentOmitted: boolean, 
+export type TaskEnvVar = { id: string, task_id: string, name: string, 
 /**
- * Optional precomputed stats for omitted content
+ * Masked when `secret` is set, except when loaded for an execution
  */
-additions: number | null, deletions: number | null, };
+value: string, secret: boolean, created_at: Date, updated_at: Date, };
 
-export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+export type UpsertTaskEnvVar = { value: string, secret: boolean, };
 
-export type CustomSoundInfo = { filename: string, };
+export type TrashItemKind = "project" | "task";
 
-export type SavedAccount = { 
 /**
- * First 8 characters of SHA256 hash of the access token
+ * A deleted task or project that can still be restored. Tasks of a trashed
+ * project are not listed on their own; they come back with the project.
  */
-hashPrefix: string, 
+export type TrashItem = { kind: TrashItemKind, id: string, 
 /**
- * Stable account UUID from Anthropic OAuth profile
+ * Project name or task title
  */
-accountUuid: string | null, 
+name: string, 
 /**
- * User-defined name for this account
+ * The task's project; None for projects
  */
-name: string | null, 
+project_id: string | null, deleted_at: Date, };
+
 /**
- * Subscription type (e.g., "pro", "free")
+ * What an external link points at
  */
-subscriptionType: string, 
+export type TaskLinkKind = "design_doc" | "ticket" | "pull_request" | "other";
+
 /**
- * Rate limit tier if available
+ * A design doc, ticket or pull request referenced by a task
  */
-rateLimitTier: string | null, 
+export type TaskLink = { id: string, task_id: string, url: string, 
 /**
- * When this account was saved
+ * Title given by the user; takes precedence over `unfurled_title`
  */
-createdAt: string, };
-
-export type SaveAccountRequest = { 
+title: string | null, kind: TaskLinkKind, 
 /**
- * Optional name for this account
+ * Whether the link is listed in the prompt of new attempts
  */
-name: string | null, };
-
-export type UpdateNameRequest = { 
+include_in_prompt: boolean, 
 /**
- * New name for this account
+ * Title fetched from GitHub, Linear or Figma
  */
-name: string, };
-
-export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+unfurled_title: string | null, 
+/**
+ * Status fetched from the linked service, e.g. `open`, `merged` or
+ * `In Progress`
+ */
+unfurled_status: string | null, unfurled_at: Date | null, created_at: Date, updated_at: Date, };
 
-export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
+export type CreateTaskLink = { url: string, title: string | null, 
+/**
+ * Inferred from the URL when omitted
+ */
+kind?: TaskLinkKind | null, include_in_prompt?: boolean, };
 
-export type ProfileResponse = { user_id: string, username: string | null, email: string, providers: Array<ProviderProfile>, };
+export type UpdateTaskLink = { title: string | null, kind: TaskLinkKind | null, include_in_prompt: boolean | null, };
 
-export type ProviderProfile = { provider: string, username: string | null, display_name: string | null, email: string | null, avatar_url: string | null, };
+/**
+ * A repository the scheduled task's workspace is opened on
+ */
+export type ScheduleRepo = { repo_id: string, target_branch: string, };
 
-export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
+/**
+ * A task created and started on a cron schedule
+ */
+export type TaskSchedule = { id: string, project_id: string, 
+/**
+ * Five-field cron expression in the server's local time, e.g.
+ * `0 9 * * 1-5` for weekdays at 09:00
+ */
+cron: string, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<ScheduleRepo>, enabled: boolean, 
+/**
+ * None while disabled
+ */
+next_run_at: Date | null, last_run_at: Date | null, 
+/**
+ * Task created by the most recent run
+ */
+last_task_id: string | null, 
+/**
+ * Why the most recent run failed; None when it succeeded
+ */
+last_error: string | null, created_at: Date, updated_at: Date, };
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+export type CreateTaskSchedule = { cron: string, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<ScheduleRepo>, enabled?: boolean, };
 
-export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
+/**
+ * Replaces a schedule's settings
+ */
+export type UpdateTaskSchedule = { cron: string, task_title: string, task_description: string | null, executor_profile_id: ExecutorProfileId, repos: Array<ScheduleRepo>, enabled: boolean, };
 
-export type Organization = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, };
+export type Notification = { id: string, project_id: string | null, notification_type: NotificationType, title: string, message: string, is_read: boolean, metadata: JsonValue | null, workspace_id: string | null, session_id: string | null, conversation_session_id: string | null, 
+/**
+ * The user this notification is meant for; None for everyone
+ */
+user_id: string | null, 
+/**
+ * Notifications with the same key raised shortly after this one are
+ * folded into it
+ */
+group_key: string | null, 
+/**
+ * How many notifications this one stands for
+ */
+occurrences: number, created_at: string, updated_at: string, };
 
-export type OrganizationWithRole = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, user_role: MemberRole, };
+export type NotificationType = "agent_complete" | "agent_approval_needed" | "agent_question_needed" | "agent_error" | "conversation_response" | "task_status_changed" | "task_merged";
 
-export type ListOrganizationsResponse = { organizations: Array<OrganizationWithRole>, };
+export type CreateNotification = { project_id: string | null, notification_type: NotificationType, title: string, message: string, metadata: JsonValue | null, workspace_id: string | null, session_id: string | null, conversation_session_id: string | null, user_id: string | null, group_key: string | null, };
 
-export type GetOrganizationResponse = { organization: Organization, user_role: string, };
+export type UpdateNotification = { title: string | null, message: string | null, is_read: boolean | null, metadata: JsonValue | null, };
 
-export type CreateOrganizationRequest = { name: string, slug: string, };
+export type NotificationStats = { total: bigint, unread: bigint, };
 
-export type CreateOrganizationResponse = { organization: OrganizationWithRole, };
+export type MarkAllReadRequest = { project_id: string | null, 
+/**
+ * Only mark notifications of this type read
+ */
+notification_type: NotificationType | null, };
 
-export type UpdateOrganizationRequest = { name: string, };
+export type MarkNotificationsReadRequest = { notification_ids: Array<string>, 
+/**
+ * False marks the notifications unread again
+ */
+is_read: boolean, };
 
-export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, };
+export type AgentFeedback = { id: string, execution_process_id: string, task_id: string, workspace_id: string, feedback_json: string | null, collected_at: string, created_at: string, updated_at: string, };
 
-export type CreateInvitationRequest = { email: string, role: MemberRole, };
+export type CreateAgentFeedback = { execution_process_id: string, task_id: string, workspace_id: string, feedback_json: string | null, };
 
-export type CreateInvitationResponse = { invitation: Invitation, };
+export type ReviewAttention = { id: string, execution_process_id: string, task_id: string, workspace_id: string, needs_attention: boolean, reasoning: string | null, analyzed_at: string, created_at: string, updated_at: string, };
 
-export type ListInvitationsResponse = { invitations: Array<Invitation>, };
+export type CreateReviewAttention = { execution_process_id: string, task_id: string, workspace_id: string, needs_attention: boolean, reasoning: string | null, };
 
-export type GetInvitationResponse = { id: string, organization_slug: string, role: MemberRole, expires_at: string, };
+export type FeedbackResponse = { id: string, task_id: string, workspace_id: string, execution_process_id: string, feedback: JsonValue | null, collected_at: string, };
 
-export type AcceptInvitationResponse = { organization_id: string, organization_slug: string, role: MemberRole, };
+/**
+ * Resource usage of one execution: the persisted time series plus the
+ * per-process breakdown of the latest sample while it is still running
+ */
+export type ExecutionProcessResources = { latest: ResourceSample | null, history: Array<ExecutionProcessResourceSample>, };
 
-export type RevokeInvitationRequest = { invitation_id: string, };
+export type KillProcessRequest = { pid: number, };
 
-export type OrganizationMember = { user_id: string, role: MemberRole, joined_at: string, };
+/**
+ * The workspace created for a replay and the execution running in it, or
+ * its queue entry when the concurrency limits held the replay back
+ */
+export type ReplayExecutionResponse = { workspace: Workspace, execution_process: ExecutionProcess | null, queue_entry: ExecutionQueue | null, };
 
-export type OrganizationMemberWithProfile = { user_id: string, role: MemberRole, joined_at: string, first_name: string | null, last_name: string | null, username: string | null, email: string | null, avatar_url: string | null, };
+export type BackupInfo = { 
+/**
+ * Filename without `.zip`, e.g. `backup_20260101_120000`
+ */
+id: string, created_at: string, size_bytes: number, 
+/**
+ * Most recent check of this archive; None if it was never checked
+ */
+last_verification: BackupVerification | null, };
 
-export type ListMembersResponse = { members: Array<OrganizationMemberWithProfile>, };
+/**
+ * Why a backup archive was checked
+ */
+export type BackupVerificationKind = "after_backup" | "drill" | "manual";
 
-export type UpdateMemberRoleRequest = { role: MemberRole, };
+/**
+ * Rows found in one table of the archived database
+ */
+export type TableRowCount = { table: string, rows: bigint, 
+/**
+ * Rows in the live database when the archive was made; None when the
+ * archive is checked later and there is nothing to compare with
+ */
+expected_rows: bigint | null, };
 
-export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
+export type BackupVerification = { id: string, 
+/**
+ * Archive filename without `.zip`
+ */
+backup_id: string, kind: BackupVerificationKind, passed: boolean, 
+/**
+ * Output of `PRAGMA integrity_check`; None when the database could not
+ * be opened
+ */
+integrity: string | null, table_counts: Array<TableRowCount>, 
+/**
+ * Why the check failed
+ */
+error: string | null, duration_ms: bigint, verified_at: Date, };
 
-export type ServerLogEntry = { timestamp: string, 
 /**
- * Log level: "TRACE", "DEBUG", "INFO", "WARN", "ERROR"
+ * Outcome of a successful restore
  */
-level: string, 
+export type RestoreReport = { 
 /**
- * Module path (e.g., "server::routes::tasks")
+ * Files and directories put in place from the archive
  */
-target: string, message: string, };
+restored: Array<string>, 
+/**
+ * Where the replaced files are moved, in case the restore needs undoing
+ */
+rollback_dir: string, 
+/**
+ * The live database cannot be swapped while connections are open, so
+ * the files are put in place when the app next starts
+ */
+restart_required: boolean, };
 
-export type RemoteProject = { id: string, organization_id: string, name: string, metadata: Record<string, unknown>, created_at: string, };
+/**
+ * Where the asset directory came from
+ */
+export type AuditAction = "redaction" | "project_purge";
 
-export type ListProjectsResponse = { projects: Array<RemoteProject>, };
+/**
+ * An administrative action that changed or removed user data
+ */
+export type AuditLogEntry = { id: string, action: AuditAction, 
+/**
+ * The signed-in user who acted; None on a server without accounts
+ */
+actor_id: string | null, 
+/**
+ * One line for lists
+ */
+summary: string, 
+/**
+ * JSON object whose shape depends on the action
+ */
+details: string, created_at: Date, };
 
-export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
+/**
+ * Rows changed per kind of data
+ */
+export type RedactionCounts = { tasks: number, conversations: number, conversation_messages: number, 
+/**
+ * Coding agent prompts and summaries
+ */
+agent_turns: number, normalized_entries: number, execution_logs: number, };
 
-export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
+/**
+ * Rows that belong to a project, per kind of data
+ */
+export type ProjectPurgeCounts = { tasks: number, workspaces: number, sessions: number, execution_processes: number, 
+/**
+ * Coding agent prompts and summaries
+ */
+agent_turns: number, normalized_entries: number, execution_logs: number, conversations: number, conversation_messages: number, operations: number, 
+/**
+ * Images used by nothing outside the project
+ */
+images: number, 
+/**
+ * Semantic search vectors of tasks, messages and agent summaries
+ */
+embeddings: number, };
 
-export type LinkToExistingRequest = { remote_project_id: string, };
+export type RedactRequest = { 
+/**
+ * Exact, case-sensitive text to remove
+ */
+text: string, 
+/**
+ * Defaults to `[REDACTED]`
+ */
+placeholder: string | null, 
+/**
+ * Why, e.g. an incident reference; kept in the audit log
+ */
+reason: string | null, 
+/**
+ * Only count what would change
+ */
+dry_run: boolean, };
 
-export type PrWithComments = { unresolved_count: number | null, number: bigint, title: string, url: string, author: string, head_branch: string, base_branch: string, created_at: string, updated_at: string, };
+export type RedactionReport = { counts: RedactionCounts, dry_run: boolean, 
+/**
+ * The audit log entry recording the redaction; None for dry runs and
+ * when nothing matched
+ */
+audit_log_id: string | null, };
 
-export type RepoPrs = { repo_id: string, repo_name: string, display_name: string, pull_requests: Array<PrWithComments>, };
+export type ProjectPurgeReport = { counts: ProjectPurgeCounts, 
+/**
+ * Workspace directories on disk whose worktrees are removed
+ */
+workspace_dirs: number, 
+/**
+ * File names of local backups that hold this project and nothing else
+ */
+backups: Array<string>, dry_run: boolean, 
+/**
+ * The audit log entry recording the purge; None for dry runs
+ */
+audit_log_id: string | null, };
 
-export type ProjectPrsResponse = { repos: Array<RepoPrs>, };
+export type AssetDirSource = "default" | "relocated" | "environment";
 
-export type PrUnresolvedCount = { repo_id: string, pr_number: bigint, unresolved_count: number, };
+/**
+ * Where the app keeps its database, config files and sounds
+ */
+export type AssetDirInfo = { path: string, source: AssetDirSource, 
+/**
+ * Where the assets live when neither relocated nor overridden
+ */
+default_path: string, };
 
-export type PrUnresolvedCountsResponse = { counts: Array<PrUnresolvedCount>, };
+export type RelocateAssetDirRequest = { 
+/**
+ * Absolute path of an empty or missing directory
+ */
+target_dir: string, };
 
-export type PrThreadsResponse = { threads: Array<UnifiedPrComment>, };
+/**
+ * Outcome of a successful relocation
+ */
+export type RelocationReport = { from: string, to: string, 
+/**
+ * Files copied besides the database
+ */
+files_copied: number, bytes_copied: number, 
+/**
+ * The server shuts down after a relocation and uses the new directory
+ * from the next start; the original can be deleted once it has
+ */
+restart_required: boolean, };
 
-export type GetPrThreadsError = { "type": "github_not_configured" } | { "type": "repo_not_found" } | { "type": "pr_not_found" } | { "type": "github_auth_failed" };
+export type GanttTask = { id: string, name: string, start: string, end: string, progress: number, dependencies: Array<string>, task_status: TaskStatus, task_group_id: string | null, total_input_tokens: bigint | null, total_output_tokens: bigint | null, token_usage_metadata: JsonValue | null, };
 
-export type MatchingTaskGroup = { id: string, name: string, };
+/**
+ * Tasks of a project that are not done or cancelled, by status
+ */
+export type OpenTaskCounts = { todo: bigint, inprogress: bigint, inreview: bigint, };
 
-export type WorktreeInfo = { path: string, branch: string | null, is_main: boolean, matching_groups: Array<MatchingTaskGroup>, };
+export type PaginatedGanttTasks = { tasks: Array<GanttTask>, total: bigint, hasMore: boolean, };
 
-export type ProjectWorktreesResponse = { worktrees: Array<WorktreeInfo>, };
+export type CreateTaskGroup = { project_id: string, name: string, description: string | null, base_branch: string | null, };
 
-export type RegisterRepoRequest = { path: string, display_name: string | null, };
+export type UpdateTaskGroup = { name: string | null, description: string | null, base_branch: string | null, };
 
-export type InitRepoRequest = { parent_path: string, folder_name: string, };
+export type ConversationSession = { id: string, project_id: string, title: string, status: ConversationSessionStatus, executor: string | null, worktree_path: string | null, worktree_branch: string | null, 
+/**
+ * The conversation this one was forked from
+ */
+forked_from_session_id: string | null, 
+/**
+ * The last message copied from the original conversation
+ */
+forked_from_message_id: string | null, 
+/**
+ * Agent session the fork's first turn resumes; None starts a new one
+ */
+fork_agent_session_id: string | null, created_at: string, updated_at: string, };
 
-export type CloneRepoRequest = { url: string, destination: string | null, };
+export type ConversationSessionStatus = "active" | "archived";
 
-export type CreateBranchRequest = { name: string, base_branch: string | null, };
+export type CreateConversationSession = { project_id: string, title: string, executor: string | null, worktree_path: string | null, worktree_branch: string | null, };
 
-export type CheckBranchMergeStatusRequest = { branch_name: string, project_id: string, };
+export type UpdateConversationSession = { title: string | null, status: ConversationSessionStatus | null, executor: string | null, };
 
-export type BranchMergeStatus = { exists: boolean, is_merged: boolean, target_branch: string | null, };
+export type ConversationMessage = { id: string, conversation_session_id: string, execution_process_id: string | null, role: MessageRole, content: string, metadata: string | null, created_at: string, updated_at: string, };
 
-export type BatchCheckBranchMergeStatusRequest = { branches: Array<string>, project_id: string, };
+export type ConversationMessagesPage = { messages: Array<ConversationMessage>, next_cursor: string | null, has_more: boolean, };
 
-export type BatchBranchMergeStatus = { statuses: { [key in string]?: BranchMergeStatus }, };
+export type MessageRole = "user" | "assistant";
 
-export type CheckBranchSyncStatusRequest = { branch_name: string, project_id: string, };
+export type CreateConversationMessage = { conversation_session_id: string, execution_process_id: string | null, role: MessageRole, content: string, metadata: string | null, };
 
-export type BranchSyncStatus = { branch_name: string, 
+export type ConversationWithMessages = { messages: Array<ConversationMessage>, id: string, project_id: string, title: string, status: ConversationSessionStatus, executor: string | null, worktree_path: string | null, worktree_branch: string | null, 
 /**
- * Commits local has that remote doesn't (needs push)
+ * The conversation this one was forked from
  */
-remote_ahead: number | null, 
+forked_from_session_id: string | null, 
 /**
- * Commits remote has that local doesn't (needs pull)
+ * The last message copied from the original conversation
  */
-remote_behind: number | null, 
+forked_from_message_id: string | null, 
 /**
- * Error message if branch has no remote tracking
+ * Agent session the fork's first turn resumes; None starts a new one
  */
-error: string | null, };
-
-export type BatchCheckBranchSyncStatusRequest = { branches: Array<string>, project_id: string, };
+fork_agent_session_id: string | null, created_at: string, updated_at: string, };
 
-export type BatchBranchSyncStatus = { statuses: { [key in string]?: BranchSyncStatus }, };
+export type SendMessageResponse = { user_message: ConversationMessage, execution_process_id: string, };
 
-export type CreateRepoPrRequest = { head_branch: string, base_branch: string, title: string, body: string | null, draft: boolean | null, };
+export type CreateConversationRequest = { title: string, initial_message: string, executor_profile_id: ExecutorProfileId | null, worktree_path: string | null, worktree_branch: string | null, 
+/**
+ * Create worktrees of the project's repos for this conversation instead
+ * of using `worktree_path`. `worktree_branch` names their branch.
+ */
+create_worktree: boolean, };
 
-export type CreateRepoPrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+export type CreateConversationResponse = { session: ConversationSession, initial_message: ConversationMessage, execution_process_id: string, };
 
-export type PushBranchRequest = { branch_name: string, force: boolean, };
+export type UpdateConversationRequest = { title: string | null, status: ConversationSessionStatus | null, };
 
-export type PushBranchError = { "type": "force_push_required" } | { "type": "no_remote_tracking" } | { "type": "auth_failed" };
+export type SendMessageRequest = { content: string, variant: string | null, };
 
-export type TagSearchParams = { search: string | null, };
+export type ForkConversationRequest = { 
+/**
+ * The agent reply to fork at; it and everything before it is copied
+ */
+message_id: string, 
+/**
+ * Defaults to the original title followed by "(fork)"
+ */
+title: string | null, 
+/**
+ * First message to send in the fork
+ */
+content: string | null, variant: string | null, };
 
-export type TokenResponse = { access_token: string, expires_at: string | null, };
+export type ForkConversationResponse = { session: ConversationSession, 
+/**
+ * Set when the request included `content`
+ */
+sent_message: SendMessageResponse | null, };
 
-export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+export type ReorderConversationQueueRequest = { 
 /**
- * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ * Every queued message of the conversation, in the desired order
  */
-capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
+message_ids: Array<string>, };
+
+export type MergeTaskGroupRequest = { target_group_id: string, };
+
+export type TaskNotes = { 
+/**
+ * Markdown
+ */
+content: string, 
+/**
+ * Pass back when saving; 0 while the task has no notes
+ */
+version: number, updated_at: Date | null, };
+
+export type UpdateTaskNotes = { content: string, 
+/**
+ * The version the edit was based on
+ */
+version: number, };
+
+export type TaskWatchers = { watchers: Array<User>, 
+/**
+ * Whether the signed-in user is one of them
+ */
+watching: boolean, };
+
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, task_group_id: string | null, priority: TaskPriority | null, };
+
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, task_group_id: string | null, priority: TaskPriority | null, };
+
+export type DraftFollowUpData = { message: string, variant: string | null, };
+
+export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData } | { "type": "DRAFT_CONVERSATION_MESSAGE", "data": DraftFollowUpData } | { "type": "TASK_NOTES", "data": string };
+
+export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP", DRAFT_CONVERSATION_MESSAGE = "DRAFT_CONVERSATION_MESSAGE", TASK_NOTES = "TASK_NOTES" }
+
+export type Scratch = { id: string, payload: ScratchPayload, created_at: string, updated_at: string, 
+/**
+ * Bumped on every save
+ */
+version: number, };
+
+export type CreateScratch = { payload: ScratchPayload, };
+
+export type UpdateScratch = { payload: ScratchPayload, };
+
+export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
+
+export type GitHubSettingsStatus = { configured: boolean, };
+
+export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+
+export type WorkspaceWithSession = { session: Session | null, 
+/**
+ * Executor name if workspace is queued for execution (populated from execution_queue)
+ */
+queued_executor: string | null, id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+
+export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+
+export type ExecutionProcess = { id: string, 
+/**
+ * Session ID for workspace-based executions (nullable for conversation-based)
+ */
+session_id: string | null, 
+/**
+ * Conversation session ID for disposable conversations (nullable for workspace-based)
+ */
+conversation_session_id: string | null, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+/**
+ * dropped: true if this process is excluded from the current
+ * history view (due to restore/trimming). Hidden from logs/timeline;
+ * still listed in the Processes tab.
+ */
+dropped: boolean, input_tokens: bigint | null, output_tokens: bigint | null, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver" | "internalagent" | "disposableconversation" | "replay";
+
+export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+
+/**
+ * CPU and memory of an execution's whole process tree at one point in time
+ */
+export type ExecutionProcessResourceSample = { id: string, execution_process_id: string, 
+/**
+ * Summed over every process in the tree; 100 is one fully busy core
+ */
+cpu_percent: number, rss_bytes: number, process_count: number, sampled_at: Date, };
+
+/**
+ * What made an execution fail, as far as its output tells
+ */
+export type FailureReason = "auth_expired" | "rate_limited" | "disk_full" | "cli_not_found" | "merge_conflict" | "network" | "git_hooks";
+
+/**
+ * The classified cause of a failed execution
+ */
+export type ExecutionProcessFailure = { execution_process_id: string, reason: FailureReason, 
+/**
+ * The stderr line, or the exit code, the failure was classified by
+ */
+matched_line: string | null, 
+/**
+ * What the user can do about it
+ */
+suggested_fix: string, created_at: Date, };
+
+/**
+ * Why a failed execution was considered worth re-running
+ */
+export type RetryReason = "rate_limit" | "network" | "startup_crash" | "exit_code";
+
+/**
+ * An automatic re-run of a failed execution
+ */
+export type ExecutionProcessRetry = { id: string, 
+/**
+ * The execution that failed
+ */
+execution_process_id: string, 
+/**
+ * The execution started in its place; None until it starts, or if it
+ * never did (see `error`)
+ */
+retry_execution_process_id: string | null, 
+/**
+ * 1 for the first retry of the original run
+ */
+attempt: number, reason: RetryReason, delay_secs: number, 
+/**
+ * The stderr line, or the exit code, the failure was classified by
+ */
+matched_line: string | null, 
+/**
+ * Why the retry was not started
+ */
+error: string | null, created_at: Date, };
+
+export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
+
+export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+
+export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, 
+/**
+ * Opened as a draft and not yet marked ready for review by the PR monitor
+ */
+is_draft: boolean, };
+
+export type MergeStatus = "open" | "merged" | "closed" | "unknown";
+
+export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+
+export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "answered", answers: Array<QuestionAnswer>, } | { "status": "timed_out" };
+
+export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
+
+export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, answers?: Array<QuestionAnswer>, };
+
+export type QuestionOption = { label: string, description?: string, };
+
+export type QuestionData = { question: string, header?: string, multi_select: boolean, options: Array<QuestionOption>, };
+
+export type QuestionAnswer = { question_index: number, selected_indices: Array<number>, other_text?: string, };
+
+export type ApprovalRequestType = { "type": "tool_approval", tool_name: string, tool_input: JsonValue, } | { "type": "user_question", questions: Array<QuestionData>, };
+
+export type ApprovalRequest = { id: string, request_type: ApprovalRequestType, tool_call_id: string, execution_process_id: string, created_at: string, timeout_at?: string, };
+
+export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
+/**
+ * True when file contents are intentionally omitted (e.g., too large)
+ */
+contentOmitted: boolean, 
+/**
+ * Optional precomputed stats for omitted content
+ */
+additions: number | null, deletions: number | null, 
+/**
+ * File mode before the change; None when the file was added
+ */
+oldMode: DiffFileMode | null, 
+/**
+ * File mode after the change; None when the file was deleted
+ */
+newMode: DiffFileMode | null, 
+/**
+ * Where the old side pointed, when it is a symlink
+ */
+oldSymlinkTarget: string | null, 
+/**
+ * Where the new side points, when it is a symlink
+ */
+newSymlinkTarget: string | null, 
+/**
+ * True when the executable bit was turned on or off; the modes say which
+ */
+executableChanged: boolean, };
+
+export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+
+export type DiffFileMode = "regular" | "executable" | "symlink";
+
+export type CustomSoundInfo = { filename: string, };
+
+export type SavedAccount = { 
+/**
+ * First 8 characters of SHA256 hash of the access token
+ */
+hashPrefix: string, 
+/**
+ * Stable account UUID from Anthropic OAuth profile
+ */
+accountUuid: string | null, 
+/**
+ * User-defined name for this account
+ */
+name: string | null, 
+/**
+ * Subscription type (e.g., "pro", "free")
+ */
+subscriptionType: string, 
+/**
+ * Rate limit tier if available
+ */
+rateLimitTier: string | null, 
+/**
+ * When this account was saved
+ */
+createdAt: string, };
+
+export type SaveAccountRequest = { 
+/**
+ * Optional name for this account
+ */
+name: string | null, };
+
+export type UpdateNameRequest = { 
+/**
+ * New name for this account
+ */
+name: string, };
+
+export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+
+export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
+
+export type ProfileResponse = { user_id: string, username: string | null, email: string, providers: Array<ProviderProfile>, };
+
+export type ProviderProfile = { provider: string, username: string | null, display_name: string | null, email: string | null, avatar_url: string | null, };
+
+export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
+
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+
+export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
+
+export type Organization = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, };
+
+export type OrganizationWithRole = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, user_role: MemberRole, };
+
+export type ListOrganizationsResponse = { organizations: Array<OrganizationWithRole>, };
+
+export type GetOrganizationResponse = { organization: Organization, user_role: string, };
+
+export type CreateOrganizationRequest = { name: string, slug: string, };
+
+export type CreateOrganizationResponse = { organization: OrganizationWithRole, };
+
+export type UpdateOrganizationRequest = { name: string, };
+
+export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, };
+
+export type CreateInvitationRequest = { email: string, role: MemberRole, };
+
+export type CreateInvitationResponse = { invitation: Invitation, };
+
+export type ListInvitationsResponse = { invitations: Array<Invitation>, };
+
+export type GetInvitationResponse = { id: string, organization_slug: string, role: MemberRole, expires_at: string, };
+
+export type AcceptInvitationResponse = { organization_id: string, organization_slug: string, role: MemberRole, };
+
+export type RevokeInvitationRequest = { invitation_id: string, };
+
+export type OrganizationMember = { user_id: string, role: MemberRole, joined_at: string, };
+
+export type OrganizationMemberWithProfile = { user_id: string, role: MemberRole, joined_at: string, first_name: string | null, last_name: string | null, username: string | null, email: string | null, avatar_url: string | null, };
+
+export type ListMembersResponse = { members: Array<OrganizationMemberWithProfile>, };
+
+export type UpdateMemberRoleRequest = { role: MemberRole, };
+
+export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
+
+export type ServerLogEntry = { timestamp: string, 
+/**
+ * Log level: "TRACE", "DEBUG", "INFO", "WARN", "ERROR"
+ */
+level: string, 
+/**
+ * Module path (e.g., "server::routes::tasks")
+ */
+target: string, message: string, };
+
+export type RemoteProject = { id: string, organization_id: string, name: string, metadata: Record<string, unknown>, created_at: string, };
+
+export type ListProjectsResponse = { projects: Array<RemoteProject>, };
+
+export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
+
+export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
+
+export type LinkToExistingRequest = { remote_project_id: string, };
+
+export type PrWithComments = { unresolved_count: number | null, number: bigint, title: string, url: string, author: string, head_branch: string, base_branch: string, created_at: string, updated_at: string, };
+
+export type RepoPrs = { repo_id: string, repo_name: string, display_name: string, pull_requests: Array<PrWithComments>, };
+
+export type ProjectPrsResponse = { repos: Array<RepoPrs>, };
+
+export type PrUnresolvedCount = { repo_id: string, pr_number: bigint, unresolved_count: number, };
+
+export type PrUnresolvedCountsResponse = { counts: Array<PrUnresolvedCount>, };
+
+export type PrThreadsResponse = { threads: Array<UnifiedPrComment>, };
+
+export type GetPrThreadsError = { "type": "github_not_configured" } | { "type": "repo_not_found" } | { "type": "pr_not_found" } | { "type": "github_auth_failed" };
+
+export type MatchingTaskGroup = { id: string, name: string, };
+
+export type WorktreeInfo = { path: string, branch: string | null, is_main: boolean, matching_groups: Array<MatchingTaskGroup>, };
+
+export type ProjectWorktreesResponse = { worktrees: Array<WorktreeInfo>, };
+
+/**
+ * At-a-glance health of a project
+ */
+export type ProjectDashboardResponse = { open_tasks: OpenTaskCounts, 
+/**
+ * Runs whose review attention check found nothing to look at, over the
+ * last `window_days`
+ */
+autopilot_reviewed: bigint, autopilot_passed: bigint, 
+/**
+ * `autopilot_passed / autopilot_reviewed`; None when nothing was reviewed
+ */
+autopilot_success_rate: number | null, 
+/**
+ * Mean time from task creation to its first merge, over tasks merged in
+ * the last `window_days`
+ */
+mean_seconds_to_merge: number | null, 
+/**
+ * Open tasks whose latest setup or cleanup script failed
+ */
+failing_validations: bigint, pending_approvals: bigint, 
+/**
+ * Bytes used by the project's workspace worktrees
+ */
+disk_usage_bytes: bigint, window_days: bigint, };
+
+export type RegisterRepoRequest = { path: string, display_name: string | null, };
+
+export type InitRepoRequest = { parent_path: string, folder_name: string, };
+
+export type CloneRepoRequest = { url: string, destination: string | null, };
+
+export type CreateBranchRequest = { name: string, base_branch: string | null, };
+
+export type CheckBranchMergeStatusRequest = { branch_name: string, project_id: string, };
+
+export type BranchMergeStatus = { exists: boolean, is_merged: boolean, target_branch: string | null, };
+
+export type BatchCheckBranchMergeStatusRequest = { branches: Array<string>, project_id: string, };
+
+export type BatchBranchMergeStatus = { statuses: { [key in string]?: BranchMergeStatus }, };
+
+export type CheckBranchSyncStatusRequest = { branch_name: string, project_id: string, };
+
+export type BranchSyncStatus = { branch_name: string, 
+/**
+ * Commits local has that remote doesn't (needs push)
+ */
+remote_ahead: number | null, 
+/**
+ * Commits remote has that local doesn't (needs pull)
+ */
+remote_behind: number | null, 
+/**
+ * Error message if branch has no remote tracking
+ */
+error: string | null, };
+
+export type BatchCheckBranchSyncStatusRequest = { branches: Array<string>, project_id: string, };
+
+export type BatchBranchSyncStatus = { statuses: { [key in string]?: BranchSyncStatus }, };
+
+export type CreateRepoPrRequest = { head_branch: string, base_branch: string, title: string, body: string | null, draft: boolean | null, };
+
+export type CreateRepoPrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+
+export type PushBranchRequest = { branch_name: string, force: boolean, };
+
+export type PushBranchError = { "type": "force_push_required" } | { "type": "no_remote_tracking" } | { "type": "auth_failed" };
+
+export type RepoStats = { local_branch_count: number, 
+/**
+ * Remote-tracking branches, not counting `origin/HEAD` style aliases
+ */
+remote_branch_count: number, 
+/**
+ * One entry per day, oldest first, including days without commits
+ */
+commit_activity: Array<DailyCommitCount>, 
+/**
+ * Most active authors first
+ */
+top_contributors: Array<Contributor>, 
+/**
+ * Largest first
+ */
+languages: Array<LanguageStat>, 
+/**
+ * Files in HEAD's tree
+ */
+file_count: number, 
+/**
+ * Total size of the files in HEAD's tree
+ */
+tracked_bytes: number, 
+/**
+ * Size of the object database on disk, packs included
+ */
+object_store_bytes: number, 
+/**
+ * Commits the contributor and activity figures are based on
+ */
+commits_scanned: number, };
+
+export type DailyCommitCount = { date: string, commits: number, };
+
+export type Contributor = { 
+/**
+ * Name on the contributor's most recent commit
+ */
+name: string, email: string, commits: number, last_commit_at: Date, };
+
+export type LanguageStat = { language: string, files: number, bytes: number, };
+
+export type TagSearchParams = { search: string | null, };
+
+/**
+ * Variables to substitute into a template preview
+ */
+export type PreviewPromptTemplateRequest = { variables: { [key in string]?: string }, };
+
+export type CreateProjectFromTemplate = { name: string, };
+
+export type ImportProjectRequest = { 
+/**
+ * Name of the new project; defaults to the exported project's name
+ */
+name: string | null, 
+/**
+ * Path on this machine of each exported repository, by repository name;
+ * repositories not listed are looked up at their exported path
+ */
+repo_paths: { [key in string]?: string }, 
+/**
+ * The file downloaded from the export endpoint
+ */
+archive: unknown, };
+
+export type TokenResponse = { access_token: string, expires_at: string | null, };
+
+export type SessionStatus = { 
+/**
+ * Whether the server has accounts, and so requires signing in
+ */
+accounts_enabled: boolean, user: User | null, };
+
+export type LoginRequest = { username: string, password: string, };
+
+export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+/**
+ * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ */
+capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
 export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
 
-export type McpServerQuery = { executor: BaseCodingAgent, };
+export type McpServerQuery = { executor: BaseCodingAgent, };
+
+export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+
+export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+
+export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+
+export type CheckEditorAvailabilityResponse = { available: boolean, };
+
+export type CreateCustomEditorRequest = { name: string, command: string, argument: string | null, };
+
+export type UpdateCustomEditorRequest = { name: string, command: string, argument: string | null, };
+
+export type CustomEditorResponse = { id: string, name: string, command: string, argument: string, icon: string | null, created_at: string, available: boolean, };
+
+export type ListCustomEditorsResponse = { editors: Array<CustomEditorResponse>, };
+
+export type CheckCustomEditorAvailabilityResponse = { available: boolean, };
+
+export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+
+export type BundledSoundInfo = { 
+/**
+ * Identifier for API paths (e.g., "bundled:COW_MOOING")
+ */
+identifier: string, 
+/**
+ * Human-readable name (e.g., "Cow Mooing")
+ */
+display_name: string, };
+
+export type AvailableSoundsResponse = { bundled: Array<BundledSoundInfo>, custom: Array<CustomSoundInfo>, };
+
+export type SetGitHubTokenRequest = { token: string, };
+
+export type GitHubImportResponse = { success: boolean, message: string, };
+
+export type AccountInfo = { claude: ClaudeAccountInfo | null, codex: CodexAccountInfo | null, };
+
+export type ClaudeAccountInfo = { subscriptionType: string, rateLimitTier: string | null, usage: ClaudeUsage | null, };
+
+export type ClaudeUsage = { fiveHour: UsageLimit, sevenDay: UsageLimit, sevenDayOpus: UsageLimit | null, sevenDaySonnet: UsageLimit | null, };
+
+export type UsageLimit = { 
+/**
+ * Usage percentage from 0 to 100
+ */
+usedPercent: number, 
+/**
+ * ISO 8601 timestamp when this limit resets
+ */
+resetsAt: string, };
+
+export type CodexAccountInfo = { planType: string, subscriptionActiveUntil: string | null, };
+
+export type CurrentUserResponse = { user_id: string, };
+
+export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+
+export type FollowUpResult = { "status": "started", execution_process: ExecutionProcess, } | { "status": "queued", queue_entry: ExecutionQueue, };
+
+export type InterruptAndFollowUpRequest = { 
+/**
+ * Correction sent to the agent as the next turn
+ */
+prompt: string, variant: string | null, };
+
+export type ExecutionQueue = { id: string, workspace_id: string, executor_profile_id: ExecutorProfileId, queued_at: string, 
+/**
+ * Session ID for follow-up executions (None for initial workspace starts)
+ */
+session_id: string | null, 
+/**
+ * Serialized ExecutorAction for follow-up executions (None for initial workspace starts)
+ */
+executor_action: string | null, 
+/**
+ * Place in the queue; lower runs first. Reordering only swaps the
+ * positions of existing entries.
+ */
+position: number, 
+/**
+ * Run reason of the queued follow-up; None starts a coding agent turn
+ */
+run_reason: ExecutionProcessRunReason | null, 
+/**
+ * Retry record of a queued re-run of a transiently failed execution
+ */
+retry_id: string | null, };
+
+/**
+ * A queue entry of a project, with the task it will run for
+ */
+export type ProjectQueueEntry = { id: string, workspace_id: string, task_id: string, task_title: string, task_priority: TaskPriority, executor_profile_id: ExecutorProfileId, queued_at: string, session_id: string | null, position: number, };
+
+export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+
+export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+
+export type MergeTaskAttemptRequest = { repo_id: string, commit_message: string | null, generate_commit_message: boolean | null, };
+
+export type MergeTaskAttemptResponse = { 
+/**
+ * Output of the repo's git hooks when they failed and the merge was
+ * committed without them
+ */
+hook_failure: string | null, };
+
+export type GenerateCommitMessageRequest = { repo_id: string, };
+
+export type GenerateCommitMessageResponse = { commit_message: string, };
+
+export type PushTaskAttemptRequest = { repo_id: string, };
+
+export type RenameBranchRequest = { new_branch_name: string, };
+
+export type RenameBranchResponse = { branch: string, };
+
+export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+
+export type OpenEditorResponse = { url: string | null, };
+
+export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+
+export type ShareTaskResponse = { shared_task_id: string, };
+
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+
+/**
+ * A plan given either as Markdown or as a list of tasks
+ */
+export type BulkCreateTasksRequest = { 
+/**
+ * Headings and checklists, parsed into tasks
+ */
+markdown: string | null, tasks: Array<PlannedTask> | null, 
+/**
+ * Group every created task belongs to
+ */
+task_group_id: string | null, 
+/**
+ * Start the tasks nothing unfinished blocks
+ */
+start: StartPlannedTasks | null, };
+
+export type StartPlannedTasks = { executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Start at most this many, in plan order
+ */
+limit: number | null, };
+
+export type PlannedTask = { title: string, description: string | null, 
+/**
+ * Tasks in the same plan that must finish first, by title or as `#n`
+ */
+depends_on: Array<string>, priority: TaskPriority | null, 
+/**
+ * Created as done, so it blocks nothing and is never started
+ */
+done: boolean, };
+
+export type CreatedPlan = { 
+/**
+ * In plan order
+ */
+tasks: Array<Task>, dependencies: Array<TaskDependency>, started_task_ids: Array<string>, };
+
+export type TaskMatchWithScore = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, task_group_id: string | null, created_at: string, updated_at: string, is_blocked: boolean, has_in_progress_attempt: boolean, last_attempt_failed: boolean, is_queued: boolean, last_executor: string, needs_attention: boolean | null, priority: TaskPriority, similarityScore: number, };
+
+export type ConversationMessageMatch = { messageId: string, conversationSessionId: string, sessionTitle: string, role: MessageRole, 
+/**
+ * Start of the message content
+ */
+snippet: string, createdAt: Date, similarityScore: number, };
+
+export type ExecutionSummaryMatch = { executionProcessId: string, workspaceId: string, taskId: string, taskTitle: string, 
+/**
+ * Start of the agent's final summary
+ */
+snippet: string, createdAt: Date, similarityScore: number, };
+
+export type SemanticSearchRequest = { projectId: string, query: string, 
+/**
+ * Maximum matches per result kind. Defaults to 10.
+ */
+limit: number | null, };
+
+export type SemanticSearchResponse = { tasks: Array<TaskMatchWithScore>, conversationMessages: Array<ConversationMessageMatch>, executionSummaries: Array<ExecutionSummaryMatch>, 
+/**
+ * "vector" when embeddings were used, "keyword" when only tasks were matched by full-text search
+ */
+searchMethod: string, };
+
+/**
+ * What a text search match was found in
+ */
+export type TextSearchSource = "conversation_message" | "execution_log";
+
+/**
+ * A run of snippet text; `highlighted` runs matched the query
+ */
+export type HighlightedText = { text: string, highlighted: boolean, };
+
+/**
+ * A conversation message or execution log entry matching a text search
+ */
+export type TextSearchMatch = { source: TextSearchSource, 
+/**
+ * None for processes that belong to neither a task nor a conversation
+ */
+projectId: string | null, executor: string | null, 
+/**
+ * Task title, or the conversation title for conversation matches
+ */
+title: string | null, taskId: string | null, workspaceId: string | null, conversationSessionId: string | null, conversationMessageId: string | null, executionProcessId: string | null, 
+/**
+ * Position of the matching entry in the process's normalized log
+ */
+entryIndex: bigint | null, 
+/**
+ * Matched content around the query terms
+ */
+snippet: Array<HighlightedText>, createdAt: Date, 
+/**
+ * BM25 relevance; higher is better
+ */
+score: number, };
+
+/**
+ * Request for full-text search over conversation messages and execution logs
+ */
+export type TextSearchRequest = { query: string, projectId: string | null, 
+/**
+ * Executor as stored on the session, e.g. "CLAUDE_CODE"
+ */
+executor: string | null, 
+/**
+ * Only matches from messages sent or processes started at or after this time
+ */
+from: Date | null, 
+/**
+ * Only matches from before this time
+ */
+to: Date | null, 
+/**
+ * Defaults to 20.
+ */
+limit: number | null, };
+
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+
+export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+
+/**
+ * Outcome of an image garbage collection run
+ */
+export type ImageGcReport = { 
+/**
+ * Unreferenced images deleted along with their files
+ */
+images_removed: number, 
+/**
+ * Files in the image cache without an image record
+ */
+stray_files_removed: number, bytes_reclaimed: number, 
+/**
+ * Images or files that could not be deleted; retried on the next run
+ */
+failed: number, };
+
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+
+export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+
+export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
+
+export type RunAgentSetupResponse = Record<string, never>;
+
+export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
 
-export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
 
-export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+export type AbortConflictsRequest = { repo_id: string, };
 
-export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+export type CherryPickRequest = { repo_id: string, 
+/**
+ * Commit SHAs or refs, applied oldest first onto the task branch
+ */
+commits: Array<string>, };
 
-export type CheckEditorAvailabilityResponse = { available: boolean, };
+export type CherryPickResponse = { head_commit: string, };
 
-export type CreateCustomEditorRequest = { name: string, command: string, argument: string | null, };
+export type CherryPickOntoRequest = { repo_id: string, 
+/**
+ * Branch that receives the task's commits, e.g. a release branch
+ */
+target_branch: string, };
 
-export type UpdateCustomEditorRequest = { name: string, command: string, argument: string | null, };
+export type CherryPickOntoResponse = { 
+/**
+ * New tip of the target branch
+ */
+head_commit: string, commit_count: number, };
 
-export type CustomEditorResponse = { id: string, name: string, command: string, argument: string, icon: string | null, created_at: string, available: boolean, };
+export type ConflictsResponse = { 
+/**
+ * None when no merge, rebase, cherry-pick or revert is in progress
+ */
+op: ConflictOp | null, files: Array<ConflictedFile>, };
 
-export type ListCustomEditorsResponse = { editors: Array<CustomEditorResponse>, };
+export type ResolveConflictRequest = { repo_id: string, path: string, resolution: ConflictResolution, };
 
-export type CheckCustomEditorAvailabilityResponse = { available: boolean, };
+export type ContinueConflictsRequest = { repo_id: string, };
 
-export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
 
-export type BundledSoundInfo = { 
+export type PushError = { "type": "force_push_required" };
+
+export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "gitlab_not_configured" } | { "type": "bitbucket_not_configured" } | { "type": "gitea_not_configured" };
+
+export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
- * Identifier for API paths (e.g., "bundled:COW_MOOING")
+ * True if a `git rebase` is currently in progress in this worktree
  */
-identifier: string, 
+is_rebase_in_progress: boolean, 
 /**
- * Human-readable name (e.g., "Cow Mooing")
+ * Current conflict operation if any
  */
-display_name: string, };
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, 
+/**
+ * True if target branch checkout location has uncommitted tracked changes.
+ * `Some(false)` when target branch is clean or not checked out anywhere.
+ * `None` only if an error occurred while checking.
+ */
+target_branch_has_uncommitted_changes: boolean | null, 
+/**
+ * Host of the repo's remote, when it is a supported one
+ */
+remote_provider: GitRemoteProviderKind | null, 
+/**
+ * Web page of the task branch on that host; it only exists once pushed
+ */
+remote_branch_url: string | null, };
 
-export type AvailableSoundsResponse = { bundled: Array<BundledSoundInfo>, custom: Array<CustomSoundInfo>, };
+export type GitStateChangedMessage = { type: string, workspace_id: string, };
 
-export type SetGitHubTokenRequest = { token: string, };
+export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
 
-export type GitHubImportResponse = { success: boolean, message: string, };
+export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
 
-export type AccountInfo = { claude: ClaudeAccountInfo | null, codex: CodexAccountInfo | null, };
+export type AttachExistingPrRequest = { repo_id: string, };
 
-export type ClaudeAccountInfo = { subscriptionType: string, rateLimitTier: string | null, usage: ClaudeUsage | null, };
+export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
 
-export type ClaudeUsage = { fiveHour: UsageLimit, sevenDay: UsageLimit, sevenDayOpus: UsageLimit | null, sevenDaySonnet: UsageLimit | null, };
+export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
 
-export type UsageLimit = { 
+export type GetPrCommentsQuery = { repo_id: string, };
+
+export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+
+export type PullRequestSummary = { number: bigint, title: string, url: string, author: string, head_branch: string, base_branch: string, created_at: string, updated_at: string, };
+
+export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
- * Usage percentage from 0 to 100
+ * True if a `git rebase` is currently in progress in this worktree
  */
-usedPercent: number, 
+is_rebase_in_progress: boolean, 
 /**
- * ISO 8601 timestamp when this limit resets
+ * Current conflict operation if any
  */
-resetsAt: string, };
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, 
+/**
+ * True if target branch checkout location has uncommitted tracked changes.
+ * `Some(false)` when target branch is clean or not checked out anywhere.
+ * `None` only if an error occurred while checking.
+ */
+target_branch_has_uncommitted_changes: boolean | null, 
+/**
+ * Host of the repo's remote, when it is a supported one
+ */
+remote_provider: GitRemoteProviderKind | null, 
+/**
+ * Web page of the task branch on that host; it only exists once pushed
+ */
+remote_branch_url: string | null, };
 
-export type CodexAccountInfo = { planType: string, subscriptionActiveUntil: string | null, };
+export type QueueMergeRequest = { repo_id: string, commit_message: string | null, generate_commit_message: boolean | null, };
 
-export type CurrentUserResponse = { user_id: string, };
+export type QueueMergeError = { "type": "no_commits_ahead" } | { "type": "has_conflicts" } | { "type": "already_merged" } | { "type": "already_queued" } | { "type": "workspace_repo_not_found" } | { "type": "commit_message_generation_failed", message: string, } | { "type": "unacknowledged_permission_findings", count: number, };
 
-export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+export type AcknowledgePermissionFindingsResponse = { acknowledged: number, };
 
-export type FollowUpResult = { "status": "started", execution_process: ExecutionProcess, } | { "status": "queued", queue_entry: ExecutionQueue, };
+export type SendReviewCommentsRequest = { 
+/**
+ * Sent before the comments
+ */
+message: string | null, variant: string | null, };
 
-export type ExecutionQueue = { id: string, workspace_id: string, executor_profile_id: ExecutorProfileId, queued_at: string, 
+export type StageChangesRequest = { repo_id: string, 
 /**
- * Session ID for follow-up executions (None for initial workspace starts)
+ * Files staged with all their changes, relative to the repository root
  */
-session_id: string | null, 
+paths: Array<string>, 
 /**
- * Serialized ExecutorAction for follow-up executions (None for initial workspace starts)
+ * Unified diff of the hunks to stage, like the ones picked in `git add -p`
  */
-executor_action: string | null, };
+patch: string | null, };
 
-export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+/**
+ * What the next auto-commit of a repo will include
+ */
+export type StagedChanges = { repo_id: string, 
+/**
+ * Paths with staged changes, relative to the repository root
+ */
+paths: Array<string>, };
 
-export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+export type ImportedWorkspace = { task: Task, workspace: Workspace, };
 
-export type MergeTaskAttemptRequest = { repo_id: string, commit_message: string | null, generate_commit_message: boolean | null, };
+export type MergeQueueCountResponse = { count: bigint, };
 
-export type GenerateCommitMessageRequest = { repo_id: string, };
+export type ReorderProjectQueueRequest = { 
+/**
+ * Every entry of the project's queue, in the desired start order
+ */
+entry_ids: Array<string>, };
 
-export type GenerateCommitMessageResponse = { commit_message: string, };
+export type ReorderMergeQueueRequest = { 
+/**
+ * Every waiting entry of the project's merge queue, in the desired order
+ */
+entry_ids: Array<string>, };
 
-export type PushTaskAttemptRequest = { repo_id: string, };
+export type ProjectEnvFileResponse = { env_file: ProjectEnvFile | null, 
+/**
+ * Secrets referenced by the template that are not defined yet
+ */
+missing_secrets: Array<string>, };
 
-export type RenameBranchRequest = { new_branch_name: string, };
+export type ToolStatus = "ok" | "missing" | "version_mismatch" | "failed";
 
-export type RenameBranchResponse = { branch: string, };
+export type ToolCheck = { name: string, required_version: string | null, 
+/**
+ * Version parsed from the check command's output
+ */
+found_version: string | null, status: ToolStatus, 
+/**
+ * First line of output, or the error when the command could not run
+ */
+output: string | null, suggestion: string | null, };
 
-export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+export type ToolchainReport = { checks: Array<ToolCheck>, 
+/**
+ * True when every declared tool is present at a matching version
+ */
+healthy: boolean, 
+/**
+ * Repo whose worktree the checks ran in, if the project has any
+ */
+repo_name: string | null, };
 
-export type OpenEditorResponse = { url: string | null, };
+export type BranchCleanupOutcome = "deleted" | "archived" | "would_delete" | "would_archive" | "skipped" | "failed";
 
-export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+export type BranchCleanupEntry = { repo_id: string, repo_name: string, branch: string, 
+/**
+ * Branch tip before cleanup, for restoring a deleted branch by hand
+ */
+tip_commit: string, outcome: BranchCleanupOutcome, 
+/**
+ * Why the branch was skipped or could not be removed
+ */
+reason: string | null, };
 
-export type ShareTaskResponse = { shared_task_id: string, };
+export type BranchCleanupReport = { project_id: string, mode: BranchCleanupMode, dry_run: boolean, entries: Array<BranchCleanupEntry>, };
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type RunBranchCleanupRequest = { 
+/**
+ * Report what would be removed without touching any repo
+ */
+dry_run: boolean, 
+/**
+ * Overrides the saved policy's mode for this run
+ */
+mode: BranchCleanupMode | null, 
+/**
+ * Overrides the saved policy's minimum merge age for this run
+ */
+min_age_days: number | null, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type PreCommitStatus = { 
+/**
+ * Whether the `pre-commit` executable was found
+ */
+installed: boolean, 
+/**
+ * Repos of the project with a `.pre-commit-config.yaml`
+ */
+configured_repos: Array<string>, };
 
-export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+export type UpdateRedactionPatterns = { 
+/**
+ * Regexes; a named group `secret` masks only that part of a match
+ */
+patterns: Array<string>, };
 
-export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+export type SandboxStatus = { 
+/**
+ * Whether bubblewrap (Linux) or sandbox-exec (macOS) was found;
+ * sandboxed executions fail to start without it
+ */
+available: boolean, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type StartBisectRequest = { repo_id: string, 
+/**
+ * Commit or ref known to pass the validation command
+ */
+good_commit: string, 
+/**
+ * Commit or ref known to fail it
+ */
+bad_commit: string, 
+/**
+ * Shell command run at each step, from the repo root
+ */
+command: string, 
+/**
+ * Task the culprit is reported on; required when `use_agent` is set, as
+ * the agent runs in the task's latest workspace
+ */
+task_id: string | null, use_agent: boolean, };
+
+export type BisectRunDetails = { run: BisectRun, steps: Array<BisectStep>, };
+
+export type CopyFileKind = "file" | "symlink" | "template";
+
+export type CopyFilePreview = { 
+/**
+ * Path relative to the source repository
+ */
+source: string, 
+/**
+ * Path relative to the worktree
+ */
+target: string, kind: CopyFileKind, 
+/**
+ * True if the target already exists and would be left untouched
+ */
+already_exists: boolean, };
+
+export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
+
+export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
+
+export type SecretRef = string;
+
+export type SecretBackend = "keychain" | "encrypted_file";
+
+/**
+ * Stored secret names; values are never returned
+ */
+export type SecretsResponse = { backend: SecretBackend, names: Array<string>, };
+
+export type SetSecretRequest = { value: string, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, default_clone_directory: string | null, commit_message_auto_generate_enabled: boolean, commit_message_prompt: string | null, commit_message_executor_profile: ExecutorProfileId | null, 
+/**
+ * Prompt template for commit messages; takes precedence over
+ * `commit_message_prompt`
+ */
+commit_message_template_id: string | null, 
+/**
+ * Maximum concurrent agent executions (0 = unlimited)
+ */
+max_concurrent_agents: number, 
+/**
+ * At the concurrency limit, interrupt a running agent of a lower-priority
+ * task for a queued higher-priority one; it is queued to resume
+ */
+preempt_lower_priority: boolean, 
+/**
+ * Files larger than this (in KiB) are shown without contents in diffs.
+ * None uses the 2 MiB default; capped at 64 MiB.
+ */
+max_inline_diff_kb: number | null, langfuse_enabled: boolean, langfuse_public_key: string | null, 
+/**
+ * Entry in the secret store holding the Langfuse secret key
+ */
+langfuse_secret_key: SecretRef | null, langfuse_host: string | null, backup: BackupConfig, 
+/**
+ * Executor profile for the review attention agent.
+ * When Some, review attention uses the specified executor.
+ * When None, review attention is disabled.
+ */
+review_attention_executor_profile: ExecutorProfileId | null, 
+/**
+ * Custom prompt for the review attention agent.
+ * When None, uses the default prompt.
+ * The prompt should include placeholders {task_description} and {agent_summary}.
+ */
+review_attention_prompt: string | null, 
+/**
+ * Prompt template for review attention; takes precedence over
+ * `review_attention_prompt`
+ */
+review_attention_template_id: string | null, 
+/**
+ * Prompt template for collecting agent feedback when a task is done
+ */
+feedback_template_id: string | null, 
+/**
+ * When enabled, completed tasks are automatically merged and dependent tasks are queued.
+ */
+autopilot_enabled: boolean, retry_policy: RetryPolicyConfig, 
+/**
+ * Replaces `retry_policy` for executions of an executor profile. Keyed
+ * by executor (e.g. `CLAUDE_CODE`) or executor and variant (e.g.
+ * `CLAUDE_CODE:PLAN`); the variant key wins.
+ */
+retry_policy_overrides: { [key in string]?: RetryPolicyConfig }, gitlab: GitLabConfig, bitbucket: BitbucketConfig, gitea: GiteaConfig, linear: LinearConfig, anomaly_detection: AnomalyDetectionConfig, usage_pricing: UsagePricingConfig, 
+/**
+ * Keyed by executor (e.g. `CLAUDE_CODE`); executions over a limit wait
+ * in the queue instead of starting
+ */
+provider_rate_limits: { [key in string]?: ProviderRateLimit }, 
+/**
+ * Deleted tasks and projects stay restorable for this many days before
+ * they are purged
+ */
+trash_retention_days: number, 
+/**
+ * Run coding agents and scripts on another machine over SSH
+ */
+ssh_remote: SshRemoteConfig, output_redaction: OutputRedactionConfig, };
+
+export type ConfigCompatibilityReport = { 
+/**
+ * `config_version` of the config file; None when there is no file yet
+ */
+file_version: string | null, 
+/**
+ * Version this build reads and writes
+ */
+current_version: string, 
+/**
+ * Whether the file was written by a newer build
+ */
+written_by_newer_version: boolean, 
+/**
+ * Fields in the file this build can't interpret, as dotted paths. They
+ * are kept when the config is saved.
+ */
+unknown_fields: Array<string>, 
+/**
+ * Copies of the config file taken before it was migrated
+ */
+backups: Array<string>, };
+
+export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, error_sound_file: SoundFile, custom_sound_path: string | null, 
+/**
+ * When true, the frontend handles sound playback instead of the backend.
+ * This is useful for remote access where backend sound playback is not desired.
+ */
+frontend_sounds_enabled: boolean, slack: SlackNotificationConfig, };
+
+/**
+ * Posts notifications to a Slack channel through an incoming webhook
+ */
+export type SlackNotificationConfig = { 
+/**
+ * Entry in the secret store holding the incoming webhook URL; nothing is
+ * posted while unset
+ */
+webhook_url: SecretRef | null, 
+/**
+ * A coding agent finished its run
+ */
+task_completed: boolean, 
+/**
+ * An agent failed, or is waiting for an approval or an answer
+ */
+needs_attention: boolean, 
+/**
+ * A queued merge hit conflicts or failed
+ */
+merge_failed: boolean, };
+
+export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
+
+export type EditorConfig = { editor_type: EditorType, custom_command: string | null, custom_editor_id: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
+
+export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
 
-export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
 
-export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
+export type EditorIdentifier = { "type": "built_in" } & EditorType | { "type": "custom" } & string;
 
-export type RunAgentSetupResponse = Record<string, never>;
+export type CustomEditor = { id: string, name: string, command: string, argument: string, icon: string | null, created_at: string, };
 
-export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
+export type CustomEditorsConfig = { custom_editors: { [key in string]?: CustomEditor }, };
 
-export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
+export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
 
-export type AbortConflictsRequest = { repo_id: string, };
+export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", ERROR_BUZZER = "ERROR_BUZZER", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
 
-export type PushError = { "type": "force_push_required" };
+export type ShowcaseState = { seen_features: Array<string>, };
 
-export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+export type BackupConfig = { enabled: boolean, interval_hours: number, retention_hours_all: number, retention_daily_days: number, retention_weekly_weeks: number, retention_monthly_months: number, 
+/**
+ * Also upload archives to an S3-compatible bucket, with the same retention
+ */
+s3: S3BackupConfig | null, 
+/**
+ * Check each new archive opens and matches the database it was made
+ * from. Archives that fail are deleted instead of uploaded.
+ */
+verify_after_backup: boolean, 
+/**
+ * Days between restore drills on the latest archive; 0 disables them
+ */
+drill_interval_days: number, };
 
-export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * S3-compatible bucket that backups are copied to
  */
-is_rebase_in_progress: boolean, 
+export type S3BackupConfig = { 
 /**
- * Current conflict operation if any
+ * Defaults to AWS for `region`; set for MinIO, R2 and other compatible stores
  */
-conflict_op: ConflictOp | null, 
+endpoint: string | null, region: string, bucket: string, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * Key prefix archives are stored under, e.g. `vibe-kanban/`
  */
-conflicted_files: Array<string>, 
+prefix: string, access_key_id: string, 
 /**
- * True if target branch checkout location has uncommitted tracked changes.
- * `Some(false)` when target branch is clean or not checked out anywhere.
- * `None` only if an error occurred while checking.
+ * Entry in the secret store holding the secret access key; uploads fail
+ * while unset
  */
-target_branch_has_uncommitted_changes: boolean | null, };
-
-export type GitStateChangedMessage = { type: string, workspace_id: string, };
-
-export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
-
-export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
-
-export type AttachExistingPrRequest = { repo_id: string, };
+secret_access_key: SecretRef | null, 
+/**
+ * Address the bucket as `endpoint/bucket` rather than `bucket.endpoint`
+ */
+path_style: boolean, };
 
-export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
+/**
+ * Automatic re-runs of coding agent executions that fail for a transient
+ * reason (rate limits, network errors, crashes on startup)
+ */
+export type RetryPolicyConfig = { enabled: boolean, 
+/**
+ * Retries after the first failure, not counting the original run
+ */
+max_attempts: number, 
+/**
+ * Delay before the first retry; doubles on each further retry
+ */
+initial_backoff_secs: number, max_backoff_secs: number, 
+/**
+ * Exit codes treated as transient whatever the agent printed, for
+ * agents that report rate limits with a dedicated code
+ */
+retry_on_exit_codes: Array<number>, };
 
-export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+/**
+ * Access to GitLab for opening and tracking merge requests on repos whose
+ * remote is hosted there
+ */
+export type GitLabConfig = { 
+/**
+ * Entry in the secret store holding a personal access token with the
+ * `api` scope
+ */
+token: SecretRef | null, 
+/**
+ * Instance URL; remotes on this host are treated as GitLab
+ */
+base_url: string, };
 
-export type GetPrCommentsQuery = { repo_id: string, };
+/**
+ * Access to Bitbucket Cloud for opening and tracking pull requests on repos
+ * hosted on bitbucket.org
+ */
+export type BitbucketConfig = { 
+/**
+ * Bitbucket username the app password belongs to
+ */
+username: string | null, 
+/**
+ * Entry in the secret store holding an app password with pull request
+ * read and write permissions
+ */
+app_password: SecretRef | null, };
 
-export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+/**
+ * Access to a Gitea (or Forgejo) instance for opening and tracking pull
+ * requests on repos hosted there
+ */
+export type GiteaConfig = { 
+/**
+ * Entry in the secret store holding an access token with the
+ * `repository` scope
+ */
+token: SecretRef | null, 
+/**
+ * Instance URL; remotes on this host are treated as Gitea. Gitea has no
+ * public default, so nothing is detected until it is set.
+ */
+base_url: string | null, };
 
-export type PullRequestSummary = { number: bigint, title: string, url: string, author: string, head_branch: string, base_branch: string, created_at: string, updated_at: string, };
+/**
+ * Access to Linear for looking up the title and status of issues linked to
+ * tasks
+ */
+export type LinearConfig = { 
+/**
+ * Entry in the secret store holding a personal API key; Linear links
+ * are not unfurled while unset
+ */
+api_key: SecretRef | null, };
 
-export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * Host executions run on when remote execution is enabled
  */
-is_rebase_in_progress: boolean, 
+export type SshRemoteConfig = { enabled: boolean, 
 /**
- * Current conflict operation if any
+ * Host name or `~/.ssh/config` alias
  */
-conflict_op: ConflictOp | null, 
+host: string, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * None uses the user from `~/.ssh/config` or the local user
  */
-conflicted_files: Array<string>, 
+user: string | null, port: number | null, 
 /**
- * True if target branch checkout location has uncommitted tracked changes.
- * `Some(false)` when target branch is clean or not checked out anywhere.
- * `None` only if an error occurred while checking.
+ * Private key to authenticate with; None uses the SSH agent
  */
-target_branch_has_uncommitted_changes: boolean | null, };
-
-export type QueueMergeRequest = { repo_id: string, commit_message: string | null, generate_commit_message: boolean | null, };
-
-export type QueueMergeError = { "type": "no_commits_ahead" } | { "type": "has_conflicts" } | { "type": "already_merged" } | { "type": "already_queued" } | { "type": "workspace_repo_not_found" } | { "type": "commit_message_generation_failed", message: string, };
-
-export type MergeQueueCountResponse = { count: bigint, };
-
-export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
+identity_file: string | null, 
+/**
+ * Directory on the remote host workspaces are copied into
+ */
+base_dir: string, };
 
-export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
+export type GitRemoteProviderKind = "github" | "gitlab" | "bitbucket" | "gitea";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, default_clone_directory: string | null, commit_message_auto_generate_enabled: boolean, commit_message_prompt: string | null, commit_message_executor_profile: ExecutorProfileId | null, 
 /**
- * Maximum concurrent agent executions (0 = unlimited)
+ * Limits past which a coding agent is considered stuck. 0 disables a check.
  */
-max_concurrent_agents: number, langfuse_enabled: boolean, langfuse_public_key: string | null, langfuse_secret_key: string | null, langfuse_host: string | null, backup: BackupConfig, 
+export type AnomalyThresholds = { 
 /**
- * Executor profile for the review attention agent.
- * When Some, review attention uses the specified executor.
- * When None, review attention is disabled.
+ * Identical tool calls in a row
  */
-review_attention_executor_profile: ExecutorProfileId | null, 
+max_repeated_tool_calls: number, 
 /**
- * Custom prompt for the review attention agent.
- * When None, uses the default prompt.
- * The prompt should include placeholders {task_description} and {agent_summary}.
+ * Edits to one file that repeat or revert an earlier edit to it
  */
-review_attention_prompt: string | null, 
+max_no_progress_edits: number, 
 /**
- * When enabled, completed tasks are automatically merged and dependent tasks are queued.
+ * Raw stdout and stderr produced by the agent process
+ */
+max_output_bytes: number, 
+/**
+ * Minutes without any new output from the agent, not counting time
+ * spent waiting for an approval
+ */
+inactivity_timeout_minutes: number, 
+/**
+ * Minutes an execution may run in total
  */
-autopilot_enabled: boolean, };
+max_duration_minutes: number, };
 
-export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, error_sound_file: SoundFile, custom_sound_path: string | null, 
 /**
- * When true, the frontend handles sound playback instead of the backend.
- * This is useful for remote access where backend sound playback is not desired.
+ * Interrupting coding agents that loop, flood their output or stall
+ */
+export type AnomalyDetectionConfig = { enabled: boolean, thresholds: AnomalyThresholds, 
+/**
+ * Overrides keyed by executor profile, either `EXECUTOR` or
+ * `EXECUTOR:VARIANT` (e.g. `CLAUDE_CODE:PLAN`)
  */
-frontend_sounds_enabled: boolean, };
+profile_thresholds: { [key in string]?: AnomalyThresholds }, };
 
-export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
+/**
+ * Prices the usage API estimates costs with
+ */
+export type UsagePricingConfig = { 
+/**
+ * Keyed by executor profile, either `EXECUTOR` or `EXECUTOR:VARIANT`
+ * (e.g. `CLAUDE_CODE:OPUS`); usage of other executors is not priced
+ */
+prices: { [key in string]?: TokenPrice }, };
 
-export type EditorConfig = { editor_type: EditorType, custom_command: string | null, custom_editor_id: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
+/**
+ * Price of a million tokens in USD
+ */
+export type TokenPrice = { input_per_million: number, output_per_million: number, };
 
-export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
+/**
+ * Limits on how hard one provider's account is driven by concurrent
+ * executions. 0 disables a limit.
+ */
+export type ProviderRateLimit = { 
+/**
+ * Executions of the provider running at once
+ */
+max_concurrent: number, 
+/**
+ * Executions of the provider started within any 60 seconds
+ */
+max_starts_per_minute: number, };
 
-export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
+/**
+ * Masking secrets in agent and script output before it is stored or shown
+ */
+export type OutputRedactionConfig = { enabled: boolean, 
+/**
+ * Mask common credential formats: cloud and API keys, access tokens,
+ * private keys and `password=...` style assignments
+ */
+builtin_patterns: boolean, 
+/**
+ * Mask long random-looking tokens. Catches keys of unknown formats, at
+ * the cost of sometimes masking ids
+ */
+entropy_detection: boolean, 
+/**
+ * Bits per character from which a token counts as random
+ */
+entropy_threshold: number, 
+/**
+ * Regexes masked in every project's output, besides each project's own
+ */
+custom_patterns: Array<string>, };
 
-export type EditorIdentifier = { "type": "built_in" } & EditorType | { "type": "custom" } & string;
+export type UsageReport = { from: string, to: string, totals: UsageTotals, 
+/**
+ * Most expensive first
+ */
+by_executor: Array<ExecutorUsage>, 
+/**
+ * Most expensive first
+ */
+by_project: Array<ProjectUsage>, 
+/**
+ * Oldest first; days without usage are omitted
+ */
+by_day: Array<DailyUsage>, };
 
-export type CustomEditor = { id: string, name: string, command: string, argument: string, icon: string | null, created_at: string, };
+export type UsageTotals = { executions: number, input_tokens: number, output_tokens: number, estimated_cost_usd: number, 
+/**
+ * Executions whose executor has no price configured; the estimate
+ * leaves them out
+ */
+unpriced_executions: number, };
 
-export type CustomEditorsConfig = { custom_editors: { [key in string]?: CustomEditor }, };
+export type ExecutorUsage = { executor: string, variant: string | null, executions: number, input_tokens: number, output_tokens: number, estimated_cost_usd: number, 
+/**
+ * Executions whose executor has no price configured; the estimate
+ * leaves them out
+ */
+unpriced_executions: number, };
 
-export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
+export type ProjectUsage = { 
+/**
+ * None for usage outside any project, such as conversations
+ */
+project_id: string | null, project_name: string | null, executions: number, input_tokens: number, output_tokens: number, estimated_cost_usd: number, 
+/**
+ * Executions whose executor has no price configured; the estimate
+ * leaves them out
+ */
+unpriced_executions: number, };
 
-export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", ERROR_BUZZER = "ERROR_BUZZER", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
+export type DailyUsage = { 
+/**
+ * `YYYY-MM-DD`, in UTC
+ */
+day: string, executions: number, input_tokens: number, output_tokens: number, estimated_cost_usd: number, 
+/**
+ * Executions whose executor has no price configured; the estimate
+ * leaves them out
+ */
+unpriced_executions: number, };
 
-export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
+export type TimeTotals = { agent_seconds: number, script_seconds: number, human_wait_seconds: number, executions: number, };
 
-export type ShowcaseState = { seen_features: Array<string>, };
+export type TaskTime = { task_id: string, task_title: string, agent_seconds: number, script_seconds: number, human_wait_seconds: number, executions: number, first_started_at: Date | null, 
+/**
+ * End of the latest run, or now while one is running
+ */
+last_active_at: Date | null, 
+/**
+ * A run is in progress; it is counted up to now
+ */
+running: boolean, };
 
-export type BackupConfig = { enabled: boolean, interval_hours: number, retention_hours_all: number, retention_daily_days: number, retention_weekly_weeks: number, retention_monthly_months: number, };
+export type ProjectTimeReport = { project_id: string, from: string, to: string, totals: TimeTotals, 
+/**
+ * Tasks with runs in the range, most agent time first
+ */
+tasks: Array<TaskTime>, };
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
@@ -659,7 +2490,7 @@ export type GitStateChangeKind = "head" | "index" | "refs" | "rebase_head" | "me
 
 export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, };
 
-export type QueuedMessage = { 
+export type QueuedMessage = { id: string, 
 /**
  * The session this message is queued for
  */
@@ -673,18 +2504,148 @@ data: DraftFollowUpData,
  */
 queued_at: string, };
 
-export type QueueStatus = { "status": "empty" } | { "status": "queued", message: QueuedMessage, };
+export type QueueStatus = { "status": "empty" } | { "status": "queued", 
+/**
+ * The message sent next
+ */
+message: QueuedMessage, 
+/**
+ * Messages queued after `message`, in the order they will be sent
+ */
+pending: Array<QueuedMessage>, };
 
 export type OperationStatus = { id: string, workspace_id: string, task_id: string, operation_type: OperationStatusType, error: string | null, started_at: string, };
 
-export type OperationStatusType = "generating_commit" | "rebasing" | "pushing" | "merging";
+export type OperationStatusType = "generating_commit" | "rebasing" | "cherry_picking" | "pushing" | "merging" | "waiting_for_rate_limit";
+
+/**
+ * One OS process within an execution's process tree
+ */
+export type ProcessStats = { pid: number, parent_pid: number | null, name: string, 
+/**
+ * 100 is one fully busy core
+ */
+cpu_percent: number, rss_bytes: number, };
+
+/**
+ * Resource usage of an execution's process tree at one point in time
+ */
+export type ResourceSample = { execution_process_id: string, sampled_at: Date, cpu_percent: number, rss_bytes: number, process_count: number, 
+/**
+ * The group leader first, then its descendants breadth-first
+ */
+processes: Array<ProcessStats>, };
+
+export type AgentAuthState = "signed_in" | "signed_out";
+
+/**
+ * What is known about a coding agent's login
+ */
+export type AgentAuthStatus = { executor: BaseCodingAgent, state: AgentAuthState, 
+/**
+ * The status command's output, or the error a failed execution reported
+ */
+detail: string | null, 
+/**
+ * How to sign in again, when signed out
+ */
+suggested_fix: string | null, checked_at: Date, };
 
-export type MergeQueueEntry = { id: string, project_id: string, workspace_id: string, repo_id: string, queued_at: string, status: MergeQueueStatus, commit_message: string, };
+export type MergeQueueEntry = { id: string, project_id: string, workspace_id: string, repo_id: string, queued_at: string, status: MergeQueueStatus, commit_message: string, 
+/**
+ * Set on entries reloaded after a restart; the processor checks they
+ * still apply before merging them
+ */
+needs_revalidation: boolean, };
 
 export type MergeQueueStatus = "queued" | "merging";
 
+export type QueuedMerge = { id: string, project_id: string, workspace_id: string, repo_id: string, queued_at: string, status: MergeQueueStatus, commit_message: string, 
+/**
+ * Set on entries reloaded after a restart; the processor checks they
+ * still apply before merging them
+ */
+needs_revalidation: boolean, 
+/**
+ * 1-based position; the entry being merged, if any, comes first
+ */
+position: bigint, };
+
+export type MergeQueueOutcome = { "type": "merged", merge_commit: string, 
+/**
+ * Output of the repo's git hooks when they failed and the merge was
+ * committed without them
+ */
+hook_failure: string | null, } | { "type": "nothing_to_merge" } | { "type": "stale", reason: string, } | { "type": "conflict", message: string, } | { "type": "checks_failed", message: string, output: string, } | { "type": "failed", message: string, };
+
+export type MergeQueueRun = { entry_id: string, project_id: string, workspace_id: string, repo_id: string, commit_message: string, queued_at: string, outcome: MergeQueueOutcome, finished_at: string, };
+
+/**
+ * What a long-running operation does
+ */
+export type OperationKind = "merge" | "commit_message" | "backup" | "restore" | "import";
+
+export type OperationState = "running" | "succeeded" | "failed";
+
+/**
+ * A long-running operation, kept after it finishes as history
+ */
+export type Operation = { id: string, kind: OperationKind, state: OperationState, workspace_id: string | null, task_id: string | null, 
+/**
+ * Percent complete, 0-100; None when the operation can't estimate it
+ */
+progress: bigint | null, 
+/**
+ * What the operation is currently doing
+ */
+message: string | null, error: string | null, started_at: Date, finished_at: Date | null, updated_at: Date, 
+/**
+ * The user who started the operation, when the server has accounts
+ */
+created_by: string | null, };
+
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
+export type ConflictHunk = { ours: string, 
+/**
+ * Only present when git writes diff3-style markers
+ */
+base: string | null, theirs: string, };
+
+export type ConflictedFile = { path: string, base: string | null, ours: string | null, theirs: string | null, 
+/**
+ * Conflicted regions of the working tree file, in order. Empty for
+ * binary files and add/delete conflicts, which resolve as a whole.
+ */
+hunks: Array<ConflictHunk>, is_binary: boolean, };
+
+export type HunkResolution = { "type": "ours" } | { "type": "theirs" } | { "type": "both" } | { "type": "custom", content: string, };
+
+export type ConflictResolution = { "type": "ours" } | { "type": "theirs" } | { "type": "hunks", hunks: Array<HunkResolution>, } | { "type": "content", content: string, };
+
+/**
+ * Breakage found in a worktree
+ */
+export type WorktreeIssue = { "type": "missing_directory" } | { "type": "broken_git_file" } | { "type": "not_registered" } | { "type": "stale_lock", path: string, };
+
+/**
+ * Outcome of checking and repairing one worktree
+ */
+export type WorktreeRepair = { issues: Array<WorktreeIssue>, 
+/**
+ * The worktree was pruned and re-added, discarding uncommitted changes
+ */
+recreated: boolean, };
+
+/**
+ * Outcome of repairing one repo's worktree within a workspace
+ */
+export type RepoWorktreeRepair = { repo_id: string, repo_name: string, issues: Array<WorktreeIssue>, 
+/**
+ * The worktree was pruned and re-added, discarding uncommitted changes
+ */
+recreated: boolean, };
+
 export type HookPoint = "pre_task_create" | "post_task_create" | "pre_task_status_change" | "post_task_status_change" | "post_agent_complete" | "post_dependency_unblocked";
 
 export type HookExecutionStatus = "running" | "completed" | "failed" | "skipped";
@@ -746,9 +2707,45 @@ working_dir: string | null, };
 
 export type ScriptRequestLanguage = "Bash";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID" }
+export type ScriptShell = "bash" | "zsh" | "sh" | "powershell" | "cmd";
+
+/**
+ * Development shell every execution for a project is wrapped in.
+ * `None` on a project runs commands directly.
+ *
+ * `Auto` runs coding agents in the workspace's devcontainer when a
+ * devcontainer.json is found and Docker is available, and otherwise uses
+ * `devenv shell` when devenv.nix sits in the working directory and
+ * `nix develop` when a flake.nix is found; `Custom` only uses the
+ * project's configured command prefix. `Devcontainer` runs coding agents in
+ * a container built from the workspace's devcontainer.json, and `Docker`
+ * runs every execution in a container of the project's image; the container
+ * service sets both up since they need Docker.
+ */
+export type DevShell = "auto" | "nix" | "devenv" | "custom" | "devcontainer" | "docker";
+
+/**
+ * Whether sandboxed executions may use the network
+ */
+export type SandboxNetwork = "allow" | "deny";
+
+export type SandboxPolicy = { 
+/**
+ * Sandbox every agent and script run for the project
+ */
+enabled: boolean, 
+/**
+ * Directories writable besides the workspace; absolute or starting with `~/`
+ */
+allowed_dirs: Array<string>, 
+/**
+ * Executables that cannot be run, by name (`curl`) or absolute path
+ */
+denied_commands: Array<string>, network: SandboxNetwork, };
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid };
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", GOOSE = "GOOSE" }
+
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "GOOSE": Goose };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
@@ -772,13 +2769,13 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "GOOSE": Goose } };
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, trusted_workspace?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
 export type SkillInfo = { name: string, description: string | null, namespace: string | null, };
 
@@ -818,6 +2815,8 @@ export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-
 
 export type DroidReasoningEffort = "none" | "dynamic" | "off" | "low" | "medium" | "high";
 
+export type Goose = { append_prompt: AppendPrompt, provider?: string | null, model?: string | null, auto_approve?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
 export type AppendPrompt = string | null;
 
 export type CodingAgentInitialRequest = { prompt: string, 